@@ -1,10 +1,12 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonAny, JsonNewMetric};
 
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
 use crate::{
-    adapters::util::{latency_as_nanos, Units},
+    adapters::util::{latency_as_nanos, read_json, Units},
     results::adapter_results::AdapterResults,
     Adaptable, AdapterError, Settings,
 };
@@ -18,6 +20,14 @@ impl Adaptable for AdapterCSharpDotNet {
             .convert(settings)
             .ok()?
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        Ok(read_json::<_, DotNet>(reader)?
+            .and_then(|dot_net| dot_net.convert(settings).ok().flatten()))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
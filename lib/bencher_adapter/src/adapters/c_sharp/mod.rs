@@ -1,5 +1,7 @@
 pub mod dot_net;
 
+use std::io::{self, BufRead, Seek};
+
 use crate::{Adaptable, AdapterResults, Settings};
 use dot_net::AdapterCSharpDotNet;
 
@@ -9,6 +11,13 @@ impl Adaptable for AdapterCSharp {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterCSharpDotNet::parse(input, settings)
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        AdapterCSharpDotNet::parse_reader(reader, settings)
+    }
 }
 
 #[cfg(test)]
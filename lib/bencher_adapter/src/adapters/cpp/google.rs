@@ -1,9 +1,11 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonAny, JsonNewMetric};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
 use crate::{
-    adapters::util::{latency_as_nanos, Units},
+    adapters::util::{latency_as_nanos, read_json, Units},
     results::adapter_results::AdapterResults,
     Adaptable, AdapterError, Settings,
 };
@@ -22,6 +24,19 @@ impl Adaptable for AdapterCppGoogle {
             .try_into()
             .ok()?
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        match settings.average {
+            Some(JsonAverage::Mean) | None => {},
+            Some(JsonAverage::Median) => return Ok(None),
+        }
+
+        Ok(read_json::<_, Google>(reader)?
+            .and_then(|google| Option::<AdapterResults>::try_from(google).ok().flatten()))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
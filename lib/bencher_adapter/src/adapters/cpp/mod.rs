@@ -1,7 +1,11 @@
 pub mod catch2;
 pub mod google;
 
-use crate::{Adaptable, AdapterResults, Settings};
+use std::io::{self, BufRead, Seek};
+
+use bencher_json::project::report::Adapter;
+
+use crate::{adapters::util::convert_reader_any, Adaptable, AdapterResults, Settings};
 use catch2::AdapterCppCatch2;
 use google::AdapterCppGoogle;
 
@@ -12,6 +16,13 @@ impl Adaptable for AdapterCpp {
         AdapterCppCatch2::parse(input, settings)
             .or_else(|| AdapterCppGoogle::parse(input, settings))
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        convert_reader_any(&[Adapter::CppCatch2, Adapter::CppGoogle], reader, settings)
+    }
 }
 
 #[cfg(test)]
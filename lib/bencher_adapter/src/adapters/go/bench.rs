@@ -1,3 +1,5 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
 use nom::{
     branch::alt,
@@ -11,7 +13,8 @@ use nom::{
 
 use crate::{
     adapters::util::{
-        latency_as_nanos, parse_benchmark_name, parse_f64, parse_u64, parse_units, NomError,
+        latency_as_nanos, parse_benchmark_name, parse_f64, parse_u64, parse_units, read_lines,
+        NomError,
     },
     results::adapter_results::AdapterResults,
     Adaptable, Settings,
@@ -21,6 +24,22 @@ pub struct AdapterGoBench;
 
 impl Adaptable for AdapterGoBench {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        Self::parse_lines(input.lines(), settings)
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        read_lines(reader, |lines| Self::parse_lines(lines, settings))
+    }
+}
+
+impl AdapterGoBench {
+    fn parse_lines<L>(lines: impl Iterator<Item = L>, settings: Settings) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
         match settings.average {
             Some(JsonAverage::Mean) | None => {},
             Some(JsonAverage::Median) => return None,
@@ -28,7 +47,8 @@ impl Adaptable for AdapterGoBench {
 
         let mut benchmark_metrics = Vec::new();
 
-        for line in input.lines() {
+        for line in lines {
+            let line = line.as_ref();
             if let Ok((remainder, benchmark_metric)) = parse_go(line) {
                 if remainder.is_empty() {
                     benchmark_metrics.push(benchmark_metric);
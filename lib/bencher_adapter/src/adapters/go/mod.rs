@@ -1,5 +1,7 @@
 pub mod bench;
 
+use std::io::{self, BufRead, Seek};
+
 use crate::{Adaptable, AdapterResults, Settings};
 use bench::AdapterGoBench;
 
@@ -9,6 +11,13 @@ impl Adaptable for AdapterGo {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterGoBench::parse(input, settings)
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        AdapterGoBench::parse_reader(reader, settings)
+    }
 }
 
 #[cfg(test)]
@@ -1,10 +1,12 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonAny, JsonNewMetric};
 
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
 use crate::{
-    adapters::util::{latency_as_nanos, throughput_as_secs},
+    adapters::util::{latency_as_nanos, read_json, throughput_as_secs},
     results::adapter_results::{AdapterMeasure, AdapterResults},
     Adaptable, AdapterError, Settings,
 };
@@ -20,6 +22,19 @@ impl Adaptable for AdapterJavaJmh {
 
         serde_json::from_str::<Jmh>(input).ok()?.try_into().ok()?
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        match settings.average {
+            Some(JsonAverage::Mean) | None => {},
+            Some(JsonAverage::Median) => return Ok(None),
+        }
+
+        Ok(read_json::<_, Jmh>(reader)?
+            .and_then(|jmh| Option::<AdapterResults>::try_from(jmh).ok().flatten()))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -1,5 +1,7 @@
 pub mod jmh;
 
+use std::io::{self, BufRead, Seek};
+
 use crate::{Adaptable, AdapterResults, Settings};
 use jmh::AdapterJavaJmh;
 
@@ -9,6 +11,13 @@ impl Adaptable for AdapterJava {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterJavaJmh::parse(input, settings)
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        AdapterJavaJmh::parse_reader(reader, settings)
+    }
 }
 
 #[cfg(test)]
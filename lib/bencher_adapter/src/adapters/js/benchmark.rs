@@ -1,3 +1,5 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
 
 use nom::{
@@ -12,7 +14,7 @@ use nom::{
 use crate::{
     adapters::util::{
         nom_error, parse_benchmark_name_chars, parse_f64, parse_number_as_f64, parse_u64,
-        throughput_as_secs, NomError, Units,
+        read_lines, throughput_as_secs, NomError, Units,
     },
     results::adapter_results::AdapterResults,
     Adaptable, Settings,
@@ -22,6 +24,22 @@ pub struct AdapterJsBenchmark;
 
 impl Adaptable for AdapterJsBenchmark {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        Self::parse_lines(input.lines(), settings)
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        read_lines(reader, |lines| Self::parse_lines(lines, settings))
+    }
+}
+
+impl AdapterJsBenchmark {
+    fn parse_lines<L>(lines: impl Iterator<Item = L>, settings: Settings) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
         match settings.average {
             Some(JsonAverage::Median) | None => {},
             Some(JsonAverage::Mean) => return None,
@@ -29,7 +47,8 @@ impl Adaptable for AdapterJsBenchmark {
 
         let mut benchmark_metrics = Vec::new();
 
-        for line in input.lines() {
+        for line in lines {
+            let line = line.as_ref();
             if let Ok((remainder, benchmark_metric)) = parse_benchmark(line) {
                 if remainder.is_empty() {
                     benchmark_metrics.push(benchmark_metric);
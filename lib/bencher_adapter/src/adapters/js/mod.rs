@@ -1,21 +1,50 @@
 pub mod benchmark;
+pub mod node_test;
 pub mod time;
+pub mod tinybench;
 
-use crate::{Adaptable, AdapterResults, Settings};
+use std::io::{self, BufRead, Seek};
+
+use bencher_json::project::report::Adapter;
+
+use crate::{adapters::util::convert_reader_any, Adaptable, AdapterResults, Settings};
 use benchmark::AdapterJsBenchmark;
+use node_test::AdapterJsNodeTest;
 use time::AdapterJsTime;
+use tinybench::AdapterJsTinybench;
 
 pub struct AdapterJs;
 
 impl Adaptable for AdapterJs {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
-        AdapterJsBenchmark::parse(input, settings).or_else(|| AdapterJsTime::parse(input, settings))
+        AdapterJsBenchmark::parse(input, settings)
+            .or_else(|| AdapterJsTime::parse(input, settings))
+            .or_else(|| AdapterJsNodeTest::parse(input, settings))
+            .or_else(|| AdapterJsTinybench::parse(input, settings))
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        convert_reader_any(
+            &[
+                Adapter::JsBenchmark,
+                Adapter::JsTime,
+                Adapter::JsNodeTest,
+                Adapter::JsTinybench,
+            ],
+            reader,
+            settings,
+        )
     }
 }
 
 #[cfg(test)]
 mod test_js {
-    use super::{time::test_js_time, AdapterJs};
+    use super::{
+        node_test::test_js_node_test, time::test_js_time, tinybench::test_js_tinybench, AdapterJs,
+    };
     use crate::adapters::{js::benchmark::test_js_benchmark, test_util::convert_file_path};
 
     #[test]
@@ -29,4 +58,16 @@ mod test_js {
         let results = convert_file_path::<AdapterJs>("./tool_output/js/time/four.txt");
         test_js_time::validate_adapter_js_time(&results);
     }
+
+    #[test]
+    fn test_adapter_js_node_test() {
+        let results = convert_file_path::<AdapterJs>("./tool_output/js/node_test/four.txt");
+        test_js_node_test::validate_adapter_js_node_test(&results);
+    }
+
+    #[test]
+    fn test_adapter_js_tinybench() {
+        let results = convert_file_path::<AdapterJs>("./tool_output/js/tinybench/v3.json");
+        test_js_tinybench::validate_adapter_js_tinybench(&results);
+    }
 }
@@ -0,0 +1,197 @@
+use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
+
+use crate::{
+    adapters::util::{latency_as_nanos, Units},
+    results::adapter_results::AdapterResults,
+    Adaptable, Settings,
+};
+
+const TAP_VERSION: &str = "TAP version";
+const SUBTEST: &str = "# Subtest: ";
+const OK: &str = "ok ";
+const NOT_OK: &str = "not ok ";
+const DURATION_MS: &str = "duration_ms: ";
+const SUITE_TYPE: &str = "type: 'suite'";
+const YAML_END: &str = "...";
+const SUITE_SEPARATOR: &str = " > ";
+
+/// The Node test runner (`node --test`) with the TAP reporter (`--test-reporter=tap`)
+pub struct AdapterJsNodeTest;
+
+impl Adaptable for AdapterJsNodeTest {
+    fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        if let Some(JsonAverage::Mean | JsonAverage::Median) = settings.average {
+            return None;
+        }
+
+        if !input
+            .lines()
+            .any(|line| line.trim_start().starts_with(TAP_VERSION))
+        {
+            return None;
+        }
+
+        let mut benchmark_metrics = Vec::new();
+        // The names and indentation of the enclosing subtests (ie `describe` suites)
+        let mut subtests: Vec<(usize, String)> = Vec::new();
+        let mut pending: Option<PendingTest> = None;
+
+        for line in input.lines() {
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+
+            if let Some(name) = trimmed.strip_prefix(SUBTEST) {
+                subtests.retain(|(subtest_indent, _)| *subtest_indent < indent);
+                subtests.push((indent, unescape(name)));
+            } else if let Some(test_point) = trimmed.strip_prefix(OK) {
+                pending = parse_test_point(test_point).map(|name| PendingTest {
+                    name: subtests
+                        .iter()
+                        .filter(|(subtest_indent, _)| *subtest_indent < indent)
+                        .map(|(_, suite)| suite.as_str())
+                        .chain(std::iter::once(name.as_str()))
+                        .collect::<Vec<_>>()
+                        .join(SUITE_SEPARATOR),
+                    duration_ms: None,
+                    is_suite: false,
+                });
+            } else if trimmed.starts_with(NOT_OK) {
+                // Failed tests are not benchmarks
+                pending = None;
+            } else if let Some(test) = pending.as_mut() {
+                if let Some(duration_ms) = trimmed.strip_prefix(DURATION_MS) {
+                    test.duration_ms = duration_ms.trim().parse::<f64>().ok();
+                } else if trimmed == SUITE_TYPE {
+                    test.is_suite = true;
+                } else if trimmed == YAML_END {
+                    if let Some(benchmark_metric) =
+                        pending.take().and_then(PendingTest::into_metric)
+                    {
+                        benchmark_metrics.push(benchmark_metric);
+                    }
+                }
+            }
+        }
+
+        AdapterResults::new_latency(benchmark_metrics)
+    }
+}
+
+struct PendingTest {
+    name: String,
+    duration_ms: Option<f64>,
+    is_suite: bool,
+}
+
+impl PendingTest {
+    fn into_metric(self) -> Option<(BenchmarkName, JsonNewMetric)> {
+        let Self {
+            name,
+            duration_ms,
+            is_suite,
+        } = self;
+        // A suite duration is the sum of its tests, so it is not a benchmark itself
+        if is_suite {
+            return None;
+        }
+        let benchmark_name = name.parse().ok()?;
+        let json_metric = JsonNewMetric {
+            value: latency_as_nanos(duration_ms?, Units::Milli),
+            lower_value: None,
+            upper_value: None,
+        };
+        Some((benchmark_name, json_metric))
+    }
+}
+
+/// Parse the test name from a passing test point, ie `1 - fib 10`.
+/// Skipped and todo tests are ignored.
+fn parse_test_point(test_point: &str) -> Option<String> {
+    let (_number, description) = test_point.split_once(" - ")?;
+    let description = description.trim();
+    // Any `#` in the test name itself is escaped
+    let directive = description.replace("\\#", "").to_uppercase();
+    if directive.contains("# SKIP") || directive.contains("# TODO") {
+        return None;
+    }
+    Some(unescape(description))
+}
+
+fn unescape(name: &str) -> String {
+    name.replace("\\#", "#").replace("\\\\", "\\")
+}
+
+#[cfg(test)]
+pub(crate) mod test_js_node_test {
+    use bencher_json::project::report::JsonAverage;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        adapters::test_util::{convert_file_path, opt_convert_file_path, validate_latency},
+        AdapterResults, Settings,
+    };
+
+    use super::AdapterJsNodeTest;
+
+    fn convert_js_node_test(suffix: &str) -> AdapterResults {
+        let file_path = format!("./tool_output/js/node_test/{suffix}.txt");
+        convert_file_path::<AdapterJsNodeTest>(&file_path)
+    }
+
+    #[test]
+    fn test_adapter_js_node_test_average() {
+        let file_path = "./tool_output/js/node_test/four.txt";
+        assert_eq!(
+            None,
+            opt_convert_file_path::<AdapterJsNodeTest>(
+                file_path,
+                Settings {
+                    average: Some(JsonAverage::Mean)
+                }
+            )
+        );
+
+        assert_eq!(
+            None,
+            opt_convert_file_path::<AdapterJsNodeTest>(
+                file_path,
+                Settings {
+                    average: Some(JsonAverage::Median)
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_adapter_js_node_test_not_tap() {
+        assert_eq!(
+            None,
+            opt_convert_file_path::<AdapterJsNodeTest>(
+                "./tool_output/js/time/four.txt",
+                Settings::default()
+            )
+        );
+    }
+
+    #[test]
+    fn test_adapter_js_node_test_four() {
+        let results = convert_js_node_test("four");
+        validate_adapter_js_node_test(&results);
+    }
+
+    pub fn validate_adapter_js_node_test(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 4);
+
+        let metrics = results.get("fib 10").unwrap();
+        validate_latency(metrics, 524_417.0, None, None);
+
+        let metrics = results.get("fib > fib 20").unwrap();
+        validate_latency(metrics, 1_612_208.0, None, None);
+
+        let metrics = results.get("fib > fib 30").unwrap();
+        validate_latency(metrics, 14_263_916.0, None, None);
+
+        let metrics = results.get("sleep # 100ms").unwrap();
+        validate_latency(metrics, 101_127_042.0, None, None);
+    }
+}
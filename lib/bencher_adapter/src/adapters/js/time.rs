@@ -1,3 +1,5 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
 
 use nom::{
@@ -11,7 +13,8 @@ use nom::{
 
 use crate::{
     adapters::util::{
-        latency_as_nanos, nom_error, parse_benchmark_name_chars, parse_u64, parse_units, NomError,
+        latency_as_nanos, nom_error, parse_benchmark_name_chars, parse_u64, parse_units,
+        read_lines, NomError,
     },
     results::adapter_results::AdapterResults,
     Adaptable, Settings,
@@ -21,13 +24,30 @@ pub struct AdapterJsTime;
 
 impl Adaptable for AdapterJsTime {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        Self::parse_lines(input.lines(), settings)
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        read_lines(reader, |lines| Self::parse_lines(lines, settings))
+    }
+}
+
+impl AdapterJsTime {
+    fn parse_lines<L>(lines: impl Iterator<Item = L>, settings: Settings) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
         if let Some(JsonAverage::Mean | JsonAverage::Median) = settings.average {
             return None;
         }
 
         let mut benchmark_metrics = Vec::new();
 
-        for line in input.lines() {
+        for line in lines {
+            let line = line.as_ref();
             if let Ok((remainder, benchmark_metric)) = parse_time(line) {
                 if remainder.is_empty() {
                     benchmark_metrics.push(benchmark_metric);
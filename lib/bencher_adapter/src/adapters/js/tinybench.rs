@@ -0,0 +1,181 @@
+use std::io::{self, BufRead, Seek};
+
+use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonAny, JsonNewMetric};
+use ordered_float::OrderedFloat;
+use serde::Deserialize;
+
+use crate::{
+    adapters::util::{latency_as_nanos, read_json, Units},
+    results::adapter_results::AdapterResults,
+    Adaptable, AdapterError, Settings,
+};
+
+/// The tinybench tasks as JSON, ie `JSON.stringify(bench.tasks.map(({ name, result }) => ({ name, result })))`
+pub struct AdapterJsTinybench;
+
+impl Adaptable for AdapterJsTinybench {
+    fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        serde_json::from_str::<Tinybench>(input)
+            .ok()?
+            .convert(settings)
+            .ok()?
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        Ok(read_json::<_, Tinybench>(reader)?
+            .and_then(|tinybench| tinybench.convert(settings).ok().flatten()))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tinybench(pub Vec<TinybenchTask>);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TinybenchTask {
+    pub name: BenchmarkName,
+    pub result: Option<TinybenchResult>,
+}
+
+// https://github.com/tinylibs/tinybench/blob/main/src/types.ts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum TinybenchResult {
+    /// The task threw an error, so it does not have any statistics
+    Error { error: JsonAny },
+    /// tinybench v3 and later nest the latency statistics
+    Latency { latency: Statistics },
+    /// tinybench v2 has flat latency statistics
+    Flat(Statistics),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Statistics {
+    pub mean: OrderedFloat<f64>,
+    pub sd: OrderedFloat<f64>,
+    pub min: OrderedFloat<f64>,
+    pub max: OrderedFloat<f64>,
+    /// The median is only available in tinybench v3 and later
+    pub p50: Option<OrderedFloat<f64>>,
+}
+
+impl Tinybench {
+    fn convert(self, settings: Settings) -> Result<Option<AdapterResults>, AdapterError> {
+        let tasks = self.0;
+        let mut benchmark_metrics = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let TinybenchTask { name, result } = task;
+            let statistics = match result {
+                Some(TinybenchResult::Latency { latency }) => latency,
+                Some(TinybenchResult::Flat(statistics)) => statistics,
+                // Skip any tasks that have not run or that failed
+                Some(TinybenchResult::Error { .. }) | None => continue,
+            };
+            let Statistics {
+                mean,
+                sd,
+                min,
+                max,
+                p50,
+            } = statistics;
+
+            // JSON output is always in milliseconds
+            let units = Units::Milli;
+            let (average, lower, upper) = match settings.average.unwrap_or_default() {
+                JsonAverage::Mean => (mean, mean - sd, mean + sd),
+                JsonAverage::Median => {
+                    let Some(median) = p50 else {
+                        return Err(AdapterError::TinybenchMedian(name));
+                    };
+                    (median, min, max)
+                },
+            };
+            let json_metric = JsonNewMetric {
+                value: latency_as_nanos(average, units),
+                lower_value: Some(latency_as_nanos(lower, units)),
+                upper_value: Some(latency_as_nanos(upper, units)),
+            };
+
+            benchmark_metrics.push((name, json_metric));
+        }
+
+        Ok(AdapterResults::new_latency(benchmark_metrics))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_js_tinybench {
+    use bencher_json::project::report::JsonAverage;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        adapters::test_util::{
+            convert_file_path, convert_file_path_median, opt_convert_file_path, validate_latency,
+        },
+        AdapterResults, Settings,
+    };
+
+    use super::AdapterJsTinybench;
+
+    fn file_path(suffix: &str) -> String {
+        format!("./tool_output/js/tinybench/{suffix}.json")
+    }
+
+    fn convert_js_tinybench(suffix: &str) -> AdapterResults {
+        convert_file_path::<AdapterJsTinybench>(&file_path(suffix))
+    }
+
+    #[test]
+    fn test_adapter_js_tinybench_v2() {
+        let results = convert_js_tinybench("v2");
+        validate_adapter_js_tinybench(&results);
+    }
+
+    #[test]
+    fn test_adapter_js_tinybench_v2_median() {
+        assert_eq!(
+            None,
+            opt_convert_file_path::<AdapterJsTinybench>(
+                &file_path("v2"),
+                Settings {
+                    average: Some(JsonAverage::Median)
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_adapter_js_tinybench_v3() {
+        let results = convert_js_tinybench("v3");
+        validate_adapter_js_tinybench(&results);
+    }
+
+    #[test]
+    fn test_adapter_js_tinybench_v3_median() {
+        let results = convert_file_path_median::<AdapterJsTinybench>(&file_path("v3"));
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("fib 10").unwrap();
+        validate_latency(metrics, 500.0, Some(300.0), Some(123_400.0));
+
+        let metrics = results.get("fib 20").unwrap();
+        validate_latency(metrics, 51_500.0, Some(41_200.0), Some(482_100.0));
+    }
+
+    pub fn validate_adapter_js_tinybench(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("fib 10").unwrap();
+        validate_latency(
+            metrics,
+            521.300_000_000_000_1,
+            Some(421.300_000_000_000_07),
+            Some(621.300_000_000_000_1),
+        );
+
+        let metrics = results.get("fib 20").unwrap();
+        validate_latency(metrics, 53_000.0, Some(43_000.0), Some(63_000.0));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{results::adapter_results::AdapterResults, Adaptable, Settings};
+use std::io::{self, BufRead, Seek};
+
+use crate::{
+    adapters::util::read_json, results::adapter_results::AdapterResults, Adaptable, Settings,
+};
 
 pub struct AdapterJson;
 
@@ -6,6 +10,13 @@ impl Adaptable for AdapterJson {
     fn parse(input: &str, _settings: Settings) -> Option<AdapterResults> {
         serde_json::from_str(input).ok()
     }
+
+    fn parse_reader<R>(reader: &mut R, _settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        read_json(reader)
+    }
 }
 
 #[cfg(test)]
@@ -1,37 +1,178 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Seek},
+};
+
+use bencher_json::project::report::Adapter;
+
 use crate::{
-    results::adapter_results::AdapterResults, Adaptable, AdapterCSharp, AdapterCpp, AdapterGo,
-    AdapterJava, AdapterJs, AdapterJson, AdapterPython, AdapterRuby, AdapterRust, AdapterShell,
+    adapters::util::convert_reader_any, results::adapter_results::AdapterResults, Adaptable,
     Settings,
 };
 
+/// The specific adapters that the magic adapter tries, in order of priority
+pub const MAGIC_ADAPTERS: [Adapter; 20] = [
+    Adapter::Json,
+    Adapter::CSharpDotNet,
+    Adapter::CppCatch2,
+    Adapter::CppGoogle,
+    Adapter::GoBench,
+    Adapter::JavaJmh,
+    Adapter::JsBenchmark,
+    Adapter::JsTime,
+    Adapter::JsNodeTest,
+    Adapter::JsTinybench,
+    Adapter::PythonAsv,
+    Adapter::PythonPyperf,
+    Adapter::PythonPytest,
+    Adapter::PythonRichbench,
+    Adapter::RubyBenchmark,
+    Adapter::RustBench,
+    Adapter::RustCriterion,
+    Adapter::RustIai,
+    Adapter::RustIaiCallgrind,
+    Adapter::ShellHyperfine,
+];
+
 pub struct AdapterMagic;
 
 impl Adaptable for AdapterMagic {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
-        AdapterJson::parse(input, settings)
-            .or_else(|| AdapterCSharp::parse(input, settings))
-            .or_else(|| AdapterCpp::parse(input, settings))
-            .or_else(|| AdapterGo::parse(input, settings))
-            .or_else(|| AdapterJava::parse(input, settings))
-            .or_else(|| AdapterJs::parse(input, settings))
-            .or_else(|| AdapterPython::parse(input, settings))
-            .or_else(|| AdapterRuby::parse(input, settings))
-            .or_else(|| AdapterRust::parse(input, settings))
-            .or_else(|| AdapterShell::parse(input, settings))
+        MAGIC_ADAPTERS
+            .iter()
+            .find_map(|adapter| adapter.convert(input, settings))
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        convert_reader_any(&MAGIC_ADAPTERS, reader, settings)
+    }
+}
+
+impl AdapterMagic {
+    /// Resolve the specific adapter that the magic adapter would use for the input
+    pub fn resolve(input: &str, settings: Settings) -> Option<Adapter> {
+        MAGIC_ADAPTERS
+            .into_iter()
+            .find(|adapter| adapter.convert(input, settings).is_some())
+    }
+
+    /// Try every specific adapter against the input,
+    /// recording which adapter was picked and why each of the others was rejected.
+    pub fn detect(input: &str, settings: Settings) -> MagicDetection {
+        let mut detection = MagicDetection::default();
+        for candidate in MAGIC_ADAPTERS {
+            detection.record(candidate, candidate.convert(input, settings).is_some());
+        }
+        detection
+    }
+
+    /// Try every specific adapter against the input from a reader,
+    /// recording which adapter was picked and why each of the others was rejected.
+    pub fn detect_reader<R>(reader: &mut R, settings: Settings) -> io::Result<MagicDetection>
+    where
+        R: BufRead + Seek,
+    {
+        let mut detection = MagicDetection::default();
+        for candidate in MAGIC_ADAPTERS {
+            let is_match = candidate.convert_reader(reader, settings)?.is_some();
+            detection.record(candidate, is_match);
+        }
+        Ok(detection)
+    }
+}
+
+/// The result of magic adapter auto-detection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MagicDetection {
+    /// The adapter that was picked, if any
+    pub adapter: Option<Adapter>,
+    /// The adapters that were not picked and why
+    pub rejected: Vec<(Adapter, MagicRejection)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MagicRejection {
+    /// The adapter could not parse the input
+    NoMatch,
+    /// The adapter could parse the input, but a higher priority adapter was picked
+    LowerPriority,
+}
+
+impl MagicDetection {
+    fn record(&mut self, candidate: Adapter, is_match: bool) {
+        match (self.adapter, is_match) {
+            (None, true) => self.adapter = Some(candidate),
+            (Some(_), true) => self
+                .rejected
+                .push((candidate, MagicRejection::LowerPriority)),
+            (_, false) => self.rejected.push((candidate, MagicRejection::NoMatch)),
+        }
+    }
+
+    /// The adapters that also matched the input but were not picked
+    pub fn ambiguous(&self) -> impl Iterator<Item = Adapter> + '_ {
+        self.rejected(MagicRejection::LowerPriority)
+    }
+
+    fn rejected(&self, rejection: MagicRejection) -> impl Iterator<Item = Adapter> + '_ {
+        self.rejected
+            .iter()
+            .filter(move |(_, r)| *r == rejection)
+            .map(|(adapter, _)| *adapter)
+    }
+}
+
+impl fmt::Display for MagicDetection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |rejection| {
+            self.rejected(rejection)
+                .map(|adapter| adapter.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if let Some(adapter) = self.adapter {
+            write!(f, "Magic adapter matched: {adapter}")?;
+        } else {
+            write!(f, "Magic adapter did not match any adapter")?;
+        }
+        let ambiguous = join(MagicRejection::LowerPriority);
+        if !ambiguous.is_empty() {
+            write!(
+                f,
+                "\nRejected (also matched, but lower priority): {ambiguous}"
+            )?;
+        }
+        let no_match = join(MagicRejection::NoMatch);
+        if !no_match.is_empty() {
+            write!(f, "\nRejected (no match): {no_match}")?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test_magic {
-    use super::AdapterMagic;
+    use bencher_json::project::report::Adapter;
+    use pretty_assertions::assert_eq;
+
+    use super::{AdapterMagic, MagicRejection, MAGIC_ADAPTERS};
     use crate::adapters::{
         c_sharp::{dot_net::test_c_sharp_dot_net, AdapterCSharp},
         cpp::{catch2::test_cpp_catch2, google::test_cpp_google},
         go::bench::test_go_bench,
         java::jmh::test_java_jmh,
-        js::{benchmark::test_js_benchmark, time::test_js_time},
+        js::{
+            benchmark::test_js_benchmark, node_test::test_js_node_test, time::test_js_time,
+            tinybench::test_js_tinybench,
+        },
         json::test_json,
-        python::{asv::test_python_asv, pytest::test_python_pytest},
+        python::{
+            asv::test_python_asv, pyperf::test_python_pyperf, pytest::test_python_pytest,
+            richbench::test_python_richbench,
+        },
         ruby::benchmark::test_ruby_benchmark,
         rust::{
             bench::test_rust_bench, criterion::test_rust_criterion, iai::test_rust_iai,
@@ -40,6 +181,49 @@ mod test_magic {
         shell::hyperfine::test_shell_hyperfine,
         test_util::convert_file_path,
     };
+    use crate::Settings;
+
+    #[test]
+    fn test_adapter_magic_detect() {
+        let contents = std::fs::read_to_string("./tool_output/rust/bench/many.txt").unwrap();
+        let detection = AdapterMagic::detect(&contents, Settings::default());
+        assert_eq!(detection.adapter, Some(Adapter::RustBench));
+        assert_eq!(detection.rejected.len(), MAGIC_ADAPTERS.len() - 1);
+        assert!(detection
+            .rejected
+            .contains(&(Adapter::RustCriterion, MagicRejection::NoMatch)));
+        assert_eq!(
+            AdapterMagic::resolve(&contents, Settings::default()),
+            Some(Adapter::RustBench)
+        );
+
+        let contents = std::fs::read_to_string("./tool_output/rust/criterion/many.txt").unwrap();
+        let detection = AdapterMagic::detect(&contents, Settings::default());
+        assert_eq!(detection.adapter, Some(Adapter::RustCriterion));
+        assert!(detection
+            .rejected
+            .contains(&(Adapter::RustBench, MagicRejection::NoMatch)));
+
+        let detection = AdapterMagic::detect("not benchmark output", Settings::default());
+        assert_eq!(detection.adapter, None);
+        assert_eq!(detection.rejected.len(), MAGIC_ADAPTERS.len());
+        assert_eq!(detection.ambiguous().count(), 0);
+    }
+
+    #[test]
+    fn test_adapter_magic_detect_reader() {
+        let contents = std::fs::read_to_string("./tool_output/rust/criterion/many.txt").unwrap();
+        let mut reader = std::io::Cursor::new(contents.as_bytes());
+        let detection = AdapterMagic::detect_reader(&mut reader, Settings::default()).unwrap();
+        assert_eq!(
+            detection,
+            AdapterMagic::detect(&contents, Settings::default())
+        );
+
+        // Invalid UTF-8 is an error when reading the input, not a failed match
+        let mut reader = std::io::Cursor::new(b"test bench_a ... \xff\xfe".as_slice());
+        assert!(AdapterMagic::detect_reader(&mut reader, Settings::default()).is_err());
+    }
 
     #[test]
     fn test_adapter_magic_json_latency() {
@@ -95,18 +279,48 @@ mod test_magic {
         test_js_time::validate_adapter_js_time(&results);
     }
 
+    #[test]
+    fn test_adapter_magic_js_node_test() {
+        let results = convert_file_path::<AdapterMagic>("./tool_output/js/node_test/four.txt");
+        test_js_node_test::validate_adapter_js_node_test(&results);
+    }
+
+    #[test]
+    fn test_adapter_magic_js_tinybench() {
+        let results = convert_file_path::<AdapterMagic>("./tool_output/js/tinybench/v2.json");
+        test_js_tinybench::validate_adapter_js_tinybench(&results);
+    }
+
     #[test]
     fn test_adapter_python_asv() {
         let results = convert_file_path::<AdapterMagic>("./tool_output/python/asv/six.txt");
         test_python_asv::validate_adapter_python_asv(&results);
     }
 
+    #[test]
+    fn test_adapter_python_asv_json() {
+        let results = convert_file_path::<AdapterMagic>("./tool_output/python/asv/results.json");
+        test_python_asv::validate_adapter_python_asv_json(&results);
+    }
+
+    #[test]
+    fn test_adapter_python_pyperf() {
+        let results = convert_file_path::<AdapterMagic>("./tool_output/python/pyperf/timeit.json");
+        test_python_pyperf::validate_adapter_python_pyperf(&results);
+    }
+
     #[test]
     fn test_adapter_python_pytest() {
         let results = convert_file_path::<AdapterMagic>("./tool_output/python/pytest/four.json");
         test_python_pytest::validate_adapter_python_pytest(&results);
     }
 
+    #[test]
+    fn test_adapter_python_richbench() {
+        let results = convert_file_path::<AdapterMagic>("./tool_output/python/richbench/two.txt");
+        test_python_richbench::validate_adapter_python_richbench(&results);
+    }
+
     #[test]
     fn test_adapter_ruby_benchmark() {
         let results = convert_file_path::<AdapterMagic>("./tool_output/ruby/benchmark/five.txt");
@@ -64,7 +64,19 @@ pub(crate) mod test_util {
     {
         let contents = std::fs::read_to_string(file_path)
             .unwrap_or_else(|e| panic!("Failed to read test file {file_path}: {e}"));
-        A::parse(&contents, settings)
+        let results = A::parse(&contents, settings);
+
+        // Parsing from a reader must always give the same results as parsing all of the input at once
+        let file = std::fs::File::open(file_path)
+            .unwrap_or_else(|e| panic!("Failed to open test file {file_path}: {e}"));
+        let reader_results = A::parse_reader(&mut std::io::BufReader::new(file), settings)
+            .unwrap_or_else(|e| panic!("Failed to read test file {file_path}: {e}"));
+        assert_eq!(
+            results, reader_results,
+            "Reader results differ for {file_path}"
+        );
+
+        results
     }
 
     pub fn validate_latency(
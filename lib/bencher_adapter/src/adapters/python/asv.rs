@@ -1,3 +1,8 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Seek},
+};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
 use nom::{
     bytes::complete::{tag, take_until1, take_while1},
@@ -6,27 +11,66 @@ use nom::{
     sequence::{delimited, tuple},
     IResult,
 };
+use serde::{de::DeserializeOwned, Deserialize};
 
 use crate::{
     adapters::util::{
-        latency_as_nanos, nom_error, parse_benchmark_name, parse_f64, parse_units, NomError,
+        latency_as_nanos, nom_error, parse_benchmark_name, parse_f64, parse_units, read_json,
+        read_lines, NomError, Units,
     },
     results::adapter_results::AdapterResults,
     Adaptable, Settings,
 };
 
+/// Either the `asv run` console output or an ASV JSON result file,
+/// ie `.asv/results/<machine>/<commit>-<environment>.json`
 pub struct AdapterPythonAsv;
 
 impl Adaptable for AdapterPythonAsv {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        if !Self::is_median(settings) {
+            return None;
+        }
+        serde_json::from_str::<AsvResults>(input)
+            .ok()
+            .and_then(AsvResults::convert)
+            .or_else(|| Self::parse_lines(input.lines()))
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        if !Self::is_median(settings) {
+            return Ok(None);
+        }
+        if let Some(results) =
+            read_json::<_, AsvResults>(&mut *reader)?.and_then(AsvResults::convert)
+        {
+            return Ok(Some(results));
+        }
+        reader.rewind()?;
+        read_lines(reader, |lines| Self::parse_lines(lines))
+    }
+}
+
+impl AdapterPythonAsv {
+    /// ASV only reports the median
+    fn is_median(settings: Settings) -> bool {
         match settings.average {
-            Some(JsonAverage::Median) | None => {},
-            Some(JsonAverage::Mean) => return None,
+            Some(JsonAverage::Median) | None => true,
+            Some(JsonAverage::Mean) => false,
         }
+    }
 
+    fn parse_lines<L>(lines: impl Iterator<Item = L>) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
         let mut benchmark_metrics = Vec::new();
 
-        for line in input.lines() {
+        for line in lines {
+            let line = line.as_ref();
             if let Ok((remainder, benchmark_metric)) = parse_asv(line) {
                 if remainder.is_empty() {
                     benchmark_metrics.push(benchmark_metric);
@@ -76,6 +120,120 @@ fn parse_asv_time(input: &str) -> IResult<&str, JsonNewMetric> {
     )(input)
 }
 
+// https://github.com/airspeed-velocity/asv/blob/main/asv/results.py
+const ASV_RESULTS_VERSION: u32 = 2;
+const RESULT_COLUMN: &str = "result";
+const PARAMS_COLUMN: &str = "params";
+const CI_99_A_COLUMN: &str = "stats_ci_99_a";
+const CI_99_B_COLUMN: &str = "stats_ci_99_b";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AsvResults {
+    pub version: u32,
+    /// The name of each column in the results for a benchmark
+    pub result_columns: Vec<String>,
+    /// The columns for each benchmark.
+    /// Trailing empty columns are omitted.
+    pub results: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+impl AsvResults {
+    fn convert(self) -> Option<AdapterResults> {
+        if self.version != ASV_RESULTS_VERSION {
+            return None;
+        }
+        let column = |name: &str| self.result_columns.iter().position(|column| column == name);
+        let result_column = column(RESULT_COLUMN)?;
+        let params_column = column(PARAMS_COLUMN);
+        let lower_column = column(CI_99_A_COLUMN);
+        let upper_column = column(CI_99_B_COLUMN);
+
+        let mut benchmark_metrics = Vec::new();
+        for (name, columns) in &self.results {
+            // Only timing benchmarks are in seconds,
+            // the units for memory and tracking benchmarks are not in the result file.
+            if !is_time_benchmark(name) {
+                continue;
+            }
+            let Some(results): Option<Vec<Option<f64>>> =
+                column_values(columns, Some(result_column))
+            else {
+                continue;
+            };
+            let params: Vec<Vec<String>> =
+                column_values(columns, params_column).unwrap_or_default();
+            let lower_values = column_values(columns, lower_column).unwrap_or_default();
+            let upper_values = column_values(columns, upper_column).unwrap_or_default();
+
+            for (index, (result, combination)) in results
+                .into_iter()
+                .zip(param_combinations(&params))
+                .enumerate()
+            {
+                // Failed or skipped benchmarks do not have a result
+                let Some(result) = result else {
+                    continue;
+                };
+                let benchmark_name = if combination.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{name}({})", combination.join(", "))
+                };
+                let Ok(benchmark_name) = benchmark_name.parse::<BenchmarkName>() else {
+                    continue;
+                };
+                let bound = |values: &[Option<f64>]| {
+                    values
+                        .get(index)
+                        .copied()
+                        .flatten()
+                        .map(|value| latency_as_nanos(value, Units::Sec))
+                };
+                let json_metric = JsonNewMetric {
+                    value: latency_as_nanos(result, Units::Sec),
+                    lower_value: bound(&lower_values),
+                    upper_value: bound(&upper_values),
+                };
+                benchmark_metrics.push((benchmark_name, json_metric));
+            }
+        }
+
+        AdapterResults::new_latency(benchmark_metrics)
+    }
+}
+
+fn is_time_benchmark(name: &str) -> bool {
+    name.rsplit('.')
+        .next()
+        .is_some_and(|name| name.starts_with("time_") || name.starts_with("timeraw_"))
+}
+
+fn column_values<T>(columns: &[serde_json::Value], column: Option<usize>) -> Option<Vec<T>>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_value(columns.get(column?)?.clone()).ok()
+}
+
+/// Every combination of the benchmark parameters, in the same order as the results.
+/// A benchmark without any parameters has a single, empty combination.
+fn param_combinations(params: &[Vec<String>]) -> Vec<Vec<&str>> {
+    params
+        .iter()
+        .fold(vec![Vec::new()], |combinations, values| {
+            combinations
+                .iter()
+                .flat_map(|combination| {
+                    values.iter().map(move |value| {
+                        let mut combination = combination.clone();
+                        combination.push(value.as_str());
+                        combination
+                    })
+                })
+                .collect()
+        })
+}
+
 #[cfg(test)]
 pub(crate) mod test_python_asv {
     use bencher_json::project::report::JsonAverage;
@@ -86,7 +244,7 @@ pub(crate) mod test_python_asv {
         AdapterResults, Settings,
     };
 
-    use super::AdapterPythonAsv;
+    use super::{param_combinations, AdapterPythonAsv};
 
     fn convert_python_asv(suffix: &str) -> AdapterResults {
         let file_path = format!("./tool_output/python/asv/{suffix}.txt");
@@ -122,6 +280,38 @@ pub(crate) mod test_python_asv {
         validate_adapter_python_asv(&results);
     }
 
+    #[test]
+    fn test_adapter_python_asv_json() {
+        let file_path = "./tool_output/python/asv/results.json";
+        let results = convert_file_path::<AdapterPythonAsv>(file_path);
+        validate_adapter_python_asv_json(&results);
+
+        assert_eq!(
+            None,
+            opt_convert_file_path::<AdapterPythonAsv>(
+                file_path,
+                Settings {
+                    average: Some(JsonAverage::Mean)
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_param_combinations() {
+        assert_eq!(param_combinations(&[]), vec![Vec::<&str>::new()]);
+
+        let params = vec![
+            vec!["1".to_owned(), "2".to_owned()],
+            vec!["'a'".to_owned(), "'b'".to_owned(), "'c'".to_owned()],
+        ];
+        let combinations = param_combinations(&params);
+        assert_eq!(combinations.len(), 6);
+        assert_eq!(combinations.first().unwrap(), &vec!["1", "'a'"]);
+        assert_eq!(combinations.get(1), Some(&vec!["1", "'b'"]));
+        assert_eq!(combinations.last().unwrap(), &vec!["2", "'c'"]);
+    }
+
     pub fn validate_adapter_python_asv(results: &AdapterResults) {
         assert_eq!(results.inner.len(), 6);
 
@@ -143,4 +333,38 @@ pub(crate) mod test_python_asv {
         let metrics = results.get("benchmarks.TimeSuite.time_range3").unwrap();
         validate_latency(metrics, 35500.0, Some(35490.0), Some(35510.0));
     }
+
+    pub fn validate_adapter_python_asv_json(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 5);
+
+        let metrics = results.get("benchmarks.TimeSuite.time_keys").unwrap();
+        validate_latency(metrics, 11200.0, Some(11190.0), Some(11210.0));
+
+        let metrics = results.get("benchmarks.TimeSuite.time_range").unwrap();
+        validate_latency(metrics, 32900.0, None, None);
+
+        let metrics = results
+            .get("benchmarks.ParamSuite.time_sort(10, 'list')")
+            .unwrap();
+        validate_latency(metrics, 250.0, Some(239.999_999_999_999_97), Some(260.0));
+
+        let metrics = results
+            .get("benchmarks.ParamSuite.time_sort(10, 'tuple')")
+            .unwrap();
+        validate_latency(metrics, 3100.0, Some(3000.0), Some(3200.0));
+
+        // Failed or skipped
+        assert!(results
+            .get("benchmarks.ParamSuite.time_sort(100, 'list')")
+            .is_none());
+
+        let metrics = results
+            .get("benchmarks.ParamSuite.time_sort(100, 'tuple')")
+            .unwrap();
+        validate_latency(metrics, 42000.0, Some(41000.0), Some(43000.0));
+
+        // Memory and tracking benchmarks are not timing benchmarks
+        assert!(results.get("benchmarks.MemSuite.mem_list").is_none());
+        assert!(results.get("benchmarks.track_items").is_none());
+    }
 }
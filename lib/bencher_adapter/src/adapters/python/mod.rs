@@ -1,16 +1,42 @@
 pub mod asv;
+pub mod pyperf;
 pub mod pytest;
+pub mod richbench;
 
-use crate::{Adaptable, AdapterResults, Settings};
+use std::io::{self, BufRead, Seek};
+
+use bencher_json::project::report::Adapter;
+
+use crate::{adapters::util::convert_reader_any, Adaptable, AdapterResults, Settings};
 use asv::AdapterPythonAsv;
+use pyperf::AdapterPythonPyperf;
 use pytest::AdapterPythonPytest;
+use richbench::AdapterPythonRichbench;
 
 pub struct AdapterPython;
 
 impl Adaptable for AdapterPython {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterPythonAsv::parse(input, settings)
+            .or_else(|| AdapterPythonPyperf::parse(input, settings))
             .or_else(|| AdapterPythonPytest::parse(input, settings))
+            .or_else(|| AdapterPythonRichbench::parse(input, settings))
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        convert_reader_any(
+            &[
+                Adapter::PythonAsv,
+                Adapter::PythonPyperf,
+                Adapter::PythonPytest,
+                Adapter::PythonRichbench,
+            ],
+            reader,
+            settings,
+        )
     }
 }
 
@@ -18,7 +44,8 @@ impl Adaptable for AdapterPython {
 mod test_go {
     use super::AdapterPython;
     use crate::adapters::{
-        python::asv::test_python_asv, python::pytest::test_python_pytest,
+        python::asv::test_python_asv, python::pyperf::test_python_pyperf,
+        python::pytest::test_python_pytest, python::richbench::test_python_richbench,
         test_util::convert_file_path,
     };
 
@@ -33,4 +60,22 @@ mod test_go {
         let results = convert_file_path::<AdapterPython>("./tool_output/python/pytest/four.json");
         test_python_pytest::validate_adapter_python_pytest(&results);
     }
+
+    #[test]
+    fn test_adapter_python_asv_json() {
+        let results = convert_file_path::<AdapterPython>("./tool_output/python/asv/results.json");
+        test_python_asv::validate_adapter_python_asv_json(&results);
+    }
+
+    #[test]
+    fn test_adapter_python_pyperf() {
+        let results = convert_file_path::<AdapterPython>("./tool_output/python/pyperf/timeit.json");
+        test_python_pyperf::validate_adapter_python_pyperf(&results);
+    }
+
+    #[test]
+    fn test_adapter_python_richbench() {
+        let results = convert_file_path::<AdapterPython>("./tool_output/python/richbench/two.txt");
+        test_python_richbench::validate_adapter_python_richbench(&results);
+    }
 }
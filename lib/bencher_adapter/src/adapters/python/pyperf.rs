@@ -0,0 +1,203 @@
+use std::io::{self, BufRead, Seek};
+
+use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
+use serde::Deserialize;
+
+use crate::{
+    adapters::util::{latency_as_nanos, read_json, Units},
+    results::{
+        adapter_results::AdapterResults,
+        results_reducer::{quantile, std_dev},
+    },
+    Adaptable, Settings,
+};
+
+/// The pyperf JSON results, ie `python -m pyperf timeit -o results.json` or `--output` for a runner script
+pub struct AdapterPythonPyperf;
+
+impl Adaptable for AdapterPythonPyperf {
+    fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        serde_json::from_str::<Pyperf>(input)
+            .ok()?
+            .convert(settings)
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        Ok(read_json::<_, Pyperf>(reader)?.and_then(|pyperf| pyperf.convert(settings)))
+    }
+}
+
+// https://pyperf.readthedocs.io/en/latest/api.html#json-format
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pyperf {
+    pub benchmarks: Vec<PyperfBenchmark>,
+    /// The metadata common to all of the benchmarks
+    pub metadata: PyperfMetadata,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PyperfBenchmark {
+    #[serde(default)]
+    pub metadata: PyperfMetadata,
+    pub runs: Vec<PyperfRun>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PyperfMetadata {
+    pub name: Option<BenchmarkName>,
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PyperfRun {
+    /// Calibration runs only have warmups
+    #[serde(default)]
+    pub values: Vec<f64>,
+}
+
+const SECOND_UNIT: &str = "second";
+
+impl Pyperf {
+    fn convert(self, settings: Settings) -> Option<AdapterResults> {
+        let Self {
+            benchmarks,
+            metadata: common,
+            ..
+        } = self;
+        let mut benchmark_metrics = Vec::with_capacity(benchmarks.len());
+        for benchmark in benchmarks {
+            let PyperfBenchmark { metadata, runs } = benchmark;
+            let Some(name) = metadata.name.or_else(|| common.name.clone()) else {
+                continue;
+            };
+            // Only timing benchmarks are supported, not byte or integer benchmarks
+            let unit = metadata.unit.as_ref().or(common.unit.as_ref());
+            if unit.is_some_and(|unit| unit != SECOND_UNIT) {
+                continue;
+            }
+
+            let mut values = runs
+                .into_iter()
+                .flat_map(|run| run.values)
+                .collect::<Vec<_>>();
+            let Some(json_metric) = metric(&mut values, settings) else {
+                continue;
+            };
+            benchmark_metrics.push((name, json_metric));
+        }
+
+        AdapterResults::new_latency(benchmark_metrics)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn metric(values: &mut [f64], settings: Settings) -> Option<JsonNewMetric> {
+    if values.is_empty() {
+        return None;
+    }
+    // JSON output is always in seconds per loop iteration
+    let units = Units::Sec;
+    let (average, lower, upper) = match settings.average.unwrap_or_default() {
+        JsonAverage::Mean => {
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let std_dev = std_dev(values)?;
+            (mean, mean - std_dev, mean + std_dev)
+        },
+        JsonAverage::Median => {
+            values.sort_unstable_by(f64::total_cmp);
+            let median = quantile(values, 0.5)?;
+            let min = *values.first()?;
+            let max = *values.last()?;
+            (median, min, max)
+        },
+    };
+    Some(JsonNewMetric {
+        value: latency_as_nanos(average, units),
+        lower_value: Some(latency_as_nanos(lower, units)),
+        upper_value: Some(latency_as_nanos(upper, units)),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test_python_pyperf {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        adapters::test_util::{convert_file_path, convert_file_path_median, validate_latency},
+        AdapterResults,
+    };
+
+    use super::AdapterPythonPyperf;
+
+    fn file_path(suffix: &str) -> String {
+        format!("./tool_output/python/pyperf/{suffix}.json")
+    }
+
+    #[test]
+    fn test_adapter_python_pyperf_timeit() {
+        let results = convert_file_path::<AdapterPythonPyperf>(&file_path("timeit"));
+        validate_adapter_python_pyperf(&results);
+    }
+
+    #[test]
+    fn test_adapter_python_pyperf_timeit_median() {
+        let results = convert_file_path_median::<AdapterPythonPyperf>(&file_path("timeit"));
+        assert_eq!(results.inner.len(), 1);
+
+        let metrics = results.get("timeit").unwrap();
+        validate_latency(metrics, 785.0, Some(760.0), Some(820.0));
+    }
+
+    #[test]
+    fn test_adapter_python_pyperf_suite() {
+        let results = convert_file_path::<AdapterPythonPyperf>(&file_path("suite"));
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("json_dumps").unwrap();
+        validate_latency(
+            metrics,
+            5_800_000.000_000_001,
+            Some(5_583_975.310_053_071),
+            Some(6_016_024.689_946_93),
+        );
+
+        let metrics = results.get("json_loads").unwrap();
+        validate_latency(
+            metrics,
+            1_600_000.0,
+            Some(1_500_000.0),
+            Some(1_700_000.000_000_000_2),
+        );
+
+        // Only timing benchmarks are supported
+        assert!(results.get("json_memory").is_none());
+    }
+
+    #[test]
+    fn test_adapter_python_pyperf_suite_median() {
+        let results = convert_file_path_median::<AdapterPythonPyperf>(&file_path("suite"));
+        assert_eq!(results.inner.len(), 2);
+
+        let metrics = results.get("json_dumps").unwrap();
+        validate_latency(metrics, 5_750_000.0, Some(5_600_000.0), Some(6_100_000.0));
+
+        let metrics = results.get("json_loads").unwrap();
+        validate_latency(metrics, 1_600_000.0, Some(1_500_000.0), Some(1_700_000.0));
+    }
+
+    pub fn validate_adapter_python_pyperf(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 1);
+
+        let metrics = results.get("timeit").unwrap();
+        validate_latency(
+            metrics,
+            786.666_666_666_666_6,
+            Some(765.064_197_671_973_8),
+            Some(808.269_135_661_359_4),
+        );
+    }
+}
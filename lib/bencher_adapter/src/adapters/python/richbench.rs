@@ -0,0 +1,174 @@
+use std::io::{self, BufRead, Seek};
+
+use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
+
+use crate::{
+    adapters::util::{latency_as_nanos, read_lines, Units},
+    results::adapter_results::AdapterResults,
+    Adaptable, Settings,
+};
+
+/// The richbench results table, either the default rich table or `--markdown`
+pub struct AdapterPythonRichbench;
+
+impl Adaptable for AdapterPythonRichbench {
+    fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        if !Self::is_mean(settings) {
+            return None;
+        }
+        Self::parse_lines(input.lines())
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        if !Self::is_mean(settings) {
+            return Ok(None);
+        }
+        read_lines(reader, |lines| Self::parse_lines(lines))
+    }
+}
+
+// The benchmark name followed by the min, max, and mean for both functions
+const RICHBENCH_COLUMNS: usize = 7;
+// The suffix richbench uses for the comparison function
+const COMPARISON_SUFFIX: &str = " (+)";
+
+impl AdapterPythonRichbench {
+    /// richbench only reports the mean
+    fn is_mean(settings: Settings) -> bool {
+        match settings.average {
+            Some(JsonAverage::Mean) | None => true,
+            Some(JsonAverage::Median) => false,
+        }
+    }
+
+    fn parse_lines<L>(lines: impl Iterator<Item = L>) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
+        let mut benchmark_metrics = Vec::new();
+
+        for line in lines {
+            if let Some(row) = parse_richbench_row(line.as_ref()) {
+                benchmark_metrics.extend(row);
+            }
+        }
+
+        AdapterResults::new_latency(benchmark_metrics)
+    }
+}
+
+/// Parse a single row of the results table.
+/// The header, separator, and title rows are all skipped.
+fn parse_richbench_row(line: &str) -> Option<[(BenchmarkName, JsonNewMetric); 2]> {
+    let cells = line
+        .split(['│', '┃', '|'])
+        .map(str::trim)
+        .filter(|cell| !cell.is_empty())
+        .collect::<Vec<_>>();
+    let [name, min, max, mean, comparison_min, comparison_max, comparison_mean] =
+        <[&str; RICHBENCH_COLUMNS]>::try_from(cells).ok()?;
+
+    let benchmark_name: BenchmarkName = name.parse().ok()?;
+    let comparison_name: BenchmarkName = format!("{name}{COMPARISON_SUFFIX}").parse().ok()?;
+    let json_metric = metric(min, max, mean)?;
+    let comparison_metric = metric(comparison_min, comparison_max, comparison_mean)?;
+
+    Some([
+        (benchmark_name, json_metric),
+        (comparison_name, comparison_metric),
+    ])
+}
+
+fn metric(min: &str, max: &str, mean: &str) -> Option<JsonNewMetric> {
+    // richbench reports the total time in seconds for all of the `number` of iterations
+    let units = Units::Sec;
+    Some(JsonNewMetric {
+        value: latency_as_nanos(parse_seconds(mean)?, units),
+        lower_value: Some(latency_as_nanos(parse_seconds(min)?, units)),
+        upper_value: Some(latency_as_nanos(parse_seconds(max)?, units)),
+    })
+}
+
+/// The comparison function times are followed by their speedup, ie `0.685 (1.5x)`
+fn parse_seconds(cell: &str) -> Option<f64> {
+    cell.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+pub(crate) mod test_python_richbench {
+    use bencher_json::project::report::JsonAverage;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        adapters::test_util::{convert_file_path, opt_convert_file_path, validate_latency},
+        AdapterResults, Settings,
+    };
+
+    use super::AdapterPythonRichbench;
+
+    fn file_path(suffix: &str) -> String {
+        format!("./tool_output/python/richbench/{suffix}.txt")
+    }
+
+    #[test]
+    fn test_adapter_python_richbench_two() {
+        let results = convert_file_path::<AdapterPythonRichbench>(&file_path("two"));
+        validate_adapter_python_richbench(&results);
+    }
+
+    #[test]
+    fn test_adapter_python_richbench_markdown() {
+        let results = convert_file_path::<AdapterPythonRichbench>(&file_path("markdown"));
+        validate_adapter_python_richbench(&results);
+    }
+
+    #[test]
+    fn test_adapter_python_richbench_median() {
+        let results = opt_convert_file_path::<AdapterPythonRichbench>(
+            &file_path("two"),
+            Settings {
+                average: Some(JsonAverage::Median),
+            },
+        );
+        assert!(results.is_none());
+    }
+
+    pub fn validate_adapter_python_richbench(results: &AdapterResults) {
+        assert_eq!(results.inner.len(), 4);
+
+        let metrics = results.get("Sequential (range/int)").unwrap();
+        validate_latency(
+            metrics,
+            1_044_000_000.0,
+            Some(1_038_999_999.999_999_9),
+            Some(1_048_999_999.999_999_9),
+        );
+
+        let metrics = results.get("Sequential (range/int) (+)").unwrap();
+        validate_latency(
+            metrics,
+            692_000_000.0,
+            Some(685_000_000.0),
+            Some(700_000_000.0),
+        );
+
+        let metrics = results.get("Sorted list building").unwrap();
+        validate_latency(
+            metrics,
+            246_000_000.0,
+            Some(242_000_000.0),
+            Some(251_000_000.0),
+        );
+
+        let metrics = results.get("Sorted list building (+)").unwrap();
+        validate_latency(
+            metrics,
+            317_000_000.0,
+            Some(313_000_000.0),
+            Some(320_000_000.0),
+        );
+    }
+}
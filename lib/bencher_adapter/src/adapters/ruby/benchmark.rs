@@ -1,3 +1,5 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
 use nom::{
     bytes::complete::tag,
@@ -9,7 +11,9 @@ use nom::{
 };
 
 use crate::{
-    adapters::util::{latency_as_nanos, parse_benchmark_name_chars, parse_f64, NomError, Units},
+    adapters::util::{
+        latency_as_nanos, parse_benchmark_name_chars, parse_f64, read_lines, NomError, Units,
+    },
     results::adapter_results::AdapterResults,
     Adaptable, Settings,
 };
@@ -18,6 +22,22 @@ pub struct AdapterRubyBenchmark;
 
 impl Adaptable for AdapterRubyBenchmark {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        Self::parse_lines(input.lines(), settings)
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        read_lines(reader, |lines| Self::parse_lines(lines, settings))
+    }
+}
+
+impl AdapterRubyBenchmark {
+    fn parse_lines<L>(lines: impl Iterator<Item = L>, settings: Settings) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
         if let Some(JsonAverage::Mean | JsonAverage::Median) = settings.average {
             return None;
         }
@@ -25,7 +45,8 @@ impl Adaptable for AdapterRubyBenchmark {
         let mut benchmark_metrics = Vec::new();
 
         let mut header = false;
-        for line in input.lines() {
+        for line in lines {
+            let line = line.as_ref();
             if !header {
                 header = parse_header(line).is_ok();
                 continue;
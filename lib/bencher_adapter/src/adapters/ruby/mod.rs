@@ -1,5 +1,7 @@
 pub mod benchmark;
 
+use std::io::{self, BufRead, Seek};
+
 use crate::{Adaptable, AdapterResults, Settings};
 use benchmark::AdapterRubyBenchmark;
 
@@ -9,6 +11,13 @@ impl Adaptable for AdapterRuby {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterRubyBenchmark::parse(input, settings)
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        AdapterRubyBenchmark::parse_reader(reader, settings)
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
 use nom::{
     bytes::complete::{tag, take_until1},
@@ -9,7 +11,8 @@ use nom::{
 
 use crate::{
     adapters::util::{
-        latency_as_nanos, parse_benchmark_name, parse_number_as_f64, parse_units, NomError,
+        latency_as_nanos, parse_benchmark_name, parse_number_as_f64, parse_units, read_lines,
+        NomError,
     },
     results::adapter_results::AdapterResults,
     Adaptable, Settings,
@@ -19,6 +22,22 @@ pub struct AdapterRustBench;
 
 impl Adaptable for AdapterRustBench {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        Self::parse_lines(input.lines(), settings)
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        read_lines(reader, |lines| Self::parse_lines(lines, settings))
+    }
+}
+
+impl AdapterRustBench {
+    fn parse_lines<L>(lines: impl Iterator<Item = L>, settings: Settings) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
         match settings.average {
             Some(JsonAverage::Median) | None => {},
             Some(JsonAverage::Mean) => return None,
@@ -26,7 +45,8 @@ impl Adaptable for AdapterRustBench {
 
         let mut benchmark_metrics = Vec::new();
 
-        for line in input.lines() {
+        for line in lines {
+            let line = line.as_ref();
             if let Ok((remainder, benchmark_metric)) = parse_cargo(line) {
                 if remainder.is_empty() {
                     benchmark_metrics.push(benchmark_metric);
@@ -1,3 +1,5 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonNewMetric};
 use nom::{
     bytes::complete::tag,
@@ -11,7 +13,8 @@ use ordered_float::OrderedFloat;
 
 use crate::{
     adapters::util::{
-        latency_as_nanos, nom_error, parse_benchmark_name, parse_f64, parse_units, NomError,
+        latency_as_nanos, nom_error, parse_benchmark_name, parse_f64, parse_units, read_lines,
+        NomError,
     },
     results::adapter_results::AdapterResults,
     Adaptable, Settings,
@@ -21,6 +24,22 @@ pub struct AdapterRustCriterion;
 
 impl Adaptable for AdapterRustCriterion {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
+        Self::parse_lines(input.lines(), settings)
+    }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        read_lines(reader, |lines| Self::parse_lines(lines, settings))
+    }
+}
+
+impl AdapterRustCriterion {
+    fn parse_lines<L>(lines: impl Iterator<Item = L>, settings: Settings) -> Option<AdapterResults>
+    where
+        L: AsRef<str>,
+    {
         match settings.average {
             Some(JsonAverage::Mean) | None => {},
             Some(JsonAverage::Median) => return None,
@@ -29,8 +48,10 @@ impl Adaptable for AdapterRustCriterion {
         let mut benchmark_metrics = Vec::new();
 
         let mut prior_line = None;
-        for line in input.lines() {
-            if let Ok((remainder, benchmark_metric)) = parse_criterion(prior_line, line) {
+        for line in lines {
+            if let Ok((remainder, benchmark_metric)) =
+                parse_criterion(prior_line.as_ref().map(AsRef::as_ref), line.as_ref())
+            {
                 if remainder.is_empty() {
                     benchmark_metrics.push(benchmark_metric);
                 }
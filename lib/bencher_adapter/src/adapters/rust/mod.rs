@@ -3,10 +3,14 @@ pub mod criterion;
 pub mod iai;
 pub mod iai_callgrind;
 
+use std::io::{self, BufRead, Seek};
+
+use bencher_json::project::report::Adapter;
+
 use self::{
     criterion::AdapterRustCriterion, iai::AdapterRustIai, iai_callgrind::AdapterRustIaiCallgrind,
 };
-use crate::{Adaptable, AdapterResults, Settings};
+use crate::{adapters::util::convert_reader_any, Adaptable, AdapterResults, Settings};
 use bench::AdapterRustBench;
 
 pub struct AdapterRust;
@@ -18,6 +22,22 @@ impl Adaptable for AdapterRust {
             .or_else(|| AdapterRustIai::parse(input, settings))
             .or_else(|| AdapterRustIaiCallgrind::parse(input, settings))
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        convert_reader_any(
+            &[
+                Adapter::RustBench,
+                Adapter::RustCriterion,
+                Adapter::RustIai,
+                Adapter::RustIaiCallgrind,
+            ],
+            reader,
+            settings,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,11 @@
+use std::io::{self, BufRead, Seek};
+
 use bencher_json::{project::report::JsonAverage, BenchmarkName, JsonAny, JsonNewMetric};
 use ordered_float::OrderedFloat;
 use serde::Deserialize;
 
 use crate::{
-    adapters::util::{latency_as_nanos, Units},
+    adapters::util::{latency_as_nanos, read_json, Units},
     results::adapter_results::AdapterResults,
     Adaptable, AdapterError, Settings,
 };
@@ -17,6 +19,14 @@ impl Adaptable for AdapterShellHyperfine {
             .convert(settings)
             .ok()?
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        Ok(read_json::<_, Hyperfine>(reader)?
+            .and_then(|hyperfine| hyperfine.convert(settings).ok().flatten()))
+    }
 }
 
 // https://github.com/sharkdp/hyperfine/blob/ef4049f8f897d4adc4c47a07e60e39d9760fb9ed/src/benchmark/benchmark_result.rs#L11
@@ -1,5 +1,7 @@
 pub mod hyperfine;
 
+use std::io::{self, BufRead, Seek};
+
 use crate::{Adaptable, AdapterResults, Settings};
 use hyperfine::AdapterShellHyperfine;
 
@@ -9,6 +11,13 @@ impl Adaptable for AdapterShell {
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterShellHyperfine::parse(input, settings)
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        AdapterShellHyperfine::parse_reader(reader, settings)
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,10 @@
-use std::{fmt, str::FromStr};
+use std::{
+    fmt,
+    io::{self, BufRead, Read, Seek},
+    str::FromStr,
+};
 
-use bencher_json::BenchmarkName;
+use bencher_json::{project::report::Adapter, BenchmarkName};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -14,11 +18,11 @@ use ordered_float::OrderedFloat;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use serde::{
-    de::{self, Visitor},
+    de::{self, DeserializeOwned, Visitor},
     Deserialize, Deserializer,
 };
 
-use crate::AdapterError;
+use crate::{Adaptable, AdapterError, AdapterResults, Settings};
 
 pub type NomError = nom::Err<nom::error::Error<String>>;
 
@@ -238,3 +242,52 @@ pub fn parse_benchmark_name(name: &str) -> Result<BenchmarkName, NomError> {
         Err(nom_error(name))
     }
 }
+
+/// Parse the lines from a reader one at a time,
+/// so that all of the input is never held in memory at once.
+/// Reading stops at the first line that can not be read, and that error is returned.
+pub fn read_lines<R, T, F>(reader: &mut R, parse_lines: F) -> io::Result<T>
+where
+    R: BufRead,
+    F: FnOnce(&mut dyn Iterator<Item = String>) -> T,
+{
+    let mut error = None;
+    let parsed = {
+        let mut lines = reader
+            .lines()
+            .map_while(|line| line.map_err(|e| error = Some(e)).ok());
+        parse_lines(&mut lines)
+    };
+    error.map_or(Ok(parsed), Err)
+}
+
+/// Deserialize JSON directly from a reader, without reading all of the input into a string first.
+/// Only errors from reading are returned, and input that is not valid JSON for the type is `None`.
+pub fn read_json<R, T>(reader: R) -> io::Result<Option<T>>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    match serde_json::from_reader(reader) {
+        Ok(json) => Ok(Some(json)),
+        Err(e) if e.is_io() => Err(e.into()),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Convert the input from a reader with the first of the adapters that is able to parse it
+pub fn convert_reader_any<R>(
+    adapters: &[Adapter],
+    reader: &mut R,
+    settings: Settings,
+) -> io::Result<Option<AdapterResults>>
+where
+    R: BufRead + Seek,
+{
+    for adapter in adapters {
+        if let Some(results) = adapter.convert_reader(reader, settings)? {
+            return Ok(Some(results));
+        }
+    }
+    Ok(None)
+}
@@ -8,4 +8,8 @@ pub enum AdapterError {
     BenchmarkUnits(String),
     #[error("Failed to convert results: {0}")]
     Convert(String),
+    #[error(
+        "Failed to get the median for benchmark ({0}). The median requires tinybench v3 or later."
+    )]
+    TinybenchMedian(bencher_json::BenchmarkName),
 }
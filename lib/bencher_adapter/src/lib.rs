@@ -1,3 +1,5 @@
+use std::io::{self, BufRead, Seek};
+
 pub mod adapters;
 pub mod error;
 pub mod results;
@@ -7,10 +9,16 @@ use adapters::{
     cpp::{catch2::AdapterCppCatch2, google::AdapterCppGoogle, AdapterCpp},
     go::{bench::AdapterGoBench, AdapterGo},
     java::{jmh::AdapterJavaJmh, AdapterJava},
-    js::{benchmark::AdapterJsBenchmark, time::AdapterJsTime, AdapterJs},
+    js::{
+        benchmark::AdapterJsBenchmark, node_test::AdapterJsNodeTest, time::AdapterJsTime,
+        tinybench::AdapterJsTinybench, AdapterJs,
+    },
     json::AdapterJson,
     magic::AdapterMagic,
-    python::{asv::AdapterPythonAsv, pytest::AdapterPythonPytest, AdapterPython},
+    python::{
+        asv::AdapterPythonAsv, pyperf::AdapterPythonPyperf, pytest::AdapterPythonPytest,
+        richbench::AdapterPythonRichbench, AdapterPython,
+    },
     ruby::{benchmark::AdapterRubyBenchmark, AdapterRuby},
     rust::{
         bench::AdapterRustBench, criterion::AdapterRustCriterion, iai::AdapterRustIai,
@@ -28,7 +36,34 @@ pub trait Adaptable {
         Self::parse(input, settings)
     }
 
+    /// Convert the input from a reader, starting from the beginning of the reader.
+    /// The reader is rewound first, so the same reader can be tried with more than one adapter.
+    fn convert_reader<R>(
+        &self,
+        reader: &mut R,
+        settings: Settings,
+    ) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        reader.rewind()?;
+        Self::parse_reader(reader, settings)
+    }
+
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults>;
+
+    /// Parse the input from a reader.
+    /// By default, all of the input is read into memory and then parsed.
+    /// Adapters that are able to parse their input incrementally should override this,
+    /// so that very large benchmark harness output never has to be held in memory all at once.
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        Ok(Self::parse(&input, settings))
+    }
 }
 
 impl Adaptable for Adapter {
@@ -48,9 +83,13 @@ impl Adaptable for Adapter {
             Adapter::Js => AdapterJs::parse(input, settings),
             Adapter::JsBenchmark => AdapterJsBenchmark::parse(input, settings),
             Adapter::JsTime => AdapterJsTime::parse(input, settings),
+            Adapter::JsNodeTest => AdapterJsNodeTest::parse(input, settings),
+            Adapter::JsTinybench => AdapterJsTinybench::parse(input, settings),
             Adapter::Python => AdapterPython::parse(input, settings),
             Adapter::PythonAsv => AdapterPythonAsv::parse(input, settings),
             Adapter::PythonPytest => AdapterPythonPytest::parse(input, settings),
+            Adapter::PythonPyperf => AdapterPythonPyperf::parse(input, settings),
+            Adapter::PythonRichbench => AdapterPythonRichbench::parse(input, settings),
             Adapter::Ruby => AdapterRuby::parse(input, settings),
             Adapter::RubyBenchmark => AdapterRubyBenchmark::parse(input, settings),
             Adapter::Rust => AdapterRust::parse(input, settings),
@@ -63,9 +102,59 @@ impl Adaptable for Adapter {
         }
     }
 
+    fn convert_reader<R>(
+        &self,
+        reader: &mut R,
+        settings: Settings,
+    ) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        reader.rewind()?;
+        match self {
+            Adapter::Magic => AdapterMagic::parse_reader(reader, settings),
+            Adapter::Json => AdapterJson::parse_reader(reader, settings),
+            Adapter::CSharp => AdapterCSharp::parse_reader(reader, settings),
+            Adapter::CSharpDotNet => AdapterCSharpDotNet::parse_reader(reader, settings),
+            Adapter::Cpp => AdapterCpp::parse_reader(reader, settings),
+            Adapter::CppCatch2 => AdapterCppCatch2::parse_reader(reader, settings),
+            Adapter::CppGoogle => AdapterCppGoogle::parse_reader(reader, settings),
+            Adapter::Go => AdapterGo::parse_reader(reader, settings),
+            Adapter::GoBench => AdapterGoBench::parse_reader(reader, settings),
+            Adapter::Java => AdapterJava::parse_reader(reader, settings),
+            Adapter::JavaJmh => AdapterJavaJmh::parse_reader(reader, settings),
+            Adapter::Js => AdapterJs::parse_reader(reader, settings),
+            Adapter::JsBenchmark => AdapterJsBenchmark::parse_reader(reader, settings),
+            Adapter::JsTime => AdapterJsTime::parse_reader(reader, settings),
+            Adapter::JsNodeTest => AdapterJsNodeTest::parse_reader(reader, settings),
+            Adapter::JsTinybench => AdapterJsTinybench::parse_reader(reader, settings),
+            Adapter::Python => AdapterPython::parse_reader(reader, settings),
+            Adapter::PythonAsv => AdapterPythonAsv::parse_reader(reader, settings),
+            Adapter::PythonPytest => AdapterPythonPytest::parse_reader(reader, settings),
+            Adapter::PythonPyperf => AdapterPythonPyperf::parse_reader(reader, settings),
+            Adapter::PythonRichbench => AdapterPythonRichbench::parse_reader(reader, settings),
+            Adapter::Ruby => AdapterRuby::parse_reader(reader, settings),
+            Adapter::RubyBenchmark => AdapterRubyBenchmark::parse_reader(reader, settings),
+            Adapter::Rust => AdapterRust::parse_reader(reader, settings),
+            Adapter::RustBench => AdapterRustBench::parse_reader(reader, settings),
+            Adapter::RustCriterion => AdapterRustCriterion::parse_reader(reader, settings),
+            Adapter::RustIai => AdapterRustIai::parse_reader(reader, settings),
+            Adapter::RustIaiCallgrind => AdapterRustIaiCallgrind::parse_reader(reader, settings),
+            Adapter::Shell => AdapterShell::parse_reader(reader, settings),
+            Adapter::ShellHyperfine => AdapterShellHyperfine::parse_reader(reader, settings),
+        }
+    }
+
     fn parse(input: &str, settings: Settings) -> Option<AdapterResults> {
         AdapterMagic::parse(input, settings)
     }
+
+    fn parse_reader<R>(reader: &mut R, settings: Settings) -> io::Result<Option<AdapterResults>>
+    where
+        R: BufRead + Seek,
+    {
+        AdapterMagic::parse_reader(reader, settings)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use bencher_json::project::{
     metric::Mean,
-    report::{Adapter, JsonFold},
+    report::{Adapter, JsonDispersion, JsonFold},
 };
 
 use crate::{Adaptable, AdapterError, Settings};
@@ -73,6 +73,17 @@ impl AdapterResultsArray {
             .into()
     }
 
+    /// The dispersion of each measure for each benchmark across all of the results.
+    /// The dispersion is stored under the original measure.
+    pub fn dispersion(self, dispersion: JsonDispersion) -> AdapterResults {
+        ResultsReducer::from(self)
+            .inner
+            .into_iter()
+            .map(|(benchmark_name, results)| (benchmark_name, results.dispersion(dispersion)))
+            .collect::<ResultsMap>()
+            .into()
+    }
+
     pub fn fold(self, fold: JsonFold) -> AdapterResults {
         if self.inner.is_empty() {
             return AdapterResults::default();
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 
-use bencher_json::{project::metric::Median, BenchmarkName, JsonNewMetric, MeasureNameId};
+use bencher_json::{
+    project::{metric::Median, report::JsonDispersion},
+    BenchmarkName, JsonNewMetric, MeasureNameId,
+};
+use ordered_float::OrderedFloat;
 
 use super::{
     adapter_metrics::AdapterMetrics, adapter_results::AdapterResults, AdapterResultsArray,
@@ -63,4 +67,93 @@ impl MeasuresMap {
         }
         metric_map.into()
     }
+
+    pub(crate) fn dispersion(self, dispersion: JsonDispersion) -> AdapterMetrics {
+        let mut metric_map = HashMap::new();
+        for (measure, metrics) in self.inner {
+            let mut values = metrics
+                .into_iter()
+                .map(|metric| metric.value.into_inner())
+                .collect::<Vec<_>>();
+            let value = match dispersion {
+                JsonDispersion::StdDev => std_dev(&values),
+                JsonDispersion::Iqr => iqr(&mut values),
+            };
+            if let Some(value) = value {
+                metric_map.insert(
+                    measure,
+                    JsonNewMetric {
+                        value: OrderedFloat(value),
+                        lower_value: None,
+                        upper_value: None,
+                    },
+                );
+            }
+        }
+        metric_map.into()
+    }
+}
+
+/// The sample standard deviation, which is zero for a single value
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn std_dev(values: &[f64]) -> Option<f64> {
+    match values.len() {
+        0 => return None,
+        1 => return Some(0.0),
+        _ => {},
+    }
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (count - 1.0);
+    Some(variance.sqrt())
+}
+
+/// The interquartile range, using linear interpolation between the closest ranks
+fn iqr(values: &mut [f64]) -> Option<f64> {
+    values.sort_unstable_by(f64::total_cmp);
+    Some(quantile(values, 0.75)? - quantile(values, 0.25)?)
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub(crate) fn quantile(sorted: &[f64], quantile: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = quantile * last as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let lower_value = *sorted.get(lower)?;
+    let upper_value = *sorted.get(upper)?;
+    Some(lower_value + (upper_value - lower_value) * (rank - rank.floor()))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{iqr, std_dev};
+
+    #[test]
+    fn test_std_dev() {
+        assert_eq!(std_dev(&[]), None);
+        assert_eq!(std_dev(&[1.0]), Some(0.0));
+        assert_eq!(
+            std_dev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]),
+            Some(2.138_089_935_299_395)
+        );
+    }
+
+    #[test]
+    fn test_iqr() {
+        assert_eq!(iqr(&mut []), None);
+        assert_eq!(iqr(&mut [1.0]), Some(0.0));
+        assert_eq!(iqr(&mut [4.0, 1.0, 3.0, 2.0, 5.0]), Some(2.0));
+        assert_eq!(iqr(&mut [1.0, 2.0, 3.0, 4.0]), Some(1.5));
+    }
 }
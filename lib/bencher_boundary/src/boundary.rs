@@ -1,6 +1,7 @@
-use bencher_json::{project::boundary::BoundaryLimit, Boundary, ModelTest, SampleSize};
+use bencher_json::{project::boundary::BoundaryLimit, Boundary, Decay, ModelTest, SampleSize};
 use slog::Logger;
 
+use crate::ewma::Ewma;
 use crate::limits::{MetricsLimits, NormalTestKind};
 use crate::ln::Ln;
 use crate::mean::Mean;
@@ -14,6 +15,7 @@ pub struct MetricsBoundary {
 }
 
 impl MetricsBoundary {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         log: &Logger,
         datum: f64,
@@ -22,6 +24,7 @@ impl MetricsBoundary {
         min_sample_size: Option<SampleSize>,
         lower_boundary: Option<Boundary>,
         upper_boundary: Option<Boundary>,
+        decay: Option<Decay>,
     ) -> Result<Self, BoundaryError> {
         Self::new_inner(
             log,
@@ -31,10 +34,12 @@ impl MetricsBoundary {
             min_sample_size,
             lower_boundary,
             upper_boundary,
+            decay,
         )
         .map(Option::unwrap_or_default)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn new_inner(
         log: &Logger,
         datum: f64,
@@ -43,6 +48,7 @@ impl MetricsBoundary {
         min_sample_size: Option<SampleSize>,
         lower_boundary: Option<Boundary>,
         upper_boundary: Option<Boundary>,
+        decay: Option<Decay>,
     ) -> Result<Option<Self>, BoundaryError> {
         // If there is no boundary, then simply return.
         if lower_boundary.is_none() && upper_boundary.is_none() {
@@ -106,6 +112,14 @@ impl MetricsBoundary {
             ModelTest::DeltaIqr => {
                 Self::new_iqr(log, datum, data, true, lower_boundary, upper_boundary)
             },
+            ModelTest::Ewma => Self::new_ewma(
+                log,
+                datum,
+                data,
+                decay.unwrap_or_default(),
+                lower_boundary,
+                upper_boundary,
+            ),
         }
     }
 
@@ -186,6 +200,42 @@ impl MetricsBoundary {
         Ok(Some(Self { limits, outlier }))
     }
 
+    fn new_ewma(
+        log: &Logger,
+        datum: f64,
+        data: &[f64],
+        decay: Decay,
+        lower_boundary: Option<Boundary>,
+        upper_boundary: Option<Boundary>,
+    ) -> Result<Option<Self>, BoundaryError> {
+        let lower_boundary = lower_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+        let upper_boundary = upper_boundary
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(BoundaryError::Valid)?;
+
+        // Get the exponentially weighted mean and standard deviation of the historical data.
+        let Some(Ewma { mean, std_dev }) = Ewma::new(data, decay) else {
+            return Ok(None);
+        };
+
+        slog::debug!(log, "EWMA: decay={decay}");
+        let limits = MetricsLimits::new_normal(
+            log,
+            mean,
+            std_dev,
+            NormalTestKind::Z,
+            lower_boundary,
+            upper_boundary,
+        )?;
+        let outlier = limits.outlier(datum);
+
+        Ok(Some(Self { limits, outlier }))
+    }
+
     fn new_log_normal(
         log: &Logger,
         datum: f64,
@@ -0,0 +1,252 @@
+use bencher_json::{Boundary, ModelTest, SampleSize};
+use statrs::distribution::{ContinuousCDF, Normal, StudentsT};
+
+use crate::mean::{mean, std_deviation};
+
+/// Results with a larger absolute skewness are not treated as normally distributed
+const MAX_ABS_SKEWNESS: f64 = 1.0;
+/// Skewness is not meaningful for very small samples
+const MIN_SKEWNESS_SAMPLE_SIZE: usize = 8;
+/// The largest allowed ratio of the Student's t critical value to the normal critical value
+/// for the recommended minimum sample size
+const MAX_CRITICAL_VALUE_RATIO: f64 = 1.1;
+/// The 75th percentile of the standard normal distribution
+const NORMAL_Q3: f64 = 0.674_489_750_196_081_7;
+/// Boundaries are rounded to this many decimal places
+const BOUNDARY_PRECISION: f64 = 10_000.0;
+
+/// The run-to-run noise in the results for a single benchmark and measure
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricsNoise {
+    pub sample_size: usize,
+    pub mean: f64,
+    /// `None` if all of the results are the same
+    pub std_dev: Option<f64>,
+    pub skewness: Option<f64>,
+    pub is_positive: bool,
+}
+
+impl MetricsNoise {
+    pub fn new(data: &[f64]) -> Option<Self> {
+        // Noise can not be estimated from a single result
+        if data.len() < 2 {
+            return None;
+        }
+        let mean = mean(data)?;
+        let std_dev = std_deviation(mean, data);
+        let skewness = std_dev.and_then(|std_dev| skewness(mean, std_dev, data));
+        Some(Self {
+            sample_size: data.len(),
+            mean,
+            std_dev,
+            skewness,
+            is_positive: data.iter().all(|value| *value > 0.0),
+        })
+    }
+
+    pub fn coefficient_of_variation(&self) -> Option<f64> {
+        // A mean of zero would result in an infinite coefficient of variation
+        if self.mean == 0.0 {
+            return None;
+        }
+        self.std_dev
+            .map(|std_dev| std_dev / self.mean.abs())
+            .filter(|cv| cv.is_finite())
+    }
+
+    fn is_skewed(&self) -> bool {
+        self.sample_size >= MIN_SKEWNESS_SAMPLE_SIZE
+            && self
+                .skewness
+                .is_some_and(|skewness| skewness.abs() > MAX_ABS_SKEWNESS)
+    }
+}
+
+fn skewness(mean: f64, std_dev: f64, data: &[f64]) -> Option<f64> {
+    #[allow(clippy::cast_precision_loss)]
+    let third_moment = data
+        .iter()
+        .map(|&value| (value - mean).powi(3))
+        .sum::<f64>()
+        / data.len() as f64;
+    let skewness = third_moment / std_dev.powi(3);
+    skewness.is_finite().then_some(skewness)
+}
+
+/// Recommended threshold settings for a measure, based on the noise for each of its benchmarks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdCalibration {
+    pub test: ModelTest,
+    pub min_sample_size: SampleSize,
+    pub max_sample_size: SampleSize,
+    pub boundary: Boundary,
+    /// The false positive rate for each benchmark,
+    /// so that the false positive rate across all of the benchmarks is kept under the target
+    pub benchmark_false_positive_rate: f64,
+    /// The number of standard deviations from the mean that a result must be to be flagged
+    pub critical_value: f64,
+}
+
+impl ThresholdCalibration {
+    /// The `false_positive_rate` is the target rate of reports with at least one false alert for the measure.
+    /// It must be greater than zero and less than one half.
+    pub fn new(noise: &[MetricsNoise], false_positive_rate: f64) -> Option<Self> {
+        if noise.is_empty() || false_positive_rate <= 0.0 || false_positive_rate >= 0.5 {
+            return None;
+        }
+
+        // Šidák correction for testing each benchmark separately
+        #[allow(clippy::cast_precision_loss)]
+        let benchmarks = noise.len() as f64;
+        let benchmark_false_positive_rate =
+            1.0 - (1.0 - false_positive_rate).powf(benchmarks.recip());
+        let percentile = 1.0 - benchmark_false_positive_rate;
+        let critical_value = Normal::new(0.0, 1.0).ok()?.inverse_cdf(percentile);
+        let min_sample_size = min_sample_size(percentile, critical_value)?;
+        let max_sample_size = if u32::from(min_sample_size) > u32::from(SampleSize::SIXTY_FOUR) {
+            min_sample_size
+        } else {
+            SampleSize::SIXTY_FOUR
+        };
+
+        let (test, boundary) = if noise.iter().all(|noise| noise.std_dev.is_none()) {
+            // Without any noise, any change is a real change
+            (ModelTest::Percentage, Boundary::ZERO)
+        } else if noise.iter().any(MetricsNoise::is_skewed) {
+            if noise.iter().all(|noise| noise.is_positive) {
+                (ModelTest::LogNormal, cdf_boundary(percentile)?)
+            } else {
+                // Solve `Q3 + k * IQR = critical value` for a normal distribution
+                let multiplier = (critical_value - NORMAL_Q3) / (2.0 * NORMAL_Q3);
+                (
+                    ModelTest::Iqr,
+                    round_up(multiplier.max(0.0)).try_into().ok()?,
+                )
+            }
+        } else {
+            (ModelTest::TTest, cdf_boundary(percentile)?)
+        };
+
+        Some(Self {
+            test,
+            min_sample_size,
+            max_sample_size,
+            boundary,
+            benchmark_false_positive_rate,
+            critical_value,
+        })
+    }
+
+    /// The smallest relative change from the mean that would be flagged for the benchmark
+    pub fn detectable_change(&self, noise: &MetricsNoise) -> Option<f64> {
+        if noise.std_dev.is_none() {
+            return Some(0.0);
+        }
+        noise
+            .coefficient_of_variation()
+            .map(|cv| cv * self.critical_value)
+    }
+}
+
+/// The smallest sample size where the Student's t critical value is close to the normal critical value
+fn min_sample_size(percentile: f64, critical_value: f64) -> Option<SampleSize> {
+    let max_critical_value = critical_value * MAX_CRITICAL_VALUE_RATIO;
+    let max_sample_size = u32::from(SampleSize::TWO_FIFTY_FIVE);
+    let sample_size = (u32::from(SampleSize::MIN)..max_sample_size)
+        .find(|&sample_size| {
+            StudentsT::new(0.0, 1.0, f64::from(sample_size - 1))
+                .is_ok_and(|students_t| students_t.inverse_cdf(percentile) <= max_critical_value)
+        })
+        .unwrap_or(max_sample_size);
+    sample_size.try_into().ok()
+}
+
+fn cdf_boundary(percentile: f64) -> Option<Boundary> {
+    round_up(percentile)
+        .min(f64::from(Boundary::MAX_STATISTICAL))
+        .try_into()
+        .ok()
+}
+
+// Rounding up is more conservative, as it results in fewer false positives
+fn round_up(value: f64) -> f64 {
+    (value * BOUNDARY_PRECISION).ceil() / BOUNDARY_PRECISION
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, clippy::unwrap_used)]
+mod test {
+    use bencher_json::{Boundary, ModelTest, SampleSize};
+    use pretty_assertions::assert_eq;
+
+    use super::{MetricsNoise, ThresholdCalibration};
+
+    const DATA_STABLE: &[f64] = &[99.0, 101.0, 100.0, 98.0, 102.0, 100.0, 99.0, 101.0];
+    const DATA_SKEWED: &[f64] = &[10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 50.0];
+    const DATA_CONST: &[f64] = &[1.0, 1.0, 1.0, 1.0];
+
+    #[test]
+    fn test_noise_too_small() {
+        assert_eq!(MetricsNoise::new(&[]), None);
+        assert_eq!(MetricsNoise::new(&[1.0]), None);
+    }
+
+    #[test]
+    fn test_noise_const() {
+        let noise = MetricsNoise::new(DATA_CONST).unwrap();
+        assert_eq!(noise.mean, 1.0);
+        assert_eq!(noise.std_dev, None);
+        assert_eq!(noise.coefficient_of_variation(), None);
+
+        let calibration = ThresholdCalibration::new(&[noise], 0.01).unwrap();
+        assert_eq!(calibration.test, ModelTest::Percentage);
+        assert_eq!(calibration.boundary, Boundary::ZERO);
+        assert_eq!(calibration.detectable_change(&noise), Some(0.0));
+    }
+
+    #[test]
+    fn test_calibration_t_test() {
+        let noise = MetricsNoise::new(DATA_STABLE).unwrap();
+        let calibration = ThresholdCalibration::new(&[noise], 0.01).unwrap();
+        assert_eq!(calibration.test, ModelTest::TTest);
+        assert_eq!(calibration.boundary, Boundary::NINETY_NINE);
+        assert!(u32::from(calibration.min_sample_size) > u32::from(SampleSize::MIN));
+        assert_eq!(calibration.max_sample_size, SampleSize::SIXTY_FOUR);
+        let detectable_change = calibration.detectable_change(&noise).unwrap();
+        assert!(detectable_change > 0.0 && detectable_change < 0.1);
+    }
+
+    #[test]
+    fn test_calibration_multiple_benchmarks() {
+        let noise = MetricsNoise::new(DATA_STABLE).unwrap();
+        let single = ThresholdCalibration::new(&[noise], 0.01).unwrap();
+        let multiple = ThresholdCalibration::new(&[noise; 10], 0.01).unwrap();
+        assert!(multiple.benchmark_false_positive_rate < single.benchmark_false_positive_rate);
+        assert!(f64::from(multiple.boundary) > f64::from(single.boundary));
+        assert!(multiple.critical_value > single.critical_value);
+    }
+
+    #[test]
+    fn test_calibration_skewed() {
+        let noise = MetricsNoise::new(DATA_SKEWED).unwrap();
+        let calibration = ThresholdCalibration::new(&[noise], 0.01).unwrap();
+        assert_eq!(calibration.test, ModelTest::LogNormal);
+
+        let negative = DATA_SKEWED
+            .iter()
+            .map(|value| value - 20.0)
+            .collect::<Vec<_>>();
+        let noise = MetricsNoise::new(&negative).unwrap();
+        let calibration = ThresholdCalibration::new(&[noise], 0.01).unwrap();
+        assert_eq!(calibration.test, ModelTest::Iqr);
+        assert!(f64::from(calibration.boundary) > 1.0);
+    }
+
+    #[test]
+    fn test_calibration_invalid_false_positive_rate() {
+        let noise = MetricsNoise::new(DATA_STABLE).unwrap();
+        assert_eq!(ThresholdCalibration::new(&[noise], 0.0), None);
+        assert_eq!(ThresholdCalibration::new(&[noise], 0.5), None);
+        assert_eq!(ThresholdCalibration::new(&[], 0.01), None);
+    }
+}
@@ -0,0 +1,143 @@
+/// A metric or a daily aggregate of metrics.
+/// The value, lower value, and upper value are the means of the aggregated metrics,
+/// so that they keep the same meaning as for a single metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricsAggregate {
+    pub value: f64,
+    pub lower_value: Option<f64>,
+    pub upper_value: Option<f64>,
+    pub min_value: f64,
+    pub max_value: f64,
+    pub sample_count: i32,
+}
+
+impl MetricsAggregate {
+    pub fn metric(value: f64, lower_value: Option<f64>, upper_value: Option<f64>) -> Self {
+        Self {
+            value,
+            lower_value,
+            upper_value,
+            min_value: value,
+            max_value: value,
+            sample_count: 1,
+        }
+    }
+
+    /// Combine metrics and aggregates into a single aggregate,
+    /// weighting each one by its sample count.
+    /// The lower and upper values are only kept if every one has them.
+    pub fn combine(aggregates: &[Self]) -> Option<Self> {
+        let sample_count = aggregates.iter().try_fold(0i32, |total, aggregate| {
+            total.checked_add(aggregate.sample_count.max(1))
+        })?;
+        if sample_count == 0 {
+            return None;
+        }
+        let weighted_mean = |value: fn(&Self) -> Option<f64>| {
+            aggregates
+                .iter()
+                .map(|aggregate| {
+                    value(aggregate).map(|value| value * f64::from(aggregate.sample_count.max(1)))
+                })
+                .sum::<Option<f64>>()
+                .map(|total| total / f64::from(sample_count))
+        };
+        Some(Self {
+            value: weighted_mean(|aggregate| Some(aggregate.value))?,
+            lower_value: weighted_mean(|aggregate| aggregate.lower_value),
+            upper_value: weighted_mean(|aggregate| aggregate.upper_value),
+            min_value: aggregates
+                .iter()
+                .map(|aggregate| aggregate.min_value)
+                .fold(f64::INFINITY, f64::min),
+            max_value: aggregates
+                .iter()
+                .map(|aggregate| aggregate.max_value)
+                .fold(f64::NEG_INFINITY, f64::max),
+            sample_count,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, clippy::unwrap_used)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::MetricsAggregate;
+
+    #[test]
+    fn test_combine_empty() {
+        assert_eq!(MetricsAggregate::combine(&[]), None);
+    }
+
+    #[test]
+    fn test_combine_metrics() {
+        let aggregate = MetricsAggregate::combine(&[
+            MetricsAggregate::metric(1.0, Some(0.5), Some(1.5)),
+            MetricsAggregate::metric(3.0, Some(2.5), Some(3.5)),
+        ]);
+        assert_eq!(
+            aggregate,
+            Some(MetricsAggregate {
+                value: 2.0,
+                lower_value: Some(1.5),
+                upper_value: Some(2.5),
+                min_value: 1.0,
+                max_value: 3.0,
+                sample_count: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_combine_aggregate() {
+        let aggregate = MetricsAggregate {
+            value: 2.0,
+            lower_value: Some(1.0),
+            upper_value: Some(3.0),
+            min_value: 0.0,
+            max_value: 4.0,
+            sample_count: 3,
+        };
+        let combined = MetricsAggregate::combine(&[
+            aggregate,
+            MetricsAggregate::metric(6.0, Some(5.0), Some(7.0)),
+        ]);
+        assert_eq!(
+            combined,
+            Some(MetricsAggregate {
+                value: 3.0,
+                lower_value: Some(2.0),
+                upper_value: Some(4.0),
+                min_value: 0.0,
+                max_value: 6.0,
+                sample_count: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_combine_missing_bounds() {
+        let aggregate = MetricsAggregate::combine(&[
+            MetricsAggregate::metric(1.0, Some(0.5), None),
+            MetricsAggregate::metric(3.0, None, None),
+        ])
+        .unwrap();
+        assert_eq!(aggregate.value, 2.0);
+        assert_eq!(aggregate.lower_value, None);
+        assert_eq!(aggregate.upper_value, None);
+    }
+
+    #[test]
+    fn test_combine_overflow() {
+        let aggregate = MetricsAggregate {
+            sample_count: i32::MAX,
+            ..MetricsAggregate::metric(1.0, None, None)
+        };
+        assert_eq!(
+            MetricsAggregate::combine(&[aggregate, MetricsAggregate::metric(1.0, None, None)]),
+            None
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use bencher_json::Decay;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ewma {
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl Ewma {
+    pub fn new(data: &[f64], decay: Decay) -> Option<Self> {
+        // Do not calculate the exponentially weighted variance if there are less than 2 data points
+        if data.len() < 2 {
+            return None;
+        }
+        let decay = f64::from(decay);
+
+        // The historical data is ordered from newest to oldest,
+        // so start with the oldest data point and work forward in time.
+        // https://fanf2.user.srcf.net/hermes/doc/antiforgery/stats.pdf
+        let mut data = data.iter().rev();
+        let mut mean = *data.next()?;
+        let mut variance = 0.0;
+        for &value in data {
+            let diff = value - mean;
+            let increment = decay * diff;
+            mean += increment;
+            variance = (1.0 - decay) * (variance + diff * increment);
+        }
+
+        let std_dev = variance.sqrt();
+        // If the variance is zero then the standard deviation is not going to work with `statrs`
+        (mean.is_finite() && std_dev.is_finite() && std_dev > 0.0).then_some(Self { mean, std_dev })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, clippy::unreadable_literal, clippy::unwrap_used)]
+mod test {
+    use bencher_json::Decay;
+    use pretty_assertions::assert_eq;
+
+    use super::Ewma;
+
+    const DATA_ZERO: &[f64] = &[];
+    const DATA_ONE: &[f64] = &[1.0];
+    const DATA_TWO: &[f64] = &[1.0, 2.0];
+    const DATA_THREE: &[f64] = &[1.0, 2.0, 3.0];
+    const DATA_FIVE: &[f64] = &[1.0, 2.0, 3.0, 4.0, 5.0];
+    const DATA_FIVE_DESC: &[f64] = &[5.0, 4.0, 3.0, 2.0, 1.0];
+    const DATA_FIVE_CONST: &[f64] = &[1.0, 1.0, 1.0, 1.0, 1.0];
+
+    #[test]
+    fn test_ewma_zero() {
+        let ewma = Ewma::new(DATA_ZERO, Decay::FIFTY);
+        assert_eq!(ewma, None);
+    }
+
+    #[test]
+    fn test_ewma_one() {
+        let ewma = Ewma::new(DATA_ONE, Decay::FIFTY);
+        assert_eq!(ewma, None);
+    }
+
+    #[test]
+    fn test_ewma_two() {
+        let ewma = Ewma::new(DATA_TWO, Decay::FIFTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 1.5,
+                std_dev: 0.5
+            }
+        );
+
+        let ewma = Ewma::new(DATA_TWO, Decay::TWENTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 1.8,
+                std_dev: 0.4
+            }
+        );
+    }
+
+    #[test]
+    fn test_ewma_three() {
+        let ewma = Ewma::new(DATA_THREE, Decay::FIFTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 1.75,
+                std_dev: 0.82915619758885
+            }
+        );
+
+        let ewma = Ewma::new(DATA_THREE, Decay::TWENTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 2.44,
+                std_dev: 0.8039900496896712
+            }
+        );
+    }
+
+    #[test]
+    fn test_ewma_five() {
+        // The most recent data point is first, so it is weighted the most heavily.
+        let ewma = Ewma::new(DATA_FIVE, Decay::FIFTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 1.9375,
+                std_dev: 1.197327753791751
+            }
+        );
+
+        let ewma = Ewma::new(DATA_FIVE, Decay::TWENTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 3.3615999999999997,
+                std_dev: 1.6031361264721096
+            }
+        );
+    }
+
+    #[test]
+    fn test_ewma_five_desc() {
+        let ewma = Ewma::new(DATA_FIVE_DESC, Decay::FIFTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 4.0625,
+                std_dev: 1.197327753791751
+            }
+        );
+
+        let ewma = Ewma::new(DATA_FIVE_DESC, Decay::TWENTY).unwrap();
+        assert_eq!(
+            ewma,
+            Ewma {
+                mean: 2.6384,
+                std_dev: 1.6031361264721098
+            }
+        );
+    }
+
+    #[test]
+    fn test_ewma_five_const() {
+        let ewma = Ewma::new(DATA_FIVE_CONST, Decay::FIFTY);
+        assert_eq!(ewma, None);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{mean::Mean, MetricsData};
+
+/// Each side of a level shift must have at least this many data points
+const MIN_SEGMENT_SIZE: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricsFlakiness {
+    pub coefficient_of_variation: f64,
+}
+
+impl MetricsFlakiness {
+    /// The data must be in chronological order, either newest or oldest first.
+    pub fn new(metrics_data: &MetricsData, min_sample_size: usize) -> Option<Self> {
+        let data = &metrics_data.data;
+        // Do not check for flakiness if there are not enough data points
+        if data.len() < min_sample_size {
+            return None;
+        }
+        let mean = Mean::new(data)?;
+        // A mean of zero would result in an infinite coefficient of variation
+        if mean.mean == 0.0 {
+            return None;
+        }
+        let std_dev = level_shift_std_deviation(data)?;
+        let coefficient_of_variation = std_dev / mean.mean.abs();
+        coefficient_of_variation.is_finite().then_some(Self {
+            coefficient_of_variation,
+        })
+    }
+
+    pub fn is_flaky(self, max_coefficient_of_variation: f64) -> bool {
+        self.coefficient_of_variation > max_coefficient_of_variation
+    }
+}
+
+/// The standard deviation of the data around the means on either side of its most likely level shift.
+/// A level shift, such as a real regression or improvement, is a change in the mean and not noise.
+/// The most likely level shift is the split that leaves the least variance within the two segments.
+/// If no split leaves less variance than the data as a whole, then the data is treated as a single segment.
+fn level_shift_std_deviation(data: &[f64]) -> Option<f64> {
+    // Prefix sums of the values and their squares,
+    // so the variance of any segment can be found in constant time
+    let mut sums = Vec::with_capacity(data.len() + 1);
+    let mut squares = Vec::with_capacity(data.len() + 1);
+    sums.push(0.0);
+    squares.push(0.0);
+    for value in data {
+        sums.push(sums.last().copied().unwrap_or_default() + value);
+        squares.push(squares.last().copied().unwrap_or_default() + value.powi(2));
+    }
+    #[allow(clippy::cast_precision_loss, clippy::indexing_slicing)]
+    let sum_of_squares = |start: usize, end: usize| {
+        let sum = sums[end] - sums[start];
+        let square = squares[end] - squares[start];
+        (square - sum.powi(2) / (end - start) as f64).max(0.0)
+    };
+
+    let len = data.len();
+    let unsplit = sum_of_squares(0, len);
+    let split = (MIN_SEGMENT_SIZE..=len.saturating_sub(MIN_SEGMENT_SIZE))
+        .map(|split| sum_of_squares(0, split) + sum_of_squares(split, len))
+        .min_by(f64::total_cmp)
+        .unwrap_or(unsplit);
+    #[allow(clippy::cast_precision_loss)]
+    let variance = unsplit.min(split) / len as f64;
+    let std_dev = variance.sqrt();
+    (std_dev.is_finite() && std_dev > 0.0).then_some(std_dev)
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, clippy::unwrap_used)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::MetricsData;
+
+    use super::MetricsFlakiness;
+
+    fn metrics_data(data: &[f64]) -> MetricsData {
+        MetricsData {
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_flakiness_min_sample_size() {
+        let data = metrics_data(&[1.0, 2.0, 3.0]);
+        assert_eq!(MetricsFlakiness::new(&data, 4), None);
+        assert!(MetricsFlakiness::new(&data, 3).is_some());
+    }
+
+    #[test]
+    fn test_flakiness_zero_mean() {
+        let data = metrics_data(&[-1.0, 1.0, -1.0, 1.0]);
+        assert_eq!(MetricsFlakiness::new(&data, 2), None);
+    }
+
+    #[test]
+    fn test_flakiness_constant() {
+        let data = metrics_data(&[1.0, 1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(MetricsFlakiness::new(&data, 2), None);
+    }
+
+    #[test]
+    fn test_flakiness_stable() {
+        let data = metrics_data(&[99.0, 101.0, 99.0, 101.0]);
+        let flakiness = MetricsFlakiness::new(&data, 2).unwrap();
+        assert_eq!(flakiness.coefficient_of_variation, 0.01);
+        assert!(!flakiness.is_flaky(0.1));
+    }
+
+    #[test]
+    fn test_flakiness_noisy() {
+        let data = metrics_data(&[50.0, 150.0, 50.0, 150.0]);
+        let flakiness = MetricsFlakiness::new(&data, 2).unwrap();
+        assert_eq!(flakiness.coefficient_of_variation, 0.5);
+        assert!(flakiness.is_flaky(0.1));
+    }
+
+    #[test]
+    fn test_flakiness_level_shift() {
+        // A single step regression is not noise
+        let data = metrics_data(&[100.0, 100.0, 100.0, 100.0, 200.0, 200.0, 200.0, 200.0]);
+        assert_eq!(MetricsFlakiness::new(&data, 2), None);
+
+        let data = metrics_data(&[
+            99.0, 101.0, 99.0, 101.0, 99.0, 199.0, 201.0, 199.0, 201.0, 199.0,
+        ]);
+        let flakiness = MetricsFlakiness::new(&data, 2).unwrap();
+        assert!(flakiness.coefficient_of_variation < 0.01);
+        assert!(!flakiness.is_flaky(0.25));
+    }
+
+    #[test]
+    fn test_flakiness_noisy_level_shift() {
+        // Noise on either side of a level shift is still noise
+        let data = metrics_data(&[
+            50.0, 150.0, 50.0, 150.0, 50.0, 150.0, 250.0, 350.0, 250.0, 350.0, 250.0, 350.0,
+        ]);
+        let flakiness = MetricsFlakiness::new(&data, 2).unwrap();
+        assert!(flakiness.is_flaky(0.2));
+    }
+
+    #[test]
+    fn test_flakiness_negative() {
+        let data = metrics_data(&[-50.0, -150.0, -50.0, -150.0]);
+        let flakiness = MetricsFlakiness::new(&data, 2).unwrap();
+        assert_eq!(flakiness.coefficient_of_variation, 0.5);
+    }
+}
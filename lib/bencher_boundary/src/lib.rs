@@ -1,12 +1,19 @@
 pub mod boundary;
+pub mod calibration;
+pub mod downsample;
 mod error;
+mod ewma;
+pub mod flaky;
 pub mod limits;
 mod ln;
 mod mean;
 mod quartiles;
 
 pub use boundary::MetricsBoundary;
+pub use calibration::{MetricsNoise, ThresholdCalibration};
+pub use downsample::MetricsAggregate;
 pub use error::BoundaryError;
+pub use flaky::MetricsFlakiness;
 
 #[derive(Debug, Clone)]
 pub struct MetricsData {
@@ -2,7 +2,7 @@ use bencher_json::{Boundary, IqrBoundary, PercentageBoundary};
 
 use crate::quartiles::Quartiles;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MetricsLimit {
     pub value: f64,
 }
@@ -214,6 +214,25 @@ impl MetricsLimits {
             (None, None) => None,
         }
     }
+
+    // The exceedance is how far the datum is from the baseline,
+    // as a multiple of how far the boundary limit is from the baseline.
+    // A datum right at the boundary limit has an exceedance of `1.0`.
+    // Without a baseline, as is the case for static limits, zero is used as the baseline.
+    pub fn exceedance(&self, datum: f64, boundary_limit: BoundaryLimit) -> Option<f64> {
+        let limit = match boundary_limit {
+            BoundaryLimit::Lower => self.lower.as_ref(),
+            BoundaryLimit::Upper => self.upper.as_ref(),
+        }?
+        .value;
+        let baseline = self.baseline.unwrap_or_default();
+        let limit_distance = (limit - baseline).abs();
+        if limit_distance == 0.0 || !limit_distance.is_finite() {
+            return None;
+        }
+        let exceedance = 1.0 + (datum - limit).abs() / limit_distance;
+        exceedance.is_finite().then_some(exceedance)
+    }
 }
 
 #[cfg(test)]
@@ -1353,4 +1372,44 @@ mod test {
         let side = limits.outlier(150.0);
         assert_eq!(side, Some(BoundaryLimit::Upper));
     }
+
+    #[test]
+    fn test_limits_exceedance() {
+        let log = bootstrap_logger();
+        let limits = MetricsLimits::new_iqr(
+            &log,
+            QUARTILES,
+            None,
+            Some(*IQR_MULTIPLIER),
+            Some(*IQR_MULTIPLIER),
+        );
+        assert_eq!(
+            limits.lower,
+            Some(MetricsLimit {
+                value: IQR_NEGATIVE_LIMIT
+            })
+        );
+        assert_eq!(
+            limits.upper,
+            Some(MetricsLimit {
+                value: IQR_POSITIVE_LIMIT
+            })
+        );
+
+        let exceedance = limits.exceedance(IQR_POSITIVE_LIMIT, BoundaryLimit::Upper);
+        assert_eq!(exceedance, Some(1.0));
+
+        let exceedance = limits.exceedance(8.0, BoundaryLimit::Upper);
+        assert_eq!(exceedance, Some(2.0));
+
+        let exceedance = limits.exceedance(-7.0, BoundaryLimit::Lower);
+        assert_eq!(exceedance, Some(3.0));
+
+        let limits = MetricsLimits::new_static(None, Some(*STATIC_LIMIT));
+        let exceedance = limits.exceedance(STATIC_POSITIVE_OUTLIER, BoundaryLimit::Upper);
+        assert_eq!(exceedance, Some(2.0));
+
+        let exceedance = limits.exceedance(STATIC_POSITIVE_OUTLIER, BoundaryLimit::Lower);
+        assert_eq!(exceedance, None);
+    }
 }
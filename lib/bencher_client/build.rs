@@ -11,7 +11,13 @@ fn main() {
     let spec = serde_json::from_reader(file).unwrap();
     let mut generator = progenitor::Generator::new(
         progenitor::GenerationSettings::default()
-            .with_interface(progenitor::InterfaceStyle::Builder),
+            .with_interface(progenitor::InterfaceStyle::Builder)
+            // Used to record the time to first byte for each request
+            // and to revalidate any cached responses
+            .with_inner_type(quote::quote!(crate::hooks::RequestHooks))
+            .with_pre_hook(quote::quote!(crate::hooks::pre_hook))
+            .with_pre_hook_async(quote::quote!(crate::hooks::pre_hook_async))
+            .with_post_hook(quote::quote!(crate::hooks::post_hook)),
     );
 
     let tokens = generator.generate_tokens(&spec).unwrap();
@@ -0,0 +1,168 @@
+use std::{
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+    process,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bencher_json::Jwt;
+use reqwest::{
+    header::{HeaderValue, ETAG, IF_NONE_MATCH},
+    Method,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An on-disk cache of `GET` responses from the API server.
+///
+/// Each cached response is revalidated with its entity tag (`ETag`),
+/// so a response body is only downloaded again if it has changed.
+/// The cache does nothing unless it is enabled.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseCache(Option<PathBuf>);
+
+/// The cache state for a single call to the API server.
+/// It is passed to the generated client so the request hooks can revalidate the cached response.
+#[derive(Debug, Clone, Default)]
+pub struct CachedRequest(Option<Arc<Mutex<CacheState>>>);
+
+#[derive(Debug)]
+struct CacheState {
+    dir: PathBuf,
+    token: Option<Jwt>,
+    path: Option<PathBuf>,
+    cached: Option<CacheEntry>,
+    etag: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CacheEntry {
+    etag: String,
+    body: serde_json::Value,
+}
+
+impl ResponseCache {
+    /// Create a cache that stores responses in the given directory
+    pub fn new(dir: PathBuf) -> Self {
+        Self(Some(dir))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn request(&self, token: Option<&Jwt>) -> CachedRequest {
+        CachedRequest(self.0.as_ref().map(|dir| {
+            Arc::new(Mutex::new(CacheState {
+                dir: dir.clone(),
+                token: token.cloned(),
+                path: None,
+                cached: None,
+                etag: None,
+            }))
+        }))
+    }
+}
+
+impl CachedRequest {
+    fn with_state<F, T>(&self, f: F) -> Option<T>
+    where
+        F: FnOnce(&mut CacheState) -> T,
+    {
+        self.0
+            .as_ref()
+            .map(|state| f(&mut state.lock().unwrap_or_else(PoisonError::into_inner)))
+    }
+
+    /// Look up the cached response for a `GET` request
+    /// and ask the API server to only send the response body if it has changed
+    pub(crate) fn revalidate(&self, request: &mut reqwest::Request) {
+        self.with_state(|state| {
+            state.path = None;
+            state.cached = None;
+            state.etag = None;
+            if request.method() != Method::GET {
+                return;
+            }
+
+            // The API token is part of the key, as the response depends on who is asking.
+            // The client version is part of the key, as the response type may change between versions.
+            let mut hasher = DefaultHasher::new();
+            CLIENT_VERSION.hash(&mut hasher);
+            state.token.hash(&mut hasher);
+            request.url().as_str().hash(&mut hasher);
+            let path = state.dir.join(format!("{:016x}.json", hasher.finish()));
+
+            // A missing or corrupt cache entry is just a cache miss
+            let cached = fs::read(&path)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<CacheEntry>(&bytes).ok());
+            if let Some(if_none_match) = cached
+                .as_ref()
+                .and_then(|cached| HeaderValue::from_str(&cached.etag).ok())
+            {
+                request.headers_mut().insert(IF_NONE_MATCH, if_none_match);
+            }
+            state.path = Some(path);
+            state.cached = cached;
+        });
+    }
+
+    /// Record the entity tag of a successful response
+    pub(crate) fn record(&self, result: &reqwest::Result<reqwest::Response>) {
+        self.with_state(|state| {
+            state.etag = result
+                .as_ref()
+                .ok()
+                .filter(|response| response.status().is_success())
+                .and_then(|response| response.headers().get(ETAG))
+                .and_then(|etag| etag.to_str().ok())
+                .map(ToOwned::to_owned);
+        });
+    }
+
+    /// Store a successful response, if it has an entity tag.
+    /// The cache is best effort, so any errors are ignored.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn store<Json>(&self, json: &Json)
+    where
+        Json: Serialize,
+    {
+        self.with_state(|state| {
+            let (Some(path), Some(etag)) = (state.path.as_ref(), state.etag.take()) else {
+                return;
+            };
+            let Ok(body) = serde_json::to_value(json) else {
+                return;
+            };
+            let Ok(bytes) = serde_json::to_vec(&CacheEntry { etag, body }) else {
+                return;
+            };
+            // Write to a temporary file and then rename it,
+            // so concurrent invocations never read a partially written entry
+            let tmp_path = path.with_extension(format!("{}.tmp", process::id()));
+            let stored = fs::create_dir_all(&state.dir)
+                .and_then(|()| fs::write(&tmp_path, bytes))
+                .and_then(|()| fs::rename(&tmp_path, path));
+            if stored.is_err() {
+                drop(fs::remove_file(&tmp_path));
+            }
+        });
+    }
+
+    /// Load the cached response, after the API server responds that it has not been modified
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn load<Json>(&self) -> Option<Json>
+    where
+        Json: DeserializeOwned,
+    {
+        self.with_state(|state| {
+            let cached = state.cached.take()?;
+            serde_json::from_value(cached.body).ok()
+        })
+        .flatten()
+    }
+}
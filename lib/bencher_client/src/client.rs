@@ -1,21 +1,34 @@
 #![allow(clippy::absolute_paths)]
 
-use bencher_json::{Jwt, BENCHER_API_URL};
+use bencher_json::{
+    system::version::{BENCHER_MEDIA_TYPE_PREFIX, BENCHER_MEDIA_TYPE_SUFFIX},
+    ApiErrorCode, Jwt, BENCHER_API_URL,
+};
 use serde::{de::DeserializeOwned, Serialize};
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
+
+use crate::{cache::ResponseCache, failover::HostFailover, hooks::RequestHooks, timing::ApiTimer};
 
 const DEFAULT_ATTEMPTS: usize = 10;
 const DEFAULT_RETRY_AFTER: u64 = 1;
+const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// A client for the Bencher API
 #[derive(Debug, Clone)]
 pub struct BencherClient {
     pub host: url::Url,
+    /// Other API hosts to fail over to if the selected host can not be reached,
+    /// such as a mirrored read replica or a host that is being migrated to
+    pub fallback_hosts: Vec<url::Url>,
     pub token: Option<Jwt>,
     pub attempts: usize,
     pub retry_after: u64,
     pub strict: bool,
     pub log: bool,
+    pub timer: ApiTimer,
+    pub failover: HostFailover,
+    /// An on-disk cache of `GET` responses that are revalidated with their entity tag (`ETag`)
+    pub cache: ResponseCache,
 }
 
 #[allow(clippy::absolute_paths)]
@@ -24,7 +37,7 @@ pub enum ClientError {
     #[error("Failed to build. Missing `host` field.")]
     NoHost,
 
-    #[error("Failed to parse Authorization header: {0}")]
+    #[error("Failed to parse request header: {0}")]
     HeaderValue(reqwest::header::InvalidHeaderValue),
     #[error("Failed to build API client: {0}")]
     BuildClient(reqwest::Error),
@@ -79,11 +92,15 @@ impl BencherClient {
     ) -> Self {
         BencherClientBuilder {
             host,
+            fallback_hosts: Vec::new(),
             token,
             attempts,
             retry_after,
             strict,
             log,
+            timer: None,
+            failover: None,
+            cache: None,
         }
         .build()
     }
@@ -97,14 +114,25 @@ impl BencherClient {
     pub fn into_builder(self) -> BencherClientBuilder {
         BencherClientBuilder {
             host: Some(self.host),
+            fallback_hosts: self.fallback_hosts,
             token: self.token,
             attempts: Some(self.attempts),
             retry_after: Some(self.retry_after),
             strict: Some(self.strict),
             log: Some(self.log),
+            timer: Some(self.timer),
+            failover: Some(self.failover),
+            cache: Some(self.cache),
         }
     }
 
+    /// All of the API hosts, starting with the primary host
+    pub fn hosts(&self) -> Vec<&url::Url> {
+        std::iter::once(&self.host)
+            .chain(&self.fallback_hosts)
+            .collect()
+    }
+
     /// Send a request to the Bencher API
     ///
     /// Returns a generic JSON value as the response.
@@ -146,6 +174,7 @@ impl BencherClient {
     /// # Returns
     ///
     /// A `Result` containing the response JSON or an `Error`
+    #[allow(clippy::too_many_lines)]
     pub async fn send_with<F, R, T, Json, E>(&self, sender: F) -> Result<Json, ClientError>
     where
         F: Fn(crate::codegen::Client) -> R,
@@ -162,28 +191,54 @@ impl BencherClient {
         let timeout = Duration::from_secs(15);
         let mut client_builder = reqwest::ClientBuilder::new().connect_timeout(timeout);
 
+        let mut headers = reqwest::header::HeaderMap::new();
+        // Request the API version that matches this client,
+        // so the server can serialize responses that this client understands
+        let accept = reqwest::header::HeaderValue::from_str(&format!(
+            "{BENCHER_MEDIA_TYPE_PREFIX}{CLIENT_VERSION}{BENCHER_MEDIA_TYPE_SUFFIX}, application/json"
+        ))
+        .map_err(ClientError::HeaderValue)?;
+        headers.insert(reqwest::header::ACCEPT, accept);
         if let Some(token) = &self.token {
-            let mut headers = reqwest::header::HeaderMap::new();
             let bearer_token = reqwest::header::HeaderValue::from_str(&format!("Bearer {token}"))
                 .map_err(ClientError::HeaderValue)?;
             headers.insert("Authorization", bearer_token);
-            client_builder = client_builder.default_headers(headers);
         }
+        client_builder = client_builder.default_headers(headers);
 
         let reqwest_client = client_builder.build().map_err(ClientError::BuildClient)?;
-        let client = crate::codegen::Client::new_with_client(self.host.as_ref(), reqwest_client);
+        let hosts = self.hosts();
+        let hooks = RequestHooks {
+            timer: self.timer.request(),
+            cache: self.cache.request(self.token.as_ref()),
+        };
+        let new_client = |index: usize| {
+            let host = hosts.get(index).copied().unwrap_or(&self.host);
+            let client = crate::codegen::Client::new_with_client(
+                host.as_ref(),
+                reqwest_client.clone(),
+                hooks.clone(),
+            );
+            (host, client)
+        };
+        let mut host_index = self.failover.current(&hosts).await;
+        let (mut host, mut client) = new_client(host_index);
 
         let attempts = self.attempts;
         let max_attempts = attempts.checked_sub(1).unwrap_or_default();
         let mut retry_after = self.retry_after;
 
         for attempt in 0..attempts {
-            match sender(client.clone()).await {
+            let start = Instant::now();
+            let result = sender(client.clone()).await;
+            hooks.timer.record(attempt + 1, start.elapsed());
+            match result {
                 Ok(response_value) => {
                     let response = response_value.into_inner();
                     let json_response = Json::try_from(response)
                         .map_err(Into::into)
                         .map_err(ClientError::DeserializeResponse)?;
+                    hooks.cache.store(&json_response);
                     self.log(&json_response)?;
                     return Ok(json_response);
                 },
@@ -193,6 +248,17 @@ impl BencherClient {
                         eprintln!("\nSend attempt #{}/{attempts}: {e}", attempt + 1);
                     }
                     if attempt != max_attempts {
+                        // Fail over to another healthy host right away, instead of waiting on the failed host
+                        if let Some(index) = self.failover.fail_over(&hosts, host_index).await {
+                            let (next_host, next_client) = new_client(index);
+                            if self.log {
+                                eprintln!("Failing over from {host} to {next_host}.");
+                            }
+                            host_index = index;
+                            host = next_host;
+                            client = next_client;
+                            continue;
+                        }
                         if self.log {
                             eprintln!("Will retry after {retry_after} second(s).");
                         }
@@ -238,6 +304,13 @@ impl BencherClient {
                     }
                 },
                 Err(crate::codegen::Error::UnexpectedResponse(response)) => {
+                    // The cached response has not been modified
+                    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                        if let Some(json_response) = hooks.cache.load() {
+                            self.log(&json_response)?;
+                            return Ok(json_response);
+                        }
+                    }
                     return if response.status().is_success() {
                         if self.strict {
                             Err(ClientError::UnexpectedResponseOkStrict(response))
@@ -252,7 +325,7 @@ impl BencherClient {
                         }
                     } else {
                         Err(ClientError::UnexpectedResponseErr(response))
-                    }
+                    };
                 },
             }
         }
@@ -284,6 +357,20 @@ pub struct ErrorResponse {
     pub message: String,
 }
 
+impl ErrorResponse {
+    /// The kind of error returned by the Bencher API
+    pub fn kind(&self) -> ErrorKind {
+        self.status.into()
+    }
+
+    /// The machine-readable error code returned by the Bencher API, if any.
+    /// This allows for branching on the specific error and resource,
+    /// ie `not_found.benchmark`, instead of matching on the error message.
+    pub fn code(&self) -> Option<ApiErrorCode> {
+        self.error_code.as_deref()?.parse().ok()
+    }
+}
+
 impl std::fmt::Display for ErrorResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Status: {}", self.status)?;
@@ -298,15 +385,91 @@ impl std::fmt::Display for ErrorResponse {
     }
 }
 
+impl ClientError {
+    /// The kind of error returned by the Bencher API, if the API returned an error response
+    pub fn kind(&self) -> Option<ErrorKind> {
+        match self {
+            Self::ErrorResponse(e) => Some(e.kind()),
+            Self::UnexpectedResponseErr(response) => Some(response.status().into()),
+            Self::NoHost
+            | Self::HeaderValue(_)
+            | Self::BuildClient(_)
+            | Self::DeserializeResponse(_)
+            | Self::SerializeResponse(_)
+            | Self::InvalidRequest(_)
+            | Self::PreHookError(_)
+            | Self::InvalidUpgrade(_)
+            | Self::ResponseBodyError(_)
+            | Self::InvalidResponsePayloadStrict(_, _)
+            | Self::InvalidResponsePayload(_)
+            | Self::UnexpectedResponseOkStrict(_)
+            | Self::UnexpectedResponseOk(_)
+            | Self::SendTimeout(_) => None,
+        }
+    }
+
+    /// The machine-readable error code returned by the Bencher API, if the API returned an error response with one
+    pub fn code(&self) -> Option<ApiErrorCode> {
+        if let Self::ErrorResponse(e) = self {
+            e.code()
+        } else {
+            None
+        }
+    }
+}
+
+/// The kind of error returned by the Bencher API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The request was malformed or failed validation
+    BadRequest,
+    /// The request requires authentication
+    Unauthorized,
+    /// The request requires a paid plan
+    PaymentRequired,
+    /// The authenticated user does not have permission
+    Forbidden,
+    /// The requested resource was not found
+    NotFound,
+    /// The request conflicts with an existing resource
+    Conflict,
+    /// Too many requests were sent
+    TooManyRequests,
+    /// The server failed to process the request
+    Server,
+    /// Any other error status
+    Other(reqwest::StatusCode),
+}
+
+impl From<reqwest::StatusCode> for ErrorKind {
+    fn from(status: reqwest::StatusCode) -> Self {
+        match status {
+            reqwest::StatusCode::BAD_REQUEST => Self::BadRequest,
+            reqwest::StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            reqwest::StatusCode::PAYMENT_REQUIRED => Self::PaymentRequired,
+            reqwest::StatusCode::FORBIDDEN => Self::Forbidden,
+            reqwest::StatusCode::NOT_FOUND => Self::NotFound,
+            reqwest::StatusCode::CONFLICT => Self::Conflict,
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Self::TooManyRequests,
+            status if status.is_server_error() => Self::Server,
+            status => Self::Other(status),
+        }
+    }
+}
+
 /// A builder for `BencherClient`
 #[derive(Debug, Clone, Default)]
 pub struct BencherClientBuilder {
     host: Option<url::Url>,
+    fallback_hosts: Vec<url::Url>,
     token: Option<Jwt>,
     attempts: Option<usize>,
     retry_after: Option<u64>,
     strict: Option<bool>,
     log: Option<bool>,
+    timer: Option<ApiTimer>,
+    failover: Option<HostFailover>,
+    cache: Option<ResponseCache>,
 }
 
 impl BencherClientBuilder {
@@ -317,6 +480,15 @@ impl BencherClientBuilder {
         self
     }
 
+    #[must_use]
+    /// Add a fallback host URL to fail over to if the selected host can not be reached.
+    /// The healthy host with the lowest latency is selected before the first request,
+    /// and it is used for all requests until it can not be reached.
+    pub fn fallback_host(mut self, host: url::Url) -> Self {
+        self.fallback_hosts.push(host);
+        self
+    }
+
     #[must_use]
     /// Set the JWT token
     pub fn token(mut self, token: Jwt) -> Self {
@@ -352,28 +524,148 @@ impl BencherClientBuilder {
         self
     }
 
+    #[must_use]
+    /// Set the timer used to record the timings for each API call
+    pub fn timer(mut self, timer: ApiTimer) -> Self {
+        self.timer = Some(timer);
+        self
+    }
+
+    #[must_use]
+    /// Set the on-disk cache used to revalidate `GET` responses
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     /// Build the `BencherClient`
     ///
     /// Default values:
     /// - `host`: `https://api.bencher.dev`
+    /// - `fallback_hosts`: none
     /// - `attempts`: `10`
     /// - `retry_after`: `1`
+    /// - `cache`: disabled
     pub fn build(self) -> BencherClient {
         let Self {
             host,
+            fallback_hosts,
             token,
             attempts,
             retry_after,
             strict,
             log,
+            timer,
+            failover,
+            cache,
         } = self;
         BencherClient {
             host: host.unwrap_or_else(|| BENCHER_API_URL.clone()),
+            fallback_hosts,
             token,
             attempts: attempts.unwrap_or(DEFAULT_ATTEMPTS),
             retry_after: retry_after.unwrap_or(DEFAULT_RETRY_AFTER),
             strict: strict.unwrap_or_default(),
             log: log.unwrap_or_default(),
+            timer: timer.unwrap_or_default(),
+            failover: failover.unwrap_or_default(),
+            cache: cache.unwrap_or_default(),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{ApiErrorCode, ApiErrorKind, BencherResource};
+    use pretty_assertions::assert_eq;
+    use reqwest::{header::HeaderMap, StatusCode};
+
+    use super::{ClientError, ErrorKind, ErrorResponse};
+
+    fn error_response(status: StatusCode, error_code: Option<&str>) -> ErrorResponse {
+        ErrorResponse {
+            status,
+            headers: HeaderMap::new(),
+            request_id: "request_id".into(),
+            error_code: error_code.map(Into::into),
+            message: "message".into(),
+        }
+    }
+
+    #[test]
+    fn test_error_kind_from_status() {
+        assert_eq!(
+            ErrorKind::from(StatusCode::BAD_REQUEST),
+            ErrorKind::BadRequest
+        );
+        assert_eq!(
+            ErrorKind::from(StatusCode::UNAUTHORIZED),
+            ErrorKind::Unauthorized
+        );
+        assert_eq!(
+            ErrorKind::from(StatusCode::PAYMENT_REQUIRED),
+            ErrorKind::PaymentRequired
+        );
+        assert_eq!(ErrorKind::from(StatusCode::FORBIDDEN), ErrorKind::Forbidden);
+        assert_eq!(ErrorKind::from(StatusCode::NOT_FOUND), ErrorKind::NotFound);
+        assert_eq!(ErrorKind::from(StatusCode::CONFLICT), ErrorKind::Conflict);
+        assert_eq!(
+            ErrorKind::from(StatusCode::TOO_MANY_REQUESTS),
+            ErrorKind::TooManyRequests
+        );
+        assert_eq!(
+            ErrorKind::from(StatusCode::INTERNAL_SERVER_ERROR),
+            ErrorKind::Server
+        );
+        assert_eq!(
+            ErrorKind::from(StatusCode::SERVICE_UNAVAILABLE),
+            ErrorKind::Server
+        );
+        assert_eq!(
+            ErrorKind::from(StatusCode::PAYLOAD_TOO_LARGE),
+            ErrorKind::Other(StatusCode::PAYLOAD_TOO_LARGE)
+        );
+    }
+
+    #[test]
+    fn test_client_error_kind() {
+        let err = ClientError::ErrorResponse(error_response(StatusCode::NOT_FOUND, None));
+        assert_eq!(err.kind(), Some(ErrorKind::NotFound));
+        assert_eq!(err.code(), None);
+
+        assert_eq!(ClientError::NoHost.kind(), None);
+        assert_eq!(ClientError::SendTimeout(3).kind(), None);
+    }
+
+    #[test]
+    fn test_client_error_code() {
+        let err = ClientError::ErrorResponse(error_response(
+            StatusCode::NOT_FOUND,
+            Some("not_found.benchmark"),
+        ));
+        assert_eq!(
+            err.code(),
+            Some(ApiErrorCode::with_resource(
+                ApiErrorKind::NotFound,
+                BencherResource::Benchmark
+            ))
+        );
+
+        let err = ClientError::ErrorResponse(error_response(
+            StatusCode::BAD_REQUEST,
+            Some("bad_request"),
+        ));
+        assert_eq!(
+            err.code(),
+            Some(ApiErrorCode::new(ApiErrorKind::BadRequest))
+        );
+
+        // An unknown error code is ignored instead of failing
+        let err = ClientError::ErrorResponse(error_response(
+            StatusCode::BAD_REQUEST,
+            Some("not_an_error_code"),
+        ));
+        assert_eq!(err.kind(), Some(ErrorKind::BadRequest));
+        assert_eq!(err.code(), None);
+    }
+}
@@ -0,0 +1,79 @@
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Selects which API host to send requests to, with failover between hosts.
+///
+/// The selection is shared by all clones of a `BencherClient`,
+/// so once a healthy host has been selected every request sticks to it
+/// until a request to that host fails to connect.
+/// It does nothing unless the client has fallback hosts.
+#[derive(Debug, Clone, Default)]
+pub struct HostFailover(Arc<Mutex<Option<usize>>>);
+
+impl HostFailover {
+    fn selected(&self) -> Option<usize> {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn select(&self, index: usize) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(index);
+    }
+
+    /// Get the index of the host to use, selecting the healthy host with the lowest latency if none has been selected yet.
+    /// If no host is healthy, then the first host is used.
+    pub(crate) async fn current(&self, hosts: &[&url::Url]) -> usize {
+        if hosts.len() < 2 {
+            return 0;
+        }
+        if let Some(index) = self.selected() {
+            return index;
+        }
+        let index = fastest_healthy(hosts, None).await.unwrap_or_default();
+        self.select(index);
+        index
+    }
+
+    /// Fail over from the host at `failed` to the healthy host with the lowest latency out of all of the other hosts.
+    /// Returns the index of the new host, or `None` if there are no other healthy hosts.
+    /// If there are no other healthy hosts, then the failed host stays selected.
+    pub(crate) async fn fail_over(&self, hosts: &[&url::Url], failed: usize) -> Option<usize> {
+        if hosts.len() < 2 {
+            return None;
+        }
+        let index = fastest_healthy(hosts, Some(failed)).await?;
+        self.select(index);
+        Some(index)
+    }
+}
+
+/// Health check each host and return the index of the healthy host with the lowest latency
+async fn fastest_healthy(hosts: &[&url::Url], skip: Option<usize>) -> Option<usize> {
+    let client = reqwest::ClientBuilder::new()
+        .timeout(HEALTH_CHECK_TIMEOUT)
+        .build()
+        .ok()?;
+    let mut fastest: Option<(usize, Duration)> = None;
+    for (index, host) in hosts.iter().enumerate() {
+        if Some(index) == skip {
+            continue;
+        }
+        let Some(latency) = health_check(&client, host).await else {
+            continue;
+        };
+        if !fastest.is_some_and(|(_, fastest)| fastest <= latency) {
+            fastest = Some((index, latency));
+        }
+    }
+    fastest.map(|(index, _)| index)
+}
+
+/// Ping the root of the API server, returning the round trip time if it is healthy
+async fn health_check(client: &reqwest::Client, host: &url::Url) -> Option<Duration> {
+    let start = Instant::now();
+    let response = client.get(host.clone()).send().await.ok()?;
+    response.status().is_success().then(|| start.elapsed())
+}
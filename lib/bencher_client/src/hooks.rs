@@ -0,0 +1,33 @@
+use std::convert::Infallible;
+
+use crate::{cache::CachedRequest, timing::RequestTimer};
+
+/// The state used by the request hooks for a single call to the API server.
+/// It is passed to the generated client as its inner type.
+#[derive(Debug, Clone, Default)]
+pub struct RequestHooks {
+    pub(crate) timer: RequestTimer,
+    pub(crate) cache: CachedRequest,
+}
+
+/// Called by the generated client right before a request is sent
+pub fn pre_hook(hooks: &RequestHooks, request: &reqwest::Request) {
+    crate::timing::pre_hook(&hooks.timer, request);
+}
+
+/// Called by the generated client right before a request is sent,
+/// with the ability to modify the request
+#[allow(clippy::unused_async)]
+pub async fn pre_hook_async(
+    hooks: &RequestHooks,
+    request: &mut reqwest::Request,
+) -> Result<(), Infallible> {
+    hooks.cache.revalidate(request);
+    Ok(())
+}
+
+/// Called by the generated client as soon as the response headers have been received
+pub fn post_hook(hooks: &RequestHooks, result: &reqwest::Result<reqwest::Response>) {
+    crate::timing::post_hook(&hooks.timer, result);
+    hooks.cache.record(result);
+}
@@ -9,11 +9,44 @@ mod codegen {
     )]
     include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 }
+mod cache;
+// The full client relies on `tokio` networking,
+// so only the minimal `wasm` client is available for `wasm32` targets.
+#[cfg(not(target_arch = "wasm32"))]
 mod client;
+#[cfg(not(target_arch = "wasm32"))]
+mod failover;
+mod hooks;
+#[cfg(not(target_arch = "wasm32"))]
+mod project;
+#[cfg(not(target_arch = "wasm32"))]
+mod query;
+mod timing;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+use bencher_json::project::head::VersionNumber;
 
 pub use bencher_json as json;
-pub use client::{BencherClient, BencherClientBuilder, ClientError, ErrorResponse};
+pub use cache::ResponseCache;
+#[cfg(not(target_arch = "wasm32"))]
+pub use client::{BencherClient, BencherClientBuilder, ClientError, ErrorKind, ErrorResponse};
 pub use codegen::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use failover::HostFailover;
+pub use hooks::RequestHooks;
+#[cfg(not(target_arch = "wasm32"))]
+pub use project::{
+    AlertsClient, MeasuresClient, PerfClient, ProjectClient, ReportsClient, ThresholdsClient,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use query::{
+    AlertsQuery, AlertsQueryBuilder, AlertsSort, Pagination, ProjectsQuery, ProjectsQueryBuilder,
+    ProjectsSort, QueryError, ReportsQuery, ReportsQueryBuilder, ReportsSort,
+};
+pub use timing::{ApiTimer, ApiTiming, ApiTimingSummary};
+#[cfg(feature = "wasm")]
+pub use wasm::{submit_report, WasmClient, WasmClientError};
 
 macro_rules! from_client {
     ($($name:ident),*) => {
@@ -31,6 +64,8 @@ from_client!(
     BenchmarkName,
     Boundary,
     BranchName,
+    Cidr,
+    Decay,
     Email,
     GitHash,
     Index,
@@ -41,6 +76,7 @@ from_client!(
     ResourceName,
     SampleSize,
     Slug,
+    TimeZone,
     Url,
     UserName,
     Window
@@ -51,10 +87,16 @@ from_client!(
     ProjectUuid,
     ReportUuid,
     PlotUuid,
+    SavedQueryUuid,
+    AnnotationUuid,
+    RuleUuid,
+    ExpectationUuid,
     BranchUuid,
+    HeadUuid,
     TestbedUuid,
     BenchmarkUuid,
     MeasureUuid,
+    MeasureAliasUuid,
     MetricUuid,
     ThresholdUuid,
     ModelUuid,
@@ -111,30 +153,54 @@ try_from_client!(
     JsonAllowed,
     JsonProjects,
     JsonProject,
+    JsonProjectMembers,
+    JsonProjectMember,
     JsonReports,
     JsonReport,
+    JsonReportShard,
+    JsonRun,
     JsonPerf,
+    JsonOrganizationPerf,
     JsonPlots,
     JsonPlot,
+    JsonSavedQueries,
+    JsonSavedQuery,
+    JsonAnnotations,
+    JsonAnnotation,
+    JsonRules,
+    JsonRule,
+    JsonExpectations,
+    JsonExpectation,
     JsonBranches,
     JsonBranch,
+    JsonBranchLineage,
+    JsonBranchVersions,
+    JsonBranchTags,
     JsonBenchmarks,
     JsonBenchmark,
     JsonTestbeds,
     JsonTestbed,
     JsonMeasures,
     JsonMeasure,
+    JsonMeasureAliases,
+    JsonMeasureAlias,
     JsonOneMetric,
     JsonThresholds,
     JsonThreshold,
+    JsonRecompute,
     JsonModel,
     JsonAlerts,
     JsonAlert,
+    JsonAlertUpdate,
+    JsonAlertStats,
     JsonUsers,
     JsonUser,
+    JsonUserPrefs,
     JsonPubUser,
     JsonTokens,
     JsonToken,
+    JsonTokenPolicy,
+    JsonRevokedTokens,
     JsonSignup,
     JsonLogin,
     JsonConfirm,
@@ -142,10 +208,13 @@ try_from_client!(
     JsonAuthAck,
     JsonAuthUser,
     JsonBackupCreated,
+    JsonDownsampled,
     JsonConfig,
+    JsonSecretRotated,
     JsonConsole,
     JsonApiVersion,
-    JsonSpec
+    JsonSpec,
+    JsonRbac
 );
 
 #[cfg(feature = "plus")]
@@ -163,6 +232,37 @@ impl From<bencher_json::DateTimeMillis> for types::DateTimeMillis {
     }
 }
 
+impl From<bencher_json::ModelTest> for types::ModelTest {
+    fn from(test: bencher_json::ModelTest) -> Self {
+        match test {
+            bencher_json::ModelTest::Static => Self::Static,
+            bencher_json::ModelTest::Percentage => Self::Percentage,
+            bencher_json::ModelTest::ZScore => Self::ZScore,
+            bencher_json::ModelTest::TTest => Self::TTest,
+            bencher_json::ModelTest::LogNormal => Self::LogNormal,
+            bencher_json::ModelTest::Iqr => Self::Iqr,
+            bencher_json::ModelTest::DeltaIqr => Self::DeltaIqr,
+            bencher_json::ModelTest::Ewma => Self::Ewma,
+        }
+    }
+}
+
+impl From<bencher_json::DateFormat> for types::DateFormat {
+    fn from(date_format: bencher_json::DateFormat) -> Self {
+        match date_format {
+            bencher_json::DateFormat::Rfc3339 => Self::Rfc3339,
+            bencher_json::DateFormat::DateTime => Self::DateTime,
+            bencher_json::DateFormat::Date => Self::Date,
+        }
+    }
+}
+
+impl From<VersionNumber> for types::VersionNumber {
+    fn from(version_number: VersionNumber) -> Self {
+        Self(version_number.0)
+    }
+}
+
 macro_rules! into_uuids {
     ($($list:ident[$name:ident]),*) => {
         $(
@@ -190,6 +290,9 @@ into_uuids!(
     JsonProjects[JsonProject],
     JsonReports[JsonReport],
     JsonPlots[JsonPlot],
+    JsonAnnotations[JsonAnnotation],
+    JsonRules[JsonRule],
+    JsonExpectations[JsonExpectation],
     JsonBranches[JsonBranch],
     JsonTestbeds[JsonTestbed],
     JsonBenchmarks[JsonBenchmark],
@@ -221,6 +324,9 @@ into_uuid!(
     JsonProject,
     JsonReport,
     JsonPlot,
+    JsonAnnotation,
+    JsonRule,
+    JsonExpectation,
     JsonBranch,
     JsonTestbed,
     JsonBenchmark,
@@ -0,0 +1,530 @@
+use bencher_json::{
+    DateTimeMillis, JsonAlerts, JsonMeasure, JsonMeasures, JsonPerf, JsonPerfQuery, JsonProjects,
+    JsonReport, JsonReportShard, JsonReports, JsonRun, JsonThreshold, JsonThresholds, ReportUuid,
+    ResourceId, ThresholdUuid,
+};
+
+use crate::{
+    codegen::types,
+    query::{paginate, AlertsQuery, ProjectsQuery, ReportsQuery},
+    BencherClient, ClientError,
+};
+
+// The typed helpers are kept alongside the typed clients that they return
+#[allow(clippy::multiple_inherent_impl)]
+impl BencherClient {
+    /// Get a typed client for a project
+    ///
+    /// # Parameters
+    ///
+    /// - `project`: The slug or UUID for the project
+    pub fn project<P>(&self, project: P) -> ProjectClient<'_>
+    where
+        P: Into<ResourceId>,
+    {
+        ProjectClient {
+            client: self,
+            project: project.into(),
+        }
+    }
+
+    /// List a page of projects that match a query
+    ///
+    /// # Parameters
+    ///
+    /// - `query`: The validated query, from `ProjectsQuery::builder`
+    pub async fn projects(&self, query: &ProjectsQuery) -> Result<JsonProjects, ClientError> {
+        self.send_with(|client| async move {
+            let mut client = client.projects_get();
+            if let Some(name) = query.name.clone() {
+                client = client.name(name);
+            }
+            if let Some(search) = query.search.clone() {
+                client = client.search(search);
+            }
+            if let Some(visibility) = query.visibility {
+                client = client.visibility(visibility);
+            }
+            paginate!(client, query.pagination);
+            client.send().await
+        })
+        .await
+    }
+
+    /// Create a report for an unclaimed project, without authentication
+    ///
+    /// If the project does not exist, it is created along with a claim token.
+    ///
+    /// # Parameters
+    ///
+    /// - `run`: The project slug and the new report to create
+    pub async fn run(&self, run: types::JsonNewRun) -> Result<JsonRun, ClientError> {
+        self.send_with(|client| {
+            let run = run.clone();
+            async move { client.run_post().body(run).send().await }
+        })
+        .await
+    }
+}
+
+/// A typed client for a single project
+#[derive(Debug, Clone)]
+pub struct ProjectClient<'c> {
+    client: &'c BencherClient,
+    project: ResourceId,
+}
+
+impl<'c> ProjectClient<'c> {
+    /// Get a typed client for the project reports
+    pub fn reports(&self) -> ReportsClient<'c> {
+        ReportsClient {
+            client: self.client,
+            project: self.project.clone(),
+        }
+    }
+
+    /// Get a typed client for the project alerts
+    pub fn alerts(&self) -> AlertsClient<'c> {
+        AlertsClient {
+            client: self.client,
+            project: self.project.clone(),
+        }
+    }
+
+    /// Get a typed client for the project perf query
+    pub fn perf(&self) -> PerfClient<'c> {
+        PerfClient {
+            client: self.client,
+            project: self.project.clone(),
+        }
+    }
+
+    /// Get a typed client for the project thresholds
+    pub fn thresholds(&self) -> ThresholdsClient<'c> {
+        ThresholdsClient {
+            client: self.client,
+            project: self.project.clone(),
+        }
+    }
+
+    /// Get a typed client for the project measures
+    pub fn measures(&self) -> MeasuresClient<'c> {
+        MeasuresClient {
+            client: self.client,
+            project: self.project.clone(),
+        }
+    }
+
+    /// Get a typed client for the project measures
+    #[deprecated(
+        since = "0.4.0",
+        note = "Metric kinds have been renamed to measures. Use `measures` instead."
+    )]
+    pub fn metric_kinds(&self) -> MeasuresClient<'c> {
+        self.measures()
+    }
+}
+
+/// A typed client for the reports of a project
+#[derive(Debug, Clone)]
+pub struct ReportsClient<'c> {
+    client: &'c BencherClient,
+    project: ResourceId,
+}
+
+impl ReportsClient<'_> {
+    /// List the most recent page of reports for the project
+    pub async fn list(&self) -> Result<JsonReports, ClientError> {
+        self.client
+            .send_with(|client| async move {
+                client
+                    .proj_reports_get()
+                    .project(self.project.clone())
+                    .send()
+                    .await
+            })
+            .await
+    }
+
+    /// List a page of reports for the project that match a query
+    ///
+    /// # Parameters
+    ///
+    /// - `query`: The validated query, from `ReportsQuery::builder`
+    pub async fn query(&self, query: &ReportsQuery) -> Result<JsonReports, ClientError> {
+        self.client
+            .send_with(|client| async move {
+                let mut client = client.proj_reports_get().project(self.project.clone());
+                if let Some(branch) = query.branch() {
+                    client = client.branch(branch);
+                }
+                if let Some(testbed) = query.testbed() {
+                    client = client.testbed(testbed);
+                }
+                if let Some(tag) = query.tag() {
+                    client = client.tag(tag);
+                }
+                if let Some(start_time) = query.start_time {
+                    client = client.start_time(DateTimeMillis::from(start_time));
+                }
+                if let Some(end_time) = query.end_time {
+                    client = client.end_time(DateTimeMillis::from(end_time));
+                }
+                if let Some(hash) = query.hash.clone() {
+                    client = client.hash(hash);
+                }
+                if let Some(start_version) = query.start_version {
+                    client = client.start_version(start_version);
+                }
+                if let Some(end_version) = query.end_version {
+                    client = client.end_version(end_version);
+                }
+                if let Some(archived) = query.archived {
+                    client = client.archived(archived);
+                }
+                paginate!(client, query.pagination);
+                client.send().await
+            })
+            .await
+    }
+
+    /// Create a new report for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `report`: The new report to create
+    pub async fn create(&self, report: types::JsonNewReport) -> Result<JsonReport, ClientError> {
+        self.client
+            .send_with(|client| {
+                let report = report.clone();
+                async move {
+                    client
+                        .proj_report_post()
+                        .project(self.project.clone())
+                        .body(report)
+                        .send()
+                        .await
+                }
+            })
+            .await
+    }
+
+    /// Submit a shard of a report for the project
+    ///
+    /// The shards for a run are merged into a single report once all of them have been received.
+    ///
+    /// # Parameters
+    ///
+    /// - `shard`: The new report shard to submit
+    pub async fn shard(
+        &self,
+        shard: types::JsonNewReportShard,
+    ) -> Result<JsonReportShard, ClientError> {
+        self.client
+            .send_with(|client| {
+                let shard = shard.clone();
+                async move {
+                    client
+                        .proj_shard_post()
+                        .project(self.project.clone())
+                        .body(shard)
+                        .send()
+                        .await
+                }
+            })
+            .await
+    }
+
+    /// Import multiple reports for the project at once
+    ///
+    /// # Parameters
+    ///
+    /// - `reports`: The new reports to import
+    pub async fn import(
+        &self,
+        reports: Vec<types::JsonNewReport>,
+    ) -> Result<JsonReports, ClientError> {
+        self.client
+            .send_with(|client| {
+                let reports = types::JsonNewReports(reports.clone());
+                async move {
+                    client
+                        .proj_import_post()
+                        .project(self.project.clone())
+                        .body(reports)
+                        .send()
+                        .await
+                }
+            })
+            .await
+    }
+
+    /// View a report for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `report`: The UUID for the report
+    pub async fn get(&self, report: ReportUuid) -> Result<JsonReport, ClientError> {
+        self.client
+            .send_with(|client| async move {
+                client
+                    .proj_report_get()
+                    .project(self.project.clone())
+                    .report(report)
+                    .send()
+                    .await
+            })
+            .await
+    }
+
+    /// Delete a report for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `report`: The UUID for the report
+    pub async fn delete(&self, report: ReportUuid) -> Result<(), ClientError> {
+        self.client
+            .send(|client| async move {
+                client
+                    .proj_report_delete()
+                    .project(self.project.clone())
+                    .report(report)
+                    .send()
+                    .await
+            })
+            .await
+            .map(|_json| ())
+    }
+}
+
+/// A typed client for the alerts of a project
+#[derive(Debug, Clone)]
+pub struct AlertsClient<'c> {
+    client: &'c BencherClient,
+    project: ResourceId,
+}
+
+impl AlertsClient<'_> {
+    /// List a page of alerts for the project that match a query
+    ///
+    /// # Parameters
+    ///
+    /// - `query`: The validated query, from `AlertsQuery::builder`
+    pub async fn list(&self, query: &AlertsQuery) -> Result<JsonAlerts, ClientError> {
+        self.client
+            .send_with(|client| async move {
+                let mut client = client.proj_alerts_get().project(self.project.clone());
+                if let Some(status) = query.status {
+                    client = client.status(status);
+                }
+                if let Some(severity) = query.severity {
+                    client = client.severity(severity);
+                }
+                if let Some(archived) = query.archived {
+                    client = client.archived(archived);
+                }
+                paginate!(client, query.pagination);
+                client.send().await
+            })
+            .await
+    }
+}
+
+/// A typed client for the perf query of a project
+#[derive(Debug, Clone)]
+pub struct PerfClient<'c> {
+    client: &'c BencherClient,
+    project: ResourceId,
+}
+
+impl PerfClient<'_> {
+    /// Query the performance metrics for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `query`: The perf query
+    pub async fn get(&self, query: &JsonPerfQuery) -> Result<JsonPerf, ClientError> {
+        self.client
+            .send_with(|client| async move {
+                let mut client = client
+                    .proj_perf_get()
+                    .project(self.project.clone())
+                    .branches(query.branches())
+                    .testbeds(query.testbeds())
+                    .benchmarks(query.benchmarks())
+                    .measures(query.measures());
+
+                if let Some(heads) = query.heads() {
+                    client = client.heads(heads);
+                }
+                if let Some(start_time) = query.start_time() {
+                    client = client.start_time(start_time);
+                }
+                if let Some(end_time) = query.end_time() {
+                    client = client.end_time(end_time);
+                }
+                if let Some(tags) = query.tags() {
+                    client = client.tags(tags);
+                }
+                if let Some(unit) = query.unit() {
+                    client = client.unit(unit);
+                }
+
+                client.send().await
+            })
+            .await
+    }
+}
+
+/// A typed client for the thresholds of a project
+#[derive(Debug, Clone)]
+pub struct ThresholdsClient<'c> {
+    client: &'c BencherClient,
+    project: ResourceId,
+}
+
+impl ThresholdsClient<'_> {
+    /// List a page of thresholds for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `per_page`: The number of thresholds per page
+    /// - `page`: The page number, starting at `1`
+    pub async fn list(&self, per_page: u8, page: u32) -> Result<JsonThresholds, ClientError> {
+        self.client
+            .send_with(|client| async move {
+                client
+                    .proj_thresholds_get()
+                    .project(self.project.clone())
+                    .per_page(per_page)
+                    .page(page)
+                    .send()
+                    .await
+            })
+            .await
+    }
+
+    /// Create a new threshold for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `threshold`: The new threshold to create
+    pub async fn create(
+        &self,
+        threshold: types::JsonNewThreshold,
+    ) -> Result<JsonThreshold, ClientError> {
+        self.client
+            .send_with(|client| {
+                let threshold = threshold.clone();
+                async move {
+                    client
+                        .proj_threshold_post()
+                        .project(self.project.clone())
+                        .body(threshold)
+                        .send()
+                        .await
+                }
+            })
+            .await
+    }
+
+    /// Update the model for a threshold for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `threshold`: The UUID for the threshold
+    /// - `update`: The new model or model removal
+    pub async fn update(
+        &self,
+        threshold: ThresholdUuid,
+        update: types::JsonUpdateThreshold,
+    ) -> Result<JsonThreshold, ClientError> {
+        self.client
+            .send_with(|client| {
+                let update = update.clone();
+                async move {
+                    client
+                        .proj_threshold_put()
+                        .project(self.project.clone())
+                        .threshold(threshold)
+                        .body(update)
+                        .send()
+                        .await
+                }
+            })
+            .await
+    }
+
+    /// Delete a threshold for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `threshold`: The UUID for the threshold
+    pub async fn delete(&self, threshold: ThresholdUuid) -> Result<(), ClientError> {
+        self.client
+            .send(|client| async move {
+                client
+                    .proj_threshold_delete()
+                    .project(self.project.clone())
+                    .threshold(threshold)
+                    .send()
+                    .await
+            })
+            .await
+            .map(|_json| ())
+    }
+}
+
+/// A typed client for the measures of a project
+#[derive(Debug, Clone)]
+pub struct MeasuresClient<'c> {
+    client: &'c BencherClient,
+    project: ResourceId,
+}
+
+impl MeasuresClient<'_> {
+    /// List a page of measures for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `per_page`: The number of measures per page
+    /// - `page`: The page number, starting at `1`
+    pub async fn list(&self, per_page: u8, page: u32) -> Result<JsonMeasures, ClientError> {
+        self.client
+            .send_with(|client| async move {
+                client
+                    .proj_measures_get()
+                    .project(self.project.clone())
+                    .per_page(per_page)
+                    .page(page)
+                    .send()
+                    .await
+            })
+            .await
+    }
+
+    /// View a measure for the project
+    ///
+    /// # Parameters
+    ///
+    /// - `measure`: The slug or UUID for the measure
+    pub async fn get<M>(&self, measure: M) -> Result<JsonMeasure, ClientError>
+    where
+        M: Into<ResourceId>,
+    {
+        let measure = measure.into();
+        self.client
+            .send_with(|client| {
+                let measure = measure.clone();
+                async move {
+                    client
+                        .proj_measure_get()
+                        .project(self.project.clone())
+                        .measure(measure)
+                        .send()
+                        .await
+                }
+            })
+            .await
+    }
+}
@@ -0,0 +1,533 @@
+use bencher_json::{
+    project::{
+        alert::{AlertSeverity, AlertStatus},
+        head::VersionNumber,
+        report::is_valid_git_hash_prefix,
+        Visibility,
+    },
+    urlencoded::to_urlencoded,
+    DateTime, JsonDirection, NameId, NonEmpty, ResourceName,
+};
+
+use crate::codegen::types;
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum QueryError {
+    #[error("The number of items per page must be at least 1")]
+    PerPage,
+    #[error("The page number must be at least 1")]
+    Page,
+    #[error("The search string must not be empty")]
+    Search,
+    #[error("The start time ({start_time}) must be at or before the end time ({end_time})")]
+    TimeRange {
+        start_time: DateTime,
+        end_time: DateTime,
+    },
+    #[error("The git hash ({0}) must be 4 to 64 hexadecimal characters")]
+    Hash(String),
+    #[error(
+        "The start version ({start_version}) must be at or before the end version ({end_version})"
+    )]
+    VersionRange {
+        start_version: VersionNumber,
+        end_version: VersionNumber,
+    },
+}
+
+/// The sort order and page for a list endpoint
+#[derive(Debug, Clone, Copy)]
+pub struct Pagination<S> {
+    pub(crate) sort: Option<S>,
+    pub(crate) direction: Option<JsonDirection>,
+    pub(crate) per_page: Option<u8>,
+    pub(crate) page: Option<u32>,
+}
+
+impl<S> Default for Pagination<S> {
+    fn default() -> Self {
+        Self {
+            sort: None,
+            direction: None,
+            per_page: None,
+            page: None,
+        }
+    }
+}
+
+impl<S> Pagination<S> {
+    fn validate(&self) -> Result<(), QueryError> {
+        if self.per_page == Some(0) {
+            return Err(QueryError::PerPage);
+        }
+        if self.page == Some(0) {
+            return Err(QueryError::Page);
+        }
+        Ok(())
+    }
+}
+
+/// The field to sort projects by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectsSort {
+    /// Sort by project name
+    Name,
+}
+
+impl From<ProjectsSort> for types::ProjectsSort {
+    fn from(sort: ProjectsSort) -> Self {
+        match sort {
+            ProjectsSort::Name => Self::Name,
+        }
+    }
+}
+
+/// The field to sort reports by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportsSort {
+    /// Sort by report date time
+    DateTime,
+    /// Sort by report branch version number
+    Version,
+}
+
+impl From<ReportsSort> for types::ProjReportsSort {
+    fn from(sort: ReportsSort) -> Self {
+        match sort {
+            ReportsSort::DateTime => Self::DateTime,
+            ReportsSort::Version => Self::Version,
+        }
+    }
+}
+
+/// The field to sort alerts by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertsSort {
+    /// Sort by alert creation date time
+    Created,
+    /// Sort by alert modified date time
+    Modified,
+}
+
+impl From<AlertsSort> for types::ProjAlertsSort {
+    fn from(sort: AlertsSort) -> Self {
+        match sort {
+            AlertsSort::Created => Self::Created,
+            AlertsSort::Modified => Self::Modified,
+        }
+    }
+}
+
+impl From<JsonDirection> for types::JsonDirection {
+    fn from(direction: JsonDirection) -> Self {
+        match direction {
+            JsonDirection::Asc => Self::Asc,
+            JsonDirection::Desc => Self::Desc,
+        }
+    }
+}
+
+impl From<Visibility> for types::Visibility {
+    fn from(visibility: Visibility) -> Self {
+        match visibility {
+            Visibility::Public => Self::Public,
+            #[cfg(feature = "plus")]
+            Visibility::Private => Self::Private,
+            #[cfg(feature = "plus")]
+            Visibility::Internal => Self::Internal,
+        }
+    }
+}
+
+impl From<AlertStatus> for types::AlertStatus {
+    fn from(status: AlertStatus) -> Self {
+        match status {
+            AlertStatus::Active => Self::Active,
+            AlertStatus::Dismissed => Self::Dismissed,
+            AlertStatus::Silenced => Self::Silenced,
+        }
+    }
+}
+
+impl From<AlertSeverity> for types::AlertSeverity {
+    fn from(severity: AlertSeverity) -> Self {
+        match severity {
+            AlertSeverity::Minor => Self::Minor,
+            AlertSeverity::Major => Self::Major,
+            AlertSeverity::Critical => Self::Critical,
+        }
+    }
+}
+
+// Each query builder wraps the query that it builds,
+// so the query can only be used once it has been validated.
+macro_rules! query_builder {
+    ($query:ident, $builder:ident, $sort:ident) => {
+        #[derive(Debug, Clone, Default)]
+        pub struct $builder($query);
+
+        impl $query {
+            /// Create a new query builder
+            pub fn builder() -> $builder {
+                $builder::default()
+            }
+        }
+
+        impl $builder {
+            /// Set the field to sort by
+            #[must_use]
+            pub fn sort(mut self, sort: $sort) -> Self {
+                self.0.pagination.sort = Some(sort);
+                self
+            }
+
+            /// Set the direction to sort by
+            #[must_use]
+            pub fn direction(mut self, direction: JsonDirection) -> Self {
+                self.0.pagination.direction = Some(direction);
+                self
+            }
+
+            /// Set the number of items per page, which must be at least `1`
+            #[must_use]
+            pub fn per_page(mut self, per_page: u8) -> Self {
+                self.0.pagination.per_page = Some(per_page);
+                self
+            }
+
+            /// Set the page number, starting at `1`
+            #[must_use]
+            pub fn page(mut self, page: u32) -> Self {
+                self.0.pagination.page = Some(page);
+                self
+            }
+
+            /// Validate and build the query
+            pub fn build(self) -> Result<$query, QueryError> {
+                self.0.pagination.validate()?;
+                self.0.validate()?;
+                Ok(self.0)
+            }
+        }
+    };
+}
+
+/// A query for the list of projects
+#[derive(Debug, Clone, Default)]
+pub struct ProjectsQuery {
+    pub(crate) name: Option<ResourceName>,
+    pub(crate) search: Option<String>,
+    pub(crate) visibility: Option<Visibility>,
+    pub(crate) pagination: Pagination<ProjectsSort>,
+}
+
+query_builder!(ProjectsQuery, ProjectsQueryBuilder, ProjectsSort);
+
+impl ProjectsQuery {
+    fn validate(&self) -> Result<(), QueryError> {
+        validate_search(self.search.as_deref())
+    }
+}
+
+impl ProjectsQueryBuilder {
+    /// Filter by project name exact match
+    #[must_use]
+    pub fn name(mut self, name: ResourceName) -> Self {
+        self.0.name = Some(name);
+        self
+    }
+
+    /// Search by project name, slug, or UUID
+    #[must_use]
+    pub fn search(mut self, search: String) -> Self {
+        self.0.search = Some(search);
+        self
+    }
+
+    /// Filter by project visibility
+    #[must_use]
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.0.visibility = Some(visibility);
+        self
+    }
+}
+
+/// A query for the list of reports for a project
+#[derive(Debug, Clone, Default)]
+pub struct ReportsQuery {
+    pub(crate) branch: Option<NameId>,
+    pub(crate) testbed: Option<NameId>,
+    pub(crate) tag: Option<NonEmpty>,
+    pub(crate) start_time: Option<DateTime>,
+    pub(crate) end_time: Option<DateTime>,
+    pub(crate) hash: Option<String>,
+    pub(crate) start_version: Option<VersionNumber>,
+    pub(crate) end_version: Option<VersionNumber>,
+    pub(crate) archived: Option<bool>,
+    pub(crate) pagination: Pagination<ReportsSort>,
+}
+
+query_builder!(ReportsQuery, ReportsQueryBuilder, ReportsSort);
+
+impl ReportsQuery {
+    fn validate(&self) -> Result<(), QueryError> {
+        if let (Some(start_time), Some(end_time)) = (self.start_time, self.end_time) {
+            if start_time.timestamp() > end_time.timestamp() {
+                return Err(QueryError::TimeRange {
+                    start_time,
+                    end_time,
+                });
+            }
+        }
+        if let Some(hash) = self.hash.as_ref() {
+            if !is_valid_git_hash_prefix(hash) {
+                return Err(QueryError::Hash(hash.clone()));
+            }
+        }
+        if let (Some(start_version), Some(end_version)) = (self.start_version, self.end_version) {
+            if start_version.0 > end_version.0 {
+                return Err(QueryError::VersionRange {
+                    start_version,
+                    end_version,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn branch(&self) -> Option<String> {
+        self.branch.as_ref().map(to_urlencoded)
+    }
+
+    pub(crate) fn testbed(&self) -> Option<String> {
+        self.testbed.as_ref().map(to_urlencoded)
+    }
+
+    pub(crate) fn tag(&self) -> Option<String> {
+        self.tag.as_ref().map(to_urlencoded)
+    }
+}
+
+impl ReportsQueryBuilder {
+    /// Filter by branch name, slug, or UUID
+    #[must_use]
+    pub fn branch(mut self, branch: NameId) -> Self {
+        self.0.branch = Some(branch);
+        self
+    }
+
+    /// Filter by testbed name, slug, or UUID
+    #[must_use]
+    pub fn testbed(mut self, testbed: NameId) -> Self {
+        self.0.testbed = Some(testbed);
+        self
+    }
+
+    /// Filter by release tag
+    #[must_use]
+    pub fn tag(mut self, tag: NonEmpty) -> Self {
+        self.0.tag = Some(tag);
+        self
+    }
+
+    /// Filter for reports that started at or after the given date time
+    #[must_use]
+    pub fn start_time(mut self, start_time: DateTime) -> Self {
+        self.0.start_time = Some(start_time);
+        self
+    }
+
+    /// Filter for reports that ended at or before the given date time
+    #[must_use]
+    pub fn end_time(mut self, end_time: DateTime) -> Self {
+        self.0.end_time = Some(end_time);
+        self
+    }
+
+    /// Filter by git hash, either a full hash or a short hash of at least 4 characters
+    #[must_use]
+    pub fn hash<H>(mut self, hash: H) -> Self
+    where
+        H: Into<String>,
+    {
+        self.0.hash = Some(hash.into());
+        self
+    }
+
+    /// Filter for reports at or after the given branch version number
+    #[must_use]
+    pub fn start_version(mut self, start_version: VersionNumber) -> Self {
+        self.0.start_version = Some(start_version);
+        self
+    }
+
+    /// Filter for reports at or before the given branch version number
+    #[must_use]
+    pub fn end_version(mut self, end_version: VersionNumber) -> Self {
+        self.0.end_version = Some(end_version);
+        self
+    }
+
+    /// Only return reports with an archived branch or testbed
+    #[must_use]
+    pub fn archived(mut self, archived: bool) -> Self {
+        self.0.archived = Some(archived);
+        self
+    }
+}
+
+/// A query for the list of alerts for a project
+#[derive(Debug, Clone, Default)]
+pub struct AlertsQuery {
+    pub(crate) status: Option<AlertStatus>,
+    pub(crate) severity: Option<AlertSeverity>,
+    pub(crate) archived: Option<bool>,
+    pub(crate) pagination: Pagination<AlertsSort>,
+}
+
+query_builder!(AlertsQuery, AlertsQueryBuilder, AlertsSort);
+
+impl AlertsQuery {
+    #[allow(clippy::unnecessary_wraps, clippy::unused_self)]
+    fn validate(&self) -> Result<(), QueryError> {
+        Ok(())
+    }
+}
+
+impl AlertsQueryBuilder {
+    /// Filter by alert status
+    #[must_use]
+    pub fn status(mut self, status: AlertStatus) -> Self {
+        self.0.status = Some(status);
+        self
+    }
+
+    /// Filter by alert severity
+    #[must_use]
+    pub fn severity(mut self, severity: AlertSeverity) -> Self {
+        self.0.severity = Some(severity);
+        self
+    }
+
+    /// Only return alerts with an archived branch, testbed, or measure
+    #[must_use]
+    pub fn archived(mut self, archived: bool) -> Self {
+        self.0.archived = Some(archived);
+        self
+    }
+}
+
+// Add the pagination query parameters to a generated request builder
+macro_rules! paginate {
+    ($builder:ident, $pagination:expr) => {
+        if let Some(sort) = $pagination.sort {
+            $builder = $builder.sort(sort);
+        }
+        if let Some(direction) = $pagination.direction {
+            $builder = $builder.direction(direction);
+        }
+        if let Some(per_page) = $pagination.per_page {
+            $builder = $builder.per_page(per_page);
+        }
+        if let Some(page) = $pagination.page {
+            $builder = $builder.page(page);
+        }
+    };
+}
+
+pub(crate) use paginate;
+
+fn validate_search(search: Option<&str>) -> Result<(), QueryError> {
+    if search.is_some_and(str::is_empty) {
+        Err(QueryError::Search)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{
+        project::head::VersionNumber, DateTime, JsonDirection, NameId, ResourceName,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{ProjectsQuery, QueryError, ReportsQuery, ReportsSort};
+
+    #[test]
+    fn test_projects_query() {
+        let name: ResourceName = "Project".parse().unwrap();
+        let query = ProjectsQuery::builder()
+            .name(name.clone())
+            .direction(JsonDirection::Asc)
+            .per_page(10)
+            .page(2)
+            .build()
+            .unwrap();
+        assert_eq!(query.name, Some(name));
+        assert_eq!(query.pagination.per_page, Some(10));
+        assert_eq!(query.pagination.page, Some(2));
+
+        let err = ProjectsQuery::builder()
+            .search(String::new())
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, QueryError::Search));
+    }
+
+    #[test]
+    fn test_query_pagination() {
+        let err = ProjectsQuery::builder().per_page(0).build().unwrap_err();
+        assert!(matches!(err, QueryError::PerPage));
+
+        let err = ReportsQuery::builder().page(0).build().unwrap_err();
+        assert!(matches!(err, QueryError::Page));
+    }
+
+    #[test]
+    fn test_reports_query() {
+        let branch: NameId = "main".parse().unwrap();
+        let query = ReportsQuery::builder()
+            .branch(branch)
+            .sort(ReportsSort::Version)
+            .start_time(DateTime::try_from(1_000).unwrap())
+            .end_time(DateTime::try_from(2_000).unwrap())
+            .hash("abcd")
+            .start_version(VersionNumber(1))
+            .end_version(VersionNumber(1))
+            .build()
+            .unwrap();
+        assert_eq!(query.branch().as_deref(), Some("main"));
+        assert_eq!(query.pagination.sort, Some(ReportsSort::Version));
+        assert_eq!(query.hash.as_deref(), Some("abcd"));
+    }
+
+    #[test]
+    fn test_reports_query_time_range() {
+        let err = ReportsQuery::builder()
+            .start_time(DateTime::try_from(2_000).unwrap())
+            .end_time(DateTime::try_from(1_000).unwrap())
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, QueryError::TimeRange { .. }));
+    }
+
+    #[test]
+    fn test_reports_query_hash() {
+        let err = ReportsQuery::builder().hash("abc").build().unwrap_err();
+        assert!(matches!(err, QueryError::Hash(hash) if hash == "abc"));
+
+        let err = ReportsQuery::builder().hash("not_hex").build().unwrap_err();
+        assert!(matches!(err, QueryError::Hash(_)));
+    }
+
+    #[test]
+    fn test_reports_query_version_range() {
+        let err = ReportsQuery::builder()
+            .start_version(VersionNumber(2))
+            .end_version(VersionNumber(1))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, QueryError::VersionRange { .. }));
+    }
+}
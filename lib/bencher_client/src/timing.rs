@@ -0,0 +1,196 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Records the timings for each API call.
+///
+/// The timer is shared by all clones of a `BencherClient`,
+/// and it does nothing unless it is enabled.
+#[derive(Debug, Clone, Default)]
+pub struct ApiTimer(Option<Arc<Mutex<Vec<ApiTiming>>>>);
+
+/// The timer for a single call to the API server.
+/// It is passed to the generated client so the request hooks can record the time to first byte.
+/// Each call has its own pending request, so concurrent calls do not overwrite each other.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestTimer {
+    timer: ApiTimer,
+    pending: Arc<Mutex<Option<PendingRequest>>>,
+}
+
+#[derive(Debug)]
+struct PendingRequest {
+    method: String,
+    path: String,
+    start: Instant,
+    status: Option<u16>,
+    ttfb: Option<Duration>,
+}
+
+/// The timings for a single API call attempt
+#[derive(Debug, Clone)]
+pub struct ApiTiming {
+    pub method: String,
+    pub path: String,
+    pub attempt: usize,
+    pub status: Option<u16>,
+    pub ttfb: Option<Duration>,
+    pub total: Duration,
+}
+
+impl ApiTimer {
+    /// Create a timer that records API call timings
+    pub fn enabled() -> Self {
+        Self(Some(Arc::default()))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Create a timer for a single call to the API server
+    pub(crate) fn request(&self) -> RequestTimer {
+        RequestTimer {
+            timer: self.clone(),
+            pending: Arc::default(),
+        }
+    }
+
+    /// Get the timings for all of the API calls so far
+    pub fn timings(&self) -> Vec<ApiTiming> {
+        self.0
+            .as_ref()
+            .map(|timings| {
+                timings
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clone()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Summarize the timings for all of the API calls so far
+    pub fn summary(&self) -> ApiTimingSummary {
+        ApiTimingSummary(self.timings())
+    }
+}
+
+impl RequestTimer {
+    fn with_pending<F>(&self, f: F)
+    where
+        F: FnOnce(&mut Option<PendingRequest>),
+    {
+        if self.timer.is_enabled() {
+            f(&mut self.pending.lock().unwrap_or_else(PoisonError::into_inner));
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn record(&self, attempt: usize, total: Duration) {
+        let Some(timings) = self.timer.0.as_ref() else {
+            return;
+        };
+        // The request hooks are not run if the request could not be built
+        let Some(pending) = self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        else {
+            return;
+        };
+        timings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(ApiTiming {
+                method: pending.method,
+                path: pending.path,
+                attempt,
+                status: pending.status,
+                ttfb: pending.ttfb,
+                total,
+            });
+    }
+}
+
+/// Called by the generated client right before a request is sent
+pub(crate) fn pre_hook(timer: &RequestTimer, request: &reqwest::Request) {
+    timer.with_pending(|pending| {
+        *pending = Some(PendingRequest {
+            method: request.method().to_string(),
+            path: request.url().path().to_owned(),
+            start: Instant::now(),
+            status: None,
+            ttfb: None,
+        });
+    });
+}
+
+/// Called by the generated client as soon as the response headers have been received
+pub(crate) fn post_hook(timer: &RequestTimer, result: &reqwest::Result<reqwest::Response>) {
+    timer.with_pending(|pending| {
+        if let Some(pending) = pending.as_mut() {
+            pending.ttfb = Some(pending.start.elapsed());
+            pending.status = result
+                .as_ref()
+                .ok()
+                .map(|response| response.status().as_u16());
+        }
+    });
+}
+
+/// A summary of the timings for all of the API calls
+pub struct ApiTimingSummary(pub Vec<ApiTiming>);
+
+impl fmt::Display for ApiTimingSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "API call timings:")?;
+        writeln!(
+            f,
+            "{:<7} {:<48} {:>7} {:>6} {:>10} {:>10}",
+            "Method", "Path", "Attempt", "Status", "TTFB", "Total"
+        )?;
+        for timing in &self.0 {
+            writeln!(
+                f,
+                "{:<7} {:<48} {:>7} {:>6} {:>10} {:>10}",
+                timing.method,
+                timing.path,
+                timing.attempt,
+                timing
+                    .status
+                    .map_or_else(|| "-".to_owned(), |status| status.to_string()),
+                Millis(timing.ttfb),
+                Millis(Some(timing.total)),
+            )?;
+        }
+
+        let sum = |duration: fn(&ApiTiming) -> Option<Duration>| {
+            self.0.iter().filter_map(duration).sum::<Duration>()
+        };
+        let total = sum(|timing| Some(timing.total));
+        // The time to first byte includes setting up the connection and waiting on the API server
+        let ttfb = sum(|timing| timing.ttfb);
+        write!(
+            f,
+            "{count} API call(s) took {total} in total: {ttfb} until the first byte of the responses (connecting and waiting on the API server).",
+            count = self.0.len(),
+            total = Millis(Some(total)),
+            ttfb = Millis(Some(ttfb)),
+        )
+    }
+}
+
+struct Millis(Option<Duration>);
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.0.map_or_else(
+            || "-".to_owned(),
+            |duration| format!("{:.1}ms", duration.as_secs_f64() * 1_000.0),
+        );
+        f.pad(&text)
+    }
+}
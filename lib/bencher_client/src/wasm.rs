@@ -0,0 +1,146 @@
+use std::str::FromStr;
+
+use bencher_json::{
+    system::version::{BENCHER_MEDIA_TYPE_PREFIX, BENCHER_MEDIA_TYPE_SUFFIX},
+    JsonNewReport, JsonReport, Jwt, ResourceId, ValidError,
+};
+use wasm_bindgen::prelude::*;
+
+use crate::{codegen::types, hooks::RequestHooks};
+
+const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A minimal client for the Bencher API that compiles to `wasm32-unknown-unknown`
+///
+/// This allows for browser-based benchmark harnesses to submit results directly from a test page.
+/// Requests are sent once using the browser `fetch` API,
+/// without the retries, failover, and timings of the full `BencherClient`.
+#[derive(Debug, Clone)]
+pub struct WasmClient {
+    pub host: url::Url,
+    pub token: Option<Jwt>,
+}
+
+#[allow(clippy::absolute_paths)]
+#[derive(thiserror::Error, Debug)]
+pub enum WasmClientError {
+    #[error("Failed to parse host URL: {0}")]
+    Host(url::ParseError),
+    #[error("Failed to parse API token: {0}")]
+    Token(ValidError),
+    #[error("Failed to parse project: {0}")]
+    Project(ValidError),
+    #[error("Failed to parse report JSON: {0}")]
+    ParseReport(serde_json::Error),
+
+    #[error("Failed to parse request header: {0}")]
+    HeaderValue(reqwest::header::InvalidHeaderValue),
+    #[error("Failed to build API client: {0}")]
+    BuildClient(reqwest::Error),
+    #[error("Failed to serialize request JSON: {0}")]
+    SerializeRequest(serde_json::Error),
+    #[error("Failed to deserialize response JSON: {0}")]
+    DeserializeResponse(serde_json::Error),
+    #[error("Failed to serialize response JSON: {0}")]
+    SerializeResponse(serde_json::Error),
+    #[error("Failed to send request: {0}")]
+    Send(crate::codegen::Error<types::Error>),
+}
+
+impl WasmClient {
+    /// Create a new `WasmClient`
+    ///
+    /// # Parameters
+    /// - `host`: The host URL
+    /// - `token`: The JWT token, required to submit to a private project
+    pub fn new(host: url::Url, token: Option<Jwt>) -> Self {
+        Self { host, token }
+    }
+
+    /// Create a new report for a project
+    ///
+    /// # Parameters
+    ///
+    /// - `project`: The project UUID or slug
+    /// - `report`: The new report to create, with its results in Bencher Metric Format (BMF)
+    pub async fn create_report(
+        &self,
+        project: ResourceId,
+        report: &JsonNewReport,
+    ) -> Result<JsonReport, WasmClientError> {
+        // This is a bit of a kludge, but it should always work!
+        let report: types::JsonNewReport = serde_json::to_value(report)
+            .and_then(serde_json::from_value)
+            .map_err(WasmClientError::SerializeRequest)?;
+        let json_report = self
+            .client()?
+            .proj_report_post()
+            .project(project)
+            .body(report)
+            .send()
+            .await
+            .map_err(WasmClientError::Send)?
+            .into_inner();
+        JsonReport::try_from(json_report).map_err(WasmClientError::DeserializeResponse)
+    }
+
+    fn client(&self) -> Result<crate::codegen::Client, WasmClientError> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        let accept = reqwest::header::HeaderValue::from_str(&format!(
+            "{BENCHER_MEDIA_TYPE_PREFIX}{CLIENT_VERSION}{BENCHER_MEDIA_TYPE_SUFFIX}, application/json"
+        ))
+        .map_err(WasmClientError::HeaderValue)?;
+        headers.insert(reqwest::header::ACCEPT, accept);
+        if let Some(token) = &self.token {
+            let bearer_token = reqwest::header::HeaderValue::from_str(&format!("Bearer {token}"))
+                .map_err(WasmClientError::HeaderValue)?;
+            headers.insert("Authorization", bearer_token);
+        }
+        let reqwest_client = reqwest::ClientBuilder::new()
+            .default_headers(headers)
+            .build()
+            .map_err(WasmClientError::BuildClient)?;
+        // The timer and cache must stay disabled,
+        // as `std::time::Instant` and `std::fs` are not supported on `wasm32-unknown-unknown`
+        Ok(crate::codegen::Client::new_with_client(
+            self.host.as_ref(),
+            reqwest_client,
+            RequestHooks::default(),
+        ))
+    }
+}
+
+/// Submit a report to the Bencher API from JavaScript
+///
+/// The `report` is a JSON string of a new report,
+/// and the created report is returned as a JSON string.
+#[wasm_bindgen(js_name = submitReport)]
+pub async fn submit_report(
+    host: String,
+    token: Option<String>,
+    project: String,
+    report: String,
+) -> Result<String, JsError> {
+    submit_report_inner(&host, token.as_deref(), &project, &report)
+        .await
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+async fn submit_report_inner(
+    host: &str,
+    token: Option<&str>,
+    project: &str,
+    report: &str,
+) -> Result<String, WasmClientError> {
+    let host = url::Url::parse(host).map_err(WasmClientError::Host)?;
+    let token = token
+        .map(Jwt::from_str)
+        .transpose()
+        .map_err(WasmClientError::Token)?;
+    let project = ResourceId::from_str(project).map_err(WasmClientError::Project)?;
+    let report = serde_json::from_str(report).map_err(WasmClientError::ParseReport)?;
+    let json_report = WasmClient::new(host, token)
+        .create_report(project, &report)
+        .await?;
+    serde_json::to_string(&json_report).map_err(WasmClientError::SerializeResponse)
+}
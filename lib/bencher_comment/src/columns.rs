@@ -0,0 +1,59 @@
+/// An optional column of the benchmark results tables in the HTML report comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentColumn {
+    /// The links to the plot, alert, and threshold
+    View,
+    /// The percent change of the benchmark result from the baseline
+    PercentChange,
+    /// The units of the measure
+    Units,
+    /// The lower boundary limit
+    LowerBoundary,
+    /// The upper boundary limit
+    UpperBoundary,
+}
+
+/// The set of optional columns to show in the HTML report comment.
+/// All columns are shown by default.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy)]
+pub struct CommentColumns {
+    pub view: bool,
+    pub percent_change: bool,
+    pub units: bool,
+    pub lower_boundary: bool,
+    pub upper_boundary: bool,
+}
+
+impl Default for CommentColumns {
+    fn default() -> Self {
+        Self {
+            view: true,
+            percent_change: true,
+            units: true,
+            lower_boundary: true,
+            upper_boundary: true,
+        }
+    }
+}
+
+impl CommentColumns {
+    #[must_use]
+    pub fn hide(mut self, column: CommentColumn) -> Self {
+        match column {
+            CommentColumn::View => self.view = false,
+            CommentColumn::PercentChange => self.percent_change = false,
+            CommentColumn::Units => self.units = false,
+            CommentColumn::LowerBoundary => self.lower_boundary = false,
+            CommentColumn::UpperBoundary => self.upper_boundary = false,
+        }
+        self
+    }
+}
+
+impl FromIterator<CommentColumn> for CommentColumns {
+    // Hide each of the given columns
+    fn from_iter<I: IntoIterator<Item = CommentColumn>>(hidden: I) -> Self {
+        hidden.into_iter().fold(Self::default(), Self::hide)
+    }
+}
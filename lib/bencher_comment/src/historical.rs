@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+use bencher_json::{JsonReport, Slug};
+
+use crate::{Benchmark, Measure};
+
+/// A prior report to compare the benchmark results against,
+/// in addition to the baseline used by the threshold.
+/// This highlights slow drift that never exceeds a threshold boundary for any single report.
+#[derive(Debug, Clone)]
+pub struct HistoricalComparison {
+    label: String,
+    values: BTreeMap<(Slug, Slug), f64>,
+}
+
+impl HistoricalComparison {
+    /// Create a comparison against a prior report.
+    /// The `label` describes the prior report in the column header, such as a release tag.
+    pub fn new(label: String, json_report: &JsonReport) -> Self {
+        let mut values = BTreeMap::new();
+        // If the prior report had multiple iterations, then the first value for each benchmark and measure is used
+        for result in json_report.results.iter().flatten() {
+            for report_measure in &result.measures {
+                values
+                    .entry((
+                        result.benchmark.slug.clone(),
+                        report_measure.measure.slug.clone(),
+                    ))
+                    .or_insert_with(|| report_measure.metric.value.into());
+            }
+        }
+        Self { label, values }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub(crate) fn value(&self, benchmark: &Benchmark, measure: &Measure) -> Option<f64> {
+        self.values
+            .get(&(benchmark.slug.clone(), measure.slug.clone()))
+            .copied()
+    }
+}
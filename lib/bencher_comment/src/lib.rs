@@ -1,20 +1,32 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     time::Duration,
 };
 
 use bencher_json::{
     project::{
+        alert::AlertSeverity,
         boundary::BoundaryLimit,
         plot::{LOWER_BOUNDARY, UPPER_BOUNDARY},
         threshold::JsonThresholdModel,
     },
-    AlertUuid, BenchmarkName, BenchmarkUuid, BranchUuid, DateTime, HeadUuid, JsonBoundary,
-    JsonPerfQuery, JsonReport, MeasureUuid, ModelUuid, ReportUuid, ResourceName, Slug, TestbedUuid,
-    ThresholdUuid,
+    AlertGroupUuid, AlertUuid, BenchmarkName, BenchmarkUuid, BranchUuid, DateTime, HeadUuid,
+    JsonBoundary, JsonPerfQuery, JsonReport, JsonUserPrefs, MeasureUuid, ModelUuid, ReportUuid,
+    ResourceName, Slug, TestbedUuid, ThresholdUuid,
 };
+use serde::Serialize;
 use url::Url;
 
+mod columns;
+mod historical;
+mod locale;
+mod template;
+
+pub use columns::{CommentColumn, CommentColumns};
+pub use historical::HistoricalComparison;
+pub use locale::CommentLocale;
+pub use template::{CommentTemplate, TemplateError};
+
 pub struct ReportComment {
     console_url: Url,
     project_slug: Slug,
@@ -23,6 +35,12 @@ pub struct ReportComment {
     benchmark_urls: BenchmarkUrls,
     alert_urls: AlertUrls,
     source: String,
+    locale: CommentLocale,
+    template: Option<CommentTemplate>,
+    columns: CommentColumns,
+    collapse_measures: bool,
+    historical: Option<HistoricalComparison>,
+    date: Option<String>,
 }
 
 impl ReportComment {
@@ -35,20 +53,73 @@ impl ReportComment {
             json_report,
             console_url,
             source,
+            locale: CommentLocale::default(),
+            template: None,
+            columns: CommentColumns::default(),
+            collapse_measures: false,
+            historical: None,
+            date: None,
         }
     }
 
+    #[must_use]
+    pub fn with_locale(mut self, locale: CommentLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    #[must_use]
+    pub fn with_template(mut self, template: CommentTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    #[must_use]
+    pub fn with_columns(mut self, columns: CommentColumns) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Put the results for each measure in its own collapsible section
+    #[must_use]
+    pub fn with_collapse_measures(mut self, collapse_measures: bool) -> Self {
+        self.collapse_measures = collapse_measures;
+        self
+    }
+
+    /// Add a column to the benchmark results tables that compares each result to a prior report
+    #[must_use]
+    pub fn with_historical(mut self, historical: HistoricalComparison) -> Self {
+        self.historical = Some(historical);
+        self
+    }
+
+    /// Display the report with the display preferences for a user.
+    /// The results and alerts are converted into the preferred units,
+    /// and if the user has a preferred time zone or date format, then the report date is shown.
+    /// This should be set before `with_historical`, so the prior report is created with the same units.
+    #[must_use]
+    pub fn with_prefs(mut self, prefs: &JsonUserPrefs) -> Self {
+        prefs.convert_report(&mut self.json_report);
+        self.alert_urls = AlertUrls::new(&self.console_url, &self.json_report);
+        self.benchmark_urls = BenchmarkUrls::new(self.console_url.clone(), &self.json_report);
+        self.date = prefs
+            .has_date_prefs()
+            .then(|| prefs.format_date_time(self.json_report.end_time));
+        self
+    }
+
     pub fn human(&self) -> String {
         let mut comment = String::new();
 
-        comment.push_str("View results:");
+        comment.push_str(&self.locale.view_results);
         let multiple_iterations = self.json_report.results.len() > 1;
         for (iter, benchmark_map) in self.benchmark_urls.0.iter().enumerate() {
             if multiple_iterations {
                 if iter != 0 {
                     comment.push('\n');
                 }
-                comment.push_str(&format!("\nIteration {iter}:"));
+                comment.push_str(&format!("\n{} {iter}:", self.locale.iteration));
             }
             for (benchmark, measure_map) in benchmark_map {
                 for (measure, MeasureData { console_url, .. }) in measure_map {
@@ -61,22 +132,70 @@ impl ReportComment {
             }
         }
 
+        if let Some(notes) = &self.json_report.notes {
+            comment.push_str(&format!("\n\n{}:", self.locale.notes));
+            for note in notes {
+                comment.push_str(&format!("\n- {note}"));
+            }
+        }
+        if let Some(links) = &self.json_report.links {
+            comment.push_str(&format!("\n\n{}:", self.locale.links));
+            for link in links {
+                comment.push_str(&format!("\n- {link}"));
+            }
+        }
+        if self.is_dirty() {
+            comment.push_str(&format!(
+                "\n\n{warning}: {dirty}",
+                warning = self.locale.warning,
+                dirty = self.locale.dirty_working_tree
+            ));
+        }
+
         if self.json_report.alerts.is_empty() {
             return comment;
         }
 
-        comment.push_str("\n\nView alerts:");
-        for ((iteration, benchmark, measure), AlertData { console_url, .. }) in &self.alert_urls.0 {
-            comment.push_str(&format!(
-                "\n- {benchmark_name} ({measure_name}){iter}: {console_url}",
-                benchmark_name = benchmark.name,
-                measure_name = measure.name,
-                iter = if multiple_iterations {
-                    format!(" (Iteration {iteration})")
-                } else {
-                    String::new()
+        comment.push_str(&format!("\n\n{}", self.locale.view_alerts));
+        let mut current_group = None;
+        for (
+            (iteration, benchmark, measure),
+            AlertData {
+                console_url,
+                severity,
+                group,
+                ..
+            },
+        ) in &self.alert_urls.0
+        {
+            let iter = if multiple_iterations {
+                format!(" ({} {iteration})", self.locale.iteration)
+            } else {
+                String::new()
+            };
+            // The alerts in a correlated group are listed together under their benchmark
+            if group.is_some() {
+                if *group != current_group {
+                    comment.push_str(&format!(
+                        "\n- {benchmark_name}{iter} [{correlated}]:",
+                        benchmark_name = benchmark.name,
+                        correlated = self.locale.correlated_regression,
+                    ));
+                    current_group = *group;
                 }
-            ));
+                comment.push_str(&format!(
+                    "\n  - {measure_name} [{severity}]: {console_url}",
+                    measure_name = measure.name,
+                    severity = self.locale.severity(*severity),
+                ));
+            } else {
+                comment.push_str(&format!(
+                    "\n- {benchmark_name} ({measure_name}){iter} [{severity}]: {console_url}",
+                    benchmark_name = benchmark.name,
+                    measure_name = measure.name,
+                    severity = self.locale.severity(*severity),
+                ));
+            }
         }
 
         comment
@@ -86,27 +205,75 @@ impl ReportComment {
         serde_json::to_string_pretty(&self.json_report)
     }
 
-    pub fn html(&self, require_threshold: bool, id: Option<&str>) -> String {
-        let mut html = String::new();
-        let html_mut = &mut html;
-        self.html_header(html_mut);
-        self.html_report_table(html_mut);
-        self.html_benchmarks(html_mut, require_threshold);
-        self.html_footer(html_mut);
+    pub fn html(&self, require_threshold: bool, id: Option<&str>) -> Result<String, TemplateError> {
+        let mut html = if let Some(template) = &self.template {
+            template.render(&self.template_context(require_threshold))?
+        } else {
+            let mut html = String::new();
+            let html_mut = &mut html;
+            self.html_header(html_mut);
+            self.html_report_table(html_mut);
+            self.html_benchmarks(html_mut, require_threshold);
+            self.html_footer(html_mut);
+            html
+        };
         // DO NOT MOVE: The Bencher tag must be the last thing in the HTML for updates to work
-        self.html_bencher_tag(html_mut, id);
-        html
+        self.html_bencher_tag(&mut html, id);
+        Ok(html)
     }
 
-    fn utm_query(&self) -> String {
-        format!(
-            "utm_medium=referral&utm_source={source}&utm_content=comment&utm_campaign=pr+comments&utm_term={project}",
-            source = self.source,
-            project = self.project_slug,
-        )
+    // Each section of the default comment is pre-rendered,
+    // so a custom template can reorder them or mix them with its own content.
+    fn template_context(&self, require_threshold: bool) -> tera::Context {
+        let section = |f: &dyn Fn(&mut String)| {
+            let mut html = String::new();
+            f(&mut html);
+            html
+        };
+        let mut context = tera::Context::new();
+        context.insert("report", &self.json_report);
+        context.insert("report_url", &self.report_url().to_string());
+        context.insert(
+            "alerts",
+            &self
+                .alert_urls
+                .0
+                .iter()
+                .map(|((iteration, benchmark, measure), alert)| TemplateAlert {
+                    iteration: *iteration,
+                    benchmark: &benchmark.name,
+                    measure: &measure.name,
+                    severity: self.locale.severity(alert.severity),
+                    group: alert.group,
+                    url: if self.public_links {
+                        alert.public_url.as_str()
+                    } else {
+                        alert.console_url.as_str()
+                    },
+                })
+                .collect::<Vec<_>>(),
+        );
+        context.insert("has_threshold", &self.has_threshold());
+        context.insert("has_alert", &self.has_alert());
+        context.insert("header", &section(&|html| self.html_header(html)));
+        context.insert(
+            "report_table",
+            &section(&|html| self.html_report_table(html)),
+        );
+        context.insert(
+            "benchmarks",
+            &section(&|html| self.html_benchmarks(html, require_threshold)),
+        );
+        context.insert("alerts_table", &section(&|html| self.html_alerts(html)));
+        context.insert(
+            "benchmark_details",
+            &section(&|html| self.html_benchmark_details(html, require_threshold)),
+        );
+        context.insert("footer", &section(&|html| self.html_footer(html)));
+        context
     }
 
-    fn html_header(&self, html: &mut String) {
+    fn report_url(&self) -> Url {
         let url = self.console_url.clone();
         let path = if self.public_links {
             format!(
@@ -119,10 +286,23 @@ impl ReportComment {
                 self.project_slug, self.json_report.uuid
             )
         };
-        let report_url = url.join(&path).unwrap_or(url);
+        url.join(&path).unwrap_or(url)
+    }
+
+    fn utm_query(&self) -> String {
+        format!(
+            "utm_medium=referral&utm_source={source}&utm_content=comment&utm_campaign=pr+comments&utm_term={project}",
+            source = self.source,
+            project = self.project_slug,
+        )
+    }
+
+    fn html_header(&self, html: &mut String) {
         html.push_str(&format!(
-            r#"<h2><a href="{report_url}?{utm}"><img src="https://bencher.dev/favicon.svg" width="24" height="24" alt="🐰" /> Bencher Report</a></h2>"#,
+            r#"<h2><a href="{report_url}?{utm}"><img src="https://bencher.dev/favicon.svg" width="24" height="24" alt="🐰" /> {bencher_report}</a></h2>"#,
+            report_url = self.report_url(),
             utm = self.utm_query(),
+            bencher_report = self.locale.bencher_report,
         ));
     }
 
@@ -130,7 +310,7 @@ impl ReportComment {
         html.push_str("<table>");
         for (row, name, path) in [
             (
-                "Branch",
+                &self.locale.branch,
                 self.json_report.branch.name.to_string(),
                 if self.public_links {
                     format!(
@@ -145,7 +325,7 @@ impl ReportComment {
                 },
             ),
             (
-                "Testbed",
+                &self.locale.testbed,
                 self.json_report.testbed.name.to_string(),
                 if self.public_links {
                     format!(
@@ -167,13 +347,84 @@ impl ReportComment {
                 utm = self.utm_query()
             ));
         }
+        if let Some(date) = &self.date {
+            html.push_str(&format!(
+                "<tr><td>{row}</td><td>{date}</td></tr>",
+                row = self.locale.date,
+            ));
+        }
+        if let Some(notes) = &self.json_report.notes {
+            html.push_str(&format!(
+                "<tr><td>{row}</td><td>{notes}</td></tr>",
+                row = self.locale.notes,
+                notes = notes
+                    .iter()
+                    .map(|note| escape_html(note.as_ref()))
+                    .collect::<Vec<_>>()
+                    .join("<br/>"),
+            ));
+        }
+        if let Some(links) = &self.json_report.links {
+            html.push_str(&format!(
+                "<tr><td>{row}</td><td>{links}</td></tr>",
+                row = self.locale.links,
+                links = links
+                    .iter()
+                    .map(|link| {
+                        let escaped = escape_html(link.as_ref());
+                        // Only link to web pages, even if an older report stored some other scheme
+                        if link.is_web() {
+                            format!(r#"<a href="{escaped}">{escaped}</a>"#)
+                        } else {
+                            escaped
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("<br/>"),
+            ));
+        }
+        if let Some(git) = &self.json_report.git {
+            let mut git_info = git
+                .branch
+                .iter()
+                .chain(&git.tag)
+                .map(|name| format!("<code>{}</code>", escape_html(name.as_ref())))
+                .chain(
+                    git.subject
+                        .iter()
+                        .map(|subject| escape_html(subject.as_ref())),
+                )
+                .collect::<Vec<_>>();
+            if self.is_dirty() {
+                git_info.push(format!("⚠️ {}", self.locale.dirty_working_tree));
+            }
+            if !git_info.is_empty() {
+                html.push_str(&format!(
+                    "<tr><td>{row}</td><td>{git_info}</td></tr>",
+                    row = self.locale.git,
+                    git_info = git_info.join("<br/>"),
+                ));
+            }
+        }
         html.push_str("</table>");
     }
 
+    fn is_dirty(&self) -> bool {
+        self.json_report
+            .git
+            .as_ref()
+            .and_then(|git| git.dirty)
+            .unwrap_or_default()
+    }
+
     fn html_benchmarks(&self, html: &mut String, require_threshold: bool) {
         let no_benchmarks = self.benchmark_urls.0.iter().all(BTreeMap::is_empty);
         if no_benchmarks {
-            html.push_str("<blockquote><b>⚠️ WARNING:</b> No benchmarks found!</blockquote>");
+            html.push_str(&format!(
+                "<blockquote><b>⚠️ {warning}:</b> {no_benchmarks}</blockquote>",
+                warning = self.locale.warning,
+                no_benchmarks = self.locale.no_benchmarks,
+            ));
             return;
         }
         self.html_no_threshold_warning(html);
@@ -197,12 +448,15 @@ impl ReportComment {
         if no_threshold.is_empty() {
             return;
         }
-        let plural_measure = if no_threshold.len() == 1 {
-            "Measure does"
+        let no_threshold_warning = if no_threshold.len() == 1 {
+            &self.locale.measure_no_threshold
         } else {
-            "Measures do"
+            &self.locale.measures_no_threshold
         };
-        html.push_str(&format!("<blockquote><p><b>⚠️ WARNING:</b> The following {plural_measure} not have a Threshold. Without a Threshold, no Alerts will ever be generated!</p>"));
+        html.push_str(&format!(
+            "<blockquote><p><b>⚠️ {warning}:</b> {no_threshold_warning}</p>",
+            warning = self.locale.warning,
+        ));
         html.push_str("<ul>");
         for measure in no_threshold {
             let url = self.console_url.clone();
@@ -222,40 +476,122 @@ impl ReportComment {
             ));
         }
         html.push_str("</ul>");
-        html.push_str(&format!("<p><a href=\"{console_url}console/projects/{project}/thresholds/add?{utm}\">Click here to create a new Threshold</a><br/>", console_url = self.console_url, project = self.project_slug, utm = self.utm_query()));
-        html.push_str(&format!("For more information, see <a href=\"https://bencher.dev/docs/explanation/thresholds/?{utm}\">the Threshold documentation</a>.<br/>", utm = self.utm_query()));
-        html.push_str(&format!("To only post results if a Threshold exists, set <a href=\"https://bencher.dev/docs/explanation/bencher-run/#--ci-only-thresholds?{utm}\">the <code lang=\"rust\">--ci-only-thresholds</code> CLI flag</a>.</p>", utm = self.utm_query()));
+        html.push_str(&format!("<p><a href=\"{console_url}console/projects/{project}/thresholds/add?{utm}\">{create_threshold}</a><br/>", console_url = self.console_url, project = self.project_slug, utm = self.utm_query(), create_threshold = self.locale.create_threshold));
+        html.push_str(&format!("{threshold_docs} <a href=\"https://bencher.dev/docs/explanation/thresholds/?{utm}\">{threshold_docs_link}</a>.<br/>", utm = self.utm_query(), threshold_docs = self.locale.threshold_docs, threshold_docs_link = self.locale.threshold_docs_link));
+        html.push_str(&format!("{ci_only_thresholds} <a href=\"https://bencher.dev/docs/explanation/bencher-run/#--ci-only-thresholds?{utm}\">{ci_only_thresholds_link}</a>.</p>", utm = self.utm_query(), ci_only_thresholds = self.locale.ci_only_thresholds, ci_only_thresholds_link = self.locale.ci_only_thresholds_link));
         html.push_str("</blockquote>");
     }
 
     fn html_alerts(&self, html: &mut String) {
         let alerts_len = self.alert_urls.0.len();
         if alerts_len > 0 {
-            let (alert, limit) = if alerts_len == 1 {
-                ("ALERT", "Limit")
+            let (alert, limit_exceeded) = if alerts_len == 1 {
+                (&self.locale.alert, &self.locale.limit_exceeded)
             } else {
-                ("ALERTS", "Limits")
+                (&self.locale.alerts, &self.locale.limits_exceeded)
             };
             html.push_str(&format!(
-                "<blockquote><b>🚨 {alerts_len} {alert}:</b> Threshold Boundary {limit} exceeded!</blockquote>",
+                "<blockquote><b>🚨 {alerts_len} {alert}:</b> {limit_exceeded}{severities}{correlated}</blockquote>",
+                severities = self.html_alert_severities(),
+                correlated = self.html_alert_groups(),
             ));
             self.html_alerts_table(html);
         }
     }
 
+    // Break down the alerts by severity, most severe first
+    fn html_alert_severities(&self) -> String {
+        let mut severities = BTreeMap::<AlertSeverity, usize>::new();
+        for alert in self.alert_urls.0.values() {
+            *severities.entry(alert.severity).or_default() += 1;
+        }
+        if severities.is_empty() {
+            return String::new();
+        }
+        format!(
+            "<br/>{}",
+            severities
+                .iter()
+                .rev()
+                .map(|(severity, count)| format!(
+                    "{emoji} {count} {label}",
+                    emoji = severity_emoji(*severity),
+                    label = self.locale.severity(*severity)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    // Count the correlated alert groups, where multiple measures for a benchmark regressed together
+    fn html_alert_groups(&self) -> String {
+        let groups = self
+            .alert_urls
+            .0
+            .values()
+            .filter_map(|alert| alert.group)
+            .collect::<HashSet<_>>()
+            .len();
+        if groups == 0 {
+            return String::new();
+        }
+        let correlated = if groups == 1 {
+            &self.locale.correlated_regression
+        } else {
+            &self.locale.correlated_regressions
+        };
+        format!("<br/>🔗 {groups} {correlated}")
+    }
+
+    #[allow(clippy::too_many_lines)]
     fn html_alerts_table(&self, html: &mut String) {
         html.push_str("<table>");
 
         let multiple_iterations = self.json_report.results.len() > 1;
-        html.push_str(&format!("<thead><tr>{iteration}<th>Benchmark</th><th>Measure<br/>Units</th><th>View</th><th>Benchmark Result<br/>(Result Δ%)</th><th>Lower Boundary<br/>(Limit %)</th><th>Upper Boundary<br/>(Limit %)</th></tr></thead>", iteration = if multiple_iterations {
-            "<th>Iteration</th>"
+        let CommentLocale {
+            benchmark,
+            measure,
+            units,
+            view,
+            benchmark_result,
+            result_delta,
+            lower_boundary,
+            upper_boundary,
+            limit_percent,
+            ..
+        } = &self.locale;
+        html.push_str("<thead><tr>");
+        if multiple_iterations {
+            html.push_str(&format!("<th>{}</th>", self.locale.iteration));
+        }
+        html.push_str(&format!("<th>{benchmark}</th>"));
+        if self.columns.units {
+            html.push_str(&format!("<th>{measure}<br/>{units}</th>"));
+        } else {
+            html.push_str(&format!("<th>{measure}</th>"));
+        }
+        if self.columns.view {
+            html.push_str(&format!("<th>{view}</th>"));
+        }
+        if self.columns.percent_change {
+            html.push_str(&format!("<th>{benchmark_result}<br/>({result_delta})</th>"));
         } else {
-            ""
-        }));
+            html.push_str(&format!("<th>{benchmark_result}</th>"));
+        }
+        if self.columns.lower_boundary {
+            html.push_str(&format!("<th>{lower_boundary}<br/>({limit_percent})</th>"));
+        }
+        if self.columns.upper_boundary {
+            html.push_str(&format!("<th>{upper_boundary}<br/>({limit_percent})</th>"));
+        }
+        html.push_str("</tr></thead>");
 
         html.push_str("<tbody>");
-        for ((iteration, benchmark, measure), alert) in &self.alert_urls.0 {
-            let Some(measure_data) =
+        let alert_rows = self
+            .alert_urls
+            .0
+            .iter()
+            .filter_map(|((iteration, benchmark, measure), alert)| {
                 self.benchmark_urls
                     .0
                     .get(*iteration)
@@ -264,32 +600,65 @@ impl ReportComment {
                             .get(benchmark)
                             .and_then(|measure_map| measure_map.get(measure))
                     })
-            else {
-                continue;
+                    .map(|measure_data| (iteration, benchmark, measure, alert, measure_data))
+            })
+            .collect::<Vec<_>>();
+        // The alerts in a correlated group share a single benchmark cell
+        let mut group_sizes = HashMap::<AlertGroupUuid, usize>::new();
+        for (_, _, _, alert, _) in &alert_rows {
+            if let Some(group) = alert.group {
+                *group_sizes.entry(group).or_default() += 1;
+            }
+        }
+        let mut current_group = None;
+        for (iteration, benchmark, measure, alert, measure_data) in alert_rows {
+            let rowspan = match alert.group {
+                Some(group) if current_group == Some(group) => None,
+                Some(group) => {
+                    current_group = Some(group);
+                    Some(group_sizes.get(&group).copied().unwrap_or(1))
+                },
+                None => {
+                    current_group = None;
+                    Some(1)
+                },
             };
 
             html.push_str("<tr>");
 
-            if multiple_iterations {
-                html.push_str(&format!("<td>{iteration}</td>"));
-            }
+            if let Some(rowspan) = rowspan {
+                let rowspan_attr = if rowspan > 1 {
+                    format!(r#" rowspan="{rowspan}""#)
+                } else {
+                    String::new()
+                };
 
-            // Benchmark
-            let url = self.console_url.clone();
-            let path = if self.public_links {
-                format!("/perf/{}/benchmarks/{}", self.project_slug, benchmark.slug)
-            } else {
-                format!(
-                    "/console/projects/{}/benchmarks/{}",
-                    self.project_slug, benchmark.slug
-                )
-            };
-            let url = url.join(&path).unwrap_or(url);
-            html.push_str(&format!(
-                r#"<td><a href="{url}?{utm}">{name}</a></td>"#,
-                utm = self.utm_query(),
-                name = benchmark.name,
-            ));
+                if multiple_iterations {
+                    html.push_str(&format!("<td{rowspan_attr}>{iteration}</td>"));
+                }
+
+                // Benchmark
+                let url = self.console_url.clone();
+                let path = if self.public_links {
+                    format!("/perf/{}/benchmarks/{}", self.project_slug, benchmark.slug)
+                } else {
+                    format!(
+                        "/console/projects/{}/benchmarks/{}",
+                        self.project_slug, benchmark.slug
+                    )
+                };
+                let url = url.join(&path).unwrap_or(url);
+                html.push_str(&format!(
+                    r#"<td{rowspan_attr}><a href="{url}?{utm}">{name}</a>{correlated}</td>"#,
+                    utm = self.utm_query(),
+                    name = benchmark.name,
+                    correlated = if alert.group.is_some() {
+                        format!("<br/>🔗 {}", self.locale.correlated_regression)
+                    } else {
+                        String::new()
+                    },
+                ));
+            }
 
             // Measure
             let url = self.console_url.clone();
@@ -303,50 +672,21 @@ impl ReportComment {
             };
             let url = url.join(&path).unwrap_or(url);
             html.push_str(&format!(
-                r#"<td><a href="{url}?{utm}">{name}<br/>{units}</a></td>"#,
+                r#"<td><a href="{url}?{utm}">{name}{units}</a></td>"#,
                 utm = self.utm_query(),
                 name = measure.name,
-                units = measure.units,
-            ));
-
-            html.push_str("<td>");
-            // Plot
-            html.push_str(&format!(
-                r#"📈 <a href="{plot}&{utm}">plot</a>"#,
-                plot = if self.public_links {
-                    &measure_data.public_url
-                } else {
-                    &measure_data.console_url
-                },
-                utm = self.utm_query(),
-            ));
-
-            html.push_str("<br/>");
-            // Alert
-            html.push_str(&format!(
-                r#"🚨 <a href="{alert}?{utm}">alert</a>"#,
-                alert = if self.public_links {
-                    &alert.public_url
+                units = if self.columns.units {
+                    format!("<br/>{}", measure.units)
                 } else {
-                    &alert.console_url
+                    String::new()
                 },
-                utm = self.utm_query(),
             ));
 
-            html.push_str("<br/>");
-            // Threshold
-            html.push_str(&format!(
-                r#"🚷 <a href="{threshold}&{utm}">threshold</a>"#,
-                threshold = if self.public_links {
-                    &alert.public_threshold_url
-                } else {
-                    &alert.console_threshold_url
-                },
-                utm = self.utm_query(),
-            ));
-            html.push_str("</td>");
+            if self.columns.view {
+                self.html_alert_view_cell(html, measure_data, alert);
+            }
 
-            Self::html_metric_boundary_cells(
+            self.html_metric_boundary_cells(
                 html,
                 measure_data.value,
                 measure_data.boundary,
@@ -359,15 +699,116 @@ impl ReportComment {
         html.push_str("</table>");
     }
 
+    fn html_alert_view_cell(
+        &self,
+        html: &mut String,
+        measure_data: &MeasureData,
+        alert: &AlertData,
+    ) {
+        html.push_str("<td>");
+        // Plot
+        html.push_str(&format!(
+            r#"📈 <a href="{plot}&{utm}">{plot_link}</a>"#,
+            plot = if self.public_links {
+                &measure_data.public_url
+            } else {
+                &measure_data.console_url
+            },
+            utm = self.utm_query(),
+            plot_link = self.locale.plot_link,
+        ));
+
+        html.push_str("<br/>");
+        // Alert
+        html.push_str(&format!(
+            r#"🚨 <a href="{alert}?{utm}">{alert_link}</a> ({emoji} {label})"#,
+            alert = if self.public_links {
+                &alert.public_url
+            } else {
+                &alert.console_url
+            },
+            utm = self.utm_query(),
+            alert_link = self.locale.alert_link,
+            emoji = severity_emoji(alert.severity),
+            label = self.locale.severity(alert.severity),
+        ));
+
+        html.push_str("<br/>");
+        // Threshold
+        html.push_str(&format!(
+            r#"🚷 <a href="{threshold}&{utm}">{threshold_link}</a>"#,
+            threshold = if self.public_links {
+                &alert.public_threshold_url
+            } else {
+                &alert.console_threshold_url
+            },
+            utm = self.utm_query(),
+            threshold_link = self.locale.threshold_link,
+        ));
+        html.push_str("</td>");
+    }
+
     fn html_benchmark_details(&self, html: &mut String, require_threshold: bool) {
-        html.push_str("<details><summary>Click to view all benchmark results</summary>");
+        html.push_str(&format!(
+            "<details><summary>{}</summary>",
+            self.locale.view_all_results
+        ));
         html.push_str("<br/>");
         for (iteration, benchmark_map) in self.benchmark_urls.0.iter().enumerate() {
-            self.html_benchmarks_table(html, iteration, benchmark_map, require_threshold);
+            if self.collapse_measures {
+                self.html_measure_details(html, iteration, benchmark_map, require_threshold);
+            } else {
+                self.html_benchmarks_table(html, iteration, benchmark_map, require_threshold);
+            }
         }
         html.push_str("</details>");
     }
 
+    // Each measure gets its own collapsible table,
+    // which is open by default if any of its benchmarks have an alert.
+    fn html_measure_details(
+        &self,
+        html: &mut String,
+        iteration: usize,
+        benchmark_map: &BenchmarkMap,
+        require_threshold: bool,
+    ) {
+        for measure in BoundaryLimits::for_iteration(benchmark_map, require_threshold).into_keys() {
+            let measure_benchmark_map = benchmark_map
+                .iter()
+                .filter_map(|(benchmark, measure_map)| {
+                    measure_map.get(&measure).map(|measure_data| {
+                        (
+                            benchmark.clone(),
+                            MeasureMap::from([(measure.clone(), measure_data.clone())]),
+                        )
+                    })
+                })
+                .collect::<BenchmarkMap>();
+            let alerts_len = self
+                .alert_urls
+                .0
+                .keys()
+                .filter(|(alert_iteration, _, alert_measure)| {
+                    *alert_iteration == iteration && *alert_measure == measure
+                })
+                .count();
+            html.push_str(&format!(
+                "<details{open}><summary>{name} ({benchmarks_len}){alerts}</summary>",
+                open = if alerts_len > 0 { " open" } else { "" },
+                name = measure.name,
+                benchmarks_len = measure_benchmark_map.len(),
+                alerts = if alerts_len > 0 {
+                    format!(" 🚨 {alerts_len}")
+                } else {
+                    String::new()
+                },
+            ));
+            self.html_benchmarks_table(html, iteration, &measure_benchmark_map, require_threshold);
+            html.push_str("</details>");
+        }
+    }
+
     fn html_benchmarks_table(
         &self,
         html: &mut String,
@@ -388,7 +829,7 @@ impl ReportComment {
         require_threshold: bool,
     ) {
         html.push_str("<thead><tr>");
-        html.push_str("<th>Benchmark</th>");
+        html.push_str(&format!("<th>{}</th>", self.locale.benchmark));
 
         let mbl = BoundaryLimits::for_iteration(benchmark_map, require_threshold);
         for (measure, boundary_limits) in mbl {
@@ -402,41 +843,77 @@ impl ReportComment {
                 )
             };
             let url = url.join(&path).unwrap_or(url);
-            html.push_str(&format!(
-                r#"<th><a href="{url}?{utm}">{measure}</a></th>"#,
+            let measure_link = format!(
+                r#"<a href="{url}?{utm}">{measure}</a>"#,
                 utm = self.utm_query(),
                 measure = &measure.name,
-            ));
-            Self::html_metric_boundary_header(html, &measure, boundary_limits);
+            );
+            // Without the view column, the measure is named in the benchmark result column instead
+            if self.columns.view {
+                html.push_str(&format!("<th>{measure_link}</th>"));
+                self.html_metric_boundary_header(html, &measure, None, boundary_limits);
+            } else {
+                self.html_metric_boundary_header(
+                    html,
+                    &measure,
+                    Some(&measure_link),
+                    boundary_limits,
+                );
+            }
+            self.html_historical_header(html, &measure);
         }
 
         html.push_str("</tr></thead>");
     }
 
     fn html_metric_boundary_header(
+        &self,
         html: &mut String,
         measure: &Measure,
+        label: Option<&str>,
         boundary_limits: BoundaryLimits,
     ) {
-        let units = &measure.units;
+        let label = label
+            .map(|label| format!("{label}<br/>"))
+            .unwrap_or_default();
+        let units = if self.columns.units {
+            format!("<br/>{}", measure.units)
+        } else {
+            String::new()
+        };
+        let CommentLocale {
+            benchmark_result,
+            result_delta,
+            lower_boundary,
+            upper_boundary,
+            limit_percent,
+            ..
+        } = &self.locale;
 
         // If there is a boundary limit then we will show the percentage difference
         if boundary_limits.lower || boundary_limits.upper {
+            let result_delta = if self.columns.percent_change {
+                format!("<br/>({result_delta})")
+            } else {
+                String::new()
+            };
             html.push_str(&format!(
-                "<th>Benchmark Result<br/>{units}<br/>(Result Δ%)</th>",
+                "<th>{label}{benchmark_result}{units}{result_delta}</th>",
             ));
+        } else if self.columns.units {
+            html.push_str(&format!("<th>{label}{}</th>", measure.units));
         } else {
-            html.push_str(&format!("<th>{units}</th>",));
+            html.push_str(&format!("<th>{label}{benchmark_result}</th>"));
         }
 
-        if boundary_limits.lower {
+        if boundary_limits.lower && self.columns.lower_boundary {
             html.push_str(&format!(
-                "<th>Lower Boundary<br/>{units}<br/>(Limit %)</th>"
+                "<th>{lower_boundary}{units}<br/>({limit_percent})</th>"
             ));
         }
-        if boundary_limits.upper {
+        if boundary_limits.upper && self.columns.upper_boundary {
             html.push_str(&format!(
-                "<th>Upper Boundary<br/>{units}<br/>(Limit %)</th>"
+                "<th>{upper_boundary}{units}<br/>({limit_percent})</th>"
             ));
         }
     }
@@ -505,6 +982,8 @@ impl ReportComment {
                         public_threshold_url,
                         console_threshold_url,
                         limit,
+                        severity: _,
+                        group: _,
                     } = alert;
 
                     (
@@ -519,16 +998,24 @@ impl ReportComment {
                     (None, None)
                 };
 
-                let utm = self.utm_query();
-                html.push_str("<td>");
-                html.push_str(&format!(r#"📈 <a href="{plot_url}&{utm}">view plot</a>"#));
-                let row = if let Some((alert_url, threshold_url)) = alert_url {
-                    format!(
-                        r#"<br/>🚨 <a href="{alert_url}?{utm}">view alert</a><br/>🚷 <a href="{threshold_url}&{utm}">view threshold</a>"#,
-                    )
-                } else if let Some(threshold) = threshold {
-                    let url = self.console_url.clone();
-                    let threshold_url = if self.public_links {
+                if self.columns.view {
+                    let utm = self.utm_query();
+                    let CommentLocale {
+                        view_plot,
+                        view_alert,
+                        view_threshold,
+                        no_threshold,
+                        ..
+                    } = &self.locale;
+                    html.push_str("<td>");
+                    html.push_str(&format!(r#"📈 <a href="{plot_url}&{utm}">{view_plot}</a>"#));
+                    let row = if let Some((alert_url, threshold_url)) = alert_url {
+                        format!(
+                            r#"<br/>🚨 <a href="{alert_url}?{utm}">{view_alert}</a><br/>🚷 <a href="{threshold_url}&{utm}">{view_threshold}</a>"#,
+                        )
+                    } else if let Some(threshold) = threshold {
+                        let url = self.console_url.clone();
+                        let threshold_url = if self.public_links {
                         let path = format!(
                             "/perf/{project}/thresholds/{threshold}?model={model}&{utm}",
                             project = self.project_slug,
@@ -548,14 +1035,16 @@ impl ReportComment {
                         url.join(&path)
                     }
                     .unwrap_or(url);
-                    format!(r#"<br/>🚷 <a href="{threshold_url}?{utm}">view threshold</a>"#)
-                } else {
-                    "<br/>⚠️ NO THRESHOLD".to_owned()
-                };
-                html.push_str(&row);
-                html.push_str("</td>");
+                        format!(r#"<br/>🚷 <a href="{threshold_url}?{utm}">{view_threshold}</a>"#)
+                    } else {
+                        format!("<br/>⚠️ {no_threshold}")
+                    };
+                    html.push_str(&row);
+                    html.push_str("</td>");
+                }
 
-                Self::html_metric_boundary_cells(html, *value, *boundary, limit, false);
+                self.html_metric_boundary_cells(html, *value, *boundary, limit, false);
+                self.html_historical_cell(html, benchmark, measure, *value);
             }
             html.push_str("</tr>");
         }
@@ -563,6 +1052,7 @@ impl ReportComment {
     }
 
     fn html_metric_boundary_cells(
+        &self,
         html: &mut String,
         value: f64,
         boundary: Option<Boundary>,
@@ -575,19 +1065,17 @@ impl ReportComment {
             ..
         }) = boundary
         {
-            let value_percent = if value.is_normal() && baseline.is_normal() {
-                ((value - baseline) / baseline) * 100.0
+            let value_delta = if self.columns.percent_change {
+                format!("<br/>({})", format_percent_change(value, baseline))
             } else {
-                0.0
+                String::new()
             };
-            let value_plus = if value_percent > 0.0 { "+" } else { "" };
 
             let bold = limit.is_some();
             html.push_str(&format!(
-                "<td>{}{}<br/>({value_plus}{}%){}</td>",
+                "<td>{}{}{value_delta}{}</td>",
                 if bold { "<b>" } else { "" },
                 format_number(value),
-                format_number(value_percent),
                 if bold { "</b>" } else { "" },
             ));
         } else {
@@ -597,63 +1085,134 @@ impl ReportComment {
         let Some(boundary) = boundary else {
             return;
         };
-        if let Some(lower_limit) = boundary.lower_limit {
-            let limit_percent = if value.is_normal() && lower_limit.is_normal() {
-                (lower_limit / value) * 100.0
-            } else {
-                0.0
-            };
-            let bold = matches!(limit, Some(BoundaryLimit::Lower));
-            html.push_str(&format!(
-                "<td>{}{}<br/>({}%){}</td>",
-                if bold { "<b>" } else { "" },
-                format_number(lower_limit),
-                format_number(limit_percent),
-                if bold { "</b>" } else { "" },
-            ));
-        } else if pad {
-            html.push_str("<td></td>");
+        if self.columns.lower_boundary {
+            if let Some(lower_limit) = boundary.lower_limit {
+                let limit_percent = if value.is_normal() && lower_limit.is_normal() {
+                    (lower_limit / value) * 100.0
+                } else {
+                    0.0
+                };
+                let bold = matches!(limit, Some(BoundaryLimit::Lower));
+                html.push_str(&format!(
+                    "<td>{}{}<br/>({}%){}</td>",
+                    if bold { "<b>" } else { "" },
+                    format_number(lower_limit),
+                    format_number(limit_percent),
+                    if bold { "</b>" } else { "" },
+                ));
+            } else if pad {
+                html.push_str("<td></td>");
+            }
         }
 
-        if let Some(upper_limit) = boundary.upper_limit {
-            let limit_percent = if value.is_normal() && upper_limit.is_normal() {
-                (value / upper_limit) * 100.0
-            } else {
-                0.0
-            };
-            let bold = matches!(limit, Some(BoundaryLimit::Upper));
-            html.push_str(&format!(
-                "<td>{}{}<br/>({}%){}</td>",
-                if bold { "<b>" } else { "" },
-                format_number(upper_limit),
-                format_number(limit_percent),
-                if bold { "</b>" } else { "" },
-            ));
-        } else if pad {
-            html.push_str("<td></td>");
+        if self.columns.upper_boundary {
+            if let Some(upper_limit) = boundary.upper_limit {
+                let limit_percent = if value.is_normal() && upper_limit.is_normal() {
+                    (value / upper_limit) * 100.0
+                } else {
+                    0.0
+                };
+                let bold = matches!(limit, Some(BoundaryLimit::Upper));
+                html.push_str(&format!(
+                    "<td>{}{}<br/>({}%){}</td>",
+                    if bold { "<b>" } else { "" },
+                    format_number(upper_limit),
+                    format_number(limit_percent),
+                    if bold { "</b>" } else { "" },
+                ));
+            } else if pad {
+                html.push_str("<td></td>");
+            }
         }
     }
 
-    fn html_footer(&self, html: &mut String) {
-        let url = self.console_url.clone();
-        let path = if self.public_links {
-            format!(
-                "/perf/{}/reports/{}",
-                self.project_slug, self.json_report.uuid
-            )
+    fn html_historical_header(&self, html: &mut String, measure: &Measure) {
+        let Some(historical) = &self.historical else {
+            return;
+        };
+        let units = if self.columns.units {
+            format!("<br/>{}", measure.units)
         } else {
-            format!(
-                "/console/projects/{}/reports/{}",
-                self.project_slug, self.json_report.uuid
-            )
+            String::new()
+        };
+        let result_delta = if self.columns.percent_change {
+            format!("<br/>({})", self.locale.result_delta)
+        } else {
+            String::new()
         };
-        let url = url.join(&path).unwrap_or(url);
         html.push_str(&format!(
-            r#"<a href="{url}?{utm}">🐰 View full continuous benchmarking report in Bencher</a>"#,
-            utm = self.utm_query()
+            "<th>{prior_result}<br/>{label}{units}{result_delta}</th>",
+            prior_result = self.locale.prior_result,
+            label = escape_html(historical.label()),
         ));
     }
 
+    // The percent change is from the prior result to the current result
+    fn html_historical_cell(
+        &self,
+        html: &mut String,
+        benchmark: &Benchmark,
+        measure: &Measure,
+        value: f64,
+    ) {
+        let Some(historical) = &self.historical else {
+            return;
+        };
+        let Some(prior) = historical.value(benchmark, measure) else {
+            html.push_str("<td></td>");
+            return;
+        };
+        let value_delta = if self.columns.percent_change {
+            format!("<br/>({})", format_percent_change(value, prior))
+        } else {
+            String::new()
+        };
+        html.push_str(&format!("<td>{}{value_delta}</td>", format_number(prior)));
+    }
+
+    fn html_footer(&self, html: &mut String) {
+        html.push_str(&format!(
+            r#"<a href="{url}?{utm}">🐰 {view_full_report}</a>"#,
+            url = self.report_url(),
+            utm = self.utm_query(),
+            view_full_report = self.locale.view_full_report,
+        ));
+        self.html_flaky_benchmarks(html);
+    }
+
+    fn html_flaky_benchmarks(&self, html: &mut String) {
+        let flaky_benchmarks = self.flaky_benchmarks();
+        if flaky_benchmarks.is_empty() {
+            return;
+        }
+        html.push_str(&format!(
+            "<br/><br/><small>⚠️ {} {}</small>",
+            self.locale.flaky_benchmarks,
+            flaky_benchmarks
+                .iter()
+                .map(|benchmark| format!("<code>{benchmark}</code>"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    pub fn flaky_benchmarks(&self) -> BTreeSet<&BenchmarkName> {
+        self.json_report
+            .results
+            .iter()
+            .flatten()
+            .filter_map(|result| {
+                // A benchmark is flaky if it is quarantined or if any of its measures are flaky
+                (result.benchmark.flaky.is_some()
+                    || result
+                        .measures
+                        .iter()
+                        .any(|measure| measure.flaky.is_some()))
+                .then_some(&result.benchmark.name)
+            })
+            .collect()
+    }
+
     fn html_bencher_tag(&self, html: &mut String, id: Option<&str>) {
         html.push_str(&self.bencher_tag(id));
     }
@@ -850,6 +1409,8 @@ impl BenchmarkUrl {
             measures: vec![measure],
             start_time: Some((self.start_time.into_inner() - DEFAULT_REPORT_HISTORY).into()),
             end_time: Some(self.end_time),
+            tags: Vec::new(),
+            unit: None,
         };
 
         let mut url = self.console_url.clone();
@@ -959,6 +1520,17 @@ impl BoundaryLimits {
     }
 }
 
+// An alert as exposed to a custom comment template
+#[derive(Serialize)]
+struct TemplateAlert<'a> {
+    iteration: usize,
+    benchmark: &'a BenchmarkName,
+    measure: &'a ResourceName,
+    severity: &'a str,
+    group: Option<AlertGroupUuid>,
+    url: &'a str,
+}
+
 pub struct AlertUrls(BTreeMap<(usize, Benchmark, Measure), AlertData>);
 
 #[derive(Clone)]
@@ -969,6 +1541,8 @@ pub struct AlertData {
     pub public_threshold_url: Url,
     pub console_threshold_url: Url,
     pub limit: BoundaryLimit,
+    pub severity: AlertSeverity,
+    pub group: Option<AlertGroupUuid>,
 }
 
 impl AlertUrls {
@@ -1019,6 +1593,8 @@ impl AlertUrls {
                 public_threshold_url,
                 console_threshold_url,
                 limit: alert.limit,
+                severity: alert.severity,
+                group: alert.group,
             };
             urls.insert((iteration, benchmark, measure), data);
         }
@@ -1094,3 +1670,36 @@ fn format_number(number: f64) -> String {
     }
     number_str.chars().rev().collect()
 }
+
+fn format_percent_change(value: f64, baseline: f64) -> String {
+    let value_percent = if value.is_normal() && baseline.is_normal() {
+        ((value - baseline) / baseline) * 100.0
+    } else {
+        0.0
+    };
+    let value_plus = if value_percent > 0.0 { "+" } else { "" };
+    format!("{value_plus}{}%", format_number(value_percent))
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn severity_emoji(severity: AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Minor => "🟡",
+        AlertSeverity::Major => "🟠",
+        AlertSeverity::Critical => "🔴",
+    }
+}
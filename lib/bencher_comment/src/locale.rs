@@ -0,0 +1,158 @@
+use bencher_json::project::alert::AlertSeverity;
+use serde::Deserialize;
+
+/// The labels used to generate a report comment.
+/// A locale bundle only needs to include the labels that it translates,
+/// any missing labels fall back to the default English labels.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CommentLocale {
+    pub view_results: String,
+    pub view_alerts: String,
+    pub iteration: String,
+    pub bencher_report: String,
+    pub branch: String,
+    pub testbed: String,
+    pub date: String,
+    pub notes: String,
+    pub links: String,
+    pub git: String,
+    pub dirty_working_tree: String,
+    pub benchmark: String,
+    pub measure: String,
+    pub units: String,
+    pub view: String,
+    pub benchmark_result: String,
+    pub result_delta: String,
+    pub lower_boundary: String,
+    pub upper_boundary: String,
+    pub limit_percent: String,
+    pub prior_result: String,
+    pub warning: String,
+    pub no_benchmarks: String,
+    pub measure_no_threshold: String,
+    pub measures_no_threshold: String,
+    pub create_threshold: String,
+    pub threshold_docs: String,
+    pub threshold_docs_link: String,
+    pub ci_only_thresholds: String,
+    pub ci_only_thresholds_link: String,
+    pub alert: String,
+    pub alerts: String,
+    pub limit_exceeded: String,
+    pub limits_exceeded: String,
+    pub correlated_regression: String,
+    pub correlated_regressions: String,
+    pub plot_link: String,
+    pub alert_link: String,
+    pub threshold_link: String,
+    pub view_plot: String,
+    pub view_alert: String,
+    pub view_threshold: String,
+    pub no_threshold: String,
+    pub view_all_results: String,
+    pub view_full_report: String,
+    pub flaky_benchmarks: String,
+    pub minor: String,
+    pub major: String,
+    pub critical: String,
+}
+
+impl Default for CommentLocale {
+    fn default() -> Self {
+        Self {
+            view_results: "View results:".into(),
+            view_alerts: "View alerts:".into(),
+            iteration: "Iteration".into(),
+            bencher_report: "Bencher Report".into(),
+            branch: "Branch".into(),
+            testbed: "Testbed".into(),
+            date: "Date".into(),
+            notes: "Notes".into(),
+            links: "Links".into(),
+            git: "Git".into(),
+            dirty_working_tree: "Run from a dirty working tree with uncommitted changes".into(),
+            benchmark: "Benchmark".into(),
+            measure: "Measure".into(),
+            units: "Units".into(),
+            view: "View".into(),
+            benchmark_result: "Benchmark Result".into(),
+            result_delta: "Result Δ%".into(),
+            lower_boundary: "Lower Boundary".into(),
+            upper_boundary: "Upper Boundary".into(),
+            limit_percent: "Limit %".into(),
+            prior_result: "Prior Result".into(),
+            warning: "WARNING".into(),
+            no_benchmarks: "No benchmarks found!".into(),
+            measure_no_threshold: "The following Measure does not have a Threshold. Without a Threshold, no Alerts will ever be generated!".into(),
+            measures_no_threshold: "The following Measures do not have a Threshold. Without a Threshold, no Alerts will ever be generated!".into(),
+            create_threshold: "Click here to create a new Threshold".into(),
+            threshold_docs: "For more information, see".into(),
+            threshold_docs_link: "the Threshold documentation".into(),
+            ci_only_thresholds: "To only post results if a Threshold exists, set".into(),
+            ci_only_thresholds_link: "the <code lang=\"rust\">--ci-only-thresholds</code> CLI flag".into(),
+            alert: "ALERT".into(),
+            alerts: "ALERTS".into(),
+            limit_exceeded: "Threshold Boundary Limit exceeded!".into(),
+            limits_exceeded: "Threshold Boundary Limits exceeded!".into(),
+            correlated_regression: "correlated regression".into(),
+            correlated_regressions: "correlated regressions".into(),
+            plot_link: "plot".into(),
+            alert_link: "alert".into(),
+            threshold_link: "threshold".into(),
+            view_plot: "view plot".into(),
+            view_alert: "view alert".into(),
+            view_threshold: "view threshold".into(),
+            no_threshold: "NO THRESHOLD".into(),
+            view_all_results: "Click to view all benchmark results".into(),
+            view_full_report: "View full continuous benchmarking report in Bencher".into(),
+            flaky_benchmarks: "The following Benchmarks are marked as flaky and are excluded from alert generation:".into(),
+            minor: "minor".into(),
+            major: "major".into(),
+            critical: "critical".into(),
+        }
+    }
+}
+
+impl CommentLocale {
+    pub fn severity(&self, severity: AlertSeverity) -> &str {
+        match severity {
+            AlertSeverity::Minor => &self.minor,
+            AlertSeverity::Major => &self.major,
+            AlertSeverity::Critical => &self.critical,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::project::alert::AlertSeverity;
+    use pretty_assertions::assert_eq;
+
+    use super::CommentLocale;
+
+    #[test]
+    fn test_locale_partial() {
+        let locale: CommentLocale =
+            serde_json::from_str(r#"{"branch": "Zweig", "critical": "kritisch"}"#).unwrap();
+        assert_eq!(locale.branch, "Zweig");
+        assert_eq!(locale.critical, "kritisch");
+        // Missing labels fall back to the default English labels
+        let default = CommentLocale::default();
+        assert_eq!(locale.testbed, default.testbed);
+        assert_eq!(locale.view_full_report, default.view_full_report);
+    }
+
+    #[test]
+    fn test_locale_severity() {
+        let locale: CommentLocale = serde_json::from_str(r#"{"major": "majeur"}"#).unwrap();
+        assert_eq!(locale.severity(AlertSeverity::Minor), "minor");
+        assert_eq!(locale.severity(AlertSeverity::Major), "majeur");
+        assert_eq!(locale.severity(AlertSeverity::Critical), "critical");
+    }
+
+    #[test]
+    fn test_locale_invalid() {
+        assert!(serde_json::from_str::<CommentLocale>(r#"{"branch": 1}"#).is_err());
+    }
+}
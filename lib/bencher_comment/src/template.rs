@@ -0,0 +1,77 @@
+use tera::{Context, Tera};
+
+const TEMPLATE_NAME: &str = "comment";
+
+#[derive(Debug, thiserror::Error)]
+pub enum TemplateError {
+    #[error("Failed to parse comment template: {0}")]
+    Parse(tera::Error),
+    #[error("Failed to render comment template: {0}")]
+    Render(tera::Error),
+}
+
+/// A user supplied Tera template for the HTML report comment.
+#[derive(Debug, Clone)]
+pub struct CommentTemplate(Tera);
+
+impl CommentTemplate {
+    pub fn new(template: &str) -> Result<Self, TemplateError> {
+        let mut tera = Tera::default();
+        // The template is always autoescaped, as the report contains user supplied values.
+        // The pre-rendered sections must be explicitly marked as `safe` by the template.
+        tera.autoescape_on(vec![TEMPLATE_NAME]);
+        tera.add_raw_template(TEMPLATE_NAME, template)
+            .map_err(TemplateError::Parse)?;
+        Ok(Self(tera))
+    }
+
+    pub(crate) fn render(&self, context: &Context) -> Result<String, TemplateError> {
+        self.0
+            .render(TEMPLATE_NAME, context)
+            .map_err(TemplateError::Render)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tera::Context;
+
+    use super::CommentTemplate;
+
+    fn context() -> Context {
+        let mut context = Context::new();
+        context.insert("header", "<h2>Bencher Report</h2>");
+        context.insert("branch", "<script>alert(1)</script>");
+        context
+    }
+
+    #[test]
+    fn test_template_autoescape() {
+        let template = CommentTemplate::new("{{ branch }}").unwrap();
+        assert_eq!(
+            template.render(&context()).unwrap(),
+            "&lt;script&gt;alert(1)&lt;&#x2F;script&gt;"
+        );
+    }
+
+    #[test]
+    fn test_template_safe_section() {
+        let template = CommentTemplate::new("{{ header | safe }}<p>{{ branch }}</p>").unwrap();
+        assert_eq!(
+            template.render(&context()).unwrap(),
+            "<h2>Bencher Report</h2><p>&lt;script&gt;alert(1)&lt;&#x2F;script&gt;</p>"
+        );
+    }
+
+    #[test]
+    fn test_template_parse_error() {
+        assert!(CommentTemplate::new("{{ header").is_err());
+    }
+
+    #[test]
+    fn test_template_render_error() {
+        let template = CommentTemplate::new("{{ missing }}").unwrap();
+        assert!(template.render(&context()).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use bencher_harness::{black_box, Bencher};
+
+fn fibonacci(n: u64) -> u64 {
+    (1..=n).fold((0, 1), |(a, b), _| (b, a + b)).0
+}
+
+fn bench_fibonacci(bencher: &mut Bencher) {
+    bencher.iter(|| fibonacci(black_box(32)));
+}
+
+fn bench_sort(bencher: &mut Bencher) {
+    let numbers = (0..1_000u32).rev().collect::<Vec<_>>();
+    bencher.iter(|| {
+        let mut numbers = numbers.clone();
+        numbers.sort_unstable();
+        numbers
+    });
+}
+
+bencher_harness::run_benchmarks!(bench_fibonacci, bench_sort);
@@ -0,0 +1,99 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::report::BmfMetric;
+
+const NANOS_PER_SEC: f64 = 1e9;
+
+/// Times the routine for a single benchmark
+#[derive(Debug)]
+pub struct Bencher {
+    mode: Mode,
+    // The time per iteration in nanoseconds for each sample
+    samples: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Mode {
+    /// Run the routine once to check that it works
+    Test,
+    Bench(Config),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Config {
+    pub warm_up: Duration,
+    pub measurement: Duration,
+    pub sample_size: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warm_up: Duration::from_millis(500),
+            measurement: Duration::from_secs(2),
+            sample_size: 50,
+        }
+    }
+}
+
+impl Bencher {
+    pub(crate) fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Time the routine.
+    /// The routine is first run repeatedly to warm up and estimate how long it takes.
+    /// It is then run in batches of iterations, with one sample per batch,
+    /// so that the measurement time is spread across all of the samples.
+    /// The return value of the routine is passed to `black_box`,
+    /// so the compiler does not optimize it away.
+    #[allow(clippy::iter_not_returning_iterator)]
+    pub fn iter<T, F>(&mut self, mut routine: F)
+    where
+        F: FnMut() -> T,
+    {
+        let config = match self.mode {
+            Mode::Test => {
+                black_box(routine());
+                return;
+            },
+            Mode::Bench(config) => config,
+        };
+
+        let mut warm_up_iterations = 0u32;
+        let start = Instant::now();
+        while start.elapsed() < config.warm_up || warm_up_iterations == 0 {
+            black_box(routine());
+            warm_up_iterations = warm_up_iterations.saturating_add(1);
+        }
+        let iteration_secs = start.elapsed().as_secs_f64() / f64::from(warm_up_iterations);
+
+        let sample_secs = config.measurement.as_secs_f64() / f64::from(config.sample_size);
+        // The number of iterations is clamped to the range of a `u32`
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let iterations = (sample_secs / iteration_secs)
+            .round()
+            .clamp(1.0, f64::from(u32::MAX)) as u32;
+
+        self.samples.clear();
+        for _ in 0..config.sample_size {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                black_box(routine());
+            }
+            let elapsed = start.elapsed();
+            self.samples
+                .push(elapsed.as_secs_f64() * NANOS_PER_SEC / f64::from(iterations));
+        }
+    }
+
+    pub(crate) fn latency(&self) -> Option<BmfMetric> {
+        BmfMetric::from_samples(&self.samples)
+    }
+}
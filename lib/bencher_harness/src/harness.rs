@@ -0,0 +1,219 @@
+use std::{
+    env, fmt,
+    io::{self, Write},
+    time::Duration,
+};
+
+use crate::{
+    bencher::{Bencher, Config, Mode},
+    report::{BmfResults, LATENCY},
+};
+
+/// Cargo passes this flag to bench targets when running `cargo bench`
+const BENCH_FLAG: &str = "--bench";
+
+/// Runs benchmarks and collects their results
+#[derive(Debug)]
+pub struct Harness {
+    mode: Mode,
+    filter: Option<String>,
+    results: BmfResults,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    /// Create a harness that runs all benchmarks
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Bench(Config::default()),
+            filter: None,
+            results: BmfResults::default(),
+        }
+    }
+
+    /// Create a harness from the command line arguments passed by Cargo.
+    /// When run with `cargo bench`, the benchmarks are timed.
+    /// Otherwise, such as with `cargo test --benches`,
+    /// each benchmark is only run once to check that it works.
+    /// The first argument that is not a flag filters the benchmarks by name.
+    pub fn from_args() -> Self {
+        let mut bench = false;
+        let mut filter = None;
+        for arg in env::args().skip(1) {
+            if arg == BENCH_FLAG {
+                bench = true;
+            } else if !arg.starts_with('-') && filter.is_none() {
+                filter = Some(arg);
+            }
+        }
+        Self {
+            mode: if bench {
+                Mode::Bench(Config::default())
+            } else {
+                Mode::Test
+            },
+            filter,
+            results: BmfResults::default(),
+        }
+    }
+
+    /// Only run benchmarks whose name contains the filter
+    #[must_use]
+    pub fn filter<S>(mut self, filter: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// The time to run each benchmark before it is measured (default: 500ms)
+    #[must_use]
+    pub fn warm_up_time(mut self, warm_up: Duration) -> Self {
+        self.config(|config| config.warm_up = warm_up);
+        self
+    }
+
+    /// The target time to measure each benchmark (default: 2s)
+    #[must_use]
+    pub fn measurement_time(mut self, measurement: Duration) -> Self {
+        self.config(|config| config.measurement = measurement);
+        self
+    }
+
+    /// The number of samples to take for each benchmark (default: 50)
+    #[must_use]
+    pub fn sample_size(mut self, sample_size: u32) -> Self {
+        self.config(|config| config.sample_size = sample_size.max(1));
+        self
+    }
+
+    fn config<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Config),
+    {
+        if let Mode::Bench(config) = &mut self.mode {
+            f(config);
+        }
+    }
+
+    /// Run a benchmark, unless it is filtered out
+    pub fn bench<F>(&mut self, name: &str, mut benchmark: F)
+    where
+        F: FnMut(&mut Bencher),
+    {
+        if self
+            .filter
+            .as_ref()
+            .is_some_and(|filter| !name.contains(filter.as_str()))
+        {
+            return;
+        }
+
+        let mut bencher = Bencher::new(self.mode);
+        benchmark(&mut bencher);
+        match self.mode {
+            Mode::Test => progress(format_args!("Testing {name} ... Success")),
+            Mode::Bench(_) => {
+                if let Some(latency) = bencher.latency() {
+                    progress(format_args!(
+                        "Benchmarking {name} ... {value:.2} ns/iter",
+                        value = latency.value
+                    ));
+                    self.results
+                        .insert(name.to_owned(), LATENCY.to_owned(), latency);
+                } else {
+                    progress(format_args!(
+                        "Benchmarking {name} ... Skipped: `Bencher::iter` was never called"
+                    ));
+                }
+            },
+        }
+    }
+
+    pub fn results(&self) -> &BmfResults {
+        &self.results
+    }
+
+    /// Print the results as BMF JSON to stdout.
+    /// Progress is printed to stderr, so stdout only has the results.
+    /// Nothing is printed when the benchmarks are only being tested.
+    pub fn finish(self) -> io::Result<()> {
+        if matches!(self.mode, Mode::Test) {
+            return Ok(());
+        }
+        let json = self.results.to_json()?;
+        writeln!(io::stdout().lock(), "{json}")
+    }
+}
+
+fn progress(args: fmt::Arguments<'_>) {
+    // Failing to print progress should not stop the benchmarks
+    drop(writeln!(io::stderr().lock(), "{args}"));
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use super::Harness;
+    use crate::{bencher::Mode, black_box, LATENCY};
+
+    fn harness() -> Harness {
+        Harness::new()
+            .warm_up_time(Duration::from_millis(1))
+            .measurement_time(Duration::from_millis(5))
+            .sample_size(5)
+    }
+
+    #[test]
+    fn test_harness_bench() {
+        let mut harness = harness();
+        harness.bench("bench_sum", |bencher| {
+            bencher.iter(|| (0..black_box(100u64)).sum::<u64>());
+        });
+        harness.bench("bench_skipped", |_bencher| {});
+
+        let latency = harness.results().get("bench_sum", LATENCY).unwrap();
+        assert!(latency.value > 0.0, "Latency should be positive");
+        assert!(
+            latency.lower_value <= Some(latency.value)
+                && Some(latency.value) <= latency.upper_value,
+            "Latency should be within its bounds"
+        );
+        assert_eq!(harness.results().get("bench_skipped", LATENCY), None);
+    }
+
+    #[test]
+    fn test_harness_filter() {
+        let mut harness = harness().filter("sum");
+        let mut ran = Vec::new();
+        for name in ["bench_sum", "bench_product"] {
+            harness.bench(name, |bencher| {
+                ran.push(name);
+                bencher.iter(|| black_box(1u64));
+            });
+        }
+        assert_eq!(ran, vec!["bench_sum"]);
+    }
+
+    #[test]
+    fn test_harness_test_mode() {
+        let mut harness = harness();
+        harness.mode = Mode::Test;
+        let mut iterations = 0;
+        harness.bench("bench_once", |bencher| bencher.iter(|| iterations += 1));
+        assert_eq!(iterations, 1);
+        assert!(
+            harness.results().is_empty(),
+            "Test mode should not have results"
+        );
+    }
+}
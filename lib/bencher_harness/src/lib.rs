@@ -0,0 +1,45 @@
+//! A lightweight in-process benchmark harness for Rust
+//! that prints [Bencher Metric Format (BMF) JSON](https://bencher.dev/docs/reference/bencher-metric-format/).
+//!
+//! Add a bench target with `harness = false` and list the benchmark functions:
+//!
+//! ```no_run
+//! use bencher_harness::{black_box, Bencher};
+//!
+//! fn fibonacci(n: u64) -> u64 {
+//!     (1..=n).fold((0, 1), |(a, b), _| (b, a + b)).0
+//! }
+//!
+//! fn bench_fibonacci(bencher: &mut Bencher) {
+//!     bencher.iter(|| fibonacci(black_box(32)));
+//! }
+//!
+//! bencher_harness::run_benchmarks!(bench_fibonacci);
+//! ```
+//!
+//! Then track the results with the `json` adapter:
+//! `bencher run --adapter json "cargo bench --bench my_bench"`
+
+mod bencher;
+mod harness;
+mod report;
+
+pub use std::hint::black_box;
+
+pub use bencher::Bencher;
+pub use harness::Harness;
+pub use report::{BmfMetric, BmfResults, LATENCY};
+
+/// Generate a `main` function that runs each of the benchmark functions
+/// and prints the results as BMF JSON.
+/// Each benchmark function takes a `&mut Bencher` and is named after the function.
+#[macro_export]
+macro_rules! run_benchmarks {
+    ($($benchmark:ident),+ $(,)?) => {
+        fn main() -> ::std::io::Result<()> {
+            let mut harness = $crate::Harness::from_args();
+            $(harness.bench(stringify!($benchmark), $benchmark);)+
+            harness.finish()
+        }
+    };
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// The measure for the time per iteration, in nanoseconds
+pub const LATENCY: &str = "latency";
+
+/// Bencher Metric Format (BMF) JSON results,
+/// keyed by benchmark name and then by measure
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BmfResults(BTreeMap<String, BTreeMap<String, BmfMetric>>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BmfMetric {
+    pub value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lower_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upper_value: Option<f64>,
+}
+
+impl BmfResults {
+    pub fn insert(&mut self, benchmark: String, measure: String, metric: BmfMetric) {
+        self.0.entry(benchmark).or_default().insert(measure, metric);
+    }
+
+    pub fn get(&self, benchmark: &str, measure: &str) -> Option<&BmfMetric> {
+        self.0.get(benchmark)?.get(measure)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl BmfMetric {
+    /// The mean of the samples, with the fastest and slowest samples as the lower and upper values
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let (first, rest) = samples.split_first()?;
+        let (sum, min, max) = rest
+            .iter()
+            .fold((*first, *first, *first), |(sum, min, max), &sample| {
+                (sum + sample, min.min(sample), max.max(sample))
+            });
+        let count = u32::try_from(samples.len()).ok()?;
+        Some(Self {
+            value: sum / f64::from(count),
+            lower_value: Some(min),
+            upper_value: Some(max),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_adapter::{adapters::json::AdapterJson, Adaptable, Settings};
+    use ordered_float::OrderedFloat;
+    use pretty_assertions::assert_eq;
+
+    use super::{BmfMetric, BmfResults, LATENCY};
+
+    #[test]
+    fn test_from_samples() {
+        assert_eq!(BmfMetric::from_samples(&[]), None);
+        assert_eq!(
+            BmfMetric::from_samples(&[2.0, 1.0, 6.0]),
+            Some(BmfMetric {
+                value: 3.0,
+                lower_value: Some(1.0),
+                upper_value: Some(6.0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_adapter() {
+        let mut results = BmfResults::default();
+        results.insert(
+            "bench_fibonacci".to_owned(),
+            LATENCY.to_owned(),
+            BmfMetric {
+                value: 3247.0,
+                lower_value: Some(1044.0),
+                upper_value: Some(5000.0),
+            },
+        );
+        results.insert(
+            "bench_sort".to_owned(),
+            LATENCY.to_owned(),
+            BmfMetric {
+                value: 42.0,
+                lower_value: None,
+                upper_value: None,
+            },
+        );
+
+        let json = results.to_json().unwrap();
+        let adapter_results = AdapterJson::parse(&json, Settings::default()).unwrap();
+        assert_eq!(adapter_results.inner.len(), 2);
+
+        let metric = adapter_results
+            .get("bench_fibonacci")
+            .unwrap()
+            .get(LATENCY)
+            .unwrap();
+        assert_eq!(metric.value, OrderedFloat::from(3247.0));
+        assert_eq!(metric.lower_value, Some(OrderedFloat::from(1044.0)));
+        assert_eq!(metric.upper_value, Some(OrderedFloat::from(5000.0)));
+
+        let metric = adapter_results
+            .get("bench_sort")
+            .unwrap()
+            .get(LATENCY)
+            .unwrap();
+        assert_eq!(metric.value, OrderedFloat::from(42.0));
+        assert_eq!(metric.lower_value, None);
+        assert_eq!(metric.upper_value, None);
+    }
+}
@@ -1,8 +1,8 @@
 pub use bencher_valid::{
-    BenchmarkName, Boundary, BranchName, CdfBoundary, DateTime, DateTimeMillis, Email, GitHash,
-    Index, IqrBoundary, Jwt, Model, ModelTest, NameId, NameIdKind, NonEmpty, PercentageBoundary,
-    ResourceId, ResourceIdKind, ResourceName, SampleSize, Sanitize, Secret, Slug, Url, UserName,
-    ValidError, Window,
+    is_public_ip, BenchmarkName, Boundary, BranchName, CdfBoundary, Cidr, DateTime, DateTimeMillis,
+    Decay, Email, GitHash, Index, IqrBoundary, Jwt, Model, ModelTest, NameId, NameIdKind, NonEmpty,
+    PercentageBoundary, ResourceId, ResourceIdKind, ResourceName, SampleSize, Sanitize, Secret,
+    Slug, TimeZone, Url, UserName, ValidError, Weekday, Window,
 };
 #[cfg(feature = "plus")]
 pub use bencher_valid::{
@@ -29,27 +29,62 @@ pub use organization::{plan::JsonPlan, usage::JsonUsage};
 pub use big_int::BigInt;
 pub use organization::{
     member::{JsonMember, JsonMembers},
+    perf::{JsonOrganizationPerf, JsonOrganizationPerfQuery},
+    token_policy::{JsonRevokedTokens, JsonTokenPolicy, JsonUpdateTokenPolicy},
     JsonNewOrganization, JsonOrganization, JsonOrganizations, OrganizationUuid,
 };
 pub use pagination::{JsonDirection, JsonPagination};
+#[allow(deprecated)]
+pub use project::measure::{JsonMetricKind, JsonMetricKinds, JsonNewMetricKind, MetricKindUuid};
 pub use project::{
-    alert::{AlertUuid, JsonAlert, JsonAlerts},
+    alert::{
+        AlertGroupUuid, AlertUpdateUuid, AlertUuid, JsonAlert, JsonAlertStats, JsonAlertUpdate,
+        JsonAlerts, JsonBulkUpdateAlerts,
+    },
+    annotation::{AnnotationUuid, JsonAnnotation, JsonAnnotations, JsonNewAnnotation},
     benchmark::{BenchmarkUuid, JsonBenchmark, JsonBenchmarks},
     boundary::{BoundaryUuid, JsonBoundaries, JsonBoundary},
-    branch::{BranchUuid, JsonBranch, JsonBranches, JsonNewBranch, JsonNewStartPoint},
+    branch::{
+        BranchUuid, JsonBranch, JsonBranchLineage, JsonBranchTags, JsonBranchVersions,
+        JsonBranches, JsonNewBranch, JsonNewBranchTags, JsonNewStartPoint,
+    },
+    expectation::{ExpectationUuid, JsonExpectation, JsonExpectations, JsonNewExpectation},
+    github::{JsonGitHubDelivery, JsonGitHubWebhook},
     head::{HeadUuid, JsonHead, JsonStartPoint, VersionUuid},
-    measure::{JsonMeasure, JsonMeasures, JsonNewMeasure, MeasureUuid},
+    measure::{
+        alias::{JsonMeasureAlias, JsonMeasureAliases, JsonNewMeasureAlias, MeasureAliasUuid},
+        JsonMeasure, JsonMeasures, JsonNewMeasure, MeasureUuid,
+    },
+    member::{JsonProjectMember, JsonProjectMembers},
     metric::{
-        JsonMetric, JsonMetricsMap, JsonNewMetric, JsonOneMetric, JsonResultsMap, MeasureNameId,
-        MetricUuid,
+        JsonMetric, JsonMetricDownsample, JsonMetricsMap, JsonNewMetric, JsonOneMetric,
+        JsonResultsMap, MeasureNameId, MetricUuid, MetricsBuilder, MetricsBuilderError,
     },
     model::{JsonModel, ModelUuid},
-    perf::{JsonPerf, JsonPerfQuery, ReportBenchmarkUuid},
+    perf::{JsonNewPerfEmbed, JsonPerf, JsonPerfEmbed, JsonPerfQuery, ReportBenchmarkUuid},
+    perf_fingerprint::{JsonPerfFingerprint, JsonPerfFingerprints, JsonPerfFingerprintsQuery},
+    perf_group::{JsonPerfGroup, JsonPerfGroups, JsonPerfGroupsQuery},
     plot::{JsonNewPlot, JsonPlot, JsonPlots, PlotUuid},
-    report::{JsonNewReport, JsonReport, JsonReports, ReportUuid},
-    testbed::{JsonNewTestbed, JsonTestbed, JsonTestbeds, TestbedUuid},
-    threshold::{JsonNewThreshold, JsonThreshold, JsonThresholds, ThresholdUuid},
-    JsonNewProject, JsonProject, JsonProjects, ProjectUuid,
+    report::{
+        JsonNewReport, JsonNewReportShard, JsonNewReports, JsonNewRun, JsonReport, JsonReportShard,
+        JsonReports, JsonRun, ReportUuid,
+    },
+    report_hook::{JsonNewReportHook, JsonReportHook},
+    rule::{JsonNewRule, JsonRule, JsonRules, RuleUuid},
+    saved_query::{
+        JsonNewSavedQuery, JsonSavedQueries, JsonSavedQuery, JsonSavedQueryPayload,
+        JsonUpdateSavedQuery, SavedQueryUuid,
+    },
+    testbed::{JsonNewTestbed, JsonTestbed, JsonTestbedSpec, JsonTestbeds, TestbedUuid},
+    threshold::{
+        JsonNewRecompute, JsonNewThreshold, JsonRecompute, JsonThreshold, JsonThresholds,
+        ThresholdUuid,
+    },
+    webhook::{
+        JsonNewWebhook, JsonWebhook, JsonWebhookDeliveries, JsonWebhookDelivery,
+        JsonWebhookPayload, WebhookDeliveryUuid,
+    },
+    JsonNewProject, JsonProject, JsonProjectClaim, JsonProjectTransfer, JsonProjects, ProjectUuid,
 };
 #[cfg(feature = "plus")]
 pub use system::{
@@ -62,12 +97,19 @@ pub use system::{
     auth::{JsonAccept, JsonAuthAck, JsonAuthUser, JsonConfirm, JsonLogin, JsonSignup},
     backup::{JsonBackup, JsonBackupCreated},
     config::JsonConfig,
+    db::{JsonDbCheck, JsonDbOrphans},
+    downsample::{JsonDownsampled, JsonNewDownsample},
+    error::{ApiErrorCode, ApiErrorCodeError, ApiErrorKind, BencherResource},
+    rbac::{JsonRbac, JsonRbacAccess, JsonRbacEndpoint},
     restart::JsonRestart,
+    secret::{JsonRotateSecret, JsonSecretRotated},
     spec::JsonSpec,
-    version::JsonApiVersion,
+    telemetry::{JsonServerTelemetry, JsonTelemetryStats},
+    version::{ApiVersion, ApiVersionError, JsonApiVersion},
 };
 pub use user::{
-    token::{JsonNewToken, JsonToken, JsonTokens, TokenUuid},
+    prefs::{DateFormat, JsonNewUserPrefs, JsonUserPrefs, UserPrefsError},
+    token::{JsonNewToken, JsonRevokeToken, JsonToken, JsonTokens, TokenUuid},
     JsonPubUser, JsonUpdateUser, JsonUser, JsonUsers, UserUuid,
 };
 
@@ -63,8 +63,8 @@ pub struct JsonUpdateMember {
 #[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
 #[serde(rename_all = "snake_case")]
 pub enum OrganizationRole {
-    // TODO Team Management
-    // Member,
+    /// The organization member role.
+    Member,
     /// The organization leader role.
     Leader,
 }
@@ -74,8 +74,7 @@ impl FromStr for OrganizationRole {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            // TODO Team Management
-            // MEMBER_ROLE => Ok(Self::Member),
+            MEMBER_ROLE => Ok(Self::Member),
             LEADER_ROLE => Ok(Self::Leader),
             _ => Err(s.into()),
         }
@@ -88,8 +87,7 @@ impl fmt::Display for OrganizationRole {
             f,
             "{}",
             match self {
-                // TODO Team Management
-                // Self::Member => MEMBER_ROLE,
+                Self::Member => MEMBER_ROLE,
                 Self::Leader => LEADER_ROLE,
             }
         )
@@ -98,7 +96,7 @@ impl fmt::Display for OrganizationRole {
 
 #[cfg(feature = "db")]
 mod organization_role {
-    use super::{OrganizationRole, LEADER_ROLE};
+    use super::{OrganizationRole, LEADER_ROLE, MEMBER_ROLE};
 
     #[derive(Debug, thiserror::Error)]
     pub enum OrganizationRoleError {
@@ -117,6 +115,7 @@ mod organization_role {
             out: &mut diesel::serialize::Output<'b, '_, DB>,
         ) -> diesel::serialize::Result {
             match self {
+                Self::Member => out.set_value(MEMBER_ROLE.to_owned()),
                 Self::Leader => out.set_value(LEADER_ROLE.to_owned()),
             }
             Ok(diesel::serialize::IsNull::No)
@@ -131,6 +130,7 @@ mod organization_role {
         fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
             let role = String::from_sql(bytes)?;
             match role.as_str() {
+                MEMBER_ROLE => Ok(Self::Member),
                 LEADER_ROLE => Ok(Self::Leader),
                 _ => Err(Box::new(OrganizationRoleError::Invalid(role))),
             }
@@ -10,7 +10,10 @@ use serde::{
 };
 
 pub mod member;
+pub mod perf;
 pub mod plan;
+pub mod scim;
+pub mod token_policy;
 pub mod usage;
 
 crate::typed_uuid::typed_uuid!(OrganizationUuid);
@@ -0,0 +1,135 @@
+use bencher_valid::{DateTime, DateTimeMillis, NameId, ResourceId};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    project::perf::JsonPerfMetrics,
+    urlencoded::{from_urlencoded_list, UrlEncodedError},
+    JsonOrganization, JsonProject,
+};
+
+/// `JsonOrganizationPerfQueryParams` is the actual query parameters accepted by the server.
+/// It should always be converted into `JsonOrganizationPerfQuery` for full type level validation.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonOrganizationPerfQueryParams {
+    /// An optional comma separated list of project slugs or UUIDs to query.
+    /// If not provided, every project in the organization that the user is allowed to view is queried.
+    pub projects: Option<String>,
+    /// The name, slug, or UUID for the branch in each project.
+    /// The current head of the branch is used.
+    pub branch: NameId,
+    /// The name, slug, or UUID for the testbed in each project.
+    pub testbed: NameId,
+    /// The slug or UUID for the benchmark in each project.
+    /// If not provided, all of the benchmarks in each project are queried.
+    pub benchmark: Option<ResourceId>,
+    /// The name, slug, or UUID for the measure in each project.
+    pub measure: NameId,
+    /// Search for metrics after the given date time in milliseconds.
+    pub start_time: Option<DateTimeMillis>,
+    /// Search for metrics before the given date time in milliseconds.
+    pub end_time: Option<DateTimeMillis>,
+}
+
+/// `JsonOrganizationPerfQuery` is the full, strongly typed version of `JsonOrganizationPerfQueryParams`.
+#[derive(Debug, Clone)]
+pub struct JsonOrganizationPerfQuery {
+    pub projects: Vec<ResourceId>,
+    pub branch: NameId,
+    pub testbed: NameId,
+    pub benchmark: Option<ResourceId>,
+    pub measure: NameId,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+}
+
+impl TryFrom<JsonOrganizationPerfQueryParams> for JsonOrganizationPerfQuery {
+    type Error = UrlEncodedError;
+
+    fn try_from(query_params: JsonOrganizationPerfQueryParams) -> Result<Self, Self::Error> {
+        let JsonOrganizationPerfQueryParams {
+            projects,
+            branch,
+            testbed,
+            benchmark,
+            measure,
+            start_time,
+            end_time,
+        } = query_params;
+
+        let projects = if let Some(projects) = projects.as_deref() {
+            from_urlencoded_list(projects)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            projects,
+            branch,
+            testbed,
+            benchmark,
+            measure,
+            start_time: start_time.map(Into::into),
+            end_time: end_time.map(Into::into),
+        })
+    }
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonOrganizationPerf {
+    pub organization: JsonOrganization,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    /// The results for each project, as a separate series per project.
+    pub projects: Vec<JsonOrganizationPerfProject>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonOrganizationPerfProject {
+    pub project: JsonProject,
+    /// The results will be empty if the project does not have the branch, testbed, benchmark, or measure.
+    pub results: Vec<JsonPerfMetrics>,
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{JsonOrganizationPerfQuery, JsonOrganizationPerfQueryParams};
+
+    fn query_params(projects: Option<&str>) -> JsonOrganizationPerfQueryParams {
+        JsonOrganizationPerfQueryParams {
+            projects: projects.map(Into::into),
+            branch: "main".parse().unwrap(),
+            testbed: "localhost".parse().unwrap(),
+            benchmark: None,
+            measure: "file-size".parse().unwrap(),
+            start_time: None,
+            end_time: None,
+        }
+    }
+
+    #[test]
+    fn test_organization_perf_query_projects() {
+        let query = JsonOrganizationPerfQuery::try_from(query_params(None)).unwrap();
+        assert!(query.projects.is_empty());
+
+        let query = JsonOrganizationPerfQuery::try_from(query_params(Some("api,web-app"))).unwrap();
+        assert_eq!(
+            query
+                .projects
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["api", "web-app"]
+        );
+
+        assert!(JsonOrganizationPerfQuery::try_from(query_params(Some("api,,web-app"))).is_err());
+    }
+}
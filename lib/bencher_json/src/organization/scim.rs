@@ -0,0 +1,477 @@
+#![cfg(feature = "plus")]
+
+use std::{fmt, str::FromStr};
+
+use bencher_valid::{DateTime, Email, UserName};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::UserUuid;
+
+use super::member::OrganizationRole;
+
+// https://datatracker.ietf.org/doc/html/rfc7643
+pub const SCIM_USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub const SCIM_GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+// https://datatracker.ietf.org/doc/html/rfc7644
+pub const SCIM_LIST_RESPONSE_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+pub const SCIM_PATCH_OP_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+
+pub const SCIM_USER_RESOURCE: &str = "User";
+pub const SCIM_GROUP_RESOURCE: &str = "Group";
+
+pub const SCIM_MEMBERS_PATH: &str = "members";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonScimNewUser {
+    /// The SCIM schemas for the user.
+    pub schemas: Option<Vec<String>>,
+    /// The unique identifier for the user in the identity provider.
+    /// If this is not an email address, then the primary email address is used.
+    pub user_name: String,
+    /// The components of the user's name.
+    pub name: Option<JsonScimName>,
+    /// The name of the user, suitable for display.
+    /// If not provided, the user name is derived from `name`.
+    pub display_name: Option<String>,
+    /// The email addresses for the user.
+    pub emails: Option<Vec<JsonScimEmail>>,
+    /// Whether the user is an active member of the organization.
+    /// If `false`, then the user is removed from the organization.
+    pub active: Option<bool>,
+}
+
+impl JsonScimNewUser {
+    /// The email for the user.
+    /// This is the primary email, the first email, or the SCIM `userName`, in that order.
+    pub fn email(&self) -> Option<Email> {
+        let emails = self.emails.as_deref().unwrap_or_default();
+        emails
+            .iter()
+            .find(|email| email.primary.unwrap_or_default())
+            .or_else(|| emails.first())
+            .map(|email| email.value.clone())
+            .or_else(|| self.user_name.parse().ok())
+    }
+
+    /// The Bencher user name for the user.
+    /// This is the SCIM `displayName`, the formatted name,
+    /// the given and family names, or the email address local part, in that order.
+    pub fn user_name(&self, email: &Email) -> Option<UserName> {
+        self.display_name
+            .as_deref()
+            .and_then(|name| name.parse().ok())
+            .or_else(|| self.name.as_ref().and_then(JsonScimName::user_name))
+            .or_else(|| {
+                email
+                    .as_ref()
+                    .split_once('@')
+                    .and_then(|(local, _)| local.parse().ok())
+            })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonScimName {
+    /// The full name of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<String>,
+    /// The given name of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    /// The family name of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+}
+
+impl JsonScimName {
+    fn user_name(&self) -> Option<UserName> {
+        if let Some(name) = self.formatted.as_deref().and_then(|name| name.parse().ok()) {
+            return Some(name);
+        }
+        let name = [self.given_name.as_deref(), self.family_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        name.parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonScimEmail {
+    /// The email address.
+    pub value: Email,
+    /// Whether this is the primary email address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary: Option<bool>,
+    /// The type of email address, such as `work`.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonScimUser {
+    /// The SCIM schemas for the user.
+    pub schemas: Vec<String>,
+    /// The user UUID.
+    pub id: UserUuid,
+    /// The user email.
+    pub user_name: Email,
+    /// The user name.
+    pub display_name: UserName,
+    /// The components of the user's name.
+    pub name: JsonScimName,
+    /// The email addresses for the user.
+    pub emails: Vec<JsonScimEmail>,
+    /// Whether the user is active.
+    /// Locked users are never active.
+    pub active: bool,
+    /// The organization roles for the user.
+    pub groups: Vec<JsonScimMember>,
+    /// The SCIM resource metadata.
+    pub meta: JsonScimMeta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonScimGroup {
+    /// The SCIM schemas for the group.
+    pub schemas: Vec<String>,
+    /// The organization role for the group.
+    pub id: OrganizationRole,
+    /// The name of the group.
+    pub display_name: String,
+    /// The organization members with the role.
+    pub members: Vec<JsonScimMember>,
+    /// The SCIM resource metadata.
+    pub meta: JsonScimMeta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonScimMember {
+    /// The user UUID or organization role.
+    pub value: String,
+    /// The user name or organization role name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonScimMeta {
+    /// The SCIM resource type.
+    pub resource_type: String,
+    /// The date time the resource was created.
+    pub created: DateTime,
+    /// The date time the resource was last modified.
+    pub last_modified: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonScimUsers {
+    /// The SCIM schemas for the list response.
+    pub schemas: Vec<String>,
+    /// The total number of results.
+    pub total_results: u32,
+    /// The 1-based index of the first result.
+    pub start_index: u32,
+    /// The number of results returned.
+    pub items_per_page: u32,
+    /// The users.
+    #[serde(rename = "Resources")]
+    pub resources: Vec<JsonScimUser>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonScimGroups {
+    /// The SCIM schemas for the list response.
+    pub schemas: Vec<String>,
+    /// The total number of results.
+    pub total_results: u32,
+    /// The 1-based index of the first result.
+    pub start_index: u32,
+    /// The number of results returned.
+    pub items_per_page: u32,
+    /// The groups.
+    #[serde(rename = "Resources")]
+    pub resources: Vec<JsonScimGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonScimPatch {
+    /// The SCIM schemas for the patch request.
+    pub schemas: Option<Vec<String>>,
+    /// The patch operations to apply in order.
+    #[serde(rename = "Operations")]
+    pub operations: Vec<JsonScimPatchOperation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonScimPatchOperation {
+    /// The patch operation.
+    pub op: JsonScimPatchOp,
+    /// The attribute path for the operation.
+    pub path: Option<String>,
+    /// The value for the operation.
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JsonScimPatchOp {
+    // Some identity providers capitalize the operation
+    #[serde(alias = "Add")]
+    Add,
+    #[serde(alias = "Remove")]
+    Remove,
+    #[serde(alias = "Replace")]
+    Replace,
+}
+
+/// A SCIM filter expression.
+/// Only the `eq` operator on a single attribute is supported,
+/// which is what identity providers use to check for existing resources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScimFilter {
+    pub attribute: String,
+    pub value: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScimFilterError {
+    #[error("Unsupported SCIM filter (only `<attribute> eq \"<value>\"` is supported): {0}")]
+    Unsupported(String),
+    #[error("Unsupported SCIM group member filter attribute: {0}")]
+    MemberAttribute(ScimFilter),
+    #[error("Invalid SCIM group member ({0}): {1}")]
+    Member(String, uuid::Error),
+}
+
+impl FromStr for ScimFilter {
+    type Err = ScimFilterError;
+
+    fn from_str(filter: &str) -> Result<Self, Self::Err> {
+        let unsupported = || ScimFilterError::Unsupported(filter.to_owned());
+        let mut parts = filter.trim().splitn(3, char::is_whitespace);
+        let (Some(attribute), Some(operator), Some(value)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(unsupported());
+        };
+        if !operator.eq_ignore_ascii_case("eq") {
+            return Err(unsupported());
+        }
+        let value = value
+            .trim()
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            // A quote inside of the value means that there are multiple expressions,
+            // ie `userName eq "muriel" and active eq "true"`
+            .filter(|value| !value.contains('"'))
+            .ok_or_else(unsupported)?;
+        Ok(Self {
+            attribute: attribute.to_owned(),
+            value: value.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for ScimFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} eq \"{}\"", self.attribute, self.value)
+    }
+}
+
+impl ScimFilter {
+    pub fn is_attribute(&self, attribute: &str) -> bool {
+        self.attribute.eq_ignore_ascii_case(attribute)
+    }
+
+    /// Parse a SCIM group patch path of either `members` or `members[value eq "<uuid>"]`.
+    /// Returns `None` if the path is not for `members`,
+    /// and `Some(None)` if the path is for all of the members.
+    #[allow(clippy::option_option)]
+    pub fn members_path(path: &str) -> Result<Option<Option<UserUuid>>, ScimFilterError> {
+        if path.eq_ignore_ascii_case(SCIM_MEMBERS_PATH) {
+            return Ok(Some(None));
+        }
+        let Some(filter) = path
+            .get(..SCIM_MEMBERS_PATH.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(SCIM_MEMBERS_PATH))
+            .and_then(|_| path.get(SCIM_MEMBERS_PATH.len()..))
+            .and_then(|filter| filter.strip_prefix('['))
+            .and_then(|filter| filter.strip_suffix(']'))
+        else {
+            return Ok(None);
+        };
+        let filter: Self = filter.parse()?;
+        if !filter.is_attribute("value") {
+            return Err(ScimFilterError::MemberAttribute(filter));
+        }
+        let user = filter
+            .value
+            .parse()
+            .map_err(|e| ScimFilterError::Member(filter.value, e))?;
+        Ok(Some(Some(user)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{JsonScimEmail, JsonScimName, JsonScimNewUser, ScimFilter, ScimFilterError};
+
+    #[test]
+    fn test_scim_filter() {
+        let filter: ScimFilter = "userName eq \"muriel@example.com\"".parse().unwrap();
+        assert_eq!(
+            filter,
+            ScimFilter {
+                attribute: "userName".into(),
+                value: "muriel@example.com".into(),
+            }
+        );
+        assert!(filter.is_attribute("username"));
+
+        let filter: ScimFilter = "displayName EQ \"Courage the Cowardly Dog\""
+            .parse()
+            .unwrap();
+        assert_eq!(filter.attribute, "displayName");
+        assert_eq!(filter.value, "Courage the Cowardly Dog");
+
+        assert!("userName".parse::<ScimFilter>().is_err());
+        assert!("userName sw \"muriel\"".parse::<ScimFilter>().is_err());
+        assert!("userName eq muriel".parse::<ScimFilter>().is_err());
+    }
+
+    #[test]
+    fn test_scim_filter_value() {
+        let filter: ScimFilter = "  userName eq \"\"  ".parse().unwrap();
+        assert_eq!(filter.attribute, "userName");
+        assert_eq!(filter.value, "");
+
+        let filter: ScimFilter = "displayName eq \"Eustace  Bagge \"".parse().unwrap();
+        assert_eq!(filter.value, "Eustace  Bagge ");
+        assert_eq!(filter.to_string(), "displayName eq \"Eustace  Bagge \"");
+        assert_eq!(filter.to_string().parse::<ScimFilter>().unwrap(), filter);
+
+        assert!("".parse::<ScimFilter>().is_err());
+        assert!("userName eq".parse::<ScimFilter>().is_err());
+        assert!("userName  eq \"muriel\"".parse::<ScimFilter>().is_err());
+        assert!("userName eq \"muriel".parse::<ScimFilter>().is_err());
+        assert!("userName eq muriel\"".parse::<ScimFilter>().is_err());
+        assert!("userName eq \"muriel\" and active eq \"true\""
+            .parse::<ScimFilter>()
+            .is_err());
+        assert!("userName eq \"muriel\" or userName eq \"courage\""
+            .parse::<ScimFilter>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_scim_members_path() {
+        const UUID: &str = "a9ee0bc9-b1a6-4b2c-8ff8-3a9be3e5f6b1";
+
+        assert_eq!(ScimFilter::members_path("members").unwrap(), Some(None));
+        assert_eq!(ScimFilter::members_path("Members").unwrap(), Some(None));
+        assert_eq!(
+            ScimFilter::members_path(&format!("members[value eq \"{UUID}\"]")).unwrap(),
+            Some(Some(UUID.parse().unwrap()))
+        );
+        assert_eq!(
+            ScimFilter::members_path(&format!("MEMBERS[Value EQ \"{UUID}\"]")).unwrap(),
+            Some(Some(UUID.parse().unwrap()))
+        );
+
+        assert_eq!(ScimFilter::members_path("displayName").unwrap(), None);
+        assert_eq!(ScimFilter::members_path("member").unwrap(), None);
+        assert_eq!(ScimFilter::members_path("membersOf").unwrap(), None);
+        assert_eq!(
+            ScimFilter::members_path(&format!("members[value eq \"{UUID}\"")).unwrap(),
+            None
+        );
+
+        assert!(matches!(
+            ScimFilter::members_path(&format!("members[display eq \"{UUID}\"]")),
+            Err(ScimFilterError::MemberAttribute(_))
+        ));
+        assert!(matches!(
+            ScimFilter::members_path("members[value eq \"courage\"]"),
+            Err(ScimFilterError::Member(_, _))
+        ));
+        assert!(matches!(
+            ScimFilter::members_path("members[value sw \"courage\"]"),
+            Err(ScimFilterError::Unsupported(_))
+        ));
+        assert!(ScimFilter::members_path("members[]").is_err());
+    }
+
+    #[test]
+    fn test_scim_new_user() {
+        let mut user = JsonScimNewUser {
+            schemas: None,
+            user_name: "muriel".into(),
+            name: Some(JsonScimName {
+                formatted: None,
+                given_name: Some("Muriel".into()),
+                family_name: Some("Bagge".into()),
+            }),
+            display_name: None,
+            emails: Some(vec![
+                JsonScimEmail {
+                    value: "eustace@example.com".parse().unwrap(),
+                    primary: None,
+                    kind: None,
+                },
+                JsonScimEmail {
+                    value: "muriel@example.com".parse().unwrap(),
+                    primary: Some(true),
+                    kind: Some("work".into()),
+                },
+            ]),
+            active: None,
+        };
+        let email = user.email().unwrap();
+        assert_eq!(email.as_ref(), "muriel@example.com");
+        assert_eq!(user.user_name(&email).unwrap().as_ref(), "Muriel Bagge");
+        assert!(user.is_active());
+
+        user.display_name = Some("Mrs. Muriel Bagge".into());
+        assert_eq!(
+            user.user_name(&email).unwrap().as_ref(),
+            "Mrs. Muriel Bagge"
+        );
+
+        user.display_name = None;
+        user.name = None;
+        assert_eq!(user.user_name(&email).unwrap().as_ref(), "muriel");
+
+        user.user_name = "courage@example.com".into();
+        user.emails = None;
+        assert_eq!(user.email().unwrap().as_ref(), "courage@example.com");
+    }
+}
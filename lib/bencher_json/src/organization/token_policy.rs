@@ -0,0 +1,58 @@
+use bencher_valid::DateTime;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::OrganizationUuid;
+
+/// The policy for API tokens created by members of an organization.
+/// If a user is a member of multiple organizations,
+/// then their new tokens must satisfy the policies for all of them.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonTokenPolicy {
+    pub organization: OrganizationUuid,
+    /// The maximum time-to-live (TTL) in seconds for new tokens.
+    /// New tokens without a TTL are given this TTL.
+    pub max_ttl: Option<u32>,
+    /// New tokens must be created with a TTL.
+    pub require_ttl: bool,
+    /// New tokens may not be created with a TTL longer than one year,
+    /// which in practice never expires.
+    pub forbid_non_expiring: bool,
+    /// Only organization leaders may create new tokens.
+    pub admin_only: bool,
+    /// The time the policy was last modified, if it has ever been set.
+    pub modified: Option<DateTime>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonUpdateTokenPolicy {
+    /// The maximum time-to-live (TTL) in seconds for new tokens.
+    /// If not provided, there is no maximum.
+    pub max_ttl: Option<u32>,
+    /// New tokens must be created with a TTL.
+    /// If not provided, defaults to `false`.
+    pub require_ttl: Option<bool>,
+    /// New tokens may not be created with a TTL longer than one year,
+    /// which in practice never expires.
+    /// If not provided, defaults to `false`.
+    pub forbid_non_expiring: Option<bool>,
+    /// Only organization leaders may create new tokens.
+    /// If not provided, defaults to `false`.
+    pub admin_only: Option<bool>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRevokedTokens {
+    pub organization: OrganizationUuid,
+    /// The number of tokens that were revoked.
+    pub tokens: u32,
+    /// The time at which the tokens were or will be revoked.
+    pub revoked: DateTime,
+}
@@ -1,12 +1,21 @@
+use std::str::FromStr;
+
+use chrono::Days;
+use derive_more::Display;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
-use crate::{DateTime, JsonBenchmark, JsonBoundary, JsonMetric, JsonThreshold};
+use crate::{
+    BranchName, DateTime, JsonBenchmark, JsonBoundary, JsonMetric, JsonThreshold, NameId, NonEmpty,
+    ProjectUuid, ResourceName, TimeZone, UserUuid, Weekday,
+};
 
 use super::{boundary::BoundaryLimit, report::Iteration, report::ReportUuid};
 
 crate::typed_uuid::typed_uuid!(AlertUuid);
+crate::typed_uuid::typed_uuid!(AlertUpdateUuid);
+crate::typed_uuid::typed_uuid!(AlertGroupUuid);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -26,7 +35,11 @@ pub struct JsonAlert {
     pub threshold: JsonThreshold,
     pub boundary: JsonBoundary,
     pub limit: BoundaryLimit,
+    pub severity: AlertSeverity,
     pub status: AlertStatus,
+    /// The correlated alert group, if multiple measures for the benchmark regressed together.
+    /// All of the alerts in a group are for the same report iteration and benchmark.
+    pub group: Option<AlertGroupUuid>,
     pub created: DateTime,
     pub modified: DateTime,
 }
@@ -48,7 +61,7 @@ pub enum AlertStatus {
     Active = ACTIVE_INT,
     /// The alert has been dismissed by a user.
     Dismissed = DISMISSED_INT,
-    /// The alert has been silenced by the system.
+    /// The alert has been silenced by the system or in bulk by a user.
     Silenced = SILENCED_INT,
 }
 
@@ -57,6 +70,7 @@ impl From<UpdateAlertStatus> for AlertStatus {
         match status {
             UpdateAlertStatus::Active => Self::Active,
             UpdateAlertStatus::Dismissed => Self::Dismissed,
+            UpdateAlertStatus::Silenced => Self::Silenced,
         }
     }
 }
@@ -104,6 +118,82 @@ mod alert_status {
     }
 }
 
+const MINOR_INT: i32 = 0;
+const MAJOR_INT: i32 = 1;
+const CRITICAL_INT: i32 = 2;
+
+#[typeshare::typeshare]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    derive_more::Display,
+    Serialize,
+    Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum AlertSeverity {
+    #[default]
+    /// The metric exceeded the boundary limit by less than the major multiplier.
+    Minor = MINOR_INT,
+    /// The metric exceeded the boundary limit by at least the major multiplier.
+    Major = MAJOR_INT,
+    /// The metric exceeded the boundary limit by at least the critical multiplier.
+    Critical = CRITICAL_INT,
+}
+
+#[cfg(feature = "db")]
+mod alert_severity {
+    use super::{AlertSeverity, CRITICAL_INT, MAJOR_INT, MINOR_INT};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum AlertSeverityError {
+        #[error("Invalid alert severity value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for AlertSeverity
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Minor => MINOR_INT.to_sql(out),
+                Self::Major => MAJOR_INT.to_sql(out),
+                Self::Critical => CRITICAL_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for AlertSeverity
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                MINOR_INT => Ok(Self::Minor),
+                MAJOR_INT => Ok(Self::Major),
+                CRITICAL_INT => Ok(Self::Critical),
+                value => Err(Box::new(AlertSeverityError::Invalid(value))),
+            }
+        }
+    }
+}
+
 #[typeshare::typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -121,6 +211,49 @@ pub enum UpdateAlertStatus {
     Active,
     /// The alert has been dismissed by a user.
     Dismissed,
+    /// The alert has been silenced by a user.
+    Silenced,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBulkUpdateAlerts {
+    /// The new status of the matching alerts.
+    /// If `active`, then dismissed alerts are matched.
+    /// Otherwise, active alerts are matched.
+    pub status: UpdateAlertStatus,
+    /// The justification for updating the alerts, such as a known infrastructure incident.
+    /// It is stored and logged for auditing.
+    pub reason: NonEmpty,
+    /// Only match alerts for this branch name, slug, or UUID.
+    pub branch: Option<NameId>,
+    /// Only match alerts for this testbed name, slug, or UUID.
+    pub testbed: Option<NameId>,
+    /// Only match alerts for this measure name, slug, or UUID.
+    pub measure: Option<NameId>,
+    /// Only match alerts of this severity.
+    pub severity: Option<AlertSeverity>,
+    /// Only match alerts for reports that started before this date time.
+    pub before: Option<DateTime>,
+    /// Only match alerts for reports that started after this date time.
+    pub after: Option<DateTime>,
+}
+
+/// The record of a bulk update of the alerts for a project
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAlertUpdate {
+    pub uuid: AlertUpdateUuid,
+    pub project: ProjectUuid,
+    /// The user that updated the alerts.
+    pub user: UserUuid,
+    pub status: AlertStatus,
+    pub reason: NonEmpty,
+    /// The number of alerts that were updated.
+    pub alerts: u32,
+    pub created: DateTime,
 }
 
 #[typeshare::typeshare]
@@ -129,6 +262,233 @@ pub enum UpdateAlertStatus {
 pub struct JsonPerfAlert {
     pub uuid: AlertUuid,
     pub limit: BoundaryLimit,
+    pub severity: AlertSeverity,
     pub status: AlertStatus,
     pub modified: DateTime,
 }
+
+/// The default window for alert statistics, in days
+pub const DEFAULT_ALERT_STATS_WINDOW_DAYS: u32 = 90;
+/// The maximum window for alert statistics, in days
+pub const MAX_ALERT_STATS_WINDOW_DAYS: u32 = 3_650;
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAlertStatsQuery {
+    /// The time window to count alerts over, ending now.
+    /// Either a number of days (ex: `90d`) or weeks (ex: `12w`).
+    /// If not set, the last 90 days are used.
+    pub window: Option<AlertStatsWindow>,
+    /// The size of the time buckets to count alerts in.
+    /// If not set, weekly buckets are used.
+    pub bucket: Option<AlertStatsBucket>,
+}
+
+/// A time window for alert statistics, in days (ex: `90d`) or weeks (ex: `12w`)
+#[derive(Debug, Display, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct AlertStatsWindow(String);
+
+#[derive(Debug, thiserror::Error)]
+pub enum AlertStatsWindowError {
+    #[error("Invalid alert stats window ({0}). Expected a number of days (ex: `90d`) or weeks (ex: `12w`) up to {max} days.", max = MAX_ALERT_STATS_WINDOW_DAYS)]
+    Invalid(String),
+}
+
+impl FromStr for AlertStatsWindow {
+    type Err = AlertStatsWindowError;
+
+    fn from_str(window: &str) -> Result<Self, Self::Err> {
+        if parse_window_days(window).is_some() {
+            Ok(Self(window.to_owned()))
+        } else {
+            Err(AlertStatsWindowError::Invalid(window.to_owned()))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AlertStatsWindow {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl AlertStatsWindow {
+    /// The number of days in the window
+    pub fn days(&self) -> u32 {
+        parse_window_days(&self.0).unwrap_or(DEFAULT_ALERT_STATS_WINDOW_DAYS)
+    }
+}
+
+fn parse_window_days(window: &str) -> Option<u32> {
+    let (count, days_per) = if let Some(days) = window.strip_suffix('d') {
+        (days, 1)
+    } else if let Some(weeks) = window.strip_suffix('w') {
+        (weeks, 7)
+    } else {
+        return None;
+    };
+    let days = count.parse::<u32>().ok()?.checked_mul(days_per)?;
+    (1..=MAX_ALERT_STATS_WINDOW_DAYS)
+        .contains(&days)
+        .then_some(days)
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatsBucket {
+    /// Daily buckets, in the project time zone.
+    Day,
+    /// Weekly buckets, in the project time zone and starting on the project week start.
+    #[default]
+    Week,
+    /// Monthly buckets, in the project time zone.
+    Month,
+}
+
+impl AlertStatsBucket {
+    /// The start of the bucket containing the date time
+    pub fn start(self, date_time: DateTime, time_zone: &TimeZone, week_start: Weekday) -> DateTime {
+        match self {
+            Self::Day => date_time.start_of_day(time_zone),
+            Self::Week => date_time.start_of_week(time_zone, week_start),
+            Self::Month => date_time.start_of_month(time_zone),
+        }
+    }
+
+    /// The start times of all of the buckets that overlap with the time range
+    pub fn starts(
+        self,
+        start_time: DateTime,
+        end_time: DateTime,
+        time_zone: &TimeZone,
+        week_start: Weekday,
+    ) -> Vec<DateTime> {
+        // Step past the end of the current bucket and then round back down to the start of the next one.
+        // No month is longer than 31 days, and no two months are shorter than 31 days.
+        let step = Days::new(match self {
+            Self::Day => 1,
+            Self::Week => 7,
+            Self::Month => 31,
+        });
+        let mut starts = Vec::new();
+        let mut bucket_start = self.start(start_time, time_zone, week_start);
+        while bucket_start.timestamp() <= end_time.timestamp() {
+            starts.push(bucket_start);
+            let Some(next_time) = bucket_start.into_inner().checked_add_days(step) else {
+                break;
+            };
+            let next_start = self.start(DateTime::from(next_time), time_zone, week_start);
+            if next_start.timestamp() <= bucket_start.timestamp() {
+                break;
+            }
+            bucket_start = next_start;
+        }
+        starts
+    }
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAlertStats {
+    /// The start of the time window.
+    pub start_time: DateTime,
+    /// The end of the time window.
+    pub end_time: DateTime,
+    /// The size of the time buckets.
+    pub bucket: AlertStatsBucket,
+    /// The alert counts for each time bucket in chronological order.
+    /// Buckets without any alerts are included with a total of zero.
+    pub buckets: Vec<JsonAlertStatsBucket>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAlertStatsBucket {
+    /// The start of the time bucket.
+    pub start_time: DateTime,
+    /// The total number of alerts in the time bucket.
+    pub total: u32,
+    /// The number of alerts for each branch, testbed, measure, and severity in the time bucket.
+    pub counts: Vec<JsonAlertStatsCount>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAlertStatsCount {
+    pub branch: BranchName,
+    pub testbed: ResourceName,
+    pub measure: ResourceName,
+    pub severity: AlertSeverity,
+    pub count: u32,
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{DateTime, TimeZone, Weekday};
+
+    use super::{AlertStatsBucket, AlertStatsWindow};
+
+    fn date_time(timestamp: i64) -> DateTime {
+        DateTime::try_from(timestamp).unwrap()
+    }
+
+    #[test]
+    fn test_alert_stats_window() {
+        assert_eq!(90, "90d".parse::<AlertStatsWindow>().unwrap().days());
+        assert_eq!(84, "12w".parse::<AlertStatsWindow>().unwrap().days());
+        assert_eq!(3_650, "3650d".parse::<AlertStatsWindow>().unwrap().days());
+
+        for window in [
+            "", "d", "90", "0d", "0w", "-1d", "1.5d", "3651d", "90m", "d90",
+        ] {
+            assert!(
+                window.parse::<AlertStatsWindow>().is_err(),
+                "Window should be invalid: {window}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_alert_stats_bucket_starts() {
+        let utc = TimeZone::default();
+        // Wednesday, 2024-10-23 02:30:00 UTC to Friday, 2024-11-08 02:30:00 UTC
+        let start_time = date_time(1_729_650_600);
+        let end_time = date_time(1_731_033_000);
+
+        let days = AlertStatsBucket::Day.starts(start_time, end_time, &utc, Weekday::Monday);
+        assert_eq!(17, days.len());
+        assert_eq!(Some(&date_time(1_729_641_600)), days.first());
+        assert_eq!(Some(&date_time(1_731_024_000)), days.last());
+
+        // Mondays, 2024-10-21, 2024-10-28, and 2024-11-04
+        let weeks = AlertStatsBucket::Week.starts(start_time, end_time, &utc, Weekday::Monday);
+        assert_eq!(
+            vec![
+                date_time(1_729_468_800),
+                date_time(1_730_073_600),
+                date_time(1_730_678_400)
+            ],
+            weeks
+        );
+
+        // 2024-10-01 and 2024-11-01
+        let months = AlertStatsBucket::Month.starts(start_time, end_time, &utc, Weekday::Monday);
+        assert_eq!(
+            vec![date_time(1_727_740_800), date_time(1_730_419_200)],
+            months
+        );
+    }
+}
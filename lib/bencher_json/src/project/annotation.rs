@@ -0,0 +1,163 @@
+use std::fmt;
+
+use bencher_valid::{DateTime, NonEmpty, ResourceName};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::ProjectUuid;
+
+crate::typed_uuid::typed_uuid!(AnnotationUuid);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewAnnotation {
+    /// The title of the annotation.
+    /// Maximum length is 64 characters.
+    pub title: ResourceName,
+    /// A longer description of the annotation.
+    pub description: Option<NonEmpty>,
+    /// The time of the event that the annotation marks.
+    /// If not provided, the current time is used.
+    pub time: Option<DateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAnnotations(pub Vec<JsonAnnotation>);
+
+crate::from_vec!(JsonAnnotations[JsonAnnotation]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAnnotation {
+    pub uuid: AnnotationUuid,
+    pub project: ProjectUuid,
+    pub title: ResourceName,
+    pub description: Option<NonEmpty>,
+    pub time: DateTime,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl fmt::Display for JsonAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum JsonUpdateAnnotation {
+    Patch(JsonAnnotationPatch),
+    Null(JsonAnnotationPatchNull),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAnnotationPatch {
+    /// The new title of the annotation.
+    /// Maximum length is 64 characters.
+    pub title: Option<ResourceName>,
+    /// The new description of the annotation.
+    /// Set to `null` to remove the current description.
+    pub description: Option<NonEmpty>,
+    /// The new time of the event that the annotation marks.
+    pub time: Option<DateTime>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonAnnotationPatchNull {
+    pub title: Option<ResourceName>,
+    pub description: (),
+    pub time: Option<DateTime>,
+}
+
+impl<'de> Deserialize<'de> for JsonUpdateAnnotation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const TITLE_FIELD: &str = "title";
+        const DESCRIPTION_FIELD: &str = "description";
+        const TIME_FIELD: &str = "time";
+        const FIELDS: &[&str] = &[TITLE_FIELD, DESCRIPTION_FIELD, TIME_FIELD];
+
+        #[derive(Deserialize)]
+        #[serde(field_identifier, rename_all = "snake_case")]
+        enum Field {
+            Title,
+            Description,
+            Time,
+        }
+
+        struct UpdateAnnotationVisitor;
+
+        impl<'de> Visitor<'de> for UpdateAnnotationVisitor {
+            type Value = JsonUpdateAnnotation;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("JsonUpdateAnnotation")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut title = None;
+                let mut description = None;
+                let mut time = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::Title => {
+                            if title.is_some() {
+                                return Err(de::Error::duplicate_field(TITLE_FIELD));
+                            }
+                            title = Some(map.next_value()?);
+                        },
+                        Field::Description => {
+                            if description.is_some() {
+                                return Err(de::Error::duplicate_field(DESCRIPTION_FIELD));
+                            }
+                            description = Some(map.next_value()?);
+                        },
+                        Field::Time => {
+                            if time.is_some() {
+                                return Err(de::Error::duplicate_field(TIME_FIELD));
+                            }
+                            time = Some(map.next_value()?);
+                        },
+                    }
+                }
+
+                Ok(match description {
+                    Some(Some(description)) => Self::Value::Patch(JsonAnnotationPatch {
+                        title,
+                        description: Some(description),
+                        time,
+                    }),
+                    Some(None) => Self::Value::Null(JsonAnnotationPatchNull {
+                        title,
+                        description: (),
+                        time,
+                    }),
+                    None => Self::Value::Patch(JsonAnnotationPatch {
+                        title,
+                        description: None,
+                        time,
+                    }),
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("JsonUpdateAnnotation", FIELDS, UpdateAnnotationVisitor)
+    }
+}
@@ -39,6 +39,7 @@ pub struct JsonBenchmark {
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
+    pub flaky: Option<DateTime>,
 }
 
 impl fmt::Display for JsonBenchmark {
@@ -58,4 +59,9 @@ pub struct JsonUpdateBenchmark {
     pub slug: Option<Slug>,
     /// Set whether the benchmark is archived.
     pub archived: Option<bool>,
+    /// Set whether the benchmark is quarantined as flaky.
+    /// All of the measures of a quarantined benchmark are flaky,
+    /// in addition to any measures that are detected as flaky.
+    /// Flaky benchmarks are excluded from alert generation by default.
+    pub flaky: Option<bool>,
 }
@@ -1,12 +1,15 @@
 use std::fmt;
 
-use bencher_valid::{BranchName, DateTime, GitHash, NameId, Slug};
+use bencher_valid::{BranchName, DateTime, GitHash, NameId, ResourceName, Slug};
 use once_cell::sync::Lazy;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{JsonHead, ProjectUuid};
+use crate::{
+    project::head::{HeadUuid, JsonVersion, VersionNumber},
+    JsonAnnotation, JsonHead, ProjectUuid,
+};
 
 crate::typed_uuid::typed_uuid!(BranchUuid);
 
@@ -95,6 +98,9 @@ pub struct JsonBranch {
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
+    /// The time that the threshold baselines for the branch were frozen, if they are.
+    /// Reports on a frozen branch are compared against the metrics from before this time.
+    pub frozen_baseline: Option<DateTime>,
 }
 
 impl fmt::Display for JsonBranch {
@@ -116,6 +122,12 @@ pub struct JsonUpdateBranch {
     pub start_point: Option<JsonUpdateStartPoint>,
     /// Set whether the branch is archived.
     pub archived: Option<bool>,
+    /// Set whether the threshold baselines for the branch are frozen.
+    /// Once frozen, new metrics are no longer included in the baselines,
+    /// so all subsequent reports are compared against the frozen statistics.
+    /// This is useful for release branches, where a rolling baseline would slowly absorb regressions.
+    /// Freezing a branch that is already frozen keeps its original freeze time.
+    pub frozen_baseline: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -160,3 +172,144 @@ impl From<JsonUpdateStartPoint> for Option<JsonNewStartPoint> {
         })
     }
 }
+
+/// The lineage of a branch head.
+/// The first head is the requested branch head,
+/// followed by the head of its start point, and so on back to a head without a start point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBranchLineage(pub Vec<JsonLineageHead>);
+
+crate::from_vec!(JsonBranchLineage[JsonLineageHead]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonLineageHead {
+    pub branch: BranchUuid,
+    pub name: BranchName,
+    pub slug: Slug,
+    pub head: HeadUuid,
+    /// The version of the next head in the lineage that this head was started from, if any.
+    pub start_point: Option<JsonVersion>,
+    /// The range of versions that were shallow copied from the start point when the head was created.
+    pub cloned: Option<JsonVersionRange>,
+    /// The range of versions that were created on this head.
+    pub native: Option<JsonVersionRange>,
+    pub created: DateTime,
+    pub replaced: Option<DateTime>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonVersionRange {
+    pub first: JsonVersion,
+    pub last: JsonVersion,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBranchVersions(pub Vec<JsonBranchVersion>);
+
+crate::from_vec!(JsonBranchVersions[JsonBranchVersion]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBranchVersion {
+    pub number: VersionNumber,
+    pub hash: Option<GitHash>,
+    /// The time the version was recorded from a git push, if any.
+    pub pushed: Option<DateTime>,
+    /// The number of reports for the version on the branch head.
+    /// A version without any reports has never been benchmarked on the branch head.
+    pub reports: u32,
+    /// The start time of the first report for the version on the branch head, if any.
+    pub first_report: Option<DateTime>,
+    /// The start time of the last report for the version on the branch head, if any.
+    pub last_report: Option<DateTime>,
+}
+
+/// The most git tags that can be recorded on a branch at once
+pub const MAX_BRANCH_TAGS: usize = 1024;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewBranchTags {
+    /// The git tags to record on the branch.
+    /// Maximum number of tags is 1024.
+    pub tags: Vec<JsonNewBranchTag>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewBranchTag {
+    /// The name of the git tag.
+    /// Maximum length is 64 characters.
+    pub name: ResourceName,
+    /// The full `git` commit hash that the tag points to.
+    pub hash: GitHash,
+    /// The time of the tag, such as the time of the commit that it points to.
+    pub time: DateTime,
+}
+
+/// The result of recording git tags on a branch.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonBranchTags {
+    pub branch: BranchUuid,
+    /// The annotations that were created for the tags.
+    /// Tags that already have an annotation with the same title and time are skipped.
+    pub annotations: Vec<JsonAnnotation>,
+    /// The versions that were recorded for the tags.
+    /// Tags for a commit that already has a version on the branch are skipped.
+    pub versions: Vec<JsonVersion>,
+}
+
+#[cfg(feature = "table")]
+pub mod table {
+    use bencher_valid::{DateTime, GitHash};
+    use tabled::{Table, Tabled};
+
+    use super::JsonBranchVersions;
+    use crate::project::{head::VersionNumber, perf::table::DisplayOption};
+
+    impl From<JsonBranchVersions> for Table {
+        fn from(json_versions: JsonBranchVersions) -> Self {
+            Self::new(
+                json_versions
+                    .0
+                    .into_iter()
+                    .map(|version| BranchVersionTable {
+                        number: version.number,
+                        hash: DisplayOption(version.hash),
+                        reports: version.reports,
+                        first_report: DisplayOption(version.first_report),
+                        last_report: DisplayOption(version.last_report),
+                        pushed: DisplayOption(version.pushed),
+                    }),
+            )
+        }
+    }
+
+    #[derive(Tabled)]
+    pub struct BranchVersionTable {
+        #[tabled(rename = "Version Number")]
+        pub number: VersionNumber,
+        #[tabled(rename = "Version Hash")]
+        pub hash: DisplayOption<GitHash>,
+        #[tabled(rename = "Reports")]
+        pub reports: u32,
+        #[tabled(rename = "First Report")]
+        pub first_report: DisplayOption<DateTime>,
+        #[tabled(rename = "Last Report")]
+        pub last_report: DisplayOption<DateTime>,
+        #[tabled(rename = "Pushed")]
+        pub pushed: DisplayOption<DateTime>,
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use bencher_valid::{DateTime, NameId, NonEmpty};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{AnnotationUuid, JsonBranch, ProjectUuid};
+
+use super::head::VersionNumber;
+
+crate::typed_uuid::typed_uuid!(ExpectationUuid);
+
+/// An expected regression window.
+/// Benchmarks that match the `pattern` on the branch do not generate alerts
+/// for reports within the time range and version range of the window.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewExpectation {
+    /// The name, slug, or UUID for the branch.
+    pub branch: NameId,
+    /// A regular expression to match against benchmark names.
+    /// Use `.*` to match all benchmarks.
+    pub pattern: NonEmpty,
+    /// The reason that the regression is expected and accepted, such as added security checks.
+    /// This is recorded in an annotation for the project.
+    pub reason: NonEmpty,
+    /// Reports that started at or after this time are in the window.
+    pub start_time: Option<DateTime>,
+    /// Reports that started at or before this time are in the window.
+    pub end_time: Option<DateTime>,
+    /// Reports for this branch version number or later are in the window.
+    pub start_version: Option<VersionNumber>,
+    /// Reports for this branch version number or earlier are in the window.
+    pub end_version: Option<VersionNumber>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonExpectations(pub Vec<JsonExpectation>);
+
+crate::from_vec!(JsonExpectations[JsonExpectation]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonExpectation {
+    pub uuid: ExpectationUuid,
+    pub project: ProjectUuid,
+    pub branch: JsonBranch,
+    pub pattern: NonEmpty,
+    pub reason: NonEmpty,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub start_version: Option<VersionNumber>,
+    pub end_version: Option<VersionNumber>,
+    /// The annotation that was recorded when the expected regression window was created.
+    /// This is unset if the annotation has since been deleted.
+    pub annotation: Option<AnnotationUuid>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl fmt::Display for JsonExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.branch.name, self.pattern)
+    }
+}
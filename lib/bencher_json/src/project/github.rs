@@ -0,0 +1,36 @@
+use bencher_valid::{DateTime, Secret};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{project::head::JsonVersion, BranchUuid, ProjectUuid};
+
+/// A GitHub webhook for a project.
+/// Each git push to a branch that already exists in the project
+/// is recorded as a version before it has been benchmarked.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonGitHubWebhook {
+    pub project: ProjectUuid,
+    /// The secret used to sign each webhook delivery.
+    /// It is only returned when the webhook is created or its secret is rotated.
+    pub secret: Option<Secret>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+/// The result of a GitHub webhook delivery.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonGitHubDelivery {
+    pub project: ProjectUuid,
+    /// The GitHub event type of the delivery.
+    pub event: String,
+    /// The branch that was pushed to, if it exists in the project.
+    pub branch: Option<BranchUuid>,
+    /// The versions that were recorded for the push.
+    /// Commits that already have a version on the branch are skipped.
+    pub versions: Vec<JsonVersion>,
+}
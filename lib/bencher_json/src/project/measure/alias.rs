@@ -0,0 +1,56 @@
+use std::fmt;
+
+use bencher_valid::{DateTime, ResourceId, ResourceName};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectUuid;
+
+use super::MeasureUuid;
+
+crate::typed_uuid::typed_uuid!(MeasureAliasUuid);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewMeasureAlias {
+    /// The alias for the measure.
+    /// When results are reported with a measure name that exactly matches the alias,
+    /// they are stored under the aliased measure instead.
+    /// The alias must not already be the name or slug of another measure in the project.
+    /// Maximum length is 64 characters.
+    pub alias: ResourceName,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonMeasureAliases(pub Vec<JsonMeasureAlias>);
+
+crate::from_vec!(JsonMeasureAliases[JsonMeasureAlias]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonMeasureAlias {
+    pub uuid: MeasureAliasUuid,
+    pub project: ProjectUuid,
+    pub measure: MeasureUuid,
+    pub alias: ResourceName,
+    pub created: DateTime,
+}
+
+impl fmt::Display for JsonMeasureAlias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.alias)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonMergeMeasure {
+    /// The slug or UUID of the measure to merge into this measure.
+    /// All of its metrics, thresholds, and plots are moved to this measure,
+    /// its name and slug become aliases for this measure,
+    /// and then it is deleted.
+    pub measure: ResourceId,
+}
@@ -129,6 +129,16 @@ pub mod iai_callgrind {
     }
 }
 
+pub mod perf_stat {
+    create_measure!(Instructions, "Instructions", "instructions", "instructions");
+
+    create_measure!(Cycles, "Cycles", "cycles", "cycles");
+
+    create_measure!(BranchMisses, "Branch Misses", "branch-misses", "misses");
+
+    create_measure!(CacheMisses, "Cache Misses", "cache-misses", "misses");
+}
+
 pub mod file_size {
     create_measure!(FileSize, "File Size", "file-size", "bytes (B)");
 }
@@ -9,10 +9,35 @@ use serde::{Deserialize, Serialize};
 
 use crate::ProjectUuid;
 
+pub mod alias;
 pub mod built_in;
+pub mod units;
 
 crate::typed_uuid::typed_uuid!(MeasureUuid);
 
+// Metric kinds were renamed to measures.
+// These aliases keep code written against the old names compiling during the transition.
+#[deprecated(
+    since = "0.4.0",
+    note = "Metric kinds have been renamed to measures. Use `MeasureUuid` instead."
+)]
+pub type MetricKindUuid = MeasureUuid;
+#[deprecated(
+    since = "0.4.0",
+    note = "Metric kinds have been renamed to measures. Use `JsonNewMeasure` instead."
+)]
+pub type JsonNewMetricKind = JsonNewMeasure;
+#[deprecated(
+    since = "0.4.0",
+    note = "Metric kinds have been renamed to measures. Use `JsonMeasures` instead."
+)]
+pub type JsonMetricKinds = JsonMeasures;
+#[deprecated(
+    since = "0.4.0",
+    note = "Metric kinds have been renamed to measures. Use `JsonMeasure` instead."
+)]
+pub type JsonMetricKind = JsonMeasure;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct JsonNewMeasure {
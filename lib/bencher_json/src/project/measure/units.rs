@@ -0,0 +1,264 @@
+use std::fmt;
+
+use bencher_valid::ResourceName;
+
+/// The physical quantity that a unit of measure is for.
+/// Only units with the same dimension can be converted between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitDimension {
+    Time,
+    Throughput,
+    Size,
+    /// A count of discrete events, such as instructions or cache misses.
+    /// Count units can not be converted between.
+    Count,
+}
+
+impl fmt::Display for UnitDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Time => "time",
+                Self::Throughput => "throughput",
+                Self::Size => "size",
+                Self::Count => "count",
+            }
+        )
+    }
+}
+
+/// A unit of measure in the known unit registry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasureUnit {
+    /// The plural name of the unit
+    pub name: &'static str,
+    /// The abbreviated symbol of the unit, if any
+    pub symbol: Option<&'static str>,
+    /// Other ways to write the symbol of the unit
+    pub aliases: &'static [&'static str],
+    pub dimension: UnitDimension,
+    /// The size of the unit relative to the base unit of its dimension
+    pub scale: f64,
+}
+
+macro_rules! measure_unit {
+    ($name:literal, $symbol:literal, $dimension:ident, $scale:expr) => {
+        measure_unit!($name, $symbol, [], $dimension, $scale)
+    };
+    ($name:literal, $symbol:literal, [$($alias:literal),*], $dimension:ident, $scale:expr) => {
+        MeasureUnit {
+            name: $name,
+            symbol: Some($symbol),
+            aliases: &[$($alias),*],
+            dimension: UnitDimension::$dimension,
+            scale: $scale,
+        }
+    };
+    ($name:literal) => {
+        MeasureUnit {
+            name: $name,
+            symbol: None,
+            aliases: &[],
+            dimension: UnitDimension::Count,
+            scale: 1.0,
+        }
+    };
+}
+
+/// The known unit registry.
+/// This includes the units for all of the built-in measures.
+pub const MEASURE_UNITS: &[MeasureUnit] = &[
+    // Time, with a base unit of nanoseconds
+    measure_unit!("picoseconds", "ps", Time, 1.0 / 1_000.0),
+    measure_unit!("nanoseconds", "ns", Time, 1.0),
+    measure_unit!("microseconds", "µs", ["μs", "us"], Time, 1_000.0),
+    measure_unit!("milliseconds", "ms", Time, 1_000_000.0),
+    measure_unit!("seconds", "s", Time, 1_000_000_000.0),
+    // Throughput, with a base unit of operations per second
+    measure_unit!("operations / second", "ops/s", Throughput, 1.0),
+    measure_unit!("operations / millisecond", "ops/ms", Throughput, 1_000.0),
+    measure_unit!(
+        "operations / microsecond",
+        "ops/µs",
+        ["ops/μs", "ops/us"],
+        Throughput,
+        1_000_000.0
+    ),
+    measure_unit!(
+        "operations / nanosecond",
+        "ops/ns",
+        Throughput,
+        1_000_000_000.0
+    ),
+    // Size, with a base unit of bytes
+    measure_unit!("bytes", "B", Size, 1.0),
+    measure_unit!("kilobytes", "KB", ["kB"], Size, 1_000.0),
+    measure_unit!("megabytes", "MB", Size, 1_000_000.0),
+    measure_unit!("gigabytes", "GB", Size, 1_000_000_000.0),
+    measure_unit!("kibibytes", "KiB", Size, 1_024.0),
+    measure_unit!("mebibytes", "MiB", Size, 1_048_576.0),
+    measure_unit!("gibibytes", "GiB", Size, 1_073_741_824.0),
+    // Counts
+    measure_unit!("instructions"),
+    measure_unit!("cycles"),
+    measure_unit!("accesses"),
+    measure_unit!("hits"),
+    measure_unit!("misses"),
+    measure_unit!("events"),
+    measure_unit!("blocks"),
+    measure_unit!("reads/writes"),
+];
+
+impl fmt::Display for MeasureUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(symbol) = self.symbol {
+            write!(f, "{} ({symbol})", self.name)
+        } else {
+            write!(f, "{}", self.name)
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UnitError {
+    #[error("Unknown units ({0}). Use one of the known units instead, such as `ms` or `nanoseconds (ns)`.")]
+    Unknown(String),
+    #[error("Units ({from}) can not be converted into ({to})")]
+    Incompatible { from: String, to: MeasureUnit },
+}
+
+impl MeasureUnit {
+    /// Find a known unit by its full units string, name, or symbol.
+    /// The full units string and name are case insensitive, but the symbol is not.
+    /// For example, `nanoseconds (ns)`, `Nanoseconds`, and `ns` are all found,
+    /// but `NS` is not, as `MB` and `mB` are different units.
+    pub fn find(units: &str) -> Option<&'static Self> {
+        let units = units.trim();
+        MEASURE_UNITS.iter().find(|unit| {
+            unit.to_string().eq_ignore_ascii_case(units)
+                || unit.name.eq_ignore_ascii_case(units)
+                || unit.symbol == Some(units)
+                || unit.aliases.contains(&units)
+        })
+    }
+
+    /// Find a known unit the same as `find`, but return an error if it is not found.
+    pub fn parse(units: &str) -> Result<&'static Self, UnitError> {
+        Self::find(units).ok_or_else(|| UnitError::Unknown(units.into()))
+    }
+
+    /// The full units string for the unit, as used by a measure.
+    #[allow(clippy::expect_used)]
+    pub fn units(&self) -> ResourceName {
+        self.to_string()
+            .parse()
+            .expect("Failed to parse measure units.")
+    }
+
+    /// Convert a value in this unit into another unit.
+    /// Returns `None` if the units are not of the same dimension.
+    pub fn convert(&self, value: f64, to: &Self) -> Option<f64> {
+        if self == to {
+            Some(value)
+        } else if self.dimension == to.dimension && self.dimension != UnitDimension::Count {
+            Some(value * self.scale / to.scale)
+        } else {
+            None
+        }
+    }
+
+    /// Convert a value in the given units string into this unit.
+    /// The units string must be for a known unit of the same dimension.
+    pub fn convert_from(&self, units: &str, value: f64) -> Result<f64, UnitError> {
+        Self::parse(units)?
+            .convert(value, self)
+            .ok_or_else(|| UnitError::Incompatible {
+                from: units.into(),
+                to: *self,
+            })
+    }
+
+    /// All of the other known units that this unit can be converted into.
+    pub fn conversions(&self) -> impl Iterator<Item = &'static Self> + '_ {
+        MEASURE_UNITS
+            .iter()
+            .filter(move |unit| *unit != self && self.convert(1.0, unit).is_some())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{MeasureUnit, UnitDimension, MEASURE_UNITS};
+    use crate::project::measure::built_in::{
+        file_size::FileSize,
+        generic::{Latency, Throughput},
+        iai::Instructions,
+        BuiltInMeasure,
+    };
+
+    #[test]
+    fn test_measure_unit_find() {
+        for units in ["nanoseconds (ns)", "Nanoseconds", "ns", " ns "] {
+            assert_eq!(MeasureUnit::find(units).unwrap().name, "nanoseconds");
+        }
+        for units in ["µs", "μs", "us"] {
+            assert_eq!(MeasureUnit::find(units).unwrap().name, "microseconds");
+        }
+        assert_eq!(MeasureUnit::find("MB").unwrap().name, "megabytes");
+        assert_eq!(MeasureUnit::find("misses").unwrap().symbol, None);
+        assert!(MeasureUnit::find("NS").is_none());
+        assert!(MeasureUnit::find("mB").is_none());
+        assert!(MeasureUnit::find("furlongs").is_none());
+    }
+
+    #[test]
+    fn test_measure_unit_built_in() {
+        for units in [
+            Latency::UNITS_STR,
+            Throughput::UNITS_STR,
+            Instructions::UNITS_STR,
+            FileSize::UNITS_STR,
+        ] {
+            assert_eq!(MeasureUnit::find(units).unwrap().to_string(), units);
+        }
+    }
+
+    #[test]
+    fn test_measure_unit_unique() {
+        for unit in MEASURE_UNITS {
+            assert_eq!(MeasureUnit::find(&unit.to_string()), Some(unit));
+            if let Some(symbol) = unit.symbol {
+                assert_eq!(MeasureUnit::find(symbol), Some(unit));
+            }
+            for alias in unit.aliases {
+                assert_eq!(MeasureUnit::find(alias), Some(unit));
+            }
+        }
+    }
+
+    #[test]
+    fn test_measure_unit_convert() {
+        let ns = MeasureUnit::find("ns").unwrap();
+        let ms = MeasureUnit::find("ms").unwrap();
+        assert_eq!(ms.convert(1.5, ns), Some(1_500_000.0));
+        assert_eq!(ns.convert(1_500_000.0, ms), Some(1.5));
+
+        let kib = MeasureUnit::find("KiB").unwrap();
+        let b = MeasureUnit::find("B").unwrap();
+        assert_eq!(kib.convert(2.0, b), Some(2_048.0));
+        assert_eq!(ns.convert(1.0, b), None);
+
+        let hits = MeasureUnit::find("hits").unwrap();
+        let misses = MeasureUnit::find("misses").unwrap();
+        assert_eq!(hits.dimension, UnitDimension::Count);
+        assert_eq!(hits.convert(1.0, hits), Some(1.0));
+        assert_eq!(hits.convert(1.0, misses), None);
+        assert_eq!(hits.conversions().count(), 0);
+        assert_eq!(ns.conversions().count(), 4);
+    }
+}
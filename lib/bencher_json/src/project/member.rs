@@ -0,0 +1,57 @@
+use bencher_valid::{DateTime, Email, Slug, UserName};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::UserUuid;
+
+use super::ProjectRole;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewProjectMember {
+    /// The user name for the invitee.
+    pub name: Option<UserName>,
+    /// The email for the invitee.
+    /// This will be used to both send the invite
+    /// and to create the user account if they do not exist.
+    /// The invitee does not need to be a member of the project's organization.
+    pub email: Email,
+    /// The project role for the invitee.
+    pub role: ProjectRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonProjectMembers(pub Vec<JsonProjectMember>);
+
+crate::from_vec!(JsonProjectMembers[JsonProjectMember]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonProjectMember {
+    /// The member UUID.
+    pub uuid: UserUuid,
+    /// The member user name.
+    pub name: UserName,
+    /// The member slug.
+    pub slug: Slug,
+    /// The member email.
+    pub email: Email,
+    /// The member project role.
+    pub role: ProjectRole,
+    /// The date time the member was created.
+    pub created: DateTime,
+    /// The date time the member was last modified.
+    pub modified: DateTime,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonUpdateProjectMember {
+    /// The new project role for the member.
+    pub role: Option<ProjectRole>,
+}
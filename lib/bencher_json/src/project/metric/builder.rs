@@ -0,0 +1,272 @@
+use std::collections::hash_map::Entry;
+
+use bencher_valid::{BenchmarkName, ValidError};
+use ordered_float::OrderedFloat;
+
+use super::{JsonMetricsMap, JsonNewMetric, JsonResultsMap, MeasureNameId};
+
+/// Build Bencher Metric Format (BMF) results from a custom benchmark harness.
+/// Serializing the results produces the same JSON that the `json` adapter expects.
+///
+/// ```
+/// use bencher_json::MetricsBuilder;
+///
+/// let results = MetricsBuilder::new()
+///     .benchmark("fib_10")
+///     .measure("latency", 1_000.0)
+///     .lower_bound(900.0)
+///     .upper_bound(1_100.0)
+///     .benchmark("fib_20")
+///     .measure("latency", 10_000.0)
+///     .build()
+///     .unwrap();
+/// let bmf = serde_json::to_string(&results).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct MetricsBuilder {
+    benchmarks: Vec<(String, Vec<(String, JsonNewMetric)>)>,
+    error: Option<MetricsBuilderError>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MetricsBuilderError {
+    #[error("Measure ({0}) was added before any benchmark")]
+    NoBenchmark(String),
+    #[error("Bound was added before any measure")]
+    NoMeasure,
+    #[error("Invalid benchmark name ({name}): {error}")]
+    BenchmarkName { name: String, error: ValidError },
+    #[error("Invalid measure name or slug ({name}): {error}")]
+    MeasureName { name: String, error: ValidError },
+    #[error("Benchmark ({0}) was added more than once")]
+    DuplicateBenchmark(BenchmarkName),
+    #[error("Measure ({measure}) was added more than once for benchmark ({benchmark})")]
+    DuplicateMeasure {
+        benchmark: BenchmarkName,
+        measure: MeasureNameId,
+    },
+}
+
+impl MetricsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a new benchmark.
+    /// All following measures are added to this benchmark.
+    #[must_use]
+    pub fn benchmark<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.benchmarks.push((name.into(), Vec::new()));
+        self
+    }
+
+    /// Add a measure to the current benchmark.
+    /// All following bounds are added to this measure.
+    #[must_use]
+    pub fn measure<N>(mut self, name: N, value: f64) -> Self
+    where
+        N: Into<String>,
+    {
+        let name = name.into();
+        if let Some((_, measures)) = self.benchmarks.last_mut() {
+            let metric = JsonNewMetric {
+                value: OrderedFloat(value),
+                lower_value: None,
+                upper_value: None,
+            };
+            measures.push((name, metric));
+        } else {
+            self.error
+                .get_or_insert(MetricsBuilderError::NoBenchmark(name));
+        }
+        self
+    }
+
+    /// Set the lower bound for the current measure.
+    #[must_use]
+    pub fn lower_bound(self, lower_value: f64) -> Self {
+        self.with_metric(|metric| metric.lower_value = Some(OrderedFloat(lower_value)))
+    }
+
+    /// Set the upper bound for the current measure.
+    #[must_use]
+    pub fn upper_bound(self, upper_value: f64) -> Self {
+        self.with_metric(|metric| metric.upper_value = Some(OrderedFloat(upper_value)))
+    }
+
+    fn with_metric<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut JsonNewMetric),
+    {
+        if let Some((_, metric)) = self
+            .benchmarks
+            .last_mut()
+            .and_then(|(_, measures)| measures.last_mut())
+        {
+            f(metric);
+        } else {
+            self.error.get_or_insert(MetricsBuilderError::NoMeasure);
+        }
+        self
+    }
+
+    /// Validate the benchmark and measure names and build the results.
+    pub fn build(self) -> Result<JsonResultsMap, MetricsBuilderError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut results = JsonResultsMap::with_capacity(self.benchmarks.len());
+        for (name, measures) in self.benchmarks {
+            let benchmark: BenchmarkName = name
+                .parse()
+                .map_err(|error| MetricsBuilderError::BenchmarkName { name, error })?;
+
+            let mut metrics = JsonMetricsMap::with_capacity(measures.len());
+            for (name, metric) in measures {
+                let measure: MeasureNameId = name
+                    .parse()
+                    .map_err(|error| MetricsBuilderError::MeasureName { name, error })?;
+                match metrics.entry(measure) {
+                    Entry::Occupied(entry) => {
+                        return Err(MetricsBuilderError::DuplicateMeasure {
+                            benchmark,
+                            measure: entry.key().clone(),
+                        })
+                    },
+                    Entry::Vacant(entry) => {
+                        entry.insert(metric);
+                    },
+                }
+            }
+
+            match results.entry(benchmark) {
+                Entry::Occupied(entry) => {
+                    return Err(MetricsBuilderError::DuplicateBenchmark(entry.key().clone()))
+                },
+                Entry::Vacant(entry) => {
+                    entry.insert(metrics);
+                },
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{MetricsBuilder, MetricsBuilderError};
+
+    #[test]
+    fn test_metrics_builder() {
+        let results = MetricsBuilder::new()
+            .benchmark("fib_10")
+            .measure("latency", 1_000.0)
+            .lower_bound(900.0)
+            .upper_bound(1_100.0)
+            .measure("throughput", 2.0)
+            .benchmark("fib_20")
+            .measure("latency", 10_000.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(results).unwrap(),
+            json!({
+                "fib_10": {
+                    "latency": {
+                        "value": 1_000.0,
+                        "lower_value": 900.0,
+                        "upper_value": 1_100.0
+                    },
+                    "throughput": {
+                        "value": 2.0,
+                        "lower_value": null,
+                        "upper_value": null
+                    }
+                },
+                "fib_20": {
+                    "latency": {
+                        "value": 10_000.0,
+                        "lower_value": null,
+                        "upper_value": null
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_metrics_builder_empty() {
+        let results = MetricsBuilder::new().build().unwrap();
+        assert_eq!(results.len(), 0);
+    }
+
+    #[test]
+    fn test_metrics_builder_no_benchmark() {
+        let error = MetricsBuilder::new()
+            .measure("latency", 1.0)
+            .benchmark("fib_10")
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(&error, MetricsBuilderError::NoBenchmark(name) if name == "latency"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_metrics_builder_no_measure() {
+        let error = MetricsBuilder::new()
+            .benchmark("fib_10")
+            .lower_bound(1.0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(error, MetricsBuilderError::NoMeasure), "{error}");
+    }
+
+    #[test]
+    fn test_metrics_builder_invalid_benchmark() {
+        let error = MetricsBuilder::new()
+            .benchmark("")
+            .measure("latency", 1.0)
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(error, MetricsBuilderError::BenchmarkName { .. }),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_metrics_builder_duplicate() {
+        let error = MetricsBuilder::new()
+            .benchmark("fib_10")
+            .measure("latency", 1.0)
+            .measure("latency", 2.0)
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(error, MetricsBuilderError::DuplicateMeasure { .. }),
+            "{error}"
+        );
+
+        let error = MetricsBuilder::new()
+            .benchmark("fib_10")
+            .measure("latency", 1.0)
+            .benchmark("fib_10")
+            .measure("latency", 2.0)
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(error, MetricsBuilderError::DuplicateBenchmark(_)),
+            "{error}"
+        );
+    }
+}
@@ -6,9 +6,11 @@ use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+mod builder;
 mod mean;
 mod median;
 
+pub use builder::{MetricsBuilder, MetricsBuilderError};
 pub use mean::Mean;
 pub use median::Median;
 
@@ -169,6 +171,20 @@ impl fmt::Display for JsonMetric {
     }
 }
 
+/// A metric that is the daily aggregate of downsampled metrics.
+/// The value, lower value, and upper value of the metric are the means of the aggregated metrics.
+#[typeshare::typeshare]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonMetricDownsample {
+    /// The number of metrics that were aggregated
+    pub sample_count: u32,
+    /// The minimum value of the aggregated metrics
+    pub min_value: OrderedFloat<f64>,
+    /// The maximum value of the aggregated metrics
+    pub max_value: OrderedFloat<f64>,
+}
+
 #[typeshare::typeshare]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -183,6 +199,8 @@ pub struct JsonOneMetric {
     pub benchmark: JsonBenchmark,
     pub measure: JsonMeasure,
     pub metric: JsonMetric,
+    /// Only set if the metric is a daily aggregate of downsampled metrics
+    pub downsample: Option<JsonMetricDownsample>,
     pub threshold: Option<JsonThresholdModel>,
     pub boundary: Option<JsonBoundary>,
     pub alert: Option<JsonPerfAlert>,
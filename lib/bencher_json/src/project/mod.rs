@@ -1,6 +1,6 @@
 use std::{fmt, str::FromStr};
 
-use bencher_valid::{DateTime, ResourceName, Slug, Url};
+use bencher_valid::{DateTime, Jwt, ResourceName, Slug, TimeZone, Url, Weekday};
 use derive_more::Display;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
@@ -9,21 +9,31 @@ use serde::{
     Deserialize, Deserializer, Serialize,
 };
 
-use crate::OrganizationUuid;
+use crate::{BigInt, OrganizationUuid, ResourceId};
 
 pub mod alert;
+pub mod annotation;
 pub mod benchmark;
 pub mod boundary;
 pub mod branch;
+pub mod expectation;
+pub mod github;
 pub mod head;
 pub mod measure;
+pub mod member;
 pub mod metric;
 pub mod model;
 pub mod perf;
+pub mod perf_fingerprint;
+pub mod perf_group;
 pub mod plot;
 pub mod report;
+pub mod report_hook;
+pub mod rule;
+pub mod saved_query;
 pub mod testbed;
 pub mod threshold;
+pub mod webhook;
 
 crate::typed_uuid::typed_uuid!(ProjectUuid);
 
@@ -45,6 +55,17 @@ pub struct JsonNewProject {
     /// ➕ Bencher Plus: Set the visibility of the project.
     /// Creating a `private` project requires a valid Bencher Plus subscription.
     pub visibility: Option<Visibility>,
+    /// The time zone for the project, as an IANA time zone name (ie `America/New_York`).
+    /// Alert statistics buckets, saved query summaries, and metric downsampling are aligned to days in this time zone.
+    /// If not provided, `UTC` is used.
+    pub time_zone: Option<TimeZone>,
+    /// The first day of the reporting week for the project.
+    /// Weekly alert statistics buckets and weekly saved query summaries start on this day.
+    /// If not provided, `monday` is used.
+    pub week_start: Option<Weekday>,
+    /// Reject reports with benchmarks or measures that do not already exist in the project.
+    /// If not provided, `false` is used.
+    pub strict: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,8 +84,39 @@ pub struct JsonProject {
     pub slug: Slug,
     pub url: Option<Url>,
     pub visibility: Visibility,
+    pub time_zone: TimeZone,
+    pub week_start: Weekday,
+    /// Whether reports with benchmarks or measures that do not already exist in the project are rejected.
+    pub strict: bool,
     pub created: DateTime,
     pub modified: DateTime,
+    /// Resource counts and storage usage for the project.
+    /// Only included when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<JsonProjectStats>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonProjectStats {
+    /// The number of branches in the project.
+    pub branches: u32,
+    /// The number of testbeds in the project.
+    pub testbeds: u32,
+    /// The number of benchmarks in the project.
+    pub benchmarks: u32,
+    /// The number of measures in the project.
+    pub measures: u32,
+    /// The number of reports in the project.
+    pub reports: u32,
+    /// The number of metrics in the project.
+    pub metrics: u32,
+    /// The number of threshold boundaries in the project.
+    pub boundaries: u32,
+    /// The approximate number of bytes of database storage used by the project.
+    /// This is estimated from the number of rows, so it does not include indexes or free pages.
+    pub storage: BigInt,
 }
 
 impl Display for JsonProject {
@@ -108,6 +160,12 @@ pub struct JsonProjectPatch {
     /// ➕ Bencher Plus: Set the new visibility of the project.
     /// Moving to a `private` project requires a valid Bencher Plus subscription.
     pub visibility: Option<Visibility>,
+    /// The new time zone for the project, as an IANA time zone name (ie `America/New_York`).
+    pub time_zone: Option<TimeZone>,
+    /// The new first day of the reporting week for the project.
+    pub week_start: Option<Weekday>,
+    /// Whether to reject reports with benchmarks or measures that do not already exist in the project.
+    pub strict: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -117,9 +175,13 @@ pub struct JsonProjectPatchNull {
     pub slug: Option<Slug>,
     pub url: (),
     pub visibility: Option<Visibility>,
+    pub time_zone: Option<TimeZone>,
+    pub week_start: Option<Weekday>,
+    pub strict: Option<bool>,
 }
 
 impl<'de> Deserialize<'de> for JsonUpdateProject {
+    #[allow(clippy::too_many_lines)]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -128,7 +190,18 @@ impl<'de> Deserialize<'de> for JsonUpdateProject {
         const SLUG_FIELD: &str = "slug";
         const URL_FIELD: &str = "url";
         const VISIBILITY_FIELD: &str = "visibility";
-        const FIELDS: &[&str] = &[NAME_FIELD, SLUG_FIELD, URL_FIELD, VISIBILITY_FIELD];
+        const TIME_ZONE_FIELD: &str = "time_zone";
+        const WEEK_START_FIELD: &str = "week_start";
+        const STRICT_FIELD: &str = "strict";
+        const FIELDS: &[&str] = &[
+            NAME_FIELD,
+            SLUG_FIELD,
+            URL_FIELD,
+            VISIBILITY_FIELD,
+            TIME_ZONE_FIELD,
+            WEEK_START_FIELD,
+            STRICT_FIELD,
+        ];
 
         #[derive(Deserialize)]
         #[serde(field_identifier, rename_all = "snake_case")]
@@ -137,6 +210,9 @@ impl<'de> Deserialize<'de> for JsonUpdateProject {
             Slug,
             Url,
             Visibility,
+            TimeZone,
+            WeekStart,
+            Strict,
         }
 
         struct UpdateProjectVisitor;
@@ -156,6 +232,9 @@ impl<'de> Deserialize<'de> for JsonUpdateProject {
                 let mut slug = None;
                 let mut url = None;
                 let mut visibility = None;
+                let mut time_zone = None;
+                let mut week_start = None;
+                let mut strict = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -183,6 +262,24 @@ impl<'de> Deserialize<'de> for JsonUpdateProject {
                             }
                             visibility = Some(map.next_value()?);
                         },
+                        Field::TimeZone => {
+                            if time_zone.is_some() {
+                                return Err(de::Error::duplicate_field(TIME_ZONE_FIELD));
+                            }
+                            time_zone = Some(map.next_value()?);
+                        },
+                        Field::WeekStart => {
+                            if week_start.is_some() {
+                                return Err(de::Error::duplicate_field(WEEK_START_FIELD));
+                            }
+                            week_start = Some(map.next_value()?);
+                        },
+                        Field::Strict => {
+                            if strict.is_some() {
+                                return Err(de::Error::duplicate_field(STRICT_FIELD));
+                            }
+                            strict = Some(map.next_value()?);
+                        },
                     }
                 }
 
@@ -192,18 +289,27 @@ impl<'de> Deserialize<'de> for JsonUpdateProject {
                         slug,
                         url: Some(url),
                         visibility,
+                        time_zone,
+                        week_start,
+                        strict,
                     }),
                     Some(None) => Self::Value::Null(JsonProjectPatchNull {
                         name,
                         slug,
                         url: (),
                         visibility,
+                        time_zone,
+                        week_start,
+                        strict,
                     }),
                     None => Self::Value::Patch(JsonProjectPatch {
                         name,
                         slug,
                         url: None,
                         visibility,
+                        time_zone,
+                        week_start,
+                        strict,
                     }),
                 })
             }
@@ -222,9 +328,42 @@ impl JsonUpdateProject {
     }
 }
 
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonProjectTransfer {
+    /// The slug or UUID of the organization to transfer the project to.
+    pub organization: ResourceId,
+    /// The new name of the project.
+    /// If not provided, the current name is used.
+    /// The name must be unique within the new organization.
+    /// Maximum length is 64 characters.
+    pub name: Option<ResourceName>,
+    /// The preferred new slug for the project.
+    /// If not provided, the current slug is used.
+    /// If the provided slug is already in use, a unique slug will be generated.
+    /// Maximum length is 64 characters.
+    pub slug: Option<Slug>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonProjectClaim {
+    /// The claim token that was returned when the unclaimed project was created.
+    pub token: Jwt,
+    /// The new name of the project.
+    /// If not provided, the current name is used.
+    /// The name must be unique within the organization.
+    /// Maximum length is 64 characters.
+    pub name: Option<ResourceName>,
+}
+
 const PUBLIC_INT: i32 = 0;
 #[cfg(feature = "plus")]
 const PRIVATE_INT: i32 = 1;
+#[cfg(feature = "plus")]
+const INTERNAL_INT: i32 = 2;
 
 #[typeshare::typeshare]
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
@@ -238,19 +377,34 @@ pub enum Visibility {
     Public = PUBLIC_INT,
     #[cfg(feature = "plus")]
     Private = PRIVATE_INT,
+    #[cfg(feature = "plus")]
+    Internal = INTERNAL_INT,
 }
 
 impl Visibility {
     pub fn is_public(self) -> bool {
         matches!(self, Self::Public)
     }
+
+    /// Any member of the organization can view an internal project
+    #[cfg_attr(not(feature = "plus"), allow(clippy::unused_self))]
+    pub fn is_internal(self) -> bool {
+        #[cfg(feature = "plus")]
+        {
+            matches!(self, Self::Internal)
+        }
+        #[cfg(not(feature = "plus"))]
+        {
+            false
+        }
+    }
 }
 
 #[cfg(feature = "db")]
 mod visibility {
-    #[cfg(feature = "plus")]
-    use super::PRIVATE_INT;
     use super::{Visibility, PUBLIC_INT};
+    #[cfg(feature = "plus")]
+    use super::{INTERNAL_INT, PRIVATE_INT};
 
     #[derive(Debug, thiserror::Error)]
     pub enum VisibilityError {
@@ -271,6 +425,8 @@ mod visibility {
                 Self::Public => PUBLIC_INT.to_sql(out),
                 #[cfg(feature = "plus")]
                 Self::Private => PRIVATE_INT.to_sql(out),
+                #[cfg(feature = "plus")]
+                Self::Internal => INTERNAL_INT.to_sql(out),
             }
         }
     }
@@ -285,6 +441,8 @@ mod visibility {
                 PUBLIC_INT => Ok(Self::Public),
                 #[cfg(feature = "plus")]
                 PRIVATE_INT => Ok(Self::Private),
+                #[cfg(feature = "plus")]
+                INTERNAL_INT => Ok(Self::Internal),
                 value => Err(Box::new(VisibilityError::Invalid(value))),
             }
         }
@@ -298,6 +456,8 @@ mod visibility {
 pub enum ProjectPermission {
     #[display("view")]
     View,
+    #[display("report")]
+    Report,
     #[display("create")]
     Create,
     #[display("edit")]
@@ -317,18 +477,27 @@ pub enum ProjectPermission {
 }
 
 pub const VIEWER_ROLE: &str = "viewer";
+pub const REPORTER_ROLE: &str = "reporter";
 pub const DEVELOPER_ROLE: &str = "developer";
 pub const MAINTAINER_ROLE: &str = "maintainer";
 
+#[typeshare::typeshare]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
 #[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectRole {
+    /// The project viewer role.
+    /// Viewers can view the project, even if it is private.
+    Viewer,
+    /// The project reporter role.
+    /// Reporters can also submit reports to the project.
+    Reporter,
     // TODO Team Management
-    // Viewer,
     // Developer,
+    /// The project maintainer role.
+    /// Maintainers can also manage the project and its members.
     Maintainer,
 }
 
@@ -337,8 +506,8 @@ impl FromStr for ProjectRole {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            // TODO Team Management
-            // MEMBER_ROLE => Ok(Self::Member),
+            VIEWER_ROLE => Ok(Self::Viewer),
+            REPORTER_ROLE => Ok(Self::Reporter),
             MAINTAINER_ROLE => Ok(Self::Maintainer),
             _ => Err(s.into()),
         }
@@ -351,8 +520,8 @@ impl Display for ProjectRole {
             f,
             "{}",
             match self {
-                // TODO Team Management
-                // Self::Member => MEMBER_ROLE,
+                Self::Viewer => VIEWER_ROLE,
+                Self::Reporter => REPORTER_ROLE,
                 Self::Maintainer => MAINTAINER_ROLE,
             }
         )
@@ -360,8 +529,8 @@ impl Display for ProjectRole {
 }
 
 #[cfg(feature = "db")]
-mod organization_role {
-    use super::{ProjectRole, MAINTAINER_ROLE};
+mod project_role {
+    use super::{ProjectRole, MAINTAINER_ROLE, REPORTER_ROLE, VIEWER_ROLE};
 
     #[derive(Debug, thiserror::Error)]
     pub enum ProjectRoleError {
@@ -380,6 +549,8 @@ mod organization_role {
             out: &mut diesel::serialize::Output<'b, '_, DB>,
         ) -> diesel::serialize::Result {
             match self {
+                Self::Viewer => out.set_value(VIEWER_ROLE.to_owned()),
+                Self::Reporter => out.set_value(REPORTER_ROLE.to_owned()),
                 Self::Maintainer => out.set_value(MAINTAINER_ROLE.to_owned()),
             }
             Ok(diesel::serialize::IsNull::No)
@@ -394,6 +565,8 @@ mod organization_role {
         fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
             let role = String::from_sql(bytes)?;
             match role.as_str() {
+                VIEWER_ROLE => Ok(Self::Viewer),
+                REPORTER_ROLE => Ok(Self::Reporter),
                 MAINTAINER_ROLE => Ok(Self::Maintainer),
                 _ => Err(Box::new(ProjectRoleError::Invalid(role))),
             }
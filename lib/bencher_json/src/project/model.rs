@@ -1,4 +1,4 @@
-use bencher_valid::{Boundary, DateTime, ModelTest, SampleSize, Window};
+use bencher_valid::{Boundary, DateTime, Decay, ModelTest, SampleSize, Window};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,7 @@ pub struct JsonModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub decay: Option<Decay>,
     pub created: DateTime,
     pub replaced: Option<DateTime>,
 }
@@ -1,3 +1,4 @@
+use ordered_float::OrderedFloat;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::ser::{self, SerializeStruct};
@@ -9,13 +10,15 @@ use crate::urlencoded::{
     to_urlencoded_optional_list, UrlEncodedError,
 };
 use crate::{
-    BenchmarkUuid, BranchUuid, DateTime, DateTimeMillis, HeadUuid, JsonBenchmark, JsonBranch,
-    JsonMeasure, JsonProject, JsonTestbed, MeasureUuid, ReportUuid, TestbedUuid,
+    BenchmarkUuid, BranchUuid, DateTime, DateTimeMillis, HeadUuid, JsonAnnotation, JsonBenchmark,
+    JsonBranch, JsonMeasure, JsonProject, JsonTestbed, Jwt, MeasureUuid, NameId, NonEmpty,
+    ProjectUuid, ReportUuid, ResourceId, ResourceName, TestbedUuid,
 };
 
 use super::alert::JsonPerfAlert;
 use super::boundary::JsonBoundary;
 use super::head::JsonVersion;
+use super::measure::units::{MeasureUnit, UnitError};
 use super::metric::JsonMetric;
 use super::report::Iteration;
 use super::threshold::JsonThresholdModel;
@@ -45,6 +48,13 @@ pub struct JsonPerfQueryParams {
     pub start_time: Option<DateTimeMillis>,
     /// Search for metrics before the given date time in milliseconds.
     pub end_time: Option<DateTimeMillis>,
+    /// An optional comma separated list of report release tags.
+    /// If provided, only metrics from reports with one of the given tags are returned.
+    pub tags: Option<String>,
+    /// The units to convert the metric values into, such as `ms`.
+    /// This must be a known unit of the same dimension as the units of every queried measure.
+    /// If not provided, the metric values are in the units of their measure.
+    pub unit: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -68,6 +78,59 @@ pub struct JsonPerfImgQueryParams {
     pub start_time: Option<DateTimeMillis>,
     /// Search for metrics before the given date time in milliseconds.
     pub end_time: Option<DateTimeMillis>,
+    /// An optional comma separated list of report release tags.
+    /// If provided, only metrics from reports with one of the given tags are returned.
+    pub tags: Option<String>,
+    /// The units to convert the metric values into, such as `ms`.
+    /// This must be a known unit of the same dimension as the units of every queried measure.
+    /// If not provided, the metric values are in the units of their measure.
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfBadgeQueryParams {
+    /// The name, slug, or UUID for the branch.
+    /// The current head of the branch is used.
+    pub branch: NameId,
+    /// The name, slug, or UUID for the testbed.
+    /// If not provided, the `localhost` testbed is used.
+    pub testbed: Option<NameId>,
+    /// The slug or UUID for the benchmark.
+    /// If not provided, the benchmark with the most recent metric
+    /// for the branch, testbed, and measure is used.
+    pub benchmark: Option<ResourceId>,
+    /// The name, slug, or UUID for the measure.
+    pub measure: NameId,
+    /// The label for the badge.
+    /// If not provided, the benchmark and measure names are used.
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewPerfEmbed {
+    /// The time-to-live (TTL) for the embed token in seconds.
+    /// If not provided, the embed token will expire after 30 days.
+    /// The maximum TTL is 365 days.
+    pub ttl: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfEmbedQueryParams {
+    /// The embed token for the perf query.
+    pub token: Jwt,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfEmbed {
+    pub project: ProjectUuid,
+    /// The embed token only grants access to this perf image query.
+    pub query: String,
+    pub token: Jwt,
+    pub expiration: DateTime,
 }
 
 impl From<JsonPerfImgQueryParams> for JsonPerfQueryParams {
@@ -81,6 +144,8 @@ impl From<JsonPerfImgQueryParams> for JsonPerfQueryParams {
             measures,
             start_time,
             end_time,
+            tags,
+            unit,
         } = query;
         Self {
             branches,
@@ -90,6 +155,8 @@ impl From<JsonPerfImgQueryParams> for JsonPerfQueryParams {
             measures,
             start_time,
             end_time,
+            tags,
+            unit,
         }
     }
 }
@@ -106,6 +173,8 @@ pub struct JsonPerfQuery {
     pub measures: Vec<MeasureUuid>,
     pub start_time: Option<DateTime>,
     pub end_time: Option<DateTime>,
+    pub tags: Vec<NonEmpty>,
+    pub unit: Option<ResourceName>,
 }
 
 impl TryFrom<JsonPerfQueryParams> for JsonPerfQuery {
@@ -120,6 +189,8 @@ impl TryFrom<JsonPerfQueryParams> for JsonPerfQuery {
             measures,
             start_time,
             end_time,
+            tags,
+            unit,
         } = query_params;
 
         if branches.is_empty() {
@@ -140,6 +211,16 @@ impl TryFrom<JsonPerfQueryParams> for JsonPerfQuery {
         let testbeds = from_urlencoded_list(&testbeds)?;
         let benchmarks = from_urlencoded_list(&benchmarks)?;
         let measures = from_urlencoded_list(&measures)?;
+        let tags = if let Some(tags) = tags {
+            from_urlencoded_list(&tags)?
+        } else {
+            Vec::new()
+        };
+        // Normalize the units, for example `ms` becomes `milliseconds (ms)`
+        let unit = unit
+            .as_deref()
+            .map(|unit| MeasureUnit::parse(unit).map(MeasureUnit::units))
+            .transpose()?;
 
         // Guarantee that the `heads` array is the same length as the `branches` array.
         let heads = size_heads_to_branches(&branches, &heads);
@@ -152,6 +233,8 @@ impl TryFrom<JsonPerfQueryParams> for JsonPerfQuery {
             measures,
             start_time: start_time.map(Into::into),
             end_time: end_time.map(Into::into),
+            tags,
+            unit,
         })
     }
 }
@@ -210,7 +293,7 @@ impl JsonPerfQuery {
         serde_urlencoded::to_string(query).map_err(Into::into)
     }
 
-    fn urlencoded(&self) -> Result<[(&'static str, Option<String>); 7], UrlEncodedError> {
+    fn urlencoded(&self) -> Result<[(&'static str, Option<String>); 9], UrlEncodedError> {
         QUERY_KEYS
             .into_iter()
             .zip([
@@ -221,6 +304,8 @@ impl JsonPerfQuery {
                 Some(self.measures()),
                 self.start_time_str(),
                 self.end_time_str(),
+                self.tags(),
+                self.unit(),
             ])
             .collect::<Vec<_>>()
             .try_into()
@@ -259,6 +344,18 @@ impl JsonPerfQuery {
         self.end_time.map(Into::into)
     }
 
+    pub fn tags(&self) -> Option<String> {
+        if self.tags.is_empty() {
+            None
+        } else {
+            Some(to_urlencoded_list(&self.tags))
+        }
+    }
+
+    pub fn unit(&self) -> Option<String> {
+        self.unit.as_ref().map(ToString::to_string)
+    }
+
     fn start_time_str(&self) -> Option<String> {
         self.start_time().as_ref().map(to_urlencoded)
     }
@@ -280,6 +377,8 @@ pub enum PerfQueryKey {
     Measures,
     StartTime,
     EndTime,
+    Tags,
+    Unit,
 }
 
 pub const BRANCHES: &str = "branches";
@@ -289,8 +388,10 @@ pub const BENCHMARKS: &str = "benchmarks";
 pub const MEASURES: &str = "measures";
 pub const START_TIME: &str = "start_time";
 pub const END_TIME: &str = "end_time";
-const QUERY_KEYS: [&str; 7] = [
-    BRANCHES, HEADS, TESTBEDS, BENCHMARKS, MEASURES, START_TIME, END_TIME,
+pub const TAGS: &str = "tags";
+pub const UNIT: &str = "unit";
+const QUERY_KEYS: [&str; 9] = [
+    BRANCHES, HEADS, TESTBEDS, BENCHMARKS, MEASURES, START_TIME, END_TIME, TAGS, UNIT,
 ];
 
 #[typeshare::typeshare]
@@ -301,6 +402,8 @@ pub struct JsonPerf {
     pub start_time: Option<DateTime>,
     pub end_time: Option<DateTime>,
     pub results: Vec<JsonPerfMetrics>,
+    /// The project annotations within the queried time range.
+    pub annotations: Option<Vec<JsonAnnotation>>,
 }
 
 #[typeshare::typeshare]
@@ -330,6 +433,34 @@ pub struct JsonPerfMetric {
     pub alert: Option<JsonPerfAlert>,
 }
 
+impl JsonPerfMetrics {
+    /// Convert the metric values and boundary limits into the given unit.
+    /// The measure units are updated to match.
+    pub fn convert_units(&mut self, unit: &MeasureUnit) -> Result<(), UnitError> {
+        let units = self.measure.units.as_ref();
+        if MeasureUnit::find(units) == Some(unit) {
+            return Ok(());
+        }
+        let convert = |value: OrderedFloat<f64>| {
+            unit.convert_from(units, value.into_inner())
+                .map(OrderedFloat)
+        };
+        for perf_metric in &mut self.metrics {
+            let metric = &mut perf_metric.metric;
+            metric.value = convert(metric.value)?;
+            metric.lower_value = metric.lower_value.map(convert).transpose()?;
+            metric.upper_value = metric.upper_value.map(convert).transpose()?;
+            if let Some(boundary) = &mut perf_metric.boundary {
+                boundary.baseline = boundary.baseline.map(convert).transpose()?;
+                boundary.lower_limit = boundary.lower_limit.map(convert).transpose()?;
+                boundary.upper_limit = boundary.upper_limit.map(convert).transpose()?;
+            }
+        }
+        self.measure.units = unit.units();
+        Ok(())
+    }
+}
+
 #[cfg(feature = "table")]
 pub mod table {
     use std::fmt;
@@ -341,13 +472,66 @@ pub mod table {
     use crate::{
         project::{head::VersionNumber, report::Iteration},
         DateTime, JsonBenchmark, JsonBranch, JsonMeasure, JsonMetric, JsonPerf, JsonProject,
-        JsonTestbed,
+        JsonTestbed, JsonUserPrefs,
     };
 
     impl From<JsonPerf> for Table {
         fn from(json_perf: JsonPerf) -> Self {
+            json_perf.into_table(None)
+        }
+    }
+
+    #[derive(Tabled)]
+    pub struct PerfTable {
+        #[tabled(rename = "Project")]
+        pub project: JsonProject,
+        #[tabled(rename = "Branch")]
+        pub branch: JsonBranch,
+        #[tabled(rename = "Testbed")]
+        pub testbed: JsonTestbed,
+        #[tabled(rename = "Benchmark")]
+        pub benchmark: JsonBenchmark,
+        #[tabled(rename = "Measure")]
+        pub measure: JsonMeasure,
+        #[tabled(rename = "Iteration")]
+        pub iteration: Iteration,
+        #[tabled(rename = "Start Time")]
+        pub start_time: String,
+        #[tabled(rename = "End Time")]
+        pub end_time: String,
+        #[tabled(rename = "Version Number")]
+        pub version_number: VersionNumber,
+        #[tabled(rename = "Version Hash")]
+        pub version_hash: DisplayOption<GitHash>,
+        #[tabled(rename = "Metric Value")]
+        pub metric: JsonMetric,
+        #[tabled(rename = "Boundary Baseline")]
+        pub baseline: DisplayOption<OrderedFloat<f64>>,
+        #[tabled(rename = "Lower Boundary Limit")]
+        pub lower_limit: DisplayOption<OrderedFloat<f64>>,
+        #[tabled(rename = "Upper Boundary Limit")]
+        pub upper_limit: DisplayOption<OrderedFloat<f64>>,
+    }
+
+    /// The characters used to draw a sparkline, from lowest to highest
+    const SPARKLINE_TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    const SPARKLINE_MAX_TICK: char = '█';
+    const SPARKLINE_FLAT_INDEX: usize = 3;
+    /// The maximum number of characters in a sparkline
+    const SPARKLINE_WIDTH: usize = 32;
+
+    impl JsonPerf {
+        /// Create a table with a row for each metric.
+        /// If user preferences are given, then the start and end times are displayed with them.
+        pub fn into_table(self, prefs: Option<&JsonUserPrefs>) -> Table {
+            let format_date_time = |date_time: DateTime| {
+                prefs.map_or_else(
+                    || date_time.to_string(),
+                    |prefs| prefs.format_date_time(date_time),
+                )
+            };
             let mut perf_table = Vec::new();
-            for result in json_perf.results {
+            for result in self.results {
                 for metric in result.metrics {
                     let (baseline, lower_limit, upper_limit) =
                         if let Some(boundary) = metric.boundary {
@@ -364,14 +548,14 @@ pub mod table {
                             )
                         };
                     perf_table.push(PerfTable {
-                        project: json_perf.project.clone(),
+                        project: self.project.clone(),
                         branch: result.branch.clone(),
                         testbed: result.testbed.clone(),
                         benchmark: result.benchmark.clone(),
                         measure: result.measure.clone(),
                         iteration: metric.iteration,
-                        start_time: metric.start_time,
-                        end_time: metric.end_time,
+                        start_time: format_date_time(metric.start_time),
+                        end_time: format_date_time(metric.end_time),
                         version_number: metric.version.number,
                         version_hash: DisplayOption(metric.version.hash),
                         metric: metric.metric,
@@ -381,14 +565,53 @@ pub mod table {
                     });
                 }
             }
-            Self::new(perf_table)
+            Table::new(perf_table)
+        }
+
+        /// Summarize each result as a single row,
+        /// with a sparkline of the metrics over the queried time window
+        /// and the most recent metric compared to its boundary baseline.
+        pub fn into_sparkline_table(self) -> Table {
+            let sparkline_table = self
+                .results
+                .into_iter()
+                .map(|result| {
+                    let values = result
+                        .metrics
+                        .iter()
+                        .map(|metric| metric.metric.value.into_inner())
+                        .collect::<Vec<_>>();
+                    let current = result.metrics.last().map(|metric| metric.metric.value);
+                    let baseline = result
+                        .metrics
+                        .last()
+                        .and_then(|metric| metric.boundary)
+                        .and_then(|boundary| boundary.baseline);
+                    let change = current
+                        .zip(baseline)
+                        .map(|(current, baseline)| {
+                            (current.into_inner() - baseline.into_inner()) / baseline.into_inner()
+                                * 100.0
+                        })
+                        .filter(|change| change.is_finite());
+                    PerfSparklineTable {
+                        branch: result.branch,
+                        testbed: result.testbed,
+                        benchmark: result.benchmark,
+                        measure: result.measure,
+                        sparkline: sparkline(&values, SPARKLINE_WIDTH),
+                        current: DisplayOption(current),
+                        baseline: DisplayOption(baseline),
+                        change: DisplayOption(change.map(DisplayChange)),
+                    }
+                })
+                .collect::<Vec<_>>();
+            Table::new(sparkline_table)
         }
     }
 
     #[derive(Tabled)]
-    pub struct PerfTable {
-        #[tabled(rename = "Project")]
-        pub project: JsonProject,
+    pub struct PerfSparklineTable {
         #[tabled(rename = "Branch")]
         pub branch: JsonBranch,
         #[tabled(rename = "Testbed")]
@@ -397,28 +620,69 @@ pub mod table {
         pub benchmark: JsonBenchmark,
         #[tabled(rename = "Measure")]
         pub measure: JsonMeasure,
-        #[tabled(rename = "Iteration")]
-        pub iteration: Iteration,
-        #[tabled(rename = "Start Time")]
-        pub start_time: DateTime,
-        #[tabled(rename = "End Time")]
-        pub end_time: DateTime,
-        #[tabled(rename = "Version Number")]
-        pub version_number: VersionNumber,
-        #[tabled(rename = "Version Hash")]
-        pub version_hash: DisplayOption<GitHash>,
-        #[tabled(rename = "Metric Value")]
-        pub metric: JsonMetric,
-        #[tabled(rename = "Boundary Baseline")]
+        #[tabled(rename = "Trend")]
+        pub sparkline: String,
+        #[tabled(rename = "Current")]
+        pub current: DisplayOption<OrderedFloat<f64>>,
+        #[tabled(rename = "Baseline")]
         pub baseline: DisplayOption<OrderedFloat<f64>>,
-        #[tabled(rename = "Lower Boundary Limit")]
-        pub lower_limit: DisplayOption<OrderedFloat<f64>>,
-        #[tabled(rename = "Upper Boundary Limit")]
-        pub upper_limit: DisplayOption<OrderedFloat<f64>>,
+        #[tabled(rename = "Change")]
+        pub change: DisplayOption<DisplayChange>,
+    }
+
+    pub struct DisplayChange(f64);
+
+    impl fmt::Display for DisplayChange {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{:+.2}%", self.0)
+        }
+    }
+
+    /// Draw the values as a unicode sparkline that is at most `width` characters wide.
+    /// If there are more values than `width`, then adjacent values are averaged together.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn sparkline(values: &[f64], width: usize) -> String {
+        let values = values
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect::<Vec<_>>();
+        if values.is_empty() || width == 0 {
+            return String::new();
+        }
+        // Average adjacent values together so that the sparkline fits within the width
+        let chunk_size = values.len().div_ceil(width);
+        let values = values
+            .chunks(chunk_size)
+            .map(|chunk| chunk.iter().sum::<f64>() / chunk.len() as f64)
+            .collect::<Vec<_>>();
+
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+        values
+            .into_iter()
+            .map(|value| {
+                let index = if range > 0.0 {
+                    (((value - min) / range) * (SPARKLINE_TICKS.len() - 1) as f64).round() as usize
+                } else {
+                    // A flat line is drawn through the middle
+                    SPARKLINE_FLAT_INDEX
+                };
+                SPARKLINE_TICKS
+                    .get(index)
+                    .copied()
+                    .unwrap_or(SPARKLINE_MAX_TICK)
+            })
+            .collect()
     }
 
     #[derive(Default)]
-    pub struct DisplayOption<T>(Option<T>);
+    pub struct DisplayOption<T>(pub Option<T>);
 
     impl<T> fmt::Display for DisplayOption<T>
     where
@@ -432,4 +696,100 @@ pub mod table {
             }
         }
     }
+
+    #[cfg(test)]
+    mod test {
+        use pretty_assertions::assert_eq;
+
+        use super::sparkline;
+
+        #[test]
+        fn test_sparkline() {
+            assert_eq!(sparkline(&[], 32), "");
+            assert_eq!(sparkline(&[1.0, 2.0, 3.0], 0), "");
+            assert_eq!(
+                sparkline(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], 32),
+                "▁▂▃▄▅▆▇█"
+            );
+            assert_eq!(sparkline(&[8.0, 1.0, f64::NAN, 8.0], 32), "█▁█");
+            assert_eq!(sparkline(&[5.0, 5.0, 5.0], 32), "▄▄▄");
+        }
+
+        #[test]
+        fn test_sparkline_width() {
+            assert_eq!(sparkline(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0], 3), "▁▅█");
+            assert_eq!(sparkline(&[1.0; 100], 32).chars().count(), 25);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{JsonPerfQuery, JsonPerfQueryParams};
+    use crate::urlencoded::UrlEncodedError;
+
+    const UUID: &str = "00000000-0000-0000-0000-000000000000";
+
+    fn query_params(unit: Option<&str>) -> JsonPerfQueryParams {
+        JsonPerfQueryParams {
+            branches: UUID.into(),
+            heads: None,
+            testbeds: UUID.into(),
+            benchmarks: UUID.into(),
+            measures: UUID.into(),
+            start_time: None,
+            end_time: None,
+            tags: None,
+            unit: unit.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn test_perf_query_unit() {
+        let query = JsonPerfQuery::try_from(query_params(None)).unwrap();
+        assert_eq!(query.unit, None);
+        assert!(!query.to_query_string(&[]).unwrap().contains("unit"));
+
+        let query = JsonPerfQuery::try_from(query_params(Some("ms"))).unwrap();
+        assert_eq!(query.unit.unwrap().as_ref(), "milliseconds (ms)");
+
+        let query = JsonPerfQuery::try_from(query_params(Some("microseconds"))).unwrap();
+        assert!(query
+            .to_query_string(&[])
+            .unwrap()
+            .ends_with("&unit=microseconds+%28%C2%B5s%29"));
+
+        assert!(matches!(
+            JsonPerfQuery::try_from(query_params(Some("furlongs"))),
+            Err(UrlEncodedError::Unit(_))
+        ));
+    }
+
+    #[test]
+    fn test_perf_query_tags() {
+        let query = JsonPerfQuery::try_from(query_params(None)).unwrap();
+        assert!(query.tags.is_empty());
+        assert!(!query.to_query_string(&[]).unwrap().contains("tags"));
+
+        let mut params = query_params(None);
+        params.tags = Some("v1.41.0,v1.42.0".into());
+        let query = JsonPerfQuery::try_from(params).unwrap();
+        assert_eq!(
+            query.tags.iter().map(AsRef::as_ref).collect::<Vec<&str>>(),
+            ["v1.41.0", "v1.42.0"]
+        );
+        assert!(query
+            .to_query_string(&[])
+            .unwrap()
+            .contains("&tags=v1.41.0%2Cv1.42.0"));
+
+        let mut params = query_params(None);
+        params.tags = Some("v1.41.0,".into());
+        assert!(matches!(
+            JsonPerfQuery::try_from(params),
+            Err(UrlEncodedError::EmptyValue(_))
+        ));
+    }
 }
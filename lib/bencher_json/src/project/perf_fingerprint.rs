@@ -0,0 +1,274 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bencher_valid::{DateTime, DateTimeMillis, NameId};
+use ordered_float::OrderedFloat;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{JsonBranch, JsonMeasure, JsonProject, JsonTestbed, ReportUuid};
+
+use super::report::JsonReportFingerprint;
+
+/// `JsonPerfFingerprintsQueryParams` is the actual query parameters accepted by the server.
+/// It should always be converted into `JsonPerfFingerprintsQuery` for full type level validation.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfFingerprintsQueryParams {
+    /// The name, slug, or UUID for the branch.
+    /// The current head of the branch is used.
+    pub branch: NameId,
+    /// The name, slug, or UUID for the testbed.
+    /// If not provided, the reports for all testbeds are included.
+    pub testbed: Option<NameId>,
+    /// The name, slug, or UUID for the measure.
+    pub measure: NameId,
+    /// Search for metrics after the given date time in milliseconds.
+    pub start_time: Option<DateTimeMillis>,
+    /// Search for metrics before the given date time in milliseconds.
+    pub end_time: Option<DateTimeMillis>,
+}
+
+/// `JsonPerfFingerprintsQuery` is the full, strongly typed version of `JsonPerfFingerprintsQueryParams`.
+#[derive(Debug, Clone)]
+pub struct JsonPerfFingerprintsQuery {
+    pub branch: NameId,
+    pub testbed: Option<NameId>,
+    pub measure: NameId,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+}
+
+impl From<JsonPerfFingerprintsQueryParams> for JsonPerfFingerprintsQuery {
+    fn from(query_params: JsonPerfFingerprintsQueryParams) -> Self {
+        let JsonPerfFingerprintsQueryParams {
+            branch,
+            testbed,
+            measure,
+            start_time,
+            end_time,
+        } = query_params;
+        Self {
+            branch,
+            testbed,
+            measure,
+            start_time: start_time.map(Into::into),
+            end_time: end_time.map(Into::into),
+        }
+    }
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfFingerprints {
+    pub project: JsonProject,
+    pub branch: JsonBranch,
+    pub testbed: Option<JsonTestbed>,
+    pub measure: JsonMeasure,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    /// The variance of the metrics for each hardware fingerprint, ordered from the most to the least noisy.
+    pub fingerprints: Vec<JsonPerfFingerprint>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfFingerprint {
+    /// The hardware fingerprint.
+    /// Reports without a hardware fingerprint are grouped together, with all of the fingerprint fields unset.
+    pub fingerprint: JsonReportFingerprint,
+    /// The number of reports with the hardware fingerprint.
+    pub reports: u32,
+    /// The number of benchmarks with at least two metrics for the hardware fingerprint.
+    /// Only these benchmarks have a coefficient of variation.
+    pub benchmarks: u32,
+    /// The mean of the coefficient of variation for each benchmark.
+    /// The coefficient of variation is the sample standard deviation divided by the mean of the metrics for a benchmark.
+    pub mean_cv: Option<OrderedFloat<f64>>,
+    /// The median of the coefficient of variation for each benchmark.
+    pub median_cv: Option<OrderedFloat<f64>>,
+    /// The maximum of the coefficient of variation for each benchmark.
+    pub max_cv: Option<OrderedFloat<f64>>,
+}
+
+/// Group metrics by the hardware fingerprint of their report,
+/// and then find the variance of each benchmark for each fingerprint.
+#[derive(Debug, Default)]
+pub struct PerfFingerprintsBuilder {
+    fingerprints: BTreeMap<JsonReportFingerprint, FingerprintNode>,
+}
+
+#[derive(Debug, Default)]
+struct FingerprintNode {
+    reports: HashSet<ReportUuid>,
+    benchmarks: HashMap<String, Vec<f64>>,
+}
+
+impl PerfFingerprintsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_metric(
+        &mut self,
+        fingerprint: JsonReportFingerprint,
+        report: ReportUuid,
+        benchmark: &str,
+        value: f64,
+    ) {
+        let node = self.fingerprints.entry(fingerprint).or_default();
+        node.reports.insert(report);
+        if let Some(values) = node.benchmarks.get_mut(benchmark) {
+            values.push(value);
+        } else {
+            node.benchmarks.insert(benchmark.into(), vec![value]);
+        }
+    }
+
+    pub fn build(self) -> Vec<JsonPerfFingerprint> {
+        let mut fingerprints = self
+            .fingerprints
+            .into_iter()
+            .map(|(fingerprint, node)| node.into_json(fingerprint))
+            .collect::<Vec<_>>();
+        // The most noisy fingerprints are first, and fingerprints without a coefficient of variation are last
+        fingerprints.sort_by(|a, b| b.mean_cv.cmp(&a.mean_cv));
+        fingerprints
+    }
+}
+
+impl FingerprintNode {
+    fn into_json(self, fingerprint: JsonReportFingerprint) -> JsonPerfFingerprint {
+        let Self {
+            reports,
+            benchmarks,
+        } = self;
+        let mut cvs = benchmarks
+            .values()
+            .filter_map(|values| coefficient_of_variation(values))
+            .map(OrderedFloat)
+            .collect::<Vec<_>>();
+        cvs.sort_unstable();
+        #[allow(clippy::cast_precision_loss)]
+        let mean_cv = (!cvs.is_empty())
+            .then(|| cvs.iter().map(|cv| cv.0).sum::<f64>() / cvs.len() as f64)
+            .map(OrderedFloat);
+        let median_cv = median(&cvs);
+        let max_cv = cvs.last().copied();
+        JsonPerfFingerprint {
+            fingerprint,
+            reports: u32::try_from(reports.len()).unwrap_or(u32::MAX),
+            benchmarks: u32::try_from(cvs.len()).unwrap_or(u32::MAX),
+            mean_cv,
+            median_cv,
+            max_cv,
+        }
+    }
+}
+
+/// The sample standard deviation divided by the absolute value of the mean.
+/// Returns `None` if there are fewer than two values or the mean is zero.
+#[allow(clippy::cast_precision_loss)]
+fn coefficient_of_variation(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+    let len = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / len;
+    if mean == 0.0 {
+        return None;
+    }
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (len - 1.0);
+    let cv = variance.sqrt() / mean.abs();
+    cv.is_finite().then_some(cv)
+}
+
+/// The median of sorted values
+fn median(sorted: &[OrderedFloat<f64>]) -> Option<OrderedFloat<f64>> {
+    #[allow(clippy::integer_division)]
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        let low = sorted.get(mid.checked_sub(1)?)?;
+        let high = sorted.get(mid)?;
+        Some(OrderedFloat((low.0 + high.0) / 2.0))
+    } else {
+        sorted.get(mid).copied()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, clippy::indexing_slicing)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{project::report::JsonReportFingerprint, ReportUuid};
+
+    use super::{coefficient_of_variation, PerfFingerprintsBuilder};
+
+    fn fingerprint(cpu_model: &str, virtualized: bool) -> JsonReportFingerprint {
+        JsonReportFingerprint {
+            cpu_model: Some(cpu_model.parse().unwrap()),
+            governor: None,
+            virtualized: Some(virtualized),
+            hypervisor: None,
+        }
+    }
+
+    #[test]
+    fn test_coefficient_of_variation() {
+        assert_eq!(coefficient_of_variation(&[]), None);
+        assert_eq!(coefficient_of_variation(&[1.0]), None);
+        assert_eq!(coefficient_of_variation(&[0.0, 0.0]), None);
+        assert_eq!(coefficient_of_variation(&[5.0, 5.0, 5.0]), Some(0.0));
+        assert_eq!(
+            coefficient_of_variation(&[8.0, 12.0]),
+            Some(8.0f64.sqrt() / 10.0)
+        );
+    }
+
+    #[test]
+    fn test_perf_fingerprints() {
+        let mut builder = PerfFingerprintsBuilder::new();
+        let bare_metal = fingerprint("AMD EPYC 7763", false);
+        let cloud = fingerprint("Intel Xeon", true);
+        for (value, noise) in [(100.0, 1.0), (100.0, -1.0)] {
+            let report = ReportUuid::new();
+            builder.add_metric(bare_metal.clone(), report, "a", value + noise);
+            builder.add_metric(bare_metal.clone(), report, "b", value * 2.0 + noise);
+            let report = ReportUuid::new();
+            builder.add_metric(cloud.clone(), report, "a", value + noise * 20.0);
+            builder.add_metric(cloud.clone(), report, "b", value * 2.0 + noise * 20.0);
+        }
+        builder.add_metric(
+            JsonReportFingerprint::default(),
+            ReportUuid::new(),
+            "a",
+            100.0,
+        );
+        let fingerprints = builder.build();
+        assert_eq!(fingerprints.len(), 3);
+
+        let noisy = &fingerprints[0];
+        assert_eq!(noisy.fingerprint, cloud);
+        assert_eq!(noisy.reports, 2);
+        assert_eq!(noisy.benchmarks, 2);
+        assert_eq!(noisy.max_cv.unwrap().0, 800.0f64.sqrt() / 100.0);
+        assert_eq!(noisy.median_cv, noisy.mean_cv);
+
+        let quiet = &fingerprints[1];
+        assert_eq!(quiet.fingerprint, bare_metal);
+        assert!(quiet.mean_cv < noisy.mean_cv);
+
+        let unknown = &fingerprints[2];
+        assert!(unknown.fingerprint.is_empty());
+        assert_eq!(unknown.reports, 1);
+        assert_eq!(unknown.benchmarks, 0);
+        assert_eq!(unknown.mean_cv, None);
+    }
+}
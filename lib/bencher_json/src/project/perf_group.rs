@@ -0,0 +1,451 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bencher_valid::{DateTime, DateTimeMillis, NameId};
+use ordered_float::OrderedFloat;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    urlencoded::UrlEncodedError, JsonBranch, JsonMeasure, JsonProject, JsonTestbed, ReportUuid,
+};
+
+use super::{head::JsonVersion, report::Iteration};
+
+pub const DEFAULT_GROUP_SEPARATOR: &str = "::";
+
+/// `JsonPerfGroupsQueryParams` is the actual query parameters accepted by the server.
+/// It should always be converted into `JsonPerfGroupsQuery` for full type level validation.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfGroupsQueryParams {
+    /// The name, slug, or UUID for the branch.
+    /// The current head of the branch is used.
+    pub branch: NameId,
+    /// The name, slug, or UUID for the testbed.
+    pub testbed: NameId,
+    /// The name, slug, or UUID for the measure.
+    pub measure: NameId,
+    /// The separator used to split benchmark names into groups.
+    /// For example, `parser::json::large` is in the `parser` and `parser::json` groups.
+    /// If not provided, `::` is used.
+    pub separator: Option<String>,
+    /// Only include the benchmarks in this group, such as `parser::json`.
+    /// If not provided, all of the benchmarks are included.
+    pub group: Option<String>,
+    /// The maximum depth of the group tree below the root group.
+    /// Any deeper groups are rolled up into their ancestor at the maximum depth.
+    /// If not provided, the full group tree is returned.
+    pub depth: Option<u32>,
+    /// Search for metrics after the given date time in milliseconds.
+    pub start_time: Option<DateTimeMillis>,
+    /// Search for metrics before the given date time in milliseconds.
+    pub end_time: Option<DateTimeMillis>,
+}
+
+/// `JsonPerfGroupsQuery` is the full, strongly typed version of `JsonPerfGroupsQueryParams`.
+#[derive(Debug, Clone)]
+pub struct JsonPerfGroupsQuery {
+    pub branch: NameId,
+    pub testbed: NameId,
+    pub measure: NameId,
+    pub separator: String,
+    pub group: Option<String>,
+    pub depth: Option<u32>,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+}
+
+impl TryFrom<JsonPerfGroupsQueryParams> for JsonPerfGroupsQuery {
+    type Error = UrlEncodedError;
+
+    fn try_from(query_params: JsonPerfGroupsQueryParams) -> Result<Self, Self::Error> {
+        let JsonPerfGroupsQueryParams {
+            branch,
+            testbed,
+            measure,
+            separator,
+            group,
+            depth,
+            start_time,
+            end_time,
+        } = query_params;
+
+        let separator = separator.unwrap_or_else(|| DEFAULT_GROUP_SEPARATOR.into());
+        if separator.is_empty() {
+            return Err(UrlEncodedError::EmptySeparator);
+        }
+
+        Ok(Self {
+            branch,
+            testbed,
+            measure,
+            separator,
+            group: group.filter(|group| !group.is_empty()),
+            depth,
+            start_time: start_time.map(Into::into),
+            end_time: end_time.map(Into::into),
+        })
+    }
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfGroups {
+    pub project: JsonProject,
+    pub branch: JsonBranch,
+    pub testbed: JsonTestbed,
+    pub measure: JsonMeasure,
+    pub separator: String,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    /// The root of the benchmark group tree.
+    /// If a group was queried, then it is the root group.
+    /// Otherwise, the root group has an empty path and includes every benchmark.
+    pub group: JsonPerfGroup,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfGroup {
+    /// The last segment of the group path, such as `json`.
+    pub name: String,
+    /// The full group path, such as `parser::json`.
+    pub path: String,
+    /// The number of benchmarks in the group, including all of its subgroups.
+    pub benchmarks: u32,
+    /// The rollup of the metrics for every benchmark in the group, including all of its subgroups.
+    /// There is one rollup for each report iteration, ordered by version number, report start time, and iteration.
+    pub metrics: Vec<JsonPerfRollup>,
+    /// The subgroups of the group, ordered by name.
+    pub groups: Vec<JsonPerfGroup>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPerfRollup {
+    pub report: ReportUuid,
+    pub iteration: Iteration,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub version: JsonVersion,
+    /// The number of benchmarks in the group with a metric for the report iteration.
+    pub benchmarks: u32,
+    /// The mean of the metric values for the benchmarks.
+    pub mean: OrderedFloat<f64>,
+    /// The maximum of the metric values for the benchmarks.
+    pub max: OrderedFloat<f64>,
+}
+
+/// A single report iteration that metrics are rolled up for
+#[derive(Debug, Clone)]
+pub struct PerfRollupPoint {
+    pub report: ReportUuid,
+    pub iteration: Iteration,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub version: JsonVersion,
+}
+
+/// Build a benchmark group tree from benchmark names,
+/// rolling up the metrics for each group.
+#[derive(Debug)]
+pub struct PerfGroupsBuilder {
+    separator: String,
+    group: Option<String>,
+    depth: Option<usize>,
+    points: Vec<PerfRollupPoint>,
+    point_indices: HashMap<(ReportUuid, Iteration), usize>,
+    root: GroupNode,
+}
+
+#[derive(Debug, Default)]
+struct GroupNode {
+    benchmarks: u32,
+    rollups: BTreeMap<usize, Rollup>,
+    groups: BTreeMap<String, GroupNode>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rollup {
+    benchmarks: u32,
+    sum: f64,
+    max: f64,
+}
+
+impl PerfGroupsBuilder {
+    pub fn new(separator: &str, group: Option<&str>, depth: Option<u32>) -> Self {
+        Self {
+            separator: separator.into(),
+            group: group.map(Into::into),
+            depth: depth.and_then(|depth| usize::try_from(depth).ok()),
+            points: Vec::new(),
+            point_indices: HashMap::new(),
+            root: GroupNode::default(),
+        }
+    }
+
+    /// The group path segments for a benchmark name, relative to the root group.
+    /// The last segment of the benchmark name is the benchmark itself, so it is not a group.
+    /// Returns `None` if the benchmark is not in the root group.
+    fn group_segments<'n>(&self, name: &'n str) -> Option<Vec<&'n str>> {
+        let relative = if let Some(group) = &self.group {
+            name.strip_prefix(group.as_str())?
+                .strip_prefix(self.separator.as_str())?
+        } else {
+            name
+        };
+        let mut segments = relative.split(self.separator.as_str()).collect::<Vec<_>>();
+        segments.pop();
+        if let Some(depth) = self.depth {
+            segments.truncate(depth);
+        }
+        Some(segments)
+    }
+
+    /// Add a benchmark to the group tree.
+    /// Returns `false` if the benchmark is not in the root group.
+    pub fn add_benchmark(&mut self, name: &str) -> bool {
+        let Some(segments) = self.group_segments(name) else {
+            return false;
+        };
+        let mut node = &mut self.root;
+        node.benchmarks += 1;
+        for segment in segments {
+            node = node.groups.entry(segment.into()).or_default();
+            node.benchmarks += 1;
+        }
+        true
+    }
+
+    /// Add a benchmark metric to the rollup for each of the groups that the benchmark is in.
+    /// Metrics should be added in order, as the rollups are ordered by when their report iteration is first added.
+    pub fn add_metric(&mut self, name: &str, point: PerfRollupPoint, value: f64) {
+        let Some(segments) = self.group_segments(name) else {
+            return;
+        };
+        let key = (point.report, point.iteration);
+        let index = if let Some(index) = self.point_indices.get(&key) {
+            *index
+        } else {
+            let index = self.points.len();
+            self.points.push(point);
+            self.point_indices.insert(key, index);
+            index
+        };
+
+        let mut node = &mut self.root;
+        node.add_value(index, value);
+        for segment in segments {
+            node = node.groups.entry(segment.into()).or_default();
+            node.add_value(index, value);
+        }
+    }
+
+    pub fn build(self) -> JsonPerfGroup {
+        let Self {
+            separator,
+            group,
+            points,
+            root,
+            ..
+        } = self;
+        let path = group.unwrap_or_default();
+        let name = path
+            .rsplit(separator.as_str())
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+        root.into_json(&separator, &points, name, path)
+    }
+}
+
+impl GroupNode {
+    fn add_value(&mut self, index: usize, value: f64) {
+        self.rollups
+            .entry(index)
+            .and_modify(|rollup| {
+                rollup.benchmarks += 1;
+                rollup.sum += value;
+                rollup.max = rollup.max.max(value);
+            })
+            .or_insert(Rollup {
+                benchmarks: 1,
+                sum: value,
+                max: value,
+            });
+    }
+
+    fn into_json(
+        self,
+        separator: &str,
+        points: &[PerfRollupPoint],
+        name: String,
+        path: String,
+    ) -> JsonPerfGroup {
+        let Self {
+            benchmarks,
+            rollups,
+            groups,
+        } = self;
+        let metrics = rollups
+            .into_iter()
+            .filter_map(|(index, rollup)| {
+                let point = points.get(index)?.clone();
+                Some(JsonPerfRollup {
+                    report: point.report,
+                    iteration: point.iteration,
+                    start_time: point.start_time,
+                    end_time: point.end_time,
+                    version: point.version,
+                    benchmarks: rollup.benchmarks,
+                    mean: (rollup.sum / f64::from(rollup.benchmarks)).into(),
+                    max: rollup.max.into(),
+                })
+            })
+            .collect();
+        let groups = groups
+            .into_iter()
+            .map(|(name, node)| {
+                let path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{path}{separator}{name}")
+                };
+                node.into_json(separator, points, name, path)
+            })
+            .collect();
+        JsonPerfGroup {
+            name,
+            path,
+            benchmarks,
+            metrics,
+            groups,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp, clippy::indexing_slicing)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        project::{
+            head::{JsonVersion, VersionNumber},
+            report::Iteration,
+        },
+        DateTime, JsonPerfGroup, ReportUuid,
+    };
+
+    use super::{PerfGroupsBuilder, PerfRollupPoint};
+
+    const BENCHMARKS: [&str; 5] = [
+        "parser::json::small",
+        "parser::json::large",
+        "parser::toml::large",
+        "lexer::tokens",
+        "startup",
+    ];
+
+    fn point(report: ReportUuid) -> PerfRollupPoint {
+        PerfRollupPoint {
+            report,
+            iteration: Iteration(0),
+            start_time: DateTime::now(),
+            end_time: DateTime::now(),
+            version: JsonVersion {
+                number: VersionNumber(1),
+                hash: None,
+            },
+        }
+    }
+
+    fn build(group: Option<&str>, depth: Option<u32>) -> JsonPerfGroup {
+        let mut builder = PerfGroupsBuilder::new("::", group, depth);
+        for name in BENCHMARKS {
+            builder.add_benchmark(name);
+        }
+        let first = ReportUuid::new();
+        let second = ReportUuid::new();
+        for (value, name) in BENCHMARKS.into_iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let value = value as f64;
+            builder.add_metric(name, point(first), value);
+            builder.add_metric(name, point(second), value * 10.0);
+        }
+        builder.build()
+    }
+
+    fn paths(group: &JsonPerfGroup) -> Vec<String> {
+        std::iter::once(group.path.clone())
+            .chain(group.groups.iter().flat_map(paths))
+            .collect()
+    }
+
+    #[test]
+    fn test_perf_groups_tree() {
+        let root = build(None, None);
+        assert_eq!(root.path, "");
+        assert_eq!(root.benchmarks, 5);
+        assert_eq!(
+            paths(&root),
+            ["", "lexer", "parser", "parser::json", "parser::toml"]
+        );
+
+        let parser = &root.groups[1];
+        assert_eq!(parser.name, "parser");
+        assert_eq!(parser.benchmarks, 3);
+        assert_eq!(parser.metrics.len(), 2);
+        assert_eq!(parser.metrics[0].benchmarks, 3);
+        assert_eq!(parser.metrics[0].mean, 1.0);
+        assert_eq!(parser.metrics[0].max, 2.0);
+        assert_eq!(parser.metrics[1].mean, 10.0);
+        assert_eq!(parser.metrics[1].max, 20.0);
+
+        let json = &parser.groups[0];
+        assert_eq!(json.name, "json");
+        assert_eq!(json.benchmarks, 2);
+        assert_eq!(json.metrics[0].mean, 0.5);
+        assert_eq!(json.metrics[0].max, 1.0);
+        assert!(json.groups.is_empty());
+
+        assert_eq!(root.metrics[0].benchmarks, 5);
+        assert_eq!(root.metrics[0].mean, 2.0);
+        assert_eq!(root.metrics[0].max, 4.0);
+    }
+
+    #[test]
+    fn test_perf_groups_group() {
+        let parser = build(Some("parser"), None);
+        assert_eq!(parser.name, "parser");
+        assert_eq!(parser.path, "parser");
+        assert_eq!(parser.benchmarks, 3);
+        assert_eq!(paths(&parser), ["parser", "parser::json", "parser::toml"]);
+        assert_eq!(parser.metrics[0].mean, 1.0);
+
+        let json = build(Some("parser::json"), None);
+        assert_eq!(json.name, "json");
+        assert_eq!(json.benchmarks, 2);
+        assert!(json.groups.is_empty());
+
+        let none = build(Some("parse"), None);
+        assert_eq!(none.benchmarks, 0);
+        assert!(none.metrics.is_empty());
+    }
+
+    #[test]
+    fn test_perf_groups_depth() {
+        let root = build(None, Some(1));
+        assert_eq!(paths(&root), ["", "lexer", "parser"]);
+        assert_eq!(root.groups[1].benchmarks, 3);
+        assert_eq!(root.groups[1].metrics[0].mean, 1.0);
+
+        let root = build(None, Some(0));
+        assert_eq!(paths(&root), [""]);
+        assert_eq!(root.benchmarks, 5);
+    }
+}
@@ -1,17 +1,17 @@
 use std::{collections::HashMap, fmt};
 
-use bencher_valid::{DateTime, DateTimeMillis, GitHash, Model};
+use bencher_valid::{DateTime, DateTimeMillis, GitHash, Jwt, Model, NonEmpty, Slug, Url};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     urlencoded::{from_urlencoded, to_urlencoded, UrlEncodedError},
-    JsonAlert, JsonBenchmark, JsonBoundary, JsonBranch, JsonMeasure, JsonMetric, JsonProject,
-    JsonPubUser, JsonTestbed, NameId,
+    JsonAlert, JsonBenchmark, JsonBoundary, JsonBranch, JsonMeasure, JsonMetric, JsonNewMetric,
+    JsonProject, JsonPubUser, JsonResultsMap, JsonTestbed, NameId,
 };
 
-use super::{branch::JsonUpdateStartPoint, threshold::JsonThresholdModel};
+use super::{branch::JsonUpdateStartPoint, head::VersionNumber, threshold::JsonThresholdModel};
 
 crate::typed_uuid::typed_uuid!(ReportUuid);
 
@@ -35,6 +35,15 @@ pub struct JsonNewReport {
     /// For example, pull request branches often use their base branch as their start point branch.
     /// If a new branch is created, it is not kept in sync with the start point branch.
     pub start_point: Option<JsonUpdateStartPoint>,
+    /// Reference branch UUID, slug, or name.
+    /// If provided, the baseline for the thresholds is calculated from the historical metrics
+    /// of the reference branch instead of the report branch.
+    /// The current head of the reference branch is always used, so the baseline moves along with it.
+    /// If the report branch does not have a threshold for a testbed and measure,
+    /// then the reference branch threshold is used.
+    /// For example, a fork can compare against the `main` branch of its upstream project.
+    /// The reference branch must already exist.
+    pub reference_branch: Option<NameId>,
     /// Testbed UUID, slug, or name.
     /// If the testbed does not exist, it will be created.
     pub testbed: NameId,
@@ -47,10 +56,161 @@ pub struct JsonNewReport {
     pub start_time: DateTime,
     /// End time for the report. Must be an ISO 8601 formatted string.
     pub end_time: DateTime,
+    /// A release tag for the report, such as `v1.42.0`.
+    /// Reports can be filtered by their tag, and perf queries can be limited to the reports for specific tags.
+    /// This makes it possible to compare releases without knowing the exact dates or report UUIDs.
+    /// Unlike the `git` tag, this is set explicitly and does not need to point at the commit.
+    pub tag: Option<NonEmpty>,
     /// An array of benchmarks results.
     pub results: Vec<String>,
     /// Settings for how to handle the report.
     pub settings: Option<JsonReportSettings>,
+    /// Reject the report if it has any benchmarks or measures that do not already exist in the project.
+    /// The names are checked after any project rules and report hook are applied.
+    /// If the project is in strict mode, then the report is always checked.
+    pub strict: Option<bool>,
+    /// Warm-up iterations run before the benchmark results were collected.
+    /// Warm-up results are not recorded.
+    pub warmup: Option<JsonReportWarmup>,
+    /// Free-form notes to attach to the report, such as why the benchmarks were run.
+    pub notes: Option<Vec<NonEmpty>>,
+    /// Links to attach to the report, such as the CI job or a flamegraph artifact.
+    /// Only `http` and `https` links are allowed.
+    pub links: Option<Vec<Url>>,
+    /// Diagnostics for benchmark iterations that failed and were skipped.
+    /// Failed iterations do not have any results.
+    pub failures: Option<Vec<JsonReportFailure>>,
+    /// The `git` metadata for the working tree that the report was run from,
+    /// such as the `git` branch, tag, and whether there were uncommitted changes.
+    /// This is only used to correlate reports with releases and local runs,
+    /// and it does not affect the report branch or version.
+    pub git: Option<JsonReportGit>,
+    /// The hardware fingerprint of the machine that the report was run on,
+    /// such as the CPU model and whether it is a virtual machine.
+    /// This is used to correlate the variance of the results with the hardware that they were run on.
+    pub fingerprint: Option<JsonReportFingerprint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewReports(pub Vec<JsonNewReport>);
+
+crate::from_vec!(JsonNewReports[JsonNewReport]);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewReportShard {
+    /// The key for the logical run that the shard is a part of, such as the CI run ID.
+    /// All of the shards for a run must use the same key.
+    pub key: NonEmpty,
+    /// The one-based index of the shard, from `1` to `count`.
+    pub index: u32,
+    /// The total number of shards for the run.
+    pub count: u32,
+    /// The partial report for the shard.
+    /// All of the shards for a run must use the same branch, hash, and testbed.
+    /// The start point, reference branch, thresholds, and warm-up of the first shard are used for the merged report.
+    pub report: JsonNewReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonReportShard {
+    pub key: NonEmpty,
+    pub index: u32,
+    pub count: u32,
+    /// The number of shards for the run that have been received so far.
+    pub received: u32,
+    /// The merged report, once all of the shards for the run have been received.
+    pub report: Option<JsonReport>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReportShardError {
+    #[error("There are no report shards to merge")]
+    Empty,
+    #[error("All of the report shards must use the same branch ({branch}), hash ({hash}), and testbed ({testbed})")]
+    Mismatch {
+        branch: NameId,
+        hash: String,
+        testbed: NameId,
+    },
+}
+
+impl JsonNewReport {
+    /// The total number of bytes of raw benchmark results in the report.
+    pub fn results_bytes(&self) -> usize {
+        self.results.iter().map(String::len).sum()
+    }
+
+    /// Merge all of the shards for a run into a single report.
+    /// The first shard is used as the base report, spanning the start and end times of all of the shards.
+    /// The results of the rest of the shards are returned separately,
+    /// so they can be processed into the same report with their own settings.
+    pub fn merge_shards(shards: Vec<Self>) -> Result<(Self, Vec<Self>), ReportShardError> {
+        let mut shards = shards.into_iter();
+        let Some(mut report) = shards.next() else {
+            return Err(ReportShardError::Empty);
+        };
+        let shard_reports = shards.collect::<Vec<_>>();
+        for shard_report in &shard_reports {
+            if shard_report.branch != report.branch
+                || shard_report.hash != report.hash
+                || shard_report.testbed != report.testbed
+            {
+                return Err(ReportShardError::Mismatch {
+                    branch: report.branch,
+                    hash: report
+                        .hash
+                        .as_ref()
+                        .map_or_else(|| "none".to_owned(), ToString::to_string),
+                    testbed: report.testbed,
+                });
+            }
+            if shard_report.start_time < report.start_time {
+                report.start_time = shard_report.start_time;
+            }
+            if shard_report.end_time > report.end_time {
+                report.end_time = shard_report.end_time;
+            }
+            // The merged report is dirty if any of the shards were run from a dirty working tree
+            if shard_report.git.as_ref().and_then(|git| git.dirty) == Some(true) {
+                report.git.get_or_insert_with(Default::default).dirty = Some(true);
+            }
+            // The merged report is strict if any of the shards are strict
+            if shard_report.strict == Some(true) {
+                report.strict = Some(true);
+            }
+            // Shards may be run on different machines,
+            // so the fingerprint is only kept if all of the shards have the same one
+            if shard_report.fingerprint != report.fingerprint {
+                report.fingerprint = None;
+            }
+        }
+        Ok((report, shard_reports))
+    }
+}
+
+/// A report for a project that may not exist yet, submitted without authentication.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewRun {
+    /// The slug for the project.
+    /// If a project with the slug does not exist, then it is created as an unclaimed project.
+    /// If the project already exists, then it must be unclaimed.
+    pub project: Slug,
+    /// The report for the project.
+    pub report: JsonNewReport,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRun {
+    pub report: JsonReport,
+    /// A token to claim the project for an organization.
+    /// It is only returned when the unclaimed project is created.
+    /// It expires after 30 days, after which the unclaimed project is removed.
+    pub claim: Option<Jwt>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +238,94 @@ pub struct JsonReportSettings {
     /// Fold multiple results into a single result using the selected operation.
     /// This can be useful for taking the min, max, mean, or median of the benchmark results.
     pub fold: Option<JsonFold>,
+    /// Keep each result as its own iteration and then add an iteration with their central tendency and dispersion.
+    /// The standard deviation is stored alongside the mean, and the interquartile range alongside the median.
+    /// Each dispersion is stored as a separate measure with the same units as the original measure.
+    /// Only the central tendency and dispersion are checked against thresholds.
+    /// This can not be used with `fold`.
+    pub dispersion: Option<JsonDispersion>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonReportWarmup {
+    /// The number of warm-up iterations.
+    pub iterations: u32,
+    /// The total wall time of all warm-up iterations in nanoseconds.
+    pub wall_time: f64,
+}
+
+/// The most bytes for the release tag of a report
+pub const REPORT_TAG_MAX_LEN: usize = 255;
+
+/// The most bytes for each of the `git` metadata fields of a report
+pub const REPORT_GIT_MAX_LEN: usize = 255;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonReportGit {
+    /// The name of the checked out `git` branch.
+    /// This is not set for a detached `HEAD`.
+    pub branch: Option<NonEmpty>,
+    /// A `git` tag that points at the commit.
+    pub tag: Option<NonEmpty>,
+    /// Whether the working tree had uncommitted changes to tracked files.
+    pub dirty: Option<bool>,
+    /// The commit author, as `Name <email>`.
+    pub author: Option<NonEmpty>,
+    /// The commit time.
+    pub commit_time: Option<DateTime>,
+    /// The first line of the commit message.
+    pub subject: Option<NonEmpty>,
+}
+
+impl JsonReportGit {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// The most bytes for each of the hardware fingerprint fields of a report
+pub const REPORT_FINGERPRINT_MAX_LEN: usize = 255;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonReportFingerprint {
+    /// The CPU model name, such as `AMD EPYC 7763 64-Core Processor`.
+    pub cpu_model: Option<NonEmpty>,
+    /// The CPU frequency scaling governor, such as `performance` or `powersave`.
+    pub governor: Option<NonEmpty>,
+    /// Whether the machine is a virtual machine.
+    pub virtualized: Option<bool>,
+    /// Hints about the hypervisor or platform, such as `KVM` or `Amazon EC2`.
+    pub hypervisor: Option<NonEmpty>,
+}
+
+impl JsonReportFingerprint {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// The most bytes of standard error that may be attached to a report failure
+pub const REPORT_FAILURE_STDERR_MAX_LEN: usize = 4096;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonReportFailure {
+    /// The zero-based index of the benchmark iteration that failed.
+    pub iteration: u32,
+    /// The exit code of the benchmark command.
+    pub exit_code: i32,
+    /// The wall time of the failed iteration in nanoseconds.
+    pub wall_time: f64,
+    /// The end of the standard error output of the benchmark command.
+    /// At most `4096` bytes are kept.
+    pub stderr: Option<String>,
 }
 
 const MAGIC_INT: i32 = 0;
@@ -99,16 +347,20 @@ const C_SHARP_DOT_NET_INT: i32 = 61;
 const JS_INT: i32 = 70;
 const JS_BENCHMARK_INT: i32 = 71;
 const JS_TIME_INT: i32 = 72;
+const JS_NODE_TEST_INT: i32 = 73;
+const JS_TINYBENCH_INT: i32 = 74;
 const PYTHON_INT: i32 = 80;
 const PYTHON_ASV_INT: i32 = 81;
 const PYTHON_PYTEST_INT: i32 = 82;
+const PYTHON_PYPERF_INT: i32 = 83;
+const PYTHON_RICHBENCH_INT: i32 = 84;
 const RUBY_INT: i32 = 90;
 const RUBY_BENCHMARK_INT: i32 = 91;
 const SHELL_INT: i32 = 100;
 const SHELL_HYPERFINE_INT: i32 = 101;
 
 #[typeshare::typeshare]
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
 #[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
@@ -135,9 +387,13 @@ pub enum Adapter {
     Js = JS_INT,
     JsBenchmark = JS_BENCHMARK_INT,
     JsTime = JS_TIME_INT,
+    JsNodeTest = JS_NODE_TEST_INT,
+    JsTinybench = JS_TINYBENCH_INT,
     Python = PYTHON_INT,
     PythonAsv = PYTHON_ASV_INT,
     PythonPytest = PYTHON_PYTEST_INT,
+    PythonPyperf = PYTHON_PYPERF_INT,
+    PythonRichbench = PYTHON_RICHBENCH_INT,
     Ruby = RUBY_INT,
     RubyBenchmark = RUBY_BENCHMARK_INT,
     Shell = SHELL_INT,
@@ -166,9 +422,13 @@ impl fmt::Display for Adapter {
             Self::Js => write!(f, "js"),
             Self::JsBenchmark => write!(f, "js_benchmark"),
             Self::JsTime => write!(f, "js_time"),
+            Self::JsNodeTest => write!(f, "js_node_test"),
+            Self::JsTinybench => write!(f, "js_tinybench"),
             Self::Python => write!(f, "python"),
             Self::PythonAsv => write!(f, "python_asv"),
             Self::PythonPytest => write!(f, "python_pytest"),
+            Self::PythonPyperf => write!(f, "python_pyperf"),
+            Self::PythonRichbench => write!(f, "python_richbench"),
             Self::Ruby => write!(f, "ruby"),
             Self::RubyBenchmark => write!(f, "ruby_benchmark"),
             Self::Shell => write!(f, "shell"),
@@ -182,9 +442,10 @@ mod adapter {
     use super::{
         Adapter, CPP_CATCH2_INT, CPP_GOOGLE_INT, CPP_INT, C_SHARP_DOT_NET_INT, C_SHARP_INT,
         GO_BENCH_INT, GO_INT, JAVA_INT, JAVA_JMH_INT, JSON_INT, JS_BENCHMARK_INT, JS_INT,
-        JS_TIME_INT, MAGIC_INT, PYTHON_ASV_INT, PYTHON_INT, PYTHON_PYTEST_INT, RUBY_BENCHMARK_INT,
-        RUBY_INT, RUST_BENCH_INT, RUST_CRITERION_INT, RUST_IAI_CALLGRIND_INT, RUST_IAI_INT,
-        RUST_INT, SHELL_HYPERFINE_INT, SHELL_INT,
+        JS_NODE_TEST_INT, JS_TIME_INT, JS_TINYBENCH_INT, MAGIC_INT, PYTHON_ASV_INT, PYTHON_INT,
+        PYTHON_PYPERF_INT, PYTHON_PYTEST_INT, PYTHON_RICHBENCH_INT, RUBY_BENCHMARK_INT, RUBY_INT,
+        RUST_BENCH_INT, RUST_CRITERION_INT, RUST_IAI_CALLGRIND_INT, RUST_IAI_INT, RUST_INT,
+        SHELL_HYPERFINE_INT, SHELL_INT,
     };
 
     #[derive(Debug, thiserror::Error)]
@@ -222,9 +483,13 @@ mod adapter {
                 Self::Js => JS_INT.to_sql(out),
                 Self::JsBenchmark => JS_BENCHMARK_INT.to_sql(out),
                 Self::JsTime => JS_TIME_INT.to_sql(out),
+                Self::JsNodeTest => JS_NODE_TEST_INT.to_sql(out),
+                Self::JsTinybench => JS_TINYBENCH_INT.to_sql(out),
                 Self::Python => PYTHON_INT.to_sql(out),
                 Self::PythonAsv => PYTHON_ASV_INT.to_sql(out),
                 Self::PythonPytest => PYTHON_PYTEST_INT.to_sql(out),
+                Self::PythonPyperf => PYTHON_PYPERF_INT.to_sql(out),
+                Self::PythonRichbench => PYTHON_RICHBENCH_INT.to_sql(out),
                 Self::Ruby => RUBY_INT.to_sql(out),
                 Self::RubyBenchmark => RUBY_BENCHMARK_INT.to_sql(out),
                 Self::Shell => SHELL_INT.to_sql(out),
@@ -259,9 +524,13 @@ mod adapter {
                 JS_INT => Ok(Self::Js),
                 JS_BENCHMARK_INT => Ok(Self::JsBenchmark),
                 JS_TIME_INT => Ok(Self::JsTime),
+                JS_NODE_TEST_INT => Ok(Self::JsNodeTest),
+                JS_TINYBENCH_INT => Ok(Self::JsTinybench),
                 PYTHON_INT => Ok(Self::Python),
                 PYTHON_ASV_INT => Ok(Self::PythonAsv),
                 PYTHON_PYTEST_INT => Ok(Self::PythonPytest),
+                PYTHON_PYPERF_INT => Ok(Self::PythonPyperf),
+                PYTHON_RICHBENCH_INT => Ok(Self::PythonRichbench),
                 RUBY_INT => Ok(Self::Ruby),
                 RUBY_BENCHMARK_INT => Ok(Self::RubyBenchmark),
                 SHELL_INT => Ok(Self::Shell),
@@ -291,6 +560,43 @@ pub enum JsonFold {
     Median,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JsonDispersion {
+    /// Sample standard deviation, stored alongside the mean
+    #[serde(rename = "stddev")]
+    StdDev,
+    /// Interquartile range, stored alongside the median
+    Iqr,
+}
+
+impl JsonDispersion {
+    /// The central tendency that the dispersion is measured around
+    pub fn central_tendency(self) -> JsonFold {
+        match self {
+            Self::StdDev => JsonFold::Mean,
+            Self::Iqr => JsonFold::Median,
+        }
+    }
+
+    /// Appended to the name of the original measure for the dispersion measure name
+    pub fn name_suffix(self) -> &'static str {
+        match self {
+            Self::StdDev => "Standard Deviation",
+            Self::Iqr => "Interquartile Range",
+        }
+    }
+
+    /// Appended to the slug of the original measure for the dispersion measure slug
+    pub fn slug_suffix(self) -> &'static str {
+        match self {
+            Self::StdDev => "stddev",
+            Self::Iqr => "iqr",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct JsonReports(pub Vec<JsonReport>);
@@ -302,18 +608,66 @@ crate::from_vec!(JsonReports[JsonReport]);
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct JsonReport {
     pub uuid: ReportUuid,
-    pub user: JsonPubUser,
+    /// The user that submitted the report.
+    /// Reports for unclaimed projects may be submitted anonymously.
+    pub user: Option<JsonPubUser>,
     pub project: JsonProject,
     pub branch: JsonBranch,
     pub testbed: JsonTestbed,
     pub start_time: DateTime,
     pub end_time: DateTime,
+    pub tag: Option<NonEmpty>,
+    pub warmup: Option<JsonReportWarmup>,
+    pub git: Option<JsonReportGit>,
+    pub fingerprint: Option<JsonReportFingerprint>,
+    pub notes: Option<Vec<NonEmpty>>,
+    pub links: Option<Vec<Url>>,
+    pub failures: Option<Vec<JsonReportFailure>>,
     pub adapter: Adapter,
     pub results: JsonReportResults,
     pub alerts: JsonReportAlerts,
     pub created: DateTime,
 }
 
+impl JsonReport {
+    /// The results for each iteration of the report in Bencher Metric Format (BMF),
+    /// with each measure keyed by its slug.
+    /// This is the same format that the adapter parsed the benchmark harness output into.
+    pub fn results_map(&self) -> Vec<JsonResultsMap> {
+        self.results
+            .iter()
+            .map(|iteration| {
+                iteration
+                    .iter()
+                    .map(|result| {
+                        let metrics = result
+                            .measures
+                            .iter()
+                            .map(|report_measure| {
+                                let JsonMetric {
+                                    value,
+                                    lower_value,
+                                    upper_value,
+                                    ..
+                                } = report_measure.metric;
+                                (
+                                    report_measure.measure.slug.clone().into(),
+                                    JsonNewMetric {
+                                        value,
+                                        lower_value,
+                                        upper_value,
+                                    },
+                                )
+                            })
+                            .collect();
+                        (result.benchmark.name.clone(), metrics)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
 #[typeshare::typeshare]
 pub type JsonReportResults = Vec<JsonReportIteration>;
 
@@ -337,6 +691,8 @@ pub struct JsonReportMeasure {
     pub metric: JsonMetric,
     pub threshold: Option<JsonThresholdModel>,
     pub boundary: Option<JsonBoundary>,
+    /// When the measure of the benchmark was detected as flaky, if it is flaky.
+    pub flaky: Option<DateTime>,
 }
 
 #[typeshare::typeshare]
@@ -349,10 +705,19 @@ pub struct JsonReportQueryParams {
     pub branch: Option<String>,
     /// Filter by testbed UUID, slug, or name exact match.
     pub testbed: Option<String>,
+    /// Filter by release tag exact match.
+    pub tag: Option<String>,
     /// Filter for reports after the given date time in milliseconds.
     pub start_time: Option<DateTimeMillis>,
     /// Filter for reports before the given date time in milliseconds.
     pub end_time: Option<DateTimeMillis>,
+    /// Filter by git hash.
+    /// A short hash of at least 4 hexadecimal characters matches any hash that starts with it.
+    pub hash: Option<String>,
+    /// Filter for reports on or after the given branch version number.
+    pub start_version: Option<VersionNumber>,
+    /// Filter for reports on or before the given branch version number.
+    pub end_version: Option<VersionNumber>,
     /// If set to `true`, only return reports with an archived branch or testbed.
     /// If not set or set to `false`, only returns reports with non-archived branches and testbeds.
     pub archived: Option<bool>,
@@ -362,11 +727,20 @@ pub struct JsonReportQueryParams {
 pub struct JsonReportQuery {
     pub branch: Option<NameId>,
     pub testbed: Option<NameId>,
+    pub tag: Option<NonEmpty>,
     pub start_time: Option<DateTime>,
     pub end_time: Option<DateTime>,
+    pub hash: Option<String>,
+    pub start_version: Option<VersionNumber>,
+    pub end_version: Option<VersionNumber>,
     pub archived: Option<bool>,
 }
 
+/// The minimum length of a short git hash
+pub const GIT_HASH_PREFIX_MIN_LEN: usize = 4;
+// SHA-256 object IDs are 64 hexadecimal characters
+const GIT_HASH_MAX_LEN: usize = 64;
+
 impl TryFrom<JsonReportQueryParams> for JsonReportQuery {
     type Error = UrlEncodedError;
 
@@ -374,8 +748,12 @@ impl TryFrom<JsonReportQueryParams> for JsonReportQuery {
         let JsonReportQueryParams {
             branch,
             testbed,
+            tag,
             start_time,
             end_time,
+            hash,
+            start_version,
+            end_version,
             archived,
         } = query_params;
 
@@ -389,12 +767,27 @@ impl TryFrom<JsonReportQueryParams> for JsonReportQuery {
         } else {
             None
         };
+        let tag = if let Some(tag) = tag {
+            Some(from_urlencoded(&tag)?)
+        } else {
+            None
+        };
+        let hash = hash.map(|hash| git_hash_prefix(&hash)).transpose()?;
+        if let (Some(start), Some(end)) = (start_version, end_version) {
+            if start.0 > end.0 {
+                return Err(UrlEncodedError::VersionRange { start, end });
+            }
+        }
 
         Ok(Self {
             branch,
             testbed,
+            tag,
             start_time: start_time.map(Into::into),
             end_time: end_time.map(Into::into),
+            hash,
+            start_version,
+            end_version,
             archived,
         })
     }
@@ -409,6 +802,10 @@ impl JsonReportQuery {
         self.testbed.as_ref().map(to_urlencoded)
     }
 
+    pub fn tag(&self) -> Option<String> {
+        self.tag.as_ref().map(to_urlencoded)
+    }
+
     pub fn start_time(&self) -> Option<DateTimeMillis> {
         self.start_time.map(Into::into)
     }
@@ -418,6 +815,21 @@ impl JsonReportQuery {
     }
 }
 
+/// Whether the hash is a full git hash or a short hash of at least `GIT_HASH_PREFIX_MIN_LEN` characters.
+pub fn is_valid_git_hash_prefix(hash: &str) -> bool {
+    (GIT_HASH_PREFIX_MIN_LEN..=GIT_HASH_MAX_LEN).contains(&hash.len())
+        && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Validate a full or short git hash, and normalize it to lowercase.
+fn git_hash_prefix(hash: &str) -> Result<String, UrlEncodedError> {
+    if is_valid_git_hash_prefix(hash) {
+        Ok(hash.to_ascii_lowercase())
+    } else {
+        Err(UrlEncodedError::GitHash(hash.into()))
+    }
+}
+
 #[typeshare::typeshare]
 #[derive(
     Debug, Clone, Copy, Default, PartialEq, Eq, Hash, derive_more::Display, Serialize, Deserialize,
@@ -468,3 +880,183 @@ mod iteration {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        JsonNewReport, JsonReportQuery, JsonReportQueryParams, ReportShardError, VersionNumber,
+    };
+
+    fn empty_query_params() -> JsonReportQueryParams {
+        JsonReportQueryParams {
+            branch: None,
+            testbed: None,
+            tag: None,
+            start_time: None,
+            end_time: None,
+            hash: None,
+            start_version: None,
+            end_version: None,
+            archived: None,
+        }
+    }
+
+    #[test]
+    fn test_report_query_hash() {
+        for (hash, expected) in [
+            ("1234", Some("1234")),
+            ("ABCDEF12", Some("abcdef12")),
+            (
+                "1234567890abcdef1234567890abcdef12345678",
+                Some("1234567890abcdef1234567890abcdef12345678"),
+            ),
+            ("123", None),
+            ("xyz123", None),
+            ("", None),
+        ] {
+            let query_params = JsonReportQueryParams {
+                hash: Some(hash.into()),
+                ..empty_query_params()
+            };
+            let query = JsonReportQuery::try_from(query_params).ok();
+            assert_eq!(
+                query.and_then(|query| query.hash).as_deref(),
+                expected,
+                "{hash}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_report_query_version_range() {
+        let query_params = JsonReportQueryParams {
+            start_version: Some(VersionNumber(2)),
+            end_version: Some(VersionNumber(5)),
+            ..empty_query_params()
+        };
+        assert!(JsonReportQuery::try_from(query_params).is_ok());
+
+        let query_params = JsonReportQueryParams {
+            start_version: Some(VersionNumber(5)),
+            end_version: Some(VersionNumber(5)),
+            ..empty_query_params()
+        };
+        assert!(JsonReportQuery::try_from(query_params).is_ok());
+
+        let query_params = JsonReportQueryParams {
+            start_version: Some(VersionNumber(5)),
+            end_version: Some(VersionNumber(2)),
+            ..empty_query_params()
+        };
+        assert!(JsonReportQuery::try_from(query_params).is_err());
+    }
+
+    fn shard(testbed: &str, start_time: &str, end_time: &str, extra: &str) -> JsonNewReport {
+        serde_json::from_str(&format!(
+            r#"{{
+                "branch": "main",
+                "hash": "1234567890abcdef1234567890abcdef12345678",
+                "testbed": "{testbed}",
+                "start_time": "{start_time}",
+                "end_time": "{end_time}",
+                "results": ["{{}}"]
+                {extra}
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_shards() {
+        let shards = vec![
+            shard(
+                "localhost",
+                "2024-01-01T00:00:02Z",
+                "2024-01-01T00:00:03Z",
+                r#", "fingerprint": { "cpu_model": "cpu" }"#,
+            ),
+            shard(
+                "localhost",
+                "2024-01-01T00:00:01Z",
+                "2024-01-01T00:00:02Z",
+                r#", "strict": true, "git": { "dirty": true }"#,
+            ),
+            shard(
+                "localhost",
+                "2024-01-01T00:00:02Z",
+                "2024-01-01T00:00:04Z",
+                r#", "fingerprint": { "cpu_model": "cpu" }"#,
+            ),
+        ];
+        let (report, shard_reports) = JsonNewReport::merge_shards(shards).unwrap();
+        assert_eq!(shard_reports.len(), 2);
+        assert_eq!(report.start_time.timestamp(), 1_704_067_201);
+        assert_eq!(report.end_time.timestamp(), 1_704_067_204);
+        assert_eq!(report.strict, Some(true));
+        assert_eq!(report.git.as_ref().and_then(|git| git.dirty), Some(true));
+        assert_eq!(report.fingerprint, None);
+        assert_eq!(
+            report.results_bytes()
+                + shard_reports
+                    .iter()
+                    .map(JsonNewReport::results_bytes)
+                    .sum::<usize>(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_merge_shards_same_fingerprint() {
+        let fingerprint = r#", "fingerprint": { "cpu_model": "cpu" }"#;
+        let shards = vec![
+            shard(
+                "localhost",
+                "2024-01-01T00:00:01Z",
+                "2024-01-01T00:00:02Z",
+                fingerprint,
+            ),
+            shard(
+                "localhost",
+                "2024-01-01T00:00:01Z",
+                "2024-01-01T00:00:02Z",
+                fingerprint,
+            ),
+        ];
+        let (report, _) = JsonNewReport::merge_shards(shards).unwrap();
+        assert_eq!(
+            report
+                .fingerprint
+                .and_then(|fingerprint| fingerprint.cpu_model)
+                .map(|cpu_model| cpu_model.to_string()),
+            Some("cpu".to_owned())
+        );
+        assert_eq!(report.strict, None);
+    }
+
+    #[test]
+    fn test_merge_shards_mismatch() {
+        let shards = vec![
+            shard(
+                "localhost",
+                "2024-01-01T00:00:01Z",
+                "2024-01-01T00:00:02Z",
+                "",
+            ),
+            shard("remote", "2024-01-01T00:00:01Z", "2024-01-01T00:00:02Z", ""),
+        ];
+        assert!(matches!(
+            JsonNewReport::merge_shards(shards),
+            Err(ReportShardError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_shards_empty() {
+        assert!(matches!(
+            JsonNewReport::merge_shards(Vec::new()),
+            Err(ReportShardError::Empty)
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+use bencher_valid::{DateTime, NonEmpty};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectUuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewReportHook {
+    /// The Rhai script that is run on the results of each report at ingest time.
+    /// The script may define any of these functions:
+    /// `benchmark(name)` returns the new name for a benchmark, or `()` to drop the benchmark.
+    /// `measure(benchmark, measure)` returns the new name for a measure of a benchmark, or `()` to drop the measure.
+    /// `notes(benchmarks)` is given an array of the benchmark names in the report
+    /// and returns a note or an array of notes to attach to the report.
+    /// Only these functions are run, so any top-level statements are ignored.
+    /// Maximum length is 65,536 bytes.
+    pub script: NonEmpty,
+}
+
+/// A report hook for a project.
+/// The hook script is run on the results of each report at ingest time.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonReportHook {
+    pub project: ProjectUuid,
+    pub script: NonEmpty,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+
+use bencher_valid::{DateTime, NonEmpty};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectUuid;
+
+crate::typed_uuid::typed_uuid!(RuleUuid);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewRule {
+    /// A regular expression to match against benchmark names.
+    /// Every match in a benchmark name is replaced with the `replacement`.
+    pub pattern: NonEmpty,
+    /// The replacement for each match of the `pattern`.
+    /// Capture groups from the `pattern` can be referenced with `$1`, `$2`, or `$name`.
+    /// Set to an empty string to remove the matched text.
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRules(pub Vec<JsonRule>);
+
+crate::from_vec!(JsonRules[JsonRule]);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRule {
+    pub uuid: RuleUuid,
+    pub project: ProjectUuid,
+    pub pattern: NonEmpty,
+    pub replacement: String,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl fmt::Display for JsonRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.pattern, self.replacement)
+    }
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonUpdateRule {
+    /// The new regular expression to match against benchmark names.
+    pub pattern: Option<NonEmpty>,
+    /// The new replacement for each match of the `pattern`.
+    pub replacement: Option<String>,
+}
@@ -0,0 +1,266 @@
+use bencher_valid::{BenchmarkName, BranchName, DateTime, Email, ResourceName, Weekday, Window};
+use chrono::{Datelike, NaiveDate};
+use ordered_float::OrderedFloat;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    project::{perf::JsonPerfMetrics, webhook::WebhookEvent},
+    BenchmarkUuid, BranchUuid, MeasureUuid, ProjectUuid, TestbedUuid,
+};
+
+crate::typed_uuid::typed_uuid!(SavedQueryUuid);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewSavedQuery {
+    /// The name of the saved query.
+    /// Maximum length is 64 characters.
+    pub name: ResourceName,
+    /// The window of time for the saved query, in seconds.
+    /// The window ends at the start of the day, in the project time zone, when the saved query is run.
+    pub window: Window,
+    /// The branches to include in the saved query.
+    /// At least one branch must be specified.
+    pub branches: Vec<BranchUuid>,
+    /// The testbeds to include in the saved query.
+    /// At least one testbed must be specified.
+    pub testbeds: Vec<TestbedUuid>,
+    /// The benchmarks to include in the saved query.
+    /// At least one benchmark must be specified.
+    pub benchmarks: Vec<BenchmarkUuid>,
+    /// The measures to include in the saved query.
+    /// At least one measure must be specified.
+    pub measures: Vec<MeasureUuid>,
+    /// How often to send a summary of the saved query.
+    /// If not provided, `never` is used.
+    pub schedule: Option<SavedQuerySchedule>,
+    /// The email addresses to send each summary to.
+    /// Each must belong to a member of the project or its organization.
+    pub emails: Option<Vec<Email>>,
+    /// Also send each summary to the project webhook.
+    pub webhook: Option<bool>,
+}
+
+/// The branch, testbed, benchmark, and measure UUIDs for a saved query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonSavedQueryDimensions {
+    pub branches: Vec<BranchUuid>,
+    pub testbeds: Vec<TestbedUuid>,
+    pub benchmarks: Vec<BenchmarkUuid>,
+    pub measures: Vec<MeasureUuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonSavedQueries(pub Vec<JsonSavedQuery>);
+
+crate::from_vec!(JsonSavedQueries[JsonSavedQuery]);
+
+/// A saved perf query for a project.
+/// A summary of the saved query can be sent on a schedule.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonSavedQuery {
+    pub uuid: SavedQueryUuid,
+    pub project: ProjectUuid,
+    pub name: ResourceName,
+    pub window: Window,
+    pub branches: Vec<BranchUuid>,
+    pub testbeds: Vec<TestbedUuid>,
+    pub benchmarks: Vec<BenchmarkUuid>,
+    pub measures: Vec<MeasureUuid>,
+    pub schedule: SavedQuerySchedule,
+    pub emails: Vec<Email>,
+    pub webhook: bool,
+    /// The time that the last scheduled summary was sent, if any.
+    pub last_sent: Option<DateTime>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonUpdateSavedQuery {
+    /// The new name of the saved query.
+    /// Maximum length is 64 characters.
+    pub name: Option<ResourceName>,
+    /// The new window of time for the saved query, in seconds.
+    pub window: Option<Window>,
+    /// The new schedule for sending a summary of the saved query.
+    pub schedule: Option<SavedQuerySchedule>,
+    /// The new email addresses to send each summary to.
+    /// Each must belong to a member of the project or its organization.
+    /// An empty list stops sending emails.
+    pub emails: Option<Vec<Email>>,
+    /// Whether to also send each summary to the project webhook.
+    pub webhook: Option<bool>,
+}
+
+const NEVER_INT: i32 = 0;
+const DAILY_INT: i32 = 1;
+const WEEKLY_INT: i32 = 2;
+
+/// How often a summary of a saved query is sent.
+#[typeshare::typeshare]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, derive_more::Display, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum SavedQuerySchedule {
+    /// A summary is never sent.
+    #[default]
+    #[display("never")]
+    Never = NEVER_INT,
+    /// A summary is sent every day.
+    #[display("daily")]
+    Daily = DAILY_INT,
+    /// A summary is sent once a week, on the first day of the reporting week for the project.
+    #[display("weekly")]
+    Weekly = WEEKLY_INT,
+}
+
+impl SavedQuerySchedule {
+    /// Whether a summary is due to be sent on the given date
+    pub fn is_due(self, date: NaiveDate, week_start: Weekday) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Daily => true,
+            Self::Weekly => date.weekday() == week_start.into(),
+        }
+    }
+}
+
+#[cfg(feature = "db")]
+mod saved_query_schedule {
+    use super::{SavedQuerySchedule, DAILY_INT, NEVER_INT, WEEKLY_INT};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum SavedQueryScheduleError {
+        #[error("Invalid saved query schedule value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for SavedQuerySchedule
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Never => NEVER_INT.to_sql(out),
+                Self::Daily => DAILY_INT.to_sql(out),
+                Self::Weekly => WEEKLY_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for SavedQuerySchedule
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                NEVER_INT => Ok(Self::Never),
+                DAILY_INT => Ok(Self::Daily),
+                WEEKLY_INT => Ok(Self::Weekly),
+                value => Err(Box::new(SavedQueryScheduleError::Invalid(value))),
+            }
+        }
+    }
+}
+
+/// A summary of the results of a saved query for a single
+/// branch, testbed, benchmark, and measure permutation.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonSavedQueryResult {
+    pub branch: BranchName,
+    pub testbed: ResourceName,
+    pub benchmark: BenchmarkName,
+    pub measure: ResourceName,
+    pub units: ResourceName,
+    /// The number of metrics in the window.
+    pub count: u32,
+    /// The value of the first metric in the window.
+    pub first: OrderedFloat<f64>,
+    /// The value of the latest metric in the window.
+    pub latest: OrderedFloat<f64>,
+    /// The percent change from the first to the latest metric value.
+    /// This is not included if the first value is zero.
+    pub change: Option<OrderedFloat<f64>>,
+}
+
+impl JsonSavedQueryResult {
+    /// Summarize the perf metrics, which are ordered from oldest to newest.
+    /// Returns `None` if there are no metrics.
+    pub fn new(perf_metrics: &JsonPerfMetrics) -> Option<Self> {
+        let first = perf_metrics.metrics.first()?.metric.value;
+        let latest = perf_metrics.metrics.last()?.metric.value;
+        let change = (first != OrderedFloat(0.0))
+            .then(|| OrderedFloat((latest.0 - first.0) / first.0.abs() * 100.0));
+        Some(Self {
+            branch: perf_metrics.branch.name.clone(),
+            testbed: perf_metrics.testbed.name.clone(),
+            benchmark: perf_metrics.benchmark.name.clone(),
+            measure: perf_metrics.measure.name.clone(),
+            units: perf_metrics.measure.units.clone(),
+            count: u32::try_from(perf_metrics.metrics.len()).unwrap_or(u32::MAX),
+            first,
+            latest,
+            change,
+        })
+    }
+}
+
+/// The JSON body sent to the project webhook for a saved query summary.
+/// It is signed with the webhook secret in the `X-Bencher-Signature-256` header.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonSavedQueryPayload {
+    pub event: WebhookEvent,
+    pub saved_query: SavedQueryUuid,
+    pub project: ProjectUuid,
+    pub name: ResourceName,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub results: Vec<JsonSavedQueryResult>,
+    /// A chart of the results as a base64 encoded JPEG image.
+    pub image: String,
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_valid::Weekday;
+    use chrono::NaiveDate;
+
+    use super::SavedQuerySchedule;
+
+    #[test]
+    fn test_saved_query_schedule_is_due() {
+        // 2024-11-18 is a Monday
+        let monday = NaiveDate::from_ymd_opt(2024, 11, 18).unwrap();
+        let wednesday = NaiveDate::from_ymd_opt(2024, 11, 20).unwrap();
+
+        assert!(!SavedQuerySchedule::Never.is_due(monday, Weekday::Monday));
+        assert!(SavedQuerySchedule::Daily.is_due(monday, Weekday::Monday));
+        assert!(SavedQuerySchedule::Daily.is_due(wednesday, Weekday::Monday));
+        assert!(SavedQuerySchedule::Weekly.is_due(monday, Weekday::Monday));
+        assert!(!SavedQuerySchedule::Weekly.is_due(wednesday, Weekday::Monday));
+        assert!(SavedQuerySchedule::Weekly.is_due(wednesday, Weekday::Wednesday));
+    }
+}
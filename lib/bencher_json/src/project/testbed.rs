@@ -1,12 +1,12 @@
 use std::fmt;
 
-use bencher_valid::{DateTime, ResourceName, Slug};
+use bencher_valid::{DateTime, NameId, NonEmpty, ResourceName, Slug};
 use once_cell::sync::Lazy;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::ProjectUuid;
+use crate::{BigInt, ProjectUuid};
 
 pub const TESTBED_LOCALHOST_STR: &str = "localhost";
 #[allow(clippy::expect_used)]
@@ -23,6 +23,12 @@ static TESTBED_LOCALHOST_SLUG: Lazy<Option<Slug>> = Lazy::new(|| {
             .expect("Failed to parse testbed slug."),
     )
 });
+#[allow(clippy::expect_used)]
+pub static TESTBED_LOCALHOST_NAME_ID: Lazy<NameId> = Lazy::new(|| {
+    TESTBED_LOCALHOST_STR
+        .parse()
+        .expect("Failed to parse testbed name ID.")
+});
 
 crate::typed_uuid::typed_uuid!(TestbedUuid);
 
@@ -37,6 +43,8 @@ pub struct JsonNewTestbed {
     /// If the provided or generated slug is already in use, a unique slug will be generated.
     /// Maximum length is 64 characters.
     pub slug: Option<Slug>,
+    /// The hardware specification of the testbed.
+    pub spec: Option<JsonTestbedSpec>,
 }
 
 impl JsonNewTestbed {
@@ -44,6 +52,7 @@ impl JsonNewTestbed {
         Self {
             name: TESTBED_LOCALHOST.clone(),
             slug: TESTBED_LOCALHOST_SLUG.clone(),
+            spec: None,
         }
     }
 }
@@ -62,6 +71,8 @@ pub struct JsonTestbed {
     pub project: ProjectUuid,
     pub name: ResourceName,
     pub slug: Slug,
+    /// The hardware specification of the testbed, if one has been declared.
+    pub spec: Option<JsonTestbedSpec>,
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
@@ -82,6 +93,197 @@ pub struct JsonUpdateTestbed {
     /// The preferred new slug for the testbed.
     /// Maximum length is 64 characters.
     pub slug: Option<Slug>,
+    /// The new hardware specification of the testbed.
+    /// This replaces the entire current specification.
+    /// Set to an empty object to remove the current specification.
+    pub spec: Option<JsonTestbedSpec>,
     /// Set whether the testbed is archived.
     pub archived: Option<bool>,
 }
+
+/// The detected RAM may differ from the declared RAM by up to `1 / TESTBED_MEMORY_TOLERANCE` of the declared RAM.
+/// The operating system reserves some RAM, so the detected RAM is usually a little less than what is installed.
+pub const TESTBED_MEMORY_TOLERANCE: u64 = 10;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonTestbedSpec {
+    /// The CPU model name, such as `AMD EPYC 7763 64-Core Processor`.
+    pub cpu: Option<NonEmpty>,
+    /// The number of logical CPU cores.
+    pub cores: Option<u32>,
+    /// The amount of RAM, in bytes.
+    pub memory: Option<BigInt>,
+    /// The operating system, such as `linux`, `macos`, or `windows`.
+    pub os: Option<NonEmpty>,
+}
+
+impl JsonTestbedSpec {
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_none() && self.cores.is_none() && self.memory.is_none() && self.os.is_none()
+    }
+
+    /// Compare this declared specification to the specification detected for the current machine.
+    /// Only the fields that are both declared and detected are compared.
+    /// The CPU model and operating system are compared ignoring case and whitespace,
+    /// and the RAM is compared within the `TESTBED_MEMORY_TOLERANCE`.
+    pub fn mismatches(&self, detected: &Self) -> Vec<TestbedSpecMismatch> {
+        let mut mismatches = Vec::new();
+        if let (Some(declared), Some(detected)) = (&self.cpu, &detected.cpu) {
+            if !eq_normalized(declared.as_ref(), detected.as_ref()) {
+                mismatches.push(TestbedSpecMismatch::new("CPU", declared, detected));
+            }
+        }
+        if let (Some(declared), Some(detected)) = (self.cores, detected.cores) {
+            if declared != detected {
+                mismatches.push(TestbedSpecMismatch::new("cores", declared, detected));
+            }
+        }
+        if let (Some(declared), Some(detected)) = (self.memory, detected.memory) {
+            let (declared, detected) = (u64::from(declared), u64::from(detected));
+            #[allow(clippy::integer_division)]
+            let tolerance = declared / TESTBED_MEMORY_TOLERANCE;
+            if declared.abs_diff(detected) > tolerance {
+                mismatches.push(TestbedSpecMismatch::new("RAM bytes", declared, detected));
+            }
+        }
+        if let (Some(declared), Some(detected)) = (&self.os, &detected.os) {
+            if !eq_normalized(declared.as_ref(), detected.as_ref()) {
+                mismatches.push(TestbedSpecMismatch::new("OS", declared, detected));
+            }
+        }
+        mismatches
+    }
+}
+
+fn eq_normalized(left: &str, right: &str) -> bool {
+    let normalize = |value: &str| value.split_whitespace().collect::<Vec<_>>().join(" ");
+    normalize(left).eq_ignore_ascii_case(&normalize(right))
+}
+
+/// A field of a declared testbed specification that does not match the current machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestbedSpecMismatch {
+    pub field: &'static str,
+    pub declared: String,
+    pub detected: String,
+}
+
+impl TestbedSpecMismatch {
+    fn new<D: fmt::Display>(field: &'static str, declared: D, detected: D) -> Self {
+        Self {
+            field,
+            declared: declared.to_string(),
+            detected: detected.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TestbedSpecMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{field} declared as ({declared}) but detected as ({detected})",
+            field = self.field,
+            declared = self.declared,
+            detected = self.detected
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{JsonTestbedSpec, TestbedSpecMismatch};
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn spec(
+        cpu: Option<&str>,
+        cores: Option<u32>,
+        memory: Option<u64>,
+        os: Option<&str>,
+    ) -> JsonTestbedSpec {
+        JsonTestbedSpec {
+            cpu: cpu.map(|cpu| cpu.parse().unwrap()),
+            cores,
+            memory: memory.map(Into::into),
+            os: os.map(|os| os.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_testbed_spec_matches() {
+        let declared = spec(
+            Some("AMD EPYC 7763 64-Core Processor"),
+            Some(4),
+            Some(16 * GIB),
+            Some("linux"),
+        );
+        let detected = spec(
+            Some("amd epyc  7763 64-core processor"),
+            Some(4),
+            // The operating system reserves some of the RAM
+            Some(15 * GIB),
+            Some("Linux"),
+        );
+        assert_eq!(declared.mismatches(&detected), Vec::new());
+
+        // Fields that are not declared or not detected are not compared
+        assert_eq!(declared.mismatches(&JsonTestbedSpec::default()), Vec::new());
+        assert_eq!(JsonTestbedSpec::default().mismatches(&detected), Vec::new());
+        assert!(JsonTestbedSpec::default().is_empty());
+        assert!(!declared.is_empty());
+    }
+
+    #[test]
+    fn test_testbed_spec_mismatches() {
+        let declared = spec(
+            Some("AMD EPYC 7763 64-Core Processor"),
+            Some(4),
+            Some(16 * GIB),
+            Some("linux"),
+        );
+        let detected = spec(
+            Some("Intel(R) Xeon(R) Platinum 8370C CPU @ 2.80GHz"),
+            Some(2),
+            Some(8 * GIB),
+            Some("macos"),
+        );
+        assert_eq!(
+            declared.mismatches(&detected),
+            vec![
+                TestbedSpecMismatch {
+                    field: "CPU",
+                    declared: "AMD EPYC 7763 64-Core Processor".to_owned(),
+                    detected: "Intel(R) Xeon(R) Platinum 8370C CPU @ 2.80GHz".to_owned(),
+                },
+                TestbedSpecMismatch {
+                    field: "cores",
+                    declared: "4".to_owned(),
+                    detected: "2".to_owned(),
+                },
+                TestbedSpecMismatch {
+                    field: "RAM bytes",
+                    declared: (16 * GIB).to_string(),
+                    detected: (8 * GIB).to_string(),
+                },
+                TestbedSpecMismatch {
+                    field: "OS",
+                    declared: "linux".to_owned(),
+                    detected: "macos".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            declared
+                .mismatches(&detected)
+                .get(1)
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("cores declared as (4) but detected as (2)")
+        );
+    }
+}
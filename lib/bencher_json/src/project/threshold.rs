@@ -152,6 +152,7 @@ pub struct JsonRemoveModel {
 }
 
 impl<'de> Deserialize<'de> for JsonUpdateThreshold {
+    #[allow(clippy::too_many_lines)]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -162,6 +163,7 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
         const WINDOW_FIELD: &str = "window";
         const LOWER_BOUNDARY_FIELD: &str = "lower_boundary";
         const UPPER_BOUNDARY_FIELD: &str = "upper_boundary";
+        const DECAY_FIELD: &str = "decay";
 
         const FIELDS: &[&str] = &[
             TEST_FIELD,
@@ -170,6 +172,7 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
             WINDOW_FIELD,
             LOWER_BOUNDARY_FIELD,
             UPPER_BOUNDARY_FIELD,
+            DECAY_FIELD,
         ];
 
         #[derive(Deserialize)]
@@ -181,6 +184,7 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
             Window,
             LowerBoundary,
             UpperBoundary,
+            Decay,
         }
 
         struct UpdateThresholdVisitor;
@@ -202,6 +206,7 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
                 let mut window = None;
                 let mut lower_boundary = None;
                 let mut upper_boundary = None;
+                let mut decay = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -241,6 +246,12 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
                             }
                             upper_boundary = Some(map.next_value()?);
                         },
+                        Field::Decay => {
+                            if decay.is_some() {
+                                return Err(de::Error::duplicate_field(DECAY_FIELD));
+                            }
+                            decay = Some(map.next_value()?);
+                        },
                     }
                 }
 
@@ -253,6 +264,7 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
                             window,
                             lower_boundary,
                             upper_boundary,
+                            decay,
                         },
                     })),
                     Some(None) => Ok(Self::Value::Remove(JsonRemoveModel { test: () })),
@@ -264,3 +276,25 @@ impl<'de> Deserialize<'de> for JsonUpdateThreshold {
         deserializer.deserialize_struct("JsonUpdateThreshold", FIELDS, UpdateThresholdVisitor)
     }
 }
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewRecompute {
+    /// Only recompute the boundaries for reports that started at or after this date time.
+    /// If not set, then the boundaries for the oldest reports are recomputed.
+    pub start_time: Option<DateTime>,
+    /// Only recompute the boundaries for reports that started at or before this date time.
+    /// If not set, then the boundaries for the newest reports are recomputed.
+    pub end_time: Option<DateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRecompute {
+    /// The threshold whose boundaries are being recomputed.
+    pub threshold: ThresholdUuid,
+    /// The start of the window of reports whose boundaries are being recomputed.
+    pub start_time: Option<DateTime>,
+    /// The end of the window of reports whose boundaries are being recomputed.
+    pub end_time: Option<DateTime>,
+}
@@ -0,0 +1,122 @@
+use bencher_valid::{BenchmarkName, BranchName, DateTime, ResourceName, Secret, Url};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    project::{
+        alert::{AlertSeverity, AlertStatus},
+        boundary::BoundaryLimit,
+    },
+    AlertGroupUuid, AlertUuid, ProjectUuid,
+};
+
+crate::typed_uuid::typed_uuid!(WebhookDeliveryUuid);
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewWebhook {
+    /// The URL that each new alert for the project is delivered to.
+    pub url: Url,
+}
+
+/// An alert webhook for a project.
+/// Each new alert for the project is delivered to the webhook URL.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhook {
+    pub project: ProjectUuid,
+    pub url: Url,
+    /// The secret used to sign each webhook delivery.
+    /// It is only returned when the webhook is created or replaced.
+    pub secret: Option<Secret>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookDeliveries(pub Vec<JsonWebhookDelivery>);
+
+crate::from_vec!(JsonWebhookDeliveries[JsonWebhookDelivery]);
+
+/// A delivery of an alert to the webhook of a project.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookDelivery {
+    pub uuid: WebhookDeliveryUuid,
+    pub project: ProjectUuid,
+    pub alert: AlertUuid,
+    /// The number of times that delivery has been attempted.
+    pub attempts: u32,
+    /// The HTTP status code of the response to the last attempt, if a response was received.
+    pub response_status: Option<u16>,
+    /// The error from the last attempt, if any.
+    pub last_error: Option<String>,
+    /// The time that the delivery ran out of attempts, if it has.
+    pub failed: Option<DateTime>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+/// The kind of event that a webhook delivery is for.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A new alert was created.
+    #[display("alert_created")]
+    AlertCreated,
+    /// A scheduled summary of a saved query was sent.
+    #[display("saved_query")]
+    SavedQuery,
+}
+
+/// The JSON body sent to the webhook URL.
+/// It is signed with the webhook secret in the `X-Bencher-Signature-256` header.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookPayload {
+    pub event: WebhookEvent,
+    pub delivery: WebhookDeliveryUuid,
+    pub project: ProjectUuid,
+    pub alert: AlertUuid,
+    pub severity: AlertSeverity,
+    pub status: AlertStatus,
+    pub limit: BoundaryLimit,
+    pub branch: BranchName,
+    pub testbed: ResourceName,
+    pub benchmark: BenchmarkName,
+    pub measure: ResourceName,
+    /// The correlated alert group, if multiple measures for the benchmark regressed together.
+    /// Only a single delivery is sent for a group, for its most severe alert,
+    /// and the other alerts in the group are included here.
+    pub group: Option<JsonWebhookAlertGroup>,
+}
+
+/// A group of correlated alerts for the measures of a single benchmark that regressed together.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookAlertGroup {
+    pub uuid: AlertGroupUuid,
+    /// The other alerts in the group.
+    pub alerts: Vec<JsonWebhookGroupAlert>,
+}
+
+/// An alert in a correlated alert group.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonWebhookGroupAlert {
+    pub alert: AlertUuid,
+    pub severity: AlertSeverity,
+    pub status: AlertStatus,
+    pub limit: BoundaryLimit,
+    pub measure: ResourceName,
+}
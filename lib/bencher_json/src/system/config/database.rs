@@ -11,6 +11,10 @@ pub struct JsonDatabase {
     pub file: PathBuf,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data_store: Option<DataStore>,
+    /// Log any pending database migrations on startup without applying them, and then exit.
+    /// This can be used to check what an upgrade will change before running it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migrations_dry_run: Option<bool>,
 }
 
 impl Sanitize for JsonDatabase {
@@ -0,0 +1,13 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonDownsample {
+    /// The number of months after which the metrics for a report are replaced with daily aggregates.
+    /// Each daily aggregate stores the mean of the values, lower values, and upper values, along with the min, max, and sample count. Metrics with an alert are never downsampled.
+    pub after_months: u32,
+    /// Only log the metrics that would be downsampled, without changing any data.
+    pub dry_run: Option<bool>,
+}
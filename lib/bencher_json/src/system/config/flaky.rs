@@ -0,0 +1,15 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonFlaky {
+    /// The maximum coefficient of variation (standard deviation divided by the mean)
+    /// before a benchmark is marked as flaky.
+    pub max_coefficient_of_variation: Option<f64>,
+    /// The minimum number of samples required before checking a benchmark for flakiness.
+    pub min_sample_size: Option<u32>,
+    /// Whether to still generate alerts for benchmarks that are marked as flaky.
+    pub alerts: Option<bool>,
+}
@@ -5,14 +5,21 @@ use serde::{Deserialize, Serialize};
 
 mod console;
 mod database;
+mod downsample;
+mod flaky;
 mod logging;
 mod plus;
 mod security;
 mod server;
+mod severity;
 mod smtp;
+mod statistics;
+mod telemetry;
 
 pub use console::JsonConsole;
 pub use database::{DataStore, JsonDatabase};
+pub use downsample::JsonDownsample;
+pub use flaky::JsonFlaky;
 pub use logging::{IfExists, JsonLogging, LogLevel, ServerLog};
 #[cfg(feature = "plus")]
 pub use plus::{
@@ -25,9 +32,12 @@ pub use plus::{
     stats::JsonStats,
     JsonPlus,
 };
-pub use security::JsonSecurity;
-pub use server::{JsonServer, JsonTls};
+pub use security::{JsonPreviousSecretKey, JsonSecurity};
+pub use server::{JsonServer, JsonSocket, JsonTls};
+pub use severity::JsonSeverity;
 pub use smtp::JsonSmtp;
+pub use statistics::JsonStatistics;
+pub use telemetry::JsonTelemetry;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -46,6 +56,16 @@ pub struct JsonConfig {
     pub database: JsonDatabase,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub smtp: Option<JsonSmtp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flaky: Option<JsonFlaky>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<JsonSeverity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<JsonStatistics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsample: Option<JsonDownsample>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<JsonTelemetry>,
     #[cfg(feature = "plus")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plus: Option<JsonPlus>,
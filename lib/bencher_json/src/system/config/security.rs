@@ -1,4 +1,4 @@
-use bencher_valid::{Sanitize, Secret};
+use bencher_valid::{DateTime, Sanitize, Secret};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,9 +9,28 @@ pub struct JsonSecurity {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub issuer: Option<String>,
     pub secret_key: Secret,
+    /// The secret key that was rotated out.
+    /// Tokens signed with it are still valid until it expires.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_secret_key: Option<JsonPreviousSecretKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonPreviousSecretKey {
+    pub secret_key: Secret,
+    /// The end of the grace period for the previous secret key.
+    pub expiration: DateTime,
 }
 
 impl Sanitize for JsonSecurity {
+    fn sanitize(&mut self) {
+        self.secret_key.sanitize();
+        self.previous_secret_key.sanitize();
+    }
+}
+
+impl Sanitize for JsonPreviousSecretKey {
     fn sanitize(&mut self) {
         self.secret_key.sanitize();
     }
@@ -9,8 +9,23 @@ use serde::{Deserialize, Serialize};
 pub struct JsonServer {
     pub bind_address: SocketAddr,
     pub request_body_max_bytes: usize,
+    /// The request header that holds the client IP address, set by a trusted reverse proxy.
+    /// For a list of addresses, such as `X-Forwarded-For`, the last address is used.
+    /// If not set, the IP address of the connection is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ip_header: Option<String>,
+    /// Allow reports to be submitted without authentication for projects that do not exist yet.
+    /// The project is created as an unclaimed project, which can later be claimed by an organization.
+    /// Defaults to `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unclaimed_projects: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tls: Option<JsonTls>,
+    /// Only accept connections on a Unix domain socket or a systemd activated socket.
+    /// When set, `bind_address` is ignored and the server is only bound to an ephemeral loopback port
+    /// that the socket forwards to. Any authenticated request that does not come through the socket is rejected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket: Option<JsonSocket>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,3 +41,20 @@ pub enum JsonTls {
         key: Vec<u8>,
     },
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonSocket {
+    /// Bind a Unix domain socket at the given path.
+    /// Any existing file at the path is replaced.
+    Unix {
+        path: PathBuf,
+        /// The file permissions for the socket, as a decimal number (ie `432` for `0o660`).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mode: Option<u32>,
+    },
+    /// Use the socket passed to the server by systemd socket activation.
+    /// Either a Unix domain socket or a TCP socket may be used.
+    Systemd,
+}
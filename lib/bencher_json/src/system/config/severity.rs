@@ -0,0 +1,16 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonSeverity {
+    /// The multiple of the boundary limit's distance from the baseline
+    /// that a metric must reach for its alert to be marked as major.
+    /// It must be greater than or equal to `1.0`.
+    pub major: Option<f64>,
+    /// The multiple of the boundary limit's distance from the baseline
+    /// that a metric must reach for its alert to be marked as critical.
+    /// It must be greater than or equal to the major threshold.
+    pub critical: Option<f64>,
+}
@@ -0,0 +1,14 @@
+use bencher_valid::SampleSize;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonStatistics {
+    /// The default minimum number of samples required in the baseline
+    /// before a statistical threshold model can generate an alert.
+    /// This is only used for threshold models that do not set their own minimum sample size.
+    /// Static threshold models are not affected.
+    pub min_sample_size: Option<SampleSize>,
+}
@@ -0,0 +1,15 @@
+use bencher_valid::Url;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonTelemetry {
+    /// Opt in to sending anonymous aggregate stats to the telemetry endpoint once a day.
+    /// This is disabled by default.
+    /// The exact stats that are sent can be viewed at `GET /v0/server/telemetry`.
+    pub enabled: Option<bool>,
+    /// The URL to send the telemetry stats to.
+    pub endpoint: Url,
+}
@@ -0,0 +1,28 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[allow(clippy::doc_markdown)]
+pub struct JsonDbCheck {
+    /// Whether the database passed all of the checks.
+    pub ok: bool,
+    /// The problems found by the SQLite integrity check.
+    /// This is empty if the integrity check passed.
+    pub integrity: Vec<String>,
+    /// The rows that reference a parent row that does not exist, grouped by table.
+    /// This is empty if the foreign key check passed.
+    pub orphans: Vec<JsonDbOrphans>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonDbOrphans {
+    /// The table with the orphaned rows.
+    pub table: String,
+    /// The parent table that the orphaned rows reference.
+    pub parent: String,
+    /// The number of orphaned rows.
+    pub count: u64,
+}
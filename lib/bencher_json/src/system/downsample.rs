@@ -0,0 +1,27 @@
+use bencher_valid::DateTime;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewDownsample {
+    /// Downsample metrics from reports that started more than this many months ago.
+    /// If not provided, the server `downsample` config is used.
+    pub after_months: Option<u32>,
+    /// Only count the metrics that would be downsampled, without changing any data.
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonDownsampled {
+    /// Metrics from reports that started before this time were downsampled.
+    pub cutoff: DateTime,
+    /// Whether this was a dry run that did not change any data.
+    pub dry_run: bool,
+    /// The number of daily aggregates.
+    pub aggregates: u32,
+    /// The number of metrics that were merged into the daily aggregates and removed.
+    pub removed: u32,
+}
@@ -0,0 +1,338 @@
+use std::{fmt, str::FromStr};
+
+/// The separator between the error kind and the resource in an error code,
+/// ie `not_found.benchmark`
+pub const ERROR_CODE_SEPARATOR: char = '.';
+
+/// A machine-readable error code returned by the API in the `error_code` field of an error response.
+/// It is made up of the kind of error and optionally the resource that the error is about,
+/// ie `bad_request` or `not_found.benchmark`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApiErrorCode {
+    pub kind: ApiErrorKind,
+    pub resource: Option<BencherResource>,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ApiErrorCodeError {
+    #[error("Invalid API error kind: {0}")]
+    Kind(String),
+    #[error("Invalid API error resource: {0}")]
+    Resource(String),
+}
+
+impl ApiErrorCode {
+    pub const fn new(kind: ApiErrorKind) -> Self {
+        Self {
+            kind,
+            resource: None,
+        }
+    }
+
+    pub const fn with_resource(kind: ApiErrorKind, resource: BencherResource) -> Self {
+        Self {
+            kind,
+            resource: Some(resource),
+        }
+    }
+
+    pub fn is_resource(&self, kind: ApiErrorKind, resource: BencherResource) -> bool {
+        self.kind == kind && self.resource == Some(resource)
+    }
+}
+
+impl fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(resource) = self.resource {
+            write!(
+                f,
+                "{kind}{ERROR_CODE_SEPARATOR}{resource}",
+                kind = self.kind,
+                resource = resource.code()
+            )
+        } else {
+            write!(f, "{}", self.kind)
+        }
+    }
+}
+
+impl FromStr for ApiErrorCode {
+    type Err = ApiErrorCodeError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let (kind, resource) = match code.split_once(ERROR_CODE_SEPARATOR) {
+            Some((kind, resource)) => (kind, Some(resource)),
+            None => (code, None),
+        };
+        Ok(Self {
+            kind: kind.parse()?,
+            resource: resource.map(BencherResource::from_code).transpose()?,
+        })
+    }
+}
+
+/// The kind of error returned by the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiErrorKind {
+    BadRequest,
+    Unauthorized,
+    PaymentRequired,
+    Forbidden,
+    NotFound,
+    NotAcceptable,
+    Conflict,
+    PayloadTooLarge,
+    Locked,
+    TooManyRequests,
+    Internal,
+}
+
+const BAD_REQUEST: &str = "bad_request";
+const UNAUTHORIZED: &str = "unauthorized";
+const PAYMENT_REQUIRED: &str = "payment_required";
+const FORBIDDEN: &str = "forbidden";
+const NOT_FOUND: &str = "not_found";
+const NOT_ACCEPTABLE: &str = "not_acceptable";
+const CONFLICT: &str = "conflict";
+const PAYLOAD_TOO_LARGE: &str = "payload_too_large";
+const LOCKED: &str = "locked";
+const TOO_MANY_REQUESTS: &str = "too_many_requests";
+const INTERNAL: &str = "internal";
+
+impl ApiErrorKind {
+    /// Get the error kind for an HTTP status code
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            401 => Self::Unauthorized,
+            402 => Self::PaymentRequired,
+            403 => Self::Forbidden,
+            404 => Self::NotFound,
+            406 => Self::NotAcceptable,
+            409 => Self::Conflict,
+            413 => Self::PayloadTooLarge,
+            423 => Self::Locked,
+            429 => Self::TooManyRequests,
+            400..=499 => Self::BadRequest,
+            _ => Self::Internal,
+        }
+    }
+
+    /// Get the HTTP status code for the error kind
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::BadRequest => 400,
+            Self::Unauthorized => 401,
+            Self::PaymentRequired => 402,
+            Self::Forbidden => 403,
+            Self::NotFound => 404,
+            Self::NotAcceptable => 406,
+            Self::Conflict => 409,
+            Self::PayloadTooLarge => 413,
+            Self::Locked => 423,
+            Self::TooManyRequests => 429,
+            Self::Internal => 500,
+        }
+    }
+}
+
+impl fmt::Display for ApiErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::BadRequest => BAD_REQUEST,
+                Self::Unauthorized => UNAUTHORIZED,
+                Self::PaymentRequired => PAYMENT_REQUIRED,
+                Self::Forbidden => FORBIDDEN,
+                Self::NotFound => NOT_FOUND,
+                Self::NotAcceptable => NOT_ACCEPTABLE,
+                Self::Conflict => CONFLICT,
+                Self::PayloadTooLarge => PAYLOAD_TOO_LARGE,
+                Self::Locked => LOCKED,
+                Self::TooManyRequests => TOO_MANY_REQUESTS,
+                Self::Internal => INTERNAL,
+            }
+        )
+    }
+}
+
+impl FromStr for ApiErrorKind {
+    type Err = ApiErrorCodeError;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        Ok(match kind {
+            BAD_REQUEST => Self::BadRequest,
+            UNAUTHORIZED => Self::Unauthorized,
+            PAYMENT_REQUIRED => Self::PaymentRequired,
+            FORBIDDEN => Self::Forbidden,
+            NOT_FOUND => Self::NotFound,
+            NOT_ACCEPTABLE => Self::NotAcceptable,
+            CONFLICT => Self::Conflict,
+            PAYLOAD_TOO_LARGE => Self::PayloadTooLarge,
+            LOCKED => Self::Locked,
+            TOO_MANY_REQUESTS => Self::TooManyRequests,
+            INTERNAL => Self::Internal,
+            _ => return Err(ApiErrorCodeError::Kind(kind.to_owned())),
+        })
+    }
+}
+
+macro_rules! bencher_resource {
+    ($($resource:ident => ($code:literal, $name:literal),)*) => {
+        /// A resource that an API error is about
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum BencherResource {
+            $($resource,)*
+        }
+
+        impl BencherResource {
+            /// The machine-readable code for the resource, used in error codes
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Self::$resource => $code,)*
+                }
+            }
+
+            pub fn from_code(code: &str) -> Result<Self, ApiErrorCodeError> {
+                match code {
+                    $($code => Ok(Self::$resource),)*
+                    _ => Err(ApiErrorCodeError::Resource(code.to_owned())),
+                }
+            }
+        }
+
+        impl fmt::Display for BencherResource {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "{}",
+                    match self {
+                        $(Self::$resource => $name,)*
+                    }
+                )
+            }
+        }
+    };
+}
+
+bencher_resource! {
+    Organization => ("organization", "Organization"),
+    OrganizationRole => ("organization_role", "Organization Role"),
+    Project => ("project", "Project"),
+    ProjectRole => ("project_role", "Project Role"),
+    UnclaimedProject => ("unclaimed_project", "Unclaimed Project"),
+    Report => ("report", "Report"),
+    ReportBenchmark => ("report_benchmark", "Report Benchmark"),
+    ReportFailure => ("report_failure", "Report Failure"),
+    ReportLink => ("report_link", "Report Link"),
+    ReportNote => ("report_note", "Report Note"),
+    ReportRaw => ("report_raw", "Report Raw Results"),
+    ReportHook => ("report_hook", "Report Hook"),
+    ReportShard => ("report_shard", "Report Shard"),
+    Plot => ("plot", "Plot"),
+    PlotBranch => ("plot_branch", "Plot Branch"),
+    PlotTestbed => ("plot_testbed", "Plot Testbed"),
+    PlotBenchmark => ("plot_benchmark", "Plot Benchmark"),
+    PlotMeasure => ("plot_measure", "Plot Measure"),
+    Branch => ("branch", "Branch"),
+    Head => ("head", "Head"),
+    Version => ("version", "Version"),
+    HeadVersion => ("head_version", "Head Version"),
+    Testbed => ("testbed", "Testbed"),
+    Benchmark => ("benchmark", "Benchmark"),
+    FlakyMeasure => ("flaky_measure", "Flaky Measure"),
+    Measure => ("measure", "Measure"),
+    MeasureAlias => ("measure_alias", "Measure Alias"),
+    Metric => ("metric", "Metric"),
+    Threshold => ("threshold", "Threshold"),
+    Model => ("model", "Model"),
+    Boundary => ("boundary", "Boundary"),
+    Alert => ("alert", "Alert"),
+    AlertGroup => ("alert_group", "Alert Group"),
+    Annotation => ("annotation", "Annotation"),
+    Rule => ("rule", "Rule"),
+    PerfEmbed => ("perf_embed", "Perf Embed"),
+    Expectation => ("expectation", "Expectation"),
+    GitHubWebhook => ("github_webhook", "GitHub Webhook"),
+    Webhook => ("webhook", "Webhook"),
+    WebhookDelivery => ("webhook_delivery", "Webhook Delivery"),
+    SavedQuery => ("saved_query", "Saved Query"),
+    User => ("user", "User"),
+    UserPrefs => ("user_prefs", "User Preferences"),
+    Token => ("token", "Token"),
+    TokenPolicy => ("token_policy", "Token Policy"),
+    Job => ("job", "Job"),
+    Plan => ("plan", "Plan"),
+    Server => ("server", "Server"),
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{ApiErrorCode, ApiErrorKind, BencherResource};
+
+    #[test]
+    fn test_api_error_code() {
+        let code = ApiErrorCode::new(ApiErrorKind::BadRequest);
+        assert_eq!(code.to_string(), "bad_request");
+        assert_eq!("bad_request".parse::<ApiErrorCode>().unwrap(), code);
+
+        let code = ApiErrorCode::with_resource(ApiErrorKind::NotFound, BencherResource::Benchmark);
+        assert_eq!(code.to_string(), "not_found.benchmark");
+        assert_eq!("not_found.benchmark".parse::<ApiErrorCode>().unwrap(), code);
+        assert!(code.is_resource(ApiErrorKind::NotFound, BencherResource::Benchmark));
+        assert!(!code.is_resource(ApiErrorKind::Conflict, BencherResource::Benchmark));
+
+        let code =
+            ApiErrorCode::with_resource(ApiErrorKind::Conflict, BencherResource::ReportShard);
+        assert_eq!(code.to_string(), "conflict.report_shard");
+        assert_eq!(
+            "conflict.report_shard".parse::<ApiErrorCode>().unwrap(),
+            code
+        );
+
+        assert!("".parse::<ApiErrorCode>().is_err());
+        assert!("not_found.".parse::<ApiErrorCode>().is_err());
+        assert!("not_found.bench".parse::<ApiErrorCode>().is_err());
+        assert!("nope".parse::<ApiErrorCode>().is_err());
+        // Error codes used to be UUIDs
+        assert!("8f2a7a0c-1c5b-4d3c-9b0a-6f7d2c4b1e3a"
+            .parse::<ApiErrorCode>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_api_error_kind_status() {
+        for kind in [
+            ApiErrorKind::BadRequest,
+            ApiErrorKind::Unauthorized,
+            ApiErrorKind::PaymentRequired,
+            ApiErrorKind::Forbidden,
+            ApiErrorKind::NotFound,
+            ApiErrorKind::NotAcceptable,
+            ApiErrorKind::Conflict,
+            ApiErrorKind::PayloadTooLarge,
+            ApiErrorKind::Locked,
+            ApiErrorKind::TooManyRequests,
+            ApiErrorKind::Internal,
+        ] {
+            assert_eq!(ApiErrorKind::from_status(kind.status()), kind);
+            assert_eq!(kind.to_string().parse::<ApiErrorKind>().unwrap(), kind);
+        }
+        assert_eq!(ApiErrorKind::from_status(418), ApiErrorKind::BadRequest);
+        assert_eq!(ApiErrorKind::from_status(503), ApiErrorKind::Internal);
+    }
+
+    #[test]
+    fn test_bencher_resource() {
+        let resource = BencherResource::OrganizationRole;
+        assert_eq!(resource.to_string(), "Organization Role");
+        assert_eq!(resource.code(), "organization_role");
+        assert_eq!(
+            BencherResource::from_code("organization_role").unwrap(),
+            resource
+        );
+    }
+}
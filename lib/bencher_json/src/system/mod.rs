@@ -1,8 +1,14 @@
 pub mod auth;
 pub mod backup;
 pub mod config;
+pub mod db;
+pub mod downsample;
+pub mod error;
 pub mod payment;
+pub mod rbac;
 pub mod restart;
+pub mod secret;
 pub mod server;
 pub mod spec;
+pub mod telemetry;
 pub mod version;
@@ -0,0 +1,108 @@
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The permission matrix for every API endpoint.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRbac {
+    pub endpoints: Vec<JsonRbacEndpoint>,
+}
+
+impl JsonRbac {
+    /// The endpoints that do not have their authorization mapped in the permission matrix.
+    pub fn unmapped(&self) -> impl Iterator<Item = &JsonRbacEndpoint> {
+        self.endpoints
+            .iter()
+            .filter(|endpoint| matches!(endpoint.access, JsonRbacAccess::Unmapped))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[allow(clippy::doc_markdown)]
+pub struct JsonRbacEndpoint {
+    /// The HTTP method for the endpoint.
+    pub method: String,
+    /// The path for the endpoint.
+    pub path: String,
+    /// The OpenAPI operation ID for the endpoint.
+    pub operation_id: String,
+    /// The kind of authorization required by the endpoint.
+    pub access: JsonRbacAccess,
+    /// The permission that is checked, if any.
+    pub permission: Option<String>,
+    /// The roles that are allowed to use the endpoint,
+    /// as `resource:role` (ie `server:admin` or `project:developer`).
+    /// Empty if the endpoint is public.
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JsonRbacAccess {
+    /// No authentication required
+    Public,
+    /// Any authenticated user
+    User,
+    /// Only the user themselves or a server admin
+    SameUser,
+    /// Only a server admin
+    Admin,
+    /// A permission on an organization
+    Organization,
+    /// A permission on a project
+    Project,
+    /// No authentication required for public projects,
+    /// otherwise a permission on the project
+    PublicProject,
+    /// The authorization for the endpoint has not been mapped
+    Unmapped,
+}
+
+#[cfg(feature = "table")]
+pub mod table {
+    use tabled::{Table, Tabled};
+
+    use super::{JsonRbac, JsonRbacAccess};
+
+    impl From<JsonRbac> for Table {
+        fn from(json_rbac: JsonRbac) -> Self {
+            Self::new(json_rbac.endpoints.into_iter().map(|endpoint| RbacTable {
+                method: endpoint.method,
+                path: endpoint.path,
+                access: access_str(endpoint.access).to_owned(),
+                permission: endpoint.permission.unwrap_or_default(),
+                roles: endpoint.roles.join(", "),
+            }))
+        }
+    }
+
+    #[derive(Tabled)]
+    pub struct RbacTable {
+        #[tabled(rename = "Method")]
+        pub method: String,
+        #[tabled(rename = "Path")]
+        pub path: String,
+        #[tabled(rename = "Access")]
+        pub access: String,
+        #[tabled(rename = "Permission")]
+        pub permission: String,
+        #[tabled(rename = "Roles")]
+        pub roles: String,
+    }
+
+    fn access_str(access: JsonRbacAccess) -> &'static str {
+        match access {
+            JsonRbacAccess::Public => "public",
+            JsonRbacAccess::User => "user",
+            JsonRbacAccess::SameUser => "same_user",
+            JsonRbacAccess::Admin => "admin",
+            JsonRbacAccess::Organization => "organization",
+            JsonRbacAccess::Project => "project",
+            JsonRbacAccess::PublicProject => "public_project",
+            JsonRbacAccess::Unmapped => "unmapped",
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use bencher_valid::DateTime;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRotateSecret {
+    /// The grace period in seconds that tokens signed with the previous secret key are still valid.
+    /// Defaults to 7 days, if not specified.
+    pub grace_period: Option<u32>,
+    /// The delay in seconds before the server restarts.
+    /// Defaults to 3 seconds, if not specified.
+    pub delay: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonSecretRotated {
+    /// The end of the grace period for the previous secret key.
+    /// Any token that is still needed after this time must be regenerated.
+    pub expiration: DateTime,
+}
@@ -1,16 +1,360 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+const OPENAPI_REF_PREFIX: &str = "#/components/schemas/";
+const JSON_SCHEMA_REF_PREFIX: &str = "#/definitions/";
+const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
-pub struct JsonSpec(pub serde_json::Value);
+pub struct JsonSpec(pub Value);
 
 impl JsonSpec {
     pub fn version(&self) -> Option<&str> {
         self.0
             .get("info")
             .and_then(|info| info.get("version"))
-            .and_then(serde_json::Value::as_str)
+            .and_then(Value::as_str)
+    }
+
+    fn title(&self) -> &str {
+        self.0
+            .get("info")
+            .and_then(|info| info.get("title"))
+            .and_then(Value::as_str)
+            .unwrap_or("Bencher API")
+    }
+
+    /// The schema for each API type, sorted by name
+    fn schemas(&self) -> Option<BTreeMap<&String, &Value>> {
+        self.0
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .map(|schemas| schemas.iter().collect())
+    }
+
+    /// A JSON Schema (draft-07) document with a definition for each API type.
+    /// The `OpenAPI` specific `nullable` keyword is converted to a `null` type.
+    pub fn to_json_schema(&self) -> Option<Value> {
+        let definitions = self
+            .schemas()?
+            .into_iter()
+            .map(|(name, schema)| (name.clone(), json_schema(schema.clone())))
+            .collect::<Map<String, Value>>();
+        Some(json!({
+            "$schema": JSON_SCHEMA_DRAFT,
+            "title": self.title(),
+            "version": self.version(),
+            "definitions": definitions,
+        }))
+    }
+
+    /// TypeScript type definitions for each API type
+    pub fn typescript(&self) -> Option<String> {
+        let schemas = self.schemas()?;
+        let mut typescript = format!(
+            "// Generated from the {title} v{version} OpenAPI specification\n",
+            title = self.title(),
+            version = self.version().unwrap_or_default()
+        );
+        for (name, schema) in schemas {
+            typescript.push('\n');
+            typescript.push_str(&ts_declaration(name, schema));
+        }
+        Some(typescript)
+    }
+}
+
+fn json_schema(schema: Value) -> Value {
+    match schema {
+        Value::Object(object) => {
+            let mut nullable = false;
+            let mut object = object
+                .into_iter()
+                .filter_map(|(key, value)| match (key.as_str(), value) {
+                    // Only the keyword is a boolean, a property named `nullable` is an object
+                    ("nullable", Value::Bool(is_nullable)) => {
+                        nullable = is_nullable;
+                        None
+                    },
+                    ("$ref", Value::String(reference)) => Some((
+                        key,
+                        Value::String(
+                            reference
+                                .strip_prefix(OPENAPI_REF_PREFIX)
+                                .map_or(reference.clone(), |name| {
+                                    format!("{JSON_SCHEMA_REF_PREFIX}{name}")
+                                }),
+                        ),
+                    )),
+                    (_, value) => Some((key, json_schema(value))),
+                })
+                .collect::<Map<String, Value>>();
+            if !nullable {
+                return Value::Object(object);
+            }
+            if let Some(Value::String(schema_type)) = object.get("type") {
+                let schema_type = schema_type.clone();
+                object.insert("type".to_owned(), json!([schema_type, "null"]));
+                if let Some(Value::Array(values)) = object.get_mut("enum") {
+                    values.push(Value::Null);
+                }
+                Value::Object(object)
+            } else {
+                json!({ "anyOf": [object, { "type": "null" }] })
+            }
+        },
+        Value::Array(array) => Value::Array(array.into_iter().map(json_schema).collect()),
+        value @ (Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_)) => value,
+    }
+}
+
+fn ts_declaration(name: &str, schema: &Value) -> String {
+    let mut declaration = ts_doc(schema, "");
+    if let Some(properties) = ts_properties(schema, "\t") {
+        declaration.push_str(&format!("export interface {name} {{\n{properties}}}\n"));
+    } else {
+        declaration.push_str(&format!("export type {name} = {};\n", ts_type(schema)));
+    }
+    declaration
+}
+
+fn ts_doc(schema: &Value, indent: &str) -> String {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(|description| {
+            format!(
+                "{indent}/** {description} */\n",
+                description = description.replace("*/", "*\\/")
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// The properties of an object schema, if it is a plain object
+fn ts_properties(schema: &Value, indent: &str) -> Option<String> {
+    if ["oneOf", "anyOf", "allOf", "enum"]
+        .iter()
+        .any(|keyword| schema.get(keyword).is_some())
+    {
+        return None;
+    }
+    let properties = schema.get("properties").and_then(Value::as_object)?;
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map_or_else(BTreeSet::new, |required| {
+            required.iter().filter_map(Value::as_str).collect()
+        });
+    let mut typescript = String::new();
+    for (property, property_schema) in properties.iter().collect::<BTreeMap<_, _>>() {
+        typescript.push_str(&ts_doc(property_schema, indent));
+        typescript.push_str(&format!(
+            "{indent}{property}{optional}: {property_type};\n",
+            property = ts_property_name(property),
+            optional = if required.contains(property.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            property_type = ts_type(property_schema)
+        ));
+    }
+    Some(typescript)
+}
+
+fn ts_property_name(property: &str) -> String {
+    let mut chars = property.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        property.to_owned()
+    } else {
+        format!("\"{property}\"")
+    }
+}
+
+fn ts_type(schema: &Value) -> String {
+    let Some(object) = schema.as_object() else {
+        return "unknown".to_owned();
+    };
+    let typescript = if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+        reference
+            .strip_prefix(OPENAPI_REF_PREFIX)
+            .unwrap_or(reference)
+            .to_owned()
+    } else if let Some(values) = object.get("enum").and_then(Value::as_array) {
+        ts_union(values.iter().map(Value::to_string))
+    } else if let Some(schemas) = object
+        .get("oneOf")
+        .or_else(|| object.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        ts_union(schemas.iter().map(ts_type))
+    } else if let Some(schemas) = object.get("allOf").and_then(Value::as_array) {
+        schemas.iter().map(ts_type).collect::<Vec<_>>().join(" & ")
+    } else {
+        match object.get("type") {
+            Some(Value::String(schema_type)) => ts_primitive(schema_type, schema),
+            Some(Value::Array(schema_types)) => ts_union(
+                schema_types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|schema_type| ts_primitive(schema_type, schema)),
+            ),
+            _ => "unknown".to_owned(),
+        }
+    };
+    if object.get("nullable").and_then(Value::as_bool) == Some(true) {
+        format!("{typescript} | null")
+    } else {
+        typescript
+    }
+}
+
+fn ts_union<I>(types: I) -> String
+where
+    I: Iterator<Item = String>,
+{
+    let mut union = Vec::new();
+    for ts_type in types {
+        if !union.contains(&ts_type) {
+            union.push(ts_type);
+        }
+    }
+    if union.is_empty() {
+        "never".to_owned()
+    } else {
+        union.join(" | ")
+    }
+}
+
+fn ts_primitive(schema_type: &str, schema: &Value) -> String {
+    match schema_type {
+        "string" => "string".to_owned(),
+        "integer" | "number" => "number".to_owned(),
+        "boolean" => "boolean".to_owned(),
+        "null" => "null".to_owned(),
+        "array" => {
+            let items = schema
+                .get("items")
+                .map_or_else(|| "unknown".to_owned(), ts_type);
+            if items.contains(' ') {
+                format!("({items})[]")
+            } else {
+                format!("{items}[]")
+            }
+        },
+        "object" => {
+            if let Some(properties) = ts_properties(schema, "") {
+                format!("{{ {} }}", properties.replace('\n', " ").trim_end())
+            } else if let Some(values) =
+                schema.get("additionalProperties").filter(|v| v.is_object())
+            {
+                format!("Record<string, {}>", ts_type(values))
+            } else {
+                "Record<string, unknown>".to_owned()
+            }
+        },
+        _ => "unknown".to_owned(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::indexing_slicing)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::JsonSpec;
+
+    fn spec() -> JsonSpec {
+        JsonSpec(json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Bencher API", "version": "1.2.3" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "JsonFold": {
+                        "type": "string",
+                        "enum": ["min", "max"]
+                    },
+                    "JsonThing": {
+                        "description": "A thing",
+                        "type": "object",
+                        "properties": {
+                            "name": { "description": "The name", "type": "string" },
+                            "count": { "nullable": true, "type": "integer", "format": "uint32" },
+                            "fold": {
+                                "nullable": true,
+                                "allOf": [{ "$ref": "#/components/schemas/JsonFold" }]
+                            },
+                            "tags": { "type": "array", "items": { "type": "string" } },
+                            "extra": {
+                                "type": "object",
+                                "additionalProperties": { "type": "number" }
+                            }
+                        },
+                        "required": ["name", "tags"]
+                    }
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_spec_typescript() {
+        assert_eq!(
+            spec().typescript().unwrap(),
+            r#"// Generated from the Bencher API v1.2.3 OpenAPI specification
+
+export type JsonFold = "min" | "max";
+
+/** A thing */
+export interface JsonThing {
+	count?: number | null;
+	extra?: Record<string, number>;
+	fold?: JsonFold | null;
+	/** The name */
+	name: string;
+	tags: string[];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_spec_json_schema() {
+        let json_schema = spec().to_json_schema().unwrap();
+        assert_eq!(
+            json_schema["$schema"],
+            json!("http://json-schema.org/draft-07/schema#")
+        );
+        assert_eq!(json_schema["version"], json!("1.2.3"));
+        let properties = &json_schema["definitions"]["JsonThing"]["properties"];
+        assert_eq!(properties["count"]["type"], json!(["integer", "null"]));
+        assert_eq!(
+            properties["fold"],
+            json!({
+                "anyOf": [
+                    { "allOf": [{ "$ref": "#/definitions/JsonFold" }] },
+                    { "type": "null" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_spec_missing_schemas() {
+        let spec = JsonSpec(json!({ "openapi": "3.0.3" }));
+        assert_eq!(spec.typescript(), None);
+        assert_eq!(spec.to_json_schema(), None);
     }
 }
@@ -0,0 +1,30 @@
+use bencher_valid::Url;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonServerTelemetry {
+    /// Whether the server has opted in to sending telemetry.
+    pub enabled: bool,
+    /// The URL that the telemetry stats are sent to, if the server has opted in.
+    pub endpoint: Option<Url>,
+    /// The exact stats that are sent.
+    pub stats: JsonTelemetryStats,
+}
+
+/// Anonymous aggregate stats for a server.
+/// They do not include any identifiers or names.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonTelemetryStats {
+    /// The API server version.
+    pub version: String,
+    /// The total number of projects.
+    pub projects: u64,
+    /// The total number of reports.
+    pub reports: u64,
+    /// The number of reports created in the last week.
+    pub weekly_reports: u64,
+}
@@ -1,9 +1,151 @@
+use std::{fmt, str::FromStr};
+
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// The vendor media type prefix used to request a specific API version,
+/// ie `application/vnd.bencher.v0.4.24+json`
+pub const BENCHER_MEDIA_TYPE_PREFIX: &str = "application/vnd.bencher.v";
+pub const BENCHER_MEDIA_TYPE_SUFFIX: &str = "+json";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct JsonApiVersion {
     pub version: String,
 }
+
+/// An API version requested by a client.
+/// If the patch version is omitted (ie `v0.4`), then the latest patch version is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ApiVersionError {
+    #[error("Invalid API version ({0}). Expected `MAJOR.MINOR` or `MAJOR.MINOR.PATCH`.")]
+    Version(String),
+    #[error("Invalid Bencher media type ({0}). Expected `{BENCHER_MEDIA_TYPE_PREFIX}MAJOR.MINOR.PATCH{BENCHER_MEDIA_TYPE_SUFFIX}`.")]
+    MediaType(String),
+}
+
+impl ApiVersion {
+    /// The first version to support the `ewma` Threshold Model Test
+    pub const V0_4_23: Self = Self::new(0, 4, 23);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Get the requested API version from an `Accept` header value, if any.
+    /// Media types other than the Bencher vendor media type are ignored.
+    pub fn from_accept(accept: &str) -> Result<Option<Self>, ApiVersionError> {
+        for media_type in accept.split(',') {
+            // Ignore any media type parameters, ie `;q=0.9`
+            let media_type = media_type.split(';').next().unwrap_or_default().trim();
+            let Some(version) = media_type.strip_prefix(BENCHER_MEDIA_TYPE_PREFIX) else {
+                continue;
+            };
+            let Some(version) = version.strip_suffix(BENCHER_MEDIA_TYPE_SUFFIX) else {
+                return Err(ApiVersionError::MediaType(media_type.to_owned()));
+            };
+            return version.parse().map(Some);
+        }
+        Ok(None)
+    }
+
+    pub fn media_type(&self) -> String {
+        format!("{BENCHER_MEDIA_TYPE_PREFIX}{self}{BENCHER_MEDIA_TYPE_SUFFIX}")
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = ApiVersionError;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let parse = |part: Option<&str>| {
+            part.and_then(|p| p.parse::<u32>().ok())
+                .ok_or_else(|| ApiVersionError::Version(version.to_owned()))
+        };
+        let mut parts = version.trim_start_matches('v').split('.');
+        let major = parse(parts.next())?;
+        let minor = parse(parts.next())?;
+        let patch = if let Some(patch) = parts.next() {
+            parse(Some(patch))?
+        } else {
+            u32::MAX
+        };
+        if parts.next().is_some() {
+            return Err(ApiVersionError::Version(version.to_owned()));
+        }
+        Ok(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.patch == u32::MAX {
+            write!(f, "{}.{}", self.major, self.minor)
+        } else {
+            write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::ApiVersion;
+
+    #[test]
+    fn test_api_version_parse() {
+        assert_eq!(
+            ApiVersion::new(0, 4, 24),
+            "0.4.24".parse::<ApiVersion>().unwrap()
+        );
+        assert_eq!(
+            ApiVersion::new(0, 4, u32::MAX),
+            "v0.4".parse::<ApiVersion>().unwrap()
+        );
+        assert!("0".parse::<ApiVersion>().is_err());
+        assert!("0.4.24.1".parse::<ApiVersion>().is_err());
+        assert!("zero.four".parse::<ApiVersion>().is_err());
+    }
+
+    #[test]
+    fn test_api_version_from_accept() {
+        assert_eq!(None, ApiVersion::from_accept("application/json").unwrap());
+        assert_eq!(None, ApiVersion::from_accept("*/*").unwrap());
+        assert_eq!(
+            Some(ApiVersion::new(0, 4, 23)),
+            ApiVersion::from_accept("application/vnd.bencher.v0.4.23+json").unwrap()
+        );
+        assert_eq!(
+            Some(ApiVersion::new(0, 4, u32::MAX)),
+            ApiVersion::from_accept("application/json;q=0.9, application/vnd.bencher.v0.4+json")
+                .unwrap()
+        );
+        assert!(ApiVersion::from_accept("application/vnd.bencher.v0.4.23").is_err());
+        assert!(ApiVersion::from_accept("application/vnd.bencher.vlatest+json").is_err());
+    }
+
+    #[test]
+    fn test_api_version_media_type() {
+        let version = ApiVersion::new(0, 4, 24);
+        assert_eq!("application/vnd.bencher.v0.4.24+json", version.media_type());
+        assert_eq!(
+            Some(version),
+            ApiVersion::from_accept(&version.media_type()).unwrap()
+        );
+        assert!(ApiVersion::new(0, 4, 22) < ApiVersion::V0_4_23);
+        assert!(ApiVersion::new(0, 4, u32::MAX) > ApiVersion::V0_4_23);
+    }
+}
@@ -3,6 +3,8 @@ use std::str::FromStr;
 use percent_encoding::{percent_decode, utf8_percent_encode, AsciiSet, CONTROLS};
 use thiserror::Error;
 
+use crate::project::{head::VersionNumber, measure::units::UnitError};
+
 // https://url.spec.whatwg.org/#fragment-percent-encode-set
 const FRAGMENT: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'<').add(b'>').add(b'`');
 
@@ -16,6 +18,10 @@ pub enum UrlEncodedError {
     EmptyBenchmarks,
     #[error("Empty `measures` parameter")]
     EmptyMeasures,
+    #[error("Empty `separator` parameter")]
+    EmptySeparator,
+    #[error("Units: {0}")]
+    Unit(#[from] UnitError),
     #[error("Empty value in list: {0}")]
     EmptyValue(String),
     #[error("JSON: {0}")]
@@ -38,6 +44,13 @@ pub enum UrlEncodedError {
     Timestamp(i64),
     #[error("Failed to decode urlencoded: {0}")]
     Utf8Error(#[from] std::str::Utf8Error),
+    #[error("Invalid git hash (must be 4 to 64 hexadecimal characters): {0}")]
+    GitHash(String),
+    #[error("The start version ({start}) is after the end version ({end})")]
+    VersionRange {
+        start: VersionNumber,
+        end: VersionNumber,
+    },
 }
 
 pub fn from_urlencoded_list<T>(list: &str) -> Result<Vec<T>, UrlEncodedError>
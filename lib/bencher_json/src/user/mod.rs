@@ -1,3 +1,4 @@
+pub mod prefs;
 pub mod token;
 use bencher_valid::{Email, Slug, UserName};
 
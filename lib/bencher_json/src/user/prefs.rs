@@ -0,0 +1,348 @@
+use bencher_valid::{DateTime, ResourceId, ResourceName, TimeZone};
+use ordered_float::OrderedFloat;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    project::measure::units::{MeasureUnit, UnitDimension, UnitError},
+    JsonBoundary, JsonMetric, JsonPerf, JsonReport, ProjectUuid, UserUuid,
+};
+
+/// The display preferences for a user.
+/// They are used when displaying dates and metrics,
+/// such as in the CLI table output and the report comment.
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonNewUserPrefs {
+    /// The time zone to display dates in, as an IANA time zone name (ie `America/New_York`).
+    /// If not set, dates are displayed in UTC.
+    pub time_zone: Option<TimeZone>,
+    /// The format to display dates in.
+    /// If not set, dates are displayed in RFC 3339 format.
+    pub date_format: Option<DateFormat>,
+    /// The units to display time measures in, such as `ms`.
+    pub time_units: Option<ResourceName>,
+    /// The units to display throughput measures in, such as `ops/s`.
+    pub throughput_units: Option<ResourceName>,
+    /// The units to display size measures in, such as `MiB`.
+    pub size_units: Option<ResourceName>,
+    /// The slug or UUID of the default project for the user.
+    pub default_project: Option<ResourceId>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonUserPrefs {
+    pub user: UserUuid,
+    pub time_zone: Option<TimeZone>,
+    pub date_format: Option<DateFormat>,
+    pub time_units: Option<ResourceName>,
+    pub throughput_units: Option<ResourceName>,
+    pub size_units: Option<ResourceName>,
+    pub default_project: Option<ProjectUuid>,
+}
+
+const RFC3339_INT: i32 = 0;
+const DATE_TIME_INT: i32 = 1;
+const DATE_INT: i32 = 2;
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Integer))]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum DateFormat {
+    /// `2024-11-24T08:12:17+00:00`
+    #[default]
+    Rfc3339 = RFC3339_INT,
+    /// `2024-11-24 08:12:17`
+    DateTime = DATE_TIME_INT,
+    /// `2024-11-24`
+    Date = DATE_INT,
+}
+
+impl DateFormat {
+    pub fn format(self, date_time: DateTime, time_zone: &TimeZone) -> String {
+        let date_time = date_time.into_inner().with_timezone(&time_zone.tz());
+        match self {
+            Self::Rfc3339 => date_time.to_rfc3339(),
+            Self::DateTime => date_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            Self::Date => date_time.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "db")]
+mod date_format {
+    use super::{DateFormat, DATE_INT, DATE_TIME_INT, RFC3339_INT};
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum DateFormatError {
+        #[error("Invalid date format value: {0}")]
+        Invalid(i32),
+    }
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for DateFormat
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            match self {
+                Self::Rfc3339 => RFC3339_INT.to_sql(out),
+                Self::DateTime => DATE_TIME_INT.to_sql(out),
+                Self::Date => DATE_INT.to_sql(out),
+            }
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for DateFormat
+    where
+        DB: diesel::backend::Backend,
+        i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            match i32::from_sql(bytes)? {
+                RFC3339_INT => Ok(Self::Rfc3339),
+                DATE_TIME_INT => Ok(Self::DateTime),
+                DATE_INT => Ok(Self::Date),
+                value => Err(Box::new(DateFormatError::Invalid(value))),
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UserPrefsError {
+    #[error("{0}")]
+    Unit(#[from] UnitError),
+    #[error("Units ({units}) are not {dimension} units")]
+    Dimension {
+        units: String,
+        dimension: UnitDimension,
+    },
+}
+
+impl JsonNewUserPrefs {
+    /// Check that each of the preferred units is a known unit of the right dimension.
+    pub fn validate(&self) -> Result<(), UserPrefsError> {
+        for (units, dimension) in [
+            (&self.time_units, UnitDimension::Time),
+            (&self.throughput_units, UnitDimension::Throughput),
+            (&self.size_units, UnitDimension::Size),
+        ] {
+            let Some(units) = units else {
+                continue;
+            };
+            let unit = MeasureUnit::parse(units.as_ref())?;
+            if unit.dimension != dimension {
+                return Err(UserPrefsError::Dimension {
+                    units: units.to_string(),
+                    dimension,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<JsonUserPrefs> for JsonNewUserPrefs {
+    fn from(prefs: JsonUserPrefs) -> Self {
+        let JsonUserPrefs {
+            time_zone,
+            date_format,
+            time_units,
+            throughput_units,
+            size_units,
+            default_project,
+            ..
+        } = prefs;
+        Self {
+            time_zone,
+            date_format,
+            time_units,
+            throughput_units,
+            size_units,
+            default_project: default_project.map(Into::into),
+        }
+    }
+}
+
+impl JsonUserPrefs {
+    /// Whether the user has a preference for how dates are displayed.
+    pub fn has_date_prefs(&self) -> bool {
+        self.time_zone.is_some() || self.date_format.is_some()
+    }
+
+    /// Format the date time with the preferred time zone and date format.
+    pub fn format_date_time(&self, date_time: DateTime) -> String {
+        self.date_format
+            .unwrap_or_default()
+            .format(date_time, &self.time_zone.clone().unwrap_or_default())
+    }
+
+    /// The preferred unit to display a measure with the given units in, if any.
+    /// Units that are not known or that are counts never have a preferred unit.
+    pub fn preferred_unit(&self, units: &str) -> Option<&'static MeasureUnit> {
+        let unit = MeasureUnit::find(units)?;
+        let preferred_units = match unit.dimension {
+            UnitDimension::Time => self.time_units.as_ref(),
+            UnitDimension::Throughput => self.throughput_units.as_ref(),
+            UnitDimension::Size => self.size_units.as_ref(),
+            UnitDimension::Count => None,
+        }?;
+        let preferred_unit = MeasureUnit::find(preferred_units.as_ref())?;
+        (preferred_unit != unit && unit.convert(1.0, preferred_unit).is_some())
+            .then_some(preferred_unit)
+    }
+
+    /// Convert the metric and boundary from the measure units into the preferred units.
+    /// Returns the preferred units, if the values were converted.
+    #[allow(clippy::redundant_closure_call)]
+    fn convert(
+        &self,
+        units: &str,
+        metric: &mut JsonMetric,
+        boundary: Option<&mut JsonBoundary>,
+    ) -> Option<ResourceName> {
+        let unit = MeasureUnit::find(units)?;
+        let preferred_unit = self.preferred_unit(units)?;
+        let convert = |value: OrderedFloat<f64>| {
+            OrderedFloat(
+                unit.convert(value.into_inner(), preferred_unit)
+                    .unwrap_or(value.into_inner()),
+            )
+        };
+        metric.value = convert(metric.value);
+        metric.lower_value = metric.lower_value.map(convert);
+        metric.upper_value = metric.upper_value.map(convert);
+        if let Some(boundary) = boundary {
+            boundary.baseline = boundary.baseline.map(convert);
+            boundary.lower_limit = boundary.lower_limit.map(convert);
+            boundary.upper_limit = boundary.upper_limit.map(convert);
+        }
+        Some(preferred_unit.units())
+    }
+
+    /// Convert the results and alerts of the report into the preferred units.
+    pub fn convert_report(&self, json_report: &mut JsonReport) {
+        for result in json_report.results.iter_mut().flatten() {
+            for report_measure in &mut result.measures {
+                if let Some(units) = self.convert(
+                    report_measure.measure.units.as_ref(),
+                    &mut report_measure.metric,
+                    report_measure.boundary.as_mut(),
+                ) {
+                    report_measure.measure.units = units;
+                }
+            }
+        }
+        for alert in &mut json_report.alerts {
+            if let Some(units) = self.convert(
+                alert.threshold.measure.units.as_ref(),
+                &mut alert.metric,
+                Some(&mut alert.boundary),
+            ) {
+                alert.threshold.measure.units = units;
+            }
+        }
+    }
+
+    /// Convert the metrics of the perf query results into the preferred units.
+    pub fn convert_perf(&self, json_perf: &mut JsonPerf) {
+        for result in &mut json_perf.results {
+            let Some(preferred_unit) = self.preferred_unit(result.measure.units.as_ref()) else {
+                continue;
+            };
+            // The preferred unit is always of the same dimension, so this should never fail
+            drop(result.convert_units(preferred_unit));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{DateFormat, JsonNewUserPrefs, JsonUserPrefs};
+    use crate::{DateTime, UserUuid};
+
+    fn prefs() -> JsonUserPrefs {
+        JsonUserPrefs {
+            user: UserUuid::new(),
+            time_zone: Some("Asia/Kolkata".parse().unwrap()),
+            date_format: Some(DateFormat::DateTime),
+            time_units: Some("ms".parse().unwrap()),
+            throughput_units: None,
+            size_units: Some("MiB".parse().unwrap()),
+            default_project: None,
+        }
+    }
+
+    #[test]
+    fn test_format_date_time() {
+        let date_time = DateTime::try_from(1_732_436_000).unwrap();
+        let prefs = prefs();
+        assert_eq!(prefs.format_date_time(date_time), "2024-11-24 13:43:20");
+
+        let prefs = JsonUserPrefs {
+            time_zone: None,
+            date_format: None,
+            ..prefs
+        };
+        assert_eq!(
+            prefs.format_date_time(date_time),
+            "2024-11-24T08:13:20+00:00"
+        );
+
+        let prefs = JsonUserPrefs {
+            date_format: Some(DateFormat::Date),
+            ..prefs
+        };
+        assert_eq!(prefs.format_date_time(date_time), "2024-11-24");
+    }
+
+    #[test]
+    fn test_preferred_unit() {
+        let prefs = prefs();
+        let unit = prefs.preferred_unit("nanoseconds (ns)").unwrap();
+        assert_eq!(unit.symbol, Some("ms"));
+        let unit = prefs.preferred_unit("B").unwrap();
+        assert_eq!(unit.symbol, Some("MiB"));
+        // Already in the preferred unit
+        assert_eq!(prefs.preferred_unit("milliseconds (ms)"), None);
+        // No preference
+        assert_eq!(prefs.preferred_unit("operations / second (ops/s)"), None);
+        // Counts can not be converted
+        assert_eq!(prefs.preferred_unit("instructions"), None);
+        // Unknown units
+        assert_eq!(prefs.preferred_unit("widgets"), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let prefs: JsonNewUserPrefs = prefs().into();
+        assert!(prefs.validate().is_ok());
+        assert!(JsonNewUserPrefs::default().validate().is_ok());
+
+        let prefs = JsonNewUserPrefs {
+            time_units: Some("MiB".parse().unwrap()),
+            ..JsonNewUserPrefs::default()
+        };
+        assert!(prefs.validate().is_err());
+
+        let prefs = JsonNewUserPrefs {
+            size_units: Some("widgets".parse().unwrap()),
+            ..JsonNewUserPrefs::default()
+        };
+        assert!(prefs.validate().is_err());
+    }
+}
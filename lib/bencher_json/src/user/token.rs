@@ -1,4 +1,4 @@
-use bencher_valid::{DateTime, Jwt, ResourceName};
+use bencher_valid::{Cidr, DateTime, Jwt, ResourceName};
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -17,6 +17,9 @@ pub struct JsonNewToken {
     /// The time-to-live (TTL) for the token in seconds.
     /// If not provided, the token will not expire for over 128 years.
     pub ttl: Option<u32>,
+    /// The IP addresses or networks, in CIDR notation, that are allowed to use the token.
+    /// If not provided or empty, the token can be used from any IP address.
+    pub allowed_ips: Option<Vec<Cidr>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +38,11 @@ pub struct JsonToken {
     pub token: Jwt,
     pub creation: DateTime,
     pub expiration: DateTime,
+    /// The time at which the token was or will be revoked, if any.
+    pub revoked: Option<DateTime>,
+    /// The IP addresses or networks, in CIDR notation, that are allowed to use the token.
+    /// If not set, the token can be used from any IP address.
+    pub allowed_ips: Option<Vec<Cidr>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,4 +51,17 @@ pub struct JsonUpdateToken {
     /// The new name of the token.
     /// Maximum length is 64 characters.
     pub name: Option<ResourceName>,
+    /// The new IP addresses or networks, in CIDR notation, that are allowed to use the token.
+    /// Set to an empty list to allow the token to be used from any IP address.
+    pub allowed_ips: Option<Vec<Cidr>>,
+}
+
+#[typeshare::typeshare]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct JsonRevokeToken {
+    /// The grace period in seconds before the token is revoked.
+    /// This allows for the token to be rotated without breaking any running jobs.
+    /// If not provided, the token is revoked immediately.
+    pub after: Option<u32>,
 }
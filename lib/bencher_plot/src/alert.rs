@@ -0,0 +1,401 @@
+use std::{io::Cursor, ops::Range};
+
+use bencher_json::{project::perf::JsonPerfMetrics, AlertUuid};
+use chrono::{DateTime, Duration, Utc};
+use image::ImageBuffer;
+use plotters::{
+    coord::Shift,
+    prelude::{
+        BitMapBackend, ChartBuilder, Circle, DrawingArea, DrawingAreaErrorKind, DrawingBackend,
+        IntoDrawingArea, Polygon, SVGBackend,
+    },
+    series::LineSeries,
+    style::{Color, FontFamily, RGBColor, WHITE},
+};
+
+use crate::PlotError;
+
+const IMG_WIDTH: u32 = 600;
+const IMG_HEIGHT: u32 = 300;
+const TITLE_SIZE: u32 = 20;
+const MAX_TITLE_LEN: usize = 48;
+
+// RGB is three units in size
+// https://docs.rs/image/latest/image/struct.Rgb.html
+const BUFFER_SIZE: usize = IMG_WIDTH as usize * IMG_HEIGHT as usize * 3;
+
+/// The maximum number of metrics leading up to and including the alert to plot
+const MAX_METRICS: usize = 30;
+const X_LABELS: usize = 4;
+const Y_LABELS: usize = 5;
+const DATE_FMT: &str = "%d %b";
+const DATE_TIME_FMT: &str = "%d %b %H:%M";
+
+// #4e79a7
+const METRIC_COLOR: RGBColor = RGBColor(78, 121, 167);
+// #76b7b2
+const BOUNDARY_COLOR: RGBColor = RGBColor(118, 183, 178);
+// #e15759
+const ALERT_COLOR: RGBColor = RGBColor(225, 87, 89);
+
+/// A small plot of the metric history for an alert,
+/// with the boundary limits shaded and the alerted metric highlighted.
+/// It is meant to be embedded in alert notifications.
+pub struct AlertPlot {
+    width: u32,
+    height: u32,
+}
+
+impl Default for AlertPlot {
+    fn default() -> Self {
+        Self {
+            width: IMG_WIDTH,
+            height: IMG_HEIGHT,
+        }
+    }
+}
+
+impl AlertPlot {
+    pub fn new() -> AlertPlot {
+        Self::default()
+    }
+
+    pub fn png(
+        &self,
+        title: &str,
+        json_perf_metrics: &JsonPerfMetrics,
+        alert: AlertUuid,
+    ) -> Result<Vec<u8>, PlotError> {
+        let alert_data = AlertData::new(json_perf_metrics, alert);
+
+        let mut plot_buffer = vec![0; BUFFER_SIZE];
+        {
+            let root_area = BitMapBackend::with_buffer(&mut plot_buffer, (self.width, self.height))
+                .into_drawing_area();
+            draw(&root_area, title, alert_data.as_ref())?;
+            root_area.present()?;
+        }
+
+        let image_buffer: ImageBuffer<image::Rgb<u8>, Vec<u8>> =
+            ImageBuffer::from_vec(self.width, self.height, plot_buffer)
+                .ok_or(PlotError::ImageBuffer)?;
+        let mut image_cursor = Cursor::new(Vec::new());
+        image_buffer.write_to(&mut image_cursor, image::ImageFormat::Png)?;
+
+        Ok(image_cursor.into_inner())
+    }
+
+    pub fn svg(
+        &self,
+        title: &str,
+        json_perf_metrics: &JsonPerfMetrics,
+        alert: AlertUuid,
+    ) -> Result<String, PlotError> {
+        let alert_data = AlertData::new(json_perf_metrics, alert);
+
+        let mut svg = String::new();
+        {
+            let root_area =
+                SVGBackend::with_string(&mut svg, (self.width, self.height)).into_drawing_area();
+            draw(&root_area, title, alert_data.as_ref())?;
+            root_area.present()?;
+        }
+
+        Ok(svg)
+    }
+}
+
+fn draw<DB>(
+    root_area: &DrawingArea<DB, Shift>,
+    title: &str,
+    alert_data: Option<&AlertData>,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+{
+    root_area.fill(&WHITE)?;
+    let plot_area =
+        root_area.titled(&truncate_title(title), (FontFamily::Monospace, TITLE_SIZE))?;
+
+    let Some(alert_data) = alert_data else {
+        // Return an informative message if the alerted metric is not found
+        let _chart_context = ChartBuilder::on(&plot_area)
+            .caption("No Data Found", (FontFamily::Monospace, TITLE_SIZE))
+            .build_cartesian_2d(0.0..1.0, 0.0..1.0)?;
+        return Ok(());
+    };
+
+    let mut chart_context = ChartBuilder::on(&plot_area)
+        .x_label_area_size(24)
+        .y_label_area_size(64)
+        .margin_left(4)
+        .margin_right(16)
+        .margin_bottom(4)
+        .build_cartesian_2d(alert_data.x_range(), alert_data.y_range())?;
+
+    chart_context
+        .configure_mesh()
+        .x_labels(X_LABELS)
+        .x_label_style((FontFamily::Monospace, 12))
+        .x_label_formatter(&|x| alert_data.x_label_fmt(x))
+        .y_labels(Y_LABELS)
+        .y_label_style((FontFamily::Monospace, 12))
+        .y_label_formatter(&|&y| y_label_fmt(y))
+        .max_light_lines(2)
+        .draw()?;
+
+    // Shade the area between the boundary limits.
+    // If there is only one limit, then the band extends to the edge of the plot.
+    let y_range = alert_data.y_range();
+    for run in &alert_data.boundaries {
+        let upper = run
+            .iter()
+            .map(|point| (point.x, point.upper.unwrap_or(y_range.end)));
+        let lower = run
+            .iter()
+            .rev()
+            .map(|point| (point.x, point.lower.unwrap_or(y_range.start)));
+        let _series = chart_context.draw_series(std::iter::once(Polygon::new(
+            upper.chain(lower).collect::<Vec<_>>(),
+            BOUNDARY_COLOR.mix(0.2).filled(),
+        )))?;
+        for limit in [
+            run.iter()
+                .filter_map(|point| point.lower.map(|lower| (point.x, lower)))
+                .collect::<Vec<_>>(),
+            run.iter()
+                .filter_map(|point| point.upper.map(|upper| (point.x, upper)))
+                .collect(),
+        ] {
+            let _series = chart_context
+                .draw_series(LineSeries::new(limit, BOUNDARY_COLOR.stroke_width(1)))?;
+        }
+    }
+
+    let _series = chart_context.draw_series(
+        LineSeries::new(alert_data.metrics.iter().copied(), METRIC_COLOR.filled()).point_size(2),
+    )?;
+
+    let _series = chart_context.draw_series(std::iter::once(Circle::new(
+        alert_data.alert,
+        5,
+        ALERT_COLOR.filled(),
+    )))?;
+
+    Ok(())
+}
+
+fn truncate_title(title: &str) -> String {
+    if title.chars().count() > MAX_TITLE_LEN {
+        let mut title = title.chars().take(MAX_TITLE_LEN - 1).collect::<String>();
+        title.push('…');
+        title
+    } else {
+        title.to_owned()
+    }
+}
+
+fn y_label_fmt(y: f64) -> String {
+    if y.abs() < 1.0 {
+        format!("{y:.4}")
+    } else if y.abs() < 1_000.0 {
+        format!("{y:.2}")
+    } else {
+        format!("{y:.0}")
+    }
+}
+
+struct AlertData {
+    metrics: Vec<(DateTime<Utc>, f64)>,
+    alert: (DateTime<Utc>, f64),
+    /// Contiguous runs of metrics that have at least one boundary limit
+    boundaries: Vec<Vec<BoundaryPoint>>,
+    x: (DateTime<Utc>, DateTime<Utc>),
+    y: (f64, f64),
+}
+
+struct BoundaryPoint {
+    x: DateTime<Utc>,
+    lower: Option<f64>,
+    upper: Option<f64>,
+}
+
+impl AlertData {
+    fn new(json_perf_metrics: &JsonPerfMetrics, alert: AlertUuid) -> Option<AlertData> {
+        let alert_index = json_perf_metrics.metrics.iter().position(|metric| {
+            metric
+                .alert
+                .as_ref()
+                .is_some_and(|perf_alert| perf_alert.uuid == alert)
+        })?;
+        let start = alert_index.saturating_sub(MAX_METRICS - 1);
+        let history = json_perf_metrics.metrics.get(start..=alert_index)?;
+
+        let mut metrics: Vec<(DateTime<Utc>, f64)> = Vec::with_capacity(history.len());
+        let mut boundaries = Vec::new();
+        let mut run = Vec::new();
+        for metric in history {
+            let x = metric.start_time.into_inner();
+            metrics.push((x, metric.metric.value.into()));
+            let (lower, upper) = metric.boundary.as_ref().map_or((None, None), |boundary| {
+                (
+                    boundary.lower_limit.map(Into::into),
+                    boundary.upper_limit.map(Into::into),
+                )
+            });
+            if lower.is_some() || upper.is_some() {
+                run.push(BoundaryPoint { x, lower, upper });
+            } else if !run.is_empty() {
+                boundaries.push(std::mem::take(&mut run));
+            }
+        }
+        if !run.is_empty() {
+            boundaries.push(run);
+        }
+
+        let alert = *metrics.last()?;
+        let (mut min_x, mut max_x) = (alert.0, alert.0);
+        let (mut min_y, mut max_y) = (alert.1, alert.1);
+        let limits = boundaries
+            .iter()
+            .flatten()
+            .flat_map(|point| point.lower.into_iter().chain(point.upper));
+        for (x, y) in metrics.iter().copied() {
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+        }
+        for y in limits {
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+        }
+
+        Some(AlertData {
+            metrics,
+            alert,
+            boundaries,
+            x: (min_x, max_x),
+            y: (min_y, max_y),
+        })
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn x_range(&self) -> Range<DateTime<Utc>> {
+        let padding = Duration::seconds(((self.x.1 - self.x.0).num_seconds() as f64 * 0.04) as i64)
+            .max(Duration::minutes(1));
+        (self.x.0 - padding)..(self.x.1 + padding)
+    }
+
+    fn x_label_fmt(&self, x: &DateTime<Utc>) -> String {
+        let fmt = if self.x.1 - self.x.0 < Duration::days(2) {
+            DATE_TIME_FMT
+        } else {
+            DATE_FMT
+        };
+        format!("{}", x.format(fmt))
+    }
+
+    fn y_range(&self) -> Range<f64> {
+        let diff = self.y.1 - self.y.0;
+        let padding = if diff > 0.0 {
+            diff * 0.08
+        } else if self.y.0 == 0.0 {
+            1.0
+        } else {
+            self.y.0.abs() * 0.1
+        };
+        (self.y.0 - padding)..(self.y.1 + padding)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, clippy::unwrap_used)]
+mod test {
+    use std::{fs::File, io::Write};
+
+    use bencher_json::{project::perf::JsonPerfMetrics, AlertUuid, JsonPerf};
+    use once_cell::sync::Lazy;
+    use serde_json::json;
+
+    use crate::AlertPlot;
+
+    pub const PERF_DOT_JSON: &str = include_str!("../perf.json");
+    static JSON_PERF: Lazy<JsonPerf> =
+        Lazy::new(|| serde_json::from_str(PERF_DOT_JSON).expect("Failed to serialize perf JSON"));
+
+    const ALERT_UUID: &str = "9a2b7e1c-4f1d-4c3a-9d55-0d2f5e0b8c11";
+
+    fn alert_uuid() -> AlertUuid {
+        ALERT_UUID.parse().unwrap()
+    }
+
+    fn json_perf_metrics() -> JsonPerfMetrics {
+        let mut json_perf_metrics = JSON_PERF.results.first().cloned().unwrap();
+        let last = json_perf_metrics.metrics.len() - 1;
+        for (index, metric) in json_perf_metrics.metrics.iter_mut().enumerate() {
+            let value = f64::from(metric.metric.value);
+            metric.boundary = Some(
+                serde_json::from_value(json!({
+                    "baseline": value,
+                    "lower_limit": value * 0.9,
+                    "upper_limit": value * 1.1,
+                }))
+                .unwrap(),
+            );
+            if index == last {
+                metric.metric.value = (value * 1.5).into();
+                metric.alert = Some(
+                    serde_json::from_value(json!({
+                        "uuid": ALERT_UUID,
+                        "limit": "upper",
+                        "severity": "major",
+                        "status": "active",
+                        "modified": "2023-07-02T12:53:33Z",
+                    }))
+                    .unwrap(),
+                );
+            }
+        }
+        json_perf_metrics
+    }
+
+    #[test]
+    fn test_alert_plot_png() {
+        let png = AlertPlot::new()
+            .png(
+                "bencher::mock_0: Latency",
+                &json_perf_metrics(),
+                alert_uuid(),
+            )
+            .unwrap();
+        let mut file = File::create("alert.png").unwrap();
+        file.write_all(&png).unwrap();
+    }
+
+    #[test]
+    fn test_alert_plot_svg() {
+        let svg = AlertPlot::new()
+            .svg(
+                "bencher::mock_0: Latency",
+                &json_perf_metrics(),
+                alert_uuid(),
+            )
+            .unwrap();
+        assert!(svg.starts_with("<svg"));
+        let mut file = File::create("alert.svg").unwrap();
+        file.write_all(svg.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_alert_plot_not_found() {
+        let mut json_perf_metrics = json_perf_metrics();
+        for metric in &mut json_perf_metrics.metrics {
+            metric.alert = None;
+        }
+        let svg = AlertPlot::new()
+            .svg("bencher::mock_0: Latency", &json_perf_metrics, alert_uuid())
+            .unwrap();
+        assert!(svg.contains("No Data Found"));
+    }
+}
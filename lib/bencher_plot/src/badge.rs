@@ -0,0 +1,220 @@
+use std::fmt;
+
+const BADGE_HEIGHT: u32 = 20;
+// Approximate character width for 11px Verdana
+const CHAR_WIDTH: u32 = 7;
+const TEXT_PADDING: u32 = 10;
+const MAX_LABEL_LEN: usize = 64;
+
+const LABEL_COLOR: &str = "#555";
+const NO_DATA_MESSAGE: &str = "no data";
+
+/// A shields.io style SVG badge for the latest value of a metric
+#[derive(Debug, Clone)]
+pub struct Badge {
+    label: String,
+    message: String,
+    color: BadgeColor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeTrend {
+    Up,
+    Down,
+    Flat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeColor {
+    /// There is a value and no active alerts
+    Blue,
+    /// There is a value with an active alert
+    Red,
+    /// There is no value
+    Grey,
+}
+
+impl BadgeTrend {
+    pub fn new(value: f64, previous: f64) -> Self {
+        if value > previous {
+            Self::Up
+        } else if value < previous {
+            Self::Down
+        } else {
+            Self::Flat
+        }
+    }
+}
+
+impl fmt::Display for BadgeTrend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Up => "↑",
+                Self::Down => "↓",
+                Self::Flat => "→",
+            }
+        )
+    }
+}
+
+impl fmt::Display for BadgeColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Blue => "#007ec6",
+                Self::Red => "#e05d44",
+                Self::Grey => "#9f9f9f",
+            }
+        )
+    }
+}
+
+impl Badge {
+    pub fn new(label: &str, value: Option<f64>, trend: Option<BadgeTrend>, alert: bool) -> Self {
+        let label = label.chars().take(MAX_LABEL_LEN).collect();
+        let (message, color) = match value {
+            Some(value) => {
+                let mut message = format_value(value);
+                if let Some(trend) = trend {
+                    message.push(' ');
+                    message.push_str(&trend.to_string());
+                }
+                let color = if alert {
+                    BadgeColor::Red
+                } else {
+                    BadgeColor::Blue
+                };
+                (message, color)
+            },
+            None => (NO_DATA_MESSAGE.to_owned(), BadgeColor::Grey),
+        };
+        Self {
+            label,
+            message,
+            color,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn color(&self) -> BadgeColor {
+        self.color
+    }
+
+    /// Render the badge as an SVG image
+    pub fn svg(&self) -> String {
+        let label_width = text_width(&self.label);
+        let message_width = text_width(&self.message);
+        let width = label_width + message_width;
+        let label_x = f64::from(label_width) / 2.0;
+        let message_x = f64::from(label_width) + f64::from(message_width) / 2.0;
+        let label = escape_xml(&self.label);
+        let message = escape_xml(&self.message);
+        let color = self.color;
+
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{BADGE_HEIGHT}" role="img" aria-label="{label}: {message}"><title>{label}: {message}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{width}" height="{BADGE_HEIGHT}" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="{BADGE_HEIGHT}" fill="{LABEL_COLOR}"/><rect x="{label_width}" width="{message_width}" height="{BADGE_HEIGHT}" fill="{color}"/><rect width="{width}" height="{BADGE_HEIGHT}" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text><text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text><text x="{message_x}" y="14">{message}</text></g></svg>"##
+        )
+    }
+}
+
+fn text_width(text: &str) -> u32 {
+    let chars = u32::try_from(text.chars().count()).unwrap_or(u32::MAX);
+    chars
+        .saturating_mul(CHAR_WIDTH)
+        .saturating_add(TEXT_PADDING)
+}
+
+/// Format the value with at most three significant decimal places,
+/// using SI suffixes for large values.
+fn format_value(value: f64) -> String {
+    const SUFFIXES: [(f64, &str); 4] = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k")];
+    for (scale, suffix) in SUFFIXES {
+        if value.abs() >= scale {
+            return format!("{}{suffix}", trim_decimal(value / scale));
+        }
+    }
+    trim_decimal(value)
+}
+
+fn trim_decimal(value: f64) -> String {
+    let decimal = format!("{value:.3}");
+    decimal
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_owned()
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_value, Badge, BadgeColor, BadgeTrend};
+
+    #[test]
+    fn test_badge() {
+        let badge = Badge::new("latency", Some(1_234.0), Some(BadgeTrend::Down), false);
+        assert_eq!(badge.message(), "1.234k ↓");
+        assert_eq!(badge.color(), BadgeColor::Blue);
+        let svg = badge.svg();
+        assert!(svg.starts_with("<svg"), "{svg}");
+        assert!(svg.contains("latency: 1.234k ↓"), "{svg}");
+
+        let badge = Badge::new("latency", Some(1.0), None, true);
+        assert_eq!(badge.message(), "1");
+        assert_eq!(badge.color(), BadgeColor::Red);
+
+        let badge = Badge::new("latency", None, Some(BadgeTrend::Up), true);
+        assert_eq!(badge.message(), "no data");
+        assert_eq!(badge.color(), BadgeColor::Grey);
+    }
+
+    #[test]
+    fn test_badge_escape() {
+        let svg = Badge::new("<fib> & \"friends\"", Some(1.0), None, false).svg();
+        assert!(
+            svg.contains("&lt;fib&gt; &amp; &quot;friends&quot;"),
+            "{svg}"
+        );
+        assert!(!svg.contains("<fib>"), "{svg}");
+    }
+
+    #[test]
+    fn test_badge_trend() {
+        assert_eq!(BadgeTrend::new(2.0, 1.0), BadgeTrend::Up);
+        assert_eq!(BadgeTrend::new(1.0, 2.0), BadgeTrend::Down);
+        assert_eq!(BadgeTrend::new(1.0, 1.0), BadgeTrend::Flat);
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(0.0), "0");
+        assert_eq!(format_value(1.5), "1.5");
+        assert_eq!(format_value(0.123_456), "0.123");
+        assert_eq!(format_value(999.0), "999");
+        assert_eq!(format_value(1_000.0), "1k");
+        assert_eq!(format_value(2_500_000.0), "2.5M");
+        assert_eq!(format_value(-3_000_000_000.0), "-3G");
+        assert_eq!(format_value(4e12), "4T");
+    }
+}
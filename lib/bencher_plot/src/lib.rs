@@ -1,5 +1,9 @@
+mod alert;
+mod badge;
 mod error;
 mod line;
 
+pub use alert::AlertPlot;
+pub use badge::{Badge, BadgeColor, BadgeTrend};
 pub use error::PlotError;
 pub use line::LinePlot;
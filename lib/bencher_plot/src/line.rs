@@ -33,6 +33,7 @@ const DATE_TIME_FMT: &str = "%d %b %Y %H:%M:%S";
 const BUFFER_SIZE: usize = IMG_WIDTH as usize * IMG_HEIGHT as usize * 3;
 
 const MAX_LINES: usize = 8;
+const ANNOTATION_COLOR: RGBColor = RGBColor(128, 128, 128);
 
 pub const BENCHER_WORDMARK: &[u8; 4910] = include_bytes!("../wordmark.png");
 #[allow(clippy::expect_used)]
@@ -132,6 +133,15 @@ impl LinePlot {
                 .max_light_lines(4)
                 .draw()?;
 
+            // Annotation markers
+            let (y_min, y_max) = (perf_data.y_range().start, perf_data.y_range().end);
+            for annotation in &perf_data.annotations {
+                let _series = chart_context.draw_series(LineSeries::new(
+                    [(*annotation, y_min), (*annotation, y_max)],
+                    ANNOTATION_COLOR.stroke_width(1),
+                ))?;
+            }
+
             const KEY_LEFT_MARGIN: usize = 48;
             const BOX_GAP: usize = 12;
             let lines_len = perf_data.lines.len();
@@ -210,6 +220,7 @@ impl LinePlot {
 
 struct PerfData {
     lines: Vec<LineData>,
+    annotations: Vec<DateTime<Utc>>,
     x: (DateTime<Utc>, DateTime<Utc>),
     y: (OrderedFloat<f64>, OrderedFloat<f64>),
     x_time: bool,
@@ -274,8 +285,16 @@ impl PerfData {
                 .map_or("Measure: unitless".to_owned(), |result| {
                     result.measure.to_string()
                 });
+            let annotations = json_perf
+                .annotations
+                .iter()
+                .flatten()
+                .map(|annotation| annotation.time.into_inner())
+                .filter(|time| (min_x..=max_x).contains(time))
+                .collect();
             Some(PerfData {
                 lines,
+                annotations,
                 x: (min_x, max_x),
                 y: (min_y, max_y),
                 x_time,
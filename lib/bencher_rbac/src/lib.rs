@@ -1,5 +1,6 @@
 use oso::{ClassBuilder, Oso, PolarClass};
 
+pub mod matrix;
 pub mod organization;
 pub mod project;
 pub mod server;
@@ -11,6 +12,7 @@ pub use server::Server;
 pub use user::User;
 
 const VIEW_PERM: &str = "view";
+const REPORT_PERM: &str = "report";
 const CREATE_PERM: &str = "create";
 const EDIT_PERM: &str = "edit";
 const DELETE_PERM: &str = "delete";
@@ -156,6 +158,7 @@ mod test {
         let proj = Project {
             id: proj_id.to_string(),
             organization_id: org_id.to_string(),
+            internal: false,
         };
 
         assert!(oso
@@ -235,6 +238,7 @@ mod test {
         let other_proj = Project {
             id: Uuid::new_v4().to_string(),
             organization_id: other_org_id.to_string(),
+            internal: false,
         };
 
         assert!(oso
@@ -300,4 +304,180 @@ mod test {
             .is_allowed(proj_member, ProjPerm::Manage, other_proj)
             .unwrap());
     }
+
+    #[test]
+    fn test_rbac_project_roles() {
+        let oso = &*OSO;
+
+        let org_id = Uuid::new_v4();
+        let proj_id = Uuid::new_v4();
+        let org = Organization {
+            id: org_id.to_string(),
+        };
+        let proj = Project {
+            id: proj_id.to_string(),
+            organization_id: org_id.to_string(),
+            internal: false,
+        };
+        let other_proj = Project {
+            id: Uuid::new_v4().to_string(),
+            organization_id: org_id.to_string(),
+            internal: false,
+        };
+
+        let proj_user = |role: ProjRole| User {
+            admin: false,
+            locked: false,
+            organizations: HashMap::new(),
+            projects: literally::hmap! {
+                proj_id.to_string() => role
+            },
+        };
+
+        let viewer = proj_user(ProjRole::Viewer);
+        assert!(oso
+            .is_allowed(viewer.clone(), ProjPerm::View, proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(viewer.clone(), ProjPerm::Report, proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(viewer, ProjPerm::Create, proj.clone())
+            .unwrap());
+
+        // A reporter can submit reports to their project without being a member of the organization
+        let reporter = proj_user(ProjRole::Reporter);
+        assert!(oso
+            .is_allowed(reporter.clone(), ProjPerm::View, proj.clone())
+            .unwrap());
+        assert!(oso
+            .is_allowed(reporter.clone(), ProjPerm::Report, proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(reporter.clone(), ProjPerm::Create, proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(reporter.clone(), ProjPerm::CreateRole, proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(reporter.clone(), OrgPerm::View, org.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(reporter.clone(), ProjPerm::View, other_proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(reporter, ProjPerm::Report, other_proj.clone())
+            .unwrap());
+
+        let developer = proj_user(ProjRole::Developer);
+        assert!(oso
+            .is_allowed(developer.clone(), ProjPerm::Report, proj.clone())
+            .unwrap());
+        assert!(oso
+            .is_allowed(developer.clone(), ProjPerm::Create, proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(developer, ProjPerm::CreateRole, proj.clone())
+            .unwrap());
+
+        let maintainer = proj_user(ProjRole::Maintainer);
+        assert!(oso
+            .is_allowed(maintainer.clone(), ProjPerm::Report, proj.clone())
+            .unwrap());
+        assert!(oso
+            .is_allowed(maintainer.clone(), ProjPerm::CreateRole, proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(maintainer.clone(), OrgPerm::View, org)
+            .unwrap());
+        assert!(!oso
+            .is_allowed(maintainer, ProjPerm::View, other_proj)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_rbac_internal_project() {
+        let oso = &*OSO;
+
+        let org_id = Uuid::new_v4();
+        let internal_proj = Project {
+            id: Uuid::new_v4().to_string(),
+            organization_id: org_id.to_string(),
+            internal: true,
+        };
+        let private_proj = Project {
+            internal: false,
+            ..internal_proj.clone()
+        };
+        let other_internal_proj = Project {
+            id: Uuid::new_v4().to_string(),
+            organization_id: Uuid::new_v4().to_string(),
+            internal: true,
+        };
+
+        // Any member of the organization can view an internal project
+        let org_member = User {
+            admin: false,
+            locked: false,
+            organizations: literally::hmap! {
+                org_id.to_string() => OrgRole::Member
+            },
+            projects: HashMap::new(),
+        };
+        assert!(oso
+            .is_allowed(org_member.clone(), ProjPerm::View, internal_proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(org_member.clone(), ProjPerm::Report, internal_proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(org_member.clone(), ProjPerm::ViewRole, private_proj.clone())
+            .unwrap());
+        assert!(!oso
+            .is_allowed(org_member.clone(), ProjPerm::View, private_proj)
+            .unwrap());
+        assert!(!oso
+            .is_allowed(org_member, ProjPerm::View, other_internal_proj)
+            .unwrap());
+
+        let user = User {
+            admin: false,
+            locked: false,
+            organizations: HashMap::new(),
+            projects: HashMap::new(),
+        };
+        assert!(!oso.is_allowed(user, ProjPerm::View, internal_proj).unwrap());
+    }
+
+    #[test]
+    fn test_rbac_matrix() {
+        let oso = &*OSO;
+
+        let roles = matrix::server_roles(oso, SvrPerm::Session).unwrap();
+        assert_eq!(role_names(&roles), "user,admin");
+        let roles = matrix::server_roles(oso, SvrPerm::Administer).unwrap();
+        assert_eq!(role_names(&roles), "admin");
+
+        let roles = matrix::organization_roles(oso, OrgPerm::View).unwrap();
+        assert_eq!(role_names(&roles), "member,leader");
+        let roles = matrix::organization_roles(oso, OrgPerm::Manage).unwrap();
+        assert_eq!(role_names(&roles), "leader");
+
+        let roles = matrix::project_roles(oso, ProjPerm::View).unwrap();
+        assert_eq!(role_names(&roles), "viewer,reporter,developer,maintainer");
+        let roles = matrix::project_roles(oso, ProjPerm::Report).unwrap();
+        assert_eq!(role_names(&roles), "reporter,developer,maintainer");
+        let roles = matrix::project_roles(oso, ProjPerm::CreateRole).unwrap();
+        assert_eq!(role_names(&roles), "maintainer");
+        let roles = matrix::organization_project_roles(oso, ProjPerm::Delete).unwrap();
+        assert_eq!(role_names(&roles), "leader");
+    }
+
+    fn role_names<R: ToString>(roles: &[R]) -> String {
+        roles
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
 }
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use oso::Oso;
+
+use crate::{organization, project, server, Organization, Project, Server, User};
+
+// The ID used for the example organization and project that each role is checked against
+const MATRIX_ID: &str = "matrix";
+
+const SERVER_ROLES: [server::Role; 3] = [
+    server::Role::Locked,
+    server::Role::User,
+    server::Role::Admin,
+];
+const ORGANIZATION_ROLES: [organization::Role; 2] =
+    [organization::Role::Member, organization::Role::Leader];
+const PROJECT_ROLES: [project::Role; 4] = [
+    project::Role::Viewer,
+    project::Role::Reporter,
+    project::Role::Developer,
+    project::Role::Maintainer,
+];
+
+/// The server roles that are granted the permission by the RBAC policy
+pub fn server_roles(oso: &Oso, permission: server::Permission) -> oso::Result<Vec<server::Role>> {
+    let mut roles = Vec::new();
+    for role in SERVER_ROLES {
+        let user = User {
+            admin: matches!(role, server::Role::Admin),
+            locked: matches!(role, server::Role::Locked),
+            organizations: HashMap::new(),
+            projects: HashMap::new(),
+        };
+        if oso.is_allowed(user, permission, Server {})? {
+            roles.push(role);
+        }
+    }
+    Ok(roles)
+}
+
+/// The organization roles that are granted the organization permission by the RBAC policy
+pub fn organization_roles(
+    oso: &Oso,
+    permission: organization::Permission,
+) -> oso::Result<Vec<organization::Role>> {
+    let mut roles = Vec::new();
+    for role in ORGANIZATION_ROLES {
+        let organization = Organization {
+            id: MATRIX_ID.into(),
+        };
+        if oso.is_allowed(organization_user(role), permission, organization)? {
+            roles.push(role);
+        }
+    }
+    Ok(roles)
+}
+
+/// The project roles that are granted the project permission by the RBAC policy
+pub fn project_roles(
+    oso: &Oso,
+    permission: project::Permission,
+) -> oso::Result<Vec<project::Role>> {
+    let mut roles = Vec::new();
+    for role in PROJECT_ROLES {
+        let user = User {
+            admin: false,
+            locked: false,
+            organizations: HashMap::new(),
+            projects: [(MATRIX_ID.into(), role)].into(),
+        };
+        if oso.is_allowed(user, permission, matrix_project())? {
+            roles.push(role);
+        }
+    }
+    Ok(roles)
+}
+
+/// The roles for the organization that owns a project
+/// that are granted the project permission by the RBAC policy
+pub fn organization_project_roles(
+    oso: &Oso,
+    permission: project::Permission,
+) -> oso::Result<Vec<organization::Role>> {
+    let mut roles = Vec::new();
+    for role in ORGANIZATION_ROLES {
+        if oso.is_allowed(organization_user(role), permission, matrix_project())? {
+            roles.push(role);
+        }
+    }
+    Ok(roles)
+}
+
+fn organization_user(role: organization::Role) -> User {
+    User {
+        admin: false,
+        locked: false,
+        organizations: [(MATRIX_ID.into(), role)].into(),
+        projects: HashMap::new(),
+    }
+}
+
+fn matrix_project() -> Project {
+    Project {
+        id: MATRIX_ID.into(),
+        organization_id: MATRIX_ID.into(),
+        internal: false,
+    }
+}
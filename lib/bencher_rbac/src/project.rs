@@ -4,10 +4,11 @@ use oso::{PolarClass, PolarValue, ToPolar};
 
 use crate::{
     CREATE_PERM, CREATE_ROLE_PERM, DELETE_PERM, DELETE_ROLE_PERM, EDIT_PERM, EDIT_ROLE_PERM,
-    MANAGE_PERM, VIEW_PERM, VIEW_ROLE_PERM,
+    MANAGE_PERM, REPORT_PERM, VIEW_PERM, VIEW_ROLE_PERM,
 };
 
 const VIEWER_ROLE: &str = "viewer";
+const REPORTER_ROLE: &str = "reporter";
 const DEVELOPER_ROLE: &str = "developer";
 const MAINTAINER_ROLE: &str = "maintainer";
 
@@ -17,11 +18,15 @@ pub struct Project {
     pub id: String,
     #[polar(attribute)]
     pub organization_id: String,
+    /// Any member of the organization can view an internal project
+    #[polar(attribute)]
+    pub internal: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum Role {
     Viewer,
+    Reporter,
     Developer,
     Maintainer,
 }
@@ -33,6 +38,7 @@ impl fmt::Display for Role {
             "{}",
             match self {
                 Self::Viewer => VIEWER_ROLE,
+                Self::Reporter => REPORTER_ROLE,
                 Self::Developer => DEVELOPER_ROLE,
                 Self::Maintainer => MAINTAINER_ROLE,
             }
@@ -52,6 +58,7 @@ impl FromStr for Role {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             VIEWER_ROLE => Ok(Self::Viewer),
+            REPORTER_ROLE => Ok(Self::Reporter),
             DEVELOPER_ROLE => Ok(Self::Developer),
             MAINTAINER_ROLE => Ok(Self::Maintainer),
             _ => Err(s.into()),
@@ -62,6 +69,7 @@ impl FromStr for Role {
 #[derive(Debug, Clone, Copy)]
 pub enum Permission {
     View,
+    Report,
     Create,
     Edit,
     Delete,
@@ -79,6 +87,7 @@ impl fmt::Display for Permission {
             "{}",
             match self {
                 Self::View => VIEW_PERM,
+                Self::Report => REPORT_PERM,
                 Self::Create => CREATE_PERM,
                 Self::Edit => EDIT_PERM,
                 Self::Delete => DELETE_PERM,
@@ -4,6 +4,9 @@ const AUDIENCE_AUTH: &str = "auth";
 const AUDIENCE_CLIENT: &str = "client";
 const AUDIENCE_API_KEY: &str = "api_key";
 const AUDIENCE_INVITE: &str = "invite";
+const AUDIENCE_PROJECT_INVITE: &str = "project_invite";
+const AUDIENCE_EMBED: &str = "embed";
+const AUDIENCE_CLAIM: &str = "claim";
 
 #[derive(Debug, Copy, Clone)]
 pub enum Audience {
@@ -11,6 +14,9 @@ pub enum Audience {
     Client,
     ApiKey,
     Invite,
+    ProjectInvite,
+    Embed,
+    Claim,
 }
 impl fmt::Display for Audience {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -22,6 +28,9 @@ impl fmt::Display for Audience {
                 Self::Client => AUDIENCE_CLIENT,
                 Self::ApiKey => AUDIENCE_API_KEY,
                 Self::Invite => AUDIENCE_INVITE,
+                Self::ProjectInvite => AUDIENCE_PROJECT_INVITE,
+                Self::Embed => AUDIENCE_EMBED,
+                Self::Claim => AUDIENCE_CLAIM,
             }
         )
     }
@@ -1,4 +1,7 @@
-use bencher_json::{organization::member::OrganizationRole, DateTime, Email, OrganizationUuid};
+use bencher_json::{
+    organization::member::OrganizationRole, project::ProjectRole, DateTime, Email,
+    OrganizationUuid, ProjectUuid,
+};
 use chrono::Utc;
 use jsonwebtoken::errors::ErrorKind as JsonWebTokenErrorKind;
 use serde::{Deserialize, Serialize};
@@ -7,12 +10,14 @@ use super::{audience::Audience, TokenError};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
-    pub aud: String,            // Audience
-    pub exp: i64,               // Expiration time (as UTC timestamp)
-    pub iat: i64,               // Issued at (as UTC timestamp)
-    pub iss: String,            // Issuer
-    pub sub: Email,             // Subject (whom token refers to)
-    pub org: Option<OrgClaims>, // Organization (for invitation)
+    pub aud: String,              // Audience
+    pub exp: i64,                 // Expiration time (as UTC timestamp)
+    pub iat: i64,                 // Issued at (as UTC timestamp)
+    pub iss: String,              // Issuer
+    pub sub: Email,               // Subject (whom token refers to)
+    pub org: Option<OrgClaims>,   // Organization (for invitation)
+    pub perf: Option<PerfClaims>, // Perf query (for embedding)
+    pub proj: Option<ProjClaims>, // Project (for claiming or invitation)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +26,19 @@ pub struct OrgClaims {
     pub role: OrganizationRole,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerfClaims {
+    pub project: ProjectUuid,
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjClaims {
+    pub uuid: ProjectUuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<ProjectRole>,
+}
+
 impl Claims {
     pub fn new(
         audience: Audience,
@@ -28,6 +46,8 @@ impl Claims {
         email: Email,
         ttl: u32,
         org: Option<OrgClaims>,
+        perf: Option<PerfClaims>,
+        proj: Option<ProjClaims>,
     ) -> Self {
         let now = Utc::now().timestamp();
         Self {
@@ -37,6 +57,8 @@ impl Claims {
             iss: issuer,
             sub: email,
             org,
+            perf,
+            proj,
         }
     }
 
@@ -92,3 +114,118 @@ impl InviteClaims {
         &self.sub
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct ProjectInviteClaims {
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub iss: String,
+    pub sub: Email,
+    pub project: ProjectUuid,
+    pub role: ProjectRole,
+}
+
+impl TryFrom<Claims> for ProjectInviteClaims {
+    type Error = TokenError;
+
+    fn try_from(claims: Claims) -> Result<Self, Self::Error> {
+        match claims.proj {
+            Some(ProjClaims {
+                uuid,
+                role: Some(role),
+            }) => Ok(Self {
+                aud: claims.aud,
+                exp: claims.exp,
+                iat: claims.iat,
+                iss: claims.iss,
+                sub: claims.sub,
+                project: uuid,
+                role,
+            }),
+            _ => Err(TokenError::ProjectInvite {
+                error: JsonWebTokenErrorKind::MissingRequiredClaim("proj".into()).into(),
+            }),
+        }
+    }
+}
+
+impl ProjectInviteClaims {
+    pub fn email(&self) -> &Email {
+        &self.sub
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EmbedClaims {
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub iss: String,
+    pub sub: Email,
+    pub perf: PerfClaims,
+}
+
+impl TryFrom<Claims> for EmbedClaims {
+    type Error = TokenError;
+
+    fn try_from(claims: Claims) -> Result<Self, Self::Error> {
+        match claims.perf {
+            Some(perf) => Ok(Self {
+                aud: claims.aud,
+                exp: claims.exp,
+                iat: claims.iat,
+                iss: claims.iss,
+                sub: claims.sub,
+                perf,
+            }),
+            None => Err(TokenError::Embed {
+                error: JsonWebTokenErrorKind::MissingRequiredClaim("perf".into()).into(),
+            }),
+        }
+    }
+}
+
+impl EmbedClaims {
+    pub fn expiration(&self) -> DateTime {
+        let date_time = DateTime::try_from(self.exp);
+        debug_assert!(date_time.is_ok(), "Expiration time is invalid");
+        date_time.unwrap_or_default()
+    }
+
+    pub fn issued_at(&self) -> DateTime {
+        let date_time = DateTime::try_from(self.iat);
+        debug_assert!(date_time.is_ok(), "Issued at time is invalid");
+        date_time.unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ClaimClaims {
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub iss: String,
+    pub sub: Email,
+    pub proj: ProjClaims,
+}
+
+impl TryFrom<Claims> for ClaimClaims {
+    type Error = TokenError;
+
+    fn try_from(claims: Claims) -> Result<Self, Self::Error> {
+        match claims.proj {
+            Some(proj) => Ok(Self {
+                aud: claims.aud,
+                exp: claims.exp,
+                iat: claims.iat,
+                iss: claims.iss,
+                sub: claims.sub,
+                proj,
+            }),
+            None => Err(TokenError::Claim {
+                error: JsonWebTokenErrorKind::MissingRequiredClaim("proj".into()).into(),
+            }),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use crate::claims::Claims;
 pub enum TokenError {
     #[error("Failed to encode JSON Web Token: {error}")]
     Encode {
-        claims: Claims,
+        claims: Box<Claims>,
         error: jsonwebtoken::errors::Error,
     },
     #[error("Failed to decode JSON Web Token: {error}")]
@@ -24,4 +24,10 @@ pub enum TokenError {
     },
     #[error("Invalid organizational invite: {error}")]
     Invite { error: jsonwebtoken::errors::Error },
+    #[error("Invalid project invite: {error}")]
+    ProjectInvite { error: jsonwebtoken::errors::Error },
+    #[error("Invalid perf embed: {error}")]
+    Embed { error: jsonwebtoken::errors::Error },
+    #[error("Invalid project claim: {error}")]
+    Claim { error: jsonwebtoken::errors::Error },
 }
@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use bencher_json::{organization::member::OrganizationRole, Email, Jwt, OrganizationUuid, Secret};
+use bencher_json::{
+    organization::member::OrganizationRole, project::ProjectRole, DateTime, Email, Jwt,
+    OrganizationUuid, ProjectUuid, Secret,
+};
 use chrono::Utc;
 use jsonwebtoken::{
     decode, encode, errors::ErrorKind as JsonWebTokenErrorKind, Algorithm, DecodingKey,
@@ -8,15 +11,34 @@ use jsonwebtoken::{
 };
 use once_cell::sync::Lazy;
 
-use crate::{Audience, Claims, InviteClaims, OrgClaims, TokenError};
+use crate::{
+    Audience, ClaimClaims, Claims, EmbedClaims, InviteClaims, OrgClaims, PerfClaims, ProjClaims,
+    ProjectInviteClaims, TokenError,
+};
 
 static HEADER: Lazy<Header> = Lazy::new(Header::default);
 static ALGORITHM: Lazy<Algorithm> = Lazy::new(Algorithm::default);
+// Claim tokens are created for anonymous reports,
+// so there is no user email to use as the subject.
+#[allow(clippy::expect_used)]
+static CLAIM_SUBJECT: Lazy<Email> = Lazy::new(|| {
+    "unclaimed@bencher.dev"
+        .parse()
+        .expect("Invalid claim subject")
+});
 
 pub struct TokenKey {
     pub issuer: String,
     pub encoding: EncodingKey,
     pub decoding: DecodingKey,
+    pub previous: Option<PreviousKey>,
+}
+
+/// A rotated out secret key that is still valid for decoding until it expires.
+/// New tokens are never encoded with the previous key.
+pub struct PreviousKey {
+    pub decoding: DecodingKey,
+    pub expiration: DateTime,
 }
 
 impl TokenKey {
@@ -25,34 +47,48 @@ impl TokenKey {
             issuer,
             encoding: EncodingKey::from_secret(secret_key.as_ref().as_bytes()),
             decoding: DecodingKey::from_secret(secret_key.as_ref().as_bytes()),
+            previous: None,
         }
     }
 
+    #[must_use]
+    pub fn with_previous_key(mut self, secret_key: &Secret, expiration: DateTime) -> Self {
+        self.previous = Some(PreviousKey {
+            decoding: DecodingKey::from_secret(secret_key.as_ref().as_bytes()),
+            expiration,
+        });
+        self
+    }
+
     fn new_jwt(
         &self,
         audience: Audience,
         email: Email,
         ttl: u32,
         org: Option<OrgClaims>,
+        perf: Option<PerfClaims>,
+        proj: Option<ProjClaims>,
     ) -> Result<Jwt, TokenError> {
-        let claims = Claims::new(audience, self.issuer.clone(), email, ttl, org);
-        Jwt::from_str(
-            &encode(&HEADER, &claims, &self.encoding)
-                .map_err(|e| TokenError::Encode { claims, error: e })?,
-        )
+        let claims = Claims::new(audience, self.issuer.clone(), email, ttl, org, perf, proj);
+        Jwt::from_str(&encode(&HEADER, &claims, &self.encoding).map_err(|e| {
+            TokenError::Encode {
+                claims: Box::new(claims),
+                error: e,
+            }
+        })?)
         .map_err(TokenError::Parse)
     }
 
     pub fn new_auth(&self, email: Email, ttl: u32) -> Result<Jwt, TokenError> {
-        self.new_jwt(Audience::Auth, email, ttl, None)
+        self.new_jwt(Audience::Auth, email, ttl, None, None, None)
     }
 
     pub fn new_client(&self, email: Email, ttl: u32) -> Result<Jwt, TokenError> {
-        self.new_jwt(Audience::Client, email, ttl, None)
+        self.new_jwt(Audience::Client, email, ttl, None, None, None)
     }
 
     pub fn new_api_key(&self, email: Email, ttl: u32) -> Result<Jwt, TokenError> {
-        self.new_jwt(Audience::ApiKey, email, ttl, None)
+        self.new_jwt(Audience::ApiKey, email, ttl, None, None, None)
     }
 
     pub fn new_invite(
@@ -66,7 +102,57 @@ impl TokenKey {
             uuid: org_uuid,
             role,
         };
-        self.new_jwt(Audience::Invite, email, ttl, Some(org_claims))
+        self.new_jwt(Audience::Invite, email, ttl, Some(org_claims), None, None)
+    }
+
+    pub fn new_project_invite(
+        &self,
+        email: Email,
+        ttl: u32,
+        project_uuid: ProjectUuid,
+        role: ProjectRole,
+    ) -> Result<Jwt, TokenError> {
+        let proj_claims = ProjClaims {
+            uuid: project_uuid,
+            role: Some(role),
+        };
+        self.new_jwt(
+            Audience::ProjectInvite,
+            email,
+            ttl,
+            None,
+            None,
+            Some(proj_claims),
+        )
+    }
+
+    pub fn new_embed(
+        &self,
+        email: Email,
+        ttl: u32,
+        project_uuid: ProjectUuid,
+        query: String,
+    ) -> Result<Jwt, TokenError> {
+        let perf_claims = PerfClaims {
+            project: project_uuid,
+            query,
+        };
+        self.new_jwt(Audience::Embed, email, ttl, None, Some(perf_claims), None)
+    }
+
+    pub fn new_claim(&self, ttl: u32, project_uuid: ProjectUuid) -> Result<Jwt, TokenError> {
+        let proj_claims = ProjClaims {
+            uuid: project_uuid,
+            role: None,
+        };
+        self.new_jwt(
+            Audience::Claim,
+            CLAIM_SUBJECT.clone(),
+            ttl,
+            None,
+            None,
+            Some(proj_claims),
+        )
     }
 
     fn validate(
@@ -79,13 +165,22 @@ impl TokenKey {
         validation.set_issuer(&[self.issuer.as_str()]);
         validation.set_required_spec_claims(&["aud", "exp", "iss", "sub"]);
 
+        let now = Utc::now().timestamp();
         let token_data: TokenData<Claims> = decode(token.as_ref(), &self.decoding, &validation)
+            .or_else(|error| match (error.kind(), &self.previous) {
+                // Tokens signed with the previous key are still valid during its grace period
+                (JsonWebTokenErrorKind::InvalidSignature, Some(previous))
+                    if now < previous.expiration.timestamp() =>
+                {
+                    decode(token.as_ref(), &previous.decoding, &validation)
+                },
+                _ => Err(error),
+            })
             .map_err(|error| TokenError::Decode {
                 token: token.clone(),
                 error,
             })?;
         let exp = token_data.claims.exp;
-        let now = Utc::now().timestamp();
         if exp < now {
             Err(TokenError::Expired {
                 exp,
@@ -114,13 +209,38 @@ impl TokenKey {
     pub fn validate_invite(&self, token: &Jwt) -> Result<InviteClaims, TokenError> {
         self.validate(token, &[Audience::Invite])?.claims.try_into()
     }
+
+    pub fn validate_project_invite(&self, token: &Jwt) -> Result<ProjectInviteClaims, TokenError> {
+        self.validate(token, &[Audience::ProjectInvite])?
+            .claims
+            .try_into()
+    }
+
+    /// Invites are either for an organization or for a single project.
+    /// An invite that is not valid for either audience is an error.
+    pub fn is_project_invite(&self, token: &Jwt) -> Result<bool, TokenError> {
+        let token_data = self.validate(token, &[Audience::Invite, Audience::ProjectInvite])?;
+        Ok(token_data.claims.aud == Audience::ProjectInvite.to_string())
+    }
+
+    pub fn validate_embed(&self, token: &Jwt) -> Result<EmbedClaims, TokenError> {
+        self.validate(token, &[Audience::Embed])?.claims.try_into()
+    }
+
+    pub fn validate_claim(&self, token: &Jwt) -> Result<ClaimClaims, TokenError> {
+        self.validate(token, &[Audience::Claim])?.claims.try_into()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::{thread, time};
 
-    use bencher_json::{organization::member::OrganizationRole, Email, OrganizationUuid};
+    use bencher_json::{
+        organization::member::OrganizationRole, project::ProjectRole, DateTime, Email,
+        OrganizationUuid, ProjectUuid, Secret,
+    };
+    use chrono::Utc;
     use once_cell::sync::Lazy;
 
     use crate::{Audience, DEFAULT_SECRET_KEY};
@@ -162,6 +282,38 @@ mod test {
         assert!(secret_key.validate_auth(&token).is_err());
     }
 
+    #[test]
+    fn test_jwt_previous_key() {
+        let previous_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+        let token = previous_key.new_auth(EMAIL.clone(), TTL).unwrap();
+
+        let new_secret_key: Secret = "NEW_SECRET_KEY".parse().unwrap();
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &new_secret_key);
+        assert!(secret_key.validate_auth(&token).is_err());
+
+        let expiration = DateTime::try_from(Utc::now().timestamp() + 60).unwrap();
+        let secret_key = secret_key.with_previous_key(&DEFAULT_SECRET_KEY, expiration);
+        let claims = secret_key.validate_auth(&token).unwrap();
+        assert_eq!(claims.sub, *EMAIL);
+
+        // New tokens are only signed with the new key
+        let new_token = secret_key.new_auth(EMAIL.clone(), TTL).unwrap();
+        assert!(previous_key.validate_auth(&new_token).is_err());
+        assert!(secret_key.validate_auth(&new_token).is_ok());
+    }
+
+    #[test]
+    fn test_jwt_previous_key_expired() {
+        let previous_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+        let token = previous_key.new_auth(EMAIL.clone(), TTL).unwrap();
+
+        let new_secret_key: Secret = "NEW_SECRET_KEY".parse().unwrap();
+        let expiration = DateTime::try_from(Utc::now().timestamp() - 1).unwrap();
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &new_secret_key)
+            .with_previous_key(&DEFAULT_SECRET_KEY, expiration);
+        assert!(secret_key.validate_auth(&token).is_err());
+    }
+
     #[test]
     fn test_jwt_client() {
         let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
@@ -249,4 +401,157 @@ mod test {
 
         assert!(secret_key.validate_invite(&token).is_err());
     }
+
+    #[test]
+    fn test_jwt_project_invite() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let project_uuid = ProjectUuid::new();
+        let role = ProjectRole::Reporter;
+
+        let token = secret_key
+            .new_project_invite(EMAIL.clone(), TTL, project_uuid, role)
+            .unwrap();
+
+        let claims = secret_key.validate_project_invite(&token).unwrap();
+
+        assert_eq!(claims.aud, Audience::ProjectInvite.to_string());
+        assert_eq!(claims.iss, BENCHER_DOT_DEV_ISSUER.to_owned());
+        assert_eq!(claims.iat, claims.exp - i64::from(TTL));
+        assert_eq!(claims.sub, *EMAIL);
+
+        assert_eq!(claims.project, project_uuid);
+        assert_eq!(claims.role, role);
+
+        // A project invite must not be usable as an organization invite or a claim token
+        assert!(secret_key.validate_invite(&token).is_err());
+        assert!(secret_key.validate_claim(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwt_project_invite_expired() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let token = secret_key
+            .new_project_invite(EMAIL.clone(), 0, ProjectUuid::new(), ProjectRole::Viewer)
+            .unwrap();
+
+        sleep_for_a_second();
+
+        assert!(secret_key.validate_project_invite(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwt_embed() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let project_uuid = ProjectUuid::new();
+        let query = "branches=a&testbeds=b&benchmarks=c&measures=d".to_owned();
+
+        let token = secret_key
+            .new_embed(EMAIL.clone(), TTL, project_uuid, query.clone())
+            .unwrap();
+
+        let claims = secret_key.validate_embed(&token).unwrap();
+
+        assert_eq!(claims.aud, Audience::Embed.to_string());
+        assert_eq!(claims.iss, BENCHER_DOT_DEV_ISSUER.to_owned());
+        assert_eq!(claims.iat, claims.exp - i64::from(TTL));
+        assert_eq!(claims.sub, *EMAIL);
+
+        assert_eq!(claims.perf.project, project_uuid);
+        assert_eq!(claims.perf.query, query);
+
+        // An embed token must not be usable as any other kind of token
+        assert!(secret_key.validate_client(&token).is_err());
+        assert!(secret_key.validate_api_key(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwt_embed_expired() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let project_uuid = ProjectUuid::new();
+        let query = "branches=a&testbeds=b&benchmarks=c&measures=d".to_owned();
+
+        let token = secret_key
+            .new_embed(EMAIL.clone(), 0, project_uuid, query)
+            .unwrap();
+
+        sleep_for_a_second();
+
+        assert!(secret_key.validate_embed(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwt_claim() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let project_uuid = ProjectUuid::new();
+
+        let token = secret_key.new_claim(TTL, project_uuid).unwrap();
+
+        let claims = secret_key.validate_claim(&token).unwrap();
+
+        assert_eq!(claims.aud, Audience::Claim.to_string());
+        assert_eq!(claims.iss, BENCHER_DOT_DEV_ISSUER.to_owned());
+        assert_eq!(claims.iat, claims.exp - i64::from(TTL));
+
+        assert_eq!(claims.proj.uuid, project_uuid);
+
+        // A claim token must not be usable as any other kind of token
+        assert!(secret_key.validate_client(&token).is_err());
+        assert!(secret_key.validate_invite(&token).is_err());
+        assert!(secret_key.validate_project_invite(&token).is_err());
+        assert!(secret_key.validate_embed(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwt_claim_expired() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let token = secret_key.new_claim(0, ProjectUuid::new()).unwrap();
+
+        sleep_for_a_second();
+
+        assert!(secret_key.validate_claim(&token).is_err());
+    }
+
+    #[test]
+    fn test_jwt_is_project_invite() {
+        let secret_key = TokenKey::new(BENCHER_DOT_DEV_ISSUER.to_owned(), &DEFAULT_SECRET_KEY);
+
+        let project_invite = secret_key
+            .new_project_invite(EMAIL.clone(), TTL, ProjectUuid::new(), ProjectRole::Viewer)
+            .unwrap();
+        assert!(secret_key.is_project_invite(&project_invite).unwrap());
+
+        let org_invite = secret_key
+            .new_invite(
+                EMAIL.clone(),
+                TTL,
+                OrganizationUuid::new(),
+                OrganizationRole::Member,
+            )
+            .unwrap();
+        assert!(!secret_key.is_project_invite(&org_invite).unwrap());
+
+        // Any other kind of token is neither kind of invite
+        let auth = secret_key.new_auth(EMAIL.clone(), TTL).unwrap();
+        assert!(secret_key.is_project_invite(&auth).is_err());
+
+        // An expired project invite must not fall back to being an organization invite
+        let expired = secret_key
+            .new_project_invite(EMAIL.clone(), 0, ProjectUuid::new(), ProjectRole::Viewer)
+            .unwrap();
+        sleep_for_a_second();
+        assert!(secret_key.is_project_invite(&expired).is_err());
+
+        // A project invite from another issuer is not valid
+        let other_key = TokenKey::new("other.dev".to_owned(), &DEFAULT_SECRET_KEY);
+        let other_invite = other_key
+            .new_project_invite(EMAIL.clone(), TTL, ProjectUuid::new(), ProjectRole::Viewer)
+            .unwrap();
+        assert!(secret_key.is_project_invite(&other_invite).is_err());
+    }
 }
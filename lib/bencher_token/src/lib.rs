@@ -7,9 +7,12 @@ mod error;
 mod key;
 
 pub use audience::Audience;
-pub use claims::{Claims, InviteClaims, OrgClaims};
+pub use claims::{
+    ClaimClaims, Claims, EmbedClaims, InviteClaims, OrgClaims, PerfClaims, ProjClaims,
+    ProjectInviteClaims,
+};
 pub use error::TokenError;
-pub use key::TokenKey;
+pub use key::{PreviousKey, TokenKey};
 
 #[cfg(debug_assertions)]
 #[allow(clippy::expect_used)]
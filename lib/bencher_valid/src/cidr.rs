@@ -0,0 +1,178 @@
+use derive_more::Display;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use std::{fmt, net::IpAddr, str::FromStr};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::ValidError;
+
+const IPV4_BITS: u8 = 32;
+const IPV6_BITS: u8 = 128;
+
+/// An IPv4 or IPv6 network in CIDR notation, such as `10.0.0.0/8` or `2001:db8::/32`.
+/// A bare IP address is treated as a single host network.
+#[typeshare::typeshare]
+#[derive(Debug, Display, Clone, Eq, PartialEq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+pub struct Cidr(String);
+
+impl FromStr for Cidr {
+    type Err = ValidError;
+
+    fn from_str(cidr: &str) -> Result<Self, Self::Err> {
+        if is_valid_cidr(cidr) {
+            Ok(Self(cidr.into()))
+        } else {
+            Err(ValidError::Cidr(cidr.into()))
+        }
+    }
+}
+
+impl AsRef<str> for Cidr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Cidr> for String {
+    fn from(cidr: Cidr) -> Self {
+        cidr.0
+    }
+}
+
+impl<'de> Deserialize<'de> for Cidr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CidrVisitor)
+    }
+}
+
+struct CidrVisitor;
+
+impl Visitor<'_> for CidrVisitor {
+    type Value = Cidr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an IP address or network in CIDR notation")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+}
+
+impl Cidr {
+    /// Check whether an IP address is within the network.
+    /// IPv4-mapped IPv6 addresses are compared as IPv4 addresses.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let Some((network, prefix)) = parse_cidr(&self.0) else {
+            return false;
+        };
+        match (network, to_canonical(ip)) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(u32::from(IPV4_BITS - prefix))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            },
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(u32::from(IPV6_BITS - prefix))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            },
+            (IpAddr::V4(_), IpAddr::V6(_)) | (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+}
+
+fn to_canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V4(_) => ip,
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+    }
+}
+
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u8)> {
+    let (network, prefix) = match cidr.split_once('/') {
+        Some((network, prefix)) => (network, Some(prefix)),
+        None => (cidr, None),
+    };
+    let network = network.parse::<IpAddr>().ok()?;
+    let max_prefix = match network {
+        IpAddr::V4(_) => IPV4_BITS,
+        IpAddr::V6(_) => IPV6_BITS,
+    };
+    let prefix = match prefix {
+        // Only allow plain decimal digits, so that `+8` or ` 8` are rejected
+        Some(prefix) if !prefix.is_empty() && prefix.bytes().all(|b| b.is_ascii_digit()) => {
+            prefix.parse::<u8>().ok()?
+        },
+        Some(_) => return None,
+        None => max_prefix,
+    };
+    (prefix <= max_prefix).then_some((network, prefix))
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn is_valid_cidr(cidr: &str) -> bool {
+    parse_cidr(cidr).is_some()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_valid_cidr, Cidr};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_cidr() {
+        assert_eq!(true, is_valid_cidr("10.0.0.0/8"));
+        assert_eq!(true, is_valid_cidr("192.168.1.0/24"));
+        assert_eq!(true, is_valid_cidr("192.168.1.1"));
+        assert_eq!(true, is_valid_cidr("0.0.0.0/0"));
+        assert_eq!(true, is_valid_cidr("2001:db8::/32"));
+        assert_eq!(true, is_valid_cidr("::1"));
+        assert_eq!(true, is_valid_cidr("::/0"));
+
+        assert_eq!(false, is_valid_cidr(""));
+        assert_eq!(false, is_valid_cidr("10.0.0.0/"));
+        assert_eq!(false, is_valid_cidr("10.0.0.0/33"));
+        assert_eq!(false, is_valid_cidr("10.0.0.0/+8"));
+        assert_eq!(false, is_valid_cidr(" 10.0.0.0/8"));
+        assert_eq!(false, is_valid_cidr("10.0.0/8"));
+        assert_eq!(false, is_valid_cidr("2001:db8::/129"));
+        assert_eq!(false, is_valid_cidr("bencher.dev"));
+    }
+
+    #[test]
+    fn test_cidr_contains() {
+        let cidr: Cidr = "10.0.0.0/8".parse().unwrap();
+        assert_eq!(true, cidr.contains("10.1.2.3".parse().unwrap()));
+        assert_eq!(true, cidr.contains("::ffff:10.1.2.3".parse().unwrap()));
+        assert_eq!(false, cidr.contains("11.0.0.1".parse().unwrap()));
+        assert_eq!(false, cidr.contains("::1".parse().unwrap()));
+
+        let cidr: Cidr = "192.168.1.1".parse().unwrap();
+        assert_eq!(true, cidr.contains("192.168.1.1".parse().unwrap()));
+        assert_eq!(false, cidr.contains("192.168.1.2".parse().unwrap()));
+
+        let cidr: Cidr = "0.0.0.0/0".parse().unwrap();
+        assert_eq!(true, cidr.contains("203.0.113.7".parse().unwrap()));
+
+        let cidr: Cidr = "2001:db8::/32".parse().unwrap();
+        assert_eq!(true, cidr.contains("2001:db8:1::1".parse().unwrap()));
+        assert_eq!(false, cidr.contains("2001:db9::1".parse().unwrap()));
+        assert_eq!(false, cidr.contains("10.0.0.1".parse().unwrap()));
+    }
+}
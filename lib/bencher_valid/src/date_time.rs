@@ -1,6 +1,7 @@
 use std::{fmt, str::FromStr};
 
-use chrono::{TimeZone, Utc};
+use chrono::{Datelike, Days, NaiveDate, TimeDelta, TimeZone as _, Utc};
+use chrono_tz::Tz;
 use derive_more::Display;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
@@ -10,10 +11,23 @@ use serde::{
     Deserialize, Deserializer, Serialize,
 };
 
-use crate::ValidError;
+use crate::{TimeZone, ValidError, Weekday};
 
 #[typeshare::typeshare]
-#[derive(Debug, Display, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug,
+    Display,
+    Clone,
+    Copy,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
 #[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::BigInt))]
@@ -104,6 +118,55 @@ impl DateTime {
     pub fn into_inner(self) -> chrono::DateTime<Utc> {
         self.0
     }
+
+    /// The start of the day containing this date time, in the given time zone
+    #[must_use]
+    pub fn start_of_day(self, time_zone: &TimeZone) -> Self {
+        let tz = time_zone.tz();
+        let date = self.0.with_timezone(&tz).date_naive();
+        Self::local_midnight(date, tz).unwrap_or(self)
+    }
+
+    /// The start of the week containing this date time, in the given time zone
+    /// with weeks starting on `week_start`
+    #[must_use]
+    pub fn start_of_week(self, time_zone: &TimeZone, week_start: Weekday) -> Self {
+        let tz = time_zone.tz();
+        let date = self.0.with_timezone(&tz).date_naive();
+        let week_start: chrono::Weekday = week_start.into();
+        let days_since_week_start = (date.weekday().num_days_from_monday() + 7
+            - week_start.num_days_from_monday())
+        .rem_euclid(7);
+        date.checked_sub_days(Days::new(days_since_week_start.into()))
+            .and_then(|date| Self::local_midnight(date, tz))
+            .unwrap_or(self)
+    }
+
+    /// The start of the month containing this date time, in the given time zone
+    #[must_use]
+    pub fn start_of_month(self, time_zone: &TimeZone) -> Self {
+        let tz = time_zone.tz();
+        let date = self.0.with_timezone(&tz).date_naive();
+        date.with_day(1)
+            .and_then(|date| Self::local_midnight(date, tz))
+            .unwrap_or(self)
+    }
+
+    fn local_midnight(date: NaiveDate, tz: Tz) -> Option<Self> {
+        let midnight = date.and_hms_opt(0, 0, 0)?;
+        midnight
+            .and_local_timezone(tz)
+            .earliest()
+            // Some time zones skip midnight when changing to daylight saving time,
+            // in which case the day starts an hour later.
+            .or_else(|| {
+                midnight
+                    .checked_add_signed(TimeDelta::hours(1))?
+                    .and_local_timezone(tz)
+                    .earliest()
+            })
+            .map(|date_time| Self(date_time.with_timezone(&Utc)))
+    }
 }
 
 impl FromStr for DateTime {
@@ -169,3 +232,132 @@ mod db {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{TimeZone, Weekday};
+
+    use super::DateTime;
+
+    // Wednesday, 2024-10-23 02:30:00 UTC
+    const WEDNESDAY: i64 = 1_729_650_600;
+
+    fn date_time(timestamp: i64) -> DateTime {
+        DateTime::try_from(timestamp).unwrap()
+    }
+
+    #[test]
+    fn test_start_of_day() {
+        let utc = TimeZone::default();
+        assert_eq!(
+            date_time(1_729_641_600),
+            date_time(WEDNESDAY).start_of_day(&utc)
+        );
+
+        // Still Tuesday in UTC-05:00
+        let behind: TimeZone = "America/Bogota".parse().unwrap();
+        assert_eq!(
+            date_time(1_729_573_200),
+            date_time(WEDNESDAY).start_of_day(&behind)
+        );
+
+        // Already Wednesday in UTC+05:30
+        let ahead: TimeZone = "Asia/Kolkata".parse().unwrap();
+        assert_eq!(
+            date_time(1_729_621_800),
+            date_time(WEDNESDAY).start_of_day(&ahead)
+        );
+    }
+
+    #[test]
+    fn test_start_of_week() {
+        let utc = TimeZone::default();
+        // Monday, 2024-10-21 00:00:00 UTC
+        assert_eq!(
+            date_time(1_729_468_800),
+            date_time(WEDNESDAY).start_of_week(&utc, Weekday::Monday)
+        );
+        // Sunday, 2024-10-20 00:00:00 UTC
+        assert_eq!(
+            date_time(1_729_382_400),
+            date_time(WEDNESDAY).start_of_week(&utc, Weekday::Sunday)
+        );
+        // Wednesday, 2024-10-23 00:00:00 UTC
+        assert_eq!(
+            date_time(1_729_641_600),
+            date_time(WEDNESDAY).start_of_week(&utc, Weekday::Wednesday)
+        );
+        // Thursday, 2024-10-17 00:00:00 UTC
+        assert_eq!(
+            date_time(1_729_123_200),
+            date_time(WEDNESDAY).start_of_week(&utc, Weekday::Thursday)
+        );
+
+        // Still Tuesday in UTC-05:00, so the week starts on Monday, 2024-10-21 00:00:00 UTC-05:00
+        let behind: TimeZone = "America/Bogota".parse().unwrap();
+        assert_eq!(
+            date_time(1_729_486_800),
+            date_time(WEDNESDAY).start_of_week(&behind, Weekday::Monday)
+        );
+        // Still Tuesday in UTC-05:00, so the week starts on Tuesday, 2024-10-22 00:00:00 UTC-05:00
+        assert_eq!(
+            date_time(1_729_573_200),
+            date_time(WEDNESDAY).start_of_week(&behind, Weekday::Tuesday)
+        );
+    }
+
+    #[test]
+    fn test_start_of_week_daylight_saving_time() {
+        // Wednesday, 2024-03-13 12:00:00 UTC, after the change to daylight saving time
+        let wednesday = date_time(1_710_331_200);
+        let new_york: TimeZone = "America/New_York".parse().unwrap();
+        // Monday, 2024-03-11 00:00:00 UTC-04:00
+        assert_eq!(
+            date_time(1_710_129_600),
+            wednesday.start_of_week(&new_york, Weekday::Monday)
+        );
+        // Sunday, 2024-03-10 00:00:00 UTC-05:00, before the change to daylight saving time
+        assert_eq!(
+            date_time(1_710_046_800),
+            wednesday.start_of_week(&new_york, Weekday::Sunday)
+        );
+    }
+
+    #[test]
+    fn test_start_of_day_skipped_midnight() {
+        // Sunday, 2024-09-08 12:00:00 UTC
+        // The clocks in Santiago skip from midnight to 01:00 that day
+        let santiago: TimeZone = "America/Santiago".parse().unwrap();
+        // Sunday, 2024-09-08 01:00:00 UTC-03:00
+        assert_eq!(
+            date_time(1_725_768_000),
+            date_time(1_725_796_800).start_of_day(&santiago)
+        );
+    }
+
+    #[test]
+    fn test_start_of_month() {
+        let utc = TimeZone::default();
+        // Tuesday, 2024-10-01 00:00:00 UTC
+        assert_eq!(
+            date_time(1_727_740_800),
+            date_time(WEDNESDAY).start_of_month(&utc)
+        );
+
+        // Tuesday, 2024-10-01 00:00:00 UTC+05:30
+        let ahead: TimeZone = "Asia/Kolkata".parse().unwrap();
+        assert_eq!(
+            date_time(1_727_721_000),
+            date_time(WEDNESDAY).start_of_month(&ahead)
+        );
+
+        // Friday, 2024-11-01 02:30:00 UTC is still October in UTC-05:00
+        let behind: TimeZone = "America/Bogota".parse().unwrap();
+        assert_eq!(
+            date_time(1_727_758_800),
+            date_time(1_730_428_200).start_of_month(&behind)
+        );
+    }
+}
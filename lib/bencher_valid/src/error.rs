@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::{Boundary, SampleSize, Window};
+use crate::{Boundary, Decay, ModelTest, SampleSize, Window};
 
 pub(crate) const REGEX_ERROR: &str = "Failed to compile regex.";
 
@@ -52,10 +52,20 @@ pub enum ValidError {
     Window(u32),
     #[error("Failed to parse model window: {0}")]
     WindowStr(std::num::ParseIntError),
+    #[error("Invalid model decay factor: {0}")]
+    Decay(f64),
+    #[error("Failed to parse model decay factor: {0}")]
+    DecayStr(std::num::ParseFloatError),
     #[error("Invalid plot index: {0}")]
     Index(u8),
     #[error("Failed to parse plot index: {0}")]
     IndexStr(std::num::ParseIntError),
+    #[error("Failed to validate time zone: {0}")]
+    TimeZone(String),
+    #[error("Failed to validate weekday: {0}")]
+    Weekday(String),
+    #[error("Failed to validate CIDR IP address or network: {0}")]
+    Cidr(String),
 
     #[cfg(feature = "plus")]
     #[error("Failed to validate plan level: {0}")]
@@ -114,6 +124,8 @@ pub enum ValidError {
     StaticMaxSampleSize(SampleSize),
     #[error("Invalid static model, includes a sampling window: {0}")]
     StaticWindow(Window),
+    #[error("Invalid {test} model, includes a decay factor: {decay}")]
+    NonEwmaDecay { test: ModelTest, decay: Decay },
     #[error("Invalid percentage boundary: {0}")]
     PercentageBoundary(f64),
     #[error("Invalid statistical boundary: {0}")]
@@ -5,6 +5,7 @@ use wasm_bindgen::prelude::*;
 
 mod benchmark_name;
 mod branch_name;
+mod cidr;
 mod date_time;
 mod email;
 mod error;
@@ -19,14 +20,17 @@ mod resource_id;
 mod resource_name;
 mod secret;
 mod slug;
+mod time_zone;
 mod url;
 mod user_name;
+mod weekday;
 
 pub use crate::git_hash::GitHash;
 pub use crate::slug::Slug;
-pub use crate::url::Url;
+pub use crate::url::{is_public_ip, Url};
 pub use benchmark_name::BenchmarkName;
 pub use branch_name::BranchName;
+pub use cidr::Cidr;
 pub use date_time::{DateTime, DateTimeMillis};
 pub use email::Email;
 pub use error::ValidError;
@@ -35,6 +39,7 @@ pub use index::Index;
 pub use jwt::Jwt;
 pub use model::{
     boundary::{Boundary, CdfBoundary, IqrBoundary, PercentageBoundary},
+    decay::Decay,
     model_test::ModelTest,
     sample_size::SampleSize,
     window::Window,
@@ -50,7 +55,9 @@ pub use plus::{
 pub use resource_id::{ResourceId, ResourceIdKind};
 pub use resource_name::ResourceName;
 pub use secret::Secret;
+pub use time_zone::TimeZone;
 pub use user_name::UserName;
+pub use weekday::Weekday;
 
 const MAX_LEN: usize = 64;
 
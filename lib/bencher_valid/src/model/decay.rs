@@ -0,0 +1,165 @@
+use derive_more::Display;
+use ordered_float::OrderedFloat;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use std::{fmt, str::FromStr};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::ValidError;
+
+#[typeshare::typeshare]
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Double))]
+pub struct Decay(OrderedFloat<f64>);
+
+impl TryFrom<f64> for Decay {
+    type Error = ValidError;
+
+    fn try_from(decay: f64) -> Result<Self, Self::Error> {
+        is_valid_decay(decay)
+            .then(|| Self(decay.into()))
+            .ok_or(ValidError::Decay(decay))
+    }
+}
+
+impl From<Decay> for f64 {
+    fn from(decay: Decay) -> Self {
+        decay.0.into()
+    }
+}
+
+impl FromStr for Decay {
+    type Err = ValidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(f64::from_str(s).map_err(ValidError::DecayStr)?)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decay {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_f64(DecayVisitor)
+    }
+}
+
+struct DecayVisitor;
+
+impl Visitor<'_> for DecayVisitor {
+    type Value = Decay;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decay factor greater than 0.0 and less than 1.0")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        #[allow(clippy::cast_precision_loss)]
+        (value as f64).try_into().map_err(E::custom)
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.try_into().map_err(E::custom)
+    }
+}
+
+impl Decay {
+    pub const TEN: Self = Self(OrderedFloat(0.1));
+    pub const TWENTY: Self = Self(OrderedFloat(0.2));
+    pub const THIRTY: Self = Self(OrderedFloat(0.3));
+    pub const FIFTY: Self = Self(OrderedFloat(0.5));
+    // A decay factor of `0.2` gives an effective window of about nine samples.
+    pub const DEFAULT: Self = Self::TWENTY;
+}
+
+impl Default for Decay {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(feature = "db")]
+mod db {
+    use super::Decay;
+
+    impl<DB> diesel::serialize::ToSql<diesel::sql_types::Double, DB> for Decay
+    where
+        DB: diesel::backend::Backend,
+        for<'a> f64: diesel::serialize::ToSql<diesel::sql_types::Double, DB>
+            + Into<<DB::BindCollector<'a> as diesel::query_builder::BindCollector<'a, DB>>::Buffer>,
+    {
+        fn to_sql<'b>(
+            &'b self,
+            out: &mut diesel::serialize::Output<'b, '_, DB>,
+        ) -> diesel::serialize::Result {
+            out.set_value(f64::from(*self));
+            Ok(diesel::serialize::IsNull::No)
+        }
+    }
+
+    impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Double, DB> for Decay
+    where
+        DB: diesel::backend::Backend,
+        f64: diesel::deserialize::FromSql<diesel::sql_types::Double, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+            f64::from_sql(bytes)?.try_into().map_err(Into::into)
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn is_valid_decay(decay: f64) -> bool {
+    // The decay factor must be greater than 0.0 and less than 1.0
+    decay > 0.0 && decay < 1.0
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{is_valid_decay, Decay};
+
+    #[test]
+    fn test_decay() {
+        assert_eq!(true, is_valid_decay(Decay::TEN.into()));
+        assert_eq!(true, is_valid_decay(Decay::TWENTY.into()));
+        assert_eq!(true, is_valid_decay(Decay::THIRTY.into()));
+        assert_eq!(true, is_valid_decay(Decay::FIFTY.into()));
+        assert_eq!(true, is_valid_decay(f64::EPSILON));
+        assert_eq!(true, is_valid_decay(0.999));
+
+        assert_eq!(false, is_valid_decay(0.0));
+        assert_eq!(false, is_valid_decay(1.0));
+        assert_eq!(false, is_valid_decay(-0.5));
+        assert_eq!(false, is_valid_decay(1.5));
+        assert_eq!(false, is_valid_decay(f64::INFINITY));
+        assert_eq!(false, is_valid_decay(f64::NEG_INFINITY));
+        assert_eq!(false, is_valid_decay(f64::NAN));
+    }
+
+    #[test]
+    fn test_decay_serde() {
+        let decay: Decay = serde_json::from_str("0.5").unwrap();
+        assert_eq!(Decay::FIFTY, decay);
+
+        assert!(serde_json::from_str::<Decay>("0").is_err());
+        assert!(serde_json::from_str::<Decay>("1").is_err());
+        assert!(serde_json::from_str::<Decay>("1.5").is_err());
+    }
+}
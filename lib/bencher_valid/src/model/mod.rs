@@ -8,11 +8,13 @@ use serde::{Deserialize, Serialize};
 use crate::ValidError;
 
 pub mod boundary;
+pub mod decay;
 pub mod model_test;
 pub mod sample_size;
 pub mod window;
 
 use boundary::{Boundary, CdfBoundary, IqrBoundary, PercentageBoundary};
+use decay::Decay;
 use model_test::ModelTest;
 use sample_size::SampleSize;
 use window::Window;
@@ -37,6 +39,10 @@ pub struct Model {
     /// The upper boundary used to calculate the upper boundary limit.
     /// The requirements for this field depend on which `test` is selected.
     pub upper_boundary: Option<Boundary>,
+    /// The decay factor used by the exponentially weighted moving average (EWMA) test.
+    /// It must be greater than 0.0 and less than 1.0, with higher values weighting recent samples more heavily.
+    /// This field is only valid for the `ewma` test and defaults to `0.2`.
+    pub decay: Option<Decay>,
 }
 
 impl Model {
@@ -48,6 +54,7 @@ impl Model {
             window: None,
             lower_boundary: Some(Boundary::NINETY_NINE),
             upper_boundary: None,
+            decay: None,
         }
     }
 
@@ -59,6 +66,7 @@ impl Model {
             window: None,
             lower_boundary: None,
             upper_boundary: Some(Boundary::NINETY_NINE),
+            decay: None,
         }
     }
 
@@ -75,7 +83,13 @@ pub fn validate_model(model: Model) -> Result<(), ValidError> {
         window,
         lower_boundary,
         upper_boundary,
+        decay,
     } = model;
+    if let Some(decay) = decay {
+        if !matches!(test, ModelTest::Ewma) {
+            return Err(ValidError::NonEwmaDecay { test, decay });
+        }
+    }
     match test {
         ModelTest::Static => {
             if let Some(&min_sample_size) = min_sample_size.as_ref() {
@@ -102,7 +116,7 @@ pub fn validate_model(model: Model) -> Result<(), ValidError> {
             validate_sample_size(min_sample_size, max_sample_size)?;
             validate_boundary::<PercentageBoundary>(lower_boundary, upper_boundary)
         },
-        ModelTest::ZScore | ModelTest::TTest | ModelTest::LogNormal => {
+        ModelTest::ZScore | ModelTest::TTest | ModelTest::LogNormal | ModelTest::Ewma => {
             validate_sample_size(min_sample_size, max_sample_size)?;
             validate_boundary::<CdfBoundary>(lower_boundary, upper_boundary)
         },
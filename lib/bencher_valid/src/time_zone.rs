@@ -0,0 +1,130 @@
+use chrono_tz::Tz;
+use derive_more::Display;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use std::{fmt, str::FromStr};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::ValidError;
+
+/// An IANA time zone name (ie `America/New_York`)
+#[typeshare::typeshare]
+#[derive(Debug, Display, Clone, Eq, PartialEq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
+pub struct TimeZone(String);
+
+#[cfg(feature = "db")]
+crate::typed_string!(TimeZone);
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self(Self::UTC.into())
+    }
+}
+
+impl FromStr for TimeZone {
+    type Err = ValidError;
+
+    fn from_str(time_zone: &str) -> Result<Self, Self::Err> {
+        if is_valid_time_zone(time_zone) {
+            Ok(Self(time_zone.into()))
+        } else {
+            Err(ValidError::TimeZone(time_zone.into()))
+        }
+    }
+}
+
+impl AsRef<str> for TimeZone {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<TimeZone> for String {
+    fn from(time_zone: TimeZone) -> Self {
+        time_zone.0
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeZone {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TimeZoneVisitor)
+    }
+}
+
+struct TimeZoneVisitor;
+
+impl Visitor<'_> for TimeZoneVisitor {
+    type Value = TimeZone;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a valid IANA time zone name")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn is_valid_time_zone(time_zone: &str) -> bool {
+    time_zone.parse::<Tz>().is_ok()
+}
+
+impl TimeZone {
+    pub const UTC: &'static str = "UTC";
+
+    pub fn tz(&self) -> Tz {
+        let tz = self.as_ref().parse();
+        debug_assert!(tz.is_ok(), "TimeZone is invalid");
+        tz.unwrap_or(Tz::UTC)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono_tz::Tz;
+    use pretty_assertions::assert_eq;
+
+    use super::{is_valid_time_zone, TimeZone};
+
+    #[test]
+    fn test_time_zone() {
+        assert_eq!(true, is_valid_time_zone("UTC"));
+        assert_eq!(true, is_valid_time_zone("America/New_York"));
+        assert_eq!(true, is_valid_time_zone("Asia/Kolkata"));
+        assert_eq!(true, is_valid_time_zone("Australia/Adelaide"));
+        assert_eq!(true, is_valid_time_zone("Etc/GMT+5"));
+
+        assert_eq!(false, is_valid_time_zone(""));
+        assert_eq!(false, is_valid_time_zone("+00:00"));
+        assert_eq!(false, is_valid_time_zone("+05:30"));
+        assert_eq!(false, is_valid_time_zone("America/Nowhere"));
+        assert_eq!(false, is_valid_time_zone("america/new_york"));
+        assert_eq!(false, is_valid_time_zone(" UTC"));
+        assert_eq!(false, is_valid_time_zone("UTC "));
+    }
+
+    #[test]
+    fn test_time_zone_tz() {
+        let time_zone: TimeZone = "America/New_York".parse().unwrap();
+        assert_eq!(Tz::America__New_York, time_zone.tz());
+        let time_zone: TimeZone = "Asia/Kolkata".parse().unwrap();
+        assert_eq!(Tz::Asia__Kolkata, time_zone.tz());
+        assert_eq!(Tz::UTC, TimeZone::default().tz());
+    }
+}
@@ -1,7 +1,11 @@
 use derive_more::Display;
 #[cfg(feature = "schema")]
 use schemars::JsonSchema;
-use std::{fmt, str::FromStr};
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
@@ -34,6 +38,31 @@ impl FromStr for Url {
     }
 }
 
+impl Url {
+    /// Check that the URL uses the `http` or `https` scheme,
+    /// so it is safe to render as a link.
+    pub fn is_web(&self) -> bool {
+        ::url::Url::from_str(&self.0).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+    }
+
+    /// Check that the host of the URL is not `localhost` or an IP address that is not publicly routable.
+    /// A domain name must still be checked once it has been resolved to IP addresses.
+    pub fn has_public_host(&self) -> bool {
+        let Ok(url) = ::url::Url::from_str(&self.0) else {
+            return false;
+        };
+        match url.host() {
+            Some(::url::Host::Domain(domain)) => {
+                let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+                domain != LOCALHOST && !domain.ends_with(&format!(".{LOCALHOST}"))
+            },
+            Some(::url::Host::Ipv4(ip)) => is_public_ip(ip.into()),
+            Some(::url::Host::Ipv6(ip)) => is_public_ip(ip.into()),
+            None => false,
+        }
+    }
+}
+
 impl AsRef<str> for Url {
     fn as_ref(&self) -> &str {
         &self.0
@@ -91,9 +120,64 @@ pub fn is_valid_url(url: &str) -> bool {
     ::url::Url::from_str(url).is_ok()
 }
 
+const LOCALHOST: &str = "localhost";
+
+/// Check that an IP address is publicly routable.
+/// Unspecified, loopback, private, shared, link-local, documentation, benchmarking,
+/// multicast, broadcast, and reserved addresses are not.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(a == 0
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || ip.is_broadcast()
+        // Shared address space, 100.64.0.0/10
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // IETF protocol assignments, 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking, 198.18.0.0/15
+        || (a == 198 && (b & 0b1111_1110) == 18)
+        // Reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    // IPv4-mapped addresses, ::ffff:0:0/96
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return is_public_ipv4(ipv4);
+    }
+    let segments = ip.segments();
+    // IPv4/IPv6 translation, 64:ff9b::/96
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [.., a, b, c, d] = ip.octets();
+        return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+    }
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local, fc00::/7
+        || (segments[0] & 0xfe00) == 0xfc00
+        // Link-local, fe80::/10
+        || (segments[0] & 0xffc0) == 0xfe80
+        // Site-local (deprecated), fec0::/10
+        || (segments[0] & 0xffc0) == 0xfec0
+        // Documentation, 2001:db8::/32
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+}
+
 #[cfg(test)]
 mod test {
-    use super::is_valid_url;
+    use super::{is_public_ip, is_valid_url, Url};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -105,4 +189,118 @@ mod test {
         assert_eq!(false, is_valid_url("bad"));
         assert_eq!(false, is_valid_url("example.com"));
     }
+
+    #[test]
+    fn test_url_is_web() {
+        for url in [
+            "http://example.com",
+            "https://example.com/report?id=1",
+            "HTTPS://EXAMPLE.COM",
+        ] {
+            let url: Url = url.parse().unwrap();
+            assert_eq!(true, url.is_web(), "{url}");
+        }
+
+        for url in [
+            "javascript:alert(1)",
+            "data:text/html,<script>alert(1)</script>",
+            "file:///etc/passwd",
+            "ftp://example.com",
+            "mailto:muriel@example.com",
+        ] {
+            let url: Url = url.parse().unwrap();
+            assert_eq!(false, url.is_web(), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in [
+            "1.1.1.1",
+            "8.8.8.8",
+            "100.63.255.255",
+            "100.128.0.0",
+            "172.15.255.255",
+            "172.32.0.0",
+            "192.0.1.1",
+            "198.17.255.255",
+            "198.20.0.0",
+            "223.255.255.255",
+            "2606:4700:4700::1111",
+            "::ffff:1.1.1.1",
+            "64:ff9b::808:808",
+        ] {
+            assert_eq!(true, is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+
+        for ip in [
+            "0.0.0.0",
+            "0.1.2.3",
+            "127.0.0.1",
+            "127.255.255.254",
+            "10.0.0.1",
+            "172.16.0.1",
+            "172.31.255.255",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "100.127.255.255",
+            "192.0.0.1",
+            "192.0.2.1",
+            "198.18.0.1",
+            "198.19.255.255",
+            "198.51.100.1",
+            "203.0.113.1",
+            "224.0.0.1",
+            "240.0.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "fc00::1",
+            "fd12:3456:789a::1",
+            "fe80::1",
+            "febf::1",
+            "fec0::1",
+            "ff02::1",
+            "2001:db8::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "64:ff9b::a00:1",
+        ] {
+            assert_eq!(false, is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_url_has_public_host() {
+        for url in [
+            "https://example.com/webhook",
+            "http://bencher.dev:8080",
+            "https://1.1.1.1/webhook",
+            "https://[2606:4700:4700::1111]/webhook",
+            "https://localhost.example.com",
+        ] {
+            let url: Url = url.parse().unwrap();
+            assert_eq!(true, url.has_public_host(), "{url}");
+        }
+
+        for url in [
+            "http://localhost:61016",
+            "http://LOCALHOST.",
+            "http://api.localhost",
+            "http://127.0.0.1",
+            "http://0.0.0.0:61016",
+            "http://10.0.0.1/webhook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]:61016",
+            "http://[::ffff:127.0.0.1]",
+            "http://[fd00::1]",
+            "http://0x7f.0.0.1",
+            "http://2130706433",
+            "mailto:muriel@example.com",
+        ] {
+            let url: Url = url.parse().unwrap();
+            assert_eq!(false, url.has_public_host(), "{url}");
+        }
+    }
 }
@@ -0,0 +1,99 @@
+use derive_more::Display;
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::ValidError;
+
+const MONDAY: &str = "monday";
+const TUESDAY: &str = "tuesday";
+const WEDNESDAY: &str = "wednesday";
+const THURSDAY: &str = "thursday";
+const FRIDAY: &str = "friday";
+const SATURDAY: &str = "saturday";
+const SUNDAY: &str = "sunday";
+
+#[typeshare::typeshare]
+#[derive(Debug, Display, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "db", derive(diesel::FromSqlRow, diesel::AsExpression))]
+#[cfg_attr(feature = "db", diesel(sql_type = diesel::sql_types::Text))]
+#[serde(rename_all = "snake_case")]
+pub enum Weekday {
+    #[default]
+    #[display("monday")]
+    Monday,
+    #[display("tuesday")]
+    Tuesday,
+    #[display("wednesday")]
+    Wednesday,
+    #[display("thursday")]
+    Thursday,
+    #[display("friday")]
+    Friday,
+    #[display("saturday")]
+    Saturday,
+    #[display("sunday")]
+    Sunday,
+}
+
+#[cfg(feature = "db")]
+crate::typed_string!(Weekday);
+
+impl FromStr for Weekday {
+    type Err = ValidError;
+
+    fn from_str(weekday: &str) -> Result<Self, Self::Err> {
+        match weekday {
+            MONDAY => Ok(Self::Monday),
+            TUESDAY => Ok(Self::Tuesday),
+            WEDNESDAY => Ok(Self::Wednesday),
+            THURSDAY => Ok(Self::Thursday),
+            FRIDAY => Ok(Self::Friday),
+            SATURDAY => Ok(Self::Saturday),
+            SUNDAY => Ok(Self::Sunday),
+            _ => Err(ValidError::Weekday(weekday.into())),
+        }
+    }
+}
+
+impl From<Weekday> for chrono::Weekday {
+    fn from(weekday: Weekday) -> Self {
+        match weekday {
+            Weekday::Monday => Self::Mon,
+            Weekday::Tuesday => Self::Tue,
+            Weekday::Wednesday => Self::Wed,
+            Weekday::Thursday => Self::Thu,
+            Weekday::Friday => Self::Fri,
+            Weekday::Saturday => Self::Sat,
+            Weekday::Sunday => Self::Sun,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::Weekday;
+
+    #[test]
+    fn test_weekday() {
+        for weekday in [
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ] {
+            assert_eq!(weekday, weekday.to_string().parse::<Weekday>().unwrap());
+        }
+
+        assert_eq!(true, "Monday".parse::<Weekday>().is_err());
+        assert_eq!(true, "mon".parse::<Weekday>().is_err());
+        assert_eq!(true, "".parse::<Weekday>().is_err());
+    }
+}
@@ -0,0 +1,39 @@
+PRAGMA foreign_keys = off;
+DROP TABLE flaky_measure;
+-- benchmark
+CREATE TABLE down_benchmark (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    archived BIGINT,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    UNIQUE(project_id, name),
+    UNIQUE(project_id, slug)
+);
+INSERT INTO down_benchmark(
+        id,
+        uuid,
+        project_id,
+        name,
+        slug,
+        created,
+        modified,
+        archived
+    )
+SELECT id,
+    uuid,
+    project_id,
+    name,
+    slug,
+    created,
+    modified,
+    archived
+FROM benchmark;
+DROP TABLE benchmark;
+ALTER TABLE down_benchmark
+    RENAME TO benchmark;
+PRAGMA foreign_keys = on;
\ No newline at end of file
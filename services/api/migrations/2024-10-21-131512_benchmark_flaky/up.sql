@@ -0,0 +1,54 @@
+PRAGMA foreign_keys = off;
+-- benchmark
+CREATE TABLE up_benchmark (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    archived BIGINT,
+    flaky BIGINT,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    UNIQUE(project_id, name),
+    UNIQUE(project_id, slug)
+);
+INSERT INTO up_benchmark(
+        id,
+        uuid,
+        project_id,
+        name,
+        slug,
+        created,
+        modified,
+        archived,
+        flaky
+    )
+SELECT id,
+    uuid,
+    project_id,
+    name,
+    slug,
+    created,
+    modified,
+    archived,
+    null
+FROM benchmark;
+DROP TABLE benchmark;
+ALTER TABLE up_benchmark
+    RENAME TO benchmark;
+-- flaky measure
+-- The measures of a benchmark that were detected as flaky by the daily flaky benchmark job
+CREATE TABLE flaky_measure (
+    id INTEGER PRIMARY KEY NOT NULL,
+    benchmark_id INTEGER NOT NULL,
+    measure_id INTEGER NOT NULL,
+    -- The coefficient of variation of the measure, excluding any level shift
+    coefficient_of_variation DOUBLE NOT NULL,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (benchmark_id) REFERENCES benchmark (id) ON DELETE CASCADE,
+    FOREIGN KEY (measure_id) REFERENCES measure (id) ON DELETE CASCADE,
+    UNIQUE(benchmark_id, measure_id)
+);
+PRAGMA foreign_keys = on;
\ No newline at end of file
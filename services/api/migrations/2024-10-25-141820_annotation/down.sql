@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS index_annotation_project_time;
+DROP TABLE annotation;
\ No newline at end of file
@@ -0,0 +1,38 @@
+PRAGMA foreign_keys = off;
+-- alert
+CREATE TABLE up_alert (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    boundary_id INTEGER NOT NULL,
+    boundary_limit BOOLEAN NOT NULL,
+    -- How far the metric exceeded the boundary limit
+    -- 0: minor, 1: major, 2: critical
+    severity INTEGER NOT NULL,
+    status INTEGER NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (boundary_id) REFERENCES boundary (id) ON DELETE CASCADE
+);
+INSERT INTO up_alert(
+        id,
+        uuid,
+        boundary_id,
+        boundary_limit,
+        severity,
+        status,
+        modified
+    )
+SELECT id,
+    uuid,
+    boundary_id,
+    boundary_limit,
+    0,
+    status,
+    modified
+FROM alert;
+DROP TABLE alert;
+ALTER TABLE up_alert
+    RENAME TO alert;
+-- index
+DROP INDEX IF EXISTS index_alert_boundary;
+CREATE INDEX index_alert_boundary ON alert(boundary_id);
+PRAGMA foreign_keys = on;
\ No newline at end of file
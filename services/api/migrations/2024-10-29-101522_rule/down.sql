@@ -0,0 +1 @@
+DROP TABLE rule;
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE downsample;
\ No newline at end of file
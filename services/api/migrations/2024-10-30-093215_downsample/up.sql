@@ -0,0 +1,12 @@
+CREATE TABLE downsample (
+    id INTEGER PRIMARY KEY NOT NULL,
+    metric_id INTEGER NOT NULL UNIQUE,
+    -- The number of metrics that were aggregated into the metric
+    sample_count INTEGER NOT NULL,
+    -- The min and max of the aggregated metrics,
+    -- as the lower and upper values of the metric are the means of their lower and upper values
+    min_value DOUBLE NOT NULL,
+    max_value DOUBLE NOT NULL,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (metric_id) REFERENCES metric (id) ON DELETE CASCADE
+);
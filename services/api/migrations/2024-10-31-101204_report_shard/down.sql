@@ -0,0 +1 @@
+DROP TABLE report_shard;
\ No newline at end of file
@@ -0,0 +1,15 @@
+CREATE TABLE job (
+    id INTEGER PRIMARY KEY NOT NULL,
+    job_key TEXT UNIQUE,
+    payload TEXT NOT NULL,
+    status INTEGER NOT NULL,
+    attempts INTEGER NOT NULL,
+    max_attempts INTEGER NOT NULL,
+    run_at BIGINT NOT NULL,
+    lease_owner TEXT,
+    lease_expires BIGINT,
+    last_error TEXT,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL
+);
+CREATE INDEX index_job_status_run_at ON job(status, run_at);
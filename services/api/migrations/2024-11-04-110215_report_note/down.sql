@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS index_report_link_report;
+DROP TABLE report_link;
+DROP INDEX IF EXISTS index_report_note_report;
+DROP TABLE report_note;
\ No newline at end of file
@@ -0,0 +1,14 @@
+CREATE TABLE report_note (
+    id INTEGER PRIMARY KEY NOT NULL,
+    report_id INTEGER NOT NULL,
+    note TEXT NOT NULL,
+    FOREIGN KEY (report_id) REFERENCES report (id) ON DELETE CASCADE
+);
+CREATE INDEX index_report_note_report ON report_note(report_id);
+CREATE TABLE report_link (
+    id INTEGER PRIMARY KEY NOT NULL,
+    report_id INTEGER NOT NULL,
+    url TEXT NOT NULL,
+    FOREIGN KEY (report_id) REFERENCES report (id) ON DELETE CASCADE
+);
+CREATE INDEX index_report_link_report ON report_link(report_id);
\ No newline at end of file
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS index_report_failure_report;
+DROP TABLE report_failure;
\ No newline at end of file
@@ -0,0 +1,13 @@
+CREATE TABLE report_failure (
+    id INTEGER PRIMARY KEY NOT NULL,
+    report_id INTEGER NOT NULL,
+    -- The zero-based iteration of the benchmark command that failed
+    iteration INTEGER NOT NULL,
+    exit_code INTEGER NOT NULL,
+    -- Wall time in nanoseconds
+    wall_time DOUBLE NOT NULL,
+    -- The end of the standard error output, truncated
+    stderr TEXT,
+    FOREIGN KEY (report_id) REFERENCES report (id) ON DELETE CASCADE
+);
+CREATE INDEX index_report_failure_report ON report_failure(report_id);
\ No newline at end of file
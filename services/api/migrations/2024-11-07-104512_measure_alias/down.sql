@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS index_measure_alias_measure;
+DROP TABLE measure_alias;
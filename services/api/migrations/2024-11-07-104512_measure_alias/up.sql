@@ -0,0 +1,13 @@
+CREATE TABLE measure_alias (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    measure_id INTEGER NOT NULL,
+    -- The measure name used by an adapter or harness that maps onto the canonical measure
+    alias TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    FOREIGN KEY (measure_id) REFERENCES measure (id) ON DELETE CASCADE,
+    UNIQUE(project_id, alias)
+);
+CREATE INDEX index_measure_alias_measure ON measure_alias(measure_id);
@@ -0,0 +1,14 @@
+CREATE TABLE token_policy (
+    id INTEGER PRIMARY KEY NOT NULL,
+    organization_id INTEGER NOT NULL UNIQUE,
+    -- The maximum time-to-live (TTL) in seconds for new API tokens
+    max_ttl BIGINT,
+    -- New API tokens must be created with an explicit TTL
+    require_ttl BOOLEAN NOT NULL,
+    -- New API tokens may not use the maximum possible TTL, which never expires in practice
+    forbid_non_expiring BOOLEAN NOT NULL,
+    -- Only organization leaders may create new API tokens
+    admin_only BOOLEAN NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (organization_id) REFERENCES organization (id) ON DELETE CASCADE
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS index_report_raw_report;
+DROP TABLE report_raw;
\ No newline at end of file
@@ -0,0 +1,15 @@
+CREATE TABLE report_raw (
+    id INTEGER PRIMARY KEY NOT NULL,
+    report_id INTEGER NOT NULL,
+    -- The zero-based order the raw results were processed in, with any shards after the report itself
+    position INTEGER NOT NULL,
+    -- The report settings as submitted, as JSON
+    settings TEXT NOT NULL,
+    -- The raw benchmark harness output for each iteration, as a JSON array
+    results TEXT NOT NULL,
+    -- The head of the reference branch when the report was created, if any
+    reference_head_id INTEGER,
+    FOREIGN KEY (report_id) REFERENCES report (id) ON DELETE CASCADE,
+    FOREIGN KEY (reference_head_id) REFERENCES head (id) ON DELETE SET NULL
+);
+CREATE INDEX index_report_raw_report ON report_raw(report_id);
\ No newline at end of file
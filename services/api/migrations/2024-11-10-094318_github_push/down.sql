@@ -0,0 +1,29 @@
+PRAGMA foreign_keys = off;
+-- github_webhook
+DROP TABLE github_webhook;
+-- version
+CREATE TABLE down_version (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    hash TEXT,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
+INSERT INTO down_version(
+        id,
+        uuid,
+        project_id,
+        number,
+        hash
+    )
+SELECT id,
+    uuid,
+    project_id,
+    number,
+    hash
+FROM version;
+DROP TABLE version;
+ALTER TABLE down_version
+    RENAME TO version;
+PRAGMA foreign_keys = on;
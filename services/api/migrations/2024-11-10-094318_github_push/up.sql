@@ -0,0 +1,40 @@
+PRAGMA foreign_keys = off;
+-- version
+CREATE TABLE up_version (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    hash TEXT,
+    -- The time the version was recorded from a git push,
+    -- which may be before it was ever benchmarked
+    pushed BIGINT,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
+INSERT INTO up_version(
+        id,
+        uuid,
+        project_id,
+        number,
+        hash
+    )
+SELECT id,
+    uuid,
+    project_id,
+    number,
+    hash
+FROM version;
+DROP TABLE version;
+ALTER TABLE up_version
+    RENAME TO version;
+-- github_webhook
+CREATE TABLE github_webhook (
+    id INTEGER PRIMARY KEY NOT NULL,
+    project_id INTEGER NOT NULL UNIQUE,
+    -- The shared secret used to verify the signature of each webhook delivery
+    secret TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
+PRAGMA foreign_keys = on;
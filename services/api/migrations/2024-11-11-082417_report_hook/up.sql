@@ -0,0 +1,9 @@
+CREATE TABLE report_hook (
+    id INTEGER PRIMARY KEY NOT NULL,
+    project_id INTEGER NOT NULL UNIQUE,
+    -- The Rhai script that is run on the report results at ingest time
+    script TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
@@ -0,0 +1,15 @@
+CREATE TABLE alert_update (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    -- The new status of the alerts
+    status INTEGER NOT NULL,
+    -- The justification for the bulk update, for auditing
+    reason TEXT NOT NULL,
+    -- The number of alerts that were updated
+    alerts INTEGER NOT NULL,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+DROP TABLE expectation;
\ No newline at end of file
@@ -0,0 +1,19 @@
+CREATE TABLE expectation (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    branch_id INTEGER NOT NULL,
+    pattern TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    start_time BIGINT,
+    end_time BIGINT,
+    start_version INTEGER,
+    end_version INTEGER,
+    annotation_id INTEGER,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    FOREIGN KEY (branch_id) REFERENCES branch (id) ON DELETE CASCADE,
+    FOREIGN KEY (annotation_id) REFERENCES annotation (id) ON DELETE SET NULL
+);
+CREATE INDEX index_expectation_branch ON expectation(branch_id);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS index_webhook_delivery_failed;
+DROP TABLE webhook_delivery;
+DROP TABLE webhook;
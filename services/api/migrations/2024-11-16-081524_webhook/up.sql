@@ -0,0 +1,30 @@
+CREATE TABLE webhook (
+    id INTEGER PRIMARY KEY NOT NULL,
+    project_id INTEGER NOT NULL UNIQUE,
+    -- The URL that each new alert is delivered to
+    url TEXT NOT NULL,
+    -- The shared secret used to sign each delivery
+    secret TEXT NOT NULL,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
+-- Successful deliveries are removed,
+-- so this only holds deliveries that are pending or have failed
+CREATE TABLE webhook_delivery (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    alert_id INTEGER NOT NULL,
+    attempts INTEGER NOT NULL,
+    -- The HTTP status code of the response to the last attempt
+    response_status INTEGER,
+    last_error TEXT,
+    -- The time the delivery ran out of attempts
+    failed BIGINT,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    FOREIGN KEY (alert_id) REFERENCES alert (id) ON DELETE CASCADE
+);
+CREATE INDEX index_webhook_delivery_failed ON webhook_delivery(project_id, failed);
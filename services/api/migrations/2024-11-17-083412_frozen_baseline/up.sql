@@ -0,0 +1,45 @@
+PRAGMA foreign_keys = off;
+-- branch
+CREATE TABLE up_branch (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    head_id INTEGER,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    archived BIGINT,
+    -- The time that the threshold baselines for the branch were frozen
+    -- After this time, new metrics are no longer included in the baselines
+    frozen_baseline BIGINT,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    FOREIGN KEY (head_id) REFERENCES head (id),
+    UNIQUE(project_id, name),
+    UNIQUE(project_id, slug)
+);
+INSERT INTO up_branch(
+        id,
+        uuid,
+        project_id,
+        name,
+        slug,
+        head_id,
+        created,
+        modified,
+        archived
+    )
+SELECT id,
+    uuid,
+    project_id,
+    name,
+    slug,
+    head_id,
+    created,
+    modified,
+    archived
+FROM branch;
+DROP TABLE branch;
+ALTER TABLE up_branch
+    RENAME TO branch;
+PRAGMA foreign_keys = on;
\ No newline at end of file
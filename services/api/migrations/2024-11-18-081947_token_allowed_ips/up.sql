@@ -0,0 +1,43 @@
+PRAGMA foreign_keys = off;
+-- token
+CREATE TABLE up_token (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    jwt TEXT NOT NULL,
+    creation BIGINT NOT NULL,
+    expiration BIGINT NOT NULL,
+    -- The token is no longer valid after it is revoked,
+    -- even if it has not yet expired
+    revoked BIGINT,
+    -- A JSON array of the IP addresses or networks in CIDR notation
+    -- that are allowed to use the token, if restricted
+    allowed_ips TEXT,
+    FOREIGN KEY (user_id) REFERENCES user (id)
+);
+INSERT INTO up_token(
+        id,
+        uuid,
+        user_id,
+        name,
+        jwt,
+        creation,
+        expiration,
+        revoked
+    )
+SELECT id,
+    uuid,
+    user_id,
+    name,
+    jwt,
+    creation,
+    expiration,
+    revoked
+FROM token;
+DROP TABLE token;
+ALTER TABLE up_token
+    RENAME TO token;
+-- index
+CREATE INDEX index_token_jwt ON token(jwt);
+PRAGMA foreign_keys = on;
\ No newline at end of file
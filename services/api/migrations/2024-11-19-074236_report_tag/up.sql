@@ -0,0 +1,102 @@
+PRAGMA foreign_keys = off;
+-- report
+CREATE TABLE up_report (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    user_id INTEGER NOT NULL,
+    project_id INTEGER NOT NULL,
+    -- Connect to the head and version individually and not to their head_version
+    -- This is necessary in order for cloned heads to work
+    -- Cloned heads will *not* have a report tied to their specific head_version
+    -- So we don't want to have to query through the head_version table
+    -- to filter on the branch and list all of the versions
+    head_id INTEGER NOT NULL,
+    version_id INTEGER NOT NULL,
+    testbed_id INTEGER NOT NULL,
+    adapter INTEGER NOT NULL,
+    start_time BIGINT NOT NULL,
+    end_time BIGINT NOT NULL,
+    -- The release tag for the report, such as `v1.42.0`
+    tag TEXT,
+    -- Warm-up iterations run before the measured iterations
+    warmup_iterations INTEGER,
+    -- Total warm-up wall time in nanoseconds
+    warmup_wall_time DOUBLE,
+    -- The `git` metadata for the working tree that the report was run from
+    git_branch TEXT,
+    git_tag TEXT,
+    git_dirty BOOLEAN,
+    git_author TEXT,
+    git_commit_time BIGINT,
+    git_subject TEXT,
+    -- The hardware fingerprint of the machine that the report was run on
+    fingerprint_cpu_model TEXT,
+    fingerprint_governor TEXT,
+    fingerprint_virtualized BOOLEAN,
+    fingerprint_hypervisor TEXT,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES user (id),
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    FOREIGN KEY (head_id) REFERENCES head (id),
+    FOREIGN KEY (version_id) REFERENCES version (id),
+    FOREIGN KEY (testbed_id) REFERENCES testbed (id)
+);
+INSERT INTO up_report(
+        id,
+        uuid,
+        user_id,
+        project_id,
+        head_id,
+        version_id,
+        testbed_id,
+        adapter,
+        start_time,
+        end_time,
+        warmup_iterations,
+        warmup_wall_time,
+        git_branch,
+        git_tag,
+        git_dirty,
+        git_author,
+        git_commit_time,
+        git_subject,
+        fingerprint_cpu_model,
+        fingerprint_governor,
+        fingerprint_virtualized,
+        fingerprint_hypervisor,
+        created
+    )
+SELECT id,
+    uuid,
+    user_id,
+    project_id,
+    head_id,
+    version_id,
+    testbed_id,
+    adapter,
+    start_time,
+    end_time,
+    warmup_iterations,
+    warmup_wall_time,
+    git_branch,
+    git_tag,
+    git_dirty,
+    git_author,
+    git_commit_time,
+    git_subject,
+    fingerprint_cpu_model,
+    fingerprint_governor,
+    fingerprint_virtualized,
+    fingerprint_hypervisor,
+    created
+FROM report;
+DROP TABLE report;
+ALTER TABLE up_report
+    RENAME TO report;
+-- index
+DROP INDEX IF EXISTS index_report_testbed_end_time;
+DROP INDEX IF EXISTS index_report_version;
+CREATE INDEX index_report_testbed_end_time ON report(testbed_id, end_time);
+CREATE INDEX index_report_version ON report(version_id, end_time);
+CREATE INDEX index_report_tag ON report(project_id, tag);
+PRAGMA foreign_keys = on;
\ No newline at end of file
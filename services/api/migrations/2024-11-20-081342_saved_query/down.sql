@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS index_saved_query_schedule;
+DROP TABLE saved_query;
\ No newline at end of file
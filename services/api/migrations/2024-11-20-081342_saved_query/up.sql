@@ -0,0 +1,22 @@
+CREATE TABLE saved_query (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    -- The branch, testbed, benchmark, and measure UUIDs as JSON
+    query TEXT NOT NULL,
+    -- The window of time that ends when the query is run, in seconds
+    window BIGINT NOT NULL,
+    schedule INTEGER NOT NULL,
+    -- The email addresses that each summary is sent to as JSON
+    emails TEXT NOT NULL,
+    -- Whether each summary is also sent to the project webhook
+    webhook BOOLEAN NOT NULL,
+    -- The time the last scheduled summary was sent
+    last_sent BIGINT,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    UNIQUE(project_id, name)
+);
+CREATE INDEX index_saved_query_schedule ON saved_query(schedule);
\ No newline at end of file
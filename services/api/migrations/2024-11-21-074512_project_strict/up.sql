@@ -0,0 +1,50 @@
+PRAGMA foreign_keys = off;
+-- project
+CREATE TABLE up_project (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    organization_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE,
+    url TEXT,
+    visibility INTEGER NOT NULL,
+    time_zone TEXT NOT NULL DEFAULT 'UTC',
+    week_start TEXT NOT NULL DEFAULT 'monday',
+    -- Reject reports with benchmarks or measures that do not already exist
+    strict BOOLEAN NOT NULL DEFAULT 0,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (organization_id) REFERENCES organization (id) ON DELETE CASCADE,
+    UNIQUE(organization_id, name)
+);
+INSERT INTO up_project(
+        id,
+        uuid,
+        organization_id,
+        name,
+        slug,
+        url,
+        visibility,
+        time_zone,
+        week_start,
+        strict,
+        created,
+        modified
+    )
+SELECT id,
+    uuid,
+    organization_id,
+    name,
+    slug,
+    url,
+    visibility,
+    time_zone,
+    week_start,
+    0,
+    created,
+    modified
+FROM project;
+DROP TABLE project;
+ALTER TABLE up_project
+    RENAME TO project;
+PRAGMA foreign_keys = on;
\ No newline at end of file
@@ -0,0 +1,44 @@
+PRAGMA foreign_keys = off;
+-- testbed
+CREATE TABLE up_testbed (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    project_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    -- The declared hardware specification of the testbed
+    spec_cpu TEXT,
+    spec_cores INTEGER,
+    -- RAM in bytes
+    spec_memory BIGINT,
+    spec_os TEXT,
+    created BIGINT NOT NULL,
+    modified BIGINT NOT NULL,
+    archived BIGINT,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    UNIQUE(project_id, name),
+    UNIQUE(project_id, slug)
+);
+INSERT INTO up_testbed(
+        id,
+        uuid,
+        project_id,
+        name,
+        slug,
+        created,
+        modified,
+        archived
+    )
+SELECT id,
+    uuid,
+    project_id,
+    name,
+    slug,
+    created,
+    modified,
+    archived
+FROM testbed;
+DROP TABLE testbed;
+ALTER TABLE up_testbed
+    RENAME TO testbed;
+PRAGMA foreign_keys = on;
\ No newline at end of file
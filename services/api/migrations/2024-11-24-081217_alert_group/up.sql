@@ -0,0 +1,50 @@
+PRAGMA foreign_keys = off;
+-- A group of alerts for the measures of a single benchmark that regressed together
+CREATE TABLE alert_group (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    report_benchmark_id INTEGER NOT NULL UNIQUE,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (report_benchmark_id) REFERENCES report_benchmark (id) ON DELETE CASCADE
+);
+-- alert
+CREATE TABLE up_alert (
+    id INTEGER PRIMARY KEY NOT NULL,
+    uuid TEXT NOT NULL UNIQUE,
+    boundary_id INTEGER NOT NULL,
+    -- The correlated alert group, if the alert is part of one
+    group_id INTEGER,
+    boundary_limit BOOLEAN NOT NULL,
+    -- How far the metric exceeded the boundary limit
+    -- 0: minor, 1: major, 2: critical
+    severity INTEGER NOT NULL,
+    status INTEGER NOT NULL,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (boundary_id) REFERENCES boundary (id) ON DELETE CASCADE,
+    FOREIGN KEY (group_id) REFERENCES alert_group (id) ON DELETE SET NULL
+);
+INSERT INTO up_alert(
+        id,
+        uuid,
+        boundary_id,
+        boundary_limit,
+        severity,
+        status,
+        modified
+    )
+SELECT id,
+    uuid,
+    boundary_id,
+    boundary_limit,
+    severity,
+    status,
+    modified
+FROM alert;
+DROP TABLE alert;
+ALTER TABLE up_alert
+    RENAME TO alert;
+-- index
+DROP INDEX IF EXISTS index_alert_boundary;
+CREATE INDEX index_alert_boundary ON alert(boundary_id);
+CREATE INDEX index_alert_group ON alert(group_id);
+PRAGMA foreign_keys = on;
\ No newline at end of file
@@ -0,0 +1,15 @@
+CREATE TABLE user_prefs (
+    id INTEGER PRIMARY KEY NOT NULL,
+    user_id INTEGER NOT NULL UNIQUE,
+    -- The time zone to display dates in, as a UTC offset
+    time_zone TEXT,
+    date_format INTEGER,
+    -- The preferred units for each unit dimension
+    time_units TEXT,
+    throughput_units TEXT,
+    size_units TEXT,
+    default_project_id INTEGER,
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+    FOREIGN KEY (default_project_id) REFERENCES project (id) ON DELETE SET NULL
+);
\ No newline at end of file
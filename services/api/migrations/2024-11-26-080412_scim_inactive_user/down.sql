@@ -0,0 +1 @@
+DROP TABLE scim_inactive_user;
\ No newline at end of file
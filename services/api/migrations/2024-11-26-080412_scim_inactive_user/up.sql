@@ -0,0 +1,11 @@
+-- A user that was deactivated in an organization by SCIM,
+-- so they can still be found and reactivated by the identity provider
+CREATE TABLE scim_inactive_user (
+    id INTEGER PRIMARY KEY NOT NULL,
+    organization_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    created BIGINT NOT NULL,
+    FOREIGN KEY (organization_id) REFERENCES organization (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE,
+    UNIQUE(organization_id, user_id)
+);
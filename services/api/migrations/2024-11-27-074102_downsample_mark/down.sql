@@ -0,0 +1 @@
+DROP TABLE downsample_mark;
\ No newline at end of file
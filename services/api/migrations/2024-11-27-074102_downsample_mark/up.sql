@@ -0,0 +1,15 @@
+-- How far the metrics for a branch head and testbed have already been downsampled,
+-- so that each downsample only has to look at the reports that came in since
+CREATE TABLE downsample_mark (
+    id INTEGER PRIMARY KEY NOT NULL,
+    head_id INTEGER NOT NULL,
+    testbed_id INTEGER NOT NULL,
+    -- Reports that started before the cutoff have been downsampled
+    cutoff BIGINT NOT NULL,
+    -- Reports that were created after the mark was last modified have not been downsampled,
+    -- even if they started before the cutoff
+    modified BIGINT NOT NULL,
+    FOREIGN KEY (head_id) REFERENCES head (id) ON DELETE CASCADE,
+    FOREIGN KEY (testbed_id) REFERENCES testbed (id) ON DELETE CASCADE,
+    UNIQUE(head_id, testbed_id)
+);
@@ -0,0 +1 @@
+DROP TABLE perf_embed_revocation;
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- Perf embed tokens for the project that were issued at or before the revoked time are no longer valid
+CREATE TABLE perf_embed_revocation (
+    id INTEGER PRIMARY KEY NOT NULL,
+    project_id INTEGER NOT NULL UNIQUE,
+    revoked BIGINT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+DROP TABLE token_revocation;
\ No newline at end of file
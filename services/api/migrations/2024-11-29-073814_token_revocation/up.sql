@@ -0,0 +1,10 @@
+-- API tokens that can no longer be used for an organization after the revoked time
+CREATE TABLE token_revocation (
+    id INTEGER PRIMARY KEY NOT NULL,
+    token_id INTEGER NOT NULL,
+    organization_id INTEGER NOT NULL,
+    revoked BIGINT NOT NULL,
+    FOREIGN KEY (token_id) REFERENCES token (id) ON DELETE CASCADE,
+    FOREIGN KEY (organization_id) REFERENCES organization (id) ON DELETE CASCADE,
+    UNIQUE(token_id, organization_id)
+);
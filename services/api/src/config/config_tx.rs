@@ -4,10 +4,11 @@ use std::sync::Arc;
 use bencher_json::system::config::{JsonLitestream, JsonPlus};
 use bencher_json::{
     system::config::{
-        IfExists, JsonConsole, JsonDatabase, JsonLogging, JsonSecurity, JsonServer, JsonSmtp,
-        JsonTls, LogLevel, ServerLog,
+        IfExists, JsonConsole, JsonDatabase, JsonDownsample, JsonFlaky, JsonLogging, JsonSecurity,
+        JsonServer, JsonSeverity, JsonSmtp, JsonStatistics, JsonTelemetry, JsonTls, LogLevel,
+        ServerLog,
     },
-    JsonConfig,
+    DateTime, JsonConfig,
 };
 use bencher_rbac::init_rbac;
 use bencher_token::TokenKey;
@@ -18,18 +19,23 @@ use dropshot::{
     ConfigTls, HttpServer,
 };
 use slog::{debug, error, info, Logger};
-use tokio::sync::mpsc::Sender;
+use tokio::{sync::mpsc::Sender, task::JoinHandle};
 
 #[cfg(feature = "plus")]
-use crate::model::server::QueryServer;
+use crate::model::server::{QueryServer, ServerStatsSender};
 use crate::{
     context::{ApiContext, Database, DbConnection},
     endpoints::Api,
+    model::{job::worker::JobWorker, project::threshold::recompute::RecomputeSettings},
 };
 
-use super::Config;
 #[cfg(feature = "plus")]
 use super::{plus::Plus, DEFAULT_BUSY_TIMEOUT};
+use super::{
+    socket::{server_bind_address, SocketBridge, SocketPeers},
+    telemetry::TelemetrySettings,
+    Config,
+};
 
 const DATABASE_URL: &str = "DATABASE_URL";
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
@@ -45,6 +51,8 @@ pub enum ConfigTxError {
     CreateLogger(std::io::Error),
     #[error("Failed to run database migrations: {0}")]
     Migrations(Box<dyn std::error::Error + Send + Sync>),
+    #[error("Database migrations dry run: {0} pending migration(s) were not applied. Unset `database.migrations_dry_run` to apply them.")]
+    MigrationsDryRun(usize),
     #[error("Failed to run database pragma: {0}")]
     Pragma(diesel::result::Error),
     #[error("Failed to parse role based access control (RBAC) rules: {0}")]
@@ -59,6 +67,10 @@ pub enum ConfigTxError {
     Register(dropshot::ApiDescriptionRegisterError),
     #[error("Failed to create server: {0}")]
     CreateServer(Box<dyn std::error::Error + Send + Sync>),
+    #[error("{0}")]
+    Severity(super::severity::SeverityError),
+    #[error("{0}")]
+    Socket(super::socket::SocketError),
 
     #[cfg(feature = "plus")]
     #[error("{0}")]
@@ -68,8 +80,15 @@ pub enum ConfigTxError {
     ServerId(dropshot::HttpError),
 }
 
+/// The API server along with the background tasks that must be stopped when it restarts
+pub struct ApiServer {
+    pub http_server: HttpServer<ApiContext>,
+    pub socket_bridge: Option<SocketBridge>,
+    pub job_worker: JoinHandle<()>,
+}
+
 impl ConfigTx {
-    pub async fn into_server(self) -> Result<HttpServer<ApiContext>, ConfigTxError> {
+    pub async fn into_server(self) -> Result<ApiServer, ConfigTxError> {
         let log = into_log(self.config.0.logging.clone())?;
         self.into_inner(&log).await.map_err(|e| {
             error!(&log, "{e}");
@@ -77,7 +96,8 @@ impl ConfigTx {
         })
     }
 
-    async fn into_inner(self, log: &Logger) -> Result<HttpServer<ApiContext>, ConfigTxError> {
+    #[allow(clippy::too_many_lines)]
+    async fn into_inner(self, log: &Logger) -> Result<ApiServer, ConfigTxError> {
         let ConfigTx { config, restart_tx } = self;
 
         let Config(JsonConfig {
@@ -86,18 +106,37 @@ impl ConfigTx {
             mut server,
             database,
             smtp,
+            flaky,
+            severity,
+            statistics,
+            downsample,
+            telemetry,
             logging: _,
             #[cfg(feature = "plus")]
             plus,
         }) = config;
 
         debug!(log, "Creating internal configuration");
+        let request_body_max_bytes = server.request_body_max_bytes;
+        let client_ip_header = server.client_ip_header.take();
+        let json_socket = server.socket.take();
+        let socket_peers = json_socket.as_ref().map(|_| SocketPeers::default());
+        let unclaimed_projects = server.unclaimed_projects.unwrap_or_default();
         let context = into_context(
             log,
             console,
             security,
             smtp,
             database,
+            flaky,
+            severity,
+            statistics,
+            downsample,
+            telemetry,
+            request_body_max_bytes,
+            client_ip_header,
+            socket_peers.clone(),
+            unclaimed_projects,
             restart_tx,
             #[cfg(feature = "plus")]
             plus,
@@ -113,13 +152,30 @@ impl ConfigTx {
             },
             JsonTls::AsBytes { certs, key } => ConfigTls::AsBytes { certs, key },
         });
-        let config_dropshot = into_config_dropshot(server);
+        let mut config_dropshot = into_config_dropshot(server);
+        // The server must only be reachable through the socket
+        if json_socket.is_some() {
+            config_dropshot.bind_address = server_bind_address();
+            info!(
+                log,
+                "Binding to {} for socket connections only", config_dropshot.bind_address
+            );
+        }
 
+        if let Some(downsample) = context.downsample {
+            info!(
+                log,
+                "Downsampling metrics older than {} months", downsample.after_months
+            );
+        }
+        if let Some(telemetry) = &context.telemetry {
+            info!(log, "Sending anonymous telemetry to {}", telemetry.endpoint);
+        }
         #[cfg(feature = "plus")]
-        {
-            let conn = context.database.connection.clone();
-            let query_server = QueryServer::get_or_create(&mut *conn.lock().await)
-                .map_err(ConfigTxError::ServerId)?;
+        let server_stats = {
+            let query_server =
+                QueryServer::get_or_create(&mut *context.database.connection.lock().await)
+                    .map_err(ConfigTxError::ServerId)?;
             info!(log, "Bencher API Server ID: {}", query_server.uuid);
 
             // Bencher Cloud does not need to send stats to itself,
@@ -130,8 +186,18 @@ impl ConfigTx {
             } else {
                 (Some(context.licensor.clone()), None)
             };
-            query_server.spawn_stats(log.clone(), conn, context.stats, licensor, messenger);
-        }
+            ServerStatsSender::new(context.stats, licensor, messenger)
+        };
+        let job_worker = JobWorker::new(
+            log.clone(),
+            context.database.connection.clone(),
+            context.messenger.clone(),
+            context.downsample,
+            context.telemetry.clone(),
+            RecomputeSettings::from(&context),
+            #[cfg(feature = "plus")]
+            server_stats,
+        );
 
         let mut api = ApiDescription::new();
         debug!(log, "Registering server APIs");
@@ -143,24 +209,54 @@ impl ConfigTx {
         )
         .map_err(ConfigTxError::Register)?;
 
-        Ok(
+        let http_server =
             dropshot::HttpServerStarter::new_with_tls(&config_dropshot, api, context, log, tls)
                 .map_err(ConfigTxError::CreateServer)?
-                .start(),
-        )
+                .start();
+        // Connections to the socket are forwarded to the address that the server actually bound
+        let socket_bridge = json_socket
+            .zip(socket_peers)
+            .map(|(json_socket, socket_peers)| {
+                SocketBridge::new(log, json_socket, http_server.local_addr(), socket_peers)
+            })
+            .transpose()
+            .map_err(ConfigTxError::Socket)?;
+        // Only start the job worker once the server has started,
+        // so it is never left running without a server to stop it.
+        let job_worker = job_worker.spawn();
+        Ok(ApiServer {
+            http_server,
+            socket_bridge,
+            job_worker,
+        })
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn into_context(
     log: &Logger,
     console: JsonConsole,
     security: JsonSecurity,
     smtp: Option<JsonSmtp>,
     json_database: JsonDatabase,
+    flaky: Option<JsonFlaky>,
+    severity: Option<JsonSeverity>,
+    statistics: Option<JsonStatistics>,
+    downsample: Option<JsonDownsample>,
+    telemetry: Option<JsonTelemetry>,
+    request_body_max_bytes: usize,
+    client_ip_header: Option<String>,
+    socket_peers: Option<SocketPeers>,
+    unclaimed_projects: bool,
     restart_tx: Sender<()>,
     #[cfg(feature = "plus")] plus: Option<JsonPlus>,
 ) -> Result<ApiContext, ConfigTxError> {
     let console_url: url::Url = console.url.try_into().map_err(ConfigTxError::Endpoint)?;
+    let telemetry = telemetry
+        .map(TelemetrySettings::new)
+        .transpose()
+        .map_err(ConfigTxError::Endpoint)?
+        .flatten();
     let database_path = json_database.file.to_string_lossy();
     diesel_database_url(log, &database_path);
 
@@ -174,8 +270,13 @@ fn into_context(
         run_litestream(&mut database_connection, litestream)?;
     }
 
-    info!(&log, "Running database migrations");
-    run_migrations(&mut database_connection)?;
+    if json_database.migrations_dry_run.unwrap_or_default() {
+        info!(&log, "Checking pending database migrations (dry run)");
+        dry_run_migrations(log, &mut database_connection)?;
+    } else {
+        info!(&log, "Running database migrations");
+        run_migrations(&mut database_connection)?;
+    }
 
     let data_store = if let Some(data_store) = json_database.data_store {
         Some(data_store.try_into().map_err(ConfigTxError::DataStore)?)
@@ -184,10 +285,24 @@ fn into_context(
     };
 
     info!(&log, "Loading secret key");
-    let token_key = TokenKey::new(
+    let mut token_key = TokenKey::new(
         security.issuer.unwrap_or_else(|| console_url.to_string()),
         &security.secret_key,
     );
+    if let Some(previous) = security.previous_secret_key {
+        if previous.expiration.timestamp() > DateTime::now().timestamp() {
+            info!(
+                &log,
+                "Loading previous secret key (valid until {})", previous.expiration
+            );
+            token_key = token_key.with_previous_key(&previous.secret_key, previous.expiration);
+        } else {
+            info!(
+                &log,
+                "Previous secret key expired at {}, ignoring it", previous.expiration
+            );
+        }
+    }
 
     info!(&log, "Configuring Bencher Plus");
     #[cfg(feature = "plus")]
@@ -213,6 +328,19 @@ fn into_context(
             connection: Arc::new(tokio::sync::Mutex::new(database_connection)),
             data_store,
         },
+        flaky: flaky.map(Into::into).unwrap_or_default(),
+        severity: severity
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(ConfigTxError::Severity)?
+            .unwrap_or_default(),
+        statistics: statistics.map(Into::into).unwrap_or_default(),
+        downsample: downsample.map(Into::into),
+        telemetry,
+        request_body_max_bytes,
+        client_ip_header,
+        socket_peers,
+        unclaimed_projects,
         restart_tx,
         #[cfg(feature = "plus")]
         github,
@@ -246,7 +374,7 @@ fn diesel_database_url(log: &Logger, database_path: &str) {
     std::env::set_var(DATABASE_URL, database_path);
 }
 
-fn run_migrations(database: &mut DbConnection) -> Result<(), ConfigTxError> {
+pub(crate) fn run_migrations(database: &mut DbConnection) -> Result<(), ConfigTxError> {
     // It is not possible to enable or disable foreign key constraints in the middle of a multi-statement transaction
     // (when SQLite is not in autocommit mode).
     // Attempting to do so does not return an error; it simply has no effect.
@@ -267,6 +395,22 @@ fn run_migrations(database: &mut DbConnection) -> Result<(), ConfigTxError> {
     Ok(())
 }
 
+// List the pending migrations without applying them.
+// If there are any, the server can not safely start, so an error is returned.
+fn dry_run_migrations(log: &Logger, database: &mut DbConnection) -> Result<(), ConfigTxError> {
+    let pending = database
+        .pending_migrations(MIGRATIONS)
+        .map_err(ConfigTxError::Migrations)?;
+    if pending.is_empty() {
+        info!(log, "No pending database migrations");
+        return Ok(());
+    }
+    for migration in &pending {
+        info!(log, "Pending database migration: {}", migration.name());
+    }
+    Err(ConfigTxError::MigrationsDryRun(pending.len()))
+}
+
 #[cfg(feature = "plus")]
 fn run_litestream(
     database: &mut DbConnection,
@@ -309,7 +453,10 @@ fn into_config_dropshot(server: JsonServer) -> ConfigDropshot {
     let JsonServer {
         bind_address,
         request_body_max_bytes,
+        client_ip_header: _,
+        unclaimed_projects: _,
         tls: _,
+        socket: _,
     } = server;
     ConfigDropshot {
         bind_address,
@@ -0,0 +1,37 @@
+use bencher_json::{system::config::JsonDownsample, DateTime};
+use chrono::{Months, Utc};
+
+const MIN_AFTER_MONTHS: u32 = 1;
+
+#[derive(Debug, Clone, Copy)]
+pub struct DownsampleSettings {
+    pub after_months: u32,
+    pub dry_run: bool,
+}
+
+impl From<JsonDownsample> for DownsampleSettings {
+    fn from(json: JsonDownsample) -> Self {
+        let JsonDownsample {
+            after_months,
+            dry_run,
+        } = json;
+        Self::new(after_months, dry_run.unwrap_or_default())
+    }
+}
+
+impl DownsampleSettings {
+    pub fn new(after_months: u32, dry_run: bool) -> Self {
+        Self {
+            after_months: after_months.max(MIN_AFTER_MONTHS),
+            dry_run,
+        }
+    }
+
+    // Metrics from reports that started before the cutoff are downsampled
+    pub fn cutoff(&self) -> DateTime {
+        Utc::now()
+            .checked_sub_months(Months::new(self.after_months))
+            .unwrap_or(chrono::DateTime::<Utc>::MIN_UTC)
+            .into()
+    }
+}
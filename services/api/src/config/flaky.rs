@@ -0,0 +1,40 @@
+use bencher_json::system::config::JsonFlaky;
+
+const DEFAULT_MAX_COEFFICIENT_OF_VARIATION: f64 = 0.25;
+const DEFAULT_MIN_SAMPLE_SIZE: usize = 10;
+const DEFAULT_ALERTS: bool = false;
+
+#[derive(Debug, Clone, Copy)]
+pub struct FlakySettings {
+    pub max_coefficient_of_variation: f64,
+    pub min_sample_size: usize,
+    pub alerts: bool,
+}
+
+impl Default for FlakySettings {
+    fn default() -> Self {
+        Self {
+            max_coefficient_of_variation: DEFAULT_MAX_COEFFICIENT_OF_VARIATION,
+            min_sample_size: DEFAULT_MIN_SAMPLE_SIZE,
+            alerts: DEFAULT_ALERTS,
+        }
+    }
+}
+
+impl From<JsonFlaky> for FlakySettings {
+    fn from(json: JsonFlaky) -> Self {
+        let JsonFlaky {
+            max_coefficient_of_variation,
+            min_sample_size,
+            alerts,
+        } = json;
+        Self {
+            max_coefficient_of_variation: max_coefficient_of_variation
+                .unwrap_or(DEFAULT_MAX_COEFFICIENT_OF_VARIATION),
+            min_sample_size: min_sample_size
+                .and_then(|min_sample_size| usize::try_from(min_sample_size).ok())
+                .unwrap_or(DEFAULT_MIN_SAMPLE_SIZE),
+            alerts: alerts.unwrap_or(DEFAULT_ALERTS),
+        }
+    }
+}
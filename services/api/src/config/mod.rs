@@ -16,7 +16,13 @@ use slog::{error, info, Logger};
 use url::Url;
 
 pub mod config_tx;
+pub mod downsample;
+pub mod flaky;
 pub mod plus;
+pub mod severity;
+pub mod socket;
+pub mod statistics;
+pub mod telemetry;
 
 pub const API_NAME: &str = "Bencher API";
 
@@ -202,17 +208,27 @@ impl Default for Config {
             security: JsonSecurity {
                 issuer: Some(DEFAULT_CONSOLE_URL.to_string()),
                 secret_key: DEFAULT_SECRET_KEY.clone(),
+                previous_secret_key: None,
             },
             server: JsonServer {
                 bind_address: *DEFAULT_BIND_ADDRESS,
                 request_body_max_bytes: DEFAULT_MAX_BODY_SIZE,
+                client_ip_header: None,
+                unclaimed_projects: None,
                 tls: None,
+                socket: None,
             },
             database: JsonDatabase {
                 file: DEFAULT_DB_PATH.into(),
                 data_store: None,
+                migrations_dry_run: None,
             },
             smtp: None,
+            flaky: None,
+            severity: None,
+            statistics: None,
+            downsample: None,
+            telemetry: None,
             logging: JsonLogging {
                 name: API_NAME.into(),
                 log: ServerLog::StderrTerminal {
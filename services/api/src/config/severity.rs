@@ -0,0 +1,135 @@
+use bencher_json::{project::alert::AlertSeverity, system::config::JsonSeverity};
+
+const DEFAULT_MAJOR: f64 = 2.0;
+const DEFAULT_CRITICAL: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SeveritySettings {
+    pub major: f64,
+    pub critical: f64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SeverityError {
+    #[error("Invalid severity {name} threshold ({value}). It must be a finite number greater than or equal to 1.0.")]
+    Threshold { name: &'static str, value: f64 },
+    #[error("Invalid severity thresholds. The critical threshold ({critical}) must be greater than or equal to the major threshold ({major}).")]
+    Order { major: f64, critical: f64 },
+}
+
+impl Default for SeveritySettings {
+    fn default() -> Self {
+        Self {
+            major: DEFAULT_MAJOR,
+            critical: DEFAULT_CRITICAL,
+        }
+    }
+}
+
+impl TryFrom<JsonSeverity> for SeveritySettings {
+    type Error = SeverityError;
+
+    fn try_from(json: JsonSeverity) -> Result<Self, Self::Error> {
+        let JsonSeverity { major, critical } = json;
+        let major = major.unwrap_or(DEFAULT_MAJOR);
+        let critical = critical.unwrap_or(DEFAULT_CRITICAL);
+        // A metric at the boundary limit has an exceedance of 1.0,
+        // so anything lower would mark every alert as at least that severity.
+        for (name, value) in [("major", major), ("critical", critical)] {
+            if !value.is_finite() || value < 1.0 {
+                return Err(SeverityError::Threshold { name, value });
+            }
+        }
+        if critical < major {
+            return Err(SeverityError::Order { major, critical });
+        }
+        Ok(Self { major, critical })
+    }
+}
+
+impl SeveritySettings {
+    // The exceedance is how far the metric is from the baseline,
+    // as a multiple of how far the boundary limit is from the baseline.
+    pub fn severity(&self, exceedance: Option<f64>) -> AlertSeverity {
+        match exceedance {
+            Some(exceedance) if exceedance >= self.critical => AlertSeverity::Critical,
+            Some(exceedance) if exceedance >= self.major => AlertSeverity::Major,
+            Some(_) | None => AlertSeverity::Minor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{project::alert::AlertSeverity, system::config::JsonSeverity};
+    use pretty_assertions::assert_eq;
+
+    use super::{SeverityError, SeveritySettings};
+
+    #[test]
+    fn test_severity() {
+        let settings = SeveritySettings::default();
+        assert_eq!(settings.severity(None), AlertSeverity::Minor);
+        assert_eq!(settings.severity(Some(1.0)), AlertSeverity::Minor);
+        assert_eq!(settings.severity(Some(1.99)), AlertSeverity::Minor);
+        assert_eq!(settings.severity(Some(2.0)), AlertSeverity::Major);
+        assert_eq!(settings.severity(Some(3.99)), AlertSeverity::Major);
+        assert_eq!(settings.severity(Some(4.0)), AlertSeverity::Critical);
+        assert_eq!(
+            settings.severity(Some(f64::INFINITY)),
+            AlertSeverity::Critical
+        );
+        assert_eq!(settings.severity(Some(f64::NAN)), AlertSeverity::Minor);
+    }
+
+    #[test]
+    fn test_severity_equal_thresholds() {
+        let settings = SeveritySettings::try_from(JsonSeverity {
+            major: Some(3.0),
+            critical: Some(3.0),
+        })
+        .unwrap();
+        assert_eq!(settings.severity(Some(2.99)), AlertSeverity::Minor);
+        assert_eq!(settings.severity(Some(3.0)), AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_severity_settings_defaults() {
+        let settings = SeveritySettings::try_from(JsonSeverity {
+            major: None,
+            critical: Some(8.0),
+        })
+        .unwrap();
+        assert_eq!(settings.severity(Some(2.0)), AlertSeverity::Major);
+        assert_eq!(settings.severity(Some(4.0)), AlertSeverity::Major);
+        assert_eq!(settings.severity(Some(8.0)), AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_severity_settings_invalid() {
+        assert!(matches!(
+            SeveritySettings::try_from(JsonSeverity {
+                major: Some(5.0),
+                critical: Some(4.0),
+            }),
+            Err(SeverityError::Order { .. })
+        ));
+        // The default critical threshold is lower than the major threshold
+        assert!(matches!(
+            SeveritySettings::try_from(JsonSeverity {
+                major: Some(5.0),
+                critical: None,
+            }),
+            Err(SeverityError::Order { .. })
+        ));
+        for value in [0.5, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                SeveritySettings::try_from(JsonSeverity {
+                    major: Some(value),
+                    critical: None,
+                }),
+                Err(SeverityError::Threshold { name: "major", .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
+#[cfg(unix)]
+use std::{
+    fs::{self, Permissions},
+    io::ErrorKind,
+    net::TcpListener as StdTcpListener,
+    os::{
+        fd::{FromRawFd, OwnedFd, RawFd},
+        unix::{fs::PermissionsExt, net::UnixListener as StdUnixListener},
+    },
+};
+
+use bencher_json::system::config::JsonSocket;
+use slog::{debug, error, info, Logger};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::{
+    io::copy_bidirectional,
+    net::{TcpListener, TcpStream},
+};
+
+// The first file descriptor passed by systemd socket activation
+// https://www.freedesktop.org/software/systemd/man/latest/sd_listen_fds.html
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: RawFd = 3;
+#[cfg(unix)]
+const LISTEN_PID: &str = "LISTEN_PID";
+#[cfg(unix)]
+const LISTEN_FDS: &str = "LISTEN_FDS";
+
+// The systemd activated socket is taken out of the environment once, before the runtime starts,
+// so it is kept around for when the server restarts.
+#[cfg(unix)]
+static SYSTEMD_SOCKET: Mutex<SystemdSocket> = Mutex::new(SystemdSocket::None);
+
+#[cfg(unix)]
+enum SystemdSocket {
+    None,
+    Count(usize),
+    Socket(OwnedFd),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SocketError {
+    #[error(
+        "Unix domain sockets and systemd socket activation are not supported on this platform"
+    )]
+    Unsupported,
+    #[error("Failed to remove existing socket file ({path}): {error}")]
+    RemoveSocket { path: PathBuf, error: io::Error },
+    #[error("Failed to bind Unix domain socket ({path}): {error}")]
+    BindUnix { path: PathBuf, error: io::Error },
+    #[error("Failed to set Unix domain socket ({path}) permissions to {mode:o}: {error}")]
+    Permissions {
+        path: PathBuf,
+        mode: u32,
+        error: io::Error,
+    },
+    #[error("No socket was passed by systemd socket activation. Make sure the server is started by a systemd socket unit.")]
+    NoSystemdSocket,
+    #[error("Expected a single socket from systemd socket activation but {0} were passed")]
+    SystemdSocketCount(usize),
+    #[error("Failed to use systemd activated socket: {0}")]
+    SystemdSocket(io::Error),
+}
+
+/// When a socket is configured, the API server is only bound to an ephemeral loopback port.
+/// Each connection to the socket is forwarded to it byte for byte,
+/// which works for both plaintext and TLS connections.
+pub fn server_bind_address() -> SocketAddr {
+    SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0)
+}
+
+/// The original peer of each connection forwarded by the socket bridge,
+/// keyed by the local address of the forwarded connection to the API server.
+/// This is the remote address that the API server sees for the request.
+#[derive(Debug, Clone, Default)]
+pub struct SocketPeers(Arc<Mutex<HashMap<SocketAddr, SocketPeer>>>);
+
+#[derive(Debug, Clone, Copy)]
+pub enum SocketPeer {
+    /// Unix domain socket peers do not have an IP address
+    Unix,
+    Tcp(IpAddr),
+}
+
+impl SocketPeers {
+    pub fn get(&self, remote_addr: SocketAddr) -> Option<SocketPeer> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&remote_addr)
+            .copied()
+    }
+
+    fn insert(&self, remote_addr: SocketAddr, peer: SocketPeer) -> SocketPeerGuard {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(remote_addr, peer);
+        SocketPeerGuard {
+            peers: self.clone(),
+            remote_addr,
+        }
+    }
+}
+
+// Remove the peer once its connection is closed,
+// so that a later connection from the same local address is not mistaken for it.
+struct SocketPeerGuard {
+    peers: SocketPeers,
+    remote_addr: SocketAddr,
+}
+
+impl Drop for SocketPeerGuard {
+    fn drop(&mut self) {
+        self.peers
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.remote_addr);
+    }
+}
+
+/// Forwards the connections from a Unix domain socket or a systemd activated socket to the API server.
+/// The original peer of each connection is registered in the `SocketPeers`,
+/// so the API server can reject any connection that did not come through the socket
+/// and use the actual client IP address.
+pub struct SocketBridge {
+    log: Logger,
+    listener: BridgeListener,
+    server_address: SocketAddr,
+    peers: SocketPeers,
+}
+
+enum BridgeListener {
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        // Only set if the socket file was created by the server
+        path: Option<PathBuf>,
+    },
+    Tcp(TcpListener),
+}
+
+impl SocketBridge {
+    pub fn new(
+        log: &Logger,
+        json_socket: JsonSocket,
+        server_address: SocketAddr,
+        peers: SocketPeers,
+    ) -> Result<Self, SocketError> {
+        let listener = match json_socket {
+            JsonSocket::Unix { path, mode } => bind_unix(path, mode)?,
+            JsonSocket::Systemd => systemd_listener()?,
+        };
+        match &listener {
+            #[cfg(unix)]
+            BridgeListener::Unix {
+                path: Some(path), ..
+            } => info!(log, "Listening on Unix domain socket: {}", path.display()),
+            #[cfg(unix)]
+            BridgeListener::Unix { path: None, .. } => {
+                info!(log, "Listening on systemd activated Unix domain socket");
+            },
+            BridgeListener::Tcp(listener) => match listener.local_addr() {
+                Ok(address) => info!(log, "Listening on systemd activated TCP socket: {address}"),
+                Err(_) => info!(log, "Listening on systemd activated TCP socket"),
+            },
+        }
+        Ok(Self {
+            log: log.clone(),
+            listener,
+            server_address,
+            peers,
+        })
+    }
+
+    /// Accept connections until the bridge is dropped,
+    /// which happens when the API server shuts down or restarts.
+    pub async fn run(self) {
+        #[allow(clippy::infinite_loop)]
+        loop {
+            let accepted = match &self.listener {
+                #[cfg(unix)]
+                BridgeListener::Unix { listener, .. } => listener
+                    .accept()
+                    .await
+                    .map(|(stream, _)| Connection::Unix(stream)),
+                BridgeListener::Tcp(listener) => listener
+                    .accept()
+                    .await
+                    .map(|(stream, address)| Connection::Tcp(stream, address.ip())),
+            };
+            match accepted {
+                Ok(connection) => {
+                    let log = self.log.clone();
+                    let server_address = self.server_address;
+                    let peers = self.peers.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = connection.forward(server_address, &peers).await {
+                            debug!(log, "Failed to forward socket connection: {e}");
+                        }
+                    });
+                },
+                // Failing to accept a single connection should not take down the server
+                Err(e) => error!(self.log, "Failed to accept socket connection: {e}"),
+            }
+        }
+    }
+}
+
+impl Drop for SocketBridge {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let BridgeListener::Unix {
+            path: Some(path), ..
+        } = &self.listener
+        {
+            if let Err(e) = fs::remove_file(path) {
+                debug!(
+                    self.log,
+                    "Failed to remove Unix domain socket ({}): {e}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
+
+enum Connection {
+    #[cfg(unix)]
+    Unix(UnixStream),
+    Tcp(TcpStream, IpAddr),
+}
+
+impl Connection {
+    async fn forward(self, server_address: SocketAddr, peers: &SocketPeers) -> io::Result<()> {
+        let mut server = TcpStream::connect(server_address).await?;
+        // The peer must be registered before any of the request is forwarded
+        let _guard = peers.insert(server.local_addr()?, self.peer());
+        match self {
+            #[cfg(unix)]
+            Self::Unix(mut client) => copy_bidirectional(&mut client, &mut server).await?,
+            Self::Tcp(mut client, _) => copy_bidirectional(&mut client, &mut server).await?,
+        };
+        Ok(())
+    }
+
+    fn peer(&self) -> SocketPeer {
+        match self {
+            #[cfg(unix)]
+            Self::Unix(_) => SocketPeer::Unix,
+            Self::Tcp(_, ip) => SocketPeer::Tcp(*ip),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn bind_unix(path: PathBuf, mode: Option<u32>) -> Result<BridgeListener, SocketError> {
+    // A socket file left over from a previous run would cause the bind to fail
+    match fs::remove_file(&path) {
+        Ok(()) => {},
+        Err(e) if e.kind() == ErrorKind::NotFound => {},
+        Err(error) => return Err(SocketError::RemoveSocket { path, error }),
+    }
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(error) => return Err(SocketError::BindUnix { path, error }),
+    };
+    if let Some(mode) = mode {
+        if let Err(error) = fs::set_permissions(&path, Permissions::from_mode(mode)) {
+            return Err(SocketError::Permissions { path, mode, error });
+        }
+    }
+    Ok(BridgeListener::Unix {
+        listener,
+        path: Some(path),
+    })
+}
+
+#[cfg(not(unix))]
+fn bind_unix(_path: PathBuf, _mode: Option<u32>) -> Result<BridgeListener, SocketError> {
+    Err(SocketError::Unsupported)
+}
+
+#[cfg(unix)]
+fn systemd_listener() -> Result<BridgeListener, SocketError> {
+    // Each time the server restarts, it gets its own copy of the systemd activated socket
+    let socket = match &*SYSTEMD_SOCKET
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+    {
+        SystemdSocket::Socket(socket) => socket.try_clone().map_err(SocketError::SystemdSocket)?,
+        SystemdSocket::Count(count) => return Err(SocketError::SystemdSocketCount(*count)),
+        SystemdSocket::None => return Err(SocketError::NoSystemdSocket),
+    };
+
+    let unix_listener = StdUnixListener::from(socket);
+    // Getting the local address fails if the socket is not a Unix domain socket
+    if unix_listener.local_addr().is_ok() {
+        unix_listener
+            .set_nonblocking(true)
+            .map_err(SocketError::SystemdSocket)?;
+        let listener = UnixListener::from_std(unix_listener).map_err(SocketError::SystemdSocket)?;
+        return Ok(BridgeListener::Unix {
+            listener,
+            path: None,
+        });
+    }
+
+    let tcp_listener = StdTcpListener::from(OwnedFd::from(unix_listener));
+    tcp_listener
+        .set_nonblocking(true)
+        .map_err(SocketError::SystemdSocket)?;
+    let listener = TcpListener::from_std(tcp_listener).map_err(SocketError::SystemdSocket)?;
+    Ok(BridgeListener::Tcp(listener))
+}
+
+/// Take the socket passed by systemd socket activation out of the environment.
+/// This must be called at the start of `main`, before the runtime or any other threads are started,
+/// as modifying the environment is only sound while the process is single threaded.
+#[cfg(unix)]
+pub fn take_systemd_socket() {
+    // The file descriptors are only meant for this process
+    let for_this_process = std::env::var(LISTEN_PID)
+        .ok()
+        .and_then(|pid| pid.parse::<u32>().ok())
+        .is_some_and(|pid| pid == std::process::id());
+    let fds = std::env::var(LISTEN_FDS)
+        .ok()
+        .and_then(|fds| fds.parse::<usize>().ok())
+        .unwrap_or_default();
+    // Do not pass the socket on to any child processes, such as Litestream
+    std::env::remove_var(LISTEN_PID);
+    std::env::remove_var(LISTEN_FDS);
+
+    let systemd_socket = if !for_this_process || fds == 0 {
+        SystemdSocket::None
+    } else if fds > 1 {
+        SystemdSocket::Count(fds)
+    } else {
+        // SAFETY: systemd passes ownership of the listening socket as the first file descriptor,
+        // and the environment variables have been removed so that it is only ever taken once.
+        #[allow(unsafe_code)]
+        let socket = unsafe { OwnedFd::from_raw_fd(SD_LISTEN_FDS_START) };
+        SystemdSocket::Socket(socket)
+    };
+    *SYSTEMD_SOCKET
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = systemd_socket;
+}
+
+#[cfg(not(unix))]
+pub fn take_systemd_socket() {}
+
+#[cfg(not(unix))]
+fn systemd_listener() -> Result<BridgeListener, SocketError> {
+    Err(SocketError::Unsupported)
+}
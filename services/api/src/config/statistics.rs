@@ -0,0 +1,34 @@
+use bencher_json::{system::config::JsonStatistics, ModelTest, SampleSize};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatisticsSettings {
+    pub min_sample_size: Option<SampleSize>,
+}
+
+impl From<JsonStatistics> for StatisticsSettings {
+    fn from(json: JsonStatistics) -> Self {
+        let JsonStatistics { min_sample_size } = json;
+        Self { min_sample_size }
+    }
+}
+
+impl StatisticsSettings {
+    // A threshold model's own minimum sample size always takes precedence.
+    // Static threshold models do not use the baseline, so they never need a minimum sample size.
+    pub fn min_sample_size(
+        &self,
+        model_test: ModelTest,
+        min_sample_size: Option<SampleSize>,
+    ) -> Option<SampleSize> {
+        match model_test {
+            ModelTest::Static => min_sample_size,
+            ModelTest::Percentage
+            | ModelTest::ZScore
+            | ModelTest::TTest
+            | ModelTest::LogNormal
+            | ModelTest::Iqr
+            | ModelTest::DeltaIqr
+            | ModelTest::Ewma => min_sample_size.or(self.min_sample_size),
+        }
+    }
+}
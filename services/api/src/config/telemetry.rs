@@ -0,0 +1,20 @@
+use bencher_json::{system::config::JsonTelemetry, ValidError};
+use url::Url;
+
+#[derive(Debug, Clone)]
+pub struct TelemetrySettings {
+    pub endpoint: Url,
+}
+
+impl TelemetrySettings {
+    // Telemetry is opt-in, so it is only enabled if explicitly set to `true`
+    pub fn new(json: JsonTelemetry) -> Result<Option<Self>, ValidError> {
+        let JsonTelemetry { enabled, endpoint } = json;
+        if !enabled.unwrap_or_default() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            endpoint: endpoint.try_into()?,
+        }))
+    }
+}
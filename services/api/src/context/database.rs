@@ -4,9 +4,43 @@ use std::{
 };
 
 use bencher_json::{system::config::DataStore as DataStoreConfig, Secret};
+use diesel::Connection;
+use dropshot::HttpError;
+use http::StatusCode;
+
+use crate::error::issue_error;
 
 pub type DbConnection = diesel::SqliteConnection;
 
+/// Run all of the writes in a single database transaction.
+/// If the closure returns an error, then all of its writes are rolled back.
+pub fn transaction<T, F>(conn: &mut DbConnection, f: F) -> Result<T, HttpError>
+where
+    F: FnOnce(&mut DbConnection) -> Result<T, HttpError>,
+{
+    conn.transaction(|conn| f(conn).map_err(TransactionError::Http))
+        .map_err(|e| match e {
+            TransactionError::Http(e) => e,
+            TransactionError::Diesel(e) => issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to run database transaction",
+                "Failed to commit or roll back database transaction",
+                e,
+            ),
+        })
+}
+
+enum TransactionError {
+    Http(HttpError),
+    Diesel(diesel::result::Error),
+}
+
+impl From<diesel::result::Error> for TransactionError {
+    fn from(error: diesel::result::Error) -> Self {
+        Self::Diesel(error)
+    }
+}
+
 pub struct Database {
     pub path: PathBuf,
     pub connection: Arc<tokio::sync::Mutex<DbConnection>>,
@@ -1,9 +1,11 @@
 mod button;
 mod new_user;
+mod saved_query;
 mod server_stats;
 
 pub use button::ButtonBody;
 pub use new_user::NewUserBody;
+pub use saved_query::SavedQueryBody;
 #[cfg(feature = "plus")]
 pub use server_stats::ServerStatsBody;
 use slog::Logger;
@@ -17,6 +19,7 @@ pub trait FmtBody {
 pub enum Body {
     Button(Box<ButtonBody>),
     NewUser(NewUserBody),
+    SavedQuery(SavedQueryBody),
     #[cfg(feature = "plus")]
     ServerStats(ServerStatsBody),
 }
@@ -26,6 +29,7 @@ impl FmtBody for Body {
         match self {
             Self::Button(body) => body.text(),
             Self::NewUser(body) => body.text(),
+            Self::SavedQuery(body) => body.text(),
             #[cfg(feature = "plus")]
             Self::ServerStats(body) => body.text(),
         }
@@ -35,6 +39,7 @@ impl FmtBody for Body {
         match self {
             Self::Button(body) => body.html(log),
             Self::NewUser(body) => body.html(log),
+            Self::SavedQuery(body) => body.html(log),
             #[cfg(feature = "plus")]
             Self::ServerStats(body) => body.html(log),
         }
@@ -0,0 +1,165 @@
+use bencher_json::{project::saved_query::JsonSavedQueryResult, DateTime};
+use slog::Logger;
+
+use super::FmtBody;
+
+#[derive(Debug)]
+pub struct SavedQueryBody {
+    pub project: String,
+    pub name: String,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub results: Vec<JsonSavedQueryResult>,
+    /// The content ID of the embedded chart image
+    pub image_cid: String,
+}
+
+impl SavedQueryBody {
+    fn title(&self) -> String {
+        let Self { project, name, .. } = self;
+        format!("{project}: {name}")
+    }
+
+    fn columns(result: &JsonSavedQueryResult) -> [String; 8] {
+        let JsonSavedQueryResult {
+            branch,
+            testbed,
+            benchmark,
+            measure,
+            units,
+            count,
+            first,
+            latest,
+            change,
+        } = result;
+        [
+            branch.to_string(),
+            testbed.to_string(),
+            benchmark.to_string(),
+            format!("{measure} ({units})"),
+            count.to_string(),
+            format!("{first:.3}"),
+            format!("{latest:.3}"),
+            change.map_or_else(|| "-".to_owned(), |change| format!("{change:+.2}%")),
+        ]
+    }
+}
+
+const HEADERS: [&str; 8] = [
+    "Branch",
+    "Testbed",
+    "Benchmark",
+    "Measure",
+    "Metrics",
+    "First",
+    "Latest",
+    "Change",
+];
+
+impl FmtBody for SavedQueryBody {
+    fn text(&self) -> String {
+        let Self {
+            start_time,
+            end_time,
+            results,
+            ..
+        } = self;
+
+        let lines = if results.is_empty() {
+            "\nNo results in this time range.\n".to_owned()
+        } else {
+            results
+                .iter()
+                .map(|result| {
+                    let line = HEADERS
+                        .iter()
+                        .zip(Self::columns(result))
+                        .map(|(header, column)| format!("{header}: {column}"))
+                        .collect::<Vec<_>>()
+                        .join(" | ");
+                    format!("\n{line}\n")
+                })
+                .collect::<Vec<_>>()
+                .concat()
+        };
+        format!(
+            "\n{title}\n{start_time} to {end_time}\n{lines}\nBencher - Continuous Benchmarking",
+            title = self.title()
+        )
+    }
+
+    fn html(&self, _log: &Logger) -> String {
+        let Self {
+            start_time,
+            end_time,
+            results,
+            image_cid,
+            ..
+        } = self;
+        let title = escape(&self.title());
+
+        let header_row = HEADERS
+            .iter()
+            .map(|header| {
+                format!(
+                    "<th style=\"text-align:left;padding:4px 8px;border-bottom:1px solid #ddd;\">{header}</th>"
+                )
+            })
+            .collect::<Vec<_>>()
+            .concat();
+        let rows = if results.is_empty() {
+            format!(
+                "<tr><td colspan=\"{}\" style=\"padding:4px 8px;\">No results in this time range.</td></tr>",
+                HEADERS.len()
+            )
+        } else {
+            results
+                .iter()
+                .map(|result| {
+                    let columns = Self::columns(result)
+                        .iter()
+                        .map(|column| {
+                            format!(
+                                "<td style=\"padding:4px 8px;border-bottom:1px solid #eee;\">{}</td>",
+                                escape(column)
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .concat();
+                    format!("<tr>{columns}</tr>")
+                })
+                .collect::<Vec<_>>()
+                .concat()
+        };
+
+        format!(
+            "<!doctype html>
+<html>
+  <head>
+    <meta charset=\"utf-8\" />
+    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1, shrink-to-fit=no\" />
+    <title>{title}</title>
+  </head>
+  <body style=\"font-family:sans-serif;\">
+    <h1><img src=\"https://bencher.dev/favicon.png\" width=\"32\" height=\"32\" alt=\"🐰\" /> {title}</h1>
+    <p>{start_time} to {end_time}</p>
+    <img src=\"cid:{image_cid}\" alt=\"{title}\" style=\"max-width:100%;\" />
+    <table style=\"border-collapse:collapse;margin-top:16px;\">
+      <thead><tr>{header_row}</tr></thead>
+      <tbody>{rows}</tbody>
+    </table>
+    <hr/>
+    <p>Bencher - Continuous Benchmarking</p>
+  </body>
+</html>
+"
+        )
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
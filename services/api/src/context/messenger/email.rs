@@ -1,14 +1,16 @@
 use std::{fmt, sync::Arc};
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use bencher_json::system::config::JsonSmtp;
 use bencher_json::Secret;
 use mail_send::{mail_builder::MessageBuilder, SmtpClientBuilder};
 use slog::{error, trace, Logger};
 use tokio::sync::RwLock;
 
-use super::body::FmtBody;
-use super::Message;
-use crate::config::DEFAULT_SMTP_PORT;
+use crate::{
+    config::DEFAULT_SMTP_PORT,
+    model::job::{JobEmail, JobEmailImage},
+};
 
 #[derive(Debug, Clone)]
 pub struct Email {
@@ -43,7 +45,16 @@ impl From<JsonSmtp> for Email {
 }
 
 impl Email {
-    pub fn send(&self, log: &Logger, message: Message) {
+    pub async fn deliver(&self, log: &Logger, job_email: JobEmail) -> Result<(), mail_send::Error> {
+        let JobEmail {
+            to_name,
+            to_email,
+            subject,
+            text,
+            html,
+            images,
+        } = job_email;
+
         let mut message_builder = MessageBuilder::new();
 
         message_builder = if let Some(name) = self.from_name.clone() {
@@ -52,42 +63,55 @@ impl Email {
             message_builder.from(self.from_email.clone())
         };
 
-        let from_email = self.from_email.clone();
-        let to_email = message.to_email.clone();
-        message_builder = if let Some(name) = message.to_name {
-            message_builder.to((name, message.to_email))
+        message_builder = if let Some(name) = to_name {
+            message_builder.to((name, to_email.clone()))
         } else {
-            message_builder.to(message.to_email)
+            message_builder.to(to_email.clone())
         };
 
-        if let Some(subject) = message.subject {
+        if let Some(subject) = subject {
             message_builder = message_builder.subject(subject);
         }
 
-        if let Some(body) = message.body {
-            slog::debug!(log, "Setting email body: {body:?}");
-            message_builder = message_builder
-                .text_body(body.text())
-                .html_body(body.html(log));
+        if let Some(text) = text {
+            slog::debug!(log, "Setting email body: {text:?}");
+            message_builder = message_builder.text_body(text);
         }
-
-        slog::debug!(log, "Spawning email send task");
-        let send_log = log.clone();
-        let send_client = self.client.clone();
-        tokio::spawn(async move {
-            let mut client = send_client.write().await;
-            match client.send(&send_log, message_builder).await {
-                Ok(()) => trace!(send_log, "Email sent email from {from_email} to {to_email}"),
-                Err(err) => {
-                    error!(
-                        send_log,
-                        "Failed to send email from {from_email} to {to_email}: {err}"
-                    );
-                    #[cfg(feature = "sentry")]
-                    sentry::capture_error(&err);
-                },
+        if let Some(html) = html {
+            message_builder = message_builder.html_body(html);
+        }
+        for JobEmailImage {
+            cid,
+            content_type,
+            data,
+        } in images
+        {
+            match STANDARD.decode(data) {
+                Ok(bytes) => message_builder = message_builder.inline(content_type, cid, bytes),
+                Err(err) => error!(log, "Failed to decode email image ({cid}): {err}"),
             }
-        });
+        }
+
+        let mut client = self.client.write().await;
+        match client.send(log, message_builder).await {
+            Ok(()) => {
+                trace!(
+                    log,
+                    "Email sent email from {} to {to_email}",
+                    self.from_email
+                );
+                Ok(())
+            },
+            Err(err) => {
+                error!(
+                    log,
+                    "Failed to send email from {} to {to_email}: {err}", self.from_email
+                );
+                #[cfg(feature = "sentry")]
+                sentry::capture_error(&err);
+                Err(err)
+            },
+        }
     }
 }
 
@@ -1,5 +1,9 @@
 use std::fmt;
 
+use slog::Logger;
+
+use crate::model::job::JobEmail;
+
 use super::body::{Body, FmtBody};
 
 #[derive(Debug)]
@@ -10,6 +14,26 @@ pub struct Message {
     pub body: Option<Body>,
 }
 
+impl Message {
+    /// Render the message, so it can be sent from the job queue
+    pub fn into_job_email(self, log: &Logger) -> JobEmail {
+        let Self {
+            to_name,
+            to_email,
+            subject,
+            body,
+        } = self;
+        JobEmail {
+            to_name,
+            to_email,
+            subject,
+            text: body.as_ref().map(FmtBody::text),
+            html: body.as_ref().map(|body| body.html(log)),
+            images: Vec::new(),
+        }
+    }
+}
+
 impl fmt::Display for Message {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let to_email = format!("<{}>", self.to_email);
@@ -5,10 +5,15 @@ mod message;
 use bencher_json::system::config::JsonSmtp;
 #[cfg(feature = "plus")]
 pub use body::ServerStatsBody;
-pub use body::{Body, ButtonBody, NewUserBody};
+pub use body::{Body, ButtonBody, NewUserBody, SavedQueryBody};
 pub use email::Email;
 pub use message::Message;
-use slog::{info, Logger};
+use slog::{error, info, Logger};
+
+use crate::{
+    context::DbConnection,
+    model::job::{JobEmail, JobPayload, QueryJob},
+};
 
 #[derive(Debug, Clone, Default)]
 pub enum Messenger {
@@ -24,11 +29,33 @@ impl From<Option<JsonSmtp>> for Messenger {
 }
 
 impl Messenger {
-    pub fn send(&self, log: &Logger, message: Message) {
+    pub fn send(&self, log: &Logger, conn: &mut DbConnection, message: Message) {
         slog::debug!(log, "Sending message: {message:?}");
         match self {
             Self::StdOut => info!(log, "{message}"),
-            Self::Email(email) => email.send(log, message),
+            // Emails are sent from the job queue, so they are retried on failure and survive restarts
+            Self::Email(_) => {
+                let payload = JobPayload::Email(message.into_job_email(log));
+                if let Err(e) = QueryJob::enqueue(conn, &payload, None) {
+                    error!(log, "Failed to enqueue email: {e}");
+                }
+            },
+        }
+    }
+
+    pub async fn deliver(&self, log: &Logger, job_email: JobEmail) -> Result<(), mail_send::Error> {
+        match self {
+            Self::StdOut => {
+                info!(
+                    log,
+                    "\nTo: <{}>\nSubject: {}\nBody: {}",
+                    job_email.to_email,
+                    job_email.subject.unwrap_or_default(),
+                    job_email.text.unwrap_or_default()
+                );
+                Ok(())
+            },
+            Self::Email(email) => email.deliver(log, job_email).await,
         }
     }
 }
@@ -4,12 +4,22 @@ use bencher_billing::Biller;
 use bencher_github::GitHub;
 #[cfg(feature = "plus")]
 use bencher_license::Licensor;
+use std::net::SocketAddr;
+
 use bencher_token::TokenKey;
 use tokio::sync::mpsc::Sender;
 use url::Url;
 
 #[cfg(feature = "plus")]
 use crate::config::plus::StatsSettings;
+use crate::config::{
+    downsample::DownsampleSettings,
+    flaky::FlakySettings,
+    severity::SeveritySettings,
+    socket::{SocketPeer, SocketPeers},
+    statistics::StatisticsSettings,
+    telemetry::TelemetrySettings,
+};
 #[cfg(feature = "plus")]
 use crate::model::project::QueryProject;
 
@@ -18,12 +28,12 @@ mod indexer;
 mod messenger;
 mod rbac;
 
-pub use database::{DataStoreError, Database, DbConnection};
+pub use database::{transaction, DataStoreError, Database, DbConnection};
 #[cfg(feature = "plus")]
 pub use indexer::Indexer;
 #[cfg(feature = "plus")]
 pub use messenger::ServerStatsBody;
-pub use messenger::{Body, ButtonBody, Email, Message, Messenger, NewUserBody};
+pub use messenger::{Body, ButtonBody, Email, Message, Messenger, NewUserBody, SavedQueryBody};
 pub use rbac::{Rbac, RbacError};
 
 pub struct ApiContext {
@@ -32,6 +42,16 @@ pub struct ApiContext {
     pub rbac: Rbac,
     pub messenger: Messenger,
     pub database: Database,
+    pub flaky: FlakySettings,
+    pub severity: SeveritySettings,
+    pub statistics: StatisticsSettings,
+    pub downsample: Option<DownsampleSettings>,
+    pub telemetry: Option<TelemetrySettings>,
+    pub request_body_max_bytes: usize,
+    pub client_ip_header: Option<String>,
+    /// Only set if the server is behind a Unix domain socket or a systemd activated socket
+    pub socket_peers: Option<SocketPeers>,
+    pub unclaimed_projects: bool,
     pub restart_tx: Sender<()>,
     #[cfg(feature = "plus")]
     pub github: Option<GitHub>,
@@ -65,6 +85,22 @@ impl ApiContext {
         self.database.connection.lock().await
     }
 
+    /// If the server is behind a socket, then only requests forwarded from the socket are allowed,
+    /// so the socket file permissions can not be bypassed by connecting to the server directly.
+    /// Authenticated requests are checked along with their token.
+    /// Endpoints that do not authenticate must check the remote address of the request themselves.
+    pub fn socket_peer(
+        &self,
+        remote_addr: SocketAddr,
+    ) -> Result<Option<SocketPeer>, dropshot::HttpError> {
+        let Some(socket_peers) = &self.socket_peers else {
+            return Ok(None);
+        };
+        socket_peers.get(remote_addr).map(Some).ok_or_else(|| {
+            crate::error::forbidden_error("Requests must be made through the server socket")
+        })
+    }
+
     #[cfg(feature = "plus")]
     pub fn biller(&self) -> Result<&Biller, dropshot::HttpError> {
         self.biller.as_ref().ok_or_else(|| {
@@ -0,0 +1,359 @@
+use std::marker::PhantomData;
+
+use async_compression::tokio::write::{BrotliEncoder, GzipEncoder};
+use async_trait::async_trait;
+use dropshot::{
+    ApiEndpointBodyContentType, ApiEndpointResponse, ExtensionMode, ExtractorMetadata, HttpError,
+    HttpResponse, RequestContext, ServerContext, SharedExtractor,
+};
+use http::{header, HeaderValue, Response, StatusCode};
+use hyper::Body;
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    error::{bad_request_error, issue_error},
+    model::user::auth::Headers,
+};
+
+/// Response bodies smaller than this are not worth compressing
+const MIN_ENCODE_BYTES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Brotli,
+    Gzip,
+}
+
+impl ContentEncoding {
+    fn from_coding(coding: &str) -> Option<Self> {
+        match coding {
+            "br" | "*" => Some(Self::Brotli),
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Brotli => "br",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    async fn encode(self, bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Brotli => {
+                let mut encoder = BrotliEncoder::new(Vec::new());
+                encoder.write_all(bytes).await?;
+                encoder.shutdown().await?;
+                Ok(encoder.into_inner())
+            },
+            Self::Gzip => {
+                let mut encoder = GzipEncoder::new(Vec::new());
+                encoder.write_all(bytes).await?;
+                encoder.shutdown().await?;
+                Ok(encoder.into_inner())
+            },
+        }
+    }
+}
+
+/// The preferred response encoding from the `Accept-Encoding` request header,
+/// ie `Accept-Encoding: br;q=1.0, gzip;q=0.8`
+/// If no supported encoding is accepted, then the response is not encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct AcceptEncoding(Option<ContentEncoding>);
+
+#[async_trait]
+impl SharedExtractor for AcceptEncoding {
+    async fn from_request<Context: ServerContext>(
+        rqctx: &RequestContext<Context>,
+    ) -> Result<Self, HttpError> {
+        let headers = Headers::from_request(rqctx).await?;
+
+        let Some(accept_encoding) = headers.0.get(header::ACCEPT_ENCODING) else {
+            return Ok(Self(None));
+        };
+        let accept_encoding = accept_encoding.to_str().map_err(|e| {
+            bad_request_error(format!(
+                "Request has an invalid \"Accept-Encoding\" header: {e}"
+            ))
+        })?;
+        Ok(Self(preferred_encoding(accept_encoding)))
+    }
+
+    fn metadata(_body_content_type: ApiEndpointBodyContentType) -> ExtractorMetadata {
+        ExtractorMetadata {
+            extension_mode: ExtensionMode::None,
+            parameters: Vec::new(),
+        }
+    }
+}
+
+// Pick the supported encoding with the highest quality value.
+// Ties go to the encoding listed first, and a quality value of zero means "not acceptable".
+fn preferred_encoding(accept_encoding: &str) -> Option<ContentEncoding> {
+    let mut preferred: Option<(ContentEncoding, f32)> = None;
+    for value in accept_encoding.split(',') {
+        let mut params = value.split(';').map(str::trim);
+        let Some(encoding) = params
+            .next()
+            .and_then(|coding| ContentEncoding::from_coding(&coding.to_ascii_lowercase()))
+        else {
+            continue;
+        };
+        let quality = params
+            .find_map(|param| param.strip_prefix("q="))
+            .map_or(Some(1.0), |q| q.parse::<f32>().ok())
+            .unwrap_or_default();
+        if quality <= 0.0 {
+            continue;
+        }
+        if preferred.map_or(true, |(_, preferred_quality)| quality > preferred_quality) {
+            preferred = Some((encoding, quality));
+        }
+    }
+    preferred.map(|(encoding, _)| encoding)
+}
+
+impl AcceptEncoding {
+    // This is required due to a limitation in `dropshot` where only four extractors are allowed.
+    pub async fn new<Context: ServerContext>(
+        rqctx: &RequestContext<Context>,
+    ) -> Result<Self, HttpError> {
+        Self::from_request(rqctx).await
+    }
+
+    /// Encode the response body with the preferred encoding, if any.
+    pub async fn encode<R>(self, response: R) -> Result<Encoded<R>, HttpError>
+    where
+        R: HttpResponse + Send + Sync + 'static,
+    {
+        let mut response = response.to_result()?;
+        response.headers_mut().insert(
+            header::VARY,
+            HeaderValue::from_static(header::ACCEPT_ENCODING.as_str()),
+        );
+        let Some(encoding) = self.0 else {
+            return Ok(Encoded::new(response));
+        };
+
+        let (mut parts, body) = response.into_parts();
+        let bytes = hyper::body::to_bytes(body)
+            .await
+            .map_err(|e| encode_error(encoding, e))?;
+        if bytes.len() < MIN_ENCODE_BYTES {
+            return Ok(Encoded::new(Response::from_parts(parts, bytes.into())));
+        }
+
+        let encoded = encoding
+            .encode(&bytes)
+            .await
+            .map_err(|e| encode_error(encoding, e))?;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        // The encoded body is no longer byte-for-byte the same as the tagged body
+        if let Some(etag) = parts.headers.get(header::ETAG).and_then(weak_etag) {
+            parts.headers.insert(header::ETAG, etag);
+        }
+        parts.headers.insert(
+            header::CONTENT_ENCODING,
+            HeaderValue::from_static(encoding.as_str()),
+        );
+        Ok(Encoded::new(Response::from_parts(parts, encoded.into())))
+    }
+}
+
+fn weak_etag(etag: &HeaderValue) -> Option<HeaderValue> {
+    let etag = etag.to_str().ok()?;
+    if etag.starts_with("W/") {
+        return None;
+    }
+    HeaderValue::from_str(&format!("W/{etag}")).ok()
+}
+
+fn encode_error<E>(encoding: ContentEncoding, error: E) -> HttpError
+where
+    E: std::fmt::Display,
+{
+    issue_error(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Failed to encode response",
+        &format!("Failed to encode response ({})", encoding.as_str()),
+        error,
+    )
+}
+
+/// A response that may have had its body compressed.
+/// The response metadata is the same as the original response,
+/// so the API documentation is unchanged.
+pub struct Encoded<R> {
+    response: Response<Body>,
+    _response: PhantomData<R>,
+}
+
+impl<R> Encoded<R> {
+    fn new(response: Response<Body>) -> Self {
+        Self {
+            response,
+            _response: PhantomData,
+        }
+    }
+}
+
+impl<R> HttpResponse for Encoded<R>
+where
+    R: HttpResponse + Send + Sync + 'static,
+{
+    fn to_result(self) -> Result<Response<Body>, HttpError> {
+        Ok(self.response)
+    }
+
+    fn response_metadata() -> ApiEndpointResponse {
+        R::response_metadata()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        self.response.status()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use async_compression::tokio::write::{BrotliDecoder, GzipDecoder};
+    use dropshot::HttpResponse;
+    use http::{header, HeaderValue, Response};
+    use hyper::Body;
+    use pretty_assertions::assert_eq;
+    use tokio::io::AsyncWriteExt;
+
+    use super::{preferred_encoding, weak_etag, AcceptEncoding, ContentEncoding, MIN_ENCODE_BYTES};
+
+    async fn decode(encoding: ContentEncoding, bytes: &[u8]) -> Vec<u8> {
+        match encoding {
+            ContentEncoding::Brotli => {
+                let mut decoder = BrotliDecoder::new(Vec::new());
+                decoder.write_all(bytes).await.unwrap();
+                decoder.shutdown().await.unwrap();
+                decoder.into_inner()
+            },
+            ContentEncoding::Gzip => {
+                let mut decoder = GzipDecoder::new(Vec::new());
+                decoder.write_all(bytes).await.unwrap();
+                decoder.shutdown().await.unwrap();
+                decoder.into_inner()
+            },
+        }
+    }
+
+    fn response(body: &str) -> Response<Body> {
+        Response::builder()
+            .header(header::ETAG, "\"etag\"")
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(body.to_owned().into())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_preferred_encoding() {
+        for (accept_encoding, expected) in [
+            ("", None),
+            ("identity", None),
+            ("deflate", None),
+            ("br", Some(ContentEncoding::Brotli)),
+            ("gzip", Some(ContentEncoding::Gzip)),
+            ("x-gzip", Some(ContentEncoding::Gzip)),
+            ("GZIP", Some(ContentEncoding::Gzip)),
+            ("*", Some(ContentEncoding::Brotli)),
+            // Ties go to the encoding listed first
+            ("gzip, br", Some(ContentEncoding::Gzip)),
+            ("br, gzip", Some(ContentEncoding::Brotli)),
+            ("deflate, gzip, br", Some(ContentEncoding::Gzip)),
+            // The highest quality value wins
+            ("br;q=0.5, gzip;q=0.8", Some(ContentEncoding::Gzip)),
+            ("gzip;q=0.8, br", Some(ContentEncoding::Brotli)),
+            ("gzip ; q=0.8 , br ; q=0.9", Some(ContentEncoding::Brotli)),
+            // A quality value of zero means not acceptable
+            ("br;q=0", None),
+            ("br;q=0, gzip", Some(ContentEncoding::Gzip)),
+            ("gzip;q=0.0, *;q=0", None),
+            // An invalid quality value is treated as not acceptable
+            ("br;q=high, gzip;q=0.1", Some(ContentEncoding::Gzip)),
+        ] {
+            assert_eq!(
+                preferred_encoding(accept_encoding),
+                expected,
+                "{accept_encoding}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_weak_etag() {
+        assert_eq!(
+            weak_etag(&HeaderValue::from_static("\"etag\"")),
+            Some(HeaderValue::from_static("W/\"etag\""))
+        );
+        // Already weak
+        assert_eq!(weak_etag(&HeaderValue::from_static("W/\"etag\"")), None);
+    }
+
+    #[tokio::test]
+    async fn test_content_encoding_round_trip() {
+        let bytes = "bencher ".repeat(MIN_ENCODE_BYTES).into_bytes();
+        for encoding in [ContentEncoding::Brotli, ContentEncoding::Gzip] {
+            let encoded = encoding.encode(&bytes).await.unwrap();
+            assert!(encoded.len() < bytes.len());
+            assert_eq!(decode(encoding, &encoded).await, bytes);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accept_encoding_encode() {
+        let body = "bencher ".repeat(MIN_ENCODE_BYTES);
+        for encoding in [ContentEncoding::Brotli, ContentEncoding::Gzip] {
+            let encoded = AcceptEncoding(Some(encoding))
+                .encode(response(&body))
+                .await
+                .unwrap()
+                .to_result()
+                .unwrap();
+            let headers = encoded.headers();
+            assert_eq!(
+                headers.get(header::CONTENT_ENCODING).unwrap(),
+                encoding.as_str()
+            );
+            assert_eq!(headers.get(header::VARY).unwrap(), "accept-encoding");
+            assert_eq!(headers.get(header::ETAG).unwrap(), "W/\"etag\"");
+            assert!(headers.get(header::CONTENT_LENGTH).is_none());
+            let bytes = hyper::body::to_bytes(encoded.into_body()).await.unwrap();
+            assert_eq!(decode(encoding, &bytes).await, body.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accept_encoding_not_encoded() {
+        // No encoding is accepted
+        let body = "bencher ".repeat(MIN_ENCODE_BYTES);
+        let response = AcceptEncoding(None)
+            .encode(response(&body))
+            .await
+            .unwrap()
+            .to_result()
+            .unwrap();
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        assert_eq!(response.headers().get(header::ETAG).unwrap(), "\"etag\"");
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(bytes, body.as_bytes());
+
+        // The body is too small to be worth encoding
+        let response = AcceptEncoding(Some(ContentEncoding::Gzip))
+            .encode(self::response("bencher"))
+            .await
+            .unwrap()
+            .to_result()
+            .unwrap();
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(bytes, "bencher");
+    }
+}
@@ -0,0 +1,152 @@
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use dropshot::{
+    ApiEndpointBodyContentType, ApiEndpointResponse, ExtensionMode, ExtractorMetadata, HttpError,
+    HttpResponse, RequestContext, ServerContext, SharedExtractor,
+};
+use http::{header, HeaderValue, Response, StatusCode};
+use hyper::Body;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::{bad_request_error, issue_error},
+    model::user::auth::Headers,
+};
+
+/// The number of bytes of the response body hash used for the entity tag
+const ETAG_HASH_BYTES: usize = 16;
+
+/// The entity tags from the `If-None-Match` request header,
+/// ie `If-None-Match: "2c26b46b68ffc68ff99b453c1d304134"`
+/// If the response body has not changed, then a `304 Not Modified` response is sent instead.
+#[derive(Debug, Clone)]
+pub struct IfNoneMatch(Option<String>);
+
+#[async_trait]
+impl SharedExtractor for IfNoneMatch {
+    async fn from_request<Context: ServerContext>(
+        rqctx: &RequestContext<Context>,
+    ) -> Result<Self, HttpError> {
+        let headers = Headers::from_request(rqctx).await?;
+
+        let Some(if_none_match) = headers.0.get(header::IF_NONE_MATCH) else {
+            return Ok(Self(None));
+        };
+        let if_none_match = if_none_match.to_str().map_err(|e| {
+            bad_request_error(format!(
+                "Request has an invalid \"If-None-Match\" header: {e}"
+            ))
+        })?;
+        Ok(Self(Some(if_none_match.to_owned())))
+    }
+
+    fn metadata(_body_content_type: ApiEndpointBodyContentType) -> ExtractorMetadata {
+        ExtractorMetadata {
+            extension_mode: ExtensionMode::None,
+            parameters: Vec::new(),
+        }
+    }
+}
+
+impl IfNoneMatch {
+    // This is required due to a limitation in `dropshot` where only four extractors are allowed.
+    pub async fn new<Context: ServerContext>(
+        rqctx: &RequestContext<Context>,
+    ) -> Result<Self, HttpError> {
+        Self::from_request(rqctx).await
+    }
+
+    /// Tag the response with a strong `ETag` for its body.
+    /// If the tag matches one of the `If-None-Match` request header tags,
+    /// then the body is dropped and a `304 Not Modified` response is sent instead.
+    pub async fn tag<R>(self, response: R) -> Result<Tagged<R>, HttpError>
+    where
+        R: HttpResponse + Send + Sync + 'static,
+    {
+        let response = response.to_result()?;
+        if !response.status().is_success() {
+            return Ok(Tagged::new(response));
+        }
+
+        let (mut parts, body) = response.into_parts();
+        let bytes = hyper::body::to_bytes(body).await.map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to tag response",
+                "Failed to read response body for entity tag",
+                e,
+            )
+        })?;
+        let etag = etag(&bytes);
+        let is_match = self.0.as_deref().is_some_and(|tags| matches(tags, &etag));
+        let etag = HeaderValue::from_str(&etag).map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to tag response",
+                &format!("Failed to create entity tag header ({etag})"),
+                e,
+            )
+        })?;
+        parts.headers.insert(header::ETAG, etag);
+
+        if is_match {
+            parts.status = StatusCode::NOT_MODIFIED;
+            parts.headers.remove(header::CONTENT_LENGTH);
+            parts.headers.remove(header::CONTENT_TYPE);
+            return Ok(Tagged::new(Response::from_parts(parts, Body::empty())));
+        }
+        Ok(Tagged::new(Response::from_parts(parts, bytes.into())))
+    }
+}
+
+fn etag(bytes: &[u8]) -> String {
+    let hash = Sha256::digest(bytes);
+    format!(
+        "\"{}\"",
+        hex::encode(hash.get(..ETAG_HASH_BYTES).unwrap_or_default())
+    )
+}
+
+// Weak comparison is used for `If-None-Match`, so a weak tag (`W/"..."`) also matches.
+// https://www.rfc-editor.org/rfc/rfc9110#field.if-none-match
+fn matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
+
+/// A response that has been tagged with an `ETag`.
+/// The response metadata is the same as the original response,
+/// so the API documentation is unchanged.
+pub struct Tagged<R> {
+    response: Response<Body>,
+    _response: PhantomData<R>,
+}
+
+impl<R> Tagged<R> {
+    fn new(response: Response<Body>) -> Self {
+        Self {
+            response,
+            _response: PhantomData,
+        }
+    }
+}
+
+impl<R> HttpResponse for Tagged<R>
+where
+    R: HttpResponse + Send + Sync + 'static,
+{
+    fn to_result(self) -> Result<Response<Body>, HttpError> {
+        Ok(self.response)
+    }
+
+    fn response_metadata() -> ApiEndpointResponse {
+        R::response_metadata()
+    }
+
+    fn status_code(&self) -> StatusCode {
+        self.response.status()
+    }
+}
@@ -2,11 +2,15 @@
 
 use dropshot::{ApiDescription, ApiDescriptionRegisterError};
 
+pub mod encoding;
 pub mod endpoint;
+pub mod etag;
 pub mod organization;
 pub mod project;
+pub mod report_body;
 pub mod system;
 pub mod user;
+pub mod version;
 
 pub use endpoint::Endpoint;
 
@@ -79,9 +83,26 @@ impl Api {
         // Organization Projects
         if http_options {
             api.register(organization::projects::org_projects_options)?;
+            api.register(organization::projects::org_claim_options)?;
         }
         api.register(organization::projects::org_projects_get)?;
         api.register(organization::projects::org_project_post)?;
+        api.register(organization::projects::org_claim_post)?;
+
+        // Organization Perf
+        if http_options {
+            api.register(organization::perf::org_perf_options)?;
+        }
+        api.register(organization::perf::org_perf_get)?;
+
+        // Organization Tokens
+        if http_options {
+            api.register(organization::tokens::org_token_policy_options)?;
+            api.register(organization::tokens::org_tokens_revoke_options)?;
+        }
+        api.register(organization::tokens::org_token_policy_get)?;
+        api.register(organization::tokens::org_token_policy_put)?;
+        api.register(organization::tokens::org_tokens_revoke_post)?;
 
         #[cfg(feature = "plus")]
         {
@@ -101,6 +122,23 @@ impl Api {
                 api.register(organization::usage::org_usage_options)?;
             }
             api.register(organization::usage::org_usage_get)?;
+
+            // Organization SCIM
+            if http_options {
+                api.register(organization::scim::users::org_scim_users_options)?;
+                api.register(organization::scim::users::org_scim_user_options)?;
+                api.register(organization::scim::groups::org_scim_groups_options)?;
+                api.register(organization::scim::groups::org_scim_group_options)?;
+            }
+            api.register(organization::scim::users::org_scim_users_get)?;
+            api.register(organization::scim::users::org_scim_user_post)?;
+            api.register(organization::scim::users::org_scim_user_get)?;
+            api.register(organization::scim::users::org_scim_user_put)?;
+            api.register(organization::scim::users::org_scim_user_patch)?;
+            api.register(organization::scim::users::org_scim_user_delete)?;
+            api.register(organization::scim::groups::org_scim_groups_get)?;
+            api.register(organization::scim::groups::org_scim_group_get)?;
+            api.register(organization::scim::groups::org_scim_group_patch)?;
         }
 
         // Projects
@@ -108,11 +146,13 @@ impl Api {
         if http_options {
             api.register(project::projects::projects_options)?;
             api.register(project::projects::project_options)?;
+            api.register(project::projects::project_transfer_options)?;
         }
         api.register(project::projects::projects_get)?;
         api.register(project::projects::project_get)?;
         api.register(project::projects::project_patch)?;
         api.register(project::projects::project_delete)?;
+        api.register(project::projects::project_transfer_post)?;
 
         // Project Permission
         if http_options {
@@ -120,15 +160,38 @@ impl Api {
         }
         api.register(project::allowed::proj_allowed_get)?;
 
+        // Project Members
+        if http_options {
+            api.register(project::members::proj_members_options)?;
+            api.register(project::members::proj_member_options)?;
+        }
+        api.register(project::members::proj_members_get)?;
+        api.register(project::members::proj_member_post)?;
+        api.register(project::members::proj_member_get)?;
+        api.register(project::members::proj_member_patch)?;
+        api.register(project::members::proj_member_delete)?;
+
         // Reports
         if http_options {
             api.register(project::reports::proj_reports_options)?;
             api.register(project::reports::proj_report_options)?;
+            api.register(project::reports::proj_import_options)?;
+            api.register(project::reports::proj_shard_options)?;
+            api.register(project::reports::proj_report_reparse_options)?;
         }
         api.register(project::reports::proj_report_post)?;
         api.register(project::reports::proj_reports_get)?;
         api.register(project::reports::proj_report_get)?;
         api.register(project::reports::proj_report_delete)?;
+        api.register(project::reports::proj_import_post)?;
+        api.register(project::reports::proj_shard_post)?;
+        api.register(project::reports::proj_report_reparse_post)?;
+
+        // Run
+        if http_options {
+            api.register(project::run::run_options)?;
+        }
+        api.register(project::run::run_post)?;
 
         // Perf
         if http_options {
@@ -142,6 +205,32 @@ impl Api {
         }
         api.register(project::perf::img::proj_perf_img_get)?;
 
+        // Perf Groups
+        if http_options {
+            api.register(project::perf::groups::proj_perf_groups_options)?;
+        }
+        api.register(project::perf::groups::proj_perf_groups_get)?;
+
+        // Perf Fingerprints
+        if http_options {
+            api.register(project::perf::fingerprints::proj_perf_fingerprints_options)?;
+        }
+        api.register(project::perf::fingerprints::proj_perf_fingerprints_get)?;
+
+        // Perf Embed
+        if http_options {
+            api.register(project::perf::embed::proj_perf_embed_options)?;
+        }
+        api.register(project::perf::embed::proj_perf_embed_get)?;
+        api.register(project::perf::embed::proj_perf_embed_post)?;
+        api.register(project::perf::embed::proj_perf_embed_delete)?;
+
+        // Perf Badge
+        if http_options {
+            api.register(project::perf::badge::proj_perf_badge_options)?;
+        }
+        api.register(project::perf::badge::proj_perf_badge_get)?;
+
         // Plots
         if http_options {
             api.register(project::plots::proj_plots_options)?;
@@ -153,16 +242,94 @@ impl Api {
         api.register(project::plots::proj_plot_patch)?;
         api.register(project::plots::proj_plot_delete)?;
 
+        // Saved Queries
+        if http_options {
+            api.register(project::queries::proj_queries_options)?;
+            api.register(project::queries::proj_query_options)?;
+        }
+        api.register(project::queries::proj_queries_get)?;
+        api.register(project::queries::proj_query_post)?;
+        api.register(project::queries::proj_query_get)?;
+        api.register(project::queries::proj_query_patch)?;
+        api.register(project::queries::proj_query_delete)?;
+
+        // Annotations
+        if http_options {
+            api.register(project::annotations::proj_annotations_options)?;
+            api.register(project::annotations::proj_annotation_options)?;
+        }
+        api.register(project::annotations::proj_annotations_get)?;
+        api.register(project::annotations::proj_annotation_post)?;
+        api.register(project::annotations::proj_annotation_get)?;
+        api.register(project::annotations::proj_annotation_patch)?;
+        api.register(project::annotations::proj_annotation_delete)?;
+
+        // Rules
+        if http_options {
+            api.register(project::rules::proj_rules_options)?;
+            api.register(project::rules::proj_rule_options)?;
+        }
+        api.register(project::rules::proj_rules_get)?;
+        api.register(project::rules::proj_rule_post)?;
+        api.register(project::rules::proj_rule_get)?;
+        api.register(project::rules::proj_rule_patch)?;
+        api.register(project::rules::proj_rule_delete)?;
+
+        // Expected regression windows
+        if http_options {
+            api.register(project::expectations::proj_expectations_options)?;
+            api.register(project::expectations::proj_expectation_options)?;
+        }
+        api.register(project::expectations::proj_expectations_get)?;
+        api.register(project::expectations::proj_expectation_post)?;
+        api.register(project::expectations::proj_expectation_get)?;
+        api.register(project::expectations::proj_expectation_delete)?;
+
         // Branches
         if http_options {
             api.register(project::branches::proj_branches_options)?;
             api.register(project::branches::proj_branch_options)?;
+            api.register(project::branches::proj_branch_lineage_options)?;
+            api.register(project::branches::proj_branch_versions_options)?;
+            api.register(project::branches::proj_branch_tags_options)?;
         }
         api.register(project::branches::proj_branches_get)?;
         api.register(project::branches::proj_branch_post)?;
         api.register(project::branches::proj_branch_get)?;
         api.register(project::branches::proj_branch_patch)?;
         api.register(project::branches::proj_branch_delete)?;
+        api.register(project::branches::proj_branch_lineage_get)?;
+        api.register(project::branches::proj_branch_versions_get)?;
+        api.register(project::branches::proj_branch_tags_post)?;
+
+        // GitHub
+        if http_options {
+            api.register(project::github::proj_github_webhook_options)?;
+        }
+        api.register(project::github::proj_github_webhook_get)?;
+        api.register(project::github::proj_github_webhook_put)?;
+        api.register(project::github::proj_github_webhook_delete)?;
+        api.register(project::github::proj_github_webhook_post)?;
+
+        // Report Hook
+        if http_options {
+            api.register(project::hook::proj_report_hook_options)?;
+        }
+        api.register(project::hook::proj_report_hook_get)?;
+        api.register(project::hook::proj_report_hook_put)?;
+        api.register(project::hook::proj_report_hook_delete)?;
+
+        // Webhook
+        if http_options {
+            api.register(project::webhook::proj_webhook_options)?;
+            api.register(project::webhook::proj_webhook_failed_options)?;
+            api.register(project::webhook::proj_webhook_redeliver_options)?;
+        }
+        api.register(project::webhook::proj_webhook_get)?;
+        api.register(project::webhook::proj_webhook_put)?;
+        api.register(project::webhook::proj_webhook_delete)?;
+        api.register(project::webhook::proj_webhook_failed_get)?;
+        api.register(project::webhook::proj_webhook_redeliver_post)?;
 
         // Testbeds
         if http_options {
@@ -190,12 +357,19 @@ impl Api {
         if http_options {
             api.register(project::measures::proj_measures_options)?;
             api.register(project::measures::proj_measure_options)?;
+            api.register(project::measures::proj_measure_merge_options)?;
+            api.register(project::measure_aliases::proj_measure_aliases_options)?;
+            api.register(project::measure_aliases::proj_measure_alias_options)?;
         }
         api.register(project::measures::proj_measures_get)?;
         api.register(project::measures::proj_measure_post)?;
         api.register(project::measures::proj_measure_get)?;
         api.register(project::measures::proj_measure_patch)?;
         api.register(project::measures::proj_measure_delete)?;
+        api.register(project::measures::proj_measure_merge_post)?;
+        api.register(project::measure_aliases::proj_measure_aliases_get)?;
+        api.register(project::measure_aliases::proj_measure_alias_post)?;
+        api.register(project::measure_aliases::proj_measure_alias_delete)?;
 
         // Metrics
         if http_options {
@@ -207,21 +381,28 @@ impl Api {
         if http_options {
             api.register(project::thresholds::proj_thresholds_options)?;
             api.register(project::thresholds::proj_threshold_options)?;
+            api.register(project::thresholds::proj_threshold_recompute_options)?;
         }
         api.register(project::thresholds::proj_thresholds_get)?;
         api.register(project::thresholds::proj_threshold_post)?;
         api.register(project::thresholds::proj_threshold_get)?;
         api.register(project::thresholds::proj_threshold_put)?;
         api.register(project::thresholds::proj_threshold_delete)?;
+        api.register(project::thresholds::proj_threshold_recompute_post)?;
 
         // Threshold Alerts
         if http_options {
             api.register(project::alerts::proj_alerts_options)?;
             api.register(project::alerts::proj_alert_options)?;
+            api.register(project::alerts::proj_alert_stats_options)?;
+            api.register(project::alerts::proj_alert_img_options)?;
         }
         api.register(project::alerts::proj_alerts_get)?;
+        api.register(project::alerts::proj_alerts_patch)?;
         api.register(project::alerts::proj_alert_get)?;
         api.register(project::alerts::proj_alert_patch)?;
+        api.register(project::alerts::proj_alert_stats_get)?;
+        api.register(project::alerts::proj_alert_img_get)?;
 
         // Users
         if http_options {
@@ -232,32 +413,60 @@ impl Api {
         api.register(user::users::user_get)?;
         api.register(user::users::user_patch)?;
 
+        // User Preferences
+        if http_options {
+            api.register(user::prefs::user_prefs_options)?;
+        }
+        api.register(user::prefs::user_prefs_get)?;
+        api.register(user::prefs::user_prefs_put)?;
+
         // Tokens
         if http_options {
             api.register(user::tokens::user_tokens_options)?;
             api.register(user::tokens::user_token_options)?;
+            api.register(user::tokens::user_token_revoke_options)?;
         }
         api.register(user::tokens::user_tokens_get)?;
         api.register(user::tokens::user_token_post)?;
         api.register(user::tokens::user_token_get)?;
         api.register(user::tokens::user_token_patch)?;
+        api.register(user::tokens::user_token_revoke_post)?;
 
         // Server
         if http_options {
             api.register(system::server::version::server_version_options)?;
             api.register(system::server::spec::server_spec_options)?;
             api.register(system::server::restart::server_restart_options)?;
+            api.register(system::server::secret::server_secret_rotate_options)?;
             api.register(system::server::config::server_config_options)?;
             api.register(system::server::config::server_config_console_options)?;
             api.register(system::server::backup::server_backup_options)?;
+            api.register(system::server::db::server_db_check_options)?;
+            api.register(system::server::downsample::server_downsample_options)?;
+            api.register(system::server::telemetry::server_telemetry_options)?;
+            api.register(system::server::rbac::server_rbac_options)?;
         }
         api.register(system::server::version::server_version_get)?;
         api.register(system::server::spec::server_spec_get)?;
         api.register(system::server::restart::server_restart_post)?;
+        api.register(system::server::secret::server_secret_rotate_post)?;
         api.register(system::server::config::server_config_get)?;
         api.register(system::server::config::server_config_put)?;
         api.register(system::server::config::server_config_console_get)?;
         api.register(system::server::backup::server_backup_post)?;
+        api.register(system::server::db::server_db_check_get)?;
+        api.register(system::server::downsample::server_downsample_post)?;
+        api.register(system::server::telemetry::server_telemetry_get)?;
+        api.register(system::server::rbac::server_rbac_get)?;
+
+        #[cfg(feature = "graphql")]
+        {
+            // GraphQL
+            if http_options {
+                api.register(system::graphql::server_graphql_options)?;
+            }
+            api.register(system::graphql::server_graphql_post)?;
+        }
 
         #[cfg(feature = "plus")]
         {
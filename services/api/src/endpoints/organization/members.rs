@@ -16,6 +16,7 @@ use crate::{
     conn_lock,
     context::{ApiContext, Body, ButtonBody, DbConnection, Message},
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
             CorsResponse, Delete, Get, Patch, Post, ResponseAccepted, ResponseDeleted, ResponseOk,
         },
@@ -91,7 +92,8 @@ pub async fn org_members_get(
     path_params: Path<OrgMembersParams>,
     pagination_params: Query<OrgMembersPagination>,
     query_params: Query<OrgMembersQuery>,
-) -> Result<ResponseOk<JsonMembers>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonMembers>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -101,7 +103,9 @@ pub async fn org_members_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
 }
 
 async fn get_ls_inner(
@@ -322,7 +326,7 @@ async fn post_inner(
         subject: Some(format!("Invitation to join {org_name}")),
         body: Some(body),
     };
-    context.messenger.send(log, message);
+    context.messenger.send(log, conn_lock!(context), message);
 
     Ok(JsonAuthAck { email })
 }
@@ -360,11 +364,12 @@ pub async fn org_member_options(
 pub async fn org_member_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<OrgMemberParams>,
-) -> Result<ResponseOk<JsonMember>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonMember>>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_one_inner(
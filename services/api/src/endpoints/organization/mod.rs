@@ -1,6 +1,9 @@
 pub mod allowed;
 pub mod members;
 pub mod organizations;
+pub mod perf;
 pub mod plan;
 pub mod projects;
+pub mod scim;
+pub mod tokens;
 pub mod usage;
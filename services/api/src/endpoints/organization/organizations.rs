@@ -15,6 +15,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
             CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
         },
@@ -80,7 +81,8 @@ pub async fn organizations_get(
     bearer_token: BearerToken,
     pagination_params: Query<OrganizationsPagination>,
     query_params: Query<OrganizationsQuery>,
-) -> Result<ResponseOk<JsonOrganizations>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonOrganizations>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -89,7 +91,9 @@ pub async fn organizations_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
 }
 
 async fn get_ls_inner(
@@ -249,11 +253,12 @@ pub async fn organization_options(
 pub async fn organization_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<OrganizationParams>,
-) -> Result<ResponseOk<JsonOrganization>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonOrganization>>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_one_inner(
@@ -0,0 +1,259 @@
+use bencher_json::{
+    organization::perf::{JsonOrganizationPerfProject, JsonOrganizationPerfQueryParams},
+    project::perf::JsonPerfMetrics,
+    BenchmarkUuid, JsonOrganizationPerf, JsonOrganizationPerfQuery, NameId, ResourceId,
+};
+use bencher_rbac::{organization::Permission as OrganizationPermission, project::Permission};
+use diesel::{BelongingToDsl, ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, ResponseOk},
+        project::perf::{perf_results, Times, MAX_PERMUTATIONS},
+        Endpoint,
+    },
+    error::{bad_request_error, not_found_error, resource_not_found_err},
+    model::{
+        organization::QueryOrganization,
+        project::{
+            benchmark::QueryBenchmark, branch::QueryBranch, measure::QueryMeasure,
+            testbed::QueryTestbed, QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken},
+    },
+    schema,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrgPerfParams {
+    /// The slug or UUID for an organization.
+    pub organization: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/perf",
+    tags = ["organizations", "perf"]
+}]
+pub async fn org_perf_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgPerfParams>,
+    _query_params: Query<JsonOrganizationPerfQueryParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// Query organization performance metrics
+///
+/// Query the performance metrics for the same branch, testbed, and measure across multiple projects in an organization.
+/// The branch, testbed, benchmark, and measure are found by name, slug, or UUID within each project.
+/// The results for each project are returned as a separate series.
+/// If no projects are specified, then every project in the organization that the user is allowed to view is queried.
+/// There is a limit of 255 benchmarks across all projects for a single request.
+/// Therefore, only the first 255 benchmarks are queried.
+/// The user must have `view` permissions for the organization.
+/// Each specified project must either be public or the user must have `view` permissions for it.
+#[endpoint {
+    method = GET,
+    path =  "/v0/organizations/{organization}/perf",
+    tags = ["organizations", "perf"]
+}]
+pub async fn org_perf_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgPerfParams>,
+    query_params: Query<JsonOrganizationPerfQueryParams>,
+) -> Result<Encoded<ResponseOk<JsonOrganizationPerf>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    // Second round of marshaling
+    let json_perf_query = query_params
+        .into_inner()
+        .try_into()
+        .map_err(bad_request_error)?;
+
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        json_perf_query,
+        &auth_user,
+    )
+    .await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_inner(
+    context: &ApiContext,
+    path_params: OrgPerfParams,
+    json_perf_query: JsonOrganizationPerfQuery,
+    auth_user: &AuthUser,
+) -> Result<JsonOrganizationPerf, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        OrganizationPermission::View,
+    )?;
+
+    let JsonOrganizationPerfQuery {
+        projects,
+        branch,
+        testbed,
+        benchmark,
+        measure,
+        start_time,
+        end_time,
+    } = json_perf_query;
+
+    let query_projects = if projects.is_empty() {
+        // Only include the projects that the user is allowed to view
+        QueryProject::belonging_to(&query_organization)
+            .order(schema::project::name.asc())
+            .load::<QueryProject>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Project, &query_organization))?
+            .into_iter()
+            .filter(|query_project| {
+                query_project.is_public()
+                    || context
+                        .rbac
+                        .is_allowed_project(auth_user, Permission::View, query_project)
+                        .is_ok()
+            })
+            .collect::<Vec<_>>()
+    } else {
+        let mut query_projects = Vec::with_capacity(projects.len());
+        for project in &projects {
+            let query_project = QueryProject::is_allowed_public(
+                conn_lock!(context),
+                &context.rbac,
+                project,
+                Some(auth_user),
+            )?;
+            if query_project.organization_id != query_organization.id {
+                return Err(not_found_error(format!(
+                    "Project ({project}) does not belong to organization ({organization})",
+                    organization = path_params.organization
+                )));
+            }
+            query_projects.push(query_project);
+        }
+        query_projects
+    };
+
+    let times = Times {
+        start_time,
+        end_time,
+    };
+    let dimensions = Dimensions {
+        branch: &branch,
+        testbed: &testbed,
+        benchmark: benchmark.as_ref(),
+        measure: &measure,
+    };
+
+    let mut remaining = MAX_PERMUTATIONS;
+    let mut json_projects = Vec::with_capacity(query_projects.len());
+    for query_project in query_projects {
+        let results = project_results(context, &query_project, dimensions, times, remaining)
+            .await?
+            .map(|(results, queried)| {
+                remaining = remaining.saturating_sub(queried);
+                results
+            })
+            .unwrap_or_default();
+        json_projects.push(JsonOrganizationPerfProject {
+            project: query_project.into_json_for_organization(&query_organization),
+            results,
+        });
+    }
+
+    Ok(JsonOrganizationPerf {
+        organization: query_organization.into_json(),
+        start_time,
+        end_time,
+        projects: json_projects,
+    })
+}
+
+#[derive(Clone, Copy)]
+struct Dimensions<'a> {
+    branch: &'a NameId,
+    testbed: &'a NameId,
+    benchmark: Option<&'a ResourceId>,
+    measure: &'a NameId,
+}
+
+/// Query the results for a single project along with the number of benchmarks queried.
+/// Returns `None` if the project does not have the branch, testbed, benchmark, or measure.
+async fn project_results(
+    context: &ApiContext,
+    query_project: &QueryProject,
+    dimensions: Dimensions<'_>,
+    times: Times,
+    remaining: usize,
+) -> Result<Option<(Vec<JsonPerfMetrics>, usize)>, HttpError> {
+    let Dimensions {
+        branch,
+        testbed,
+        benchmark,
+        measure,
+    } = dimensions;
+    let project_id = query_project.id;
+    // Each query is separate to prevent a deadlock when getting the conn_lock
+    let Ok(query_branch) = QueryBranch::from_name_id(conn_lock!(context), project_id, branch)
+    else {
+        return Ok(None);
+    };
+    let Ok(query_testbed) = QueryTestbed::from_name_id(conn_lock!(context), project_id, testbed)
+    else {
+        return Ok(None);
+    };
+    let Ok(query_measure) = QueryMeasure::from_name_id(conn_lock!(context), project_id, measure)
+    else {
+        return Ok(None);
+    };
+
+    let benchmarks = if let Some(benchmark) = benchmark {
+        let Ok(query_benchmark) =
+            QueryBenchmark::from_resource_id(conn_lock!(context), project_id, benchmark)
+        else {
+            return Ok(None);
+        };
+        vec![query_benchmark.uuid]
+    } else {
+        schema::benchmark::table
+            .filter(schema::benchmark::project_id.eq(project_id))
+            .filter(schema::benchmark::archived.is_null())
+            .order(schema::benchmark::name.asc())
+            .select(schema::benchmark::uuid)
+            .limit(i64::try_from(remaining).unwrap_or(i64::MAX))
+            .load::<BenchmarkUuid>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Benchmark, query_project))?
+    };
+    let benchmarks = benchmarks.get(..remaining).unwrap_or(benchmarks.as_slice());
+    if benchmarks.is_empty() {
+        return Ok(None);
+    }
+
+    let results = perf_results(
+        &context.database.connection,
+        query_project,
+        &[query_branch.uuid],
+        &[None],
+        &[query_testbed.uuid],
+        benchmarks,
+        &[query_measure.uuid],
+        times,
+        &[],
+    )
+    .await?;
+    Ok(Some((results, benchmarks.len())))
+}
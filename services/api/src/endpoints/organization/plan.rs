@@ -16,6 +16,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Delete, Get, Post, ResponseCreated, ResponseDeleted, ResponseOk},
         Endpoint,
     },
@@ -61,11 +62,12 @@ pub async fn org_plan_options(
 pub async fn org_plan_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<OrgPlanParams>,
-) -> Result<ResponseOk<JsonPlan>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonPlan>>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_one_inner(
@@ -1,10 +1,7 @@
 use bencher_json::{
-    project::{
-        measure::built_in::generic::{Latency, Throughput},
-        ProjectRole,
-    },
-    DateTime, JsonDirection, JsonNewProject, JsonPagination, JsonProject, JsonProjects, ResourceId,
-    ResourceName,
+    project::{ProjectRole, Visibility},
+    DateTime, JsonDirection, JsonNewProject, JsonPagination, JsonProject, JsonProjectClaim,
+    JsonProjects, ResourceId, ResourceName,
 };
 use bencher_rbac::organization::Permission;
 use diesel::{
@@ -22,18 +19,15 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, Post, ResponseCreated, ResponseOk},
         Endpoint,
     },
-    error::{forbidden_error, resource_conflict_err, resource_not_found_err},
+    error::{forbidden_error, resource_conflict_err, resource_not_found_err, unauthorized_error},
     model::{
         organization::QueryOrganization,
         project::{
-            branch::InsertBranch,
-            measure::{InsertMeasure, QueryMeasure},
-            project_role::InsertProjectRole,
-            testbed::{InsertTestbed, QueryTestbed},
-            threshold::InsertThreshold,
+            project_role::InsertProjectRole, unclaimed_project::QueryUnclaimedProject,
             InsertProject, QueryProject,
         },
         user::auth::{AuthUser, BearerToken},
@@ -64,6 +58,8 @@ pub struct OrgProjectsQuery {
     pub name: Option<ResourceName>,
     /// Search by project name, slug, or UUID.
     pub search: Option<Search>,
+    /// Filter by project visibility.
+    pub visibility: Option<Visibility>,
 }
 
 #[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
@@ -97,7 +93,8 @@ pub async fn org_projects_get(
     path_params: Path<OrgProjectsParams>,
     pagination_params: Query<OrgProjectsPagination>,
     query_params: Query<OrgProjectsQuery>,
-) -> Result<ResponseOk<JsonProjects>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonProjects>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -107,7 +104,9 @@ pub async fn org_projects_get(
         &auth_user,
     )
     .await?;
-    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
 }
 
 async fn get_ls_inner(
@@ -162,6 +161,9 @@ fn get_ls_query<'q>(
     if let Some(name) = query_params.name.as_ref() {
         query = query.filter(schema::project::name.eq(name));
     }
+    if let Some(visibility) = query_params.visibility {
+        query = query.filter(schema::project::visibility.eq(visibility));
+    }
     if let Some(search) = query_params.search.as_ref() {
         query = query.filter(
             schema::project::name
@@ -244,14 +246,7 @@ async fn post_inner(
         .is_allowed_organization(auth_user, Permission::Create, &insert_project)
         .map_err(forbidden_error)?;
 
-    diesel::insert_into(schema::project::table)
-        .values(&insert_project)
-        .execute(conn_lock!(context))
-        .map_err(resource_conflict_err!(Project, insert_project))?;
-    let query_project = schema::project::table
-        .filter(schema::project::uuid.eq(&insert_project.uuid))
-        .first::<QueryProject>(conn_lock!(context))
-        .map_err(resource_not_found_err!(Project, insert_project))?;
+    let query_project = insert_project.insert_with_defaults(log, context).await?;
     slog::debug!(log, "Created project: {query_project:?}");
 
     let timestamp = DateTime::now();
@@ -269,60 +264,96 @@ async fn post_inner(
         .map_err(resource_conflict_err!(ProjectRole, insert_proj_role))?;
     slog::debug!(log, "Added project role: {insert_proj_role:?}");
 
-    // Add a `main` branch to the project
-    let query_branch = InsertBranch::main(log, context, query_project.id).await?;
-    slog::debug!(log, "Added project branch: {query_branch:?}");
-    let branch_id = query_branch.id;
+    #[cfg(feature = "plus")]
+    context.update_index(log, &query_project).await;
 
-    // Add a `localhost` testbed to the project
-    let insert_testbed = InsertTestbed::localhost(conn_lock!(context), query_project.id)?;
-    diesel::insert_into(schema::testbed::table)
-        .values(&insert_testbed)
-        .execute(conn_lock!(context))
-        .map_err(resource_conflict_err!(Testbed, insert_testbed))?;
-    let testbed_id = QueryTestbed::get_id(conn_lock!(context), insert_testbed.uuid)?;
-    slog::debug!(log, "Added project testbed: {insert_testbed:?}");
+    query_project.into_json(conn_lock!(context))
+}
 
-    // Add a `latency` measure to the project
-    let insert_measure =
-        InsertMeasure::from_measure::<Latency>(conn_lock!(context), query_project.id)?;
-    diesel::insert_into(schema::measure::table)
-        .values(&insert_measure)
-        .execute(conn_lock!(context))
-        .map_err(resource_conflict_err!(Measure, insert_measure))?;
-    let measure_id = QueryMeasure::get_id(conn_lock!(context), insert_measure.uuid)?;
-    slog::debug!(log, "Added project measure: {insert_measure:?}");
-    // Add a `latency` threshold to the project
-    let threshold_id = InsertThreshold::upper_boundary(
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/claim",
+    tags = ["organizations", "projects"]
+}]
+pub async fn org_claim_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgProjectsParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Claim an unclaimed project for an organization
+///
+/// Claim an unclaimed project, which was created by a report without authentication, for an organization.
+/// The claim token that was returned when the unclaimed project was created must be provided.
+/// The user must have `create` permissions for the organization.
+/// The project is moved to the organization, and the user is added to the project as a `Maintainer`.
+/// If the organization already has a project with the same name, then a new `name` must be provided.
+#[endpoint {
+    method = POST,
+    path =  "/v0/organizations/{organization}/claim",
+    tags = ["organizations", "projects"]
+}]
+pub async fn org_claim_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgProjectsParams>,
+    body: TypedBody<JsonProjectClaim>,
+) -> Result<ResponseOk<JsonProject>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = claim_inner(
+        &rqctx.log,
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_ok(json))
+}
+
+async fn claim_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: OrgProjectsParams,
+    json_claim: JsonProjectClaim,
+    auth_user: &AuthUser,
+) -> Result<JsonProject, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
         conn_lock!(context),
-        query_project.id,
-        branch_id,
-        testbed_id,
-        measure_id,
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::Create,
     )?;
-    slog::debug!(log, "Added project threshold: {threshold_id}");
 
-    // Add a `throughput` measure to the project
-    let insert_measure =
-        InsertMeasure::from_measure::<Throughput>(conn_lock!(context), query_project.id)?;
-    diesel::insert_into(schema::measure::table)
-        .values(&insert_measure)
-        .execute(conn_lock!(context))
-        .map_err(resource_conflict_err!(Measure, insert_measure))?;
-    let measure_id = QueryMeasure::get_id(conn_lock!(context), insert_measure.uuid)?;
-    slog::debug!(log, "Added project measure: {insert_measure:?}");
-    // Add a `throughput` threshold to the project
-    let threshold_id = InsertThreshold::lower_boundary(
+    let claims = context
+        .token_key
+        .validate_claim(&json_claim.token)
+        .map_err(unauthorized_error)?;
+    let query_project = schema::project::table
+        .filter(schema::project::uuid.eq(claims.proj.uuid))
+        .first::<QueryProject>(conn_lock!(context))
+        .map_err(resource_not_found_err!(Project, claims.proj.uuid))?;
+    QueryUnclaimedProject::claim(
         conn_lock!(context),
-        query_project.id,
-        branch_id,
-        testbed_id,
-        measure_id,
+        &query_project,
+        &query_organization,
+        json_claim.name,
+        auth_user.id(),
     )?;
-    slog::debug!(log, "Added project threshold: {threshold_id}");
+    slog::debug!(
+        log,
+        "Claimed project ({}) for organization ({})",
+        query_project.uuid,
+        query_organization.uuid
+    );
+
+    let query_project = QueryProject::get(conn_lock!(context), query_project.id)?;
 
     #[cfg(feature = "plus")]
     context.update_index(log, &query_project).await;
 
-    query_project.into_json(conn_lock!(context))
+    Ok(query_project.into_json_for_organization(&query_organization))
 }
@@ -0,0 +1,402 @@
+use bencher_json::{
+    organization::{
+        member::OrganizationRole,
+        scim::{
+            JsonScimGroup, JsonScimGroups, JsonScimMember, JsonScimMeta, JsonScimPatch,
+            JsonScimPatchOp, JsonScimPatchOperation, ScimFilter, SCIM_GROUP_RESOURCE,
+            SCIM_GROUP_SCHEMA, SCIM_LIST_RESPONSE_SCHEMA, SCIM_MEMBERS_PATH,
+        },
+    },
+    ResourceId, UserName, UserUuid,
+};
+use bencher_rbac::organization::Permission;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::{transaction, ApiContext, DbConnection},
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, Patch, ResponseOk},
+        Endpoint,
+    },
+    error::{bad_request_error, resource_not_found_err},
+    model::{
+        organization::{OrganizationId, QueryOrganization},
+        user::{
+            auth::{AuthUser, BearerToken},
+            QueryUser, UserId,
+        },
+    },
+    schema,
+};
+
+use super::{
+    check_scim_license, remove_scim_member, upsert_scim_member, ScimQuery, SCIM_DEFAULT_ROLE,
+    SCIM_GROUPS,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrgScimGroupsParams {
+    /// The slug or UUID for an organization.
+    pub organization: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/scim/v2/Groups",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_groups_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgScimGroupsParams>,
+    _query_params: Query<ScimQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// List SCIM groups
+///
+/// List the roles of an organization as SCIM groups.
+/// Each group contains the organization members with that role.
+/// The user must have `view_role` permissions for the organization.
+/// Only the `displayName eq "<role>"` filter is supported.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = GET,
+    path =  "/v0/organizations/{organization}/scim/v2/Groups",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_groups_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgScimGroupsParams>,
+    query_params: Query<ScimQuery>,
+) -> Result<Encoded<ResponseOk<JsonScimGroups>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_ls_inner(
+        rqctx.context(),
+        &auth_user,
+        path_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    path_params: OrgScimGroupsParams,
+    query_params: ScimQuery,
+) -> Result<JsonScimGroups, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::ViewRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+
+    let display_name = if let Some(filter) = &query_params.filter {
+        let filter: ScimFilter = filter.parse().map_err(bad_request_error)?;
+        if filter.is_attribute("displayName") {
+            Some(filter.value)
+        } else {
+            return Err(bad_request_error(format!(
+                "Unsupported SCIM group filter attribute: {filter}"
+            )));
+        }
+    } else {
+        None
+    };
+
+    let roles = SCIM_GROUPS
+        .into_iter()
+        .filter(|role| {
+            display_name
+                .as_ref()
+                .map_or(true, |display_name| role.to_string() == *display_name)
+        })
+        .collect::<Vec<_>>();
+    let total_results = u32::try_from(roles.len()).unwrap_or(u32::MAX);
+    let mut resources = Vec::with_capacity(roles.len());
+    for role in roles
+        .into_iter()
+        .skip(usize::try_from(query_params.offset()).unwrap_or(usize::MAX))
+        .take(usize::try_from(query_params.count()).unwrap_or(usize::MAX))
+    {
+        resources.push(into_scim_group(
+            conn_lock!(context),
+            &query_organization,
+            role,
+        )?);
+    }
+
+    Ok(JsonScimGroups {
+        schemas: vec![SCIM_LIST_RESPONSE_SCHEMA.to_owned()],
+        total_results,
+        start_index: query_params.start_index(),
+        items_per_page: u32::try_from(resources.len()).unwrap_or(u32::MAX),
+        resources,
+    })
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrgScimGroupParams {
+    /// The slug or UUID for an organization.
+    pub organization: ResourceId,
+    /// The organization role for the group.
+    pub group: OrganizationRole,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/scim/v2/Groups/{group}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_group_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgScimGroupParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Patch.into()]))
+}
+
+/// View a SCIM group
+///
+/// View an organization role as a SCIM group.
+/// The user must have `view_role` permissions for the organization.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = GET,
+    path =  "/v0/organizations/{organization}/scim/v2/Groups/{group}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_group_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<OrgScimGroupParams>,
+) -> Result<Encoded<ResponseOk<JsonScimGroup>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: OrgScimGroupParams,
+    auth_user: &AuthUser,
+) -> Result<JsonScimGroup, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::ViewRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+
+    into_scim_group(conn_lock!(context), &query_organization, path_params.group)
+}
+
+/// Update a SCIM group
+///
+/// Update the members of an organization role from an identity provider.
+/// The user must have `edit_role` permissions for the organization.
+/// Adding a user to a group gives them that organization role.
+/// Removing a user from the `leader` group gives them the `member` role,
+/// and removing a user from the `member` group removes them from the organization.
+/// The last leader of the organization can not be removed.
+/// Only the `members` attribute can be updated.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/organizations/{organization}/scim/v2/Groups/{group}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_group_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgScimGroupParams>,
+    body: TypedBody<JsonScimPatch>,
+) -> Result<ResponseOk<JsonScimGroup>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = patch_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+async fn patch_inner(
+    context: &ApiContext,
+    path_params: OrgScimGroupParams,
+    json_patch: JsonScimPatch,
+    auth_user: &AuthUser,
+) -> Result<JsonScimGroup, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::EditRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+    let role = path_params.group;
+
+    // All of the operations are applied in a single transaction,
+    // so a failed operation does not leave the group partially updated.
+    conn_lock!(context, |conn| transaction(conn, |conn| {
+        for operation in json_patch.operations {
+            patch_operation(conn, &query_organization, role, operation)?;
+        }
+        into_scim_group(conn, &query_organization, role)
+    }))
+}
+
+fn patch_operation(
+    conn: &mut DbConnection,
+    query_organization: &QueryOrganization,
+    role: OrganizationRole,
+    operation: JsonScimPatchOperation,
+) -> Result<(), HttpError> {
+    let JsonScimPatchOperation { op, path, value } = operation;
+    let path = path.unwrap_or_else(|| SCIM_MEMBERS_PATH.to_owned());
+    // Attributes other than `members` (ie `displayName`) are ignored
+    let Some(member_filter) = ScimFilter::members_path(&path).map_err(bad_request_error)? else {
+        return Ok(());
+    };
+    let mut user_ids = if let Some(user) = member_filter {
+        vec![QueryUser::get_id(conn, user)?]
+    } else {
+        Vec::new()
+    };
+    if let Some(value) = value {
+        for user in parse_members_value(value)? {
+            user_ids.push(QueryUser::get_id(conn, user)?);
+        }
+    }
+
+    match op {
+        JsonScimPatchOp::Add => {
+            for user_id in user_ids {
+                upsert_scim_member(conn, query_organization.id, user_id, role)?;
+            }
+        },
+        JsonScimPatchOp::Replace => {
+            let member_ids = get_group_member_ids(conn, query_organization, role)?;
+            // Add the new members before removing the old ones,
+            // so replacing the last leader does not leave the organization without one.
+            for user_id in &user_ids {
+                upsert_scim_member(conn, query_organization.id, *user_id, role)?;
+            }
+            for member_id in member_ids {
+                if !user_ids.contains(&member_id) {
+                    remove_group_member(conn, query_organization.id, member_id, role)?;
+                }
+            }
+        },
+        JsonScimPatchOp::Remove => {
+            let member_ids = get_group_member_ids(conn, query_organization, role)?;
+            for user_id in user_ids {
+                // Only remove the user if they have the role for this group
+                if member_ids.contains(&user_id) {
+                    remove_group_member(conn, query_organization.id, user_id, role)?;
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+// Removing a user from a group other than the default group gives them the default role,
+// while removing them from the default group removes them from the organization.
+fn remove_group_member(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+    role: OrganizationRole,
+) -> Result<(), HttpError> {
+    if role == SCIM_DEFAULT_ROLE {
+        remove_scim_member(conn, organization_id, user_id)
+    } else {
+        upsert_scim_member(conn, organization_id, user_id, SCIM_DEFAULT_ROLE)
+    }
+}
+
+fn parse_members_value(value: serde_json::Value) -> Result<Vec<UserUuid>, HttpError> {
+    // Some identity providers send a single member instead of an array
+    let members = if value.is_array() {
+        serde_json::from_value::<Vec<JsonScimMember>>(value)
+    } else {
+        serde_json::from_value::<JsonScimMember>(value).map(|member| vec![member])
+    }
+    .map_err(bad_request_error)?;
+    members
+        .into_iter()
+        .map(|member| member.value.parse().map_err(bad_request_error))
+        .collect()
+}
+
+fn get_group_member_ids(
+    conn: &mut DbConnection,
+    query_organization: &QueryOrganization,
+    role: OrganizationRole,
+) -> Result<Vec<UserId>, HttpError> {
+    schema::organization_role::table
+        .filter(schema::organization_role::organization_id.eq(query_organization.id))
+        .filter(schema::organization_role::role.eq(role))
+        .select(schema::organization_role::user_id)
+        .load::<UserId>(conn)
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (query_organization, role)
+        ))
+}
+
+fn into_scim_group(
+    conn: &mut DbConnection,
+    query_organization: &QueryOrganization,
+    role: OrganizationRole,
+) -> Result<JsonScimGroup, HttpError> {
+    let members = schema::user::table
+        .inner_join(schema::organization_role::table)
+        .filter(schema::organization_role::organization_id.eq(query_organization.id))
+        .filter(schema::organization_role::role.eq(role))
+        .order(schema::user::email.asc())
+        .select((schema::user::uuid, schema::user::name))
+        .load::<(UserUuid, UserName)>(conn)
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (query_organization, role)
+        ))?
+        .into_iter()
+        .map(|(uuid, name)| JsonScimMember {
+            value: uuid.to_string(),
+            display: Some(name.into()),
+        })
+        .collect();
+
+    Ok(JsonScimGroup {
+        schemas: vec![SCIM_GROUP_SCHEMA.to_owned()],
+        id: role,
+        display_name: role.to_string(),
+        members,
+        meta: JsonScimMeta {
+            resource_type: SCIM_GROUP_RESOURCE.to_owned(),
+            created: query_organization.created,
+            last_modified: query_organization.modified,
+        },
+    })
+}
@@ -0,0 +1,454 @@
+#![cfg(feature = "plus")]
+
+use bencher_json::{
+    organization::{
+        member::OrganizationRole,
+        scim::{
+            JsonScimEmail, JsonScimMember, JsonScimMeta, JsonScimName, JsonScimUser,
+            SCIM_USER_RESOURCE, SCIM_USER_SCHEMA,
+        },
+    },
+    DateTime, PlanLevel,
+};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    context::{ApiContext, DbConnection},
+    error::{
+        forbidden_error, locked_error, payment_required_error, resource_conflict_err,
+        resource_not_found_err,
+    },
+    model::{
+        organization::{
+            organization_role::{InsertOrganizationRole, QueryOrganizationRole},
+            plan::LicenseUsage,
+            OrganizationId, QueryOrganization,
+        },
+        user::{QueryUser, UserId},
+    },
+    schema,
+};
+
+pub mod groups;
+pub mod users;
+
+// Every organization role is exposed as a SCIM group
+pub const SCIM_GROUPS: [OrganizationRole; 2] = [OrganizationRole::Member, OrganizationRole::Leader];
+// The role given to newly provisioned users.
+// Users must be explicitly added to the leader group to manage the organization.
+pub const SCIM_DEFAULT_ROLE: OrganizationRole = OrganizationRole::Member;
+
+const DEFAULT_COUNT: u32 = 100;
+const MAX_COUNT: u32 = 255;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimQuery {
+    /// SCIM filter expression.
+    /// Only `<attribute> eq "<value>"` is supported.
+    pub filter: Option<String>,
+    /// The 1-based index of the first result.
+    pub start_index: Option<u32>,
+    /// The maximum number of results to return.
+    pub count: Option<u32>,
+}
+
+impl ScimQuery {
+    pub fn start_index(&self) -> u32 {
+        self.start_index.unwrap_or(1).max(1)
+    }
+
+    pub fn offset(&self) -> i64 {
+        i64::from(self.start_index() - 1)
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count.unwrap_or(DEFAULT_COUNT).min(MAX_COUNT)
+    }
+}
+
+/// SCIM provisioning is only available on Bencher Self-Hosted
+/// for organizations with a valid Bencher Plus Enterprise license.
+pub fn check_scim_license(
+    conn: &mut DbConnection,
+    context: &ApiContext,
+    query_organization: &QueryOrganization,
+) -> Result<(), HttpError> {
+    if context.is_bencher_cloud {
+        return Err(locked_error(
+            "SCIM provisioning is not available on Bencher Cloud. It is only available on Bencher Self-Hosted.",
+        ));
+    }
+
+    let is_enterprise = LicenseUsage::get(conn, &context.licensor, query_organization)?
+        .is_some_and(|license_usage| license_usage.level >= PlanLevel::Enterprise);
+    if is_enterprise {
+        Ok(())
+    } else {
+        Err(payment_required_error(format!(
+            "You must have a valid Bencher Plus Enterprise license for the organization ({}) to use SCIM provisioning",
+            query_organization.uuid
+        )))
+    }
+}
+
+pub type ScimMember = (QueryUser, QueryOrganizationRole);
+
+pub fn get_scim_member(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+) -> Result<ScimMember, HttpError> {
+    schema::user::table
+        .inner_join(schema::organization_role::table)
+        .filter(schema::organization_role::organization_id.eq(organization_id))
+        .filter(schema::organization_role::user_id.eq(user_id))
+        .select((
+            schema::user::all_columns,
+            schema::organization_role::all_columns,
+        ))
+        .first::<ScimMember>(conn)
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (organization_id, user_id)
+        ))
+}
+
+/// Get a SCIM user that is either a member of the organization or was deactivated by SCIM.
+/// The organization role is only returned for members.
+pub fn get_scim_user(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+) -> Result<(QueryUser, Option<QueryOrganizationRole>), HttpError> {
+    if is_scim_member(conn, organization_id, user_id)? {
+        let (query_user, query_org_role) = get_scim_member(conn, organization_id, user_id)?;
+        return Ok((query_user, Some(query_org_role)));
+    }
+    schema::user::table
+        .inner_join(schema::scim_inactive_user::table)
+        .filter(schema::scim_inactive_user::organization_id.eq(organization_id))
+        .filter(schema::scim_inactive_user::user_id.eq(user_id))
+        .select(schema::user::all_columns)
+        .first::<QueryUser>(conn)
+        .map(|query_user| (query_user, None))
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (organization_id, user_id)
+        ))
+}
+
+/// Check if the user is a member of the organization
+pub fn is_scim_member(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+) -> Result<bool, HttpError> {
+    diesel::select(diesel::dsl::exists(
+        schema::organization_role::table
+            .filter(schema::organization_role::organization_id.eq(organization_id))
+            .filter(schema::organization_role::user_id.eq(user_id)),
+    ))
+    .get_result(conn)
+    .map_err(resource_not_found_err!(
+        OrganizationRole,
+        (organization_id, user_id)
+    ))
+}
+
+/// Add the user to the organization with the given role,
+/// or update their role if they are already a member.
+/// A user that was deactivated is reactivated.
+/// The last leader of the organization can not be given a different role.
+pub fn upsert_scim_member(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+    role: OrganizationRole,
+) -> Result<(), HttpError> {
+    if role != OrganizationRole::Leader {
+        check_last_leader(conn, organization_id, user_id)?;
+    }
+    clear_scim_inactive_user(conn, organization_id, user_id)?;
+    let updated = diesel::update(
+        schema::organization_role::table
+            .filter(schema::organization_role::organization_id.eq(organization_id))
+            .filter(schema::organization_role::user_id.eq(user_id)),
+    )
+    .set((
+        schema::organization_role::role.eq(role),
+        schema::organization_role::modified.eq(DateTime::now()),
+    ))
+    .execute(conn)
+    .map_err(resource_conflict_err!(
+        OrganizationRole,
+        (organization_id, user_id, role)
+    ))?;
+    if updated > 0 {
+        return Ok(());
+    }
+
+    let timestamp = DateTime::now();
+    let insert_org_role = InsertOrganizationRole {
+        user_id,
+        organization_id,
+        role,
+        created: timestamp,
+        modified: timestamp,
+    };
+    diesel::insert_into(schema::organization_role::table)
+        .values(&insert_org_role)
+        .execute(conn)
+        .map_err(resource_conflict_err!(OrganizationRole, insert_org_role))?;
+
+    Ok(())
+}
+
+/// Remove the user from the organization and from all of the projects in the organization.
+/// Otherwise, their project roles would still grant them access to those projects.
+/// The last leader of the organization can not be removed.
+pub fn remove_scim_member(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+) -> Result<(), HttpError> {
+    check_last_leader(conn, organization_id, user_id)?;
+    diesel::delete(
+        schema::project_role::table
+            .filter(schema::project_role::user_id.eq(user_id))
+            .filter(
+                schema::project_role::project_id.eq_any(
+                    schema::project::table
+                        .filter(schema::project::organization_id.eq(organization_id))
+                        .select(schema::project::id),
+                ),
+            ),
+    )
+    .execute(conn)
+    .map_err(resource_conflict_err!(
+        ProjectRole,
+        (organization_id, user_id)
+    ))?;
+    diesel::delete(
+        schema::organization_role::table
+            .filter(schema::organization_role::organization_id.eq(organization_id))
+            .filter(schema::organization_role::user_id.eq(user_id)),
+    )
+    .execute(conn)
+    .map_err(resource_conflict_err!(
+        OrganizationRole,
+        (organization_id, user_id)
+    ))?;
+    Ok(())
+}
+
+/// Remove the user from the organization,
+/// but keep track of them so they can be reactivated.
+/// The last leader of the organization can not be deactivated.
+pub fn deactivate_scim_member(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+) -> Result<(), HttpError> {
+    remove_scim_member(conn, organization_id, user_id)?;
+    let insert_inactive_user = InsertScimInactiveUser {
+        organization_id,
+        user_id,
+        created: DateTime::now(),
+    };
+    diesel::insert_or_ignore_into(schema::scim_inactive_user::table)
+        .values(&insert_inactive_user)
+        .execute(conn)
+        .map_err(resource_conflict_err!(
+            OrganizationRole,
+            insert_inactive_user
+        ))?;
+    Ok(())
+}
+
+/// Forget that the user was deactivated in the organization
+pub fn clear_scim_inactive_user(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+) -> Result<(), HttpError> {
+    diesel::delete(
+        schema::scim_inactive_user::table
+            .filter(schema::scim_inactive_user::organization_id.eq(organization_id))
+            .filter(schema::scim_inactive_user::user_id.eq(user_id)),
+    )
+    .execute(conn)
+    .map_err(resource_conflict_err!(
+        OrganizationRole,
+        (organization_id, user_id)
+    ))?;
+    Ok(())
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = schema::scim_inactive_user)]
+struct InsertScimInactiveUser {
+    organization_id: OrganizationId,
+    user_id: UserId,
+    created: DateTime,
+}
+
+// An identity provider must not be able to lock everyone out of the organization,
+// so it must always be left with at least one leader.
+fn check_last_leader(
+    conn: &mut DbConnection,
+    organization_id: OrganizationId,
+    user_id: UserId,
+) -> Result<(), HttpError> {
+    let leader_ids = schema::organization_role::table
+        .filter(schema::organization_role::organization_id.eq(organization_id))
+        .filter(schema::organization_role::role.eq(OrganizationRole::Leader))
+        .select(schema::organization_role::user_id)
+        .load::<UserId>(conn)
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (organization_id, OrganizationRole::Leader)
+        ))?;
+    if leader_ids.len() == 1 && leader_ids.contains(&user_id) {
+        Err(forbidden_error(format!(
+            "Cannot remove user ({user_id}) as they are the last leader of the organization ({organization_id})"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn into_scim_user(
+    query_user: QueryUser,
+    query_org_role: Option<QueryOrganizationRole>,
+) -> JsonScimUser {
+    let active = query_org_role.is_some() && !query_user.locked;
+    let groups = query_org_role
+        .as_ref()
+        .map(|org_role| {
+            vec![JsonScimMember {
+                value: org_role.role.to_string(),
+                display: Some(org_role.role.to_string()),
+            }]
+        })
+        .unwrap_or_default();
+    let (created, last_modified) = query_org_role
+        .map_or((query_user.created, query_user.modified), |org_role| {
+            (org_role.created, org_role.modified)
+        });
+    JsonScimUser {
+        schemas: vec![SCIM_USER_SCHEMA.to_owned()],
+        id: query_user.uuid,
+        user_name: query_user.email.clone(),
+        display_name: query_user.name.clone(),
+        name: JsonScimName {
+            formatted: Some(query_user.name.into()),
+            ..Default::default()
+        },
+        emails: vec![JsonScimEmail {
+            value: query_user.email,
+            primary: Some(true),
+            kind: Some("work".to_owned()),
+        }],
+        active,
+        groups,
+        meta: JsonScimMeta {
+            resource_type: SCIM_USER_RESOURCE.to_owned(),
+            created,
+            last_modified,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{
+        organization::member::OrganizationRole,
+        project::{ProjectRole, Visibility},
+        DateTime,
+    };
+    use bencher_rbac::{init_rbac, project::Permission};
+    use diesel::RunQueryDsl;
+
+    use crate::{
+        context::{DbConnection, Rbac},
+        model::{
+            project::{project_role::InsertProjectRole, QueryProject},
+            user::{auth::AuthUser, QueryUser},
+        },
+        schema,
+        util::test_util::{create_organization, create_project, create_user, setup_db},
+    };
+
+    use super::{deactivate_scim_member, remove_scim_member, upsert_scim_member};
+
+    fn add_maintainer(
+        conn: &mut DbConnection,
+        query_user: &QueryUser,
+        query_project: &QueryProject,
+    ) {
+        let timestamp = DateTime::now();
+        diesel::insert_into(schema::project_role::table)
+            .values(&InsertProjectRole {
+                user_id: query_user.id,
+                project_id: query_project.id,
+                role: ProjectRole::Maintainer,
+                created: timestamp,
+                modified: timestamp,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn can_manage(
+        conn: &mut DbConnection,
+        rbac: &Rbac,
+        query_user: &QueryUser,
+        query_project: &QueryProject,
+    ) -> bool {
+        let auth_user = AuthUser::from_email(conn, &query_user.email).unwrap();
+        rbac.is_allowed_project(&auth_user, Permission::Manage, query_project)
+            .is_ok()
+    }
+
+    #[test]
+    fn test_remove_scim_member_project_roles() {
+        let mut conn = setup_db();
+        let rbac = Rbac::from(init_rbac().unwrap());
+        let org = create_organization(&mut conn, "Organization");
+        let other_org = create_organization(&mut conn, "Other Organization");
+        let leader = create_user(&mut conn, "Leader");
+        let creator = create_user(&mut conn, "Creator");
+        upsert_scim_member(&mut conn, org.id, leader.id, OrganizationRole::Leader).unwrap();
+        upsert_scim_member(&mut conn, org.id, creator.id, OrganizationRole::Member).unwrap();
+        upsert_scim_member(
+            &mut conn,
+            other_org.id,
+            creator.id,
+            OrganizationRole::Member,
+        )
+        .unwrap();
+
+        // The creator of a project is its maintainer
+        let project = create_project(&mut conn, &org, "Project", Visibility::Private);
+        add_maintainer(&mut conn, &creator, &project);
+        let other_project =
+            create_project(&mut conn, &other_org, "Other Project", Visibility::Private);
+        add_maintainer(&mut conn, &creator, &other_project);
+        assert!(can_manage(&mut conn, &rbac, &creator, &project));
+
+        // A deactivated user loses access to the projects in the organization,
+        // but not to the projects in other organizations
+        deactivate_scim_member(&mut conn, org.id, creator.id).unwrap();
+        assert!(!can_manage(&mut conn, &rbac, &creator, &project));
+        assert!(can_manage(&mut conn, &rbac, &creator, &other_project));
+
+        // The same goes for a user that is removed
+        upsert_scim_member(&mut conn, other_org.id, leader.id, OrganizationRole::Leader).unwrap();
+        remove_scim_member(&mut conn, other_org.id, creator.id).unwrap();
+        assert!(!can_manage(&mut conn, &rbac, &creator, &other_project));
+    }
+}
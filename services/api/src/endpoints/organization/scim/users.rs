@@ -0,0 +1,657 @@
+use bencher_json::{
+    organization::scim::{
+        JsonScimNewUser, JsonScimPatch, JsonScimPatchOp, JsonScimPatchOperation, JsonScimUser,
+        JsonScimUsers, ScimFilter, SCIM_LIST_RESPONSE_SCHEMA,
+    },
+    DateTime, Email, ResourceId, UserName, UserUuid,
+};
+use bencher_rbac::organization::Permission;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use slog::Logger;
+
+use crate::{
+    conn_lock,
+    context::{transaction, ApiContext, DbConnection},
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{
+            CorsResponse, Delete, Get, Patch, Post, Put, ResponseCreated, ResponseDeleted,
+            ResponseOk,
+        },
+        Endpoint,
+    },
+    error::{
+        bad_request_error, conflict_error, forbidden_error, resource_conflict_err,
+        resource_not_found_err,
+    },
+    model::{
+        organization::{OrganizationId, QueryOrganization},
+        user::{
+            auth::{AuthUser, BearerToken},
+            InsertUser, QueryUser, UpdateUser,
+        },
+    },
+    schema,
+};
+
+use super::{
+    check_scim_license, clear_scim_inactive_user, deactivate_scim_member, get_scim_member,
+    get_scim_user, into_scim_user, is_scim_member, remove_scim_member, upsert_scim_member,
+    ScimMember, ScimQuery, SCIM_DEFAULT_ROLE,
+};
+
+const SCIM_METHOD: &str = "SCIM";
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrgScimUsersParams {
+    /// The slug or UUID for an organization.
+    pub organization: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/scim/v2/Users",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_users_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgScimUsersParams>,
+    _query_params: Query<ScimQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List SCIM users
+///
+/// List the members of an organization as SCIM users.
+/// The user must have `view_role` permissions for the organization.
+/// Only the `userName eq "<email>"` filter is supported.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = GET,
+    path =  "/v0/organizations/{organization}/scim/v2/Users",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_users_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgScimUsersParams>,
+    query_params: Query<ScimQuery>,
+) -> Result<Encoded<ResponseOk<JsonScimUsers>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_ls_inner(
+        rqctx.context(),
+        &auth_user,
+        path_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    path_params: OrgScimUsersParams,
+    query_params: ScimQuery,
+) -> Result<JsonScimUsers, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::ViewRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+
+    let email = if let Some(filter) = &query_params.filter {
+        let filter: ScimFilter = filter.parse().map_err(bad_request_error)?;
+        if filter.is_attribute("userName") || filter.is_attribute("emails.value") {
+            Some(filter.value.parse::<Email>().map_err(bad_request_error)?)
+        } else {
+            return Err(bad_request_error(format!(
+                "Unsupported SCIM user filter attribute: {filter}"
+            )));
+        }
+    } else {
+        None
+    };
+
+    let mut query = schema::user::table
+        .inner_join(schema::organization_role::table)
+        .filter(schema::organization_role::organization_id.eq(query_organization.id))
+        .into_boxed();
+    if let Some(email) = &email {
+        query = query.filter(schema::user::email.eq(email));
+    }
+    let members = query
+        .order((schema::user::email.asc(), schema::user::id.asc()))
+        .offset(query_params.offset())
+        .limit(i64::from(query_params.count()))
+        .select((
+            schema::user::all_columns,
+            schema::organization_role::all_columns,
+        ))
+        .load::<ScimMember>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (&query_organization, &query_params)
+        ))?;
+
+    let mut count_query = schema::user::table
+        .inner_join(schema::organization_role::table)
+        .filter(schema::organization_role::organization_id.eq(query_organization.id))
+        .into_boxed();
+    if let Some(email) = &email {
+        count_query = count_query.filter(schema::user::email.eq(email));
+    }
+    let total_results = count_query
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (&query_organization, &query_params)
+        ))?;
+
+    let resources: Vec<JsonScimUser> = members
+        .into_iter()
+        .map(|(query_user, query_org_role)| into_scim_user(query_user, Some(query_org_role)))
+        .collect();
+    Ok(JsonScimUsers {
+        schemas: vec![SCIM_LIST_RESPONSE_SCHEMA.to_owned()],
+        total_results: u32::try_from(total_results).unwrap_or(u32::MAX),
+        start_index: query_params.start_index(),
+        items_per_page: u32::try_from(resources.len()).unwrap_or(u32::MAX),
+        resources,
+    })
+}
+
+/// Provision a SCIM user
+///
+/// Add a user to an organization from an identity provider.
+/// If a user with the same email does not exist, it will be created.
+/// The user must have `create_role` permissions for the organization.
+/// Unlike inviting a member, the user is added to the organization immediately.
+/// Provisioned users are given the `member` role.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = POST,
+    path =  "/v0/organizations/{organization}/scim/v2/Users",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_user_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgScimUsersParams>,
+    body: TypedBody<JsonScimNewUser>,
+) -> Result<ResponseCreated<JsonScimUser>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        &rqctx.log,
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: OrgScimUsersParams,
+    json_user: JsonScimNewUser,
+    auth_user: &AuthUser,
+) -> Result<JsonScimUser, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::CreateRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+
+    if !json_user.is_active() {
+        return Err(bad_request_error(
+            "Cannot provision an inactive SCIM user. Set `active` to `true` or omit it.",
+        ));
+    }
+    let email = json_user.email().ok_or_else(|| {
+        bad_request_error(format!(
+            "SCIM user does not have a valid email address: {}",
+            json_user.user_name
+        ))
+    })?;
+
+    let existing_user = schema::user::table
+        .filter(schema::user::email.eq(&email))
+        .first::<QueryUser>(conn_lock!(context))
+        .optional()
+        .map_err(resource_not_found_err!(User, email))?;
+    let user_id = if let Some(query_user) = existing_user {
+        if is_scim_member(conn_lock!(context), query_organization.id, query_user.id)? {
+            return Err(conflict_error(format!(
+                "SCIM user ({email}) is already a member of the organization ({})",
+                query_organization.uuid
+            )));
+        }
+        query_user.id
+    } else {
+        let name = json_user.user_name(&email).ok_or_else(|| {
+            bad_request_error(format!("SCIM user ({email}) does not have a valid name"))
+        })?;
+        let insert_user = InsertUser::new(conn_lock!(context), name, None, email)?;
+        diesel::insert_into(schema::user::table)
+            .values(&insert_user)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(User, insert_user))?;
+        insert_user.notify(
+            log,
+            conn_lock!(context),
+            &context.messenger,
+            &context.console_url,
+            true,
+            SCIM_METHOD,
+        )?;
+        QueryUser::get_id(conn_lock!(context), insert_user.uuid)?
+    };
+
+    upsert_scim_member(
+        conn_lock!(context),
+        query_organization.id,
+        user_id,
+        SCIM_DEFAULT_ROLE,
+    )?;
+    let (query_user, query_org_role) =
+        get_scim_member(conn_lock!(context), query_organization.id, user_id)?;
+    Ok(into_scim_user(query_user, Some(query_org_role)))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrgScimUserParams {
+    /// The slug or UUID for an organization.
+    pub organization: ResourceId,
+    /// The UUID for a user.
+    pub user: UserUuid,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/scim/v2/Users/{user}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_user_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgScimUserParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[
+        Get.into(),
+        Put.into(),
+        Patch.into(),
+        Delete.into(),
+    ]))
+}
+
+/// View a SCIM user
+///
+/// View a member of an organization as a SCIM user.
+/// Users that were deactivated by SCIM are also returned, as inactive.
+/// The user must have `view_role` permissions for the organization.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = GET,
+    path =  "/v0/organizations/{organization}/scim/v2/Users/{user}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_user_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<OrgScimUserParams>,
+) -> Result<Encoded<ResponseOk<JsonScimUser>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: OrgScimUserParams,
+    auth_user: &AuthUser,
+) -> Result<JsonScimUser, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::ViewRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+    let user_id = QueryUser::get_id(conn_lock!(context), path_params.user)?;
+
+    let (query_user, query_org_role) =
+        get_scim_user(conn_lock!(context), query_organization.id, user_id)?;
+    Ok(into_scim_user(query_user, query_org_role))
+}
+
+/// Replace a SCIM user
+///
+/// Replace the attributes of an organization member from an identity provider.
+/// The user must have `edit_role` permissions for the organization.
+/// If `active` is `false`, then the user is removed from the organization.
+/// If `active` is `true` for a user that was deactivated, then they are added back to the organization.
+/// The email for a user can only be changed if they are not a member of any other organization or its projects.
+/// The email for a server admin or a locked user can only be changed by a server admin.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = PUT,
+    path =  "/v0/organizations/{organization}/scim/v2/Users/{user}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_user_put(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgScimUserParams>,
+    body: TypedBody<JsonScimNewUser>,
+) -> Result<ResponseOk<JsonScimUser>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json_user = body.into_inner();
+    let email = json_user.email();
+    let update = ScimUserUpdate {
+        name: email.as_ref().and_then(|email| json_user.user_name(email)),
+        email,
+        active: Some(json_user.is_active()),
+    };
+    let json = update_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        update,
+        &auth_user,
+    )
+    .await?;
+    Ok(Put::auth_response_ok(json))
+}
+
+/// Update a SCIM user
+///
+/// Update the attributes of an organization member from an identity provider.
+/// The user must have `edit_role` permissions for the organization.
+/// Only the `active`, `displayName`, `name.formatted`, and `userName` attributes are updated.
+/// If `active` is `false`, then the user is removed from the organization.
+/// If `active` is `true` for a user that was deactivated, then they are added back to the organization.
+/// The email for a user can only be changed if they are not a member of any other organization or its projects.
+/// The email for a server admin or a locked user can only be changed by a server admin.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/organizations/{organization}/scim/v2/Users/{user}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_user_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgScimUserParams>,
+    body: TypedBody<JsonScimPatch>,
+) -> Result<ResponseOk<JsonScimUser>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let update = ScimUserUpdate::try_from(body.into_inner())?;
+    let json = update_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        update,
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+async fn update_inner(
+    context: &ApiContext,
+    path_params: OrgScimUserParams,
+    update: ScimUserUpdate,
+    auth_user: &AuthUser,
+) -> Result<JsonScimUser, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::EditRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+    let user_id = QueryUser::get_id(conn_lock!(context), path_params.user)?;
+
+    // All of the writes are made in a single transaction,
+    // so a failed update does not leave the user partially updated.
+    conn_lock!(context, |conn| transaction(conn, |conn| {
+        let (query_user, query_org_role) = get_scim_user(conn, query_organization.id, user_id)?;
+        let is_member = query_org_role.is_some();
+
+        let ScimUserUpdate {
+            name,
+            email,
+            active,
+        } = update;
+        let name = name.filter(|name| *name != query_user.name);
+        let email = email.filter(|email| *email != query_user.email);
+        if email.is_some() {
+            check_email_change(conn, context, auth_user, query_organization.id, &query_user)?;
+        }
+        if name.is_some() || email.is_some() {
+            let update_user = UpdateUser {
+                name,
+                slug: None,
+                email,
+                admin: None,
+                locked: None,
+                modified: DateTime::now(),
+            };
+            diesel::update(schema::user::table.filter(schema::user::id.eq(user_id)))
+                .set(&update_user)
+                .execute(conn)
+                .map_err(resource_conflict_err!(User, (&query_user, &update_user)))?;
+        }
+
+        match active {
+            Some(false) if is_member => {
+                deactivate_scim_member(conn, query_organization.id, user_id)?;
+            },
+            Some(true) if !is_member => {
+                upsert_scim_member(conn, query_organization.id, user_id, SCIM_DEFAULT_ROLE)?;
+            },
+            Some(_) | None => {},
+        }
+
+        let (query_user, query_org_role) = get_scim_user(conn, query_organization.id, user_id)?;
+        Ok(into_scim_user(query_user, query_org_role))
+    }))
+}
+
+/// Deprovision a SCIM user
+///
+/// Remove a member of an organization from an identity provider.
+/// The user must have `delete_role` permissions for the organization.
+/// The user account itself is not deleted.
+/// This endpoint is only available on Bencher Self-Hosted.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/organizations/{organization}/scim/v2/Users/{user}",
+    tags = ["organizations", "scim"]
+}]
+pub async fn org_scim_user_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgScimUserParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: OrgScimUserParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::DeleteRole,
+    )?;
+    check_scim_license(conn_lock!(context), context, &query_organization)?;
+    let user_id = QueryUser::get_id(conn_lock!(context), path_params.user)?;
+
+    conn_lock!(context, |conn| transaction(conn, |conn| {
+        // Make sure the user is a member of the organization or was deactivated by SCIM
+        let (_, query_org_role) = get_scim_user(conn, query_organization.id, user_id)?;
+        if query_org_role.is_some() {
+            remove_scim_member(conn, query_organization.id, user_id)?;
+        }
+        clear_scim_inactive_user(conn, query_organization.id, user_id)
+    }))
+}
+
+// Changing the email for a user changes how they log in,
+// so only allow it for users that are exclusively managed by this organization.
+// Server admins and locked users can only have their email changed by a server admin,
+// as otherwise an organization leader could take over a server admin account.
+fn check_email_change(
+    conn: &mut DbConnection,
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    organization_id: OrganizationId,
+    query_user: &QueryUser,
+) -> Result<(), HttpError> {
+    if auth_user.is_admin(&context.rbac) {
+        return Ok(());
+    }
+    let user_id = query_user.id;
+    if query_user.admin || query_user.locked {
+        return Err(forbidden_error(format!(
+            "Cannot change the email for user ({user_id}) because they are a server admin or locked"
+        )));
+    }
+
+    let other_organizations = schema::organization_role::table
+        .filter(schema::organization_role::user_id.eq(user_id))
+        .filter(schema::organization_role::organization_id.ne(organization_id))
+        .count()
+        .get_result::<i64>(conn)
+        .map_err(resource_not_found_err!(
+            OrganizationRole,
+            (organization_id, user_id)
+        ))?;
+    if other_organizations > 0 {
+        return Err(forbidden_error(format!(
+            "Cannot change the email for user ({user_id}) because they are a member of other organizations"
+        )));
+    }
+
+    let other_projects = schema::project_role::table
+        .inner_join(schema::project::table)
+        .filter(schema::project_role::user_id.eq(user_id))
+        .filter(schema::project::organization_id.ne(organization_id))
+        .count()
+        .get_result::<i64>(conn)
+        .map_err(resource_not_found_err!(
+            ProjectRole,
+            (organization_id, user_id)
+        ))?;
+    if other_projects > 0 {
+        Err(forbidden_error(format!(
+            "Cannot change the email for user ({user_id}) because they are a member of projects in other organizations"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct ScimUserUpdate {
+    name: Option<UserName>,
+    email: Option<Email>,
+    active: Option<bool>,
+}
+
+impl TryFrom<JsonScimPatch> for ScimUserUpdate {
+    type Error = HttpError;
+
+    fn try_from(json_patch: JsonScimPatch) -> Result<Self, Self::Error> {
+        let mut update = Self::default();
+        for operation in json_patch.operations {
+            let JsonScimPatchOperation { op, path, value } = operation;
+            match op {
+                JsonScimPatchOp::Add | JsonScimPatchOp::Replace => {},
+                // Removing an attribute is not meaningful for any of the supported attributes
+                JsonScimPatchOp::Remove => continue,
+            }
+            let Some(value) = value else {
+                return Err(bad_request_error(format!(
+                    "SCIM patch operation is missing a value: {path:?}"
+                )));
+            };
+            match (path, value) {
+                (Some(path), value) => update.set(&path, &value)?,
+                (None, serde_json::Value::Object(attributes)) => {
+                    for (path, value) in attributes {
+                        update.set(&path, &value)?;
+                    }
+                },
+                (None, value) => {
+                    return Err(bad_request_error(format!(
+                        "SCIM patch operation without a path must have an object value: {value}"
+                    )))
+                },
+            }
+        }
+        Ok(update)
+    }
+}
+
+impl ScimUserUpdate {
+    fn set(&mut self, path: &str, value: &serde_json::Value) -> Result<(), HttpError> {
+        if path.eq_ignore_ascii_case("active") {
+            self.active = Some(scim_bool(value)?);
+        } else if path.eq_ignore_ascii_case("displayName")
+            || path.eq_ignore_ascii_case("name.formatted")
+        {
+            self.name = Some(scim_str(value)?.parse().map_err(bad_request_error)?);
+        } else if path.eq_ignore_ascii_case("userName") {
+            self.email = Some(scim_str(value)?.parse().map_err(bad_request_error)?);
+        }
+        // All other attributes are ignored
+        Ok(())
+    }
+}
+
+// Some identity providers send booleans as strings
+fn scim_bool(value: &serde_json::Value) -> Result<bool, HttpError> {
+    match value {
+        serde_json::Value::Bool(value) => Ok(*value),
+        serde_json::Value::String(value) if value.eq_ignore_ascii_case("true") => Ok(true),
+        serde_json::Value::String(value) if value.eq_ignore_ascii_case("false") => Ok(false),
+        serde_json::Value::Null
+        | serde_json::Value::Number(_)
+        | serde_json::Value::String(_)
+        | serde_json::Value::Array(_)
+        | serde_json::Value::Object(_) => Err(bad_request_error(format!(
+            "Invalid SCIM boolean value: {value}"
+        ))),
+    }
+}
+
+fn scim_str(value: &serde_json::Value) -> Result<&str, HttpError> {
+    value
+        .as_str()
+        .ok_or_else(|| bad_request_error(format!("Invalid SCIM string value: {value}")))
+}
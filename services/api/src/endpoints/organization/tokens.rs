@@ -0,0 +1,211 @@
+use bencher_json::{
+    JsonRevokeToken, JsonRevokedTokens, JsonTokenPolicy, JsonUpdateTokenPolicy, ResourceId,
+};
+use bencher_rbac::organization::Permission;
+use dropshot::{endpoint, HttpError, Path, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, Post, Put, ResponseOk},
+        Endpoint,
+    },
+    model::{
+        organization::{
+            token_policy::{QueryTokenPolicy, UpsertTokenPolicy},
+            QueryOrganization,
+        },
+        user::{
+            auth::{AuthUser, BearerToken},
+            token::RevokeToken,
+        },
+    },
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct OrgTokensParams {
+    /// The slug or UUID for an organization.
+    pub organization: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/tokens/policy",
+    tags = ["organizations", "tokens"]
+}]
+pub async fn org_token_policy_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgTokensParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Put.into()]))
+}
+
+/// View the token policy for an organization
+///
+/// View the policy for API tokens created by members of an organization.
+/// The user must have `view` permissions for the organization.
+/// If the policy has never been set, then there are no restrictions.
+#[endpoint {
+    method = GET,
+    path =  "/v0/organizations/{organization}/tokens/policy",
+    tags = ["organizations", "tokens"]
+}]
+pub async fn org_token_policy_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<OrgTokensParams>,
+) -> Result<Encoded<ResponseOk<JsonTokenPolicy>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: OrgTokensParams,
+    auth_user: &AuthUser,
+) -> Result<JsonTokenPolicy, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::View,
+    )?;
+
+    let policy =
+        QueryTokenPolicy::get_for_organization(conn_lock!(context), query_organization.id)?;
+    Ok(QueryTokenPolicy::into_json(
+        policy.as_ref(),
+        &query_organization,
+    ))
+}
+
+/// Set the token policy for an organization
+///
+/// Set the policy for API tokens created by members of an organization.
+/// The user must have `manage` permissions for the organization.
+/// The policy is replaced as a whole, so any values not provided are cleared.
+/// The policy only applies to new tokens.
+/// If a user is a member of multiple organizations,
+/// then their new tokens must satisfy the policies for all of them.
+/// To invalidate existing tokens, revoke them.
+#[endpoint {
+    method = PUT,
+    path =  "/v0/organizations/{organization}/tokens/policy",
+    tags = ["organizations", "tokens"]
+}]
+pub async fn org_token_policy_put(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgTokensParams>,
+    body: TypedBody<JsonUpdateTokenPolicy>,
+) -> Result<ResponseOk<JsonTokenPolicy>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = put_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Put::auth_response_ok(json))
+}
+
+async fn put_inner(
+    context: &ApiContext,
+    path_params: OrgTokensParams,
+    json_policy: JsonUpdateTokenPolicy,
+    auth_user: &AuthUser,
+) -> Result<JsonTokenPolicy, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    UpsertTokenPolicy::new(&query_organization, &json_policy).execute(conn_lock!(context))?;
+
+    let policy =
+        QueryTokenPolicy::get_for_organization(conn_lock!(context), query_organization.id)?;
+    Ok(QueryTokenPolicy::into_json(
+        policy.as_ref(),
+        &query_organization,
+    ))
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/organizations/{organization}/tokens/revoke",
+    tags = ["organizations", "tokens"]
+}]
+pub async fn org_tokens_revoke_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<OrgTokensParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Revoke all tokens for an organization
+///
+/// Revoke the API tokens for all members of an organization.
+/// The user must have `manage` permissions for the organization.
+/// This includes the tokens of the authenticated user.
+/// The tokens are only revoked for this organization, so they can still be used for any other organizations that the members belong to.
+/// An optional grace period can be given, so the tokens can be rotated without breaking any running jobs.
+/// Tokens that are already set to be revoked sooner keep that time.
+#[endpoint {
+    method = POST,
+    path =  "/v0/organizations/{organization}/tokens/revoke",
+    tags = ["organizations", "tokens"]
+}]
+pub async fn org_tokens_revoke_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<OrgTokensParams>,
+    body: TypedBody<JsonRevokeToken>,
+) -> Result<ResponseOk<JsonRevokedTokens>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = revoke_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_ok(json))
+}
+
+async fn revoke_inner(
+    context: &ApiContext,
+    path_params: OrgTokensParams,
+    json_revoke: JsonRevokeToken,
+    auth_user: &AuthUser,
+) -> Result<JsonRevokedTokens, HttpError> {
+    let query_organization = QueryOrganization::is_allowed_resource_id(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.organization,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let revoked = RevokeToken::revoked(&json_revoke);
+    let tokens =
+        RevokeToken::revoke_for_organization(conn_lock!(context), query_organization.id, revoked)?;
+
+    Ok(JsonRevokedTokens {
+        organization: query_organization.uuid,
+        tokens: u32::try_from(tokens).unwrap_or(u32::MAX),
+        revoked,
+    })
+}
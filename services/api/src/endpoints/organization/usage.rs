@@ -17,6 +17,7 @@ use crate::{
     conn_lock,
     context::{ApiContext, DbConnection},
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, ResponseOk},
         Endpoint,
     },
@@ -64,11 +65,12 @@ pub async fn org_usage_options(
 pub async fn org_usage_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<OrgUsageParams>,
-) -> Result<ResponseOk<JsonUsage>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonUsage>>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 #[allow(clippy::too_many_lines)]
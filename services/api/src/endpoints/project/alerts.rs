@@ -1,24 +1,43 @@
+use std::collections::BTreeMap;
+
 use bencher_json::{
-    project::alert::{AlertStatus, JsonUpdateAlert},
-    AlertUuid, JsonAlert, JsonAlerts, JsonDirection, JsonPagination, ResourceId,
+    project::alert::{
+        AlertSeverity, AlertStatsWindow, AlertStatus, JsonAlertStatsBucket, JsonAlertStatsCount,
+        JsonAlertStatsQuery, JsonUpdateAlert, DEFAULT_ALERT_STATS_WINDOW_DAYS,
+    },
+    AlertUuid, BenchmarkUuid, BranchName, BranchUuid, DateTime, HeadUuid, JsonAlert,
+    JsonAlertStats, JsonAlertUpdate, JsonAlerts, JsonBulkUpdateAlerts, JsonDirection,
+    JsonPagination, JsonPerfQuery, MeasureUuid, ResourceId, ResourceName, TestbedUuid,
 };
+use bencher_plot::AlertPlot;
 use bencher_rbac::project::Permission;
-use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+use chrono::Days;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl, SelectableHelper,
+};
 use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use http::{Response, StatusCode};
+use hyper::Body;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use slog::Logger;
 
 use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, Patch, ResponseOk},
+        version::AcceptVersion,
         Endpoint,
     },
-    error::{resource_conflict_err, resource_not_found_err},
+    error::{issue_error, not_found_error, resource_conflict_err, resource_not_found_err},
     model::{
         project::{
-            threshold::alert::{QueryAlert, UpdateAlert},
+            branch::QueryBranch,
+            measure::QueryMeasure,
+            testbed::QueryTestbed,
+            threshold::alert::{AlertId, InsertAlertUpdate, QueryAlert, UpdateAlert},
             QueryProject,
         },
         user::auth::{AuthUser, BearerToken, PubBearerToken},
@@ -27,6 +46,8 @@ use crate::{
     util::headers::TotalCount,
 };
 
+use super::perf::ProjPerfParams;
+
 #[derive(Deserialize, JsonSchema)]
 pub struct ProjAlertsParams {
     /// The slug or UUID for a project.
@@ -50,6 +71,9 @@ pub struct ProjAlertsQuery {
     /// Filter alerts by their status.
     /// If not set, returns all alerts.
     pub status: Option<AlertStatus>,
+    /// Filter alerts by their severity.
+    /// If not set, returns alerts of all severities.
+    pub severity: Option<AlertSeverity>,
     /// If set to `true`, only returns archived alerts.
     /// If not set or set to `false`, only returns alerts with non-archived branches, testbeds, or measures.
     pub archived: Option<bool>,
@@ -67,7 +91,7 @@ pub async fn proj_alerts_options(
     _pagination_params: Query<ProjAlertsPagination>,
     _query_params: Query<ProjAlertsQuery>,
 ) -> Result<CorsResponse, HttpError> {
-    Ok(Endpoint::cors(&[Get.into()]))
+    Ok(Endpoint::cors(&[Get.into(), Patch.into()]))
 }
 
 /// List alerts for a project
@@ -87,7 +111,9 @@ pub async fn proj_alerts_get(
     path_params: Path<ProjAlertsParams>,
     pagination_params: Query<ProjAlertsPagination>,
     query_params: Query<ProjAlertsQuery>,
-) -> Result<ResponseOk<JsonAlerts>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonAlerts>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let accept_version = AcceptVersion::new(&rqctx).await?;
     let auth_user = AuthUser::new_pub(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -97,11 +123,13 @@ pub async fn proj_alerts_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            accept_version.json(json),
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -181,6 +209,10 @@ fn get_ls_query<'q>(
         query = query.filter(schema::alert::status.eq(status));
     }
 
+    if let Some(severity) = query_params.severity {
+        query = query.filter(schema::alert::severity.eq(severity));
+    }
+
     if let Some(true) = query_params.archived {
         query = query.filter(
             schema::branch::archived
@@ -283,6 +315,332 @@ type BoxedQuery<'q> = diesel::internal::table_macro::BoxedSelectStatement<
     diesel::sqlite::Sqlite,
 >;
 
+// Keep the number of bound parameters for each update well under the SQLite limit
+const BULK_UPDATE_CHUNK_SIZE: usize = 1_000;
+
+/// Update alerts for a project in bulk
+///
+/// Update all of the alerts for a project that match the given filters.
+/// Use this endpoint to dismiss or silence the alerts caused by a known infrastructure incident.
+/// If the new status is `active`, then dismissed alerts are matched.
+/// Otherwise, active alerts are matched.
+/// Alerts for archived branches, testbeds, or measures are not matched.
+/// A justification is required, and it is stored along with the user and the number of alerts updated for auditing.
+/// The user must have `edit` permissions for the project.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/projects/{project}/alerts",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_alerts_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjAlertsParams>,
+    body: TypedBody<JsonBulkUpdateAlerts>,
+) -> Result<ResponseOk<JsonAlertUpdate>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = patch_ls_inner(
+        &rqctx.log,
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+#[allow(clippy::too_many_lines)]
+async fn patch_ls_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: ProjAlertsParams,
+    json_update: JsonBulkUpdateAlerts,
+    auth_user: &AuthUser,
+) -> Result<JsonAlertUpdate, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+    let project_id = query_project.id;
+
+    let JsonBulkUpdateAlerts {
+        status,
+        reason,
+        branch,
+        testbed,
+        measure,
+        severity,
+        before,
+        after,
+    } = json_update;
+    let status = AlertStatus::from(status);
+    let match_status = if let AlertStatus::Active = status {
+        AlertStatus::Dismissed
+    } else {
+        AlertStatus::Active
+    };
+
+    let mut query = schema::alert::table
+        .inner_join(
+            schema::boundary::table
+                .inner_join(
+                    schema::threshold::table
+                        .inner_join(schema::branch::table)
+                        .inner_join(schema::testbed::table)
+                        .inner_join(schema::measure::table),
+                )
+                .inner_join(
+                    schema::metric::table.inner_join(
+                        schema::report_benchmark::table
+                            .inner_join(schema::report::table)
+                            .inner_join(schema::benchmark::table),
+                    ),
+                ),
+        )
+        .filter(schema::benchmark::project_id.eq(project_id))
+        .filter(schema::alert::status.eq(match_status))
+        .filter(
+            schema::branch::archived
+                .is_null()
+                .and(schema::testbed::archived.is_null())
+                .and(schema::measure::archived.is_null()),
+        )
+        .into_boxed();
+    if let Some(branch) = &branch {
+        let query_branch = QueryBranch::from_name_id(conn_lock!(context), project_id, branch)?;
+        query = query.filter(schema::threshold::branch_id.eq(query_branch.id));
+    }
+    if let Some(testbed) = &testbed {
+        let query_testbed = QueryTestbed::from_name_id(conn_lock!(context), project_id, testbed)?;
+        query = query.filter(schema::threshold::testbed_id.eq(query_testbed.id));
+    }
+    if let Some(measure) = &measure {
+        let query_measure = QueryMeasure::from_name_id(conn_lock!(context), project_id, measure)?;
+        query = query.filter(schema::threshold::measure_id.eq(query_measure.id));
+    }
+    if let Some(severity) = severity {
+        query = query.filter(schema::alert::severity.eq(severity));
+    }
+    if let Some(before) = before {
+        query = query.filter(schema::report::start_time.lt(before));
+    }
+    if let Some(after) = after {
+        query = query.filter(schema::report::start_time.gt(after));
+    }
+    let alert_ids = query
+        .select(schema::alert::id)
+        .load::<AlertId>(conn_lock!(context))
+        .map_err(resource_not_found_err!(Alert, &query_project))?;
+
+    let update_alert = UpdateAlert {
+        status: Some(status),
+        modified: DateTime::now(),
+    };
+    for alert_ids in alert_ids.chunks(BULK_UPDATE_CHUNK_SIZE) {
+        diesel::update(schema::alert::table.filter(schema::alert::id.eq_any(alert_ids)))
+            .set(&update_alert)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(
+                Alert,
+                (&query_project, &update_alert)
+            ))?;
+    }
+
+    let insert_alert_update = InsertAlertUpdate::new(
+        project_id,
+        auth_user.user.id,
+        status,
+        reason,
+        alert_ids.len().try_into().unwrap_or(i32::MAX),
+    );
+    diesel::insert_into(schema::alert_update::table)
+        .values(&insert_alert_update)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Alert, insert_alert_update))?;
+    slog::info!(
+        log,
+        "User ({}) updated {} alert(s) to {} for project ({}): {}",
+        auth_user.user.uuid,
+        insert_alert_update.alerts,
+        insert_alert_update.status,
+        query_project.uuid,
+        insert_alert_update.reason
+    );
+
+    Ok(insert_alert_update.into_json(&query_project, &auth_user.user))
+}
+
+// The alert statistics can not live at `/v0/projects/{project}/alerts/stats`,
+// because the `dropshot` router does not allow a literal path segment (`stats`)
+// next to a variable path segment (`{alert}`), and it panics when the API is registered.
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/stats/alerts",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_alert_stats_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjAlertsParams>,
+    _query_params: Query<JsonAlertStatsQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// View alert statistics for a project
+///
+/// View the number of alerts for a project over a time window, ending now.
+/// The alerts are counted by branch, testbed, measure, and severity in time buckets,
+/// based on the start time of the report that generated each alert.
+/// The time buckets use the project time zone and week start.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/stats/alerts",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_alert_stats_get(
+    rqctx: RequestContext<ApiContext>,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjAlertsParams>,
+    query_params: Query<JsonAlertStatsQuery>,
+) -> Result<Encoded<ResponseOk<JsonAlertStats>>, HttpError> {
+    let auth_user = AuthUser::new_pub(&rqctx).await?;
+    let json = get_stats_inner(
+        rqctx.context(),
+        auth_user.as_ref(),
+        path_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+#[allow(clippy::too_many_lines)]
+async fn get_stats_inner(
+    context: &ApiContext,
+    auth_user: Option<&AuthUser>,
+    path_params: ProjAlertsParams,
+    query_params: JsonAlertStatsQuery,
+) -> Result<JsonAlertStats, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let window_days = query_params
+        .window
+        .as_ref()
+        .map_or(DEFAULT_ALERT_STATS_WINDOW_DAYS, AlertStatsWindow::days);
+    let bucket = query_params.bucket.unwrap_or_default();
+    let end_time = DateTime::now();
+    let start_time = end_time
+        .into_inner()
+        .checked_sub_days(Days::new(window_days.into()))
+        .map_or(end_time, DateTime::from);
+
+    let alerts =
+        schema::alert::table
+            .inner_join(
+                schema::boundary::table
+                    .inner_join(
+                        schema::threshold::table
+                            .inner_join(schema::branch::table)
+                            .inner_join(schema::testbed::table)
+                            .inner_join(schema::measure::table),
+                    )
+                    .inner_join(schema::metric::table.inner_join(
+                        schema::report_benchmark::table.inner_join(schema::report::table),
+                    )),
+            )
+            .filter(schema::threshold::project_id.eq(query_project.id))
+            .filter(schema::report::start_time.ge(start_time))
+            .filter(schema::report::start_time.le(end_time))
+            .select((
+                schema::report::start_time,
+                schema::branch::name,
+                schema::testbed::name,
+                schema::measure::name,
+                schema::alert::severity,
+            ))
+            .load::<(
+                DateTime,
+                BranchName,
+                ResourceName,
+                ResourceName,
+                AlertSeverity,
+            )>(conn_lock!(context))
+            .map_err(resource_not_found_err!(
+                Alert,
+                (&query_project, start_time, end_time)
+            ))?;
+
+    let bucket_starts = bucket.starts(
+        start_time,
+        end_time,
+        &query_project.time_zone,
+        query_project.week_start,
+    );
+    let mut counts = BTreeMap::new();
+    for (report_start_time, branch, testbed, measure, severity) in alerts {
+        let alert_bucket_start = bucket
+            .start(
+                report_start_time,
+                &query_project.time_zone,
+                query_project.week_start,
+            )
+            .timestamp();
+        // The buckets are in chronological order, so find the last bucket that starts at or before the alert
+        let Some(index) = bucket_starts
+            .partition_point(|bucket_start| bucket_start.timestamp() <= alert_bucket_start)
+            .checked_sub(1)
+        else {
+            continue;
+        };
+        *counts
+            .entry((index, branch, testbed, measure, severity))
+            .or_insert(0u32) += 1;
+    }
+
+    let mut buckets = bucket_starts
+        .into_iter()
+        .map(|start_time| JsonAlertStatsBucket {
+            start_time,
+            total: 0,
+            counts: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    for ((index, branch, testbed, measure, severity), count) in counts {
+        if let Some(json_bucket) = buckets.get_mut(index) {
+            json_bucket.total = json_bucket.total.saturating_add(count);
+            json_bucket.counts.push(JsonAlertStatsCount {
+                branch,
+                testbed,
+                measure,
+                severity,
+                count,
+            });
+        }
+    }
+
+    Ok(JsonAlertStats {
+        start_time,
+        end_time,
+        bucket,
+        buckets,
+    })
+}
+
 #[derive(Deserialize, JsonSchema)]
 pub struct ProjAlertParams {
     /// The slug or UUID for a project.
@@ -317,8 +675,10 @@ pub async fn proj_alert_options(
 pub async fn proj_alert_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
+    accept_version: AcceptVersion,
     path_params: Path<ProjAlertParams>,
-) -> Result<ResponseOk<JsonAlert>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonAlert>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -326,7 +686,12 @@ pub async fn proj_alert_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    accept_encoding
+        .encode(Get::response_ok(
+            accept_version.json(json),
+            auth_user.is_some(),
+        ))
+        .await
 }
 
 async fn get_one_inner(
@@ -362,6 +727,7 @@ pub async fn proj_alert_patch(
     path_params: Path<ProjAlertParams>,
     body: TypedBody<JsonUpdateAlert>,
 ) -> Result<ResponseOk<JsonAlert>, HttpError> {
+    let accept_version = AcceptVersion::new(&rqctx).await?;
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = patch_inner(
         rqctx.context(),
@@ -370,7 +736,7 @@ pub async fn proj_alert_patch(
         &auth_user,
     )
     .await?;
-    Ok(Patch::auth_response_ok(json))
+    Ok(Patch::auth_response_ok(accept_version.json(json)))
 }
 
 async fn patch_inner(
@@ -401,3 +767,198 @@ async fn patch_inner(
     // Separate out this query to prevent a deadlock when getting the conn_lock
     alert.into_json(context).await
 }
+
+// Cache public alert images for a short time, so notification embeds stay reasonably live
+const PUBLIC_CACHE_CONTROL: &str = "public, max-age=300";
+const PRIVATE_CACHE_CONTROL: &str = "private, max-age=0, no-cache";
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProjAlertImgQuery {
+    /// The image format.
+    /// If not set, a PNG image is returned.
+    pub format: Option<ProjAlertImgFormat>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjAlertImgFormat {
+    /// PNG image
+    #[default]
+    Png,
+    /// SVG image
+    Svg,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/alerts/{alert}/img",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_alert_img_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjAlertParams>,
+    _query_params: Query<ProjAlertImgQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// Generate an image of an alert
+///
+/// Generate a small plot of the metric history for an alert.
+/// The boundary limits are shaded and the metric that generated the alert is highlighted.
+/// This image is meant to be embedded in alert notifications.
+/// The image is a PNG, unless the `format` is set to `svg`.
+/// If the project is public, then the user does not need to be authenticated
+/// and the image may be cached for up to five minutes.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/alerts/{alert}/img",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_alert_img_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    path_params: Path<ProjAlertParams>,
+    query_params: Query<ProjAlertImgQuery>,
+) -> Result<Response<Body>, HttpError> {
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let format = query_params.into_inner().format.unwrap_or_default();
+    let (img, is_public) = get_img_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        format,
+        auth_user.as_ref(),
+    )
+    .await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            http::header::CONTENT_TYPE,
+            match format {
+                ProjAlertImgFormat::Png => "image/png",
+                ProjAlertImgFormat::Svg => "image/svg+xml",
+            },
+        )
+        .header(
+            http::header::CACHE_CONTROL,
+            if is_public {
+                PUBLIC_CACHE_CONTROL
+            } else {
+                PRIVATE_CACHE_CONTROL
+            },
+        )
+        .body(img.into())
+        .map_err(Into::into)
+}
+
+async fn get_img_inner(
+    context: &ApiContext,
+    path_params: ProjAlertParams,
+    format: ProjAlertImgFormat,
+    auth_user: Option<&AuthUser>,
+) -> Result<(Vec<u8>, bool), HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let query_alert =
+        QueryAlert::from_uuid(conn_lock!(context), query_project.id, path_params.alert)?;
+    let (branch_uuid, head_uuid, testbed_uuid, benchmark_uuid, measure_uuid, end_time) =
+        schema::alert::table
+            .filter(schema::alert::id.eq(query_alert.id))
+            .inner_join(
+                schema::boundary::table.inner_join(
+                    schema::metric::table
+                        .inner_join(schema::measure::table)
+                        .inner_join(
+                            schema::report_benchmark::table
+                                .inner_join(
+                                    schema::report::table
+                                        .inner_join(
+                                            schema::head::table.inner_join(
+                                                schema::branch::table
+                                                    .on(schema::head::branch_id
+                                                        .eq(schema::branch::id)),
+                                            ),
+                                        )
+                                        .inner_join(schema::testbed::table),
+                                )
+                                .inner_join(schema::benchmark::table),
+                        ),
+                ),
+            )
+            .select((
+                schema::branch::uuid,
+                schema::head::uuid,
+                schema::testbed::uuid,
+                schema::benchmark::uuid,
+                schema::measure::uuid,
+                schema::report::end_time,
+            ))
+            .first::<(
+                BranchUuid,
+                HeadUuid,
+                TestbedUuid,
+                BenchmarkUuid,
+                MeasureUuid,
+                DateTime,
+            )>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Alert, query_alert))?;
+
+    // The metric history for the alert is all of the metrics up to and including its report
+    let json_perf_query = JsonPerfQuery {
+        branches: vec![branch_uuid],
+        heads: vec![Some(head_uuid)],
+        testbeds: vec![testbed_uuid],
+        benchmarks: vec![benchmark_uuid],
+        measures: vec![measure_uuid],
+        start_time: None,
+        end_time: Some(end_time),
+        tags: Vec::new(),
+        unit: None,
+    };
+    let json_perf = super::perf::get_inner(
+        context,
+        ProjPerfParams {
+            project: path_params.project,
+        },
+        json_perf_query,
+        auth_user,
+    )
+    .await?;
+    let Some(json_perf_metrics) = json_perf.results.first() else {
+        return Err(not_found_error(format!(
+            "No metrics found for alert ({alert})",
+            alert = path_params.alert
+        )));
+    };
+
+    let title = format!(
+        "{benchmark}: {measure}",
+        benchmark = json_perf_metrics.benchmark.name,
+        measure = json_perf_metrics.measure.name
+    );
+    let alert_plot = AlertPlot::new();
+    let img = match format {
+        ProjAlertImgFormat::Png => alert_plot.png(&title, json_perf_metrics, path_params.alert),
+        ProjAlertImgFormat::Svg => alert_plot
+            .svg(&title, json_perf_metrics, path_params.alert)
+            .map(String::into_bytes),
+    }
+    .map_err(|e| {
+        issue_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to draw alert plot",
+            &format!("Failed draw alert plot: {json_perf_metrics:?}"),
+            e,
+        )
+    })?;
+
+    Ok((img, query_project.is_public()))
+}
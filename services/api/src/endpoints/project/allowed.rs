@@ -7,6 +7,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, ResponseOk},
         Endpoint,
     },
@@ -45,11 +46,12 @@ pub async fn proj_allowed_options(
 pub async fn proj_allowed_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<ProjAllowedParams>,
-) -> Result<ResponseOk<JsonAllowed>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonAllowed>>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_inner(
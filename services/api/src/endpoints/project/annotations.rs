@@ -0,0 +1,421 @@
+use bencher_json::{
+    project::annotation::JsonUpdateAnnotation, AnnotationUuid, DateTime, DateTimeMillis,
+    JsonAnnotation, JsonAnnotations, JsonDirection, JsonNewAnnotation, JsonPagination, ResourceId,
+};
+use bencher_rbac::project::Permission;
+use diesel::{
+    BelongingToDsl, BoolExpressionMethods, ExpressionMethods, NullableExpressionMethods, QueryDsl,
+    RunQueryDsl, TextExpressionMethods,
+};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{
+            CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
+        },
+        Endpoint,
+    },
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            annotation::{InsertAnnotation, QueryAnnotation, UpdateAnnotation},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken, PubBearerToken},
+    },
+    schema,
+    util::{headers::TotalCount, search::Search},
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjAnnotationsParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+pub type ProjAnnotationsPagination = JsonPagination<ProjAnnotationsSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjAnnotationsSort {
+    /// Sort by annotation time.
+    #[default]
+    Time,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProjAnnotationsQuery {
+    /// Search by annotation title, description, or UUID.
+    pub search: Option<Search>,
+    /// Filter for annotations at or after the given date time in milliseconds.
+    pub start_time: Option<DateTimeMillis>,
+    /// Filter for annotations at or before the given date time in milliseconds.
+    pub end_time: Option<DateTimeMillis>,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/annotations",
+    tags = ["projects", "annotations"]
+}]
+pub async fn proj_annotations_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjAnnotationsParams>,
+    _pagination_params: Query<ProjAnnotationsPagination>,
+    _query_params: Query<ProjAnnotationsQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List annotations for a project
+///
+/// List all annotations for a project.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+/// By default, the annotations are sorted by time in reverse chronological order.
+/// The HTTP response header `X-Total-Count` contains the total number of annotations.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/annotations",
+    tags = ["projects", "annotations"]
+}]
+pub async fn proj_annotations_get(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjAnnotationsParams>,
+    pagination_params: Query<ProjAnnotationsPagination>,
+    query_params: Query<ProjAnnotationsQuery>,
+) -> Result<Encoded<ResponseOk<JsonAnnotations>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::new_pub(&rqctx).await?;
+    let (json, total_count) = get_ls_inner(
+        rqctx.context(),
+        auth_user.as_ref(),
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: Option<&AuthUser>,
+    path_params: ProjAnnotationsParams,
+    pagination_params: ProjAnnotationsPagination,
+    query_params: ProjAnnotationsQuery,
+) -> Result<(JsonAnnotations, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let annotations = get_ls_query(&query_project, &pagination_params, &query_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryAnnotation>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Annotation,
+            (&query_project, &pagination_params, &query_params)
+        ))?;
+
+    // Drop connection lock before iterating
+    let json_annotations = annotations
+        .into_iter()
+        .map(|annotation| annotation.into_json_for_project(&query_project))
+        .collect();
+
+    let total_count = get_ls_query(&query_project, &pagination_params, &query_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Annotation,
+            (&query_project, &pagination_params, &query_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_annotations, total_count))
+}
+
+fn get_ls_query<'q>(
+    query_project: &'q QueryProject,
+    pagination_params: &ProjAnnotationsPagination,
+    query_params: &'q ProjAnnotationsQuery,
+) -> schema::annotation::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let mut query = QueryAnnotation::belonging_to(query_project).into_boxed();
+
+    if let Some(search) = query_params.search.as_ref() {
+        query = query.filter(
+            schema::annotation::title
+                .nullable()
+                .like(search)
+                .or(schema::annotation::description.like(search))
+                .or(schema::annotation::uuid.like(search)),
+        );
+    }
+
+    if let Some(start_time) = query_params.start_time {
+        query = query.filter(schema::annotation::time.ge(DateTime::from(start_time)));
+    }
+    if let Some(end_time) = query_params.end_time {
+        query = query.filter(schema::annotation::time.le(DateTime::from(end_time)));
+    }
+
+    match pagination_params.order() {
+        ProjAnnotationsSort::Time => match pagination_params.direction {
+            Some(JsonDirection::Asc) => query.order((
+                schema::annotation::time.asc(),
+                schema::annotation::created.asc(),
+            )),
+            Some(JsonDirection::Desc) | None => query.order((
+                schema::annotation::time.desc(),
+                schema::annotation::created.desc(),
+            )),
+        },
+    }
+}
+
+/// Create an annotation
+///
+/// Create an annotation for a project.
+/// Annotations mark events, such as infrastructure or toolchain changes,
+/// that explain step changes in performance that are not code regressions.
+/// The user must have `create` permissions for the project.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/annotations",
+    tags = ["projects", "annotations"]
+}]
+pub async fn proj_annotation_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjAnnotationsParams>,
+    body: TypedBody<JsonNewAnnotation>,
+) -> Result<ResponseCreated<JsonAnnotation>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjAnnotationsParams,
+    json_annotation: JsonNewAnnotation,
+    auth_user: &AuthUser,
+) -> Result<JsonAnnotation, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Create,
+    )?;
+
+    let insert_annotation = InsertAnnotation::from_json(query_project.id, json_annotation);
+
+    diesel::insert_into(schema::annotation::table)
+        .values(&insert_annotation)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Annotation, insert_annotation))?;
+
+    QueryAnnotation::get_with_uuid(conn_lock!(context), &query_project, insert_annotation.uuid)
+        .map(|annotation| annotation.into_json_for_project(&query_project))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjAnnotationParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The UUID for an annotation.
+    pub annotation: AnnotationUuid,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/annotations/{annotation}",
+    tags = ["projects", "annotations"]
+}]
+pub async fn proj_annotation_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjAnnotationParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Patch.into(), Delete.into()]))
+}
+
+/// View an annotation
+///
+/// View an annotation for a project.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/annotations/{annotation}",
+    tags = ["projects", "annotations"]
+}]
+pub async fn proj_annotation_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjAnnotationParams>,
+) -> Result<Encoded<ResponseOk<JsonAnnotation>>, HttpError> {
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjAnnotationParams,
+    auth_user: Option<&AuthUser>,
+) -> Result<JsonAnnotation, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    QueryAnnotation::get_with_uuid(conn_lock!(context), &query_project, path_params.annotation)
+        .map(|annotation| annotation.into_json_for_project(&query_project))
+}
+
+/// Update an annotation
+///
+/// Update an annotation for a project.
+/// The user must have `edit` permissions for the project.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/projects/{project}/annotations/{annotation}",
+    tags = ["projects", "annotations"]
+}]
+pub async fn proj_annotation_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjAnnotationParams>,
+    body: TypedBody<JsonUpdateAnnotation>,
+) -> Result<ResponseOk<JsonAnnotation>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let context = rqctx.context();
+    let json = patch_inner(
+        context,
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+async fn patch_inner(
+    context: &ApiContext,
+    path_params: ProjAnnotationParams,
+    json_annotation: JsonUpdateAnnotation,
+    auth_user: &AuthUser,
+) -> Result<JsonAnnotation, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+
+    let query_annotation = QueryAnnotation::get_with_uuid(
+        conn_lock!(context),
+        &query_project,
+        path_params.annotation,
+    )?;
+    let update_annotation = UpdateAnnotation::from(json_annotation.clone());
+    diesel::update(
+        schema::annotation::table.filter(schema::annotation::id.eq(query_annotation.id)),
+    )
+    .set(&update_annotation)
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(
+        Annotation,
+        (&query_annotation, &json_annotation)
+    ))?;
+
+    QueryAnnotation::get(conn_lock!(context), query_annotation.id)
+        .map(|annotation| annotation.into_json_for_project(&query_project))
+        .map_err(resource_not_found_err!(Annotation, query_annotation))
+}
+
+/// Delete an annotation
+///
+/// Delete an annotation for a project.
+/// The user must have `delete` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/annotations/{annotation}",
+    tags = ["projects", "annotations"]
+}]
+pub async fn proj_annotation_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjAnnotationParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjAnnotationParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Delete,
+    )?;
+
+    let query_annotation = QueryAnnotation::get_with_uuid(
+        conn_lock!(context),
+        &query_project,
+        path_params.annotation,
+    )?;
+
+    diesel::delete(
+        schema::annotation::table.filter(schema::annotation::id.eq(query_annotation.id)),
+    )
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(Annotation, query_annotation))?;
+
+    Ok(())
+}
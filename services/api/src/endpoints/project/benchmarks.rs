@@ -15,9 +15,11 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
             CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
         },
+        etag::{IfNoneMatch, Tagged},
         Endpoint,
     },
     error::{resource_conflict_err, resource_not_found_err},
@@ -53,10 +55,19 @@ pub struct ProjBenchmarksQuery {
     /// Filter by benchmark name, exact match.
     pub name: Option<BenchmarkName>,
     /// Search by benchmark name, slug, or UUID.
+    /// The search is a case-insensitive substring match.
     pub search: Option<Search>,
+    /// If set to `true`, the search also matches benchmarks that contain the characters of the search in order.
+    /// The results are then ranked by how closely they match the search instead of being sorted.
+    /// Exact matches are ranked first, followed by prefix, substring, and then fuzzy matches.
+    pub fuzzy: Option<bool>,
     /// If set to `true`, only returns archived benchmarks.
     /// If not set or set to `false`, only returns non-archived benchmarks.
     pub archived: Option<bool>,
+    /// If set to `true`, only returns benchmarks that are quarantined or have a measure detected as flaky.
+    /// If set to `false`, only returns benchmarks that are neither.
+    /// If not set, returns both flaky and non-flaky benchmarks.
+    pub flaky: Option<bool>,
 }
 
 #[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
@@ -91,7 +102,8 @@ pub async fn proj_benchmarks_get(
     path_params: Path<ProjBenchmarksParams>,
     pagination_params: Query<ProjBenchmarksPagination>,
     query_params: Query<ProjBenchmarksQuery>,
-) -> Result<ResponseOk<JsonBenchmarks>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonBenchmarks>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new_pub(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -101,11 +113,13 @@ pub async fn proj_benchmarks_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -122,6 +136,17 @@ async fn get_ls_inner(
         auth_user,
     )?;
 
+    if let (Some(search), Some(true)) = (query_params.search.as_ref(), query_params.fuzzy) {
+        return get_ls_fuzzy(
+            context,
+            &query_project,
+            &pagination_params,
+            &query_params,
+            search,
+        )
+        .await;
+    }
+
     let benchmarks = get_ls_query(&query_project, &pagination_params, &query_params)
         .offset(pagination_params.offset())
         .limit(pagination_params.limit())
@@ -149,16 +174,65 @@ async fn get_ls_inner(
     Ok((json_benchmarks, total_count))
 }
 
+// Fuzzy search results are ranked in memory, so all of the matching benchmarks are loaded before paginating
+async fn get_ls_fuzzy(
+    context: &ApiContext,
+    query_project: &QueryProject,
+    pagination_params: &ProjBenchmarksPagination,
+    query_params: &ProjBenchmarksQuery,
+    search: &Search,
+) -> Result<(JsonBenchmarks, TotalCount), HttpError> {
+    let fuzzy = search.fuzzy();
+    let benchmarks = get_ls_filter(query_project, query_params)
+        .filter(
+            schema::benchmark::name
+                .like(&fuzzy)
+                .or(schema::benchmark::slug.like(&fuzzy))
+                .or(schema::benchmark::uuid.like(&fuzzy)),
+        )
+        .order(schema::benchmark::name.asc())
+        .load::<QueryBenchmark>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Benchmark,
+            (query_project, pagination_params, query_params)
+        ))?;
+
+    let mut ranked_benchmarks = benchmarks
+        .into_iter()
+        .filter_map(|benchmark| {
+            let uuid = benchmark.uuid.to_string();
+            search
+                .rank([
+                    benchmark.name.as_ref(),
+                    benchmark.slug.as_ref(),
+                    uuid.as_str(),
+                ])
+                .map(|rank| (rank, benchmark))
+        })
+        .collect::<Vec<_>>();
+    // The sort is stable, so benchmarks with the same rank stay in alphabetical order
+    ranked_benchmarks.sort_by_key(|(rank, _)| *rank);
+
+    let total_count = i64::try_from(ranked_benchmarks.len())
+        .unwrap_or(i64::MAX)
+        .try_into()?;
+    let json_benchmarks = ranked_benchmarks
+        .into_iter()
+        .skip(usize::try_from(pagination_params.offset()).unwrap_or_default())
+        .take(usize::try_from(pagination_params.limit()).unwrap_or_default())
+        .map(|(_, benchmark)| benchmark.into_json_for_project(query_project))
+        .collect();
+
+    Ok((json_benchmarks, total_count))
+}
+
 fn get_ls_query<'q>(
     query_project: &'q QueryProject,
     pagination_params: &ProjBenchmarksPagination,
     query_params: &'q ProjBenchmarksQuery,
 ) -> schema::benchmark::BoxedQuery<'q, diesel::sqlite::Sqlite> {
-    let mut query = QueryBenchmark::belonging_to(&query_project).into_boxed();
+    let mut query = get_ls_filter(query_project, query_params);
 
-    if let Some(name) = query_params.name.as_ref() {
-        query = query.filter(schema::benchmark::name.eq(name));
-    }
     if let Some(search) = query_params.search.as_ref() {
         query = query.filter(
             schema::benchmark::name
@@ -168,18 +242,50 @@ fn get_ls_query<'q>(
         );
     }
 
+    match pagination_params.order() {
+        ProjBenchmarksSort::Name => match pagination_params.direction {
+            Some(JsonDirection::Asc) | None => query.order(schema::benchmark::name.asc()),
+            Some(JsonDirection::Desc) => query.order(schema::benchmark::name.desc()),
+        },
+    }
+}
+
+fn get_ls_filter<'q>(
+    query_project: &'q QueryProject,
+    query_params: &'q ProjBenchmarksQuery,
+) -> schema::benchmark::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let mut query = QueryBenchmark::belonging_to(&query_project).into_boxed();
+
+    if let Some(name) = query_params.name.as_ref() {
+        query = query.filter(schema::benchmark::name.eq(name));
+    }
+
     if let Some(true) = query_params.archived {
         query = query.filter(schema::benchmark::archived.is_not_null());
     } else {
         query = query.filter(schema::benchmark::archived.is_null());
     };
 
-    match pagination_params.order() {
-        ProjBenchmarksSort::Name => match pagination_params.direction {
-            Some(JsonDirection::Asc) | None => query.order(schema::benchmark::name.asc()),
-            Some(JsonDirection::Desc) => query.order(schema::benchmark::name.desc()),
+    // A benchmark is flaky if it is quarantined or if any of its measures were detected as flaky
+    let has_flaky_measure = diesel::dsl::exists(
+        schema::flaky_measure::table
+            .filter(schema::flaky_measure::benchmark_id.eq(schema::benchmark::id)),
+    );
+    match query_params.flaky {
+        Some(true) => {
+            query = query.filter(schema::benchmark::flaky.is_not_null().or(has_flaky_measure));
         },
+        Some(false) => {
+            query = query.filter(
+                schema::benchmark::flaky
+                    .is_null()
+                    .and(diesel::dsl::not(has_flaky_measure)),
+            );
+        },
+        None => {},
     }
+
+    query
 }
 
 /// Create a benchmark
@@ -272,8 +378,10 @@ pub async fn proj_benchmark_options(
 pub async fn proj_benchmark_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<ProjBenchmarkParams>,
-) -> Result<ResponseOk<JsonBenchmark>, HttpError> {
+) -> Result<Encoded<Tagged<ResponseOk<JsonBenchmark>>>, HttpError> {
+    let if_none_match = IfNoneMatch::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -281,7 +389,10 @@ pub async fn proj_benchmark_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    let response = if_none_match
+        .tag(Get::response_ok(json, auth_user.is_some()))
+        .await?;
+    accept_encoding.encode(response).await
 }
 
 async fn get_one_inner(
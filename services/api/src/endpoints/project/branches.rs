@@ -1,6 +1,11 @@
 use bencher_json::{
-    project::branch::JsonUpdateBranch, BranchName, HeadUuid, JsonBranch, JsonBranches,
-    JsonDirection, JsonNewBranch, JsonPagination, ResourceId,
+    project::{
+        branch::{JsonBranchVersion, JsonNewBranchTag, JsonUpdateBranch, MAX_BRANCH_TAGS},
+        head::VersionNumber,
+    },
+    BranchName, DateTime, GitHash, HeadUuid, JsonBranch, JsonBranchLineage, JsonBranchTags,
+    JsonBranchVersions, JsonBranches, JsonDirection, JsonNewBranch, JsonNewBranchTags,
+    JsonPagination, ResourceId,
 };
 use bencher_rbac::project::Permission;
 use diesel::{
@@ -14,19 +19,28 @@ use slog::Logger;
 
 use crate::{
     conn_lock,
-    context::ApiContext,
+    context::{ApiContext, DbConnection},
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
             CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
         },
+        etag::{IfNoneMatch, Tagged},
         Endpoint,
     },
     error::{
-        resource_conflict_err, resource_not_found_err, resource_not_found_error, BencherResource,
+        bad_request_error, resource_conflict_err, resource_not_found_err, resource_not_found_error,
+        BencherResource,
     },
     model::{
         project::{
-            branch::{head::QueryHead, InsertBranch, QueryBranch, UpdateBranch},
+            annotation::{InsertAnnotation, QueryAnnotation},
+            branch::{
+                head::{HeadId, QueryHead},
+                lineage::branch_lineage,
+                version::{QueryVersion, VersionId},
+                InsertBranch, QueryBranch, UpdateBranch,
+            },
             QueryProject,
         },
         user::auth::{AuthUser, BearerToken, PubBearerToken},
@@ -94,7 +108,8 @@ pub async fn proj_branches_get(
     path_params: Path<ProjBranchesParams>,
     pagination_params: Query<ProjBranchesPagination>,
     query_params: Query<ProjBranchesQuery>,
-) -> Result<ResponseOk<JsonBranches>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonBranches>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new_pub(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -104,11 +119,13 @@ pub async fn proj_branches_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -287,7 +304,9 @@ pub async fn proj_branch_get(
     bearer_token: PubBearerToken,
     path_params: Path<ProjBranchParams>,
     query_params: Query<ProjBranchQuery>,
-) -> Result<ResponseOk<JsonBranch>, HttpError> {
+) -> Result<Encoded<Tagged<ResponseOk<JsonBranch>>>, HttpError> {
+    let if_none_match = IfNoneMatch::new(&rqctx).await?;
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -296,7 +315,10 @@ pub async fn proj_branch_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    let response = if_none_match
+        .tag(Get::response_ok(json, auth_user.is_some()))
+        .await?;
+    accept_encoding.encode(response).await
 }
 
 async fn get_one_inner(
@@ -321,23 +343,428 @@ async fn get_one_inner(
         ))?;
 
     if let Some(head_uuid) = query_params.head {
-        let query_head = QueryHead::from_uuid(conn_lock!(context), query_project.id, head_uuid)?;
-        if query_head.branch_id != query_branch.id {
-            return Err(resource_not_found_error(
-                BencherResource::Head,
-                head_uuid,
-                format!(
-                    "Specified head {head_uuid} does not belong to branch {branch_uuid}",
-                    branch_uuid = query_branch.uuid
-                ),
-            ));
-        }
+        let query_head = branch_head_from_uuid(
+            conn_lock!(context),
+            &query_project,
+            &query_branch,
+            head_uuid,
+        )?;
         query_branch.into_json_for_head(conn_lock!(context), &query_project, &query_head, None)
     } else {
         query_branch.into_json_for_project(conn_lock!(context), &query_project)
     }
 }
 
+fn branch_head_from_uuid(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+    query_branch: &QueryBranch,
+    head_uuid: HeadUuid,
+) -> Result<QueryHead, HttpError> {
+    let query_head = QueryHead::from_uuid(conn, query_project.id, head_uuid)?;
+    if query_head.branch_id == query_branch.id {
+        Ok(query_head)
+    } else {
+        Err(resource_not_found_error(
+            BencherResource::Head,
+            head_uuid,
+            format!(
+                "Specified head {head_uuid} does not belong to branch {branch_uuid}",
+                branch_uuid = query_branch.uuid
+            ),
+        ))
+    }
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/branches/{branch}/lineage",
+    tags = ["projects", "branches"]
+}]
+pub async fn proj_branch_lineage_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjBranchParams>,
+    _query_params: Query<ProjBranchQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// View a branch lineage
+///
+/// View the lineage of a branch head for a project.
+/// The lineage starts with the branch head and follows its start point back to the head it was started from,
+/// and so on until a head without a start point is reached.
+/// For each head, the range of versions that were shallow copied from its start point
+/// and the range of versions that were created on the head itself are returned.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/branches/{branch}/lineage",
+    tags = ["projects", "branches"]
+}]
+pub async fn proj_branch_lineage_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    path_params: Path<ProjBranchParams>,
+    query_params: Query<ProjBranchQuery>,
+) -> Result<Encoded<ResponseOk<JsonBranchLineage>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let json = get_lineage_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        query_params.into_inner(),
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+async fn get_lineage_inner(
+    context: &ApiContext,
+    path_params: ProjBranchParams,
+    query_params: ProjBranchQuery,
+    auth_user: Option<&AuthUser>,
+) -> Result<JsonBranchLineage, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let query_branch =
+        QueryBranch::from_resource_id(conn_lock!(context), query_project.id, &path_params.branch)?;
+
+    let query_head = if let Some(head_uuid) = query_params.head {
+        branch_head_from_uuid(
+            conn_lock!(context),
+            &query_project,
+            &query_branch,
+            head_uuid,
+        )?
+    } else {
+        QueryHead::get(conn_lock!(context), query_branch.head_id()?)?
+    };
+
+    branch_lineage(conn_lock!(context), query_head)
+}
+
+pub type ProjBranchVersionsPagination = JsonPagination<ProjBranchVersionsSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjBranchVersionsSort {
+    /// Sort by version number.
+    #[default]
+    Number,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProjBranchVersionsQuery {
+    /// List the versions for the specified head UUID.
+    /// If not specified, then the current head is used.
+    pub head: Option<HeadUuid>,
+    /// If set to `true`, only returns versions that have been benchmarked on the branch head.
+    /// If set to `false`, only returns versions that have never been benchmarked on the branch head.
+    /// If not set, returns all versions.
+    pub benchmarked: Option<bool>,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/branches/{branch}/versions",
+    tags = ["projects", "branches"]
+}]
+pub async fn proj_branch_versions_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjBranchParams>,
+    _pagination_params: Query<ProjBranchVersionsPagination>,
+    _query_params: Query<ProjBranchVersionsQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// List versions for a branch
+///
+/// List the versions for a branch head, along with the number of reports for each version
+/// and the start times of the first and last of those reports.
+/// This includes versions recorded from git pushes by a GitHub webhook,
+/// so versions without any reports were never benchmarked on the branch head.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+/// By default, the versions are sorted by version number in descending order.
+/// The HTTP response header `X-Total-Count` contains the total number of versions.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/branches/{branch}/versions",
+    tags = ["projects", "branches"]
+}]
+pub async fn proj_branch_versions_get(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjBranchParams>,
+    pagination_params: Query<ProjBranchVersionsPagination>,
+    query_params: Query<ProjBranchVersionsQuery>,
+) -> Result<Encoded<ResponseOk<JsonBranchVersions>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::new_pub(&rqctx).await?;
+    let (json, total_count) = get_versions_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+        query_params.into_inner(),
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
+}
+
+async fn get_versions_inner(
+    context: &ApiContext,
+    path_params: ProjBranchParams,
+    pagination_params: ProjBranchVersionsPagination,
+    query_params: ProjBranchVersionsQuery,
+    auth_user: Option<&AuthUser>,
+) -> Result<(JsonBranchVersions, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let query_branch =
+        QueryBranch::from_resource_id(conn_lock!(context), query_project.id, &path_params.branch)?;
+
+    let head_id = if let Some(head_uuid) = query_params.head {
+        branch_head_from_uuid(
+            conn_lock!(context),
+            &query_project,
+            &query_branch,
+            head_uuid,
+        )?
+        .id
+    } else {
+        query_branch.head_id()?
+    };
+
+    let versions = get_versions_query(head_id, &pagination_params, &query_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<(VersionId, VersionNumber, Option<GitHash>, Option<DateTime>)>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            HeadVersion,
+            (head_id, &pagination_params, &query_params)
+        ))?;
+
+    // Separate out these queries to prevent a deadlock when getting the conn_lock
+    let mut json_versions = Vec::with_capacity(versions.len());
+    for (version_id, number, hash, pushed) in versions {
+        let (reports, first_report, last_report) = schema::report::table
+            .filter(schema::report::head_id.eq(head_id))
+            .filter(schema::report::version_id.eq(version_id))
+            .select((
+                diesel::dsl::count_star(),
+                diesel::dsl::min(schema::report::start_time),
+                diesel::dsl::max(schema::report::start_time),
+            ))
+            .get_result::<(i64, Option<DateTime>, Option<DateTime>)>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Report, (head_id, version_id)))?;
+        json_versions.push(JsonBranchVersion {
+            number,
+            hash,
+            pushed,
+            reports: u32::try_from(reports).unwrap_or(u32::MAX),
+            first_report,
+            last_report,
+        });
+    }
+
+    let total_count = get_versions_query(head_id, &pagination_params, &query_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            HeadVersion,
+            (head_id, &pagination_params, &query_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_versions.into(), total_count))
+}
+
+fn get_versions_query(
+    head_id: HeadId,
+    pagination_params: &ProjBranchVersionsPagination,
+    query_params: &ProjBranchVersionsQuery,
+) -> BoxedVersionsQuery<'static> {
+    let mut query = schema::version::table
+        .inner_join(schema::head_version::table)
+        .filter(schema::head_version::head_id.eq(head_id))
+        .select((
+            schema::version::id,
+            schema::version::number,
+            schema::version::hash,
+            schema::version::pushed,
+        ))
+        .into_boxed();
+
+    let benchmarked = schema::report::table
+        .filter(schema::report::head_id.eq(head_id))
+        .select(schema::report::version_id);
+    match query_params.benchmarked {
+        Some(true) => query = query.filter(schema::version::id.eq_any(benchmarked)),
+        Some(false) => query = query.filter(schema::version::id.ne_all(benchmarked)),
+        None => {},
+    }
+
+    match pagination_params.order() {
+        ProjBranchVersionsSort::Number => match pagination_params.direction {
+            Some(JsonDirection::Asc) => query.order(schema::version::number.asc()),
+            Some(JsonDirection::Desc) | None => query.order(schema::version::number.desc()),
+        },
+    }
+}
+
+// TODO refactor out internal types
+type BoxedVersionsQuery<'q> = diesel::internal::table_macro::BoxedSelectStatement<
+    'q,
+    (
+        diesel::sql_types::Integer,
+        diesel::sql_types::Integer,
+        diesel::sql_types::Nullable<diesel::sql_types::Text>,
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+    ),
+    diesel::internal::table_macro::FromClause<
+        diesel::helper_types::InnerJoinQuerySource<
+            schema::version::table,
+            schema::head_version::table,
+        >,
+    >,
+    diesel::sqlite::Sqlite,
+>;
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/branches/{branch}/tags",
+    tags = ["projects", "branches"]
+}]
+pub async fn proj_branch_tags_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjBranchParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Record git tags on a branch
+///
+/// Record historical git tags on a branch for a project,
+/// so that perf charts and comparisons can be aligned to releases retroactively.
+/// Each tag is recorded as a version on the current branch head, as if its commit had been pushed at the time of the tag,
+/// and as an annotation for the project at the time of the tag.
+/// The tags are recorded in chronological order, and recording the same tags again does not create duplicates.
+/// The user must have `create` permissions for the project.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/branches/{branch}/tags",
+    tags = ["projects", "branches"]
+}]
+pub async fn proj_branch_tags_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjBranchParams>,
+    body: TypedBody<JsonNewBranchTags>,
+) -> Result<ResponseCreated<JsonBranchTags>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_tags_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_tags_inner(
+    context: &ApiContext,
+    path_params: ProjBranchParams,
+    json_tags: JsonNewBranchTags,
+    auth_user: &AuthUser,
+) -> Result<JsonBranchTags, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Create,
+    )?;
+
+    let query_branch =
+        QueryBranch::from_resource_id(conn_lock!(context), query_project.id, &path_params.branch)?;
+    let head_id = query_branch.head_id()?;
+
+    let JsonNewBranchTags { mut tags } = json_tags;
+    if tags.len() > MAX_BRANCH_TAGS {
+        return Err(bad_request_error(format!(
+            "Too many git tags ({count}). The maximum is {MAX_BRANCH_TAGS}.",
+            count = tags.len()
+        )));
+    }
+    // Record the tags in chronological order, so the version numbers follow the release order
+    tags.sort_by_key(|tag| tag.time.timestamp());
+
+    let mut json = JsonBranchTags {
+        branch: query_branch.uuid,
+        annotations: Vec::new(),
+        versions: Vec::new(),
+    };
+    for tag in tags {
+        let JsonNewBranchTag { name, hash, time } = tag;
+        if let Some(version_id) = QueryVersion::push(
+            conn_lock!(context),
+            query_project.id,
+            head_id,
+            hash.clone(),
+            time,
+        )? {
+            let query_version = QueryVersion::get(conn_lock!(context), version_id)?;
+            json.versions.push(query_version.into_json());
+        }
+
+        if QueryAnnotation::exists(conn_lock!(context), query_project.id, &name, time)? {
+            continue;
+        }
+        let insert_annotation =
+            InsertAnnotation::from_tag(query_project.id, &query_branch.name, name, &hash, time);
+        diesel::insert_into(schema::annotation::table)
+            .values(&insert_annotation)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(Annotation, insert_annotation))?;
+        let query_annotation = QueryAnnotation::get_with_uuid(
+            conn_lock!(context),
+            &query_project,
+            insert_annotation.uuid,
+        )?;
+        json.annotations
+            .push(query_annotation.into_json_for_project(&query_project));
+    }
+
+    Ok(json)
+}
+
 /// Update a branch
 ///
 /// Update a branch for a project.
@@ -393,7 +820,13 @@ async fn patch_inner(
         )
         .await?;
 
-    let update_branch = UpdateBranch::from(json_branch.clone());
+    let mut update_branch = UpdateBranch::from(json_branch.clone());
+    // Freezing a branch that is already frozen keeps its original freeze time
+    if query_branch.frozen_baseline.is_some()
+        && matches!(update_branch.frozen_baseline, Some(Some(_)))
+    {
+        update_branch.frozen_baseline = None;
+    }
     diesel::update(schema::branch::table.filter(schema::branch::id.eq(query_branch.id)))
         .set(&update_branch)
         .execute(conn_lock!(context))
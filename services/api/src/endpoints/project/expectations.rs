@@ -0,0 +1,396 @@
+use bencher_json::{
+    ExpectationUuid, JsonDirection, JsonExpectation, JsonExpectations, JsonNewExpectation,
+    JsonPagination, NameId, ResourceId,
+};
+use bencher_rbac::project::Permission;
+use diesel::{
+    BelongingToDsl, BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl,
+    TextExpressionMethods,
+};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Delete, Get, Post, ResponseCreated, ResponseDeleted, ResponseOk},
+        Endpoint,
+    },
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            annotation::{InsertAnnotation, QueryAnnotation},
+            branch::{BranchId, QueryBranch},
+            expectation::{InsertExpectation, QueryExpectation},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken, PubBearerToken},
+    },
+    schema,
+    util::{headers::TotalCount, search::Search},
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjExpectationsParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+pub type ProjExpectationsPagination = JsonPagination<ProjExpectationsSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjExpectationsSort {
+    /// Sort by expected regression window creation date time.
+    #[default]
+    Created,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProjExpectationsQuery {
+    /// Filter by branch name, slug, or UUID.
+    pub branch: Option<NameId>,
+    /// Search by expected regression window pattern, reason, or UUID.
+    pub search: Option<Search>,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/expectations",
+    tags = ["projects", "expectations"]
+}]
+pub async fn proj_expectations_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjExpectationsParams>,
+    _pagination_params: Query<ProjExpectationsPagination>,
+    _query_params: Query<ProjExpectationsQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List expected regression windows for a project
+///
+/// List all expected regression windows for a project.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+/// By default, the expected regression windows are sorted by creation date time, oldest first.
+/// The HTTP response header `X-Total-Count` contains the total number of expected regression windows.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/expectations",
+    tags = ["projects", "expectations"]
+}]
+pub async fn proj_expectations_get(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjExpectationsParams>,
+    pagination_params: Query<ProjExpectationsPagination>,
+    query_params: Query<ProjExpectationsQuery>,
+) -> Result<Encoded<ResponseOk<JsonExpectations>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::new_pub(&rqctx).await?;
+    let (json, total_count) = get_ls_inner(
+        rqctx.context(),
+        auth_user.as_ref(),
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: Option<&AuthUser>,
+    path_params: ProjExpectationsParams,
+    pagination_params: ProjExpectationsPagination,
+    query_params: ProjExpectationsQuery,
+) -> Result<(JsonExpectations, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+    let branch_id = if let Some(branch) = query_params.branch.as_ref() {
+        Some(QueryBranch::from_name_id(conn_lock!(context), query_project.id, branch)?.id)
+    } else {
+        None
+    };
+
+    let expectations = get_ls_query(&query_project, branch_id, &pagination_params, &query_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryExpectation>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Expectation,
+            (&query_project, &pagination_params, &query_params)
+        ))?;
+
+    // Separate out these queries to prevent a deadlock when getting the conn_lock
+    let mut json_expectations = Vec::with_capacity(expectations.len());
+    for expectation in expectations {
+        json_expectations
+            .push(expectation.into_json_for_project(conn_lock!(context), &query_project)?);
+    }
+
+    let total_count = get_ls_query(&query_project, branch_id, &pagination_params, &query_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Expectation,
+            (&query_project, &pagination_params, &query_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_expectations.into(), total_count))
+}
+
+fn get_ls_query<'q>(
+    query_project: &'q QueryProject,
+    branch_id: Option<BranchId>,
+    pagination_params: &ProjExpectationsPagination,
+    query_params: &'q ProjExpectationsQuery,
+) -> schema::expectation::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let mut query = QueryExpectation::belonging_to(query_project).into_boxed();
+
+    if let Some(branch_id) = branch_id {
+        query = query.filter(schema::expectation::branch_id.eq(branch_id));
+    }
+    if let Some(search) = query_params.search.as_ref() {
+        query = query.filter(
+            schema::expectation::pattern
+                .like(search)
+                .or(schema::expectation::reason.like(search))
+                .or(schema::expectation::uuid.like(search)),
+        );
+    }
+
+    match pagination_params.order() {
+        ProjExpectationsSort::Created => match pagination_params.direction {
+            Some(JsonDirection::Asc) | None => query.order((
+                schema::expectation::created.asc(),
+                schema::expectation::id.asc(),
+            )),
+            Some(JsonDirection::Desc) => query.order((
+                schema::expectation::created.desc(),
+                schema::expectation::id.desc(),
+            )),
+        },
+    }
+}
+
+/// Create an expected regression window
+///
+/// Create an expected regression window for a branch in a project.
+/// Known and accepted slowdowns, such as added security checks, can be declared ahead of time
+/// so that they do not generate alerts or fail `bencher run --err`.
+/// Benchmarks with a name that matches the regular expression `pattern`
+/// do not generate alerts for reports on the branch within the time range and version range of the window.
+/// Boundaries are still calculated and stored for these benchmarks.
+/// Either a time range or a version range is required, and any bound that is not set is open-ended.
+/// An annotation is also recorded for the project with the `reason` for the window.
+/// The user must have `create` permissions for the project.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/expectations",
+    tags = ["projects", "expectations"]
+}]
+pub async fn proj_expectation_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjExpectationsParams>,
+    body: TypedBody<JsonNewExpectation>,
+) -> Result<ResponseCreated<JsonExpectation>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjExpectationsParams,
+    json_expectation: JsonNewExpectation,
+    auth_user: &AuthUser,
+) -> Result<JsonExpectation, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Create,
+    )?;
+
+    let query_branch = QueryBranch::from_name_id(
+        conn_lock!(context),
+        query_project.id,
+        &json_expectation.branch,
+    )?;
+    let mut insert_expectation = InsertExpectation::from_json(&query_branch, json_expectation)?;
+
+    // Record the expected regression window in an annotation
+    let insert_annotation = InsertAnnotation::from_json(
+        query_project.id,
+        insert_expectation.annotation(&query_branch)?,
+    );
+    diesel::insert_into(schema::annotation::table)
+        .values(&insert_annotation)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Annotation, insert_annotation))?;
+    insert_expectation.annotation_id = Some(
+        QueryAnnotation::get_with_uuid(
+            conn_lock!(context),
+            &query_project,
+            insert_annotation.uuid,
+        )?
+        .id,
+    );
+
+    diesel::insert_into(schema::expectation::table)
+        .values(&insert_expectation)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Expectation, insert_expectation))?;
+
+    let query_expectation = QueryExpectation::get_with_uuid(
+        conn_lock!(context),
+        &query_project,
+        insert_expectation.uuid,
+    )?;
+    query_expectation.into_json_for_project(conn_lock!(context), &query_project)
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjExpectationParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The UUID for an expected regression window.
+    pub expectation: ExpectationUuid,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/expectations/{expectation}",
+    tags = ["projects", "expectations"]
+}]
+pub async fn proj_expectation_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjExpectationParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Delete.into()]))
+}
+
+/// View an expected regression window
+///
+/// View an expected regression window for a project.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/expectations/{expectation}",
+    tags = ["projects", "expectations"]
+}]
+pub async fn proj_expectation_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjExpectationParams>,
+) -> Result<Encoded<ResponseOk<JsonExpectation>>, HttpError> {
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjExpectationParams,
+    auth_user: Option<&AuthUser>,
+) -> Result<JsonExpectation, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let query_expectation = QueryExpectation::get_with_uuid(
+        conn_lock!(context),
+        &query_project,
+        path_params.expectation,
+    )?;
+    query_expectation.into_json_for_project(conn_lock!(context), &query_project)
+}
+
+/// Delete an expected regression window
+///
+/// Delete an expected regression window for a project.
+/// Any alerts that were not generated while the window was in place are not created retroactively,
+/// and the annotation that was recorded for the window is kept.
+/// The user must have `delete` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/expectations/{expectation}",
+    tags = ["projects", "expectations"]
+}]
+pub async fn proj_expectation_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjExpectationParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjExpectationParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Delete,
+    )?;
+
+    let query_expectation = QueryExpectation::get_with_uuid(
+        conn_lock!(context),
+        &query_project,
+        path_params.expectation,
+    )?;
+
+    diesel::delete(
+        schema::expectation::table.filter(schema::expectation::id.eq(query_expectation.id)),
+    )
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(Expectation, query_expectation))?;
+
+    Ok(())
+}
@@ -0,0 +1,343 @@
+use bencher_json::{
+    BranchName, DateTime, GitHash, JsonGitHubDelivery, JsonGitHubWebhook, ResourceId,
+};
+use bencher_rbac::project::Permission;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, RequestContext, UntypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Delete, Get, Post, Put, ResponseDeleted, ResponseOk},
+        Endpoint,
+    },
+    error::{
+        bad_request_error, not_found_error, resource_conflict_err, resource_not_found_err,
+        unauthorized_error,
+    },
+    model::{
+        project::{
+            branch::{version::QueryVersion, QueryBranch},
+            github_webhook::{InsertGitHubWebhook, QueryGitHubWebhook},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken},
+    },
+    schema,
+};
+
+const GITHUB_EVENT_HEADER: &str = "X-GitHub-Event";
+const GITHUB_SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+const PUSH_EVENT: &str = "push";
+const BRANCH_REF_PREFIX: &str = "refs/heads/";
+// GitHub includes at most 2048 commits in a push event payload
+const MAX_PUSH_VERSIONS: usize = 255;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjGitHubWebhookParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/github/webhook",
+    tags = ["projects", "github"]
+}]
+pub async fn proj_github_webhook_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjGitHubWebhookParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[
+        Get.into(),
+        Post.into(),
+        Put.into(),
+        Delete.into(),
+    ]))
+}
+
+/// View the GitHub webhook for a project
+///
+/// View the GitHub webhook for a project.
+/// The webhook secret is not included.
+/// The user must have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/github/webhook",
+    tags = ["projects", "github"]
+}]
+pub async fn proj_github_webhook_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjGitHubWebhookParams>,
+) -> Result<Encoded<ResponseOk<JsonGitHubWebhook>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjGitHubWebhookParams,
+    auth_user: &AuthUser,
+) -> Result<JsonGitHubWebhook, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+
+    let query_webhook = get_webhook(context, &query_project).await?;
+    Ok(query_webhook.into_json(&query_project, None))
+}
+
+/// Create or rotate the GitHub webhook for a project
+///
+/// Create the GitHub webhook for a project with a new secret.
+/// If the webhook already exists, then its secret is replaced.
+/// The secret is only returned by this endpoint, so it must be saved as the webhook secret in GitHub.
+/// The webhook payload URL is this same endpoint path, and the content type must be `application/json`.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = PUT,
+    path =  "/v0/projects/{project}/github/webhook",
+    tags = ["projects", "github"]
+}]
+pub async fn proj_github_webhook_put(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjGitHubWebhookParams>,
+) -> Result<ResponseOk<JsonGitHubWebhook>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = put_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Put::auth_response_ok(json))
+}
+
+async fn put_inner(
+    context: &ApiContext,
+    path_params: ProjGitHubWebhookParams,
+    auth_user: &AuthUser,
+) -> Result<JsonGitHubWebhook, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let secret = InsertGitHubWebhook::upsert(conn_lock!(context), query_project.id)?;
+
+    let query_webhook = get_webhook(context, &query_project).await?;
+    Ok(query_webhook.into_json(&query_project, Some(secret)))
+}
+
+/// Delete the GitHub webhook for a project
+///
+/// Delete the GitHub webhook for a project.
+/// Any further webhook deliveries for the project are rejected.
+/// Versions that were already recorded from git pushes are kept.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/github/webhook",
+    tags = ["projects", "github"]
+}]
+pub async fn proj_github_webhook_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjGitHubWebhookParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjGitHubWebhookParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let query_webhook = get_webhook(context, &query_project).await?;
+    diesel::delete(
+        schema::github_webhook::table.filter(schema::github_webhook::id.eq(query_webhook.id)),
+    )
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(GitHubWebhook, query_webhook))?;
+
+    Ok(())
+}
+
+/// Receive a GitHub webhook delivery for a project
+///
+/// Receive a webhook delivery from GitHub for a project.
+/// The delivery must be signed with the project GitHub webhook secret in the `X-Hub-Signature-256` header.
+/// For a `push` event to a branch that already exists in the project,
+/// each pushed commit is recorded as a version on the current branch head before it has been benchmarked.
+/// This allows for commits that were never benchmarked to be found.
+/// All other events, such as `ping`, are acknowledged and ignored.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/github/webhook",
+    tags = ["projects", "github"]
+}]
+pub async fn proj_github_webhook_post(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjGitHubWebhookParams>,
+    body: UntypedBody,
+) -> Result<ResponseOk<JsonGitHubDelivery>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
+    let headers = rqctx.request.headers();
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned)
+    };
+    let event = header(GITHUB_EVENT_HEADER).ok_or_else(|| {
+        bad_request_error(format!(
+            "Missing GitHub event header: {GITHUB_EVENT_HEADER}"
+        ))
+    })?;
+    let signature = header(GITHUB_SIGNATURE_HEADER).ok_or_else(|| {
+        unauthorized_error(format!(
+            "Missing GitHub signature header: {GITHUB_SIGNATURE_HEADER}"
+        ))
+    })?;
+
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        event,
+        &signature,
+        body.as_bytes(),
+    )
+    .await?;
+    Ok(Post::pub_response_ok(json))
+}
+
+/// The subset of the GitHub `push` event payload that is used
+/// <https://docs.github.com/en/webhooks/webhook-events-and-payloads#push>
+#[derive(Debug, Deserialize)]
+struct GitHubPush {
+    #[serde(rename = "ref")]
+    git_ref: String,
+    after: String,
+    #[serde(default)]
+    deleted: bool,
+    #[serde(default)]
+    commits: Vec<GitHubCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCommit {
+    id: String,
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjGitHubWebhookParams,
+    event: String,
+    signature: &str,
+    payload: &[u8],
+) -> Result<JsonGitHubDelivery, HttpError> {
+    let query_project = QueryProject::from_resource_id(conn_lock!(context), &path_params.project)?;
+    let query_webhook = get_webhook(context, &query_project).await?;
+    if !query_webhook.verify(signature, payload) {
+        return Err(unauthorized_error(format!(
+            "Invalid GitHub webhook signature for project ({})",
+            query_project.uuid
+        )));
+    }
+
+    let mut delivery = JsonGitHubDelivery {
+        project: query_project.uuid,
+        event,
+        branch: None,
+        versions: Vec::new(),
+    };
+    if delivery.event != PUSH_EVENT {
+        return Ok(delivery);
+    }
+
+    let push: GitHubPush = serde_json::from_slice(payload).map_err(bad_request_error)?;
+    // Pushes that delete a branch or are to a tag are ignored
+    if push.deleted {
+        return Ok(delivery);
+    }
+    let Some(Ok(branch_name)) = push
+        .git_ref
+        .strip_prefix(BRANCH_REF_PREFIX)
+        .map(str::parse::<BranchName>)
+    else {
+        return Ok(delivery);
+    };
+    let Some(query_branch) = schema::branch::table
+        .filter(schema::branch::project_id.eq(query_project.id))
+        .filter(schema::branch::name.eq(&branch_name))
+        .filter(schema::branch::archived.is_null())
+        .first::<QueryBranch>(conn_lock!(context))
+        .optional()
+        .map_err(resource_not_found_err!(
+            Branch,
+            (&query_project, &branch_name)
+        ))?
+    else {
+        return Ok(delivery);
+    };
+    let head_id = query_branch.head_id()?;
+    delivery.branch = Some(query_branch.uuid);
+
+    // The commits are in chronological order, so only the most recent are recorded.
+    // A push without any new commits, such as a force push to an existing commit, only records the new branch tip.
+    let mut hashes = if push.commits.is_empty() {
+        vec![push.after]
+    } else {
+        push.commits.into_iter().map(|commit| commit.id).collect()
+    };
+    let hashes = hashes.split_off(hashes.len().saturating_sub(MAX_PUSH_VERSIONS));
+
+    let pushed = DateTime::now();
+    for hash in hashes {
+        let Ok(hash) = hash.parse::<GitHash>() else {
+            continue;
+        };
+        let Some(version_id) =
+            QueryVersion::push(conn_lock!(context), query_project.id, head_id, hash, pushed)?
+        else {
+            continue;
+        };
+        let query_version = QueryVersion::get(conn_lock!(context), version_id)?;
+        delivery.versions.push(query_version.into_json());
+    }
+
+    Ok(delivery)
+}
+
+async fn get_webhook(
+    context: &ApiContext,
+    query_project: &QueryProject,
+) -> Result<QueryGitHubWebhook, HttpError> {
+    QueryGitHubWebhook::get_for_project(conn_lock!(context), query_project.id)?.ok_or_else(|| {
+        not_found_error(format!(
+            "GitHub webhook not found for project ({})",
+            query_project.uuid
+        ))
+    })
+}
@@ -0,0 +1,158 @@
+use bencher_json::{JsonNewReportHook, JsonReportHook, ResourceId};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Delete, Get, Put, ResponseDeleted, ResponseOk},
+        Endpoint,
+    },
+    error::{not_found_error, resource_conflict_err},
+    model::{
+        project::{
+            report_hook::{InsertReportHook, QueryReportHook, ReportHook},
+            QueryProject,
+        },
+        user::{admin::AdminUser, auth::BearerToken},
+    },
+    schema,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjReportHookParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/hook",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_report_hook_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjReportHookParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Put.into(), Delete.into()]))
+}
+
+/// View the report hook for a project
+///
+/// View the report hook script for a project.
+/// The user must be an admin on the server to use this route.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/hook",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_report_hook_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjReportHookParams>,
+) -> Result<Encoded<ResponseOk<JsonReportHook>>, HttpError> {
+    let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner()).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjReportHookParams,
+) -> Result<JsonReportHook, HttpError> {
+    let query_project = QueryProject::from_resource_id(conn_lock!(context), &path_params.project)?;
+    let query_hook = get_hook(context, &query_project).await?;
+    Ok(query_hook.into_json(&query_project))
+}
+
+/// Create or replace the report hook for a project
+///
+/// Create the report hook for a project.
+/// If the hook already exists, then its script is replaced.
+/// The hook is a Rhai script that is run on the results of each new report for the project before they are stored.
+/// It can rename or drop benchmarks and measures, and it can attach notes to the report.
+/// The script must compile and define at least one of the hook functions.
+/// The hook is also run when a report is reparsed, but its notes are only attached when a report is created.
+/// The user must be an admin on the server to use this route.
+#[endpoint {
+    method = PUT,
+    path =  "/v0/projects/{project}/hook",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_report_hook_put(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjReportHookParams>,
+    body: TypedBody<JsonNewReportHook>,
+) -> Result<ResponseOk<JsonReportHook>, HttpError> {
+    let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = put_inner(rqctx.context(), path_params.into_inner(), body.into_inner()).await?;
+    Ok(Put::auth_response_ok(json))
+}
+
+async fn put_inner(
+    context: &ApiContext,
+    path_params: ProjReportHookParams,
+    json_hook: JsonNewReportHook,
+) -> Result<JsonReportHook, HttpError> {
+    let query_project = QueryProject::from_resource_id(conn_lock!(context), &path_params.project)?;
+
+    // Make sure that the script is valid before saving it
+    ReportHook::compile(json_hook.script.as_ref())?;
+    InsertReportHook::upsert(conn_lock!(context), query_project.id, json_hook.script)?;
+
+    let query_hook = get_hook(context, &query_project).await?;
+    Ok(query_hook.into_json(&query_project))
+}
+
+/// Delete the report hook for a project
+///
+/// Delete the report hook for a project.
+/// Reports that were already created with the hook are not changed.
+/// The user must be an admin on the server to use this route.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/hook",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_report_hook_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjReportHookParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner()).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjReportHookParams,
+) -> Result<(), HttpError> {
+    let query_project = QueryProject::from_resource_id(conn_lock!(context), &path_params.project)?;
+
+    let query_hook = get_hook(context, &query_project).await?;
+    diesel::delete(schema::report_hook::table.filter(schema::report_hook::id.eq(query_hook.id)))
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(ReportHook, query_hook))?;
+
+    Ok(())
+}
+
+async fn get_hook(
+    context: &ApiContext,
+    query_project: &QueryProject,
+) -> Result<QueryReportHook, HttpError> {
+    QueryReportHook::get_for_project(conn_lock!(context), query_project.id)?.ok_or_else(|| {
+        not_found_error(format!(
+            "Report hook not found for project ({})",
+            query_project.uuid
+        ))
+    })
+}
@@ -0,0 +1,251 @@
+use bencher_json::{
+    project::measure::alias::{
+        JsonMeasureAlias, JsonMeasureAliases, JsonNewMeasureAlias, MeasureAliasUuid,
+    },
+    ResourceId,
+};
+use bencher_rbac::project::Permission;
+use diesel::{BelongingToDsl, ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Delete, Get, Post, ResponseCreated, ResponseDeleted, ResponseOk},
+        Endpoint,
+    },
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            measure::QueryMeasure,
+            measure_alias::{InsertMeasureAlias, MeasureAliasId, QueryMeasureAlias},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken, PubBearerToken},
+    },
+    schema,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjMeasureAliasesParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The slug or UUID for a measure.
+    pub measure: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/measures/{measure}/aliases",
+    tags = ["projects", "measures"]
+}]
+pub async fn proj_measure_aliases_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjMeasureAliasesParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List aliases for a measure
+///
+/// List all aliases for a measure.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+/// The aliases are sorted in alphabetical order.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/measures/{measure}/aliases",
+    tags = ["projects", "measures"]
+}]
+pub async fn proj_measure_aliases_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjMeasureAliasesParams>,
+) -> Result<Encoded<ResponseOk<JsonMeasureAliases>>, HttpError> {
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let json = get_ls_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    path_params: ProjMeasureAliasesParams,
+    auth_user: Option<&AuthUser>,
+) -> Result<JsonMeasureAliases, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+    let query_measure = QueryMeasure::from_resource_id(
+        conn_lock!(context),
+        query_project.id,
+        &path_params.measure,
+    )?;
+
+    let aliases = QueryMeasureAlias::belonging_to(&query_measure)
+        .order(schema::measure_alias::alias.asc())
+        .load::<QueryMeasureAlias>(conn_lock!(context))
+        .map_err(resource_not_found_err!(MeasureAlias, &query_measure))?;
+
+    Ok(aliases
+        .into_iter()
+        .map(|alias| alias.into_json_for_measure(&query_project, &query_measure))
+        .collect())
+}
+
+/// Create a measure alias
+///
+/// Create an alias for a measure.
+/// The user must have `edit` permissions for the project.
+/// Results that are reported with a measure name that exactly matches the alias
+/// are stored under this measure instead.
+/// Aliases are unique within a project,
+/// and an alias may not be the name or slug of another measure.
+/// To combine the history of two existing measures, merge them instead.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/measures/{measure}/aliases",
+    tags = ["projects", "measures"]
+}]
+pub async fn proj_measure_alias_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjMeasureAliasesParams>,
+    body: TypedBody<JsonNewMeasureAlias>,
+) -> Result<ResponseCreated<JsonMeasureAlias>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjMeasureAliasesParams,
+    json_alias: JsonNewMeasureAlias,
+    auth_user: &AuthUser,
+) -> Result<JsonMeasureAlias, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+    let query_measure = QueryMeasure::from_resource_id(
+        conn_lock!(context),
+        query_project.id,
+        &path_params.measure,
+    )?;
+
+    let insert_alias =
+        InsertMeasureAlias::from_json(conn_lock!(context), &query_measure, json_alias)?;
+    diesel::insert_into(schema::measure_alias::table)
+        .values(&insert_alias)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(MeasureAlias, insert_alias))?;
+
+    schema::measure_alias::table
+        .filter(schema::measure_alias::uuid.eq(&insert_alias.uuid))
+        .first::<QueryMeasureAlias>(conn_lock!(context))
+        .map(|alias| alias.into_json_for_measure(&query_project, &query_measure))
+        .map_err(resource_not_found_err!(MeasureAlias, insert_alias))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjMeasureAliasParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The slug or UUID for a measure.
+    pub measure: ResourceId,
+    /// The UUID for a measure alias.
+    pub alias: MeasureAliasUuid,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/measures/{measure}/aliases/{alias}",
+    tags = ["projects", "measures"]
+}]
+pub async fn proj_measure_alias_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjMeasureAliasParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Delete.into()]))
+}
+
+/// Delete a measure alias
+///
+/// Delete an alias for a measure.
+/// The user must have `edit` permissions for the project.
+/// Results that are reported with the alias will no longer be stored under this measure.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/measures/{measure}/aliases/{alias}",
+    tags = ["projects", "measures"]
+}]
+pub async fn proj_measure_alias_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjMeasureAliasParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjMeasureAliasParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+    let query_measure = QueryMeasure::from_resource_id(
+        conn_lock!(context),
+        query_project.id,
+        &path_params.measure,
+    )?;
+
+    let alias_id = QueryMeasureAlias::belonging_to(&query_measure)
+        .filter(schema::measure_alias::uuid.eq(path_params.alias))
+        .select(schema::measure_alias::id)
+        .first::<MeasureAliasId>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            MeasureAlias,
+            (&query_measure, path_params.alias)
+        ))?;
+    diesel::delete(schema::measure_alias::table.filter(schema::measure_alias::id.eq(alias_id)))
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(MeasureAlias, alias_id))?;
+
+    Ok(())
+}
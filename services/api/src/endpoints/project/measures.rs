@@ -1,6 +1,7 @@
 use bencher_json::{
-    project::measure::JsonUpdateMeasure, JsonDirection, JsonMeasure, JsonMeasures, JsonNewMeasure,
-    JsonPagination, ResourceId, ResourceName,
+    project::measure::{alias::JsonMergeMeasure, JsonUpdateMeasure},
+    JsonDirection, JsonMeasure, JsonMeasures, JsonNewMeasure, JsonPagination, ResourceId,
+    ResourceName,
 };
 use bencher_rbac::project::Permission;
 use diesel::{
@@ -15,15 +16,18 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
             CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
         },
+        etag::{IfNoneMatch, Tagged},
         Endpoint,
     },
     error::{resource_conflict_err, resource_not_found_err},
     model::{
         project::{
             measure::{InsertMeasure, QueryMeasure, UpdateMeasure},
+            measure_alias::merge_measures,
             QueryProject,
         },
         user::auth::{AuthUser, BearerToken, PubBearerToken},
@@ -91,7 +95,8 @@ pub async fn proj_measures_get(
     path_params: Path<ProjMeasuresParams>,
     pagination_params: Query<ProjMeasuresPagination>,
     query_params: Query<ProjMeasuresQuery>,
-) -> Result<ResponseOk<JsonMeasures>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonMeasures>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new_pub(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -101,11 +106,13 @@ pub async fn proj_measures_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -272,8 +279,10 @@ pub async fn proj_measure_options(
 pub async fn proj_measure_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<ProjMeasureParams>,
-) -> Result<ResponseOk<JsonMeasure>, HttpError> {
+) -> Result<Encoded<Tagged<ResponseOk<JsonMeasure>>>, HttpError> {
+    let if_none_match = IfNoneMatch::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -281,7 +290,10 @@ pub async fn proj_measure_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    let response = if_none_match
+        .tag(Get::response_ok(json, auth_user.is_some()))
+        .await?;
+    accept_encoding.encode(response).await
 }
 
 async fn get_one_inner(
@@ -412,3 +424,78 @@ async fn delete_inner(
 
     Ok(())
 }
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/measures/{measure}/merge",
+    tags = ["projects", "measures"]
+}]
+pub async fn proj_measure_merge_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjMeasureParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Merge a measure
+///
+/// Merge another measure into this measure.
+/// The user must have `delete` permissions for the project.
+/// All of the metrics, thresholds, plots, and aliases for the other measure are moved to this measure.
+/// If both measures have a threshold for the same branch and testbed,
+/// then the history of the other measure's threshold is kept under this measure's threshold.
+/// The name and slug of the other measure become aliases for this measure,
+/// and then the other measure is deleted.
+/// The boundaries for this measure's thresholds are then recomputed in the background.
+/// If any benchmark result has a metric for both measures, then the merge is rejected.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/measures/{measure}/merge",
+    tags = ["projects", "measures"]
+}]
+pub async fn proj_measure_merge_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjMeasureParams>,
+    body: TypedBody<JsonMergeMeasure>,
+) -> Result<ResponseOk<JsonMeasure>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_merge_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_ok(json))
+}
+
+async fn post_merge_inner(
+    context: &ApiContext,
+    path_params: ProjMeasureParams,
+    json_merge: JsonMergeMeasure,
+    auth_user: &AuthUser,
+) -> Result<JsonMeasure, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Delete,
+    )?;
+
+    let target_measure = QueryMeasure::from_resource_id(
+        conn_lock!(context),
+        query_project.id,
+        &path_params.measure,
+    )?;
+    let source_measure =
+        QueryMeasure::from_resource_id(conn_lock!(context), query_project.id, &json_merge.measure)?;
+    merge_measures(conn_lock!(context), &target_measure, &source_measure)?;
+
+    QueryMeasure::get(conn_lock!(context), target_measure.id)
+        .map(|measure| measure.into_json_for_project(&query_project))
+        .map_err(resource_not_found_err!(Measure, target_measure))
+}
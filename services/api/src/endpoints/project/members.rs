@@ -0,0 +1,759 @@
+use bencher_json::{
+    project::{
+        member::{JsonNewProjectMember, JsonUpdateProjectMember},
+        ProjectRole,
+    },
+    DateTime, JsonAuthAck, JsonDirection, JsonPagination, JsonProjectMember, JsonProjectMembers,
+    ResourceId, UserName,
+};
+use bencher_rbac::project::Permission;
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl, TextExpressionMethods,
+};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use slog::Logger;
+
+use crate::{
+    conn_lock,
+    context::{ApiContext, Body, ButtonBody, DbConnection, Message},
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{
+            CorsResponse, Delete, Get, Patch, Post, ResponseAccepted, ResponseDeleted, ResponseOk,
+        },
+        organization::members::INVITE_TOKEN_TTL,
+        Endpoint,
+    },
+    error::{forbidden_error, issue_error, resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{member::QueryProjectMember, ProjectId, QueryProject},
+        user::{
+            auth::{AuthUser, BearerToken},
+            QueryUser, UserId,
+        },
+    },
+    schema,
+    util::{headers::TotalCount, search::Search},
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjMembersParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+pub type ProjMembersPagination = JsonPagination<ProjMembersSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjMembersSort {
+    /// Sort by user name.
+    #[default]
+    Name,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProjMembersQuery {
+    /// Filter by user name, exact match.
+    pub name: Option<UserName>,
+    /// Search by user name, slug, or UUID.
+    pub search: Option<Search>,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/members",
+    tags = ["projects", "members"]
+}]
+pub async fn proj_members_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjMembersParams>,
+    _pagination_params: Query<ProjMembersPagination>,
+    _query_params: Query<ProjMembersQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List project members
+///
+/// List members for a project.
+/// Project members have a role for the project itself,
+/// independent of any role they may have in the project's organization.
+/// The user must have `view_role` permissions for the project.
+/// By default, the members are sorted in alphabetical order by name.
+/// The HTTP response header `X-Total-Count` contains the total number of members.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/members",
+    tags = ["projects", "members"]
+}]
+pub async fn proj_members_get(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjMembersParams>,
+    pagination_params: Query<ProjMembersPagination>,
+    query_params: Query<ProjMembersQuery>,
+) -> Result<Encoded<ResponseOk<JsonProjectMembers>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::new(&rqctx).await?;
+    let (json, total_count) = get_ls_inner(
+        rqctx.context(),
+        &auth_user,
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    path_params: ProjMembersParams,
+    pagination_params: ProjMembersPagination,
+    query_params: ProjMembersQuery,
+) -> Result<(JsonProjectMembers, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::ViewRole,
+    )?;
+
+    let members = get_ls_query(&query_project, &pagination_params, &query_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryProjectMember>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            ProjectRole,
+            (&query_project, &pagination_params, &query_params)
+        ))?;
+
+    // Drop connection lock before iterating
+    let json_members = members
+        .into_iter()
+        .map(QueryProjectMember::into_json)
+        .collect();
+
+    let total_count = get_ls_query(&query_project, &pagination_params, &query_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            ProjectRole,
+            (&query_project, &pagination_params, &query_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_members, total_count))
+}
+
+fn get_ls_query<'q>(
+    query_project: &QueryProject,
+    pagination_params: &ProjMembersPagination,
+    query_params: &'q ProjMembersQuery,
+) -> BoxedQuery<'q> {
+    let mut query = schema::user::table
+        .inner_join(schema::project_role::table)
+        .filter(schema::project_role::project_id.eq(query_project.id))
+        .select((
+            schema::user::uuid,
+            schema::user::name,
+            schema::user::slug,
+            schema::user::email,
+            schema::project_role::role,
+            schema::project_role::created,
+            schema::project_role::modified,
+        ))
+        .into_boxed();
+
+    if let Some(name) = query_params.name.as_ref() {
+        query = query.filter(schema::user::name.eq(name));
+    }
+    if let Some(search) = query_params.search.as_ref() {
+        query = query.filter(
+            schema::user::name
+                .like(search)
+                .or(schema::user::slug.like(search))
+                .or(schema::user::uuid.like(search)),
+        );
+    }
+
+    match pagination_params.order() {
+        ProjMembersSort::Name => match pagination_params.direction {
+            Some(JsonDirection::Asc) | None => {
+                query.order((schema::user::name.asc(), schema::user::slug.asc()))
+            },
+            Some(JsonDirection::Desc) => {
+                query.order((schema::user::name.desc(), schema::user::slug.desc()))
+            },
+        },
+    }
+}
+
+// TODO refactor out internal types
+type BoxedQuery<'q> = diesel::internal::table_macro::BoxedSelectStatement<
+    'q,
+    (
+        diesel::sql_types::Text,
+        diesel::sql_types::Text,
+        diesel::sql_types::Text,
+        diesel::sql_types::Text,
+        diesel::sql_types::Text,
+        diesel::sql_types::BigInt,
+        diesel::sql_types::BigInt,
+    ),
+    diesel::internal::table_macro::FromClause<
+        diesel::helper_types::InnerJoinQuerySource<
+            schema::user::table,
+            schema::project_role::table,
+        >,
+    >,
+    diesel::sqlite::Sqlite,
+>;
+
+/// Invite a project member
+///
+/// Invite a user to become a member of a project.
+/// The user does not need to be a member of the project's organization,
+/// and they will only have access to this project.
+/// The user must have `create_role` permissions for the project.
+/// The invitee is sent an email with a link to accept the invitation, and
+/// they are not added to the project until they accept the invitation.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/members",
+    tags = ["projects", "members"]
+}]
+pub async fn proj_member_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjMembersParams>,
+    body: TypedBody<JsonNewProjectMember>,
+) -> Result<ResponseAccepted<JsonAuthAck>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        &rqctx.log,
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_accepted(json))
+}
+
+async fn post_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: ProjMembersParams,
+    mut json_new_member: JsonNewProjectMember,
+    auth_user: &AuthUser,
+) -> Result<JsonAuthAck, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::CreateRole,
+    )?;
+
+    let email = json_new_member.email.clone();
+    // If a user already exists for the email then direct them to login.
+    // Otherwise, direct them to signup.
+    // Either way, the response is the same so that it does not reveal which emails have an account.
+    let (name, route): (Option<String>, &str) =
+        if let Ok(user) = QueryUser::get_with_email(conn_lock!(context), &email) {
+            (Some(user.name.into()), "/auth/login")
+        } else {
+            (json_new_member.name.take().map(Into::into), "/auth/signup")
+        };
+
+    // Create a project invite token
+    let token = context
+        .token_key
+        .new_project_invite(
+            json_new_member.email,
+            INVITE_TOKEN_TTL,
+            query_project.uuid,
+            json_new_member.role,
+        )
+        .map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to create new project invite token",
+                "Failed to create new project invite token.",
+                e,
+            )
+        })?;
+    let token_string = token.to_string();
+
+    let project_name = &query_project.name;
+    let project_role = json_new_member.role;
+    let body = Body::Button(Box::new(ButtonBody {
+        title: format!("Invitation to join {project_name}"),
+        preheader: "Click the provided link to join.".into(),
+        greeting: if let Some(name) = name {
+            format!("Ahoy {name}!") } else { "Ahoy!".into() },
+        pre_body: format!(
+            "Please, click the button below or use the provided token to accept the invitation from {user_name} ({user_email}) to join the {project_name} project as a {project_role} on Bencher.",
+            user_name = auth_user.user.name,
+            user_email = auth_user.user.email,
+        ),
+        button_text: format!("Join {project_name}"),
+        button_url: context
+            .console_url
+            .clone()
+            .join(route)
+            .map(|mut url| {
+                url.query_pairs_mut().append_pair("invite", &token_string);
+                url.into()
+            })
+            .unwrap_or_default(),
+        clipboard_text: "Invite Code".into(),
+        clipboard_target: token_string,
+        post_body: String::new(),
+        closing: "See you soon,".into(),
+        signature: "The Bencher Team".into(),
+        settings_url: context
+            .console_url
+            .clone()
+            .join("/help")
+            .map(Into::into)
+            .unwrap_or_default(),
+    }));
+    let message = Message {
+        to_name: None,
+        to_email: email.to_string(),
+        subject: Some(format!("Invitation to join {project_name}")),
+        body: Some(body),
+    };
+    context.messenger.send(log, conn_lock!(context), message);
+
+    Ok(JsonAuthAck { email })
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjMemberParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The slug or UUID for a project member.
+    pub user: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/members/{user}",
+    tags = ["projects", "members"]
+}]
+pub async fn proj_member_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjMemberParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Patch.into(), Delete.into()]))
+}
+
+/// View a project member
+///
+/// View a member of a project.
+/// The user must have `view_role` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/members/{user}",
+    tags = ["projects", "members"]
+}]
+pub async fn proj_member_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjMemberParams>,
+) -> Result<Encoded<ResponseOk<JsonProjectMember>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjMemberParams,
+    auth_user: &AuthUser,
+) -> Result<JsonProjectMember, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::ViewRole,
+    )?;
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+
+    json_member(conn_lock!(context), query_user.id, query_project.id)
+}
+
+/// Update a project member
+///
+/// Update the role for a member of a project.
+/// The last maintainer of the project can not be given a different role.
+/// The user must have `edit_role` permissions for the project.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/projects/{project}/members/{user}",
+    tags = ["projects", "members"]
+}]
+pub async fn proj_member_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjMemberParams>,
+    body: TypedBody<JsonUpdateProjectMember>,
+) -> Result<ResponseOk<JsonProjectMember>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = patch_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+async fn patch_inner(
+    context: &ApiContext,
+    path_params: ProjMemberParams,
+    json_update: JsonUpdateProjectMember,
+    auth_user: &AuthUser,
+) -> Result<JsonProjectMember, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::EditRole,
+    )?;
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+
+    if let Some(role) = json_update.role {
+        // Hold the connection so the maintainers can not change in between
+        conn_lock!(context, |conn| {
+            if role != ProjectRole::Maintainer {
+                check_last_maintainer(conn, query_project.id, query_user.id)?;
+            }
+            diesel::update(
+                schema::project_role::table
+                    .filter(schema::project_role::user_id.eq(query_user.id))
+                    .filter(schema::project_role::project_id.eq(query_project.id)),
+            )
+            .set((
+                schema::project_role::role.eq(role.to_string()),
+                schema::project_role::modified.eq(DateTime::now()),
+            ))
+            .execute(conn)
+            .map_err(resource_conflict_err!(
+                ProjectRole,
+                (&query_user, &query_project, role)
+            ))
+        })?;
+    }
+
+    json_member(conn_lock!(context), query_user.id, query_project.id)
+}
+
+/// Remove a project member
+///
+/// Remove a member of a project.
+/// This does not affect any role the member may have in the project's organization.
+/// The last maintainer of the project can not be removed.
+/// The user must have `delete_role` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/members/{user}",
+    tags = ["projects", "members"]
+}]
+pub async fn proj_member_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjMemberParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjMemberParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::DeleteRole,
+    )?;
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+
+    // Hold the connection so the maintainers can not change in between
+    conn_lock!(context, |conn| {
+        check_last_maintainer(conn, query_project.id, query_user.id)?;
+        diesel::delete(
+            schema::project_role::table
+                .filter(schema::project_role::user_id.eq(query_user.id))
+                .filter(schema::project_role::project_id.eq(query_project.id)),
+        )
+        .execute(conn)
+        .map_err(resource_conflict_err!(
+            ProjectRole,
+            (&query_user, &query_project)
+        ))
+    })?;
+
+    Ok(())
+}
+
+// A project that has maintainers must always be left with at least one of them,
+// so that its members can still be managed from within the project.
+fn check_last_maintainer(
+    conn: &mut DbConnection,
+    project_id: ProjectId,
+    user_id: UserId,
+) -> Result<(), HttpError> {
+    let maintainer_ids = schema::project_role::table
+        .filter(schema::project_role::project_id.eq(project_id))
+        .filter(schema::project_role::role.eq(ProjectRole::Maintainer))
+        .select(schema::project_role::user_id)
+        .load::<UserId>(conn)
+        .map_err(resource_not_found_err!(
+            ProjectRole,
+            (project_id, ProjectRole::Maintainer)
+        ))?;
+    if maintainer_ids.len() == 1 && maintainer_ids.contains(&user_id) {
+        Err(forbidden_error(format!(
+            "Cannot remove user ({user_id}) as they are the last maintainer of the project ({project_id})"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn json_member(
+    conn: &mut DbConnection,
+    user_id: UserId,
+    project_id: ProjectId,
+) -> Result<JsonProjectMember, HttpError> {
+    Ok(schema::user::table
+        .inner_join(schema::project_role::table)
+        .filter(schema::project_role::user_id.eq(user_id))
+        .filter(schema::project_role::project_id.eq(project_id))
+        .select((
+            schema::user::uuid,
+            schema::user::name,
+            schema::user::slug,
+            schema::user::email,
+            schema::project_role::role,
+            schema::project_role::created,
+            schema::project_role::modified,
+        ))
+        .first::<QueryProjectMember>(conn)
+        .map_err(resource_not_found_err!(ProjectRole, (user_id, project_id)))?
+        .into_json())
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{
+        organization::member::OrganizationRole,
+        project::{ProjectRole, Visibility},
+        DateTime,
+    };
+    use bencher_token::{TokenKey, DEFAULT_SECRET_KEY};
+    use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        context::DbConnection,
+        model::{
+            project::{project_role::InsertProjectRole, ProjectId},
+            user::UserId,
+        },
+        schema,
+        util::test_util::{create_organization, create_project, create_user, setup_db},
+    };
+
+    use super::{check_last_maintainer, json_member};
+
+    const TTL: u32 = 60;
+
+    fn add_member(
+        conn: &mut DbConnection,
+        user_id: UserId,
+        project_id: ProjectId,
+        role: ProjectRole,
+    ) {
+        let timestamp = DateTime::now();
+        diesel::insert_into(schema::project_role::table)
+            .values(&InsertProjectRole {
+                user_id,
+                project_id,
+                role,
+                created: timestamp,
+                modified: timestamp,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn token_key() -> TokenKey {
+        TokenKey::new("bencher.dev".to_owned(), &DEFAULT_SECRET_KEY)
+    }
+
+    #[test]
+    fn test_check_last_maintainer() {
+        let mut conn = setup_db();
+        let org = create_organization(&mut conn, "Organization");
+        let project = create_project(&mut conn, &org, "Project", Visibility::Public);
+        let alice = create_user(&mut conn, "Alice");
+        let bob = create_user(&mut conn, "Bob");
+
+        // A project without any maintainers can have any member removed
+        add_member(&mut conn, bob.id, project.id, ProjectRole::Viewer);
+        assert!(check_last_maintainer(&mut conn, project.id, bob.id).is_ok());
+
+        // The only maintainer can not be removed, but other members can
+        add_member(&mut conn, alice.id, project.id, ProjectRole::Maintainer);
+        assert!(check_last_maintainer(&mut conn, project.id, alice.id).is_err());
+        assert!(check_last_maintainer(&mut conn, project.id, bob.id).is_ok());
+
+        // Once there is another maintainer, either one can be removed
+        diesel::update(
+            schema::project_role::table
+                .filter(schema::project_role::user_id.eq(bob.id))
+                .filter(schema::project_role::project_id.eq(project.id)),
+        )
+        .set(schema::project_role::role.eq(ProjectRole::Maintainer))
+        .execute(&mut conn)
+        .unwrap();
+        assert!(check_last_maintainer(&mut conn, project.id, alice.id).is_ok());
+        assert!(check_last_maintainer(&mut conn, project.id, bob.id).is_ok());
+
+        // Maintainers of other projects do not count
+        let other_project = create_project(&mut conn, &org, "Other Project", Visibility::Public);
+        add_member(
+            &mut conn,
+            alice.id,
+            other_project.id,
+            ProjectRole::Maintainer,
+        );
+        assert!(check_last_maintainer(&mut conn, other_project.id, alice.id).is_err());
+    }
+
+    #[test]
+    fn test_json_member() {
+        let mut conn = setup_db();
+        let org = create_organization(&mut conn, "Organization");
+        let project = create_project(&mut conn, &org, "Project", Visibility::Public);
+        let alice = create_user(&mut conn, "Alice");
+        let bob = create_user(&mut conn, "Bob");
+        add_member(&mut conn, alice.id, project.id, ProjectRole::Reporter);
+
+        let json_member = json_member(&mut conn, alice.id, project.id).unwrap();
+        assert_eq!(json_member.uuid, alice.uuid);
+        assert_eq!(json_member.email, alice.email);
+        assert_eq!(json_member.role, ProjectRole::Reporter);
+
+        // Not a member of the project
+        assert!(json_member(&mut conn, bob.id, project.id).is_err());
+    }
+
+    #[test]
+    fn test_accept_project_invite() {
+        let mut conn = setup_db();
+        let token_key = token_key();
+        let org = create_organization(&mut conn, "Organization");
+        let project = create_project(&mut conn, &org, "Project", Visibility::Public);
+        let alice = create_user(&mut conn, "Alice");
+
+        let invite = token_key
+            .new_project_invite(
+                alice.email.clone(),
+                TTL,
+                project.uuid,
+                ProjectRole::Reporter,
+            )
+            .unwrap();
+        alice.accept_invite(&mut conn, &token_key, &invite).unwrap();
+
+        let json_member = json_member(&mut conn, alice.id, project.id).unwrap();
+        assert_eq!(json_member.role, ProjectRole::Reporter);
+        // A project invite does not grant a role in the organization
+        let org_roles = schema::organization_role::table
+            .filter(schema::organization_role::user_id.eq(alice.id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .unwrap();
+        assert_eq!(org_roles, 0);
+    }
+
+    #[test]
+    fn test_accept_organization_invite() {
+        let mut conn = setup_db();
+        let token_key = token_key();
+        let org = create_organization(&mut conn, "Organization");
+        let project = create_project(&mut conn, &org, "Project", Visibility::Public);
+        let alice = create_user(&mut conn, "Alice");
+
+        let invite = token_key
+            .new_invite(alice.email.clone(), TTL, org.uuid, OrganizationRole::Member)
+            .unwrap();
+        alice.accept_invite(&mut conn, &token_key, &invite).unwrap();
+
+        let role = schema::organization_role::table
+            .filter(schema::organization_role::user_id.eq(alice.id))
+            .filter(schema::organization_role::organization_id.eq(org.id))
+            .select(schema::organization_role::role)
+            .first::<OrganizationRole>(&mut conn)
+            .unwrap();
+        assert_eq!(role, OrganizationRole::Member);
+        assert!(json_member(&mut conn, alice.id, project.id).is_err());
+    }
+
+    #[test]
+    fn test_accept_invalid_invite() {
+        let mut conn = setup_db();
+        let token_key = token_key();
+        let org = create_organization(&mut conn, "Organization");
+        let project = create_project(&mut conn, &org, "Project", Visibility::Public);
+        let alice = create_user(&mut conn, "Alice");
+
+        // An invite from another server is neither kind of invite,
+        // so it must not fall back to being an organization invite.
+        let other_key = TokenKey::new("other.dev".to_owned(), &DEFAULT_SECRET_KEY);
+        let invite = other_key
+            .new_project_invite(
+                alice.email.clone(),
+                TTL,
+                project.uuid,
+                ProjectRole::Maintainer,
+            )
+            .unwrap();
+        assert!(alice.accept_invite(&mut conn, &token_key, &invite).is_err());
+
+        // Any other kind of token is not an invite
+        let auth = token_key.new_auth(alice.email.clone(), TTL).unwrap();
+        assert!(alice.accept_invite(&mut conn, &token_key, &auth).is_err());
+
+        let member_count = schema::project_role::table
+            .filter(schema::project_role::user_id.eq(alice.id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .unwrap();
+        assert_eq!(member_count, 0);
+    }
+}
@@ -13,6 +13,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, ResponseOk},
         Endpoint,
     },
@@ -23,6 +24,7 @@ use crate::{
             branch::{head::QueryHead, version::QueryVersion, QueryBranch},
             measure::QueryMeasure,
             metric_boundary::QueryMetricBoundary,
+            metric_downsample::get_downsample,
             testbed::QueryTestbed,
             threshold::{
                 alert::QueryAlert, boundary::QueryBoundary, model::QueryModel, QueryThreshold,
@@ -70,8 +72,9 @@ pub async fn proj_metric_options(
 pub async fn proj_metric_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<ProjMetricParams>,
-) -> Result<ResponseOk<JsonOneMetric>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonOneMetric>>, HttpError> {
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -79,7 +82,9 @@ pub async fn proj_metric_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
 }
 
 async fn get_one_inner(
@@ -154,6 +159,7 @@ async fn get_one_inner(
                     schema::model::window,
                     schema::model::lower_boundary,
                     schema::model::upper_boundary,
+                    schema::model::decay,
                     schema::model::created,
                     schema::model::replaced,
                 ),
@@ -161,7 +167,9 @@ async fn get_one_inner(
                     schema::alert::id,
                     schema::alert::uuid,
                     schema::alert::boundary_id,
+                    schema::alert::group_id,
                     schema::alert::boundary_limit,
+                    schema::alert::severity,
                     schema::alert::status,
                     schema::alert::modified,
                 ).nullable(),
@@ -215,6 +223,7 @@ async fn metric_query_json(
     let (threshold, alert) = threshold_model_alert(project, tma);
     let (metric, boundary) = QueryMetricBoundary::split(query_metric_boundary);
     let metric_uuid = metric.uuid;
+    let downsample = get_downsample(conn_lock!(context), metric.id)?;
     let metric = metric.into_json();
     let boundary = boundary.map(QueryBoundary::into_json);
 
@@ -229,6 +238,7 @@ async fn metric_query_json(
         benchmark,
         measure,
         metric,
+        downsample,
         threshold,
         boundary,
         alert,
@@ -1,12 +1,22 @@
 pub mod alerts;
 pub mod allowed;
+pub mod annotations;
 pub mod benchmarks;
 pub mod branches;
+pub mod expectations;
+pub mod github;
+pub mod hook;
+pub mod measure_aliases;
 pub mod measures;
+pub mod members;
 pub mod metrics;
 pub mod perf;
 pub mod plots;
 pub mod projects;
+pub mod queries;
 pub mod reports;
+pub mod rules;
+pub mod run;
 pub mod testbeds;
 pub mod thresholds;
+pub mod webhook;
@@ -0,0 +1,351 @@
+use bencher_json::{
+    project::{
+        alert::AlertStatus, perf::JsonPerfBadgeQueryParams, testbed::TESTBED_LOCALHOST_NAME_ID,
+    },
+    BenchmarkName, NameId, ResourceName,
+};
+use bencher_plot::{Badge, BadgeTrend};
+use diesel::{dsl::count, ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext};
+use http::{Response, StatusCode};
+use hyper::Body;
+
+use crate::{
+    conn_lock,
+    context::{ApiContext, DbConnection},
+    endpoints::{
+        endpoint::{CorsResponse, Get},
+        Endpoint,
+    },
+    error::resource_not_found_err,
+    model::{
+        project::{
+            benchmark::{BenchmarkId, QueryBenchmark},
+            branch::{head::HeadId, QueryBranch},
+            measure::{MeasureId, QueryMeasure},
+            report::ReportId,
+            testbed::{QueryTestbed, TestbedId},
+            ProjectId, QueryProject,
+        },
+        user::auth::{AuthUser, PubBearerToken},
+    },
+    schema,
+};
+
+use super::ProjPerfParams;
+
+// Cache public badges for a short time, so READMEs stay reasonably live
+const PUBLIC_CACHE_CONTROL: &str = "public, max-age=300";
+const PRIVATE_CACHE_CONTROL: &str = "private, max-age=0, no-cache";
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/badge.svg",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_badge_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjPerfParams>,
+    _query_params: Query<JsonPerfBadgeQueryParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// Generate a project badge
+///
+/// Generate an SVG badge with the latest metric value for a branch and measure,
+/// along with a trend arrow compared to the previous report.
+/// The metric is for a single benchmark on a single testbed.
+/// If no testbed is provided, then the `localhost` testbed is used.
+/// If no benchmark is provided, then the benchmark with the most recent metric for the measure is used.
+/// The badge is red if the latest report has an active alert for the metric.
+/// If the project is public, then the user does not need to be authenticated
+/// and the badge may be cached for up to five minutes.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/badge.svg",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_badge_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    path_params: Path<ProjPerfParams>,
+    query_params: Query<JsonPerfBadgeQueryParams>,
+) -> Result<Response<Body>, HttpError> {
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let (svg, is_public) = get_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        query_params.into_inner(),
+        auth_user.as_ref(),
+    )
+    .await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, "image/svg+xml")
+        .header(
+            http::header::CACHE_CONTROL,
+            if is_public {
+                PUBLIC_CACHE_CONTROL
+            } else {
+                PRIVATE_CACHE_CONTROL
+            },
+        )
+        .body(svg.into())
+        .map_err(Into::into)
+}
+
+async fn get_inner(
+    context: &ApiContext,
+    path_params: ProjPerfParams,
+    query_params: JsonPerfBadgeQueryParams,
+    auth_user: Option<&AuthUser>,
+) -> Result<(String, bool), HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let svg = badge_svg(conn_lock!(context), query_project.id, query_params)?;
+    Ok((svg, query_project.is_public()))
+}
+
+/// Generate the SVG badge for the latest metric value of the benchmark measure
+fn badge_svg(
+    conn: &mut DbConnection,
+    project_id: ProjectId,
+    query_params: JsonPerfBadgeQueryParams,
+) -> Result<String, HttpError> {
+    let JsonPerfBadgeQueryParams {
+        branch,
+        testbed,
+        benchmark,
+        measure,
+        label,
+    } = query_params;
+    let head_id = QueryBranch::from_name_id(conn, project_id, &branch)?.head_id()?;
+    let testbed_id = QueryTestbed::from_name_id(conn, project_id, &badge_testbed(testbed))?.id;
+    let query_measure = QueryMeasure::from_name_id(conn, project_id, &measure)?;
+    let query_benchmark = if let Some(benchmark) = benchmark {
+        QueryBenchmark::from_resource_id(conn, project_id, &benchmark)?
+    } else {
+        let benchmark_id = latest_benchmark_id(conn, head_id, testbed_id, query_measure.id)?;
+        QueryBenchmark::get(conn, benchmark_id)?
+    };
+    let benchmark_id = query_benchmark.id;
+
+    let report_ids = latest_report_ids(conn, head_id, testbed_id, benchmark_id, query_measure.id)?;
+    let mut values = Vec::with_capacity(report_ids.len());
+    for report_id in &report_ids {
+        values.push(report_mean(
+            conn,
+            *report_id,
+            benchmark_id,
+            query_measure.id,
+        )?);
+    }
+    let value = values.first().copied().flatten();
+    let previous = values.get(1).copied().flatten();
+    let trend = value
+        .zip(previous)
+        .map(|(value, previous)| BadgeTrend::new(value, previous));
+    let alert = if let Some(report_id) = report_ids.first() {
+        has_active_alert(conn, *report_id, benchmark_id, query_measure.id)?
+    } else {
+        false
+    };
+
+    let label = badge_label(label, &query_benchmark.name, &query_measure.name);
+    Ok(Badge::new(&label, value, trend, alert).svg())
+}
+
+/// If no testbed is provided, then the `localhost` testbed is used
+fn badge_testbed(testbed: Option<NameId>) -> NameId {
+    testbed.unwrap_or_else(|| TESTBED_LOCALHOST_NAME_ID.clone())
+}
+
+/// If no label is provided, then the benchmark and measure names are used
+fn badge_label(
+    label: Option<String>,
+    benchmark_name: &BenchmarkName,
+    measure_name: &ResourceName,
+) -> String {
+    label.unwrap_or_else(|| format!("{benchmark_name} {measure_name}"))
+}
+
+/// Get the benchmark with the most recent metric for the measure on the branch head and testbed
+fn latest_benchmark_id(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    measure_id: MeasureId,
+) -> Result<BenchmarkId, HttpError> {
+    schema::metric::table
+        .inner_join(
+            schema::report_benchmark::table.inner_join(
+                schema::report::table.inner_join(
+                    schema::version::table.inner_join(
+                        schema::head_version::table
+                            .on(schema::head_version::version_id.eq(schema::version::id)),
+                    ),
+                ),
+            ),
+        )
+        .filter(schema::head_version::head_id.eq(head_id))
+        .filter(schema::report::testbed_id.eq(testbed_id))
+        .filter(schema::metric::measure_id.eq(measure_id))
+        .order((
+            schema::report::start_time.desc(),
+            schema::report_benchmark::iteration.asc(),
+            schema::report_benchmark::id.asc(),
+        ))
+        .select(schema::report_benchmark::benchmark_id)
+        .first::<BenchmarkId>(conn)
+        .map_err(resource_not_found_err!(
+            Benchmark,
+            (head_id, testbed_id, measure_id)
+        ))
+}
+
+/// Get the two most recent reports for the branch head and testbed with metrics for the benchmark measure
+fn latest_report_ids(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    benchmark_id: BenchmarkId,
+    measure_id: MeasureId,
+) -> Result<Vec<ReportId>, HttpError> {
+    schema::metric::table
+        .inner_join(
+            schema::report_benchmark::table.inner_join(
+                schema::report::table.inner_join(
+                    schema::version::table.inner_join(
+                        schema::head_version::table
+                            .on(schema::head_version::version_id.eq(schema::version::id)),
+                    ),
+                ),
+            ),
+        )
+        .filter(schema::head_version::head_id.eq(head_id))
+        .filter(schema::report::testbed_id.eq(testbed_id))
+        .filter(schema::report_benchmark::benchmark_id.eq(benchmark_id))
+        .filter(schema::metric::measure_id.eq(measure_id))
+        .select(schema::report::id)
+        .distinct()
+        .order(schema::report::start_time.desc())
+        .limit(2)
+        .load::<ReportId>(conn)
+        .map_err(resource_not_found_err!(Report, (head_id, measure_id)))
+}
+
+/// Get the mean of the metrics for the benchmark measure across all iterations of the report
+fn report_mean(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+    benchmark_id: BenchmarkId,
+    measure_id: MeasureId,
+) -> Result<Option<f64>, HttpError> {
+    let values = schema::metric::table
+        .inner_join(schema::report_benchmark::table)
+        .filter(schema::report_benchmark::report_id.eq(report_id))
+        .filter(schema::report_benchmark::benchmark_id.eq(benchmark_id))
+        .filter(schema::metric::measure_id.eq(measure_id))
+        .select(schema::metric::value)
+        .load::<f64>(conn)
+        .map_err(resource_not_found_err!(Metric, (report_id, measure_id)))?;
+    #[allow(clippy::cast_precision_loss)]
+    Ok((!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64))
+}
+
+/// Check to see if any of the metrics for the benchmark measure in the report have an active alert
+fn has_active_alert(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+    benchmark_id: BenchmarkId,
+    measure_id: MeasureId,
+) -> Result<bool, HttpError> {
+    let alerts = schema::alert::table
+        .inner_join(
+            schema::boundary::table
+                .inner_join(schema::metric::table.inner_join(schema::report_benchmark::table)),
+        )
+        .filter(schema::report_benchmark::report_id.eq(report_id))
+        .filter(schema::report_benchmark::benchmark_id.eq(benchmark_id))
+        .filter(schema::metric::measure_id.eq(measure_id))
+        .filter(schema::alert::status.eq(AlertStatus::Active))
+        .select(count(schema::alert::id))
+        .first::<i64>(conn)
+        .map_err(resource_not_found_err!(Alert, (report_id, measure_id)))?;
+    Ok(alerts > 0)
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{
+        project::{perf::JsonPerfBadgeQueryParams, testbed::TESTBED_LOCALHOST_NAME_ID},
+        NameId,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{badge_label, badge_testbed};
+
+    fn query_params(query: &str) -> JsonPerfBadgeQueryParams {
+        serde_urlencoded::from_str(query).unwrap()
+    }
+
+    #[test]
+    fn test_badge_defaults() {
+        // Only the branch and measure are required
+        let JsonPerfBadgeQueryParams {
+            branch,
+            testbed,
+            benchmark,
+            measure,
+            label,
+        } = query_params("branch=main&measure=latency");
+        assert_eq!(branch, "main".parse::<NameId>().unwrap());
+        assert_eq!(measure, "latency".parse::<NameId>().unwrap());
+        assert!(benchmark.is_none());
+
+        assert_eq!(badge_testbed(testbed), *TESTBED_LOCALHOST_NAME_ID);
+        assert_eq!(
+            badge_label(
+                label,
+                &"bench".parse().unwrap(),
+                &"Latency".parse().unwrap()
+            ),
+            "bench Latency"
+        );
+    }
+
+    #[test]
+    fn test_badge_explicit() {
+        let JsonPerfBadgeQueryParams {
+            testbed,
+            benchmark,
+            label,
+            ..
+        } = query_params("branch=main&measure=latency&testbed=other&benchmark=bench&label=Speed");
+        assert!(benchmark.is_some());
+        assert_eq!(badge_testbed(testbed), "other".parse::<NameId>().unwrap());
+        assert_eq!(
+            badge_label(
+                label,
+                &"bench".parse().unwrap(),
+                &"Latency".parse().unwrap()
+            ),
+            "Speed"
+        );
+    }
+
+    #[test]
+    fn test_badge_missing_required() {
+        assert!(serde_urlencoded::from_str::<JsonPerfBadgeQueryParams>("branch=main").is_err());
+        assert!(serde_urlencoded::from_str::<JsonPerfBadgeQueryParams>("measure=latency").is_err());
+    }
+}
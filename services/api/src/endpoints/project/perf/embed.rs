@@ -0,0 +1,288 @@
+use bencher_json::{
+    project::perf::{JsonPerfEmbedQueryParams, JsonPerfImgQueryParams, JsonPerfQueryParams},
+    JsonNewPerfEmbed, JsonPerfEmbed, JsonPerfQuery,
+};
+use bencher_rbac::project::Permission;
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use http::{Response, StatusCode};
+use hyper::Body;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        endpoint::{CorsResponse, Delete, Get, Post, ResponseCreated, ResponseDeleted},
+        Endpoint,
+    },
+    error::{bad_request_error, forbidden_error, issue_error, unauthorized_error},
+    model::{
+        project::{
+            perf_embed::{InsertPerfEmbedRevocation, QueryPerfEmbedRevocation},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken},
+    },
+};
+
+use super::ProjPerfParams;
+
+/// The default time-to-live (TTL) for an embed token: 30 days
+const DEFAULT_EMBED_TTL: u32 = 30 * 24 * 60 * 60;
+/// The maximum time-to-live (TTL) for an embed token: 365 days
+const MAX_EMBED_TTL: u32 = 365 * 24 * 60 * 60;
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/perf/embed",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_embed_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjPerfParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into(), Delete.into()]))
+}
+
+/// Generate an embedded image of project performance metrics
+///
+/// Generate the image of performance metrics for a project perf embed token.
+/// The embed token only grants access to the perf query that it was created for.
+/// The user does not need to be authenticated, but the project must still be public.
+/// The embed token is no longer valid if the embed tokens for the project have been revoked.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/perf/embed",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_embed_get(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjPerfParams>,
+    query_params: Query<JsonPerfEmbedQueryParams>,
+) -> Result<Response<Body>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
+    let jpeg = get_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, "image/jpeg")
+        .header(http::header::CACHE_CONTROL, "private, max-age=0, no-cache")
+        .body(jpeg.into())
+        .map_err(Into::into)
+}
+
+async fn get_inner(
+    context: &ApiContext,
+    path_params: ProjPerfParams,
+    query_params: JsonPerfEmbedQueryParams,
+) -> Result<Vec<u8>, HttpError> {
+    let claims = context
+        .token_key
+        .validate_embed(&query_params.token)
+        .map_err(unauthorized_error)?;
+
+    let query_project = QueryProject::from_resource_id(conn_lock!(context), &path_params.project)?;
+    if query_project.uuid != claims.perf.project {
+        return Err(forbidden_error(format!(
+            "Embed token is for project ({embed_project}) not project ({project})",
+            embed_project = claims.perf.project,
+            project = query_project.uuid,
+        )));
+    }
+    if QueryPerfEmbedRevocation::is_revoked(
+        conn_lock!(context),
+        query_project.id,
+        claims.issued_at(),
+    )? {
+        return Err(unauthorized_error(format!(
+            "Embed token for project ({}) has been revoked",
+            query_project.uuid
+        )));
+    }
+
+    let mut json_perf_img_query_params: JsonPerfImgQueryParams =
+        serde_urlencoded::from_str(&claims.perf.query).map_err(bad_request_error)?;
+    let title = json_perf_img_query_params.title.take();
+    let json_perf_query_params: JsonPerfQueryParams = json_perf_img_query_params.into();
+    // Second round of marshaling
+    let json_perf_query = json_perf_query_params
+        .try_into()
+        .map_err(bad_request_error)?;
+
+    // No user is provided, so the perf query will only succeed if the project is public
+    super::img::get_inner(
+        context,
+        path_params,
+        title.as_deref(),
+        json_perf_query,
+        None,
+    )
+    .await
+}
+
+/// Create a project perf embed token
+///
+/// Create a signed, expiring token that only grants access to the image for a single perf query.
+/// The token can be used to embed the perf image in dashboards, iframes, and badges
+/// without exposing an API token.
+/// The user must have `view` permissions for the project, and the project must be public.
+/// The time-to-live (TTL) for the token can be at most 365 days.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/perf/embed",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_embed_post(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjPerfParams>,
+    query_params: Query<JsonPerfImgQueryParams>,
+    body: TypedBody<JsonNewPerfEmbed>,
+) -> Result<ResponseCreated<JsonPerfEmbed>, HttpError> {
+    let auth_user = AuthUser::new(&rqctx).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        query_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjPerfParams,
+    mut json_perf_img_query_params: JsonPerfImgQueryParams,
+    json_perf_embed: JsonNewPerfEmbed,
+    auth_user: &AuthUser,
+) -> Result<JsonPerfEmbed, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+    if !query_project.is_public() {
+        return Err(bad_request_error(format!(
+            "Embed tokens are only available for public projects, and project ({}) is private",
+            query_project.slug
+        )));
+    }
+    let ttl = embed_ttl(json_perf_embed.ttl)?;
+
+    let title = json_perf_img_query_params.title.take();
+    let json_perf_query_params: JsonPerfQueryParams = json_perf_img_query_params.into();
+    // Second round of marshaling
+    let json_perf_query: JsonPerfQuery = json_perf_query_params
+        .try_into()
+        .map_err(bad_request_error)?;
+    let query = json_perf_query
+        .to_query_string(&[("title", title)])
+        .map_err(bad_request_error)?;
+
+    let token = context
+        .token_key
+        .new_embed(
+            auth_user.user.email.clone(),
+            ttl,
+            query_project.uuid,
+            query.clone(),
+        )
+        .map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to create new embed token",
+                &format!(
+                    "Failed to create new embed token for project ({}).",
+                    query_project.uuid
+                ),
+                e,
+            )
+        })?;
+    let claims = context.token_key.validate_embed(&token).map_err(|e| {
+        issue_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to validate new embed token",
+            &format!("Failed to validate new embed token: {token}"),
+            e,
+        )
+    })?;
+
+    Ok(JsonPerfEmbed {
+        project: query_project.uuid,
+        query,
+        token,
+        expiration: claims.expiration(),
+    })
+}
+
+fn embed_ttl(ttl: Option<u32>) -> Result<u32, HttpError> {
+    let ttl = ttl.unwrap_or(DEFAULT_EMBED_TTL);
+    if ttl > MAX_EMBED_TTL {
+        return Err(bad_request_error(format!(
+            "Embed token time-to-live (TTL) ({ttl}) is greater than the maximum allowed ({MAX_EMBED_TTL})"
+        )));
+    }
+    Ok(ttl)
+}
+
+/// Revoke all project perf embed tokens
+///
+/// Revoke all of the perf embed tokens that have been created for a project.
+/// Any embedded perf images that use these tokens will stop working.
+/// New embed tokens can still be created afterwards.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/perf/embed",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_embed_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjPerfParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjPerfParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    InsertPerfEmbedRevocation::new(query_project.id).revoke_all(conn_lock!(context))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{embed_ttl, DEFAULT_EMBED_TTL, MAX_EMBED_TTL};
+
+    #[test]
+    fn test_embed_ttl() {
+        assert_eq!(embed_ttl(None).unwrap(), DEFAULT_EMBED_TTL);
+        assert_eq!(embed_ttl(Some(60)).unwrap(), 60);
+        assert_eq!(embed_ttl(Some(MAX_EMBED_TTL)).unwrap(), MAX_EMBED_TTL);
+        assert!(embed_ttl(Some(MAX_EMBED_TTL + 1)).is_err());
+        assert!(embed_ttl(Some(u32::MAX)).is_err());
+    }
+}
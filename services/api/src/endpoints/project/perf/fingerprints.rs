@@ -0,0 +1,225 @@
+use bencher_json::{
+    project::{
+        perf_fingerprint::{JsonPerfFingerprintsQueryParams, PerfFingerprintsBuilder},
+        report::JsonReportFingerprint,
+    },
+    BenchmarkName, JsonPerfFingerprints, JsonPerfFingerprintsQuery, NonEmpty, ReportUuid,
+};
+use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext};
+
+use crate::{
+    conn_lock,
+    context::{ApiContext, DbConnection},
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, ResponseOk},
+        Endpoint,
+    },
+    error::resource_not_found_err,
+    model::{
+        project::{
+            branch::{head::HeadId, QueryBranch},
+            measure::{MeasureId, QueryMeasure},
+            testbed::{QueryTestbed, TestbedId},
+            QueryProject,
+        },
+        user::auth::{AuthUser, PubBearerToken},
+    },
+    schema,
+};
+
+use super::{ProjPerfParams, Times};
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/perf/fingerprints",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_fingerprints_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjPerfParams>,
+    _query_params: Query<JsonPerfFingerprintsQueryParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// Query project performance variance by hardware fingerprint
+///
+/// Query the performance metrics for a branch and measure, grouped by the hardware fingerprint of their report.
+/// For each hardware fingerprint, the coefficient of variation is found for each benchmark,
+/// and then the mean, median, and max of those coefficients of variation are returned.
+/// This can be used to show that certain runner types are too noisy for benchmarking.
+/// Real changes in performance also add to the variance,
+/// so it is best to compare a time range where the benchmarked code did not change.
+/// Archived benchmarks are not included.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/perf/fingerprints",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_fingerprints_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    path_params: Path<ProjPerfParams>,
+    query_params: Query<JsonPerfFingerprintsQueryParams>,
+) -> Result<Encoded<ResponseOk<JsonPerfFingerprints>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let json = get_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        query_params.into_inner().into(),
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+async fn get_inner(
+    context: &ApiContext,
+    path_params: ProjPerfParams,
+    json_perf_fingerprints_query: JsonPerfFingerprintsQuery,
+    auth_user: Option<&AuthUser>,
+) -> Result<JsonPerfFingerprints, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+    let project_id = query_project.id;
+
+    let JsonPerfFingerprintsQuery {
+        branch,
+        testbed,
+        measure,
+        start_time,
+        end_time,
+    } = json_perf_fingerprints_query;
+
+    let query_branch = QueryBranch::from_name_id(conn_lock!(context), project_id, &branch)?;
+    let query_head = query_branch.head(conn_lock!(context))?;
+    let query_testbed = if let Some(testbed) = &testbed {
+        Some(QueryTestbed::from_name_id(
+            conn_lock!(context),
+            project_id,
+            testbed,
+        )?)
+    } else {
+        None
+    };
+    let query_measure = QueryMeasure::from_name_id(conn_lock!(context), project_id, &measure)?;
+
+    let times = Times {
+        start_time,
+        end_time,
+    };
+    let fingerprint_metrics = fingerprint_metrics(
+        conn_lock!(context),
+        query_head.id,
+        query_testbed.as_ref().map(|query_testbed| query_testbed.id),
+        query_measure.id,
+        times,
+    )?;
+    let mut builder = PerfFingerprintsBuilder::new();
+    for (benchmark_name, report, cpu_model, governor, virtualized, hypervisor, value) in
+        fingerprint_metrics
+    {
+        let fingerprint = JsonReportFingerprint {
+            cpu_model,
+            governor,
+            virtualized,
+            hypervisor,
+        };
+        builder.add_metric(fingerprint, report, benchmark_name.as_ref(), value);
+    }
+
+    let json_branch =
+        query_branch.into_json_for_head(conn_lock!(context), &query_project, &query_head, None)?;
+    let json_testbed =
+        query_testbed.map(|query_testbed| query_testbed.into_json_for_project(&query_project));
+    let json_measure = query_measure.into_json_for_project(&query_project);
+    Ok(JsonPerfFingerprints {
+        project: query_project.into_json(conn_lock!(context))?,
+        branch: json_branch,
+        testbed: json_testbed,
+        measure: json_measure,
+        start_time,
+        end_time,
+        fingerprints: builder.build(),
+    })
+}
+
+type FingerprintMetricQuery = (
+    BenchmarkName,
+    ReportUuid,
+    Option<NonEmpty>,
+    Option<NonEmpty>,
+    Option<bool>,
+    Option<NonEmpty>,
+    f64,
+);
+
+/// Get the metric value and report hardware fingerprint for every non-archived benchmark on the branch head and measure
+fn fingerprint_metrics(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: Option<TestbedId>,
+    measure_id: MeasureId,
+    times: Times,
+) -> Result<Vec<FingerprintMetricQuery>, HttpError> {
+    let mut query = schema::metric::table
+        .inner_join(
+            schema::report_benchmark::table
+                .inner_join(
+                    schema::report::table.inner_join(
+                        schema::version::table.inner_join(
+                            schema::head_version::table
+                                .on(schema::head_version::version_id.eq(schema::version::id)),
+                        ),
+                    ),
+                )
+                .inner_join(schema::benchmark::table),
+        )
+        // Filter for the branch through the `head_version` table, the same as the perf query
+        .filter(schema::head_version::head_id.eq(head_id))
+        .filter(schema::metric::measure_id.eq(measure_id))
+        .filter(schema::benchmark::archived.is_null())
+        .into_boxed();
+
+    if let Some(testbed_id) = testbed_id {
+        query = query.filter(schema::report::testbed_id.eq(testbed_id));
+    }
+    let Times {
+        start_time,
+        end_time,
+    } = times;
+    if let Some(start_time) = start_time {
+        query = query.filter(schema::report::start_time.ge(start_time));
+    }
+    if let Some(end_time) = end_time {
+        query = query.filter(schema::report::end_time.le(end_time));
+    }
+
+    query
+        .select((
+            schema::benchmark::name,
+            schema::report::uuid,
+            schema::report::fingerprint_cpu_model,
+            schema::report::fingerprint_governor,
+            schema::report::fingerprint_virtualized,
+            schema::report::fingerprint_hypervisor,
+            schema::metric::value,
+        ))
+        .load::<FingerprintMetricQuery>(conn)
+        .map_err(resource_not_found_err!(
+            Metric,
+            (head_id, testbed_id, measure_id)
+        ))
+}
@@ -0,0 +1,243 @@
+use bencher_json::{
+    project::{
+        head::{JsonVersion, VersionNumber},
+        perf_group::{JsonPerfGroupsQueryParams, PerfGroupsBuilder, PerfRollupPoint},
+        report::Iteration,
+    },
+    BenchmarkName, DateTime, GitHash, JsonPerfGroups, JsonPerfGroupsQuery, ReportUuid,
+};
+use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext};
+
+use crate::{
+    conn_lock,
+    context::{ApiContext, DbConnection},
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, ResponseOk},
+        Endpoint,
+    },
+    error::{bad_request_error, resource_not_found_err},
+    model::{
+        project::{
+            branch::{head::HeadId, QueryBranch},
+            measure::{MeasureId, QueryMeasure},
+            testbed::{QueryTestbed, TestbedId},
+            QueryProject,
+        },
+        user::auth::{AuthUser, PubBearerToken},
+    },
+    schema,
+};
+
+use super::{ProjPerfParams, Times};
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/perf/groups",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_groups_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjPerfParams>,
+    _query_params: Query<JsonPerfGroupsQueryParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// Query project performance metrics by benchmark group
+///
+/// Query the performance metrics for a branch, testbed, and measure, rolled up by benchmark group.
+/// Benchmark names are split into a tree of groups by a separator, such as `::` for `parser::json::large`.
+/// Each group includes the mean and max of the metrics for all of the benchmarks in the group, for each report iteration.
+/// Unlike the perf query, there is no limit on the number of benchmarks,
+/// as only the group rollups are returned and not the metrics for each benchmark.
+/// Archived benchmarks are not included.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/perf/groups",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_perf_groups_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    path_params: Path<ProjPerfParams>,
+    query_params: Query<JsonPerfGroupsQueryParams>,
+) -> Result<Encoded<ResponseOk<JsonPerfGroups>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    // Second round of marshaling
+    let json_perf_groups_query = query_params
+        .into_inner()
+        .try_into()
+        .map_err(bad_request_error)?;
+
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let json = get_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        json_perf_groups_query,
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+async fn get_inner(
+    context: &ApiContext,
+    path_params: ProjPerfParams,
+    json_perf_groups_query: JsonPerfGroupsQuery,
+    auth_user: Option<&AuthUser>,
+) -> Result<JsonPerfGroups, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+    let project_id = query_project.id;
+
+    let JsonPerfGroupsQuery {
+        branch,
+        testbed,
+        measure,
+        separator,
+        group,
+        depth,
+        start_time,
+        end_time,
+    } = json_perf_groups_query;
+
+    let query_branch = QueryBranch::from_name_id(conn_lock!(context), project_id, &branch)?;
+    let query_head = query_branch.head(conn_lock!(context))?;
+    let query_testbed = QueryTestbed::from_name_id(conn_lock!(context), project_id, &testbed)?;
+    let query_measure = QueryMeasure::from_name_id(conn_lock!(context), project_id, &measure)?;
+
+    let mut builder = PerfGroupsBuilder::new(&separator, group.as_deref(), depth);
+    let benchmark_names = schema::benchmark::table
+        .filter(schema::benchmark::project_id.eq(project_id))
+        .filter(schema::benchmark::archived.is_null())
+        .select(schema::benchmark::name)
+        .load::<BenchmarkName>(conn_lock!(context))
+        .map_err(resource_not_found_err!(Benchmark, &query_project))?;
+    for benchmark_name in &benchmark_names {
+        builder.add_benchmark(benchmark_name.as_ref());
+    }
+
+    let times = Times {
+        start_time,
+        end_time,
+    };
+    let group_metrics = group_metrics(
+        conn_lock!(context),
+        query_head.id,
+        query_testbed.id,
+        query_measure.id,
+        times,
+    )?;
+    for (benchmark_name, report, iteration, start_time, end_time, number, hash, value) in
+        group_metrics
+    {
+        let point = PerfRollupPoint {
+            report,
+            iteration,
+            start_time,
+            end_time,
+            version: JsonVersion { number, hash },
+        };
+        builder.add_metric(benchmark_name.as_ref(), point, value);
+    }
+
+    let json_branch =
+        query_branch.into_json_for_head(conn_lock!(context), &query_project, &query_head, None)?;
+    let json_testbed = query_testbed.into_json_for_project(&query_project);
+    let json_measure = query_measure.into_json_for_project(&query_project);
+    Ok(JsonPerfGroups {
+        project: query_project.into_json(conn_lock!(context))?,
+        branch: json_branch,
+        testbed: json_testbed,
+        measure: json_measure,
+        separator,
+        start_time,
+        end_time,
+        group: builder.build(),
+    })
+}
+
+type GroupMetricQuery = (
+    BenchmarkName,
+    ReportUuid,
+    Iteration,
+    DateTime,
+    DateTime,
+    VersionNumber,
+    Option<GitHash>,
+    f64,
+);
+
+/// Get the metric value for every non-archived benchmark on the branch head, testbed, and measure
+fn group_metrics(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    measure_id: MeasureId,
+    times: Times,
+) -> Result<Vec<GroupMetricQuery>, HttpError> {
+    let mut query = schema::metric::table
+        .inner_join(
+            schema::report_benchmark::table
+                .inner_join(
+                    schema::report::table.inner_join(
+                        schema::version::table.inner_join(
+                            schema::head_version::table
+                                .on(schema::head_version::version_id.eq(schema::version::id)),
+                        ),
+                    ),
+                )
+                .inner_join(schema::benchmark::table),
+        )
+        // Filter for the branch through the `head_version` table, the same as the perf query
+        .filter(schema::head_version::head_id.eq(head_id))
+        .filter(schema::report::testbed_id.eq(testbed_id))
+        .filter(schema::metric::measure_id.eq(measure_id))
+        .filter(schema::benchmark::archived.is_null())
+        .into_boxed();
+
+    let Times {
+        start_time,
+        end_time,
+    } = times;
+    if let Some(start_time) = start_time {
+        query = query.filter(schema::report::start_time.ge(start_time));
+    }
+    if let Some(end_time) = end_time {
+        query = query.filter(schema::report::end_time.le(end_time));
+    }
+
+    query
+        // Order the same as the perf query, so the rollups are in the same order
+        .order((
+            schema::version::number,
+            schema::report::start_time,
+            schema::report_benchmark::iteration,
+        ))
+        .select((
+            schema::benchmark::name,
+            schema::report::uuid,
+            schema::report_benchmark::iteration,
+            schema::report::start_time,
+            schema::report::end_time,
+            schema::version::number,
+            schema::version::hash,
+            schema::metric::value,
+        ))
+        .load::<GroupMetricQuery>(conn)
+        .map_err(resource_not_found_err!(
+            Metric,
+            (head_id, testbed_id, measure_id)
+        ))
+}
@@ -78,7 +78,7 @@ pub async fn proj_perf_img_get(
         .map_err(Into::into)
 }
 
-async fn get_inner(
+pub(super) async fn get_inner(
     context: &ApiContext,
     path_params: ProjPerfParams,
     title: Option<&str>,
@@ -2,12 +2,13 @@ use bencher_json::{
     project::{
         alert::JsonPerfAlert,
         head::{JsonVersion, VersionNumber},
+        measure::units::MeasureUnit,
         perf::{JsonPerfMetric, JsonPerfMetrics, JsonPerfQueryParams},
         report::Iteration,
         threshold::JsonThresholdModel,
     },
     BenchmarkUuid, BranchUuid, DateTime, GitHash, HeadUuid, JsonPerf, JsonPerfQuery, MeasureUuid,
-    ReportUuid, ResourceId, TestbedUuid,
+    NonEmpty, ReportUuid, ResourceId, TestbedUuid,
 };
 use diesel::{
     ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl, RunQueryDsl,
@@ -16,17 +17,20 @@ use diesel::{
 use dropshot::{endpoint, HttpError, Path, Query, RequestContext};
 use schemars::JsonSchema;
 use serde::Deserialize;
+use tokio::sync::Mutex;
 
 use crate::{
     conn_lock,
     context::{ApiContext, DbConnection},
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, ResponseOk},
         Endpoint,
     },
     error::{bad_request_error, resource_not_found_err},
     model::{
         project::{
+            annotation::QueryAnnotation,
             benchmark::QueryBenchmark,
             branch::{head::QueryHead, QueryBranch},
             measure::QueryMeasure,
@@ -42,9 +46,13 @@ use crate::{
     schema, view,
 };
 
+pub mod badge;
+pub mod embed;
+pub mod fingerprints;
+pub mod groups;
 pub mod img;
 
-const MAX_PERMUTATIONS: usize = 255;
+pub(crate) const MAX_PERMUTATIONS: usize = 255;
 
 #[derive(Deserialize, JsonSchema)]
 pub struct ProjPerfParams {
@@ -72,6 +80,8 @@ pub async fn proj_perf_options(
 /// The query results are every permutation of each branch, testbed, benchmark, and measure.
 /// There is a limit of 255 permutations for a single request.
 /// Therefore, only the first 255 permutations are returned.
+/// If a `unit` is provided, then the metric values and boundary limits are converted into that unit on the server.
+/// The unit must be a known unit of the same dimension as the units of every queried measure.
 /// If the project is public, then the user does not need to be authenticated.
 /// If the project is private, then the user must be authenticated and have `view` permissions for the project.
 #[endpoint {
@@ -84,7 +94,8 @@ pub async fn proj_perf_get(
     bearer_token: PubBearerToken,
     path_params: Path<ProjPerfParams>,
     query_params: Query<JsonPerfQueryParams>,
-) -> Result<ResponseOk<JsonPerf>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonPerf>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     // Second round of marshaling
     let json_perf_query = query_params
         .into_inner()
@@ -99,10 +110,12 @@ pub async fn proj_perf_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
 }
 
-async fn get_inner(
+pub(super) async fn get_inner(
     context: &ApiContext,
     path_params: ProjPerfParams,
     json_perf_query: JsonPerfQuery,
@@ -123,6 +136,8 @@ async fn get_inner(
         measures,
         start_time,
         end_time,
+        tags,
+        unit,
     } = json_perf_query;
 
     let times = Times {
@@ -130,8 +145,8 @@ async fn get_inner(
         end_time,
     };
 
-    let results = perf_results(
-        context,
+    let mut results = perf_results(
+        &context.database.connection,
         &project,
         &branches,
         &heads,
@@ -139,26 +154,45 @@ async fn get_inner(
         &benchmarks,
         &measures,
         times,
+        &tags,
     )
     .await?;
+    if let Some(unit) = unit {
+        let unit = MeasureUnit::parse(unit.as_ref()).map_err(bad_request_error)?;
+        for perf_metrics in &mut results {
+            perf_metrics.convert_units(unit).map_err(|e| {
+                bad_request_error(format!(
+                    "Failed to convert measure ({}) into the requested unit: {e}",
+                    perf_metrics.measure.name
+                ))
+            })?;
+        }
+    }
+
+    let annotations =
+        QueryAnnotation::in_time_range(conn_lock!(context), &project, start_time, end_time)?
+            .into_iter()
+            .map(|annotation| annotation.into_json_for_project(&project))
+            .collect();
 
     Ok(JsonPerf {
         project: project.into_json(conn_lock!(context))?,
         start_time,
         end_time,
         results,
+        annotations: Some(annotations),
     })
 }
 
 #[derive(Clone, Copy)]
-struct Times {
-    start_time: Option<DateTime>,
-    end_time: Option<DateTime>,
+pub(crate) struct Times {
+    pub(crate) start_time: Option<DateTime>,
+    pub(crate) end_time: Option<DateTime>,
 }
 
 #[allow(clippy::too_many_arguments)]
-async fn perf_results(
-    context: &ApiContext,
+pub(crate) async fn perf_results(
+    conn: &Mutex<DbConnection>,
     project: &QueryProject,
     branches: &[BranchUuid],
     heads: &[Option<HeadUuid>],
@@ -166,6 +200,7 @@ async fn perf_results(
     benchmarks: &[BenchmarkUuid],
     measures: &[MeasureUuid],
     times: Times,
+    tags: &[NonEmpty],
 ) -> Result<Vec<JsonPerfMetrics>, HttpError> {
     let permutations = branches.len() * testbeds.len() * benchmarks.len() * measures.len();
     let gt_max_permutations = permutations > MAX_PERMUTATIONS;
@@ -186,7 +221,7 @@ async fn perf_results(
                     }
 
                     let pq = perf_query(
-                        context,
+                        conn,
                         project,
                         *branch_uuid,
                         *head_uuid,
@@ -194,6 +229,7 @@ async fn perf_results(
                         *benchmark_uuid,
                         *measure_uuid,
                         times,
+                        tags,
                     )
                     .await?;
 
@@ -205,7 +241,7 @@ async fn perf_results(
                             perf_metrics.metrics.push(perf_metric);
                         } else {
                             perf_metrics = new_perf_metrics(
-                                conn_lock!(context),
+                                &mut *conn.lock().await,
                                 project,
                                 query_dimensions,
                                 perf_metric,
@@ -225,7 +261,7 @@ async fn perf_results(
 
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 async fn perf_query(
-    context: &ApiContext,
+    conn: &Mutex<DbConnection>,
     project: &QueryProject,
     branch_uuid: BranchUuid,
     head_uuid: Option<HeadUuid>,
@@ -233,6 +269,7 @@ async fn perf_query(
     benchmark_uuid: BenchmarkUuid,
     measure_uuid: MeasureUuid,
     times: Times,
+    tags: &[NonEmpty],
 ) -> Result<Vec<PerfQuery>, HttpError> {
     let mut query = view::metric_boundary::table
         .inner_join(
@@ -291,6 +328,10 @@ async fn perf_query(
     if let Some(end_time) = end_time {
         query = query.filter(schema::report::end_time.le(end_time));
     }
+    // Only include the reports for the given release tags, if any.
+    if !tags.is_empty() {
+        query = query.filter(schema::report::tag.eq_any(tags));
+    }
 
     let query = query
         // Order by the version number so that the oldest version is first.
@@ -335,6 +376,7 @@ async fn perf_query(
                     schema::model::window,
                     schema::model::lower_boundary,
                     schema::model::upper_boundary,
+                    schema::model::decay,
                     schema::model::created,
                     schema::model::replaced,
                 ),
@@ -342,7 +384,9 @@ async fn perf_query(
                     schema::alert::id,
                     schema::alert::uuid,
                     schema::alert::boundary_id,
+                    schema::alert::group_id,
                     schema::alert::boundary_limit,
+                    schema::alert::severity,
                     schema::alert::status,
                     schema::alert::modified,
                 ).nullable(),
@@ -358,7 +402,7 @@ async fn perf_query(
         // Acquire the lock on the database connection for every query.
         // This helps to avoid resource contention when the database is under heavy load.
         // This will make the perf query itself slower, but it will make the overall system more stable.
-        .load::<PerfQuery>(conn_lock!(context))
+        .load::<PerfQuery>(&mut *conn.lock().await)
         .map_err(resource_not_found_err!(Metric, (project,  branch_uuid, testbed_uuid, benchmark_uuid, measure_uuid)))
 }
 
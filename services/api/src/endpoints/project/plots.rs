@@ -15,6 +15,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
             CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
         },
@@ -90,7 +91,8 @@ pub async fn proj_plots_get(
     path_params: Path<ProjPlotsParams>,
     pagination_params: Query<ProjPlotsPagination>,
     query_params: Query<ProjPlotsQuery>,
-) -> Result<ResponseOk<JsonPlots>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonPlots>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new_pub(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -100,11 +102,13 @@ pub async fn proj_plots_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -267,8 +271,9 @@ pub async fn proj_plot_options(
 pub async fn proj_plot_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<ProjPlotParams>,
-) -> Result<ResponseOk<JsonPlot>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonPlot>>, HttpError> {
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -276,7 +281,9 @@ pub async fn proj_plot_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
 }
 
 async fn get_one_inner(
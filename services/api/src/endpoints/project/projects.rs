@@ -1,8 +1,9 @@
 use bencher_json::{
     project::{JsonUpdateProject, Visibility},
-    JsonDirection, JsonPagination, JsonProject, JsonProjects, ResourceId, ResourceName,
+    DateTime, JsonDirection, JsonPagination, JsonProject, JsonProjectTransfer, JsonProjects,
+    ResourceId, ResourceName,
 };
-use bencher_rbac::project::Permission;
+use bencher_rbac::{organization::Permission as OrganizationPermission, project::Permission};
 use diesel::{
     BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl, TextExpressionMethods,
 };
@@ -17,16 +18,22 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
-        endpoint::{CorsResponse, Delete, Get, Patch, ResponseDeleted, ResponseOk},
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Delete, Get, Patch, Post, ResponseDeleted, ResponseOk},
+        etag::{IfNoneMatch, Tagged},
         Endpoint,
     },
-    error::{resource_conflict_err, resource_not_found_err},
+    error::{
+        bad_request_error, conflict_error, forbidden_error, resource_conflict_err,
+        resource_not_found_err,
+    },
     model::{
-        project::{QueryProject, UpdateProject},
+        organization::QueryOrganization,
+        project::{stats::project_stats, QueryProject, UpdateProject},
         user::auth::{AuthUser, BearerToken, PubBearerToken},
     },
     schema,
-    util::{headers::TotalCount, search::Search},
+    util::{headers::TotalCount, search::Search, slug::ok_slug},
 };
 
 pub type ProjectsPagination = JsonPagination<ProjectsSort>;
@@ -45,6 +52,8 @@ pub struct ProjectsQuery {
     pub name: Option<ResourceName>,
     /// Search by project name, slug, or UUID.
     pub search: Option<Search>,
+    /// Filter by project visibility.
+    pub visibility: Option<Visibility>,
 }
 
 #[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
@@ -65,7 +74,8 @@ pub async fn projects_options(
 ///
 /// List all projects.
 /// If the user is not authenticated, then only public projects are returned.
-/// If the user is authenticated, then all public projects and
+/// If the user is authenticated, then all public projects,
+/// any internal project for an organization where the user is a member, and
 /// any private project where the user has `view` permissions are returned.
 /// By default, the projects are sorted in alphabetical order by name.
 /// The HTTP response header `X-Total-Count` contains the total number of projects.
@@ -79,7 +89,8 @@ pub async fn projects_get(
     bearer_token: PubBearerToken,
     pagination_params: Query<ProjectsPagination>,
     query_params: Query<ProjectsQuery>,
-) -> Result<ResponseOk<JsonProjects>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonProjects>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -88,11 +99,13 @@ pub async fn projects_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -152,6 +165,17 @@ fn get_ls_query<'q>(
                     .eq_any(projects)
                     .or(schema::project::visibility.eq(Visibility::Public)),
             );
+            // Any member of an organization can view its internal projects
+            #[cfg(feature = "plus")]
+            {
+                let organizations =
+                    auth_user.organizations(&context.rbac, OrganizationPermission::View);
+                query = query.or_filter(
+                    schema::project::visibility
+                        .eq(Visibility::Internal)
+                        .and(schema::project::organization_id.eq_any(organizations)),
+                );
+            }
         }
     } else {
         query = query.filter(schema::project::visibility.eq(Visibility::Public));
@@ -160,6 +184,9 @@ fn get_ls_query<'q>(
     if let Some(name) = query_params.name.as_ref() {
         query = query.filter(schema::project::name.eq(name));
     }
+    if let Some(visibility) = query_params.visibility {
+        query = query.filter(schema::project::visibility.eq(visibility));
+    }
     if let Some(search) = query_params.search.as_ref() {
         query = query.filter(
             schema::project::name
@@ -183,6 +210,12 @@ pub struct ProjectParams {
     pub project: ResourceId,
 }
 
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ProjectQuery {
+    /// Include resource counts and approximate storage usage for the project.
+    pub stats: Option<bool>,
+}
+
 #[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
 #[endpoint {
     method = OPTIONS,
@@ -192,6 +225,7 @@ pub struct ProjectParams {
 pub async fn project_options(
     _rqctx: RequestContext<ApiContext>,
     _path_params: Path<ProjectParams>,
+    _query_params: Query<ProjectQuery>,
 ) -> Result<CorsResponse, HttpError> {
     Ok(Endpoint::cors(&[Get.into(), Patch.into(), Delete.into()]))
 }
@@ -200,8 +234,10 @@ pub async fn project_options(
 ///
 /// View a project.
 /// If the user is not authenticated, then only a public project is available.
-/// If the user is authenticated, then any public project and
+/// If the user is authenticated, then any public project,
+/// any internal project for an organization where the user is a member, and
 /// any private project where the user has `view` permissions is available.
+/// If `stats` is set, then resource counts and approximate storage usage for the project are included.
 #[endpoint {
     method = GET,
     path =  "/v0/projects/{project}",
@@ -211,29 +247,43 @@ pub async fn project_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
     path_params: Path<ProjectParams>,
-) -> Result<ResponseOk<JsonProject>, HttpError> {
+    query_params: Query<ProjectQuery>,
+) -> Result<Encoded<Tagged<ResponseOk<JsonProject>>>, HttpError> {
+    let if_none_match = IfNoneMatch::new(&rqctx).await?;
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
         path_params.into_inner(),
+        query_params.into_inner(),
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    let response = if_none_match
+        .tag(Get::response_ok(json, auth_user.is_some()))
+        .await?;
+    accept_encoding.encode(response).await
 }
 
 async fn get_one_inner(
     context: &ApiContext,
     path_params: ProjectParams,
+    query_params: ProjectQuery,
     auth_user: Option<&AuthUser>,
 ) -> Result<JsonProject, HttpError> {
-    conn_lock!(context, |conn| QueryProject::is_allowed_public(
-        conn,
-        &context.rbac,
-        &path_params.project,
-        auth_user
-    )?
-    .into_json(conn))
+    conn_lock!(context, |conn| {
+        let query_project =
+            QueryProject::is_allowed_public(conn, &context.rbac, &path_params.project, auth_user)?;
+        let stats = query_params
+            .stats
+            .unwrap_or_default()
+            .then(|| project_stats(conn, query_project.id))
+            .transpose()?;
+        Ok(JsonProject {
+            stats,
+            ..query_project.into_json(conn)?
+        })
+    })
 }
 
 /// Update a project
@@ -367,3 +417,157 @@ async fn delete_inner(
 
     Ok(())
 }
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/transfer",
+    tags = ["projects"]
+}]
+pub async fn project_transfer_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjectParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Transfer a project
+///
+/// Transfer a project to another organization.
+/// All of the project's branches, testbeds, benchmarks, measures, reports, metrics, thresholds, and alerts are transferred along with it.
+/// The user must have `delete` permissions for the project and `create` permissions for the new organization.
+/// If the new organization already has a project with the same name, then a new `name` must be provided.
+/// ➕ Bencher Plus: A private project can only be transferred to an organization with a valid Bencher Plus subscription.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/transfer",
+    tags = ["projects"]
+}]
+pub async fn project_transfer_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjectParams>,
+    body: TypedBody<JsonProjectTransfer>,
+) -> Result<ResponseOk<JsonProject>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = transfer_inner(
+        &rqctx.log,
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_ok(json))
+}
+
+async fn transfer_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: ProjectParams,
+    json_transfer: JsonProjectTransfer,
+    auth_user: &AuthUser,
+) -> Result<JsonProject, HttpError> {
+    // Verify that the user is allowed to remove the project from its current organization
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Delete,
+    )?;
+
+    // Verify that the user is allowed to add the project to the new organization
+    let query_organization =
+        QueryOrganization::from_resource_id(conn_lock!(context), &json_transfer.organization)?;
+    if query_organization.id == query_project.organization_id {
+        return Err(bad_request_error(format!(
+            "Project ({project}) is already in organization ({organization})",
+            project = query_project.slug,
+            organization = query_organization.slug,
+        )));
+    }
+    context
+        .rbac
+        .is_allowed_organization(
+            auth_user,
+            OrganizationPermission::Create,
+            &query_organization,
+        )
+        .map_err(forbidden_error)?;
+
+    // Check project visibility for the new organization
+    #[cfg(not(feature = "plus"))]
+    QueryProject::is_visibility_public(query_project.visibility)?;
+    #[cfg(feature = "plus")]
+    PlanKind::check_for_organization(
+        conn_lock!(context),
+        context.biller.as_ref(),
+        &context.licensor,
+        &query_organization,
+        query_project.visibility,
+    )
+    .await?;
+
+    // Project names must be unique within an organization
+    let name = json_transfer
+        .name
+        .unwrap_or_else(|| query_project.name.clone());
+    let name_conflict = schema::project::table
+        .filter(schema::project::organization_id.eq(query_organization.id))
+        .filter(schema::project::name.eq(&name))
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Project,
+            (&query_organization, &name)
+        ))?;
+    if name_conflict > 0 {
+        return Err(conflict_error(format!(
+            "Organization ({organization}) already has a project named ({name}). Provide a new name for the project.",
+            organization = query_organization.slug,
+        )));
+    }
+    // Project slugs are unique across all organizations,
+    // so the current slug can always be kept.
+    let slug = match json_transfer.slug {
+        Some(slug) if slug != query_project.slug => ok_slug!(
+            conn_lock!(context),
+            &name,
+            Some(slug),
+            project,
+            QueryProject
+        )?,
+        _ => query_project.slug.clone(),
+    };
+
+    diesel::update(schema::project::table.filter(schema::project::id.eq(query_project.id)))
+        .set((
+            schema::project::organization_id.eq(query_organization.id),
+            schema::project::name.eq(&name),
+            schema::project::slug.eq(&slug),
+            schema::project::modified.eq(DateTime::now()),
+        ))
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(
+            Project,
+            (&query_project, &query_organization)
+        ))?;
+    slog::debug!(
+        log,
+        "Transferred project ({}) to organization ({})",
+        query_project.uuid,
+        query_organization.uuid
+    );
+
+    let new_query_project = QueryProject::get(conn_lock!(context), query_project.id)
+        .map_err(resource_not_found_err!(Project, query_project))?;
+
+    #[cfg(feature = "plus")]
+    if query_project.slug != new_query_project.slug {
+        context.delete_index(log, &query_project).await;
+        context.update_index(log, &new_query_project).await;
+    }
+
+    new_query_project.into_json(conn_lock!(context))
+}
@@ -0,0 +1,414 @@
+use bencher_json::{
+    JsonDirection, JsonNewSavedQuery, JsonPagination, JsonSavedQueries, JsonSavedQuery,
+    JsonUpdateSavedQuery, ResourceId, ResourceName, SavedQueryUuid,
+};
+use bencher_rbac::project::Permission;
+use diesel::{
+    BelongingToDsl, BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl,
+    TextExpressionMethods,
+};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{
+            CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
+        },
+        Endpoint,
+    },
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            saved_query::{check_recipients, InsertSavedQuery, QuerySavedQuery, UpdateSavedQuery},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken},
+    },
+    schema,
+    util::{headers::TotalCount, search::Search},
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjQueriesParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+pub type ProjQueriesPagination = JsonPagination<ProjQueriesSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjQueriesSort {
+    /// Sort by saved query name.
+    #[default]
+    Name,
+    /// Sort by the time the last summary was sent.
+    LastSent,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProjQueriesQuery {
+    /// Filter by saved query name, exact match.
+    pub name: Option<ResourceName>,
+    /// Search by saved query name or UUID.
+    pub search: Option<Search>,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/queries",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_queries_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjQueriesParams>,
+    _pagination_params: Query<ProjQueriesPagination>,
+    _query_params: Query<ProjQueriesQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List saved queries for a project
+///
+/// List all saved perf queries for a project.
+/// Saved queries include the email addresses that their summaries are sent to,
+/// so the user must be authenticated and have `view` permissions for the project,
+/// even if the project is public.
+/// By default, the saved queries are sorted by name.
+/// The HTTP response header `X-Total-Count` contains the total number of saved queries.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/queries",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_queries_get(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjQueriesParams>,
+    pagination_params: Query<ProjQueriesPagination>,
+    query_params: Query<ProjQueriesQuery>,
+) -> Result<Encoded<ResponseOk<JsonSavedQueries>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::new(&rqctx).await?;
+    let (json, total_count) = get_ls_inner(
+        rqctx.context(),
+        &auth_user,
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: &AuthUser,
+    path_params: ProjQueriesParams,
+    pagination_params: ProjQueriesPagination,
+    query_params: ProjQueriesQuery,
+) -> Result<(JsonSavedQueries, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+
+    let saved_queries = get_ls_query(&query_project, &pagination_params, &query_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QuerySavedQuery>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            SavedQuery,
+            (&query_project, &pagination_params, &query_params)
+        ))?;
+
+    let mut json_saved_queries = Vec::with_capacity(saved_queries.len());
+    for saved_query in saved_queries {
+        match saved_query.into_json_for_project(&query_project) {
+            Ok(saved_query) => json_saved_queries.push(saved_query),
+            Err(err) => {
+                debug_assert!(false, "{err}");
+                #[cfg(feature = "sentry")]
+                sentry::capture_error(&err);
+            },
+        }
+    }
+
+    let total_count = get_ls_query(&query_project, &pagination_params, &query_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            SavedQuery,
+            (&query_project, &pagination_params, &query_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_saved_queries.into(), total_count))
+}
+
+fn get_ls_query<'q>(
+    query_project: &'q QueryProject,
+    pagination_params: &ProjQueriesPagination,
+    query_params: &'q ProjQueriesQuery,
+) -> schema::saved_query::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let mut query = QuerySavedQuery::belonging_to(&query_project).into_boxed();
+
+    if let Some(name) = query_params.name.as_ref() {
+        query = query.filter(schema::saved_query::name.eq(name));
+    }
+    if let Some(search) = query_params.search.as_ref() {
+        query = query.filter(
+            schema::saved_query::name
+                .like(search)
+                .or(schema::saved_query::uuid.like(search)),
+        );
+    }
+
+    match pagination_params.order() {
+        ProjQueriesSort::Name => match pagination_params.direction {
+            Some(JsonDirection::Asc) | None => query.order(schema::saved_query::name.asc()),
+            Some(JsonDirection::Desc) => query.order(schema::saved_query::name.desc()),
+        },
+        ProjQueriesSort::LastSent => match pagination_params.direction {
+            Some(JsonDirection::Asc) => query.order(schema::saved_query::last_sent.asc()),
+            Some(JsonDirection::Desc) | None => query.order(schema::saved_query::last_sent.desc()),
+        },
+    }
+}
+
+/// Create a saved query
+///
+/// Create a saved perf query for a project.
+/// The user must have `create` permissions for the project.
+/// A summary of the saved query is sent on its schedule,
+/// to each of its email addresses and optionally to the project webhook.
+/// Each email address must belong to a member of the project or its organization.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/queries",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_query_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjQueriesParams>,
+    body: TypedBody<JsonNewSavedQuery>,
+) -> Result<ResponseCreated<JsonSavedQuery>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjQueriesParams,
+    json_saved_query: JsonNewSavedQuery,
+    auth_user: &AuthUser,
+) -> Result<JsonSavedQuery, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Create,
+    )?;
+
+    if let Some(emails) = &json_saved_query.emails {
+        check_recipients(conn_lock!(context), &query_project, emails)?;
+    }
+    let insert_saved_query = InsertSavedQuery::from_json(query_project.id, json_saved_query)?;
+    diesel::insert_into(schema::saved_query::table)
+        .values(&insert_saved_query)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(SavedQuery, insert_saved_query))?;
+
+    QuerySavedQuery::from_uuid(
+        conn_lock!(context),
+        query_project.id,
+        insert_saved_query.uuid,
+    )?
+    .into_json_for_project(&query_project)
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjQueryParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The UUID for a saved query.
+    pub query: SavedQueryUuid,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/queries/{query}",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_query_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjQueryParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Patch.into(), Delete.into()]))
+}
+
+/// View a saved query
+///
+/// View a saved perf query for a project.
+/// The user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/queries/{query}",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_query_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjQueryParams>,
+) -> Result<Encoded<ResponseOk<JsonSavedQuery>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjQueryParams,
+    auth_user: &AuthUser,
+) -> Result<JsonSavedQuery, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+
+    QuerySavedQuery::from_uuid(conn_lock!(context), query_project.id, path_params.query)?
+        .into_json_for_project(&query_project)
+}
+
+/// Update a saved query
+///
+/// Update a saved perf query for a project.
+/// The user must have `edit` permissions for the project.
+/// Each email address must belong to a member of the project or its organization.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/projects/{project}/queries/{query}",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_query_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjQueryParams>,
+    body: TypedBody<JsonUpdateSavedQuery>,
+) -> Result<ResponseOk<JsonSavedQuery>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = patch_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+async fn patch_inner(
+    context: &ApiContext,
+    path_params: ProjQueryParams,
+    json_saved_query: JsonUpdateSavedQuery,
+    auth_user: &AuthUser,
+) -> Result<JsonSavedQuery, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+
+    let query_saved_query =
+        QuerySavedQuery::from_uuid(conn_lock!(context), query_project.id, path_params.query)?;
+
+    if let Some(emails) = &json_saved_query.emails {
+        check_recipients(conn_lock!(context), &query_project, emails)?;
+    }
+    let update_saved_query = UpdateSavedQuery::from_json(json_saved_query.clone())?;
+    diesel::update(
+        schema::saved_query::table.filter(schema::saved_query::id.eq(query_saved_query.id)),
+    )
+    .set(&update_saved_query)
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(
+        SavedQuery,
+        (&query_saved_query, &json_saved_query)
+    ))?;
+
+    QuerySavedQuery::from_uuid(conn_lock!(context), query_project.id, path_params.query)?
+        .into_json_for_project(&query_project)
+}
+
+/// Delete a saved query
+///
+/// Delete a saved perf query for a project.
+/// The user must have `delete` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/queries/{query}",
+    tags = ["projects", "perf"]
+}]
+pub async fn proj_query_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjQueryParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjQueryParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Delete,
+    )?;
+
+    let query_saved_query =
+        QuerySavedQuery::from_uuid(conn_lock!(context), query_project.id, path_params.query)?;
+
+    diesel::delete(
+        schema::saved_query::table.filter(schema::saved_query::id.eq(query_saved_query.id)),
+    )
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(SavedQuery, query_saved_query))?;
+
+    Ok(())
+}
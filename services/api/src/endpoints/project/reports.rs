@@ -1,18 +1,26 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, iter};
 
+use bencher_adapter::{
+    adapters::magic::AdapterMagic, AdapterResultsArray, Settings as AdapterSettings,
+};
 use bencher_json::{
     project::{
         head::VersionNumber,
-        report::{JsonReportQuery, JsonReportQueryParams},
+        report::{
+            Adapter, JsonReportFailure, JsonReportFingerprint, JsonReportGit, JsonReportQuery,
+            JsonReportQueryParams, REPORT_FAILURE_STDERR_MAX_LEN, REPORT_FINGERPRINT_MAX_LEN,
+            REPORT_GIT_MAX_LEN, REPORT_TAG_MAX_LEN,
+        },
     },
-    JsonDirection, JsonNewReport, JsonPagination, JsonReport, JsonReports, ReportUuid, ResourceId,
+    DateTime, JsonDirection, JsonNewReport, JsonNewReportShard, JsonNewReports, JsonPagination,
+    JsonReport, JsonReportShard, JsonReports, NonEmpty, ReportUuid, ResourceId, Url,
 };
 use bencher_rbac::project::Permission;
 use diesel::{
     dsl::count, BelongingToDsl, BoolExpressionMethods, ExpressionMethods, JoinOnDsl, QueryDsl,
-    RunQueryDsl, SelectableHelper,
+    RunQueryDsl, SelectableHelper, TextExpressionMethods,
 };
-use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext};
 use http::StatusCode;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -24,23 +32,44 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Delete, Get, Post, ResponseCreated, ResponseDeleted, ResponseOk},
+        report_body::ReportBody,
+        version::AcceptVersion,
         Endpoint,
     },
-    error::{bad_request_error, issue_error, resource_conflict_err, resource_not_found_err},
+    error::{
+        bad_request_error, forbidden_error, issue_error, payload_too_large_error,
+        resource_conflict_err, resource_not_found_err,
+    },
     model::{
         project::{
             branch::{
-                head::HeadId,
+                head::{HeadId, QueryHead},
                 version::{QueryVersion, VersionId},
-                QueryBranch,
+                BranchId, QueryBranch,
             },
-            report::{results::ReportResults, InsertReport, QueryReport, ReportId},
-            testbed::QueryTestbed,
-            threshold::InsertThreshold,
+            expectation::Expectations,
+            flaky::FlakyMeasures,
+            report::{
+                failure::{insert_report_failures, MAX_REPORT_FAILURES},
+                note::{insert_report_notes, MAX_REPORT_NOTES},
+                raw::{get_report_raw, insert_report_raw, ReportRaw},
+                results::{check_strict, detector::ReferenceBranch, ReportResults},
+                shard::{InsertReportShard, QueryReportShard, ReceivedShards},
+                InsertReport, QueryReport, ReportId,
+            },
+            report_hook::ReportHook,
+            rule::BenchmarkRules,
+            testbed::{QueryTestbed, TestbedId},
+            threshold::{recompute::enqueue_recompute_after, InsertThreshold},
             QueryProject,
         },
-        user::auth::{AuthUser, BearerToken, PubBearerToken},
+        user::{
+            admin::AdminUser,
+            auth::{AuthUser, BearerToken, PubBearerToken},
+            UserId,
+        },
     },
     schema,
     util::{
@@ -49,6 +78,8 @@ use crate::{
     },
 };
 
+const MAX_IMPORT_REPORTS: usize = 255;
+
 #[derive(Deserialize, JsonSchema)]
 pub struct ProjReportsParams {
     /// The slug or UUID for a project.
@@ -63,6 +94,8 @@ pub enum ProjReportsSort {
     /// Sort by date time.
     #[default]
     DateTime,
+    /// Sort by branch version number, then by date time.
+    Version,
 }
 
 #[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
@@ -97,7 +130,9 @@ pub async fn proj_reports_get(
     path_params: Path<ProjReportsParams>,
     pagination_params: Query<ProjReportsPagination>,
     query_params: Query<JsonReportQueryParams>,
-) -> Result<ResponseOk<JsonReports>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonReports>>, HttpError> {
+    let accept_version = AcceptVersion::new(&rqctx).await?;
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     // Second round of marshaling
     let json_report_query = query_params
         .into_inner()
@@ -114,11 +149,13 @@ pub async fn proj_reports_get(
         json_report_query,
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            accept_version.json(json),
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -181,6 +218,7 @@ fn get_ls_query<'q>(
                 schema::branch::table.on(schema::head::branch_id.eq(schema::branch::id)),
             ))
             .inner_join(schema::testbed::table)
+            .inner_join(schema::version::table)
             .into_boxed();
 
     if let Some(branch) = query_params.branch.as_ref() {
@@ -189,6 +227,9 @@ fn get_ls_query<'q>(
     if let Some(testbed) = query_params.testbed.as_ref() {
         filter_testbed_name_id!(query, testbed);
     }
+    if let Some(tag) = query_params.tag.as_ref() {
+        query = query.filter(schema::report::tag.eq(tag));
+    }
 
     if let Some(start_time) = query_params.start_time {
         query = query.filter(schema::report::start_time.ge(start_time));
@@ -197,6 +238,16 @@ fn get_ls_query<'q>(
         query = query.filter(schema::report::end_time.le(end_time));
     }
 
+    if let Some(hash) = query_params.hash.as_ref() {
+        query = query.filter(schema::version::hash.like(format!("{hash}%")));
+    }
+    if let Some(start_version) = query_params.start_version {
+        query = query.filter(schema::version::number.ge(start_version));
+    }
+    if let Some(end_version) = query_params.end_version {
+        query = query.filter(schema::version::number.le(end_version));
+    }
+
     if let Some(true) = query_params.archived {
         query = query.filter(
             schema::branch::archived
@@ -224,6 +275,18 @@ fn get_ls_query<'q>(
                 schema::report::created.desc(),
             )),
         },
+        ProjReportsSort::Version => match pagination_params.direction {
+            Some(JsonDirection::Asc) => query.order((
+                schema::version::number.asc(),
+                schema::report::start_time.asc(),
+                schema::report::created.asc(),
+            )),
+            Some(JsonDirection::Desc) | None => query.order((
+                schema::version::number.desc(),
+                schema::report::start_time.desc(),
+                schema::report::created.desc(),
+            )),
+        },
     }
     .select(QueryReport::as_select()))
 }
@@ -235,21 +298,24 @@ type BoxedQuery<'q> = diesel::internal::table_macro::BoxedSelectStatement<
     diesel::internal::table_macro::FromClause<
         diesel::helper_types::InnerJoinQuerySource<
             diesel::helper_types::InnerJoinQuerySource<
-                schema::report::table,
-                diesel::internal::table_macro::SelectStatement<
-                    diesel::internal::table_macro::FromClause<
-                        diesel::helper_types::InnerJoinQuerySource<
-                            schema::head::table,
-                            schema::branch::table,
-                            diesel::dsl::Eq<
-                                schema::head::columns::branch_id,
-                                schema::branch::columns::id,
+                diesel::helper_types::InnerJoinQuerySource<
+                    schema::report::table,
+                    diesel::internal::table_macro::SelectStatement<
+                        diesel::internal::table_macro::FromClause<
+                            diesel::helper_types::InnerJoinQuerySource<
+                                schema::head::table,
+                                schema::branch::table,
+                                diesel::dsl::Eq<
+                                    schema::head::columns::branch_id,
+                                    schema::branch::columns::id,
+                                >,
                             >,
                         >,
                     >,
                 >,
+                schema::testbed::table,
             >,
-            schema::testbed::table,
+            schema::version::table,
         >,
     >,
     diesel::sqlite::Sqlite,
@@ -258,7 +324,10 @@ type BoxedQuery<'q> = diesel::internal::table_macro::BoxedSelectStatement<
 /// Create a report
 ///
 /// Create a report for a project.
-/// The user must have `create` permissions for the project.
+/// The user must have `report` permissions for the project.
+/// If the report includes thresholds, then the user must also have `create` permissions for the project.
+/// The raw benchmark results for the report must not exceed the server's maximum report size.
+/// If the project has a report hook, then it is run on the results before they are stored.
 /// If using the Bencher CLI, it is recommended to use the `bencher run` subcommand
 /// instead of trying to create a report manually.
 #[endpoint {
@@ -274,37 +343,134 @@ pub async fn proj_report_post(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
     path_params: Path<ProjReportsParams>,
-    body: TypedBody<JsonNewReport>,
+    body: ReportBody<JsonNewReport>,
 ) -> Result<ResponseCreated<JsonReport>, HttpError> {
+    let accept_version = AcceptVersion::new(&rqctx).await?;
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = post_inner(
         &rqctx.log,
         rqctx.context(),
-        path_params.into_inner(),
+        &path_params.into_inner(),
         body.into_inner(),
+        Vec::new(),
         &auth_user,
     )
     .await?;
-    Ok(Post::auth_response_created(json))
+    Ok(Post::auth_response_created(accept_version.json(json)))
 }
 
 async fn post_inner(
     log: &Logger,
     context: &ApiContext,
-    path_params: ProjReportsParams,
-    mut json_report: JsonNewReport,
+    path_params: &ProjReportsParams,
+    json_report: JsonNewReport,
+    shard_reports: Vec<JsonNewReport>,
     auth_user: &AuthUser,
 ) -> Result<JsonReport, HttpError> {
-    // Verify that the user is allowed
+    // Verify that the user is allowed to submit reports
     let project = QueryProject::is_allowed(
         conn_lock!(context),
         &context.rbac,
         &path_params.project,
         auth_user,
-        Permission::Create,
+        Permission::Report,
     )?;
+    // Creating thresholds along with the report requires the user to be able to create them directly
+    if json_report.thresholds.is_some() {
+        context
+            .rbac
+            .is_allowed_project(auth_user, Permission::Create, &project)
+            .map_err(forbidden_error)?;
+    }
+
+    create_report(
+        log,
+        context,
+        &project,
+        Some(auth_user.id()),
+        json_report,
+        shard_reports,
+    )
+    .await
+}
+
+/// Create a report for the `project` from `json_report`.
+/// The results of any `shard_reports` are processed into the same report,
+/// each with its own settings.
+/// If there is no `user_id`, then the report is anonymous.
+/// Anonymous reports are only allowed for unclaimed projects.
+#[allow(clippy::too_many_lines)]
+pub async fn create_report(
+    log: &Logger,
+    context: &ApiContext,
+    project: &QueryProject,
+    user_id: Option<UserId>,
+    mut json_report: JsonNewReport,
+    shard_reports: Vec<JsonNewReport>,
+) -> Result<JsonReport, HttpError> {
+    // Notes and links from all of the shards are attached to the same report
+    let notes = json_report
+        .notes
+        .take()
+        .into_iter()
+        .chain(shard_reports.iter().filter_map(|shard| shard.notes.clone()))
+        .flatten()
+        .collect::<Vec<_>>();
+    let links = json_report
+        .links
+        .take()
+        .into_iter()
+        .chain(shard_reports.iter().filter_map(|shard| shard.links.clone()))
+        .flatten()
+        .collect::<Vec<_>>();
+    check_notes_count(&notes, &links)?;
+    check_links(&links)?;
+    let failures = json_report
+        .failures
+        .take()
+        .into_iter()
+        .chain(
+            shard_reports
+                .iter()
+                .filter_map(|shard| shard.failures.clone()),
+        )
+        .flatten()
+        .collect::<Vec<_>>();
+    check_failures(&failures)?;
+    check_tag(json_report.tag.as_ref())?;
+    check_git(json_report.git.as_ref())?;
+    check_fingerprint(json_report.fingerprint.as_ref())?;
+
     let project_id = project.id;
 
+    let benchmark_rules = BenchmarkRules::for_project(conn_lock!(context), project_id)?;
+    let report_hook = ReportHook::for_project(conn_lock!(context), project_id)?;
+    // In strict mode, reject the report before anything is created for it
+    if project.strict || json_report.strict.unwrap_or_default() {
+        let raw_results = iter::once(&json_report)
+            .chain(shard_reports.iter())
+            .map(|report| {
+                (
+                    report.results.as_slice(),
+                    report.settings.unwrap_or_default(),
+                )
+            });
+        let threshold_measures = json_report
+            .thresholds
+            .as_ref()
+            .and_then(|thresholds| thresholds.models.as_ref())
+            .into_iter()
+            .flat_map(HashMap::keys);
+        check_strict(
+            conn_lock!(context),
+            project_id,
+            &benchmark_rules,
+            report_hook.as_ref(),
+            raw_results,
+            threshold_measures,
+        )?;
+    }
+
     // Get or create the branch and testbed
     let (branch_id, head_id) = QueryBranch::get_or_create(
         log,
@@ -314,6 +480,16 @@ async fn post_inner(
         json_report.start_point.as_ref(),
     )
     .await?;
+    // The reference branch must already exist
+    let reference_branch = if let Some(reference_branch) = json_report.reference_branch.as_ref() {
+        Some(ReferenceBranch::from_name_id(
+            conn_lock!(context),
+            project_id,
+            reference_branch,
+        )?)
+    } else {
+        None
+    };
     let testbed_id = QueryTestbed::get_or_create(context, project_id, &json_report.testbed).await?;
 
     // Insert the thresholds for the report
@@ -334,7 +510,7 @@ async fn post_inner(
         conn_lock!(context),
         context.biller.as_ref(),
         &context.licensor,
-        &project,
+        project,
     )
     .await?;
 
@@ -349,11 +525,16 @@ async fn post_inner(
     )?;
 
     let json_settings = json_report.settings.take().unwrap_or_default();
-    let adapter = json_settings.adapter.unwrap_or_default();
+    let adapter = resolve_adapter(
+        log,
+        json_settings.adapter.unwrap_or_default(),
+        &json_report.results,
+        AdapterSettings::new(json_settings.average),
+    );
 
     // Create a new report and add it to the database
     let insert_report = InsertReport::from_json(
-        auth_user.id(),
+        user_id,
         project_id,
         head_id,
         version_id,
@@ -379,40 +560,502 @@ async fn post_inner(
             )
         })?;
 
+    // Store the raw results, so the report can be reparsed with the current adapters
+    let raw_results = iter::once(ReportRaw {
+        results: json_report.results,
+        settings: json_settings,
+    })
+    .chain(shard_reports.into_iter().map(|mut shard_report| ReportRaw {
+        settings: shard_report.settings.take().unwrap_or_default(),
+        results: shard_report.results,
+    }))
+    .collect::<Vec<_>>();
+
+    insert_report_notes(conn_lock!(context), query_report.id, &notes, &links)?;
+    insert_report_failures(conn_lock!(context), query_report.id, &failures)?;
+    insert_report_raw(
+        conn_lock!(context),
+        query_report.id,
+        reference_branch,
+        &raw_results,
+    )?;
+
     #[cfg(feature = "plus")]
     let mut usage = 0;
 
     // Process and record the report results
-    let mut report_results =
-        ReportResults::new(project_id, branch_id, head_id, testbed_id, query_report.id);
-    let results_array = json_report
-        .results
-        .iter()
-        .map(AsRef::as_ref)
-        .collect::<Vec<&str>>();
-    let processed_report = report_results
-        .process(
-            log,
-            context,
-            &results_array,
-            adapter,
-            json_settings,
-            #[cfg(feature = "plus")]
-            &mut usage,
-        )
-        .await;
+    let version_number = QueryVersion::get(conn_lock!(context), version_id)?.number;
+    let expectations = Expectations::for_report(
+        conn_lock!(context),
+        branch_id,
+        query_report.start_time,
+        version_number,
+    )?;
+    let flaky_measures = FlakyMeasures::for_project(conn_lock!(context), project_id)?;
+    let mut report_results = ReportResults::new(
+        project_id,
+        branch_id,
+        head_id,
+        reference_branch,
+        testbed_id,
+        query_report.id,
+        benchmark_rules,
+        expectations,
+        flaky_measures,
+        report_hook,
+    );
+    let processed_report = process_raw_results(
+        log,
+        context,
+        &mut report_results,
+        adapter,
+        &raw_results,
+        #[cfg(feature = "plus")]
+        &mut usage,
+    )
+    .await;
 
     #[cfg(feature = "plus")]
     plan_kind
-        .check_usage(context.biller.as_ref(), &project, usage)
+        .check_usage(context.biller.as_ref(), project, usage)
         .await?;
 
     // Don't return the error from processing the report until after the metrics usage has been checked
     processed_report?;
+
+    // Attach any notes from the project rules and report hook, after the notes from the report itself
+    let mut generated_notes = report_results.rule_notes();
+    generated_notes.extend(report_results.hook_notes()?);
+    generated_notes.truncate(MAX_REPORT_NOTES.saturating_sub(notes.len()));
+    insert_report_notes(conn_lock!(context), query_report.id, &generated_notes, &[])?;
+
+    // If the report was backfilled before any existing reports,
+    // then the boundaries for those later reports should now include its metrics
+    let later_reports = schema::report::table
+        .filter(schema::report::head_id.eq(head_id))
+        .filter(schema::report::testbed_id.eq(testbed_id))
+        .filter(schema::report::start_time.gt(query_report.start_time))
+        .select(count(schema::report::id))
+        .first::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(Report, (head_id, testbed_id)))?;
+    if later_reports > 0 {
+        enqueue_recompute_after(
+            conn_lock!(context),
+            branch_id,
+            testbed_id,
+            query_report.start_time,
+        )?;
+    }
+
     // If the report was processed successfully, then return the report with the results
     query_report.into_json(log, context).await
 }
 
+/// Process the raw results for a report, with the report itself first followed by any shards.
+/// The report itself uses the resolved adapter, while each shard uses the adapter from its own settings.
+async fn process_raw_results(
+    log: &Logger,
+    context: &ApiContext,
+    report_results: &mut ReportResults,
+    adapter: Adapter,
+    raw_results: &[ReportRaw],
+    #[cfg(feature = "plus")] usage: &mut u32,
+) -> Result<(), HttpError> {
+    for (adapter, raw) in raw_results_adapters(adapter, raw_results) {
+        let results_array = raw.results.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+        report_results
+            .process(
+                log,
+                context,
+                &results_array,
+                adapter,
+                raw.settings,
+                #[cfg(feature = "plus")]
+                usage,
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+fn raw_results_adapters(
+    adapter: Adapter,
+    raw_results: &[ReportRaw],
+) -> impl Iterator<Item = (Adapter, &ReportRaw)> {
+    raw_results.iter().enumerate().map(move |(index, raw)| {
+        if index == 0 {
+            (adapter, raw)
+        } else {
+            (raw.settings.adapter.unwrap_or_default(), raw)
+        }
+    })
+}
+
+/// If the magic adapter is used, then resolve the specific adapter that matches the results.
+/// This resolved adapter is persisted on the report and used for all of its results,
+/// so every iteration is parsed the same way.
+fn resolve_adapter(
+    log: &Logger,
+    adapter: Adapter,
+    results: &[String],
+    settings: AdapterSettings,
+) -> Adapter {
+    if adapter != Adapter::Magic {
+        return adapter;
+    }
+    let Some(resolved) = results
+        .first()
+        .and_then(|results| AdapterMagic::resolve(results, settings))
+    else {
+        return adapter;
+    };
+    slog::debug!(log, "Resolved magic adapter to {resolved}");
+    resolved
+}
+
+fn check_notes_count(notes: &[NonEmpty], links: &[Url]) -> Result<(), HttpError> {
+    if notes.len() > MAX_REPORT_NOTES || links.len() > MAX_REPORT_NOTES {
+        return Err(bad_request_error(format!(
+            "Too many report notes ({notes}) or links ({links}). At most {MAX_REPORT_NOTES} of each may be attached to a report.",
+            notes = notes.len(),
+            links = links.len(),
+        )));
+    }
+    Ok(())
+}
+
+// Links are rendered as anchors in the Console and in pull request comments,
+// so schemes like `javascript:` and `data:` must never be stored.
+fn check_links(links: &[Url]) -> Result<(), HttpError> {
+    if let Some(link) = links.iter().find(|link| !link.is_web()) {
+        return Err(bad_request_error(format!(
+            "Report link ({link}) must use the http or https scheme."
+        )));
+    }
+    Ok(())
+}
+
+fn check_failures(failures: &[JsonReportFailure]) -> Result<(), HttpError> {
+    if failures.len() > MAX_REPORT_FAILURES {
+        return Err(bad_request_error(format!(
+            "Too many report failures ({failures}). At most {MAX_REPORT_FAILURES} may be attached to a report.",
+            failures = failures.len(),
+        )));
+    }
+    for failure in failures {
+        let stderr_len = failure.stderr.as_ref().map_or(0, String::len);
+        if stderr_len > REPORT_FAILURE_STDERR_MAX_LEN {
+            return Err(bad_request_error(format!(
+                "Report failure for iteration {iteration} has too much standard error output ({stderr_len} bytes). At most {REPORT_FAILURE_STDERR_MAX_LEN} bytes may be attached.",
+                iteration = failure.iteration,
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_tag(tag: Option<&NonEmpty>) -> Result<(), HttpError> {
+    let Some(tag) = tag else {
+        return Ok(());
+    };
+    let len = tag.as_ref().len();
+    if len > REPORT_TAG_MAX_LEN {
+        return Err(bad_request_error(format!(
+            "Report tag is too long ({len} bytes). At most {REPORT_TAG_MAX_LEN} bytes may be attached."
+        )));
+    }
+    // Tags are queried as a comma separated list
+    if tag.as_ref().contains(',') {
+        return Err(bad_request_error(format!(
+            "Report tag ({tag}) must not contain a comma."
+        )));
+    }
+    Ok(())
+}
+
+fn check_git(git: Option<&JsonReportGit>) -> Result<(), HttpError> {
+    let Some(git) = git else {
+        return Ok(());
+    };
+    for (field, value) in [
+        ("branch", &git.branch),
+        ("tag", &git.tag),
+        ("author", &git.author),
+        ("subject", &git.subject),
+    ] {
+        let len = value.as_ref().map_or(0, |value| value.as_ref().len());
+        if len > REPORT_GIT_MAX_LEN {
+            return Err(bad_request_error(format!(
+                "Report git {field} is too long ({len} bytes). At most {REPORT_GIT_MAX_LEN} bytes may be attached."
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_fingerprint(fingerprint: Option<&JsonReportFingerprint>) -> Result<(), HttpError> {
+    let Some(fingerprint) = fingerprint else {
+        return Ok(());
+    };
+    for (field, value) in [
+        ("CPU model", &fingerprint.cpu_model),
+        ("governor", &fingerprint.governor),
+        ("hypervisor", &fingerprint.hypervisor),
+    ] {
+        let len = value.as_ref().map_or(0, |value| value.as_ref().len());
+        if len > REPORT_FINGERPRINT_MAX_LEN {
+            return Err(bad_request_error(format!(
+                "Report fingerprint {field} is too long ({len} bytes). At most {REPORT_FINGERPRINT_MAX_LEN} bytes may be attached."
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/import",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_import_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjReportsParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Import reports
+///
+/// Create multiple reports for a project in a single request.
+/// The user must have `report` permissions for the project.
+/// If any of the reports include thresholds, then the user must also have `create` permissions for the project.
+/// This is useful for backfilling historical benchmark results.
+/// The reports are created in chronological order by their start time.
+/// If a report fails to be created, then all of the reports before it will have already been created.
+/// At most 255 reports may be imported per request.
+/// If using the Bencher CLI, it is recommended to use the `bencher import` subcommand.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/import",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_import_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjReportsParams>,
+    body: ReportBody<JsonNewReports>,
+) -> Result<ResponseCreated<JsonReports>, HttpError> {
+    let accept_version = AcceptVersion::new(&rqctx).await?;
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = import_inner(
+        &rqctx.log,
+        rqctx.context(),
+        &path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(accept_version.json(json)))
+}
+
+async fn import_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: &ProjReportsParams,
+    json_reports: JsonNewReports,
+    auth_user: &AuthUser,
+) -> Result<JsonReports, HttpError> {
+    let mut json_reports: Vec<JsonNewReport> = json_reports.into();
+    if json_reports.len() > MAX_IMPORT_REPORTS {
+        return Err(bad_request_error(format!(
+            "Too many reports to import ({count}). The maximum is {MAX_IMPORT_REPORTS}.",
+            count = json_reports.len()
+        )));
+    }
+
+    // Creating a report assumes that all reports are posted chronologically
+    json_reports.sort_by_key(|json_report| json_report.start_time.timestamp());
+
+    let mut reports = Vec::with_capacity(json_reports.len());
+    for json_report in json_reports {
+        reports.push(
+            post_inner(
+                log,
+                context,
+                path_params,
+                json_report,
+                Vec::new(),
+                auth_user,
+            )
+            .await?,
+        );
+    }
+    Ok(reports.into())
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/shards",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_shard_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjReportsParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Submit a report shard
+///
+/// Submit one shard of a benchmark run that has been split across multiple CI jobs.
+/// The user must have `report` permissions for the project.
+/// The shards are held by the server until all of the shards for the run have been received.
+/// Once the last shard is received, all of the shards are merged into a single report.
+/// Submitting the same shard index again replaces the previous shard.
+/// Shards that are not merged within 24 hours are discarded.
+/// The results of all of the shards count towards the maximum request body size of the server.
+/// If the merged report fails, then the shards are kept so that the run can be retried by submitting any of its shards again.
+/// At most 255 shards may be used per run.
+/// If using the Bencher CLI, it is recommended to use the `bencher run --shard` option.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/shards",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_shard_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjReportsParams>,
+    body: ReportBody<JsonNewReportShard>,
+) -> Result<ResponseCreated<JsonReportShard>, HttpError> {
+    let accept_version = AcceptVersion::new(&rqctx).await?;
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = shard_inner(
+        &rqctx.log,
+        rqctx.context(),
+        &path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(accept_version.json(json)))
+}
+
+// Each shard is limited by the maximum request body size,
+// so the results of all of the shards together are held to the same limit.
+fn check_shards_size(
+    context: &ApiContext,
+    json_report: &JsonNewReport,
+    shard_reports: &[JsonNewReport],
+) -> Result<(), HttpError> {
+    let results_bytes = json_report.results_bytes()
+        + shard_reports
+            .iter()
+            .map(JsonNewReport::results_bytes)
+            .sum::<usize>();
+    if results_bytes > context.request_body_max_bytes {
+        return Err(payload_too_large_error(format!(
+            "Report shard results are too large ({results_bytes} bytes). The maximum size for this server is {max} bytes. Split the benchmarks into multiple runs, or ask your server administrator to increase `server.request_body_max_bytes`.",
+            max = context.request_body_max_bytes
+        )));
+    }
+    Ok(())
+}
+
+async fn shard_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: &ProjReportsParams,
+    json_shard: JsonNewReportShard,
+    auth_user: &AuthUser,
+) -> Result<JsonReportShard, HttpError> {
+    let JsonNewReportShard {
+        key,
+        index,
+        count,
+        report,
+    } = json_shard;
+    // Verify that the user is allowed to submit reports
+    let project_id = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Report,
+    )?
+    .id;
+
+    QueryReportShard::delete_expired(conn_lock!(context), project_id)?;
+    let insert_shard =
+        InsertReportShard::from_json(project_id, key.clone(), index, count, &report)?;
+    let shards = match insert_shard.receive(conn_lock!(context))? {
+        ReceivedShards::CountMismatch { shard_count } => {
+            return Err(bad_request_error(format!(
+                "Report shard count ({count}) does not match the count ({shard_count}) of the shards already received for key ({key})",
+            )));
+        },
+        ReceivedShards::Pending { received } => {
+            return Ok(JsonReportShard {
+                key,
+                index,
+                count,
+                received,
+                report: None,
+            });
+        },
+        ReceivedShards::Complete(shards) => shards,
+    };
+    let received = u32::try_from(shards.len()).unwrap_or(u32::MAX);
+
+    let merged = async {
+        let (json_report, shard_reports) = QueryReportShard::merge(&shards)?;
+        check_shards_size(context, &json_report, &shard_reports)?;
+        post_inner(
+            log,
+            context,
+            path_params,
+            json_report,
+            shard_reports,
+            auth_user,
+        )
+        .await
+    }
+    .await;
+    let report = match merged {
+        Ok(report) => report,
+        Err(mut e) => {
+            // The shards were claimed, so put them back for the run to be retried.
+            // If they can not be put back, then report both errors,
+            // as the run can then only be retried by submitting all of its shards again.
+            if let Err(restore_error) = QueryReportShard::restore(conn_lock!(context), shards) {
+                slog::error!(
+                    log,
+                    "Failed to restore report shards ({key}): {restore_error}"
+                );
+                e.external_message = format!(
+                    "{}\nFailed to restore the report shards, so all of the shards for the run ({key}) must be submitted again: {}",
+                    e.external_message, restore_error.external_message
+                );
+                e.internal_message = format!(
+                    "{}\nFailed to restore report shards: {}",
+                    e.internal_message, restore_error.internal_message
+                );
+            }
+            return Err(e);
+        },
+    };
+
+    Ok(JsonReportShard {
+        key,
+        index,
+        count,
+        received,
+        report: Some(report),
+    })
+}
+
 #[derive(Deserialize, JsonSchema)]
 pub struct ProjReportParams {
     /// The slug or UUID for a project.
@@ -447,8 +1090,10 @@ pub async fn proj_report_options(
 pub async fn proj_report_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
+    accept_version: AcceptVersion,
     path_params: Path<ProjReportParams>,
-) -> Result<ResponseOk<JsonReport>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonReport>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         &rqctx.log,
@@ -457,7 +1102,12 @@ pub async fn proj_report_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    accept_encoding
+        .encode(Get::response_ok(
+            accept_version.json(json),
+            auth_user.is_some(),
+        ))
+        .await
 }
 
 async fn get_one_inner(
@@ -520,17 +1170,27 @@ async fn delete_inner(
         Permission::Delete,
     )?;
 
-    let (report_id, version_id) = QueryReport::belonging_to(&query_project)
-        .filter(schema::report::uuid.eq(path_params.report.to_string()))
-        .select((schema::report::id, schema::report::version_id))
-        .first::<(ReportId, VersionId)>(conn_lock!(context))
-        .map_err(resource_not_found_err!(
-            Report,
-            (&query_project, path_params.report)
-        ))?;
+    let (report_id, version_id, branch_id, testbed_id, start_time) =
+        QueryReport::belonging_to(&query_project)
+            .inner_join(schema::head::table)
+            .filter(schema::report::uuid.eq(path_params.report.to_string()))
+            .select((
+                schema::report::id,
+                schema::report::version_id,
+                schema::head::branch_id,
+                schema::report::testbed_id,
+                schema::report::start_time,
+            ))
+            .first::<(ReportId, VersionId, BranchId, TestbedId, DateTime)>(conn_lock!(context))
+            .map_err(resource_not_found_err!(
+                Report,
+                (&query_project, path_params.report)
+            ))?;
     diesel::delete(schema::report::table.filter(schema::report::id.eq(report_id)))
         .execute(conn_lock!(context))
         .map_err(resource_conflict_err!(Report, report_id))?;
+    // The boundaries for any later reports may have used the deleted metrics
+    enqueue_recompute_after(conn_lock!(context), branch_id, testbed_id, start_time)?;
 
     // If there are no more reports for this version, delete the version
     // This is necessary because multiple reports can use the same version via a git hash
@@ -609,3 +1269,166 @@ async fn delete_inner(
 
     Ok(())
 }
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/reports/{report}/reparse",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_report_reparse_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjReportParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Reparse a report
+///
+/// Reparse the stored raw results for a report with the current benchmark harness adapters.
+/// The existing metrics for the report are replaced in place, along with their boundaries and alerts.
+/// This is useful for healing historical data after an adapter bug has been fixed.
+/// The user must be a server admin.
+/// Only reports created after raw results began being stored can be reparsed.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/reports/{report}/reparse",
+    tags = ["projects", "reports"]
+}]
+pub async fn proj_report_reparse_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_version: AcceptVersion,
+    path_params: Path<ProjReportParams>,
+) -> Result<ResponseOk<JsonReport>, HttpError> {
+    let admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = reparse_inner(
+        &rqctx.log,
+        rqctx.context(),
+        path_params.into_inner(),
+        admin_user.user(),
+    )
+    .await?;
+    Ok(Post::auth_response_ok(accept_version.json(json)))
+}
+
+#[allow(clippy::too_many_lines)]
+async fn reparse_inner(
+    log: &Logger,
+    context: &ApiContext,
+    path_params: ProjReportParams,
+    auth_user: &AuthUser,
+) -> Result<JsonReport, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+
+    let query_report = QueryReport::belonging_to(&query_project)
+        .filter(schema::report::uuid.eq(path_params.report.to_string()))
+        .first::<QueryReport>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Report,
+            (&query_project, path_params.report)
+        ))?;
+
+    let (raw_results, reference_branch) = get_report_raw(conn_lock!(context), query_report.id)?;
+    let Some(report_raw) = raw_results.first() else {
+        return Err(bad_request_error(format!(
+            "Report ({uuid}) does not have any raw results stored, so it can not be reparsed. Only reports created after raw results began being stored can be reparsed.",
+            uuid = query_report.uuid
+        )));
+    };
+    let adapter = resolve_adapter(
+        log,
+        report_raw.settings.adapter.unwrap_or_default(),
+        &report_raw.results,
+        AdapterSettings::new(report_raw.settings.average),
+    );
+
+    // Make sure that all of the raw results can still be parsed before removing the existing metrics
+    for (adapter, raw) in raw_results_adapters(adapter, &raw_results) {
+        let results_array = raw.results.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+        AdapterResultsArray::new(
+            &results_array,
+            adapter,
+            AdapterSettings::new(raw.settings.average),
+        )
+        .map_err(|e| {
+            bad_request_error(format!(
+                "Failed to reparse report ({uuid}) with adapter ({adapter} | {settings:?}): {e}",
+                uuid = query_report.uuid,
+                settings = raw.settings,
+            ))
+        })?;
+    }
+
+    // This will cascade and delete all of the metrics, boundaries, and alerts for the report
+    diesel::delete(
+        schema::report_benchmark::table
+            .filter(schema::report_benchmark::report_id.eq(query_report.id)),
+    )
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(ReportBenchmark, query_report.id))?;
+    diesel::update(schema::report::table.filter(schema::report::id.eq(query_report.id)))
+        .set(schema::report::adapter.eq(adapter))
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Report, (query_report.id, adapter)))?;
+
+    // Reparsing replaces the existing metrics, so it does not count toward the metrics usage
+    #[cfg(feature = "plus")]
+    let mut usage = 0;
+
+    let branch_id = QueryHead::get(conn_lock!(context), query_report.head_id)?.branch_id;
+    let benchmark_rules = BenchmarkRules::for_project(conn_lock!(context), query_project.id)?;
+    let version_number = QueryVersion::get(conn_lock!(context), query_report.version_id)?.number;
+    let expectations = Expectations::for_report(
+        conn_lock!(context),
+        branch_id,
+        query_report.start_time,
+        version_number,
+    )?;
+    // The report hook renames and drops the results, but its notes are only attached when the report is created
+    let report_hook = ReportHook::for_project(conn_lock!(context), query_project.id)?;
+    let flaky_measures = FlakyMeasures::for_project(conn_lock!(context), query_project.id)?;
+    let mut report_results = ReportResults::new(
+        query_project.id,
+        branch_id,
+        query_report.head_id,
+        reference_branch,
+        query_report.testbed_id,
+        query_report.id,
+        benchmark_rules,
+        expectations,
+        flaky_measures,
+        report_hook,
+    );
+    process_raw_results(
+        log,
+        context,
+        &mut report_results,
+        adapter,
+        &raw_results,
+        #[cfg(feature = "plus")]
+        &mut usage,
+    )
+    .await?;
+
+    // The boundaries for any later reports may have used the replaced metrics
+    enqueue_recompute_after(
+        conn_lock!(context),
+        branch_id,
+        query_report.testbed_id,
+        query_report.start_time,
+    )?;
+
+    QueryReport {
+        adapter,
+        ..query_report
+    }
+    .into_json(log, context)
+    .await
+}
@@ -0,0 +1,399 @@
+use bencher_json::{
+    project::rule::JsonUpdateRule, JsonDirection, JsonNewRule, JsonPagination, JsonRule, JsonRules,
+    ResourceId, RuleUuid,
+};
+use bencher_rbac::project::Permission;
+use diesel::{
+    BelongingToDsl, BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl,
+    TextExpressionMethods,
+};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{
+            CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
+        },
+        Endpoint,
+    },
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            rule::{InsertRule, QueryRule, UpdateRule},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken, PubBearerToken},
+    },
+    schema,
+    util::{headers::TotalCount, search::Search},
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjRulesParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+pub type ProjRulesPagination = JsonPagination<ProjRulesSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjRulesSort {
+    /// Sort by rule creation date time.
+    #[default]
+    Created,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ProjRulesQuery {
+    /// Search by rule pattern, replacement, or UUID.
+    pub search: Option<Search>,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/rules",
+    tags = ["projects", "rules"]
+}]
+pub async fn proj_rules_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjRulesParams>,
+    _pagination_params: Query<ProjRulesPagination>,
+    _query_params: Query<ProjRulesQuery>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Post.into()]))
+}
+
+/// List rules for a project
+///
+/// List all benchmark name rules for a project.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+/// By default, the rules are sorted in the order that they are applied, oldest first.
+/// The HTTP response header `X-Total-Count` contains the total number of rules.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/rules",
+    tags = ["projects", "rules"]
+}]
+pub async fn proj_rules_get(
+    rqctx: RequestContext<ApiContext>,
+    path_params: Path<ProjRulesParams>,
+    pagination_params: Query<ProjRulesPagination>,
+    query_params: Query<ProjRulesQuery>,
+) -> Result<Encoded<ResponseOk<JsonRules>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::new_pub(&rqctx).await?;
+    let (json, total_count) = get_ls_inner(
+        rqctx.context(),
+        auth_user.as_ref(),
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+        query_params.into_inner(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
+}
+
+async fn get_ls_inner(
+    context: &ApiContext,
+    auth_user: Option<&AuthUser>,
+    path_params: ProjRulesParams,
+    pagination_params: ProjRulesPagination,
+    query_params: ProjRulesQuery,
+) -> Result<(JsonRules, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    let rules = get_ls_query(&query_project, &pagination_params, &query_params)
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryRule>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Rule,
+            (&query_project, &pagination_params, &query_params)
+        ))?;
+
+    // Drop connection lock before iterating
+    let json_rules = rules
+        .into_iter()
+        .map(|rule| rule.into_json_for_project(&query_project))
+        .collect();
+
+    let total_count = get_ls_query(&query_project, &pagination_params, &query_params)
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            Rule,
+            (&query_project, &pagination_params, &query_params)
+        ))?
+        .try_into()?;
+
+    Ok((json_rules, total_count))
+}
+
+fn get_ls_query<'q>(
+    query_project: &'q QueryProject,
+    pagination_params: &ProjRulesPagination,
+    query_params: &'q ProjRulesQuery,
+) -> schema::rule::BoxedQuery<'q, diesel::sqlite::Sqlite> {
+    let mut query = QueryRule::belonging_to(query_project).into_boxed();
+
+    if let Some(search) = query_params.search.as_ref() {
+        query = query.filter(
+            schema::rule::pattern
+                .like(search)
+                .or(schema::rule::replacement.like(search))
+                .or(schema::rule::uuid.like(search)),
+        );
+    }
+
+    match pagination_params.order() {
+        ProjRulesSort::Created => match pagination_params.direction {
+            Some(JsonDirection::Asc) | None => {
+                query.order((schema::rule::created.asc(), schema::rule::id.asc()))
+            },
+            Some(JsonDirection::Desc) => {
+                query.order((schema::rule::created.desc(), schema::rule::id.desc()))
+            },
+        },
+    }
+}
+
+/// Create a rule
+///
+/// Create a benchmark name rule for a project.
+/// Rules rewrite benchmark names when results are reported,
+/// so that names with volatile components, such as temporary paths or random seeds,
+/// collapse into a single stable benchmark.
+/// Each rule replaces every match of its regular expression `pattern` with its `replacement`,
+/// and rules are applied in the order that they were created.
+/// If multiple benchmarks in a report are rewritten to the same name, then only the first one is kept,
+/// and a note is added to the report for each benchmark that was skipped.
+/// Rules are not applied to any existing benchmarks.
+/// The user must have `create` permissions for the project.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/rules",
+    tags = ["projects", "rules"]
+}]
+pub async fn proj_rule_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjRulesParams>,
+    body: TypedBody<JsonNewRule>,
+) -> Result<ResponseCreated<JsonRule>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_created(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    path_params: ProjRulesParams,
+    json_rule: JsonNewRule,
+    auth_user: &AuthUser,
+) -> Result<JsonRule, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Create,
+    )?;
+
+    let insert_rule = InsertRule::from_json(query_project.id, json_rule)?;
+
+    diesel::insert_into(schema::rule::table)
+        .values(&insert_rule)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Rule, insert_rule))?;
+
+    QueryRule::get_with_uuid(conn_lock!(context), &query_project, insert_rule.uuid)
+        .map(|rule| rule.into_json_for_project(&query_project))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjRuleParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The UUID for a rule.
+    pub rule: RuleUuid,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/rules/{rule}",
+    tags = ["projects", "rules"]
+}]
+pub async fn proj_rule_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjRuleParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Patch.into(), Delete.into()]))
+}
+
+/// View a rule
+///
+/// View a benchmark name rule for a project.
+/// If the project is public, then the user does not need to be authenticated.
+/// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/rules/{rule}",
+    tags = ["projects", "rules"]
+}]
+pub async fn proj_rule_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjRuleParams>,
+) -> Result<Encoded<ResponseOk<JsonRule>>, HttpError> {
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        auth_user.as_ref(),
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::response_ok(json, auth_user.is_some()))
+        .await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjRuleParams,
+    auth_user: Option<&AuthUser>,
+) -> Result<JsonRule, HttpError> {
+    let query_project = QueryProject::is_allowed_public(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+    )?;
+
+    QueryRule::get_with_uuid(conn_lock!(context), &query_project, path_params.rule)
+        .map(|rule| rule.into_json_for_project(&query_project))
+}
+
+/// Update a rule
+///
+/// Update a benchmark name rule for a project.
+/// The user must have `edit` permissions for the project.
+#[endpoint {
+    method = PATCH,
+    path =  "/v0/projects/{project}/rules/{rule}",
+    tags = ["projects", "rules"]
+}]
+pub async fn proj_rule_patch(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjRuleParams>,
+    body: TypedBody<JsonUpdateRule>,
+) -> Result<ResponseOk<JsonRule>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = patch_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Patch::auth_response_ok(json))
+}
+
+async fn patch_inner(
+    context: &ApiContext,
+    path_params: ProjRuleParams,
+    json_rule: JsonUpdateRule,
+    auth_user: &AuthUser,
+) -> Result<JsonRule, HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+
+    let query_rule =
+        QueryRule::get_with_uuid(conn_lock!(context), &query_project, path_params.rule)?;
+    let update_rule = UpdateRule::try_from(json_rule.clone())?;
+    diesel::update(schema::rule::table.filter(schema::rule::id.eq(query_rule.id)))
+        .set(&update_rule)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Rule, (&query_rule, &json_rule)))?;
+
+    QueryRule::get(conn_lock!(context), query_rule.id)
+        .map(|rule| rule.into_json_for_project(&query_project))
+        .map_err(resource_not_found_err!(Rule, query_rule))
+}
+
+/// Delete a rule
+///
+/// Delete a benchmark name rule for a project.
+/// The user must have `delete` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/rules/{rule}",
+    tags = ["projects", "rules"]
+}]
+pub async fn proj_rule_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjRuleParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjRuleParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Delete,
+    )?;
+
+    let query_rule =
+        QueryRule::get_with_uuid(conn_lock!(context), &query_project, path_params.rule)?;
+
+    diesel::delete(schema::rule::table.filter(schema::rule::id.eq(query_rule.id)))
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Rule, query_rule))?;
+
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use bencher_json::{JsonNewOrganization, JsonNewProject, JsonNewRun, JsonRun, Slug};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, RequestContext};
+use http::StatusCode;
+use slog::Logger;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        endpoint::{CorsResponse, Post, ResponseCreated},
+        report_body::ReportBody,
+        Endpoint,
+    },
+    error::{
+        forbidden_error, issue_error, resource_conflict_err, resource_not_found_err,
+        unauthorized_error,
+    },
+    model::{
+        organization::{InsertOrganization, QueryOrganization},
+        project::{
+            unclaimed_project::{InsertUnclaimedProject, QueryUnclaimedProject, CLAIM_TOKEN_TTL},
+            InsertProject, QueryProject,
+        },
+        user::auth::BEARER_TOKEN_FORMAT,
+    },
+    schema,
+};
+
+use super::reports::create_report;
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/run",
+    tags = ["projects", "reports"]
+}]
+pub async fn run_options(_rqctx: RequestContext<ApiContext>) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Create a report for an unclaimed project
+///
+/// Create a report without authentication for an unclaimed project.
+/// If a project with the slug does not exist, then it is created as an unclaimed project in a new organization,
+/// and a claim token is returned along with the report.
+/// The claim token can be used to claim the project for an organization.
+/// It is only returned when the unclaimed project is created.
+/// Once the claim token expires, the unclaimed project is removed,
+/// and the next report for the slug creates a new unclaimed project with a new claim token.
+/// Reports for a claimed project must be submitted with authentication to `/v0/projects/{project}/reports`.
+/// This endpoint is only available if the `server.unclaimed_projects` server config setting is enabled.
+#[endpoint {
+    method = POST,
+    path =  "/v0/run",
+    tags = ["projects", "reports"]
+}]
+pub async fn run_post(
+    rqctx: RequestContext<ApiContext>,
+    body: ReportBody<JsonNewRun>,
+) -> Result<ResponseCreated<JsonRun>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
+    let json = post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
+    Ok(Post::pub_response_created(json))
+}
+
+async fn post_inner(
+    log: &Logger,
+    context: &ApiContext,
+    json_run: JsonNewRun,
+) -> Result<JsonRun, HttpError> {
+    if !context.unclaimed_projects {
+        return Err(forbidden_error(format!(
+            "Unclaimed projects are not enabled on this server. Submit the report with authentication instead.\n{BEARER_TOKEN_FORMAT}"
+        )));
+    }
+
+    let JsonNewRun { project, report } = json_run;
+    // Only create the project if it does not exist, and not on any other database error
+    let existing_project = schema::project::table
+        .filter(schema::project::slug.eq(&project))
+        .first::<QueryProject>(conn_lock!(context))
+        .optional()
+        .map_err(resource_not_found_err!(Project, project))?;
+    if let Some(query_project) = existing_project {
+        match QueryUnclaimedProject::get_for_project(conn_lock!(context), query_project.id)? {
+            // Anonymous reports are only allowed until the project is claimed
+            None => {
+                return Err(unauthorized_error(format!(
+                    "Project ({project}) has already been claimed. Submit the report with authentication instead.\n{BEARER_TOKEN_FORMAT}"
+                )));
+            },
+            // The claim token has expired, so the project can never be claimed.
+            // Start over with a new unclaimed project and claim token.
+            Some(query_unclaimed_project) if query_unclaimed_project.is_expired() => {
+                QueryUnclaimedProject::delete_organization(
+                    conn_lock!(context),
+                    query_project.organization_id,
+                )?;
+                slog::debug!(log, "Removed expired unclaimed project: {query_project:?}");
+            },
+            Some(_) => {
+                let report =
+                    create_report(log, context, &query_project, None, report, Vec::new()).await?;
+                return Ok(JsonRun {
+                    report,
+                    claim: None,
+                });
+            },
+        }
+    }
+
+    let query_project = create_unclaimed_project(log, context, project).await?;
+    let claim = context
+        .token_key
+        .new_claim(CLAIM_TOKEN_TTL, query_project.uuid)
+        .map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to create claim token",
+                &format!(
+                    "Failed to create claim token for unclaimed project ({project})",
+                    project = query_project.uuid
+                ),
+                e,
+            )
+        })?;
+    match create_report(log, context, &query_project, None, report, Vec::new()).await {
+        Ok(report) => Ok(JsonRun {
+            report,
+            claim: Some(claim),
+        }),
+        Err(e) => {
+            // The claim token is only returned when the project is created,
+            // so remove the project if its first report fails.
+            // Otherwise, the project could never be claimed.
+            QueryUnclaimedProject::delete_organization(
+                conn_lock!(context),
+                query_project.organization_id,
+            )?;
+            Err(e)
+        },
+    }
+}
+
+/// Create an unclaimed project, in a new organization that has no members.
+async fn create_unclaimed_project(
+    log: &Logger,
+    context: &ApiContext,
+    slug: Slug,
+) -> Result<QueryProject, HttpError> {
+    let json_organization = JsonNewOrganization {
+        name: slug.clone().into(),
+        slug: Some(slug.clone()),
+    };
+    let insert_organization =
+        InsertOrganization::from_json(conn_lock!(context), json_organization)?;
+    diesel::insert_into(schema::organization::table)
+        .values(&insert_organization)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Organization, insert_organization))?;
+    let query_organization = schema::organization::table
+        .filter(schema::organization::uuid.eq(&insert_organization.uuid))
+        .first::<QueryOrganization>(conn_lock!(context))
+        .map_err(resource_not_found_err!(Organization, insert_organization))?;
+
+    // Unclaimed projects are always public
+    let json_project = JsonNewProject {
+        name: slug.clone().into(),
+        slug: Some(slug),
+        url: None,
+        visibility: None,
+        time_zone: None,
+        week_start: None,
+        strict: None,
+    };
+    let insert_project =
+        InsertProject::from_json(conn_lock!(context), &query_organization, json_project)?;
+    let query_project = insert_project.insert_with_defaults(log, context).await?;
+
+    let insert_unclaimed_project = InsertUnclaimedProject::new(query_project.id);
+    diesel::insert_into(schema::unclaimed_project::table)
+        .values(&insert_unclaimed_project)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(
+            UnclaimedProject,
+            insert_unclaimed_project
+        ))?;
+    slog::debug!(log, "Created unclaimed project: {query_project:?}");
+
+    Ok(query_project)
+}
@@ -15,9 +15,11 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
             CorsResponse, Delete, Get, Patch, Post, ResponseCreated, ResponseDeleted, ResponseOk,
         },
+        etag::{IfNoneMatch, Tagged},
         Endpoint,
     },
     error::{resource_conflict_err, resource_not_found_err},
@@ -91,7 +93,8 @@ pub async fn proj_testbeds_get(
     path_params: Path<ProjTestbedsParams>,
     pagination_params: Query<ProjTestbedsPagination>,
     query_params: Query<ProjTestbedsQuery>,
-) -> Result<ResponseOk<JsonTestbeds>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonTestbeds>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new_pub(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -101,11 +104,13 @@ pub async fn proj_testbeds_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            json,
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -272,8 +277,10 @@ pub async fn proj_testbed_options(
 pub async fn proj_testbed_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<ProjTestbedParams>,
-) -> Result<ResponseOk<JsonTestbed>, HttpError> {
+) -> Result<Encoded<Tagged<ResponseOk<JsonTestbed>>>, HttpError> {
+    let if_none_match = IfNoneMatch::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -281,7 +288,10 @@ pub async fn proj_testbed_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    let response = if_none_match
+        .tag(Get::response_ok(json, auth_user.is_some()))
+        .await?;
+    accept_encoding.encode(response).await
 }
 
 async fn get_one_inner(
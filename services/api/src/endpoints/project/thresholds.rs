@@ -1,7 +1,7 @@
 use bencher_json::{
     project::threshold::{
-        JsonNewThreshold, JsonRemoveModel, JsonThreshold, JsonThresholdQuery,
-        JsonThresholdQueryParams, JsonUpdateModel, JsonUpdateThreshold,
+        JsonNewRecompute, JsonNewThreshold, JsonRecompute, JsonRemoveModel, JsonThreshold,
+        JsonThresholdQuery, JsonThresholdQueryParams, JsonUpdateModel, JsonUpdateThreshold,
     },
     JsonDirection, JsonPagination, JsonThresholds, ModelUuid, ResourceId, ThresholdUuid,
 };
@@ -18,9 +18,12 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{
-            CorsResponse, Delete, Get, Post, Put, ResponseCreated, ResponseDeleted, ResponseOk,
+            CorsResponse, Delete, Get, Post, Put, ResponseAccepted, ResponseCreated,
+            ResponseDeleted, ResponseOk,
         },
+        version::AcceptVersion,
         Endpoint,
     },
     error::{
@@ -32,7 +35,9 @@ use crate::{
             branch::QueryBranch,
             measure::QueryMeasure,
             testbed::QueryTestbed,
-            threshold::{model::QueryModel, InsertThreshold, QueryThreshold},
+            threshold::{
+                model::QueryModel, recompute::enqueue_recompute, InsertThreshold, QueryThreshold,
+            },
             QueryProject,
         },
         user::auth::{AuthUser, BearerToken, PubBearerToken},
@@ -94,7 +99,9 @@ pub async fn proj_thresholds_get(
     path_params: Path<ProjThresholdsParams>,
     pagination_params: Query<ProjThresholdsPagination>,
     query_params: Query<JsonThresholdQueryParams>,
-) -> Result<ResponseOk<JsonThresholds>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonThresholds>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let accept_version = AcceptVersion::new(&rqctx).await?;
     // Second round of marshaling
     let json_threshold_query = query_params
         .into_inner()
@@ -110,11 +117,13 @@ pub async fn proj_thresholds_get(
         json_threshold_query,
     )
     .await?;
-    Ok(Get::response_ok_with_total_count(
-        json,
-        auth_user.is_some(),
-        total_count,
-    ))
+    accept_encoding
+        .encode(Get::response_ok_with_total_count(
+            accept_version.json(json),
+            auth_user.is_some(),
+            total_count,
+        ))
+        .await
 }
 
 async fn get_ls_inner(
@@ -250,6 +259,7 @@ pub async fn proj_threshold_post(
     path_params: Path<ProjThresholdsParams>,
     body: TypedBody<JsonNewThreshold>,
 ) -> Result<ResponseCreated<JsonThreshold>, HttpError> {
+    let accept_version = AcceptVersion::new(&rqctx).await?;
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = post_inner(
         rqctx.context(),
@@ -258,7 +268,7 @@ pub async fn proj_threshold_post(
         &auth_user,
     )
     .await?;
-    Ok(Post::auth_response_created(json))
+    Ok(Post::auth_response_created(accept_version.json(json)))
 }
 
 async fn post_inner(
@@ -354,7 +364,9 @@ pub async fn proj_threshold_get(
     bearer_token: PubBearerToken,
     path_params: Path<ProjThresholdParams>,
     query_params: Query<ProjThresholdQuery>,
-) -> Result<ResponseOk<JsonThreshold>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonThreshold>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let accept_version = AcceptVersion::new(&rqctx).await?;
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(
         rqctx.context(),
@@ -363,7 +375,12 @@ pub async fn proj_threshold_get(
         auth_user.as_ref(),
     )
     .await?;
-    Ok(Get::response_ok(json, auth_user.is_some()))
+    accept_encoding
+        .encode(Get::response_ok(
+            accept_version.json(json),
+            auth_user.is_some(),
+        ))
+        .await
 }
 
 async fn get_one_inner(
@@ -419,6 +436,7 @@ pub async fn proj_threshold_put(
     path_params: Path<ProjThresholdParams>,
     body: TypedBody<JsonUpdateThreshold>,
 ) -> Result<ResponseOk<JsonThreshold>, HttpError> {
+    let accept_version = AcceptVersion::new(&rqctx).await?;
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = put_inner(
         rqctx.context(),
@@ -427,7 +445,7 @@ pub async fn proj_threshold_put(
         &auth_user,
     )
     .await?;
-    Ok(Put::auth_response_ok(json))
+    Ok(Put::auth_response_ok(accept_version.json(json)))
 }
 
 async fn put_inner(
@@ -513,3 +531,90 @@ async fn delete_inner(
 
     Ok(())
 }
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/thresholds/{threshold}/recompute",
+    tags = ["projects", "thresholds"]
+}]
+pub async fn proj_threshold_recompute_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjThresholdParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Recompute threshold boundaries
+///
+/// Recompute the boundaries for a threshold in the background.
+/// The user must have `edit` permissions for the project.
+/// Each boundary is recomputed using the model that created it
+/// and the metrics that are currently available as of its report.
+/// Alerts are then created, updated, or dismissed to match the recomputed boundaries.
+/// This is useful after reports have been deleted or backfilled.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/thresholds/{threshold}/recompute",
+    tags = ["projects", "thresholds"]
+}]
+pub async fn proj_threshold_recompute_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjThresholdParams>,
+    body: TypedBody<JsonNewRecompute>,
+) -> Result<ResponseAccepted<JsonRecompute>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_recompute_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_accepted(json))
+}
+
+async fn post_recompute_inner(
+    context: &ApiContext,
+    path_params: ProjThresholdParams,
+    json_recompute: JsonNewRecompute,
+    auth_user: &AuthUser,
+) -> Result<JsonRecompute, HttpError> {
+    let JsonNewRecompute {
+        start_time,
+        end_time,
+    } = json_recompute;
+    if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+        if start_time.timestamp() > end_time.timestamp() {
+            return Err(bad_request_error(format!(
+                "The start time ({start_time}) must be before the end time ({end_time})"
+            )));
+        }
+    }
+
+    // Verify that the user is allowed
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Edit,
+    )?;
+
+    let query_threshold =
+        QueryThreshold::get_with_uuid(conn_lock!(context), &query_project, path_params.threshold)?;
+
+    enqueue_recompute(
+        conn_lock!(context),
+        query_threshold.uuid,
+        start_time,
+        end_time,
+    )?;
+
+    Ok(JsonRecompute {
+        threshold: query_threshold.uuid,
+        start_time,
+        end_time,
+    })
+}
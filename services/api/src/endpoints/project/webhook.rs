@@ -0,0 +1,393 @@
+use bencher_json::{
+    JsonDirection, JsonNewWebhook, JsonPagination, JsonWebhook, JsonWebhookDeliveries,
+    JsonWebhookDelivery, ResourceId, WebhookDeliveryUuid,
+};
+use bencher_rbac::project::Permission;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::{endpoint, HttpError, Path, Query, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Delete, Get, Post, Put, ResponseDeleted, ResponseOk},
+        Endpoint,
+    },
+    error::{bad_request_error, not_found_error, resource_conflict_err, resource_not_found_err},
+    model::{
+        project::{
+            webhook::{InsertWebhook, QueryWebhook, QueryWebhookDelivery},
+            QueryProject,
+        },
+        user::auth::{AuthUser, BearerToken},
+    },
+    schema,
+    util::headers::TotalCount,
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjWebhookParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/webhook",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjWebhookParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Put.into(), Delete.into()]))
+}
+
+/// View the alert webhook for a project
+///
+/// View the alert webhook for a project.
+/// The webhook secret is not included.
+/// The user must have `view` permissions for the project.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/webhook",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<ProjWebhookParams>,
+) -> Result<Encoded<ResponseOk<JsonWebhook>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: ProjWebhookParams,
+    auth_user: &AuthUser,
+) -> Result<JsonWebhook, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+
+    let query_webhook = get_webhook(context, &query_project).await?;
+    Ok(query_webhook.into_json(&query_project, None))
+}
+
+/// Create or replace the alert webhook for a project
+///
+/// Create the alert webhook for a project with a new secret.
+/// If the webhook already exists, then its URL and secret are replaced.
+/// The secret is only returned by this endpoint.
+/// Each new alert for the project is sent to the webhook URL as a JSON `POST` request,
+/// signed with the secret in the `X-Bencher-Signature-256` header.
+/// The signature is the hex encoded HMAC SHA-256 digest of the request body, prefixed with `sha256=`.
+/// Failed deliveries are retried with exponential backoff until they run out of attempts.
+/// The webhook URL must have a publicly routable host, and redirects are not followed.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = PUT,
+    path =  "/v0/projects/{project}/webhook",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_put(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhookParams>,
+    body: TypedBody<JsonNewWebhook>,
+) -> Result<ResponseOk<JsonWebhook>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = put_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Put::auth_response_ok(json))
+}
+
+async fn put_inner(
+    context: &ApiContext,
+    path_params: ProjWebhookParams,
+    json_webhook: JsonNewWebhook,
+    auth_user: &AuthUser,
+) -> Result<JsonWebhook, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    if !json_webhook.url.has_public_host() {
+        return Err(bad_request_error(format!(
+            "Webhook URL ({url}) must have a publicly routable host",
+            url = json_webhook.url
+        )));
+    }
+    let secret = InsertWebhook::upsert(conn_lock!(context), query_project.id, json_webhook.url)?;
+
+    let query_webhook = get_webhook(context, &query_project).await?;
+    Ok(query_webhook.into_json(&query_project, Some(secret)))
+}
+
+/// Delete the alert webhook for a project
+///
+/// Delete the alert webhook for a project.
+/// Any pending deliveries are dropped, and new alerts are no longer delivered.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = DELETE,
+    path =  "/v0/projects/{project}/webhook",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_delete(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhookParams>,
+) -> Result<ResponseDeleted, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    delete_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Delete::auth_response_deleted())
+}
+
+async fn delete_inner(
+    context: &ApiContext,
+    path_params: ProjWebhookParams,
+    auth_user: &AuthUser,
+) -> Result<(), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+
+    let query_webhook = get_webhook(context, &query_project).await?;
+    diesel::delete(schema::webhook::table.filter(schema::webhook::id.eq(query_webhook.id)))
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Webhook, query_webhook))?;
+    diesel::delete(
+        schema::webhook_delivery::table
+            .filter(schema::webhook_delivery::project_id.eq(query_project.id)),
+    )
+    .execute(conn_lock!(context))
+    .map_err(resource_conflict_err!(WebhookDelivery, query_project.id))?;
+
+    Ok(())
+}
+
+async fn get_webhook(
+    context: &ApiContext,
+    query_project: &QueryProject,
+) -> Result<QueryWebhook, HttpError> {
+    QueryWebhook::get_for_project(conn_lock!(context), query_project.id)?.ok_or_else(|| {
+        not_found_error(format!(
+            "Webhook not found for project ({})",
+            query_project.uuid
+        ))
+    })
+}
+
+pub type ProjWebhookFailedPagination = JsonPagination<ProjWebhookFailedSort>;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjWebhookFailedSort {
+    /// Sort by the date time that the delivery failed.
+    #[default]
+    Failed,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/webhook/failed",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_failed_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjWebhookParams>,
+    _pagination_params: Query<ProjWebhookFailedPagination>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// List failed webhook deliveries for a project
+///
+/// List the alert webhook deliveries for a project that ran out of attempts.
+/// This is the dead-letter queue for the webhook,
+/// and each delivery includes the response status code and error from its last attempt.
+/// A failed delivery can be sent again with the redeliver endpoint.
+/// The user must have `view` permissions for the project.
+/// By default, the deliveries are sorted by the date time they failed in reverse chronological order.
+/// The HTTP response header `X-Total-Count` contains the total number of failed deliveries.
+#[endpoint {
+    method = GET,
+    path =  "/v0/projects/{project}/webhook/failed",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_failed_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhookParams>,
+    pagination_params: Query<ProjWebhookFailedPagination>,
+) -> Result<Encoded<ResponseOk<JsonWebhookDeliveries>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let (json, total_count) = get_failed_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        pagination_params.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
+}
+
+async fn get_failed_inner(
+    context: &ApiContext,
+    path_params: ProjWebhookParams,
+    pagination_params: ProjWebhookFailedPagination,
+    auth_user: &AuthUser,
+) -> Result<(JsonWebhookDeliveries, TotalCount), HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::View,
+    )?;
+
+    let mut query = schema::webhook_delivery::table
+        .filter(schema::webhook_delivery::project_id.eq(query_project.id))
+        .filter(schema::webhook_delivery::failed.is_not_null())
+        .into_boxed();
+    query = match pagination_params.order() {
+        ProjWebhookFailedSort::Failed => match pagination_params.direction {
+            Some(JsonDirection::Asc) => query.order(schema::webhook_delivery::failed.asc()),
+            Some(JsonDirection::Desc) | None => {
+                query.order(schema::webhook_delivery::failed.desc())
+            },
+        },
+    };
+    let deliveries = query
+        .offset(pagination_params.offset())
+        .limit(pagination_params.limit())
+        .load::<QueryWebhookDelivery>(conn_lock!(context))
+        .map_err(resource_not_found_err!(
+            WebhookDelivery,
+            (&query_project, &pagination_params)
+        ))?;
+
+    // Separate out these queries to prevent a deadlock when getting the conn_lock
+    let mut json_deliveries = Vec::with_capacity(deliveries.len());
+    for delivery in deliveries {
+        json_deliveries.push(delivery.into_json(conn_lock!(context))?);
+    }
+
+    let total_count = schema::webhook_delivery::table
+        .filter(schema::webhook_delivery::project_id.eq(query_project.id))
+        .filter(schema::webhook_delivery::failed.is_not_null())
+        .count()
+        .get_result::<i64>(conn_lock!(context))
+        .map_err(resource_not_found_err!(WebhookDelivery, &query_project))?
+        .try_into()?;
+
+    Ok((json_deliveries.into(), total_count))
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ProjWebhookDeliveryParams {
+    /// The slug or UUID for a project.
+    pub project: ResourceId,
+    /// The UUID for a webhook delivery.
+    pub delivery: WebhookDeliveryUuid,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/projects/{project}/webhook/failed/{delivery}",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_redeliver_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<ProjWebhookDeliveryParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Redeliver a failed webhook delivery for a project
+///
+/// Send a failed alert webhook delivery for a project again.
+/// The delivery is removed from the failed deliveries and queued to be sent,
+/// and it is retried with exponential backoff like a new delivery.
+/// Only deliveries that ran out of attempts can be redelivered.
+/// The user must have `manage` permissions for the project.
+#[endpoint {
+    method = POST,
+    path =  "/v0/projects/{project}/webhook/failed/{delivery}",
+    tags = ["projects", "alerts"]
+}]
+pub async fn proj_webhook_redeliver_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<ProjWebhookDeliveryParams>,
+) -> Result<ResponseOk<JsonWebhookDelivery>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = redeliver_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    Ok(Post::auth_response_ok(json))
+}
+
+async fn redeliver_inner(
+    context: &ApiContext,
+    path_params: ProjWebhookDeliveryParams,
+    auth_user: &AuthUser,
+) -> Result<JsonWebhookDelivery, HttpError> {
+    let query_project = QueryProject::is_allowed(
+        conn_lock!(context),
+        &context.rbac,
+        &path_params.project,
+        auth_user,
+        Permission::Manage,
+    )?;
+    // The webhook must still exist to redeliver to it
+    get_webhook(context, &query_project).await?;
+
+    let query_delivery = QueryWebhookDelivery::from_uuid(
+        conn_lock!(context),
+        query_project.id,
+        path_params.delivery,
+    )?;
+    if query_delivery.failed.is_none() {
+        return Err(bad_request_error(format!(
+            "Webhook delivery ({}) has not failed, so it can not be redelivered",
+            query_delivery.uuid
+        )));
+    }
+    query_delivery.redeliver(conn_lock!(context))?;
+
+    let query_delivery = QueryWebhookDelivery::from_uuid(
+        conn_lock!(context),
+        query_project.id,
+        path_params.delivery,
+    )?;
+    query_delivery.into_json(conn_lock!(context))
+}
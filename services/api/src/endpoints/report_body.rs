@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use dropshot::{
+    ApiEndpointBodyContentType, ExclusiveExtractor, ExtractorMetadata, HttpError, RequestContext,
+    ServerContext, TypedBody,
+};
+use http::{header, HeaderMap};
+use hyper::body::HttpBody;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use crate::error::{bad_request_error, payload_too_large_error};
+
+/// A JSON report request body.
+/// Like `TypedBody`, the body is limited to `server.request_body_max_bytes` before it is parsed,
+/// but a report that is too large is rejected with an error that explains the limit.
+/// The API documentation is the same as for `TypedBody`.
+#[derive(Debug)]
+pub struct ReportBody<T>(T);
+
+impl<T> ReportBody<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[async_trait]
+impl<T> ExclusiveExtractor for ReportBody<T>
+where
+    T: JsonSchema + DeserializeOwned + Send + Sync + 'static,
+{
+    async fn from_request<Context: ServerContext>(
+        rqctx: &RequestContext<Context>,
+        request: hyper::Request<hyper::Body>,
+    ) -> Result<Self, HttpError> {
+        let max_bytes = rqctx.server.config.request_body_max_bytes;
+        let (parts, body) = request.into_parts();
+        read_report(&parts.headers, body, max_bytes).await.map(Self)
+    }
+
+    fn metadata(body_content_type: ApiEndpointBodyContentType) -> ExtractorMetadata {
+        TypedBody::<T>::metadata(body_content_type)
+    }
+}
+
+async fn read_report<T>(
+    headers: &HeaderMap,
+    mut body: hyper::Body,
+    max_bytes: usize,
+) -> Result<T, HttpError>
+where
+    T: DeserializeOwned,
+{
+    // Reject a report with a known size up front, without reading any of it
+    let content_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|content_length| content_length.to_str().ok())
+        .and_then(|content_length| content_length.parse::<usize>().ok());
+    if let Some(content_length) = content_length {
+        if content_length > max_bytes {
+            return Err(report_too_large_error(max_bytes));
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(content_length.unwrap_or_default());
+    while let Some(chunk) = body.data().await {
+        let chunk =
+            chunk.map_err(|e| bad_request_error(format!("Failed to read report body: {e}")))?;
+        if bytes.len() + chunk.len() > max_bytes {
+            return Err(report_too_large_error(max_bytes));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    serde_json::from_slice(&bytes)
+        .map_err(|e| bad_request_error(format!("Failed to parse report body: {e}")))
+}
+
+fn report_too_large_error(max_bytes: usize) -> HttpError {
+    payload_too_large_error(format!(
+        "Report is larger than the maximum report size for this server ({max_bytes} bytes). Split the benchmarks into multiple reports, or ask your server administrator to increase `server.request_body_max_bytes`."
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use http::{header, HeaderMap, HeaderValue, StatusCode};
+    use pretty_assertions::assert_eq;
+
+    use super::read_report;
+
+    const MAX_BYTES: usize = 32;
+
+    type Report = HashMap<String, u32>;
+
+    fn headers(content_length: Option<usize>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(content_length) = content_length {
+            headers.insert(header::CONTENT_LENGTH, HeaderValue::from(content_length));
+        }
+        headers
+    }
+
+    // A body without a known size, as if it were sent with chunked transfer encoding
+    fn chunked(chunks: &[&'static str]) -> hyper::Body {
+        let (mut sender, body) = hyper::Body::channel();
+        let chunks = chunks.to_vec();
+        tokio::spawn(async move {
+            for chunk in chunks {
+                if sender.send_data(chunk.into()).await.is_err() {
+                    break;
+                }
+            }
+        });
+        body
+    }
+
+    #[tokio::test]
+    async fn test_read_report() {
+        let json = r#"{"a": 1, "b": 2}"#;
+        let report: Report = read_report(&headers(Some(json.len())), json.into(), MAX_BYTES)
+            .await
+            .unwrap();
+        assert_eq!(report.get("a"), Some(&1));
+        assert_eq!(report.get("b"), Some(&2));
+
+        let report: Report = read_report(&headers(None), chunked(&[r#"{"a":"#, " 1}"]), MAX_BYTES)
+            .await
+            .unwrap();
+        assert_eq!(report.get("a"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn test_read_report_content_length_too_large() {
+        // The body itself is never read
+        let err = read_report::<Report>(&headers(Some(MAX_BYTES + 1)), "{}".into(), MAX_BYTES)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_read_report_chunked_too_large() {
+        let err = read_report::<Report>(
+            &headers(None),
+            chunked(&[r#"{"a": 1, "#, r#""b": 2, "#, r#""c": 3, "#, r#""dd": 4}"#]),
+            MAX_BYTES,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_read_report_invalid() {
+        let err = read_report::<Report>(&headers(None), "{".into(), MAX_BYTES)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code, StatusCode::BAD_REQUEST);
+    }
+}
@@ -36,6 +36,7 @@ pub async fn auth_confirm_post(
     rqctx: RequestContext<ApiContext>,
     body: TypedBody<JsonConfirm>,
 ) -> Result<ResponseOk<JsonAuthUser>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
     let json = post_inner(rqctx.context(), body.into_inner()).await?;
     Ok(Post::pub_response_ok(json))
 }
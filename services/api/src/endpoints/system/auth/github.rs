@@ -44,6 +44,7 @@ pub async fn auth_github_post(
     rqctx: RequestContext<ApiContext>,
     body: TypedBody<JsonOAuth>,
 ) -> Result<ResponseAccepted<JsonAuthUser>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
     let json = post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
     Ok(Post::pub_response_accepted(json))
 }
@@ -40,6 +40,7 @@ pub async fn auth_login_post(
     rqctx: RequestContext<ApiContext>,
     body: TypedBody<JsonLogin>,
 ) -> Result<ResponseAccepted<JsonAuthAck>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
     let json = post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
     Ok(Post::pub_response_accepted(json))
 }
@@ -109,7 +110,7 @@ async fn post_inner(
         subject: Some("Confirm Bencher Login".into()),
         body: Some(body),
     };
-    context.messenger.send(log, message);
+    context.messenger.send(log, conn_lock!(context), message);
 
     Ok(JsonAuthAck {
         email: json_login.email,
@@ -41,6 +41,7 @@ pub async fn auth_signup_post(
     rqctx: RequestContext<ApiContext>,
     body: TypedBody<JsonSignup>,
 ) -> Result<ResponseAccepted<JsonAuthAck>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
     let json = post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
     Ok(Post::pub_response_accepted(json))
 }
@@ -110,7 +111,7 @@ async fn post_inner(
         subject: Some("Confirm Bencher Signup".into()),
         body: Some(body),
     };
-    context.messenger.send(log, message);
+    context.messenger.send(log, conn_lock!(context), message);
 
     insert_user.notify(
         log,
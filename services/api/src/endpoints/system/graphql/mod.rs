@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use async_graphql::{EmptyMutation, EmptySubscription, Schema, Variables};
+use dropshot::{endpoint, DropshotState, HttpError, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    context::ApiContext,
+    endpoints::{
+        endpoint::{CorsResponse, Post, ResponseOk},
+        Endpoint,
+    },
+    error::{bad_request_error, issue_error},
+    model::user::auth::{AuthUser, PubBearerToken},
+};
+
+mod query;
+
+use query::QueryRoot;
+
+// Limit the shape of a query, so a single request can not load the entire database
+const MAX_DEPTH: usize = 8;
+const MAX_COMPLEXITY: usize = 512;
+
+pub type GraphQlSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// The data available to every GraphQL resolver
+pub struct GraphQlContext {
+    // The `ApiContext` is borrowed from the request, but GraphQL data must be `'static`
+    server: Arc<DropshotState<ApiContext>>,
+    auth_user: Option<AuthUser>,
+}
+
+impl GraphQlContext {
+    fn context(&self) -> &ApiContext {
+        &self.server.private
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonGraphQlRequest {
+    /// The GraphQL query document.
+    pub query: String,
+    /// The name of the operation to run, if the query document has more than one.
+    pub operation_name: Option<String>,
+    /// The values for the variables in the query document.
+    pub variables: Option<serde_json::Value>,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/graphql",
+    tags = ["server"]
+}]
+pub async fn server_graphql_options(
+    _rqctx: RequestContext<ApiContext>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Query the server with GraphQL
+///
+/// Run a read-only GraphQL query over projects and their branches, testbeds, benchmarks, measures, metrics, and alerts.
+/// This allows reporting tools to fetch exactly the shape of data that they need in a single request.
+/// All changes must still be made with the REST API.
+/// If a project is public, then the user does not need to be authenticated.
+/// If a project is private, then the user must be authenticated and have `view` permissions for the project.
+/// This endpoint is only available if the server was built with the `graphql` feature.
+#[endpoint {
+    method = POST,
+    path =  "/v0/graphql",
+    tags = ["server"]
+}]
+pub async fn server_graphql_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: PubBearerToken,
+    body: TypedBody<JsonGraphQlRequest>,
+) -> Result<ResponseOk<serde_json::Value>, HttpError> {
+    let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
+    let is_auth = auth_user.is_some();
+    let graphql_context = GraphQlContext {
+        server: rqctx.server.clone(),
+        auth_user,
+    };
+    let json = post_inner(graphql_context, body.into_inner()).await?;
+    Ok(Post::response_ok(json, is_auth))
+}
+
+async fn post_inner(
+    graphql_context: GraphQlContext,
+    json_request: JsonGraphQlRequest,
+) -> Result<serde_json::Value, HttpError> {
+    let JsonGraphQlRequest {
+        query,
+        operation_name,
+        variables,
+    } = json_request;
+
+    let mut request = async_graphql::Request::new(query).data(graphql_context);
+    if let Some(operation_name) = operation_name {
+        request = request.operation_name(operation_name);
+    }
+    if let Some(variables) = variables {
+        if !variables.is_object() {
+            return Err(bad_request_error("GraphQL variables must be a JSON object"));
+        }
+        request = request.variables(Variables::from_json(variables));
+    }
+
+    // Errors from the query itself are returned in the response body, as GraphQL clients expect
+    let response = schema().execute(request).await;
+    serde_json::to_value(response).map_err(|e| {
+        issue_error(
+            http::StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to serialize GraphQL response",
+            "Failed to serialize GraphQL response.",
+            e,
+        )
+    })
+}
+
+fn schema() -> GraphQlSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
@@ -0,0 +1,577 @@
+use async_graphql::{Context, Enum, Object, SimpleObject};
+use bencher_json::{
+    project::{
+        alert::{AlertSeverity as JsonAlertSeverity, AlertStatus as JsonAlertStatus},
+        boundary::BoundaryLimit,
+        head::VersionNumber,
+        report::Iteration,
+    },
+    DateTime, GitHash, JsonAlert, NameId, ReportUuid, ResourceId,
+};
+use chrono::Utc;
+use diesel::SelectableHelper;
+use diesel::{BelongingToDsl, ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    conn_lock,
+    error::resource_not_found_err,
+    model::project::{
+        benchmark::QueryBenchmark, branch::QueryBranch, measure::QueryMeasure,
+        testbed::QueryTestbed, threshold::alert::QueryAlert, QueryProject,
+    },
+    schema,
+};
+
+use super::GraphQlContext;
+
+const DEFAULT_LIMIT: u8 = 64;
+
+type Timestamp = chrono::DateTime<Utc>;
+
+fn gql_error(error: HttpError) -> async_graphql::Error {
+    async_graphql::Error::new(error.external_message)
+}
+
+fn parse_arg<T>(name: &str, value: &str) -> async_graphql::Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| async_graphql::Error::new(format!("Invalid {name} ({value}): {e}")))
+}
+
+fn page(limit: Option<u8>, offset: Option<u32>) -> (i64, i64) {
+    (
+        i64::from(limit.unwrap_or(DEFAULT_LIMIT)),
+        i64::from(offset.unwrap_or_default()),
+    )
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// A project, by its slug or UUID.
+    /// If the project is private, then the user must be authenticated and have `view` permissions for the project.
+    async fn project(&self, ctx: &Context<'_>, project: String) -> async_graphql::Result<Project> {
+        let graphql_context = ctx.data::<GraphQlContext>()?;
+        let context = graphql_context.context();
+        let project: ResourceId = parse_arg("project", &project)?;
+        QueryProject::is_allowed_public(
+            conn_lock!(context),
+            &context.rbac,
+            &project,
+            graphql_context.auth_user.as_ref(),
+        )
+        .map(Project)
+        .map_err(gql_error)
+    }
+}
+
+pub struct Project(QueryProject);
+
+#[Object]
+impl Project {
+    async fn uuid(&self) -> String {
+        self.0.uuid.to_string()
+    }
+
+    async fn name(&self) -> String {
+        self.0.name.to_string()
+    }
+
+    async fn slug(&self) -> String {
+        self.0.slug.to_string()
+    }
+
+    async fn url(&self) -> Option<String> {
+        self.0.url.as_ref().map(ToString::to_string)
+    }
+
+    async fn created(&self) -> Timestamp {
+        self.0.created.into_inner()
+    }
+
+    async fn modified(&self) -> Timestamp {
+        self.0.modified.into_inner()
+    }
+
+    /// The branches for the project, ordered by name.
+    /// If `archived` is `true`, then only archived branches are returned.
+    async fn branches(
+        &self,
+        ctx: &Context<'_>,
+        archived: Option<bool>,
+        limit: Option<u8>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<Resource>> {
+        let context = ctx.data::<GraphQlContext>()?.context();
+        let (limit, offset) = page(limit, offset);
+        let mut query = QueryBranch::belonging_to(&self.0).into_boxed();
+        query = if archived.unwrap_or_default() {
+            query.filter(schema::branch::archived.is_not_null())
+        } else {
+            query.filter(schema::branch::archived.is_null())
+        };
+        let branches = query
+            .order(schema::branch::name.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<QueryBranch>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Branch, &self.0))
+            .map_err(gql_error)?;
+        Ok(branches
+            .into_iter()
+            .map(|branch| Resource {
+                uuid: branch.uuid.to_string(),
+                name: branch.name.to_string(),
+                slug: branch.slug.to_string(),
+                created: branch.created.into_inner(),
+                modified: branch.modified.into_inner(),
+                archived: branch.archived.map(DateTime::into_inner),
+            })
+            .collect())
+    }
+
+    /// The testbeds for the project, ordered by name.
+    /// If `archived` is `true`, then only archived testbeds are returned.
+    async fn testbeds(
+        &self,
+        ctx: &Context<'_>,
+        archived: Option<bool>,
+        limit: Option<u8>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<Resource>> {
+        let context = ctx.data::<GraphQlContext>()?.context();
+        let (limit, offset) = page(limit, offset);
+        let mut query = QueryTestbed::belonging_to(&self.0).into_boxed();
+        query = if archived.unwrap_or_default() {
+            query.filter(schema::testbed::archived.is_not_null())
+        } else {
+            query.filter(schema::testbed::archived.is_null())
+        };
+        let testbeds = query
+            .order(schema::testbed::name.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<QueryTestbed>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Testbed, &self.0))
+            .map_err(gql_error)?;
+        Ok(testbeds
+            .into_iter()
+            .map(|testbed| Resource {
+                uuid: testbed.uuid.to_string(),
+                name: testbed.name.to_string(),
+                slug: testbed.slug.to_string(),
+                created: testbed.created.into_inner(),
+                modified: testbed.modified.into_inner(),
+                archived: testbed.archived.map(DateTime::into_inner),
+            })
+            .collect())
+    }
+
+    /// The benchmarks for the project, ordered by name.
+    /// If `archived` is `true`, then only archived benchmarks are returned.
+    async fn benchmarks(
+        &self,
+        ctx: &Context<'_>,
+        archived: Option<bool>,
+        limit: Option<u8>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<Resource>> {
+        let context = ctx.data::<GraphQlContext>()?.context();
+        let (limit, offset) = page(limit, offset);
+        let mut query = QueryBenchmark::belonging_to(&self.0).into_boxed();
+        query = if archived.unwrap_or_default() {
+            query.filter(schema::benchmark::archived.is_not_null())
+        } else {
+            query.filter(schema::benchmark::archived.is_null())
+        };
+        let benchmarks = query
+            .order(schema::benchmark::name.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<QueryBenchmark>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Benchmark, &self.0))
+            .map_err(gql_error)?;
+        Ok(benchmarks
+            .into_iter()
+            .map(|benchmark| Resource {
+                uuid: benchmark.uuid.to_string(),
+                name: benchmark.name.to_string(),
+                slug: benchmark.slug.to_string(),
+                created: benchmark.created.into_inner(),
+                modified: benchmark.modified.into_inner(),
+                archived: benchmark.archived.map(DateTime::into_inner),
+            })
+            .collect())
+    }
+
+    /// The measures for the project, ordered by name.
+    /// If `archived` is `true`, then only archived measures are returned.
+    async fn measures(
+        &self,
+        ctx: &Context<'_>,
+        archived: Option<bool>,
+        limit: Option<u8>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<Measure>> {
+        let context = ctx.data::<GraphQlContext>()?.context();
+        let (limit, offset) = page(limit, offset);
+        let mut query = QueryMeasure::belonging_to(&self.0).into_boxed();
+        query = if archived.unwrap_or_default() {
+            query.filter(schema::measure::archived.is_not_null())
+        } else {
+            query.filter(schema::measure::archived.is_null())
+        };
+        let measures = query
+            .order(schema::measure::name.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<QueryMeasure>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Measure, &self.0))
+            .map_err(gql_error)?;
+        Ok(measures
+            .into_iter()
+            .map(|measure| Measure {
+                uuid: measure.uuid.to_string(),
+                name: measure.name.to_string(),
+                slug: measure.slug.to_string(),
+                units: measure.units.to_string(),
+                created: measure.created.into_inner(),
+                modified: measure.modified.into_inner(),
+                archived: measure.archived.map(DateTime::into_inner),
+            })
+            .collect())
+    }
+
+    /// The alerts for the project, with the most recently modified first.
+    async fn alerts(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<AlertStatus>,
+        severity: Option<AlertSeverity>,
+        limit: Option<u8>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<Alert>> {
+        let context = ctx.data::<GraphQlContext>()?.context();
+        let (limit, offset) = page(limit, offset);
+        let mut query = schema::alert::table
+            .inner_join(schema::boundary::table.inner_join(
+                schema::metric::table.inner_join(
+                    schema::report_benchmark::table.inner_join(schema::benchmark::table),
+                ),
+            ))
+            .filter(schema::benchmark::project_id.eq(self.0.id))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(schema::alert::status.eq(JsonAlertStatus::from(status)));
+        }
+        if let Some(severity) = severity {
+            query = query.filter(schema::alert::severity.eq(JsonAlertSeverity::from(severity)));
+        }
+        let alerts = query
+            .order((schema::alert::modified.desc(), schema::alert::id.desc()))
+            .offset(offset)
+            .limit(limit)
+            .select(QueryAlert::as_select())
+            .load::<QueryAlert>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Alert, &self.0))
+            .map_err(gql_error)?;
+
+        // Separate out these queries to prevent a deadlock when getting the conn_lock
+        let mut gql_alerts = Vec::with_capacity(alerts.len());
+        for alert in alerts {
+            let json_alert = alert.into_json(context).await.map_err(gql_error)?;
+            gql_alerts.push(json_alert.into());
+        }
+        Ok(gql_alerts)
+    }
+
+    /// The metrics for a benchmark on the current head of a branch, for a testbed and measure.
+    /// The branch, testbed, and measure may be a name, slug, or UUID.
+    /// The benchmark may be a slug or UUID.
+    /// The metrics are ordered by version number, report start time, and iteration.
+    #[allow(clippy::too_many_arguments)]
+    async fn metrics(
+        &self,
+        ctx: &Context<'_>,
+        branch: String,
+        testbed: String,
+        benchmark: String,
+        measure: String,
+        start_time: Option<Timestamp>,
+        end_time: Option<Timestamp>,
+        limit: Option<u8>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<Metric>> {
+        let context = ctx.data::<GraphQlContext>()?.context();
+        let project_id = self.0.id;
+        let branch: NameId = parse_arg("branch", &branch)?;
+        let testbed: NameId = parse_arg("testbed", &testbed)?;
+        let benchmark: ResourceId = parse_arg("benchmark", &benchmark)?;
+        let measure: NameId = parse_arg("measure", &measure)?;
+
+        let query_branch = QueryBranch::from_name_id(conn_lock!(context), project_id, &branch)
+            .map_err(gql_error)?;
+        let query_head = query_branch.head(conn_lock!(context)).map_err(gql_error)?;
+        let query_testbed = QueryTestbed::from_name_id(conn_lock!(context), project_id, &testbed)
+            .map_err(gql_error)?;
+        let query_benchmark =
+            QueryBenchmark::from_resource_id(conn_lock!(context), project_id, &benchmark)
+                .map_err(gql_error)?;
+        let query_measure = QueryMeasure::from_name_id(conn_lock!(context), project_id, &measure)
+            .map_err(gql_error)?;
+
+        let (limit, offset) = page(limit, offset);
+        let mut query = schema::metric::table
+            .inner_join(
+                schema::report_benchmark::table.inner_join(
+                    schema::report::table.inner_join(
+                        schema::version::table.inner_join(
+                            schema::head_version::table
+                                .on(schema::head_version::version_id.eq(schema::version::id)),
+                        ),
+                    ),
+                ),
+            )
+            // Filter for the branch through the `head_version` table, the same as the perf query
+            .filter(schema::head_version::head_id.eq(query_head.id))
+            .filter(schema::report::testbed_id.eq(query_testbed.id))
+            .filter(schema::report_benchmark::benchmark_id.eq(query_benchmark.id))
+            .filter(schema::metric::measure_id.eq(query_measure.id))
+            .into_boxed();
+        if let Some(start_time) = start_time {
+            query = query.filter(schema::report::start_time.ge(DateTime::from(start_time)));
+        }
+        if let Some(end_time) = end_time {
+            query = query.filter(schema::report::end_time.le(DateTime::from(end_time)));
+        }
+        let metrics = query
+            .order((
+                schema::version::number,
+                schema::report::start_time,
+                schema::report_benchmark::iteration,
+            ))
+            .offset(offset)
+            .limit(limit)
+            .select((
+                schema::report::uuid,
+                schema::report_benchmark::iteration,
+                schema::report::start_time,
+                schema::report::end_time,
+                schema::version::number,
+                schema::version::hash,
+                schema::metric::value,
+                schema::metric::lower_value,
+                schema::metric::upper_value,
+            ))
+            .load::<MetricQuery>(conn_lock!(context))
+            .map_err(resource_not_found_err!(
+                Metric,
+                (
+                    &query_head,
+                    &query_testbed,
+                    &query_benchmark,
+                    &query_measure
+                )
+            ))
+            .map_err(gql_error)?;
+        Ok(metrics.into_iter().map(Into::into).collect())
+    }
+}
+
+/// A branch, testbed, or benchmark
+#[derive(SimpleObject)]
+pub struct Resource {
+    uuid: String,
+    name: String,
+    slug: String,
+    created: Timestamp,
+    modified: Timestamp,
+    archived: Option<Timestamp>,
+}
+
+#[derive(SimpleObject)]
+pub struct Measure {
+    uuid: String,
+    name: String,
+    slug: String,
+    units: String,
+    created: Timestamp,
+    modified: Timestamp,
+    archived: Option<Timestamp>,
+}
+
+#[derive(SimpleObject)]
+pub struct Alert {
+    uuid: String,
+    report: String,
+    iteration: u32,
+    branch: String,
+    testbed: String,
+    benchmark: String,
+    measure: String,
+    /// The metric value that generated the alert.
+    value: f64,
+    /// The boundary limit that was exceeded, either `lower` or `upper`.
+    limit: String,
+    /// The boundary limit value that was exceeded.
+    limit_value: Option<f64>,
+    severity: AlertSeverity,
+    status: AlertStatus,
+    /// The correlated alert group, if multiple measures for the benchmark regressed together.
+    group: Option<String>,
+    created: Timestamp,
+    modified: Timestamp,
+}
+
+impl From<JsonAlert> for Alert {
+    fn from(json_alert: JsonAlert) -> Self {
+        let JsonAlert {
+            uuid,
+            report,
+            iteration,
+            benchmark,
+            metric,
+            threshold,
+            boundary,
+            limit,
+            severity,
+            status,
+            group,
+            created,
+            modified,
+        } = json_alert;
+        let (limit, limit_value) = match limit {
+            BoundaryLimit::Lower => ("lower", boundary.lower_limit),
+            BoundaryLimit::Upper => ("upper", boundary.upper_limit),
+        };
+        Self {
+            uuid: uuid.to_string(),
+            report: report.to_string(),
+            iteration: iteration.0,
+            branch: threshold.branch.name.to_string(),
+            testbed: threshold.testbed.name.to_string(),
+            benchmark: benchmark.name.to_string(),
+            measure: threshold.measure.name.to_string(),
+            value: metric.value.0,
+            limit: limit.to_owned(),
+            limit_value: limit_value.map(|limit| limit.0),
+            severity: severity.into(),
+            status: status.into(),
+            group: group.map(|group| group.to_string()),
+            created: created.into_inner(),
+            modified: modified.into_inner(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum AlertStatus {
+    Active,
+    Dismissed,
+    Silenced,
+}
+
+impl From<JsonAlertStatus> for AlertStatus {
+    fn from(status: JsonAlertStatus) -> Self {
+        match status {
+            JsonAlertStatus::Active => Self::Active,
+            JsonAlertStatus::Dismissed => Self::Dismissed,
+            JsonAlertStatus::Silenced => Self::Silenced,
+        }
+    }
+}
+
+impl From<AlertStatus> for JsonAlertStatus {
+    fn from(status: AlertStatus) -> Self {
+        match status {
+            AlertStatus::Active => Self::Active,
+            AlertStatus::Dismissed => Self::Dismissed,
+            AlertStatus::Silenced => Self::Silenced,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum AlertSeverity {
+    Minor,
+    Major,
+    Critical,
+}
+
+impl From<JsonAlertSeverity> for AlertSeverity {
+    fn from(severity: JsonAlertSeverity) -> Self {
+        match severity {
+            JsonAlertSeverity::Minor => Self::Minor,
+            JsonAlertSeverity::Major => Self::Major,
+            JsonAlertSeverity::Critical => Self::Critical,
+        }
+    }
+}
+
+impl From<AlertSeverity> for JsonAlertSeverity {
+    fn from(severity: AlertSeverity) -> Self {
+        match severity {
+            AlertSeverity::Minor => Self::Minor,
+            AlertSeverity::Major => Self::Major,
+            AlertSeverity::Critical => Self::Critical,
+        }
+    }
+}
+
+type MetricQuery = (
+    ReportUuid,
+    Iteration,
+    DateTime,
+    DateTime,
+    VersionNumber,
+    Option<GitHash>,
+    f64,
+    Option<f64>,
+    Option<f64>,
+);
+
+#[derive(SimpleObject)]
+pub struct Metric {
+    report: String,
+    iteration: u32,
+    start_time: Timestamp,
+    end_time: Timestamp,
+    version: u32,
+    hash: Option<String>,
+    value: f64,
+    lower_value: Option<f64>,
+    upper_value: Option<f64>,
+}
+
+impl From<MetricQuery> for Metric {
+    fn from(metric: MetricQuery) -> Self {
+        let (
+            report,
+            iteration,
+            start_time,
+            end_time,
+            version,
+            hash,
+            value,
+            lower_value,
+            upper_value,
+        ) = metric;
+        Self {
+            report: report.to_string(),
+            iteration: iteration.0,
+            start_time: start_time.into_inner(),
+            end_time: end_time.into_inner(),
+            version: version.0,
+            hash: hash.as_ref().map(ToString::to_string),
+            value,
+            lower_value,
+            upper_value,
+        }
+    }
+}
@@ -30,14 +30,15 @@ pub async fn server_root_options(
     ]))
 }
 
-#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[allow(clippy::unused_async)]
 #[endpoint {
     method = GET,
     path = "/",
     tags = ["server"]
 }]
 pub async fn server_root_get(
-    _rqctx: RequestContext<ApiContext>,
+    rqctx: RequestContext<ApiContext>,
 ) -> Result<ResponseOk<()>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
     Ok(Get::pub_response_ok(()))
 }
@@ -10,6 +10,7 @@ use crate::{
     config::{Config, BENCHER_CONFIG},
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, Put, ResponseAccepted, ResponseOk},
         Endpoint,
     },
@@ -46,10 +47,11 @@ pub async fn server_config_options(
 pub async fn server_config_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
-) -> Result<ResponseOk<JsonConfig>, HttpError> {
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonConfig>>, HttpError> {
     let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(&rqctx.log).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_one_inner(log: &Logger) -> Result<JsonConfig, HttpError> {
@@ -139,12 +141,15 @@ pub async fn server_config_console_options(
 pub async fn server_config_console_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: PubBearerToken,
-) -> Result<ResponseOk<JsonConsole>, HttpError> {
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonConsole>>, HttpError> {
     let auth_user = AuthUser::from_pub_token(rqctx.context(), bearer_token).await?;
-    Ok(Get::response_ok(
-        JsonConsole {
-            url: rqctx.context().console_url.clone().into(),
-        },
-        auth_user.is_some(),
-    ))
+    accept_encoding
+        .encode(Get::response_ok(
+            JsonConsole {
+                url: rqctx.context().console_url.clone().into(),
+            },
+            auth_user.is_some(),
+        ))
+        .await
 }
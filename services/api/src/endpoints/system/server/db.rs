@@ -0,0 +1,121 @@
+use bencher_json::{JsonDbCheck, JsonDbOrphans};
+use diesel::{
+    sql_types::{BigInt, Text},
+    RunQueryDsl,
+};
+use dropshot::{endpoint, HttpError, RequestContext};
+use http::StatusCode;
+
+use crate::{
+    conn_lock,
+    context::{ApiContext, DbConnection},
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, ResponseOk},
+        Endpoint,
+    },
+    error::issue_error,
+    model::user::{admin::AdminUser, auth::BearerToken},
+};
+
+// The single row returned by `PRAGMA integrity_check` when no problems are found
+const INTEGRITY_OK: &str = "ok";
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/server/db/check",
+    tags = ["server"]
+}]
+pub async fn server_db_check_options(
+    _rqctx: RequestContext<ApiContext>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// Check server database
+///
+/// Check the integrity of the API server database.
+/// This runs the SQLite integrity check and foreign key check,
+/// and it reports any rows that reference a parent row that does not exist.
+/// No data is changed.
+/// The user must be an admin on the server to use this route.
+#[allow(clippy::doc_markdown)]
+#[endpoint {
+    method = GET,
+    path =  "/v0/server/db/check",
+    tags = ["server"]
+}]
+pub async fn server_db_check_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonDbCheck>>, HttpError> {
+    let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context()).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(context: &ApiContext) -> Result<JsonDbCheck, HttpError> {
+    let conn = conn_lock!(context);
+    let integrity = integrity_check(conn)?;
+    let orphans = foreign_key_check(conn)?;
+    Ok(JsonDbCheck {
+        ok: integrity.is_empty() && orphans.is_empty(),
+        integrity,
+        orphans,
+    })
+}
+
+#[derive(diesel::QueryableByName)]
+struct IntegrityCheck {
+    #[diesel(sql_type = Text)]
+    integrity_check: String,
+}
+
+fn integrity_check(conn: &mut DbConnection) -> Result<Vec<String>, HttpError> {
+    let rows = diesel::sql_query("PRAGMA integrity_check")
+        .load::<IntegrityCheck>(conn)
+        .map_err(|e| check_error("integrity", e))?;
+    Ok(rows
+        .into_iter()
+        .map(|row| row.integrity_check)
+        .filter(|message| message != INTEGRITY_OK)
+        .collect())
+}
+
+#[derive(diesel::QueryableByName)]
+struct ForeignKeyCheck {
+    #[diesel(sql_type = Text)]
+    table: String,
+    #[diesel(sql_type = Text)]
+    parent: String,
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn foreign_key_check(conn: &mut DbConnection) -> Result<Vec<JsonDbOrphans>, HttpError> {
+    let rows = diesel::sql_query(
+        r#"SELECT "table", parent, COUNT(*) AS count FROM pragma_foreign_key_check GROUP BY "table", parent ORDER BY "table", parent"#,
+    )
+    .load::<ForeignKeyCheck>(conn)
+    .map_err(|e| check_error("foreign key", e))?;
+    Ok(rows
+        .into_iter()
+        .map(|row| JsonDbOrphans {
+            table: row.table,
+            parent: row.parent,
+            count: row.count as u64,
+        })
+        .collect())
+}
+
+fn check_error(check: &str, e: diesel::result::Error) -> HttpError {
+    issue_error(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        &format!("Failed to run database {check} check"),
+        &format!("Failed to run the database {check} check."),
+        e,
+    )
+}
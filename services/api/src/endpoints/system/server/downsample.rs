@@ -0,0 +1,78 @@
+use bencher_json::{JsonDownsampled, JsonNewDownsample};
+use dropshot::{endpoint, HttpError, RequestContext, TypedBody};
+
+use crate::{
+    config::downsample::DownsampleSettings,
+    context::ApiContext,
+    endpoints::{
+        endpoint::{CorsResponse, Post, ResponseOk},
+        Endpoint,
+    },
+    error::bad_request_error,
+    model::{
+        project::metric_downsample::downsample,
+        user::{admin::AdminUser, auth::BearerToken},
+    },
+};
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/server/downsample",
+    tags = ["server"]
+}]
+pub async fn server_downsample_options(
+    _rqctx: RequestContext<ApiContext>,
+    _body: TypedBody<JsonNewDownsample>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Downsample metrics
+///
+/// Replace the metrics from old reports with daily aggregates.
+/// If `after_months` is not provided, the server `downsample` config is used.
+/// The user must be an admin on the server to use this route.
+#[endpoint {
+    method = POST,
+    path =  "/v0/server/downsample",
+    tags = ["server"]
+}]
+pub async fn server_downsample_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    body: TypedBody<JsonNewDownsample>,
+) -> Result<ResponseOk<JsonDownsampled>, HttpError> {
+    let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(rqctx.context(), body.into_inner()).await?;
+    Ok(Post::auth_response_ok(json))
+}
+
+async fn post_inner(
+    context: &ApiContext,
+    json_downsample: JsonNewDownsample,
+) -> Result<JsonDownsampled, HttpError> {
+    let JsonNewDownsample {
+        after_months,
+        dry_run,
+    } = json_downsample;
+    let settings = if let Some(after_months) = after_months {
+        DownsampleSettings::new(after_months, dry_run.unwrap_or_default())
+    } else if let Some(settings) = context.downsample {
+        DownsampleSettings {
+            dry_run: dry_run.unwrap_or(settings.dry_run),
+            ..settings
+        }
+    } else {
+        return Err(bad_request_error(
+            "The server does not have a downsample config, so `after_months` is required",
+        ));
+    };
+
+    downsample(
+        &context.database.connection,
+        settings.cutoff(),
+        settings.dry_run,
+    )
+    .await
+}
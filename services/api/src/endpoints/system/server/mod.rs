@@ -1,6 +1,11 @@
 pub mod backup;
 pub mod config;
+pub mod db;
+pub mod downsample;
+pub mod rbac;
 pub mod restart;
+pub mod secret;
 pub mod spec;
 pub mod stats;
+pub mod telemetry;
 pub mod version;
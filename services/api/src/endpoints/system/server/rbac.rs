@@ -0,0 +1,352 @@
+use bencher_json::{JsonRbac, JsonRbacAccess, JsonRbacEndpoint};
+use bencher_rbac::{
+    matrix,
+    organization::Permission as OrgPerm,
+    project::Permission as ProjPerm,
+    server::{Permission as SvrPerm, Role as SvrRole},
+};
+use dropshot::{endpoint, HttpError, RequestContext};
+use http::StatusCode;
+use oso::Oso;
+
+use crate::{
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, ResponseOk},
+        Endpoint,
+    },
+    error::issue_error,
+    SPEC,
+};
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/server/rbac",
+    tags = ["server"]
+}]
+pub async fn server_rbac_options(
+    _rqctx: RequestContext<ApiContext>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// View server RBAC permission matrix
+///
+/// View the permission matrix for every API server endpoint.
+/// Each endpoint lists the kind of authorization it requires,
+/// the permission that is checked, and the roles that are allowed.
+/// Endpoints with an `unmapped` access have not been added to the permission matrix.
+#[allow(clippy::doc_markdown, clippy::unused_async)]
+#[endpoint {
+    method = GET,
+    path = "/v0/server/rbac",
+    tags = ["server"]
+}]
+pub async fn server_rbac_get(
+    rqctx: RequestContext<ApiContext>,
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonRbac>>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
+    let json = get_one_inner(&rqctx.context().rbac.0)?;
+    accept_encoding.encode(Get::pub_response_ok(json)).await
+}
+
+fn get_one_inner(oso: &Oso) -> Result<JsonRbac, HttpError> {
+    let mut endpoints = Vec::new();
+    let paths = SPEC.0.get("paths").and_then(serde_json::Value::as_object);
+    for (path, item) in paths.into_iter().flatten() {
+        let Some(operations) = item.as_object() else {
+            continue;
+        };
+        for (method, operation) in operations {
+            let Some(operation_id) = operation
+                .get("operationId")
+                .and_then(serde_json::Value::as_str)
+            else {
+                continue;
+            };
+            let (access, permission, roles) = Access::from_operation_id(operation_id)
+                .roles(oso)
+                .map_err(|e| {
+                    issue_error(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Failed to generate RBAC permission matrix",
+                        &format!("Failed to generate RBAC permission matrix for {operation_id}."),
+                        e,
+                    )
+                })?;
+            endpoints.push(JsonRbacEndpoint {
+                method: method.to_uppercase(),
+                path: path.clone(),
+                operation_id: operation_id.to_owned(),
+                access,
+                permission,
+                roles,
+            });
+        }
+    }
+    Ok(JsonRbac { endpoints })
+}
+
+// The user themselves, for endpoints that act on a single user
+const SAME_USER_ROLE: &str = "user:self";
+
+#[derive(Debug, Clone, Copy)]
+enum Access {
+    Public,
+    User,
+    SameUser,
+    Admin,
+    Organization(OrgPerm),
+    Project(ProjPerm),
+    PublicProject,
+    Unmapped,
+}
+
+impl Access {
+    // Any new endpoint must be added here, otherwise it will show up as unmapped.
+    #[allow(clippy::too_many_lines)]
+    fn from_operation_id(operation_id: &str) -> Self {
+        match operation_id {
+            "server_root_get"
+            | "root_server_stats_post"
+            | "server_stats_post"
+            | "server_version_get"
+            | "server_spec_get"
+            | "server_rbac_get"
+            | "server_config_console_get"
+            | "auth_confirm_post"
+            | "auth_github_post"
+            | "auth_login_post"
+            | "auth_signup_post"
+            // Authorized by the embed token for the project
+            | "proj_perf_embed_get"
+            // Authorized by the GitHub webhook secret for the project
+            | "proj_github_webhook_post" => Self::Public,
+
+            "auth_accept_post"
+            | "organizations_get"
+            | "organization_post"
+            | "org_allowed_get"
+            | "proj_allowed_get" => Self::User,
+
+            "user_get"
+            | "user_patch"
+            | "user_tokens_get"
+            | "user_token_post"
+            | "user_token_get"
+            | "user_token_patch"
+            | "user_token_revoke_post" => Self::SameUser,
+
+            "users_get"
+            | "proj_report_reparse_post"
+            | "proj_report_hook_get"
+            | "proj_report_hook_put"
+            | "proj_report_hook_delete"
+            | "server_backup_post"
+            | "server_config_get"
+            | "server_db_check_get"
+            | "server_config_put"
+            | "server_downsample_post"
+            | "server_restart_post"
+            | "server_stats_get"
+            | "server_telemetry_get" => Self::Admin,
+
+            "organization_get" | "org_projects_get" | "org_perf_get" | "org_token_policy_get" => {
+                Self::Organization(OrgPerm::View)
+            },
+            "org_project_post" => Self::Organization(OrgPerm::Create),
+            // Updating the license requires the manage permission
+            "organization_patch" => Self::Organization(OrgPerm::Edit),
+            "organization_delete" => Self::Organization(OrgPerm::Delete),
+            "org_plan_get"
+            | "org_plan_post"
+            | "org_plan_delete"
+            | "org_usage_get"
+            | "org_token_policy_put"
+            | "org_tokens_revoke_post"
+            | "checkouts_post" => Self::Organization(OrgPerm::Manage),
+            "org_members_get"
+            | "org_member_get"
+            | "org_scim_groups_get"
+            | "org_scim_group_get"
+            | "org_scim_users_get"
+            | "org_scim_user_get" => Self::Organization(OrgPerm::ViewRole),
+            "org_member_post" | "org_scim_user_post" => Self::Organization(OrgPerm::CreateRole),
+            "org_member_patch"
+            | "org_scim_group_patch"
+            | "org_scim_user_put"
+            | "org_scim_user_patch" => Self::Organization(OrgPerm::EditRole),
+            "org_member_delete" | "org_scim_user_delete" => {
+                Self::Organization(OrgPerm::DeleteRole)
+            },
+
+            "projects_get"
+            | "project_get"
+            | "proj_alerts_get"
+            | "proj_alert_get"
+            | "proj_alert_stats_get"
+            | "proj_alert_img_get"
+            | "proj_annotations_get"
+            | "proj_annotation_get"
+            | "proj_benchmarks_get"
+            | "proj_benchmark_get"
+            | "proj_branches_get"
+            | "proj_branch_get"
+            | "proj_branch_lineage_get"
+            | "proj_branch_versions_get"
+            | "proj_measures_get"
+            | "proj_measure_get"
+            | "proj_measure_aliases_get"
+            | "proj_metric_get"
+            | "proj_perf_get"
+            | "proj_perf_img_get"
+            | "proj_perf_groups_get"
+            | "proj_perf_fingerprints_get"
+            | "proj_perf_badge_get"
+            | "proj_plots_get"
+            | "proj_plot_get"
+            | "proj_reports_get"
+            | "proj_report_get"
+            | "proj_expectations_get"
+            | "proj_expectation_get"
+            | "proj_rules_get"
+            | "proj_rule_get"
+            | "proj_testbeds_get"
+            | "proj_testbed_get"
+            | "proj_thresholds_get"
+            | "proj_threshold_get" => Self::PublicProject,
+
+            "proj_perf_embed_post"
+            | "proj_github_webhook_get"
+            | "proj_webhook_get"
+            | "proj_webhook_failed_get" => Self::Project(ProjPerm::View),
+            "proj_report_post" | "proj_import_post" | "proj_shard_post" => {
+                Self::Project(ProjPerm::Report)
+            },
+            "proj_annotation_post"
+            | "proj_benchmark_post"
+            | "proj_branch_post"
+            | "proj_expectation_post"
+            | "proj_measure_post"
+            | "proj_plot_post"
+            | "proj_rule_post"
+            | "proj_testbed_post"
+            | "proj_threshold_post" => Self::Project(ProjPerm::Create),
+            "project_patch"
+            | "proj_alerts_patch"
+            | "proj_alert_patch"
+            | "proj_annotation_patch"
+            | "proj_benchmark_patch"
+            | "proj_branch_patch"
+            | "proj_measure_patch"
+            | "proj_measure_alias_post"
+            | "proj_measure_alias_delete"
+            | "proj_plot_patch"
+            | "proj_rule_patch"
+            | "proj_testbed_patch"
+            | "proj_threshold_put"
+            | "proj_threshold_recompute_post" => Self::Project(ProjPerm::Edit),
+            // Transferring a project also requires the create permission on the new organization
+            "project_delete"
+            | "project_transfer_post"
+            | "proj_annotation_delete"
+            | "proj_benchmark_delete"
+            | "proj_branch_delete"
+            | "proj_expectation_delete"
+            | "proj_measure_delete"
+            | "proj_measure_merge_post"
+            | "proj_plot_delete"
+            | "proj_report_delete"
+            | "proj_rule_delete"
+            | "proj_testbed_delete"
+            | "proj_threshold_delete" => Self::Project(ProjPerm::Delete),
+            "proj_github_webhook_put"
+            | "proj_github_webhook_delete"
+            | "proj_perf_embed_delete"
+            | "proj_webhook_put"
+            | "proj_webhook_delete"
+            | "proj_webhook_redeliver_post" => Self::Project(ProjPerm::Manage),
+            "proj_members_get" | "proj_member_get" => Self::Project(ProjPerm::ViewRole),
+            "proj_member_post" => Self::Project(ProjPerm::CreateRole),
+            "proj_member_patch" => Self::Project(ProjPerm::EditRole),
+            "proj_member_delete" => Self::Project(ProjPerm::DeleteRole),
+
+            _ => Self::Unmapped,
+        }
+    }
+
+    fn roles(self, oso: &Oso) -> oso::Result<(JsonRbacAccess, Option<String>, Vec<String>)> {
+        // Server admins are allowed to do anything that requires authentication
+        let admin = || server_role(SvrRole::Admin);
+        Ok(match self {
+            Self::Public => (JsonRbacAccess::Public, None, Vec::new()),
+            Self::User => (
+                JsonRbacAccess::User,
+                Some(SvrPerm::Session.to_string()),
+                matrix::server_roles(oso, SvrPerm::Session)?
+                    .into_iter()
+                    .map(server_role)
+                    .collect(),
+            ),
+            Self::SameUser => (
+                JsonRbacAccess::SameUser,
+                None,
+                vec![SAME_USER_ROLE.to_owned(), admin()],
+            ),
+            Self::Admin => (
+                JsonRbacAccess::Admin,
+                Some(SvrPerm::Administer.to_string()),
+                matrix::server_roles(oso, SvrPerm::Administer)?
+                    .into_iter()
+                    .map(server_role)
+                    .collect(),
+            ),
+            Self::Organization(permission) => {
+                let mut roles = vec![admin()];
+                roles.extend(
+                    matrix::organization_roles(oso, permission)?
+                        .into_iter()
+                        .map(|role| format!("organization:{role}")),
+                );
+                (
+                    JsonRbacAccess::Organization,
+                    Some(permission.to_string()),
+                    roles,
+                )
+            },
+            Self::Project(permission) => (
+                JsonRbacAccess::Project,
+                Some(permission.to_string()),
+                project_roles(oso, permission)?,
+            ),
+            Self::PublicProject => (
+                JsonRbacAccess::PublicProject,
+                Some(ProjPerm::View.to_string()),
+                project_roles(oso, ProjPerm::View)?,
+            ),
+            Self::Unmapped => (JsonRbacAccess::Unmapped, None, Vec::new()),
+        })
+    }
+}
+
+fn project_roles(oso: &Oso, permission: ProjPerm) -> oso::Result<Vec<String>> {
+    let mut roles = vec![server_role(SvrRole::Admin)];
+    roles.extend(
+        matrix::organization_project_roles(oso, permission)?
+            .into_iter()
+            .map(|role| format!("organization:{role}")),
+    );
+    roles.extend(
+        matrix::project_roles(oso, permission)?
+            .into_iter()
+            .map(|role| format!("project:{role}")),
+    );
+    Ok(roles)
+}
+
+fn server_role(role: SvrRole) -> String {
+    format!("server:{role}")
+}
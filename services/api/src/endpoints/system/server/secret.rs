@@ -0,0 +1,114 @@
+use bencher_json::{
+    system::config::JsonPreviousSecretKey, DateTime, JsonRotateSecret, JsonSecretRotated, Secret,
+};
+use dropshot::{endpoint, HttpError, RequestContext, TypedBody};
+use http::StatusCode;
+use slog::{warn, Logger};
+
+use crate::{
+    config::{Config, BENCHER_CONFIG},
+    context::ApiContext,
+    endpoints::{
+        endpoint::{CorsResponse, Post, ResponseAccepted},
+        Endpoint,
+    },
+    error::{bad_request_error, issue_error},
+    model::user::{admin::AdminUser, auth::BearerToken},
+};
+
+use super::restart::countdown;
+
+// 7 days
+const DEFAULT_GRACE_PERIOD: u32 = 7 * 24 * 60 * 60;
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/server/secret/rotate",
+    tags = ["server"]
+}]
+pub async fn server_secret_rotate_options(
+    _rqctx: RequestContext<ApiContext>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Rotate server secret key
+///
+/// Rotate the secret key used to sign all tokens for the API server.
+/// New tokens are signed with a newly generated secret key.
+/// Tokens signed with the previous secret key are still valid until the end of the grace period.
+/// Only the most recent previous secret key is kept,
+/// so rotating again during the grace period immediately invalidates any tokens signed with the key before that.
+/// The user must be an admin on the server to use this route.
+/// Rotating the secret key will cause the server to restart.
+#[endpoint {
+    method = POST,
+    path =  "/v0/server/secret/rotate",
+    tags = ["server"]
+}]
+pub async fn server_secret_rotate_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    body: TypedBody<JsonRotateSecret>,
+) -> Result<ResponseAccepted<JsonSecretRotated>, HttpError> {
+    let admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = post_inner(&rqctx.log, rqctx.context(), body.into_inner(), &admin_user).await?;
+    Ok(Post::auth_response_accepted(json))
+}
+
+async fn post_inner(
+    log: &Logger,
+    context: &ApiContext,
+    json_rotate: JsonRotateSecret,
+    admin_user: &AdminUser,
+) -> Result<JsonSecretRotated, HttpError> {
+    let JsonRotateSecret {
+        grace_period,
+        delay,
+    } = json_rotate;
+
+    let mut config = Config::load_or_default(log)
+        .await
+        .map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load config",
+                "Failed to load configuration",
+                e,
+            )
+        })?
+        .into_inner();
+
+    let grace_period = grace_period.unwrap_or(DEFAULT_GRACE_PERIOD);
+    let expiration = DateTime::try_from(DateTime::now().timestamp() + i64::from(grace_period))
+        .map_err(bad_request_error)?;
+    let secret_key: Secret = uuid::Uuid::new_v4().into();
+    let previous_secret_key = std::mem::replace(&mut config.security.secret_key, secret_key);
+    config.security.previous_secret_key = Some(JsonPreviousSecretKey {
+        secret_key: previous_secret_key,
+        expiration,
+    });
+
+    let config_str = serde_json::to_string(&config).map_err(bad_request_error)?;
+    std::env::set_var(BENCHER_CONFIG, &config_str);
+    Config::write(log, config_str.as_bytes())
+        .await
+        .map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to write config file",
+                "Failed to write configuration file",
+                e,
+            )
+        })?;
+
+    let user_id = admin_user.user().id;
+    warn!(
+        log,
+        "Secret key rotated by {user_id}. The previous secret key is valid until {expiration}."
+    );
+    countdown(log, context.restart_tx.clone(), delay, user_id);
+
+    Ok(JsonSecretRotated { expiration })
+}
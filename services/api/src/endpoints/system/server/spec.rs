@@ -4,6 +4,7 @@ use dropshot::{endpoint, HttpError, RequestContext};
 use crate::{
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, ResponseOk},
         Endpoint,
     },
@@ -26,18 +27,18 @@ pub async fn server_spec_options(
 ///
 /// View the API server OpenAPI specification.
 /// The OpenAPI specification can be used to generate API client code.
-#[allow(
-    clippy::no_effect_underscore_binding,
-    clippy::doc_markdown,
-    clippy::unused_async
-)]
+#[allow(clippy::doc_markdown)]
 #[endpoint {
     method = GET,
     path = "/v0/server/spec",
     tags = ["server"]
 }]
 pub async fn server_spec_get(
-    _rqctx: RequestContext<ApiContext>,
-) -> Result<ResponseOk<JsonSpec>, HttpError> {
-    Ok(Get::pub_response_ok(SPEC.clone()))
+    rqctx: RequestContext<ApiContext>,
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonSpec>>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
+    accept_encoding
+        .encode(Get::pub_response_ok(SPEC.clone()))
+        .await
 }
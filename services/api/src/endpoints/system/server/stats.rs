@@ -9,6 +9,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, Post, ResponseAccepted, ResponseOk},
         Endpoint,
     },
@@ -43,10 +44,11 @@ pub async fn server_stats_options(
 pub async fn server_stats_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
-) -> Result<ResponseOk<JsonServerStats>, HttpError> {
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonServerStats>>, HttpError> {
     let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(rqctx.context()).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_one_inner(context: &ApiContext) -> Result<JsonServerStats, HttpError> {
@@ -68,6 +70,7 @@ pub async fn root_server_stats_post(
     rqctx: RequestContext<ApiContext>,
     body: TypedBody<JsonServerStats>,
 ) -> Result<ResponseAccepted<()>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
     post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
     Ok(Post::auth_response_accepted(()))
 }
@@ -81,6 +84,7 @@ pub async fn server_stats_post(
     rqctx: RequestContext<ApiContext>,
     body: TypedBody<JsonServerStats>,
 ) -> Result<ResponseAccepted<()>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
     post_inner(&rqctx.log, rqctx.context(), body.into_inner()).await?;
     Ok(Post::auth_response_accepted(()))
 }
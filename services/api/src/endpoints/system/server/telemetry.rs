@@ -0,0 +1,61 @@
+use bencher_json::JsonServerTelemetry;
+use dropshot::{endpoint, HttpError, RequestContext};
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, ResponseOk},
+        Endpoint,
+    },
+    model::{
+        telemetry::get_telemetry_stats,
+        user::{admin::AdminUser, auth::BearerToken},
+    },
+};
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/server/telemetry",
+    tags = ["server"]
+}]
+pub async fn server_telemetry_options(
+    _rqctx: RequestContext<ApiContext>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into()]))
+}
+
+/// View server telemetry
+///
+/// View whether the API server has opted in to sending telemetry
+/// and the exact anonymous aggregate stats that are sent.
+/// Telemetry is disabled unless it is enabled in the server `telemetry` config.
+/// The user must be an admin on the server to use this route.
+#[endpoint {
+    method = GET,
+    path =  "/v0/server/telemetry",
+    tags = ["server"]
+}]
+pub async fn server_telemetry_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonServerTelemetry>>, HttpError> {
+    let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context()).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(context: &ApiContext) -> Result<JsonServerTelemetry, HttpError> {
+    let stats = get_telemetry_stats(conn_lock!(context))?;
+    Ok(JsonServerTelemetry {
+        enabled: context.telemetry.is_some(),
+        endpoint: context
+            .telemetry
+            .as_ref()
+            .map(|telemetry| telemetry.endpoint.clone().into()),
+        stats,
+    })
+}
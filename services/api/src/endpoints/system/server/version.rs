@@ -4,6 +4,7 @@ use dropshot::{endpoint, HttpError, RequestContext};
 use crate::{
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, ResponseOk},
         Endpoint,
     },
@@ -27,16 +28,19 @@ pub async fn server_version_options(
 /// View the API server version.
 /// This is used to verify that the CLI and API server are compatible.
 /// It can also be used as a simple endpoint to verify that the server is running.
-#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
 #[endpoint {
     method = GET,
     path = "/v0/server/version",
     tags = ["server"]
 }]
 pub async fn server_version_get(
-    _rqctx: RequestContext<ApiContext>,
-) -> Result<ResponseOk<JsonApiVersion>, HttpError> {
-    Ok(Get::pub_response_ok(JsonApiVersion {
-        version: API_VERSION.into(),
-    }))
+    rqctx: RequestContext<ApiContext>,
+    accept_encoding: AcceptEncoding,
+) -> Result<Encoded<ResponseOk<JsonApiVersion>>, HttpError> {
+    rqctx.context().socket_peer(rqctx.request.remote_addr())?;
+    accept_encoding
+        .encode(Get::pub_response_ok(JsonApiVersion {
+            version: API_VERSION.into(),
+        }))
+        .await
 }
@@ -1,2 +1,3 @@
+pub mod prefs;
 pub mod tokens;
 pub mod users;
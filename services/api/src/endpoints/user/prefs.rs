@@ -0,0 +1,135 @@
+use bencher_json::{JsonNewUserPrefs, JsonUserPrefs, ResourceId};
+use bencher_rbac::project::Permission;
+use dropshot::{endpoint, HttpError, Path, RequestContext, TypedBody};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    conn_lock,
+    context::ApiContext,
+    endpoints::{
+        encoding::{AcceptEncoding, Encoded},
+        endpoint::{CorsResponse, Get, Put, ResponseOk},
+        Endpoint,
+    },
+    error::bad_request_error,
+    model::{
+        project::QueryProject,
+        user::{
+            auth::{AuthUser, BearerToken},
+            prefs::{InsertUserPrefs, QueryUserPrefs},
+            same_user, QueryUser,
+        },
+    },
+};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UserPrefsParams {
+    /// The slug or UUID for a user.
+    pub user: ResourceId,
+}
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/users/{user}/prefs",
+    tags = ["users"]
+}]
+pub async fn user_prefs_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<UserPrefsParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Get.into(), Put.into()]))
+}
+
+/// View the preferences for a user
+///
+/// View the display preferences for a user.
+/// If the user has never set any preferences, then all of them are empty.
+/// Only the authenticated user themselves and server admins have access to this endpoint.
+#[endpoint {
+    method = GET,
+    path =  "/v0/users/{user}/prefs",
+    tags = ["users"]
+}]
+pub async fn user_prefs_get(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
+    path_params: Path<UserPrefsParams>,
+) -> Result<Encoded<ResponseOk<JsonUserPrefs>>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
+    accept_encoding.encode(Get::auth_response_ok(json)).await
+}
+
+async fn get_one_inner(
+    context: &ApiContext,
+    path_params: UserPrefsParams,
+    auth_user: &AuthUser,
+) -> Result<JsonUserPrefs, HttpError> {
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+    same_user!(auth_user, context.rbac, query_user.uuid);
+
+    QueryUserPrefs::get_json(conn_lock!(context), query_user.id, query_user.uuid)
+}
+
+/// Replace the preferences for a user
+///
+/// Replace all of the display preferences for a user.
+/// Any preference that is not set is cleared.
+/// The preferred units must be known units for their unit dimension.
+/// The authenticated user must have permission to view the default project.
+/// Only the authenticated user themselves and server admins have access to this endpoint.
+#[endpoint {
+    method = PUT,
+    path =  "/v0/users/{user}/prefs",
+    tags = ["users"]
+}]
+pub async fn user_prefs_put(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<UserPrefsParams>,
+    body: TypedBody<JsonNewUserPrefs>,
+) -> Result<ResponseOk<JsonUserPrefs>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = put_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Put::auth_response_ok(json))
+}
+
+async fn put_inner(
+    context: &ApiContext,
+    path_params: UserPrefsParams,
+    json_prefs: JsonNewUserPrefs,
+    auth_user: &AuthUser,
+) -> Result<JsonUserPrefs, HttpError> {
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+    same_user!(auth_user, context.rbac, query_user.uuid);
+
+    json_prefs.validate().map_err(bad_request_error)?;
+    let default_project_id = if let Some(project) = json_prefs.default_project.as_ref() {
+        Some(
+            QueryProject::is_allowed(
+                conn_lock!(context),
+                &context.rbac,
+                project,
+                auth_user,
+                Permission::View,
+            )?
+            .id,
+        )
+    } else {
+        None
+    };
+
+    let insert_prefs = InsertUserPrefs::new(query_user.id, json_prefs, default_project_id);
+    insert_prefs.upsert(conn_lock!(context))?;
+
+    QueryUserPrefs::get_json(conn_lock!(context), query_user.id, query_user.uuid)
+}
@@ -1,6 +1,6 @@
 use bencher_json::{
-    user::token::JsonUpdateToken, JsonDirection, JsonNewToken, JsonPagination, JsonToken,
-    JsonTokens, ResourceId, ResourceName,
+    user::token::JsonUpdateToken, JsonDirection, JsonNewToken, JsonPagination, JsonRevokeToken,
+    JsonToken, JsonTokens, ResourceId, ResourceName,
 };
 use diesel::{
     BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl, TextExpressionMethods,
@@ -14,6 +14,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, Patch, Post, ResponseCreated, ResponseOk},
         Endpoint,
     },
@@ -21,7 +22,7 @@ use crate::{
     model::user::{
         auth::{AuthUser, BearerToken},
         same_user,
-        token::{InsertToken, QueryToken, UpdateToken},
+        token::{InsertToken, QueryToken, RevokeToken, UpdateToken},
         QueryUser, UserId,
     },
     schema,
@@ -83,7 +84,8 @@ pub async fn user_tokens_get(
     path_params: Path<UserTokensParams>,
     pagination_params: Query<UserTokensPagination>,
     query_params: Query<UserTokensQuery>,
-) -> Result<ResponseOk<JsonTokens>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonTokens>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let auth_user = AuthUser::new(&rqctx).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -93,7 +95,9 @@ pub async fn user_tokens_get(
         &auth_user,
     )
     .await?;
-    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
 }
 
 async fn get_ls_inner(
@@ -251,11 +255,12 @@ pub async fn user_token_options(
 pub async fn user_token_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<UserTokenParams>,
-) -> Result<ResponseOk<JsonToken>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonToken>>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_one_inner(
@@ -328,3 +333,70 @@ async fn patch_inner(
     conn_lock!(context, |conn| QueryToken::get(conn, query_token.id)?
         .into_json(conn))
 }
+
+#[allow(clippy::no_effect_underscore_binding, clippy::unused_async)]
+#[endpoint {
+    method = OPTIONS,
+    path =  "/v0/users/{user}/tokens/{token}/revoke",
+    tags = ["users", "tokens"]
+}]
+pub async fn user_token_revoke_options(
+    _rqctx: RequestContext<ApiContext>,
+    _path_params: Path<UserTokenParams>,
+) -> Result<CorsResponse, HttpError> {
+    Ok(Endpoint::cors(&[Post.into()]))
+}
+
+/// Revoke a token
+///
+/// Revoke an API token for a user.
+/// Once revoked, the token can no longer be used to authenticate, even if it has not yet expired.
+/// An optional grace period can be given, so the token can be rotated without breaking any running jobs.
+/// If the token is already set to be revoked sooner, then that time is kept.
+/// Only the authenticated user themselves and server admins have access to this endpoint.
+#[endpoint {
+    method = POST,
+    path =  "/v0/users/{user}/tokens/{token}/revoke",
+    tags = ["users", "tokens"]
+}]
+pub async fn user_token_revoke_post(
+    rqctx: RequestContext<ApiContext>,
+    bearer_token: BearerToken,
+    path_params: Path<UserTokenParams>,
+    body: TypedBody<JsonRevokeToken>,
+) -> Result<ResponseOk<JsonToken>, HttpError> {
+    let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
+    let json = revoke_inner(
+        rqctx.context(),
+        path_params.into_inner(),
+        body.into_inner(),
+        &auth_user,
+    )
+    .await?;
+    Ok(Post::auth_response_ok(json))
+}
+
+async fn revoke_inner(
+    context: &ApiContext,
+    path_params: UserTokenParams,
+    json_revoke: JsonRevokeToken,
+    auth_user: &AuthUser,
+) -> Result<JsonToken, HttpError> {
+    let query_user = QueryUser::from_resource_id(conn_lock!(context), &path_params.user)?;
+    same_user!(auth_user, context.rbac, query_user.uuid);
+
+    let query_token = QueryToken::get_user_token(
+        conn_lock!(context),
+        query_user.id,
+        &path_params.token.to_string(),
+    )?;
+
+    let revoke_token = RevokeToken::new(&query_token, &json_revoke);
+    diesel::update(schema::token::table.filter(schema::token::id.eq(query_token.id)))
+        .set(&revoke_token)
+        .execute(conn_lock!(context))
+        .map_err(resource_conflict_err!(Token, (&query_user, &query_token)))?;
+
+    conn_lock!(context, |conn| QueryToken::get(conn, query_token.id)?
+        .into_json(conn))
+}
@@ -13,6 +13,7 @@ use crate::{
     conn_lock,
     context::ApiContext,
     endpoints::{
+        encoding::{AcceptEncoding, Encoded},
         endpoint::{CorsResponse, Get, Patch, ResponseOk},
         Endpoint,
     },
@@ -73,7 +74,8 @@ pub async fn users_get(
     bearer_token: BearerToken,
     pagination_params: Query<UsersPagination>,
     query_params: Query<UsersQuery>,
-) -> Result<ResponseOk<JsonUsers>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonUsers>>, HttpError> {
+    let accept_encoding = AcceptEncoding::new(&rqctx).await?;
     let _admin_user = AdminUser::from_token(rqctx.context(), bearer_token).await?;
     let (json, total_count) = get_ls_inner(
         rqctx.context(),
@@ -81,7 +83,9 @@ pub async fn users_get(
         query_params.into_inner(),
     )
     .await?;
-    Ok(Get::auth_response_ok_with_total_count(json, total_count))
+    accept_encoding
+        .encode(Get::auth_response_ok_with_total_count(json, total_count))
+        .await
 }
 
 async fn get_ls_inner(
@@ -169,11 +173,12 @@ pub async fn user_options(
 pub async fn user_get(
     rqctx: RequestContext<ApiContext>,
     bearer_token: BearerToken,
+    accept_encoding: AcceptEncoding,
     path_params: Path<UserParams>,
-) -> Result<ResponseOk<JsonUser>, HttpError> {
+) -> Result<Encoded<ResponseOk<JsonUser>>, HttpError> {
     let auth_user = AuthUser::from_token(rqctx.context(), bearer_token).await?;
     let json = get_one_inner(rqctx.context(), path_params.into_inner(), &auth_user).await?;
-    Ok(Get::auth_response_ok(json))
+    accept_encoding.encode(Get::auth_response_ok(json)).await
 }
 
 async fn get_one_inner(
@@ -0,0 +1,275 @@
+use async_trait::async_trait;
+use bencher_json::{
+    project::report::{Adapter, JsonReportMeasure},
+    ApiVersion, JsonAlert, JsonAlerts, JsonModel, JsonReport, JsonReportShard, JsonReports,
+    JsonThreshold, JsonThresholds, ModelTest,
+};
+use dropshot::{
+    ApiEndpointBodyContentType, ExtensionMode, ExtractorMetadata, HttpError, RequestContext,
+    ServerContext, SharedExtractor,
+};
+
+use crate::{
+    error::{bad_request_error, not_acceptable_error},
+    model::user::auth::Headers,
+};
+
+/// The oldest API version that the server can still serialize responses for
+pub const MIN_API_VERSION: ApiVersion = ApiVersion::new(0, 4, 0);
+
+/// The API version requested by the client with the `Accept` header,
+/// ie `Accept: application/vnd.bencher.v0.4.23+json`
+/// If no version is requested, then the latest version is used.
+/// Only clients that pin an older version get downgraded responses.
+#[derive(Debug, Clone, Copy)]
+pub struct AcceptVersion(Option<ApiVersion>);
+
+#[async_trait]
+impl SharedExtractor for AcceptVersion {
+    async fn from_request<Context: ServerContext>(
+        rqctx: &RequestContext<Context>,
+    ) -> Result<Self, HttpError> {
+        let headers = Headers::from_request(rqctx).await?;
+
+        let Some(accept) = headers.0.get(http::header::ACCEPT) else {
+            return Ok(Self(None));
+        };
+        let accept = accept.to_str().map_err(|e| {
+            bad_request_error(format!("Request has an invalid \"Accept\" header: {e}"))
+        })?;
+        let Some(version) = ApiVersion::from_accept(accept).map_err(not_acceptable_error)? else {
+            return Ok(Self(None));
+        };
+        if version < MIN_API_VERSION {
+            return Err(not_acceptable_error(format!(
+                "Requested API version ({version}) is no longer supported. The oldest supported API version is {MIN_API_VERSION}."
+            )));
+        }
+        Ok(Self(Some(version)))
+    }
+
+    fn metadata(_body_content_type: ApiEndpointBodyContentType) -> ExtractorMetadata {
+        ExtractorMetadata {
+            extension_mode: ExtensionMode::None,
+            parameters: Vec::new(),
+        }
+    }
+}
+
+impl AcceptVersion {
+    // This is required due to a limitation in `dropshot` where only four extractors are allowed.
+    pub async fn new<Context: ServerContext>(
+        rqctx: &RequestContext<Context>,
+    ) -> Result<Self, HttpError> {
+        Self::from_request(rqctx).await
+    }
+
+    /// Serialize the response JSON for the requested API version
+    pub fn json<T>(self, json: T) -> T
+    where
+        T: Versioned,
+    {
+        if let Some(version) = self.0 {
+            json.downgrade(version)
+        } else {
+            json
+        }
+    }
+}
+
+/// A response body that has changed in a way that older clients can not deserialize.
+/// Each change should be gated on the first API version that supports it.
+/// Older clients ignore any fields that they do not know about,
+/// so only new enum variants need to be downgraded.
+pub trait Versioned: Sized {
+    #[must_use]
+    fn downgrade(self, version: ApiVersion) -> Self;
+}
+
+impl Versioned for JsonThresholds {
+    fn downgrade(self, version: ApiVersion) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .map(|threshold| threshold.downgrade(version))
+                .collect(),
+        )
+    }
+}
+
+impl Versioned for JsonThreshold {
+    fn downgrade(mut self, version: ApiVersion) -> Self {
+        // Present the threshold as not having a model
+        if self
+            .model
+            .is_some_and(|model| !is_supported_model(&model, version))
+        {
+            self.model = None;
+        }
+        self
+    }
+}
+
+// The `ewma` Threshold Model Test is unknown before v0.4.23
+fn is_supported_model(model: &JsonModel, version: ApiVersion) -> bool {
+    version >= ApiVersion::V0_4_23 || !matches!(model.test, ModelTest::Ewma)
+}
+
+impl Versioned for JsonAlerts {
+    fn downgrade(self, version: ApiVersion) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .map(|alert| alert.downgrade(version))
+                .collect(),
+        )
+    }
+}
+
+impl Versioned for JsonAlert {
+    fn downgrade(mut self, version: ApiVersion) -> Self {
+        self.threshold = self.threshold.downgrade(version);
+        self
+    }
+}
+
+impl Versioned for JsonReports {
+    fn downgrade(self, version: ApiVersion) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .map(|report| report.downgrade(version))
+                .collect(),
+        )
+    }
+}
+
+impl Versioned for JsonReport {
+    fn downgrade(mut self, version: ApiVersion) -> Self {
+        self.adapter = self.adapter.downgrade(version);
+        self.results = self
+            .results
+            .into_iter()
+            .map(|iteration| {
+                iteration
+                    .into_iter()
+                    .map(|mut result| {
+                        result.measures = result
+                            .measures
+                            .into_iter()
+                            .map(|measure| measure.downgrade(version))
+                            .collect();
+                        result
+                    })
+                    .collect()
+            })
+            .collect();
+        self.alerts = self
+            .alerts
+            .into_iter()
+            .map(|alert| alert.downgrade(version))
+            .collect();
+        self
+    }
+}
+
+impl Versioned for JsonReportMeasure {
+    fn downgrade(mut self, version: ApiVersion) -> Self {
+        // Present the measure as not having a threshold, which also means it has no boundary
+        if self
+            .threshold
+            .as_ref()
+            .is_some_and(|threshold| !is_supported_model(&threshold.model, version))
+        {
+            self.threshold = None;
+            self.boundary = None;
+        }
+        self
+    }
+}
+
+impl Versioned for JsonReportShard {
+    fn downgrade(mut self, version: ApiVersion) -> Self {
+        self.report = self.report.map(|report| report.downgrade(version));
+        self
+    }
+}
+
+impl Versioned for Adapter {
+    fn downgrade(self, version: ApiVersion) -> Self {
+        if version >= ApiVersion::V0_4_23 {
+            return self;
+        }
+        // The Node test runner, tinybench, pyperf, and richbench adapters are unknown before v0.4.23,
+        // so present them as the magic adapter for their language.
+        match self {
+            Self::JsNodeTest | Self::JsTinybench => Self::Js,
+            Self::PythonPyperf | Self::PythonRichbench => Self::Python,
+            adapter @ (Self::Magic
+            | Self::Json
+            | Self::Rust
+            | Self::RustBench
+            | Self::RustCriterion
+            | Self::RustIai
+            | Self::RustIaiCallgrind
+            | Self::Cpp
+            | Self::CppGoogle
+            | Self::CppCatch2
+            | Self::Go
+            | Self::GoBench
+            | Self::Java
+            | Self::JavaJmh
+            | Self::CSharp
+            | Self::CSharpDotNet
+            | Self::Js
+            | Self::JsBenchmark
+            | Self::JsTime
+            | Self::Python
+            | Self::PythonAsv
+            | Self::PythonPytest
+            | Self::Ruby
+            | Self::RubyBenchmark
+            | Self::Shell
+            | Self::ShellHyperfine) => adapter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{project::report::Adapter, ApiVersion};
+    use pretty_assertions::assert_eq;
+
+    use super::{AcceptVersion, Versioned, MIN_API_VERSION};
+
+    #[test]
+    fn test_adapter_downgrade() {
+        for (adapter, downgraded) in [
+            (Adapter::JsNodeTest, Adapter::Js),
+            (Adapter::JsTinybench, Adapter::Js),
+            (Adapter::PythonPyperf, Adapter::Python),
+            (Adapter::PythonRichbench, Adapter::Python),
+            (Adapter::RustCriterion, Adapter::RustCriterion),
+        ] {
+            assert_eq!(adapter.downgrade(MIN_API_VERSION), downgraded);
+            assert_eq!(adapter.downgrade(ApiVersion::V0_4_23), adapter);
+        }
+    }
+
+    #[test]
+    fn test_accept_version_unversioned() {
+        // Requests without a version get the latest version
+        assert_eq!(
+            AcceptVersion(None).json(Adapter::JsNodeTest),
+            Adapter::JsNodeTest
+        );
+        assert_eq!(
+            AcceptVersion(Some(MIN_API_VERSION)).json(Adapter::JsNodeTest),
+            Adapter::Js
+        );
+        assert_eq!(
+            AcceptVersion(Some(ApiVersion::V0_4_23)).json(Adapter::JsNodeTest),
+            Adapter::JsNodeTest
+        );
+    }
+}
@@ -1,132 +1,87 @@
 use std::fmt;
 
+use bencher_json::{ApiErrorCode, ApiErrorKind};
 use dropshot::HttpError;
 use http::StatusCode;
 use once_cell::sync::Lazy;
 use thiserror::Error;
 
-#[derive(Debug, Clone, Copy)]
-pub enum BencherResource {
-    Organization,
-    OrganizationRole,
-    Project,
-    ProjectRole,
-    Report,
-    ReportBenchmark,
-    Plot,
-    PlotBranch,
-    PlotTestbed,
-    PlotBenchmark,
-    PlotMeasure,
-    Branch,
-    Head,
-    Version,
-    HeadVersion,
-    Testbed,
-    Benchmark,
-    Measure,
-    Metric,
-    Threshold,
-    Model,
-    Boundary,
-    Alert,
-    User,
-    Token,
-    #[cfg(feature = "plus")]
-    Plan,
-    #[cfg(feature = "plus")]
-    Server,
-}
-
-impl fmt::Display for BencherResource {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Organization => "Organization",
-                Self::OrganizationRole => "Organization Role",
-                Self::Project => "Project",
-                Self::ProjectRole => "Project Role",
-                Self::Report => "Report",
-                Self::ReportBenchmark => "Report Benchmark",
-                Self::Plot => "Plot",
-                Self::PlotBranch => "Plot Branch",
-                Self::PlotTestbed => "Plot Testbed",
-                Self::PlotBenchmark => "Plot Benchmark",
-                Self::PlotMeasure => "Plot Measure",
-                Self::Branch => "Branch",
-                Self::Head => "Head",
-                Self::Version => "Version",
-                Self::HeadVersion => "Head Version",
-                Self::Testbed => "Testbed",
-                Self::Benchmark => "Benchmark",
-                Self::Measure => "Measure",
-                Self::Metric => "Metric",
-                Self::Threshold => "Threshold",
-                Self::Model => "Model",
-                Self::Boundary => "Boundary",
-                Self::Alert => "Alert",
-                Self::User => "User",
-                Self::Token => "Token",
-                #[cfg(feature = "plus")]
-                Self::Plan => "Plan",
-                #[cfg(feature = "plus")]
-                Self::Server => "Server",
-            }
-        )
-    }
-}
+pub use bencher_json::BencherResource;
 
 // https://developer.mozilla.org/en-US/docs/Web/HTTP/Status
 
+/// Create a client error with a machine-readable error code.
+/// The HTTP status code is determined by the kind of error.
+pub fn client_error<E>(error_code: ApiErrorCode, error: E) -> HttpError
+where
+    E: fmt::Display,
+{
+    let status_code =
+        StatusCode::from_u16(error_code.kind.status()).unwrap_or(StatusCode::BAD_REQUEST);
+    HttpError::for_client_error(Some(error_code.to_string()), status_code, error.to_string())
+}
+
 pub fn bad_request_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
 {
-    HttpError::for_client_error(None, StatusCode::BAD_REQUEST, error.to_string())
+    client_error(ApiErrorCode::new(ApiErrorKind::BadRequest), error)
 }
 
 pub fn unauthorized_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
 {
-    HttpError::for_client_error(None, StatusCode::UNAUTHORIZED, error.to_string())
+    client_error(ApiErrorCode::new(ApiErrorKind::Unauthorized), error)
 }
 
 pub fn payment_required_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
 {
-    HttpError::for_client_error(None, StatusCode::PAYMENT_REQUIRED, error.to_string())
+    client_error(ApiErrorCode::new(ApiErrorKind::PaymentRequired), error)
 }
 
 pub fn forbidden_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
 {
-    HttpError::for_client_error(None, StatusCode::FORBIDDEN, error.to_string())
+    client_error(ApiErrorCode::new(ApiErrorKind::Forbidden), error)
 }
 
 pub fn not_found_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
 {
-    HttpError::for_client_error(None, StatusCode::NOT_FOUND, error.to_string())
+    client_error(ApiErrorCode::new(ApiErrorKind::NotFound), error)
+}
+
+pub fn not_acceptable_error<E>(error: E) -> HttpError
+where
+    E: fmt::Display,
+{
+    client_error(ApiErrorCode::new(ApiErrorKind::NotAcceptable), error)
 }
 
 pub fn conflict_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
 {
-    HttpError::for_client_error(None, StatusCode::CONFLICT, error.to_string())
+    client_error(ApiErrorCode::new(ApiErrorKind::Conflict), error)
+}
+
+pub fn payload_too_large_error<E>(error: E) -> HttpError
+where
+    E: fmt::Display,
+{
+    client_error(ApiErrorCode::new(ApiErrorKind::PayloadTooLarge), error)
 }
 
 pub fn locked_error<E>(error: E) -> HttpError
 where
     E: fmt::Display,
 {
-    HttpError::for_client_error(None, StatusCode::LOCKED, error.to_string())
+    client_error(ApiErrorCode::new(ApiErrorKind::Locked), error)
 }
 
 pub fn resource_not_found_error<V, E>(resource: BencherResource, value: V, error: E) -> HttpError
@@ -134,7 +89,10 @@ where
     V: fmt::Debug,
     E: fmt::Display,
 {
-    not_found_error(format!("{resource} ({value:?}) not found: {error}",))
+    client_error(
+        ApiErrorCode::with_resource(ApiErrorKind::NotFound, resource),
+        format!("{resource} ({value:?}) not found: {error}"),
+    )
 }
 
 pub fn resource_conflict_error<V, E>(resource: BencherResource, value: V, error: E) -> HttpError
@@ -153,7 +111,10 @@ where
         #[cfg(feature = "sentry")]
         sentry::capture_error(&err);
     }
-    conflict_error(err)
+    client_error(
+        ApiErrorCode::with_resource(ApiErrorKind::Conflict, resource),
+        err,
+    )
 }
 
 #[derive(Debug, Error)]
@@ -209,16 +170,21 @@ pub fn issue_error<E>(status_code: StatusCode, title: &str, body: &str, error: E
 where
     E: fmt::Display,
 {
-    let error_code = uuid::Uuid::new_v4();
+    // The error ID is unique to this error, so it can be found in the logs.
+    // The error code is the same for all errors of the same kind.
+    let error_id = uuid::Uuid::new_v4();
     let issue_url = github_issue_url(
         title,
-        &format!("{body}\nError code: {error_code}\nError: {error}"),
+        &format!("{body}\nError ID: {error_id}\nError: {error}"),
     );
+    let error_code = ApiErrorCode::new(ApiErrorKind::from_status(status_code.as_u16()));
     let http_error = HttpError {
         error_code: Some(error_code.to_string()),
         status_code,
-        external_message: format!("{title}: {error}\nPlease report this issue: {issue_url}"),
-        internal_message: format!("INTERNAL ERROR ({error_code}): {error}"),
+        external_message: format!(
+            "{title} ({error_id}): {error}\nPlease report this issue: {issue_url}"
+        ),
+        internal_message: format!("INTERNAL ERROR ({error_id}): {error}"),
     };
     // debug_assert!(false, "Internal Error Found: {http_error}");
     #[cfg(feature = "sentry")]
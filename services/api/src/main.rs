@@ -2,7 +2,10 @@
 use std::path::PathBuf;
 
 use bencher_api::{
-    config::{config_tx::ConfigTx, Config},
+    config::{
+        config_tx::{ApiServer, ConfigTx},
+        socket, Config,
+    },
     API_VERSION,
 };
 #[cfg(feature = "plus")]
@@ -30,10 +33,21 @@ pub enum ApiError {
     RunServer(String),
     #[error("Failed to join handle: {0}")]
     JoinHandle(tokio::task::JoinError),
+    #[error("Failed to start async runtime: {0}")]
+    Runtime(std::io::Error),
 }
 
-#[tokio::main]
-async fn main() -> Result<(), ApiError> {
+fn main() -> Result<(), ApiError> {
+    // This must happen before the runtime starts any threads
+    socket::take_systemd_socket();
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(ApiError::Runtime)?
+        .block_on(async_main())
+}
+
+async fn async_main() -> Result<(), ApiError> {
     let log = bencher_logger::bootstrap_logger();
     #[cfg(feature = "sentry")]
     let guard = sentry::init(sentry::ClientOptions {
@@ -78,12 +92,13 @@ async fn run(
             // Wait for Litestream to start replicating
             replicate_rx.await.map_err(LitestreamError::ReplicateRecv)?;
 
-            let mut api_handle = run_api_server(config, restart_tx);
+            let (mut api_handle, job_worker) = run_api_server(config, restart_tx).await?;
             tokio::select! {
                 _ = tokio::signal::ctrl_c() => return Ok(()),
                 restart = restart_rx.recv() => {
                     if restart.is_some() {
                         api_handle.abort();
+                        job_worker.abort();
                         litestream_handle.abort();
                         continue;
                     }
@@ -104,12 +119,13 @@ async fn run(
             }
         }
 
-        let mut api_handle = run_api_server(config, restart_tx);
+        let (mut api_handle, job_worker) = run_api_server(config, restart_tx).await?;
         tokio::select! {
             _ = tokio::signal::ctrl_c() => return Ok(()),
             restart = restart_rx.recv() => {
                 if restart.is_some() {
                     api_handle.abort();
+                    job_worker.abort();
                     continue;
                 }
                 return Err(ApiError::EmptyShutdown);
@@ -226,17 +242,29 @@ fn run_litestream(
     }))
 }
 
-fn run_api_server(
+async fn run_api_server(
     config: Config,
     restart_tx: sync::mpsc::Sender<()>,
-) -> JoinHandle<Result<(), ApiError>> {
+) -> Result<(JoinHandle<Result<(), ApiError>>, JoinHandle<()>), ApiError> {
     let config_tx = ConfigTx { config, restart_tx };
-    tokio::spawn(async move {
-        config_tx
-            .into_server()
-            .await
-            .map_err(ApiError::ConfigTxError)?
-            .await
-            .map_err(ApiError::RunServer)
-    })
+    let ApiServer {
+        http_server,
+        socket_bridge,
+        job_worker,
+    } = config_tx
+        .into_server()
+        .await
+        .map_err(ApiError::ConfigTxError)?;
+    let api_handle = tokio::spawn(async move {
+        // The socket bridge is dropped along with the server when it shuts down or restarts
+        if let Some(socket_bridge) = socket_bridge {
+            tokio::select! {
+                result = http_server => result.map_err(ApiError::RunServer),
+                () = socket_bridge.run() => Ok(()),
+            }
+        } else {
+            http_server.await.map_err(ApiError::RunServer)
+        }
+    });
+    Ok((api_handle, job_worker))
 }
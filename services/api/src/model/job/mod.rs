@@ -0,0 +1,548 @@
+use bencher_json::{DateTime, SavedQueryUuid, ThresholdUuid, WebhookDeliveryUuid};
+use chrono::{Duration, Utc};
+use diesel::{BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    context::DbConnection,
+    error::{issue_error, resource_conflict_err, resource_not_found_err},
+    schema::{self, job as job_table},
+};
+
+mod status;
+pub mod worker;
+
+pub use status::JobStatus;
+
+crate::util::typed_id::typed_id!(JobId);
+
+/// The default number of times a job is attempted before it is marked as failed
+const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+/// The delay before the first retry, which doubles for each failed attempt
+const RETRY_BASE_SECONDS: i64 = 30;
+/// The longest delay between retries
+const RETRY_MAX_SECONDS: i64 = 60 * 60;
+/// Jobs that have finished are kept for this long before they are pruned
+pub const JOB_RETENTION_DAYS: i64 = 7;
+
+/// The work to be done for a job, serialized as JSON in the job table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobPayload {
+    /// Send an email
+    Email(JobEmail),
+    /// Downsample the metrics from reports that started before the cutoff
+    Downsample { cutoff: DateTime, dry_run: bool },
+    /// Check the benchmark measures with a threshold for flakiness
+    DetectFlaky,
+    /// Delete the finished jobs that were last modified before the cutoff
+    PruneJobs { cutoff: DateTime },
+    /// Delete the unclaimed projects that were created before the cutoff
+    PruneUnclaimed { cutoff: DateTime },
+    /// Send the anonymous telemetry stats to the endpoint
+    Telemetry { endpoint: String },
+    /// Send the daily server stats
+    #[cfg(feature = "plus")]
+    ServerStats,
+    /// Recompute the boundaries and alerts for a threshold for the reports in the time range
+    RecomputeBoundaries {
+        threshold: ThresholdUuid,
+        start_time: Option<DateTime>,
+        end_time: Option<DateTime>,
+    },
+    /// Deliver a new alert to the webhook for its project
+    Webhook { delivery: WebhookDeliveryUuid },
+    /// Send a summary of a saved query for the window that ends at the end time
+    SavedQuery {
+        saved_query: SavedQueryUuid,
+        end_time: DateTime,
+    },
+}
+
+/// A fully rendered email, so it can be sent by any API server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEmail {
+    pub to_name: Option<String>,
+    pub to_email: String,
+    pub subject: Option<String>,
+    pub text: Option<String>,
+    pub html: Option<String>,
+    /// Images that are embedded in the HTML body, ie `<img src="cid:chart" />`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<JobEmailImage>,
+}
+
+/// An image embedded in an email, so it is displayed without loading any remote content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEmailImage {
+    /// The content ID used to reference the image from the HTML body
+    pub cid: String,
+    pub content_type: String,
+    /// The base64 encoded image
+    pub data: String,
+}
+
+impl JobPayload {
+    /// How long a worker may hold a job before another worker is allowed to take it over
+    fn lease(&self) -> Duration {
+        match self {
+            Self::Email(_)
+            | Self::PruneJobs { .. }
+            | Self::PruneUnclaimed { .. }
+            | Self::Telemetry { .. }
+            | Self::Webhook { .. }
+            | Self::SavedQuery { .. } => Duration::minutes(5),
+            #[cfg(feature = "plus")]
+            Self::ServerStats => Duration::minutes(5),
+            Self::Downsample { .. } | Self::DetectFlaky | Self::RecomputeBoundaries { .. } => {
+                Duration::hours(1)
+            },
+        }
+    }
+}
+
+#[derive(Debug, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = job_table)]
+pub struct QueryJob {
+    pub id: JobId,
+    pub job_key: Option<String>,
+    pub payload: String,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime,
+    pub lease_owner: Option<String>,
+    pub lease_expires: Option<DateTime>,
+    pub last_error: Option<String>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryJob {
+    /// Add a job to the queue.
+    /// If a key is given and a job with the same key already exists,
+    /// then the new job is ignored.
+    /// This allows multiple API servers to schedule the same job without it running more than once.
+    pub fn enqueue(
+        conn: &mut DbConnection,
+        payload: &JobPayload,
+        job_key: Option<String>,
+    ) -> Result<(), HttpError> {
+        Self::enqueue_at(conn, payload, job_key, DateTime::now())
+    }
+
+    /// Add a job to the queue that will not run until the given time.
+    pub fn enqueue_at(
+        conn: &mut DbConnection,
+        payload: &JobPayload,
+        job_key: Option<String>,
+        run_at: DateTime,
+    ) -> Result<(), HttpError> {
+        let insert_job = InsertJob::new(payload, job_key, run_at)?;
+        diesel::insert_or_ignore_into(schema::job::table)
+            .values(&insert_job)
+            .execute(conn)
+            .map_err(resource_conflict_err!(Job, insert_job))?;
+        Ok(())
+    }
+
+    /// Claim the next job that is ready to run for the worker.
+    /// A job is ready to run if it is pending and its run time has passed,
+    /// or if it is running but the lease of its worker has expired.
+    pub fn claim(conn: &mut DbConnection, lease_owner: &str) -> Result<Option<Self>, HttpError> {
+        loop {
+            let now = DateTime::now();
+            let Some(query_job) = schema::job::table
+                .filter(
+                    schema::job::status
+                        .eq(JobStatus::Pending)
+                        .and(schema::job::run_at.le(now))
+                        .or(schema::job::status
+                            .eq(JobStatus::Running)
+                            .and(schema::job::lease_expires.lt(now))),
+                )
+                .order(schema::job::run_at.asc())
+                .first::<Self>(conn)
+                .optional()
+                .map_err(resource_not_found_err!(Job, now))?
+            else {
+                return Ok(None);
+            };
+
+            // A worker gave up on the job without finishing it, likely due to a restart
+            if query_job.attempts >= query_job.max_attempts {
+                query_job.finish(conn, Err("The job lease expired".to_owned()))?;
+                continue;
+            }
+
+            // A job that can never be run must not block the rest of the queue
+            let payload = match query_job.payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    query_job.fail(conn, e.to_string())?;
+                    continue;
+                },
+            };
+            let lease_expires = DateTime::from(now.into_inner() + payload.lease());
+            // Only claim the job if no other worker has claimed it in the meantime,
+            // since the number of attempts is incremented every time a job is claimed.
+            let claimed = diesel::update(
+                schema::job::table
+                    .filter(schema::job::id.eq(query_job.id))
+                    .filter(schema::job::attempts.eq(query_job.attempts)),
+            )
+            .set((
+                schema::job::status.eq(JobStatus::Running),
+                schema::job::attempts.eq(query_job.attempts + 1),
+                schema::job::lease_owner.eq(lease_owner),
+                schema::job::lease_expires.eq(lease_expires),
+                schema::job::modified.eq(now),
+            ))
+            .execute(conn)
+            .map_err(resource_conflict_err!(Job, query_job.id))?;
+            if claimed == 1 {
+                return Ok(Some(Self {
+                    status: JobStatus::Running,
+                    attempts: query_job.attempts + 1,
+                    lease_owner: Some(lease_owner.to_owned()),
+                    lease_expires: Some(lease_expires),
+                    modified: now,
+                    ..query_job
+                }));
+            }
+        }
+    }
+
+    pub fn payload(&self) -> Result<JobPayload, HttpError> {
+        serde_json::from_str(&self.payload).map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to parse job payload",
+                &format!("Failed to parse job ({id}) payload", id = self.id),
+                e,
+            )
+        })
+    }
+
+    /// Record the result of running the job.
+    /// A failed job is retried with exponential backoff until it runs out of attempts.
+    pub fn finish(
+        &self,
+        conn: &mut DbConnection,
+        result: Result<(), String>,
+    ) -> Result<(), HttpError> {
+        let now = DateTime::now();
+        let (status, run_at, last_error) = match result {
+            Ok(()) => (JobStatus::Succeeded, self.run_at, None),
+            Err(err) if self.attempts < self.max_attempts => {
+                let backoff = RETRY_BASE_SECONDS
+                    .saturating_mul(1i64 << self.attempts.clamp(0, 16))
+                    .min(RETRY_MAX_SECONDS);
+                let run_at = DateTime::from(now.into_inner() + Duration::seconds(backoff));
+                (JobStatus::Pending, run_at, Some(err))
+            },
+            Err(err) => (JobStatus::Failed, self.run_at, Some(err)),
+        };
+        self.update_status(conn, status, run_at, last_error)
+    }
+
+    /// Mark the job as failed without retrying it.
+    fn fail(&self, conn: &mut DbConnection, err: String) -> Result<(), HttpError> {
+        self.update_status(conn, JobStatus::Failed, self.run_at, Some(err))
+    }
+
+    fn update_status(
+        &self,
+        conn: &mut DbConnection,
+        status: JobStatus,
+        run_at: DateTime,
+        last_error: Option<String>,
+    ) -> Result<(), HttpError> {
+        let now = DateTime::now();
+        // Ignore the result if another worker has since taken over the job
+        diesel::update(
+            schema::job::table
+                .filter(schema::job::id.eq(self.id))
+                .filter(schema::job::attempts.eq(self.attempts)),
+        )
+        .set((
+            schema::job::status.eq(status),
+            schema::job::run_at.eq(run_at),
+            schema::job::lease_owner.eq(None::<String>),
+            schema::job::lease_expires.eq(None::<DateTime>),
+            schema::job::last_error.eq(last_error),
+            schema::job::modified.eq(now),
+        ))
+        .execute(conn)
+        .map_err(resource_conflict_err!(Job, self.id))?;
+        Ok(())
+    }
+
+    /// Delete the finished jobs that were last modified before the cutoff.
+    pub fn prune(conn: &mut DbConnection, cutoff: DateTime) -> Result<usize, HttpError> {
+        diesel::delete(
+            schema::job::table
+                .filter(schema::job::status.eq_any([JobStatus::Succeeded, JobStatus::Failed]))
+                .filter(schema::job::modified.lt(cutoff)),
+        )
+        .execute(conn)
+        .map_err(resource_conflict_err!(Job, cutoff))
+    }
+
+    pub fn prune_cutoff() -> DateTime {
+        DateTime::from(Utc::now() - Duration::days(JOB_RETENTION_DAYS))
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = job_table)]
+pub struct InsertJob {
+    pub job_key: Option<String>,
+    pub payload: String,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertJob {
+    fn new(
+        payload: &JobPayload,
+        job_key: Option<String>,
+        run_at: DateTime,
+    ) -> Result<Self, HttpError> {
+        let payload = serde_json::to_string(payload).map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to serialize job payload",
+                &format!("Failed to serialize job payload: {payload:?}"),
+                e,
+            )
+        })?;
+        let now = DateTime::now();
+        Ok(Self {
+            job_key,
+            payload,
+            status: JobStatus::Pending,
+            attempts: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            run_at,
+            created: now,
+            modified: now,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::DateTime;
+    use chrono::Duration;
+    use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+    use pretty_assertions::assert_eq;
+
+    use crate::{context::DbConnection, schema, util::test_util::setup_db};
+
+    use super::{InsertJob, JobId, JobPayload, JobStatus, QueryJob, DEFAULT_MAX_ATTEMPTS};
+
+    fn payload() -> JobPayload {
+        JobPayload::PruneJobs {
+            cutoff: DateTime::now(),
+        }
+    }
+
+    fn get_job(conn: &mut DbConnection, job_id: JobId) -> QueryJob {
+        schema::job::table
+            .filter(schema::job::id.eq(job_id))
+            .first(conn)
+            .unwrap()
+    }
+
+    fn job_count(conn: &mut DbConnection) -> i64 {
+        schema::job::table.count().get_result(conn).unwrap()
+    }
+
+    fn seconds_ago(seconds: i64) -> DateTime {
+        DateTime::from(DateTime::now().into_inner() - Duration::seconds(seconds))
+    }
+
+    #[test]
+    fn test_enqueue_job_key() {
+        let mut conn = setup_db();
+        QueryJob::enqueue(&mut conn, &payload(), Some("key".to_owned())).unwrap();
+        QueryJob::enqueue(&mut conn, &payload(), Some("key".to_owned())).unwrap();
+        assert_eq!(job_count(&mut conn), 1);
+        QueryJob::enqueue(&mut conn, &payload(), None).unwrap();
+        QueryJob::enqueue(&mut conn, &payload(), None).unwrap();
+        assert_eq!(job_count(&mut conn), 3);
+    }
+
+    #[test]
+    fn test_claim_finish() {
+        let mut conn = setup_db();
+        assert!(QueryJob::claim(&mut conn, "worker").unwrap().is_none());
+
+        QueryJob::enqueue(&mut conn, &payload(), None).unwrap();
+        let query_job = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+        assert_eq!(query_job.status, JobStatus::Running);
+        assert_eq!(query_job.attempts, 1);
+        assert_eq!(query_job.lease_owner.as_deref(), Some("worker"));
+        assert!(matches!(
+            query_job.payload().unwrap(),
+            JobPayload::PruneJobs { .. }
+        ));
+        // The job is leased to the first worker
+        assert!(QueryJob::claim(&mut conn, "other").unwrap().is_none());
+
+        query_job.finish(&mut conn, Ok(())).unwrap();
+        let finished = get_job(&mut conn, query_job.id);
+        assert_eq!(finished.status, JobStatus::Succeeded);
+        assert_eq!(finished.lease_owner, None);
+        assert_eq!(finished.lease_expires, None);
+        assert!(QueryJob::claim(&mut conn, "worker").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_claim_run_at() {
+        let mut conn = setup_db();
+        let run_at = DateTime::from(DateTime::now().into_inner() + Duration::hours(1));
+        QueryJob::enqueue_at(&mut conn, &payload(), None, run_at).unwrap();
+        assert!(QueryJob::claim(&mut conn, "worker").unwrap().is_none());
+
+        // Jobs are claimed in the order they are ready to run
+        QueryJob::enqueue_at(&mut conn, &payload(), None, seconds_ago(10)).unwrap();
+        QueryJob::enqueue_at(&mut conn, &payload(), None, seconds_ago(20)).unwrap();
+        let first = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+        let second = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+        assert!(first.run_at < second.run_at);
+        assert!(QueryJob::claim(&mut conn, "worker").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_finish_backoff() {
+        let mut conn = setup_db();
+        QueryJob::enqueue(&mut conn, &payload(), None).unwrap();
+
+        for attempt in 1..DEFAULT_MAX_ATTEMPTS {
+            let query_job = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+            assert_eq!(query_job.attempts, attempt);
+            let before = DateTime::now();
+            query_job
+                .finish(&mut conn, Err("error".to_owned()))
+                .unwrap();
+
+            let retry = get_job(&mut conn, query_job.id);
+            assert_eq!(retry.status, JobStatus::Pending);
+            assert_eq!(retry.last_error.as_deref(), Some("error"));
+            // The delay doubles for each failed attempt
+            let backoff = (retry.run_at.into_inner() - before.into_inner()).num_seconds();
+            let expected = 30 * (1 << attempt);
+            assert!(
+                (expected - 1..=expected + 1).contains(&backoff),
+                "{backoff} != {expected}"
+            );
+            assert!(QueryJob::claim(&mut conn, "worker").unwrap().is_none());
+
+            // Skip ahead to the retry
+            diesel::update(schema::job::table.filter(schema::job::id.eq(query_job.id)))
+                .set(schema::job::run_at.eq(seconds_ago(1)))
+                .execute(&mut conn)
+                .unwrap();
+        }
+
+        // The last attempt fails the job for good
+        let query_job = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+        assert_eq!(query_job.attempts, DEFAULT_MAX_ATTEMPTS);
+        query_job
+            .finish(&mut conn, Err("error".to_owned()))
+            .unwrap();
+        assert_eq!(get_job(&mut conn, query_job.id).status, JobStatus::Failed);
+        assert!(QueryJob::claim(&mut conn, "worker").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_claim_lease_takeover() {
+        let mut conn = setup_db();
+        QueryJob::enqueue(&mut conn, &payload(), None).unwrap();
+        let stale_job = QueryJob::claim(&mut conn, "stale").unwrap().unwrap();
+
+        // The lease of the first worker expires
+        diesel::update(schema::job::table.filter(schema::job::id.eq(stale_job.id)))
+            .set(schema::job::lease_expires.eq(seconds_ago(1)))
+            .execute(&mut conn)
+            .unwrap();
+        let query_job = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+        assert_eq!(query_job.id, stale_job.id);
+        assert_eq!(query_job.attempts, 2);
+        assert_eq!(query_job.lease_owner.as_deref(), Some("worker"));
+
+        // The result from the first worker is ignored
+        stale_job.finish(&mut conn, Ok(())).unwrap();
+        let running = get_job(&mut conn, query_job.id);
+        assert_eq!(running.status, JobStatus::Running);
+        assert_eq!(running.lease_owner.as_deref(), Some("worker"));
+
+        query_job.finish(&mut conn, Ok(())).unwrap();
+        assert_eq!(
+            get_job(&mut conn, query_job.id).status,
+            JobStatus::Succeeded
+        );
+    }
+
+    #[test]
+    fn test_claim_lease_expired_last_attempt() {
+        let mut conn = setup_db();
+        QueryJob::enqueue(&mut conn, &payload(), None).unwrap();
+        let query_job = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+        diesel::update(schema::job::table.filter(schema::job::id.eq(query_job.id)))
+            .set((
+                schema::job::attempts.eq(DEFAULT_MAX_ATTEMPTS),
+                schema::job::lease_expires.eq(seconds_ago(1)),
+            ))
+            .execute(&mut conn)
+            .unwrap();
+
+        assert!(QueryJob::claim(&mut conn, "other").unwrap().is_none());
+        let failed = get_job(&mut conn, query_job.id);
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(failed.last_error.as_deref(), Some("The job lease expired"));
+    }
+
+    #[test]
+    fn test_claim_bad_payload() {
+        let mut conn = setup_db();
+        // The bad job is ready to run before the good one
+        let now = seconds_ago(10);
+        diesel::insert_into(schema::job::table)
+            .values(&InsertJob {
+                job_key: None,
+                payload: "{\"kind\":\"unknown\"}".to_owned(),
+                status: JobStatus::Pending,
+                attempts: 0,
+                max_attempts: DEFAULT_MAX_ATTEMPTS,
+                run_at: now,
+                created: now,
+                modified: now,
+            })
+            .execute(&mut conn)
+            .unwrap();
+        QueryJob::enqueue(&mut conn, &payload(), None).unwrap();
+
+        let query_job = QueryJob::claim(&mut conn, "worker").unwrap().unwrap();
+        assert!(matches!(
+            query_job.payload().unwrap(),
+            JobPayload::PruneJobs { .. }
+        ));
+
+        let bad_job = schema::job::table
+            .filter(schema::job::id.ne(query_job.id))
+            .first::<QueryJob>(&mut conn)
+            .unwrap();
+        assert_eq!(bad_job.status, JobStatus::Failed);
+        assert_eq!(bad_job.attempts, 0);
+        assert!(bad_job.last_error.is_some());
+    }
+}
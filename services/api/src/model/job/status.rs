@@ -0,0 +1,58 @@
+const PENDING_INT: i32 = 0;
+const RUNNING_INT: i32 = 1;
+const SUCCEEDED_INT: i32 = 2;
+const FAILED_INT: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, diesel::FromSqlRow, diesel::AsExpression)]
+#[diesel(sql_type = diesel::sql_types::Integer)]
+#[repr(i32)]
+pub enum JobStatus {
+    /// The job is waiting to be run.
+    Pending = PENDING_INT,
+    /// The job has been claimed by a worker.
+    Running = RUNNING_INT,
+    /// The job finished successfully.
+    Succeeded = SUCCEEDED_INT,
+    /// The job ran out of attempts.
+    Failed = FAILED_INT,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobStatusError {
+    #[error("Invalid job status value: {0}")]
+    Invalid(i32),
+}
+
+impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for JobStatus
+where
+    DB: diesel::backend::Backend,
+    i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        match self {
+            Self::Pending => PENDING_INT.to_sql(out),
+            Self::Running => RUNNING_INT.to_sql(out),
+            Self::Succeeded => SUCCEEDED_INT.to_sql(out),
+            Self::Failed => FAILED_INT.to_sql(out),
+        }
+    }
+}
+
+impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for JobStatus
+where
+    DB: diesel::backend::Backend,
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        match i32::from_sql(bytes)? {
+            PENDING_INT => Ok(Self::Pending),
+            RUNNING_INT => Ok(Self::Running),
+            SUCCEEDED_INT => Ok(Self::Succeeded),
+            FAILED_INT => Ok(Self::Failed),
+            value => Err(Box::new(JobStatusError::Invalid(value))),
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::sync::Arc;
+
+use bencher_json::{DateTime, JsonDownsampled, SavedQueryUuid, WebhookDeliveryUuid};
+use chrono::{NaiveDate, Utc};
+use dropshot::HttpError;
+use slog::Logger;
+use tokio::{sync::Mutex, task::JoinHandle};
+
+#[cfg(feature = "plus")]
+use crate::model::server::ServerStatsSender;
+use crate::{
+    config::{downsample::DownsampleSettings, telemetry::TelemetrySettings},
+    context::{DbConnection, Messenger},
+    model::{
+        project::{
+            flaky::{detect_flaky, DetectedFlaky},
+            metric_downsample::downsample,
+            saved_query::QuerySavedQuery,
+            threshold::recompute::{recompute_boundaries, RecomputeSettings},
+            unclaimed_project::QueryUnclaimedProject,
+            webhook::QueryWebhookDelivery,
+        },
+        telemetry::get_telemetry_stats,
+    },
+};
+
+use super::{JobPayload, QueryJob};
+
+const JOB_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// A background worker that runs jobs from the durable job queue.
+/// Each API server runs its own worker,
+/// and jobs are leased to a single worker at a time.
+pub struct JobWorker {
+    log: Logger,
+    conn: Arc<Mutex<DbConnection>>,
+    messenger: Messenger,
+    downsample: Option<DownsampleSettings>,
+    telemetry: Option<TelemetrySettings>,
+    recompute: RecomputeSettings,
+    #[cfg(feature = "plus")]
+    server_stats: ServerStatsSender,
+    lease_owner: String,
+}
+
+impl JobWorker {
+    pub fn new(
+        log: Logger,
+        conn: Arc<Mutex<DbConnection>>,
+        messenger: Messenger,
+        downsample: Option<DownsampleSettings>,
+        telemetry: Option<TelemetrySettings>,
+        recompute: RecomputeSettings,
+        #[cfg(feature = "plus")] server_stats: ServerStatsSender,
+    ) -> Self {
+        Self {
+            log,
+            conn,
+            messenger,
+            downsample,
+            telemetry,
+            recompute,
+            #[cfg(feature = "plus")]
+            server_stats,
+            lease_owner: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Run the worker until the returned handle is aborted
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            slog::info!(self.log, "Starting job worker: {}", self.lease_owner);
+            let mut scheduled = None;
+            #[allow(clippy::infinite_loop)]
+            loop {
+                let today = Utc::now().date_naive();
+                if scheduled != Some(today) {
+                    match self.schedule(today).await {
+                        Ok(()) => scheduled = Some(today),
+                        Err(e) => slog::error!(self.log, "Failed to schedule daily jobs: {e}"),
+                    }
+                }
+
+                match self.run_next().await {
+                    // Keep going while there are jobs ready to run
+                    Ok(true) => continue,
+                    Ok(false) => {},
+                    Err(e) => slog::error!(self.log, "Failed to run job: {e}"),
+                }
+                tokio::time::sleep(JOB_POLL_INTERVAL).await;
+            }
+        })
+    }
+
+    /// Schedule the daily jobs.
+    /// They are keyed by the UTC date, so they only run once a day across all API servers.
+    async fn schedule(&self, today: NaiveDate) -> Result<(), HttpError> {
+        let conn = &mut *self.conn.lock().await;
+        if let Some(downsample) = self.downsample {
+            let payload = JobPayload::Downsample {
+                cutoff: downsample.cutoff(),
+                dry_run: downsample.dry_run,
+            };
+            QueryJob::enqueue(conn, &payload, Some(format!("downsample-{today}")))?;
+        }
+        if let Some(telemetry) = &self.telemetry {
+            let payload = JobPayload::Telemetry {
+                endpoint: telemetry.endpoint.to_string(),
+            };
+            QueryJob::enqueue(conn, &payload, Some(format!("telemetry-{today}")))?;
+        }
+        QueryJob::enqueue(
+            conn,
+            &JobPayload::DetectFlaky,
+            Some(format!("detect-flaky-{today}")),
+        )?;
+        #[cfg(feature = "plus")]
+        QueryJob::enqueue_at(
+            conn,
+            &JobPayload::ServerStats,
+            Some(format!("server-stats-{today}")),
+            self.server_stats.run_at(today),
+        )?;
+        let end_time = DateTime::now();
+        for saved_query in QuerySavedQuery::due(conn, end_time)? {
+            let payload = JobPayload::SavedQuery {
+                saved_query: saved_query.uuid,
+                end_time,
+            };
+            let job_key = format!("saved-query-{uuid}-{today}", uuid = saved_query.uuid);
+            QueryJob::enqueue(conn, &payload, Some(job_key))?;
+        }
+        let payload = JobPayload::PruneUnclaimed {
+            cutoff: QueryUnclaimedProject::prune_cutoff(),
+        };
+        QueryJob::enqueue(conn, &payload, Some(format!("prune-unclaimed-{today}")))?;
+        let payload = JobPayload::PruneJobs {
+            cutoff: QueryJob::prune_cutoff(),
+        };
+        QueryJob::enqueue(conn, &payload, Some(format!("prune-jobs-{today}")))
+    }
+
+    /// Run the next job that is ready to run, if there is one.
+    async fn run_next(&self) -> Result<bool, HttpError> {
+        let Some(query_job) = QueryJob::claim(&mut *self.conn.lock().await, &self.lease_owner)?
+        else {
+            return Ok(false);
+        };
+
+        // The job will not be retried if this attempt fails
+        let last_attempt = query_job.attempts >= query_job.max_attempts;
+        let result = match query_job.payload() {
+            Ok(payload) => self.run(payload, last_attempt).await,
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = &result {
+            slog::warn!(
+                self.log,
+                "Job ({}) attempt {} of {} failed: {e}",
+                query_job.id,
+                query_job.attempts,
+                query_job.max_attempts
+            );
+        }
+        query_job.finish(&mut *self.conn.lock().await, result)?;
+        Ok(true)
+    }
+
+    async fn run(&self, payload: JobPayload, last_attempt: bool) -> Result<(), String> {
+        match payload {
+            JobPayload::Email(job_email) => self
+                .messenger
+                .deliver(&self.log, job_email)
+                .await
+                .map_err(|e| e.to_string()),
+            JobPayload::Downsample { cutoff, dry_run } => {
+                slog::info!(
+                    self.log,
+                    "Downsampling metrics before {cutoff} (dry run: {dry_run})"
+                );
+                let JsonDownsampled {
+                    aggregates,
+                    removed,
+                    ..
+                } = downsample(&self.conn, cutoff, dry_run)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                slog::info!(
+                    self.log,
+                    "Downsampled metrics before {cutoff} into {aggregates} daily aggregates, removing {removed} metrics (dry run: {dry_run})"
+                );
+                Ok(())
+            },
+            JobPayload::DetectFlaky => {
+                let DetectedFlaky {
+                    checked,
+                    flaky,
+                    resolved,
+                } = detect_flaky(&self.log, &self.conn, self.recompute.flaky)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                slog::info!(
+                    self.log,
+                    "Checked {checked} benchmark measures for flakiness: {flaky} flaky and {resolved} no longer flaky"
+                );
+                Ok(())
+            },
+            JobPayload::PruneJobs { cutoff } => {
+                let pruned = QueryJob::prune(&mut *self.conn.lock().await, cutoff)
+                    .map_err(|e| e.to_string())?;
+                slog::debug!(self.log, "Pruned {pruned} finished jobs before {cutoff}");
+                Ok(())
+            },
+            JobPayload::PruneUnclaimed { cutoff } => {
+                let pruned = QueryUnclaimedProject::prune(&mut *self.conn.lock().await, cutoff)
+                    .map_err(|e| e.to_string())?;
+                slog::debug!(
+                    self.log,
+                    "Pruned {pruned} unclaimed projects created before {cutoff}"
+                );
+                Ok(())
+            },
+            JobPayload::Telemetry { endpoint } => self.send_telemetry(&endpoint).await,
+            JobPayload::Webhook { delivery } => self.deliver_webhook(delivery, last_attempt).await,
+            JobPayload::SavedQuery {
+                saved_query,
+                end_time,
+            } => self.send_saved_query(saved_query, end_time).await,
+            JobPayload::RecomputeBoundaries {
+                threshold,
+                start_time,
+                end_time,
+            } => {
+                let recomputed = recompute_boundaries(
+                    &self.log,
+                    &self.conn,
+                    self.recompute,
+                    threshold,
+                    start_time,
+                    end_time,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+                slog::info!(
+                    self.log,
+                    "Recomputed {boundaries} boundaries for threshold ({threshold}), skipping {skipped}: {created} alerts created, {updated} updated, and {resolved} resolved",
+                    boundaries = recomputed.boundaries,
+                    skipped = recomputed.skipped,
+                    created = recomputed.created,
+                    updated = recomputed.updated,
+                    resolved = recomputed.resolved,
+                );
+                Ok(())
+            },
+            #[cfg(feature = "plus")]
+            JobPayload::ServerStats => self.server_stats.send(&self.log, &self.conn).await,
+        }
+    }
+
+    async fn deliver_webhook(
+        &self,
+        delivery: WebhookDeliveryUuid,
+        last_attempt: bool,
+    ) -> Result<(), String> {
+        let request = {
+            let conn = &mut *self.conn.lock().await;
+            match QueryWebhookDelivery::from_delivery_uuid(conn, delivery) {
+                Ok(Some(query_delivery)) => query_delivery.request(conn),
+                // The delivery was removed along with its alert or project
+                Ok(None) => Ok(None),
+                Err(e) => Err(e),
+            }
+            .map_err(|e| e.to_string())?
+        };
+        // The webhook was deleted after the delivery was queued
+        let Some(request) = request else {
+            slog::debug!(
+                self.log,
+                "Skipping webhook delivery ({delivery}) without a webhook"
+            );
+            return Ok(());
+        };
+
+        let (response_status, result) = request.send().await;
+        request
+            .record(
+                &mut *self.conn.lock().await,
+                response_status,
+                &result,
+                last_attempt,
+            )
+            .map_err(|e| e.to_string())?;
+        result
+    }
+
+    async fn send_saved_query(
+        &self,
+        saved_query: SavedQueryUuid,
+        end_time: DateTime,
+    ) -> Result<(), String> {
+        let query_saved_query =
+            QuerySavedQuery::from_saved_query_uuid(&mut *self.conn.lock().await, saved_query)
+                .map_err(|e| e.to_string())?;
+        // The saved query was deleted after the summary was queued
+        let Some(query_saved_query) = query_saved_query else {
+            slog::debug!(
+                self.log,
+                "Skipping summary for deleted saved query ({saved_query})"
+            );
+            return Ok(());
+        };
+
+        let summary = query_saved_query
+            .summary(&self.conn, end_time)
+            .await
+            .map_err(|e| e.to_string())?;
+        let request = {
+            let conn = &mut *self.conn.lock().await;
+            query_saved_query
+                .enqueue_emails(&self.log, conn, &summary)
+                .and_then(|()| query_saved_query.webhook_request(conn, summary))
+                .map_err(|e| e.to_string())?
+        };
+        if let Some(request) = request {
+            let (_, result) = request.send().await;
+            result?;
+        }
+        query_saved_query
+            .sent(&mut *self.conn.lock().await, end_time)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn send_telemetry(&self, endpoint: &str) -> Result<(), String> {
+        let json_stats =
+            get_telemetry_stats(&mut *self.conn.lock().await).map_err(|e| e.to_string())?;
+        let json_stats_str = serde_json::to_string(&json_stats).map_err(|e| e.to_string())?;
+        slog::info!(
+            self.log,
+            "Sending telemetry to {endpoint}: {json_stats_str}"
+        );
+        reqwest::Client::new()
+            .post(endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(json_stats_str)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
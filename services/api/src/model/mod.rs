@@ -1,6 +1,8 @@
+pub mod job;
 pub mod organization;
 pub mod project;
 pub mod server;
+pub mod telemetry;
 pub mod user;
 
 // https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDateTime.html#impl-Display-for-NaiveDateTime
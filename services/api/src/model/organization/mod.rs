@@ -24,6 +24,7 @@ use crate::{
 pub mod member;
 pub mod organization_role;
 pub mod plan;
+pub mod token_policy;
 
 crate::util::typed_id::typed_id!(OrganizationId);
 
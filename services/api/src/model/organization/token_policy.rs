@@ -0,0 +1,380 @@
+use bencher_json::{
+    organization::token_policy::{JsonTokenPolicy, JsonUpdateTokenPolicy},
+    ApiErrorCode, ApiErrorKind, DateTime,
+};
+use bencher_rbac::organization::Permission;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    context::{DbConnection, Rbac},
+    error::{client_error, resource_conflict_err, resource_not_found_err, BencherResource},
+    model::user::{auth::AuthUser, QueryUser},
+    schema::{self, token_policy as token_policy_table},
+};
+
+use super::{OrganizationId, QueryOrganization};
+
+crate::util::typed_id::typed_id!(TokenPolicyId);
+
+/// The longest time-to-live (TTL) in seconds for a new token when non-expiring tokens are forbidden.
+/// A token that lives any longer than a year in practice never expires.
+pub const MAX_EXPIRING_TTL: u32 = 365 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = token_policy_table)]
+pub struct QueryTokenPolicy {
+    pub id: TokenPolicyId,
+    pub organization_id: OrganizationId,
+    pub max_ttl: Option<i64>,
+    pub require_ttl: bool,
+    pub forbid_non_expiring: bool,
+    pub admin_only: bool,
+    pub modified: DateTime,
+}
+
+impl QueryTokenPolicy {
+    pub fn get_for_organization(
+        conn: &mut DbConnection,
+        organization_id: OrganizationId,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::token_policy::table
+            .filter(schema::token_policy::organization_id.eq(organization_id))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(TokenPolicy, organization_id))
+    }
+
+    fn max_ttl(&self) -> Option<u32> {
+        // A stored TTL that does not fit is larger than any requested TTL
+        self.max_ttl
+            .map(|max_ttl| u32::try_from(max_ttl).unwrap_or(u32::MAX))
+    }
+
+    /// An organization without a stored policy has no restrictions.
+    pub fn into_json(
+        policy: Option<&Self>,
+        query_organization: &QueryOrganization,
+    ) -> JsonTokenPolicy {
+        let max_ttl = policy.and_then(Self::max_ttl);
+        let Some(&Self {
+            require_ttl,
+            forbid_non_expiring,
+            admin_only,
+            modified,
+            ..
+        }) = policy
+        else {
+            return JsonTokenPolicy {
+                organization: query_organization.uuid,
+                max_ttl: None,
+                require_ttl: false,
+                forbid_non_expiring: false,
+                admin_only: false,
+                modified: None,
+            };
+        };
+        JsonTokenPolicy {
+            organization: query_organization.uuid,
+            max_ttl,
+            require_ttl,
+            forbid_non_expiring,
+            admin_only,
+            modified: Some(modified),
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = token_policy_table)]
+#[diesel(treat_none_as_null = true)]
+pub struct UpsertTokenPolicy {
+    pub organization_id: OrganizationId,
+    pub max_ttl: Option<i64>,
+    pub require_ttl: bool,
+    pub forbid_non_expiring: bool,
+    pub admin_only: bool,
+    pub modified: DateTime,
+}
+
+impl UpsertTokenPolicy {
+    pub fn new(query_organization: &QueryOrganization, policy: &JsonUpdateTokenPolicy) -> Self {
+        let &JsonUpdateTokenPolicy {
+            max_ttl,
+            require_ttl,
+            forbid_non_expiring,
+            admin_only,
+        } = policy;
+        Self {
+            organization_id: query_organization.id,
+            max_ttl: max_ttl.map(Into::into),
+            require_ttl: require_ttl.unwrap_or_default(),
+            forbid_non_expiring: forbid_non_expiring.unwrap_or_default(),
+            admin_only: admin_only.unwrap_or_default(),
+            modified: DateTime::now(),
+        }
+    }
+
+    pub fn execute(&self, conn: &mut DbConnection) -> Result<(), HttpError> {
+        let exists = QueryTokenPolicy::get_for_organization(conn, self.organization_id)?.is_some();
+        if exists {
+            diesel::update(
+                schema::token_policy::table
+                    .filter(schema::token_policy::organization_id.eq(self.organization_id)),
+            )
+            .set(self)
+            .execute(conn)
+        } else {
+            diesel::insert_into(schema::token_policy::table)
+                .values(self)
+                .execute(conn)
+        }
+        .map(|_| ())
+        .map_err(resource_conflict_err!(TokenPolicy, self))
+    }
+}
+
+/// Check a new API token for the user against the token policies of all of their organizations.
+/// Returns the time-to-live (TTL) to use for the new token.
+/// If no TTL is requested, then the smallest maximum TTL from the policies is used.
+pub fn check_token_policies(
+    conn: &mut DbConnection,
+    rbac: &Rbac,
+    query_user: &QueryUser,
+    auth_user: &AuthUser,
+    ttl: Option<u32>,
+) -> Result<u32, HttpError> {
+    let policies = schema::token_policy::table
+        .inner_join(schema::organization::table)
+        .filter(
+            schema::token_policy::organization_id.eq_any(
+                schema::organization_role::table
+                    .filter(schema::organization_role::user_id.eq(query_user.id))
+                    .select(schema::organization_role::organization_id),
+            ),
+        )
+        .select((
+            schema::organization::all_columns,
+            schema::token_policy::all_columns,
+        ))
+        .load::<(QueryOrganization, QueryTokenPolicy)>(conn)
+        .map_err(resource_not_found_err!(TokenPolicy, query_user))?;
+
+    for (query_organization, policy) in &policies {
+        let slug = &query_organization.slug;
+        if policy.admin_only
+            && rbac
+                .is_allowed_organization(auth_user, Permission::Manage, query_organization)
+                .is_err()
+        {
+            return Err(token_policy_error(format!(
+                "The token policy for organization ({slug}) only allows organization leaders to create API tokens"
+            )));
+        }
+        if policy.require_ttl && ttl.is_none() {
+            return Err(token_policy_error(format!(
+                "The token policy for organization ({slug}) requires API tokens to have a time-to-live (TTL)"
+            )));
+        }
+        if let (Some(ttl), Some(max_ttl)) = (ttl, policy.max_ttl()) {
+            if ttl > max_ttl {
+                return Err(token_policy_error(format!(
+                    "The token policy for organization ({slug}) allows a maximum time-to-live (TTL) of {max_ttl} seconds, but {ttl} seconds was requested"
+                )));
+            }
+        }
+    }
+
+    let ttl = ttl
+        .or_else(|| {
+            policies
+                .iter()
+                .filter_map(|(_, policy)| policy.max_ttl())
+                .min()
+        })
+        .unwrap_or(u32::MAX);
+    if ttl > MAX_EXPIRING_TTL {
+        if let Some((query_organization, _)) = policies
+            .iter()
+            .find(|(_, policy)| policy.forbid_non_expiring)
+        {
+            return Err(token_policy_error(format!(
+                "The token policy for organization ({slug}) does not allow API tokens that never expire. The maximum time-to-live (TTL) is {MAX_EXPIRING_TTL} seconds, but {ttl} seconds was requested.",
+                slug = query_organization.slug
+            )));
+        }
+    }
+
+    Ok(ttl)
+}
+
+fn token_policy_error(error: String) -> HttpError {
+    client_error(
+        ApiErrorCode::with_resource(ApiErrorKind::Forbidden, BencherResource::TokenPolicy),
+        error,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{organization::member::OrganizationRole, DateTime};
+    use bencher_rbac::init_rbac;
+    use diesel::RunQueryDsl;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        context::{DbConnection, Rbac},
+        model::{
+            organization::{organization_role::InsertOrganizationRole, QueryOrganization},
+            user::{auth::AuthUser, QueryUser},
+        },
+        schema,
+        util::test_util::{create_organization, create_user, setup_db},
+    };
+
+    use super::{check_token_policies, UpsertTokenPolicy, MAX_EXPIRING_TTL};
+
+    fn add_member(
+        conn: &mut DbConnection,
+        query_user: &QueryUser,
+        query_organization: &QueryOrganization,
+        role: OrganizationRole,
+    ) {
+        let timestamp = DateTime::now();
+        diesel::insert_into(schema::organization_role::table)
+            .values(&InsertOrganizationRole {
+                user_id: query_user.id,
+                organization_id: query_organization.id,
+                role,
+                created: timestamp,
+                modified: timestamp,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn set_policy(
+        conn: &mut DbConnection,
+        query_organization: &QueryOrganization,
+        max_ttl: Option<u32>,
+        require_ttl: bool,
+        forbid_non_expiring: bool,
+        admin_only: bool,
+    ) {
+        UpsertTokenPolicy {
+            organization_id: query_organization.id,
+            max_ttl: max_ttl.map(Into::into),
+            require_ttl,
+            forbid_non_expiring,
+            admin_only,
+            modified: DateTime::now(),
+        }
+        .execute(conn)
+        .unwrap();
+    }
+
+    fn check(
+        conn: &mut DbConnection,
+        rbac: &Rbac,
+        query_user: &QueryUser,
+        ttl: Option<u32>,
+    ) -> Option<u32> {
+        let auth_user = AuthUser::from_email(conn, &query_user.email).unwrap();
+        check_token_policies(conn, rbac, query_user, &auth_user, ttl).ok()
+    }
+
+    #[test]
+    fn test_no_policy() {
+        let mut conn = setup_db();
+        let rbac = Rbac::from(init_rbac().unwrap());
+        let org = create_organization(&mut conn, "Organization");
+        let alice = create_user(&mut conn, "Alice");
+        add_member(&mut conn, &alice, &org, OrganizationRole::Member);
+
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(60)), Some(60));
+        assert_eq!(check(&mut conn, &rbac, &alice, None), Some(u32::MAX));
+    }
+
+    #[test]
+    fn test_max_ttl() {
+        let mut conn = setup_db();
+        let rbac = Rbac::from(init_rbac().unwrap());
+        let org = create_organization(&mut conn, "Organization");
+        let other_org = create_organization(&mut conn, "Other Organization");
+        let alice = create_user(&mut conn, "Alice");
+        add_member(&mut conn, &alice, &org, OrganizationRole::Member);
+        add_member(&mut conn, &alice, &other_org, OrganizationRole::Member);
+        set_policy(&mut conn, &org, Some(100), false, false, false);
+
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(100)), Some(100));
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(101)), None);
+        // The maximum TTL is used if none is requested
+        assert_eq!(check(&mut conn, &rbac, &alice, None), Some(100));
+
+        // The smallest maximum TTL of all the policies is used
+        set_policy(&mut conn, &other_org, Some(50), false, false, false);
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(100)), None);
+        assert_eq!(check(&mut conn, &rbac, &alice, None), Some(50));
+
+        // The policies of other organizations do not apply
+        let bob = create_user(&mut conn, "Bob");
+        assert_eq!(check(&mut conn, &rbac, &bob, Some(1_000)), Some(1_000));
+    }
+
+    #[test]
+    fn test_require_ttl() {
+        let mut conn = setup_db();
+        let rbac = Rbac::from(init_rbac().unwrap());
+        let org = create_organization(&mut conn, "Organization");
+        let alice = create_user(&mut conn, "Alice");
+        add_member(&mut conn, &alice, &org, OrganizationRole::Member);
+        set_policy(&mut conn, &org, None, true, false, false);
+
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(60)), Some(60));
+        assert_eq!(check(&mut conn, &rbac, &alice, None), None);
+    }
+
+    #[test]
+    fn test_forbid_non_expiring() {
+        let mut conn = setup_db();
+        let rbac = Rbac::from(init_rbac().unwrap());
+        let org = create_organization(&mut conn, "Organization");
+        let alice = create_user(&mut conn, "Alice");
+        add_member(&mut conn, &alice, &org, OrganizationRole::Member);
+        set_policy(&mut conn, &org, None, false, true, false);
+
+        assert_eq!(
+            check(&mut conn, &rbac, &alice, Some(MAX_EXPIRING_TTL)),
+            Some(MAX_EXPIRING_TTL)
+        );
+        assert_eq!(
+            check(&mut conn, &rbac, &alice, Some(MAX_EXPIRING_TTL + 1)),
+            None
+        );
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(u32::MAX - 1)), None);
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(u32::MAX)), None);
+        assert_eq!(check(&mut conn, &rbac, &alice, None), None);
+
+        // A maximum TTL gives tokens without a requested TTL an expiration
+        set_policy(&mut conn, &org, Some(MAX_EXPIRING_TTL), false, true, false);
+        assert_eq!(
+            check(&mut conn, &rbac, &alice, None),
+            Some(MAX_EXPIRING_TTL)
+        );
+    }
+
+    #[test]
+    fn test_admin_only() {
+        let mut conn = setup_db();
+        let rbac = Rbac::from(init_rbac().unwrap());
+        let org = create_organization(&mut conn, "Organization");
+        let alice = create_user(&mut conn, "Alice");
+        let bob = create_user(&mut conn, "Bob");
+        add_member(&mut conn, &alice, &org, OrganizationRole::Leader);
+        add_member(&mut conn, &bob, &org, OrganizationRole::Member);
+        set_policy(&mut conn, &org, None, false, false, true);
+
+        assert_eq!(check(&mut conn, &rbac, &alice, Some(60)), Some(60));
+        assert_eq!(check(&mut conn, &rbac, &bob, Some(60)), None);
+    }
+}
@@ -0,0 +1,213 @@
+use bencher_json::{
+    project::annotation::{JsonAnnotationPatch, JsonAnnotationPatchNull, JsonUpdateAnnotation},
+    AnnotationUuid, BranchName, DateTime, GitHash, JsonAnnotation, JsonNewAnnotation, NonEmpty,
+    ResourceName,
+};
+use diesel::{BelongingToDsl, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use super::{ProjectId, QueryProject};
+use crate::{
+    context::DbConnection,
+    error::{assert_parentage, resource_not_found_err, BencherResource},
+    schema::annotation as annotation_table,
+    util::fn_get::fn_get,
+};
+
+crate::util::typed_id::typed_id!(AnnotationId);
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = annotation_table)]
+#[diesel(belongs_to(QueryProject, foreign_key = project_id))]
+pub struct QueryAnnotation {
+    pub id: AnnotationId,
+    pub uuid: AnnotationUuid,
+    pub project_id: ProjectId,
+    pub title: ResourceName,
+    pub description: Option<NonEmpty>,
+    pub time: DateTime,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryAnnotation {
+    fn_get!(annotation, AnnotationId);
+
+    pub fn get_with_uuid(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        uuid: AnnotationUuid,
+    ) -> Result<Self, HttpError> {
+        Self::belonging_to(&query_project)
+            .filter(annotation_table::uuid.eq(uuid))
+            .first::<Self>(conn)
+            .map_err(resource_not_found_err!(Annotation, (query_project, uuid)))
+    }
+
+    /// Get all annotations for a project within the given time range, sorted by time.
+    pub fn in_time_range(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        start_time: Option<DateTime>,
+        end_time: Option<DateTime>,
+    ) -> Result<Vec<Self>, HttpError> {
+        let mut query = Self::belonging_to(query_project).into_boxed();
+        if let Some(start_time) = start_time {
+            query = query.filter(annotation_table::time.ge(start_time));
+        }
+        if let Some(end_time) = end_time {
+            query = query.filter(annotation_table::time.le(end_time));
+        }
+        query
+            .order(annotation_table::time.asc())
+            .load::<Self>(conn)
+            .map_err(resource_not_found_err!(
+                Annotation,
+                (query_project, start_time, end_time)
+            ))
+    }
+
+    /// Check if the project already has an annotation with the given title at the given time.
+    pub fn exists(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        title: &ResourceName,
+        time: DateTime,
+    ) -> Result<bool, HttpError> {
+        annotation_table::table
+            .filter(annotation_table::project_id.eq(project_id))
+            .filter(annotation_table::title.eq(title))
+            .filter(annotation_table::time.eq(time))
+            .select(annotation_table::id)
+            .first::<AnnotationId>(conn)
+            .optional()
+            .map(|id| id.is_some())
+            .map_err(resource_not_found_err!(
+                Annotation,
+                (project_id, title, time)
+            ))
+    }
+
+    pub fn into_json_for_project(self, project: &QueryProject) -> JsonAnnotation {
+        let Self {
+            uuid,
+            project_id,
+            title,
+            description,
+            time,
+            created,
+            modified,
+            ..
+        } = self;
+        assert_parentage(
+            BencherResource::Project,
+            project.id,
+            BencherResource::Annotation,
+            project_id,
+        );
+        JsonAnnotation {
+            uuid,
+            project: project.uuid,
+            title,
+            description,
+            time,
+            created,
+            modified,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = annotation_table)]
+pub struct InsertAnnotation {
+    pub uuid: AnnotationUuid,
+    pub project_id: ProjectId,
+    pub title: ResourceName,
+    pub description: Option<NonEmpty>,
+    pub time: DateTime,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertAnnotation {
+    pub fn from_json(project_id: ProjectId, annotation: JsonNewAnnotation) -> Self {
+        let JsonNewAnnotation {
+            title,
+            description,
+            time,
+        } = annotation;
+        let timestamp = DateTime::now();
+        Self {
+            uuid: AnnotationUuid::new(),
+            project_id,
+            title,
+            description,
+            time: time.unwrap_or(timestamp),
+            created: timestamp,
+            modified: timestamp,
+        }
+    }
+
+    /// Create an annotation for a git tag on a branch, at the time of the tag.
+    pub fn from_tag(
+        project_id: ProjectId,
+        branch: &BranchName,
+        name: ResourceName,
+        hash: &GitHash,
+        time: DateTime,
+    ) -> Self {
+        let description = format!("Git tag `{name}` at commit `{hash}` on branch `{branch}`")
+            .parse()
+            .ok();
+        let timestamp = DateTime::now();
+        Self {
+            uuid: AnnotationUuid::new(),
+            project_id,
+            title: name,
+            description,
+            time,
+            created: timestamp,
+            modified: timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, diesel::AsChangeset)]
+#[diesel(table_name = annotation_table)]
+pub struct UpdateAnnotation {
+    pub title: Option<ResourceName>,
+    pub description: Option<Option<NonEmpty>>,
+    pub time: Option<DateTime>,
+    pub modified: DateTime,
+}
+
+impl From<JsonUpdateAnnotation> for UpdateAnnotation {
+    fn from(update: JsonUpdateAnnotation) -> Self {
+        let (title, description, time) = match update {
+            JsonUpdateAnnotation::Patch(patch) => {
+                let JsonAnnotationPatch {
+                    title,
+                    description,
+                    time,
+                } = patch;
+                (title, description.map(Some), time)
+            },
+            JsonUpdateAnnotation::Null(patch_null) => {
+                let JsonAnnotationPatchNull {
+                    title,
+                    description: (),
+                    time,
+                } = patch_null;
+                (title, Some(None), time)
+            },
+        };
+        Self {
+            title,
+            description,
+            time,
+            modified: DateTime::now(),
+        }
+    }
+}
@@ -34,6 +34,7 @@ pub struct QueryBenchmark {
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
+    pub flaky: Option<DateTime>,
 }
 
 impl QueryBenchmark {
@@ -109,6 +110,7 @@ impl QueryBenchmark {
             created,
             modified,
             archived,
+            flaky,
             ..
         } = self;
         assert_parentage(
@@ -125,6 +127,7 @@ impl QueryBenchmark {
             created,
             modified,
             archived,
+            flaky,
         }
     }
 }
@@ -139,6 +142,7 @@ pub struct InsertBenchmark {
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
+    pub flaky: Option<DateTime>,
 }
 
 impl InsertBenchmark {
@@ -158,6 +162,7 @@ impl InsertBenchmark {
             created: timestamp,
             modified: timestamp,
             archived: None,
+            flaky: None,
         })
     }
 }
@@ -169,6 +174,7 @@ pub struct UpdateBenchmark {
     pub slug: Option<Slug>,
     pub modified: DateTime,
     pub archived: Option<Option<DateTime>>,
+    pub flaky: Option<Option<DateTime>>,
 }
 
 impl From<JsonUpdateBenchmark> for UpdateBenchmark {
@@ -177,14 +183,17 @@ impl From<JsonUpdateBenchmark> for UpdateBenchmark {
             name,
             slug,
             archived,
+            flaky,
         } = update;
         let modified = DateTime::now();
         let archived = archived.map(|archived| archived.then_some(modified));
+        let flaky = flaky.map(|flaky| flaky.then_some(modified));
         Self {
             name,
             slug,
             modified,
             archived,
+            flaky,
         }
     }
 }
@@ -195,6 +204,7 @@ impl UpdateBenchmark {
             name: None,
             slug: None,
             archived: Some(false),
+            flaky: None,
         }
         .into()
     }
@@ -8,7 +8,6 @@ use diesel::{
 };
 
 use dropshot::HttpError;
-use http::StatusCode;
 use slog::Logger;
 
 use super::{
@@ -19,8 +18,8 @@ use super::{
 };
 use crate::{
     conn_lock,
-    context::{ApiContext, DbConnection},
-    error::{issue_error, resource_conflict_err, resource_not_found_err},
+    context::{transaction, ApiContext, DbConnection},
+    error::{resource_conflict_err, resource_not_found_err},
     model::project::{
         threshold::{alert::QueryAlert, InsertThreshold},
         ProjectId,
@@ -114,44 +113,14 @@ impl QueryHead {
         })
     }
 
-    pub async fn clone_start_point(
-        &self,
-        log: &Logger,
-        context: &ApiContext,
-        query_branch: &QueryBranch,
-        branch_start_point: Option<&StartPoint>,
-    ) -> Result<(), HttpError> {
-        match (self.start_point_id, branch_start_point) {
-            (Some(start_point_id), Some(branch_start_point)) => {
-                debug_assert_eq!(
-                    start_point_id, branch_start_point.head_version.id,
-                    "Branch start point mismatch"
-                );
-                self.clone_versions(log, context, branch_start_point)
-                    .await?;
-                InsertThreshold::from_start_point(log, context, query_branch, branch_start_point)
-                    .await
-            },
-            (None, None) => Ok(()),
-            _ => Err(issue_error(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Branch start point mismatch",
-                "Failed to match branch start point for head",
-                format!("{branch_start_point:?}\n{self:?}"),
-            )),
-        }
-    }
-
-    async fn clone_versions(
+    fn clone_versions(
         &self,
+        conn: &mut DbConnection,
         log: &Logger,
-        context: &ApiContext,
         branch_start_point: &StartPoint,
     ) -> Result<(), HttpError> {
-        let start_point_version = QueryVersion::get(
-            conn_lock!(context),
-            branch_start_point.head_version.version_id,
-        )?;
+        let start_point_version =
+            QueryVersion::get(conn, branch_start_point.head_version.version_id)?;
         slog::debug!(log, "Got start point version: {start_point_version:?}");
 
         // Get all prior versions (version number less than or equal to) for the start point head
@@ -162,7 +131,7 @@ impl QueryHead {
             .order(schema::version::number.desc())
             .limit(i64::from(branch_start_point.max_versions()))
             .select(schema::head_version::version_id)
-            .load::<VersionId>(conn_lock!(context))
+            .load::<VersionId>(conn)
             .map_err(resource_not_found_err!(
                 HeadVersion,
                 (branch_start_point, start_point_version)
@@ -177,7 +146,7 @@ impl QueryHead {
             };
             diesel::insert_into(schema::head_version::table)
                 .values(&insert_head_version)
-                .execute(conn_lock!(context))
+                .execute(conn)
                 .map_err(resource_conflict_err!(HeadVersion, insert_head_version))?;
             slog::debug!(log, "Inserted head version: {insert_head_version:?}");
         }
@@ -219,6 +188,44 @@ impl InsertHead {
         query_branch: QueryBranch,
         branch_start_point: Option<&StartPoint>,
     ) -> Result<(QueryBranch, QueryHead), HttpError> {
+        let query_head = conn_lock!(context, |conn| transaction(conn, |conn| {
+            Self::publish(conn, log, &query_branch, branch_start_point)
+        }))?;
+        // Concurrent reports (ie CI shards) may each try to replace the branch head,
+        // so only the first one wins and the rest use its head.
+        let Some(query_head) = query_head else {
+            slog::debug!(log, "Branch head was already replaced: {query_branch:?}");
+            let query_branch = QueryBranch::get(conn_lock!(context), query_branch.id)?;
+            let query_head = query_branch.head(conn_lock!(context))?;
+            return Ok((query_branch, query_head));
+        };
+
+        // Silence all alerts for the old head
+        if let Some(old_head_id) = query_branch.head_id {
+            let count = QueryAlert::silence_all(context, old_head_id).await?;
+            slog::debug!(log, "Silenced {count} alerts for old head");
+        }
+
+        // Get the updated branch
+        let query_branch = QueryBranch::get(conn_lock!(context), query_branch.id)?;
+        slog::debug!(log, "Got updated branch: {query_branch:?}");
+
+        Self::clone_thresholds(log, context, &query_branch, branch_start_point).await?;
+        Ok((query_branch, query_head))
+    }
+
+    /// Create a new head for the branch and make it the branch head.
+    /// The versions from the start point are cloned before the head is made the branch head,
+    /// so the branch head never has missing versions.
+    /// If the branch head was replaced since the branch was read (ie by a concurrent report),
+    /// then nothing is kept and `None` is returned.
+    /// This must be run in a transaction.
+    pub fn publish(
+        conn: &mut DbConnection,
+        log: &Logger,
+        query_branch: &QueryBranch,
+        branch_start_point: Option<&StartPoint>,
+    ) -> Result<Option<QueryHead>, HttpError> {
         // Create the head for the branch
         let insert_head = Self::new(
             query_branch.id,
@@ -226,22 +233,48 @@ impl InsertHead {
         );
         diesel::insert_into(schema::head::table)
             .values(&insert_head)
-            .execute(conn_lock!(context))
+            .execute(conn)
             .map_err(resource_conflict_err!(Head, insert_head))?;
         slog::debug!(log, "Created head: {insert_head:?}");
 
         // Get the new head
         let query_head = schema::head::table
             .filter(schema::head::uuid.eq(&insert_head.uuid))
-            .first::<QueryHead>(conn_lock!(context))
+            .first::<QueryHead>(conn)
             .map_err(resource_not_found_err!(Head, insert_head))?;
         slog::debug!(log, "Got head: {query_head:?}");
 
-        // Update the branch head
-        diesel::update(schema::branch::table.filter(schema::branch::id.eq(query_branch.id)))
-            .set(schema::branch::head_id.eq(query_head.id))
-            .execute(conn_lock!(context))
-            .map_err(resource_conflict_err!(Branch, (&query_branch, &query_head)))?;
+        // Clone the versions from the start point for the head
+        if let Some(branch_start_point) = branch_start_point {
+            query_head.clone_versions(conn, log, branch_start_point)?;
+        }
+
+        // Update the branch head, but only if it has not been replaced since the branch was read
+        let update_branch =
+            diesel::update(schema::branch::table.filter(schema::branch::id.eq(query_branch.id)));
+        let updated = if let Some(old_head_id) = query_branch.head_id {
+            update_branch
+                .filter(schema::branch::head_id.eq(old_head_id))
+                .set(schema::branch::head_id.eq(query_head.id))
+                .execute(conn)
+        } else {
+            update_branch
+                .filter(schema::branch::head_id.is_null())
+                .set(schema::branch::head_id.eq(query_head.id))
+                .execute(conn)
+        }
+        .map_err(resource_conflict_err!(Branch, (query_branch, &query_head)))?;
+        if updated == 0 {
+            diesel::delete(
+                schema::head_version::table.filter(schema::head_version::head_id.eq(query_head.id)),
+            )
+            .execute(conn)
+            .map_err(resource_conflict_err!(HeadVersion, &query_head))?;
+            diesel::delete(schema::head::table.filter(schema::head::id.eq(query_head.id)))
+                .execute(conn)
+                .map_err(resource_conflict_err!(Head, &query_head))?;
+            return Ok(None);
+        }
         slog::debug!(log, "Updated branch: {query_branch:?}");
 
         // If the branch has an old head, then mark it as replaced.
@@ -250,29 +283,31 @@ impl InsertHead {
             let update_head = UpdateHead::replace();
             diesel::update(schema::head::table.filter(schema::head::id.eq(old_head_id)))
                 .set(&update_head)
-                .execute(conn_lock!(context))
-                .map_err(resource_conflict_err!(Head, (&query_branch, &update_head)))?;
+                .execute(conn)
+                .map_err(resource_conflict_err!(Head, (query_branch, &update_head)))?;
             slog::debug!(log, "Updated old head to replaced: {update_head:?}");
-            // Silence all alerts for the old head
-            let count = QueryAlert::silence_all(context, old_head_id).await?;
-            slog::debug!(log, "Silenced {count} alerts for old head");
         }
 
-        // Get the updated branch
-        // Make sure to do this after updating the old branch head to replaced
-        let query_branch = QueryBranch::get(conn_lock!(context), query_branch.id)?;
-        slog::debug!(log, "Got updated branch: {query_branch:?}");
+        Ok(Some(query_head))
+    }
 
-        // Clone data from the start point for the head
-        query_head
-            .clone_start_point(log, context, &query_branch, branch_start_point)
-            .await?;
+    /// Thresholds belong to the branch rather than the head,
+    /// so they are cloned from the start point once the head has been published.
+    pub async fn clone_thresholds(
+        log: &Logger,
+        context: &ApiContext,
+        query_branch: &QueryBranch,
+        branch_start_point: Option<&StartPoint>,
+    ) -> Result<(), HttpError> {
+        let Some(branch_start_point) = branch_start_point else {
+            return Ok(());
+        };
+        InsertThreshold::from_start_point(log, context, query_branch, branch_start_point).await?;
         slog::debug!(
             log,
-            "Cloned start point for head: {query_head:?} {branch_start_point:?}"
+            "Cloned start point thresholds for branch: {query_branch:?} {branch_start_point:?}"
         );
-
-        Ok((query_branch, query_head))
+        Ok(())
     }
 }
 
@@ -0,0 +1,108 @@
+use bencher_json::{
+    project::{
+        branch::{JsonLineageHead, JsonVersionRange},
+        head::{JsonVersion, VersionNumber},
+    },
+    GitHash, JsonBranchLineage,
+};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{context::DbConnection, error::resource_not_found_err, schema};
+
+use super::{
+    head::{HeadId, QueryHead},
+    head_version::QueryHeadVersion,
+    version::QueryVersion,
+    QueryBranch,
+};
+
+/// The maximum number of heads to follow back through their start points.
+/// This guards against a cycle of start points.
+const MAX_LINEAGE_DEPTH: usize = 64;
+
+/// Get the lineage of a branch head by following its start points.
+pub fn branch_lineage(
+    conn: &mut DbConnection,
+    query_head: QueryHead,
+) -> Result<JsonBranchLineage, HttpError> {
+    let mut lineage = Vec::new();
+    let mut visited = Vec::new();
+    let mut next_head = Some(query_head);
+    while let Some(query_head) = next_head.take() {
+        if visited.contains(&query_head.id) || visited.len() >= MAX_LINEAGE_DEPTH {
+            break;
+        }
+        visited.push(query_head.id);
+
+        let start_point = if let Some(start_point_id) = query_head.start_point_id {
+            let head_version = QueryHeadVersion::get(conn, start_point_id)?;
+            let version = QueryVersion::get(conn, head_version.version_id)?;
+            next_head = Some(QueryHead::get(conn, head_version.head_id)?);
+            Some(version.into_json())
+        } else {
+            None
+        };
+        lineage.push(lineage_head(conn, &query_head, start_point)?);
+    }
+    Ok(lineage.into())
+}
+
+fn lineage_head(
+    conn: &mut DbConnection,
+    query_head: &QueryHead,
+    start_point: Option<JsonVersion>,
+) -> Result<JsonLineageHead, HttpError> {
+    let query_branch = QueryBranch::get(conn, query_head.branch_id)?;
+    let versions = head_versions(conn, query_head.id)?;
+    // Version numbers always increase within a project,
+    // so any version up to and including the start point version was cloned from the start point.
+    // All later versions were created on this head.
+    let (cloned, native): (Vec<_>, Vec<_>) = versions.into_iter().partition(|version| {
+        start_point
+            .as_ref()
+            .is_some_and(|start_point| version.number.0 <= start_point.number.0)
+    });
+
+    let &QueryHead {
+        uuid,
+        created,
+        replaced,
+        ..
+    } = query_head;
+    Ok(JsonLineageHead {
+        branch: query_branch.uuid,
+        name: query_branch.name,
+        slug: query_branch.slug,
+        head: uuid,
+        start_point,
+        cloned: version_range(cloned),
+        native: version_range(native),
+        created,
+        replaced,
+    })
+}
+
+fn head_versions(conn: &mut DbConnection, head_id: HeadId) -> Result<Vec<JsonVersion>, HttpError> {
+    schema::head_version::table
+        .inner_join(schema::version::table)
+        .filter(schema::head_version::head_id.eq(head_id))
+        .order(schema::version::number.asc())
+        .select((schema::version::number, schema::version::hash))
+        .load::<(VersionNumber, Option<GitHash>)>(conn)
+        .map(|versions| {
+            versions
+                .into_iter()
+                .map(|(number, hash)| JsonVersion { number, hash })
+                .collect()
+        })
+        .map_err(resource_not_found_err!(HeadVersion, head_id))
+}
+
+fn version_range(versions: Vec<JsonVersion>) -> Option<JsonVersionRange> {
+    let count = u32::try_from(versions.len()).unwrap_or(u32::MAX);
+    let mut versions = versions.into_iter();
+    let first = versions.next()?;
+    let last = versions.last().unwrap_or_else(|| first.clone());
+    Some(JsonVersionRange { first, last, count })
+}
@@ -11,10 +11,10 @@ use version::{QueryVersion, VersionId};
 use super::{ProjectId, QueryProject};
 use crate::{
     conn_lock,
-    context::{ApiContext, DbConnection},
+    context::{transaction, ApiContext, DbConnection},
     error::{
-        assert_parentage, issue_error, resource_conflict_err, resource_not_found_err,
-        BencherResource,
+        assert_parentage, issue_error, resource_conflict_err, resource_conflict_error,
+        resource_not_found_err, BencherResource,
     },
     schema::{self, branch as branch_table},
     util::{
@@ -27,6 +27,7 @@ use crate::{
 
 pub mod head;
 pub mod head_version;
+pub mod lineage;
 pub mod start_point;
 pub mod version;
 
@@ -51,6 +52,7 @@ pub struct QueryBranch {
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
+    pub frozen_baseline: Option<DateTime>,
 }
 
 impl QueryBranch {
@@ -85,6 +87,18 @@ impl QueryBranch {
         QueryHead::get(conn, self.head_id()?)
     }
 
+    /// The time that the threshold baselines for the branch were frozen, if they are
+    pub fn frozen_baseline(
+        conn: &mut DbConnection,
+        branch_id: BranchId,
+    ) -> Result<Option<DateTime>, HttpError> {
+        schema::branch::table
+            .filter(schema::branch::id.eq(branch_id))
+            .select(schema::branch::frozen_baseline)
+            .first::<Option<DateTime>>(conn)
+            .map_err(resource_not_found_err!(Branch, branch_id))
+    }
+
     pub async fn get_or_create(
         log: &Logger,
         context: &ApiContext,
@@ -127,7 +141,7 @@ impl QueryBranch {
         let Ok(kind) = NameIdKind::<BranchName>::try_from(branch) else {
             return Err(http_error);
         };
-        let branch = match kind {
+        let new_branch = match kind {
             NameIdKind::Uuid(_) => return Err(http_error),
             NameIdKind::Slug(slug) => JsonNewBranch {
                 name: slug.clone().into(),
@@ -140,7 +154,18 @@ impl QueryBranch {
                 start_point: start_point.cloned().and_then(Into::into),
             },
         };
-        InsertBranch::from_json(log, context, project_id, branch).await
+        if let Some(branch_and_head) =
+            InsertBranch::try_from_json(log, context, project_id, new_branch).await?
+        {
+            return Ok(branch_and_head);
+        }
+        // The branch name and slug are unique for the project,
+        // so creating the branch fails if a concurrent report (ie another CI shard) created it first.
+        // In that case, use the branch that was created.
+        // The branch and its head are created together, so the branch always has a head.
+        Self::from_name_id(conn_lock!(context), project_id, branch)?
+            .update_start_point_if_changed(log, context, project_id, start_point)
+            .await
     }
 
     pub async fn update_start_point_if_changed(
@@ -289,6 +314,7 @@ impl QueryBranch {
             created,
             modified,
             archived,
+            frozen_baseline,
             ..
         } = self;
         assert_parentage(
@@ -313,6 +339,7 @@ impl QueryBranch {
             created,
             modified,
             archived,
+            frozen_baseline,
         })
     }
 }
@@ -328,6 +355,7 @@ pub struct InsertBranch {
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
+    pub frozen_baseline: Option<DateTime>,
 }
 
 impl InsertBranch {
@@ -355,6 +383,7 @@ impl InsertBranch {
             created: timestamp,
             modified: timestamp,
             archived: None,
+            frozen_baseline: None,
         })
     }
 
@@ -364,27 +393,31 @@ impl InsertBranch {
         project_id: ProjectId,
         branch: JsonNewBranch,
     ) -> Result<(QueryBranch, QueryHead), HttpError> {
+        let value = (project_id, branch.name.clone(), branch.slug.clone());
+        Self::try_from_json(log, context, project_id, branch)
+            .await?
+            .ok_or_else(|| {
+                resource_conflict_error(
+                    BencherResource::Branch,
+                    value,
+                    "A branch with the same name or slug already exists",
+                )
+            })
+    }
+
+    /// Create the branch, unless a branch with the same name or slug already exists.
+    pub async fn try_from_json(
+        log: &Logger,
+        context: &ApiContext,
+        project_id: ProjectId,
+        branch: JsonNewBranch,
+    ) -> Result<Option<(QueryBranch, QueryHead)>, HttpError> {
         let JsonNewBranch {
             name,
             slug,
             start_point,
         } = branch;
 
-        // Create branch
-        let insert_branch = Self::new(context, project_id, name, slug).await?;
-        diesel::insert_into(schema::branch::table)
-            .values(&insert_branch)
-            .execute(conn_lock!(context))
-            .map_err(resource_conflict_err!(Branch, insert_branch))?;
-        slog::debug!(log, "Created branch {insert_branch:?}");
-
-        // Get the new branch
-        let query_branch = schema::branch::table
-            .filter(schema::branch::uuid.eq(&insert_branch.uuid))
-            .first::<QueryBranch>(conn_lock!(context))
-            .map_err(resource_not_found_err!(Branch, insert_branch))?;
-        slog::debug!(log, "Got branch {query_branch:?}");
-
         // Get the branch head version for the start point
         let branch_start_point = if let Some(start_point) = start_point {
             // It is okay if the start point does not exist.
@@ -397,7 +430,65 @@ impl InsertBranch {
         };
         slog::debug!(log, "Using start point {branch_start_point:?}");
 
-        InsertHead::for_branch(log, context, query_branch, branch_start_point.as_ref()).await
+        let insert_branch = Self::new(context, project_id, name, slug).await?;
+        let Some((query_branch, query_head)) =
+            conn_lock!(context, |conn| transaction(conn, |conn| {
+                insert_branch.try_insert(conn, log, branch_start_point.as_ref())
+            }))?
+        else {
+            return Ok(None);
+        };
+
+        InsertHead::clone_thresholds(log, context, &query_branch, branch_start_point.as_ref())
+            .await?;
+        Ok(Some((query_branch, query_head)))
+    }
+
+    /// Create the branch along with its head, so that the branch is never without a head.
+    /// If a branch with the same name or slug already exists, then nothing is created and `None` is returned.
+    /// This must be run in a transaction.
+    pub fn try_insert(
+        &self,
+        conn: &mut DbConnection,
+        log: &Logger,
+        branch_start_point: Option<&StartPoint>,
+    ) -> Result<Option<(QueryBranch, QueryHead)>, HttpError> {
+        match diesel::insert_into(schema::branch::table)
+            .values(self)
+            .execute(conn)
+        {
+            Ok(_) => {},
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => {
+                slog::debug!(log, "Branch already exists {self:?}");
+                return Ok(None);
+            },
+            Err(e) => return Err(resource_conflict_error(BencherResource::Branch, self, e)),
+        }
+        slog::debug!(log, "Created branch {self:?}");
+
+        // Get the new branch
+        let query_branch = schema::branch::table
+            .filter(schema::branch::uuid.eq(&self.uuid))
+            .first::<QueryBranch>(conn)
+            .map_err(resource_not_found_err!(Branch, self))?;
+        slog::debug!(log, "Got branch {query_branch:?}");
+
+        let Some(query_head) = InsertHead::publish(conn, log, &query_branch, branch_start_point)?
+        else {
+            return Err(issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to create branch head",
+                "Head for new branch was already replaced",
+                format!("{query_branch:?}"),
+            ));
+        };
+
+        // Get the branch with its head
+        let query_branch = QueryBranch::get(conn, query_branch.id)?;
+        Ok(Some((query_branch, query_head)))
     }
 
     pub async fn main(
@@ -418,6 +509,7 @@ pub struct UpdateBranch {
     pub slug: Option<Slug>,
     pub modified: DateTime,
     pub archived: Option<Option<DateTime>>,
+    pub frozen_baseline: Option<Option<DateTime>>,
 }
 
 impl From<JsonUpdateBranch> for UpdateBranch {
@@ -427,14 +519,18 @@ impl From<JsonUpdateBranch> for UpdateBranch {
             slug,
             start_point: _,
             archived,
+            frozen_baseline,
         } = update;
         let modified = DateTime::now();
         let archived = archived.map(|archived| archived.then_some(modified));
+        let frozen_baseline =
+            frozen_baseline.map(|frozen_baseline| frozen_baseline.then_some(modified));
         Self {
             name,
             slug,
             modified,
             archived,
+            frozen_baseline,
         }
     }
 }
@@ -446,7 +542,178 @@ impl UpdateBranch {
             slug: None,
             start_point: None,
             archived: Some(false),
+            frozen_baseline: None,
         }
         .into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{project::Visibility, BranchUuid, DateTime, VersionUuid};
+    use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        context::DbConnection,
+        model::project::{
+            branch::{
+                head::{HeadId, InsertHead, QueryHead},
+                head_version::{InsertHeadVersion, QueryHeadVersion},
+                start_point::StartPoint,
+                version::{QueryVersion, VersionId},
+            },
+            QueryProject,
+        },
+        schema,
+        util::test_util::{create_organization, create_project, setup_db},
+    };
+
+    use super::InsertBranch;
+
+    fn log() -> slog::Logger {
+        slog::Logger::root(slog::Discard, slog::o!())
+    }
+
+    fn insert_branch(query_project: &QueryProject, name: &str) -> InsertBranch {
+        let timestamp = DateTime::now();
+        InsertBranch {
+            uuid: BranchUuid::new(),
+            project_id: query_project.id,
+            name: name.parse().unwrap(),
+            slug: name.parse().unwrap(),
+            head_id: None,
+            created: timestamp,
+            modified: timestamp,
+            archived: None,
+            frozen_baseline: None,
+        }
+    }
+
+    fn count_heads(conn: &mut DbConnection) -> i64 {
+        schema::head::table.count().get_result(conn).unwrap()
+    }
+
+    fn head_versions(conn: &mut DbConnection, head_id: HeadId) -> Vec<VersionId> {
+        schema::head_version::table
+            .filter(schema::head_version::head_id.eq(head_id))
+            .order(schema::head_version::version_id.asc())
+            .select(schema::head_version::version_id)
+            .load(conn)
+            .unwrap()
+    }
+
+    fn add_version(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        head_id: HeadId,
+        number: i32,
+    ) -> VersionId {
+        let uuid = VersionUuid::new();
+        diesel::insert_into(schema::version::table)
+            .values((
+                schema::version::uuid.eq(uuid),
+                schema::version::project_id.eq(query_project.id),
+                schema::version::number.eq(number),
+            ))
+            .execute(conn)
+            .unwrap();
+        let version_id = schema::version::table
+            .filter(schema::version::uuid.eq(uuid))
+            .select(schema::version::id)
+            .first(conn)
+            .unwrap();
+        diesel::insert_into(schema::head_version::table)
+            .values(&InsertHeadVersion {
+                head_id,
+                version_id,
+            })
+            .execute(conn)
+            .unwrap();
+        version_id
+    }
+
+    #[test]
+    fn test_try_insert_existing() {
+        let mut conn = setup_db();
+        let org = create_organization(&mut conn, "Test Org");
+        let project = create_project(&mut conn, &org, "Test Project", Visibility::Public);
+        let log = log();
+
+        let (query_branch, query_head) = insert_branch(&project, "main")
+            .try_insert(&mut conn, &log, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(query_branch.head_id, Some(query_head.id));
+        assert_eq!(query_head.branch_id, query_branch.id);
+
+        // A concurrent report that also tries to create the branch gets nothing
+        let existing = insert_branch(&project, "main")
+            .try_insert(&mut conn, &log, None)
+            .unwrap();
+        assert!(existing.is_none());
+        let branches: i64 = schema::branch::table.count().get_result(&mut conn).unwrap();
+        assert_eq!(branches, 1);
+        assert_eq!(count_heads(&mut conn), 1);
+    }
+
+    #[test]
+    fn test_publish_concurrent() {
+        let mut conn = setup_db();
+        let org = create_organization(&mut conn, "Test Org");
+        let project = create_project(&mut conn, &org, "Test Project", Visibility::Public);
+        let log = log();
+
+        let (main_branch, main_head) = insert_branch(&project, "main")
+            .try_insert(&mut conn, &log, None)
+            .unwrap()
+            .unwrap();
+        let first_version = add_version(&mut conn, &project, main_head.id, 1);
+        let second_version = add_version(&mut conn, &project, main_head.id, 2);
+        let head_version = schema::head_version::table
+            .filter(schema::head_version::version_id.eq(second_version))
+            .first::<QueryHeadVersion>(&mut conn)
+            .unwrap();
+        let start_point = StartPoint {
+            branch: main_branch,
+            version: QueryVersion::get(&mut conn, second_version).unwrap(),
+            head_version,
+            max_versions: None,
+            clone_thresholds: None,
+        };
+
+        // The new branch has the start point versions as soon as it has a head
+        let (feature_branch, feature_head) = insert_branch(&project, "feature")
+            .try_insert(&mut conn, &log, Some(&start_point))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            head_versions(&mut conn, feature_head.id),
+            vec![first_version, second_version]
+        );
+
+        // Two concurrent reports both read the branch before either replaces its head
+        let heads = count_heads(&mut conn);
+        let first = InsertHead::publish(&mut conn, &log, &feature_branch, Some(&start_point))
+            .unwrap()
+            .unwrap();
+        let second =
+            InsertHead::publish(&mut conn, &log, &feature_branch, Some(&start_point)).unwrap();
+        assert!(second.is_none());
+        // Only the first new head is kept, along with its cloned versions
+        assert_eq!(count_heads(&mut conn), heads + 1);
+        assert_eq!(
+            head_versions(&mut conn, first.id),
+            vec![first_version, second_version]
+        );
+
+        let head_id = schema::branch::table
+            .filter(schema::branch::id.eq(feature_branch.id))
+            .select(schema::branch::head_id)
+            .first::<Option<HeadId>>(&mut conn)
+            .unwrap();
+        assert_eq!(head_id, Some(first.id));
+        let old_head = QueryHead::get(&mut conn, feature_head.id).unwrap();
+        assert!(old_head.replaced.is_some());
+    }
+}
@@ -1,8 +1,8 @@
 use bencher_json::{
     project::head::{JsonVersion, VersionNumber},
-    GitHash, VersionUuid,
+    DateTime, GitHash, VersionUuid,
 };
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use diesel::{ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl, RunQueryDsl};
 use dropshot::HttpError;
 
 use crate::{
@@ -28,6 +28,7 @@ pub struct QueryVersion {
     pub project_id: ProjectId,
     pub number: VersionNumber,
     pub hash: Option<GitHash>,
+    pub pushed: Option<DateTime>,
 }
 
 impl QueryVersion {
@@ -55,12 +56,53 @@ impl QueryVersion {
                 .first::<VersionId>(conn)
             {
                 Ok(version_id)
+            } else if let Some(version_id) = Self::get_pushed(conn, head_id, hash) {
+                Ok(version_id)
             } else {
-                InsertVersion::increment(conn, project_id, head_id, Some(hash.clone()))
+                InsertVersion::increment(conn, project_id, head_id, Some(hash.clone()), None)
             }
         } else {
-            InsertVersion::increment(conn, project_id, head_id, None)
+            InsertVersion::increment(conn, project_id, head_id, None, None)
+        }
+    }
+
+    /// Get the most recent version with the given hash that was pushed to the branch head.
+    /// Versions that were shallow copied from the start point were pushed before the head was created,
+    /// so they are not used.
+    fn get_pushed(conn: &mut DbConnection, head_id: HeadId, hash: &GitHash) -> Option<VersionId> {
+        schema::version::table
+            .inner_join(schema::head_version::table.inner_join(
+                schema::head::table.on(schema::head::id.eq(schema::head_version::head_id)),
+            ))
+            .filter(schema::head_version::head_id.eq(head_id))
+            .filter(schema::version::hash.eq(hash.as_ref()))
+            .filter(schema::version::pushed.ge(schema::head::created.nullable()))
+            .order(schema::version::number.desc())
+            .select(schema::version::id)
+            .first::<VersionId>(conn)
+            .ok()
+    }
+
+    /// Record a git push to the branch head as a version, before it has been benchmarked.
+    /// Returns `None` if the branch head already has a version with the given hash.
+    pub fn push(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        head_id: HeadId,
+        hash: GitHash,
+        pushed: DateTime,
+    ) -> Result<Option<VersionId>, HttpError> {
+        let exists = schema::version::table
+            .inner_join(schema::head_version::table)
+            .filter(schema::head_version::head_id.eq(head_id))
+            .filter(schema::version::hash.eq(hash.as_ref()))
+            .select(schema::version::id)
+            .first::<VersionId>(conn)
+            .is_ok();
+        if exists {
+            return Ok(None);
         }
+        InsertVersion::increment(conn, project_id, head_id, Some(hash), Some(pushed)).map(Some)
     }
 
     pub fn into_json(self) -> JsonVersion {
@@ -76,6 +118,7 @@ pub struct InsertVersion {
     pub project_id: ProjectId,
     pub number: VersionNumber,
     pub hash: Option<GitHash>,
+    pub pushed: Option<DateTime>,
 }
 
 impl InsertVersion {
@@ -84,6 +127,7 @@ impl InsertVersion {
         project_id: ProjectId,
         head_id: HeadId,
         hash: Option<GitHash>,
+        pushed: Option<DateTime>,
     ) -> Result<VersionId, HttpError> {
         // Get the most recent code version number for this branch head and increment it.
         // Otherwise, start a new branch code version number count from zero.
@@ -107,6 +151,7 @@ impl InsertVersion {
             project_id,
             number,
             hash: hash.map(Into::into),
+            pushed,
         };
 
         diesel::insert_into(schema::version::table)
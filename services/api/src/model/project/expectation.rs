@@ -0,0 +1,273 @@
+use bencher_json::{
+    project::head::VersionNumber, BenchmarkName, DateTime, ExpectationUuid, JsonExpectation,
+    JsonNewAnnotation, JsonNewExpectation, NonEmpty, ResourceName,
+};
+use diesel::{BelongingToDsl, ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{
+    annotation::{AnnotationId, QueryAnnotation},
+    branch::{BranchId, QueryBranch},
+    ProjectId, QueryProject,
+};
+use crate::{
+    context::DbConnection,
+    error::{assert_parentage, bad_request_error, resource_not_found_err, BencherResource},
+    schema::expectation as expectation_table,
+    util::fn_get::fn_get,
+};
+
+crate::util::typed_id::typed_id!(ExpectationId);
+
+#[allow(clippy::expect_used)]
+static ANNOTATION_TITLE: Lazy<ResourceName> = Lazy::new(|| {
+    "Expected regression"
+        .parse()
+        .expect("Failed to parse annotation title.")
+});
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = expectation_table)]
+#[diesel(belongs_to(QueryProject, foreign_key = project_id))]
+pub struct QueryExpectation {
+    pub id: ExpectationId,
+    pub uuid: ExpectationUuid,
+    pub project_id: ProjectId,
+    pub branch_id: BranchId,
+    pub pattern: NonEmpty,
+    pub reason: NonEmpty,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub start_version: Option<VersionNumber>,
+    pub end_version: Option<VersionNumber>,
+    pub annotation_id: Option<AnnotationId>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryExpectation {
+    fn_get!(expectation, ExpectationId);
+
+    pub fn get_with_uuid(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        uuid: ExpectationUuid,
+    ) -> Result<Self, HttpError> {
+        Self::belonging_to(&query_project)
+            .filter(expectation_table::uuid.eq(uuid))
+            .first::<Self>(conn)
+            .map_err(resource_not_found_err!(Expectation, (query_project, uuid)))
+    }
+
+    /// Check whether a report with the given start time and version number is within the window.
+    fn contains(&self, start_time: DateTime, version_number: VersionNumber) -> bool {
+        let timestamp = start_time.timestamp();
+        !(self
+            .start_time
+            .is_some_and(|start| timestamp < start.timestamp())
+            || self.end_time.is_some_and(|end| end.timestamp() < timestamp)
+            || self
+                .start_version
+                .is_some_and(|start| version_number.0 < start.0)
+            || self.end_version.is_some_and(|end| end.0 < version_number.0))
+    }
+
+    pub fn into_json_for_project(
+        self,
+        conn: &mut DbConnection,
+        project: &QueryProject,
+    ) -> Result<JsonExpectation, HttpError> {
+        let Self {
+            uuid,
+            project_id,
+            branch_id,
+            pattern,
+            reason,
+            start_time,
+            end_time,
+            start_version,
+            end_version,
+            annotation_id,
+            created,
+            modified,
+            ..
+        } = self;
+        assert_parentage(
+            BencherResource::Project,
+            project.id,
+            BencherResource::Expectation,
+            project_id,
+        );
+        let branch = QueryBranch::get(conn, branch_id)?.into_json_for_project(conn, project)?;
+        let annotation = if let Some(annotation_id) = annotation_id {
+            Some(QueryAnnotation::get(conn, annotation_id)?.uuid)
+        } else {
+            None
+        };
+        Ok(JsonExpectation {
+            uuid,
+            project: project.uuid,
+            branch,
+            pattern,
+            reason,
+            start_time,
+            end_time,
+            start_version,
+            end_version,
+            annotation,
+            created,
+            modified,
+        })
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = expectation_table)]
+pub struct InsertExpectation {
+    pub uuid: ExpectationUuid,
+    pub project_id: ProjectId,
+    pub branch_id: BranchId,
+    pub pattern: NonEmpty,
+    pub reason: NonEmpty,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub start_version: Option<VersionNumber>,
+    pub end_version: Option<VersionNumber>,
+    pub annotation_id: Option<AnnotationId>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertExpectation {
+    /// Create an expected regression window for a branch.
+    /// The branch must have already been found from the `branch` in the JSON.
+    pub fn from_json(
+        query_branch: &QueryBranch,
+        expectation: JsonNewExpectation,
+    ) -> Result<Self, HttpError> {
+        let JsonNewExpectation {
+            pattern,
+            reason,
+            start_time,
+            end_time,
+            start_version,
+            end_version,
+            ..
+        } = expectation;
+        compile_pattern(&pattern)?;
+        if start_time.is_none()
+            && end_time.is_none()
+            && start_version.is_none()
+            && end_version.is_none()
+        {
+            return Err(bad_request_error(
+                "An expected regression window must have a time range or a version range",
+            ));
+        }
+        if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+            if start_time.timestamp() > end_time.timestamp() {
+                return Err(bad_request_error(format!(
+                    "The start time ({start_time}) must be at or before the end time ({end_time})"
+                )));
+            }
+        }
+        if let (Some(start_version), Some(end_version)) = (start_version, end_version) {
+            if start_version.0 > end_version.0 {
+                return Err(bad_request_error(format!(
+                    "The start version ({start_version}) must be at or before the end version ({end_version})"
+                )));
+            }
+        }
+        let timestamp = DateTime::now();
+        Ok(Self {
+            uuid: ExpectationUuid::new(),
+            project_id: query_branch.project_id,
+            branch_id: query_branch.id,
+            pattern,
+            reason,
+            start_time,
+            end_time,
+            start_version,
+            end_version,
+            annotation_id: None,
+            created: timestamp,
+            modified: timestamp,
+        })
+    }
+
+    /// The annotation that records the expected regression window for posterity.
+    /// It is placed at the start of the window, if there is a start time.
+    pub fn annotation(&self, branch: &QueryBranch) -> Result<JsonNewAnnotation, HttpError> {
+        let mut range = Vec::new();
+        match (self.start_time, self.end_time) {
+            (Some(start_time), Some(end_time)) => range.push(format!("{start_time} to {end_time}")),
+            (Some(start_time), None) => range.push(format!("from {start_time}")),
+            (None, Some(end_time)) => range.push(format!("until {end_time}")),
+            (None, None) => {},
+        }
+        match (self.start_version, self.end_version) {
+            (Some(start_version), Some(end_version)) => {
+                range.push(format!("versions {start_version} to {end_version}"));
+            },
+            (Some(start_version), None) => range.push(format!("from version {start_version}")),
+            (None, Some(end_version)) => range.push(format!("until version {end_version}")),
+            (None, None) => {},
+        }
+        let description = format!(
+            "{reason}\nBranch: {branch}\nBenchmarks: {pattern}\nWindow: {range}",
+            reason = self.reason,
+            branch = branch.name,
+            pattern = self.pattern,
+            range = range.join(", "),
+        )
+        .parse()
+        .map_err(bad_request_error)?;
+        Ok(JsonNewAnnotation {
+            title: ANNOTATION_TITLE.clone(),
+            description: Some(description),
+            time: self.start_time,
+        })
+    }
+}
+
+fn compile_pattern(pattern: &NonEmpty) -> Result<Regex, HttpError> {
+    Regex::new(pattern.as_ref()).map_err(|e| {
+        bad_request_error(format!(
+            "Invalid expected regression pattern ({pattern}): {e}"
+        ))
+    })
+}
+
+/// The expected regression windows that a report is within, compiled.
+/// Any benchmark that matches one of their patterns does not generate alerts for the report.
+pub struct Expectations(Vec<Regex>);
+
+impl Expectations {
+    pub fn for_report(
+        conn: &mut DbConnection,
+        branch_id: BranchId,
+        start_time: DateTime,
+        version_number: VersionNumber,
+    ) -> Result<Self, HttpError> {
+        let expectations = expectation_table::table
+            .filter(expectation_table::branch_id.eq(branch_id))
+            .load::<QueryExpectation>(conn)
+            .map_err(resource_not_found_err!(Expectation, branch_id))?;
+        expectations
+            .into_iter()
+            .filter(|expectation| expectation.contains(start_time, version_number))
+            .map(|expectation| compile_pattern(&expectation.pattern))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn is_expected(&self, benchmark_name: &BenchmarkName) -> bool {
+        self.0
+            .iter()
+            .any(|regex| regex.is_match(benchmark_name.as_ref()))
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::{HashMap, HashSet};
+
+use bencher_boundary::MetricsFlakiness;
+use bencher_json::DateTime;
+use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+use dropshot::HttpError;
+use slog::Logger;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::flaky::FlakySettings,
+    context::DbConnection,
+    error::{resource_conflict_err, resource_not_found_err},
+    model::project::{
+        benchmark::BenchmarkId,
+        branch::{BranchId, QueryBranch},
+        measure::MeasureId,
+        report::results::detector::{data::metrics_data, threshold::Threshold},
+        testbed::TestbedId,
+        ProjectId,
+    },
+    schema::{self, flaky_measure as flaky_measure_table},
+};
+
+crate::util::typed_id::typed_id!(FlakyMeasureId);
+
+/// A measure of a benchmark that was detected as flaky
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = flaky_measure_table)]
+pub struct QueryFlakyMeasure {
+    pub id: FlakyMeasureId,
+    pub benchmark_id: BenchmarkId,
+    pub measure_id: MeasureId,
+    pub coefficient_of_variation: f64,
+    pub created: DateTime,
+}
+
+impl QueryFlakyMeasure {
+    /// Check if the measure of the benchmark was detected as flaky
+    pub fn exists(
+        conn: &mut DbConnection,
+        benchmark_id: BenchmarkId,
+        measure_id: MeasureId,
+    ) -> Result<bool, HttpError> {
+        diesel::select(diesel::dsl::exists(
+            schema::flaky_measure::table
+                .filter(schema::flaky_measure::benchmark_id.eq(benchmark_id))
+                .filter(schema::flaky_measure::measure_id.eq(measure_id)),
+        ))
+        .get_result(conn)
+        .map_err(resource_not_found_err!(
+            FlakyMeasure,
+            (benchmark_id, measure_id)
+        ))
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = flaky_measure_table)]
+pub struct InsertFlakyMeasure {
+    pub benchmark_id: BenchmarkId,
+    pub measure_id: MeasureId,
+    pub coefficient_of_variation: f64,
+    pub created: DateTime,
+}
+
+/// The benchmarks and measures of a project that are flaky.
+/// A benchmark that is quarantined is flaky for all of its measures,
+/// while a benchmark that was detected as flaky is only flaky for the measures that are noisy.
+#[derive(Debug, Clone, Default)]
+pub struct FlakyMeasures {
+    quarantined: HashSet<BenchmarkId>,
+    detected: HashMap<(BenchmarkId, MeasureId), DateTime>,
+}
+
+impl FlakyMeasures {
+    pub fn for_project(conn: &mut DbConnection, project_id: ProjectId) -> Result<Self, HttpError> {
+        let quarantined = schema::benchmark::table
+            .filter(schema::benchmark::project_id.eq(project_id))
+            .filter(schema::benchmark::flaky.is_not_null())
+            .select(schema::benchmark::id)
+            .load::<BenchmarkId>(conn)
+            .map_err(resource_not_found_err!(Benchmark, project_id))?
+            .into_iter()
+            .collect();
+        let detected = schema::flaky_measure::table
+            .inner_join(schema::benchmark::table)
+            .filter(schema::benchmark::project_id.eq(project_id))
+            .select((
+                schema::flaky_measure::benchmark_id,
+                schema::flaky_measure::measure_id,
+                schema::flaky_measure::created,
+            ))
+            .load::<(BenchmarkId, MeasureId, DateTime)>(conn)
+            .map_err(resource_not_found_err!(FlakyMeasure, project_id))?
+            .into_iter()
+            .map(|(benchmark_id, measure_id, created)| ((benchmark_id, measure_id), created))
+            .collect();
+        Ok(Self {
+            quarantined,
+            detected,
+        })
+    }
+
+    pub fn is_flaky(&self, benchmark_id: BenchmarkId, measure_id: MeasureId) -> bool {
+        self.quarantined.contains(&benchmark_id)
+            || self.detected.contains_key(&(benchmark_id, measure_id))
+    }
+
+    /// When the measure of the benchmark was detected as flaky, if it is flaky
+    pub fn detected(&self, benchmark_id: BenchmarkId, measure_id: MeasureId) -> Option<DateTime> {
+        self.detected.get(&(benchmark_id, measure_id)).copied()
+    }
+}
+
+#[derive(Debug)]
+pub struct DetectedFlaky {
+    /// The number of benchmark measures that were checked
+    pub checked: usize,
+    /// The number of benchmark measures that are now flaky
+    pub flaky: usize,
+    /// The number of benchmark measures that are no longer flaky
+    pub resolved: usize,
+}
+
+/// Check every benchmark measure with a threshold for flakiness.
+/// The metrics checked are the same ones used to generate alerts for the threshold.
+/// A benchmark measure is flaky if it is noisy for any of its thresholds.
+/// Benchmark measures that are no longer noisy are no longer flaky.
+/// The connection is only held for one benchmark measure at a time.
+pub async fn detect_flaky(
+    log: &Logger,
+    conn: &Mutex<DbConnection>,
+    settings: FlakySettings,
+) -> Result<DetectedFlaky, HttpError> {
+    let thresholds = schema::threshold::table
+        .filter(schema::threshold::model_id.is_not_null())
+        .select((
+            schema::threshold::branch_id,
+            schema::threshold::testbed_id,
+            schema::threshold::measure_id,
+        ))
+        .load::<(BranchId, TestbedId, MeasureId)>(&mut *conn.lock().await)
+        .map_err(resource_not_found_err!(Threshold))?;
+
+    let mut checked = HashSet::new();
+    let mut detected = HashMap::new();
+    // Benchmark measures that could not be checked keep their current flakiness
+    let mut skipped = Skipped::default();
+    for (branch_id, testbed_id, measure_id) in thresholds {
+        let (threshold, query_branch, head_id, benchmark_ids) = {
+            let conn = &mut *conn.lock().await;
+            // The threshold model may have been removed since the thresholds were loaded
+            let Some(threshold) = Threshold::new(conn, branch_id, testbed_id, measure_id) else {
+                continue;
+            };
+            // A single missing branch or branch head should not stop the rest of the detection
+            let query_branch = match QueryBranch::get(conn, branch_id) {
+                Ok(query_branch) => query_branch,
+                Err(e) => {
+                    slog::warn!(
+                        log,
+                        "Skipping flaky detection for branch ({branch_id}): {e}"
+                    );
+                    continue;
+                },
+            };
+            let Some(head_id) = query_branch.head_id else {
+                slog::warn!(
+                    log,
+                    "Skipping flaky detection for branch ({branch_id}) without a head"
+                );
+                continue;
+            };
+            let benchmark_ids = match schema::metric::table
+                .inner_join(schema::report_benchmark::table.inner_join(schema::report::table))
+                .filter(schema::report::head_id.eq(head_id))
+                .filter(schema::report::testbed_id.eq(testbed_id))
+                .filter(schema::metric::measure_id.eq(measure_id))
+                .select(schema::report_benchmark::benchmark_id)
+                .distinct()
+                .load::<BenchmarkId>(conn)
+                .map_err(resource_not_found_err!(Benchmark, (head_id, testbed_id)))
+            {
+                Ok(benchmark_ids) => benchmark_ids,
+                Err(e) => {
+                    slog::warn!(
+                        log,
+                        "Skipping flaky detection for measure ({measure_id}) on branch ({branch_id}) and testbed ({testbed_id}): {e}"
+                    );
+                    skipped.measures.insert(measure_id);
+                    continue;
+                },
+            };
+            (threshold, query_branch, head_id, benchmark_ids)
+        };
+
+        for benchmark_id in benchmark_ids {
+            // A single bad metrics series should not stop the rest of the detection
+            let metrics_data = match metrics_data(
+                log,
+                &mut *conn.lock().await,
+                head_id,
+                testbed_id,
+                benchmark_id,
+                measure_id,
+                &threshold.model,
+                query_branch.frozen_baseline,
+            ) {
+                Ok(metrics_data) => metrics_data,
+                Err(e) => {
+                    slog::warn!(
+                        log,
+                        "Skipping flaky detection for benchmark ({benchmark_id}) and measure ({measure_id}): {e}"
+                    );
+                    skipped
+                        .benchmark_measures
+                        .insert((benchmark_id, measure_id));
+                    continue;
+                },
+            };
+            let Some(flakiness) = MetricsFlakiness::new(&metrics_data, settings.min_sample_size)
+            else {
+                continue;
+            };
+            checked.insert((benchmark_id, measure_id));
+            if flakiness.is_flaky(settings.max_coefficient_of_variation) {
+                let coefficient_of_variation =
+                    detected.entry((benchmark_id, measure_id)).or_default();
+                *coefficient_of_variation = flakiness
+                    .coefficient_of_variation
+                    .max(*coefficient_of_variation);
+            }
+        }
+    }
+
+    let (flaky, resolved) = replace_flaky_measures(&mut *conn.lock().await, detected, &skipped)?;
+    Ok(DetectedFlaky {
+        checked: checked.len(),
+        flaky,
+        resolved,
+    })
+}
+
+/// The benchmark measures that could not be checked for flakiness
+#[derive(Debug, Default)]
+struct Skipped {
+    measures: HashSet<MeasureId>,
+    benchmark_measures: HashSet<(BenchmarkId, MeasureId)>,
+}
+
+impl Skipped {
+    fn contains(&self, benchmark_id: BenchmarkId, measure_id: MeasureId) -> bool {
+        self.measures.contains(&measure_id)
+            || self
+                .benchmark_measures
+                .contains(&(benchmark_id, measure_id))
+    }
+}
+
+/// Replace all of the flaky benchmark measures with the ones that were just detected.
+/// Benchmark measures that were skipped are left as they are.
+/// Returns the number of benchmark measures that are now flaky and the number that are no longer flaky.
+fn replace_flaky_measures(
+    conn: &mut DbConnection,
+    mut detected: HashMap<(BenchmarkId, MeasureId), f64>,
+    skipped: &Skipped,
+) -> Result<(usize, usize), HttpError> {
+    let flaky = detected.len();
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let mut resolved = 0;
+        let existing = schema::flaky_measure::table
+            .select(QueryFlakyMeasure::as_select())
+            .load::<QueryFlakyMeasure>(conn)?;
+        for query_flaky_measure in existing {
+            let key = (
+                query_flaky_measure.benchmark_id,
+                query_flaky_measure.measure_id,
+            );
+            let flaky_measure = schema::flaky_measure::table
+                .filter(schema::flaky_measure::id.eq(query_flaky_measure.id));
+            // Keep when the measure was first detected as flaky
+            if let Some(coefficient_of_variation) = detected.remove(&key) {
+                diesel::update(flaky_measure)
+                    .set(
+                        schema::flaky_measure::coefficient_of_variation
+                            .eq(coefficient_of_variation),
+                    )
+                    .execute(conn)?;
+            } else if !skipped.contains(key.0, key.1) {
+                diesel::delete(flaky_measure).execute(conn)?;
+                resolved += 1;
+            }
+        }
+
+        let created = DateTime::now();
+        for ((benchmark_id, measure_id), coefficient_of_variation) in detected {
+            let insert_flaky_measure = InsertFlakyMeasure {
+                benchmark_id,
+                measure_id,
+                coefficient_of_variation,
+                created,
+            };
+            diesel::insert_into(schema::flaky_measure::table)
+                .values(&insert_flaky_measure)
+                .execute(conn)?;
+        }
+        Ok(resolved)
+    })
+    .map(|resolved| (flaky, resolved))
+    .map_err(resource_conflict_err!(FlakyMeasure, flaky))
+}
@@ -0,0 +1,113 @@
+use bencher_json::{project::github::JsonGitHubWebhook, DateTime, Secret};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    context::DbConnection,
+    error::{resource_conflict_err, resource_not_found_err},
+    schema::{self, github_webhook as github_webhook_table},
+};
+
+use super::{ProjectId, QueryProject};
+
+crate::util::typed_id::typed_id!(GitHubWebhookId);
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = github_webhook_table)]
+pub struct QueryGitHubWebhook {
+    pub id: GitHubWebhookId,
+    pub project_id: ProjectId,
+    pub secret: String,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryGitHubWebhook {
+    pub fn get_for_project(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::github_webhook::table
+            .filter(schema::github_webhook::project_id.eq(project_id))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(GitHubWebhook, project_id))
+    }
+
+    /// Verify the `X-Hub-Signature-256` header for a webhook delivery.
+    /// The signature is the hex encoded HMAC SHA-256 digest of the payload, using the webhook secret as the key.
+    pub fn verify(&self, signature: &str, payload: &[u8]) -> bool {
+        let Some(Ok(signature)) = signature.strip_prefix(SIGNATURE_PREFIX).map(hex::decode) else {
+            return false;
+        };
+        let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes()) else {
+            return false;
+        };
+        mac.update(payload);
+        // This is a constant time comparison
+        mac.verify_slice(&signature).is_ok()
+    }
+
+    /// The secret is only included when the webhook is created or its secret is rotated.
+    pub fn into_json(
+        self,
+        query_project: &QueryProject,
+        secret: Option<Secret>,
+    ) -> JsonGitHubWebhook {
+        let Self {
+            created, modified, ..
+        } = self;
+        JsonGitHubWebhook {
+            project: query_project.uuid,
+            secret,
+            created,
+            modified,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = github_webhook_table)]
+pub struct InsertGitHubWebhook {
+    pub project_id: ProjectId,
+    pub secret: String,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertGitHubWebhook {
+    /// Create the webhook for the project with a new random secret,
+    /// or replace the secret if the webhook already exists.
+    /// Returns the new secret.
+    pub fn upsert(conn: &mut DbConnection, project_id: ProjectId) -> Result<Secret, HttpError> {
+        let secret = Secret::from(uuid::Uuid::new_v4());
+        let now = DateTime::now();
+        if QueryGitHubWebhook::get_for_project(conn, project_id)?.is_some() {
+            diesel::update(
+                schema::github_webhook::table
+                    .filter(schema::github_webhook::project_id.eq(project_id)),
+            )
+            .set((
+                schema::github_webhook::secret.eq(secret.as_ref()),
+                schema::github_webhook::modified.eq(now),
+            ))
+            .execute(conn)
+        } else {
+            let insert_webhook = Self {
+                project_id,
+                secret: secret.as_ref().to_owned(),
+                created: now,
+                modified: now,
+            };
+            diesel::insert_into(schema::github_webhook::table)
+                .values(&insert_webhook)
+                .execute(conn)
+        }
+        .map_err(resource_conflict_err!(GitHubWebhook, project_id))?;
+        Ok(secret)
+    }
+}
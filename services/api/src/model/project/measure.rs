@@ -1,7 +1,10 @@
 use bencher_json::{
-    project::measure::{
-        built_in::{self, BuiltInMeasure},
-        JsonUpdateMeasure, MeasureUuid,
+    project::{
+        measure::{
+            built_in::{self, BuiltInMeasure},
+            JsonUpdateMeasure, MeasureUuid,
+        },
+        report::JsonDispersion,
     },
     DateTime, JsonMeasure, JsonNewMeasure, MeasureNameId, NameIdKind, ResourceName, Slug,
 };
@@ -12,7 +15,7 @@ use crate::{
     conn_lock,
     context::{ApiContext, DbConnection},
     error::{assert_parentage, resource_conflict_err, BencherResource},
-    model::project::QueryProject,
+    model::project::{measure_alias::QueryMeasureAlias, QueryProject},
     schema::{self, measure as measure_table},
     util::{
         fn_get::{fn_from_uuid, fn_get, fn_get_id, fn_get_uuid},
@@ -55,13 +58,72 @@ impl QueryMeasure {
     fn_get_uuid!(measure, MeasureId, MeasureUuid);
     fn_from_uuid!(measure, MeasureUuid, Measure);
 
+    /// Check if a measure or measure alias already exists in the project,
+    /// without creating it.
+    pub fn exists(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        measure: &MeasureNameId,
+    ) -> Result<bool, HttpError> {
+        if QueryMeasureAlias::measure_id(conn, project_id, measure.as_ref())?.is_some() {
+            return Ok(true);
+        }
+        Ok(Self::from_name_id(conn, project_id, measure).is_ok())
+    }
+
     pub async fn get_or_create(
         context: &ApiContext,
         project_id: ProjectId,
         measure: &MeasureNameId,
     ) -> Result<MeasureId, HttpError> {
         let query_measure = Self::get_or_create_inner(context, project_id, measure).await?;
+        Self::unarchive(context, query_measure).await
+    }
+
+    /// Get or create the measure for the dispersion of another measure.
+    /// The dispersion measure has the same units as the original measure.
+    pub async fn get_or_create_dispersion(
+        context: &ApiContext,
+        project_id: ProjectId,
+        measure_id: MeasureId,
+        dispersion: JsonDispersion,
+    ) -> Result<MeasureId, HttpError> {
+        let query_measure = Self::get(conn_lock!(context), measure_id)?;
+        let slug = Slug::new(format!(
+            "{slug}-{suffix}",
+            slug = query_measure.slug,
+            suffix = dispersion.slug_suffix()
+        ));
+
+        if let Ok(dispersion_measure) =
+            Self::from_name_id(conn_lock!(context), project_id, &slug.clone().into())
+        {
+            return Self::unarchive(context, dispersion_measure).await;
+        }
 
+        // Fall back to the slug if the name would be too long
+        let name: ResourceName = format!(
+            "{name} {suffix}",
+            name = query_measure.name,
+            suffix = dispersion.name_suffix()
+        )
+        .parse()
+        .unwrap_or_else(|_| slug.clone().into());
+        let measure = JsonNewMeasure {
+            name,
+            slug: Some(slug),
+            units: query_measure.units,
+        };
+        let insert_measure = InsertMeasure::from_json(conn_lock!(context), project_id, measure)?;
+        diesel::insert_into(schema::measure::table)
+            .values(&insert_measure)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(Measure, insert_measure))?;
+
+        Self::get_id(conn_lock!(context), insert_measure.uuid)
+    }
+
+    async fn unarchive(context: &ApiContext, query_measure: Self) -> Result<MeasureId, HttpError> {
         if query_measure.archived.is_some() {
             let update_measure = UpdateMeasure::unarchive();
             diesel::update(schema::measure::table.filter(schema::measure::id.eq(query_measure.id)))
@@ -78,6 +140,13 @@ impl QueryMeasure {
         project_id: ProjectId,
         measure: &MeasureNameId,
     ) -> Result<Self, HttpError> {
+        // Map measure aliases onto their canonical measure
+        if let Some(measure_id) =
+            QueryMeasureAlias::measure_id(conn_lock!(context), project_id, measure.as_ref())?
+        {
+            return Self::get(conn_lock!(context), measure_id);
+        }
+
         let query_measure = Self::from_name_id(conn_lock!(context), project_id, measure);
 
         let http_error = match query_measure {
@@ -119,6 +188,10 @@ impl QueryMeasure {
             .or_else(|| built_in::iai_callgrind::dhat_tool::AtTEndBlocks::from_str(measure_str))
             .or_else(|| built_in::iai_callgrind::dhat_tool::ReadsBytes::from_str(measure_str))
             .or_else(|| built_in::iai_callgrind::dhat_tool::WritesBytes::from_str(measure_str))
+            .or_else(|| built_in::perf_stat::Instructions::from_str(measure_str))
+            .or_else(|| built_in::perf_stat::Cycles::from_str(measure_str))
+            .or_else(|| built_in::perf_stat::BranchMisses::from_str(measure_str))
+            .or_else(|| built_in::perf_stat::CacheMisses::from_str(measure_str))
             .or_else(|| built_in::file_size::FileSize::from_str(measure_str))
         {
             measure
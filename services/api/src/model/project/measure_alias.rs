@@ -0,0 +1,324 @@
+use bencher_json::{
+    project::measure::alias::{JsonMeasureAlias, JsonNewMeasureAlias, MeasureAliasUuid},
+    DateTime, ResourceName, ThresholdUuid,
+};
+use diesel::{BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    context::DbConnection,
+    error::{
+        assert_parentage, bad_request_error, conflict_error, resource_conflict_err,
+        resource_not_found_err, BencherResource,
+    },
+    model::project::threshold::{recompute::enqueue_recompute, ThresholdId},
+    schema::{self, measure_alias as measure_alias_table},
+};
+
+use super::{
+    branch::BranchId,
+    measure::{MeasureId, QueryMeasure},
+    plot::PlotId,
+    report::report_benchmark::ReportBenchmarkId,
+    testbed::TestbedId,
+    ProjectId, QueryProject,
+};
+
+crate::util::typed_id::typed_id!(MeasureAliasId);
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = measure_alias_table)]
+#[diesel(belongs_to(QueryMeasure, foreign_key = measure_id))]
+pub struct QueryMeasureAlias {
+    pub id: MeasureAliasId,
+    pub uuid: MeasureAliasUuid,
+    pub project_id: ProjectId,
+    pub measure_id: MeasureId,
+    pub alias: ResourceName,
+    pub created: DateTime,
+}
+
+impl QueryMeasureAlias {
+    /// Get the canonical measure for an alias, if the alias exists in the project.
+    pub fn measure_id(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        alias: &str,
+    ) -> Result<Option<MeasureId>, HttpError> {
+        schema::measure_alias::table
+            .filter(schema::measure_alias::project_id.eq(project_id))
+            .filter(schema::measure_alias::alias.eq(alias))
+            .select(schema::measure_alias::measure_id)
+            .first::<MeasureId>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(MeasureAlias, (project_id, alias)))
+    }
+
+    pub fn into_json_for_measure(
+        self,
+        project: &QueryProject,
+        measure: &QueryMeasure,
+    ) -> JsonMeasureAlias {
+        let Self {
+            uuid,
+            project_id,
+            measure_id,
+            alias,
+            created,
+            ..
+        } = self;
+        assert_parentage(
+            BencherResource::Project,
+            project.id,
+            BencherResource::MeasureAlias,
+            project_id,
+        );
+        assert_parentage(
+            BencherResource::Measure,
+            measure.id,
+            BencherResource::MeasureAlias,
+            measure_id,
+        );
+        JsonMeasureAlias {
+            uuid,
+            project: project.uuid,
+            measure: measure.uuid,
+            alias,
+            created,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = measure_alias_table)]
+pub struct InsertMeasureAlias {
+    pub uuid: MeasureAliasUuid,
+    pub project_id: ProjectId,
+    pub measure_id: MeasureId,
+    pub alias: ResourceName,
+    pub created: DateTime,
+}
+
+impl InsertMeasureAlias {
+    pub fn from_json(
+        conn: &mut DbConnection,
+        query_measure: &QueryMeasure,
+        alias: JsonNewMeasureAlias,
+    ) -> Result<Self, HttpError> {
+        let JsonNewMeasureAlias { alias } = alias;
+        // An alias for another measure's name or slug would split its history,
+        // so those measures have to be merged instead.
+        let existing = schema::measure::table
+            .filter(schema::measure::project_id.eq(query_measure.project_id))
+            .filter(
+                schema::measure::name
+                    .eq(alias.as_ref())
+                    .or(schema::measure::slug.eq(alias.as_ref())),
+            )
+            .select(schema::measure::id)
+            .first::<MeasureId>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Measure, (query_measure, &alias)))?;
+        if existing.is_some() {
+            return Err(conflict_error(format!(
+                "Measure alias ({alias}) is already the name or slug of a measure. Merge the measures instead."
+            )));
+        }
+        Ok(Self::new(query_measure, alias))
+    }
+
+    fn new(query_measure: &QueryMeasure, alias: ResourceName) -> Self {
+        Self {
+            uuid: MeasureAliasUuid::new(),
+            project_id: query_measure.project_id,
+            measure_id: query_measure.id,
+            alias,
+            created: DateTime::now(),
+        }
+    }
+}
+
+/// Merge the source measure into the target measure.
+/// The metrics, thresholds, plots, and aliases for the source measure are moved to the target measure.
+/// Where both measures have a threshold for the same branch and testbed,
+/// the source threshold's models and boundaries are moved to the target threshold.
+/// The source measure's name and slug then become aliases for the target measure,
+/// and the source measure is deleted.
+pub fn merge_measures(
+    conn: &mut DbConnection,
+    target: &QueryMeasure,
+    source: &QueryMeasure,
+) -> Result<(), HttpError> {
+    if target.id == source.id {
+        return Err(bad_request_error(format!(
+            "Cannot merge measure ({uuid}) into itself",
+            uuid = target.uuid
+        )));
+    }
+
+    // A report benchmark can only have a single metric for each measure
+    let target_report_benchmarks = schema::metric::table
+        .filter(schema::metric::measure_id.eq(target.id))
+        .select(schema::metric::report_benchmark_id)
+        .load::<ReportBenchmarkId>(conn)
+        .map_err(resource_not_found_err!(Metric, target))?;
+    let overlapping = schema::metric::table
+        .filter(schema::metric::measure_id.eq(source.id))
+        .filter(schema::metric::report_benchmark_id.eq_any(target_report_benchmarks))
+        .count()
+        .get_result::<i64>(conn)
+        .map_err(resource_not_found_err!(Metric, (target, source)))?;
+    if overlapping > 0 {
+        return Err(conflict_error(format!(
+            "Cannot merge measure ({source}) into measure ({target}) because {overlapping} benchmark result(s) have metrics for both measures",
+            source = source.uuid,
+            target = target.uuid,
+        )));
+    }
+
+    diesel::update(schema::metric::table.filter(schema::metric::measure_id.eq(source.id)))
+        .set(schema::metric::measure_id.eq(target.id))
+        .execute(conn)
+        .map_err(resource_conflict_err!(Metric, (target, source)))?;
+
+    merge_thresholds(conn, target, source)?;
+    merge_plots(conn, target, source)?;
+
+    diesel::update(
+        schema::measure_alias::table.filter(schema::measure_alias::measure_id.eq(source.id)),
+    )
+    .set(schema::measure_alias::measure_id.eq(target.id))
+    .execute(conn)
+    .map_err(resource_conflict_err!(MeasureAlias, (target, source)))?;
+
+    // Delete the source measure before adding its name and slug as aliases,
+    // so ingest never finds both the alias and the measure.
+    diesel::delete(schema::measure::table.filter(schema::measure::id.eq(source.id)))
+        .execute(conn)
+        .map_err(resource_conflict_err!(Measure, source))?;
+
+    let mut aliases = vec![source.name.clone()];
+    if source.slug.as_ref() != source.name.as_ref() {
+        aliases.push(ResourceName::from(source.slug.clone()));
+    }
+    for alias in aliases {
+        // The target measure's own name or slug do not need an alias
+        if alias.as_ref() == target.name.as_ref() || alias.as_ref() == target.slug.as_ref() {
+            continue;
+        }
+        if QueryMeasureAlias::measure_id(conn, target.project_id, alias.as_ref())?.is_some() {
+            continue;
+        }
+        let insert_alias = InsertMeasureAlias::new(target, alias);
+        diesel::insert_into(schema::measure_alias::table)
+            .values(&insert_alias)
+            .execute(conn)
+            .map_err(resource_conflict_err!(MeasureAlias, insert_alias))?;
+    }
+
+    // The merged histories change the boundaries for the target measure's thresholds
+    let thresholds = schema::threshold::table
+        .filter(schema::threshold::measure_id.eq(target.id))
+        .filter(schema::threshold::model_id.is_not_null())
+        .select(schema::threshold::uuid)
+        .load::<ThresholdUuid>(conn)
+        .map_err(resource_not_found_err!(Threshold, target))?;
+    for threshold in thresholds {
+        enqueue_recompute(conn, threshold, None, None)?;
+    }
+
+    Ok(())
+}
+
+fn merge_thresholds(
+    conn: &mut DbConnection,
+    target: &QueryMeasure,
+    source: &QueryMeasure,
+) -> Result<(), HttpError> {
+    let source_thresholds = schema::threshold::table
+        .filter(schema::threshold::measure_id.eq(source.id))
+        .select((
+            schema::threshold::id,
+            schema::threshold::branch_id,
+            schema::threshold::testbed_id,
+        ))
+        .load::<(ThresholdId, BranchId, TestbedId)>(conn)
+        .map_err(resource_not_found_err!(Threshold, source))?;
+    for (source_threshold, branch_id, testbed_id) in source_thresholds {
+        let target_threshold = schema::threshold::table
+            .filter(schema::threshold::measure_id.eq(target.id))
+            .filter(schema::threshold::branch_id.eq(branch_id))
+            .filter(schema::threshold::testbed_id.eq(testbed_id))
+            .select(schema::threshold::id)
+            .first::<ThresholdId>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Threshold, target))?;
+        let Some(target_threshold) = target_threshold else {
+            diesel::update(
+                schema::threshold::table.filter(schema::threshold::id.eq(source_threshold)),
+            )
+            .set((
+                schema::threshold::measure_id.eq(target.id),
+                schema::threshold::modified.eq(DateTime::now()),
+            ))
+            .execute(conn)
+            .map_err(resource_conflict_err!(Threshold, source_threshold))?;
+            continue;
+        };
+
+        // Keep the source threshold's history, but only the target threshold's current model
+        diesel::update(
+            schema::model::table
+                .filter(schema::model::threshold_id.eq(source_threshold))
+                .filter(schema::model::replaced.is_null()),
+        )
+        .set(schema::model::replaced.eq(DateTime::now()))
+        .execute(conn)
+        .map_err(resource_conflict_err!(Model, source_threshold))?;
+        diesel::update(
+            schema::model::table.filter(schema::model::threshold_id.eq(source_threshold)),
+        )
+        .set(schema::model::threshold_id.eq(target_threshold))
+        .execute(conn)
+        .map_err(resource_conflict_err!(Model, target_threshold))?;
+        diesel::update(
+            schema::boundary::table.filter(schema::boundary::threshold_id.eq(source_threshold)),
+        )
+        .set(schema::boundary::threshold_id.eq(target_threshold))
+        .execute(conn)
+        .map_err(resource_conflict_err!(Boundary, target_threshold))?;
+        diesel::delete(schema::threshold::table.filter(schema::threshold::id.eq(source_threshold)))
+            .execute(conn)
+            .map_err(resource_conflict_err!(Threshold, source_threshold))?;
+    }
+    Ok(())
+}
+
+fn merge_plots(
+    conn: &mut DbConnection,
+    target: &QueryMeasure,
+    source: &QueryMeasure,
+) -> Result<(), HttpError> {
+    // Plots that already have the target measure keep their existing rank for it
+    let target_plots = schema::plot_measure::table
+        .filter(schema::plot_measure::measure_id.eq(target.id))
+        .select(schema::plot_measure::plot_id)
+        .load::<PlotId>(conn)
+        .map_err(resource_not_found_err!(PlotMeasure, target))?;
+    diesel::delete(
+        schema::plot_measure::table
+            .filter(schema::plot_measure::measure_id.eq(source.id))
+            .filter(schema::plot_measure::plot_id.eq_any(target_plots)),
+    )
+    .execute(conn)
+    .map_err(resource_conflict_err!(PlotMeasure, (target, source)))?;
+    diesel::update(
+        schema::plot_measure::table.filter(schema::plot_measure::measure_id.eq(source.id)),
+    )
+    .set(schema::plot_measure::measure_id.eq(target.id))
+    .execute(conn)
+    .map_err(resource_conflict_err!(PlotMeasure, (target, source)))?;
+    Ok(())
+}
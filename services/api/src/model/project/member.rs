@@ -0,0 +1,37 @@
+use bencher_json::{
+    project::ProjectRole, DateTime, Email, JsonProjectMember, Slug, UserName, UserUuid,
+};
+
+#[derive(diesel::Queryable)]
+pub struct QueryProjectMember {
+    pub uuid: UserUuid,
+    pub name: UserName,
+    pub slug: Slug,
+    pub email: Email,
+    pub role: ProjectRole,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryProjectMember {
+    pub fn into_json(self) -> JsonProjectMember {
+        let Self {
+            uuid,
+            name,
+            slug,
+            email,
+            role,
+            created,
+            modified,
+        } = self;
+        JsonProjectMember {
+            uuid,
+            name,
+            slug,
+            email,
+            role,
+            created,
+            modified,
+        }
+    }
+}
@@ -0,0 +1,684 @@
+use std::collections::HashMap;
+
+use bencher_boundary::MetricsAggregate;
+use bencher_json::{DateTime, JsonDownsampled, JsonMetricDownsample, TimeZone};
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, JoinOnDsl, NullableExpressionMethods,
+    OptionalExtension, QueryDsl, RunQueryDsl,
+};
+use dropshot::HttpError;
+use http::StatusCode;
+use tokio::sync::Mutex;
+
+use crate::{
+    context::DbConnection,
+    error::{issue_error, resource_conflict_err, resource_not_found_err},
+    schema::{self, downsample as downsample_table, downsample_mark as downsample_mark_table},
+};
+
+use super::{
+    benchmark::BenchmarkId, branch::head::HeadId, measure::MeasureId, metric::MetricId,
+    testbed::TestbedId,
+};
+
+// Metrics are aggregated by benchmark, measure, and day in the project time zone
+// for each branch head and testbed
+type DailyKey = (BenchmarkId, MeasureId, i64);
+
+#[derive(Debug, diesel::Queryable)]
+struct DownsampleMetric {
+    id: MetricId,
+    benchmark_id: BenchmarkId,
+    measure_id: MeasureId,
+    start_time: DateTime,
+    value: f64,
+    lower_value: Option<f64>,
+    upper_value: Option<f64>,
+    // Only set if the metric is already a daily aggregate
+    sample_count: Option<i32>,
+    min_value: Option<f64>,
+    max_value: Option<f64>,
+}
+
+impl DownsampleMetric {
+    fn daily_key(&self, time_zone: &TimeZone) -> DailyKey {
+        (
+            self.benchmark_id,
+            self.measure_id,
+            self.start_time.start_of_day(time_zone).timestamp(),
+        )
+    }
+
+    fn aggregate(&self) -> MetricsAggregate {
+        let metric = MetricsAggregate::metric(self.value, self.lower_value, self.upper_value);
+        if let Some(sample_count) = self.sample_count {
+            MetricsAggregate {
+                min_value: self.min_value.unwrap_or(self.value),
+                max_value: self.max_value.unwrap_or(self.value),
+                sample_count,
+                ..metric
+            }
+        } else {
+            metric
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = downsample_table)]
+struct InsertDownsample {
+    metric_id: MetricId,
+    sample_count: i32,
+    min_value: f64,
+    max_value: f64,
+    created: DateTime,
+}
+
+// How far the metrics for a branch head and testbed have already been downsampled
+#[derive(Debug, Clone, Copy, diesel::Queryable)]
+struct DownsampleMark {
+    cutoff: DateTime,
+    modified: DateTime,
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = downsample_mark_table)]
+struct InsertDownsampleMark {
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    cutoff: DateTime,
+    modified: DateTime,
+}
+
+/// Replace the metrics from reports that started before the cutoff with daily aggregates.
+/// The connection is only locked for one branch head and testbed at a time,
+/// so that the API server can continue to handle requests.
+/// Only the days with reports that have come in since the last downsample are aggregated.
+pub async fn downsample(
+    conn: &Mutex<DbConnection>,
+    cutoff: DateTime,
+    dry_run: bool,
+) -> Result<JsonDownsampled, HttpError> {
+    // Only the branch heads and testbeds with reports that have not been downsampled yet
+    let heads_testbeds = schema::report::table
+        .inner_join(schema::project::table)
+        .left_join(
+            schema::downsample_mark::table.on(schema::downsample_mark::head_id
+                .eq(schema::report::head_id)
+                .and(schema::downsample_mark::testbed_id.eq(schema::report::testbed_id))),
+        )
+        .filter(schema::report::start_time.lt(cutoff))
+        .filter(
+            schema::downsample_mark::id
+                .is_null()
+                .or(schema::report::start_time.ge(schema::downsample_mark::cutoff))
+                .or(schema::report::created.ge(schema::downsample_mark::modified)),
+        )
+        .select((
+            schema::report::head_id,
+            schema::report::testbed_id,
+            schema::project::time_zone,
+        ))
+        .distinct()
+        .load::<(HeadId, TestbedId, TimeZone)>(&mut *conn.lock().await)
+        .map_err(resource_not_found_err!(Report, cutoff))?;
+
+    let mut aggregates = 0;
+    let mut removed = 0;
+    for (head_id, testbed_id, time_zone) in heads_testbeds {
+        let conn = &mut *conn.lock().await;
+        // Any report created from here on is left for the next downsample
+        let modified = DateTime::now();
+        let mark = get_mark(conn, head_id, testbed_id)?;
+        let since = since(conn, head_id, testbed_id, &time_zone, cutoff, mark)?;
+        let daily_metrics = daily_metrics(conn, head_id, testbed_id, &time_zone, since, cutoff)?
+            .into_values()
+            // A lone metric for the day is already as small as it can get
+            .filter(|metrics| metrics.len() > 1)
+            .collect::<Vec<_>>();
+        aggregates += daily_metrics.len();
+        removed += daily_metrics
+            .iter()
+            .map(|metrics| metrics.len() - 1)
+            .sum::<usize>();
+        if !dry_run {
+            conn.transaction(|conn| {
+                daily_metrics
+                    .iter()
+                    .try_for_each(|metrics| aggregate(conn, metrics))?;
+                // A smaller cutoff does not undo the downsampling that has already been done
+                let cutoff = mark.map_or(cutoff, |mark| mark.cutoff.max(cutoff));
+                upsert_mark(conn, head_id, testbed_id, cutoff, modified)
+            })
+            .map_err(resource_conflict_err!(Metric, (head_id, testbed_id)))?;
+        }
+    }
+
+    Ok(JsonDownsampled {
+        cutoff,
+        dry_run,
+        aggregates: count_u32(aggregates)?,
+        removed: count_u32(removed)?,
+    })
+}
+
+fn get_mark(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+) -> Result<Option<DownsampleMark>, HttpError> {
+    schema::downsample_mark::table
+        .filter(schema::downsample_mark::head_id.eq(head_id))
+        .filter(schema::downsample_mark::testbed_id.eq(testbed_id))
+        .select((
+            schema::downsample_mark::cutoff,
+            schema::downsample_mark::modified,
+        ))
+        .first::<DownsampleMark>(conn)
+        .optional()
+        .map_err(resource_not_found_err!(Metric, (head_id, testbed_id)))
+}
+
+fn upsert_mark(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    cutoff: DateTime,
+    modified: DateTime,
+) -> diesel::QueryResult<()> {
+    let updated = diesel::update(
+        schema::downsample_mark::table
+            .filter(schema::downsample_mark::head_id.eq(head_id))
+            .filter(schema::downsample_mark::testbed_id.eq(testbed_id)),
+    )
+    .set((
+        schema::downsample_mark::cutoff.eq(cutoff),
+        schema::downsample_mark::modified.eq(modified),
+    ))
+    .execute(conn)?;
+    if updated > 0 {
+        return Ok(());
+    }
+
+    let insert_mark = InsertDownsampleMark {
+        head_id,
+        testbed_id,
+        cutoff,
+        modified,
+    };
+    diesel::insert_into(schema::downsample_mark::table)
+        .values(&insert_mark)
+        .execute(conn)?;
+    Ok(())
+}
+
+// The start of the earliest day with a report that has not been downsampled yet.
+// Reports that started after the last cutoff or that were created after the last downsample,
+// such as reports that were backdated, have not been downsampled.
+// If there has never been a downsample, then every report needs to be checked.
+fn since(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    time_zone: &TimeZone,
+    cutoff: DateTime,
+    mark: Option<DownsampleMark>,
+) -> Result<Option<DateTime>, HttpError> {
+    let Some(mark) = mark else {
+        return Ok(None);
+    };
+    let start_time = schema::report::table
+        .filter(schema::report::head_id.eq(head_id))
+        .filter(schema::report::testbed_id.eq(testbed_id))
+        .filter(schema::report::start_time.lt(cutoff))
+        .filter(
+            schema::report::start_time
+                .ge(mark.cutoff)
+                .or(schema::report::created.ge(mark.modified)),
+        )
+        .select(diesel::dsl::min(schema::report::start_time))
+        .first::<Option<DateTime>>(conn)
+        .map_err(resource_not_found_err!(
+            Report,
+            (head_id, testbed_id, cutoff)
+        ))?;
+    // Everything has already been downsampled, so there is nothing left to load
+    Ok(Some(start_time.map_or(cutoff, |start_time| {
+        start_time.start_of_day(time_zone)
+    })))
+}
+
+// All of the metrics for every day that has a report that has not been downsampled yet,
+// so that they are aggregated along with the metrics that were already downsampled for that day
+fn daily_metrics(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    time_zone: &TimeZone,
+    since: Option<DateTime>,
+    cutoff: DateTime,
+) -> Result<HashMap<DailyKey, Vec<DownsampleMetric>>, HttpError> {
+    // Metrics with an alert are never downsampled, so that the alert history is kept
+    let alerted = schema::boundary::table
+        .inner_join(schema::alert::table)
+        .select(schema::boundary::metric_id);
+    let mut query = schema::metric::table
+        .inner_join(schema::report_benchmark::table.inner_join(schema::report::table))
+        .left_join(
+            schema::downsample::table.on(schema::downsample::metric_id.eq(schema::metric::id)),
+        )
+        .filter(schema::report::head_id.eq(head_id))
+        .filter(schema::report::testbed_id.eq(testbed_id))
+        .filter(schema::report::start_time.lt(cutoff))
+        .filter(schema::metric::id.ne_all(alerted))
+        .into_boxed();
+    if let Some(since) = since {
+        query = query.filter(schema::report::start_time.ge(since));
+    }
+    let metrics = query
+        .order(schema::metric::id.asc())
+        .select((
+            schema::metric::id,
+            schema::report_benchmark::benchmark_id,
+            schema::metric::measure_id,
+            schema::report::start_time,
+            schema::metric::value,
+            schema::metric::lower_value,
+            schema::metric::upper_value,
+            schema::downsample::sample_count.nullable(),
+            schema::downsample::min_value.nullable(),
+            schema::downsample::max_value.nullable(),
+        ))
+        .load::<DownsampleMetric>(conn)
+        .map_err(resource_not_found_err!(
+            Metric,
+            (head_id, testbed_id, cutoff)
+        ))?;
+
+    let mut daily_metrics: HashMap<DailyKey, Vec<DownsampleMetric>> = HashMap::new();
+    for metric in metrics {
+        daily_metrics
+            .entry(metric.daily_key(time_zone))
+            .or_default()
+            .push(metric);
+    }
+    Ok(daily_metrics)
+}
+
+// Keep the oldest metric for the day as the aggregate and remove the rest
+fn aggregate(conn: &mut DbConnection, metrics: &[DownsampleMetric]) -> diesel::QueryResult<()> {
+    let Some((kept, rest)) = metrics.split_first() else {
+        return Ok(());
+    };
+    let aggregates = metrics
+        .iter()
+        .map(DownsampleMetric::aggregate)
+        .collect::<Vec<_>>();
+    // Too many samples to count, so leave the metrics as they are
+    let Some(aggregate) = MetricsAggregate::combine(&aggregates) else {
+        return Ok(());
+    };
+
+    diesel::update(schema::metric::table.filter(schema::metric::id.eq(kept.id)))
+        .set((
+            schema::metric::value.eq(aggregate.value),
+            schema::metric::lower_value.eq(aggregate.lower_value),
+            schema::metric::upper_value.eq(aggregate.upper_value),
+        ))
+        .execute(conn)?;
+
+    // The boundary of the kept metric was for its original value, so it no longer applies.
+    // It does not have an alert, as those metrics are never downsampled.
+    diesel::delete(schema::boundary::table.filter(schema::boundary::metric_id.eq(kept.id)))
+        .execute(conn)?;
+
+    diesel::delete(schema::downsample::table.filter(schema::downsample::metric_id.eq(kept.id)))
+        .execute(conn)?;
+    let insert_downsample = InsertDownsample {
+        metric_id: kept.id,
+        sample_count: aggregate.sample_count,
+        min_value: aggregate.min_value,
+        max_value: aggregate.max_value,
+        created: DateTime::now(),
+    };
+    diesel::insert_into(schema::downsample::table)
+        .values(&insert_downsample)
+        .execute(conn)?;
+
+    // The boundaries of the removed metrics are removed along with them.
+    // None of them have an alert, as those metrics are never downsampled.
+    let removed_ids = rest.iter().map(|metric| metric.id).collect::<Vec<_>>();
+    diesel::delete(schema::metric::table.filter(schema::metric::id.eq_any(&removed_ids)))
+        .execute(conn)?;
+
+    Ok(())
+}
+
+/// Get the daily aggregate for a metric, if it has been downsampled
+pub fn get_downsample(
+    conn: &mut DbConnection,
+    metric_id: MetricId,
+) -> Result<Option<JsonMetricDownsample>, HttpError> {
+    let Some((sample_count, min_value, max_value)) = schema::downsample::table
+        .filter(schema::downsample::metric_id.eq(metric_id))
+        .select((
+            schema::downsample::sample_count,
+            schema::downsample::min_value,
+            schema::downsample::max_value,
+        ))
+        .first::<(i32, f64, f64)>(conn)
+        .optional()
+        .map_err(resource_not_found_err!(Metric, metric_id))?
+    else {
+        return Ok(None);
+    };
+    Ok(Some(JsonMetricDownsample {
+        sample_count: count_u32(sample_count)?,
+        min_value: min_value.into(),
+        max_value: max_value.into(),
+    }))
+}
+
+fn count_u32<T>(count: T) -> Result<u32, HttpError>
+where
+    T: TryInto<u32, Error = std::num::TryFromIntError> + Copy + std::fmt::Display,
+{
+    count.try_into().map_err(|e| {
+        issue_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to count downsampled metrics",
+            &format!("Failed to count downsampled metrics: {count}"),
+            e,
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{
+        project::Visibility, AlertUuid, BoundaryUuid, DateTime, ModelUuid, ThresholdUuid,
+    };
+    use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+    use pretty_assertions::assert_eq;
+    use tokio::sync::Mutex;
+
+    use crate::{
+        context::DbConnection,
+        model::project::{
+            benchmark::BenchmarkId,
+            branch::{head::HeadId, BranchId},
+            measure::MeasureId,
+            metric::MetricId,
+            testbed::TestbedId,
+            QueryProject,
+        },
+        schema,
+        util::test_util::{
+            create_benchmark, create_branch, create_measure, create_metric, create_organization,
+            create_project, create_report, create_testbed, setup_db,
+        },
+    };
+
+    use super::{downsample, get_downsample};
+
+    // 2024-01-01T00:00:00Z
+    const DAY: i64 = 1_704_067_200;
+    const HOUR: i64 = 60 * 60;
+
+    struct Fixture {
+        conn: Mutex<DbConnection>,
+        project: QueryProject,
+        head_id: HeadId,
+        testbed_id: TestbedId,
+        benchmark_id: BenchmarkId,
+        measure_id: MeasureId,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut conn = setup_db();
+            let org = create_organization(&mut conn, "Test Org");
+            let project = create_project(&mut conn, &org, "Test Project", Visibility::Public);
+            let (_, head_id) = create_branch(&mut conn, &project, "main");
+            let testbed_id = create_testbed(&mut conn, &project, "localhost");
+            let benchmark_id = create_benchmark(&mut conn, &project, "bench");
+            let measure_id = create_measure(&mut conn, &project, "latency");
+            Self {
+                conn: Mutex::new(conn),
+                project,
+                head_id,
+                testbed_id,
+                benchmark_id,
+                measure_id,
+            }
+        }
+
+        async fn metric(&self, timestamp: i64, value: f64) -> MetricId {
+            let conn = &mut *self.conn.lock().await;
+            let report_id = create_report(
+                conn,
+                &self.project,
+                self.head_id,
+                self.testbed_id,
+                DateTime::try_from(timestamp).unwrap(),
+            );
+            create_metric(conn, report_id, self.benchmark_id, self.measure_id, value)
+        }
+
+        async fn values(&self) -> Vec<(MetricId, f64)> {
+            schema::metric::table
+                .order(schema::metric::id.asc())
+                .select((schema::metric::id, schema::metric::value))
+                .load(&mut *self.conn.lock().await)
+                .unwrap()
+        }
+
+        async fn boundary(&self, metric_id: MetricId, alert: bool) {
+            let conn = &mut *self.conn.lock().await;
+            let branch_id = schema::head::table
+                .filter(schema::head::id.eq(self.head_id))
+                .select(schema::head::branch_id)
+                .first::<BranchId>(conn)
+                .unwrap();
+            let threshold_uuid = ThresholdUuid::new();
+            diesel::insert_into(schema::threshold::table)
+                .values((
+                    schema::threshold::uuid.eq(threshold_uuid),
+                    schema::threshold::project_id.eq(self.project.id),
+                    schema::threshold::branch_id.eq(branch_id),
+                    schema::threshold::testbed_id.eq(self.testbed_id),
+                    schema::threshold::measure_id.eq(self.measure_id),
+                    schema::threshold::created.eq(DateTime::now()),
+                    schema::threshold::modified.eq(DateTime::now()),
+                ))
+                .execute(conn)
+                .unwrap();
+            let threshold_id = schema::threshold::table
+                .filter(schema::threshold::uuid.eq(threshold_uuid))
+                .select(schema::threshold::id)
+                .first::<i32>(conn)
+                .unwrap();
+            let model_uuid = ModelUuid::new();
+            diesel::insert_into(schema::model::table)
+                .values((
+                    schema::model::uuid.eq(model_uuid),
+                    schema::model::threshold_id.eq(threshold_id),
+                    schema::model::test.eq(0),
+                    schema::model::created.eq(DateTime::now()),
+                ))
+                .execute(conn)
+                .unwrap();
+            let model_id = schema::model::table
+                .filter(schema::model::uuid.eq(model_uuid))
+                .select(schema::model::id)
+                .first::<i32>(conn)
+                .unwrap();
+            let boundary_uuid = BoundaryUuid::new();
+            diesel::insert_into(schema::boundary::table)
+                .values((
+                    schema::boundary::uuid.eq(boundary_uuid),
+                    schema::boundary::metric_id.eq(metric_id),
+                    schema::boundary::threshold_id.eq(threshold_id),
+                    schema::boundary::model_id.eq(model_id),
+                ))
+                .execute(conn)
+                .unwrap();
+            if !alert {
+                return;
+            }
+            let boundary_id = schema::boundary::table
+                .filter(schema::boundary::uuid.eq(boundary_uuid))
+                .select(schema::boundary::id)
+                .first::<i32>(conn)
+                .unwrap();
+            diesel::insert_into(schema::alert::table)
+                .values((
+                    schema::alert::uuid.eq(AlertUuid::new()),
+                    schema::alert::boundary_id.eq(boundary_id),
+                    schema::alert::boundary_limit.eq(false),
+                    schema::alert::severity.eq(0),
+                    schema::alert::status.eq(0),
+                    schema::alert::modified.eq(DateTime::now()),
+                ))
+                .execute(conn)
+                .unwrap();
+        }
+
+        async fn boundaries(&self, metric_id: MetricId) -> i64 {
+            schema::boundary::table
+                .filter(schema::boundary::metric_id.eq(metric_id))
+                .count()
+                .get_result(&mut *self.conn.lock().await)
+                .unwrap()
+        }
+
+        async fn marks(&self) -> i64 {
+            schema::downsample_mark::table
+                .count()
+                .get_result(&mut *self.conn.lock().await)
+                .unwrap()
+        }
+
+        async fn sample_count(&self, metric_id: MetricId) -> Option<(u32, f64, f64)> {
+            get_downsample(&mut *self.conn.lock().await, metric_id)
+                .unwrap()
+                .map(|downsample| {
+                    (
+                        downsample.sample_count,
+                        downsample.min_value.into(),
+                        downsample.max_value.into(),
+                    )
+                })
+        }
+    }
+
+    fn cutoff() -> DateTime {
+        DateTime::try_from(DAY + 31 * 24 * HOUR).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_downsample_daily() {
+        let fixture = Fixture::new();
+        let kept = fixture.metric(DAY + HOUR, 1.0).await;
+        fixture.metric(DAY + 2 * HOUR, 2.0).await;
+        fixture.metric(DAY + 3 * HOUR, 3.0).await;
+        let lone = fixture.metric(DAY + 25 * HOUR, 10.0).await;
+        // After the cutoff
+        let recent = fixture.metric(DAY + 40 * 24 * HOUR, 20.0).await;
+        fixture.metric(DAY + 40 * 24 * HOUR + HOUR, 30.0).await;
+
+        let downsampled = downsample(&fixture.conn, cutoff(), false).await.unwrap();
+        assert_eq!(downsampled.aggregates, 1);
+        assert_eq!(downsampled.removed, 2);
+
+        let values = fixture.values().await;
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[0].0, kept);
+        assert!((values[0].1 - 2.0).abs() < f64::EPSILON);
+        assert_eq!(values[1].0, lone);
+        assert_eq!(values[2].0, recent);
+        assert_eq!(fixture.sample_count(kept).await, Some((3, 1.0, 3.0)));
+        assert_eq!(fixture.sample_count(lone).await, None);
+        assert_eq!(fixture.marks().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_downsample_dry_run() {
+        let fixture = Fixture::new();
+        fixture.metric(DAY + HOUR, 1.0).await;
+        fixture.metric(DAY + 2 * HOUR, 2.0).await;
+
+        let downsampled = downsample(&fixture.conn, cutoff(), true).await.unwrap();
+        assert_eq!(downsampled.aggregates, 1);
+        assert_eq!(downsampled.removed, 1);
+        assert_eq!(fixture.values().await.len(), 2);
+        // A dry run does not move the mark, so the real run still sees everything
+        assert_eq!(fixture.marks().await, 0);
+
+        let downsampled = downsample(&fixture.conn, cutoff(), false).await.unwrap();
+        assert_eq!(downsampled.aggregates, 1);
+        assert_eq!(fixture.values().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_downsample_boundaries() {
+        let fixture = Fixture::new();
+        let kept = fixture.metric(DAY + HOUR, 1.0).await;
+        let alerted = fixture.metric(DAY + 2 * HOUR, 100.0).await;
+        let removed = fixture.metric(DAY + 3 * HOUR, 3.0).await;
+        fixture.boundary(kept, false).await;
+        fixture.boundary(alerted, true).await;
+        fixture.boundary(removed, false).await;
+
+        let downsampled = downsample(&fixture.conn, cutoff(), false).await.unwrap();
+        assert_eq!(downsampled.aggregates, 1);
+        assert_eq!(downsampled.removed, 1);
+
+        let values = fixture.values().await;
+        assert_eq!(
+            values.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![kept, alerted]
+        );
+        // The alerted metric is left out of the aggregate
+        assert_eq!(fixture.sample_count(kept).await, Some((2, 1.0, 3.0)));
+        // The boundary of the kept metric is stale, so it is removed
+        assert_eq!(fixture.boundaries(kept).await, 0);
+        assert_eq!(fixture.boundaries(alerted).await, 1);
+        assert_eq!(fixture.boundaries(removed).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_downsample_mark() {
+        let fixture = Fixture::new();
+        let kept = fixture.metric(DAY + HOUR, 1.0).await;
+        fixture.metric(DAY + 2 * HOUR, 2.0).await;
+
+        let downsampled = downsample(&fixture.conn, cutoff(), false).await.unwrap();
+        assert_eq!(downsampled.aggregates, 1);
+
+        // Nothing new has come in
+        let downsampled = downsample(&fixture.conn, cutoff(), false).await.unwrap();
+        assert_eq!(downsampled.aggregates, 0);
+        assert_eq!(downsampled.removed, 0);
+
+        // A backdated report is aggregated with the metrics that were already downsampled
+        fixture.metric(DAY + 3 * HOUR, 6.0).await;
+        let downsampled = downsample(&fixture.conn, cutoff(), false).await.unwrap();
+        assert_eq!(downsampled.aggregates, 1);
+        assert_eq!(downsampled.removed, 1);
+        assert_eq!(fixture.sample_count(kept).await, Some((3, 1.0, 6.0)));
+
+        // Reports that were after the previous cutoff are aggregated once the cutoff moves
+        let later = fixture.metric(DAY + 40 * 24 * HOUR, 10.0).await;
+        fixture.metric(DAY + 40 * 24 * HOUR + HOUR, 20.0).await;
+        let later_cutoff = DateTime::try_from(DAY + 60 * 24 * HOUR).unwrap();
+        let downsampled = downsample(&fixture.conn, later_cutoff, false)
+            .await
+            .unwrap();
+        assert_eq!(downsampled.aggregates, 1);
+        assert_eq!(downsampled.removed, 1);
+        assert_eq!(fixture.sample_count(later).await, Some((2, 10.0, 20.0)));
+        assert_eq!(fixture.sample_count(kept).await, Some((3, 1.0, 6.0)));
+        assert_eq!(fixture.marks().await, 1);
+    }
+}
@@ -1,20 +1,38 @@
 use std::string::ToString;
 
 use bencher_json::{
-    project::{JsonProjectPatch, JsonProjectPatchNull, JsonUpdateProject, Visibility},
-    DateTime, JsonNewProject, JsonProject, ProjectUuid, ResourceId, ResourceName, Slug, Url,
+    project::{
+        measure::built_in::generic::{Latency, Throughput},
+        JsonProjectPatch, JsonProjectPatchNull, JsonUpdateProject, Visibility,
+    },
+    DateTime, JsonNewProject, JsonProject, ProjectUuid, ResourceId, ResourceName, Slug, TimeZone,
+    Url, Weekday,
 };
 use bencher_rbac::{project::Permission, Organization, Project};
 use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
 use dropshot::HttpError;
+use slog::Logger;
 
 use crate::{
-    context::{DbConnection, Rbac},
-    error::{assert_parentage, forbidden_error, unauthorized_error, BencherResource},
-    model::{organization::QueryOrganization, user::auth::AuthUser},
+    conn_lock,
+    context::{ApiContext, DbConnection, Rbac},
+    error::{
+        assert_parentage, forbidden_error, resource_conflict_err, resource_not_found_err,
+        unauthorized_error, BencherResource,
+    },
+    model::{
+        organization::QueryOrganization,
+        project::{
+            branch::InsertBranch,
+            measure::{InsertMeasure, QueryMeasure},
+            testbed::{InsertTestbed, QueryTestbed},
+            threshold::InsertThreshold,
+        },
+        user::auth::AuthUser,
+    },
     schema::{self, project as project_table},
     util::{
-        fn_get::{fn_get, fn_get_uuid},
+        fn_get::{fn_get, fn_get_id, fn_get_uuid},
         resource_id::{fn_eq_resource_id, fn_from_resource_id},
         slug::ok_slug,
     },
@@ -22,16 +40,30 @@ use crate::{
 
 use super::{organization::OrganizationId, user::auth::BEARER_TOKEN_FORMAT};
 
+pub mod annotation;
 pub mod benchmark;
 pub mod branch;
+pub mod expectation;
+pub mod flaky;
+pub mod github_webhook;
 pub mod measure;
+pub mod measure_alias;
+pub mod member;
 pub mod metric;
 pub mod metric_boundary;
+pub mod metric_downsample;
+pub mod perf_embed;
 pub mod plot;
 pub mod project_role;
 pub mod report;
+pub mod report_hook;
+pub mod rule;
+pub mod saved_query;
+pub mod stats;
 pub mod testbed;
 pub mod threshold;
+pub mod unclaimed_project;
+pub mod webhook;
 
 crate::util::typed_id::typed_id!(ProjectId);
 
@@ -48,6 +80,9 @@ pub struct QueryProject {
     pub slug: Slug,
     pub url: Option<Url>,
     pub visibility: Visibility,
+    pub time_zone: TimeZone,
+    pub week_start: Weekday,
+    pub strict: bool,
     pub created: DateTime,
     pub modified: DateTime,
 }
@@ -57,6 +92,7 @@ impl QueryProject {
     fn_from_resource_id!(project, Project, true);
 
     fn_get!(project, ProjectId);
+    fn_get_id!(project, ProjectId, ProjectUuid);
     fn_get_uuid!(project, ProjectId, ProjectUuid);
 
     pub fn is_public(&self) -> bool {
@@ -142,6 +178,9 @@ impl QueryProject {
             slug,
             url,
             visibility,
+            time_zone,
+            week_start,
+            strict,
             created,
             modified,
             ..
@@ -159,8 +198,12 @@ impl QueryProject {
             slug,
             url,
             visibility,
+            time_zone,
+            week_start,
+            strict,
             created,
             modified,
+            stats: None,
         }
     }
 }
@@ -174,6 +217,9 @@ pub struct InsertProject {
     pub slug: Slug,
     pub url: Option<Url>,
     pub visibility: Visibility,
+    pub time_zone: TimeZone,
+    pub week_start: Weekday,
+    pub strict: bool,
     pub created: DateTime,
     pub modified: DateTime,
 }
@@ -189,6 +235,9 @@ impl InsertProject {
             slug,
             url,
             visibility,
+            time_zone,
+            week_start,
+            strict,
         } = project;
         let slug = ok_slug!(conn, &name, slug, project, QueryProject)?;
         let timestamp = DateTime::now();
@@ -199,10 +248,85 @@ impl InsertProject {
             slug,
             url,
             visibility: visibility.unwrap_or_default(),
+            time_zone: time_zone.unwrap_or_default(),
+            week_start: week_start.unwrap_or_default(),
+            strict: strict.unwrap_or_default(),
             created: timestamp,
             modified: timestamp,
         })
     }
+
+    /// Insert the project along with its default resources:
+    /// a `main` branch, a `localhost` testbed, `latency` and `throughput` measures,
+    /// and a threshold for both measures.
+    pub async fn insert_with_defaults(
+        &self,
+        log: &Logger,
+        context: &ApiContext,
+    ) -> Result<QueryProject, HttpError> {
+        diesel::insert_into(schema::project::table)
+            .values(self)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(Project, self))?;
+        let query_project = schema::project::table
+            .filter(schema::project::uuid.eq(&self.uuid))
+            .first::<QueryProject>(conn_lock!(context))
+            .map_err(resource_not_found_err!(Project, self))?;
+
+        // Add a `main` branch to the project
+        let query_branch = InsertBranch::main(log, context, query_project.id).await?;
+        slog::debug!(log, "Added project branch: {query_branch:?}");
+        let branch_id = query_branch.id;
+
+        // Add a `localhost` testbed to the project
+        let insert_testbed = InsertTestbed::localhost(conn_lock!(context), query_project.id)?;
+        diesel::insert_into(schema::testbed::table)
+            .values(&insert_testbed)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(Testbed, insert_testbed))?;
+        let testbed_id = QueryTestbed::get_id(conn_lock!(context), insert_testbed.uuid)?;
+        slog::debug!(log, "Added project testbed: {insert_testbed:?}");
+
+        // Add a `latency` measure to the project
+        let insert_measure =
+            InsertMeasure::from_measure::<Latency>(conn_lock!(context), query_project.id)?;
+        diesel::insert_into(schema::measure::table)
+            .values(&insert_measure)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(Measure, insert_measure))?;
+        let measure_id = QueryMeasure::get_id(conn_lock!(context), insert_measure.uuid)?;
+        slog::debug!(log, "Added project measure: {insert_measure:?}");
+        // Add a `latency` threshold to the project
+        let threshold_id = InsertThreshold::upper_boundary(
+            conn_lock!(context),
+            query_project.id,
+            branch_id,
+            testbed_id,
+            measure_id,
+        )?;
+        slog::debug!(log, "Added project threshold: {threshold_id}");
+
+        // Add a `throughput` measure to the project
+        let insert_measure =
+            InsertMeasure::from_measure::<Throughput>(conn_lock!(context), query_project.id)?;
+        diesel::insert_into(schema::measure::table)
+            .values(&insert_measure)
+            .execute(conn_lock!(context))
+            .map_err(resource_conflict_err!(Measure, insert_measure))?;
+        let measure_id = QueryMeasure::get_id(conn_lock!(context), insert_measure.uuid)?;
+        slog::debug!(log, "Added project measure: {insert_measure:?}");
+        // Add a `throughput` threshold to the project
+        let threshold_id = InsertThreshold::lower_boundary(
+            conn_lock!(context),
+            query_project.id,
+            branch_id,
+            testbed_id,
+            measure_id,
+        )?;
+        slog::debug!(log, "Added project threshold: {threshold_id}");
+
+        Ok(query_project)
+    }
 }
 
 #[derive(Debug, Clone, diesel::AsChangeset)]
@@ -212,6 +336,9 @@ pub struct UpdateProject {
     pub slug: Option<Slug>,
     pub url: Option<Option<Url>>,
     pub visibility: Option<Visibility>,
+    pub time_zone: Option<TimeZone>,
+    pub week_start: Option<Weekday>,
+    pub strict: Option<bool>,
     pub modified: DateTime,
 }
 
@@ -224,12 +351,18 @@ impl From<JsonUpdateProject> for UpdateProject {
                     slug,
                     url,
                     visibility,
+                    time_zone,
+                    week_start,
+                    strict,
                 } = patch;
                 Self {
                     name,
                     slug,
                     url: url.map(Some),
                     visibility,
+                    time_zone,
+                    week_start,
+                    strict,
                     modified: DateTime::now(),
                 }
             },
@@ -239,12 +372,18 @@ impl From<JsonUpdateProject> for UpdateProject {
                     slug,
                     url: (),
                     visibility,
+                    time_zone,
+                    week_start,
+                    strict,
                 } = patch_url;
                 Self {
                     name,
                     slug,
                     url: Some(None),
                     visibility,
+                    time_zone,
+                    week_start,
+                    strict,
                     modified: DateTime::now(),
                 }
             },
@@ -273,6 +412,7 @@ impl From<&QueryProject> for Project {
         Project {
             id: project.id.to_string(),
             organization_id: project.organization_id.to_string(),
+            internal: project.visibility.is_internal(),
         }
     }
 }
@@ -0,0 +1,114 @@
+use bencher_json::DateTime;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    context::DbConnection,
+    error::{resource_conflict_err, resource_not_found_err},
+    model::project::ProjectId,
+    schema::{self, perf_embed_revocation as perf_embed_revocation_table},
+};
+
+/// The time at which all perf embed tokens for a project were last revoked
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = perf_embed_revocation_table)]
+pub struct QueryPerfEmbedRevocation {
+    pub project_id: ProjectId,
+    pub revoked: DateTime,
+}
+
+impl QueryPerfEmbedRevocation {
+    /// Check if a perf embed token for the project that was issued at the given time has been revoked
+    pub fn is_revoked(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        issued_at: DateTime,
+    ) -> Result<bool, HttpError> {
+        diesel::select(diesel::dsl::exists(
+            schema::perf_embed_revocation::table
+                .filter(schema::perf_embed_revocation::project_id.eq(project_id))
+                .filter(schema::perf_embed_revocation::revoked.ge(issued_at)),
+        ))
+        .get_result(conn)
+        .map_err(resource_not_found_err!(PerfEmbed, (project_id, issued_at)))
+    }
+}
+
+#[derive(Debug, Clone, diesel::Insertable)]
+#[diesel(table_name = perf_embed_revocation_table)]
+pub struct InsertPerfEmbedRevocation {
+    pub project_id: ProjectId,
+    pub revoked: DateTime,
+}
+
+impl InsertPerfEmbedRevocation {
+    pub fn new(project_id: ProjectId) -> Self {
+        Self {
+            project_id,
+            revoked: DateTime::now(),
+        }
+    }
+
+    /// Revoke all perf embed tokens for the project that have been issued up until now
+    pub fn revoke_all(&self, conn: &mut DbConnection) -> Result<(), HttpError> {
+        let updated = diesel::update(
+            schema::perf_embed_revocation::table
+                .filter(schema::perf_embed_revocation::project_id.eq(self.project_id)),
+        )
+        .set(schema::perf_embed_revocation::revoked.eq(self.revoked))
+        .execute(conn)
+        .map_err(resource_conflict_err!(PerfEmbed, self))?;
+        if updated > 0 {
+            return Ok(());
+        }
+
+        diesel::insert_into(schema::perf_embed_revocation::table)
+            .values(self)
+            .execute(conn)
+            .map_err(resource_conflict_err!(PerfEmbed, self))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{project::Visibility, DateTime};
+
+    use crate::util::test_util::{create_organization, create_project, setup_db};
+
+    use super::{InsertPerfEmbedRevocation, QueryPerfEmbedRevocation};
+
+    #[test]
+    fn test_revoke_all() {
+        let mut conn = setup_db();
+        let org = create_organization(&mut conn, "Organization");
+        let project = create_project(&mut conn, &org, "Project", Visibility::Private);
+        let other_project = create_project(&mut conn, &org, "Other Project", Visibility::Private);
+
+        let issued_at = DateTime::try_from(1_000).unwrap();
+        assert!(!QueryPerfEmbedRevocation::is_revoked(&mut conn, project.id, issued_at).unwrap());
+
+        InsertPerfEmbedRevocation {
+            project_id: project.id,
+            revoked: DateTime::try_from(2_000).unwrap(),
+        }
+        .revoke_all(&mut conn)
+        .unwrap();
+        assert!(QueryPerfEmbedRevocation::is_revoked(&mut conn, project.id, issued_at).unwrap());
+        assert!(
+            !QueryPerfEmbedRevocation::is_revoked(&mut conn, other_project.id, issued_at).unwrap()
+        );
+        // Tokens issued after the revocation are still valid
+        let reissued_at = DateTime::try_from(3_000).unwrap();
+        assert!(!QueryPerfEmbedRevocation::is_revoked(&mut conn, project.id, reissued_at).unwrap());
+
+        // Revoking again moves the revocation time forward
+        InsertPerfEmbedRevocation {
+            project_id: project.id,
+            revoked: DateTime::try_from(4_000).unwrap(),
+        }
+        .revoke_all(&mut conn)
+        .unwrap();
+        assert!(QueryPerfEmbedRevocation::is_revoked(&mut conn, project.id, reissued_at).unwrap());
+    }
+}
@@ -1,11 +1,18 @@
 use bencher_json::{
     project::{ProjectPermission, ProjectRole},
-    DateTime,
+    DateTime, Jwt,
 };
+use bencher_token::TokenKey;
+use dropshot::HttpError;
 
-use crate::{model::user::UserId, schema::project_role as project_role_table};
+use crate::{
+    context::DbConnection,
+    error::unauthorized_error,
+    model::user::{QueryUser, UserId},
+    schema::project_role as project_role_table,
+};
 
-use super::ProjectId;
+use super::{ProjectId, QueryProject};
 
 crate::util::typed_id::typed_id!(ProjectRoleId);
 
@@ -29,8 +36,41 @@ pub struct InsertProjectRole {
     pub modified: DateTime,
 }
 
+impl InsertProjectRole {
+    pub fn from_jwt(
+        conn: &mut DbConnection,
+        token_key: &TokenKey,
+        invite: &Jwt,
+        user_id: UserId,
+    ) -> Result<Self, HttpError> {
+        // Validate the project invite JWT
+        let claims = token_key
+            .validate_project_invite(invite)
+            .map_err(unauthorized_error)?;
+
+        let email = claims.email();
+        // Make sure the email in the invite is the same as the email associated with the user
+        let email_user_id = QueryUser::get_id_from_email(conn, email)?;
+        if user_id != email_user_id {
+            return Err(unauthorized_error(
+               format!("Invitation email ({email}) is connected to user {email_user_id} which doesn't match {user_id}")
+            ));
+        }
+
+        let timestamp = DateTime::now();
+        Ok(InsertProjectRole {
+            user_id,
+            project_id: QueryProject::get_id(conn, claims.project)?,
+            role: claims.role,
+            created: timestamp,
+            modified: timestamp,
+        })
+    }
+}
+
 pub enum Permission {
     View,
+    Report,
     Create,
     Edit,
     Delete,
@@ -45,6 +85,7 @@ impl From<ProjectPermission> for Permission {
     fn from(permission: ProjectPermission) -> Self {
         match permission {
             ProjectPermission::View => Self::View,
+            ProjectPermission::Report => Self::Report,
             ProjectPermission::Create => Self::Create,
             ProjectPermission::Edit => Self::Edit,
             ProjectPermission::Delete => Self::Delete,
@@ -61,6 +102,7 @@ impl From<Permission> for bencher_rbac::project::Permission {
     fn from(permission: Permission) -> Self {
         match permission {
             Permission::View => Self::View,
+            Permission::Report => Self::Report,
             Permission::Create => Self::Create,
             Permission::Edit => Self::Edit,
             Permission::Delete => Self::Delete,
@@ -0,0 +1,111 @@
+use bencher_json::project::report::JsonReportFailure;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    context::DbConnection,
+    error::{resource_conflict_err, resource_not_found_err},
+    schema::{self, report_failure as report_failure_table},
+};
+
+use super::ReportId;
+
+/// The most failed iterations that may be attached to a single report
+pub const MAX_REPORT_FAILURES: usize = 64;
+
+#[derive(Debug, diesel::Queryable)]
+pub struct QueryReportFailure {
+    pub iteration: i32,
+    pub exit_code: i32,
+    pub wall_time: f64,
+    pub stderr: Option<String>,
+}
+
+impl QueryReportFailure {
+    fn into_json(self) -> JsonReportFailure {
+        let Self {
+            iteration,
+            exit_code,
+            wall_time,
+            stderr,
+        } = self;
+        JsonReportFailure {
+            iteration: u32::try_from(iteration).unwrap_or_default(),
+            exit_code,
+            wall_time,
+            stderr,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = report_failure_table)]
+pub struct InsertReportFailure {
+    pub report_id: ReportId,
+    pub iteration: i32,
+    pub exit_code: i32,
+    pub wall_time: f64,
+    pub stderr: Option<String>,
+}
+
+impl InsertReportFailure {
+    fn from_json(report_id: ReportId, failure: &JsonReportFailure) -> Self {
+        let JsonReportFailure {
+            iteration,
+            exit_code,
+            wall_time,
+            stderr,
+        } = failure;
+        Self {
+            report_id,
+            iteration: i32::try_from(*iteration).unwrap_or(i32::MAX),
+            exit_code: *exit_code,
+            wall_time: *wall_time,
+            stderr: stderr.clone(),
+        }
+    }
+}
+
+/// Attach the diagnostics for failed benchmark iterations to the report.
+pub fn insert_report_failures(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+    failures: &[JsonReportFailure],
+) -> Result<(), HttpError> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let insert_failures = failures
+        .iter()
+        .map(|failure| InsertReportFailure::from_json(report_id, failure))
+        .collect::<Vec<_>>();
+    diesel::insert_into(schema::report_failure::table)
+        .values(&insert_failures)
+        .execute(conn)
+        .map_err(resource_conflict_err!(ReportFailure, insert_failures))?;
+    Ok(())
+}
+
+/// Get the diagnostics for failed benchmark iterations attached to the report, in the order they were given.
+pub fn get_report_failures(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+) -> Result<Option<Vec<JsonReportFailure>>, HttpError> {
+    let failures = schema::report_failure::table
+        .filter(schema::report_failure::report_id.eq(report_id))
+        .order(schema::report_failure::id.asc())
+        .select((
+            schema::report_failure::iteration,
+            schema::report_failure::exit_code,
+            schema::report_failure::wall_time,
+            schema::report_failure::stderr,
+        ))
+        .load::<QueryReportFailure>(conn)
+        .map_err(resource_not_found_err!(ReportFailure, report_id))?;
+    Ok((!failures.is_empty()).then(|| {
+        failures
+            .into_iter()
+            .map(QueryReportFailure::into_json)
+            .collect()
+    }))
+}
@@ -1,9 +1,9 @@
 use bencher_json::{
     project::report::{
-        Adapter, Iteration, JsonReportAlerts, JsonReportMeasure, JsonReportResult,
-        JsonReportResults,
+        Adapter, Iteration, JsonReportAlerts, JsonReportFingerprint, JsonReportGit,
+        JsonReportMeasure, JsonReportResult, JsonReportResults, JsonReportWarmup,
     },
-    DateTime, JsonNewReport, JsonReport, ReportUuid,
+    DateTime, JsonNewReport, JsonReport, NonEmpty, ReportUuid,
 };
 use diesel::{
     ExpressionMethods, NullableExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper,
@@ -18,6 +18,7 @@ use crate::{
     model::{
         project::{
             benchmark::QueryBenchmark,
+            flaky::FlakyMeasures,
             measure::QueryMeasure,
             testbed::{QueryTestbed, TestbedId},
             threshold::{alert::QueryAlert, model::QueryModel, QueryThreshold},
@@ -37,8 +38,12 @@ use super::{
     threshold::boundary::QueryBoundary,
 };
 
+pub mod failure;
+pub mod note;
+pub mod raw;
 pub mod report_benchmark;
 pub mod results;
+pub mod shard;
 
 crate::util::typed_id::typed_id!(ReportId);
 
@@ -48,7 +53,7 @@ crate::util::typed_id::typed_id!(ReportId);
 pub struct QueryReport {
     pub id: ReportId,
     pub uuid: ReportUuid,
-    pub user_id: UserId,
+    pub user_id: Option<UserId>,
     pub project_id: ProjectId,
     pub head_id: HeadId,
     pub version_id: VersionId,
@@ -56,6 +61,19 @@ pub struct QueryReport {
     pub adapter: Adapter,
     pub start_time: DateTime,
     pub end_time: DateTime,
+    pub tag: Option<NonEmpty>,
+    pub warmup_iterations: Option<i32>,
+    pub warmup_wall_time: Option<f64>,
+    pub git_branch: Option<NonEmpty>,
+    pub git_tag: Option<NonEmpty>,
+    pub git_dirty: Option<bool>,
+    pub git_author: Option<NonEmpty>,
+    pub git_commit_time: Option<DateTime>,
+    pub git_subject: Option<NonEmpty>,
+    pub fingerprint_cpu_model: Option<NonEmpty>,
+    pub fingerprint_governor: Option<NonEmpty>,
+    pub fingerprint_virtualized: Option<bool>,
+    pub fingerprint_hypervisor: Option<NonEmpty>,
     pub created: DateTime,
 }
 
@@ -79,11 +97,28 @@ impl QueryReport {
             adapter,
             start_time,
             end_time,
+            tag,
+            warmup_iterations,
+            warmup_wall_time,
+            git_branch,
+            git_tag,
+            git_dirty,
+            git_author,
+            git_commit_time,
+            git_subject,
+            fingerprint_cpu_model,
+            fingerprint_governor,
+            fingerprint_virtualized,
+            fingerprint_hypervisor,
             created,
         } = self;
 
         let query_project = QueryProject::get(conn_lock!(context), project_id)?;
-        let user = QueryUser::get(conn_lock!(context), user_id)?.into_pub_json();
+        let user = if let Some(user_id) = user_id {
+            Some(QueryUser::get(conn_lock!(context), user_id)?.into_pub_json())
+        } else {
+            None
+        };
         let branch =
             QueryBranch::get_json_for_report(context, &query_project, head_id, version_id).await?;
         let testbed = QueryTestbed::get(conn_lock!(context), testbed_id)?
@@ -91,6 +126,26 @@ impl QueryReport {
         let results = get_report_results(log, context, &query_project, id).await?;
         let alerts = get_report_alerts(context, &query_project, id, head_id, version_id).await?;
 
+        let warmup = warmup_into_json(warmup_iterations, warmup_wall_time);
+        let git = Some(JsonReportGit {
+            branch: git_branch,
+            tag: git_tag,
+            dirty: git_dirty,
+            author: git_author,
+            commit_time: git_commit_time,
+            subject: git_subject,
+        })
+        .filter(|git| !git.is_empty());
+        let fingerprint = Some(JsonReportFingerprint {
+            cpu_model: fingerprint_cpu_model,
+            governor: fingerprint_governor,
+            virtualized: fingerprint_virtualized,
+            hypervisor: fingerprint_hypervisor,
+        })
+        .filter(|fingerprint| !fingerprint.is_empty());
+        let (notes, links) = note::get_report_notes(conn_lock!(context), id)?;
+        let failures = failure::get_report_failures(conn_lock!(context), id)?;
+
         let project = query_project.into_json(conn_lock!(context))?;
         Ok(JsonReport {
             uuid,
@@ -100,6 +155,13 @@ impl QueryReport {
             testbed,
             start_time,
             end_time,
+            tag,
+            warmup,
+            git,
+            fingerprint,
+            notes,
+            links,
+            failures,
             adapter,
             results,
             alerts,
@@ -108,6 +170,18 @@ impl QueryReport {
     }
 }
 
+fn warmup_into_json(
+    warmup_iterations: Option<i32>,
+    warmup_wall_time: Option<f64>,
+) -> Option<JsonReportWarmup> {
+    let iterations = u32::try_from(warmup_iterations?).ok()?;
+    let wall_time = warmup_wall_time?;
+    Some(JsonReportWarmup {
+        iterations,
+        wall_time,
+    })
+}
+
 type ResultsQuery = (
     Iteration,
     QueryBenchmark,
@@ -122,6 +196,7 @@ async fn get_report_results(
     project: &QueryProject,
     report_id: ReportId,
 ) -> Result<JsonReportResults, HttpError> {
+    let flaky_measures = FlakyMeasures::for_project(conn_lock!(context), project.id)?;
     schema::report_benchmark::table
     .filter(schema::report_benchmark::report_id.eq(report_id))
     .inner_join(schema::benchmark::table)
@@ -161,19 +236,21 @@ async fn get_report_results(
                 schema::model::window,
                 schema::model::lower_boundary,
                 schema::model::upper_boundary,
+                schema::model::decay,
                 schema::model::created,
                 schema::model::replaced,
             )
         ).nullable(),
     ))
     .load::<ResultsQuery>(conn_lock!(context))
-    .map(|results| into_report_results_json(log, project, results))
+    .map(|results| into_report_results_json(log, project, &flaky_measures, results))
     .map_err(resource_not_found_err!(ReportBenchmark, project))
 }
 
 fn into_report_results_json(
     log: &Logger,
     project: &QueryProject,
+    flaky_measures: &FlakyMeasures,
     results: Vec<ResultsQuery>,
 ) -> JsonReportResults {
     let mut report_results = Vec::new();
@@ -215,6 +292,7 @@ fn into_report_results_json(
         }
 
         let (query_metric, query_boundary) = query_metric_boundary.split();
+        let flaky = flaky_measures.detected(query_benchmark.id, query_measure.id);
         let report_measure = JsonReportMeasure {
             measure: query_measure.into_json_for_project(project),
             metric: query_metric.into_json(),
@@ -222,6 +300,7 @@ fn into_report_results_json(
                 threshold.into_threshold_model_json_for_project(project, model)
             }),
             boundary: query_boundary.map(QueryBoundary::into_json),
+            flaky,
         };
 
         // If there is a current report result, add the report measure to it.
@@ -323,7 +402,7 @@ async fn get_report_alerts(
 #[diesel(table_name = report_table)]
 pub struct InsertReport {
     pub uuid: ReportUuid,
-    pub user_id: UserId,
+    pub user_id: Option<UserId>,
     pub project_id: ProjectId,
     pub head_id: HeadId,
     pub version_id: VersionId,
@@ -331,12 +410,25 @@ pub struct InsertReport {
     pub adapter: Adapter,
     pub start_time: DateTime,
     pub end_time: DateTime,
+    pub tag: Option<NonEmpty>,
+    pub warmup_iterations: Option<i32>,
+    pub warmup_wall_time: Option<f64>,
+    pub git_branch: Option<NonEmpty>,
+    pub git_tag: Option<NonEmpty>,
+    pub git_dirty: Option<bool>,
+    pub git_author: Option<NonEmpty>,
+    pub git_commit_time: Option<DateTime>,
+    pub git_subject: Option<NonEmpty>,
+    pub fingerprint_cpu_model: Option<NonEmpty>,
+    pub fingerprint_governor: Option<NonEmpty>,
+    pub fingerprint_virtualized: Option<bool>,
+    pub fingerprint_hypervisor: Option<NonEmpty>,
     pub created: DateTime,
 }
 
 impl InsertReport {
     pub fn from_json(
-        user_id: UserId,
+        user_id: Option<UserId>,
         project_id: ProjectId,
         head_id: HeadId,
         version_id: VersionId,
@@ -344,6 +436,29 @@ impl InsertReport {
         report: &JsonNewReport,
         adapter: Adapter,
     ) -> Self {
+        let (warmup_iterations, warmup_wall_time) = report
+            .warmup
+            .map(|warmup| {
+                (
+                    Some(i32::try_from(warmup.iterations).unwrap_or(i32::MAX)),
+                    Some(warmup.wall_time),
+                )
+            })
+            .unwrap_or_default();
+        let JsonReportGit {
+            branch: git_branch,
+            tag: git_tag,
+            dirty: git_dirty,
+            author: git_author,
+            commit_time: git_commit_time,
+            subject: git_subject,
+        } = report.git.clone().unwrap_or_default();
+        let JsonReportFingerprint {
+            cpu_model: fingerprint_cpu_model,
+            governor: fingerprint_governor,
+            virtualized: fingerprint_virtualized,
+            hypervisor: fingerprint_hypervisor,
+        } = report.fingerprint.clone().unwrap_or_default();
         Self {
             uuid: ReportUuid::new(),
             user_id,
@@ -354,6 +469,19 @@ impl InsertReport {
             adapter,
             start_time: report.start_time,
             end_time: report.end_time,
+            tag: report.tag.clone(),
+            warmup_iterations,
+            warmup_wall_time,
+            git_branch,
+            git_tag,
+            git_dirty,
+            git_author,
+            git_commit_time,
+            git_subject,
+            fingerprint_cpu_model,
+            fingerprint_governor,
+            fingerprint_virtualized,
+            fingerprint_hypervisor,
             created: DateTime::now(),
         }
     }
@@ -0,0 +1,91 @@
+use bencher_json::{NonEmpty, Url};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    context::DbConnection,
+    error::{resource_conflict_err, resource_not_found_err},
+    schema::{self, report_link as report_link_table, report_note as report_note_table},
+};
+
+use super::ReportId;
+
+/// The most notes or links that may be attached to a single report
+pub const MAX_REPORT_NOTES: usize = 32;
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = report_note_table)]
+pub struct InsertReportNote {
+    pub report_id: ReportId,
+    pub note: NonEmpty,
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = report_link_table)]
+pub struct InsertReportLink {
+    pub report_id: ReportId,
+    pub url: Url,
+}
+
+/// Attach the free-form notes and links to the report, in order.
+pub fn insert_report_notes(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+    notes: &[NonEmpty],
+    links: &[Url],
+) -> Result<(), HttpError> {
+    let insert_notes = notes
+        .iter()
+        .map(|note| InsertReportNote {
+            report_id,
+            note: note.clone(),
+        })
+        .collect::<Vec<_>>();
+    if !insert_notes.is_empty() {
+        diesel::insert_into(schema::report_note::table)
+            .values(&insert_notes)
+            .execute(conn)
+            .map_err(resource_conflict_err!(ReportNote, insert_notes))?;
+    }
+
+    let insert_links = links
+        .iter()
+        .map(|url| InsertReportLink {
+            report_id,
+            url: url.clone(),
+        })
+        .collect::<Vec<_>>();
+    if !insert_links.is_empty() {
+        diesel::insert_into(schema::report_link::table)
+            .values(&insert_links)
+            .execute(conn)
+            .map_err(resource_conflict_err!(ReportLink, insert_links))?;
+    }
+
+    Ok(())
+}
+
+type ReportNotes = (Option<Vec<NonEmpty>>, Option<Vec<Url>>);
+
+/// Get the notes and links attached to the report, in the order they were given.
+pub fn get_report_notes(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+) -> Result<ReportNotes, HttpError> {
+    let notes = schema::report_note::table
+        .filter(schema::report_note::report_id.eq(report_id))
+        .order(schema::report_note::id.asc())
+        .select(schema::report_note::note)
+        .load::<NonEmpty>(conn)
+        .map_err(resource_not_found_err!(ReportNote, report_id))?;
+    let links = schema::report_link::table
+        .filter(schema::report_link::report_id.eq(report_id))
+        .order(schema::report_link::id.asc())
+        .select(schema::report_link::url)
+        .load::<Url>(conn)
+        .map_err(resource_not_found_err!(ReportLink, report_id))?;
+    Ok((
+        (!notes.is_empty()).then_some(notes),
+        (!links.is_empty()).then_some(links),
+    ))
+}
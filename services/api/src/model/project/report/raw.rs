@@ -0,0 +1,129 @@
+use bencher_json::project::report::JsonReportSettings;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use http::StatusCode;
+
+use crate::{
+    context::DbConnection,
+    error::{issue_error, resource_conflict_err, resource_not_found_err},
+    model::project::branch::{
+        head::{HeadId, QueryHead},
+        QueryBranch,
+    },
+    schema::{self, report_raw as report_raw_table},
+};
+
+use super::{results::detector::ReferenceBranch, ReportId};
+
+/// The raw benchmark results for a report, so it can be reparsed with the current adapters.
+/// The report itself is first, followed by any shards.
+#[derive(Debug)]
+pub struct ReportRaw {
+    pub results: Vec<String>,
+    pub settings: JsonReportSettings,
+}
+
+#[derive(Debug, diesel::Queryable)]
+struct QueryReportRaw {
+    settings: String,
+    results: String,
+    reference_head_id: Option<HeadId>,
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = report_raw_table)]
+pub struct InsertReportRaw {
+    pub report_id: ReportId,
+    pub position: i32,
+    pub settings: String,
+    pub results: String,
+    pub reference_head_id: Option<HeadId>,
+}
+
+/// Store the raw results for the report.
+pub fn insert_report_raw(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+    reference_branch: Option<ReferenceBranch>,
+    raw_results: &[ReportRaw],
+) -> Result<(), HttpError> {
+    let insert_raw_results = raw_results
+        .iter()
+        .enumerate()
+        .map(|(position, raw)| {
+            Ok(InsertReportRaw {
+                report_id,
+                position: i32::try_from(position).unwrap_or(i32::MAX),
+                settings: serde_json::to_string(&raw.settings)?,
+                results: serde_json::to_string(&raw.results)?,
+                reference_head_id: reference_branch
+                    .map(|reference_branch| reference_branch.head_id),
+            })
+        })
+        .collect::<Result<Vec<_>, serde_json::Error>>()
+        .map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to serialize raw report results",
+                &format!("Failed to serialize raw results for report ({report_id})"),
+                e,
+            )
+        })?;
+    diesel::insert_into(schema::report_raw::table)
+        .values(&insert_raw_results)
+        .execute(conn)
+        .map_err(resource_conflict_err!(ReportRaw, report_id))?;
+    Ok(())
+}
+
+/// Get the raw results and the reference branch for the report.
+/// If the report was created before raw results were stored, then there are no raw results.
+pub fn get_report_raw(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+) -> Result<(Vec<ReportRaw>, Option<ReferenceBranch>), HttpError> {
+    let query_raw_results = schema::report_raw::table
+        .filter(schema::report_raw::report_id.eq(report_id))
+        .order(schema::report_raw::position.asc())
+        .select((
+            schema::report_raw::settings,
+            schema::report_raw::results,
+            schema::report_raw::reference_head_id,
+        ))
+        .load::<QueryReportRaw>(conn)
+        .map_err(resource_not_found_err!(ReportRaw, report_id))?;
+
+    let reference_head_id = query_raw_results
+        .first()
+        .and_then(|raw| raw.reference_head_id);
+    let reference_branch = if let Some(head_id) = reference_head_id {
+        let query_head = QueryHead::get(conn, head_id)?;
+        Some(ReferenceBranch {
+            branch_id: query_head.branch_id,
+            head_id,
+            frozen_baseline: QueryBranch::frozen_baseline(conn, query_head.branch_id)?,
+        })
+    } else {
+        None
+    };
+
+    let raw_results = query_raw_results
+        .into_iter()
+        .map(|raw| {
+            Ok(ReportRaw {
+                settings: serde_json::from_str(&raw.settings)?,
+                results: serde_json::from_str(&raw.results)?,
+            })
+        })
+        .collect::<Result<Vec<_>, serde_json::Error>>()
+        .map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to parse raw report results",
+                &format!("Failed to parse raw results for report ({report_id})"),
+                e,
+            )
+        })?;
+
+    Ok((raw_results, reference_branch))
+}
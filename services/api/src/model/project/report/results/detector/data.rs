@@ -1,4 +1,5 @@
 use bencher_boundary::MetricsData;
+use bencher_json::DateTime;
 use chrono::offset::Utc;
 use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl};
 use dropshot::HttpError;
@@ -15,6 +16,7 @@ use crate::{
 
 use super::threshold::ThresholdModel;
 
+#[allow(clippy::too_many_arguments)]
 pub fn metrics_data(
     log: &Logger,
     conn: &mut DbConnection,
@@ -23,6 +25,7 @@ pub fn metrics_data(
     benchmark_id: BenchmarkId,
     measure_id: MeasureId,
     model: &ThresholdModel,
+    as_of: Option<DateTime>,
 ) -> Result<MetricsData, HttpError> {
     let mut query = schema::metric::table
         .inner_join(
@@ -47,8 +50,14 @@ pub fn metrics_data(
         .filter(schema::metric::measure_id.eq(measure_id))
         .into_boxed();
 
+    // When recomputing a past boundary or using a frozen baseline,
+    // only use the metrics that were available at the time
+    if let Some(as_of) = as_of {
+        query = query.filter(schema::report::start_time.le(as_of));
+    }
+
     if let Some(window) = model.window {
-        let now = Utc::now().timestamp();
+        let now = as_of.map_or_else(|| Utc::now().timestamp(), |as_of| as_of.timestamp());
         if let Some(start_time) = now.checked_sub(window.into()) {
             query = query.filter(schema::report::start_time.ge(start_time));
         } else {
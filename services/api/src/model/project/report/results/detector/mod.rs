@@ -1,5 +1,5 @@
 use bencher_boundary::MetricsBoundary;
-use bencher_json::BoundaryUuid;
+use bencher_json::{BoundaryUuid, DateTime, NameId};
 use diesel::RunQueryDsl;
 use dropshot::HttpError;
 use slog::Logger;
@@ -10,11 +10,15 @@ use crate::{
     error::{bad_request_error, resource_conflict_err},
     model::project::{
         benchmark::BenchmarkId,
-        branch::{head::HeadId, BranchId},
+        branch::{head::HeadId, BranchId, QueryBranch},
         measure::MeasureId,
         metric::QueryMetric,
         testbed::TestbedId,
-        threshold::{alert::InsertAlert, boundary::InsertBoundary},
+        threshold::{
+            alert::{AlertId, InsertAlert},
+            boundary::InsertBoundary,
+        },
+        ProjectId,
     },
     schema,
 };
@@ -25,12 +29,39 @@ pub mod threshold;
 use data::metrics_data;
 use threshold::Threshold;
 
+/// A branch to compare against instead of the report branch.
+/// The current head of the reference branch is used,
+/// so the baseline moves along with the reference branch.
+#[derive(Debug, Clone, Copy)]
+pub struct ReferenceBranch {
+    pub branch_id: BranchId,
+    pub head_id: HeadId,
+    pub frozen_baseline: Option<DateTime>,
+}
+
+impl ReferenceBranch {
+    pub fn from_name_id(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        reference_branch: &NameId,
+    ) -> Result<Self, HttpError> {
+        let query_branch = QueryBranch::from_name_id(conn, project_id, reference_branch)?;
+        Ok(Self {
+            branch_id: query_branch.id,
+            head_id: query_branch.head_id()?,
+            frozen_baseline: query_branch.frozen_baseline,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Detector {
     pub head_id: HeadId,
     pub testbed_id: TestbedId,
     pub measure_id: MeasureId,
     pub threshold: Threshold,
+    /// If the baseline branch is frozen, then only the metrics from before it was frozen are used
+    pub frozen_baseline: Option<DateTime>,
 }
 
 impl Detector {
@@ -38,27 +69,46 @@ impl Detector {
         conn: &mut DbConnection,
         branch_id: BranchId,
         head_id: HeadId,
+        reference_branch: Option<ReferenceBranch>,
         testbed_id: TestbedId,
         measure_id: MeasureId,
     ) -> Option<Self> {
         // Check to see if there is a threshold for the branch/testbed/measure grouping.
-        // If not, then there will be nothing to detect.
-        Threshold::new(conn, branch_id, testbed_id, measure_id).map(|threshold| Self {
+        // If not, then fall back to the reference branch threshold, if there is a reference branch.
+        // If there is still no threshold, then there will be nothing to detect.
+        let threshold = Threshold::new(conn, branch_id, testbed_id, measure_id).or_else(|| {
+            reference_branch.and_then(|reference_branch| {
+                Threshold::new(conn, reference_branch.branch_id, testbed_id, measure_id)
+            })
+        })?;
+        // The historical metrics for the baseline come from the reference branch, if there is one.
+        let (head_id, frozen_baseline) = if let Some(reference_branch) = reference_branch {
+            (reference_branch.head_id, reference_branch.frozen_baseline)
+        } else {
+            (
+                head_id,
+                QueryBranch::frozen_baseline(conn, branch_id).ok().flatten(),
+            )
+        };
+        Some(Self {
             head_id,
             testbed_id,
             measure_id,
             threshold,
+            frozen_baseline,
         })
     }
 
+    /// Check the metric against the threshold and create an alert if it is an outlier.
+    /// Returns the new alert, if one was created.
     pub async fn detect(
         &self,
         log: &Logger,
         context: &ApiContext,
         benchmark_id: BenchmarkId,
         query_metric: &QueryMetric,
-        ignore_benchmark: bool,
-    ) -> Result<(), HttpError> {
+        suppress_alerts: bool,
+    ) -> Result<Option<AlertId>, HttpError> {
         // Query the historical population/sample data for the benchmark
         let metrics_data = metrics_data(
             log,
@@ -68,6 +118,7 @@ impl Detector {
             benchmark_id,
             self.measure_id,
             &self.threshold.model,
+            self.frozen_baseline,
         )?;
 
         // Check to see if the metric has a boundary check for the given threshold model.
@@ -76,9 +127,13 @@ impl Detector {
             query_metric.value,
             &metrics_data,
             self.threshold.model.test,
-            self.threshold.model.min_sample_size,
+            context.statistics.min_sample_size(
+                self.threshold.model.test,
+                self.threshold.model.min_sample_size,
+            ),
             self.threshold.model.lower_boundary,
             self.threshold.model.upper_boundary,
+            self.threshold.model.decay,
         )
         .map_err(bad_request_error)?;
 
@@ -99,13 +154,19 @@ impl Detector {
             .map_err(resource_conflict_err!(Boundary, insert_boundary))?;
 
         // If the boundary check detects an outlier then create an alert for it on the given side.
-        // As long as the benchmark is not being ignored.
-        if ignore_benchmark {
-            Ok(())
+        // As long as the alerts for the benchmark are not being suppressed.
+        if suppress_alerts {
+            Ok(None)
         } else if let Some(boundary_limit) = boundary.outlier {
-            InsertAlert::from_boundary(conn_lock!(context), boundary_uuid, boundary_limit)
+            // The severity of the alert is based on how far the metric exceeded the boundary limit.
+            let exceedance = boundary
+                .limits
+                .exceedance(query_metric.value, boundary_limit);
+            let severity = context.severity.severity(exceedance);
+            InsertAlert::from_boundary(conn_lock!(context), boundary_uuid, boundary_limit, severity)
+                .map(Some)
         } else {
-            Ok(())
+            Ok(None)
         }
     }
 }
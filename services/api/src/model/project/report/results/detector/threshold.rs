@@ -1,4 +1,4 @@
-use bencher_json::{Boundary, ModelTest, SampleSize, Window};
+use bencher_json::{Boundary, Decay, ModelTest, SampleSize, Window};
 use diesel::{
     ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl, RunQueryDsl,
     SelectableHelper,
@@ -33,6 +33,7 @@ pub struct ThresholdModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub decay: Option<Decay>,
 }
 
 impl Threshold {
@@ -52,31 +53,36 @@ impl Threshold {
             .filter(schema::threshold::measure_id.eq(measure_id))
             .select((schema::threshold::id, QueryModel::as_select()))
             .first::<(ThresholdId, QueryModel)>(conn)
-            .map(|(threshold_id, query_model)| {
-                let QueryModel {
-                    id,
-                    test,
-                    min_sample_size,
-                    max_sample_size,
-                    window,
-                    lower_boundary,
-                    upper_boundary,
-                    ..
-                } = query_model;
-                let model = ThresholdModel {
-                    id,
-                    test,
-                    min_sample_size,
-                    max_sample_size,
-                    window,
-                    lower_boundary,
-                    upper_boundary,
-                };
-                Self {
-                    id: threshold_id,
-                    model,
-                }
+            .map(|(threshold_id, query_model)| Self {
+                id: threshold_id,
+                model: query_model.into(),
             })
             .ok()
     }
 }
+
+impl From<QueryModel> for ThresholdModel {
+    fn from(query_model: QueryModel) -> Self {
+        let QueryModel {
+            id,
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            decay,
+            ..
+        } = query_model;
+        Self {
+            id,
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            decay,
+        }
+    }
+}
@@ -1,12 +1,15 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap},
+    iter,
+};
 
 use bencher_adapter::{
     results::adapter_metrics::AdapterMetrics, AdapterResults, AdapterResultsArray,
     Settings as AdapterSettings,
 };
 use bencher_json::{
-    project::report::{Adapter, Iteration, JsonReportSettings},
-    BenchmarkName, MeasureNameId,
+    project::report::{Adapter, Iteration, JsonDispersion, JsonReportSettings},
+    BenchmarkName, MeasureNameId, NonEmpty,
 };
 use diesel::RunQueryDsl;
 use dropshot::HttpError;
@@ -15,15 +18,20 @@ use slog::Logger;
 
 use crate::{
     conn_lock,
-    context::ApiContext,
+    context::{ApiContext, DbConnection},
     error::{bad_request_error, issue_error, resource_conflict_err},
     model::project::{
         benchmark::{BenchmarkId, QueryBenchmark},
         branch::{head::HeadId, BranchId},
+        expectation::Expectations,
+        flaky::FlakyMeasures,
         measure::{MeasureId, QueryMeasure},
         metric::{InsertMetric, QueryMetric},
         report::report_benchmark::{InsertReportBenchmark, QueryReportBenchmark},
+        report_hook::ReportHook,
+        rule::{BenchmarkRules, RuleCollision},
         testbed::TestbedId,
+        threshold::alert::InsertAlertGroup,
         ProjectId,
     },
     schema,
@@ -31,7 +39,7 @@ use crate::{
 
 pub mod detector;
 
-use detector::Detector;
+use detector::{Detector, ReferenceBranch};
 
 use super::ReportId;
 
@@ -40,27 +48,45 @@ pub struct ReportResults {
     pub project_id: ProjectId,
     pub branch_id: BranchId,
     pub head_id: HeadId,
+    pub reference_branch: Option<ReferenceBranch>,
     pub testbed_id: TestbedId,
     pub report_id: ReportId,
+    pub benchmark_rules: BenchmarkRules,
+    pub rule_collisions: BTreeSet<RuleCollision>,
+    pub expectations: Expectations,
+    pub flaky_measures: FlakyMeasures,
+    pub report_hook: Option<ReportHook>,
     pub benchmark_cache: HashMap<BenchmarkName, BenchmarkId>,
     pub measure_cache: HashMap<MeasureNameId, MeasureId>,
     pub detector_cache: HashMap<MeasureId, Option<Detector>>,
 }
 
 impl ReportResults {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         project_id: ProjectId,
         branch_id: BranchId,
         head_id: HeadId,
+        reference_branch: Option<ReferenceBranch>,
         testbed_id: TestbedId,
         report_id: ReportId,
+        benchmark_rules: BenchmarkRules,
+        expectations: Expectations,
+        flaky_measures: FlakyMeasures,
+        report_hook: Option<ReportHook>,
     ) -> Self {
         Self {
             project_id,
             branch_id,
             head_id,
+            reference_branch,
             testbed_id,
             report_id,
+            benchmark_rules,
+            rule_collisions: BTreeSet::new(),
+            expectations,
+            flaky_measures,
+            report_hook,
             benchmark_cache: HashMap::new(),
             measure_cache: HashMap::new(),
             detector_cache: HashMap::new(),
@@ -84,30 +110,53 @@ impl ReportResults {
                 ))
             })?;
 
-        if let Some(fold) = settings.fold {
-            let results = results_array.fold(fold);
+        let iterations = match (settings.fold, settings.dispersion) {
+            (Some(_), Some(_)) => {
+                return Err(bad_request_error(format!(
+                    "A report can not have both a fold and a dispersion ({settings:?})"
+                )));
+            },
+            (Some(fold), None) => vec![(
+                Iteration::default(),
+                IterationResults::Checked(results_array.fold(fold)),
+            )],
+            (None, Some(dispersion)) => {
+                let summary = IterationResults::Summary {
+                    central_tendency: results_array.clone().fold(dispersion.central_tendency()),
+                    dispersion,
+                    dispersion_results: results_array.clone().dispersion(dispersion),
+                };
+                // The summary is added as the iteration after all of the results
+                let summary_iteration: Iteration = results_array.inner.len().into();
+                results_array
+                    .inner
+                    .into_iter()
+                    .enumerate()
+                    .map(|(iteration, results)| {
+                        (iteration.into(), IterationResults::Unchecked(results))
+                    })
+                    .chain(iter::once((summary_iteration, summary)))
+                    .collect()
+            },
+            (None, None) => results_array
+                .inner
+                .into_iter()
+                .enumerate()
+                .map(|(iteration, results)| (iteration.into(), IterationResults::Checked(results)))
+                .collect(),
+        };
+
+        for (iteration, results) in iterations {
             self.results(
                 log,
                 context,
-                Iteration::default(),
+                iteration,
                 results,
                 #[cfg(feature = "plus")]
                 usage,
             )
             .await?;
-        } else {
-            for (iteration, results) in results_array.inner.into_iter().enumerate() {
-                self.results(
-                    log,
-                    context,
-                    iteration.into(),
-                    results,
-                    #[cfg(feature = "plus")]
-                    usage,
-                )
-                .await?;
-            }
-        };
+        }
 
         Ok(())
     }
@@ -117,15 +166,25 @@ impl ReportResults {
         log: &Logger,
         context: &ApiContext,
         iteration: Iteration,
-        results: AdapterResults,
+        results: IterationResults,
         #[cfg(feature = "plus")] usage: &mut u32,
     ) -> Result<(), HttpError> {
-        for (benchmark_name, metrics) in results.inner {
+        let results = hook(
+            self.report_hook.as_ref(),
+            results.into_stripped_benchmark_metrics(),
+        )?;
+        let rewritten = self.benchmark_rules.apply_all(results)?;
+        for collision in rewritten.collisions {
+            slog::warn!(log, "Skipping benchmark rule collision: {collision:?}");
+            self.rule_collisions.insert(collision);
+        }
+        for (benchmark_name, (ignore_benchmark, metrics)) in rewritten.results {
             self.metrics(
                 log,
                 context,
                 iteration,
-                &benchmark_name,
+                benchmark_name,
+                ignore_benchmark,
                 metrics,
                 #[cfg(feature = "plus")]
                 usage,
@@ -135,17 +194,39 @@ impl ReportResults {
         Ok(())
     }
 
+    /// Get a note for each benchmark that was skipped,
+    /// because it was rewritten by the project rules to the same name as another benchmark.
+    pub fn rule_notes(&self) -> Vec<NonEmpty> {
+        self.rule_collisions
+            .iter()
+            .filter_map(RuleCollision::note)
+            .collect()
+    }
+
+    /// Get the notes from the project report hook, if any,
+    /// given all of the benchmark names that have been processed for the report.
+    pub fn hook_notes(&self) -> Result<Vec<NonEmpty>, HttpError> {
+        let Some(report_hook) = &self.report_hook else {
+            return Ok(Vec::new());
+        };
+        let mut benchmark_names = self.benchmark_cache.keys().cloned().collect::<Vec<_>>();
+        benchmark_names.sort();
+        report_hook.notes(&benchmark_names)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn metrics(
         &mut self,
         log: &Logger,
         context: &ApiContext,
         iteration: Iteration,
-        benchmark_name: &BenchmarkName,
-        metrics: AdapterMetrics,
+        benchmark_name: BenchmarkName,
+        ignore_benchmark: bool,
+        metrics: BenchmarkMetrics,
         #[cfg(feature = "plus")] usage: &mut u32,
     ) -> Result<(), HttpError> {
-        // If benchmark name is ignored then strip the special suffix before querying
-        let (benchmark_name, ignore_benchmark) = benchmark_name.to_strip_ignore();
+        // Benchmarks within an expected regression window still get boundaries, but they do not generate alerts
+        let suppress_alerts = ignore_benchmark || self.expectations.is_expected(&benchmark_name);
         let benchmark_id = self.benchmark_id(context, benchmark_name).await?;
 
         let insert_report_benchmark =
@@ -160,9 +241,32 @@ impl ReportResults {
         let report_benchmark_id =
             QueryReportBenchmark::get_id(conn_lock!(context), insert_report_benchmark.uuid)?;
 
+        let BenchmarkMetrics {
+            metrics,
+            dispersion,
+            check,
+        } = metrics;
+        let mut measure_metrics = Vec::with_capacity(metrics.inner.len());
         for (measure_key, metric) in metrics.inner {
-            let measure_id = self.measure_id(context, measure_key).await?;
+            let measure_id = self.measure_id(context, measure_key.clone()).await?;
+            measure_metrics.push((measure_id, metric));
+            // The dispersion is stored under the original measure key
+            if let Some((dispersion, dispersion_metrics)) = &dispersion {
+                if let Some(dispersion_metric) = dispersion_metrics.inner.get(&measure_key) {
+                    let dispersion_measure_id = QueryMeasure::get_or_create_dispersion(
+                        context,
+                        self.project_id,
+                        measure_id,
+                        *dispersion,
+                    )
+                    .await?;
+                    measure_metrics.push((dispersion_measure_id, *dispersion_metric));
+                }
+            }
+        }
 
+        let mut alerts = Vec::new();
+        for (measure_id, metric) in measure_metrics {
             let insert_metric = InsertMetric::from_json(report_benchmark_id, measure_id, metric);
             diesel::insert_into(schema::metric::table)
                 .values(&insert_metric)
@@ -175,6 +279,9 @@ impl ReportResults {
                 *usage += 1;
             }
 
+            if !check {
+                continue;
+            }
             let Some(detector) = self.detector(context, measure_id).await else {
                 continue;
             };
@@ -186,12 +293,22 @@ impl ReportResults {
                         e,
                     )
                 })?;
-            detector
-                .detect(log, context, benchmark_id, &query_metric, ignore_benchmark)
-                .await?;
+            // Flaky benchmark measures still get boundaries,
+            // but they do not generate alerts unless alerts are enabled for flaky benchmarks.
+            let suppress_alerts = suppress_alerts
+                || (self.flaky_measures.is_flaky(benchmark_id, measure_id)
+                    && !context.flaky.alerts);
+            if let Some(alert_id) = detector
+                .detect(log, context, benchmark_id, &query_metric, suppress_alerts)
+                .await?
+            {
+                alerts.push(alert_id);
+            }
         }
 
-        Ok(())
+        // When multiple measures for the benchmark regress together,
+        // their alerts are grouped into a single correlated alert.
+        InsertAlertGroup::correlate(conn_lock!(context), report_benchmark_id, &alerts)
     }
 
     async fn benchmark_id(
@@ -235,6 +352,7 @@ impl ReportResults {
                 conn_lock!(context),
                 self.branch_id,
                 self.head_id,
+                self.reference_branch,
                 self.testbed_id,
                 measure_id,
             );
@@ -243,3 +361,362 @@ impl ReportResults {
         }
     }
 }
+
+type HookedResults = Vec<(BenchmarkName, (bool, BenchmarkMetrics))>;
+
+/// Run the project report hook, if any, to rename or drop each benchmark and its measures.
+fn hook<I>(report_hook: Option<&ReportHook>, results: I) -> Result<HookedResults, HttpError>
+where
+    I: Iterator<Item = (BenchmarkName, bool, BenchmarkMetrics)>,
+{
+    let Some(report_hook) = report_hook else {
+        return Ok(results
+            .map(|(benchmark_name, ignore_benchmark, metrics)| {
+                (benchmark_name, (ignore_benchmark, metrics))
+            })
+            .collect());
+    };
+    let mut originals = HashMap::new();
+    let mut hooked = Vec::new();
+    for (benchmark_name, ignore_benchmark, metrics) in results {
+        let Some(name) = report_hook.benchmark(benchmark_name.clone())? else {
+            continue;
+        };
+        let metrics = metrics.hook(report_hook, &name)?;
+        if metrics.metrics.inner.is_empty() {
+            continue;
+        }
+        if let Some(original) = originals.insert(name.clone(), benchmark_name.clone()) {
+            return Err(bad_request_error(format!(
+                "Benchmark names ({original}) and ({benchmark_name}) were both renamed by the project report hook to the same benchmark name ({name})"
+            )));
+        }
+        hooked.push((name, (ignore_benchmark, metrics)));
+    }
+    Ok(hooked)
+}
+
+/// In strict mode, reject a report before anything is created for it
+/// if any of its benchmarks or measures do not already exist in the project.
+/// The project report hook and rules are applied first,
+/// so the names that are checked are the same names that would be stored.
+pub fn check_strict<'a, R, M>(
+    conn: &mut DbConnection,
+    project_id: ProjectId,
+    benchmark_rules: &BenchmarkRules,
+    report_hook: Option<&ReportHook>,
+    raw_results: R,
+    threshold_measures: M,
+) -> Result<(), HttpError>
+where
+    R: IntoIterator<Item = (&'a [String], JsonReportSettings)>,
+    M: IntoIterator<Item = &'a MeasureNameId>,
+{
+    let mut benchmark_names = BTreeSet::new();
+    let mut measures = threshold_measures
+        .into_iter()
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    for (results, settings) in raw_results {
+        let adapter = settings.adapter.unwrap_or_default();
+        let results = results.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+        let results_array =
+            AdapterResultsArray::new(&results, adapter, AdapterSettings::new(settings.average))
+                .map_err(|e| {
+                    bad_request_error(format!(
+                        "Failed to convert results with adapter ({adapter} | {settings:?}): {e}"
+                    ))
+                })?;
+        for results in results_array.inner {
+            let results = hook(
+                report_hook,
+                IterationResults::Checked(results).into_stripped_benchmark_metrics(),
+            )?;
+            for (benchmark_name, (_, metrics)) in benchmark_rules.apply_all(results)?.results {
+                measures.extend(metrics.metrics.inner.into_keys());
+                benchmark_names.insert(benchmark_name);
+            }
+        }
+    }
+
+    let unknown_benchmarks = benchmark_names
+        .into_iter()
+        .filter(|benchmark_name| {
+            QueryBenchmark::get_from_name(conn, project_id, benchmark_name).is_err()
+        })
+        .collect::<Vec<_>>();
+    let mut unknown_measures = Vec::new();
+    for measure in measures {
+        if !QueryMeasure::exists(conn, project_id, &measure)? {
+            unknown_measures.push(measure);
+        }
+    }
+    if unknown_benchmarks.is_empty() && unknown_measures.is_empty() {
+        return Ok(());
+    }
+
+    Err(bad_request_error(format!(
+        "The report was rejected because strict mode is enabled and not all of its benchmarks and measures already exist in the project.\nUnknown benchmarks ({benchmarks_count}): {benchmarks}\nUnknown measures ({measures_count}): {measures}\nCreate them in the project first or disable strict mode.",
+        benchmarks_count = unknown_benchmarks.len(),
+        benchmarks = join_names(&unknown_benchmarks),
+        measures_count = unknown_measures.len(),
+        measures = join_names(&unknown_measures),
+    )))
+}
+
+fn join_names<T: ToString>(names: &[T]) -> String {
+    if names.is_empty() {
+        "none".to_owned()
+    } else {
+        names
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// The results for a single iteration of a report
+enum IterationResults {
+    /// Results that are checked against thresholds
+    Checked(AdapterResults),
+    /// Results that are only stored, as they are checked as part of a later summary
+    Unchecked(AdapterResults),
+    /// The central tendency and dispersion of all of the other iterations,
+    /// which are checked against thresholds
+    Summary {
+        central_tendency: AdapterResults,
+        dispersion: JsonDispersion,
+        dispersion_results: AdapterResults,
+    },
+}
+
+/// The metrics for a single benchmark in an iteration
+struct BenchmarkMetrics {
+    metrics: AdapterMetrics,
+    dispersion: Option<(JsonDispersion, AdapterMetrics)>,
+    check: bool,
+}
+
+impl BenchmarkMetrics {
+    /// Rename or drop each measure with the report hook,
+    /// keeping the dispersion metrics under the same measure key as their central tendency.
+    fn hook(
+        self,
+        report_hook: &ReportHook,
+        benchmark_name: &BenchmarkName,
+    ) -> Result<Self, HttpError> {
+        let Self {
+            metrics,
+            mut dispersion,
+            check,
+        } = self;
+        let mut metrics_map = HashMap::with_capacity(metrics.inner.len());
+        for (measure_key, metric) in metrics.inner {
+            let dispersion_metric = dispersion
+                .as_mut()
+                .and_then(|(_, dispersion_metrics)| dispersion_metrics.inner.remove(&measure_key));
+            let Some(measure_key) = report_hook.measure(benchmark_name, measure_key)? else {
+                continue;
+            };
+            if metrics_map.contains_key(&measure_key) {
+                return Err(bad_request_error(format!(
+                    "Multiple measures for benchmark ({benchmark_name}) were renamed by the project report hook to the same measure ({measure_key})"
+                )));
+            }
+            if let (Some((_, dispersion_metrics)), Some(dispersion_metric)) =
+                (dispersion.as_mut(), dispersion_metric)
+            {
+                dispersion_metrics
+                    .inner
+                    .insert(measure_key.clone(), dispersion_metric);
+            }
+            metrics_map.insert(measure_key, metric);
+        }
+        Ok(Self {
+            metrics: metrics_map.into(),
+            dispersion,
+            check,
+        })
+    }
+}
+
+impl IterationResults {
+    /// If a benchmark name is ignored, then strip the special suffix before applying the hook and rules
+    fn into_stripped_benchmark_metrics(
+        self,
+    ) -> impl Iterator<Item = (BenchmarkName, bool, BenchmarkMetrics)> {
+        self.into_benchmark_metrics()
+            .map(|(benchmark_name, metrics)| {
+                let (benchmark_name, ignore_benchmark) = benchmark_name.to_strip_ignore();
+                (benchmark_name, ignore_benchmark, metrics)
+            })
+    }
+
+    fn into_benchmark_metrics(self) -> impl Iterator<Item = (BenchmarkName, BenchmarkMetrics)> {
+        let (results, dispersion, check) = match self {
+            Self::Checked(results) => (results, None, true),
+            Self::Unchecked(results) => (results, None, false),
+            Self::Summary {
+                central_tendency,
+                dispersion,
+                dispersion_results,
+            } => (
+                central_tendency,
+                Some((dispersion, dispersion_results)),
+                true,
+            ),
+        };
+        let mut dispersion_results =
+            dispersion.map(|(dispersion, results)| (dispersion, results.inner));
+        results
+            .inner
+            .into_iter()
+            .map(move |(benchmark_name, metrics)| {
+                let dispersion = dispersion_results.as_mut().map(|(dispersion, results)| {
+                    (
+                        *dispersion,
+                        results.remove(&benchmark_name).unwrap_or_default(),
+                    )
+                });
+                (
+                    benchmark_name,
+                    BenchmarkMetrics {
+                        metrics,
+                        dispersion,
+                        check,
+                    },
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use bencher_json::{
+        project::report::JsonDispersion, BenchmarkName, JsonNewMetric, MeasureNameId,
+    };
+    use pretty_assertions::assert_eq;
+
+    use crate::model::project::report_hook::ReportHook;
+
+    use super::{hook, BenchmarkMetrics};
+
+    fn measure(name: &str) -> MeasureNameId {
+        name.parse().unwrap()
+    }
+
+    fn metric(value: f64) -> JsonNewMetric {
+        JsonNewMetric {
+            value: value.into(),
+            lower_value: None,
+            upper_value: None,
+        }
+    }
+
+    fn benchmark_metrics(measures: &[(&str, f64)]) -> BenchmarkMetrics {
+        BenchmarkMetrics {
+            metrics: measures
+                .iter()
+                .map(|(name, value)| (measure(name), metric(*value)))
+                .collect::<HashMap<_, _>>()
+                .into(),
+            dispersion: None,
+            check: true,
+        }
+    }
+
+    fn results(
+        benchmarks: &[(&str, &[(&str, f64)])],
+    ) -> impl Iterator<Item = (BenchmarkName, bool, BenchmarkMetrics)> {
+        benchmarks
+            .iter()
+            .map(|(benchmark, measures)| {
+                (
+                    benchmark.parse().unwrap(),
+                    false,
+                    benchmark_metrics(measures),
+                )
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_hook_rename_and_drop() {
+        let report_hook = ReportHook::compile(
+            r#"
+            fn benchmark(name) {
+                if name == "drop" { () } else { "bench::" + name }
+            }
+            fn measure(benchmark, measure) {
+                if measure == "throughput" { () } else { measure }
+            }
+            "#,
+        )
+        .unwrap();
+        let hooked = hook(
+            Some(&report_hook),
+            results(&[
+                ("fib", &[("latency", 1.0), ("throughput", 2.0)]),
+                ("drop", &[("latency", 3.0)]),
+                // All of the measures are dropped, so the benchmark is dropped too
+                ("fact", &[("throughput", 4.0)]),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(hooked.len(), 1);
+        let (name, (ignore_benchmark, metrics)) = hooked.first().unwrap();
+        assert_eq!(name.as_ref(), "bench::fib");
+        assert!(!ignore_benchmark);
+        assert_eq!(metrics.metrics.inner.len(), 1);
+        assert_eq!(
+            metrics.metrics.inner.get(&measure("latency")),
+            Some(&metric(1.0))
+        );
+    }
+
+    #[test]
+    fn test_hook_benchmark_collision() {
+        let report_hook = ReportHook::compile(r#"fn benchmark(name) { "same" }"#).unwrap();
+        let err = hook(
+            Some(&report_hook),
+            results(&[("fib", &[("latency", 1.0)]), ("fact", &[("latency", 2.0)])]),
+        )
+        .err()
+        .unwrap();
+        assert!(err.external_message.contains("same benchmark name (same)"));
+    }
+
+    #[test]
+    fn test_hook_measure_collision() {
+        let report_hook =
+            ReportHook::compile(r#"fn measure(benchmark, measure) { "latency" }"#).unwrap();
+        let err = hook(
+            Some(&report_hook),
+            results(&[("fib", &[("latency", 1.0), ("throughput", 2.0)])]),
+        )
+        .err()
+        .unwrap();
+        assert!(err.external_message.contains("same measure (latency)"));
+    }
+
+    #[test]
+    fn test_hook_dispersion() {
+        let report_hook =
+            ReportHook::compile(r#"fn measure(benchmark, measure) { "renamed-" + measure }"#)
+                .unwrap();
+        let mut metrics = benchmark_metrics(&[("latency", 1.0)]);
+        metrics.dispersion = Some((
+            JsonDispersion::StdDev,
+            HashMap::from([(measure("latency"), metric(0.5))]).into(),
+        ));
+        let metrics = metrics.hook(&report_hook, &"fib".parse().unwrap()).unwrap();
+        let renamed = measure("renamed-latency");
+        assert_eq!(metrics.metrics.inner.get(&renamed), Some(&metric(1.0)));
+        let (_, dispersion_metrics) = metrics.dispersion.unwrap();
+        assert_eq!(dispersion_metrics.inner.get(&renamed), Some(&metric(0.5)));
+    }
+}
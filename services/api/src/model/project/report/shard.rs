@@ -0,0 +1,199 @@
+use bencher_json::{DateTime, JsonNewReport, NonEmpty};
+use chrono::{Duration, Utc};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use http::StatusCode;
+
+use crate::{
+    context::DbConnection,
+    error::{bad_request_error, issue_error, resource_conflict_err},
+    model::project::ProjectId,
+    schema::{self, report_shard as report_shard_table},
+};
+
+crate::util::typed_id::typed_id!(ReportShardId);
+
+/// The most shards that a single run may be split into
+pub const MAX_REPORT_SHARDS: u32 = 255;
+/// Shards that have not been merged after this long are discarded
+const REPORT_SHARD_TTL_HOURS: i64 = 24;
+
+#[derive(Debug, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = report_shard_table)]
+pub struct QueryReportShard {
+    pub id: ReportShardId,
+    pub project_id: ProjectId,
+    pub shard_key: NonEmpty,
+    pub shard_index: i32,
+    pub shard_count: i32,
+    pub report: String,
+    pub created: DateTime,
+}
+
+impl QueryReportShard {
+    fn for_key(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        shard_key: &NonEmpty,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        schema::report_shard::table
+            .filter(schema::report_shard::project_id.eq(project_id))
+            .filter(schema::report_shard::shard_key.eq(shard_key))
+            .order(schema::report_shard::shard_index.asc())
+            .load::<Self>(conn)
+    }
+
+    /// Discard the shards for the project that were never merged.
+    pub fn delete_expired(conn: &mut DbConnection, project_id: ProjectId) -> Result<(), HttpError> {
+        let expired = DateTime::from(Utc::now() - Duration::hours(REPORT_SHARD_TTL_HOURS));
+        diesel::delete(
+            schema::report_shard::table
+                .filter(schema::report_shard::project_id.eq(project_id))
+                .filter(schema::report_shard::created.lt(expired)),
+        )
+        .execute(conn)
+        .map_err(resource_conflict_err!(ReportShard, (project_id, expired)))?;
+        Ok(())
+    }
+
+    /// Put claimed shards back, so that the run can be retried by submitting any of its shards again.
+    /// Shards that have since been submitted again are kept.
+    pub fn restore(conn: &mut DbConnection, shards: Vec<Self>) -> Result<(), HttpError> {
+        let insert_shards = shards
+            .into_iter()
+            .map(|shard| InsertReportShard {
+                project_id: shard.project_id,
+                shard_key: shard.shard_key,
+                shard_index: shard.shard_index,
+                shard_count: shard.shard_count,
+                report: shard.report,
+                created: shard.created,
+            })
+            .collect::<Vec<_>>();
+        diesel::insert_or_ignore_into(schema::report_shard::table)
+            .values(&insert_shards)
+            .execute(conn)
+            .map_err(resource_conflict_err!(ReportShard, insert_shards))?;
+        Ok(())
+    }
+
+    fn to_report(&self) -> Result<JsonNewReport, HttpError> {
+        serde_json::from_str(&self.report).map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to parse report shard",
+                &format!(
+                    "Failed to parse report shard ({index}/{count}) for key ({key}) in project ({project_id})",
+                    index = self.shard_index,
+                    count = self.shard_count,
+                    key = self.shard_key,
+                    project_id = self.project_id,
+                ),
+                e,
+            )
+        })
+    }
+
+    /// Merge all of the shards for a run into a single report,
+    /// along with the rest of the shards to process into the same report.
+    pub fn merge(shards: &[Self]) -> Result<(JsonNewReport, Vec<JsonNewReport>), HttpError> {
+        let reports = shards
+            .iter()
+            .map(Self::to_report)
+            .collect::<Result<Vec<_>, _>>()?;
+        JsonNewReport::merge_shards(reports).map_err(|e| bad_request_error(e.to_string()))
+    }
+}
+
+/// The shards that have been received for a run, after receiving a new shard
+pub enum ReceivedShards {
+    /// The shard count does not match the count of the shards already received
+    CountMismatch { shard_count: i32 },
+    /// Not all of the shards have been received yet
+    Pending { received: u32 },
+    /// All of the shards have been received and claimed to be merged
+    Complete(Vec<QueryReportShard>),
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = report_shard_table)]
+pub struct InsertReportShard {
+    pub project_id: ProjectId,
+    pub shard_key: NonEmpty,
+    pub shard_index: i32,
+    pub shard_count: i32,
+    pub report: String,
+    pub created: DateTime,
+}
+
+impl InsertReportShard {
+    pub fn from_json(
+        project_id: ProjectId,
+        shard_key: NonEmpty,
+        index: u32,
+        count: u32,
+        report: &JsonNewReport,
+    ) -> Result<Self, HttpError> {
+        if count == 0 || count > MAX_REPORT_SHARDS {
+            return Err(bad_request_error(format!(
+                "Invalid report shard count ({count}). The count must be between 1 and {MAX_REPORT_SHARDS}."
+            )));
+        }
+        if index == 0 || index > count {
+            return Err(bad_request_error(format!(
+                "Invalid report shard index ({index}). The index must be between 1 and the count ({count})."
+            )));
+        }
+        let report = serde_json::to_string(report).map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to serialize report shard",
+                &format!("Failed to serialize report shard ({index}/{count}) for key ({shard_key}) in project ({project_id})"),
+                e,
+            )
+        })?;
+        Ok(Self {
+            project_id,
+            shard_key,
+            // The index and count are at most `MAX_REPORT_SHARDS`
+            shard_index: i32::try_from(index).unwrap_or(i32::MAX),
+            shard_count: i32::try_from(count).unwrap_or(i32::MAX),
+            report,
+            created: DateTime::now(),
+        })
+    }
+
+    /// Store the shard, and if all of the shards for the run have been received, claim them by removing them.
+    /// This is done in a single immediate transaction,
+    /// so exactly one request claims the shards, even across servers that share the database.
+    pub fn receive(&self, conn: &mut DbConnection) -> Result<ReceivedShards, HttpError> {
+        conn.immediate_transaction::<_, diesel::result::Error, _>(|conn| {
+            let shards = QueryReportShard::for_key(conn, self.project_id, &self.shard_key)?;
+            if let Some(shard) = shards
+                .iter()
+                .find(|shard| shard.shard_count != self.shard_count)
+            {
+                return Ok(ReceivedShards::CountMismatch {
+                    shard_count: shard.shard_count,
+                });
+            }
+            diesel::replace_into(schema::report_shard::table)
+                .values(self)
+                .execute(conn)?;
+
+            let shards = QueryReportShard::for_key(conn, self.project_id, &self.shard_key)?;
+            let received = u32::try_from(shards.len()).unwrap_or(u32::MAX);
+            if i64::from(received) < i64::from(self.shard_count) {
+                return Ok(ReceivedShards::Pending { received });
+            }
+            diesel::delete(
+                schema::report_shard::table
+                    .filter(schema::report_shard::project_id.eq(self.project_id))
+                    .filter(schema::report_shard::shard_key.eq(&self.shard_key)),
+            )
+            .execute(conn)?;
+            Ok(ReceivedShards::Complete(shards))
+        })
+        .map_err(resource_conflict_err!(ReportShard, self))
+    }
+}
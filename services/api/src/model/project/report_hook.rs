@@ -0,0 +1,499 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use bencher_json::{
+    project::report_hook::JsonReportHook, BenchmarkName, DateTime, MeasureNameId, NonEmpty,
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use rhai::{CallFnOptions, Dynamic, Engine, EvalAltResult, Scope, AST};
+
+use crate::{
+    context::DbConnection,
+    error::{bad_request_error, resource_conflict_err, resource_not_found_err},
+    schema::{self, report_hook as report_hook_table},
+};
+
+use super::{ProjectId, QueryProject};
+
+crate::util::typed_id::typed_id!(ReportHookId);
+
+/// The maximum size of a report hook script in bytes
+pub const MAX_REPORT_HOOK_SCRIPT_BYTES: usize = 0x10000;
+
+// Each call to a hook function is limited, so a runaway script can not stall report ingest
+const MAX_OPERATIONS: u64 = 1_000_000;
+// All of the calls for a single report are also limited,
+// as a report may have many benchmarks and measures
+const MAX_REPORT_OPERATIONS: u64 = 10_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_STRING_SIZE: usize = 0x10000;
+const MAX_ARRAY_SIZE: usize = 0x10000;
+const MAX_MAP_SIZE: usize = 0x10000;
+
+const BENCHMARK_FN: &str = "benchmark";
+const MEASURE_FN: &str = "measure";
+const NOTES_FN: &str = "notes";
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = report_hook_table)]
+pub struct QueryReportHook {
+    pub id: ReportHookId,
+    pub project_id: ProjectId,
+    pub script: NonEmpty,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryReportHook {
+    pub fn get_for_project(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::report_hook::table
+            .filter(schema::report_hook::project_id.eq(project_id))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(ReportHook, project_id))
+    }
+
+    pub fn into_json(self, query_project: &QueryProject) -> JsonReportHook {
+        let Self {
+            script,
+            created,
+            modified,
+            ..
+        } = self;
+        JsonReportHook {
+            project: query_project.uuid,
+            script,
+            created,
+            modified,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = report_hook_table)]
+pub struct InsertReportHook {
+    pub project_id: ProjectId,
+    pub script: NonEmpty,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertReportHook {
+    /// Create the hook for the project, or replace the script if the hook already exists.
+    /// The script must already have been validated with `ReportHook::compile`.
+    pub fn upsert(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        script: NonEmpty,
+    ) -> Result<(), HttpError> {
+        let now = DateTime::now();
+        if QueryReportHook::get_for_project(conn, project_id)?.is_some() {
+            diesel::update(
+                schema::report_hook::table.filter(schema::report_hook::project_id.eq(project_id)),
+            )
+            .set((
+                schema::report_hook::script.eq(&script),
+                schema::report_hook::modified.eq(now),
+            ))
+            .execute(conn)
+        } else {
+            let insert_hook = Self {
+                project_id,
+                script,
+                created: now,
+                modified: now,
+            };
+            diesel::insert_into(schema::report_hook::table)
+                .values(&insert_hook)
+                .execute(conn)
+        }
+        .map_err(resource_conflict_err!(ReportHook, project_id))?;
+        Ok(())
+    }
+}
+
+/// The report hook for a project, compiled and ready to run on the report results.
+/// A new report hook should be used for each report, as it tracks the operations used by the report.
+pub struct ReportHook {
+    engine: Engine,
+    // Only the functions are kept, so the top-level statements are never run
+    ast: AST,
+    operations: Arc<Operations>,
+    benchmark: bool,
+    measure: bool,
+    notes: bool,
+}
+
+impl ReportHook {
+    pub fn for_project(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+    ) -> Result<Option<Self>, HttpError> {
+        QueryReportHook::get_for_project(conn, project_id)?
+            .map(|query_hook| Self::compile(query_hook.script.as_ref()))
+            .transpose()
+    }
+
+    pub fn compile(script: &str) -> Result<Self, HttpError> {
+        if script.len() > MAX_REPORT_HOOK_SCRIPT_BYTES {
+            return Err(bad_request_error(format!(
+                "Report hook script is too large ({len} bytes). At most {MAX_REPORT_HOOK_SCRIPT_BYTES} bytes are allowed.",
+                len = script.len(),
+            )));
+        }
+
+        let operations = Arc::new(Operations::default());
+        let mut engine = Engine::new();
+        let progress = operations.clone();
+        engine
+            .on_progress(move |count| progress.progress(count))
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+            .set_max_string_size(MAX_STRING_SIZE)
+            .set_max_array_size(MAX_ARRAY_SIZE)
+            .set_max_map_size(MAX_MAP_SIZE);
+        // The script has no output, so anything it prints is discarded
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+
+        let ast = engine
+            .compile(script)
+            .map_err(|e| bad_request_error(format!("Failed to compile report hook script: {e}")))?
+            .clone_functions_only();
+        let has_fn = |name: &str, arity: usize| {
+            ast.iter_functions()
+                .any(|f| f.name == name && f.params.len() == arity)
+        };
+        let benchmark = has_fn(BENCHMARK_FN, 1);
+        let measure = has_fn(MEASURE_FN, 2);
+        let notes = has_fn(NOTES_FN, 1);
+        if !(benchmark || measure || notes) {
+            return Err(bad_request_error(format!(
+                "Report hook script must define at least one of the functions: `{BENCHMARK_FN}(name)`, `{MEASURE_FN}(benchmark, measure)`, or `{NOTES_FN}(benchmarks)`"
+            )));
+        }
+
+        Ok(Self {
+            engine,
+            ast,
+            operations,
+            benchmark,
+            measure,
+            notes,
+        })
+    }
+
+    /// Rename the benchmark, or return `None` if the benchmark should be dropped.
+    pub fn benchmark(
+        &self,
+        benchmark_name: BenchmarkName,
+    ) -> Result<Option<BenchmarkName>, HttpError> {
+        if !self.benchmark {
+            return Ok(Some(benchmark_name));
+        }
+        let result = self.call(BENCHMARK_FN, (benchmark_name.to_string(),))?;
+        if result.is_unit() {
+            return Ok(None);
+        }
+        let name = into_string(BENCHMARK_FN, result)?;
+        name.parse().map(Some).map_err(|e| {
+            bad_request_error(format!(
+                "Report hook renamed benchmark ({benchmark_name}) to an invalid benchmark name ({name}): {e}"
+            ))
+        })
+    }
+
+    /// Rename the measure for the benchmark, or return `None` if the measure should be dropped.
+    pub fn measure(
+        &self,
+        benchmark_name: &BenchmarkName,
+        measure: MeasureNameId,
+    ) -> Result<Option<MeasureNameId>, HttpError> {
+        if !self.measure {
+            return Ok(Some(measure));
+        }
+        let result = self.call(
+            MEASURE_FN,
+            (benchmark_name.to_string(), measure.to_string()),
+        )?;
+        if result.is_unit() {
+            return Ok(None);
+        }
+        let name = into_string(MEASURE_FN, result)?;
+        name.parse().map(Some).map_err(|e| {
+            bad_request_error(format!(
+                "Report hook renamed measure ({measure}) for benchmark ({benchmark_name}) to an invalid measure ({name}): {e}"
+            ))
+        })
+    }
+
+    /// Get the notes to attach to the report, given all of the benchmark names in the report.
+    /// Empty notes are skipped.
+    pub fn notes(&self, benchmark_names: &[BenchmarkName]) -> Result<Vec<NonEmpty>, HttpError> {
+        if !self.notes {
+            return Ok(Vec::new());
+        }
+        let benchmarks = benchmark_names
+            .iter()
+            .map(|benchmark_name| Dynamic::from(benchmark_name.to_string()))
+            .collect::<rhai::Array>();
+        let result = self.call(NOTES_FN, (benchmarks,))?;
+        let results = if result.is_unit() {
+            Vec::new()
+        } else if result.is_array() {
+            result.into_array().unwrap_or_default()
+        } else {
+            vec![result]
+        };
+        let mut notes = Vec::with_capacity(results.len());
+        for result in results {
+            let note = into_string(NOTES_FN, result)?;
+            if let Ok(note) = note.parse() {
+                notes.push(note);
+            }
+        }
+        Ok(notes)
+    }
+
+    fn call(&self, name: &str, args: impl rhai::FuncArgs) -> Result<Dynamic, HttpError> {
+        let options = CallFnOptions::new().eval_ast(false);
+        let result = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &self.ast,
+            name,
+            args,
+        );
+        self.operations.finish();
+        result.map_err(|e| {
+            if let EvalAltResult::ErrorTerminated(..) = e.unwrap_inner() {
+                bad_request_error(format!(
+                    "Report hook function `{name}` failed: The report hook used more than {MAX_REPORT_OPERATIONS} operations for the report"
+                ))
+            } else {
+                bad_request_error(format!("Report hook function `{name}` failed: {e}"))
+            }
+        })
+    }
+}
+
+/// The number of operations used by all of the hook function calls for a report
+#[derive(Debug, Default)]
+struct Operations {
+    // The operations used by the calls that have finished
+    finished: AtomicU64,
+    // The operations used so far by the current call
+    current: AtomicU64,
+}
+
+impl Operations {
+    /// Terminate the current call if the report has run out of operations
+    fn progress(&self, count: u64) -> Option<Dynamic> {
+        self.current.store(count, Ordering::Relaxed);
+        let total = self.finished.load(Ordering::Relaxed).saturating_add(count);
+        (total > MAX_REPORT_OPERATIONS).then_some(Dynamic::UNIT)
+    }
+
+    fn finish(&self) {
+        let current = self.current.swap(0, Ordering::Relaxed);
+        self.finished.fetch_add(current, Ordering::Relaxed);
+    }
+}
+
+fn into_string(name: &str, result: Dynamic) -> Result<String, HttpError> {
+    let type_name = result.type_name();
+    result.into_string().map_err(|_e| {
+        bad_request_error(format!(
+            "Report hook function `{name}` must return a string or `()`, but it returned a value of type `{type_name}`"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{BenchmarkName, MeasureNameId, NonEmpty};
+    use pretty_assertions::assert_eq;
+
+    use super::{ReportHook, MAX_REPORT_HOOK_SCRIPT_BYTES};
+
+    fn benchmark_name(name: &str) -> BenchmarkName {
+        name.parse().unwrap()
+    }
+
+    fn measure(name: &str) -> MeasureNameId {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn test_report_hook_compile() {
+        assert!(ReportHook::compile("fn benchmark(name) { name }").is_ok());
+        assert!(ReportHook::compile("fn measure(benchmark, measure) { measure }").is_ok());
+        assert!(ReportHook::compile("fn notes(benchmarks) { () }").is_ok());
+
+        // Syntax error
+        assert!(ReportHook::compile("fn benchmark(name) { name ").is_err());
+        // No hook functions
+        assert!(ReportHook::compile("let x = 1;").is_err());
+        // Hook function with the wrong number of parameters
+        assert!(ReportHook::compile(r#"fn benchmark() { "name" }"#).is_err());
+        // Too large
+        let script = format!(
+            "fn benchmark(name) {{ name }}{}",
+            " ".repeat(MAX_REPORT_HOOK_SCRIPT_BYTES)
+        );
+        assert!(ReportHook::compile(&script).is_err());
+    }
+
+    #[test]
+    fn test_report_hook_benchmark() {
+        let report_hook = ReportHook::compile(
+            r#"
+            fn benchmark(name) {
+                if name.starts_with("skip_") {
+                    return;
+                }
+                "bench::" + name
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            report_hook.benchmark(benchmark_name("fib")).unwrap(),
+            Some(benchmark_name("bench::fib"))
+        );
+        assert_eq!(
+            report_hook.benchmark(benchmark_name("skip_fib")).unwrap(),
+            None
+        );
+        // Only the benchmark function is defined
+        assert_eq!(
+            report_hook
+                .measure(&benchmark_name("fib"), measure("latency"))
+                .unwrap(),
+            Some(measure("latency"))
+        );
+        assert!(report_hook
+            .notes(&[benchmark_name("fib")])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_report_hook_benchmark_invalid() {
+        let report_hook = ReportHook::compile("fn benchmark(name) { 42 }").unwrap();
+        assert!(report_hook.benchmark(benchmark_name("fib")).is_err());
+
+        let report_hook = ReportHook::compile(r#"fn benchmark(name) { "" }"#).unwrap();
+        assert!(report_hook.benchmark(benchmark_name("fib")).is_err());
+    }
+
+    #[test]
+    fn test_report_hook_measure() {
+        let report_hook = ReportHook::compile(
+            r#"
+            fn measure(benchmark, measure) {
+                if measure == "throughput" {
+                    ()
+                } else if benchmark == "fib" {
+                    "fib-" + measure
+                } else {
+                    measure
+                }
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            report_hook
+                .measure(&benchmark_name("fib"), measure("latency"))
+                .unwrap(),
+            Some(measure("fib-latency"))
+        );
+        assert_eq!(
+            report_hook
+                .measure(&benchmark_name("other"), measure("latency"))
+                .unwrap(),
+            Some(measure("latency"))
+        );
+        assert_eq!(
+            report_hook
+                .measure(&benchmark_name("fib"), measure("throughput"))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_report_hook_notes() {
+        let report_hook = ReportHook::compile(
+            r#"
+            fn notes(benchmarks) {
+                ["Benchmarks: " + benchmarks.len(), ""]
+            }
+            "#,
+        )
+        .unwrap();
+        let notes = report_hook
+            .notes(&[benchmark_name("fib"), benchmark_name("fact")])
+            .unwrap();
+        // Empty notes are skipped
+        assert_eq!(notes, ["Benchmarks: 2".parse::<NonEmpty>().unwrap()]);
+    }
+
+    #[test]
+    fn test_report_hook_top_level() {
+        // The top-level statements are never run
+        let report_hook = ReportHook::compile(
+            r#"
+            throw "top-level";
+            fn benchmark(name) { "bench::" + name }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            report_hook.benchmark(benchmark_name("fib")).unwrap(),
+            Some(benchmark_name("bench::fib"))
+        );
+        assert_eq!(
+            report_hook.benchmark(benchmark_name("fact")).unwrap(),
+            Some(benchmark_name("bench::fact"))
+        );
+    }
+
+    #[test]
+    fn test_report_hook_operations() {
+        // A single call is limited
+        let report_hook = ReportHook::compile("fn benchmark(name) { loop {} }").unwrap();
+        assert!(report_hook.benchmark(benchmark_name("fib")).is_err());
+
+        // All of the calls for a report are limited
+        let report_hook = ReportHook::compile(
+            "
+            fn benchmark(name) {
+                let sum = 0;
+                for i in 0..50000 {
+                    sum += i;
+                }
+                name
+            }
+            ",
+        )
+        .unwrap();
+        let mut result = Ok(None);
+        for _ in 0..1_000 {
+            result = report_hook.benchmark(benchmark_name("fib"));
+            if result.is_err() {
+                break;
+            }
+        }
+        assert!(result.is_err());
+    }
+}
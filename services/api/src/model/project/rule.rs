@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+use bencher_json::{
+    project::rule::JsonUpdateRule, BenchmarkName, DateTime, JsonNewRule, JsonRule, NonEmpty,
+    RuleUuid,
+};
+use diesel::{BelongingToDsl, ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use regex::Regex;
+
+use super::{ProjectId, QueryProject};
+use crate::{
+    context::DbConnection,
+    error::{assert_parentage, bad_request_error, resource_not_found_err, BencherResource},
+    schema::rule as rule_table,
+    util::fn_get::fn_get,
+};
+
+crate::util::typed_id::typed_id!(RuleId);
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = rule_table)]
+#[diesel(belongs_to(QueryProject, foreign_key = project_id))]
+pub struct QueryRule {
+    pub id: RuleId,
+    pub uuid: RuleUuid,
+    pub project_id: ProjectId,
+    pub pattern: NonEmpty,
+    pub replacement: String,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryRule {
+    fn_get!(rule, RuleId);
+
+    pub fn get_with_uuid(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        uuid: RuleUuid,
+    ) -> Result<Self, HttpError> {
+        Self::belonging_to(&query_project)
+            .filter(rule_table::uuid.eq(uuid))
+            .first::<Self>(conn)
+            .map_err(resource_not_found_err!(Rule, (query_project, uuid)))
+    }
+
+    pub fn into_json_for_project(self, project: &QueryProject) -> JsonRule {
+        let Self {
+            uuid,
+            project_id,
+            pattern,
+            replacement,
+            created,
+            modified,
+            ..
+        } = self;
+        assert_parentage(
+            BencherResource::Project,
+            project.id,
+            BencherResource::Rule,
+            project_id,
+        );
+        JsonRule {
+            uuid,
+            project: project.uuid,
+            pattern,
+            replacement,
+            created,
+            modified,
+        }
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = rule_table)]
+pub struct InsertRule {
+    pub uuid: RuleUuid,
+    pub project_id: ProjectId,
+    pub pattern: NonEmpty,
+    pub replacement: String,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertRule {
+    pub fn from_json(project_id: ProjectId, rule: JsonNewRule) -> Result<Self, HttpError> {
+        let JsonNewRule {
+            pattern,
+            replacement,
+        } = rule;
+        compile_pattern(&pattern)?;
+        let timestamp = DateTime::now();
+        Ok(Self {
+            uuid: RuleUuid::new(),
+            project_id,
+            pattern,
+            replacement,
+            created: timestamp,
+            modified: timestamp,
+        })
+    }
+}
+
+#[derive(Debug, Clone, diesel::AsChangeset)]
+#[diesel(table_name = rule_table)]
+pub struct UpdateRule {
+    pub pattern: Option<NonEmpty>,
+    pub replacement: Option<String>,
+    pub modified: DateTime,
+}
+
+impl TryFrom<JsonUpdateRule> for UpdateRule {
+    type Error = HttpError;
+
+    fn try_from(update: JsonUpdateRule) -> Result<Self, Self::Error> {
+        let JsonUpdateRule {
+            pattern,
+            replacement,
+        } = update;
+        if let Some(pattern) = &pattern {
+            compile_pattern(pattern)?;
+        }
+        Ok(Self {
+            pattern,
+            replacement,
+            modified: DateTime::now(),
+        })
+    }
+}
+
+fn compile_pattern(pattern: &NonEmpty) -> Result<Regex, HttpError> {
+    Regex::new(pattern.as_ref())
+        .map_err(|e| bad_request_error(format!("Invalid rule pattern ({pattern}): {e}")))
+}
+
+/// The rules for a project, compiled and in the order that they are applied.
+pub struct BenchmarkRules(Vec<(Regex, String)>);
+
+impl BenchmarkRules {
+    pub fn for_project(conn: &mut DbConnection, project_id: ProjectId) -> Result<Self, HttpError> {
+        let rules = rule_table::table
+            .filter(rule_table::project_id.eq(project_id))
+            .order((rule_table::created.asc(), rule_table::id.asc()))
+            .load::<QueryRule>(conn)
+            .map_err(resource_not_found_err!(Rule, project_id))?;
+        rules
+            .into_iter()
+            .map(|rule| compile_pattern(&rule.pattern).map(|regex| (regex, rule.replacement)))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Apply each rule in order to the benchmark name.
+    pub fn apply(&self, benchmark_name: BenchmarkName) -> Result<BenchmarkName, HttpError> {
+        if self.is_empty() {
+            return Ok(benchmark_name);
+        }
+        let mut name = benchmark_name.as_ref().to_owned();
+        for (regex, replacement) in &self.0 {
+            name = regex.replace_all(&name, replacement.as_str()).into_owned();
+        }
+        name.parse().map_err(|e| {
+            bad_request_error(format!(
+                "Benchmark name ({benchmark_name}) was rewritten by the project rules to an invalid benchmark name ({name}): {e}"
+            ))
+        })
+    }
+
+    /// Apply the rules to all of the benchmark names in a set of results.
+    /// If multiple benchmarks are rewritten to the same name, then only the first one is kept.
+    /// The others are skipped and returned as collisions, so they can be reported.
+    pub fn apply_all<I, T>(&self, results: I) -> Result<RewrittenResults<T>, HttpError>
+    where
+        I: IntoIterator<Item = (BenchmarkName, T)>,
+    {
+        let mut originals = HashMap::new();
+        let mut rewritten = RewrittenResults {
+            results: Vec::new(),
+            collisions: Vec::new(),
+        };
+        for (benchmark_name, value) in results {
+            let name = self.apply(benchmark_name.clone())?;
+            if let Some(original) = originals.get(&name) {
+                rewritten.collisions.push(RuleCollision {
+                    original: original.clone(),
+                    skipped: benchmark_name,
+                    name,
+                });
+                continue;
+            }
+            originals.insert(name.clone(), benchmark_name);
+            rewritten.results.push((name, value));
+        }
+        Ok(rewritten)
+    }
+}
+
+pub struct RewrittenResults<T> {
+    pub results: Vec<(BenchmarkName, T)>,
+    pub collisions: Vec<RuleCollision>,
+}
+
+/// A benchmark that was skipped because an earlier benchmark
+/// was already rewritten by the project rules to the same name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RuleCollision {
+    pub original: BenchmarkName,
+    pub skipped: BenchmarkName,
+    pub name: BenchmarkName,
+}
+
+impl RuleCollision {
+    pub fn note(&self) -> Option<NonEmpty> {
+        let Self {
+            original,
+            skipped,
+            name,
+        } = self;
+        format!("Benchmark ({skipped}) was skipped, as it was rewritten by the project rules to the same name ({name}) as benchmark ({original})")
+            .parse()
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::BenchmarkName;
+    use pretty_assertions::assert_eq;
+    use regex::Regex;
+
+    use super::{BenchmarkRules, RuleCollision};
+
+    fn benchmark_name(name: &str) -> BenchmarkName {
+        name.parse().unwrap()
+    }
+
+    fn rules(rules: &[(&str, &str)]) -> BenchmarkRules {
+        BenchmarkRules(
+            rules
+                .iter()
+                .map(|(pattern, replacement)| {
+                    (Regex::new(pattern).unwrap(), (*replacement).to_owned())
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_rules_apply() {
+        let rules = rules(&[(r"::v\d+$", ""), ("^bench_", "")]);
+        assert_eq!(
+            rules.apply(benchmark_name("bench_parse::v2")).unwrap(),
+            benchmark_name("parse")
+        );
+        // Rules that do not match leave the name unchanged
+        assert_eq!(
+            rules.apply(benchmark_name("serialize")).unwrap(),
+            benchmark_name("serialize")
+        );
+        // Rules are applied in order
+        let rules = self::rules(&[("a", "b"), ("b", "c")]);
+        assert_eq!(
+            rules.apply(benchmark_name("a")).unwrap(),
+            benchmark_name("c")
+        );
+    }
+
+    #[test]
+    fn test_rules_apply_empty() {
+        let rules = rules(&[]);
+        assert!(rules.is_empty());
+        assert_eq!(
+            rules.apply(benchmark_name("bench")).unwrap(),
+            benchmark_name("bench")
+        );
+    }
+
+    #[test]
+    fn test_rules_apply_invalid() {
+        // Rewriting a benchmark name to an empty name is an error
+        let rules = rules(&[(".*", "")]);
+        assert!(rules.apply(benchmark_name("bench")).is_err());
+    }
+
+    #[test]
+    fn test_rules_apply_all() {
+        let rules = rules(&[(r"\[\d+\]$", "")]);
+        let rewritten = rules
+            .apply_all([
+                (benchmark_name("parse[1]"), 1),
+                (benchmark_name("serialize"), 2),
+            ])
+            .unwrap();
+        assert_eq!(
+            rewritten.results,
+            [
+                (benchmark_name("parse"), 1),
+                (benchmark_name("serialize"), 2)
+            ]
+        );
+        assert!(rewritten.collisions.is_empty());
+    }
+
+    #[test]
+    fn test_rules_apply_all_collision() {
+        let rules = rules(&[(r"\[\d+\]$", "")]);
+        let rewritten = rules
+            .apply_all([
+                (benchmark_name("parse[1]"), 1),
+                (benchmark_name("parse[2]"), 2),
+                (benchmark_name("serialize"), 3),
+                (benchmark_name("parse"), 4),
+            ])
+            .unwrap();
+        // The first benchmark with a name is kept
+        assert_eq!(
+            rewritten.results,
+            [
+                (benchmark_name("parse"), 1),
+                (benchmark_name("serialize"), 3)
+            ]
+        );
+        assert_eq!(
+            rewritten.collisions,
+            [
+                RuleCollision {
+                    original: benchmark_name("parse[1]"),
+                    skipped: benchmark_name("parse[2]"),
+                    name: benchmark_name("parse"),
+                },
+                RuleCollision {
+                    original: benchmark_name("parse[1]"),
+                    skipped: benchmark_name("parse"),
+                    name: benchmark_name("parse"),
+                },
+            ]
+        );
+        assert!(rewritten.collisions[0]
+            .note()
+            .unwrap()
+            .as_ref()
+            .contains("(parse[2]) was skipped"));
+    }
+}
@@ -0,0 +1,508 @@
+use std::{collections::HashSet, fmt};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bencher_json::{
+    project::{
+        saved_query::{JsonSavedQueryDimensions, JsonSavedQueryResult, SavedQuerySchedule},
+        webhook::WebhookEvent,
+    },
+    DateTime, Email, JsonNewSavedQuery, JsonPerf, JsonSavedQuery, JsonSavedQueryPayload,
+    JsonUpdateSavedQuery, ResourceName, SavedQueryUuid, TimeZone, Weekday, Window,
+};
+use bencher_plot::LinePlot;
+use chrono::Duration;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper};
+use dropshot::HttpError;
+use http::StatusCode;
+use serde::{de::DeserializeOwned, Serialize};
+use slog::Logger;
+use tokio::sync::Mutex;
+
+use crate::{
+    context::{Body, DbConnection, Message, SavedQueryBody},
+    endpoints::project::perf::{perf_results, Times, MAX_PERMUTATIONS},
+    error::{
+        assert_parentage, bad_request_error, issue_error, resource_conflict_err,
+        resource_not_found_err, BencherResource,
+    },
+    model::job::{JobEmailImage, JobPayload, QueryJob},
+    schema::{self, saved_query as saved_query_table},
+    util::fn_get::fn_from_uuid,
+};
+
+use super::{
+    webhook::{QueryWebhook, SavedQueryWebhookRequest},
+    ProjectId, QueryProject,
+};
+
+crate::util::typed_id::typed_id!(SavedQueryId);
+
+/// The content ID for the chart embedded in a summary email
+const CHART_CID: &str = "chart";
+
+#[derive(
+    Debug, Clone, diesel::Queryable, diesel::Identifiable, diesel::Associations, diesel::Selectable,
+)]
+#[diesel(table_name = saved_query_table)]
+#[diesel(belongs_to(QueryProject, foreign_key = project_id))]
+pub struct QuerySavedQuery {
+    pub id: SavedQueryId,
+    pub uuid: SavedQueryUuid,
+    pub project_id: ProjectId,
+    pub name: ResourceName,
+    pub query: String,
+    pub window: Window,
+    pub schedule: SavedQuerySchedule,
+    pub emails: String,
+    pub webhook: bool,
+    pub last_sent: Option<DateTime>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QuerySavedQuery {
+    fn_from_uuid!(saved_query, SavedQueryUuid, SavedQuery);
+
+    /// Get a saved query by its UUID, if it still exists
+    pub fn from_saved_query_uuid(
+        conn: &mut DbConnection,
+        uuid: SavedQueryUuid,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::saved_query::table
+            .filter(schema::saved_query::uuid.eq(uuid))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(SavedQuery, uuid))
+    }
+
+    /// The saved queries that have a summary due at the given time.
+    /// Summaries are due based on the current date in the time zone of their project,
+    /// and weekly summaries are due on the first day of the reporting week for their project.
+    pub fn due(conn: &mut DbConnection, now: DateTime) -> Result<Vec<Self>, HttpError> {
+        Ok(schema::saved_query::table
+            .inner_join(schema::project::table)
+            .filter(schema::saved_query::schedule.ne(SavedQuerySchedule::Never))
+            .select((
+                Self::as_select(),
+                schema::project::time_zone,
+                schema::project::week_start,
+            ))
+            .load::<(Self, TimeZone, Weekday)>(conn)
+            .map_err(resource_not_found_err!(SavedQuery, now))?
+            .into_iter()
+            .filter_map(|(saved_query, time_zone, week_start)| {
+                let date = now.into_inner().with_timezone(&time_zone.tz()).date_naive();
+                saved_query
+                    .schedule
+                    .is_due(date, week_start)
+                    .then_some(saved_query)
+            })
+            .collect())
+    }
+
+    fn dimensions(&self) -> Result<JsonSavedQueryDimensions, HttpError> {
+        from_json_str(self.id, &self.query)
+    }
+
+    fn email_addresses(&self) -> Result<Vec<Email>, HttpError> {
+        from_json_str(self.id, &self.emails)
+    }
+
+    /// Run the saved query for the window that ends at the start of the day of the given time.
+    /// The window ends at midnight in the project time zone,
+    /// so summaries are aligned with the working days of the project.
+    pub async fn summary(
+        &self,
+        conn: &Mutex<DbConnection>,
+        end_time: DateTime,
+    ) -> Result<SavedQuerySummary, HttpError> {
+        let query_project = QueryProject::get(&mut *conn.lock().await, self.project_id)?;
+        let end_time = end_time.start_of_day(&query_project.time_zone);
+        let JsonSavedQueryDimensions {
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+        } = self.dimensions()?;
+        let heads = vec![None; branches.len()];
+        let start_time =
+            DateTime::from(end_time.into_inner() - Duration::seconds(i64::from(self.window)));
+        let times = Times {
+            start_time: Some(start_time),
+            end_time: Some(end_time),
+        };
+        let results = perf_results(
+            conn,
+            &query_project,
+            &branches,
+            &heads,
+            &testbeds,
+            &benchmarks,
+            &measures,
+            times,
+            &[],
+        )
+        .await?;
+
+        let json_perf = JsonPerf {
+            project: query_project.into_json(&mut *conn.lock().await)?,
+            start_time: Some(start_time),
+            end_time: Some(end_time),
+            results,
+            annotations: None,
+        };
+        let jpeg = LinePlot::new()
+            .draw(Some(self.name.as_ref()), &json_perf)
+            .map_err(|e| {
+                issue_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to draw saved query plot",
+                    &format!("Failed draw saved query ({id}) plot", id = self.id),
+                    e,
+                )
+            })?;
+        let results = json_perf
+            .results
+            .iter()
+            .filter_map(JsonSavedQueryResult::new)
+            .collect();
+
+        Ok(SavedQuerySummary {
+            project: json_perf.project.name.to_string(),
+            start_time,
+            end_time,
+            results,
+            image: STANDARD.encode(jpeg),
+        })
+    }
+
+    /// Queue a summary email for each email address.
+    /// Email addresses that no longer belong to a member of the project or its organization are skipped.
+    /// The emails are keyed by the end of the window,
+    /// so they are only sent once even if this is retried.
+    pub fn enqueue_emails(
+        &self,
+        log: &Logger,
+        conn: &mut DbConnection,
+        summary: &SavedQuerySummary,
+    ) -> Result<(), HttpError> {
+        let query_project = QueryProject::get(conn, self.project_id)?;
+        let members = member_emails(conn, &query_project)?;
+        for email in self.email_addresses()? {
+            if !members.contains(&email) {
+                slog::warn!(
+                    log,
+                    "Skipping saved query ({}) summary email to non-member ({email})",
+                    self.uuid
+                );
+                continue;
+            }
+            let body = SavedQueryBody {
+                project: summary.project.clone(),
+                name: self.name.to_string(),
+                start_time: summary.start_time,
+                end_time: summary.end_time,
+                results: summary.results.clone(),
+                image_cid: CHART_CID.to_owned(),
+            };
+            let message = Message {
+                to_name: None,
+                to_email: email.to_string(),
+                subject: Some(format!("Bencher Summary: {}", self.name)),
+                body: Some(Body::SavedQuery(body)),
+            };
+            let mut job_email = message.into_job_email(log);
+            job_email.images.push(JobEmailImage {
+                cid: CHART_CID.to_owned(),
+                content_type: "image/jpeg".to_owned(),
+                data: summary.image.clone(),
+            });
+            let job_key = format!(
+                "saved-query-{uuid}-{end_time}-{email}",
+                uuid = self.uuid,
+                end_time = summary.end_time.timestamp()
+            );
+            QueryJob::enqueue(conn, &JobPayload::Email(job_email), Some(job_key))?;
+        }
+        Ok(())
+    }
+
+    /// Build the signed request for the project webhook.
+    /// Returns `None` if the summary is not sent to the webhook or the project no longer has a webhook.
+    pub fn webhook_request(
+        &self,
+        conn: &mut DbConnection,
+        summary: SavedQuerySummary,
+    ) -> Result<Option<SavedQueryWebhookRequest>, HttpError> {
+        if !self.webhook {
+            return Ok(None);
+        }
+        let Some(query_webhook) = QueryWebhook::get_for_project(conn, self.project_id)? else {
+            return Ok(None);
+        };
+        let project = QueryProject::get_uuid(conn, self.project_id)?;
+        let SavedQuerySummary {
+            start_time,
+            end_time,
+            results,
+            image,
+            ..
+        } = summary;
+        let payload = JsonSavedQueryPayload {
+            event: WebhookEvent::SavedQuery,
+            saved_query: self.uuid,
+            project,
+            name: self.name.clone(),
+            start_time,
+            end_time,
+            results,
+            image,
+        };
+        query_webhook.saved_query_request(&payload).map(Some)
+    }
+
+    /// Record that the summary for the window that ends at the given time was sent
+    pub fn sent(&self, conn: &mut DbConnection, end_time: DateTime) -> Result<(), HttpError> {
+        diesel::update(schema::saved_query::table.filter(schema::saved_query::id.eq(self.id)))
+            .set(schema::saved_query::last_sent.eq(end_time))
+            .execute(conn)
+            .map_err(resource_conflict_err!(SavedQuery, self.id))?;
+        Ok(())
+    }
+
+    pub fn into_json_for_project(
+        self,
+        project: &QueryProject,
+    ) -> Result<JsonSavedQuery, HttpError> {
+        assert_parentage(
+            BencherResource::Project,
+            project.id,
+            BencherResource::SavedQuery,
+            self.project_id,
+        );
+        let JsonSavedQueryDimensions {
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+        } = self.dimensions()?;
+        let emails = self.email_addresses()?;
+        let Self {
+            uuid,
+            name,
+            window,
+            schedule,
+            webhook,
+            last_sent,
+            created,
+            modified,
+            ..
+        } = self;
+        Ok(JsonSavedQuery {
+            uuid,
+            project: project.uuid,
+            name,
+            window,
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+            schedule,
+            emails,
+            webhook,
+            last_sent,
+            created,
+            modified,
+        })
+    }
+}
+
+/// Summaries can only be sent to the members of the project or its organization,
+/// so a saved query can not be used to send email to arbitrary addresses.
+pub fn check_recipients(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+    emails: &[Email],
+) -> Result<(), HttpError> {
+    let members = member_emails(conn, query_project)?;
+    let non_members = emails
+        .iter()
+        .filter(|email| !members.contains(*email))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if non_members.is_empty() {
+        Ok(())
+    } else {
+        Err(bad_request_error(format!(
+            "Saved query summaries can only be sent to members of the project ({project}) or its organization. Not members: {non_members}",
+            project = query_project.slug,
+            non_members = non_members.join(", ")
+        )))
+    }
+}
+
+/// The email addresses of all the unlocked members of the project or its organization
+fn member_emails(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+) -> Result<HashSet<Email>, HttpError> {
+    let organization_members = schema::user::table
+        .inner_join(schema::organization_role::table)
+        .filter(schema::organization_role::organization_id.eq(query_project.organization_id))
+        .filter(schema::user::locked.eq(false))
+        .select(schema::user::email)
+        .load::<Email>(conn)
+        .map_err(resource_not_found_err!(OrganizationRole, query_project))?;
+    let project_members = schema::user::table
+        .inner_join(schema::project_role::table)
+        .filter(schema::project_role::project_id.eq(query_project.id))
+        .filter(schema::user::locked.eq(false))
+        .select(schema::user::email)
+        .load::<Email>(conn)
+        .map_err(resource_not_found_err!(ProjectRole, query_project))?;
+    Ok(organization_members
+        .into_iter()
+        .chain(project_members)
+        .collect())
+}
+
+/// The results of running a saved query, ready to be sent
+#[derive(Debug)]
+pub struct SavedQuerySummary {
+    project: String,
+    start_time: DateTime,
+    end_time: DateTime,
+    results: Vec<JsonSavedQueryResult>,
+    /// The base64 encoded JPEG chart of the results
+    image: String,
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = saved_query_table)]
+pub struct InsertSavedQuery {
+    pub uuid: SavedQueryUuid,
+    pub project_id: ProjectId,
+    pub name: ResourceName,
+    pub query: String,
+    pub window: Window,
+    pub schedule: SavedQuerySchedule,
+    pub emails: String,
+    pub webhook: bool,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertSavedQuery {
+    pub fn from_json(
+        project_id: ProjectId,
+        saved_query: JsonNewSavedQuery,
+    ) -> Result<Self, HttpError> {
+        let JsonNewSavedQuery {
+            name,
+            window,
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+            schedule,
+            emails,
+            webhook,
+        } = saved_query;
+        if branches.is_empty()
+            || testbeds.is_empty()
+            || benchmarks.is_empty()
+            || measures.is_empty()
+        {
+            return Err(bad_request_error(
+                "A saved query must have at least one branch, testbed, benchmark, and measure",
+            ));
+        }
+        let permutations = branches.len() * testbeds.len() * benchmarks.len() * measures.len();
+        if permutations > MAX_PERMUTATIONS {
+            return Err(bad_request_error(format!(
+                "A saved query can have at most {MAX_PERMUTATIONS} permutations of branch, testbed, benchmark, and measure but it has {permutations}",
+            )));
+        }
+
+        let query = to_json_string(&JsonSavedQueryDimensions {
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+        })?;
+        let emails = to_json_string(&emails.unwrap_or_default())?;
+        let timestamp = DateTime::now();
+        Ok(Self {
+            uuid: SavedQueryUuid::new(),
+            project_id,
+            name,
+            query,
+            window,
+            schedule: schedule.unwrap_or_default(),
+            emails,
+            webhook: webhook.unwrap_or_default(),
+            created: timestamp,
+            modified: timestamp,
+        })
+    }
+}
+
+#[derive(Debug, Clone, diesel::AsChangeset)]
+#[diesel(table_name = saved_query_table)]
+pub struct UpdateSavedQuery {
+    pub name: Option<ResourceName>,
+    pub window: Option<Window>,
+    pub schedule: Option<SavedQuerySchedule>,
+    pub emails: Option<String>,
+    pub webhook: Option<bool>,
+    pub modified: DateTime,
+}
+
+impl UpdateSavedQuery {
+    pub fn from_json(update: JsonUpdateSavedQuery) -> Result<Self, HttpError> {
+        let JsonUpdateSavedQuery {
+            name,
+            window,
+            schedule,
+            emails,
+            webhook,
+        } = update;
+        let emails = emails.as_ref().map(to_json_string).transpose()?;
+        Ok(Self {
+            name,
+            window,
+            schedule,
+            emails,
+            webhook,
+            modified: DateTime::now(),
+        })
+    }
+}
+
+fn to_json_string<T>(value: &T) -> Result<String, HttpError>
+where
+    T: Serialize + fmt::Debug,
+{
+    serde_json::to_string(value).map_err(|e| {
+        issue_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to serialize saved query",
+            &format!("Failed to serialize saved query: {value:?}"),
+            e,
+        )
+    })
+}
+
+fn from_json_str<T>(id: SavedQueryId, json: &str) -> Result<T, HttpError>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_str(json).map_err(|e| {
+        issue_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to parse saved query",
+            &format!("Failed to parse saved query ({id}): {json}"),
+            e,
+        )
+    })
+}
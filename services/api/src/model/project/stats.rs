@@ -0,0 +1,74 @@
+use bencher_json::project::JsonProjectStats;
+use diesel::{dsl::count, ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{context::DbConnection, error::resource_not_found_err, schema};
+
+use super::ProjectId;
+
+// Rough estimates of the number of bytes used by a single row of each table,
+// including its UUID index entry.
+const NAMED_ROW_BYTES: u64 = 256;
+const REPORT_ROW_BYTES: u64 = 192;
+const REPORT_BENCHMARK_ROW_BYTES: u64 = 96;
+const METRIC_ROW_BYTES: u64 = 128;
+const BOUNDARY_ROW_BYTES: u64 = 128;
+
+macro_rules! count_project {
+    ($conn:ident, $project_id:ident, $table:ident, $resource:ident) => {
+        schema::$table::table
+            .filter(schema::$table::project_id.eq($project_id))
+            .select(count(schema::$table::id))
+            .first::<i64>($conn)
+            .map_err(resource_not_found_err!($resource, $project_id))?
+    };
+}
+
+/// Count the resources in a project and estimate how much storage they use.
+pub fn project_stats(
+    conn: &mut DbConnection,
+    project_id: ProjectId,
+) -> Result<JsonProjectStats, HttpError> {
+    let branches = count_project!(conn, project_id, branch, Branch);
+    let testbeds = count_project!(conn, project_id, testbed, Testbed);
+    let benchmarks = count_project!(conn, project_id, benchmark, Benchmark);
+    let measures = count_project!(conn, project_id, measure, Measure);
+    let reports = count_project!(conn, project_id, report, Report);
+    let report_benchmarks = schema::report_benchmark::table
+        .inner_join(schema::report::table)
+        .filter(schema::report::project_id.eq(project_id))
+        .select(count(schema::report_benchmark::id))
+        .first::<i64>(conn)
+        .map_err(resource_not_found_err!(ReportBenchmark, project_id))?;
+    let metrics = schema::metric::table
+        .inner_join(schema::report_benchmark::table.inner_join(schema::report::table))
+        .filter(schema::report::project_id.eq(project_id))
+        .select(count(schema::metric::id))
+        .first::<i64>(conn)
+        .map_err(resource_not_found_err!(Metric, project_id))?;
+    let boundaries = schema::boundary::table
+        .inner_join(schema::threshold::table)
+        .filter(schema::threshold::project_id.eq(project_id))
+        .select(count(schema::boundary::id))
+        .first::<i64>(conn)
+        .map_err(resource_not_found_err!(Boundary, project_id))?;
+
+    let rows = |count: i64| u64::try_from(count).unwrap_or_default();
+    let storage = rows(branches + testbeds + benchmarks + measures) * NAMED_ROW_BYTES
+        + rows(reports) * REPORT_ROW_BYTES
+        + rows(report_benchmarks) * REPORT_BENCHMARK_ROW_BYTES
+        + rows(metrics) * METRIC_ROW_BYTES
+        + rows(boundaries) * BOUNDARY_ROW_BYTES;
+
+    let count = |count: i64| u32::try_from(count).unwrap_or(u32::MAX);
+    Ok(JsonProjectStats {
+        branches: count(branches),
+        testbeds: count(testbeds),
+        benchmarks: count(benchmarks),
+        measures: count(measures),
+        reports: count(reports),
+        metrics: count(metrics),
+        boundaries: count(boundaries),
+        storage: storage.into(),
+    })
+}
@@ -1,6 +1,6 @@
 use bencher_json::{
-    project::testbed::JsonUpdateTestbed, DateTime, JsonNewTestbed, JsonTestbed, NameId, NameIdKind,
-    ResourceName, Slug, TestbedUuid,
+    project::testbed::JsonUpdateTestbed, DateTime, JsonNewTestbed, JsonTestbed, JsonTestbedSpec,
+    NameId, NameIdKind, NonEmpty, ResourceName, Slug, TestbedUuid,
 };
 use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
 use dropshot::HttpError;
@@ -32,6 +32,10 @@ pub struct QueryTestbed {
     pub project_id: ProjectId,
     pub name: ResourceName,
     pub slug: Slug,
+    pub spec_cpu: Option<NonEmpty>,
+    pub spec_cores: Option<i32>,
+    pub spec_memory: Option<i64>,
+    pub spec_os: Option<NonEmpty>,
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
@@ -87,8 +91,13 @@ impl QueryTestbed {
             NameIdKind::Slug(slug) => JsonNewTestbed {
                 name: slug.clone().into(),
                 slug: Some(slug),
+                spec: None,
+            },
+            NameIdKind::Name(name) => JsonNewTestbed {
+                name,
+                slug: None,
+                spec: None,
             },
-            NameIdKind::Name(name) => JsonNewTestbed { name, slug: None },
         };
         let insert_testbed = InsertTestbed::from_json(conn_lock!(context), project_id, testbed)?;
         diesel::insert_into(schema::testbed::table)
@@ -105,11 +114,21 @@ impl QueryTestbed {
             project_id,
             name,
             slug,
+            spec_cpu,
+            spec_cores,
+            spec_memory,
+            spec_os,
             created,
             modified,
             archived,
             ..
         } = self;
+        let spec = JsonTestbedSpec {
+            cpu: spec_cpu,
+            cores: spec_cores.and_then(|cores| u32::try_from(cores).ok()),
+            memory: spec_memory.and_then(|memory| u64::try_from(memory).ok().map(Into::into)),
+            os: spec_os,
+        };
         assert_parentage(
             BencherResource::Project,
             project.id,
@@ -121,6 +140,7 @@ impl QueryTestbed {
             project: project.uuid,
             name,
             slug,
+            spec: (!spec.is_empty()).then_some(spec),
             created,
             modified,
             archived,
@@ -135,6 +155,10 @@ pub struct InsertTestbed {
     pub project_id: ProjectId,
     pub name: ResourceName,
     pub slug: Slug,
+    pub spec_cpu: Option<NonEmpty>,
+    pub spec_cores: Option<i32>,
+    pub spec_memory: Option<i64>,
+    pub spec_os: Option<NonEmpty>,
     pub created: DateTime,
     pub modified: DateTime,
     pub archived: Option<DateTime>,
@@ -146,14 +170,19 @@ impl InsertTestbed {
         project_id: ProjectId,
         testbed: JsonNewTestbed,
     ) -> Result<Self, HttpError> {
-        let JsonNewTestbed { name, slug } = testbed;
+        let JsonNewTestbed { name, slug, spec } = testbed;
         let slug = ok_slug!(conn, project_id, &name, slug, testbed, QueryTestbed)?;
+        let (spec_cpu, spec_cores, spec_memory, spec_os) = spec_columns(spec.unwrap_or_default());
         let timestamp = DateTime::now();
         Ok(Self {
             uuid: TestbedUuid::new(),
             project_id,
             name,
             slug,
+            spec_cpu,
+            spec_cores,
+            spec_memory,
+            spec_os,
             created: timestamp,
             modified: timestamp,
             archived: None,
@@ -170,6 +199,10 @@ impl InsertTestbed {
 pub struct UpdateTestbed {
     pub name: Option<ResourceName>,
     pub slug: Option<Slug>,
+    pub spec_cpu: Option<Option<NonEmpty>>,
+    pub spec_cores: Option<Option<i32>>,
+    pub spec_memory: Option<Option<i64>>,
+    pub spec_os: Option<Option<NonEmpty>>,
     pub modified: DateTime,
     pub archived: Option<Option<DateTime>>,
 }
@@ -179,13 +212,23 @@ impl From<JsonUpdateTestbed> for UpdateTestbed {
         let JsonUpdateTestbed {
             name,
             slug,
+            spec,
             archived,
         } = update;
+        // The entire spec is replaced, so any fields that are not set are cleared
+        let (spec_cpu, spec_cores, spec_memory, spec_os) = match spec.map(spec_columns) {
+            Some((cpu, cores, memory, os)) => (Some(cpu), Some(cores), Some(memory), Some(os)),
+            None => (None, None, None, None),
+        };
         let modified = DateTime::now();
         let archived = archived.map(|archived| archived.then_some(modified));
         Self {
             name,
             slug,
+            spec_cpu,
+            spec_cores,
+            spec_memory,
+            spec_os,
             modified,
             archived,
         }
@@ -197,8 +240,26 @@ impl UpdateTestbed {
         JsonUpdateTestbed {
             name: None,
             slug: None,
+            spec: None,
             archived: Some(false),
         }
         .into()
     }
 }
+
+type SpecColumns = (Option<NonEmpty>, Option<i32>, Option<i64>, Option<NonEmpty>);
+
+fn spec_columns(spec: JsonTestbedSpec) -> SpecColumns {
+    let JsonTestbedSpec {
+        cpu,
+        cores,
+        memory,
+        os,
+    } = spec;
+    (
+        cpu,
+        cores.map(|cores| i32::try_from(cores).unwrap_or(i32::MAX)),
+        memory.map(|memory| i64::try_from(u64::from(memory)).unwrap_or(i64::MAX)),
+        os,
+    )
+}
@@ -1,10 +1,12 @@
 use bencher_json::{
     project::{
-        alert::{AlertStatus, JsonAlert, JsonPerfAlert, JsonUpdateAlert},
+        alert::{
+            AlertSeverity, AlertStatus, JsonAlert, JsonAlertUpdate, JsonPerfAlert, JsonUpdateAlert,
+        },
         boundary::BoundaryLimit,
         report::Iteration,
     },
-    AlertUuid, BoundaryUuid, DateTime, ReportUuid,
+    AlertGroupUuid, AlertUpdateUuid, AlertUuid, BoundaryUuid, DateTime, NonEmpty, ReportUuid,
 };
 use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
 use dropshot::HttpError;
@@ -21,13 +23,20 @@ use crate::{
         benchmark::QueryBenchmark,
         branch::{head::HeadId, version::VersionId},
         metric::QueryMetric,
+        report::report_benchmark::ReportBenchmarkId,
+        webhook::InsertWebhookDelivery,
         ProjectId, QueryProject,
     },
-    schema::{self, alert as alert_table},
+    model::user::{QueryUser, UserId},
+    schema::{
+        self, alert as alert_table, alert_group as alert_group_table,
+        alert_update as alert_update_table,
+    },
     util::fn_get::{fn_get, fn_get_id, fn_get_uuid},
 };
 
 crate::util::typed_id::typed_id!(AlertId);
+crate::util::typed_id::typed_id!(AlertGroupId);
 
 #[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
 #[diesel(table_name = alert_table)]
@@ -35,7 +44,9 @@ pub struct QueryAlert {
     pub id: AlertId,
     pub uuid: AlertUuid,
     pub boundary_id: BoundaryId,
+    pub group_id: Option<AlertGroupId>,
     pub boundary_limit: BoundaryLimit,
+    pub severity: AlertSeverity,
     pub status: AlertStatus,
     pub modified: DateTime,
 }
@@ -161,11 +172,18 @@ impl QueryAlert {
     ) -> Result<JsonAlert, HttpError> {
         let Self {
             uuid,
+            group_id,
             boundary_limit,
+            severity,
             status,
             modified,
             ..
         } = self;
+        let group = if let Some(group_id) = group_id {
+            Some(QueryAlertGroup::get_uuid(conn_lock!(context), group_id)?)
+        } else {
+            None
+        };
         let threshold = QueryThreshold::get_alert_json(
             context,
             query_boundary.threshold_id,
@@ -183,7 +201,9 @@ impl QueryAlert {
             threshold,
             boundary: query_boundary.into_json(),
             limit: boundary_limit,
+            severity,
             status,
+            group,
             created,
             modified,
         })
@@ -193,6 +213,7 @@ impl QueryAlert {
         let QueryAlert {
             uuid,
             boundary_limit,
+            severity,
             status,
             modified,
             ..
@@ -200,6 +221,7 @@ impl QueryAlert {
         JsonPerfAlert {
             uuid,
             limit: boundary_limit,
+            severity,
             status,
             modified,
         }
@@ -211,21 +233,29 @@ impl QueryAlert {
 pub struct InsertAlert {
     pub uuid: AlertUuid,
     pub boundary_id: BoundaryId,
+    pub group_id: Option<AlertGroupId>,
     pub boundary_limit: BoundaryLimit,
+    pub severity: AlertSeverity,
     pub status: AlertStatus,
     pub modified: DateTime,
 }
 
 impl InsertAlert {
+    /// Create an alert for the boundary.
+    /// The webhook delivery for the alert is left to the caller,
+    /// so that correlated alerts can be delivered together.
     pub fn from_boundary(
         conn: &mut DbConnection,
         boundary_uuid: BoundaryUuid,
         boundary_limit: BoundaryLimit,
-    ) -> Result<(), HttpError> {
+        severity: AlertSeverity,
+    ) -> Result<AlertId, HttpError> {
         let insert_alert = InsertAlert {
             uuid: AlertUuid::new(),
             boundary_id: QueryBoundary::get_id(conn, boundary_uuid)?,
+            group_id: None,
             boundary_limit,
+            severity,
             status: AlertStatus::default(),
             modified: DateTime::now(),
         };
@@ -235,7 +265,73 @@ impl InsertAlert {
             .execute(conn)
             .map_err(resource_conflict_err!(Alert, insert_alert))?;
 
-        Ok(())
+        QueryAlert::get_id(conn, insert_alert.uuid)
+    }
+}
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = alert_group_table)]
+pub struct QueryAlertGroup {
+    pub id: AlertGroupId,
+    pub uuid: AlertGroupUuid,
+    pub report_benchmark_id: ReportBenchmarkId,
+    pub created: DateTime,
+}
+
+impl QueryAlertGroup {
+    fn_get_id!(alert_group, AlertGroupId, AlertGroupUuid);
+    fn_get_uuid!(alert_group, AlertGroupId, AlertGroupUuid);
+}
+
+/// A group of alerts for the measures of a single benchmark that regressed together.
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = alert_group_table)]
+pub struct InsertAlertGroup {
+    pub uuid: AlertGroupUuid,
+    pub report_benchmark_id: ReportBenchmarkId,
+    pub created: DateTime,
+}
+
+impl InsertAlertGroup {
+    /// Correlate the new alerts for a report benchmark and queue their webhook deliveries.
+    /// If more than one measure regressed, then the alerts are put into a single group
+    /// and only the most severe alert in the group is delivered, along with the rest of the group.
+    /// Otherwise, each alert is delivered on its own.
+    pub fn correlate(
+        conn: &mut DbConnection,
+        report_benchmark_id: ReportBenchmarkId,
+        alerts: &[AlertId],
+    ) -> Result<(), HttpError> {
+        if alerts.len() < 2 {
+            for alert_id in alerts {
+                InsertWebhookDelivery::enqueue(conn, *alert_id)?;
+            }
+            return Ok(());
+        }
+
+        let insert_alert_group = Self {
+            uuid: AlertGroupUuid::new(),
+            report_benchmark_id,
+            created: DateTime::now(),
+        };
+        diesel::insert_into(schema::alert_group::table)
+            .values(&insert_alert_group)
+            .execute(conn)
+            .map_err(resource_conflict_err!(AlertGroup, insert_alert_group))?;
+        let group_id = QueryAlertGroup::get_id(conn, insert_alert_group.uuid)?;
+
+        diesel::update(schema::alert::table.filter(schema::alert::id.eq_any(alerts)))
+            .set(schema::alert::group_id.eq(group_id))
+            .execute(conn)
+            .map_err(resource_conflict_err!(Alert, (group_id, alerts)))?;
+
+        let lead_alert_id = schema::alert::table
+            .filter(schema::alert::group_id.eq(group_id))
+            .order((schema::alert::severity.desc(), schema::alert::id))
+            .select(schema::alert::id)
+            .first::<AlertId>(conn)
+            .map_err(resource_not_found_err!(Alert, group_id))?;
+        InsertWebhookDelivery::enqueue(conn, lead_alert_id)
     }
 }
 
@@ -264,3 +360,60 @@ impl UpdateAlert {
         }
     }
 }
+
+/// The record of a bulk update of the alerts for a project, kept for auditing
+#[derive(Debug, Clone, diesel::Insertable)]
+#[diesel(table_name = alert_update_table)]
+pub struct InsertAlertUpdate {
+    pub uuid: AlertUpdateUuid,
+    pub project_id: ProjectId,
+    pub user_id: UserId,
+    pub status: AlertStatus,
+    pub reason: NonEmpty,
+    pub alerts: i32,
+    pub created: DateTime,
+}
+
+impl InsertAlertUpdate {
+    pub fn new(
+        project_id: ProjectId,
+        user_id: UserId,
+        status: AlertStatus,
+        reason: NonEmpty,
+        alerts: i32,
+    ) -> Self {
+        Self {
+            uuid: AlertUpdateUuid::new(),
+            project_id,
+            user_id,
+            status,
+            reason,
+            alerts,
+            created: DateTime::now(),
+        }
+    }
+
+    pub fn into_json(
+        self,
+        query_project: &QueryProject,
+        query_user: &QueryUser,
+    ) -> JsonAlertUpdate {
+        let Self {
+            uuid,
+            status,
+            reason,
+            alerts,
+            created,
+            ..
+        } = self;
+        JsonAlertUpdate {
+            uuid,
+            project: query_project.uuid,
+            user: query_user.uuid,
+            status,
+            reason,
+            alerts: alerts.unsigned_abs(),
+            created,
+        }
+    }
+}
@@ -33,6 +33,7 @@ use crate::{
 pub mod alert;
 pub mod boundary;
 pub mod model;
+pub mod recompute;
 
 crate::util::typed_id::typed_id!(ThresholdId);
 
@@ -1,5 +1,5 @@
 use bencher_json::{
-    Boundary, DateTime, JsonModel, Model, ModelTest, ModelUuid, SampleSize, Window,
+    Boundary, DateTime, Decay, JsonModel, Model, ModelTest, ModelUuid, SampleSize, Window,
 };
 use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl, SelectableHelper};
 use dropshot::HttpError;
@@ -28,6 +28,7 @@ pub struct QueryModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub decay: Option<Decay>,
     pub created: DateTime,
     pub replaced: Option<DateTime>,
 }
@@ -61,6 +62,7 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
             ..
         } = self;
         Model {
@@ -70,6 +72,7 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
         }
     }
 
@@ -83,6 +86,7 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
             created,
             replaced,
             ..
@@ -101,6 +105,7 @@ impl QueryModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
             created,
             replaced,
         }
@@ -118,6 +123,7 @@ pub struct InsertModel {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub decay: Option<Decay>,
     pub created: DateTime,
     pub replaced: Option<DateTime>,
 }
@@ -131,6 +137,7 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
         } = model;
         Self {
             uuid: ModelUuid::new(),
@@ -141,6 +148,7 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
             created: DateTime::now(),
             replaced: None,
         }
@@ -154,6 +162,7 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
             created,
             replaced,
             ..
@@ -167,6 +176,7 @@ impl InsertModel {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
             created,
             replaced,
         }
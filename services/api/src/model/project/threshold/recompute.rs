@@ -0,0 +1,352 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use bencher_boundary::MetricsBoundary;
+use bencher_json::{project::alert::AlertStatus, DateTime, ThresholdUuid};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SelectableHelper};
+use dropshot::HttpError;
+use slog::Logger;
+use tokio::sync::Mutex;
+
+use crate::{
+    config::{flaky::FlakySettings, severity::SeveritySettings, statistics::StatisticsSettings},
+    context::{ApiContext, DbConnection},
+    error::{resource_conflict_err, resource_not_found_err},
+    model::{
+        job::{JobPayload, QueryJob},
+        project::{
+            benchmark::{BenchmarkId, QueryBenchmark},
+            branch::{
+                head::{HeadId, QueryHead},
+                version::{QueryVersion, VersionId},
+                BranchId, QueryBranch,
+            },
+            expectation::Expectations,
+            flaky::QueryFlakyMeasure,
+            measure::MeasureId,
+            report::results::detector::{data::metrics_data, threshold::ThresholdModel},
+            testbed::TestbedId,
+            webhook::InsertWebhookDelivery,
+        },
+    },
+    schema,
+};
+
+use super::{
+    alert::{InsertAlert, QueryAlert},
+    boundary::QueryBoundary,
+    model::QueryModel,
+    ThresholdId,
+};
+
+/// The server settings that are used to detect alerts,
+/// so boundaries can be recomputed outside of a request.
+#[derive(Debug, Clone, Copy)]
+pub struct RecomputeSettings {
+    pub flaky: FlakySettings,
+    pub severity: SeveritySettings,
+    pub statistics: StatisticsSettings,
+}
+
+impl From<&ApiContext> for RecomputeSettings {
+    fn from(context: &ApiContext) -> Self {
+        Self {
+            flaky: context.flaky,
+            severity: context.severity,
+            statistics: context.statistics,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Recomputed {
+    pub boundaries: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub resolved: usize,
+    pub skipped: usize,
+}
+
+type RecomputeMetric = (
+    QueryBoundary,
+    f64,
+    HeadId,
+    TestbedId,
+    BenchmarkId,
+    MeasureId,
+    DateTime,
+    VersionId,
+);
+
+/// Enqueue a job to recompute the boundaries for a threshold over a window of reports.
+pub fn enqueue_recompute(
+    conn: &mut DbConnection,
+    threshold: ThresholdUuid,
+    start_time: Option<DateTime>,
+    end_time: Option<DateTime>,
+) -> Result<(), HttpError> {
+    let payload = JobPayload::RecomputeBoundaries {
+        threshold,
+        start_time,
+        end_time,
+    };
+    QueryJob::enqueue(conn, &payload, None)
+}
+
+/// Enqueue a job to recompute the boundaries for all of the thresholds for a branch and testbed,
+/// for the reports that started at or after the given time.
+/// This is used after a report is deleted or backfilled, as the later boundaries may now be stale.
+pub fn enqueue_recompute_after(
+    conn: &mut DbConnection,
+    branch_id: BranchId,
+    testbed_id: TestbedId,
+    start_time: DateTime,
+) -> Result<(), HttpError> {
+    let thresholds = schema::threshold::table
+        .filter(schema::threshold::branch_id.eq(branch_id))
+        .filter(schema::threshold::testbed_id.eq(testbed_id))
+        .filter(schema::threshold::model_id.is_not_null())
+        .select(schema::threshold::uuid)
+        .load::<ThresholdUuid>(conn)
+        .map_err(resource_not_found_err!(Threshold, (branch_id, testbed_id)))?;
+    for threshold in thresholds {
+        enqueue_recompute(conn, threshold, Some(start_time), None)?;
+    }
+    Ok(())
+}
+
+/// Recompute the boundaries for a threshold over a window of reports, oldest first.
+/// Each boundary is recomputed with the model that was used to create it,
+/// and only the metrics that were available as of its report.
+/// Alerts are then created, updated, or resolved to match the recomputed boundaries.
+pub async fn recompute_boundaries(
+    log: &Logger,
+    conn: &Mutex<DbConnection>,
+    settings: RecomputeSettings,
+    threshold: ThresholdUuid,
+    start_time: Option<DateTime>,
+    end_time: Option<DateTime>,
+) -> Result<Recomputed, HttpError> {
+    let metrics = {
+        let conn = &mut *conn.lock().await;
+        // The threshold may have been deleted since the job was enqueued
+        let Some(threshold_id) = schema::threshold::table
+            .filter(schema::threshold::uuid.eq(threshold))
+            .select(schema::threshold::id)
+            .first::<ThresholdId>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Threshold, threshold))?
+        else {
+            return Ok(Recomputed::default());
+        };
+        recompute_metrics(conn, threshold_id, start_time, end_time)?
+    };
+
+    let mut recomputed = Recomputed::default();
+    let mut models = HashMap::new();
+    for metric in metrics {
+        let conn = &mut *conn.lock().await;
+        // Use the model that was used to create the boundary, not the current threshold model
+        let model_id = metric.0.model_id;
+        let model = match models.entry(model_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let query_model = QueryModel::get(conn, model_id)?;
+                entry.insert(ThresholdModel::from(query_model))
+            },
+        };
+        match recompute_boundary(log, conn, settings, model, metric)? {
+            Some(change) => {
+                recomputed.boundaries += 1;
+                match change {
+                    AlertChange::None => {},
+                    AlertChange::Created => recomputed.created += 1,
+                    AlertChange::Updated => recomputed.updated += 1,
+                    AlertChange::Resolved => recomputed.resolved += 1,
+                }
+            },
+            None => recomputed.skipped += 1,
+        }
+    }
+    Ok(recomputed)
+}
+
+fn recompute_metrics(
+    conn: &mut DbConnection,
+    threshold_id: ThresholdId,
+    start_time: Option<DateTime>,
+    end_time: Option<DateTime>,
+) -> Result<Vec<RecomputeMetric>, HttpError> {
+    let mut query = schema::boundary::table
+        .inner_join(
+            schema::metric::table
+                .inner_join(schema::report_benchmark::table.inner_join(schema::report::table)),
+        )
+        .filter(schema::boundary::threshold_id.eq(threshold_id))
+        .into_boxed();
+    if let Some(start_time) = start_time {
+        query = query.filter(schema::report::start_time.ge(start_time));
+    }
+    if let Some(end_time) = end_time {
+        query = query.filter(schema::report::start_time.le(end_time));
+    }
+    query
+        .order((
+            schema::report::start_time.asc(),
+            schema::report_benchmark::iteration.asc(),
+        ))
+        .select((
+            QueryBoundary::as_select(),
+            schema::metric::value,
+            schema::report::head_id,
+            schema::report::testbed_id,
+            schema::report_benchmark::benchmark_id,
+            schema::metric::measure_id,
+            schema::report::start_time,
+            schema::report::version_id,
+        ))
+        .load::<RecomputeMetric>(conn)
+        .map_err(resource_not_found_err!(Boundary, threshold_id))
+}
+
+enum AlertChange {
+    None,
+    Created,
+    Updated,
+    Resolved,
+}
+
+#[allow(clippy::too_many_lines)]
+fn recompute_boundary(
+    log: &Logger,
+    conn: &mut DbConnection,
+    settings: RecomputeSettings,
+    model: &ThresholdModel,
+    (query_boundary, value, head_id, testbed_id, benchmark_id, measure_id, start_time, version_id): RecomputeMetric,
+) -> Result<Option<AlertChange>, HttpError> {
+    // If the branch was frozen before the report, then only use the metrics from before it was frozen
+    let branch_id = QueryHead::get(conn, head_id)?.branch_id;
+    let as_of = match QueryBranch::frozen_baseline(conn, branch_id)? {
+        Some(frozen_baseline) if frozen_baseline.timestamp() < start_time.timestamp() => {
+            frozen_baseline
+        },
+        _ => start_time,
+    };
+    let metrics_data = metrics_data(
+        log,
+        conn,
+        head_id,
+        testbed_id,
+        benchmark_id,
+        measure_id,
+        model,
+        Some(as_of),
+    )?;
+    let boundary = match MetricsBoundary::new(
+        log,
+        value,
+        &metrics_data,
+        model.test,
+        settings
+            .statistics
+            .min_sample_size(model.test, model.min_sample_size),
+        model.lower_boundary,
+        model.upper_boundary,
+        model.decay,
+    ) {
+        Ok(boundary) => boundary,
+        Err(e) => {
+            slog::warn!(
+                log,
+                "Skipping boundary ({}) that could not be recomputed: {e}",
+                query_boundary.uuid
+            );
+            return Ok(None);
+        },
+    };
+
+    // The boundary is updated in place, so any alerts for it stay attached
+    diesel::update(schema::boundary::table.filter(schema::boundary::id.eq(query_boundary.id)))
+        .set((
+            schema::boundary::baseline.eq(boundary.limits.baseline),
+            schema::boundary::lower_limit.eq(boundary.limits.lower.map(f64::from)),
+            schema::boundary::upper_limit.eq(boundary.limits.upper.map(f64::from)),
+        ))
+        .execute(conn)
+        .map_err(resource_conflict_err!(Boundary, query_boundary.uuid))?;
+
+    let query_alert = schema::alert::table
+        .filter(schema::alert::boundary_id.eq(query_boundary.id))
+        .select(QueryAlert::as_select())
+        .first(conn)
+        .optional()
+        .map_err(resource_not_found_err!(Alert, query_boundary.uuid))?;
+
+    let change = match (boundary.outlier, query_alert) {
+        (Some(boundary_limit), Some(query_alert)) => {
+            let exceedance = boundary.limits.exceedance(value, boundary_limit);
+            let severity = settings.severity.severity(exceedance);
+            if query_alert.boundary_limit == boundary_limit && query_alert.severity == severity {
+                AlertChange::None
+            } else {
+                diesel::update(schema::alert::table.filter(schema::alert::id.eq(query_alert.id)))
+                    .set((
+                        schema::alert::boundary_limit.eq(boundary_limit),
+                        schema::alert::severity.eq(severity),
+                        schema::alert::modified.eq(DateTime::now()),
+                    ))
+                    .execute(conn)
+                    .map_err(resource_conflict_err!(Alert, query_alert))?;
+                AlertChange::Updated
+            }
+        },
+        (Some(boundary_limit), None) => {
+            // Flaky benchmark measures only get new alerts if alerts are enabled for flaky benchmarks
+            let query_benchmark = QueryBenchmark::get(conn, benchmark_id)?;
+            let is_flaky = query_benchmark.flaky.is_some()
+                || QueryFlakyMeasure::exists(conn, benchmark_id, measure_id)?;
+            if is_flaky && !settings.flaky.alerts {
+                AlertChange::None
+            } else if is_expected(conn, head_id, start_time, version_id, &query_benchmark)? {
+                // Benchmarks within an expected regression window do not get new alerts
+                AlertChange::None
+            } else {
+                let exceedance = boundary.limits.exceedance(value, boundary_limit);
+                let severity = settings.severity.severity(exceedance);
+                let alert_id = InsertAlert::from_boundary(
+                    conn,
+                    query_boundary.uuid,
+                    boundary_limit,
+                    severity,
+                )?;
+                InsertWebhookDelivery::enqueue(conn, alert_id)?;
+                AlertChange::Created
+            }
+        },
+        // The metric is no longer an outlier, so resolve its active alert
+        (None, Some(query_alert)) if matches!(query_alert.status, AlertStatus::Active) => {
+            diesel::update(schema::alert::table.filter(schema::alert::id.eq(query_alert.id)))
+                .set((
+                    schema::alert::status.eq(AlertStatus::Dismissed),
+                    schema::alert::modified.eq(DateTime::now()),
+                ))
+                .execute(conn)
+                .map_err(resource_conflict_err!(Alert, query_alert))?;
+            AlertChange::Resolved
+        },
+        (None, _) => AlertChange::None,
+    };
+    Ok(Some(change))
+}
+
+fn is_expected(
+    conn: &mut DbConnection,
+    head_id: HeadId,
+    start_time: DateTime,
+    version_id: VersionId,
+    query_benchmark: &QueryBenchmark,
+) -> Result<bool, HttpError> {
+    let branch_id = QueryHead::get(conn, head_id)?.branch_id;
+    let version_number = QueryVersion::get(conn, version_id)?.number;
+    Expectations::for_report(conn, branch_id, start_time, version_number)
+        .map(|expectations| expectations.is_expected(&query_benchmark.name))
+}
@@ -0,0 +1,349 @@
+use bencher_json::{project::ProjectRole, DateTime, ResourceName};
+use chrono::{Duration, Utc};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    context::{transaction, DbConnection},
+    error::{bad_request_error, conflict_error, resource_conflict_err, resource_not_found_err},
+    model::{
+        organization::{OrganizationId, QueryOrganization},
+        user::UserId,
+    },
+    schema::{self, unclaimed_project as unclaimed_project_table},
+};
+
+use super::{project_role::InsertProjectRole, ProjectId, QueryProject};
+
+crate::util::typed_id::typed_id!(UnclaimedProjectId);
+
+/// The claim token for an unclaimed project is valid for 30 days
+pub const CLAIM_TOKEN_TTL: u32 = 30 * 24 * 60 * 60;
+
+/// A project that was created by an anonymous report.
+/// It stays unclaimed until an organization claims it with its claim token.
+/// Once its claim token has expired, it is removed along with its organization.
+#[derive(Debug, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = unclaimed_project_table)]
+pub struct QueryUnclaimedProject {
+    pub id: UnclaimedProjectId,
+    pub project_id: ProjectId,
+    pub created: DateTime,
+}
+
+impl QueryUnclaimedProject {
+    pub fn get_for_project(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::unclaimed_project::table
+            .filter(schema::unclaimed_project::project_id.eq(project_id))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(UnclaimedProject, project_id))
+    }
+
+    pub fn delete(&self, conn: &mut DbConnection) -> Result<(), HttpError> {
+        diesel::delete(
+            schema::unclaimed_project::table.filter(schema::unclaimed_project::id.eq(self.id)),
+        )
+        .execute(conn)
+        .map_err(resource_conflict_err!(UnclaimedProject, self))?;
+        Ok(())
+    }
+
+    /// The claim token for the unclaimed project has expired, so it can no longer be claimed.
+    pub fn is_expired(&self) -> bool {
+        self.created < Self::prune_cutoff()
+    }
+
+    /// Unclaimed projects created before the cutoff have an expired claim token.
+    pub fn prune_cutoff() -> DateTime {
+        DateTime::from(Utc::now() - Duration::seconds(CLAIM_TOKEN_TTL.into()))
+    }
+
+    /// Claim the unclaimed project for the organization, optionally with a new name,
+    /// and connect the user to the project as a `Maintainer`.
+    /// The organization that was created for the unclaimed project is deleted.
+    /// This is all done in a single transaction, so a project is never left partially claimed.
+    pub fn claim(
+        conn: &mut DbConnection,
+        query_project: &QueryProject,
+        query_organization: &QueryOrganization,
+        name: Option<ResourceName>,
+        user_id: UserId,
+    ) -> Result<(), HttpError> {
+        transaction(conn, |conn| {
+            let Some(query_unclaimed_project) = Self::get_for_project(conn, query_project.id)?
+            else {
+                return Err(conflict_error(format!(
+                    "Project ({project}) has already been claimed",
+                    project = query_project.slug,
+                )));
+            };
+            // An unclaimed project is the only project in its organization
+            if query_organization.id == query_project.organization_id {
+                return Err(bad_request_error(format!(
+                    "Project ({project}) can not be claimed for its own organization ({organization})",
+                    project = query_project.slug,
+                    organization = query_organization.slug,
+                )));
+            }
+
+            // Project names must be unique within an organization
+            let name = name.unwrap_or_else(|| query_project.name.clone());
+            let name_conflict = schema::project::table
+                .filter(schema::project::organization_id.eq(query_organization.id))
+                .filter(schema::project::name.eq(&name))
+                .count()
+                .get_result::<i64>(conn)
+                .map_err(resource_not_found_err!(
+                    Project,
+                    (query_organization, &name)
+                ))?;
+            if name_conflict > 0 {
+                return Err(conflict_error(format!(
+                    "Organization ({organization}) already has a project named ({name}). Provide a new name for the project.",
+                    organization = query_organization.slug,
+                )));
+            }
+
+            diesel::update(schema::project::table.filter(schema::project::id.eq(query_project.id)))
+                .set((
+                    schema::project::organization_id.eq(query_organization.id),
+                    schema::project::name.eq(&name),
+                    schema::project::modified.eq(DateTime::now()),
+                ))
+                .execute(conn)
+                .map_err(resource_conflict_err!(
+                    Project,
+                    (query_project, query_organization)
+                ))?;
+            query_unclaimed_project.delete(conn)?;
+            // The organization for an unclaimed project has no members and no other projects
+            Self::delete_organization(conn, query_project.organization_id)?;
+
+            let timestamp = DateTime::now();
+            let insert_proj_role = InsertProjectRole {
+                user_id,
+                project_id: query_project.id,
+                role: ProjectRole::Maintainer,
+                created: timestamp,
+                modified: timestamp,
+            };
+            diesel::insert_into(schema::project_role::table)
+                .values(&insert_proj_role)
+                .execute(conn)
+                .map_err(resource_conflict_err!(ProjectRole, insert_proj_role))?;
+
+            Ok(())
+        })
+    }
+
+    /// Delete the organization that was created for an unclaimed project.
+    /// This also deletes the unclaimed project, if it has not been claimed.
+    pub fn delete_organization(
+        conn: &mut DbConnection,
+        organization_id: OrganizationId,
+    ) -> Result<(), HttpError> {
+        diesel::delete(
+            schema::organization::table.filter(schema::organization::id.eq(organization_id)),
+        )
+        .execute(conn)
+        .map_err(resource_conflict_err!(Organization, organization_id))?;
+        Ok(())
+    }
+
+    /// Delete the unclaimed projects that were created before the cutoff,
+    /// along with the organizations that were created for them.
+    /// This frees up their slugs to be used again.
+    pub fn prune(conn: &mut DbConnection, cutoff: DateTime) -> Result<usize, HttpError> {
+        transaction(conn, |conn| {
+            let organization_ids = schema::unclaimed_project::table
+                .inner_join(schema::project::table)
+                .filter(schema::unclaimed_project::created.lt(cutoff))
+                .select(schema::project::organization_id)
+                .load::<OrganizationId>(conn)
+                .map_err(resource_not_found_err!(UnclaimedProject, cutoff))?;
+            diesel::delete(
+                schema::organization::table
+                    .filter(schema::organization::id.eq_any(&organization_ids)),
+            )
+            .execute(conn)
+            .map_err(resource_conflict_err!(UnclaimedProject, cutoff))
+        })
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = unclaimed_project_table)]
+pub struct InsertUnclaimedProject {
+    pub project_id: ProjectId,
+    pub created: DateTime,
+}
+
+impl InsertUnclaimedProject {
+    pub fn new(project_id: ProjectId) -> Self {
+        Self {
+            project_id,
+            created: DateTime::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{project::Visibility, DateTime};
+    use chrono::{Duration, Utc};
+    use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+
+    use crate::{
+        context::DbConnection,
+        model::{
+            organization::QueryOrganization,
+            project::{ProjectId, QueryProject},
+        },
+        schema,
+        util::test_util::{create_organization, create_project, create_user, setup_db},
+    };
+
+    use super::{
+        InsertUnclaimedProject, QueryUnclaimedProject, UnclaimedProjectId, CLAIM_TOKEN_TTL,
+    };
+
+    fn create_unclaimed_project(
+        conn: &mut DbConnection,
+        slug: &str,
+        created: DateTime,
+    ) -> QueryProject {
+        let org = create_organization(conn, slug);
+        let project = create_project(conn, &org, slug, Visibility::Public);
+        diesel::insert_into(schema::unclaimed_project::table)
+            .values(&InsertUnclaimedProject {
+                project_id: project.id,
+                created,
+            })
+            .execute(conn)
+            .unwrap();
+        project
+    }
+
+    fn organization_exists(conn: &mut DbConnection, query_project: &QueryProject) -> bool {
+        QueryOrganization::get(conn, query_project.organization_id).is_ok()
+    }
+
+    fn days_ago(days: i64) -> DateTime {
+        DateTime::from(Utc::now() - Duration::days(days))
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let unclaimed = |created| QueryUnclaimedProject {
+            id: UnclaimedProjectId::default(),
+            project_id: ProjectId::default(),
+            created,
+        };
+        let expired_days = i64::from(CLAIM_TOKEN_TTL / (24 * 60 * 60)) + 1;
+        assert!(unclaimed(days_ago(expired_days)).is_expired());
+        assert!(!unclaimed(days_ago(1)).is_expired());
+        assert!(!unclaimed(DateTime::now()).is_expired());
+    }
+
+    #[test]
+    fn test_claim() {
+        let mut conn = setup_db();
+        let user = create_user(&mut conn, "Muriel Bagge");
+        let org = create_organization(&mut conn, "Organization");
+        let project = create_unclaimed_project(&mut conn, "unclaimed", DateTime::now());
+
+        // A project can not be claimed for its own organization
+        let own_org = QueryOrganization::get(&mut conn, project.organization_id).unwrap();
+        assert!(
+            QueryUnclaimedProject::claim(&mut conn, &project, &own_org, None, user.id).is_err()
+        );
+
+        QueryUnclaimedProject::claim(
+            &mut conn,
+            &project,
+            &org,
+            Some("Claimed".parse().unwrap()),
+            user.id,
+        )
+        .unwrap();
+        let claimed = QueryProject::get(&mut conn, project.id).unwrap();
+        assert_eq!(claimed.organization_id, org.id);
+        assert_eq!(claimed.name.as_ref(), "Claimed");
+        assert!(
+            QueryUnclaimedProject::get_for_project(&mut conn, project.id)
+                .unwrap()
+                .is_none()
+        );
+        assert!(!organization_exists(&mut conn, &project));
+        let roles = schema::project_role::table
+            .filter(schema::project_role::project_id.eq(project.id))
+            .filter(schema::project_role::user_id.eq(user.id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .unwrap();
+        assert_eq!(roles, 1);
+
+        // A project can only be claimed once
+        let other_org = create_organization(&mut conn, "Other Organization");
+        assert!(
+            QueryUnclaimedProject::claim(&mut conn, &claimed, &other_org, None, user.id).is_err()
+        );
+    }
+
+    #[test]
+    fn test_claim_name_conflict() {
+        let mut conn = setup_db();
+        let user = create_user(&mut conn, "Muriel Bagge");
+        let org = create_organization(&mut conn, "Organization");
+        create_project(&mut conn, &org, "unclaimed", Visibility::Public);
+        let project = create_unclaimed_project(&mut conn, "unclaimed-project", DateTime::now());
+        let project_name = project.name.clone();
+        diesel::update(schema::project::table.filter(schema::project::id.eq(project.id)))
+            .set(schema::project::name.eq("unclaimed"))
+            .execute(&mut conn)
+            .unwrap();
+        let project = QueryProject::get(&mut conn, project.id).unwrap();
+
+        // Nothing is changed if the claim fails
+        assert!(QueryUnclaimedProject::claim(&mut conn, &project, &org, None, user.id).is_err());
+        let unchanged = QueryProject::get(&mut conn, project.id).unwrap();
+        assert_eq!(unchanged.organization_id, project.organization_id);
+        assert!(
+            QueryUnclaimedProject::get_for_project(&mut conn, project.id)
+                .unwrap()
+                .is_some()
+        );
+        assert!(organization_exists(&mut conn, &project));
+
+        // The project can be claimed with a new name
+        QueryUnclaimedProject::claim(&mut conn, &project, &org, Some(project_name), user.id)
+            .unwrap();
+        let claimed = QueryProject::get(&mut conn, project.id).unwrap();
+        assert_eq!(claimed.organization_id, org.id);
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut conn = setup_db();
+        let expired = create_unclaimed_project(&mut conn, "expired", days_ago(31));
+        let fresh = create_unclaimed_project(&mut conn, "fresh", days_ago(1));
+        let org = create_organization(&mut conn, "Organization");
+        let claimed = create_project(&mut conn, &org, "claimed", Visibility::Public);
+
+        let pruned =
+            QueryUnclaimedProject::prune(&mut conn, QueryUnclaimedProject::prune_cutoff()).unwrap();
+        assert_eq!(pruned, 1);
+        assert!(QueryProject::get(&mut conn, expired.id).is_err());
+        assert!(!organization_exists(&mut conn, &expired));
+        assert!(QueryProject::get(&mut conn, fresh.id).is_ok());
+        assert!(organization_exists(&mut conn, &fresh));
+        assert!(QueryProject::get(&mut conn, claimed.id).is_ok());
+
+        // The slug of the expired project can be used again
+        create_unclaimed_project(&mut conn, "expired", DateTime::now());
+    }
+}
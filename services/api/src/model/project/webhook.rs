@@ -0,0 +1,605 @@
+use std::{fmt, net::SocketAddr, sync::Arc, time::Duration};
+
+use bencher_json::{
+    is_public_ip,
+    project::{
+        alert::{AlertSeverity, AlertStatus},
+        boundary::BoundaryLimit,
+        webhook::{JsonWebhookAlertGroup, JsonWebhookGroupAlert, WebhookEvent},
+    },
+    AlertUuid, BenchmarkName, BranchName, DateTime, JsonSavedQueryPayload, JsonWebhook,
+    JsonWebhookDelivery, JsonWebhookPayload, ProjectUuid, ResourceName, Secret, Url,
+    WebhookDeliveryUuid,
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+use hmac::{Hmac, Mac};
+use http::StatusCode;
+use hyper::client::connect::dns::Name;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::{
+    context::DbConnection,
+    error::{issue_error, resource_conflict_err, resource_not_found_err},
+    model::{
+        job::{JobPayload, QueryJob},
+        project::threshold::alert::{AlertGroupId, AlertId, QueryAlert, QueryAlertGroup},
+    },
+    schema::{self, webhook as webhook_table, webhook_delivery as webhook_delivery_table},
+    util::fn_get::fn_from_uuid,
+};
+
+use super::{ProjectId, QueryProject};
+
+crate::util::typed_id::typed_id!(WebhookId);
+crate::util::typed_id::typed_id!(WebhookDeliveryId);
+
+const EVENT_HEADER: &str = "X-Bencher-Event";
+const DELIVERY_HEADER: &str = "X-Bencher-Delivery";
+const SIGNATURE_HEADER: &str = "X-Bencher-Signature-256";
+const SIGNATURE_PREFIX: &str = "sha256=";
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Webhook URLs are provided by users, so a webhook must not be able to reach the internal network of the API server.
+// Hosts are only connected to at their publicly routable addresses, and redirects are not followed.
+// Proxies are not used, as they would resolve the host instead.
+// If the client can not be built, then every delivery fails with the error instead of panicking.
+static WEBHOOK_CLIENT: Lazy<Result<reqwest::Client, String>> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .no_proxy()
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .map_err(|e| format!("Failed to build webhook client: {e}"))
+});
+
+/// Resolve a webhook host to only its publicly routable addresses
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> reqwest::dns::Resolving {
+        Box::pin(resolve_public(name.as_str().to_owned()))
+    }
+}
+
+async fn resolve_public(
+    host: String,
+) -> Result<reqwest::dns::Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    let addrs = tokio::net::lookup_host((host.as_str(), 0))
+        .await?
+        .filter(|addr| is_public_ip(addr.ip()))
+        .collect::<Vec<SocketAddr>>();
+    if addrs.is_empty() {
+        Err(format!("Webhook host ({host}) does not resolve to a publicly routable address").into())
+    } else {
+        Ok(Box::new(addrs.into_iter()))
+    }
+}
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = webhook_table)]
+pub struct QueryWebhook {
+    pub id: WebhookId,
+    pub project_id: ProjectId,
+    pub url: Url,
+    pub secret: String,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryWebhook {
+    pub fn get_for_project(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::webhook::table
+            .filter(schema::webhook::project_id.eq(project_id))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(Webhook, project_id))
+    }
+
+    /// The hex encoded HMAC SHA-256 digest of the payload, using the webhook secret as the key.
+    /// This is the same scheme that GitHub uses, so receivers can reuse their existing verification.
+    fn sign(&self, payload: &[u8]) -> Option<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes()).ok()?;
+        mac.update(payload);
+        Some(format!(
+            "{SIGNATURE_PREFIX}{}",
+            hex::encode(mac.finalize().into_bytes())
+        ))
+    }
+
+    /// The secret is only included when the webhook is created or replaced.
+    pub fn into_json(self, query_project: &QueryProject, secret: Option<Secret>) -> JsonWebhook {
+        let Self {
+            url,
+            created,
+            modified,
+            ..
+        } = self;
+        JsonWebhook {
+            project: query_project.uuid,
+            url,
+            secret,
+            created,
+            modified,
+        }
+    }
+
+    /// Serialize and sign the payload
+    fn signed_body<T>(&self, payload: &T) -> Result<(String, String), HttpError>
+    where
+        T: Serialize + fmt::Debug,
+    {
+        let body = serde_json::to_string(payload).map_err(|e| {
+            issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to serialize webhook payload",
+                &format!("Failed to serialize webhook payload: {payload:?}"),
+                e,
+            )
+        })?;
+        let Some(signature) = self.sign(body.as_bytes()) else {
+            return Err(issue_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to sign webhook payload",
+                &format!("Failed to sign webhook ({id}) payload", id = self.id),
+                "Invalid HMAC key",
+            ));
+        };
+        Ok((body, signature))
+    }
+
+    /// Build the signed request for a saved query summary.
+    /// Unlike alerts, a summary is not tracked as a webhook delivery,
+    /// so it is only retried along with the job that sent it.
+    pub fn saved_query_request(
+        self,
+        payload: &JsonSavedQueryPayload,
+    ) -> Result<SavedQueryWebhookRequest, HttpError> {
+        let (body, signature) = self.signed_body(payload)?;
+        Ok(SavedQueryWebhookRequest {
+            delivery_uuid: WebhookDeliveryUuid::new(),
+            url: self.url,
+            body,
+            signature,
+        })
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = webhook_table)]
+pub struct InsertWebhook {
+    pub project_id: ProjectId,
+    pub url: Url,
+    pub secret: String,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertWebhook {
+    /// Create the webhook for the project with a new random secret,
+    /// or replace the URL and secret if the webhook already exists.
+    /// Returns the new secret.
+    pub fn upsert(
+        conn: &mut DbConnection,
+        project_id: ProjectId,
+        url: Url,
+    ) -> Result<Secret, HttpError> {
+        let secret = Secret::from(uuid::Uuid::new_v4());
+        let now = DateTime::now();
+        if QueryWebhook::get_for_project(conn, project_id)?.is_some() {
+            diesel::update(
+                schema::webhook::table.filter(schema::webhook::project_id.eq(project_id)),
+            )
+            .set((
+                schema::webhook::url.eq(&url),
+                schema::webhook::secret.eq(secret.as_ref()),
+                schema::webhook::modified.eq(now),
+            ))
+            .execute(conn)
+        } else {
+            let insert_webhook = Self {
+                project_id,
+                url,
+                secret: secret.as_ref().to_owned(),
+                created: now,
+                modified: now,
+            };
+            diesel::insert_into(schema::webhook::table)
+                .values(&insert_webhook)
+                .execute(conn)
+        }
+        .map_err(resource_conflict_err!(Webhook, project_id))?;
+        Ok(secret)
+    }
+}
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = webhook_delivery_table)]
+pub struct QueryWebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub uuid: WebhookDeliveryUuid,
+    pub project_id: ProjectId,
+    pub alert_id: AlertId,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub failed: Option<DateTime>,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl QueryWebhookDelivery {
+    fn_from_uuid!(webhook_delivery, WebhookDeliveryUuid, WebhookDelivery);
+
+    pub fn from_delivery_uuid(
+        conn: &mut DbConnection,
+        uuid: WebhookDeliveryUuid,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::webhook_delivery::table
+            .filter(schema::webhook_delivery::uuid.eq(uuid))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(WebhookDelivery, uuid))
+    }
+
+    /// Queue the delivery to be sent again, after it has run out of attempts.
+    pub fn redeliver(&self, conn: &mut DbConnection) -> Result<(), HttpError> {
+        diesel::update(
+            schema::webhook_delivery::table.filter(schema::webhook_delivery::id.eq(self.id)),
+        )
+        .set((
+            schema::webhook_delivery::failed.eq(None::<DateTime>),
+            schema::webhook_delivery::modified.eq(DateTime::now()),
+        ))
+        .execute(conn)
+        .map_err(resource_conflict_err!(WebhookDelivery, self.id))?;
+        QueryJob::enqueue(
+            conn,
+            &JobPayload::Webhook {
+                delivery: self.uuid,
+            },
+            None,
+        )
+    }
+
+    /// Build the signed request for the delivery.
+    /// Returns `None` if the project no longer has a webhook.
+    pub fn request(&self, conn: &mut DbConnection) -> Result<Option<WebhookRequest>, HttpError> {
+        let Some(query_webhook) = QueryWebhook::get_for_project(conn, self.project_id)? else {
+            return Ok(None);
+        };
+        let (
+            alert,
+            group_id,
+            severity,
+            status,
+            limit,
+            project,
+            branch,
+            testbed,
+            benchmark,
+            measure,
+        ) = schema::alert::table
+            .filter(schema::alert::id.eq(self.alert_id))
+            .inner_join(
+                schema::boundary::table
+                    .inner_join(
+                        schema::threshold::table
+                            .inner_join(schema::project::table)
+                            .inner_join(schema::branch::table)
+                            .inner_join(schema::testbed::table)
+                            .inner_join(schema::measure::table),
+                    )
+                    .inner_join(schema::metric::table.inner_join(
+                        schema::report_benchmark::table.inner_join(schema::benchmark::table),
+                    )),
+            )
+            .select((
+                schema::alert::uuid,
+                schema::alert::group_id,
+                schema::alert::severity,
+                schema::alert::status,
+                schema::alert::boundary_limit,
+                schema::project::uuid,
+                schema::branch::name,
+                schema::testbed::name,
+                schema::benchmark::name,
+                schema::measure::name,
+            ))
+            .first::<(
+                AlertUuid,
+                Option<AlertGroupId>,
+                AlertSeverity,
+                AlertStatus,
+                BoundaryLimit,
+                ProjectUuid,
+                BranchName,
+                ResourceName,
+                BenchmarkName,
+                ResourceName,
+            )>(conn)
+            .map_err(resource_not_found_err!(Alert, self.alert_id))?;
+        let group = if let Some(group_id) = group_id {
+            Some(self.alert_group(conn, group_id)?)
+        } else {
+            None
+        };
+        let payload = JsonWebhookPayload {
+            event: WebhookEvent::AlertCreated,
+            delivery: self.uuid,
+            project,
+            alert,
+            severity,
+            status,
+            limit,
+            branch,
+            testbed,
+            benchmark,
+            measure,
+            group,
+        };
+        let (body, signature) = query_webhook.signed_body(&payload)?;
+        Ok(Some(WebhookRequest {
+            delivery_id: self.id,
+            delivery_uuid: self.uuid,
+            attempts: self.attempts,
+            url: query_webhook.url,
+            body,
+            signature,
+        }))
+    }
+
+    /// The other alerts in the correlated alert group of the delivered alert
+    fn alert_group(
+        &self,
+        conn: &mut DbConnection,
+        group_id: AlertGroupId,
+    ) -> Result<JsonWebhookAlertGroup, HttpError> {
+        let uuid = QueryAlertGroup::get_uuid(conn, group_id)?;
+        let alerts = schema::alert::table
+            .filter(schema::alert::group_id.eq(group_id))
+            .filter(schema::alert::id.ne(self.alert_id))
+            .inner_join(
+                schema::boundary::table
+                    .inner_join(schema::threshold::table.inner_join(schema::measure::table)),
+            )
+            .order((schema::alert::severity.desc(), schema::alert::id))
+            .select((
+                schema::alert::uuid,
+                schema::alert::severity,
+                schema::alert::status,
+                schema::alert::boundary_limit,
+                schema::measure::name,
+            ))
+            .load::<(
+                AlertUuid,
+                AlertSeverity,
+                AlertStatus,
+                BoundaryLimit,
+                ResourceName,
+            )>(conn)
+            .map_err(resource_not_found_err!(Alert, group_id))?
+            .into_iter()
+            .map(
+                |(alert, severity, status, limit, measure)| JsonWebhookGroupAlert {
+                    alert,
+                    severity,
+                    status,
+                    limit,
+                    measure,
+                },
+            )
+            .collect();
+        Ok(JsonWebhookAlertGroup { uuid, alerts })
+    }
+
+    pub fn into_json(self, conn: &mut DbConnection) -> Result<JsonWebhookDelivery, HttpError> {
+        let Self {
+            uuid,
+            project_id,
+            alert_id,
+            attempts,
+            response_status,
+            last_error,
+            failed,
+            created,
+            modified,
+            ..
+        } = self;
+        let project = QueryProject::get_uuid(conn, project_id)?;
+        let alert = QueryAlert::get_uuid(conn, alert_id)?;
+        Ok(JsonWebhookDelivery {
+            uuid,
+            project,
+            alert,
+            attempts: u32::try_from(attempts).unwrap_or_default(),
+            response_status: response_status.and_then(|status| u16::try_from(status).ok()),
+            last_error,
+            failed,
+            created,
+            modified,
+        })
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = webhook_delivery_table)]
+pub struct InsertWebhookDelivery {
+    pub uuid: WebhookDeliveryUuid,
+    pub project_id: ProjectId,
+    pub alert_id: AlertId,
+    pub attempts: i32,
+    pub created: DateTime,
+    pub modified: DateTime,
+}
+
+impl InsertWebhookDelivery {
+    /// Queue a delivery of a new alert, if the project has a webhook.
+    pub fn enqueue(conn: &mut DbConnection, alert_id: AlertId) -> Result<(), HttpError> {
+        let project_id = schema::alert::table
+            .filter(schema::alert::id.eq(alert_id))
+            .inner_join(schema::boundary::table.inner_join(schema::threshold::table))
+            .select(schema::threshold::project_id)
+            .first::<ProjectId>(conn)
+            .map_err(resource_not_found_err!(Alert, alert_id))?;
+        if QueryWebhook::get_for_project(conn, project_id)?.is_none() {
+            return Ok(());
+        }
+
+        let now = DateTime::now();
+        let insert_delivery = Self {
+            uuid: WebhookDeliveryUuid::new(),
+            project_id,
+            alert_id,
+            attempts: 0,
+            created: now,
+            modified: now,
+        };
+        diesel::insert_into(schema::webhook_delivery::table)
+            .values(&insert_delivery)
+            .execute(conn)
+            .map_err(resource_conflict_err!(WebhookDelivery, insert_delivery))?;
+        QueryJob::enqueue(
+            conn,
+            &JobPayload::Webhook {
+                delivery: insert_delivery.uuid,
+            },
+            None,
+        )
+    }
+}
+
+/// A signed webhook delivery, ready to be sent without holding the database connection
+#[derive(Debug)]
+pub struct WebhookRequest {
+    delivery_id: WebhookDeliveryId,
+    delivery_uuid: WebhookDeliveryUuid,
+    attempts: i32,
+    url: Url,
+    body: String,
+    signature: String,
+}
+
+impl WebhookRequest {
+    /// Send the delivery, returning the HTTP status code of the response if one was received.
+    pub async fn send(&self) -> (Option<u16>, Result<(), String>) {
+        post(
+            &self.url,
+            WebhookEvent::AlertCreated,
+            self.delivery_uuid,
+            &self.signature,
+            &self.body,
+        )
+        .await
+    }
+
+    /// Record the result of an attempt.
+    /// A successful delivery is removed, and a delivery that ran out of attempts is marked as failed.
+    pub fn record(
+        &self,
+        conn: &mut DbConnection,
+        response_status: Option<u16>,
+        result: &Result<(), String>,
+        last_attempt: bool,
+    ) -> Result<(), HttpError> {
+        match result {
+            Ok(()) => diesel::delete(
+                schema::webhook_delivery::table
+                    .filter(schema::webhook_delivery::id.eq(self.delivery_id)),
+            )
+            .execute(conn),
+            Err(err) => {
+                let now = DateTime::now();
+                diesel::update(
+                    schema::webhook_delivery::table
+                        .filter(schema::webhook_delivery::id.eq(self.delivery_id)),
+                )
+                .set((
+                    schema::webhook_delivery::attempts.eq(self.attempts + 1),
+                    schema::webhook_delivery::response_status.eq(response_status.map(i32::from)),
+                    schema::webhook_delivery::last_error.eq(err),
+                    schema::webhook_delivery::failed.eq(last_attempt.then_some(now)),
+                    schema::webhook_delivery::modified.eq(now),
+                ))
+                .execute(conn)
+            },
+        }
+        .map_err(resource_conflict_err!(WebhookDelivery, self.delivery_id))?;
+        Ok(())
+    }
+}
+
+/// A signed saved query summary, ready to be sent without holding the database connection
+#[derive(Debug)]
+pub struct SavedQueryWebhookRequest {
+    delivery_uuid: WebhookDeliveryUuid,
+    url: Url,
+    body: String,
+    signature: String,
+}
+
+impl SavedQueryWebhookRequest {
+    /// Send the summary, returning the HTTP status code of the response if one was received.
+    pub async fn send(&self) -> (Option<u16>, Result<(), String>) {
+        post(
+            &self.url,
+            WebhookEvent::SavedQuery,
+            self.delivery_uuid,
+            &self.signature,
+            &self.body,
+        )
+        .await
+    }
+}
+
+async fn post(
+    url: &Url,
+    event: WebhookEvent,
+    delivery_uuid: WebhookDeliveryUuid,
+    signature: &str,
+    body: &str,
+) -> (Option<u16>, Result<(), String>) {
+    // Hosts that are IP addresses are not resolved, so they are checked up front
+    if !url.has_public_host() {
+        return (
+            None,
+            Err(format!(
+                "Webhook URL ({url}) does not have a publicly routable host"
+            )),
+        );
+    }
+    let client = match &*WEBHOOK_CLIENT {
+        Ok(client) => client,
+        Err(e) => return (None, Err(e.clone())),
+    };
+    let response = client
+        .post(url.as_ref())
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event.to_string())
+        .header(DELIVERY_HEADER, delivery_uuid.to_string())
+        .header(SIGNATURE_HEADER, signature)
+        .body(body.to_owned())
+        .send()
+        .await;
+    match response {
+        Ok(response) => {
+            let status = response.status();
+            // The response body is not kept, as it may contain anything
+            if status.is_success() {
+                (Some(status.as_u16()), Ok(()))
+            } else {
+                (
+                    Some(status.as_u16()),
+                    Err(format!("Webhook responded with {status}")),
+                )
+            }
+        },
+        Err(e) => (None, Err(e.to_string())),
+    }
+}
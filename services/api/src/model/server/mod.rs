@@ -1,14 +1,18 @@
 #![cfg(feature = "plus")]
 
-use std::{cmp, sync::Arc};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 use bencher_json::{DateTime, JsonServer, JsonServerStats, PlanLevel, ServerUuid, BENCHER_API_URL};
 use bencher_license::Licensor;
-use chrono::{Duration, Utc};
+use chrono::{NaiveDate, Utc};
 use diesel::RunQueryDsl;
 use dropshot::HttpError;
 use once_cell::sync::Lazy;
 use slog::Logger;
+use tokio::sync::Mutex;
 
 use crate::{
     config::plus::StatsSettings,
@@ -63,97 +67,6 @@ impl QueryServer {
         }
     }
 
-    pub fn spawn_stats(
-        self,
-        log: Logger,
-        conn: Arc<tokio::sync::Mutex<DbConnection>>,
-        stats: StatsSettings,
-        licensor: Option<Licensor>,
-        messenger: Option<Messenger>,
-    ) {
-        tokio::spawn(async move {
-            let StatsSettings { offset, enabled } = stats;
-            let mut violations = 0;
-            #[allow(clippy::infinite_loop)]
-            loop {
-                let now = Utc::now().naive_utc().time();
-                let sleep_time = match now.cmp(&offset) {
-                    cmp::Ordering::Less => offset - now,
-                    cmp::Ordering::Equal => Duration::days(1),
-                    cmp::Ordering::Greater => Duration::days(1) - (now - offset),
-                }
-                .to_std()
-                .unwrap_or(std::time::Duration::from_secs(24 * 60 * 60));
-                tokio::time::sleep(sleep_time).await;
-
-                let conn = &mut *conn.lock().await;
-
-                if enabled {
-                    slog::info!(log, "Sending stats at {}", Utc::now());
-                } else if let Some(licensor) = licensor.as_ref() {
-                    match LicenseUsage::get_for_server(conn, licensor, Some(PlanLevel::Team)) {
-                        Ok(license_usages) if license_usages.is_empty() => {
-                            violations += 1;
-                            // Be kind. Allow for a seven day grace period.
-                            slog::warn!(log, "Sending stats is disabled, but there is no valid Bencher Plus license key! This is violation #{violations} of the Bencher License: https://bencher.dev/legal/license");
-                            if let Some(remaining) = LICENSE_GRACE_PERIOD.checked_sub(violations) {
-                                slog::warn!(log, "You have {remaining} days remaining in your Bencher License grace period. Please purchase a license key: https://bencher.dev/pricing");
-                                continue;
-                            }
-                            slog::warn!(log, "Sending stats at {}. Please purchase a license key: https://bencher.dev/pricing",  Utc::now());
-                        },
-                        Ok(_) => {
-                            slog::debug!(log, "Sending stats is disabled");
-                            continue;
-                        },
-                        Err(e) => {
-                            slog::error!(log, "Failed to check stats: {e}");
-                            continue;
-                        },
-                    }
-                } else {
-                    let err = "Bencher Cloud server stats are disabled!";
-                    slog::error!(log, "{err}");
-                    sentry::capture_message(err, sentry::Level::Error);
-                }
-
-                let json_stats = match self.get_stats(conn, messenger.is_some()) {
-                    Ok(json_stats) => json_stats,
-                    Err(e) => {
-                        slog::error!(log, "Failed to get stats: {e}");
-                        continue;
-                    },
-                };
-                let json_stats_str = match serde_json::to_string_pretty(&json_stats) {
-                    Ok(json_stats_str) => json_stats_str,
-                    Err(e) => {
-                        slog::error!(log, "Failed to serialize stats: {e}");
-                        continue;
-                    },
-                };
-
-                if let Some(messenger) = messenger.as_ref() {
-                    slog::info!(log, "Bencher Cloud Stats: {json_stats_str:?}");
-                    if let Err(e) =
-                        Self::send_stats_to_backend(&log, conn, messenger, &json_stats_str, None)
-                    {
-                        slog::error!(log, "Failed to send stats: {e}");
-                    }
-                } else {
-                    let client = reqwest::Client::new();
-                    if let Err(e) = client
-                        .post(BENCHER_STATS_API_URL.clone())
-                        .body(json_stats_str)
-                        .send()
-                        .await
-                    {
-                        slog::error!(log, "Failed to send stats: {e}");
-                    }
-                }
-            }
-        });
-    }
-
     pub fn get_stats(
         self,
         conn: &mut DbConnection,
@@ -184,7 +97,7 @@ impl QueryServer {
                     server_stats: server_stats.to_owned(),
                 })),
             };
-            messenger.send(log, message);
+            messenger.send(log, conn, message);
         }
         Ok(())
     }
@@ -199,6 +112,96 @@ impl QueryServer {
     }
 }
 
+/// Sends the daily server stats from the job queue,
+/// so only one API server sends them each day.
+#[derive(Clone)]
+pub struct ServerStatsSender {
+    stats: StatsSettings,
+    licensor: Option<Licensor>,
+    messenger: Option<Messenger>,
+    violations: Arc<AtomicUsize>,
+}
+
+impl ServerStatsSender {
+    /// Bencher Cloud does not need to send stats to itself,
+    /// so it only needs the Messenger.
+    /// Bencher Self-Hosted needs the Licensor in order to check for a valid license if stats are disabled.
+    pub fn new(
+        stats: StatsSettings,
+        licensor: Option<Licensor>,
+        messenger: Option<Messenger>,
+    ) -> Self {
+        Self {
+            stats,
+            licensor,
+            messenger,
+            violations: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The time to send the stats on the given day
+    pub fn run_at(&self, today: NaiveDate) -> DateTime {
+        DateTime::from(today.and_time(self.stats.offset).and_utc())
+    }
+
+    pub async fn send(&self, log: &Logger, conn: &Mutex<DbConnection>) -> Result<(), String> {
+        let json_stats_str = {
+            let conn = &mut *conn.lock().await;
+
+            if self.stats.enabled {
+                slog::info!(log, "Sending stats at {}", Utc::now());
+            } else if let Some(licensor) = self.licensor.as_ref() {
+                let license_usages =
+                    LicenseUsage::get_for_server(conn, licensor, Some(PlanLevel::Team))
+                        .map_err(|e| format!("Failed to check stats: {e}"))?;
+                if !license_usages.is_empty() {
+                    slog::debug!(log, "Sending stats is disabled");
+                    return Ok(());
+                }
+                let violations = self.violations.fetch_add(1, Ordering::Relaxed) + 1;
+                // Be kind. Allow for a seven day grace period.
+                slog::warn!(log, "Sending stats is disabled, but there is no valid Bencher Plus license key! This is violation #{violations} of the Bencher License: https://bencher.dev/legal/license");
+                if let Some(remaining) = LICENSE_GRACE_PERIOD.checked_sub(violations) {
+                    slog::warn!(log, "You have {remaining} days remaining in your Bencher License grace period. Please purchase a license key: https://bencher.dev/pricing");
+                    return Ok(());
+                }
+                slog::warn!(log, "Sending stats at {}. Please purchase a license key: https://bencher.dev/pricing",  Utc::now());
+            } else {
+                let err = "Bencher Cloud server stats are disabled!";
+                slog::error!(log, "{err}");
+                sentry::capture_message(err, sentry::Level::Error);
+            }
+
+            let json_stats = QueryServer::get_server(conn)
+                .and_then(|query_server| query_server.get_stats(conn, self.messenger.is_some()))
+                .map_err(|e| format!("Failed to get stats: {e}"))?;
+            let json_stats_str = serde_json::to_string_pretty(&json_stats)
+                .map_err(|e| format!("Failed to serialize stats: {e}"))?;
+
+            if let Some(messenger) = self.messenger.as_ref() {
+                slog::info!(log, "Bencher Cloud Stats: {json_stats_str:?}");
+                return QueryServer::send_stats_to_backend(
+                    log,
+                    conn,
+                    messenger,
+                    &json_stats_str,
+                    None,
+                )
+                .map_err(|e| format!("Failed to send stats: {e}"));
+            }
+            json_stats_str
+        };
+
+        reqwest::Client::new()
+            .post(BENCHER_STATS_API_URL.clone())
+            .body(json_stats_str)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send stats: {e}"))?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, diesel::Insertable)]
 #[diesel(table_name = server_table)]
 pub struct InsertServer {
@@ -0,0 +1,37 @@
+use bencher_json::{DateTime, JsonTelemetryStats};
+use diesel::{dsl::count, ExpressionMethods, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{context::DbConnection, error::resource_not_found_err, schema, API_VERSION};
+
+const THIS_WEEK: i64 = 7 * 24 * 60 * 60;
+
+/// Get the anonymous aggregate stats that are sent when telemetry is enabled.
+/// These must never include any identifiers or names.
+#[allow(clippy::cast_sign_loss)]
+pub fn get_telemetry_stats(conn: &mut DbConnection) -> Result<JsonTelemetryStats, HttpError> {
+    let this_week = DateTime::now().timestamp() - THIS_WEEK;
+
+    let projects = schema::project::table
+        .select(count(schema::project::id))
+        .first::<i64>(conn)
+        .map_err(resource_not_found_err!(Project))?;
+
+    let reports = schema::report::table
+        .select(count(schema::report::id))
+        .first::<i64>(conn)
+        .map_err(resource_not_found_err!(Report))?;
+
+    let weekly_reports = schema::report::table
+        .filter(schema::report::created.ge(this_week))
+        .select(count(schema::report::id))
+        .first::<i64>(conn)
+        .map_err(resource_not_found_err!(Report))?;
+
+    Ok(JsonTelemetryStats {
+        version: API_VERSION.to_owned(),
+        projects: projects as u64,
+        reports: reports as u64,
+        weekly_reports: weekly_reports as u64,
+    })
+}
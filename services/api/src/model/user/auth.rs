@@ -1,4 +1,7 @@
-use std::ops::Deref;
+use std::{
+    net::{IpAddr, SocketAddr},
+    ops::Deref,
+};
 
 use async_trait::async_trait;
 #[cfg(feature = "plus")]
@@ -18,14 +21,15 @@ use http::StatusCode;
 use oso::{PolarValue, ToPolar};
 
 use crate::{
+    config::socket::SocketPeer,
     conn_lock,
     context::{ApiContext, DbConnection, Rbac},
-    error::{bad_request_error, forbidden_error},
+    error::{bad_request_error, forbidden_error, unauthorized_error},
     model::{organization::OrganizationId, project::ProjectId},
     schema,
 };
 
-use super::{QueryUser, UserId};
+use super::{token::QueryToken, QueryUser, UserId};
 
 pub const BEARER_TOKEN_FORMAT: &str = "Expected format is `Authorization: Bearer <bencher.api.token>`. Where `<bencher.api.token>` is your Bencher API token.";
 
@@ -54,9 +58,11 @@ impl AuthUser {
         context: &ApiContext,
         bearer_token: PubBearerToken,
     ) -> Result<Option<Self>, HttpError> {
-        Ok(if let Some(bearer_token) = bearer_token.0 {
+        Ok(if let Some(bearer_token) = bearer_token.bearer_token {
             Some(Self::from_token(context, bearer_token).await?)
         } else {
+            // Requests without a token must still come through the server socket, if there is one
+            context.socket_peer(bearer_token.remote_addr)?;
             None
         })
     }
@@ -71,8 +77,52 @@ impl AuthUser {
             .map_err(|e| bad_request_error(format!("Failed to validate JSON Web Token: {e}")))?;
         let email = claims.email();
 
+        let socket_peer = context.socket_peer(bearer_token.remote_addr)?;
+        let client_ip = bearer_token.client_ip(context.client_ip_header.as_deref(), socket_peer);
+
         // Hold the connection for all permissions related queries
         let conn = conn_lock!(context);
+        if QueryToken::is_revoked(conn, &bearer_token)? {
+            return Err(unauthorized_error("API token has been revoked"));
+        }
+        if !QueryToken::is_allowed_ip(conn, &bearer_token, client_ip)? {
+            return Err(forbidden_error(if let Some(client_ip) = client_ip {
+                format!("API token is not allowed to be used from this IP address ({client_ip})")
+            } else {
+                "API token is not allowed to be used from a Unix domain socket".to_owned()
+            }));
+        }
+        let mut auth_user = Self::from_email(conn, email)?;
+        let revoked_organizations = QueryToken::revoked_organizations(conn, &bearer_token)?;
+        auth_user.remove_organizations(&revoked_organizations);
+        Ok(auth_user)
+    }
+
+    // An API token that has been revoked for an organization no longer has any role in it or its projects.
+    // It can still do anything that a user without a role could, such as view public projects.
+    fn remove_organizations(&mut self, organization_ids: &[OrganizationId]) {
+        if organization_ids.is_empty() {
+            return;
+        }
+        self.organizations
+            .retain(|org_id| !organization_ids.contains(org_id));
+        for org_id in organization_ids {
+            self.rbac.organizations.remove(&org_id.to_string());
+        }
+        self.projects.retain(|org_project_id| {
+            let revoked = organization_ids.contains(&org_project_id.org_id);
+            if revoked {
+                self.rbac
+                    .projects
+                    .remove(&org_project_id.project_id.to_string());
+            }
+            !revoked
+        });
+    }
+
+    /// Get the permissions for the user with the given email,
+    /// as long as their account is not locked.
+    pub fn from_email(conn: &mut DbConnection, email: &Email) -> Result<Self, HttpError> {
         let mut query_user = QueryUser::get_with_email(conn, email)?;
         if query_user.locked {
             query_user.sanitize();
@@ -267,19 +317,43 @@ impl SharedExtractor for Headers {
 }
 
 // https://github.com/oxidecomputer/cio/blob/master/dropshot-verify-request/src/bearer.rs
-pub struct BearerToken(Jwt);
-
-impl From<Jwt> for BearerToken {
-    fn from(jwt: Jwt) -> Self {
-        Self(jwt)
-    }
+pub struct BearerToken {
+    jwt: Jwt,
+    remote_addr: SocketAddr,
+    headers: Headers,
 }
 
 impl Deref for BearerToken {
     type Target = Jwt;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.jwt
+    }
+}
+
+impl BearerToken {
+    /// The IP address of the client that sent the request.
+    /// If a client IP header is given, then it is trusted to have been set by a reverse proxy.
+    /// For a list of addresses, the last one is used as it was added by the nearest proxy.
+    /// Otherwise, or if the header is missing or invalid, the IP address of the connection is used.
+    /// If the server is behind a socket, then the IP address of the original connection is used.
+    /// A Unix domain socket connection does not have an IP address.
+    pub fn client_ip(
+        &self,
+        client_ip_header: Option<&str>,
+        socket_peer: Option<SocketPeer>,
+    ) -> Option<IpAddr> {
+        let remote_ip = match socket_peer {
+            Some(SocketPeer::Unix) => None,
+            Some(SocketPeer::Tcp(ip)) => Some(ip),
+            None => Some(self.remote_addr.ip()),
+        };
+        client_ip_header
+            .and_then(|header| self.headers.0.get(header))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse().ok())
+            .or(remote_ip)
     }
 }
 
@@ -309,11 +383,16 @@ impl SharedExtractor for BearerToken {
             )));
         };
 
-        token
+        let jwt = token
             .trim()
             .parse::<Jwt>()
-            .map(Into::into)
-            .map_err(|e| bad_request_error(format!("Malformed JSON Web Token: {e}")))
+            .map_err(|e| bad_request_error(format!("Malformed JSON Web Token: {e}")))?;
+
+        Ok(Self {
+            jwt,
+            remote_addr: rqctx.request.remote_addr(),
+            headers,
+        })
     }
 
     fn metadata(_body_content_type: ApiEndpointBodyContentType) -> ExtractorMetadata {
@@ -324,14 +403,20 @@ impl SharedExtractor for BearerToken {
     }
 }
 
-pub struct PubBearerToken(Option<BearerToken>);
+pub struct PubBearerToken {
+    bearer_token: Option<BearerToken>,
+    remote_addr: SocketAddr,
+}
 
 #[async_trait]
 impl SharedExtractor for PubBearerToken {
     async fn from_request<Context: ServerContext>(
         rqctx: &RequestContext<Context>,
     ) -> Result<Self, HttpError> {
-        Ok(Self(BearerToken::from_request(rqctx).await.ok()))
+        Ok(Self {
+            bearer_token: BearerToken::from_request(rqctx).await.ok(),
+            remote_addr: rqctx.request.remote_addr(),
+        })
     }
 
     fn metadata(_body_content_type: ApiEndpointBodyContentType) -> ExtractorMetadata {
@@ -353,6 +438,8 @@ impl From<OrgProjectId> for Project {
         Self {
             organization_id: org_project_id.org_id.to_string(),
             id: org_project_id.project_id.to_string(),
+            // The user has an explicit role for the project
+            internal: false,
         }
     }
 }
@@ -10,7 +10,7 @@ use url::Url;
 
 use crate::{
     context::{Body, DbConnection, Message, Messenger, NewUserBody},
-    error::{forbidden_error, resource_conflict_err, resource_not_found_err},
+    error::{forbidden_error, resource_conflict_err, resource_not_found_err, unauthorized_error},
     schema::{self, user as user_table},
     util::{
         fn_get::{fn_get, fn_get_id, fn_get_uuid},
@@ -21,6 +21,7 @@ use crate::{
 
 pub mod admin;
 pub mod auth;
+pub mod prefs;
 pub mod token;
 
 crate::util::typed_id::typed_id!(UserId);
@@ -36,8 +37,11 @@ macro_rules! same_user {
 
 pub(crate) use same_user;
 
-use super::organization::{
-    organization_role::InsertOrganizationRole, InsertOrganization, QueryOrganization,
+use super::{
+    organization::{
+        organization_role::InsertOrganizationRole, InsertOrganization, QueryOrganization,
+    },
+    project::project_role::InsertProjectRole,
 };
 
 #[derive(Debug, Clone, diesel::Queryable)]
@@ -118,12 +122,22 @@ impl QueryUser {
         token_key: &TokenKey,
         invite: &Jwt,
     ) -> Result<(), HttpError> {
-        let insert_org_role = InsertOrganizationRole::from_jwt(conn, token_key, invite, self.id)?;
+        if is_project_invite(token_key, invite)? {
+            let insert_proj_role = InsertProjectRole::from_jwt(conn, token_key, invite, self.id)?;
 
-        diesel::insert_into(schema::organization_role::table)
-            .values(&insert_org_role)
-            .execute(conn)
-            .map_err(resource_conflict_err!(OrganizationRole, insert_org_role))?;
+            diesel::insert_into(schema::project_role::table)
+                .values(&insert_proj_role)
+                .execute(conn)
+                .map_err(resource_conflict_err!(ProjectRole, insert_proj_role))?;
+        } else {
+            let insert_org_role =
+                InsertOrganizationRole::from_jwt(conn, token_key, invite, self.id)?;
+
+            diesel::insert_into(schema::organization_role::table)
+                .values(&insert_org_role)
+                .execute(conn)
+                .map_err(resource_conflict_err!(OrganizationRole, insert_org_role))?;
+        }
 
         Ok(())
     }
@@ -222,7 +236,14 @@ impl InsertUser {
             .map_err(resource_conflict_err!(User, insert_user))?;
         let user_id = QueryUser::get_id(conn, insert_user.uuid)?;
 
-        let insert_org_role = if let Some(invite) = &json_signup.invite {
+        // A project invite only grants access to that one project,
+        // so the user still gets their own organization.
+        let (org_invite, project_invite) = match &json_signup.invite {
+            Some(invite) if is_project_invite(token_key, invite)? => (None, Some(invite)),
+            invite => (invite.as_ref(), None),
+        };
+
+        let insert_org_role = if let Some(invite) = org_invite {
             InsertOrganizationRole::from_jwt(conn, token_key, invite, user_id)?
         } else {
             // Create an organization for the user
@@ -249,6 +270,14 @@ impl InsertUser {
             .execute(conn)
             .map_err(resource_conflict_err!(OrganizationRole, insert_org_role))?;
 
+        if let Some(invite) = project_invite {
+            let insert_proj_role = InsertProjectRole::from_jwt(conn, token_key, invite, user_id)?;
+            diesel::insert_into(schema::project_role::table)
+                .values(&insert_proj_role)
+                .execute(conn)
+                .map_err(resource_conflict_err!(ProjectRole, insert_proj_role))?;
+        }
+
         Ok(insert_user)
     }
 
@@ -277,13 +306,22 @@ impl InsertUser {
                         method: method.to_owned(),
                     })),
                 };
-                messenger.send(log, message);
+                messenger.send(log, conn, message);
             }
         }
         Ok(())
     }
 }
 
+/// Invites are either for an organization or for a single project.
+/// Only an organization invite falls back to the organization invite path,
+/// so an invalid project invite is rejected as such.
+fn is_project_invite(token_key: &TokenKey, invite: &Jwt) -> Result<bool, HttpError> {
+    token_key
+        .is_project_invite(invite)
+        .map_err(unauthorized_error)
+}
+
 #[derive(Debug, Clone, diesel::AsChangeset)]
 #[diesel(table_name = user_table)]
 pub struct UpdateUser {
@@ -0,0 +1,151 @@
+use bencher_json::{
+    DateFormat, DateTime, JsonNewUserPrefs, JsonUserPrefs, ResourceName, TimeZone, UserUuid,
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use dropshot::HttpError;
+
+use crate::{
+    context::DbConnection,
+    error::{resource_conflict_err, resource_not_found_err},
+    model::project::{ProjectId, QueryProject},
+    schema::{self, user_prefs as user_prefs_table},
+};
+
+use super::UserId;
+
+crate::util::typed_id::typed_id!(UserPrefsId);
+
+#[derive(Debug, Clone, diesel::Queryable, diesel::Selectable)]
+#[diesel(table_name = user_prefs_table)]
+pub struct QueryUserPrefs {
+    pub id: UserPrefsId,
+    pub user_id: UserId,
+    pub time_zone: Option<TimeZone>,
+    pub date_format: Option<DateFormat>,
+    pub time_units: Option<ResourceName>,
+    pub throughput_units: Option<ResourceName>,
+    pub size_units: Option<ResourceName>,
+    pub default_project_id: Option<ProjectId>,
+    pub modified: DateTime,
+}
+
+impl QueryUserPrefs {
+    pub fn get_for_user(
+        conn: &mut DbConnection,
+        user_id: UserId,
+    ) -> Result<Option<Self>, HttpError> {
+        schema::user_prefs::table
+            .filter(schema::user_prefs::user_id.eq(user_id))
+            .first::<Self>(conn)
+            .optional()
+            .map_err(resource_not_found_err!(UserPrefs, user_id))
+    }
+
+    /// Get the preferences for the user as JSON.
+    /// If the user has never set any preferences, then all of them are empty.
+    pub fn get_json(
+        conn: &mut DbConnection,
+        user_id: UserId,
+        user_uuid: UserUuid,
+    ) -> Result<JsonUserPrefs, HttpError> {
+        let Some(query_prefs) = Self::get_for_user(conn, user_id)? else {
+            return Ok(JsonUserPrefs {
+                user: user_uuid,
+                time_zone: None,
+                date_format: None,
+                time_units: None,
+                throughput_units: None,
+                size_units: None,
+                default_project: None,
+            });
+        };
+        query_prefs.into_json(conn, user_uuid)
+    }
+
+    fn into_json(
+        self,
+        conn: &mut DbConnection,
+        user_uuid: UserUuid,
+    ) -> Result<JsonUserPrefs, HttpError> {
+        let Self {
+            time_zone,
+            date_format,
+            time_units,
+            throughput_units,
+            size_units,
+            default_project_id,
+            ..
+        } = self;
+        let default_project = default_project_id
+            .map(|project_id| QueryProject::get_uuid(conn, project_id))
+            .transpose()?;
+        Ok(JsonUserPrefs {
+            user: user_uuid,
+            time_zone,
+            date_format,
+            time_units,
+            throughput_units,
+            size_units,
+            default_project,
+        })
+    }
+}
+
+#[derive(Debug, Clone, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = user_prefs_table)]
+#[diesel(treat_none_as_null = true)]
+pub struct InsertUserPrefs {
+    pub user_id: UserId,
+    pub time_zone: Option<TimeZone>,
+    pub date_format: Option<DateFormat>,
+    pub time_units: Option<ResourceName>,
+    pub throughput_units: Option<ResourceName>,
+    pub size_units: Option<ResourceName>,
+    pub default_project_id: Option<ProjectId>,
+    pub modified: DateTime,
+}
+
+impl InsertUserPrefs {
+    /// The default project must already have been resolved from the `JsonNewUserPrefs`.
+    pub fn new(
+        user_id: UserId,
+        json_prefs: JsonNewUserPrefs,
+        default_project_id: Option<ProjectId>,
+    ) -> Self {
+        let JsonNewUserPrefs {
+            time_zone,
+            date_format,
+            time_units,
+            throughput_units,
+            size_units,
+            default_project: _,
+        } = json_prefs;
+        Self {
+            user_id,
+            time_zone,
+            date_format,
+            time_units,
+            throughput_units,
+            size_units,
+            default_project_id,
+            modified: DateTime::now(),
+        }
+    }
+
+    /// Create the preferences for the user, or replace them if they already exist.
+    pub fn upsert(&self, conn: &mut DbConnection) -> Result<(), HttpError> {
+        if QueryUserPrefs::get_for_user(conn, self.user_id)?.is_some() {
+            diesel::update(
+                schema::user_prefs::table.filter(schema::user_prefs::user_id.eq(self.user_id)),
+            )
+            .set(self)
+            .execute(conn)
+        } else {
+            diesel::insert_into(schema::user_prefs::table)
+                .values(self)
+                .execute(conn)
+        }
+        .map_err(resource_conflict_err!(UserPrefs, self))?;
+        Ok(())
+    }
+}
@@ -1,20 +1,27 @@
+use std::net::IpAddr;
+
 use bencher_json::{
-    user::token::JsonUpdateToken, DateTime, JsonNewToken, JsonToken, Jwt, ResourceId, ResourceName,
-    TokenUuid,
+    user::token::JsonUpdateToken, Cidr, DateTime, JsonNewToken, JsonRevokeToken, JsonToken, Jwt,
+    ResourceId, ResourceName, TokenUuid,
 };
 use bencher_token::TokenKey;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use chrono::Duration;
+use diesel::{BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
 use dropshot::HttpError;
 use http::StatusCode;
 
 use crate::{
-    context::{DbConnection, Rbac},
+    context::{transaction, DbConnection, Rbac},
     error::{
-        assert_parentage, bad_request_error, issue_error, resource_not_found_err, BencherResource,
+        assert_parentage, issue_error, resource_conflict_err, resource_not_found_err,
+        BencherResource,
+    },
+    model::{
+        organization::{token_policy::check_token_policies, OrganizationId},
+        user::same_user,
     },
-    model::user::same_user,
     schema,
-    schema::token as token_table,
+    schema::{token as token_table, token_revocation as token_revocation_table},
     util::fn_get::{fn_get, fn_get_id, fn_get_uuid},
 };
 
@@ -31,6 +38,8 @@ pub struct QueryToken {
     pub jwt: Jwt,
     pub creation: DateTime,
     pub expiration: DateTime,
+    pub revoked: Option<DateTime>,
+    pub allowed_ips: Option<TokenAllowedIps>,
 }
 
 impl QueryToken {
@@ -50,6 +59,56 @@ impl QueryToken {
             .map_err(resource_not_found_err!(Token, (user_id, uuid)))
     }
 
+    /// Check to see if an API token has been revoked.
+    /// Tokens that are not stored in the database, such as client tokens, are never revoked.
+    pub fn is_revoked(conn: &mut DbConnection, jwt: &Jwt) -> Result<bool, HttpError> {
+        let now = DateTime::now();
+        schema::token::table
+            .filter(schema::token::jwt.eq(jwt))
+            .filter(schema::token::revoked.le(now))
+            .count()
+            .get_result::<i64>(conn)
+            .map(|count| count > 0)
+            .map_err(resource_not_found_err!(Token, now))
+    }
+
+    /// Get the organizations that an API token has been revoked for.
+    /// The token can still be used for any other organizations.
+    pub fn revoked_organizations(
+        conn: &mut DbConnection,
+        jwt: &Jwt,
+    ) -> Result<Vec<OrganizationId>, HttpError> {
+        let now = DateTime::now();
+        schema::token_revocation::table
+            .inner_join(schema::token::table)
+            .filter(schema::token::jwt.eq(jwt))
+            .filter(schema::token_revocation::revoked.le(now))
+            .select(schema::token_revocation::organization_id)
+            .load(conn)
+            .map_err(resource_not_found_err!(Token, now))
+    }
+
+    /// Check to see if an API token is allowed to be used from an IP address.
+    /// Tokens that do not have an IP allowlist, such as client tokens, can be used from any IP address.
+    pub fn is_allowed_ip(
+        conn: &mut DbConnection,
+        jwt: &Jwt,
+        client_ip: Option<IpAddr>,
+    ) -> Result<bool, HttpError> {
+        schema::token::table
+            .filter(schema::token::jwt.eq(jwt))
+            .select(schema::token::allowed_ips)
+            .first::<Option<TokenAllowedIps>>(conn)
+            .optional()
+            .map(|allowed_ips| {
+                allowed_ips.flatten().map_or(true, |allowed_ips| {
+                    // A connection without an IP address can never be allowed
+                    client_ip.is_some_and(|client_ip| allowed_ips.contains(client_ip))
+                })
+            })
+            .map_err(resource_not_found_err!(Token, client_ip))
+    }
+
     pub fn into_json(self, conn: &mut DbConnection) -> Result<JsonToken, HttpError> {
         let query_user = QueryUser::get(conn, self.user_id)?;
         Ok(self.into_json_for_user(&query_user))
@@ -63,6 +122,8 @@ impl QueryToken {
             jwt,
             creation,
             expiration,
+            revoked,
+            allowed_ips,
             ..
         } = self;
         assert_parentage(
@@ -78,6 +139,8 @@ impl QueryToken {
             token: jwt,
             creation,
             expiration,
+            revoked,
+            allowed_ips: allowed_ips.map(Into::into),
         }
     }
 }
@@ -91,6 +154,7 @@ pub struct InsertToken {
     pub jwt: Jwt,
     pub creation: DateTime,
     pub expiration: DateTime,
+    pub allowed_ips: Option<TokenAllowedIps>,
 }
 
 impl InsertToken {
@@ -102,23 +166,16 @@ impl InsertToken {
         token: JsonNewToken,
         auth_user: &AuthUser,
     ) -> Result<Self, HttpError> {
-        let JsonNewToken { name, ttl } = token;
+        let JsonNewToken {
+            name,
+            ttl,
+            allowed_ips,
+        } = token;
 
         let query_user = QueryUser::from_resource_id(conn, user)?;
         same_user!(auth_user, rbac, query_user.uuid);
 
-        // TODO Custom max TTL
-        let max_ttl = u32::MAX;
-        let ttl = if let Some(ttl) = ttl {
-            if ttl > max_ttl {
-                return Err(bad_request_error(format!(
-                    "Requested TTL ({ttl}) is greater than max ({max_ttl})"
-                )));
-            }
-            ttl
-        } else {
-            max_ttl
-        };
+        let ttl = check_token_policies(conn, rbac, &query_user, auth_user, ttl)?;
 
         let jwt = token_key.new_api_key(query_user.email, ttl).map_err(|e| {
             issue_error(
@@ -145,6 +202,7 @@ impl InsertToken {
             jwt,
             creation: claims.issued_at(),
             expiration: claims.expiration(),
+            allowed_ips: allowed_ips.and_then(TokenAllowedIps::new),
         })
     }
 }
@@ -153,11 +211,277 @@ impl InsertToken {
 #[diesel(table_name = token_table)]
 pub struct UpdateToken {
     pub name: Option<ResourceName>,
+    pub allowed_ips: Option<Option<TokenAllowedIps>>,
 }
 
 impl From<JsonUpdateToken> for UpdateToken {
     fn from(update: JsonUpdateToken) -> Self {
-        let JsonUpdateToken { name } = update;
-        Self { name }
+        let JsonUpdateToken { name, allowed_ips } = update;
+        Self {
+            name,
+            // An empty allowlist removes the IP address restriction
+            allowed_ips: allowed_ips.map(TokenAllowedIps::new),
+        }
+    }
+}
+
+#[derive(Debug, Clone, diesel::AsChangeset)]
+#[diesel(table_name = token_table)]
+pub struct RevokeToken {
+    pub revoked: DateTime,
+}
+
+impl RevokeToken {
+    /// Revoke the token after the grace period.
+    /// If the token is already set to be revoked sooner, then that time is kept.
+    pub fn new(query_token: &QueryToken, json_revoke: &JsonRevokeToken) -> Self {
+        let revoked = Self::revoked(json_revoke);
+        let revoked = query_token
+            .revoked
+            .filter(|already| already.into_inner() < revoked.into_inner())
+            .unwrap_or(revoked);
+        Self { revoked }
+    }
+
+    /// The time at which to revoke a token after the grace period
+    pub fn revoked(json_revoke: &JsonRevokeToken) -> DateTime {
+        let &JsonRevokeToken { after } = json_revoke;
+        let now = DateTime::now();
+        after.map_or(now, |after| {
+            DateTime::from(now.into_inner() + Duration::seconds(after.into()))
+        })
+    }
+
+    /// Revoke the API tokens of all members of an organization at the given time,
+    /// but only for that organization.
+    /// The tokens can still be used for any other organizations that the members belong to.
+    /// Tokens that are already set to be revoked sooner keep that time.
+    /// Returns the number of tokens that were revoked.
+    pub fn revoke_for_organization(
+        conn: &mut DbConnection,
+        organization_id: OrganizationId,
+        revoked: DateTime,
+    ) -> Result<usize, HttpError> {
+        transaction(conn, |conn| {
+            let token_ids = schema::token::table
+                .filter(
+                    schema::token::user_id.eq_any(
+                        schema::organization_role::table
+                            .filter(schema::organization_role::organization_id.eq(organization_id))
+                            .select(schema::organization_role::user_id),
+                    ),
+                )
+                .filter(
+                    schema::token::revoked
+                        .is_null()
+                        .or(schema::token::revoked.gt(revoked)),
+                )
+                .filter(
+                    schema::token::id.ne_all(
+                        schema::token_revocation::table
+                            .filter(schema::token_revocation::organization_id.eq(organization_id))
+                            .filter(schema::token_revocation::revoked.le(revoked))
+                            .select(schema::token_revocation::token_id),
+                    ),
+                )
+                .select(schema::token::id)
+                .load::<TokenId>(conn)
+                .map_err(resource_not_found_err!(Token, (organization_id, revoked)))?;
+
+            for &token_id in &token_ids {
+                let insert_revocation = InsertTokenRevocation {
+                    token_id,
+                    organization_id,
+                    revoked,
+                };
+                let updated = diesel::update(
+                    schema::token_revocation::table
+                        .filter(schema::token_revocation::token_id.eq(token_id))
+                        .filter(schema::token_revocation::organization_id.eq(organization_id)),
+                )
+                .set(schema::token_revocation::revoked.eq(revoked))
+                .execute(conn)
+                .map_err(resource_conflict_err!(Token, insert_revocation))?;
+                if updated > 0 {
+                    continue;
+                }
+                diesel::insert_into(schema::token_revocation::table)
+                    .values(&insert_revocation)
+                    .execute(conn)
+                    .map_err(resource_conflict_err!(Token, insert_revocation))?;
+            }
+
+            Ok(token_ids.len())
+        })
+    }
+}
+
+#[derive(Debug, diesel::Insertable)]
+#[diesel(table_name = token_revocation_table)]
+struct InsertTokenRevocation {
+    token_id: TokenId,
+    organization_id: OrganizationId,
+    revoked: DateTime,
+}
+
+/// The IP addresses or networks that are allowed to use an API token,
+/// stored as a JSON array of CIDR strings.
+#[derive(Debug, Clone, diesel::FromSqlRow, diesel::AsExpression)]
+#[diesel(sql_type = diesel::sql_types::Text)]
+pub struct TokenAllowedIps(Vec<Cidr>);
+
+impl TokenAllowedIps {
+    /// An empty allowlist is not stored, so the token can be used from any IP address
+    fn new(allowed_ips: Vec<Cidr>) -> Option<Self> {
+        (!allowed_ips.is_empty()).then_some(Self(allowed_ips))
+    }
+
+    fn contains(&self, client_ip: IpAddr) -> bool {
+        self.0.iter().any(|cidr| cidr.contains(client_ip))
+    }
+}
+
+impl From<TokenAllowedIps> for Vec<Cidr> {
+    fn from(allowed_ips: TokenAllowedIps) -> Self {
+        allowed_ips.0
+    }
+}
+
+impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for TokenAllowedIps
+where
+    DB: diesel::backend::Backend,
+    for<'a> String: diesel::serialize::ToSql<diesel::sql_types::Text, DB>
+        + Into<<DB::BindCollector<'a> as diesel::query_builder::BindCollector<'a, DB>>::Buffer>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        out.set_value(serde_json::to_string(&self.0)?);
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for TokenAllowedIps
+where
+    DB: diesel::backend::Backend,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        serde_json::from_str(&String::from_sql(bytes)?)
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{organization::member::OrganizationRole, DateTime, Jwt, TokenUuid};
+    use bencher_token::{TokenKey, DEFAULT_SECRET_KEY};
+    use chrono::Duration;
+    use diesel::RunQueryDsl;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        context::DbConnection,
+        model::{
+            organization::{organization_role::InsertOrganizationRole, QueryOrganization},
+            user::QueryUser,
+        },
+        schema,
+        util::test_util::{create_organization, create_user, setup_db},
+    };
+
+    use super::{InsertToken, QueryToken, RevokeToken};
+
+    fn add_member(
+        conn: &mut DbConnection,
+        query_user: &QueryUser,
+        query_organization: &QueryOrganization,
+    ) {
+        let timestamp = DateTime::now();
+        diesel::insert_into(schema::organization_role::table)
+            .values(&InsertOrganizationRole {
+                user_id: query_user.id,
+                organization_id: query_organization.id,
+                role: OrganizationRole::Member,
+                created: timestamp,
+                modified: timestamp,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn create_token(conn: &mut DbConnection, query_user: &QueryUser) -> Jwt {
+        let token_key = TokenKey::new("bencher.dev".to_owned(), &DEFAULT_SECRET_KEY);
+        let jwt = token_key.new_api_key(query_user.email.clone(), 60).unwrap();
+        let claims = token_key.validate_api_key(&jwt).unwrap();
+        diesel::insert_into(schema::token::table)
+            .values(&InsertToken {
+                uuid: TokenUuid::new(),
+                user_id: query_user.id,
+                name: "token".parse().unwrap(),
+                jwt: jwt.clone(),
+                creation: claims.issued_at(),
+                expiration: claims.expiration(),
+                allowed_ips: None,
+            })
+            .execute(conn)
+            .unwrap();
+        jwt
+    }
+
+    #[test]
+    fn test_revoke_for_organization() {
+        let mut conn = setup_db();
+        let org = create_organization(&mut conn, "Organization");
+        let other_org = create_organization(&mut conn, "Other Organization");
+        let alice = create_user(&mut conn, "Alice");
+        let bob = create_user(&mut conn, "Bob");
+        add_member(&mut conn, &alice, &org);
+        add_member(&mut conn, &alice, &other_org);
+        add_member(&mut conn, &bob, &other_org);
+        let alice_token = create_token(&mut conn, &alice);
+        let bob_token = create_token(&mut conn, &bob);
+
+        let revoked = DateTime::now();
+        assert_eq!(
+            RevokeToken::revoke_for_organization(&mut conn, org.id, revoked).unwrap(),
+            1
+        );
+        // The token is only revoked for the organization
+        assert_eq!(
+            QueryToken::revoked_organizations(&mut conn, &alice_token).unwrap(),
+            vec![org.id]
+        );
+        assert!(!QueryToken::is_revoked(&mut conn, &alice_token).unwrap());
+        // Tokens of users that are not members are not revoked
+        assert!(QueryToken::revoked_organizations(&mut conn, &bob_token)
+            .unwrap()
+            .is_empty());
+
+        // A token that is already revoked sooner keeps that time
+        let later = DateTime::from(revoked.into_inner() + Duration::days(1));
+        assert_eq!(
+            RevokeToken::revoke_for_organization(&mut conn, org.id, later).unwrap(),
+            0
+        );
+        assert_eq!(
+            QueryToken::revoked_organizations(&mut conn, &alice_token).unwrap(),
+            vec![org.id]
+        );
+
+        // A token is not revoked until the grace period is over
+        assert_eq!(
+            RevokeToken::revoke_for_organization(&mut conn, other_org.id, later).unwrap(),
+            2
+        );
+        assert!(QueryToken::revoked_organizations(&mut conn, &bob_token)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            QueryToken::revoked_organizations(&mut conn, &alice_token).unwrap(),
+            vec![org.id]
+        );
     }
 }
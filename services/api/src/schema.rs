@@ -5,12 +5,49 @@ diesel::table! {
         id -> Integer,
         uuid -> Text,
         boundary_id -> Integer,
+        group_id -> Nullable<Integer>,
         boundary_limit -> Bool,
+        severity -> Integer,
         status -> Integer,
         modified -> BigInt,
     }
 }
 
+diesel::table! {
+    alert_group (id) {
+        id -> Integer,
+        uuid -> Text,
+        report_benchmark_id -> Integer,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    alert_update (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        user_id -> Integer,
+        status -> Integer,
+        reason -> Text,
+        alerts -> Integer,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    annotation (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        title -> Text,
+        description -> Nullable<Text>,
+        time -> BigInt,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
 diesel::table! {
     benchmark (id) {
         id -> Integer,
@@ -21,6 +58,7 @@ diesel::table! {
         created -> BigInt,
         modified -> BigInt,
         archived -> Nullable<BigInt>,
+        flaky -> Nullable<BigInt>,
     }
 }
 
@@ -48,6 +86,66 @@ diesel::table! {
         created -> BigInt,
         modified -> BigInt,
         archived -> Nullable<BigInt>,
+        frozen_baseline -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    downsample (id) {
+        id -> Integer,
+        metric_id -> Integer,
+        sample_count -> Integer,
+        min_value -> Double,
+        max_value -> Double,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    downsample_mark (id) {
+        id -> Integer,
+        head_id -> Integer,
+        testbed_id -> Integer,
+        cutoff -> BigInt,
+        modified -> BigInt,
+    }
+}
+
+diesel::table! {
+    expectation (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        branch_id -> Integer,
+        pattern -> Text,
+        reason -> Text,
+        start_time -> Nullable<BigInt>,
+        end_time -> Nullable<BigInt>,
+        start_version -> Nullable<Integer>,
+        end_version -> Nullable<Integer>,
+        annotation_id -> Nullable<Integer>,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
+diesel::table! {
+    flaky_measure (id) {
+        id -> Integer,
+        benchmark_id -> Integer,
+        measure_id -> Integer,
+        coefficient_of_variation -> Double,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    github_webhook (id) {
+        id -> Integer,
+        project_id -> Integer,
+        secret -> Text,
+        created -> BigInt,
+        modified -> BigInt,
     }
 }
 
@@ -70,6 +168,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    job (id) {
+        id -> Integer,
+        job_key -> Nullable<Text>,
+        payload -> Text,
+        status -> Integer,
+        attempts -> Integer,
+        max_attempts -> Integer,
+        run_at -> BigInt,
+        lease_owner -> Nullable<Text>,
+        lease_expires -> Nullable<BigInt>,
+        last_error -> Nullable<Text>,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
 diesel::table! {
     measure (id) {
         id -> Integer,
@@ -84,6 +199,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    measure_alias (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        measure_id -> Integer,
+        alias -> Text,
+        created -> BigInt,
+    }
+}
+
 diesel::table! {
     metric (id) {
         id -> Integer,
@@ -107,6 +233,7 @@ diesel::table! {
         window -> Nullable<BigInt>,
         lower_boundary -> Nullable<Double>,
         upper_boundary -> Nullable<Double>,
+        decay -> Nullable<Double>,
         created -> BigInt,
         replaced -> Nullable<BigInt>,
     }
@@ -135,6 +262,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    perf_embed_revocation (id) {
+        id -> Integer,
+        project_id -> Integer,
+        revoked -> BigInt,
+    }
+}
+
 diesel::table! {
     plan (id) {
         id -> Integer,
@@ -206,6 +341,9 @@ diesel::table! {
         slug -> Text,
         url -> Nullable<Text>,
         visibility -> Integer,
+        time_zone -> Text,
+        week_start -> Text,
+        strict -> Bool,
         created -> BigInt,
         modified -> BigInt,
     }
@@ -226,7 +364,7 @@ diesel::table! {
     report (id) {
         id -> Integer,
         uuid -> Text,
-        user_id -> Integer,
+        user_id -> Nullable<Integer>,
         project_id -> Integer,
         head_id -> Integer,
         version_id -> Integer,
@@ -234,6 +372,19 @@ diesel::table! {
         adapter -> Integer,
         start_time -> BigInt,
         end_time -> BigInt,
+        tag -> Nullable<Text>,
+        warmup_iterations -> Nullable<Integer>,
+        warmup_wall_time -> Nullable<Double>,
+        git_branch -> Nullable<Text>,
+        git_tag -> Nullable<Text>,
+        git_dirty -> Nullable<Bool>,
+        git_author -> Nullable<Text>,
+        git_commit_time -> Nullable<BigInt>,
+        git_subject -> Nullable<Text>,
+        fingerprint_cpu_model -> Nullable<Text>,
+        fingerprint_governor -> Nullable<Text>,
+        fingerprint_virtualized -> Nullable<Bool>,
+        fingerprint_hypervisor -> Nullable<Text>,
         created -> BigInt,
     }
 }
@@ -248,6 +399,104 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    scim_inactive_user (id) {
+        id -> Integer,
+        organization_id -> Integer,
+        user_id -> Integer,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    report_failure (id) {
+        id -> Integer,
+        report_id -> Integer,
+        iteration -> Integer,
+        exit_code -> Integer,
+        wall_time -> Double,
+        stderr -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    report_hook (id) {
+        id -> Integer,
+        project_id -> Integer,
+        script -> Text,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
+diesel::table! {
+    report_link (id) {
+        id -> Integer,
+        report_id -> Integer,
+        url -> Text,
+    }
+}
+
+diesel::table! {
+    report_note (id) {
+        id -> Integer,
+        report_id -> Integer,
+        note -> Text,
+    }
+}
+
+diesel::table! {
+    report_raw (id) {
+        id -> Integer,
+        report_id -> Integer,
+        position -> Integer,
+        settings -> Text,
+        results -> Text,
+        reference_head_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    report_shard (id) {
+        id -> Integer,
+        project_id -> Integer,
+        shard_key -> Text,
+        shard_index -> Integer,
+        shard_count -> Integer,
+        report -> Text,
+        created -> BigInt,
+    }
+}
+
+diesel::table! {
+    rule (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        pattern -> Text,
+        replacement -> Text,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
+diesel::table! {
+    saved_query (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        name -> Text,
+        query -> Text,
+        window -> BigInt,
+        schedule -> Integer,
+        emails -> Text,
+        webhook -> Bool,
+        last_sent -> Nullable<BigInt>,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
 diesel::table! {
     server (id) {
         id -> Integer,
@@ -263,6 +512,10 @@ diesel::table! {
         project_id -> Integer,
         name -> Text,
         slug -> Text,
+        spec_cpu -> Nullable<Text>,
+        spec_cores -> Nullable<Integer>,
+        spec_memory -> Nullable<BigInt>,
+        spec_os -> Nullable<Text>,
         created -> BigInt,
         modified -> BigInt,
         archived -> Nullable<BigInt>,
@@ -292,6 +545,37 @@ diesel::table! {
         jwt -> Text,
         creation -> BigInt,
         expiration -> BigInt,
+        revoked -> Nullable<BigInt>,
+        allowed_ips -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    token_policy (id) {
+        id -> Integer,
+        organization_id -> Integer,
+        max_ttl -> Nullable<BigInt>,
+        require_ttl -> Bool,
+        forbid_non_expiring -> Bool,
+        admin_only -> Bool,
+        modified -> BigInt,
+    }
+}
+
+diesel::table! {
+    token_revocation (id) {
+        id -> Integer,
+        token_id -> Integer,
+        organization_id -> Integer,
+        revoked -> BigInt,
+    }
+}
+
+diesel::table! {
+    unclaimed_project (id) {
+        id -> Integer,
+        project_id -> Integer,
+        created -> BigInt,
     }
 }
 
@@ -309,6 +593,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_prefs (id) {
+        id -> Integer,
+        user_id -> Integer,
+        time_zone -> Nullable<Text>,
+        date_format -> Nullable<Integer>,
+        time_units -> Nullable<Text>,
+        throughput_units -> Nullable<Text>,
+        size_units -> Nullable<Text>,
+        default_project_id -> Nullable<Integer>,
+        modified -> BigInt,
+    }
+}
+
 diesel::table! {
     version (id) {
         id -> Integer,
@@ -316,21 +614,65 @@ diesel::table! {
         project_id -> Integer,
         number -> Integer,
         hash -> Nullable<Text>,
+        pushed -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    webhook (id) {
+        id -> Integer,
+        project_id -> Integer,
+        url -> Text,
+        secret -> Text,
+        created -> BigInt,
+        modified -> BigInt,
+    }
+}
+
+diesel::table! {
+    webhook_delivery (id) {
+        id -> Integer,
+        uuid -> Text,
+        project_id -> Integer,
+        alert_id -> Integer,
+        attempts -> Integer,
+        response_status -> Nullable<Integer>,
+        last_error -> Nullable<Text>,
+        failed -> Nullable<BigInt>,
+        created -> BigInt,
+        modified -> BigInt,
     }
 }
 
+diesel::joinable!(alert -> alert_group (group_id));
 diesel::joinable!(alert -> boundary (boundary_id));
+diesel::joinable!(alert_group -> report_benchmark (report_benchmark_id));
+diesel::joinable!(alert_update -> project (project_id));
+diesel::joinable!(alert_update -> user (user_id));
+diesel::joinable!(annotation -> project (project_id));
 diesel::joinable!(benchmark -> project (project_id));
 diesel::joinable!(boundary -> metric (metric_id));
 diesel::joinable!(boundary -> model (model_id));
 diesel::joinable!(boundary -> threshold (threshold_id));
 diesel::joinable!(branch -> project (project_id));
+diesel::joinable!(downsample -> metric (metric_id));
+diesel::joinable!(downsample_mark -> head (head_id));
+diesel::joinable!(downsample_mark -> testbed (testbed_id));
+diesel::joinable!(expectation -> annotation (annotation_id));
+diesel::joinable!(expectation -> branch (branch_id));
+diesel::joinable!(expectation -> project (project_id));
+diesel::joinable!(flaky_measure -> benchmark (benchmark_id));
+diesel::joinable!(flaky_measure -> measure (measure_id));
+diesel::joinable!(github_webhook -> project (project_id));
 diesel::joinable!(head_version -> version (version_id));
 diesel::joinable!(measure -> project (project_id));
+diesel::joinable!(measure_alias -> measure (measure_id));
+diesel::joinable!(measure_alias -> project (project_id));
 diesel::joinable!(metric -> measure (measure_id));
 diesel::joinable!(metric -> report_benchmark (report_benchmark_id));
 diesel::joinable!(organization_role -> organization (organization_id));
 diesel::joinable!(organization_role -> user (user_id));
+diesel::joinable!(perf_embed_revocation -> project (project_id));
 diesel::joinable!(plot -> project (project_id));
 diesel::joinable!(plot_benchmark -> benchmark (benchmark_id));
 diesel::joinable!(plot_benchmark -> plot (plot_id));
@@ -350,26 +692,57 @@ diesel::joinable!(report -> user (user_id));
 diesel::joinable!(report -> version (version_id));
 diesel::joinable!(report_benchmark -> benchmark (benchmark_id));
 diesel::joinable!(report_benchmark -> report (report_id));
+diesel::joinable!(scim_inactive_user -> organization (organization_id));
+diesel::joinable!(scim_inactive_user -> user (user_id));
+diesel::joinable!(report_failure -> report (report_id));
+diesel::joinable!(report_hook -> project (project_id));
+diesel::joinable!(report_link -> report (report_id));
+diesel::joinable!(report_note -> report (report_id));
+diesel::joinable!(report_raw -> head (reference_head_id));
+diesel::joinable!(report_raw -> report (report_id));
+diesel::joinable!(report_shard -> project (project_id));
+diesel::joinable!(rule -> project (project_id));
+diesel::joinable!(saved_query -> project (project_id));
 diesel::joinable!(testbed -> project (project_id));
 diesel::joinable!(threshold -> branch (branch_id));
 diesel::joinable!(threshold -> measure (measure_id));
 diesel::joinable!(threshold -> project (project_id));
 diesel::joinable!(threshold -> testbed (testbed_id));
 diesel::joinable!(token -> user (user_id));
+diesel::joinable!(token_policy -> organization (organization_id));
+diesel::joinable!(token_revocation -> organization (organization_id));
+diesel::joinable!(token_revocation -> token (token_id));
+diesel::joinable!(unclaimed_project -> project (project_id));
+diesel::joinable!(user_prefs -> project (default_project_id));
+diesel::joinable!(user_prefs -> user (user_id));
 diesel::joinable!(version -> project (project_id));
+diesel::joinable!(webhook -> project (project_id));
+diesel::joinable!(webhook_delivery -> alert (alert_id));
+diesel::joinable!(webhook_delivery -> project (project_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     alert,
+    alert_group,
+    alert_update,
+    annotation,
     benchmark,
     boundary,
     branch,
+    downsample,
+    downsample_mark,
+    expectation,
+    flaky_measure,
+    github_webhook,
     head,
     head_version,
+    job,
     measure,
+    measure_alias,
     metric,
     model,
     organization,
     organization_role,
+    perf_embed_revocation,
     plan,
     plot,
     plot_benchmark,
@@ -380,10 +753,25 @@ diesel::allow_tables_to_appear_in_same_query!(
     project_role,
     report,
     report_benchmark,
+    scim_inactive_user,
+    report_failure,
+    report_hook,
+    report_link,
+    report_note,
+    report_raw,
+    report_shard,
+    rule,
+    saved_query,
     server,
     testbed,
     threshold,
     token,
+    token_policy,
+    token_revocation,
+    unclaimed_project,
     user,
+    user_prefs,
     version,
+    webhook,
+    webhook_delivery,
 );
@@ -21,3 +21,189 @@ where
         Ok(diesel::serialize::IsNull::No)
     }
 }
+
+impl Search {
+    /// Match the characters of the search in order, with anything in between them
+    #[must_use]
+    pub fn fuzzy(&self) -> Self {
+        Self(
+            self.0
+                .chars()
+                .map(String::from)
+                .collect::<Vec<_>>()
+                .join("%"),
+        )
+    }
+
+    /// Rank how closely the search matches the best of the given values.
+    /// Returns `None` if the search does not match any of the values.
+    pub fn rank<'a, I>(&self, values: I) -> Option<SearchRank>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let search = self.0.to_lowercase();
+        values
+            .into_iter()
+            .filter_map(|value| SearchRank::new(&search, &value.to_lowercase()))
+            .min()
+    }
+}
+
+/// How closely a search matches a value, where a lesser rank is a better match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SearchRank {
+    /// The value is the search
+    Exact,
+    /// The value starts with the search
+    Prefix { len: usize },
+    /// The value contains the search
+    Substring { position: usize, len: usize },
+    /// The value contains the characters of the search in order
+    Fuzzy { gaps: usize, len: usize },
+}
+
+impl SearchRank {
+    fn new(search: &str, value: &str) -> Option<Self> {
+        let len = value.chars().count();
+        if value == search {
+            return Some(Self::Exact);
+        }
+        if let Some(position) = value.find(search) {
+            return Some(if position == 0 {
+                Self::Prefix { len }
+            } else {
+                Self::Substring { position, len }
+            });
+        }
+
+        // Count the number of skipped characters between the first and last matching characters
+        let mut search_chars = search.chars().peekable();
+        let mut gaps = 0;
+        let mut pending = 0;
+        let mut started = false;
+        for c in value.chars() {
+            let Some(next) = search_chars.peek() else {
+                break;
+            };
+            if c == *next {
+                search_chars.next();
+                if started {
+                    gaps += pending;
+                }
+                started = true;
+                pending = 0;
+            } else {
+                pending += 1;
+            }
+        }
+        search_chars
+            .peek()
+            .is_none()
+            .then_some(Self::Fuzzy { gaps, len })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{Search, SearchRank};
+
+    fn search(search: &str) -> Search {
+        Search(search.to_owned())
+    }
+
+    #[test]
+    fn test_fuzzy() {
+        assert_eq!(search("abc").fuzzy().0, "a%b%c");
+        assert_eq!(search("a").fuzzy().0, "a");
+        assert_eq!(search("").fuzzy().0, "");
+        assert_eq!(search("é+ü").fuzzy().0, "é%+%ü");
+    }
+
+    #[test]
+    fn test_search_rank_new() {
+        assert_eq!(SearchRank::new("bench", "bench"), Some(SearchRank::Exact));
+        assert_eq!(
+            SearchRank::new("bench", "benchmark"),
+            Some(SearchRank::Prefix { len: 9 })
+        );
+        assert_eq!(
+            SearchRank::new("mark", "benchmark"),
+            Some(SearchRank::Substring {
+                position: 5,
+                len: 9
+            })
+        );
+        // The leading and trailing characters are not counted as gaps
+        assert_eq!(
+            SearchRank::new("bmk", "my_benchmark_test"),
+            Some(SearchRank::Fuzzy { gaps: 6, len: 17 })
+        );
+        assert_eq!(
+            SearchRank::new("bh", "bench"),
+            Some(SearchRank::Fuzzy { gaps: 3, len: 5 })
+        );
+        // The characters must be in order
+        assert_eq!(SearchRank::new("hb", "bench"), None);
+        assert_eq!(SearchRank::new("benchmarks", "benchmark"), None);
+        assert_eq!(SearchRank::new("x", "bench"), None);
+    }
+
+    #[test]
+    fn test_search_rank_order() {
+        let mut ranks = vec![
+            SearchRank::Fuzzy { gaps: 1, len: 5 },
+            SearchRank::Substring {
+                position: 2,
+                len: 5,
+            },
+            SearchRank::Prefix { len: 10 },
+            SearchRank::Exact,
+            SearchRank::Prefix { len: 5 },
+            SearchRank::Substring {
+                position: 1,
+                len: 8,
+            },
+            SearchRank::Fuzzy { gaps: 0, len: 9 },
+        ];
+        ranks.sort();
+        assert_eq!(
+            ranks,
+            vec![
+                SearchRank::Exact,
+                SearchRank::Prefix { len: 5 },
+                SearchRank::Prefix { len: 10 },
+                SearchRank::Substring {
+                    position: 1,
+                    len: 8
+                },
+                SearchRank::Substring {
+                    position: 2,
+                    len: 5
+                },
+                SearchRank::Fuzzy { gaps: 0, len: 9 },
+                SearchRank::Fuzzy { gaps: 1, len: 5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank() {
+        // The best match of all the values is used, ignoring case
+        assert_eq!(
+            search("Bench").rank(["my-bench", "BENCH", "benchmark"]),
+            Some(SearchRank::Exact)
+        );
+        assert_eq!(
+            search("bench").rank(["my-bench", "benchmark"]),
+            Some(SearchRank::Prefix { len: 9 })
+        );
+        assert_eq!(
+            search("bnch").rank(["other", "bench"]),
+            Some(SearchRank::Fuzzy { gaps: 1, len: 5 })
+        );
+        assert_eq!(search("bench").rank(["other", "values"]), None);
+        assert_eq!(search("bench").rank(std::iter::empty()), None);
+    }
+}
@@ -0,0 +1,321 @@
+use bencher_json::{
+    project::{report::Adapter, Visibility},
+    BenchmarkUuid, BranchUuid, DateTime, HeadUuid, JsonNewOrganization, JsonNewProject,
+    MeasureUuid, MetricUuid, ReportBenchmarkUuid, ReportUuid, TestbedUuid, VersionUuid,
+};
+use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use crate::{
+    config::config_tx::run_migrations,
+    context::DbConnection,
+    model::{
+        organization::{InsertOrganization, QueryOrganization},
+        project::{
+            benchmark::BenchmarkId,
+            branch::{head::HeadId, version::VersionId, BranchId},
+            measure::MeasureId,
+            metric::MetricId,
+            report::{report_benchmark::ReportBenchmarkId, ReportId},
+            testbed::TestbedId,
+            InsertProject, QueryProject,
+        },
+        user::{InsertUser, QueryUser},
+    },
+    schema,
+};
+
+/// An in-memory database with all of the migrations applied
+pub fn setup_db() -> DbConnection {
+    let mut conn = DbConnection::establish(":memory:").unwrap();
+    run_migrations(&mut conn).unwrap();
+    conn
+}
+
+pub fn create_user(conn: &mut DbConnection, name: &str) -> QueryUser {
+    let insert_user = InsertUser::new(
+        conn,
+        name.parse().unwrap(),
+        None,
+        format!("{}@bencher.dev", name.to_lowercase().replace(' ', "."))
+            .parse()
+            .unwrap(),
+    )
+    .unwrap();
+    diesel::insert_into(schema::user::table)
+        .values(&insert_user)
+        .execute(conn)
+        .unwrap();
+    let user_id = QueryUser::get_id(conn, insert_user.uuid).unwrap();
+    QueryUser::get(conn, user_id).unwrap()
+}
+
+pub fn create_organization(conn: &mut DbConnection, name: &str) -> QueryOrganization {
+    let insert_org = InsertOrganization::from_json(
+        conn,
+        JsonNewOrganization {
+            name: name.parse().unwrap(),
+            slug: None,
+        },
+    )
+    .unwrap();
+    diesel::insert_into(schema::organization::table)
+        .values(&insert_org)
+        .execute(conn)
+        .unwrap();
+    let organization_id = QueryOrganization::get_id(conn, insert_org.uuid).unwrap();
+    QueryOrganization::get(conn, organization_id).unwrap()
+}
+
+pub fn create_project(
+    conn: &mut DbConnection,
+    query_organization: &QueryOrganization,
+    name: &str,
+    visibility: Visibility,
+) -> QueryProject {
+    let insert_project = InsertProject::from_json(
+        conn,
+        query_organization,
+        JsonNewProject {
+            name: name.parse().unwrap(),
+            slug: None,
+            url: None,
+            visibility: Some(visibility),
+            time_zone: None,
+            week_start: None,
+            strict: None,
+        },
+    )
+    .unwrap();
+    diesel::insert_into(schema::project::table)
+        .values(&insert_project)
+        .execute(conn)
+        .unwrap();
+    let project_id = QueryProject::get_id(conn, insert_project.uuid).unwrap();
+    QueryProject::get(conn, project_id).unwrap()
+}
+
+/// Create a branch along with its head
+pub fn create_branch(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+    name: &str,
+) -> (BranchId, HeadId) {
+    let branch_uuid = BranchUuid::new();
+    diesel::insert_into(schema::branch::table)
+        .values((
+            schema::branch::uuid.eq(branch_uuid),
+            schema::branch::project_id.eq(query_project.id),
+            schema::branch::name.eq(name),
+            schema::branch::slug.eq(name),
+            schema::branch::created.eq(DateTime::now()),
+            schema::branch::modified.eq(DateTime::now()),
+        ))
+        .execute(conn)
+        .unwrap();
+    let branch_id = schema::branch::table
+        .filter(schema::branch::uuid.eq(branch_uuid))
+        .select(schema::branch::id)
+        .first::<BranchId>(conn)
+        .unwrap();
+
+    let head_uuid = HeadUuid::new();
+    diesel::insert_into(schema::head::table)
+        .values((
+            schema::head::uuid.eq(head_uuid),
+            schema::head::branch_id.eq(branch_id),
+            schema::head::created.eq(DateTime::now()),
+        ))
+        .execute(conn)
+        .unwrap();
+    let head_id = schema::head::table
+        .filter(schema::head::uuid.eq(head_uuid))
+        .select(schema::head::id)
+        .first::<HeadId>(conn)
+        .unwrap();
+    diesel::update(schema::branch::table.filter(schema::branch::id.eq(branch_id)))
+        .set(schema::branch::head_id.eq(head_id))
+        .execute(conn)
+        .unwrap();
+
+    (branch_id, head_id)
+}
+
+pub fn create_testbed(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+    name: &str,
+) -> TestbedId {
+    let uuid = TestbedUuid::new();
+    diesel::insert_into(schema::testbed::table)
+        .values((
+            schema::testbed::uuid.eq(uuid),
+            schema::testbed::project_id.eq(query_project.id),
+            schema::testbed::name.eq(name),
+            schema::testbed::slug.eq(name),
+            schema::testbed::created.eq(DateTime::now()),
+            schema::testbed::modified.eq(DateTime::now()),
+        ))
+        .execute(conn)
+        .unwrap();
+    schema::testbed::table
+        .filter(schema::testbed::uuid.eq(uuid))
+        .select(schema::testbed::id)
+        .first(conn)
+        .unwrap()
+}
+
+pub fn create_benchmark(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+    name: &str,
+) -> BenchmarkId {
+    let uuid = BenchmarkUuid::new();
+    diesel::insert_into(schema::benchmark::table)
+        .values((
+            schema::benchmark::uuid.eq(uuid),
+            schema::benchmark::project_id.eq(query_project.id),
+            schema::benchmark::name.eq(name),
+            schema::benchmark::slug.eq(name),
+            schema::benchmark::created.eq(DateTime::now()),
+            schema::benchmark::modified.eq(DateTime::now()),
+        ))
+        .execute(conn)
+        .unwrap();
+    schema::benchmark::table
+        .filter(schema::benchmark::uuid.eq(uuid))
+        .select(schema::benchmark::id)
+        .first(conn)
+        .unwrap()
+}
+
+pub fn create_measure(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+    name: &str,
+) -> MeasureId {
+    let uuid = MeasureUuid::new();
+    diesel::insert_into(schema::measure::table)
+        .values((
+            schema::measure::uuid.eq(uuid),
+            schema::measure::project_id.eq(query_project.id),
+            schema::measure::name.eq(name),
+            schema::measure::slug.eq(name),
+            schema::measure::units.eq("units"),
+            schema::measure::created.eq(DateTime::now()),
+            schema::measure::modified.eq(DateTime::now()),
+        ))
+        .execute(conn)
+        .unwrap();
+    schema::measure::table
+        .filter(schema::measure::uuid.eq(uuid))
+        .select(schema::measure::id)
+        .first(conn)
+        .unwrap()
+}
+
+/// Create a report with a new version that started at the given time
+pub fn create_report(
+    conn: &mut DbConnection,
+    query_project: &QueryProject,
+    head_id: HeadId,
+    testbed_id: TestbedId,
+    start_time: DateTime,
+) -> ReportId {
+    let number = schema::version::table
+        .filter(schema::version::project_id.eq(query_project.id))
+        .count()
+        .get_result::<i64>(conn)
+        .unwrap();
+    let version_uuid = VersionUuid::new();
+    diesel::insert_into(schema::version::table)
+        .values((
+            schema::version::uuid.eq(version_uuid),
+            schema::version::project_id.eq(query_project.id),
+            schema::version::number.eq(i32::try_from(number).unwrap()),
+        ))
+        .execute(conn)
+        .unwrap();
+    let version_id = schema::version::table
+        .filter(schema::version::uuid.eq(version_uuid))
+        .select(schema::version::id)
+        .first::<VersionId>(conn)
+        .unwrap();
+    diesel::insert_into(schema::head_version::table)
+        .values((
+            schema::head_version::head_id.eq(head_id),
+            schema::head_version::version_id.eq(version_id),
+        ))
+        .execute(conn)
+        .unwrap();
+
+    let uuid = ReportUuid::new();
+    diesel::insert_into(schema::report::table)
+        .values((
+            schema::report::uuid.eq(uuid),
+            schema::report::project_id.eq(query_project.id),
+            schema::report::head_id.eq(head_id),
+            schema::report::version_id.eq(version_id),
+            schema::report::testbed_id.eq(testbed_id),
+            schema::report::adapter.eq(Adapter::Json),
+            schema::report::start_time.eq(start_time),
+            schema::report::end_time.eq(start_time),
+            schema::report::created.eq(DateTime::now()),
+        ))
+        .execute(conn)
+        .unwrap();
+    schema::report::table
+        .filter(schema::report::uuid.eq(uuid))
+        .select(schema::report::id)
+        .first(conn)
+        .unwrap()
+}
+
+/// Create a metric for the benchmark in the first iteration of the report
+pub fn create_metric(
+    conn: &mut DbConnection,
+    report_id: ReportId,
+    benchmark_id: BenchmarkId,
+    measure_id: MeasureId,
+    value: f64,
+) -> MetricId {
+    let report_benchmark_id = if let Ok(report_benchmark_id) = schema::report_benchmark::table
+        .filter(schema::report_benchmark::report_id.eq(report_id))
+        .filter(schema::report_benchmark::benchmark_id.eq(benchmark_id))
+        .select(schema::report_benchmark::id)
+        .first::<ReportBenchmarkId>(conn)
+    {
+        report_benchmark_id
+    } else {
+        let uuid = ReportBenchmarkUuid::new();
+        diesel::insert_into(schema::report_benchmark::table)
+            .values((
+                schema::report_benchmark::uuid.eq(uuid),
+                schema::report_benchmark::report_id.eq(report_id),
+                schema::report_benchmark::iteration.eq(0),
+                schema::report_benchmark::benchmark_id.eq(benchmark_id),
+            ))
+            .execute(conn)
+            .unwrap();
+        schema::report_benchmark::table
+            .filter(schema::report_benchmark::uuid.eq(uuid))
+            .select(schema::report_benchmark::id)
+            .first(conn)
+            .unwrap()
+    };
+
+    let uuid = MetricUuid::new();
+    diesel::insert_into(schema::metric::table)
+        .values((
+            schema::metric::uuid.eq(uuid),
+            schema::metric::report_benchmark_id.eq(report_benchmark_id),
+            schema::metric::measure_id.eq(measure_id),
+            schema::metric::value.eq(value),
+        ))
+        .execute(conn)
+        .unwrap();
+    schema::metric::table
+        .filter(schema::metric::uuid.eq(uuid))
+        .select(schema::metric::id)
+        .first(conn)
+        .unwrap()
+}
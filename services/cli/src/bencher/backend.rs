@@ -1,9 +1,21 @@
-use std::{fmt, ops::Deref};
+use std::{fmt, ops::Deref, path::PathBuf, sync::OnceLock};
 
-use bencher_json::{JsonApiVersion, JsonConsole, Jwt, BENCHER_API_URL, BENCHER_URL};
+use bencher_json::{ApiErrorCode, JsonApiVersion, JsonConsole, Jwt, BENCHER_API_URL, BENCHER_URL};
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{cli_eprintln_quietable, parser::CliBackend, CLI_VERSION};
+use bencher_client::{ApiTimer, ClientError, ErrorKind, ResponseCache};
+
+use crate::{
+    bencher::credential::CredentialStore, cli_eprintln, cli_eprintln_quietable, parser::CliBackend,
+    ExitClass, CLI_VERSION,
+};
+
+// All of the backends share a single timer, so there is one summary for the whole command
+static DEBUG_TIMER: OnceLock<ApiTimer> = OnceLock::new();
+
+const CACHE_DIR_ENV: &str = "BENCHER_CACHE_DIR";
+const CACHE_DIR: &str = "bencher";
+const HTTP_CACHE_DIR: &str = "http";
 
 #[derive(Debug, Clone)]
 pub struct PubBackend {
@@ -18,6 +30,9 @@ pub struct AuthBackend {
 #[derive(Debug, Clone)]
 pub struct Backend {
     client: bencher_client::BencherClient,
+    // Fall back to the API token stored by `bencher auth login` once the client is used
+    stored_token: bool,
+    loaded: OnceLock<bencher_client::BencherClient>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -26,21 +41,119 @@ pub enum BackendError {
     ParseHost(bencher_json::ValidError),
     #[error("Failed to parse API token: {0}")]
     ParseToken(bencher_json::ValidError),
-    #[error("Failed to find Bencher API token, and this API endpoint requires authorization. Set the `--token` flag or the `BENCHER_API_TOKEN` environment variable.")]
+    #[error("Failed to find Bencher API token, and this API endpoint requires authorization. Set the `--token` flag or the `BENCHER_API_TOKEN` environment variable, or store an API token with `bencher auth login --token`.")]
     NoToken,
     #[error("Failed to get API server version: {0}")]
-    ApiVersion(bencher_client::ClientError),
+    ApiVersion(ClientError),
     #[error("{err}\nHint: This may be due to a version mismatch. {mismatch}")]
     ClientMismatch {
         mismatch: Box<VersionMismatch>,
-        err: bencher_client::ClientError,
+        err: ClientError,
     },
     #[error("{0}")]
-    Client(#[from] bencher_client::ClientError),
+    Client(#[from] ClientError),
     #[error("Invalid console URL: {0}")]
     BadConsoleUrl(bencher_json::ValidError),
 }
 
+impl BackendError {
+    /// The machine-readable error code returned by the Bencher API, if any
+    pub fn code(&self) -> Option<ApiErrorCode> {
+        match self {
+            Self::ApiVersion(err) | Self::ClientMismatch { err, .. } | Self::Client(err) => {
+                err.code()
+            },
+            Self::ParseHost(_) | Self::ParseToken(_) | Self::NoToken | Self::BadConsoleUrl(_) => {
+                None
+            },
+        }
+    }
+
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::ParseHost(_) => ExitClass::Usage,
+            Self::ParseToken(_) | Self::NoToken => ExitClass::Auth,
+            Self::ApiVersion(err) | Self::ClientMismatch { err, .. } | Self::Client(err) => {
+                client_exit_class(err)
+            },
+            Self::BadConsoleUrl(_) => ExitClass::Parse,
+        }
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::ParseHost(_) => Some("Set the `--host` flag or the `BENCHER_HOST` environment variable to the URL of the Bencher API server, ie `https://api.bencher.dev`."),
+            Self::ParseToken(_) => Some("Set the `--token` flag or the `BENCHER_API_TOKEN` environment variable to a valid API token. API tokens can be created in the Bencher Console."),
+            Self::ApiVersion(err) | Self::Client(err) => client_hint(err),
+            // These errors already include a hint
+            Self::NoToken | Self::ClientMismatch { .. } | Self::BadConsoleUrl(_) => None,
+        }
+    }
+}
+
+fn client_exit_class(err: &ClientError) -> ExitClass {
+    if let Some(kind) = err.kind() {
+        return match kind {
+            ErrorKind::Unauthorized | ErrorKind::PaymentRequired | ErrorKind::Forbidden => {
+                ExitClass::Auth
+            },
+            ErrorKind::BadRequest
+            | ErrorKind::NotFound
+            | ErrorKind::Conflict
+            | ErrorKind::Other(_) => ExitClass::Usage,
+            ErrorKind::TooManyRequests | ErrorKind::Server => ExitClass::Network,
+        };
+    }
+    match err {
+        ClientError::NoHost
+        | ClientError::HeaderValue(_)
+        | ClientError::InvalidRequest(_)
+        | ClientError::PreHookError(_) => ExitClass::Usage,
+        ClientError::BuildClient(_)
+        | ClientError::InvalidUpgrade(_)
+        | ClientError::ResponseBodyError(_)
+        | ClientError::UnexpectedResponseOkStrict(_)
+        | ClientError::UnexpectedResponseOk(_)
+        | ClientError::ErrorResponse(_)
+        | ClientError::UnexpectedResponseErr(_)
+        | ClientError::SendTimeout(_) => ExitClass::Network,
+        ClientError::DeserializeResponse(_)
+        | ClientError::SerializeResponse(_)
+        | ClientError::InvalidResponsePayloadStrict(_, _)
+        | ClientError::InvalidResponsePayload(_) => ExitClass::Parse,
+    }
+}
+
+fn client_hint(err: &ClientError) -> Option<&'static str> {
+    if let Some(kind) = err.kind() {
+        return match kind {
+            ErrorKind::Unauthorized => Some("The API token is missing, invalid, or expired. Set the `--token` flag or the `BENCHER_API_TOKEN` environment variable to a valid API token."),
+            ErrorKind::Forbidden => Some("The API token is valid, but the user does not have permission to do this. Check the role of the user in the organization."),
+            ErrorKind::PaymentRequired => Some("This requires a Bencher Plus plan for the organization. Check the plan with `bencher plan view ORGANIZATION`."),
+            ErrorKind::NotFound => Some("Check that the UUID, slug, or name is correct and that the `--host` flag is set to the right Bencher API server."),
+            ErrorKind::TooManyRequests => Some("Increase `--retry-after` to wait longer between attempts."),
+            ErrorKind::Server => Some("The Bencher API server failed to process the request. Increase `--attempts` or `--retry-after` to keep retrying for longer."),
+            ErrorKind::BadRequest | ErrorKind::Conflict | ErrorKind::Other(_) => None,
+        };
+    }
+    match err {
+        ClientError::SendTimeout(_) | ClientError::BuildClient(_) | ClientError::ResponseBodyError(_) => Some("Check that the `--host` flag is set to the right Bencher API server and that it can be reached. Increase `--attempts` or `--retry-after` to keep retrying for longer."),
+        ClientError::DeserializeResponse(_)
+        | ClientError::InvalidResponsePayloadStrict(_, _)
+        | ClientError::InvalidResponsePayload(_) => Some("The response from the Bencher API server could not be parsed. Make sure that the CLI version matches the API server version with `bencher server version`."),
+        ClientError::NoHost
+        | ClientError::HeaderValue(_)
+        | ClientError::SerializeResponse(_)
+        | ClientError::InvalidRequest(_)
+        | ClientError::PreHookError(_)
+        | ClientError::ErrorResponse(_)
+        | ClientError::InvalidUpgrade(_)
+        | ClientError::UnexpectedResponseOkStrict(_)
+        | ClientError::UnexpectedResponseOk(_)
+        | ClientError::UnexpectedResponseErr(_) => None,
+    }
+}
+
 impl TryFrom<CliBackend> for PubBackend {
     type Error = BackendError;
 
@@ -63,14 +176,25 @@ impl TryFrom<(CliBackend, bool)> for Backend {
     fn try_from((backend, is_public): (CliBackend, bool)) -> Result<Self, Self::Error> {
         let CliBackend {
             host,
+            fallback_hosts,
             token,
             attempts,
             retry_after,
             strict,
+            debug_timing,
+            no_cache,
         } = backend;
         let host = host.try_into().map_err(BackendError::ParseHost)?;
-        let token = map_token(token, is_public)?;
-        let client = bencher_client::BencherClient::new(
+        // Fall back to the API token stored by `bencher auth login`.
+        // An API token is optional for public endpoints,
+        // so the credential store is not accessed until the client is used.
+        let stored_token = is_public && token.is_none();
+        let token = if stored_token {
+            None
+        } else {
+            map_token(token.or_else(|| CredentialStore::load(&host)), is_public)?
+        };
+        let mut client = bencher_client::BencherClient::new(
             Some(host),
             token,
             Some(attempts),
@@ -78,7 +202,46 @@ impl TryFrom<(CliBackend, bool)> for Backend {
             Some(strict),
             Some(true),
         );
-        Ok(Self { client })
+        client.fallback_hosts = fallback_hosts
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(BackendError::ParseHost)?;
+        if debug_timing {
+            client.timer = DEBUG_TIMER.get_or_init(ApiTimer::enabled).clone();
+        }
+        if !no_cache {
+            if let Some(cache_dir) = cache_dir() {
+                client.cache = ResponseCache::new(cache_dir);
+            }
+        }
+        Ok(Self {
+            client,
+            stored_token,
+            loaded: OnceLock::new(),
+        })
+    }
+}
+
+/// The API response cache is skipped if there is no cache directory
+fn cache_dir() -> Option<PathBuf> {
+    if let Some(cache_dir) = std::env::var_os(CACHE_DIR_ENV) {
+        return Some(PathBuf::from(cache_dir));
+    }
+    let base_dir = if cfg!(windows) {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+    };
+    base_dir.map(|base_dir| base_dir.join(CACHE_DIR).join(HTTP_CACHE_DIR))
+}
+
+/// Print the timings for all of the API calls, if `--debug-timing` was used
+pub fn print_debug_timing() {
+    if let Some(timer) = DEBUG_TIMER.get() {
+        cli_eprintln!("\n{}", timer.summary());
     }
 }
 
@@ -102,7 +265,7 @@ impl Deref for PubBackend {
 
 impl PubBackend {
     pub fn log(mut self, log: bool) -> Self {
-        self.inner.client.log = log;
+        self.inner.client_mut().log = log;
         self
     }
 }
@@ -117,12 +280,32 @@ impl Deref for AuthBackend {
 
 impl AuthBackend {
     pub fn log(mut self, log: bool) -> Self {
-        self.inner.client.log = log;
+        self.inner.client_mut().log = log;
+        self
+    }
+
+    pub fn with_token(mut self, token: Jwt) -> Self {
+        self.inner.client_mut().token = Some(token);
         self
     }
 }
 
 impl Backend {
+    pub fn client(&self) -> &bencher_client::BencherClient {
+        self.loaded.get_or_init(|| {
+            let mut client = self.client.clone();
+            if self.stored_token && client.token.is_none() {
+                client.token = CredentialStore::load(&client.host);
+            }
+            client
+        })
+    }
+
+    fn client_mut(&mut self) -> &mut bencher_client::BencherClient {
+        self.loaded.take();
+        &mut self.client
+    }
+
     pub async fn send<F, R, T, E>(&self, sender: F) -> Result<serde_json::Value, BackendError>
     where
         F: Fn(bencher_client::Client) -> R,
@@ -137,16 +320,10 @@ impl Backend {
         bencher_client::JsonValue: TryFrom<T, Error = E>,
     {
         let mismatch = self.check_version().await?;
-        self.client.send(sender).await.map_err(|err| {
-            if let Some(mismatch) = mismatch {
-                BackendError::ClientMismatch {
-                    mismatch: Box::new(mismatch),
-                    err,
-                }
-            } else {
-                err.into()
-            }
-        })
+        self.client()
+            .send(sender)
+            .await
+            .map_err(|err| mismatch_error(mismatch.as_ref(), err))
     }
 
     pub async fn send_with<F, R, T, Json, E>(&self, sender: F) -> Result<Json, BackendError>
@@ -163,15 +340,19 @@ impl Backend {
         E: std::error::Error + Send + Sync + 'static,
     {
         let mismatch = self.check_version().await?;
-        self.client.send_with(sender).await.map_err(|err| {
-            if let Some(mismatch) = mismatch {
-                BackendError::ClientMismatch {
-                    mismatch: Box::new(mismatch),
-                    err,
-                }
-            } else {
-                err.into()
-            }
+        self.client()
+            .send_with(sender)
+            .await
+            .map_err(|err| mismatch_error(mismatch.as_ref(), err))
+    }
+
+    /// Check the API server version once, and then use the typed client helpers.
+    /// Any errors from the typed client helpers should be converted with `CheckedClient::error`.
+    pub async fn checked_client(&self) -> Result<CheckedClient<'_>, BackendError> {
+        let mismatch = self.check_version().await?;
+        Ok(CheckedClient {
+            client: self.client(),
+            mismatch,
         })
     }
 
@@ -208,7 +389,40 @@ impl Backend {
     }
 }
 
+fn mismatch_error(mismatch: Option<&VersionMismatch>, err: ClientError) -> BackendError {
+    if let Some(mismatch) = mismatch {
+        BackendError::ClientMismatch {
+            mismatch: Box::new(mismatch.clone()),
+            err,
+        }
+    } else {
+        err.into()
+    }
+}
+
+/// A client for the Bencher API server after its version has been checked
 #[derive(Debug)]
+pub struct CheckedClient<'b> {
+    client: &'b bencher_client::BencherClient,
+    mismatch: Option<VersionMismatch>,
+}
+
+impl Deref for CheckedClient<'_> {
+    type Target = bencher_client::BencherClient;
+
+    fn deref(&self) -> &Self::Target {
+        self.client
+    }
+}
+
+impl CheckedClient<'_> {
+    /// Include a hint about the version mismatch, if there is one
+    pub fn error(&self, err: ClientError) -> BackendError {
+        mismatch_error(self.mismatch.as_ref(), err)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct VersionMismatch {
     pub host: url::Url,
     pub api_version: String,
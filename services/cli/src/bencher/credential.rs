@@ -0,0 +1,396 @@
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use bencher_json::Jwt;
+use serde::{Deserialize, Serialize};
+
+/// The keychain service name for stored API tokens
+const KEYCHAIN_SERVICE: &str = "bencher";
+const CONFIG_DIR_ENV: &str = "BENCHER_CONFIG_DIR";
+const CONFIG_DIR: &str = "bencher";
+const CREDENTIALS_FILE: &str = "credentials.toml";
+const CREDENTIALS_LOCK: &str = "credentials.toml.lock";
+
+const LOCK_ATTEMPTS: u32 = 100;
+const LOCK_RETRY: Duration = Duration::from_millis(50);
+// A lock older than this was left behind by a process that did not exit cleanly
+const STALE_LOCK: Duration = Duration::from_secs(10);
+
+/// Where a user API token is stored between invocations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialStore {
+    /// The OS keychain (macOS Keychain, Windows Credential Manager, or Secret Service)
+    Keychain,
+    /// A config file that is only readable by the current user
+    File,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CredentialError {
+    #[error("Failed to access the OS keychain: {0}\nHint: Use `--no-keychain` to store the API token in a config file instead.")]
+    Keychain(keyring::Error),
+    #[error("Failed to find a config directory to store the API token. Set the `{CONFIG_DIR_ENV}` environment variable.")]
+    NoConfigDir,
+    #[error("Failed to create config directory ({path}): {err}")]
+    CreateDir { path: PathBuf, err: io::Error },
+    #[error("Failed to lock credentials file ({path}): {err}")]
+    Lock { path: PathBuf, err: io::Error },
+    #[error("Failed to read credentials file ({path}): {err}")]
+    Read { path: PathBuf, err: io::Error },
+    #[error("Failed to parse credentials file ({path}): {err}")]
+    Parse { path: PathBuf, err: toml::de::Error },
+    #[error("Failed to serialize credentials file: {0}")]
+    Serialize(toml::ser::Error),
+    #[error("Failed to write credentials file ({path}): {err}")]
+    Write { path: PathBuf, err: io::Error },
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CredentialsFile {
+    /// API tokens by host URL
+    #[serde(default)]
+    tokens: BTreeMap<String, Jwt>,
+}
+
+impl CredentialStore {
+    pub fn new(no_keychain: bool) -> Self {
+        if no_keychain {
+            Self::File
+        } else {
+            Self::Keychain
+        }
+    }
+
+    /// Load the stored API token for the host, checking the config file before the keychain.
+    /// Any errors are ignored, as a stored token is always optional.
+    pub fn load(host: &url::Url) -> Option<Jwt> {
+        Self::File
+            .get(host)
+            .ok()
+            .flatten()
+            .or_else(|| Self::Keychain.get(host).ok().flatten())
+    }
+
+    pub fn get(self, host: &url::Url) -> Result<Option<Jwt>, CredentialError> {
+        match self {
+            Self::Keychain => match keychain_entry(host)?.get_password() {
+                Ok(token) => Ok(token.parse().ok()),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(err) => Err(CredentialError::Keychain(err)),
+            },
+            Self::File => Ok(read_credentials(&credentials_path()?)?
+                .tokens
+                .remove(host.as_str())),
+        }
+    }
+
+    pub fn save(self, host: &url::Url, token: &Jwt) -> Result<(), CredentialError> {
+        match self {
+            Self::Keychain => keychain_entry(host)?
+                .set_password(token.as_ref())
+                .map_err(CredentialError::Keychain),
+            Self::File => update_credentials(&config_dir()?, |credentials| {
+                credentials
+                    .tokens
+                    .insert(host.as_str().to_owned(), token.clone());
+            }),
+        }
+    }
+
+    /// Returns `true` if there was a stored API token for the host
+    pub fn delete(self, host: &url::Url) -> Result<bool, CredentialError> {
+        match self {
+            Self::Keychain => match keychain_entry(host)?.delete_credential() {
+                Ok(()) => Ok(true),
+                Err(keyring::Error::NoEntry) => Ok(false),
+                Err(err) => Err(CredentialError::Keychain(err)),
+            },
+            Self::File => {
+                let mut deleted = false;
+                update_credentials(&config_dir()?, |credentials| {
+                    deleted = credentials.tokens.remove(host.as_str()).is_some();
+                })?;
+                Ok(deleted)
+            },
+        }
+    }
+}
+
+impl fmt::Display for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keychain => write!(f, "the OS keychain"),
+            Self::File => match credentials_path() {
+                Ok(path) => write!(f, "{}", path.display()),
+                Err(_) => write!(f, "credentials file"),
+            },
+        }
+    }
+}
+
+fn keychain_entry(host: &url::Url) -> Result<keyring::Entry, CredentialError> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, host.as_str()).map_err(CredentialError::Keychain)
+}
+
+fn config_dir() -> Result<PathBuf, CredentialError> {
+    if let Some(config_dir) = std::env::var_os(CONFIG_DIR_ENV) {
+        return Ok(PathBuf::from(config_dir));
+    }
+    let base_dir = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base_dir
+        .map(|base_dir| base_dir.join(CONFIG_DIR))
+        .ok_or(CredentialError::NoConfigDir)
+}
+
+fn credentials_path() -> Result<PathBuf, CredentialError> {
+    config_dir().map(|config_dir| config_dir.join(CREDENTIALS_FILE))
+}
+
+fn read_credentials(path: &Path) -> Result<CredentialsFile, CredentialError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents).map_err(|err| CredentialError::Parse {
+            path: path.to_path_buf(),
+            err,
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(CredentialsFile::default()),
+        Err(err) => Err(CredentialError::Read {
+            path: path.to_path_buf(),
+            err,
+        }),
+    }
+}
+
+/// Update the credentials file while holding its lock,
+/// so concurrent invocations do not overwrite each other's changes.
+fn update_credentials<F>(config_dir: &Path, f: F) -> Result<(), CredentialError>
+where
+    F: FnOnce(&mut CredentialsFile),
+{
+    create_config_dir(config_dir)?;
+    let _lock = CredentialsLock::acquire(config_dir.join(CREDENTIALS_LOCK))?;
+
+    let path = config_dir.join(CREDENTIALS_FILE);
+    let mut credentials = read_credentials(&path)?;
+    f(&mut credentials);
+    let contents = toml::to_string(&credentials).map_err(CredentialError::Serialize)?;
+
+    // Write to a temporary file and then rename it,
+    // so readers never see a partially written file.
+    let tmp_path = config_dir.join(format!("{CREDENTIALS_FILE}.{}", std::process::id()));
+    let write_err = |err| CredentialError::Write {
+        path: path.clone(),
+        err,
+    };
+    let mut file = private_file(&tmp_path).map_err(write_err)?;
+    file.write_all(contents.as_bytes())
+        .and_then(|()| file.sync_all())
+        .map_err(write_err)?;
+    fs::rename(&tmp_path, &path).map_err(write_err)
+}
+
+fn create_config_dir(config_dir: &Path) -> Result<(), CredentialError> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    builder.mode(0o700);
+    builder
+        .create(config_dir)
+        .map_err(|err| CredentialError::CreateDir {
+            path: config_dir.to_path_buf(),
+            err,
+        })
+}
+
+/// Create a new file that is only readable and writable by the current user.
+/// Any existing file is removed first, as the mode is only set when the file is created.
+fn private_file(path: &Path) -> io::Result<File> {
+    match fs::remove_file(path) {
+        Ok(()) => {},
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {},
+        Err(err) => return Err(err),
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    options.open(path)
+}
+
+/// A lock file that is removed when dropped
+struct CredentialsLock {
+    path: PathBuf,
+}
+
+impl CredentialsLock {
+    fn acquire(path: PathBuf) -> Result<Self, CredentialError> {
+        let mut attempt = 0;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Self { path }),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    if is_stale(&path) {
+                        drop(fs::remove_file(&path));
+                        continue;
+                    }
+                    attempt += 1;
+                    if attempt >= LOCK_ATTEMPTS {
+                        return Err(CredentialError::Lock { path, err });
+                    }
+                    thread::sleep(LOCK_RETRY);
+                },
+                Err(err) => return Err(CredentialError::Lock { path, err }),
+            }
+        }
+    }
+}
+
+fn is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_LOCK)
+}
+
+impl Drop for CredentialsLock {
+    fn drop(&mut self) {
+        drop(fs::remove_file(&self.path));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use bencher_json::Jwt;
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        read_credentials, update_credentials, CredentialsFile, CredentialsLock, CREDENTIALS_FILE,
+        CREDENTIALS_LOCK, STALE_LOCK,
+    };
+
+    const HOST: &str = "https://api.bencher.dev/";
+    const OTHER_HOST: &str = "http://localhost:61016/";
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("bencher_credential_{name}_{}", std::process::id()));
+        drop(fs::remove_dir_all(&dir));
+        dir
+    }
+
+    #[test]
+    fn test_credentials_file_toml() {
+        let mut credentials = CredentialsFile::default();
+        credentials.tokens.insert(HOST.into(), Jwt::test_token());
+        let contents = toml::to_string(&credentials).unwrap();
+        let parsed: CredentialsFile = toml::from_str(&contents).unwrap();
+        assert_eq!(parsed.tokens, credentials.tokens);
+
+        let empty: CredentialsFile = toml::from_str("").unwrap();
+        assert!(empty.tokens.is_empty());
+    }
+
+    #[test]
+    fn test_update_credentials() {
+        let dir = test_dir("update");
+        let path = dir.join(CREDENTIALS_FILE);
+        assert!(read_credentials(&path).unwrap().tokens.is_empty());
+
+        update_credentials(&dir, |credentials| {
+            credentials.tokens.insert(HOST.into(), Jwt::test_token());
+        })
+        .unwrap();
+        update_credentials(&dir, |credentials| {
+            credentials
+                .tokens
+                .insert(OTHER_HOST.into(), Jwt::test_token());
+        })
+        .unwrap();
+        let credentials = read_credentials(&path).unwrap();
+        assert_eq!(credentials.tokens.get(HOST), Some(&Jwt::test_token()));
+        assert_eq!(credentials.tokens.get(OTHER_HOST), Some(&Jwt::test_token()));
+
+        update_credentials(&dir, |credentials| {
+            credentials.tokens.remove(HOST);
+        })
+        .unwrap();
+        let credentials = read_credentials(&path).unwrap();
+        assert_eq!(credentials.tokens.get(HOST), None);
+        assert_eq!(credentials.tokens.len(), 1);
+        // The lock is released once the update is done
+        assert!(!dir.join(CREDENTIALS_LOCK).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_update_credentials_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = test_dir("private");
+        fs::create_dir_all(&dir).unwrap();
+        // A leftover temporary file that is readable by everyone
+        let tmp_path = dir.join(format!("{CREDENTIALS_FILE}.{}", std::process::id()));
+        fs::write(&tmp_path, "").unwrap();
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        update_credentials(&dir, |credentials| {
+            credentials.tokens.insert(HOST.into(), Jwt::test_token());
+        })
+        .unwrap();
+        let mode = fs::metadata(dir.join(CREDENTIALS_FILE))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_credentials_lock() {
+        let dir = test_dir("lock");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CREDENTIALS_LOCK);
+
+        let lock = CredentialsLock::acquire(path.clone()).unwrap();
+        assert!(path.exists());
+        drop(lock);
+        assert!(!path.exists());
+
+        // A lock left behind by a process that did not exit cleanly is taken over
+        let stale = fs::File::create(&path).unwrap();
+        stale
+            .set_modified(SystemTime::now() - STALE_LOCK - Duration::from_secs(1))
+            .unwrap();
+        drop(stale);
+        let lock = CredentialsLock::acquire(path.clone()).unwrap();
+        assert!(path.exists());
+        drop(lock);
+        assert!(!path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,9 +3,11 @@ use clap::Parser;
 use crate::{parser::CliBencher, CliError};
 
 pub mod backend;
+pub mod credential;
 pub mod sub;
 
 pub use backend::BackendError;
+pub use credential::CredentialError;
 use sub::{Sub, SubCmd};
 
 #[derive(Debug)]
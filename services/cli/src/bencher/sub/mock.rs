@@ -4,7 +4,7 @@ use bencher_adapter::{results::adapter_metrics::AdapterMetrics, AdapterResults};
 use bencher_json::{JsonNewMetric, NameId};
 use rand::{distributions::Uniform, prelude::Distribution, Rng};
 
-use crate::{cli_println, parser::mock::CliMock, CliError};
+use crate::{cli_println, parser::mock::CliMock, CliError, ExitClass};
 
 use super::SubCmd;
 
@@ -31,6 +31,15 @@ pub enum MockError {
     MockFailure,
 }
 
+impl MockError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::ParseBenchmarkName(_) => ExitClass::Usage,
+            Self::SerializeResults(_) | Self::MockFailure => ExitClass::Failure,
+        }
+    }
+}
+
 impl From<CliMock> for Mock {
     fn from(mock: CliMock) -> Self {
         let CliMock {
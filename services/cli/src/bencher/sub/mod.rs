@@ -1,4 +1,4 @@
-use crate::{parser::CliSub, CliError};
+use crate::{cli_eprintln, parser::CliSub, CliError};
 
 mod docker;
 mod mock;
@@ -12,35 +12,54 @@ pub use docker::DockerError;
 use docker::{down::Down, logs::Logs, up::Up};
 use mock::Mock;
 pub use mock::MockError;
-use organization::{member::Member, organization::Organization};
+use organization::{member::Member, organization::Organization, token_policy::TokenPolicy};
 use project::{
     alert::Alert,
+    annotation::Annotation,
     archive::{Archive, ArchiveAction},
     benchmark::Benchmark,
     branch::Branch,
+    calibrate::Calibrate,
+    expectation::Expectation,
+    import::Import,
     measure::Measure,
     metric::Metric,
     perf::Perf,
     plot::Plot,
     project::Project,
+    query::Query,
     report::Report,
-    run::Run,
+    rule::Rule,
+    run::{Run, RunMatrix},
     testbed::Testbed,
     threshold::Threshold,
 };
 pub use project::{
     archive::ArchiveError,
+    branch::BranchTagsError,
+    calibrate::CalibrateError,
+    import::ImportError,
+    measure::MeasureError,
+    report::ReportError,
     run::{runner::output::Output, thresholds::ThresholdsError, RunError},
     threshold::ThresholdError,
 };
 pub use sub_cmd::SubCmd;
-use system::{auth::Auth, server::Server};
+use system::{auth::Auth, server::Server, spec::Spec};
+pub use system::{
+    server::{RbacError, SmokeError},
+    spec::SpecError,
+};
+pub use user::token::{RotateError, TokenPolicyError};
 use user::{token::Token, user::User};
 
 #[derive(Debug)]
 pub enum Sub {
     Run(Box<Run>),
+    RunMatrix(Box<RunMatrix>),
+    Calibrate(Calibrate),
     Mock(Mock),
+    Import(Import),
     Archive(Archive),
     Up(Up),
     Logs(Logs),
@@ -49,10 +68,15 @@ pub enum Sub {
     Member(Member),
     #[cfg(feature = "plus")]
     Plan(organization::plan::Plan),
+    TokenPolicy(TokenPolicy),
     Project(Project),
     Report(Report),
     Perf(Perf),
     Plot(Plot),
+    Query(Query),
+    Annotation(Annotation),
+    Rule(Rule),
+    Expectation(Expectation),
     Branch(Branch),
     Testbed(Testbed),
     Benchmark(Benchmark),
@@ -63,6 +87,7 @@ pub enum Sub {
     User(User),
     Token(Token),
     Server(Server),
+    Spec(Spec),
     Auth(Auth),
 }
 
@@ -71,8 +96,13 @@ impl TryFrom<CliSub> for Sub {
 
     fn try_from(sub: CliSub) -> Result<Self, Self::Error> {
         Ok(match sub {
+            CliSub::Run(run) if run.matrix.matrix.is_some() => {
+                Self::RunMatrix(Box::new((*run).try_into()?))
+            },
             CliSub::Run(run) => Self::Run(Box::new((*run).try_into()?)),
+            CliSub::Calibrate(calibrate) => Self::Calibrate(calibrate.try_into()?),
             CliSub::Mock(mock) => Self::Mock(mock.into()),
+            CliSub::Import(import) => Self::Import(import.try_into()?),
             CliSub::Archive(archive) => {
                 Self::Archive((archive, ArchiveAction::Archive).try_into()?)
             },
@@ -86,20 +116,32 @@ impl TryFrom<CliSub> for Sub {
             CliSub::Member(member) => Self::Member(member.try_into()?),
             #[cfg(feature = "plus")]
             CliSub::Plan(plan) => Self::Plan(plan.try_into()?),
+            CliSub::TokenPolicy(token_policy) => Self::TokenPolicy(token_policy.try_into()?),
             CliSub::Project(project) => Self::Project(project.try_into()?),
             CliSub::Report(report) => Self::Report(report.try_into()?),
             CliSub::Perf(perf) => Self::Perf(perf.try_into()?),
             CliSub::Plot(plot) => Self::Plot(plot.try_into()?),
+            CliSub::Query(query) => Self::Query(query.try_into()?),
+            CliSub::Annotation(annotation) => Self::Annotation(annotation.try_into()?),
+            CliSub::Rule(rule) => Self::Rule(rule.try_into()?),
+            CliSub::Expectation(expectation) => Self::Expectation(expectation.try_into()?),
             CliSub::Branch(branch) => Self::Branch(branch.try_into()?),
             CliSub::Testbed(testbed) => Self::Testbed(testbed.try_into()?),
             CliSub::Benchmark(benchmark) => Self::Benchmark(benchmark.try_into()?),
             CliSub::Measure(measure) => Self::Measure(measure.try_into()?),
+            CliSub::MetricKind(measure) => {
+                cli_eprintln!(
+                    "Warning: `bencher metric-kind` is deprecated and will soon be removed. Metric kinds have been renamed to measures, so use `bencher measure` instead."
+                );
+                Self::Measure(measure.try_into()?)
+            },
             CliSub::Metric(metric) => Self::Metric(metric.try_into()?),
             CliSub::Threshold(threshold) => Self::Threshold(threshold.try_into()?),
             CliSub::Alert(alert) => Self::Alert(alert.try_into()?),
             CliSub::User(user) => Self::User(user.try_into()?),
             CliSub::Token(token) => Self::Token(token.try_into()?),
             CliSub::Server(server) => Self::Server(server.try_into()?),
+            CliSub::Spec(spec) => Self::Spec(spec.try_into()?),
             CliSub::Auth(auth) => Self::Auth(auth.try_into()?),
         })
     }
@@ -109,7 +151,10 @@ impl SubCmd for Sub {
     async fn exec(&self) -> Result<(), CliError> {
         match self {
             Self::Run(run) => run.exec().await,
+            Self::RunMatrix(run_matrix) => run_matrix.exec().await,
+            Self::Calibrate(calibrate) => calibrate.exec().await,
             Self::Mock(mock) => mock.exec().await,
+            Self::Import(import) => import.exec().await,
             Self::Archive(archive) => archive.exec().await,
             Self::Up(up) => up.exec().await,
             Self::Logs(logs) => logs.exec().await,
@@ -118,10 +163,15 @@ impl SubCmd for Sub {
             Self::Member(member) => member.exec().await,
             #[cfg(feature = "plus")]
             Self::Plan(plan) => plan.exec().await,
+            Self::TokenPolicy(token_policy) => token_policy.exec().await,
             Self::Project(project) => project.exec().await,
             Self::Report(report) => report.exec().await,
             Self::Perf(perf) => perf.exec().await,
             Self::Plot(plot) => plot.exec().await,
+            Self::Query(query) => query.exec().await,
+            Self::Annotation(annotation) => annotation.exec().await,
+            Self::Rule(rule) => rule.exec().await,
+            Self::Expectation(expectation) => expectation.exec().await,
             Self::Branch(branch) => branch.exec().await,
             Self::Testbed(testbed) => testbed.exec().await,
             Self::Benchmark(benchmark) => benchmark.exec().await,
@@ -132,6 +182,7 @@ impl SubCmd for Sub {
             Self::User(user) => user.exec().await,
             Self::Token(token) => token.exec().await,
             Self::Server(server) => server.exec().await,
+            Self::Spec(spec) => spec.exec().await,
             Self::Auth(auth) => auth.exec().await,
         }
     }
@@ -42,8 +42,7 @@ impl TryFrom<CliMemberInvite> for Invite {
 impl From<CliMemberRole> for OrganizationRole {
     fn from(role: CliMemberRole) -> Self {
         match role {
-            // TODO Team Management
-            // CliMemberRole::Member => Self::Member,
+            CliMemberRole::Member => Self::Member,
             CliMemberRole::Leader => Self::Leader,
         }
     }
@@ -2,3 +2,4 @@ pub mod member;
 #[allow(clippy::module_inception)]
 pub mod organization;
 pub mod plan;
+pub mod token_policy;
@@ -0,0 +1,65 @@
+use bencher_client::types::JsonProjectClaim;
+use bencher_json::{Jwt, ResourceId, ResourceName};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::organization::CliOrganizationClaim,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Claim {
+    pub organization: ResourceId,
+    #[allow(clippy::struct_field_names)]
+    pub claim_token: Jwt,
+    pub name: Option<ResourceName>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliOrganizationClaim> for Claim {
+    type Error = CliError;
+
+    fn try_from(claim: CliOrganizationClaim) -> Result<Self, Self::Error> {
+        let CliOrganizationClaim {
+            organization,
+            claim_token,
+            name,
+            backend,
+        } = claim;
+        Ok(Self {
+            organization,
+            claim_token,
+            name,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Claim> for JsonProjectClaim {
+    fn from(claim: Claim) -> Self {
+        let Claim {
+            claim_token, name, ..
+        } = claim;
+        Self {
+            token: claim_token.into(),
+            name: name.map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Claim {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .org_claim_post()
+                    .organization(self.organization.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{bencher::sub::SubCmd, parser::organization::CliOrganization, CliError};
 
 mod allowed;
+mod claim;
 mod create;
 mod delete;
 mod list;
+mod perf;
+mod transfer;
 mod update;
 mod usage;
 mod view;
@@ -16,6 +19,9 @@ pub enum Organization {
     Update(update::Update),
     Delete(delete::Delete),
     Allowed(allowed::Allowed),
+    Transfer(transfer::Transfer),
+    Claim(claim::Claim),
+    Perf(perf::Perf),
     #[cfg(feature = "plus")]
     Usage(usage::Usage),
 }
@@ -31,6 +37,9 @@ impl TryFrom<CliOrganization> for Organization {
             CliOrganization::Update(update) => Self::Update(update.try_into()?),
             CliOrganization::Delete(delete) => Self::Delete(delete.try_into()?),
             CliOrganization::Allowed(allowed) => Self::Allowed(allowed.try_into()?),
+            CliOrganization::Transfer(transfer) => Self::Transfer(transfer.try_into()?),
+            CliOrganization::Claim(claim) => Self::Claim(claim.try_into()?),
+            CliOrganization::Perf(perf) => Self::Perf(perf.try_into()?),
             #[cfg(feature = "plus")]
             CliOrganization::Usage(usage) => Self::Usage(usage.try_into()?),
         })
@@ -46,6 +55,9 @@ impl SubCmd for Organization {
             Self::Update(update) => update.exec().await,
             Self::Delete(delete) => delete.exec().await,
             Self::Allowed(allowed) => allowed.exec().await,
+            Self::Transfer(transfer) => transfer.exec().await,
+            Self::Claim(claim) => claim.exec().await,
+            Self::Perf(perf) => perf.exec().await,
             #[cfg(feature = "plus")]
             Self::Usage(usage) => usage.exec().await,
         }
@@ -0,0 +1,85 @@
+use bencher_json::{DateTime, DateTimeMillis, NameId, ResourceId};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::organization::CliOrganizationPerf,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Perf {
+    pub organization: ResourceId,
+    pub projects: Vec<ResourceId>,
+    pub branch: NameId,
+    pub testbed: NameId,
+    pub benchmark: Option<ResourceId>,
+    pub measure: NameId,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliOrganizationPerf> for Perf {
+    type Error = CliError;
+
+    fn try_from(perf: CliOrganizationPerf) -> Result<Self, Self::Error> {
+        let CliOrganizationPerf {
+            organization,
+            projects,
+            branch,
+            testbed,
+            benchmark,
+            measure,
+            start_time,
+            end_time,
+            backend,
+        } = perf;
+        Ok(Self {
+            organization,
+            projects,
+            branch,
+            testbed,
+            benchmark,
+            measure,
+            start_time,
+            end_time,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Perf {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client
+                    .org_perf_get()
+                    .organization(self.organization.clone())
+                    .branch(self.branch.clone())
+                    .testbed(self.testbed.clone())
+                    .measure(self.measure.clone());
+                if !self.projects.is_empty() {
+                    client = client.projects(
+                        self.projects
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    );
+                }
+                if let Some(benchmark) = self.benchmark.clone() {
+                    client = client.benchmark(benchmark);
+                }
+                if let Some(start_time) = self.start_time {
+                    client = client.start_time(DateTimeMillis::from(start_time));
+                }
+                if let Some(end_time) = self.end_time {
+                    client = client.end_time(DateTimeMillis::from(end_time));
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use bencher_client::types::JsonProjectTransfer;
+use bencher_json::{ResourceId, ResourceName, Slug};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::organization::CliOrganizationTransfer,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    pub organization: ResourceId,
+    pub project: ResourceId,
+    pub name: Option<ResourceName>,
+    pub slug: Option<Slug>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliOrganizationTransfer> for Transfer {
+    type Error = CliError;
+
+    fn try_from(transfer: CliOrganizationTransfer) -> Result<Self, Self::Error> {
+        let CliOrganizationTransfer {
+            organization,
+            project,
+            name,
+            slug,
+            backend,
+        } = transfer;
+        Ok(Self {
+            organization,
+            project,
+            name,
+            slug,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Transfer> for JsonProjectTransfer {
+    fn from(transfer: Transfer) -> Self {
+        let Transfer {
+            organization,
+            name,
+            slug,
+            ..
+        } = transfer;
+        Self {
+            organization: organization.into(),
+            name: name.map(Into::into),
+            slug: slug.map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Transfer {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .project_transfer_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{bencher::sub::SubCmd, parser::organization::token_policy::CliTokenPolicy, CliError};
+
+mod revoke;
+mod update;
+mod view;
+
+#[derive(Debug)]
+pub enum TokenPolicy {
+    View(view::View),
+    Update(update::Update),
+    Revoke(revoke::Revoke),
+}
+
+impl TryFrom<CliTokenPolicy> for TokenPolicy {
+    type Error = CliError;
+
+    fn try_from(token_policy: CliTokenPolicy) -> Result<Self, Self::Error> {
+        Ok(match token_policy {
+            CliTokenPolicy::View(view) => Self::View(view.try_into()?),
+            CliTokenPolicy::Update(update) => Self::Update(update.try_into()?),
+            CliTokenPolicy::Revoke(revoke) => Self::Revoke(revoke.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for TokenPolicy {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::View(view) => view.exec().await,
+            Self::Update(update) => update.exec().await,
+            Self::Revoke(revoke) => revoke.exec().await,
+        }
+    }
+}
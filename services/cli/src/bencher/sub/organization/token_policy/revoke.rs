@@ -0,0 +1,61 @@
+use bencher_client::types::JsonRevokeToken;
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::organization::token_policy::CliTokenPolicyRevoke,
+    CliError,
+};
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+#[derive(Debug, Clone)]
+pub struct Revoke {
+    pub organization: ResourceId,
+    #[allow(clippy::struct_field_names)]
+    pub revoke_after: Option<u32>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliTokenPolicyRevoke> for Revoke {
+    type Error = CliError;
+
+    fn try_from(revoke: CliTokenPolicyRevoke) -> Result<Self, Self::Error> {
+        let CliTokenPolicyRevoke {
+            organization,
+            revoke_after,
+            backend,
+        } = revoke;
+        Ok(Self {
+            organization,
+            revoke_after,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Revoke> for JsonRevokeToken {
+    fn from(revoke: Revoke) -> Self {
+        let Revoke { revoke_after, .. } = revoke;
+        Self {
+            after: revoke_after.map(|days| days.saturating_mul(SECONDS_PER_DAY)),
+        }
+    }
+}
+
+impl SubCmd for Revoke {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .org_tokens_revoke_post()
+                    .organization(self.organization.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use bencher_client::types::JsonUpdateTokenPolicy;
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::organization::token_policy::CliTokenPolicyUpdate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub organization: ResourceId,
+    pub max_ttl: Option<u32>,
+    pub require_ttl: bool,
+    pub forbid_non_expiring: bool,
+    pub admin_only: bool,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliTokenPolicyUpdate> for Update {
+    type Error = CliError;
+
+    fn try_from(update: CliTokenPolicyUpdate) -> Result<Self, Self::Error> {
+        let CliTokenPolicyUpdate {
+            organization,
+            max_ttl,
+            require_ttl,
+            forbid_non_expiring,
+            admin_only,
+            backend,
+        } = update;
+        Ok(Self {
+            organization,
+            max_ttl,
+            require_ttl,
+            forbid_non_expiring,
+            admin_only,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Update> for JsonUpdateTokenPolicy {
+    fn from(update: Update) -> Self {
+        let Update {
+            max_ttl,
+            require_ttl,
+            forbid_non_expiring,
+            admin_only,
+            ..
+        } = update;
+        Self {
+            max_ttl,
+            require_ttl: Some(require_ttl),
+            forbid_non_expiring: Some(forbid_non_expiring),
+            admin_only: Some(admin_only),
+        }
+    }
+}
+
+impl SubCmd for Update {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .org_token_policy_put()
+                    .organization(self.organization.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::organization::token_policy::CliTokenPolicyView,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub organization: ResourceId,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliTokenPolicyView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliTokenPolicyView) -> Result<Self, Self::Error> {
+        let CliTokenPolicyView {
+            organization,
+            backend,
+        } = view;
+        Ok(Self {
+            organization,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .org_token_policy_get()
+                    .organization(self.organization.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
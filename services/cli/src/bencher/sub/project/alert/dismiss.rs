@@ -0,0 +1,100 @@
+use bencher_client::types::{AlertSeverity, JsonBulkUpdateAlerts, UpdateAlertStatus};
+use bencher_json::{DateTime, NameId, NonEmpty, ResourceId};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::alert::CliAlertDismiss,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Dismiss {
+    pub project: ResourceId,
+    pub status: UpdateAlertStatus,
+    pub reason: NonEmpty,
+    pub branch: Option<NameId>,
+    pub testbed: Option<NameId>,
+    pub measure: Option<NameId>,
+    pub severity: Option<AlertSeverity>,
+    pub before: Option<DateTime>,
+    pub after: Option<DateTime>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliAlertDismiss> for Dismiss {
+    type Error = CliError;
+
+    fn try_from(dismiss: CliAlertDismiss) -> Result<Self, Self::Error> {
+        let CliAlertDismiss {
+            project,
+            reason,
+            branch,
+            testbed,
+            measure,
+            severity,
+            before,
+            after,
+            silence,
+            backend,
+        } = dismiss;
+        Ok(Self {
+            project,
+            status: if silence {
+                UpdateAlertStatus::Silenced
+            } else {
+                UpdateAlertStatus::Dismissed
+            },
+            reason,
+            branch,
+            testbed,
+            measure,
+            severity: severity.map(Into::into),
+            before,
+            after,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Dismiss> for JsonBulkUpdateAlerts {
+    fn from(dismiss: Dismiss) -> Self {
+        let Dismiss {
+            status,
+            reason,
+            branch,
+            testbed,
+            measure,
+            severity,
+            before,
+            after,
+            ..
+        } = dismiss;
+        Self {
+            status,
+            reason: reason.into(),
+            branch: branch.map(Into::into),
+            testbed: testbed.map(Into::into),
+            measure: measure.map(Into::into),
+            severity,
+            before: before.map(Into::into),
+            after: after.map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Dismiss {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_alerts_patch()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -1,21 +1,25 @@
-use bencher_client::types::{AlertStatus, JsonDirection, ProjAlertsSort};
+use bencher_client::types::{AlertSeverity, AlertStatus, JsonDirection, ProjAlertsSort};
 use bencher_json::ResourceId;
 
 use crate::{
     bencher::{backend::PubBackend, sub::SubCmd},
     parser::{
-        project::alert::{CliAlertList, CliAlertStatus, CliAlertsSort},
+        project::alert::{CliAlertList, CliAlertSeverity, CliAlertStatus, CliAlertsSort},
         CliPagination,
     },
     CliError,
 };
 
+use super::watch::watch_alerts;
+
 #[derive(Debug)]
 pub struct List {
     pub project: ResourceId,
     pub pagination: Pagination,
     pub status: Option<AlertStatus>,
+    pub severity: Option<AlertSeverity>,
     pub archived: bool,
+    pub watch: Option<u64>,
     pub backend: PubBackend,
 }
 
@@ -35,15 +39,20 @@ impl TryFrom<CliAlertList> for List {
             project,
             pagination,
             status,
+            severity,
             archived,
+            watch,
             backend,
         } = list;
         Ok(Self {
             project,
             pagination: pagination.into(),
             status: status.map(Into::into),
+            severity: severity.map(Into::into),
             archived,
-            backend: backend.try_into()?,
+            watch,
+            // Do not log the raw JSON response when watching for new alerts
+            backend: PubBackend::try_from(backend)?.log(watch.is_none()),
         })
     }
 }
@@ -78,8 +87,22 @@ impl From<CliAlertStatus> for AlertStatus {
     }
 }
 
+impl From<CliAlertSeverity> for AlertSeverity {
+    fn from(severity: CliAlertSeverity) -> Self {
+        match severity {
+            CliAlertSeverity::Minor => Self::Minor,
+            CliAlertSeverity::Major => Self::Major,
+            CliAlertSeverity::Critical => Self::Critical,
+        }
+    }
+}
+
 impl SubCmd for List {
     async fn exec(&self) -> Result<(), CliError> {
+        if let Some(interval) = self.watch {
+            return watch_alerts(self, interval).await;
+        }
+
         let _json = self
             .backend
             .send(|client| async move {
@@ -99,6 +122,9 @@ impl SubCmd for List {
                 if let Some(status) = self.status {
                     client = client.status(status);
                 }
+                if let Some(severity) = self.severity {
+                    client = client.severity(severity);
+                }
                 if self.archived {
                     client = client.archived(self.archived);
                 }
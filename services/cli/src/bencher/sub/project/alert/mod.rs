@@ -1,14 +1,19 @@
 use crate::{bencher::sub::SubCmd, parser::project::alert::CliAlert, CliError};
 
+mod dismiss;
 mod list;
+mod stats;
 mod update;
 mod view;
+mod watch;
 
 #[derive(Debug)]
 pub enum Alert {
     List(list::List),
     View(view::View),
     Update(update::Update),
+    Dismiss(dismiss::Dismiss),
+    Stats(stats::Stats),
 }
 
 impl TryFrom<CliAlert> for Alert {
@@ -19,6 +24,8 @@ impl TryFrom<CliAlert> for Alert {
             CliAlert::List(list) => Self::List(list.try_into()?),
             CliAlert::View(view) => Self::View(view.try_into()?),
             CliAlert::Update(update) => Self::Update(update.try_into()?),
+            CliAlert::Dismiss(dismiss) => Self::Dismiss(dismiss.try_into()?),
+            CliAlert::Stats(stats) => Self::Stats(stats.try_into()?),
         })
     }
 }
@@ -29,6 +36,8 @@ impl SubCmd for Alert {
             Self::List(list) => list.exec().await,
             Self::View(view) => view.exec().await,
             Self::Update(update) => update.exec().await,
+            Self::Dismiss(dismiss) => dismiss.exec().await,
+            Self::Stats(stats) => stats.exec().await,
         }
     }
 }
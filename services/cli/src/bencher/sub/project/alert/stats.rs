@@ -0,0 +1,64 @@
+use bencher_client::types::{AlertStatsBucket, AlertStatsWindow};
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::project::alert::{CliAlertStats, CliAlertStatsBucket},
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Stats {
+    pub project: ResourceId,
+    pub window: Option<AlertStatsWindow>,
+    pub bucket: Option<AlertStatsBucket>,
+    pub backend: PubBackend,
+}
+
+impl TryFrom<CliAlertStats> for Stats {
+    type Error = CliError;
+
+    fn try_from(stats: CliAlertStats) -> Result<Self, Self::Error> {
+        let CliAlertStats {
+            project,
+            window,
+            bucket,
+            backend,
+        } = stats;
+        Ok(Self {
+            project,
+            window: window.map(|window| AlertStatsWindow(window.to_string())),
+            bucket: bucket.map(Into::into),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<CliAlertStatsBucket> for AlertStatsBucket {
+    fn from(bucket: CliAlertStatsBucket) -> Self {
+        match bucket {
+            CliAlertStatsBucket::Day => Self::Day,
+            CliAlertStatsBucket::Week => Self::Week,
+            CliAlertStatsBucket::Month => Self::Month,
+        }
+    }
+}
+
+impl SubCmd for Stats {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client.proj_alert_stats_get().project(self.project.clone());
+                if let Some(window) = self.window.clone() {
+                    client = client.window(window);
+                }
+                if let Some(bucket) = self.bucket {
+                    client = client.bucket(bucket);
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -46,6 +46,7 @@ impl From<CliAlertStatusUpdate> for UpdateAlertStatus {
         match status {
             CliAlertStatusUpdate::Active => Self::Active,
             CliAlertStatusUpdate::Dismissed => Self::Dismissed,
+            CliAlertStatusUpdate::Silenced => Self::Silenced,
         }
     }
 }
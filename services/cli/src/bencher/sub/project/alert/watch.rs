@@ -0,0 +1,165 @@
+use std::{collections::HashSet, io::IsTerminal, time::Duration};
+
+use bencher_client::types::{JsonDirection, ProjAlertsSort};
+use bencher_json::{
+    project::{alert::AlertSeverity, boundary::BoundaryLimit},
+    AlertUuid, JsonAlert, JsonAlerts,
+};
+
+use crate::{cli_println, CliError};
+
+use super::list::List;
+
+// The maximum number of alerts that can be returned per page
+const WATCH_PER_PAGE: u8 = u8::MAX;
+
+const RESET: &str = "\x1b[0m";
+const BOLD_RED: &str = "\x1b[1;31m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+
+/// The newest alerts that have already been seen.
+/// Alerts are only compared by their creation timestamp in seconds,
+/// so all of the alerts created in that same second are kept to tell them apart.
+#[derive(Debug, Default)]
+struct LastSeen {
+    timestamp: Option<i64>,
+    alerts: HashSet<AlertUuid>,
+}
+
+impl LastSeen {
+    fn is_older(&self, json_alert: &JsonAlert) -> bool {
+        self.timestamp
+            .is_some_and(|timestamp| json_alert.created.timestamp() < timestamp)
+    }
+
+    fn is_seen(&self, json_alert: &JsonAlert) -> bool {
+        self.timestamp == Some(json_alert.created.timestamp())
+            && self.alerts.contains(&json_alert.uuid)
+    }
+
+    /// The alerts must be sorted newest first
+    fn update(&mut self, json_alerts: &[JsonAlert]) {
+        for json_alert in json_alerts {
+            let timestamp = json_alert.created.timestamp();
+            if self.timestamp.map_or(true, |last| timestamp > last) {
+                self.timestamp = Some(timestamp);
+                self.alerts.clear();
+            } else if self.timestamp != Some(timestamp) {
+                break;
+            }
+            self.alerts.insert(json_alert.uuid);
+        }
+    }
+}
+
+pub async fn watch_alerts(list: &List, interval: u64) -> Result<(), CliError> {
+    // Only alerts created after the watch starts are printed
+    let mut last_seen = LastSeen::default();
+    last_seen.update(&get_alerts(list, 1).await?.0);
+    let color = std::io::stdout().is_terminal();
+    let interval = Duration::from_secs(interval);
+
+    cli_println!("🐰 Watching for new alerts...");
+    cli_println!("");
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                cli_println!("");
+                cli_println!("🐰 Stopped watching for new alerts.");
+                break;
+            }
+            () = tokio::time::sleep(interval) => {
+                let new_alerts = get_new_alerts(list, &last_seen).await?;
+                last_seen.update(&new_alerts);
+                // The alerts are sorted newest first, so print them in reverse order
+                for json_alert in new_alerts.iter().rev() {
+                    cli_println!("{}", format_alert(json_alert, color));
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Page through the alerts, newest first, until reaching the last seen alerts
+async fn get_new_alerts(list: &List, last_seen: &LastSeen) -> Result<Vec<JsonAlert>, CliError> {
+    let mut new_alerts = Vec::new();
+    // New alerts may shift older alerts onto the next page while paginating
+    let mut new_uuids = HashSet::new();
+    for page in 1.. {
+        let json_alerts = get_alerts(list, page).await?.0;
+        let is_last_page = json_alerts.len() < usize::from(WATCH_PER_PAGE);
+        for json_alert in json_alerts {
+            if last_seen.is_older(&json_alert) {
+                return Ok(new_alerts);
+            }
+            if last_seen.is_seen(&json_alert) || !new_uuids.insert(json_alert.uuid) {
+                continue;
+            }
+            new_alerts.push(json_alert);
+        }
+        if is_last_page {
+            break;
+        }
+    }
+    Ok(new_alerts)
+}
+
+async fn get_alerts(list: &List, page: u32) -> Result<JsonAlerts, CliError> {
+    list.backend
+        .send_with(|client| async move {
+            let mut client = client
+                .proj_alerts_get()
+                .project(list.project.clone())
+                .sort(ProjAlertsSort::Created)
+                .direction(JsonDirection::Desc)
+                .per_page(WATCH_PER_PAGE)
+                .page(page);
+            if let Some(status) = list.status {
+                client = client.status(status);
+            }
+            if let Some(severity) = list.severity {
+                client = client.severity(severity);
+            }
+            if list.archived {
+                client = client.archived(list.archived);
+            }
+            client.send().await
+        })
+        .await
+        .map_err(Into::into)
+}
+
+fn format_alert(json_alert: &JsonAlert, color: bool) -> String {
+    let limit = match json_alert.limit {
+        BoundaryLimit::Lower => ("lower", json_alert.boundary.lower_limit),
+        BoundaryLimit::Upper => ("upper", json_alert.boundary.upper_limit),
+    };
+    let line = format!(
+        "[{created}] {status} {severity} {benchmark} ({measure}): {value} exceeded the {side} limit{limit}",
+        created = json_alert.created,
+        status = json_alert.status,
+        severity = json_alert.severity,
+        benchmark = json_alert.benchmark.name,
+        measure = json_alert.threshold.measure.name,
+        value = json_alert.metric.value,
+        side = limit.0,
+        limit = limit
+            .1
+            .map(|limit| format!(" of {limit}"))
+            .unwrap_or_default(),
+    );
+    if color {
+        let style = match json_alert.severity {
+            AlertSeverity::Critical => BOLD_RED,
+            AlertSeverity::Major => RED,
+            AlertSeverity::Minor => YELLOW,
+        };
+        format!("{style}{line}{RESET}")
+    } else {
+        line
+    }
+}
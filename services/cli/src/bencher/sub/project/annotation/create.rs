@@ -0,0 +1,71 @@
+use bencher_client::types::JsonNewAnnotation;
+use bencher_json::{DateTime, NonEmpty, ResourceId, ResourceName};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::annotation::CliAnnotationCreate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Create {
+    pub project: ResourceId,
+    pub title: ResourceName,
+    pub description: Option<NonEmpty>,
+    pub time: Option<DateTime>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliAnnotationCreate> for Create {
+    type Error = CliError;
+
+    fn try_from(create: CliAnnotationCreate) -> Result<Self, Self::Error> {
+        let CliAnnotationCreate {
+            project,
+            title,
+            description,
+            time,
+            backend,
+        } = create;
+        Ok(Self {
+            project,
+            title,
+            description,
+            time,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Create> for JsonNewAnnotation {
+    fn from(create: Create) -> Self {
+        let Create {
+            title,
+            description,
+            time,
+            ..
+        } = create;
+        Self {
+            title: title.into(),
+            description: description.map(Into::into),
+            time: time.map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Create {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_annotation_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
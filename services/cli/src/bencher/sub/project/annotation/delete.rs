@@ -0,0 +1,48 @@
+use bencher_json::{AnnotationUuid, ResourceId};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::annotation::CliAnnotationDelete,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Delete {
+    pub project: ResourceId,
+    pub annotation: AnnotationUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliAnnotationDelete> for Delete {
+    type Error = CliError;
+
+    fn try_from(delete: CliAnnotationDelete) -> Result<Self, Self::Error> {
+        let CliAnnotationDelete {
+            project,
+            annotation,
+            backend,
+        } = delete;
+        Ok(Self {
+            project,
+            annotation,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Delete {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_annotation_delete()
+                    .project(self.project.clone())
+                    .annotation(self.annotation)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
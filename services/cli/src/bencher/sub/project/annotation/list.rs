@@ -0,0 +1,105 @@
+use bencher_client::types::{JsonDirection, ProjAnnotationsSort};
+use bencher_json::{DateTime, DateTimeMillis, ResourceId};
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::{
+        project::annotation::{CliAnnotationList, CliAnnotationsSort},
+        CliPagination,
+    },
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct List {
+    pub project: ResourceId,
+    pub search: Option<String>,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub pagination: Pagination,
+    pub backend: PubBackend,
+}
+
+#[derive(Debug)]
+pub struct Pagination {
+    pub sort: Option<ProjAnnotationsSort>,
+    pub direction: Option<JsonDirection>,
+    pub per_page: Option<u8>,
+    pub page: Option<u32>,
+}
+
+impl TryFrom<CliAnnotationList> for List {
+    type Error = CliError;
+
+    fn try_from(list: CliAnnotationList) -> Result<Self, Self::Error> {
+        let CliAnnotationList {
+            project,
+            search,
+            start_time,
+            end_time,
+            pagination,
+            backend,
+        } = list;
+        Ok(Self {
+            project,
+            search,
+            start_time,
+            end_time,
+            pagination: pagination.into(),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<CliPagination<CliAnnotationsSort>> for Pagination {
+    fn from(pagination: CliPagination<CliAnnotationsSort>) -> Self {
+        let CliPagination {
+            sort,
+            direction,
+            per_page,
+            page,
+        } = pagination;
+        Self {
+            sort: sort.map(|sort| match sort {
+                CliAnnotationsSort::Time => ProjAnnotationsSort::Time,
+            }),
+            direction: direction.map(Into::into),
+            page,
+            per_page,
+        }
+    }
+}
+
+impl SubCmd for List {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client.proj_annotations_get().project(self.project.clone());
+                if let Some(search) = self.search.clone() {
+                    client = client.search(search);
+                }
+                if let Some(start_time) = self.start_time {
+                    client = client.start_time(DateTimeMillis::from(start_time));
+                }
+                if let Some(end_time) = self.end_time {
+                    client = client.end_time(DateTimeMillis::from(end_time));
+                }
+                if let Some(sort) = self.pagination.sort {
+                    client = client.sort(sort);
+                }
+                if let Some(direction) = self.pagination.direction {
+                    client = client.direction(direction);
+                }
+                if let Some(per_page) = self.pagination.per_page {
+                    client = client.per_page(per_page);
+                }
+                if let Some(page) = self.pagination.page {
+                    client = client.page(page);
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{bencher::sub::SubCmd, parser::project::annotation::CliAnnotation, CliError};
+
+mod create;
+mod delete;
+mod list;
+mod update;
+mod view;
+
+#[derive(Debug)]
+pub enum Annotation {
+    List(list::List),
+    Create(create::Create),
+    View(view::View),
+    Update(update::Update),
+    Delete(delete::Delete),
+}
+
+impl TryFrom<CliAnnotation> for Annotation {
+    type Error = CliError;
+
+    fn try_from(annotation: CliAnnotation) -> Result<Self, Self::Error> {
+        Ok(match annotation {
+            CliAnnotation::List(list) => Self::List(list.try_into()?),
+            CliAnnotation::Create(create) => Self::Create(create.try_into()?),
+            CliAnnotation::View(view) => Self::View(view.try_into()?),
+            CliAnnotation::Update(update) => Self::Update(update.try_into()?),
+            CliAnnotation::Delete(delete) => Self::Delete(delete.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Annotation {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::List(list) => list.exec().await,
+            Self::Create(create) => create.exec().await,
+            Self::View(view) => view.exec().await,
+            Self::Update(update) => update.exec().await,
+            Self::Delete(delete) => delete.exec().await,
+        }
+    }
+}
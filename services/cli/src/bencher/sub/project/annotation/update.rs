@@ -0,0 +1,97 @@
+use bencher_client::types::{JsonAnnotationPatch, JsonAnnotationPatchNull, JsonUpdateAnnotation};
+use bencher_json::{AnnotationUuid, DateTime, NonEmpty, ResourceId, ResourceName};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::annotation::CliAnnotationUpdate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+#[allow(clippy::option_option)]
+pub struct Update {
+    pub project: ResourceId,
+    pub annotation: AnnotationUuid,
+    pub title: Option<ResourceName>,
+    pub description: Option<Option<NonEmpty>>,
+    pub time: Option<DateTime>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliAnnotationUpdate> for Update {
+    type Error = CliError;
+
+    fn try_from(update: CliAnnotationUpdate) -> Result<Self, Self::Error> {
+        let CliAnnotationUpdate {
+            project,
+            annotation,
+            title,
+            description,
+            time,
+            backend,
+        } = update;
+        Ok(Self {
+            project,
+            annotation,
+            title,
+            description: description.map(Into::into),
+            time,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Update> for JsonUpdateAnnotation {
+    fn from(update: Update) -> Self {
+        let Update {
+            title,
+            description,
+            time,
+            ..
+        } = update;
+        match description {
+            Some(Some(description)) => Self {
+                subtype_0: Some(JsonAnnotationPatch {
+                    title: title.map(Into::into),
+                    description: Some(description.into()),
+                    time: time.map(Into::into),
+                }),
+                subtype_1: None,
+            },
+            Some(None) => Self {
+                subtype_0: None,
+                subtype_1: Some(JsonAnnotationPatchNull {
+                    title: title.map(Into::into),
+                    description: (),
+                    time: time.map(Into::into),
+                }),
+            },
+            None => Self {
+                subtype_0: Some(JsonAnnotationPatch {
+                    title: title.map(Into::into),
+                    description: None,
+                    time: time.map(Into::into),
+                }),
+                subtype_1: None,
+            },
+        }
+    }
+}
+
+impl SubCmd for Update {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_annotation_patch()
+                    .project(self.project.clone())
+                    .annotation(self.annotation)
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use bencher_json::{AnnotationUuid, ResourceId};
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::project::annotation::CliAnnotationView,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub project: ResourceId,
+    pub annotation: AnnotationUuid,
+    pub backend: PubBackend,
+}
+
+impl TryFrom<CliAnnotationView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliAnnotationView) -> Result<Self, Self::Error> {
+        let CliAnnotationView {
+            project,
+            annotation,
+            backend,
+        } = view;
+        Ok(Self {
+            project,
+            annotation,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_annotation_get()
+                    .project(self.project.clone())
+                    .annotation(self.annotation)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
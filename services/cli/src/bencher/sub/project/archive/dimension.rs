@@ -116,6 +116,7 @@ impl Dimension {
             slug: None,
             start_point: None,
             archived: Some(action.into()),
+            frozen_baseline: None,
         };
         backend
             .send(|client| async move {
@@ -174,6 +175,7 @@ impl Dimension {
         let update = &JsonUpdateTestbed {
             name: None,
             slug: None,
+            spec: None,
             archived: Some(action.into()),
         };
         backend
@@ -233,6 +235,7 @@ impl Dimension {
             name: None,
             slug: None,
             archived: Some(action.into()),
+            flaky: None,
         };
         backend
             .send(|client| async move {
@@ -3,7 +3,7 @@ use bencher_json::ResourceId;
 use crate::{
     bencher::{backend::AuthBackend, sub::SubCmd},
     parser::project::archive::CliArchive,
-    CliError,
+    CliError, ExitClass,
 };
 
 mod action;
@@ -49,6 +49,16 @@ pub enum ArchiveError {
     },
 }
 
+impl ArchiveError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::ParseDimension { .. } | Self::NotFound { .. } => ExitClass::Usage,
+            Self::GetDimension { err, .. } | Self::ArchiveDimension { err, .. } => err.exit_class(),
+            Self::MultipleWithName { .. } => ExitClass::Failure,
+        }
+    }
+}
+
 impl TryFrom<(CliArchive, ArchiveAction)> for Archive {
     type Error = CliError;
 
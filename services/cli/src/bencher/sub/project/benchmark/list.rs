@@ -17,6 +17,7 @@ pub struct List {
     pub search: Option<String>,
     pub pagination: Pagination,
     pub archived: bool,
+    pub flaky: bool,
     pub backend: PubBackend,
 }
 
@@ -38,6 +39,7 @@ impl TryFrom<CliBenchmarkList> for List {
             search,
             pagination,
             archived,
+            flaky,
             backend,
         } = list;
         Ok(Self {
@@ -46,6 +48,7 @@ impl TryFrom<CliBenchmarkList> for List {
             search,
             pagination: pagination.into(),
             archived,
+            flaky,
             backend: backend.try_into()?,
         })
     }
@@ -97,6 +100,9 @@ impl SubCmd for List {
                 if self.archived {
                     client = client.archived(self.archived);
                 }
+                if self.flaky {
+                    client = client.flaky(self.flaky);
+                }
                 client.send().await
             })
             .await?;
@@ -3,12 +3,14 @@ use crate::{bencher::sub::SubCmd, parser::project::benchmark::CliBenchmark, CliE
 mod create;
 mod delete;
 mod list;
+mod search;
 mod update;
 mod view;
 
 #[derive(Debug)]
 pub enum Benchmark {
     List(list::List),
+    Search(search::Search),
     Create(create::Create),
     View(view::View),
     Update(update::Update),
@@ -21,6 +23,7 @@ impl TryFrom<CliBenchmark> for Benchmark {
     fn try_from(benchmark: CliBenchmark) -> Result<Self, Self::Error> {
         Ok(match benchmark {
             CliBenchmark::List(list) => Self::List(list.try_into()?),
+            CliBenchmark::Search(search) => Self::Search(search.try_into()?),
             CliBenchmark::Create(create) => Self::Create(create.try_into()?),
             CliBenchmark::View(view) => Self::View(view.try_into()?),
             CliBenchmark::Update(update) => Self::Update(update.try_into()?),
@@ -33,6 +36,7 @@ impl SubCmd for Benchmark {
     async fn exec(&self) -> Result<(), CliError> {
         match self {
             Self::List(list) => list.exec().await,
+            Self::Search(search) => search.exec().await,
             Self::Create(create) => create.exec().await,
             Self::View(create) => create.exec().await,
             Self::Update(update) => update.exec().await,
@@ -0,0 +1,72 @@
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::project::benchmark::CliBenchmarkSearch,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Search {
+    pub project: ResourceId,
+    #[allow(clippy::struct_field_names)]
+    pub search: String,
+    pub fuzzy: bool,
+    pub per_page: Option<u8>,
+    pub page: Option<u32>,
+    pub archived: bool,
+    pub backend: PubBackend,
+}
+
+impl TryFrom<CliBenchmarkSearch> for Search {
+    type Error = CliError;
+
+    fn try_from(search: CliBenchmarkSearch) -> Result<Self, Self::Error> {
+        let CliBenchmarkSearch {
+            project,
+            search,
+            substring,
+            per_page,
+            page,
+            archived,
+            backend,
+        } = search;
+        Ok(Self {
+            project,
+            search,
+            fuzzy: !substring,
+            per_page,
+            page,
+            archived,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Search {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client
+                    .proj_benchmarks_get()
+                    .project(self.project.clone())
+                    .search(self.search.clone());
+                if self.fuzzy {
+                    client = client.fuzzy(self.fuzzy);
+                }
+                if let Some(per_page) = self.per_page {
+                    client = client.per_page(per_page);
+                }
+                if let Some(page) = self.page {
+                    client = client.page(page);
+                }
+                if self.archived {
+                    client = client.archived(self.archived);
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -14,6 +14,7 @@ pub struct Update {
     pub name: Option<BenchmarkName>,
     pub slug: Option<Slug>,
     pub archived: Option<bool>,
+    pub flaky: Option<bool>,
     pub backend: AuthBackend,
 }
 
@@ -27,6 +28,7 @@ impl TryFrom<CliBenchmarkUpdate> for Update {
             name,
             slug,
             archived,
+            flaky,
             backend,
         } = create;
         Ok(Self {
@@ -35,6 +37,7 @@ impl TryFrom<CliBenchmarkUpdate> for Update {
             name,
             slug,
             archived: archived.into(),
+            flaky: flaky.into(),
             backend: backend.try_into()?,
         })
     }
@@ -46,12 +49,14 @@ impl From<Update> for JsonUpdateBenchmark {
             name,
             slug,
             archived,
+            flaky,
             ..
         } = update;
         Self {
             name: name.map(Into::into),
             slug: slug.map(Into::into),
             archived,
+            flaky,
         }
     }
 }
@@ -0,0 +1,53 @@
+use bencher_json::{HeadUuid, ResourceId};
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::project::branch::CliBranchLineage,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Lineage {
+    pub project: ResourceId,
+    pub branch: ResourceId,
+    pub head: Option<HeadUuid>,
+    pub backend: PubBackend,
+}
+
+impl TryFrom<CliBranchLineage> for Lineage {
+    type Error = CliError;
+
+    fn try_from(lineage: CliBranchLineage) -> Result<Self, Self::Error> {
+        let CliBranchLineage {
+            project,
+            branch,
+            head,
+            backend,
+        } = lineage;
+        Ok(Self {
+            project,
+            branch,
+            head,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Lineage {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client
+                    .proj_branch_lineage_get()
+                    .project(self.project.clone())
+                    .branch(self.branch.clone());
+                if let Some(head) = self.head {
+                    client = client.head(head);
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -2,11 +2,16 @@ use crate::{bencher::sub::SubCmd, parser::project::branch::CliBranch, CliError};
 
 mod create;
 mod delete;
+mod lineage;
 mod list;
 pub mod start_point;
+mod tags;
 mod update;
+mod versions;
 mod view;
 
+pub use tags::BranchTagsError;
+
 #[derive(Debug)]
 pub enum Branch {
     List(list::List),
@@ -14,6 +19,9 @@ pub enum Branch {
     View(view::View),
     Update(update::Update),
     Delete(delete::Delete),
+    Lineage(lineage::Lineage),
+    Versions(versions::Versions),
+    Tags(tags::Tags),
 }
 
 impl TryFrom<CliBranch> for Branch {
@@ -26,6 +34,9 @@ impl TryFrom<CliBranch> for Branch {
             CliBranch::View(view) => Self::View(view.try_into()?),
             CliBranch::Update(update) => Self::Update(update.try_into()?),
             CliBranch::Delete(delete) => Self::Delete(delete.try_into()?),
+            CliBranch::Lineage(lineage) => Self::Lineage(lineage.try_into()?),
+            CliBranch::Versions(versions) => Self::Versions(versions.try_into()?),
+            CliBranch::Tags(tags) => Self::Tags(tags.try_into()?),
         })
     }
 }
@@ -38,6 +49,9 @@ impl SubCmd for Branch {
             Self::View(view) => view.exec().await,
             Self::Update(update) => update.exec().await,
             Self::Delete(delete) => delete.exec().await,
+            Self::Lineage(lineage) => lineage.exec().await,
+            Self::Versions(versions) => versions.exec().await,
+            Self::Tags(tags) => tags.exec().await,
         }
     }
 }
@@ -0,0 +1,372 @@
+use bencher_client::types::{JsonNewBranchTag, JsonNewBranchTags};
+use bencher_json::{
+    project::branch::MAX_BRANCH_TAGS, DateTime, GitHash, JsonBranchTags, ResourceId, ResourceName,
+};
+use camino::Utf8PathBuf;
+use octocrab::Octocrab;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    cli_println,
+    parser::project::branch::{CliBranchTags, CliBranchTagsSource},
+    CliError, ExitClass,
+};
+
+// The GitHub API returns at most 100 tags per page
+const GITHUB_TAGS_PER_PAGE: u8 = 100;
+
+#[derive(Debug)]
+pub struct Tags {
+    pub project: ResourceId,
+    pub branch: ResourceId,
+    pub source: TagsSource,
+    pub prefix: Option<String>,
+    pub since: Option<DateTime>,
+    pub dry_run: bool,
+    pub backend: AuthBackend,
+}
+
+/// Where to find the git tags
+#[derive(Debug)]
+pub enum TagsSource {
+    /// A local git checkout
+    Git(Utf8PathBuf),
+    /// A GitHub repository, using the GitHub API
+    GitHub {
+        owner: String,
+        repo: String,
+        token: Option<String>,
+    },
+}
+
+/// A git tag, along with the commit that it points to
+#[derive(Debug)]
+struct GitTag {
+    name: String,
+    hash: GitHash,
+    time: DateTime,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum BranchTagsError {
+    #[error("Failed to find a git repository at ({path}): {err}")]
+    Discover {
+        path: Utf8PathBuf,
+        err: Box<gix::discover::Error>,
+    },
+    #[error("GitHub repository is not of the form `owner/repo`: ({0})")]
+    GitHubRepo(String),
+    #[error("Failed to create GitHub API client: {0}")]
+    GitHubClient(octocrab::Error),
+    #[error("Failed to list the tags for GitHub repository ({owner}/{repo}): {err}")]
+    GitHubTags {
+        owner: String,
+        repo: String,
+        err: octocrab::Error,
+    },
+    #[error("Failed to get commit ({hash}) for GitHub repository ({owner}/{repo}): {err}")]
+    GitHubCommit {
+        owner: String,
+        repo: String,
+        hash: GitHash,
+        err: octocrab::Error,
+    },
+    #[error("No git tags were found to record")]
+    NoTags,
+    #[error("Failed to serialize git tags: {0}")]
+    SerializeTags(serde_json::Error),
+    #[error("Failed to record git tags: {0}")]
+    RecordTags(crate::BackendError),
+}
+
+impl BranchTagsError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::Discover { .. } | Self::GitHubRepo(_) | Self::NoTags => ExitClass::Usage,
+            Self::GitHubClient(_) | Self::SerializeTags(_) => ExitClass::Failure,
+            Self::GitHubTags { .. } | Self::GitHubCommit { .. } => ExitClass::Network,
+            Self::RecordTags(err) => err.exit_class(),
+        }
+    }
+}
+
+impl TryFrom<CliBranchTags> for Tags {
+    type Error = CliError;
+
+    fn try_from(tags: CliBranchTags) -> Result<Self, Self::Error> {
+        let CliBranchTags {
+            project,
+            branch,
+            source,
+            prefix,
+            since,
+            dry_run,
+            backend,
+        } = tags;
+        Ok(Self {
+            project,
+            branch,
+            source: source.try_into()?,
+            prefix,
+            since,
+            dry_run,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<CliBranchTagsSource> for TagsSource {
+    type Error = BranchTagsError;
+
+    fn try_from(source: CliBranchTagsSource) -> Result<Self, Self::Error> {
+        let CliBranchTagsSource {
+            git_dir,
+            github,
+            github_token,
+        } = source;
+        Ok(if let Some(github) = github {
+            let (owner, repo) = github
+                .split_once('/')
+                .filter(|(owner, repo)| {
+                    !owner.is_empty() && !repo.is_empty() && !repo.contains('/')
+                })
+                .ok_or_else(|| BranchTagsError::GitHubRepo(github.clone()))?;
+            Self::GitHub {
+                owner: owner.to_owned(),
+                repo: repo.to_owned(),
+                token: github_token,
+            }
+        } else {
+            Self::Git(git_dir.unwrap_or_else(|| ".".into()))
+        })
+    }
+}
+
+impl SubCmd for Tags {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl Tags {
+    async fn exec_inner(&self) -> Result<(), BranchTagsError> {
+        let git_tags = match &self.source {
+            TagsSource::Git(path) => local_tags(path)?,
+            TagsSource::GitHub { owner, repo, token } => {
+                github_tags(owner, repo, token.as_deref(), self.prefix.as_deref()).await?
+            },
+        };
+
+        let mut git_tags = git_tags
+            .into_iter()
+            .filter(|tag| self.is_included(tag))
+            .collect::<Vec<_>>();
+        // Tags must be recorded in chronological order
+        git_tags.sort_by_key(|tag| tag.time.timestamp());
+        let json_tags = git_tags
+            .into_iter()
+            .filter_map(|GitTag { name, hash, time }| {
+                // Tags with a name that is too long to be an annotation title are skipped
+                let Ok(name) = name.parse::<ResourceName>() else {
+                    cli_println!("Skipping git tag with an invalid name: {name}");
+                    return None;
+                };
+                Some(JsonNewBranchTag {
+                    name: name.into(),
+                    hash: hash.into(),
+                    time: time.into(),
+                })
+            })
+            .collect::<Vec<_>>();
+        if json_tags.is_empty() {
+            return Err(BranchTagsError::NoTags);
+        }
+
+        cli_println!("Recording {count} git tag(s)", count = json_tags.len());
+
+        // If performing a dry run, don't actually record the tags
+        if self.dry_run {
+            cli_println!(
+                "{}",
+                serde_json::to_string_pretty(&json_tags).map_err(BranchTagsError::SerializeTags)?
+            );
+            return Ok(());
+        }
+
+        let total = json_tags.len();
+        let (mut annotations, mut versions) = (0, 0);
+        for chunk in json_tags.chunks(MAX_BRANCH_TAGS) {
+            let body = &JsonNewBranchTags {
+                tags: chunk.to_vec(),
+            };
+            let json: JsonBranchTags = self
+                .backend
+                .send_with(|client| async move {
+                    client
+                        .proj_branch_tags_post()
+                        .project(self.project.clone())
+                        .branch(self.branch.clone())
+                        .body(body.clone())
+                        .send()
+                        .await
+                })
+                .await
+                .map_err(BranchTagsError::RecordTags)?;
+            annotations += json.annotations.len();
+            versions += json.versions.len();
+        }
+        cli_println!(
+            "Recorded {total} git tag(s) with {annotations} new annotation(s) and {versions} new version(s)"
+        );
+
+        Ok(())
+    }
+
+    fn is_included(&self, tag: &GitTag) -> bool {
+        self.prefix
+            .as_ref()
+            .map_or(true, |prefix| tag.name.starts_with(prefix))
+            && self
+                .since
+                .map_or(true, |since| tag.time.timestamp() >= since.timestamp())
+    }
+}
+
+/// Find all of the tags in a local git checkout, peeled to the commit that they point to.
+/// The time of each tag is the commit time.
+fn local_tags(path: &Utf8PathBuf) -> Result<Vec<GitTag>, BranchTagsError> {
+    let repo = gix::discover(path).map_err(|err| BranchTagsError::Discover {
+        path: path.clone(),
+        err: Box::new(err),
+    })?;
+    let Some(references) = repo.references().ok() else {
+        return Ok(Vec::new());
+    };
+    let Some(tags) = references.tags().ok() else {
+        return Ok(Vec::new());
+    };
+    let mut git_tags = Vec::new();
+    for mut tag in tags.flatten() {
+        let name = tag.name().shorten().to_string();
+        let Some(commit) = tag
+            .peel_to_id_in_place()
+            .ok()
+            .and_then(|id| id.object().ok())
+            .and_then(|object| object.try_into_commit().ok())
+        else {
+            continue;
+        };
+        let Some(time) = commit
+            .time()
+            .ok()
+            .and_then(|time| DateTime::try_from(time.seconds).ok())
+        else {
+            continue;
+        };
+        git_tags.push(GitTag {
+            name,
+            hash: commit.id.into(),
+            time,
+        });
+    }
+    Ok(git_tags)
+}
+
+#[derive(Serialize)]
+struct GitHubPage {
+    per_page: u8,
+    page: u32,
+}
+
+/// The subset of a GitHub tag that is used
+/// <https://docs.github.com/en/rest/repos/repos#list-repository-tags>
+#[derive(Deserialize)]
+struct GitHubTag {
+    name: String,
+    commit: GitHubTagCommit,
+}
+
+#[derive(Deserialize)]
+struct GitHubTagCommit {
+    sha: GitHash,
+}
+
+/// The subset of a GitHub commit that is used
+/// <https://docs.github.com/en/rest/commits/commits#get-a-commit>
+#[derive(Deserialize)]
+struct GitHubCommit {
+    commit: GitHubCommitDetails,
+}
+
+#[derive(Deserialize)]
+struct GitHubCommitDetails {
+    committer: GitHubCommitter,
+}
+
+#[derive(Deserialize)]
+struct GitHubCommitter {
+    date: DateTime,
+}
+
+/// List all of the tags in a GitHub repository, along with the time of the commit that each points to.
+/// The GitHub API does not include the commit time with the tag,
+/// so tags that do not start with the prefix are skipped before looking up their commit.
+async fn github_tags(
+    owner: &str,
+    repo: &str,
+    token: Option<&str>,
+    prefix: Option<&str>,
+) -> Result<Vec<GitTag>, BranchTagsError> {
+    let mut builder = Octocrab::builder();
+    if let Some(token) = token {
+        builder = builder.personal_token(token.to_owned());
+    }
+    let github_client = builder.build().map_err(BranchTagsError::GitHubClient)?;
+
+    let mut github_tags = Vec::new();
+    for page in 1.. {
+        let tags: Vec<GitHubTag> = github_client
+            .get(
+                format!("/repos/{owner}/{repo}/tags"),
+                Some(&GitHubPage {
+                    per_page: GITHUB_TAGS_PER_PAGE,
+                    page,
+                }),
+            )
+            .await
+            .map_err(|err| BranchTagsError::GitHubTags {
+                owner: owner.to_owned(),
+                repo: repo.to_owned(),
+                err,
+            })?;
+        let last_page = tags.len() < usize::from(GITHUB_TAGS_PER_PAGE);
+        github_tags.extend(
+            tags.into_iter()
+                .filter(|tag| prefix.map_or(true, |prefix| tag.name.starts_with(prefix))),
+        );
+        if last_page {
+            break;
+        }
+    }
+
+    let mut git_tags = Vec::with_capacity(github_tags.len());
+    for GitHubTag { name, commit } in github_tags {
+        let hash = commit.sha;
+        let github_commit: GitHubCommit = github_client
+            .get(format!("/repos/{owner}/{repo}/commits/{hash}"), None::<&()>)
+            .await
+            .map_err(|err| BranchTagsError::GitHubCommit {
+                owner: owner.to_owned(),
+                repo: repo.to_owned(),
+                hash: hash.clone(),
+                err,
+            })?;
+        git_tags.push(GitTag {
+            name,
+            hash,
+            time: github_commit.commit.committer.date,
+        });
+    }
+    Ok(git_tags)
+}
@@ -17,6 +17,7 @@ pub struct Update {
     pub slug: Option<Slug>,
     pub start_point: StartPoint,
     pub archived: Option<bool>,
+    pub frozen_baseline: Option<bool>,
     pub backend: AuthBackend,
 }
 
@@ -31,6 +32,7 @@ impl TryFrom<CliBranchUpdate> for Update {
             slug,
             start_point,
             archived,
+            frozen_baseline,
             backend,
         } = create;
         Ok(Self {
@@ -40,6 +42,7 @@ impl TryFrom<CliBranchUpdate> for Update {
             slug,
             start_point: start_point.into(),
             archived: archived.into(),
+            frozen_baseline: frozen_baseline.into(),
             backend: backend.try_into()?,
         })
     }
@@ -52,6 +55,7 @@ impl From<Update> for JsonUpdateBranch {
             slug,
             start_point,
             archived,
+            frozen_baseline,
             ..
         } = update;
         Self {
@@ -59,6 +63,7 @@ impl From<Update> for JsonUpdateBranch {
             slug: slug.map(Into::into),
             start_point: start_point.into(),
             archived,
+            frozen_baseline,
         }
     }
 }
@@ -0,0 +1,105 @@
+use bencher_client::types::{JsonDirection, ProjBranchVersionsSort};
+use bencher_json::{HeadUuid, ResourceId};
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::{
+        project::branch::{CliBranchVersions, CliBranchVersionsSort},
+        CliPagination,
+    },
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Versions {
+    pub project: ResourceId,
+    pub branch: ResourceId,
+    pub head: Option<HeadUuid>,
+    pub benchmarked: Option<bool>,
+    pub pagination: Pagination,
+    pub backend: PubBackend,
+}
+
+#[derive(Debug)]
+pub struct Pagination {
+    pub sort: Option<ProjBranchVersionsSort>,
+    pub direction: Option<JsonDirection>,
+    pub per_page: Option<u8>,
+    pub page: Option<u32>,
+}
+
+impl TryFrom<CliBranchVersions> for Versions {
+    type Error = CliError;
+
+    fn try_from(versions: CliBranchVersions) -> Result<Self, Self::Error> {
+        let CliBranchVersions {
+            project,
+            branch,
+            head,
+            benchmarked,
+            pagination,
+            backend,
+        } = versions;
+        Ok(Self {
+            project,
+            branch,
+            head,
+            benchmarked,
+            pagination: pagination.into(),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<CliPagination<CliBranchVersionsSort>> for Pagination {
+    fn from(pagination: CliPagination<CliBranchVersionsSort>) -> Self {
+        let CliPagination {
+            sort,
+            direction,
+            per_page,
+            page,
+        } = pagination;
+        Self {
+            sort: sort.map(|sort| match sort {
+                CliBranchVersionsSort::Number => ProjBranchVersionsSort::Number,
+            }),
+            direction: direction.map(Into::into),
+            page,
+            per_page,
+        }
+    }
+}
+
+impl SubCmd for Versions {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client
+                    .proj_branch_versions_get()
+                    .project(self.project.clone())
+                    .branch(self.branch.clone());
+                if let Some(head) = self.head {
+                    client = client.head(head);
+                }
+                if let Some(benchmarked) = self.benchmarked {
+                    client = client.benchmarked(benchmarked);
+                }
+                if let Some(sort) = self.pagination.sort {
+                    client = client.sort(sort);
+                }
+                if let Some(direction) = self.pagination.direction {
+                    client = client.direction(direction);
+                }
+                if let Some(per_page) = self.pagination.per_page {
+                    client = client.per_page(per_page);
+                }
+                if let Some(page) = self.pagination.page {
+                    client = client.page(page);
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
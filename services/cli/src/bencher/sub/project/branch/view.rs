@@ -1,7 +1,10 @@
-use bencher_json::ResourceId;
+use bencher_client::types::JsonDirection;
+use bencher_json::{JsonBranchVersions, ResourceId};
+use tabled::Table;
 
 use crate::{
     bencher::{backend::PubBackend, sub::SubCmd},
+    cli_println,
     parser::project::branch::CliBranchView,
     CliError,
 };
@@ -10,9 +13,17 @@ use crate::{
 pub struct View {
     pub project: ResourceId,
     pub branch: ResourceId,
+    pub history: Option<History>,
     pub backend: PubBackend,
 }
 
+#[derive(Debug)]
+pub struct History {
+    pub direction: Option<JsonDirection>,
+    pub per_page: Option<u8>,
+    pub page: Option<u32>,
+}
+
 impl TryFrom<CliBranchView> for View {
     type Error = CliError;
 
@@ -20,11 +31,20 @@ impl TryFrom<CliBranchView> for View {
         let CliBranchView {
             project,
             branch,
+            history,
+            direction,
+            per_page,
+            page,
             backend,
         } = view;
         Ok(Self {
             project,
             branch,
+            history: history.then(|| History {
+                direction: direction.map(Into::into),
+                per_page,
+                page,
+            }),
             backend: backend.try_into()?,
         })
     }
@@ -43,6 +63,38 @@ impl SubCmd for View {
                     .await
             })
             .await?;
+        if let Some(history) = &self.history {
+            self.history(history).await?;
+        }
+        Ok(())
+    }
+}
+
+impl View {
+    async fn history(&self, history: &History) -> Result<(), CliError> {
+        // The versions are shown as a table instead of being logged as JSON
+        let json_versions: JsonBranchVersions = self
+            .backend
+            .clone()
+            .log(false)
+            .send_with(|client| async move {
+                let mut client = client
+                    .proj_branch_versions_get()
+                    .project(self.project.clone())
+                    .branch(self.branch.clone());
+                if let Some(direction) = history.direction {
+                    client = client.direction(direction);
+                }
+                if let Some(per_page) = history.per_page {
+                    client = client.per_page(per_page);
+                }
+                if let Some(page) = history.page {
+                    client = client.page(page);
+                }
+                client.send().await
+            })
+            .await?;
+        cli_println!("{}", Table::from(json_versions));
         Ok(())
     }
 }
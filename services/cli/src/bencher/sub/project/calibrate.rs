@@ -0,0 +1,349 @@
+use std::collections::BTreeMap;
+
+use bencher_adapter::AdapterResults;
+use bencher_boundary::{MetricsNoise, ThresholdCalibration};
+use bencher_client::types::{Adapter, JsonAverage};
+use bencher_json::{Boundary, ModelTest, SampleSize};
+use serde::Serialize;
+
+use crate::{
+    bencher::sub::SubCmd,
+    cli_eprintln_quietable, cli_println, cli_println_quietable,
+    parser::project::calibrate::{CliCalibrate, CliCalibrateFormat},
+    CliError, ExitClass,
+};
+
+use super::run::{
+    adapter::{adapter_parses, parse_results},
+    runner::{capture::Captured, timeout::Timeout, Runner},
+    RunError, ADAPTER_HINT,
+};
+
+#[derive(Debug)]
+pub struct Calibrate {
+    adapters: Vec<Adapter>,
+    average: Option<JsonAverage>,
+    iter: u32,
+    warmup: u32,
+    false_positive_rate: f64,
+    allow_failure: bool,
+    format: CliCalibrateFormat,
+    log: bool,
+    timeout: Option<Timeout>,
+    runner: Runner,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CalibrateError {
+    #[error("{0}")]
+    Run(#[from] RunError),
+    #[error("None of the adapters ({0}) were able to parse the benchmark harness results")]
+    NoAdapter(String),
+    #[error("Only {successes} of the calibration iterations succeeded, but at least 2 are needed to estimate the noise")]
+    TooFewIterations { successes: usize },
+    #[error("No benchmark results with at least 2 values were found to calibrate")]
+    NoResults,
+    #[error("Failed to serialize calibration: {0}")]
+    Serialize(serde_json::Error),
+}
+
+impl CalibrateError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::Run(err) => err.exit_class(),
+            Self::NoAdapter(_) => ExitClass::Parse,
+            Self::TooFewIterations { .. } | Self::NoResults | Self::Serialize(_) => {
+                ExitClass::Failure
+            },
+        }
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Run(err) => err.hint(),
+            Self::NoAdapter(_) => Some(ADAPTER_HINT),
+            Self::TooFewIterations { .. } | Self::NoResults | Self::Serialize(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonCalibration {
+    iterations: usize,
+    false_positive_rate: f64,
+    measures: Vec<JsonMeasureCalibration>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonMeasureCalibration {
+    measure: String,
+    test: ModelTest,
+    min_sample_size: SampleSize,
+    max_sample_size: SampleSize,
+    boundary: Boundary,
+    benchmark_false_positive_rate: f64,
+    benchmarks: Vec<JsonBenchmarkNoise>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonBenchmarkNoise {
+    benchmark: String,
+    mean: f64,
+    std_dev: Option<f64>,
+    coefficient_of_variation: Option<f64>,
+    skewness: Option<f64>,
+    detectable_change: Option<f64>,
+}
+
+impl TryFrom<CliCalibrate> for Calibrate {
+    type Error = CliError;
+
+    fn try_from(calibrate: CliCalibrate) -> Result<Self, Self::Error> {
+        let CliCalibrate {
+            adapter,
+            average,
+            iter,
+            warmup,
+            false_positive_rate,
+            allow_failure,
+            format,
+            quiet,
+            cmd,
+        } = calibrate;
+        Ok(Self {
+            adapters: adapter.into_iter().map(Into::into).collect(),
+            average: average.map(Into::into),
+            iter,
+            warmup,
+            false_positive_rate,
+            allow_failure,
+            format,
+            log: !quiet,
+            timeout: Timeout::new(cmd.timeout, cmd.kill_after),
+            runner: cmd.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Calibrate {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl Calibrate {
+    async fn exec_inner(&self) -> Result<(), CalibrateError> {
+        if self.warmup > 0 {
+            cli_println_quietable!(self.log, "Running {} warm-up iteration(s)", self.warmup);
+            for _ in 0..self.warmup {
+                // Warm-up results are discarded
+                self.run_iteration().await?;
+            }
+        }
+
+        cli_println_quietable!(
+            self.log,
+            "Running {} calibration iteration(s) without submitting any results",
+            self.iter
+        );
+        let mut iterations = Vec::new();
+        for _ in 0..self.iter {
+            if let Some(results) = self.run_iteration().await? {
+                iterations.push(results);
+            }
+        }
+        if iterations.len() < 2 {
+            return Err(CalibrateError::TooFewIterations {
+                successes: iterations.len(),
+            });
+        }
+
+        let adapter_results = self.parse_iterations(&iterations)?;
+        let json_calibration = self.calibrate(&adapter_results)?;
+        match self.format {
+            CliCalibrateFormat::Human => self.display(&json_calibration),
+            CliCalibrateFormat::Json => cli_println!(
+                "{}",
+                serde_json::to_string_pretty(&json_calibration)
+                    .map_err(CalibrateError::Serialize)?
+            ),
+        }
+        Ok(())
+    }
+
+    /// Run the benchmark command once, returning `None` if it failed and failures are allowed.
+    async fn run_iteration(&self) -> Result<Option<Vec<Captured>>, CalibrateError> {
+        let output = self.runner.run(self.log, self.timeout).await?;
+        if output.is_success() {
+            Ok(Some(output.results()))
+        } else if self.allow_failure {
+            cli_eprintln_quietable!(self.log, "Skipping failure:\n{}", output);
+            Ok(None)
+        } else {
+            Err(RunError::failed(&self.runner, output).into())
+        }
+    }
+
+    /// Parse the results of each iteration with the first adapter that is able to parse all of them.
+    fn parse_iterations(
+        &self,
+        iterations: &[Vec<Captured>],
+    ) -> Result<Vec<AdapterResults>, CalibrateError> {
+        let results = iterations.iter().flatten().cloned().collect::<Vec<_>>();
+        let adapter = match self.adapters.as_slice() {
+            [] => Adapter::Magic,
+            [adapter] => *adapter,
+            adapters => {
+                let mut parsed = None;
+                for adapter in adapters {
+                    if adapter_parses(*adapter, &results)? {
+                        parsed = Some(*adapter);
+                        break;
+                    }
+                }
+                parsed.ok_or_else(|| {
+                    CalibrateError::NoAdapter(
+                        adapters
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    )
+                })?
+            },
+        };
+        cli_println_quietable!(
+            self.log,
+            "\nParsing the benchmark harness results with adapter `{adapter}`",
+            adapter = adapter.to_string()
+        );
+        iterations
+            .iter()
+            .map(|results| parse_results(adapter, self.average, results).map_err(Into::into))
+            .collect()
+    }
+
+    fn calibrate(
+        &self,
+        adapter_results: &[AdapterResults],
+    ) -> Result<JsonCalibration, CalibrateError> {
+        // Measure -> Benchmark -> the value from each iteration
+        let mut values = BTreeMap::<String, BTreeMap<String, Vec<f64>>>::new();
+        for results in adapter_results {
+            for (benchmark_name, metrics) in &results.inner {
+                for (measure, metric) in &metrics.inner {
+                    values
+                        .entry(measure.to_string())
+                        .or_default()
+                        .entry(benchmark_name.to_string())
+                        .or_default()
+                        .push(metric.value.into());
+                }
+            }
+        }
+
+        let mut measures = Vec::with_capacity(values.len());
+        for (measure, benchmarks) in values {
+            let noise = benchmarks
+                .into_iter()
+                .filter_map(|(benchmark, data)| {
+                    MetricsNoise::new(&data).map(|noise| (benchmark, noise))
+                })
+                .collect::<Vec<_>>();
+            let Some(calibration) = ThresholdCalibration::new(
+                &noise.iter().map(|(_, noise)| *noise).collect::<Vec<_>>(),
+                self.false_positive_rate,
+            ) else {
+                continue;
+            };
+            let benchmarks = noise
+                .iter()
+                .map(|(benchmark, noise)| JsonBenchmarkNoise {
+                    benchmark: benchmark.clone(),
+                    mean: noise.mean,
+                    std_dev: noise.std_dev,
+                    coefficient_of_variation: noise.coefficient_of_variation(),
+                    skewness: noise.skewness,
+                    detectable_change: calibration.detectable_change(noise),
+                })
+                .collect();
+            measures.push(JsonMeasureCalibration {
+                measure,
+                test: calibration.test,
+                min_sample_size: calibration.min_sample_size,
+                max_sample_size: calibration.max_sample_size,
+                boundary: calibration.boundary,
+                benchmark_false_positive_rate: calibration.benchmark_false_positive_rate,
+                benchmarks,
+            });
+        }
+        if measures.is_empty() {
+            return Err(CalibrateError::NoResults);
+        }
+
+        Ok(JsonCalibration {
+            iterations: adapter_results.len(),
+            false_positive_rate: self.false_positive_rate,
+            measures,
+        })
+    }
+
+    fn display(&self, json_calibration: &JsonCalibration) {
+        cli_println_quietable!(
+            self.log,
+            "\nCalibrated {iterations} iteration(s) for a target false positive rate of {rate}% per Measure in a Report",
+            iterations = json_calibration.iterations,
+            rate = json_calibration.false_positive_rate * 100.0
+        );
+        for measure in &json_calibration.measures {
+            cli_println!("\nMeasure: {}", measure.measure);
+            for benchmark in &measure.benchmarks {
+                cli_println!(
+                    "  {benchmark}: mean {mean:.3}, coefficient of variation {cv}, detectable change {change}",
+                    benchmark = benchmark.benchmark,
+                    mean = benchmark.mean,
+                    cv = percent(benchmark.coefficient_of_variation),
+                    change = percent(benchmark.detectable_change),
+                );
+            }
+            let test = model_test_flag(measure.test);
+            cli_println!(
+                "  Recommended threshold: {test} test, {min}-{max} sample size, {boundary} boundary",
+                min = measure.min_sample_size,
+                max = measure.max_sample_size,
+                boundary = measure.boundary,
+            );
+            cli_println!(
+                "  bencher run --threshold-measure {measure} --threshold-test {test} --threshold-min-sample-size {min} --threshold-max-sample-size {max} --threshold-upper-boundary {boundary}",
+                measure = measure.measure,
+                min = measure.min_sample_size,
+                max = measure.max_sample_size,
+                boundary = measure.boundary,
+            );
+        }
+        cli_println_quietable!(
+            self.log,
+            "\nFor Measures where smaller values are worse (ie throughput), use `--threshold-lower-boundary` instead."
+        );
+    }
+}
+
+fn percent(value: Option<f64>) -> String {
+    value.map_or_else(
+        || "n/a".to_owned(),
+        |value| format!("{:.2}%", value * 100.0),
+    )
+}
+
+fn model_test_flag(test: ModelTest) -> &'static str {
+    match test {
+        ModelTest::Static => "static",
+        ModelTest::Percentage => "percentage",
+        ModelTest::ZScore => "z_score",
+        ModelTest::TTest => "t_test",
+        ModelTest::LogNormal => "log_normal",
+        ModelTest::Iqr => "iqr",
+        ModelTest::DeltaIqr => "delta_iqr",
+        ModelTest::Ewma => "ewma",
+    }
+}
@@ -0,0 +1,91 @@
+use bencher_client::types::JsonNewExpectation;
+use bencher_json::{DateTime, NameId, NonEmpty, ResourceId};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::expectation::CliExpectationCreate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Create {
+    pub project: ResourceId,
+    pub branch: NameId,
+    pub pattern: NonEmpty,
+    pub reason: NonEmpty,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub start_version: Option<u32>,
+    pub end_version: Option<u32>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliExpectationCreate> for Create {
+    type Error = CliError;
+
+    fn try_from(create: CliExpectationCreate) -> Result<Self, Self::Error> {
+        let CliExpectationCreate {
+            project,
+            branch,
+            pattern,
+            reason,
+            start_time,
+            end_time,
+            start_version,
+            end_version,
+            backend,
+        } = create;
+        Ok(Self {
+            project,
+            branch,
+            pattern,
+            reason,
+            start_time,
+            end_time,
+            start_version,
+            end_version,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Create> for JsonNewExpectation {
+    fn from(create: Create) -> Self {
+        let Create {
+            branch,
+            pattern,
+            reason,
+            start_time,
+            end_time,
+            start_version,
+            end_version,
+            ..
+        } = create;
+        Self {
+            branch: branch.into(),
+            pattern: pattern.into(),
+            reason: reason.into(),
+            start_time: start_time.map(Into::into),
+            end_time: end_time.map(Into::into),
+            start_version: start_version.map(Into::into),
+            end_version: end_version.map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Create {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_expectation_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use bencher_json::{ExpectationUuid, ResourceId};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::expectation::CliExpectationDelete,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Delete {
+    pub project: ResourceId,
+    pub expectation: ExpectationUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliExpectationDelete> for Delete {
+    type Error = CliError;
+
+    fn try_from(delete: CliExpectationDelete) -> Result<Self, Self::Error> {
+        let CliExpectationDelete {
+            project,
+            expectation,
+            backend,
+        } = delete;
+        Ok(Self {
+            project,
+            expectation,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Delete {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_expectation_delete()
+                    .project(self.project.clone())
+                    .expectation(self.expectation)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
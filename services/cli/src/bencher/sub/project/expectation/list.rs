@@ -0,0 +1,99 @@
+use bencher_client::types::{JsonDirection, ProjExpectationsSort};
+use bencher_json::{NameId, ResourceId};
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::{
+        project::expectation::{CliExpectationList, CliExpectationsSort},
+        CliPagination,
+    },
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct List {
+    pub project: ResourceId,
+    pub branch: Option<NameId>,
+    pub search: Option<String>,
+    pub pagination: Pagination,
+    pub backend: PubBackend,
+}
+
+#[derive(Debug)]
+pub struct Pagination {
+    pub sort: Option<ProjExpectationsSort>,
+    pub direction: Option<JsonDirection>,
+    pub per_page: Option<u8>,
+    pub page: Option<u32>,
+}
+
+impl TryFrom<CliExpectationList> for List {
+    type Error = CliError;
+
+    fn try_from(list: CliExpectationList) -> Result<Self, Self::Error> {
+        let CliExpectationList {
+            project,
+            branch,
+            search,
+            pagination,
+            backend,
+        } = list;
+        Ok(Self {
+            project,
+            branch,
+            search,
+            pagination: pagination.into(),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<CliPagination<CliExpectationsSort>> for Pagination {
+    fn from(pagination: CliPagination<CliExpectationsSort>) -> Self {
+        let CliPagination {
+            sort,
+            direction,
+            per_page,
+            page,
+        } = pagination;
+        Self {
+            sort: sort.map(|sort| match sort {
+                CliExpectationsSort::Created => ProjExpectationsSort::Created,
+            }),
+            direction: direction.map(Into::into),
+            page,
+            per_page,
+        }
+    }
+}
+
+impl SubCmd for List {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client.proj_expectations_get().project(self.project.clone());
+                if let Some(branch) = self.branch.clone() {
+                    client = client.branch(branch);
+                }
+                if let Some(search) = self.search.clone() {
+                    client = client.search(search);
+                }
+                if let Some(sort) = self.pagination.sort {
+                    client = client.sort(sort);
+                }
+                if let Some(direction) = self.pagination.direction {
+                    client = client.direction(direction);
+                }
+                if let Some(per_page) = self.pagination.per_page {
+                    client = client.per_page(per_page);
+                }
+                if let Some(page) = self.pagination.page {
+                    client = client.page(page);
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
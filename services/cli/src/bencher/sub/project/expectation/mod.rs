@@ -0,0 +1,38 @@
+use crate::{bencher::sub::SubCmd, parser::project::expectation::CliExpectation, CliError};
+
+mod create;
+mod delete;
+mod list;
+mod view;
+
+#[derive(Debug)]
+pub enum Expectation {
+    List(list::List),
+    Create(create::Create),
+    View(view::View),
+    Delete(delete::Delete),
+}
+
+impl TryFrom<CliExpectation> for Expectation {
+    type Error = CliError;
+
+    fn try_from(expectation: CliExpectation) -> Result<Self, Self::Error> {
+        Ok(match expectation {
+            CliExpectation::List(list) => Self::List(list.try_into()?),
+            CliExpectation::Create(create) => Self::Create(create.try_into()?),
+            CliExpectation::View(view) => Self::View(view.try_into()?),
+            CliExpectation::Delete(delete) => Self::Delete(delete.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Expectation {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::List(list) => list.exec().await,
+            Self::Create(create) => create.exec().await,
+            Self::View(view) => view.exec().await,
+            Self::Delete(delete) => delete.exec().await,
+        }
+    }
+}
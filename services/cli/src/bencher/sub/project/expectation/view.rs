@@ -0,0 +1,48 @@
+use bencher_json::{ExpectationUuid, ResourceId};
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::project::expectation::CliExpectationView,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub project: ResourceId,
+    pub expectation: ExpectationUuid,
+    pub backend: PubBackend,
+}
+
+impl TryFrom<CliExpectationView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliExpectationView) -> Result<Self, Self::Error> {
+        let CliExpectationView {
+            project,
+            expectation,
+            backend,
+        } = view;
+        Ok(Self {
+            project,
+            expectation,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_expectation_get()
+                    .project(self.project.clone())
+                    .expectation(self.expectation)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
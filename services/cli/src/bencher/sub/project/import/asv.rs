@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+
+use bencher_adapter::{results::adapter_metrics::AdapterMetrics, AdapterResults};
+use bencher_client::types::Adapter;
+use bencher_json::{
+    project::measure::built_in::{self, BuiltInMeasure},
+    DateTime, JsonNewMetric, NameId,
+};
+use camino::Utf8Path;
+use chrono::{TimeZone, Utc};
+use literally::hmap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{json_files, read_json, ImportError, ImportFormat, ImportReport};
+
+// These files in an asv results directory do not contain benchmark results
+const MACHINE_FILE: &str = "machine.json";
+const BENCHMARKS_FILE: &str = "benchmarks.json";
+
+const RESULT_COLUMN: &str = "result";
+const PARAMS_COLUMN: &str = "params";
+const CI_LOWER_COLUMN: &str = "stats_ci_99_a";
+const CI_UPPER_COLUMN: &str = "stats_ci_99_b";
+
+// asv timing benchmarks are reported in seconds
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+const MEMORY_MEASURE: &str = "memory";
+const TRACK_MEASURE: &str = "track";
+
+// https://asv.readthedocs.io/en/stable/dev.html#benchmark-suite-layout-and-file-formats
+#[derive(Debug, Deserialize)]
+struct AsvResults {
+    commit_hash: String,
+    // The commit date in milliseconds since the epoch
+    date: Option<i64>,
+    #[serde(default)]
+    params: HashMap<String, Value>,
+    // Only present in version 2 and later results files
+    result_columns: Option<Vec<String>>,
+    results: HashMap<String, Value>,
+}
+
+pub fn parse(path: &Utf8Path) -> Result<Vec<ImportReport>, ImportError> {
+    let mut import_reports = Vec::new();
+    for file in json_files(path)? {
+        if matches!(file.file_name(), Some(MACHINE_FILE | BENCHMARKS_FILE)) {
+            continue;
+        }
+        let asv_results: AsvResults = read_json(ImportFormat::Asv, &file)?;
+        if let Some(import_report) = into_import_report(&file, asv_results)? {
+            import_reports.push(import_report);
+        }
+    }
+    Ok(import_reports)
+}
+
+fn into_import_report(
+    path: &Utf8Path,
+    asv_results: AsvResults,
+) -> Result<Option<ImportReport>, ImportError> {
+    let AsvResults {
+        commit_hash,
+        date,
+        params,
+        result_columns,
+        results,
+    } = asv_results;
+
+    let date_time = date
+        .and_then(|date| Utc.timestamp_millis_opt(date).single())
+        .map(DateTime::from)
+        .ok_or_else(|| invalid_file(path, format!("Missing or invalid date: {date:?}")))?;
+    // Default to the results directory name for the machine
+    let machine = params
+        .get("machine")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+        .or_else(|| {
+            path.parent()
+                .and_then(Utf8Path::file_name)
+                .map(ToOwned::to_owned)
+        });
+
+    let mut results_map = HashMap::with_capacity(results.len());
+    for (name, value) in results {
+        let Some((measure, scale)) = benchmark_measure(&name) else {
+            continue;
+        };
+        let benchmark_results = match &result_columns {
+            Some(columns) => BenchmarkResults::from_columns(columns, &value),
+            None => BenchmarkResults::from_value(&value),
+        };
+        for (benchmark_name, json_metric) in benchmark_results.into_metrics(&name, scale) {
+            let benchmark_name =
+                benchmark_name
+                    .parse()
+                    .map_err(|err| ImportError::BenchmarkName {
+                        name: benchmark_name.clone(),
+                        err,
+                    })?;
+            results_map.insert(
+                benchmark_name,
+                AdapterMetrics {
+                    inner: hmap! {
+                        measure.clone() => json_metric
+                    },
+                },
+            );
+        }
+    }
+    if results_map.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(ImportReport {
+        branch: None,
+        testbed: machine,
+        hash: commit_hash.parse().ok(),
+        start_time: date_time,
+        end_time: date_time,
+        results: serde_json::to_string(&AdapterResults::from(results_map))
+            .map_err(ImportError::SerializeResults)?,
+        adapter: Adapter::Json,
+    }))
+}
+
+// The benchmark type is determined by the prefix of the benchmark function name
+fn benchmark_measure(name: &str) -> Option<(NameId, f64)> {
+    let function = name.rsplit('.').next().unwrap_or(name);
+    if function.starts_with("time_") || function.starts_with("timeraw_") {
+        Some((built_in::generic::Latency::name_id(), NANOS_PER_SECOND))
+    } else if function.starts_with("mem_") || function.starts_with("peakmem_") {
+        MEMORY_MEASURE.parse().ok().map(|measure| (measure, 1.0))
+    } else if function.starts_with("track_") {
+        TRACK_MEASURE.parse().ok().map(|measure| (measure, 1.0))
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+struct BenchmarkResults {
+    results: Vec<Option<f64>>,
+    params: Vec<Vec<String>>,
+    lower: Vec<Option<f64>>,
+    upper: Vec<Option<f64>>,
+}
+
+impl BenchmarkResults {
+    // Version 2 results are an array of values, one for each result column
+    fn from_columns(columns: &[String], value: &Value) -> Self {
+        let column = |name: &str| {
+            columns
+                .iter()
+                .position(|column| column == name)
+                .and_then(|index| value.get(index))
+        };
+        Self {
+            results: column(RESULT_COLUMN).map(values).unwrap_or_default(),
+            params: column(PARAMS_COLUMN).map(params).unwrap_or_default(),
+            lower: column(CI_LOWER_COLUMN).map(values).unwrap_or_default(),
+            upper: column(CI_UPPER_COLUMN).map(values).unwrap_or_default(),
+        }
+    }
+
+    // Version 1 results are either a single value or an object with results and params
+    fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Object(object) => Self {
+                results: object.get(RESULT_COLUMN).map(values).unwrap_or_default(),
+                params: object.get(PARAMS_COLUMN).map(params).unwrap_or_default(),
+                ..Default::default()
+            },
+            Value::Null
+            | Value::Bool(_)
+            | Value::Number(_)
+            | Value::String(_)
+            | Value::Array(_) => Self {
+                results: values(value),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn into_metrics(self, name: &str, scale: f64) -> Vec<(String, JsonNewMetric)> {
+        let Self {
+            results,
+            params,
+            lower,
+            upper,
+        } = self;
+        let names = param_names(name, &params);
+        results
+            .into_iter()
+            .enumerate()
+            .zip(names)
+            .filter_map(|((index, result), name)| {
+                let scaled = |value: Option<&Option<f64>>| {
+                    value.copied().flatten().map(|value| (value * scale).into())
+                };
+                result.map(|result| {
+                    (
+                        name,
+                        JsonNewMetric {
+                            value: (result * scale).into(),
+                            lower_value: scaled(lower.get(index)),
+                            upper_value: scaled(upper.get(index)),
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+// A single result value or an array of result values, one for each parameter combination
+fn values(value: &Value) -> Vec<Option<f64>> {
+    match value {
+        Value::Array(array) => array.iter().map(finite_value).collect(),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) | Value::Object(_) => {
+            vec![finite_value(value)]
+        },
+    }
+}
+
+// Failed or skipped benchmarks have a `null` or `NaN` result
+fn finite_value(value: &Value) -> Option<f64> {
+    value.as_f64().filter(|value| value.is_finite())
+}
+
+fn params(value: &Value) -> Vec<Vec<String>> {
+    value
+        .as_array()
+        .map(|params| {
+            params
+                .iter()
+                .map(|param| {
+                    param
+                        .as_array()
+                        .map(|values| {
+                            values
+                                .iter()
+                                .map(|value| {
+                                    value
+                                        .as_str()
+                                        .map_or_else(|| value.to_string(), ToOwned::to_owned)
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Parameterized benchmark results are ordered by the Cartesian product of the parameters
+fn param_names(name: &str, params: &[Vec<String>]) -> Vec<String> {
+    if params.is_empty() {
+        return vec![name.to_owned()];
+    }
+    let mut combinations: Vec<Vec<&str>> = vec![Vec::new()];
+    for param in params {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                param.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.push(value.as_str());
+                    combination
+                })
+            })
+            .collect();
+    }
+    combinations
+        .into_iter()
+        .map(|combination| format!("{name}({})", combination.join(", ")))
+        .collect()
+}
+
+fn invalid_file(path: &Utf8Path, reason: String) -> ImportError {
+    super::invalid_file(ImportFormat::Asv, path, reason)
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_client::types::Adapter;
+    use camino::Utf8Path;
+    use pretty_assertions::assert_eq;
+
+    use super::{param_names, parse};
+    use crate::bencher::sub::project::import::test_util::{file_path, metric, new_metric, results};
+
+    #[test]
+    fn test_param_names() {
+        assert_eq!(param_names("time_a", &[]), vec!["time_a".to_owned()]);
+        let params = vec![
+            vec!["1".to_owned(), "2".to_owned()],
+            vec!["'a'".to_owned(), "'b'".to_owned(), "'c'".to_owned()],
+        ];
+        assert_eq!(
+            param_names("time_a", &params),
+            vec![
+                "time_a(1, 'a')".to_owned(),
+                "time_a(1, 'b')".to_owned(),
+                "time_a(1, 'c')".to_owned(),
+                "time_a(2, 'a')".to_owned(),
+                "time_a(2, 'b')".to_owned(),
+                "time_a(2, 'c')".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_asv_v2() {
+        let path = file_path("asv/v2");
+        let import_reports = parse(Utf8Path::new(&path)).unwrap();
+        // The `benchmarks.json` and `machine.json` files are skipped
+        assert_eq!(import_reports.len(), 1);
+        let import_report = import_reports.first().unwrap();
+
+        assert_eq!(import_report.branch, None);
+        assert_eq!(import_report.testbed.as_deref(), Some("machine-a"));
+        assert_eq!(
+            import_report.hash.as_ref().map(AsRef::as_ref),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert_eq!(import_report.start_time.timestamp(), 1_700_000_000);
+        assert_eq!(import_report.end_time.timestamp(), 1_700_000_000);
+        assert_eq!(import_report.adapter, Adapter::Json);
+
+        let results = results(import_report);
+        // The failed parameter combination and the `setup_cache` function are skipped
+        assert_eq!(results.inner.len(), 5);
+        assert_eq!(
+            metric(
+                &results,
+                "benchmarks.TimeSuite.time_sort(10, 'list')",
+                "latency"
+            ),
+            new_metric(500_000_000.0, Some(375_000_000.0), Some(625_000_000.0))
+        );
+        assert_eq!(
+            metric(
+                &results,
+                "benchmarks.TimeSuite.time_sort(10, 'tuple')",
+                "latency"
+            ),
+            new_metric(250_000_000.0, Some(125_000_000.0), Some(375_000_000.0))
+        );
+        assert_eq!(
+            metric(
+                &results,
+                "benchmarks.TimeSuite.time_sort(100, 'list')",
+                "latency"
+            ),
+            new_metric(125_000_000.0, Some(62_500_000.0), Some(250_000_000.0))
+        );
+        assert!(results
+            .get("benchmarks.TimeSuite.time_sort(100, 'tuple')")
+            .is_none());
+        assert_eq!(
+            metric(&results, "benchmarks.MemSuite.peakmem_list", "memory"),
+            new_metric(2048.0, None, None)
+        );
+        assert_eq!(
+            metric(&results, "benchmarks.TrackSuite.track_value", "track"),
+            new_metric(42.0, None, None)
+        );
+    }
+
+    #[test]
+    fn test_asv_v1() {
+        let path = file_path("asv/v1");
+        let import_reports = parse(Utf8Path::new(&path)).unwrap();
+        assert_eq!(import_reports.len(), 1);
+        let import_report = import_reports.first().unwrap();
+
+        // Without a `machine` param, the results directory name is used
+        assert_eq!(import_report.testbed.as_deref(), Some("machine-b"));
+        assert_eq!(
+            import_report.hash.as_ref().map(AsRef::as_ref),
+            Some("fedcba9876543210fedcba9876543210fedcba98")
+        );
+        assert_eq!(import_report.start_time.timestamp(), 1_600_000_000);
+
+        let results = results(import_report);
+        // The failed benchmark and parameter combination are skipped
+        assert_eq!(results.inner.len(), 4);
+        assert_eq!(
+            metric(&results, "benchmarks.time_single", "latency"),
+            new_metric(2_000_000_000.0, None, None)
+        );
+        assert_eq!(
+            metric(&results, "benchmarks.time_params(1, 'a')", "latency"),
+            new_metric(1_000_000_000.0, None, None)
+        );
+        assert_eq!(
+            metric(&results, "benchmarks.time_params(1, 'b')", "latency"),
+            new_metric(500_000_000.0, None, None)
+        );
+        assert_eq!(
+            metric(&results, "benchmarks.time_params(2, 'a')", "latency"),
+            new_metric(250_000_000.0, None, None)
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use bencher_adapter::AdapterResults;
+use bencher_client::types::Adapter;
+use bencher_json::{DateTime, JsonNewMetric};
+use camino::Utf8Path;
+use serde::Deserialize;
+
+use super::{parse_date_time, read_json, ImportError, ImportFormat, ImportReport};
+
+// Codespeed results are assumed to be in its default units of seconds
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+
+// https://github.com/tobami/codespeed#saving-data
+#[derive(Debug, Deserialize)]
+struct CodespeedResult {
+    commitid: String,
+    branch: Option<String>,
+    environment: Option<String>,
+    benchmark: String,
+    result_value: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    revision_date: Option<String>,
+    result_date: Option<String>,
+}
+
+// All of the results for a single commit on a branch in an environment are a single report
+type ResultsKey = (String, Option<String>, Option<String>);
+
+pub fn parse(path: &Utf8Path) -> Result<Vec<ImportReport>, ImportError> {
+    let results: Vec<CodespeedResult> = read_json(ImportFormat::Codespeed, path)?;
+
+    let mut grouped_results: BTreeMap<ResultsKey, Vec<CodespeedResult>> = BTreeMap::new();
+    for result in results {
+        let key = (
+            result.commitid.clone(),
+            result.branch.clone(),
+            result.environment.clone(),
+        );
+        grouped_results.entry(key).or_default().push(result);
+    }
+
+    grouped_results
+        .into_iter()
+        .map(|((commitid, branch, environment), results)| {
+            into_import_report(path, &commitid, branch, environment, results)
+        })
+        .collect()
+}
+
+fn into_import_report(
+    path: &Utf8Path,
+    commitid: &str,
+    branch: Option<String>,
+    environment: Option<String>,
+    results: Vec<CodespeedResult>,
+) -> Result<ImportReport, ImportError> {
+    let mut start_time: Option<DateTime> = None;
+    let mut end_time: Option<DateTime> = None;
+    let mut benchmark_metrics = Vec::with_capacity(results.len());
+    for result in results {
+        let date_time = result_date_time(path, &result)?;
+        if start_time.map_or(true, |start| date_time.timestamp() < start.timestamp()) {
+            start_time = Some(date_time);
+        }
+        if end_time.map_or(true, |end| date_time.timestamp() > end.timestamp()) {
+            end_time = Some(date_time);
+        }
+
+        let benchmark_name =
+            result
+                .benchmark
+                .parse()
+                .map_err(|err| ImportError::BenchmarkName {
+                    name: result.benchmark.clone(),
+                    err,
+                })?;
+        let json_metric = JsonNewMetric {
+            value: (result.result_value * NANOS_PER_SECOND).into(),
+            lower_value: result.min.map(|min| (min * NANOS_PER_SECOND).into()),
+            upper_value: result.max.map(|max| (max * NANOS_PER_SECOND).into()),
+        };
+        benchmark_metrics.push((benchmark_name, json_metric));
+    }
+
+    let (Some(start_time), Some(end_time)) = (start_time, end_time) else {
+        return Err(invalid_file(
+            path,
+            format!("No results for commit {commitid}"),
+        ));
+    };
+    let adapter_results = AdapterResults::new_latency(benchmark_metrics)
+        .ok_or_else(|| invalid_file(path, format!("No results for commit {commitid}")))?;
+
+    Ok(ImportReport {
+        branch,
+        testbed: environment,
+        hash: commitid.parse().ok(),
+        start_time,
+        end_time,
+        results: serde_json::to_string(&adapter_results).map_err(ImportError::SerializeResults)?,
+        adapter: Adapter::Json,
+    })
+}
+
+fn result_date_time(path: &Utf8Path, result: &CodespeedResult) -> Result<DateTime, ImportError> {
+    let date_time = result
+        .result_date
+        .as_ref()
+        .or(result.revision_date.as_ref())
+        .ok_or_else(|| {
+            invalid_file(
+                path,
+                format!(
+                    "Missing `result_date` and `revision_date` for benchmark {}",
+                    result.benchmark
+                ),
+            )
+        })?;
+    parse_date_time(date_time)
+        .ok_or_else(|| invalid_file(path, format!("Invalid date time: {date_time}")))
+}
+
+fn invalid_file(path: &Utf8Path, reason: String) -> ImportError {
+    super::invalid_file(ImportFormat::Codespeed, path, reason)
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_client::types::Adapter;
+    use camino::Utf8Path;
+    use pretty_assertions::assert_eq;
+
+    use super::parse;
+    use crate::bencher::sub::project::import::{
+        test_util::{file_path, metric, new_metric, results},
+        ImportError,
+    };
+
+    #[test]
+    fn test_codespeed() {
+        let path = file_path("codespeed/results.json");
+        let import_reports = parse(Utf8Path::new(&path)).unwrap();
+        // The results are grouped into a report for each commit, branch, and environment
+        assert_eq!(import_reports.len(), 2);
+
+        let import_report = import_reports.first().unwrap();
+        assert_eq!(import_report.branch.as_deref(), Some("default"));
+        assert_eq!(import_report.testbed.as_deref(), Some("env-a"));
+        assert_eq!(
+            import_report.hash.as_ref().map(AsRef::as_ref),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        // The result date is used over the revision date
+        assert_eq!(import_report.start_time.timestamp(), 1_704_153_600);
+        assert_eq!(import_report.end_time.timestamp(), 1_704_157_200);
+        assert_eq!(import_report.adapter, Adapter::Json);
+        let adapter_results = results(import_report);
+        assert_eq!(adapter_results.inner.len(), 2);
+        assert_eq!(
+            metric(&adapter_results, "float", "latency"),
+            new_metric(500_000_000.0, Some(250_000_000.0), Some(750_000_000.0))
+        );
+        assert_eq!(
+            metric(&adapter_results, "int", "latency"),
+            new_metric(2_000_000_000.0, None, None)
+        );
+
+        let import_report = import_reports.last().unwrap();
+        assert_eq!(import_report.branch, None);
+        assert_eq!(import_report.testbed, None);
+        assert_eq!(
+            import_report.hash.as_ref().map(AsRef::as_ref),
+            Some("fedcba9876543210fedcba9876543210fedcba98")
+        );
+        assert_eq!(import_report.start_time.timestamp(), 1_704_240_000);
+        assert_eq!(import_report.end_time.timestamp(), 1_704_240_000);
+        let adapter_results = results(import_report);
+        assert_eq!(adapter_results.inner.len(), 1);
+        assert_eq!(
+            metric(&adapter_results, "float", "latency"),
+            new_metric(250_000_000.0, None, None)
+        );
+    }
+
+    #[test]
+    fn test_codespeed_missing_date() {
+        let path = file_path("codespeed/missing_date.json");
+        assert!(matches!(
+            parse(Utf8Path::new(&path)),
+            Err(ImportError::InvalidFile { .. })
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+use bencher_client::types::Adapter;
+use bencher_json::GitHash;
+use camino::Utf8Path;
+use serde::Deserialize;
+
+use super::{json_files, parse_date_time, read_file, ImportError, ImportFormat, ImportReport};
+
+// https://github.com/google/benchmark/blob/main/docs/user_guide.md#output-formats
+#[derive(Debug, Deserialize)]
+struct GoogleBenchmarkOutput {
+    context: GoogleBenchmarkContext,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleBenchmarkContext {
+    date: String,
+    host_name: Option<String>,
+}
+
+pub fn parse(path: &Utf8Path) -> Result<Vec<ImportReport>, ImportError> {
+    json_files(path)?
+        .iter()
+        .map(|file| into_import_report(file))
+        .collect()
+}
+
+fn into_import_report(path: &Utf8Path) -> Result<ImportReport, ImportError> {
+    // The raw output is sent as is and parsed by the Google Benchmark adapter
+    let results = read_file(path)?;
+    let GoogleBenchmarkOutput { context } =
+        serde_json::from_str(&results).map_err(|err| ImportError::ParseFile {
+            format: ImportFormat::GoogleBenchmark,
+            path: path.to_owned(),
+            err,
+        })?;
+    let date_time = parse_date_time(&context.date).ok_or_else(|| {
+        super::invalid_file(
+            ImportFormat::GoogleBenchmark,
+            path,
+            format!("Invalid date time: {}", context.date),
+        )
+    })?;
+
+    Ok(ImportReport {
+        branch: None,
+        testbed: context.host_name,
+        hash: file_hash(path),
+        start_time: date_time,
+        end_time: date_time,
+        results,
+        adapter: Adapter::CppGoogle,
+    })
+}
+
+// Google Benchmark output does not include the commit hash,
+// so look for one in the file name (ie `run-<hash>.json`).
+fn file_hash(path: &Utf8Path) -> Option<GitHash> {
+    path.file_stem()?
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .find_map(|part| part.parse().ok())
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_client::types::Adapter;
+    use camino::Utf8Path;
+    use pretty_assertions::assert_eq;
+
+    use super::parse;
+    use crate::bencher::sub::project::import::test_util::file_path;
+
+    #[test]
+    fn test_google() {
+        let path = file_path("google");
+        let import_reports = parse(Utf8Path::new(&path)).unwrap();
+        assert_eq!(import_reports.len(), 2);
+
+        // The files are sorted by name
+        let import_report = import_reports.first().unwrap();
+        assert_eq!(import_report.branch, None);
+        assert_eq!(import_report.testbed, None);
+        assert_eq!(import_report.hash, None);
+        assert_eq!(import_report.start_time.timestamp(), 1_704_254_706);
+        assert_eq!(import_report.adapter, Adapter::CppGoogle);
+
+        let import_report = import_reports.last().unwrap();
+        assert_eq!(import_report.testbed.as_deref(), Some("host-a"));
+        // The commit hash is taken from the file name
+        assert_eq!(
+            import_report.hash.as_ref().map(AsRef::as_ref),
+            Some("0123456789abcdef0123456789abcdef01234567")
+        );
+        assert_eq!(import_report.start_time.timestamp(), 1_704_164_645);
+        assert_eq!(import_report.end_time.timestamp(), 1_704_164_645);
+        assert_eq!(import_report.adapter, Adapter::CppGoogle);
+        // The raw output is sent as is for the Google Benchmark adapter to parse
+        assert_eq!(
+            import_report.results,
+            std::fs::read_to_string(format!(
+                "{path}/run-0123456789abcdef0123456789abcdef01234567.json"
+            ))
+            .unwrap()
+        );
+    }
+}
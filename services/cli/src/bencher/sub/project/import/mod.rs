@@ -0,0 +1,418 @@
+use std::fmt;
+
+use bencher_client::types::{Adapter, JsonNewReport, JsonReportSettings};
+use bencher_json::{
+    project::{branch::BRANCH_MAIN_STR, testbed::TESTBED_LOCALHOST_STR},
+    DateTime, GitHash, NameId, ResourceId,
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{NaiveDateTime, Utc};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    cli_println,
+    parser::project::import::{CliImport, CliImportFormat},
+    CliError, ExitClass,
+};
+
+mod asv;
+mod codespeed;
+mod google;
+
+// The maximum number of reports that can be imported per request
+const IMPORT_CHUNK_SIZE: usize = 255;
+
+#[derive(Debug, Clone)]
+pub struct Import {
+    pub project: ResourceId,
+    pub format: ImportFormat,
+    pub path: Utf8PathBuf,
+    pub branch: Option<NameId>,
+    pub testbed: Option<NameId>,
+    pub dry_run: bool,
+    pub backend: AuthBackend,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ImportFormat {
+    Codespeed,
+    Asv,
+    GoogleBenchmark,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ImportError {
+    #[error("Failed to read import path ({path}): {err}")]
+    ReadPath {
+        path: Utf8PathBuf,
+        err: std::io::Error,
+    },
+    #[error("Failed to parse {format} file ({path}): {err}")]
+    ParseFile {
+        format: ImportFormat,
+        path: Utf8PathBuf,
+        err: serde_json::Error,
+    },
+    #[error("Invalid {format} file ({path}): {reason}")]
+    InvalidFile {
+        format: ImportFormat,
+        path: Utf8PathBuf,
+        reason: String,
+    },
+    #[error("Failed to parse benchmark name ({name}): {err}")]
+    BenchmarkName {
+        name: String,
+        err: bencher_json::ValidError,
+    },
+    #[error("Failed to parse name ({name}): {err}")]
+    NameId {
+        name: String,
+        err: bencher_json::ValidError,
+    },
+    #[error("Failed to serialize benchmark results: {0}")]
+    SerializeResults(serde_json::Error),
+    #[error("Failed to serialize reports: {0}")]
+    SerializeReports(serde_json::Error),
+    #[error("No benchmark results found to import at: {0}")]
+    NoResults(Utf8PathBuf),
+    #[error("Failed to import reports: {0}")]
+    SendReports(crate::BackendError),
+}
+
+impl ImportError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::ReadPath { .. } | Self::SerializeResults(_) | Self::SerializeReports(_) => {
+                ExitClass::Failure
+            },
+            Self::ParseFile { .. }
+            | Self::InvalidFile { .. }
+            | Self::BenchmarkName { .. }
+            | Self::NameId { .. }
+            | Self::NoResults(_) => ExitClass::Parse,
+            Self::SendReports(err) => err.exit_class(),
+        }
+    }
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Codespeed => "Codespeed",
+                Self::Asv => "airspeed velocity",
+                Self::GoogleBenchmark => "Google Benchmark",
+            }
+        )
+    }
+}
+
+/// A single historical run converted from another benchmark tracker
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    pub branch: Option<String>,
+    pub testbed: Option<String>,
+    pub hash: Option<GitHash>,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub results: String,
+    pub adapter: Adapter,
+}
+
+impl TryFrom<CliImport> for Import {
+    type Error = CliError;
+
+    fn try_from(import: CliImport) -> Result<Self, Self::Error> {
+        let CliImport {
+            project,
+            format,
+            path,
+            branch,
+            testbed,
+            dry_run,
+            backend,
+        } = import;
+        Ok(Self {
+            project,
+            format: format.into(),
+            path,
+            branch,
+            testbed,
+            dry_run,
+            backend: AuthBackend::try_from(backend)?.log(false),
+        })
+    }
+}
+
+impl From<CliImportFormat> for ImportFormat {
+    fn from(format: CliImportFormat) -> Self {
+        match format {
+            CliImportFormat::Codespeed => Self::Codespeed,
+            CliImportFormat::Asv => Self::Asv,
+            CliImportFormat::GoogleBenchmark => Self::GoogleBenchmark,
+        }
+    }
+}
+
+impl SubCmd for Import {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl Import {
+    async fn exec_inner(&self) -> Result<(), ImportError> {
+        let mut import_reports = match self.format {
+            ImportFormat::Codespeed => codespeed::parse(&self.path)?,
+            ImportFormat::Asv => asv::parse(&self.path)?,
+            ImportFormat::GoogleBenchmark => google::parse(&self.path)?,
+        };
+        if import_reports.is_empty() {
+            return Err(ImportError::NoResults(self.path.clone()));
+        }
+        // Reports must be imported in chronological order
+        import_reports.sort_by_key(|import_report| import_report.start_time.timestamp());
+
+        let json_reports = import_reports
+            .into_iter()
+            .map(|import_report| self.new_report(import_report))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        cli_println!(
+            "Importing {count} {format} report(s) from {path}",
+            count = json_reports.len(),
+            format = self.format,
+            path = self.path
+        );
+
+        // If performing a dry run, don't actually send the reports
+        if self.dry_run {
+            cli_println!(
+                "{}",
+                serde_json::to_string_pretty(&json_reports)
+                    .map_err(ImportError::SerializeReports)?
+            );
+            return Ok(());
+        }
+
+        let client = self
+            .backend
+            .checked_client()
+            .await
+            .map_err(ImportError::SendReports)?;
+        let reports = client.project(self.project.clone()).reports();
+        let total = json_reports.len();
+        let mut imported = 0;
+        for chunk in json_reports.chunks(IMPORT_CHUNK_SIZE) {
+            let imported_reports = reports
+                .import(chunk.to_vec())
+                .await
+                .map_err(|err| ImportError::SendReports(client.error(err)))?;
+            imported += imported_reports.0.len();
+            cli_println!("Imported {imported}/{total} report(s)");
+        }
+
+        Ok(())
+    }
+
+    fn new_report(&self, import_report: ImportReport) -> Result<JsonNewReport, ImportError> {
+        let ImportReport {
+            branch,
+            testbed,
+            hash,
+            start_time,
+            end_time,
+            results,
+            adapter,
+        } = import_report;
+        let branch = name_id_or(self.branch.as_ref(), branch, BRANCH_MAIN_STR)?;
+        let testbed = name_id_or(self.testbed.as_ref(), testbed, TESTBED_LOCALHOST_STR)?;
+        Ok(JsonNewReport {
+            branch: branch.into(),
+            hash: hash.map(Into::into),
+            start_point: None,
+            reference_branch: None,
+            testbed: testbed.into(),
+            thresholds: None,
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            tag: None,
+            results: vec![results],
+            settings: Some(JsonReportSettings {
+                adapter: Some(adapter),
+                average: None,
+                fold: None,
+                dispersion: None,
+            }),
+            strict: None,
+            warmup: None,
+            notes: None,
+            links: None,
+            failures: None,
+            git: None,
+            fingerprint: None,
+        })
+    }
+}
+
+// The command line value takes precedence over the imported value
+fn name_id_or(
+    cli: Option<&NameId>,
+    imported: Option<String>,
+    default: &str,
+) -> Result<NameId, ImportError> {
+    if let Some(name_id) = cli {
+        return Ok(name_id.clone());
+    }
+    let name = imported.unwrap_or_else(|| default.to_owned());
+    name.parse()
+        .map_err(|err| ImportError::NameId { name, err })
+}
+
+fn invalid_file(format: ImportFormat, path: &Utf8Path, reason: String) -> ImportError {
+    ImportError::InvalidFile {
+        format,
+        path: path.to_owned(),
+        reason,
+    }
+}
+
+fn read_json<T>(format: ImportFormat, path: &Utf8Path) -> Result<T, ImportError>
+where
+    T: DeserializeOwned,
+{
+    let contents = read_file(path)?;
+    serde_json::from_str(&contents).map_err(|err| ImportError::ParseFile {
+        format,
+        path: path.to_owned(),
+        err,
+    })
+}
+
+fn read_file(path: &Utf8Path) -> Result<String, ImportError> {
+    std::fs::read_to_string(path).map_err(|err| ImportError::ReadPath {
+        path: path.to_owned(),
+        err,
+    })
+}
+
+// Find all of the JSON files at the path, recursively if the path is a directory
+fn json_files(path: &Utf8Path) -> Result<Vec<Utf8PathBuf>, ImportError> {
+    let read_path_err = |err| ImportError::ReadPath {
+        path: path.to_owned(),
+        err,
+    };
+    if !path.is_dir() {
+        return Ok(vec![path.to_owned()]);
+    }
+    let mut files = Vec::new();
+    for entry in path.read_dir_utf8().map_err(read_path_err)? {
+        let entry_path = entry.map_err(read_path_err)?.into_path();
+        if entry_path.is_dir() {
+            files.extend(json_files(&entry_path)?);
+        } else if entry_path.extension() == Some("json") {
+            files.push(entry_path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn parse_date_time(date_time: &str) -> Option<DateTime> {
+    const NAIVE_FORMATS: &[&str] = &[
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y/%m/%d-%H:%M:%S",
+    ];
+    if let Ok(date_time) = chrono::DateTime::parse_from_rfc3339(date_time) {
+        return Some(date_time.with_timezone(&Utc).into());
+    }
+    NAIVE_FORMATS.iter().find_map(|format| {
+        NaiveDateTime::parse_from_str(date_time, format)
+            .ok()
+            .map(|date_time| date_time.and_utc().into())
+    })
+}
+
+#[cfg(test)]
+mod test_util {
+    use bencher_adapter::AdapterResults;
+    use bencher_json::{JsonNewMetric, MeasureNameId};
+
+    use super::ImportReport;
+
+    pub fn file_path(suffix: &str) -> String {
+        format!("./tool_output/import/{suffix}")
+    }
+
+    pub fn results(import_report: &ImportReport) -> AdapterResults {
+        serde_json::from_str(&import_report.results).unwrap()
+    }
+
+    pub fn metric(results: &AdapterResults, benchmark: &str, measure: &str) -> JsonNewMetric {
+        let measure: MeasureNameId = measure.parse().unwrap();
+        results
+            .get(benchmark)
+            .and_then(|metrics| metrics.inner.get(&measure))
+            .copied()
+            .unwrap()
+    }
+
+    pub fn new_metric(
+        value: f64,
+        lower_value: Option<f64>,
+        upper_value: Option<f64>,
+    ) -> JsonNewMetric {
+        JsonNewMetric {
+            value: value.into(),
+            lower_value: lower_value.map(Into::into),
+            upper_value: upper_value.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::NameId;
+    use pretty_assertions::assert_eq;
+
+    use super::{name_id_or, parse_date_time};
+
+    #[test]
+    fn test_parse_date_time() {
+        for date_time in [
+            "2024-01-02T03:04:05Z",
+            "2024-01-02T03:04:05+00:00",
+            "2024-01-02 03:04:05",
+            "2024-01-02T03:04:05.000",
+            "2024/01/02-03:04:05",
+        ] {
+            assert_eq!(
+                parse_date_time(date_time).map(|date_time| date_time.timestamp()),
+                Some(1_704_164_645),
+                "{date_time}"
+            );
+        }
+        assert_eq!(parse_date_time("January 2nd, 2024"), None);
+    }
+
+    #[test]
+    fn test_name_id_or() {
+        let cli: NameId = "cli".parse().unwrap();
+        assert_eq!(
+            name_id_or(Some(&cli), Some("imported".to_owned()), "default").unwrap(),
+            cli
+        );
+        assert_eq!(
+            name_id_or(None, Some("imported".to_owned()), "default").unwrap(),
+            "imported".parse::<NameId>().unwrap()
+        );
+        assert_eq!(
+            name_id_or(None, None, "default").unwrap(),
+            "default".parse::<NameId>().unwrap()
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use bencher_client::types::JsonNewMeasureAlias;
+use bencher_json::{ResourceId, ResourceName};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::measure::CliMeasureAliasCreate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Create {
+    pub project: ResourceId,
+    pub measure: ResourceId,
+    pub alias: ResourceName,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliMeasureAliasCreate> for Create {
+    type Error = CliError;
+
+    fn try_from(create: CliMeasureAliasCreate) -> Result<Self, Self::Error> {
+        let CliMeasureAliasCreate {
+            project,
+            measure,
+            alias,
+            backend,
+        } = create;
+        Ok(Self {
+            project,
+            measure,
+            alias,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Create> for JsonNewMeasureAlias {
+    fn from(create: Create) -> Self {
+        let Create { alias, .. } = create;
+        Self {
+            alias: alias.into(),
+        }
+    }
+}
+
+impl SubCmd for Create {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_measure_alias_post()
+                    .project(self.project.clone())
+                    .measure(self.measure.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
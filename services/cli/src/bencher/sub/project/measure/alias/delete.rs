@@ -0,0 +1,52 @@
+use bencher_json::{MeasureAliasUuid, ResourceId};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::measure::CliMeasureAliasDelete,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Delete {
+    pub project: ResourceId,
+    pub measure: ResourceId,
+    pub alias: MeasureAliasUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliMeasureAliasDelete> for Delete {
+    type Error = CliError;
+
+    fn try_from(delete: CliMeasureAliasDelete) -> Result<Self, Self::Error> {
+        let CliMeasureAliasDelete {
+            project,
+            measure,
+            alias,
+            backend,
+        } = delete;
+        Ok(Self {
+            project,
+            measure,
+            alias,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Delete {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_measure_alias_delete()
+                    .project(self.project.clone())
+                    .measure(self.measure.clone())
+                    .alias(self.alias)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
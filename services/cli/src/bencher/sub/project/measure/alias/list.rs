@@ -0,0 +1,48 @@
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::project::measure::CliMeasureAliasList,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct List {
+    pub project: ResourceId,
+    pub measure: ResourceId,
+    pub backend: PubBackend,
+}
+
+impl TryFrom<CliMeasureAliasList> for List {
+    type Error = CliError;
+
+    fn try_from(list: CliMeasureAliasList) -> Result<Self, Self::Error> {
+        let CliMeasureAliasList {
+            project,
+            measure,
+            backend,
+        } = list;
+        Ok(Self {
+            project,
+            measure,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for List {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_measure_aliases_get()
+                    .project(self.project.clone())
+                    .measure(self.measure.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
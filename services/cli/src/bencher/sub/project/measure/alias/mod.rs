@@ -0,0 +1,34 @@
+use crate::{bencher::sub::SubCmd, parser::project::measure::CliMeasureAlias, CliError};
+
+mod create;
+mod delete;
+mod list;
+
+#[derive(Debug)]
+pub enum Alias {
+    List(list::List),
+    Create(create::Create),
+    Delete(delete::Delete),
+}
+
+impl TryFrom<CliMeasureAlias> for Alias {
+    type Error = CliError;
+
+    fn try_from(alias: CliMeasureAlias) -> Result<Self, Self::Error> {
+        Ok(match alias {
+            CliMeasureAlias::List(list) => Self::List(list.try_into()?),
+            CliMeasureAlias::Create(create) => Self::Create(create.try_into()?),
+            CliMeasureAlias::Delete(delete) => Self::Delete(delete.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Alias {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::List(list) => list.exec().await,
+            Self::Create(create) => create.exec().await,
+            Self::Delete(delete) => delete.exec().await,
+        }
+    }
+}
@@ -25,13 +25,14 @@ impl TryFrom<CliMeasureCreate> for Create {
             name,
             slug,
             units,
+            custom_units,
             backend,
         } = create;
         Ok(Self {
             project,
             name,
             slug,
-            units,
+            units: super::known_units(units, custom_units)?,
             backend: backend.try_into()?,
         })
     }
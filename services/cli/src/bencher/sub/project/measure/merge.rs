@@ -0,0 +1,62 @@
+use bencher_client::types::JsonMergeMeasure;
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::measure::CliMeasureMerge,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Merge {
+    pub project: ResourceId,
+    pub measure: ResourceId,
+    pub from: ResourceId,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliMeasureMerge> for Merge {
+    type Error = CliError;
+
+    fn try_from(merge: CliMeasureMerge) -> Result<Self, Self::Error> {
+        let CliMeasureMerge {
+            project,
+            measure,
+            from,
+            backend,
+        } = merge;
+        Ok(Self {
+            project,
+            measure,
+            from,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Merge> for JsonMergeMeasure {
+    fn from(merge: Merge) -> Self {
+        let Merge { from, .. } = merge;
+        Self {
+            measure: from.into(),
+        }
+    }
+}
+
+impl SubCmd for Merge {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_measure_merge_post()
+                    .project(self.project.clone())
+                    .measure(self.measure.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
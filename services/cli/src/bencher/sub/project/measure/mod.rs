@@ -1,8 +1,13 @@
+use bencher_json::{project::measure::units::MeasureUnit, ResourceName};
+
 use crate::{bencher::sub::SubCmd, parser::project::measure::CliMeasure, CliError};
 
+mod alias;
 mod create;
 mod delete;
 mod list;
+mod merge;
+mod units;
 mod update;
 mod view;
 
@@ -13,6 +18,9 @@ pub enum Measure {
     View(view::View),
     Update(update::Update),
     Delete(delete::Delete),
+    Merge(merge::Merge),
+    Alias(alias::Alias),
+    Units(units::Units),
 }
 
 impl TryFrom<CliMeasure> for Measure {
@@ -25,6 +33,9 @@ impl TryFrom<CliMeasure> for Measure {
             CliMeasure::View(view) => Self::View(view.try_into()?),
             CliMeasure::Update(update) => Self::Update(update.try_into()?),
             CliMeasure::Delete(delete) => Self::Delete(delete.try_into()?),
+            CliMeasure::Merge(merge) => Self::Merge(merge.try_into()?),
+            CliMeasure::Alias(alias) => Self::Alias(alias.try_into()?),
+            CliMeasure::Units(units) => Self::Units(units.try_into()?),
         })
     }
 }
@@ -37,6 +48,27 @@ impl SubCmd for Measure {
             Self::View(view) => view.exec().await,
             Self::Update(update) => update.exec().await,
             Self::Delete(delete) => delete.exec().await,
+            Self::Merge(merge) => merge.exec().await,
+            Self::Alias(alias) => alias.exec().await,
+            Self::Units(units) => units.exec().await,
         }
     }
 }
+
+#[derive(thiserror::Error, Debug)]
+pub enum MeasureError {
+    #[error("Unknown units of measure ({0}). Run `bencher measure units` to list the known units of measure, or use `--custom-units` to allow any units of measure.")]
+    UnknownUnits(String),
+}
+
+/// Validate the units of measure against the known units.
+/// Known units are normalized, for example `ns` becomes `nanoseconds (ns)`.
+fn known_units(units: ResourceName, custom_units: bool) -> Result<ResourceName, MeasureError> {
+    if let Some(unit) = MeasureUnit::find(units.as_ref()) {
+        Ok(unit.units())
+    } else if custom_units {
+        Ok(units)
+    } else {
+        Err(MeasureError::UnknownUnits(units.into()))
+    }
+}
@@ -0,0 +1,49 @@
+use bencher_json::project::measure::units::{MeasureUnit, MEASURE_UNITS};
+
+use crate::{
+    bencher::sub::SubCmd, cli_println, parser::project::measure::CliMeasureUnits, CliError,
+};
+
+use super::MeasureError;
+
+#[derive(Debug, Clone)]
+pub struct Units {
+    pub units: Option<&'static MeasureUnit>,
+    pub value: Option<f64>,
+}
+
+impl TryFrom<CliMeasureUnits> for Units {
+    type Error = CliError;
+
+    fn try_from(units: CliMeasureUnits) -> Result<Self, Self::Error> {
+        let CliMeasureUnits { units, value } = units;
+        let units = units
+            .map(|units| MeasureUnit::find(&units).ok_or(MeasureError::UnknownUnits(units)))
+            .transpose()?;
+        Ok(Self { units, value })
+    }
+}
+
+impl SubCmd for Units {
+    async fn exec(&self) -> Result<(), CliError> {
+        let Some(from) = self.units else {
+            for unit in MEASURE_UNITS {
+                cli_println!("{unit} [{dimension}]", dimension = unit.dimension);
+            }
+            return Ok(());
+        };
+
+        cli_println!("{from} [{dimension}]", dimension = from.dimension);
+        let value = self.value.unwrap_or(1.0);
+        for to in from.conversions() {
+            if let Some(converted) = from.convert(value, to) {
+                cli_println!("{value} {} = {converted} {}", symbol(from), symbol(to));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn symbol(unit: &MeasureUnit) -> &'static str {
+    unit.symbol.unwrap_or(unit.name)
+}
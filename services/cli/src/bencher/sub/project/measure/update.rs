@@ -28,6 +28,7 @@ impl TryFrom<CliMeasureUpdate> for Update {
             name,
             slug,
             units,
+            custom_units,
             archived,
             backend,
         } = create;
@@ -36,7 +37,9 @@ impl TryFrom<CliMeasureUpdate> for Update {
             measure,
             name,
             slug,
-            units,
+            units: units
+                .map(|units| super::known_units(units, custom_units))
+                .transpose()?,
             archived: archived.into(),
             backend: backend.try_into()?,
         })
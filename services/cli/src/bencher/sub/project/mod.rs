@@ -1,14 +1,20 @@
 pub mod alert;
+pub mod annotation;
 pub mod archive;
 pub mod benchmark;
 pub mod branch;
+pub mod calibrate;
+pub mod expectation;
+pub mod import;
 pub mod measure;
 pub mod metric;
 pub mod perf;
 pub mod plot;
 #[allow(clippy::module_inception)]
 pub mod project;
+pub mod query;
 pub mod report;
+pub mod rule;
 pub mod run;
 pub mod testbed;
 pub mod threshold;
@@ -2,10 +2,9 @@ use std::future::Future;
 use std::pin::Pin;
 
 use bencher_json::{
-    BenchmarkUuid, BranchUuid, DateTime, HeadUuid, JsonPerf, JsonPerfQuery, MeasureUuid,
-    ResourceId, TestbedUuid,
+    BenchmarkUuid, BranchUuid, DateTime, HeadUuid, JsonPerf, JsonPerfQuery, JsonUserPrefs,
+    MeasureUuid, NonEmpty, ResourceId, ResourceName, TestbedUuid,
 };
-use tabled::Table;
 
 use crate::parser::ElidedOption;
 use crate::{bencher::backend::PubBackend, cli_println, parser::project::perf::CliPerf, CliError};
@@ -27,7 +26,11 @@ pub struct Perf {
     measures: Vec<MeasureUuid>,
     start_time: Option<DateTime>,
     end_time: Option<DateTime>,
+    tags: Vec<NonEmpty>,
+    unit: Option<ResourceName>,
     table: Option<Option<TableStyle>>,
+    sparkline: bool,
+    user: Option<ResourceId>,
     backend: PubBackend,
 }
 
@@ -44,7 +47,11 @@ impl TryFrom<CliPerf> for Perf {
             measures,
             start_time,
             end_time,
+            tags,
+            unit,
             table,
+            sparkline,
+            user,
             backend,
         } = perf;
         let backend = PubBackend::try_from(backend)?.log(table.is_none());
@@ -57,7 +64,11 @@ impl TryFrom<CliPerf> for Perf {
             measures,
             start_time,
             end_time,
+            tags,
+            unit,
             table: table.map(|t| t.map(Into::into)),
+            sparkline,
+            user,
             backend,
         })
     }
@@ -73,6 +84,8 @@ impl From<Perf> for JsonPerfQuery {
             measures,
             start_time,
             end_time,
+            tags,
+            unit,
             ..
         } = perf;
         Self {
@@ -83,6 +96,8 @@ impl From<Perf> for JsonPerfQuery {
             measures,
             start_time,
             end_time,
+            tags,
+            unit,
         }
     }
 }
@@ -91,8 +106,19 @@ impl SubCmd for Perf {
     async fn exec(&self) -> Result<(), CliError> {
         let sender = perf_sender(self.project.clone(), self.clone());
         if let Some(table_style) = self.table {
-            let json_perf: JsonPerf = self.backend.send_with(sender).await?;
-            let mut perf_table: Table = json_perf.into();
+            let mut json_perf: JsonPerf = self.backend.send_with(sender).await?;
+            let prefs = self.user_prefs().await?;
+            if let Some(prefs) = &prefs {
+                // An explicit unit takes precedence over the preferred units
+                if self.unit.is_none() {
+                    prefs.convert_perf(&mut json_perf);
+                }
+            }
+            let mut perf_table = if self.sparkline {
+                json_perf.into_sparkline_table()
+            } else {
+                json_perf.into_table(prefs.as_ref())
+            };
             if let Some(table_style) = table_style {
                 table_style.stylize(&mut perf_table);
             }
@@ -104,6 +130,21 @@ impl SubCmd for Perf {
     }
 }
 
+impl Perf {
+    async fn user_prefs(&self) -> Result<Option<JsonUserPrefs>, CliError> {
+        let Some(user) = &self.user else {
+            return Ok(None);
+        };
+        let json_prefs: JsonUserPrefs = self
+            .backend
+            .send_with(
+                |client| async move { client.user_prefs_get().user(user.clone()).send().await },
+            )
+            .await?;
+        Ok(Some(json_prefs))
+    }
+}
+
 type PerfQueryResult = Pin<
     Box<
         dyn Future<
@@ -141,6 +182,12 @@ fn perf_sender(
             if let Some(end_time) = json_perf_query.end_time() {
                 client = client.end_time(end_time);
             }
+            if let Some(tags) = json_perf_query.tags() {
+                client = client.tags(tags);
+            }
+            if let Some(unit) = json_perf_query.unit() {
+                client = client.unit(unit);
+            }
 
             client.send().await
         })
@@ -35,6 +35,7 @@ impl From<CliProjectPermission> for ProjectPermission {
     fn from(permission: CliProjectPermission) -> Self {
         match permission {
             CliProjectPermission::View => Self::View,
+            CliProjectPermission::Report => Self::Report,
             CliProjectPermission::Create => Self::Create,
             CliProjectPermission::Edit => Self::Edit,
             CliProjectPermission::Delete => Self::Delete,
@@ -1,9 +1,9 @@
-use bencher_client::types::{JsonNewProject, Visibility};
-use bencher_json::{ResourceId, ResourceName, Slug, Url};
+use bencher_client::types::{JsonNewProject, Visibility, Weekday};
+use bencher_json::{ResourceId, ResourceName, Slug, TimeZone, Url};
 
 use crate::{
     bencher::{backend::AuthBackend, sub::SubCmd},
-    parser::project::{CliProjectCreate, CliProjectVisibility},
+    parser::project::{CliProjectCreate, CliProjectVisibility, CliProjectWeekStart},
     CliError,
 };
 
@@ -14,6 +14,9 @@ pub struct Create {
     pub slug: Option<Slug>,
     pub url: Option<Url>,
     pub visibility: Visibility,
+    pub time_zone: Option<TimeZone>,
+    pub week_start: Option<Weekday>,
+    pub strict: bool,
     pub backend: AuthBackend,
 }
 
@@ -27,6 +30,9 @@ impl TryFrom<CliProjectCreate> for Create {
             slug,
             url,
             visibility,
+            time_zone,
+            week_start,
+            strict,
             backend,
         } = create;
         Ok(Self {
@@ -35,6 +41,9 @@ impl TryFrom<CliProjectCreate> for Create {
             slug,
             url,
             visibility: visibility.into(),
+            time_zone,
+            week_start: week_start.map(Into::into),
+            strict,
             backend: backend.try_into()?,
         })
     }
@@ -46,6 +55,22 @@ impl From<CliProjectVisibility> for Visibility {
             CliProjectVisibility::Public => Self::Public,
             #[cfg(feature = "plus")]
             CliProjectVisibility::Private => Self::Private,
+            #[cfg(feature = "plus")]
+            CliProjectVisibility::Internal => Self::Internal,
+        }
+    }
+}
+
+impl From<CliProjectWeekStart> for Weekday {
+    fn from(week_start: CliProjectWeekStart) -> Self {
+        match week_start {
+            CliProjectWeekStart::Monday => Self::Monday,
+            CliProjectWeekStart::Tuesday => Self::Tuesday,
+            CliProjectWeekStart::Wednesday => Self::Wednesday,
+            CliProjectWeekStart::Thursday => Self::Thursday,
+            CliProjectWeekStart::Friday => Self::Friday,
+            CliProjectWeekStart::Saturday => Self::Saturday,
+            CliProjectWeekStart::Sunday => Self::Sunday,
         }
     }
 }
@@ -57,6 +82,9 @@ impl From<Create> for JsonNewProject {
             slug,
             url,
             visibility,
+            time_zone,
+            week_start,
+            strict,
             ..
         } = create;
         Self {
@@ -64,6 +92,9 @@ impl From<Create> for JsonNewProject {
             slug: slug.map(Into::into),
             url: url.map(Into::into),
             visibility: Some(visibility),
+            time_zone: time_zone.map(Into::into),
+            week_start,
+            strict: Some(strict),
         }
     }
 }
@@ -1,4 +1,4 @@
-use bencher_client::types::{JsonDirection, OrgProjectsSort, ProjectsSort};
+use bencher_client::types::{JsonDirection, OrgProjectsSort, ProjectsSort, Visibility};
 use bencher_json::{ResourceId, ResourceName};
 
 use crate::{
@@ -15,6 +15,7 @@ pub struct List {
     pub organization: Option<ResourceId>,
     pub name: Option<ResourceName>,
     pub search: Option<String>,
+    pub visibility: Option<Visibility>,
     pub pagination: Pagination,
     pub backend: PubBackend,
 }
@@ -36,6 +37,7 @@ impl TryFrom<CliProjectList> for List {
             organization,
             name,
             search,
+            visibility,
             pagination,
             backend,
         } = list;
@@ -43,6 +45,7 @@ impl TryFrom<CliProjectList> for List {
             organization,
             name,
             search,
+            visibility: visibility.map(Into::into),
             pagination: pagination.into(),
             backend: backend.try_into()?,
         })
@@ -84,6 +87,9 @@ impl SubCmd for List {
                     if let Some(search) = self.search.clone() {
                         client = client.search(search);
                     }
+                    if let Some(visibility) = self.visibility {
+                        client = client.visibility(visibility);
+                    }
                     if let Some(sort) = self.pagination.org_projects_sort {
                         client = client.sort(sort);
                     }
@@ -105,6 +111,9 @@ impl SubCmd for List {
                     if let Some(search) = self.search.clone() {
                         client = client.search(search);
                     }
+                    if let Some(visibility) = self.visibility {
+                        client = client.visibility(visibility);
+                    }
                     if let Some(sort) = self.pagination.projects_sort {
                         client = client.sort(sort);
                     }
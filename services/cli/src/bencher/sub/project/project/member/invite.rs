@@ -0,0 +1,78 @@
+use bencher_client::types::{JsonNewProjectMember, ProjectRole};
+use bencher_json::{Email, ResourceId, UserName};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::member::{CliProjectMemberInvite, CliProjectMemberRole},
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Invite {
+    project: ResourceId,
+    name: Option<UserName>,
+    email: Email,
+    role: ProjectRole,
+    backend: AuthBackend,
+}
+
+impl TryFrom<CliProjectMemberInvite> for Invite {
+    type Error = CliError;
+
+    fn try_from(invite: CliProjectMemberInvite) -> Result<Self, Self::Error> {
+        let CliProjectMemberInvite {
+            project,
+            name,
+            email,
+            role,
+            backend,
+        } = invite;
+        Ok(Self {
+            project,
+            name,
+            email,
+            role: role.into(),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<CliProjectMemberRole> for ProjectRole {
+    fn from(role: CliProjectMemberRole) -> Self {
+        match role {
+            CliProjectMemberRole::Viewer => Self::Viewer,
+            CliProjectMemberRole::Reporter => Self::Reporter,
+            CliProjectMemberRole::Maintainer => Self::Maintainer,
+        }
+    }
+}
+
+impl From<Invite> for JsonNewProjectMember {
+    fn from(invite: Invite) -> Self {
+        let Invite {
+            name, email, role, ..
+        } = invite;
+        Self {
+            name: name.map(Into::into),
+            email: email.into(),
+            role,
+        }
+    }
+}
+
+impl SubCmd for Invite {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_member_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
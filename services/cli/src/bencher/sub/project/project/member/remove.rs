@@ -0,0 +1,48 @@
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::member::CliProjectMemberRemove,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Remove {
+    pub project: ResourceId,
+    pub user: ResourceId,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliProjectMemberRemove> for Remove {
+    type Error = CliError;
+
+    fn try_from(remove: CliProjectMemberRemove) -> Result<Self, Self::Error> {
+        let CliProjectMemberRemove {
+            project,
+            user,
+            backend,
+        } = remove;
+        Ok(Self {
+            project,
+            user,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Remove {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_member_delete()
+                    .project(self.project.clone())
+                    .user(self.user.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
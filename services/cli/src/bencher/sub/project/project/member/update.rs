@@ -0,0 +1,59 @@
+use bencher_client::types::{JsonUpdateProjectMember, ProjectRole};
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::member::CliProjectMemberUpdate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub project: ResourceId,
+    pub user: ResourceId,
+    pub role: Option<ProjectRole>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliProjectMemberUpdate> for Update {
+    type Error = CliError;
+
+    fn try_from(update: CliProjectMemberUpdate) -> Result<Self, Self::Error> {
+        let CliProjectMemberUpdate {
+            project,
+            user,
+            role,
+            backend,
+        } = update;
+        Ok(Self {
+            project,
+            user,
+            role: role.map(Into::into),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Update> for JsonUpdateProjectMember {
+    fn from(update: Update) -> Self {
+        Self { role: update.role }
+    }
+}
+
+impl SubCmd for Update {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_member_patch()
+                    .project(self.project.clone())
+                    .user(self.user.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::member::CliProjectMemberView,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub project: ResourceId,
+    pub user: ResourceId,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliProjectMemberView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliProjectMemberView) -> Result<Self, Self::Error> {
+        let CliProjectMemberView {
+            project,
+            user,
+            backend,
+        } = view;
+        Ok(Self {
+            project,
+            user,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_member_get()
+                    .project(self.project.clone())
+                    .user(self.user.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ mod allowed;
 mod create;
 mod delete;
 mod list;
+mod member;
 mod update;
 mod view;
 
@@ -15,6 +16,7 @@ pub enum Project {
     Update(update::Update),
     Delete(delete::Delete),
     Allowed(allowed::Allowed),
+    Member(member::Member),
 }
 
 impl TryFrom<CliProject> for Project {
@@ -28,6 +30,7 @@ impl TryFrom<CliProject> for Project {
             CliProject::Update(update) => Self::Update(update.try_into()?),
             CliProject::Delete(delete) => Self::Delete(delete.try_into()?),
             CliProject::Allowed(allowed) => Self::Allowed(allowed.try_into()?),
+            CliProject::Member(member) => Self::Member(member.try_into()?),
         })
     }
 }
@@ -41,6 +44,7 @@ impl SubCmd for Project {
             Self::Update(update) => update.exec().await,
             Self::Delete(delete) => delete.exec().await,
             Self::Allowed(allowed) => allowed.exec().await,
+            Self::Member(member) => member.exec().await,
         }
     }
 }
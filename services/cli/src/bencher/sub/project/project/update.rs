@@ -1,7 +1,7 @@
 use bencher_client::types::{
-    JsonProjectPatch, JsonProjectPatchNull, JsonUpdateProject, Visibility,
+    JsonProjectPatch, JsonProjectPatchNull, JsonUpdateProject, Visibility, Weekday,
 };
-use bencher_json::{ResourceId, ResourceName, Slug, Url};
+use bencher_json::{ResourceId, ResourceName, Slug, TimeZone, Url};
 
 use crate::{
     bencher::{backend::AuthBackend, sub::SubCmd},
@@ -17,6 +17,9 @@ pub struct Update {
     pub slug: Option<Slug>,
     pub url: Option<Option<Url>>,
     pub visibility: Option<Visibility>,
+    pub time_zone: Option<TimeZone>,
+    pub week_start: Option<Weekday>,
+    pub strict: Option<bool>,
     pub backend: AuthBackend,
 }
 
@@ -30,6 +33,9 @@ impl TryFrom<CliProjectUpdate> for Update {
             slug,
             url,
             visibility,
+            time_zone,
+            week_start,
+            strict,
             backend,
         } = create;
         Ok(Self {
@@ -38,6 +44,9 @@ impl TryFrom<CliProjectUpdate> for Update {
             slug,
             url: url.map(Into::into),
             visibility: visibility.map(Into::into),
+            time_zone,
+            week_start: week_start.map(Into::into),
+            strict: strict.into(),
             backend: backend.try_into()?,
         })
     }
@@ -50,6 +59,9 @@ impl From<Update> for JsonUpdateProject {
             slug,
             url,
             visibility,
+            time_zone,
+            week_start,
+            strict,
             ..
         } = update;
         match url {
@@ -59,6 +71,9 @@ impl From<Update> for JsonUpdateProject {
                     slug: slug.map(Into::into),
                     url: Some(url.into()),
                     visibility,
+                    time_zone: time_zone.map(Into::into),
+                    week_start,
+                    strict,
                 }),
                 subtype_1: None,
             },
@@ -69,6 +84,9 @@ impl From<Update> for JsonUpdateProject {
                     slug: slug.map(Into::into),
                     url: (),
                     visibility,
+                    time_zone: time_zone.map(Into::into),
+                    week_start,
+                    strict,
                 }),
             },
             None => Self {
@@ -77,6 +95,9 @@ impl From<Update> for JsonUpdateProject {
                     slug: slug.map(Into::into),
                     url: None,
                     visibility,
+                    time_zone: time_zone.map(Into::into),
+                    week_start,
+                    strict,
                 }),
                 subtype_1: None,
             },
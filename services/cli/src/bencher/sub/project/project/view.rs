@@ -9,6 +9,7 @@ use crate::{
 #[derive(Debug)]
 pub struct View {
     pub project: ResourceId,
+    pub stats: bool,
     pub backend: PubBackend,
 }
 
@@ -16,9 +17,14 @@ impl TryFrom<CliProjectView> for View {
     type Error = CliError;
 
     fn try_from(view: CliProjectView) -> Result<Self, Self::Error> {
-        let CliProjectView { project, backend } = view;
+        let CliProjectView {
+            project,
+            stats,
+            backend,
+        } = view;
         Ok(Self {
             project,
+            stats,
             backend: backend.try_into()?,
         })
     }
@@ -29,11 +35,11 @@ impl SubCmd for View {
         let _json = self
             .backend
             .send(|client| async move {
-                client
-                    .project_get()
-                    .project(self.project.clone())
-                    .send()
-                    .await
+                let mut client = client.project_get().project(self.project.clone());
+                if self.stats {
+                    client = client.stats(true);
+                }
+                client.send().await
             })
             .await?;
         Ok(())
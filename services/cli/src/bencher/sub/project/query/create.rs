@@ -0,0 +1,106 @@
+use bencher_client::types::JsonNewSavedQuery;
+use bencher_json::{
+    project::saved_query::SavedQuerySchedule, BenchmarkUuid, BranchUuid, Email, MeasureUuid,
+    ResourceId, ResourceName, TestbedUuid, Window,
+};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::query::CliQueryCreate,
+    CliError,
+};
+
+use super::into_client_schedule;
+
+#[derive(Debug, Clone)]
+pub struct Create {
+    pub project: ResourceId,
+    pub name: ResourceName,
+    pub window: Window,
+    pub branches: Vec<BranchUuid>,
+    pub testbeds: Vec<TestbedUuid>,
+    pub benchmarks: Vec<BenchmarkUuid>,
+    pub measures: Vec<MeasureUuid>,
+    pub schedule: Option<SavedQuerySchedule>,
+    pub emails: Vec<Email>,
+    pub webhook: bool,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliQueryCreate> for Create {
+    type Error = CliError;
+
+    fn try_from(create: CliQueryCreate) -> Result<Self, Self::Error> {
+        let CliQueryCreate {
+            project,
+            name,
+            window,
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+            schedule,
+            emails,
+            webhook,
+            backend,
+        } = create;
+        Ok(Self {
+            project,
+            name,
+            window,
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+            schedule: schedule.map(Into::into),
+            emails,
+            webhook,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Create> for JsonNewSavedQuery {
+    fn from(create: Create) -> Self {
+        let Create {
+            name,
+            window,
+            branches,
+            testbeds,
+            benchmarks,
+            measures,
+            schedule,
+            emails,
+            webhook,
+            ..
+        } = create;
+        Self {
+            name: name.into(),
+            window: window.into(),
+            branches: branches.into_iter().map(Into::into).collect(),
+            testbeds: testbeds.into_iter().map(Into::into).collect(),
+            benchmarks: benchmarks.into_iter().map(Into::into).collect(),
+            measures: measures.into_iter().map(Into::into).collect(),
+            schedule: schedule.map(into_client_schedule),
+            emails: (!emails.is_empty()).then(|| emails.into_iter().map(Into::into).collect()),
+            webhook: webhook.then_some(true),
+        }
+    }
+}
+
+impl SubCmd for Create {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_query_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use bencher_json::{ResourceId, SavedQueryUuid};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::query::CliQueryDelete,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Delete {
+    pub project: ResourceId,
+    pub query: SavedQueryUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliQueryDelete> for Delete {
+    type Error = CliError;
+
+    fn try_from(delete: CliQueryDelete) -> Result<Self, Self::Error> {
+        let CliQueryDelete {
+            project,
+            query,
+            backend,
+        } = delete;
+        Ok(Self {
+            project,
+            query,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Delete {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_query_delete()
+                    .project(self.project.clone())
+                    .query(self.query)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
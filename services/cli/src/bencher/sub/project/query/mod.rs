@@ -0,0 +1,67 @@
+use bencher_json::project::saved_query::SavedQuerySchedule;
+
+use crate::{
+    bencher::sub::SubCmd,
+    parser::project::query::{CliQuery, CliQuerySchedule},
+    CliError,
+};
+
+mod create;
+mod delete;
+mod list;
+mod update;
+mod view;
+
+#[derive(Debug)]
+pub enum Query {
+    List(list::List),
+    Create(create::Create),
+    View(view::View),
+    Update(update::Update),
+    Delete(delete::Delete),
+}
+
+impl TryFrom<CliQuery> for Query {
+    type Error = CliError;
+
+    fn try_from(query: CliQuery) -> Result<Self, Self::Error> {
+        Ok(match query {
+            CliQuery::List(list) => Self::List(list.try_into()?),
+            CliQuery::Create(create) => Self::Create(create.try_into()?),
+            CliQuery::View(view) => Self::View(view.try_into()?),
+            CliQuery::Update(update) => Self::Update(update.try_into()?),
+            CliQuery::Delete(delete) => Self::Delete(delete.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Query {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::List(list) => list.exec().await,
+            Self::Create(create) => create.exec().await,
+            Self::View(view) => view.exec().await,
+            Self::Update(update) => update.exec().await,
+            Self::Delete(delete) => delete.exec().await,
+        }
+    }
+}
+
+impl From<CliQuerySchedule> for SavedQuerySchedule {
+    fn from(schedule: CliQuerySchedule) -> Self {
+        match schedule {
+            CliQuerySchedule::Never => Self::Never,
+            CliQuerySchedule::Daily => Self::Daily,
+            CliQuerySchedule::Weekly => Self::Weekly,
+        }
+    }
+}
+
+#[allow(clippy::absolute_paths)]
+fn into_client_schedule(schedule: SavedQuerySchedule) -> bencher_client::types::SavedQuerySchedule {
+    match schedule {
+        SavedQuerySchedule::Never => bencher_client::types::SavedQuerySchedule::Never,
+        SavedQuerySchedule::Daily => bencher_client::types::SavedQuerySchedule::Daily,
+        SavedQuerySchedule::Weekly => bencher_client::types::SavedQuerySchedule::Weekly,
+    }
+}
@@ -0,0 +1,93 @@
+use bencher_client::types::JsonUpdateSavedQuery;
+use bencher_json::{
+    project::saved_query::SavedQuerySchedule, Email, ResourceId, ResourceName, SavedQueryUuid,
+    Window,
+};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::query::CliQueryUpdate,
+    CliError,
+};
+
+use super::into_client_schedule;
+
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub project: ResourceId,
+    pub query: SavedQueryUuid,
+    pub name: Option<ResourceName>,
+    pub window: Option<Window>,
+    pub schedule: Option<SavedQuerySchedule>,
+    pub emails: Option<Vec<Email>>,
+    pub webhook: Option<bool>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliQueryUpdate> for Update {
+    type Error = CliError;
+
+    fn try_from(update: CliQueryUpdate) -> Result<Self, Self::Error> {
+        let CliQueryUpdate {
+            project,
+            query,
+            name,
+            window,
+            schedule,
+            emails,
+            no_emails,
+            webhook,
+            backend,
+        } = update;
+        // An empty list of email addresses stops sending emails
+        let emails = (no_emails || !emails.is_empty()).then_some(emails);
+        Ok(Self {
+            project,
+            query,
+            name,
+            window,
+            schedule: schedule.map(Into::into),
+            emails,
+            webhook: webhook.into(),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Update> for JsonUpdateSavedQuery {
+    fn from(update: Update) -> Self {
+        let Update {
+            name,
+            window,
+            schedule,
+            emails,
+            webhook,
+            ..
+        } = update;
+        Self {
+            name: name.map(Into::into),
+            window: window.map(Into::into),
+            schedule: schedule.map(into_client_schedule),
+            emails: emails.map(|emails| emails.into_iter().map(Into::into).collect()),
+            webhook,
+        }
+    }
+}
+
+impl SubCmd for Update {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_query_patch()
+                    .project(self.project.clone())
+                    .query(self.query)
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
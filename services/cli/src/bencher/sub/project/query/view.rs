@@ -0,0 +1,48 @@
+use bencher_json::{ResourceId, SavedQueryUuid};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::query::CliQueryView,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub project: ResourceId,
+    pub query: SavedQueryUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliQueryView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliQueryView) -> Result<Self, Self::Error> {
+        let CliQueryView {
+            project,
+            query,
+            backend,
+        } = view;
+        Ok(Self {
+            project,
+            query,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_query_get()
+                    .project(self.project.clone())
+                    .query(self.query)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
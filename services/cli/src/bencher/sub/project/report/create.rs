@@ -1,6 +1,6 @@
 use bencher_client::types::{
-    Adapter, DateTime, GitHash, JsonAverage, JsonFold, JsonNewReport, JsonReportSettings,
-    JsonUpdateStartPoint, NameId,
+    Adapter, DateTime, GitHash, JsonAverage, JsonDispersion, JsonFold, JsonNewReport,
+    JsonReportSettings, JsonUpdateStartPoint, NameId,
 };
 use bencher_json::ResourceId;
 
@@ -22,6 +22,7 @@ pub struct Create {
     pub branch: NameId,
     pub hash: Option<GitHash>,
     pub start_point: Option<JsonUpdateStartPoint>,
+    pub reference_branch: Option<NameId>,
     pub testbed: NameId,
     pub thresholds: Thresholds,
     pub start_time: DateTime,
@@ -30,6 +31,7 @@ pub struct Create {
     pub adapter: Option<Adapter>,
     pub average: Option<JsonAverage>,
     pub fold: Option<JsonFold>,
+    pub dispersion: Option<JsonDispersion>,
     pub backend: AuthBackend,
 }
 
@@ -42,6 +44,7 @@ impl TryFrom<CliReportCreate> for Create {
             branch,
             hash,
             start_point,
+            reference_branch,
             testbed,
             thresholds,
             start_time,
@@ -50,6 +53,7 @@ impl TryFrom<CliReportCreate> for Create {
             adapter,
             average,
             fold,
+            dispersion,
             backend,
         } = create;
         Ok(Self {
@@ -57,6 +61,7 @@ impl TryFrom<CliReportCreate> for Create {
             branch: branch.into(),
             hash: hash.map(Into::into),
             start_point: StartPoint::from(start_point).into(),
+            reference_branch: reference_branch.map(Into::into),
             testbed: testbed.into(),
             thresholds: thresholds.try_into().map_err(CliError::Thresholds)?,
             start_time: start_time.into(),
@@ -65,6 +70,7 @@ impl TryFrom<CliReportCreate> for Create {
             adapter: adapter.map(Into::into),
             average: average.map(Into::into),
             fold: fold.map(Into::into),
+            dispersion: dispersion.map(Into::into),
             backend: backend.try_into()?,
         })
     }
@@ -76,6 +82,7 @@ impl From<Create> for JsonNewReport {
             branch,
             hash,
             start_point,
+            reference_branch,
             testbed,
             start_time,
             end_time,
@@ -83,22 +90,33 @@ impl From<Create> for JsonNewReport {
             adapter,
             average,
             fold,
+            dispersion,
             ..
         } = create;
         Self {
             branch,
             hash,
             start_point,
+            reference_branch,
             testbed,
             thresholds: None,
             start_time,
             end_time,
+            tag: None,
             results,
             settings: Some(JsonReportSettings {
                 adapter,
                 average,
+                dispersion,
                 fold,
             }),
+            strict: None,
+            warmup: None,
+            notes: None,
+            links: None,
+            failures: None,
+            git: None,
+            fingerprint: None,
         }
     }
 }
@@ -1,5 +1,8 @@
 use bencher_client::types::{JsonDirection, ProjReportsSort};
-use bencher_json::{project::report::JsonReportQuery, DateTime, NameId, ResourceId};
+use bencher_json::{
+    project::{head::VersionNumber, report::JsonReportQuery},
+    DateTime, NameId, NonEmpty, ResourceId,
+};
 
 use crate::{
     bencher::{backend::PubBackend, sub::SubCmd},
@@ -15,8 +18,12 @@ pub struct List {
     pub project: ResourceId,
     pub branch: Option<NameId>,
     pub testbed: Option<NameId>,
+    pub tag: Option<NonEmpty>,
     pub start_time: Option<DateTime>,
     pub end_time: Option<DateTime>,
+    pub hash: Option<String>,
+    pub start_version: Option<VersionNumber>,
+    pub end_version: Option<VersionNumber>,
     pub pagination: Pagination,
     pub archived: bool,
     pub backend: PubBackend,
@@ -38,8 +45,12 @@ impl TryFrom<CliReportList> for List {
             project,
             branch,
             testbed,
+            tag,
             start_time,
             end_time,
+            hash,
+            start_version,
+            end_version,
             pagination,
             archived,
             backend,
@@ -48,8 +59,12 @@ impl TryFrom<CliReportList> for List {
             project,
             branch,
             testbed,
+            tag,
             start_time,
             end_time,
+            hash,
+            start_version: start_version.map(VersionNumber),
+            end_version: end_version.map(VersionNumber),
             pagination: pagination.into(),
             archived,
             backend: backend.try_into()?,
@@ -68,6 +83,7 @@ impl From<CliPagination<CliReportsSort>> for Pagination {
         Self {
             sort: sort.map(|sort| match sort {
                 CliReportsSort::DateTime => ProjReportsSort::DateTime,
+                CliReportsSort::Version => ProjReportsSort::Version,
             }),
             direction: direction.map(Into::into),
             page,
@@ -81,16 +97,24 @@ impl From<List> for JsonReportQuery {
         let List {
             branch,
             testbed,
+            tag,
             start_time,
             end_time,
+            hash,
+            start_version,
+            end_version,
             archived,
             ..
         } = list;
         Self {
             branch,
             testbed,
+            tag,
             start_time,
             end_time,
+            hash,
+            start_version,
+            end_version,
             archived: archived.then_some(archived),
         }
     }
@@ -110,6 +134,9 @@ impl SubCmd for List {
                 if let Some(testbed) = json_report_query.testbed() {
                     client = client.testbed(testbed);
                 }
+                if let Some(tag) = json_report_query.tag() {
+                    client = client.tag(tag);
+                }
 
                 if let Some(start_time) = json_report_query.start_time() {
                     client = client.start_time(start_time);
@@ -118,6 +145,16 @@ impl SubCmd for List {
                     client = client.end_time(end_time);
                 }
 
+                if let Some(hash) = json_report_query.hash.clone() {
+                    client = client.hash(hash);
+                }
+                if let Some(start_version) = json_report_query.start_version {
+                    client = client.start_version(start_version);
+                }
+                if let Some(end_version) = json_report_query.end_version {
+                    client = client.end_version(end_version);
+                }
+
                 if let Some(archived) = json_report_query.archived {
                     client = client.archived(archived);
                 }
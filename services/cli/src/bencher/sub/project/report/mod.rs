@@ -5,6 +5,8 @@ mod delete;
 mod list;
 mod view;
 
+pub use view::ReportError;
+
 #[derive(Debug)]
 pub enum Report {
     List(list::List),
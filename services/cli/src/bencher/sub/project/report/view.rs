@@ -1,18 +1,46 @@
-use bencher_json::{ReportUuid, ResourceId};
+use bencher_json::{project::report::Iteration, JsonReport, ReportUuid, ResourceId};
 
 use crate::{
     bencher::{backend::PubBackend, sub::SubCmd},
+    cli_println,
     parser::project::report::CliReportView,
-    CliError,
+    CliError, ExitClass,
 };
 
 #[derive(Debug)]
 pub struct View {
     pub project: ResourceId,
     pub report: ReportUuid,
+    pub raw: bool,
+    pub iteration: Option<Iteration>,
     pub backend: PubBackend,
 }
 
+#[allow(clippy::absolute_paths)]
+#[derive(thiserror::Error, Debug)]
+pub enum ReportError {
+    #[error("Failed to get report: {0}")]
+    GetReport(crate::BackendError),
+    #[error("Report ({report}) does not have an iteration {iteration}. It has {count} iteration(s), starting from 0.")]
+    NoIteration {
+        report: ReportUuid,
+        iteration: Iteration,
+        count: usize,
+    },
+    #[error("Failed to serialize report results: {0}")]
+    SerializeResults(serde_json::Error),
+}
+
+impl ReportError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::GetReport(err) => err.exit_class(),
+            Self::NoIteration { .. } => ExitClass::Usage,
+            Self::SerializeResults(_) => ExitClass::Failure,
+        }
+    }
+}
+
 impl TryFrom<CliReportView> for View {
     type Error = CliError;
 
@@ -20,18 +48,26 @@ impl TryFrom<CliReportView> for View {
         let CliReportView {
             project,
             report,
+            raw,
+            iteration,
             backend,
         } = view;
         Ok(Self {
             project,
             report,
-            backend: backend.try_into()?,
+            raw,
+            iteration: iteration.map(Iteration),
+            // The raw results are printed on their own, so they can be piped to a file
+            backend: PubBackend::try_from(backend)?.log(!raw),
         })
     }
 }
 
 impl SubCmd for View {
     async fn exec(&self) -> Result<(), CliError> {
+        if self.raw {
+            return self.exec_raw().await.map_err(Into::into);
+        }
         let _json = self
             .backend
             .send(|client| async move {
@@ -46,3 +82,44 @@ impl SubCmd for View {
         Ok(())
     }
 }
+
+impl View {
+    async fn exec_raw(&self) -> Result<(), ReportError> {
+        let json_report: JsonReport = self
+            .backend
+            .send_with(|client| async move {
+                client
+                    .proj_report_get()
+                    .project(self.project.clone())
+                    .report(self.report)
+                    .send()
+                    .await
+            })
+            .await
+            .map_err(ReportError::GetReport)?;
+
+        let mut results = json_report.results_map();
+        // Converting to a JSON value sorts the benchmarks and measures by name
+        let value = if let Some(iteration) = self.iteration {
+            let count = results.len();
+            let Some(iteration_results) = usize::try_from(u32::from(iteration))
+                .ok()
+                .filter(|index| *index < count)
+                .map(|index| results.swap_remove(index))
+            else {
+                return Err(ReportError::NoIteration {
+                    report: self.report,
+                    iteration,
+                    count,
+                });
+            };
+            serde_json::to_value(iteration_results)
+        } else {
+            serde_json::to_value(results)
+        }
+        .map_err(ReportError::SerializeResults)?;
+        let raw = serde_json::to_string_pretty(&value).map_err(ReportError::SerializeResults)?;
+        cli_println!("{raw}");
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use bencher_client::types::JsonNewRule;
+use bencher_json::{NonEmpty, ResourceId};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::rule::CliRuleCreate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Create {
+    pub project: ResourceId,
+    pub pattern: NonEmpty,
+    pub replacement: String,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliRuleCreate> for Create {
+    type Error = CliError;
+
+    fn try_from(create: CliRuleCreate) -> Result<Self, Self::Error> {
+        let CliRuleCreate {
+            project,
+            pattern,
+            replacement,
+            backend,
+        } = create;
+        Ok(Self {
+            project,
+            pattern,
+            replacement,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Create> for JsonNewRule {
+    fn from(create: Create) -> Self {
+        let Create {
+            pattern,
+            replacement,
+            ..
+        } = create;
+        Self {
+            pattern: pattern.into(),
+            replacement,
+        }
+    }
+}
+
+impl SubCmd for Create {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_rule_post()
+                    .project(self.project.clone())
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
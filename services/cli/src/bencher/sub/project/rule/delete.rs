@@ -0,0 +1,48 @@
+use bencher_json::{ResourceId, RuleUuid};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::rule::CliRuleDelete,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct Delete {
+    pub project: ResourceId,
+    pub rule: RuleUuid,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliRuleDelete> for Delete {
+    type Error = CliError;
+
+    fn try_from(delete: CliRuleDelete) -> Result<Self, Self::Error> {
+        let CliRuleDelete {
+            project,
+            rule,
+            backend,
+        } = delete;
+        Ok(Self {
+            project,
+            rule,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for Delete {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_rule_delete()
+                    .project(self.project.clone())
+                    .rule(self.rule)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use bencher_client::types::{JsonDirection, ProjRulesSort};
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::{
+        project::rule::{CliRuleList, CliRulesSort},
+        CliPagination,
+    },
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct List {
+    pub project: ResourceId,
+    pub search: Option<String>,
+    pub pagination: Pagination,
+    pub backend: PubBackend,
+}
+
+#[derive(Debug)]
+pub struct Pagination {
+    pub sort: Option<ProjRulesSort>,
+    pub direction: Option<JsonDirection>,
+    pub per_page: Option<u8>,
+    pub page: Option<u32>,
+}
+
+impl TryFrom<CliRuleList> for List {
+    type Error = CliError;
+
+    fn try_from(list: CliRuleList) -> Result<Self, Self::Error> {
+        let CliRuleList {
+            project,
+            search,
+            pagination,
+            backend,
+        } = list;
+        Ok(Self {
+            project,
+            search,
+            pagination: pagination.into(),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<CliPagination<CliRulesSort>> for Pagination {
+    fn from(pagination: CliPagination<CliRulesSort>) -> Self {
+        let CliPagination {
+            sort,
+            direction,
+            per_page,
+            page,
+        } = pagination;
+        Self {
+            sort: sort.map(|sort| match sort {
+                CliRulesSort::Created => ProjRulesSort::Created,
+            }),
+            direction: direction.map(Into::into),
+            page,
+            per_page,
+        }
+    }
+}
+
+impl SubCmd for List {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                let mut client = client.proj_rules_get().project(self.project.clone());
+                if let Some(search) = self.search.clone() {
+                    client = client.search(search);
+                }
+                if let Some(sort) = self.pagination.sort {
+                    client = client.sort(sort);
+                }
+                if let Some(direction) = self.pagination.direction {
+                    client = client.direction(direction);
+                }
+                if let Some(per_page) = self.pagination.per_page {
+                    client = client.per_page(per_page);
+                }
+                if let Some(page) = self.pagination.page {
+                    client = client.page(page);
+                }
+                client.send().await
+            })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{bencher::sub::SubCmd, parser::project::rule::CliRule, CliError};
+
+mod create;
+mod delete;
+mod list;
+mod update;
+mod view;
+
+#[derive(Debug)]
+pub enum Rule {
+    List(list::List),
+    Create(create::Create),
+    View(view::View),
+    Update(update::Update),
+    Delete(delete::Delete),
+}
+
+impl TryFrom<CliRule> for Rule {
+    type Error = CliError;
+
+    fn try_from(rule: CliRule) -> Result<Self, Self::Error> {
+        Ok(match rule {
+            CliRule::List(list) => Self::List(list.try_into()?),
+            CliRule::Create(create) => Self::Create(create.try_into()?),
+            CliRule::View(view) => Self::View(view.try_into()?),
+            CliRule::Update(update) => Self::Update(update.try_into()?),
+            CliRule::Delete(delete) => Self::Delete(delete.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Rule {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::List(list) => list.exec().await,
+            Self::Create(create) => create.exec().await,
+            Self::View(view) => view.exec().await,
+            Self::Update(update) => update.exec().await,
+            Self::Delete(delete) => delete.exec().await,
+        }
+    }
+}
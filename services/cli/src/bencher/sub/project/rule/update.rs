@@ -0,0 +1,70 @@
+use bencher_client::types::JsonUpdateRule;
+use bencher_json::{NonEmpty, ResourceId, RuleUuid};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::rule::CliRuleUpdate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub project: ResourceId,
+    pub rule: RuleUuid,
+    pub pattern: Option<NonEmpty>,
+    pub replacement: Option<String>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliRuleUpdate> for Update {
+    type Error = CliError;
+
+    fn try_from(update: CliRuleUpdate) -> Result<Self, Self::Error> {
+        let CliRuleUpdate {
+            project,
+            rule,
+            pattern,
+            replacement,
+            backend,
+        } = update;
+        Ok(Self {
+            project,
+            rule,
+            pattern,
+            replacement,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Update> for JsonUpdateRule {
+    fn from(update: Update) -> Self {
+        let Update {
+            pattern,
+            replacement,
+            ..
+        } = update;
+        Self {
+            pattern: pattern.map(Into::into),
+            replacement,
+        }
+    }
+}
+
+impl SubCmd for Update {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_rule_patch()
+                    .project(self.project.clone())
+                    .rule(self.rule)
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
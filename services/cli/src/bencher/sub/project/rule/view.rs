@@ -0,0 +1,48 @@
+use bencher_json::{ResourceId, RuleUuid};
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    parser::project::rule::CliRuleView,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub project: ResourceId,
+    pub rule: RuleUuid,
+    pub backend: PubBackend,
+}
+
+impl TryFrom<CliRuleView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliRuleView) -> Result<Self, Self::Error> {
+        let CliRuleView {
+            project,
+            rule,
+            backend,
+        } = view;
+        Ok(Self {
+            project,
+            rule,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_rule_get()
+                    .project(self.project.clone())
+                    .rule(self.rule)
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
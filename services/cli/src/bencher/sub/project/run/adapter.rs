@@ -1,6 +1,9 @@
-use bencher_client::types::Adapter;
+use bencher_adapter::{AdapterResults, Settings as AdapterSettings};
+use bencher_client::types::{Adapter, JsonAverage};
+use bencher_json::project::report::{Adapter as JsonAdapter, JsonAverage as AdapterAverage};
 
-use crate::parser::project::run::CliRunAdapter;
+use super::runner::capture::Captured;
+use crate::{parser::project::run::CliRunAdapter, RunError};
 
 impl From<CliRunAdapter> for Adapter {
     fn from(adapter: CliRunAdapter) -> Self {
@@ -19,9 +22,13 @@ impl From<CliRunAdapter> for Adapter {
             CliRunAdapter::Js => Self::Js,
             CliRunAdapter::JsBenchmark => Self::JsBenchmark,
             CliRunAdapter::JsTime => Self::JsTime,
+            CliRunAdapter::JsNodeTest => Self::JsNodeTest,
+            CliRunAdapter::JsTinybench => Self::JsTinybench,
             CliRunAdapter::Python => Self::Python,
             CliRunAdapter::PythonAsv => Self::PythonAsv,
+            CliRunAdapter::PythonPyperf => Self::PythonPyperf,
             CliRunAdapter::PythonPytest => Self::PythonPytest,
+            CliRunAdapter::PythonRichbench => Self::PythonRichbench,
             CliRunAdapter::Ruby => Self::Ruby,
             CliRunAdapter::RubyBenchmark => Self::RubyBenchmark,
             CliRunAdapter::Rust => Self::Rust,
@@ -34,3 +41,100 @@ impl From<CliRunAdapter> for Adapter {
         }
     }
 }
+
+/// Check whether the adapter is able to parse all of the benchmark harness results.
+pub fn adapter_parses(adapter: Adapter, results: &[Captured]) -> Result<bool, RunError> {
+    let json_adapter = json_adapter(adapter);
+    // The average only changes the parsed values, not whether the results parse
+    for result in results {
+        if result
+            .convert(json_adapter, AdapterSettings::default())
+            .map_err(RunError::ReadSpilledOutput)?
+            .is_none()
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Parse the results from each output file with the adapter
+/// and combine them into a single Bencher Metric Format (BMF) JSON result.
+/// If a benchmark is in more than one file, then its measures are merged,
+/// with the later files taking precedence for the same measure.
+pub fn combine_results(
+    adapter: Adapter,
+    average: Option<JsonAverage>,
+    file_results: &[Captured],
+) -> Result<String, RunError> {
+    let combined = parse_results(adapter, average, file_results)?;
+    serde_json::to_string(&combined).map_err(RunError::SerializeOutputFiles)
+}
+
+/// Parse the results from each output file with the adapter and merge them together.
+pub fn parse_results(
+    adapter: Adapter,
+    average: Option<JsonAverage>,
+    file_results: &[Captured],
+) -> Result<AdapterResults, RunError> {
+    let json_adapter = json_adapter(adapter);
+    let settings = AdapterSettings::new(average.map(json_average));
+    let mut combined = AdapterResults::default();
+    for file_result in file_results {
+        let adapter_results = file_result
+            .convert(json_adapter, settings)
+            .map_err(RunError::ReadSpilledOutput)?
+            .ok_or(RunError::OutputFileAdapter(adapter))?;
+        for (benchmark_name, metrics) in adapter_results.inner {
+            combined
+                .inner
+                .entry(benchmark_name)
+                .or_default()
+                .inner
+                .extend(metrics.inner);
+        }
+    }
+    Ok(combined)
+}
+
+fn json_average(average: JsonAverage) -> AdapterAverage {
+    match average {
+        JsonAverage::Mean => AdapterAverage::Mean,
+        JsonAverage::Median => AdapterAverage::Median,
+    }
+}
+
+fn json_adapter(adapter: Adapter) -> JsonAdapter {
+    match adapter {
+        Adapter::Magic => JsonAdapter::Magic,
+        Adapter::Json => JsonAdapter::Json,
+        Adapter::CSharp => JsonAdapter::CSharp,
+        Adapter::CSharpDotNet => JsonAdapter::CSharpDotNet,
+        Adapter::Cpp => JsonAdapter::Cpp,
+        Adapter::CppCatch2 => JsonAdapter::CppCatch2,
+        Adapter::CppGoogle => JsonAdapter::CppGoogle,
+        Adapter::Go => JsonAdapter::Go,
+        Adapter::GoBench => JsonAdapter::GoBench,
+        Adapter::Java => JsonAdapter::Java,
+        Adapter::JavaJmh => JsonAdapter::JavaJmh,
+        Adapter::Js => JsonAdapter::Js,
+        Adapter::JsBenchmark => JsonAdapter::JsBenchmark,
+        Adapter::JsTime => JsonAdapter::JsTime,
+        Adapter::JsNodeTest => JsonAdapter::JsNodeTest,
+        Adapter::JsTinybench => JsonAdapter::JsTinybench,
+        Adapter::Python => JsonAdapter::Python,
+        Adapter::PythonAsv => JsonAdapter::PythonAsv,
+        Adapter::PythonPyperf => JsonAdapter::PythonPyperf,
+        Adapter::PythonPytest => JsonAdapter::PythonPytest,
+        Adapter::PythonRichbench => JsonAdapter::PythonRichbench,
+        Adapter::Ruby => JsonAdapter::Ruby,
+        Adapter::RubyBenchmark => JsonAdapter::RubyBenchmark,
+        Adapter::Rust => JsonAdapter::Rust,
+        Adapter::RustBench => JsonAdapter::RustBench,
+        Adapter::RustCriterion => JsonAdapter::RustCriterion,
+        Adapter::RustIai => JsonAdapter::RustIai,
+        Adapter::RustIaiCallgrind => JsonAdapter::RustIaiCallgrind,
+        Adapter::Shell => JsonAdapter::Shell,
+        Adapter::ShellHyperfine => JsonAdapter::ShellHyperfine,
+    }
+}
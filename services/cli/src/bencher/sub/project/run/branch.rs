@@ -1,4 +1,8 @@
-use bencher_json::{project::branch::BRANCH_MAIN_STR, GitHash, NameId};
+use bencher_client::types::JsonReportGit;
+use bencher_json::{
+    project::{branch::BRANCH_MAIN_STR, report::REPORT_GIT_MAX_LEN},
+    DateTime, GitHash, NameId, NonEmpty,
+};
 
 use crate::{
     bencher::sub::project::branch::start_point::StartPoint,
@@ -11,6 +15,7 @@ pub struct Branch {
     branch: NameId,
     hash: Option<GitHash>,
     start_point: StartPoint,
+    reference_branch: Option<NameId>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -31,6 +36,7 @@ impl TryFrom<CliRunBranch> for Branch {
             start_point_max_versions,
             start_point_clone_thresholds,
             start_point_reset,
+            reference_branch,
             deprecated: _,
         } = run_branch;
         let branch = try_branch(branch)?;
@@ -46,6 +52,61 @@ impl TryFrom<CliRunBranch> for Branch {
             branch,
             hash,
             start_point,
+            reference_branch,
+        })
+    }
+}
+
+impl Branch {
+    pub fn branch(&self) -> &NameId {
+        &self.branch
+    }
+
+    /// Find the `git` metadata for the report.
+    /// The branch and dirty status are for the current working tree,
+    /// while the tag, author, time, and subject are for the report commit.
+    pub fn git_metadata(&self) -> Option<JsonReportGit> {
+        let repo = find_repo()?;
+        let branch = repo
+            .head_name()
+            .ok()
+            .flatten()
+            .and_then(|name| git_field(&name.shorten().to_string()));
+        let dirty = repo.is_dirty().ok();
+        let commit = if let Some(hash) = &self.hash {
+            repo.rev_parse_single(hash.as_ref())
+                .ok()
+                .and_then(|id| id.object().ok())
+                .and_then(|object| object.try_into_commit().ok())
+        } else {
+            repo.head_commit().ok()
+        };
+        let (tag, author, commit_time, subject) = commit
+            .map(|commit| {
+                let tag = find_tag(&repo, commit.id);
+                let author = commit
+                    .author()
+                    .ok()
+                    .and_then(|author| git_field(&format!("{} <{}>", author.name, author.email)));
+                let commit_time = commit
+                    .time()
+                    .ok()
+                    .and_then(|time| DateTime::try_from(time.seconds).ok())
+                    .map(Into::into);
+                let subject = commit
+                    .message()
+                    .ok()
+                    .and_then(|message| git_field(&message.summary().to_string()));
+                (tag, author, commit_time, subject)
+            })
+            .unwrap_or_default();
+        Some(JsonReportGit {
+            author,
+            branch,
+            commit_time,
+            dirty,
+            subject,
+            tag,
         })
     }
 }
@@ -91,6 +152,33 @@ fn find_repo() -> Option<gix::Repository> {
     None
 }
 
+fn find_tag(
+    repo: &gix::Repository,
+    commit_id: gix::ObjectId,
+) -> Option<bencher_client::types::NonEmpty> {
+    let tags = repo.references().ok()?;
+    for mut tag in tags.tags().ok()?.flatten() {
+        if tag.peel_to_id_in_place().ok().map(gix::Id::detach) == Some(commit_id) {
+            return git_field(&tag.name().shorten().to_string());
+        }
+    }
+    None
+}
+
+/// Truncate the `git` metadata field to the maximum length, skipping it if it is empty.
+fn git_field(field: &str) -> Option<bencher_client::types::NonEmpty> {
+    let mut end = field.len().min(REPORT_GIT_MAX_LEN);
+    while !field.is_char_boundary(end) {
+        end -= 1;
+    }
+    field
+        .get(..end)?
+        .trim()
+        .parse::<NonEmpty>()
+        .ok()
+        .map(Into::into)
+}
+
 #[allow(clippy::needless_pass_by_value)]
 fn map_start_point(
     start_point: Vec<String>,
@@ -118,12 +206,14 @@ impl From<Branch>
         bencher_client::types::NameId,
         Option<bencher_client::types::GitHash>,
         Option<bencher_client::types::JsonUpdateStartPoint>,
+        Option<bencher_client::types::NameId>,
     )
 {
     fn from(branch: Branch) -> Self {
         let name = branch.branch.into();
         let hash = branch.hash.map(Into::into);
         let start_point = branch.start_point.into();
-        (name, hash, start_point)
+        let reference_branch = branch.reference_branch.map(Into::into);
+        (name, hash, start_point, reference_branch)
     }
 }
@@ -1,7 +1,9 @@
-use bencher_comment::ReportComment;
+use bencher_comment::{
+    CommentColumns, CommentLocale, CommentTemplate, ReportComment, TemplateError,
+};
 use octocrab::{models::CommentId, Octocrab};
 
-use crate::cli_println_quietable;
+use crate::{cli_println_quietable, parser::project::run::CliRunCiCompare, ExitClass};
 
 const GITHUB_ACTIONS: &str = "GITHUB_ACTIONS";
 const GITHUB_EVENT_PATH: &str = "GITHUB_EVENT_PATH";
@@ -22,6 +24,11 @@ pub struct GitHubActions {
     pub ci_public_links: bool,
     pub ci_id: Option<String>,
     pub ci_number: Option<u64>,
+    pub ci_template: Option<CommentTemplate>,
+    pub ci_locale: Option<CommentLocale>,
+    pub ci_columns: CommentColumns,
+    pub ci_collapse_measures: bool,
+    pub ci_compare: Option<CliRunCiCompare>,
     pub ci_i_am_vulnerable_to_pwn_requests: bool,
 }
 
@@ -77,6 +84,8 @@ pub enum GitHubError {
     CreateComment(octocrab::Error),
     #[error("Failed to update GitHub PR comment: {0}")]
     UpdateComment(octocrab::Error),
+    #[error("{0}")]
+    Template(TemplateError),
     #[error("GitHub Actions token (`GITHUB_TOKEN`) does not have `write` permissions for `pull-requests`.\n{help}\nError: {0}", help = PERMISSIONS_HELP)]
     BadPermissions(octocrab::Error),
 }
@@ -84,6 +93,33 @@ pub enum GitHubError {
 // https://docs.github.com/en/actions/using-jobs/assigning-permissions-to-jobs#setting-the-github_token-permissions-for-a-specific-job
 const PERMISSIONS_HELP: &str = "To fix, add `write` permissions to the job: `job: {{ \"permissions\": {{ \"pull-requests\": \"write\" }} }}`\nSee: https://bencher.dev/docs/how-to/github-actions/#pull-requests";
 
+impl GitHubError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::NoEventPath | Self::BadEventPath(_, _) => ExitClass::Failure,
+            Self::BadEvent(_, _)
+            | Self::NoPullRequest(_)
+            | Self::NoHead(_)
+            | Self::NoHeadRepo(_)
+            | Self::NoHeadFullName(_)
+            | Self::BadHeadFullName(_)
+            | Self::NoPRNumber(_, _)
+            | Self::BadPRNumber(_, _)
+            | Self::NoRepository(_)
+            | Self::NoFullName(_)
+            | Self::BadFullName(_)
+            | Self::InvalidFullName(_) => ExitClass::Parse,
+            Self::PwnRequest { .. } | Self::NoWorkflowRunPRNumber | Self::Template(_) => {
+                ExitClass::Usage
+            },
+            Self::Auth(_) | Self::BadPermissions(_) => ExitClass::Auth,
+            Self::Comments(_) | Self::CreateComment(_) | Self::UpdateComment(_) => {
+                ExitClass::Network
+            },
+        }
+    }
+}
+
 fn docker_env(env_var: &str) -> String {
     format!(
         "If you are running in a Docker container, then you need to pass in the `{env_var}` environment variable. See https://bencher.dev/docs/explanation/bencher-run/#--github-actions",
@@ -216,7 +252,9 @@ impl GitHubActions {
 
         // Update or create the comment
         let issue_handler = github_client.issues(owner, repo);
-        let body = report_comment.html(self.ci_only_thresholds, self.ci_id.as_deref());
+        let body = report_comment
+            .html(self.ci_only_thresholds, self.ci_id.as_deref())
+            .map_err(GitHubError::Template)?;
         // Always update the comment if it exists
         let comment = if let Some(comment_id) = comment_id {
             issue_handler.update_comment(comment_id, body).await
@@ -1,6 +1,12 @@
-use bencher_comment::ReportComment;
+use bencher_comment::{
+    CommentColumn, CommentLocale, CommentTemplate, ReportComment, TemplateError,
+};
+use camino::Utf8PathBuf;
 
-use crate::parser::project::run::CliRunCi;
+use crate::{
+    parser::project::run::{CliRunCi, CliRunCiColumn, CliRunCiCompare},
+    ExitClass,
+};
 
 mod github_actions;
 
@@ -15,6 +21,44 @@ pub enum Ci {
 pub enum CiError {
     #[error("{0}")]
     GitHub(#[from] GitHubError),
+    #[error("Failed to read CI comment template file ({path}): {err}")]
+    ReadTemplate {
+        path: Utf8PathBuf,
+        err: std::io::Error,
+    },
+    #[error("Invalid CI comment template file ({path}): {err}")]
+    Template {
+        path: Utf8PathBuf,
+        err: TemplateError,
+    },
+    #[error("Failed to read CI comment locale file ({path}): {err}")]
+    ReadLocale {
+        path: Utf8PathBuf,
+        err: std::io::Error,
+    },
+    #[error("Unsupported CI comment locale file extension ({0}). Expected `.json` or `.toml`.")]
+    LocaleExtension(Utf8PathBuf),
+    #[error("Failed to parse JSON CI comment locale file ({path}): {err}")]
+    ParseLocaleJson {
+        path: Utf8PathBuf,
+        err: serde_json::Error,
+    },
+    #[error("Failed to parse TOML CI comment locale file ({path}): {err}")]
+    ParseLocaleToml {
+        path: Utf8PathBuf,
+        err: toml::de::Error,
+    },
+}
+
+impl CiError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::GitHub(err) => err.exit_class(),
+            Self::ReadTemplate { .. } | Self::ReadLocale { .. } => ExitClass::Failure,
+            Self::Template { .. } | Self::LocaleExtension(_) => ExitClass::Usage,
+            Self::ParseLocaleJson { .. } | Self::ParseLocaleToml { .. } => ExitClass::Parse,
+        }
+    }
 }
 
 impl TryFrom<CliRunCi> for Option<Ci> {
@@ -28,20 +72,74 @@ impl TryFrom<CliRunCi> for Option<Ci> {
             ci_public_links,
             ci_id,
             ci_number,
+            ci_template,
+            ci_locale,
+            ci_hide_column,
+            ci_collapse_measures,
+            ci_compare,
             ci_i_am_vulnerable_to_pwn_requests,
             ci_deprecated: _,
         } = ci;
-        Ok(github_actions.map(|token| {
-            Ci::GitHubActions(GitHubActions {
-                token,
-                ci_only_thresholds,
-                ci_only_on_alert,
-                ci_public_links,
-                ci_id,
-                ci_number,
-                ci_i_am_vulnerable_to_pwn_requests,
-            })
-        }))
+        let Some(token) = github_actions else {
+            return Ok(None);
+        };
+        let ci_template = ci_template.as_ref().map(read_template).transpose()?;
+        let ci_locale = ci_locale.as_ref().map(read_locale).transpose()?;
+        Ok(Some(Ci::GitHubActions(GitHubActions {
+            token,
+            ci_only_thresholds,
+            ci_only_on_alert,
+            ci_public_links,
+            ci_id,
+            ci_number,
+            ci_template,
+            ci_locale,
+            ci_columns: ci_hide_column.into_iter().map(Into::into).collect(),
+            ci_collapse_measures,
+            ci_compare,
+            ci_i_am_vulnerable_to_pwn_requests,
+        })))
+    }
+}
+
+impl From<CliRunCiColumn> for CommentColumn {
+    fn from(column: CliRunCiColumn) -> Self {
+        match column {
+            CliRunCiColumn::View => Self::View,
+            CliRunCiColumn::PercentChange => Self::PercentChange,
+            CliRunCiColumn::Units => Self::Units,
+            CliRunCiColumn::LowerBoundary => Self::LowerBoundary,
+            CliRunCiColumn::UpperBoundary => Self::UpperBoundary,
+        }
+    }
+}
+
+fn read_template(path: &Utf8PathBuf) -> Result<CommentTemplate, CiError> {
+    let template_str = std::fs::read_to_string(path).map_err(|err| CiError::ReadTemplate {
+        path: path.clone(),
+        err,
+    })?;
+    CommentTemplate::new(&template_str).map_err(|err| CiError::Template {
+        path: path.clone(),
+        err,
+    })
+}
+
+fn read_locale(path: &Utf8PathBuf) -> Result<CommentLocale, CiError> {
+    let locale_str = std::fs::read_to_string(path).map_err(|err| CiError::ReadLocale {
+        path: path.clone(),
+        err,
+    })?;
+    match path.extension() {
+        Some("json") => serde_json::from_str(&locale_str).map_err(|err| CiError::ParseLocaleJson {
+            path: path.clone(),
+            err,
+        }),
+        Some("toml") => toml::from_str(&locale_str).map_err(|err| CiError::ParseLocaleToml {
+            path: path.clone(),
+            err,
+        }),
+        _ => Err(CiError::LocaleExtension(path.clone())),
     }
 }
 
@@ -63,6 +161,29 @@ impl Ci {
         }
     }
 
+    // Apply any custom template, locale, or layout to the report comment
+    pub fn comment(&self, mut report_comment: ReportComment) -> ReportComment {
+        match self {
+            Self::GitHubActions(github_actions) => {
+                if let Some(locale) = github_actions.ci_locale.clone() {
+                    report_comment = report_comment.with_locale(locale);
+                }
+                if let Some(template) = github_actions.ci_template.clone() {
+                    report_comment = report_comment.with_template(template);
+                }
+                report_comment
+                    .with_columns(github_actions.ci_columns)
+                    .with_collapse_measures(github_actions.ci_collapse_measures)
+            },
+        }
+    }
+
+    pub fn compare(&self) -> Option<CliRunCiCompare> {
+        match self {
+            Self::GitHubActions(github_actions) => github_actions.ci_compare,
+        }
+    }
+
     pub fn source(&self) -> String {
         match self {
             Self::GitHubActions(_) => "github".to_owned(),
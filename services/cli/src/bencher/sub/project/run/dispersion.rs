@@ -0,0 +1,12 @@
+use bencher_client::types::JsonDispersion;
+
+use crate::parser::project::run::CliRunDispersion;
+
+impl From<CliRunDispersion> for JsonDispersion {
+    fn from(dispersion: CliRunDispersion) -> Self {
+        match dispersion {
+            CliRunDispersion::Stddev => Self::Stddev,
+            CliRunDispersion::Iqr => Self::Iqr,
+        }
+    }
+}
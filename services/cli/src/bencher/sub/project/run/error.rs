@@ -1,16 +1,28 @@
+use crate::ExitClass;
+
 use super::runner::{command::Command, Runner};
 
 #[allow(clippy::absolute_paths)]
 #[derive(thiserror::Error, Debug)]
 pub enum RunError {
-    #[error("Failed to check API version: {0}")]
-    ApiVersion(crate::BackendError),
-
     #[error("{0}")]
     Branch(#[from] super::branch::BranchError),
     #[error("{0}")]
     Thresholds(#[from] super::thresholds::ThresholdsError),
 
+    #[error("Report link ({0}) must use the http or https scheme")]
+    NonWebLink(bencher_json::Url),
+
+    #[error("Failed to parse testbed: {0}")]
+    TestbedNameId(bencher_json::ValidError),
+    #[error("Failed to get testbed hardware spec: {0}")]
+    GetTestbed(crate::bencher::BackendError),
+    #[error("The current machine does not match the hardware spec for testbed ({testbed}): {mismatches}")]
+    TestbedMismatch {
+        testbed: bencher_json::Slug,
+        mismatches: String,
+    },
+
     #[error("No default shell command path for target family. Try setting a custom shell with the `--shell` argument.")]
     Shell,
     #[error("No default shell command flag for target family. Try setting a custom shell command flag with the `--flag` argument.")]
@@ -46,29 +58,206 @@ pub enum RunError {
         command: Command,
         err: tokio::task::JoinError,
     },
+    #[error("Failed to spill stdout to a temporary file for command `{command}`: {err}")]
+    CaptureStdout {
+        command: Command,
+        err: std::io::Error,
+    },
+    #[error("Failed to read spilled benchmark harness output: {0}")]
+    ReadSpilledOutput(std::io::Error),
     #[error("Failed to run command due to a non-zero exit code for runner `{runner}`: {output}")]
     ExitStatus {
         runner: Box<Runner>,
         output: crate::bencher::sub::Output,
     },
+    #[error("Benchmark command timed out for runner `{runner}`: {output}\nHint: Use `--allow-timeout` to skip any iterations that time out.")]
+    Timeout {
+        runner: Box<Runner>,
+        output: crate::bencher::sub::Output,
+    },
     #[error("Failed to read from output file: {0}")]
     OutputFileRead(std::io::Error),
+    #[error("Failed to parse output file glob pattern ({pattern}): {err}")]
+    OutputFileGlob {
+        pattern: camino::Utf8PathBuf,
+        err: glob::PatternError,
+    },
+    #[error("Failed to read output file path for glob pattern: {0}")]
+    OutputFileGlobPath(glob::GlobError),
+    #[error("No output files matched the glob pattern: {0}")]
+    OutputFileGlobEmpty(camino::Utf8PathBuf),
+    #[error("Adapter `{}` failed to parse the results from one of the output files", .0.to_string())]
+    OutputFileAdapter(bencher_client::types::Adapter),
+    #[error("Failed to serialize combined output file results: {0}")]
+    SerializeOutputFiles(serde_json::Error),
     #[error("Failed to parse the output file name: {0}")]
     OutputFileName(bencher_json::ValidError),
     #[error("Failed to read size of output file: {0}")]
     OutputFileSize(std::io::Error),
+    #[error("Failed to parse object file ({file_path}): {err}")]
+    ObjectFile {
+        file_path: camino::Utf8PathBuf,
+        err: object::Error,
+    },
     #[error("Failed to serialize file size results: {0}")]
     SerializeFileSize(serde_json::Error),
+    #[error("Hardware counters via `perf stat` are only supported on Linux")]
+    PerfStatUnsupported,
+    #[error("Failed to create a UTF-8 path for the `perf stat` output file: {}", .0.display())]
+    PerfStatPath(std::path::PathBuf),
+    #[error("Failed to read `perf stat` output file: {0}")]
+    PerfStatRead(std::io::Error),
+    #[error("Failed to parse `perf stat` output: {0}")]
+    PerfStatParse(serde_json::Error),
+    #[error("Failed to parse the `perf stat` benchmark name: {0}")]
+    PerfStatBenchmarkName(bencher_json::ValidError),
+    #[error("Failed to serialize `perf stat` results: {0}")]
+    SerializePerfStat(serde_json::Error),
+
+    #[error("None of the adapters ({0}) were able to parse the benchmark harness results")]
+    NoAdapter(String),
 
     #[error("Failed to serialize report JSON: {0}")]
     SerializeReport(serde_json::Error),
+    #[error("Failed to serialize run event JSON: {0}")]
+    SerializeEvent(serde_json::Error),
+    #[error("{0}")]
+    CommentTemplate(bencher_comment::TemplateError),
     #[error("Failed to create new report: {0}")]
     SendReport(crate::bencher::BackendError),
+    #[error("Reports for an unclaimed project must use a project slug, not a UUID ({0}). Set the `--token` flag or the `BENCHER_API_TOKEN` environment variable to use a claimed project.")]
+    UnclaimedProject(bencher_json::ResourceId),
+    #[error("Report shards can not be sent for an unclaimed project. Set the `--token` flag or the `BENCHER_API_TOKEN` environment variable to use a claimed project.")]
+    UnclaimedShard,
     #[error("Failed to get console URL: {0}")]
     ConsoleUrl(crate::bencher::BackendError),
     #[error("Alerts detected ({0})")]
     Alerts(usize),
+    #[error("Benchmark command failed with exit code: {0}")]
+    PassthroughExit(i32),
 
     #[error("{0}")]
     Ci(#[from] super::ci::CiError),
+    #[error("{0}")]
+    Matrix(#[from] super::matrix::MatrixError),
 }
+
+impl RunError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::GetTestbed(err) | Self::SendReport(err) | Self::ConsoleUrl(err) => {
+                err.exit_class()
+            },
+            Self::Branch(_)
+            | Self::Thresholds(_)
+            | Self::TestbedNameId(_)
+            | Self::TestbedMismatch { .. }
+            | Self::Shell
+            | Self::Flag
+            | Self::NoCommand
+            | Self::ShellWithExec(_)
+            | Self::FlagWithExec(_)
+            | Self::OutputFileGlob { .. }
+            | Self::PerfStatUnsupported
+            | Self::CommentTemplate(_)
+            | Self::UnclaimedProject(_)
+            | Self::UnclaimedShard => ExitClass::Usage,
+            Self::SpawnCommand { .. }
+            | Self::PipeStdout(_)
+            | Self::PipeStderr(_)
+            | Self::RunCommand { .. }
+            | Self::StdoutJoinError { .. }
+            | Self::StderrJoinError { .. }
+            | Self::CaptureStdout { .. }
+            | Self::ReadSpilledOutput(_)
+            | Self::ExitStatus { .. }
+            | Self::Timeout { .. }
+            | Self::OutputFileRead(_)
+            | Self::OutputFileGlobPath(_)
+            | Self::OutputFileGlobEmpty(_)
+            | Self::SerializeOutputFiles(_)
+            | Self::OutputFileSize(_)
+            | Self::SerializeFileSize(_)
+            | Self::PerfStatPath(_)
+            | Self::PerfStatRead(_)
+            | Self::SerializePerfStat(_)
+            | Self::SerializeReport(_)
+            | Self::SerializeEvent(_)
+            | Self::PassthroughExit(_) => ExitClass::Failure,
+            Self::OutputFileAdapter(_)
+            | Self::OutputFileName(_)
+            | Self::ObjectFile { .. }
+            | Self::PerfStatParse(_)
+            | Self::PerfStatBenchmarkName(_)
+            | Self::NoAdapter(_) => ExitClass::Parse,
+            Self::Alerts(_) => ExitClass::Alerts,
+            Self::Ci(err) => err.exit_class(),
+            Self::Matrix(err) => err.exit_class(),
+        }
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::GetTestbed(err)
+            | Self::SendReport(err)
+            | Self::ConsoleUrl(err) => err.hint(),
+            Self::OutputFileAdapter(_) | Self::NoAdapter(_) => Some(ADAPTER_HINT),
+            Self::ExitStatus { .. } => Some("Use `--allow-failure` to still send the results from a benchmark command that fails."),
+            Self::TestbedMismatch { .. } => Some("Run the benchmarks on a machine that matches the testbed, set `--testbed` to the testbed for this machine, or update the testbed hardware spec with `bencher testbed update`."),
+            Self::Alerts(_) => Some("Alerts are only failures with `--err` or `--err-on`. Use a higher `--err-on` severity or remove them to only report the alerts."),
+            Self::Branch(_)
+            | Self::Thresholds(_)
+            | Self::TestbedNameId(_)
+            | Self::Shell
+            | Self::Flag
+            | Self::NoCommand
+            | Self::ShellWithExec(_)
+            | Self::FlagWithExec(_)
+            | Self::SpawnCommand { .. }
+            | Self::PipeStdout(_)
+            | Self::PipeStderr(_)
+            | Self::RunCommand { .. }
+            | Self::StdoutJoinError { .. }
+            | Self::StderrJoinError { .. }
+            | Self::CaptureStdout { .. }
+            | Self::ReadSpilledOutput(_)
+            | Self::Timeout { .. }
+            | Self::OutputFileRead(_)
+            | Self::OutputFileGlob { .. }
+            | Self::OutputFileGlobPath(_)
+            | Self::OutputFileGlobEmpty(_)
+            | Self::SerializeOutputFiles(_)
+            | Self::OutputFileName(_)
+            | Self::OutputFileSize(_)
+            | Self::ObjectFile { .. }
+            | Self::SerializeFileSize(_)
+            | Self::PerfStatUnsupported
+            | Self::PerfStatPath(_)
+            | Self::PerfStatRead(_)
+            | Self::PerfStatParse(_)
+            | Self::PerfStatBenchmarkName(_)
+            | Self::SerializePerfStat(_)
+            | Self::SerializeReport(_)
+            | Self::SerializeEvent(_)
+            | Self::CommentTemplate(_)
+            | Self::UnclaimedProject(_)
+            | Self::UnclaimedShard
+            | Self::PassthroughExit(_)
+            | Self::Ci(_)
+            | Self::Matrix(_) => None,
+        }
+    }
+
+    /// The error for a benchmark command iteration that was not successful
+    #[allow(clippy::absolute_paths)]
+    pub fn failed(runner: &Runner, output: crate::bencher::sub::Output) -> Self {
+        let runner = Box::new(runner.clone());
+        if output.is_timed_out() {
+            Self::Timeout { runner, output }
+        } else {
+            Self::ExitStatus { runner, output }
+        }
+    }
+}
+
+pub const ADAPTER_HINT: &str = "Set the `--adapter` flag to the benchmark harness adapter, ie `--adapter rust_criterion`. See https://bencher.dev/docs/explanation/adapters/";
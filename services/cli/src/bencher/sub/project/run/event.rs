@@ -0,0 +1,48 @@
+use bencher_json::{DateTime, JsonAlert, JsonReport, NameId, ResourceId};
+use serde::Serialize;
+
+use crate::cli_println;
+
+use super::RunError;
+
+/// A structured event for the `ndjson` format.
+/// Each event is written to stdout as a single line of JSON as soon as it happens,
+/// so wrapper tooling can follow the progress of a run.
+/// The kind of event is in the `event` field.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum RunEvent<'a> {
+    /// The run has started, before any warm-up or measured iterations
+    RunStarted {
+        project: &'a ResourceId,
+        branch: &'a NameId,
+        testbed: &'a NameId,
+        iterations: usize,
+        warmup: u32,
+        time: DateTime,
+    },
+    /// A measured iteration of the benchmark command has finished
+    IterationFinished {
+        /// The one-based index of the iteration
+        iteration: usize,
+        iterations: usize,
+        success: bool,
+        exit_code: i32,
+        timed_out: bool,
+        /// The wall time of the iteration in nanoseconds
+        wall_time: u128,
+        time: DateTime,
+    },
+    /// The report has been submitted and created
+    ReportSubmitted { report: &'a JsonReport },
+    /// An alert was generated for the report
+    Alert { alert: &'a JsonAlert },
+}
+
+impl RunEvent<'_> {
+    pub fn emit(&self) -> Result<(), RunError> {
+        let event = serde_json::to_string(self).map_err(RunError::SerializeEvent)?;
+        cli_println!("{event}");
+        Ok(())
+    }
+}
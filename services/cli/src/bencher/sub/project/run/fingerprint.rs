@@ -0,0 +1,177 @@
+use std::env::consts::OS;
+
+use bencher_client::types::JsonReportFingerprint;
+use bencher_json::{
+    project::{report::REPORT_FINGERPRINT_MAX_LEN, testbed::JsonTestbedSpec},
+    NonEmpty,
+};
+
+/// Collect the hardware fingerprint of the current machine.
+/// Any part of the fingerprint that can not be found is left unset,
+/// and `None` is returned if nothing can be found.
+pub fn hardware_fingerprint() -> Option<JsonReportFingerprint> {
+    let fingerprint = JsonReportFingerprint {
+        cpu_model: cpu_model().map(Into::into),
+        governor: governor().map(Into::into),
+        virtualized: virtualized(),
+        hypervisor: hypervisor().map(Into::into),
+    };
+    (fingerprint.cpu_model.is_some()
+        || fingerprint.governor.is_some()
+        || fingerprint.virtualized.is_some()
+        || fingerprint.hypervisor.is_some())
+    .then_some(fingerprint)
+}
+
+/// Detect the testbed hardware spec of the current machine.
+/// Any part of the spec that can not be found is left unset.
+pub fn testbed_spec() -> JsonTestbedSpec {
+    JsonTestbedSpec {
+        cpu: cpu_model(),
+        cores: std::thread::available_parallelism()
+            .ok()
+            .and_then(|cores| u32::try_from(cores.get()).ok()),
+        memory: memory().map(Into::into),
+        os: OS.parse().ok(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn cpu_model() -> Option<NonEmpty> {
+    // x86 uses `model name`, while some ARM kernels only have `Hardware` or `Model`
+    ["model name", "Hardware", "Model"]
+        .into_iter()
+        .find_map(cpuinfo_value)
+        .and_then(|model| fingerprint_field(&model))
+}
+
+#[cfg(target_os = "macos")]
+fn cpu_model() -> Option<NonEmpty> {
+    sysctl("machdep.cpu.brand_string").and_then(|model| fingerprint_field(&model))
+}
+
+#[cfg(target_os = "windows")]
+fn cpu_model() -> Option<NonEmpty> {
+    std::env::var("PROCESSOR_IDENTIFIER")
+        .ok()
+        .and_then(|model| fingerprint_field(&model))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn cpu_model() -> Option<NonEmpty> {
+    None
+}
+
+/// The total RAM in bytes
+#[cfg(target_os = "linux")]
+fn memory() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    meminfo.lines().find_map(|line| {
+        let kilobytes = line.strip_prefix("MemTotal:")?.trim().strip_suffix("kB")?;
+        kilobytes.trim().parse::<u64>().ok()?.checked_mul(1024)
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn memory() -> Option<u64> {
+    sysctl("hw.memsize").and_then(|memsize| memsize.parse().ok())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn memory() -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn governor() -> Option<NonEmpty> {
+    read_sys("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn governor() -> Option<NonEmpty> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn virtualized() -> Option<bool> {
+    // The `hypervisor` CPU flag is set by the hypervisor for x86 guests
+    cpuinfo_value("flags").map(|flags| flags.split_whitespace().any(|flag| flag == "hypervisor"))
+}
+
+#[cfg(target_os = "macos")]
+fn virtualized() -> Option<bool> {
+    sysctl("kern.hv_vmm_present").map(|present| present == "1")
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn virtualized() -> Option<bool> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn hypervisor() -> Option<NonEmpty> {
+    // Xen guests expose the hypervisor type directly
+    if let Some(hypervisor) = read_sys("/sys/hypervisor/type") {
+        return Some(hypervisor);
+    }
+    // Otherwise, the DMI system vendor and product name hint at the hypervisor or cloud platform,
+    // such as `QEMU` or `Amazon EC2`, but on bare metal they are just the hardware vendor
+    if virtualized() != Some(true) {
+        return None;
+    }
+    let hint = [
+        "/sys/class/dmi/id/sys_vendor",
+        "/sys/class/dmi/id/product_name",
+    ]
+    .into_iter()
+    .filter_map(|path| std::fs::read_to_string(path).ok())
+    .map(|value| value.trim().to_owned())
+    .filter(|value| !value.is_empty())
+    .collect::<Vec<_>>()
+    .join(" ");
+    fingerprint_field(&hint)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn hypervisor() -> Option<NonEmpty> {
+    None
+}
+
+/// Get the value for the first entry with the key in `/proc/cpuinfo`
+#[cfg(target_os = "linux")]
+fn cpuinfo_value(key: &str) -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        (k.trim() == key).then(|| v.trim().to_owned())
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_sys(path: &str) -> Option<NonEmpty> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|value| fingerprint_field(&value))
+}
+
+#[cfg(target_os = "macos")]
+fn sysctl(name: &str) -> Option<String> {
+    let output = std::process::Command::new("sysctl")
+        .args(["-n", name])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// Truncate the hardware fingerprint field to the maximum length, skipping it if it is empty.
+fn fingerprint_field(field: &str) -> Option<NonEmpty> {
+    let field = field.trim();
+    let mut end = field.len().min(REPORT_FINGERPRINT_MAX_LEN);
+    while !field.is_char_boundary(end) {
+        end -= 1;
+    }
+    field.get(..end)?.trim().parse().ok()
+}
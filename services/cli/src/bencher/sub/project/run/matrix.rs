@@ -0,0 +1,254 @@
+use bencher_json::NameId;
+use camino::Utf8PathBuf;
+use futures_util::future::join_all;
+use serde::Deserialize;
+
+use crate::{
+    bencher::SubCmd,
+    cli_println,
+    parser::project::run::{CliRun, CliRunMatrix},
+    CliError, ExitClass,
+};
+
+use super::{Run, RunError};
+
+/// Run each combination of branch, testbed, and benchmark command in a matrix file,
+/// submitting a separate report for each one.
+#[derive(Debug)]
+pub struct RunMatrix {
+    runs: Vec<MatrixRun>,
+    parallel: bool,
+}
+
+#[derive(Debug)]
+struct MatrixRun {
+    name: String,
+    run: Run,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MatrixError {
+    #[error("No matrix file was given")]
+    NoMatrix,
+    #[error("Failed to read matrix file ({path}): {err}")]
+    Read {
+        path: Utf8PathBuf,
+        err: std::io::Error,
+    },
+    #[error("Unsupported matrix file extension ({0}). Expected `.json`, `.toml`, or `.yaml`.")]
+    Extension(Utf8PathBuf),
+    #[error("Failed to parse JSON matrix file ({path}): {err}")]
+    ParseJson {
+        path: Utf8PathBuf,
+        err: serde_json::Error,
+    },
+    #[error("Failed to parse TOML matrix file ({path}): {err}")]
+    ParseToml {
+        path: Utf8PathBuf,
+        err: toml::de::Error,
+    },
+    #[error("Failed to parse YAML matrix file ({path}): {err}")]
+    ParseYaml {
+        path: Utf8PathBuf,
+        err: serde_yaml::Error,
+    },
+    #[error("The matrix file ({0}) does not have any runs")]
+    Empty(Utf8PathBuf),
+    #[error("Matrix runs failed ({failed}/{total})")]
+    Failed {
+        failed: usize,
+        total: usize,
+        exit_class: ExitClass,
+    },
+}
+
+impl MatrixError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::NoMatrix | Self::Extension(_) | Self::Empty(_) => ExitClass::Usage,
+            Self::Read { .. } => ExitClass::Failure,
+            Self::ParseJson { .. } | Self::ParseToml { .. } | Self::ParseYaml { .. } => {
+                ExitClass::Parse
+            },
+            Self::Failed { exit_class, .. } => *exit_class,
+        }
+    }
+}
+
+/// The matrix file, with a list of runs
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MatrixFile {
+    run: Vec<MatrixEntry>,
+}
+
+/// A single combination in the matrix file.
+/// Any branch or testbed that is not set falls back to the command line arguments.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MatrixEntry {
+    name: Option<String>,
+    branch: Option<NameId>,
+    testbed: Option<NameId>,
+    command: MatrixCommand,
+}
+
+/// A benchmark command as either a shell command string
+/// or a list of the executable and its arguments
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum MatrixCommand {
+    Shell(String),
+    Exec(Vec<String>),
+}
+
+impl TryFrom<CliRun> for RunMatrix {
+    type Error = CliError;
+
+    fn try_from(run: CliRun) -> Result<Self, Self::Error> {
+        let CliRunMatrix {
+            matrix,
+            matrix_parallel,
+        } = run.matrix.clone();
+        let path = matrix.ok_or(RunError::Matrix(MatrixError::NoMatrix))?;
+        let matrix_file = read_matrix(&path).map_err(RunError::Matrix)?;
+        if matrix_file.run.is_empty() {
+            return Err(RunError::Matrix(MatrixError::Empty(path)).into());
+        }
+
+        let mut runs = Vec::with_capacity(matrix_file.run.len());
+        for (index, entry) in matrix_file.run.into_iter().enumerate() {
+            let MatrixEntry {
+                name,
+                branch,
+                testbed,
+                command,
+            } = entry;
+            let name = name.unwrap_or_else(|| format!("run {}", index + 1));
+
+            let mut cli_run = run.clone();
+            if let Some(branch) = branch {
+                cli_run.branch.branch = Some(branch);
+            }
+            if let Some(testbed) = testbed {
+                cli_run.testbed = testbed;
+            }
+            match command {
+                MatrixCommand::Shell(command) => cli_run.cmd.command = Some(vec![command]),
+                MatrixCommand::Exec(command) => {
+                    cli_run.cmd.command = Some(command);
+                    cli_run.cmd.exec = true;
+                },
+            }
+            // Each run needs its own CI comment, so that they do not overwrite one another
+            cli_run.ci.ci_id = Some(
+                cli_run
+                    .ci
+                    .ci_id
+                    .map_or_else(|| name.clone(), |ci_id| format!("{ci_id}/{name}")),
+            );
+            // The benchmark harness output of parallel runs would be interleaved
+            if matrix_parallel {
+                cli_run.output.quiet = true;
+            }
+
+            runs.push(MatrixRun {
+                name,
+                run: cli_run.try_into()?,
+            });
+        }
+
+        Ok(Self {
+            runs,
+            parallel: matrix_parallel,
+        })
+    }
+}
+
+fn read_matrix(path: &Utf8PathBuf) -> Result<MatrixFile, MatrixError> {
+    let matrix_str = std::fs::read_to_string(path).map_err(|err| MatrixError::Read {
+        path: path.clone(),
+        err,
+    })?;
+    match path.extension() {
+        Some("json") => serde_json::from_str(&matrix_str).map_err(|err| MatrixError::ParseJson {
+            path: path.clone(),
+            err,
+        }),
+        Some("toml") => toml::from_str(&matrix_str).map_err(|err| MatrixError::ParseToml {
+            path: path.clone(),
+            err,
+        }),
+        Some("yaml" | "yml") => {
+            serde_yaml::from_str(&matrix_str).map_err(|err| MatrixError::ParseYaml {
+                path: path.clone(),
+                err,
+            })
+        },
+        _ => Err(MatrixError::Extension(path.clone())),
+    }
+}
+
+impl SubCmd for RunMatrix {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl RunMatrix {
+    async fn exec_inner(&self) -> Result<(), RunError> {
+        let results = if self.parallel {
+            join_all(self.runs.iter().map(|run| run.run.exec_inner())).await
+        } else {
+            let mut results = Vec::with_capacity(self.runs.len());
+            for run in &self.runs {
+                cli_println!("\nBencher Matrix Run: {}", run.name);
+                results.push(run.run.exec_inner().await);
+            }
+            results
+        };
+
+        cli_println!("\nBencher Matrix Summary:");
+        let mut failed = 0;
+        let mut exit_code = None;
+        let mut exit_class = None;
+        for (run, result) in self.runs.iter().zip(results) {
+            match result {
+                Ok(()) => cli_println!("  passed: {}", run.name),
+                Err(err) => {
+                    failed += 1;
+                    if let RunError::PassthroughExit(code) = &err {
+                        exit_code.get_or_insert(*code);
+                    }
+                    // If all of the runs failed for the same reason, then use that exit class
+                    let class = err.exit_class();
+                    exit_class = match exit_class {
+                        None => Some(class),
+                        Some(prev) if prev == class => Some(class),
+                        Some(_) => Some(ExitClass::Failure),
+                    };
+                    cli_println!("  failed: {} ({err})", run.name);
+                },
+            }
+        }
+        let total = self.runs.len();
+        cli_println!(
+            "{passed}/{total} matrix run(s) passed",
+            passed = total - failed
+        );
+
+        // The first benchmark command exit code takes precedence over any other failures
+        if let Some(exit_code) = exit_code {
+            Err(RunError::PassthroughExit(exit_code))
+        } else if failed > 0 {
+            Err(MatrixError::Failed {
+                failed,
+                total,
+                exit_class: exit_class.unwrap_or(ExitClass::Failure),
+            }
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -1,31 +1,57 @@
-use std::{future::Future, pin::Pin};
+use std::time::Instant;
 
-use bencher_client::types::{Adapter, JsonAverage, JsonFold, JsonNewReport, JsonReportSettings};
-use bencher_comment::ReportComment;
-use bencher_json::{DateTime, JsonReport, NameId, ResourceId};
+use bencher_adapter::{AdapterResults, Settings as AdapterSettings};
+use bencher_client::{
+    types::{
+        Adapter, JsonAverage, JsonDispersion, JsonFold, JsonNewReport, JsonNewReportShard,
+        JsonNewRun, JsonReportFailure, JsonReportFingerprint, JsonReportGit, JsonReportSettings,
+        JsonReportWarmup,
+    },
+    ReportsQuery, ReportsSort,
+};
+use bencher_comment::{HistoricalComparison, ReportComment};
+use bencher_json::{
+    project::{alert::AlertSeverity, report::REPORT_FAILURE_STDERR_MAX_LEN},
+    DateTime, JsonDirection, JsonReport, JsonUserPrefs, NameId, NonEmpty, ResourceId,
+    ResourceIdKind, Url,
+};
 
 use crate::{
-    bencher::backend::AuthBackend,
-    cli_eprintln_quietable, cli_println, cli_println_quietable,
-    parser::project::run::{CliRun, CliRunOutput},
+    bencher::backend::{CheckedClient, PubBackend},
+    cli_eprintln, cli_eprintln_quietable, cli_println, cli_println_quietable,
+    parser::project::{
+        alert::CliAlertSeverity,
+        run::{CliRun, CliRunCiCompare, CliRunOutput, CliRunShard},
+    },
     CliError,
 };
 
-mod adapter;
+pub mod adapter;
 mod average;
 mod branch;
 mod ci;
+mod dispersion;
 mod error;
+mod event;
+pub mod fingerprint;
 mod fold;
 mod format;
+mod matrix;
+mod progress;
 pub mod runner;
+mod testbed;
 pub mod thresholds;
 
+use adapter::{adapter_parses, combine_results, parse_results};
 use branch::Branch;
 use ci::Ci;
-pub use error::RunError;
+pub use error::{RunError, ADAPTER_HINT};
+use event::RunEvent;
 use format::Format;
-use runner::Runner;
+pub use matrix::RunMatrix;
+use progress::Progress;
+use runner::{capture::Captured, timeout::Timeout, PerfStatCounters, Runner};
+use testbed::TestbedCheck;
 use thresholds::Thresholds;
 
 use crate::bencher::SubCmd;
@@ -36,21 +62,37 @@ pub struct Run {
     project: ResourceId,
     branch: Branch,
     testbed: NameId,
-    adapter: Adapter,
+    testbed_check: Option<TestbedCheck>,
+    adapters: Vec<Adapter>,
     average: Option<JsonAverage>,
     iter: usize,
+    warmup: u32,
     fold: Option<JsonFold>,
+    dispersion: Option<JsonDispersion>,
     backdate: Option<DateTime>,
     allow_failure: bool,
+    allow_timeout: bool,
+    capture_failures: bool,
+    passthrough_exit: bool,
     thresholds: Thresholds,
-    err: bool,
+    err_on: Option<AlertSeverity>,
+    warn_on: Option<AlertSeverity>,
+    shard: Option<(CliRunShard, NonEmpty)>,
+    tag: Option<NonEmpty>,
+    notes: Vec<NonEmpty>,
+    links: Vec<Url>,
+    git: Option<JsonReportGit>,
+    fingerprint: Option<JsonReportFingerprint>,
+    strict: bool,
     format: Format,
     log: bool,
+    user: Option<ResourceId>,
     ci: Option<Ci>,
+    timeout: Option<Timeout>,
     runner: Runner,
     #[allow(clippy::struct_field_names)]
     dry_run: bool,
-    backend: AuthBackend,
+    backend: PubBackend,
 }
 
 impl TryFrom<CliRun> for Run {
@@ -64,35 +106,95 @@ impl TryFrom<CliRun> for Run {
             adapter,
             average,
             iter,
+            warmup,
             fold,
+            dispersion,
             backdate,
             allow_failure,
+            allow_timeout,
+            capture_failures,
+            passthrough_exit,
             thresholds,
             err,
-            output: CliRunOutput { format, quiet },
+            err_on,
+            warn_on,
+            shard,
+            shard_key,
+            tag,
+            note,
+            link,
+            git_metadata,
+            fingerprint,
+            strict,
+            check_testbed,
+            strict_testbed,
+            output:
+                CliRunOutput {
+                    format,
+                    quiet,
+                    user,
+                },
             ci,
+            matrix: _,
             cmd,
             dry_run,
             backend,
         } = run;
+        let branch = Branch::try_from(branch).map_err(RunError::Branch)?;
+        // Capture the `git` metadata before running the benchmarks, which may change the working tree
+        let git = if git_metadata {
+            branch.git_metadata()
+        } else {
+            None
+        };
+        if let Some(link) = link.iter().find(|link| !link.is_web()) {
+            return Err(RunError::NonWebLink(link.clone()).into());
+        }
+        let fingerprint = if fingerprint {
+            fingerprint::hardware_fingerprint()
+        } else {
+            None
+        };
+        let format: Format = format.into();
+        // The NDJSON event stream must be the only output to stdout
+        let log = !quiet && !matches!(format, Format::Ndjson);
         Ok(Self {
             project,
-            branch: branch.try_into().map_err(RunError::Branch)?,
+            branch,
             testbed,
-            adapter: adapter.into(),
+            testbed_check: TestbedCheck::new(check_testbed, strict_testbed),
+            adapters: adapter.into_iter().map(Into::into).collect(),
             average: average.map(Into::into),
             iter,
+            warmup,
             fold: fold.map(Into::into),
+            dispersion: dispersion.map(Into::into),
             backdate,
             allow_failure,
+            allow_timeout,
+            capture_failures,
+            passthrough_exit,
             thresholds: thresholds.try_into().map_err(RunError::Thresholds)?,
-            err,
-            format: format.into(),
-            log: !quiet,
+            // `--err` is the same as erroring on alerts of any severity
+            err_on: err_on
+                .map(Into::into)
+                .or_else(|| err.then_some(AlertSeverity::Minor)),
+            warn_on: warn_on.map(Into::into),
+            shard: shard.zip(shard_key),
+            tag,
+            notes: note,
+            links: link,
+            git,
+            fingerprint,
+            strict,
+            format,
+            log,
+            user,
             ci: ci.try_into().map_err(RunError::Ci)?,
+            timeout: Timeout::new(cmd.timeout, cmd.kill_after),
             runner: cmd.try_into()?,
             dry_run,
-            backend: AuthBackend::try_from(backend)?.log(false),
+            backend: PubBackend::try_from(backend)?.log(false),
         })
     }
 }
@@ -105,23 +207,42 @@ impl SubCmd for Run {
 
 impl Run {
     async fn exec_inner(&self) -> Result<(), RunError> {
-        if let Some(mismatch) = self
-            .backend
-            .check_version()
-            .await
-            .map_err(RunError::ApiVersion)?
-        {
-            cli_eprintln_quietable!(self.log, "Warning: {mismatch}");
-        }
-
         if let Some(ci) = &self.ci {
             ci.safety_check(self.log)?;
         }
 
-        let Some(json_new_report) = self.generate_report().await? else {
+        // Check the testbed before running the benchmarks, so a mislabeled runner fails fast
+        if let Some(testbed_check) = self.testbed_check {
+            testbed_check
+                .check(&self.project, &self.testbed, &self.backend, self.log)
+                .await?;
+        }
+
+        self.emit(&RunEvent::RunStarted {
+            project: &self.project,
+            branch: self.branch.branch(),
+            testbed: &self.testbed,
+            iterations: self.iter,
+            warmup: self.warmup,
+            time: DateTime::now(),
+        })?;
+
+        let mut exit_code = None;
+        let Some(json_new_report) = self.generate_report(&mut exit_code).await? else {
             return Ok(());
         };
 
+        let result = self.send_report(json_new_report).await;
+        // The benchmark command exit code takes precedence over any alerts
+        match (result, exit_code) {
+            (Ok(()) | Err(RunError::Alerts(_)), Some(exit_code)) => {
+                Err(RunError::PassthroughExit(exit_code))
+            },
+            (result, _) => result,
+        }
+    }
+
+    async fn send_report(&self, json_new_report: JsonNewReport) -> Result<(), RunError> {
         cli_println_quietable!(self.log, "\nBencher New Report:");
         cli_println_quietable!(
             self.log,
@@ -134,44 +255,176 @@ impl Run {
             return Ok(());
         }
 
-        let sender = report_sender(self.project.clone(), json_new_report);
-        let json_report: JsonReport = self
+        let client = self
             .backend
-            .send_with(sender)
+            .checked_client()
             .await
             .map_err(RunError::SendReport)?;
+        let json_report = if client.token.is_none() {
+            self.send_unclaimed(&client, json_new_report).await?
+        } else if let Some((CliRunShard { index, count }, key)) = &self.shard {
+            let json_shard = client
+                .project(self.project.clone())
+                .reports()
+                .shard(JsonNewReportShard {
+                    key: key.clone().into(),
+                    index: *index,
+                    count: *count,
+                    report: json_new_report,
+                })
+                .await
+                .map_err(|err| RunError::SendReport(client.error(err)))?;
+            // The report is only returned once all of the shards have been received
+            let Some(json_report) = json_shard.report else {
+                cli_println_quietable!(
+                    self.log,
+                    "\nSubmitted shard {index}/{count} ({received}/{count} received)",
+                    received = json_shard.received
+                );
+                return Ok(());
+            };
+            json_report
+        } else {
+            client
+                .project(self.project.clone())
+                .reports()
+                .create(json_new_report)
+                .await
+                .map_err(|err| RunError::SendReport(client.error(err)))?
+        };
+
+        let severities = json_report
+            .alerts
+            .iter()
+            .map(|alert| alert.severity)
+            .collect::<Vec<_>>();
+        self.display_results(&client, json_report).await?;
+
+        self.check_alerts(&severities)
+    }
+
+    /// Without an API token, the report is sent for an unclaimed project.
+    /// If the unclaimed project is created, then its claim token is only shown this once.
+    async fn send_unclaimed(
+        &self,
+        client: &CheckedClient<'_>,
+        json_new_report: JsonNewReport,
+    ) -> Result<JsonReport, RunError> {
+        if self.shard.is_some() {
+            return Err(RunError::UnclaimedShard);
+        }
+        let project = match ResourceIdKind::try_from(&self.project) {
+            Ok(ResourceIdKind::Slug(slug)) => slug,
+            Ok(ResourceIdKind::Uuid(_)) | Err(_) => {
+                return Err(RunError::UnclaimedProject(self.project.clone()))
+            },
+        };
+        let json_run = client
+            .run(JsonNewRun {
+                project: project.clone().into(),
+                report: json_new_report,
+            })
+            .await
+            .map_err(|err| RunError::SendReport(client.error(err)))?;
+        if let Some(claim) = json_run.claim {
+            let message = format!(
+                "\nCreated unclaimed project ({project}). The claim token will not be shown again, and it expires in 30 days.\nTo claim the project for your organization, run:\nbencher organization claim <ORGANIZATION> --claim-token {claim}"
+            );
+            // Keep stdout for the NDJSON event stream
+            if matches!(self.format, Format::Ndjson) {
+                cli_eprintln!("{message}");
+            } else {
+                cli_println!("{message}");
+            }
+        }
+        Ok(json_run.report)
+    }
 
-        let alerts_count = json_report.alerts.len();
-        self.display_results(json_report).await?;
+    /// Error on alerts at or above the `--err-on` severity,
+    /// and warn on any remaining alerts at or above the `--warn-on` severity.
+    fn check_alerts(&self, severities: &[AlertSeverity]) -> Result<(), RunError> {
+        let is_err = |severity: AlertSeverity| self.err_on.is_some_and(|err_on| severity >= err_on);
+        let err_count = severities
+            .iter()
+            .filter(|severity| is_err(**severity))
+            .count();
+        if let Some(warn_on) = self.warn_on {
+            let warn_count = severities
+                .iter()
+                .filter(|severity| **severity >= warn_on && !is_err(**severity))
+                .count();
+            if warn_count > 0 {
+                cli_eprintln!(
+                    "Warning: Alerts detected ({warn_count}) at or above {warn_on} severity"
+                );
+            }
+        }
 
-        if self.err && alerts_count > 0 {
-            Err(RunError::Alerts(alerts_count))
+        if err_count > 0 {
+            Err(RunError::Alerts(err_count))
         } else {
             Ok(())
         }
     }
 
-    async fn generate_report(&self) -> Result<Option<JsonNewReport>, RunError> {
+    /// Generate the report from the benchmark command results.
+    /// If `--passthrough-exit` is set, the first non-zero exit code of the benchmark command is kept.
+    async fn generate_report(
+        &self,
+        exit_code: &mut Option<i32>,
+    ) -> Result<Option<JsonNewReport>, RunError> {
+        let warmup = self.run_warmup(exit_code).await?;
+
         let start_time = DateTime::now();
-        let mut results = Vec::with_capacity(self.iter);
-        for _ in 0..self.iter {
-            let output = self.runner.run(self.log).await?;
+        let mut iterations = Vec::with_capacity(self.iter);
+        let mut perf_stats = Vec::with_capacity(self.iter);
+        let mut failures = Vec::new();
+        let mut progress = Progress::new(self.log, self.iter);
+        for iteration in 0..self.iter {
+            let start = Instant::now();
+            let mut output = progress
+                .track(self.runner.run(self.log, self.timeout))
+                .await?;
+            self.emit(&RunEvent::IterationFinished {
+                iteration: iteration + 1,
+                iterations: self.iter,
+                success: output.is_success(),
+                exit_code: output.status.code(),
+                timed_out: output.is_timed_out(),
+                wall_time: start.elapsed().as_nanos(),
+                time: DateTime::now(),
+            })?;
             if output.is_success() {
-                results.push(output.result());
-            } else if self.allow_failure {
+                perf_stats.push(output.perf_stat.take());
+                iterations.push(output.results());
+            } else if self.passthrough_exit {
+                cli_eprintln_quietable!(self.log, "Benchmark command failed:\n{}", output);
+                exit_code.get_or_insert(output.status.code());
+                perf_stats.push(output.perf_stat.take());
+                iterations.push(output.results());
+            } else if self.allow_failure || (self.allow_timeout && output.is_timed_out()) {
                 cli_eprintln_quietable!(self.log, "Skipping failure:\n{}", output);
+                if self.capture_failures {
+                    #[allow(clippy::cast_precision_loss)]
+                    failures.push(JsonReportFailure {
+                        iteration: u32::try_from(iteration).unwrap_or(u32::MAX),
+                        exit_code: output.status.code(),
+                        wall_time: start.elapsed().as_nanos() as f64,
+                        stderr: output.stderr_tail(REPORT_FAILURE_STDERR_MAX_LEN),
+                    });
+                }
             } else {
-                return Err(RunError::ExitStatus {
-                    runner: Box::new(self.runner.clone()),
-                    output,
-                });
+                return Err(RunError::failed(&self.runner, output));
             }
         }
 
+        progress.finish();
+
         cli_println_quietable!(self.log, "\nBenchmark Harness Results:");
-        for result in &results {
+        for result in iterations.iter().flatten() {
             cli_println_quietable!(self.log, "{result}");
         }
+        let (results, adapter) = self.resolve_results(iterations, perf_stats)?;
 
         let end_time = DateTime::now();
         // If a backdate is set then use it as the start time and calculate the end time from there
@@ -182,45 +435,403 @@ impl Run {
             (start_time, end_time)
         };
 
-        let (branch, hash, start_point) = self.branch.clone().into();
+        let (branch, hash, start_point, reference_branch) = self.branch.clone().into();
         Ok(Some(JsonNewReport {
             branch,
             hash,
             start_point,
+            reference_branch,
             testbed: self.testbed.clone().into(),
             thresholds: self.thresholds.clone().into(),
             start_time: start_time.into(),
             end_time: end_time.into(),
+            tag: self.tag.clone().map(Into::into),
             results,
             settings: Some(JsonReportSettings {
-                adapter: Some(self.adapter),
+                adapter: Some(adapter),
                 average: self.average,
                 fold: self.fold,
+                dispersion: self.dispersion,
             }),
+            strict: self.strict.then_some(true),
+            warmup,
+            notes: (!self.notes.is_empty())
+                .then(|| self.notes.iter().cloned().map(Into::into).collect()),
+            links: (!self.links.is_empty())
+                .then(|| self.links.iter().cloned().map(Into::into).collect()),
+            failures: (!failures.is_empty()).then_some(failures),
+            git: self.git.clone(),
+            fingerprint: self.fingerprint.clone(),
+        }))
+    }
+
+    /// Resolve the adapter for the results of each iteration.
+    /// If the results from multiple output files need to be combined,
+    /// then the results from each file are parsed by the adapter and combined into a single BMF JSON result.
+    /// Likewise, any output that was too large and spilled to a temporary file is parsed locally,
+    /// so only the much smaller BMF JSON result has to be held in memory and sent.
+    /// Any `perf stat` hardware counters are also added to the results locally.
+    /// The combined results are then sent to the API server with the JSON adapter.
+    fn resolve_results(
+        &self,
+        iterations: Vec<Vec<Captured>>,
+        perf_stats: Vec<Option<PerfStatCounters>>,
+    ) -> Result<(Vec<String>, Adapter), RunError> {
+        let is_spilled = iterations.iter().flatten().any(Captured::is_spilled);
+        if perf_stats.iter().any(Option::is_some) {
+            return self.resolve_perf_stat_results(iterations, perf_stats);
+        }
+        if !self.runner.is_combined() && !is_spilled {
+            let results = iterations.into_iter().flatten().collect::<Vec<_>>();
+            let adapter = self.resolve_adapter(&results)?;
+            self.log_magic_detection(adapter, &results);
+            let results = results
+                .into_iter()
+                .filter_map(Captured::into_memory)
+                .collect();
+            return Ok((results, adapter));
+        }
+
+        let file_results = iterations.iter().flatten().cloned().collect::<Vec<_>>();
+        let adapter = self.resolve_adapter(&file_results)?;
+        self.log_magic_detection(adapter, &file_results);
+        let results = iterations
+            .iter()
+            .map(|file_results| combine_results(adapter, self.average, file_results))
+            .collect::<Result<Vec<_>, _>>()?;
+        if is_spilled {
+            cli_println_quietable!(
+                self.log,
+                "\nParsed the spilled benchmark harness results locally with adapter `{adapter}`",
+                adapter = adapter.to_string()
+            );
+        } else {
+            cli_println_quietable!(
+                self.log,
+                "\nCombined the results from {count} output file(s) with adapter `{adapter}`",
+                count = file_results.len(),
+                adapter = adapter.to_string()
+            );
+        }
+        Ok((results, Adapter::Json))
+    }
+
+    /// Parse the benchmark harness results for each iteration locally,
+    /// and add the `perf stat` hardware counters for that iteration as measures.
+    /// If the benchmark command does not output any results,
+    /// then only the hardware counters are used.
+    fn resolve_perf_stat_results(
+        &self,
+        iterations: Vec<Vec<Captured>>,
+        perf_stats: Vec<Option<PerfStatCounters>>,
+    ) -> Result<(Vec<String>, Adapter), RunError> {
+        let iterations = iterations
+            .into_iter()
+            .map(|results| {
+                results
+                    .into_iter()
+                    .filter(|result| result.is_spilled() || !result.to_string().trim().is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let harness_results = iterations.iter().flatten().cloned().collect::<Vec<_>>();
+        let adapter = if harness_results.is_empty() {
+            None
+        } else {
+            let adapter = self.resolve_adapter(&harness_results)?;
+            self.log_magic_detection(adapter, &harness_results);
+            Some(adapter)
+        };
+
+        let results = iterations
+            .iter()
+            .zip(perf_stats)
+            .map(|(file_results, perf_stat)| {
+                let mut adapter_results = if let Some(adapter) = adapter {
+                    parse_results(adapter, self.average, file_results)?
+                } else {
+                    AdapterResults::default()
+                };
+                if let Some(perf_stat) = perf_stat {
+                    perf_stat.add_to(&mut adapter_results);
+                }
+                serde_json::to_string(&adapter_results).map_err(RunError::SerializePerfStat)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        cli_println_quietable!(
+            self.log,
+            "\nAdded the `perf stat` hardware counters to the benchmark harness results"
+        );
+        Ok((results, Adapter::Json))
+    }
+
+    /// If multiple adapters are given, then use the first one that is able to parse the results.
+    fn resolve_adapter(&self, results: &[Captured]) -> Result<Adapter, RunError> {
+        match self.adapters.as_slice() {
+            [] => Ok(Adapter::Magic),
+            [adapter] => Ok(*adapter),
+            // Without any results, there is nothing to parse
+            [adapter, ..] if results.is_empty() => Ok(*adapter),
+            adapters => {
+                for adapter in adapters {
+                    if adapter_parses(*adapter, results)? {
+                        cli_println_quietable!(
+                            self.log,
+                            "\nAdapter `{adapter}` parsed the benchmark harness results",
+                            adapter = adapter.to_string()
+                        );
+                        return Ok(*adapter);
+                    }
+                    cli_println_quietable!(
+                        self.log,
+                        "\nAdapter `{adapter}` failed to parse the benchmark harness results",
+                        adapter = adapter.to_string()
+                    );
+                }
+                Err(RunError::NoAdapter(
+                    adapters
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ))
+            },
+        }
+    }
+
+    /// If the magic adapter is used, then log which adapter matched the results
+    /// and why the alternatives were rejected.
+    /// The server resolves the adapter from the first results, so only those are checked.
+    fn log_magic_detection(&self, adapter: Adapter, results: &[Captured]) {
+        if adapter != Adapter::Magic {
+            return;
+        }
+        let Some(result) = results.first() else {
+            return;
+        };
+        // The average only changes the parsed values, not which adapter matches
+        let detection = match result.detect(AdapterSettings::default()) {
+            Ok(detection) => detection,
+            Err(err) => {
+                cli_eprintln_quietable!(
+                    self.log,
+                    "Warning: Failed to detect the magic adapter: {err}"
+                );
+                return;
+            },
+        };
+        cli_println_quietable!(self.log, "\n{detection}");
+        if let Some(adapter) = detection.adapter {
+            let ambiguous = detection
+                .ambiguous()
+                .map(|adapter| adapter.to_string())
+                .collect::<Vec<_>>();
+            if !ambiguous.is_empty() {
+                cli_eprintln_quietable!(
+                    self.log,
+                    "Warning: The benchmark harness results also matched the {ambiguous} adapter(s). Set `--adapter {adapter}` to avoid misdetection.",
+                    ambiguous = ambiguous.join(", ")
+                );
+            }
+        }
+    }
+
+    /// Emit the event, if the format is the NDJSON event stream
+    fn emit(&self, event: &RunEvent<'_>) -> Result<(), RunError> {
+        if matches!(self.format, Format::Ndjson) {
+            event.emit()
+        } else {
+            Ok(())
+        }
+    }
+
+    // Warm-up iterations are run before the report start time and their results are discarded
+    async fn run_warmup(
+        &self,
+        exit_code: &mut Option<i32>,
+    ) -> Result<Option<JsonReportWarmup>, RunError> {
+        if self.warmup == 0 {
+            return Ok(None);
+        }
+
+        cli_println_quietable!(self.log, "Running {} warm-up iteration(s)", self.warmup);
+        let start = Instant::now();
+        for _ in 0..self.warmup {
+            let output = self.runner.run(self.log, self.timeout).await?;
+            if output.is_success() {
+                // Warm-up results are discarded
+            } else if self.passthrough_exit {
+                cli_eprintln_quietable!(self.log, "Warm-up benchmark command failed:\n{}", output);
+                exit_code.get_or_insert(output.status.code());
+            } else if self.allow_failure || (self.allow_timeout && output.is_timed_out()) {
+                cli_eprintln_quietable!(self.log, "Skipping warm-up failure:\n{}", output);
+            } else {
+                return Err(RunError::failed(&self.runner, output));
+            }
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let wall_time = start.elapsed().as_nanos() as f64;
+        cli_println_quietable!(
+            self.log,
+            "Warm-up complete in {:.3} seconds",
+            wall_time / 1_000_000_000.0
+        );
+
+        Ok(Some(JsonReportWarmup {
+            iterations: self.warmup,
+            wall_time,
         }))
     }
 
-    async fn display_results(&self, json_report: JsonReport) -> Result<(), RunError> {
+    // Find the prior report to compare against in the CI comment, if one was requested.
+    // Failing to find it only skips the comparison, as it should never fail the run.
+    async fn historical_comparison(
+        &self,
+        client: &CheckedClient<'_>,
+        json_report: &JsonReport,
+        prefs: Option<&JsonUserPrefs>,
+    ) -> Option<HistoricalComparison> {
+        let compare = self.ci.as_ref().and_then(Ci::compare)?;
+        let per_page = match compare {
+            CliRunCiCompare::Tag => u8::MAX,
+            CliRunCiCompare::ReportsAgo(reports_ago) => reports_ago,
+        };
+        let reports_query = match ReportsQuery::builder()
+            .branch(json_report.branch.slug.clone().into())
+            .testbed(json_report.testbed.slug.clone().into())
+            .end_time(json_report.start_time)
+            .sort(ReportsSort::DateTime)
+            .direction(JsonDirection::Desc)
+            .per_page(per_page)
+            .build()
+        {
+            Ok(reports_query) => reports_query,
+            Err(err) => {
+                cli_eprintln_quietable!(self.log, "Warning: Invalid prior reports query: {err}");
+                return None;
+            },
+        };
+        let json_reports = match client
+            .project(self.project.clone())
+            .reports()
+            .query(&reports_query)
+            .await
+            .map_err(|err| client.error(err))
+        {
+            Ok(json_reports) => json_reports,
+            Err(err) => {
+                cli_eprintln_quietable!(self.log, "Warning: Failed to get prior reports: {err}");
+                return None;
+            },
+        };
+        // The reports are sorted from most to least recent
+        let prior = match compare {
+            CliRunCiCompare::Tag => json_reports.0.into_iter().find_map(|prior| {
+                let tag = prior
+                    .tag
+                    .as_ref()
+                    .or_else(|| prior.git.as_ref()?.tag.as_ref())?
+                    .to_string();
+                Some((tag, prior))
+            }),
+            CliRunCiCompare::ReportsAgo(reports_ago) => json_reports
+                .0
+                .into_iter()
+                .nth(usize::from(reports_ago) - 1)
+                .map(|prior| (format!("{reports_ago} report(s) ago"), prior)),
+        };
+        let Some((label, mut prior)) = prior else {
+            cli_eprintln_quietable!(
+                self.log,
+                "Warning: No prior report found to compare against"
+            );
+            return None;
+        };
+        // The prior results must be in the same units as the current results
+        if let Some(prefs) = prefs {
+            prefs.convert_report(&mut prior);
+        }
+        Some(HistoricalComparison::new(label, &prior))
+    }
+
+    // Get the display preferences for the user, if one was given.
+    // Failing to get them only skips the preferences, as it should never fail the run.
+    async fn user_prefs(&self) -> Option<JsonUserPrefs> {
+        let user = self.user.as_ref()?;
+        match self
+            .backend
+            .send_with(
+                |client| async move { client.user_prefs_get().user(user.clone()).send().await },
+            )
+            .await
+        {
+            Ok(json_prefs) => Some(json_prefs),
+            Err(err) => {
+                cli_eprintln_quietable!(
+                    self.log,
+                    "Warning: Failed to get user ({user}) preferences: {err}"
+                );
+                None
+            },
+        }
+    }
+
+    async fn display_results(
+        &self,
+        client: &CheckedClient<'_>,
+        json_report: JsonReport,
+    ) -> Result<(), RunError> {
+        if matches!(self.format, Format::Ndjson) {
+            RunEvent::ReportSubmitted {
+                report: &json_report,
+            }
+            .emit()?;
+            for alert in &json_report.alerts {
+                RunEvent::Alert { alert }.emit()?;
+            }
+        }
+
         let console_url = self
             .backend
             .get_console_url()
             .await
             .map_err(RunError::ConsoleUrl)?;
-        let report_comment = ReportComment::new(
+        let prefs = self.user_prefs().await;
+        let historical = self
+            .historical_comparison(client, &json_report, prefs.as_ref())
+            .await;
+        let mut report_comment = ReportComment::new(
             console_url,
             json_report,
             self.ci
                 .as_ref()
                 .map_or_else(|| "cli".to_owned(), Ci::source),
         );
+        if let Some(prefs) = &prefs {
+            report_comment = report_comment.with_prefs(prefs);
+        }
+        if let Some(ci) = &self.ci {
+            report_comment = ci.comment(report_comment);
+        }
+        if let Some(historical) = historical {
+            report_comment = report_comment.with_historical(historical);
+        }
 
         let report_str = match self.format {
-            Format::Human => report_comment.human(),
-            Format::Json => report_comment.json().map_err(RunError::SerializeReport)?,
-            Format::Html => report_comment.html(false, None),
+            Format::Human => Some(report_comment.human()),
+            Format::Json => Some(report_comment.json().map_err(RunError::SerializeReport)?),
+            Format::Html => Some(
+                report_comment
+                    .html(false, None)
+                    .map_err(RunError::CommentTemplate)?,
+            ),
+            // The report has already been emitted as an event
+            Format::Ndjson => None,
         };
-        let newline_prefix = if self.log { "\n" } else { "" };
-        cli_println!("{newline_prefix}{report_str}");
+        if let Some(report_str) = report_str {
+            let newline_prefix = if self.log { "\n" } else { "" };
+            cli_println!("{newline_prefix}{report_str}");
+        }
 
         if let Some(ci) = &self.ci {
             ci.run(&report_comment, self.log).await?;
@@ -230,30 +841,12 @@ impl Run {
     }
 }
 
-type ReportResult = Pin<
-    Box<
-        dyn Future<
-                Output = Result<
-                    progenitor_client::ResponseValue<bencher_client::types::JsonReport>,
-                    bencher_client::Error<bencher_client::types::Error>,
-                >,
-            > + Send,
-    >,
->;
-fn report_sender(
-    project: ResourceId,
-    json_new_report: JsonNewReport,
-) -> Box<dyn Fn(bencher_client::Client) -> ReportResult + Send> {
-    Box::new(move |client: bencher_client::Client| {
-        let project = project.clone();
-        let json_new_report = json_new_report.clone();
-        Box::pin(async move {
-            client
-                .proj_report_post()
-                .project(project.clone())
-                .body(json_new_report.clone())
-                .send()
-                .await
-        })
-    })
+impl From<CliAlertSeverity> for AlertSeverity {
+    fn from(severity: CliAlertSeverity) -> Self {
+        match severity {
+            CliAlertSeverity::Minor => Self::Minor,
+            CliAlertSeverity::Major => Self::Major,
+            CliAlertSeverity::Critical => Self::Critical,
+        }
+    }
 }
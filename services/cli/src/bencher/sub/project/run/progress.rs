@@ -0,0 +1,167 @@
+use std::{
+    future::Future,
+    io::{IsTerminal, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::cli_println;
+
+// How often the interactive status line is redrawn while an iteration is running
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+// ANSI escape sequence to clear the current terminal line
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+// Whether an interactive status line is currently drawn on the terminal
+static STATUS_LINE: AtomicBool = AtomicBool::new(false);
+
+/// Clear the interactive status line, if one is drawn,
+/// so that a line of benchmark command output is not printed on top of it.
+pub fn clear_status_line() {
+    if STATUS_LINE.swap(false, Ordering::SeqCst) {
+        let mut stdout = std::io::stdout();
+        let _w = write!(stdout, "{CLEAR_LINE}");
+        let _f = stdout.flush();
+    }
+}
+
+/// Progress reporting for multi-iteration runs.
+/// When stdout is a TTY, a status line is redrawn in place while each iteration is running.
+/// Otherwise, such as in CI, a plain log line is printed before each iteration.
+#[derive(Debug)]
+pub struct Progress {
+    mode: Mode,
+    iterations: usize,
+    start: Instant,
+    iteration_start: Instant,
+    completed: usize,
+    completed_time: Duration,
+    last: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Off,
+    Plain,
+    Interactive,
+}
+
+impl Progress {
+    pub fn new(log: bool, iterations: usize) -> Self {
+        // There is nothing to report for a single iteration
+        let mode = if !log || iterations <= 1 {
+            Mode::Off
+        } else if std::io::stdout().is_terminal() {
+            Mode::Interactive
+        } else {
+            Mode::Plain
+        };
+        let now = Instant::now();
+        Self {
+            mode,
+            iterations,
+            start: now,
+            iteration_start: now,
+            completed: 0,
+            completed_time: Duration::ZERO,
+            last: None,
+        }
+    }
+
+    /// Track a single iteration while it is running.
+    pub async fn track<F>(&mut self, iteration: F) -> F::Output
+    where
+        F: Future,
+    {
+        self.iteration_start = Instant::now();
+        let output = match self.mode {
+            Mode::Off => iteration.await,
+            Mode::Plain => {
+                cli_println!("{}", self.status());
+                iteration.await
+            },
+            Mode::Interactive => {
+                tokio::pin!(iteration);
+                let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+                loop {
+                    tokio::select! {
+                        output = &mut iteration => break output,
+                        _ = interval.tick() => self.draw_status_line(),
+                    }
+                }
+            },
+        };
+        clear_status_line();
+        let last = self.iteration_start.elapsed();
+        self.completed += 1;
+        self.completed_time += last;
+        self.last = Some(last);
+        output
+    }
+
+    /// Report that all of the iterations have finished.
+    pub fn finish(&self) {
+        if self.mode == Mode::Off {
+            return;
+        }
+        clear_status_line();
+        cli_println!(
+            "Completed {completed}/{iterations} iterations in {elapsed}",
+            completed = self.completed,
+            iterations = self.iterations,
+            elapsed = format_duration(self.start.elapsed()),
+        );
+    }
+
+    fn draw_status_line(&self) {
+        let mut stdout = std::io::stdout();
+        let _w = write!(stdout, "{CLEAR_LINE}{}", self.status());
+        let _f = stdout.flush();
+        STATUS_LINE.store(true, Ordering::SeqCst);
+    }
+
+    fn status(&self) -> String {
+        let mut status = format!(
+            "Iteration {current}/{iterations} | elapsed {elapsed}",
+            current = self.completed + 1,
+            iterations = self.iterations,
+            elapsed = format_duration(self.start.elapsed()),
+        );
+        if let Some(eta) = self.eta() {
+            status.push_str(&format!(" | ETA {}", format_duration(eta)));
+        }
+        if let Some(last) = self.last {
+            status.push_str(&format!(" | last iteration {}", format_duration(last)));
+        }
+        status
+    }
+
+    // The estimated time remaining, based on the average duration of the completed iterations
+    fn eta(&self) -> Option<Duration> {
+        let completed = u32::try_from(self.completed).ok().filter(|c| *c > 0)?;
+        let remaining = u32::try_from(self.iterations.saturating_sub(self.completed)).ok()?;
+        let average = self.completed_time.checked_div(completed)?;
+        Some(
+            average
+                .checked_mul(remaining)?
+                .saturating_sub(self.iteration_start.elapsed()),
+        )
+    }
+}
+
+#[allow(clippy::integer_division)]
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 * 60 {
+        format!(
+            "{}h {:02}m {:02}s",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        )
+    } else if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
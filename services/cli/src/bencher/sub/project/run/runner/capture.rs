@@ -0,0 +1,180 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, IntoInnerError, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use bencher_adapter::{
+    adapters::magic::{AdapterMagic, MagicDetection},
+    Adaptable, AdapterResults, Settings as AdapterSettings,
+};
+use bencher_json::project::report::Adapter;
+
+/// Output past this many bytes is spilled to a temporary file,
+/// so very large benchmark harness output is never held in memory all at once.
+pub const SPILL_THRESHOLD: usize = 64 * 1024 * 1024;
+
+// Each spill file for this process has its own number
+static SPILL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Benchmark harness output, either held in memory or spilled to a temporary file
+#[derive(Debug, Clone)]
+pub enum Captured {
+    Memory(String),
+    Spilled(Arc<SpillFile>),
+}
+
+impl Default for Captured {
+    fn default() -> Self {
+        Self::Memory(String::new())
+    }
+}
+
+impl fmt::Display for Captured {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Memory(output) => write!(f, "{output}"),
+            Self::Spilled(spill_file) => write!(
+                f,
+                "({len} bytes of output spilled to {path})",
+                len = spill_file.len,
+                path = spill_file.path.display()
+            ),
+        }
+    }
+}
+
+impl Captured {
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, Self::Spilled(_))
+    }
+
+    /// The output, if it is held in memory
+    pub fn into_memory(self) -> Option<String> {
+        match self {
+            Self::Memory(output) => Some(output),
+            Self::Spilled(_) => None,
+        }
+    }
+
+    /// Parse the output with the adapter.
+    /// Spilled output is parsed from the file, without reading it all into memory.
+    pub fn convert(
+        &self,
+        adapter: Adapter,
+        settings: AdapterSettings,
+    ) -> io::Result<Option<AdapterResults>> {
+        match self {
+            Self::Memory(output) => Ok(adapter.convert(output, settings)),
+            Self::Spilled(spill_file) => {
+                adapter.convert_reader(&mut spill_file.reader()?, settings)
+            },
+        }
+    }
+
+    /// Try every specific adapter that the magic adapter would use against the output
+    pub fn detect(&self, settings: AdapterSettings) -> io::Result<MagicDetection> {
+        match self {
+            Self::Memory(output) => Ok(AdapterMagic::detect(output, settings)),
+            Self::Spilled(spill_file) => {
+                AdapterMagic::detect_reader(&mut spill_file.reader()?, settings)
+            },
+        }
+    }
+}
+
+/// A temporary file that holds spilled output, which is removed once it is no longer used
+#[derive(Debug)]
+pub struct SpillFile {
+    path: PathBuf,
+    len: u64,
+}
+
+impl SpillFile {
+    fn new() -> Self {
+        let count = SPILL_COUNT.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "bencher-output-{pid}-{count}.txt",
+            pid = std::process::id()
+        ));
+        Self { path, len: 0 }
+    }
+
+    fn reader(&self) -> io::Result<BufReader<File>> {
+        File::open(&self.path).map(BufReader::new)
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        drop(std::fs::remove_file(&self.path));
+    }
+}
+
+/// Capture lines of output in memory, until they grow past the spill threshold.
+/// After that, all of the output is written to a spill file instead.
+/// If writing to the spill file fails, then the rest of the output is discarded
+/// and the error is returned once the capture is finished,
+/// so the command is never blocked on a full pipe.
+#[derive(Debug, Default)]
+pub struct Capture {
+    output: String,
+    spill: Option<(SpillFile, BufWriter<File>)>,
+    error: Option<io::Error>,
+}
+
+impl Capture {
+    pub fn push_line(&mut self, line: &str) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(err) = self.try_push_line(line) {
+            self.error = Some(err);
+        }
+    }
+
+    fn try_push_line(&mut self, line: &str) -> io::Result<()> {
+        if let Some((spill_file, writer)) = &mut self.spill {
+            writer.write_all(b"\n")?;
+            writer.write_all(line.as_bytes())?;
+            spill_file.len += 1 + line.len() as u64;
+            return Ok(());
+        }
+
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        self.output.push_str(line);
+        if self.output.len() > SPILL_THRESHOLD {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        let mut spill_file = SpillFile::new();
+        let mut writer = BufWriter::new(File::create(&spill_file.path)?);
+        writer.write_all(self.output.as_bytes())?;
+        spill_file.len = self.output.len() as u64;
+        self.output = String::new();
+        self.spill = Some((spill_file, writer));
+        Ok(())
+    }
+
+    pub fn finish(self) -> io::Result<Captured> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        Ok(if let Some((spill_file, writer)) = self.spill {
+            writer.into_inner().map_err(IntoInnerError::into_error)?;
+            Captured::Spilled(Arc::new(spill_file))
+        } else {
+            Captured::Memory(self.output)
+        })
+    }
+}
@@ -2,8 +2,17 @@ use std::{fmt, process::Stdio};
 
 use tokio::io::{AsyncBufReadExt, BufReader};
 
-use super::{flag::Flag, output::Output, shell::Shell};
-use crate::{bencher::sub::RunError, parser::project::run::CliRunShell};
+use super::{
+    capture::Capture,
+    flag::Flag,
+    output::{ExitStatus, Output},
+    shell::Shell,
+    timeout::Timeout,
+};
+use crate::{
+    bencher::sub::{project::run::progress::clear_status_line, RunError},
+    parser::project::run::CliRunShell,
+};
 use crate::{cli_eprintln_quietable, cli_println_quietable};
 
 #[derive(Debug, Clone)]
@@ -49,25 +58,50 @@ impl Command {
         Self::Exec { program, arguments }
     }
 
-    pub async fn run(&self, log: bool) -> Result<Output, RunError> {
-        let mut child = match self {
+    // Wrap the command so that it is run as the trailing arguments of another program
+    pub fn wrap(&self, program: String, mut arguments: Vec<String>) -> Self {
+        match self {
+            Self::Shell {
+                shell,
+                flag,
+                command,
+            } => arguments.extend([shell.to_string(), flag.to_string(), command.clone()]),
+            Self::Exec {
+                program,
+                arguments: args,
+            } => {
+                arguments.push(program.clone());
+                arguments.extend(args.iter().cloned());
+            },
+        }
+        Self::Exec { program, arguments }
+    }
+
+    pub async fn run(&self, log: bool, timeout: Option<Timeout>) -> Result<Output, RunError> {
+        let mut command = match self {
             Self::Shell {
                 shell,
                 flag,
                 command,
-            } => tokio::process::Command::new(shell.as_ref())
-                .arg(flag.as_ref())
-                .arg(command)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn(),
-            Self::Exec { program, arguments } => tokio::process::Command::new(program)
-                .args(arguments)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn(),
+            } => {
+                let mut cmd = tokio::process::Command::new(shell.as_ref());
+                cmd.arg(flag.as_ref()).arg(command);
+                cmd
+            },
+            Self::Exec { program, arguments } => {
+                let mut cmd = tokio::process::Command::new(program);
+                cmd.args(arguments);
+                cmd
+            },
+        };
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        // Run the command in its own process group,
+        // so any processes that it spawns can also be stopped if it times out.
+        #[cfg(unix)]
+        if timeout.is_some() {
+            command.process_group(0);
         }
-        .map_err(|err| RunError::SpawnCommand {
+        let mut child = command.spawn().map_err(|err| RunError::SpawnCommand {
             command: self.clone(),
             err,
         })?;
@@ -80,17 +114,15 @@ impl Command {
             let stdout_reader = BufReader::new(child_stdout);
             let mut stdout_lines = stdout_reader.lines();
 
-            let mut stdout = String::new();
+            // Large output is spilled to a temporary file as it is captured
+            let mut stdout = Capture::default();
             while let Ok(Some(line)) = stdout_lines.next_line().await {
+                clear_status_line();
                 cli_println_quietable!(log, "{line}");
-                if stdout.is_empty() {
-                    stdout = line;
-                } else {
-                    stdout = format!("{stdout}\n{line}");
-                }
+                stdout.push_line(&line);
             }
 
-            stdout
+            stdout.finish()
         });
 
         let child_stderr = child
@@ -103,36 +135,50 @@ impl Command {
 
             let mut stderr = String::new();
             while let Ok(Some(line)) = stderr_lines.next_line().await {
+                clear_status_line();
                 cli_eprintln_quietable!(log, "{line}");
-                if stderr.is_empty() {
-                    stderr = line;
-                } else {
-                    stderr = format!("{stderr}\n{line}");
+                if !stderr.is_empty() {
+                    stderr.push('\n');
                 }
+                stderr.push_str(&line);
             }
 
             stderr
         });
 
-        let (status, stdout, stderr) = tokio::join!(child.wait(), stdout, stderr);
+        let wait = async {
+            if let Some(timeout) = timeout {
+                timeout.wait(&mut child).await
+            } else {
+                child.wait().await.map(Some)
+            }
+        };
+        let (status, stdout, stderr) = tokio::join!(wait, stdout, stderr);
         let status = status.map_err(|err| RunError::RunCommand {
             command: self.clone(),
             err,
         })?;
-        let stdout = stdout.map_err(|err| RunError::StdoutJoinError {
-            command: self.clone(),
-            err,
-        })?;
+        let stdout = stdout
+            .map_err(|err| RunError::StdoutJoinError {
+                command: self.clone(),
+                err,
+            })?
+            .map_err(|err| RunError::CaptureStdout {
+                command: self.clone(),
+                err,
+            })?;
         let stderr = stderr.map_err(|err| RunError::StderrJoinError {
             command: self.clone(),
             err,
         })?;
 
         Ok(Output {
-            status: status.into(),
+            status: status.map_or_else(ExitStatus::timed_out, Into::into),
             stdout,
             stderr,
-            result: None,
+            results: Vec::new(),
+            perf_stat: None,
+            timed_out: timeout.filter(|_| status.is_none()),
         })
     }
 }
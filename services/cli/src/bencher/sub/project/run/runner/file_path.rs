@@ -5,20 +5,68 @@ use camino::Utf8PathBuf;
 use crate::RunError;
 
 #[derive(Debug, Clone)]
-pub struct FilePath(Utf8PathBuf);
+pub struct FilePath(Vec<Utf8PathBuf>);
 
 impl fmt::Display for FilePath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|p| p.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        )
     }
 }
 
 impl FilePath {
-    pub fn new(file_path: Utf8PathBuf) -> Self {
-        Self(file_path)
+    pub fn new(file_paths: Vec<Utf8PathBuf>) -> Self {
+        Self(file_paths)
     }
 
-    pub fn get_results(&self) -> Result<String, RunError> {
-        std::fs::read_to_string(&self.0).map_err(RunError::OutputFileRead)
+    /// Whether the results from multiple files need to be combined.
+    /// This is decided by the arguments, not by the number of matching files,
+    /// so that every iteration is handled the same way.
+    pub fn is_combined(&self) -> bool {
+        match self.0.as_slice() {
+            [file_path] => is_glob_pattern(file_path.as_str()),
+            _ => true,
+        }
     }
+
+    /// The contents of each file, in the order of the file paths.
+    /// Glob patterns are expanded in alphabetical order.
+    pub fn get_results(&self) -> Result<Vec<String>, RunError> {
+        let mut results = Vec::new();
+        for file_path in &self.0 {
+            if !is_glob_pattern(file_path.as_str()) {
+                results.push(std::fs::read_to_string(file_path).map_err(RunError::OutputFileRead)?);
+                continue;
+            }
+
+            let paths = glob::glob(file_path.as_str())
+                .map_err(|err| RunError::OutputFileGlob {
+                    pattern: file_path.clone(),
+                    err,
+                })?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(RunError::OutputFileGlobPath)?;
+            if paths.is_empty() {
+                return Err(RunError::OutputFileGlobEmpty(file_path.clone()));
+            }
+            for path in paths {
+                // Skip any directories that match the pattern
+                if path.is_file() {
+                    results.push(std::fs::read_to_string(path).map_err(RunError::OutputFileRead)?);
+                }
+            }
+        }
+        Ok(results)
+    }
+}
+
+fn is_glob_pattern(file_path: &str) -> bool {
+    file_path.contains(['*', '?', '['])
 }
@@ -1,22 +1,29 @@
-use std::fmt;
+use std::{collections::HashMap, fmt};
 
 use bencher_json::{
     project::measure::built_in::{self, BuiltInMeasure},
-    JsonNewMetric,
+    BenchmarkName, JsonNewMetric, MeasureNameId,
 };
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
+use object::{Object, ObjectSection, ObjectSymbol, SectionKind, SymbolKind};
 
 use crate::RunError;
 
+type FileSizeResults = Vec<(BenchmarkName, Vec<(MeasureNameId, JsonNewMetric)>)>;
+
 #[derive(Debug, Clone)]
-pub struct FileSize(Vec<Utf8PathBuf>);
+pub struct FileSize {
+    file_paths: Vec<Utf8PathBuf>,
+    sections: bool,
+    symbols: Option<usize>,
+}
 
 impl fmt::Display for FileSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "{}",
-            self.0
+            self.file_paths
                 .iter()
                 .map(|p| p.as_str())
                 .collect::<Vec<&str>>()
@@ -26,35 +33,248 @@ impl fmt::Display for FileSize {
 }
 
 impl FileSize {
-    pub fn new(file_paths: Vec<Utf8PathBuf>) -> Self {
-        Self(file_paths)
+    pub fn new(file_paths: Vec<Utf8PathBuf>, sections: bool, symbols: Option<usize>) -> Self {
+        Self {
+            file_paths,
+            sections,
+            symbols,
+        }
     }
 
     pub fn get_results(&self) -> Result<String, RunError> {
-        let mut results = Vec::with_capacity(self.0.len());
-        for file_path in &self.0 {
-            let file_name = file_path
-                .file_name()
-                .unwrap_or(file_path.as_str())
-                .parse()
-                .map_err(RunError::OutputFileName)?;
+        let mut results = Vec::with_capacity(self.file_paths.len());
+        for file_path in &self.file_paths {
+            let file_name = file_path.file_name().unwrap_or(file_path.as_str());
             #[allow(clippy::cast_precision_loss)]
-            let value = (std::fs::metadata(file_path)
+            let size = std::fs::metadata(file_path)
                 .map(|m| m.len())
-                .map_err(RunError::OutputFileSize)? as f64)
-                .into();
+                .map_err(RunError::OutputFileSize)? as f64;
             results.push((
-                file_name,
-                vec![(
-                    built_in::file_size::FileSize::name_id(),
-                    JsonNewMetric {
-                        value,
-                        ..Default::default()
-                    },
-                )],
+                file_name.parse().map_err(RunError::OutputFileName)?,
+                file_size_metric(size),
             ));
+
+            if self.sections || self.symbols.is_some() {
+                results.extend(self.get_object_results(file_path, file_name)?);
+            }
         }
         let results = JsonNewMetric::results(results);
         serde_json::to_string(&results).map_err(RunError::SerializeFileSize)
     }
+
+    /// Break down the size of an object file (ELF, Mach-O, PE, etc)
+    /// by section kind and by its largest symbols.
+    fn get_object_results(
+        &self,
+        file_path: &Utf8Path,
+        file_name: &str,
+    ) -> Result<FileSizeResults, RunError> {
+        let data = std::fs::read(file_path).map_err(RunError::OutputFileSize)?;
+        let object_file = object::File::parse(&*data).map_err(|err| RunError::ObjectFile {
+            file_path: file_path.to_owned(),
+            err,
+        })?;
+
+        let mut results = Vec::new();
+        if self.sections {
+            let mut section_sizes = HashMap::new();
+            for section in object_file.sections() {
+                if let Some(section_name) = section_kind_name(section.kind()) {
+                    *section_sizes.entry(section_name).or_insert(0) += section.size();
+                }
+            }
+            let mut section_sizes = section_sizes.into_iter().collect::<Vec<_>>();
+            section_sizes.sort_unstable();
+            for (section_name, size) in section_sizes {
+                let benchmark_name = format!("{file_name}::section::{section_name}")
+                    .parse()
+                    .map_err(RunError::OutputFileName)?;
+                #[allow(clippy::cast_precision_loss)]
+                let size = size as f64;
+                results.push((benchmark_name, file_size_metric(size)));
+            }
+        }
+
+        if let Some(count) = self.symbols {
+            let mut symbol_sizes = HashMap::new();
+            for symbol in object_file.symbols() {
+                if !matches!(symbol.kind(), SymbolKind::Text | SymbolKind::Data)
+                    || symbol.size() == 0
+                {
+                    continue;
+                }
+                if let Ok(symbol_name) = symbol.name() {
+                    *symbol_sizes.entry(symbol_name).or_insert(0) += symbol.size();
+                }
+            }
+            let mut symbol_sizes = symbol_sizes.into_iter().collect::<Vec<_>>();
+            // Sort by size descending and then by name, so the order is stable
+            symbol_sizes.sort_unstable_by(|(a_name, a_size), (b_name, b_size)| {
+                b_size.cmp(a_size).then_with(|| a_name.cmp(b_name))
+            });
+            for (symbol_name, size) in symbol_sizes.into_iter().take(count) {
+                // Skip any symbol names that are too long to be a benchmark name
+                let Ok(benchmark_name) = format!("{file_name}::symbol::{symbol_name}").parse()
+                else {
+                    continue;
+                };
+                #[allow(clippy::cast_precision_loss)]
+                let size = size as f64;
+                results.push((benchmark_name, file_size_metric(size)));
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+fn file_size_metric(size: f64) -> Vec<(MeasureNameId, JsonNewMetric)> {
+    vec![(
+        built_in::file_size::FileSize::name_id(),
+        JsonNewMetric {
+            value: size.into(),
+            ..Default::default()
+        },
+    )]
+}
+
+/// Group sections by kind, so the names are the same across object file formats
+// The section kind is non-exhaustive, so any other kind is not grouped
+#[allow(clippy::wildcard_enum_match_arm)]
+fn section_kind_name(kind: SectionKind) -> Option<&'static str> {
+    match kind {
+        SectionKind::Text => Some("text"),
+        SectionKind::Data | SectionKind::Tls => Some("data"),
+        SectionKind::ReadOnlyData
+        | SectionKind::ReadOnlyDataWithRel
+        | SectionKind::ReadOnlyString => Some("rodata"),
+        SectionKind::UninitializedData | SectionKind::UninitializedTls => Some("bss"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::BenchmarkName;
+    use camino::Utf8PathBuf;
+    use object::SectionKind;
+    use pretty_assertions::assert_eq;
+
+    use crate::RunError;
+
+    use super::{file_size_metric, section_kind_name, FileSize, FileSizeResults};
+
+    // Built from `object.c` in the same directory
+    const OBJECT_FILE: &str = "./tool_output/file_size/object.o";
+    const OBJECT_FILE_NAME: &str = "object.o";
+
+    fn object_results(sections: bool, symbols: Option<usize>) -> FileSizeResults {
+        let file_path = Utf8PathBuf::from(OBJECT_FILE);
+        FileSize::new(vec![file_path.clone()], sections, symbols)
+            .get_object_results(&file_path, OBJECT_FILE_NAME)
+            .unwrap()
+    }
+
+    fn expected(results: &[(&str, f64)]) -> FileSizeResults {
+        results
+            .iter()
+            .map(|(name, size)| {
+                (
+                    format!("{OBJECT_FILE_NAME}::{name}")
+                        .parse::<BenchmarkName>()
+                        .unwrap(),
+                    file_size_metric(*size),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_object_results_sections() {
+        let results = object_results(true, None);
+        assert_eq!(
+            results,
+            expected(&[
+                ("section::bss", 32.0),
+                ("section::data", 4.0),
+                ("section::rodata", 13.0),
+                ("section::text", 58.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_object_results_symbols() {
+        let results = object_results(false, Some(3));
+        assert_eq!(
+            results,
+            expected(&[
+                ("symbol::main", 38.0),
+                ("symbol::zeroed", 32.0),
+                ("symbol::add", 20.0),
+            ])
+        );
+
+        let results = object_results(false, Some(usize::MAX));
+        assert_eq!(
+            results,
+            expected(&[
+                ("symbol::main", 38.0),
+                ("symbol::zeroed", 32.0),
+                ("symbol::add", 20.0),
+                ("symbol::greeting", 13.0),
+                ("symbol::counter", 4.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_object_results_sections_and_symbols() {
+        let results = object_results(true, Some(1));
+        assert_eq!(
+            results,
+            expected(&[
+                ("section::bss", 32.0),
+                ("section::data", 4.0),
+                ("section::rodata", 13.0),
+                ("section::text", 58.0),
+                ("symbol::main", 38.0),
+            ])
+        );
+
+        assert_eq!(object_results(false, None), Vec::new());
+    }
+
+    #[test]
+    fn test_object_results_not_object() {
+        let file_path = Utf8PathBuf::from("./tool_output/file_size/object.c");
+        let result = FileSize::new(vec![file_path.clone()], true, None)
+            .get_object_results(&file_path, "object.c");
+        assert!(
+            matches!(result, Err(RunError::ObjectFile { .. })),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_section_kind_name() {
+        for (kind, name) in [
+            (SectionKind::Text, Some("text")),
+            (SectionKind::Data, Some("data")),
+            (SectionKind::Tls, Some("data")),
+            (SectionKind::ReadOnlyData, Some("rodata")),
+            (SectionKind::ReadOnlyDataWithRel, Some("rodata")),
+            (SectionKind::ReadOnlyString, Some("rodata")),
+            (SectionKind::UninitializedData, Some("bss")),
+            (SectionKind::UninitializedTls, Some("bss")),
+            (SectionKind::Metadata, None),
+            (SectionKind::Note, None),
+            (SectionKind::Other, None),
+            (SectionKind::OtherString, None),
+            (SectionKind::Debug, None),
+            (SectionKind::Unknown, None),
+        ] {
+            assert_eq!(section_kind_name(kind), name, "{kind:?}");
+        }
+    }
 }
@@ -2,19 +2,25 @@ use std::fmt;
 
 use crate::parser::project::run::CliRunCommand;
 
+pub mod capture;
 pub mod command;
 mod file_path;
 mod file_size;
 mod flag;
 pub mod output;
+mod perf_stat;
 mod pipe;
 mod shell;
+pub mod timeout;
 
 use command::Command;
 use file_path::FilePath;
 use file_size::FileSize;
 use output::Output;
+use perf_stat::PerfStat;
+pub use perf_stat::PerfStatCounters;
 use pipe::Pipe;
+use timeout::Timeout;
 
 use super::RunError;
 
@@ -24,6 +30,7 @@ pub enum Runner {
     Command(Command),
     CommandToFile(Command, FilePath),
     CommandToFileSize(Command, FileSize),
+    CommandWithPerfStat(Command, PerfStat),
     File(FilePath),
     FileSize(FileSize),
 }
@@ -50,14 +57,23 @@ impl TryFrom<CliRunCommand> for Runner {
             Ok(if let Some(file_path) = cmd.file {
                 Self::CommandToFile(command, FilePath::new(file_path))
             } else if let Some(file_paths) = cmd.file_size {
-                Self::CommandToFileSize(command, FileSize::new(file_paths))
+                Self::CommandToFileSize(
+                    command,
+                    FileSize::new(file_paths, cmd.file_size_sections, cmd.file_size_symbols),
+                )
+            } else if cmd.perf_stat {
+                Self::CommandWithPerfStat(command, PerfStat::new()?)
             } else {
                 Self::Command(command)
             })
         } else if let Some(file_path) = cmd.file {
             Ok(Self::File(FilePath::new(file_path)))
         } else if let Some(file_paths) = cmd.file_size {
-            Ok(Self::FileSize(FileSize::new(file_paths)))
+            Ok(Self::FileSize(FileSize::new(
+                file_paths,
+                cmd.file_size_sections,
+                cmd.file_size_symbols,
+            )))
         } else if let Some(pipe) = Pipe::new() {
             Ok(Self::Pipe(pipe))
         } else {
@@ -77,6 +93,9 @@ impl fmt::Display for Runner {
             Self::CommandToFileSize(command, file_path) => {
                 write!(f, "{command} > {file_path} (size)")
             },
+            Self::CommandWithPerfStat(command, perf_stat) => {
+                write!(f, "{perf_stat} -- {command}")
+            },
             Self::File(file_path) => write!(f, "{file_path}"),
             Self::FileSize(file_path) => write!(f, "{file_path} (size)"),
         }
@@ -84,33 +103,59 @@ impl fmt::Display for Runner {
 }
 
 impl Runner {
-    pub async fn run(&self, log: bool) -> Result<Output, RunError> {
+    /// Whether the results from multiple output files need to be combined into each iteration
+    pub fn is_combined(&self) -> bool {
+        match self {
+            Self::CommandToFile(_, file_path) | Self::File(file_path) => file_path.is_combined(),
+            Self::Pipe(_)
+            | Self::Command(_)
+            | Self::CommandToFileSize(_, _)
+            | Self::CommandWithPerfStat(_, _)
+            | Self::FileSize(_) => false,
+        }
+    }
+
+    pub async fn run(&self, log: bool, timeout: Option<Timeout>) -> Result<Output, RunError> {
         Ok(match self {
             Self::Pipe(pipe) => pipe.output(),
-            Self::Command(command) => command.run(log).await?,
+            Self::Command(command) => command.run(log, timeout).await?,
+            // The output files of a command that timed out are likely incomplete
             Self::CommandToFile(command, file_path) => {
-                let mut output = command.run(log).await?;
-                let results = file_path.get_results()?;
-                output.result = Some(results);
+                let mut output = command.run(log, timeout).await?;
+                if !output.is_timed_out() {
+                    output.results = file_path.get_results()?;
+                }
                 output
             },
             Self::CommandToFileSize(command, file_size) => {
-                let mut output = command.run(log).await?;
-                let results = file_size.get_results()?;
-                output.result = Some(results);
+                let mut output = command.run(log, timeout).await?;
+                if !output.is_timed_out() {
+                    output.results = vec![file_size.get_results()?];
+                }
+                output
+            },
+            // The counters are added to the benchmark harness results from the standard output
+            Self::CommandWithPerfStat(command, perf_stat) => {
+                let output = perf_stat.wrap(command).run(log, timeout).await;
+                // Always take the counters, so the output file is removed even if the command failed
+                let counters = perf_stat.take_counters(command);
+                let mut output = output?;
+                if output.is_success() {
+                    output.perf_stat = Some(counters?);
+                }
                 output
             },
             Self::File(file_path) => {
                 let results = file_path.get_results()?;
                 Output {
-                    result: Some(results),
+                    results,
                     ..Default::default()
                 }
             },
             Self::FileSize(file_size) => {
                 let results = file_size.get_results()?;
                 Output {
-                    result: Some(results),
+                    results: vec![results],
                     ..Default::default()
                 }
             },
@@ -1,11 +1,19 @@
 use std::fmt;
 
+use super::{capture::Captured, timeout::Timeout, PerfStatCounters};
+
+/// The exit code for a command that timed out, the same as the `timeout` utility
+const TIMED_OUT_EXIT_CODE: i32 = 124;
+
 #[derive(Debug, Clone, Default)]
 pub struct Output {
     pub status: ExitStatus,
-    pub stdout: String,
+    pub stdout: Captured,
     pub stderr: String,
-    pub result: Option<String>,
+    pub results: Vec<String>,
+    /// The `perf stat` hardware counters, if they were collected
+    pub perf_stat: Option<PerfStatCounters>,
+    pub timed_out: Option<Timeout>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -19,16 +27,14 @@ impl From<std::process::ExitStatus> for ExitStatus {
 
 impl fmt::Display for Output {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let result = if let Some(result) = self.result.as_deref() {
-            format!("\n{result}")
-        } else {
-            String::new()
-        };
-        write!(
-            f,
-            "{}\n{}\n{}{result}",
-            self.status, self.stdout, self.stderr
-        )
+        if let Some(timeout) = self.timed_out {
+            writeln!(f, "Timed out after {timeout}")?;
+        }
+        write!(f, "{}\n{}\n{}", self.status, self.stdout, self.stderr)?;
+        for result in &self.results {
+            write!(f, "\n{result}")?;
+        }
+        Ok(())
     }
 }
 
@@ -40,16 +46,46 @@ impl fmt::Display for ExitStatus {
 
 impl Output {
     pub fn is_success(&self) -> bool {
-        self.status.is_success()
+        self.timed_out.is_none() && self.status.is_success()
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out.is_some()
+    }
+
+    /// The results from the output files, if any, otherwise the standard output
+    pub fn results(self) -> Vec<Captured> {
+        if self.results.is_empty() {
+            vec![self.stdout]
+        } else {
+            self.results.into_iter().map(Captured::Memory).collect()
+        }
     }
 
-    pub fn result(self) -> String {
-        self.result.unwrap_or(self.stdout)
+    /// The last `max_len` bytes of the standard error output, if there is any
+    pub fn stderr_tail(&self, max_len: usize) -> Option<String> {
+        let stderr = self.stderr.trim_end();
+        if stderr.is_empty() {
+            return None;
+        }
+        let mut start = stderr.len().saturating_sub(max_len);
+        while !stderr.is_char_boundary(start) {
+            start += 1;
+        }
+        stderr.get(start..).map(ToOwned::to_owned)
     }
 }
 
 impl ExitStatus {
+    pub fn timed_out() -> Self {
+        Self(TIMED_OUT_EXIT_CODE)
+    }
+
     pub fn is_success(&self) -> bool {
         self.0 == 0
     }
+
+    pub fn code(&self) -> i32 {
+        self.0
+    }
 }
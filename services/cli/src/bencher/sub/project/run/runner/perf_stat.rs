@@ -0,0 +1,344 @@
+use std::{collections::HashMap, fmt};
+
+use bencher_adapter::{results::adapter_metrics::AdapterMetrics, AdapterResults};
+use bencher_json::{
+    project::measure::built_in::{self, BuiltInMeasure},
+    BenchmarkName, JsonNewMetric, NameId,
+};
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+
+use super::command::Command;
+use crate::RunError;
+
+const PERF_PROGRAM: &str = "perf";
+const PERF_STAT_EVENTS: &str = "instructions,cycles,branch-misses,cache-misses";
+
+#[derive(Debug, Clone)]
+pub struct PerfStat(Utf8PathBuf);
+
+impl fmt::Display for PerfStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{PERF_PROGRAM} stat -j -e {PERF_STAT_EVENTS}")
+    }
+}
+
+// A single counter line from `perf stat -j`
+#[derive(Debug, Deserialize)]
+struct PerfStatCounter {
+    #[serde(rename = "counter-value")]
+    counter_value: String,
+    event: String,
+}
+
+impl PerfStat {
+    pub fn new() -> Result<Self, RunError> {
+        if !cfg!(target_os = "linux") {
+            return Err(RunError::PerfStatUnsupported);
+        }
+        let output_path =
+            std::env::temp_dir().join(format!("bencher-perf-stat-{}.json", std::process::id()));
+        Utf8PathBuf::from_path_buf(output_path)
+            .map(Self)
+            .map_err(RunError::PerfStatPath)
+    }
+
+    // Wrap the benchmark command so that `perf stat` writes its counters to the output file
+    pub fn wrap(&self, command: &Command) -> Command {
+        command.wrap(
+            PERF_PROGRAM.into(),
+            vec![
+                "stat".into(),
+                "-j".into(),
+                "-o".into(),
+                self.0.to_string(),
+                "-e".into(),
+                PERF_STAT_EVENTS.into(),
+                "--".into(),
+            ],
+        )
+    }
+
+    /// Read the counters from the output file, and then remove it.
+    /// The output file is always removed, even if it can not be read,
+    /// so it is not left behind or picked up by a later iteration.
+    pub fn take_counters(&self, command: &Command) -> Result<PerfStatCounters, RunError> {
+        let perf_output = std::fs::read_to_string(&self.0);
+        drop(std::fs::remove_file(&self.0));
+        let perf_output = perf_output.map_err(RunError::PerfStatRead)?;
+
+        let benchmark_name = command
+            .to_string()
+            .trim()
+            .parse()
+            .map_err(RunError::PerfStatBenchmarkName)?;
+        let metrics = parse_counters(&perf_output)?
+            .into_iter()
+            .map(|(measure, value)| {
+                (
+                    measure,
+                    JsonNewMetric {
+                        value: value.into(),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        Ok(PerfStatCounters {
+            benchmark_name,
+            metrics,
+        })
+    }
+}
+
+/// The hardware counters for a single run of the benchmark command
+#[derive(Debug, Clone)]
+pub struct PerfStatCounters {
+    benchmark_name: BenchmarkName,
+    metrics: AdapterMetrics,
+}
+
+impl PerfStatCounters {
+    /// Add the counters as measures to the benchmark harness results.
+    /// The counters are for the whole benchmark command,
+    /// so they are only added to a benchmark if it is the only one in the results.
+    /// Otherwise, they are added as their own benchmark named after the command.
+    pub fn add_to(self, results: &mut AdapterResults) {
+        let Self {
+            benchmark_name,
+            metrics,
+        } = self;
+        let adapter_metrics = if results.inner.len() == 1 {
+            results.inner.values_mut().next()
+        } else {
+            None
+        };
+        if let Some(adapter_metrics) = adapter_metrics {
+            // Keep any of the same measures from the benchmark harness
+            for (measure, metric) in metrics.inner {
+                adapter_metrics.inner.entry(measure).or_insert(metric);
+            }
+        } else {
+            results
+                .inner
+                .entry(benchmark_name)
+                .or_default()
+                .inner
+                .extend(metrics.inner);
+        }
+    }
+}
+
+// Sum the counter values for each measure from the `perf stat -j` output
+fn parse_counters(perf_output: &str) -> Result<HashMap<NameId, f64>, RunError> {
+    let mut counters = HashMap::<NameId, f64>::new();
+    for line in perf_output.lines().map(str::trim) {
+        // `perf stat` prefixes its output file with a `# started on` comment
+        if !line.starts_with('{') {
+            continue;
+        }
+        let counter: PerfStatCounter =
+            serde_json::from_str(line).map_err(RunError::PerfStatParse)?;
+        // Unsupported or uncounted events are reported as `<not supported>` or `<not counted>`
+        let Ok(value) = counter.counter_value.parse::<f64>() else {
+            continue;
+        };
+        let Some(measure) = perf_stat_measure(&counter.event) else {
+            continue;
+        };
+        // Hybrid CPUs report a counter for each core type, so sum them together
+        *counters.entry(measure).or_default() += value;
+    }
+    Ok(counters)
+}
+
+// Normalize event names such as `cpu_core/instructions/` or `instructions:u`
+fn perf_stat_measure(event: &str) -> Option<NameId> {
+    let event = event
+        .strip_suffix('/')
+        .and_then(|pmu_event| pmu_event.rsplit_once('/'))
+        .map_or(event, |(_, event)| event);
+    let event = event.split_once(':').map_or(event, |(event, _)| event);
+    match event {
+        "instructions" => Some(built_in::perf_stat::Instructions::name_id()),
+        "cycles" => Some(built_in::perf_stat::Cycles::name_id()),
+        "branch-misses" => Some(built_in::perf_stat::BranchMisses::name_id()),
+        "cache-misses" => Some(built_in::perf_stat::CacheMisses::name_id()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_adapter::{results::adapter_metrics::AdapterMetrics, AdapterResults};
+    use bencher_json::{
+        project::measure::built_in::{self, BuiltInMeasure},
+        JsonNewMetric,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_counters, perf_stat_measure, PerfStatCounters};
+
+    const PERF_OUTPUT: &str = r#"# started on Mon Oct 14 12:00:00 2024
+
+{"counter-value" : "1000.000000", "unit" : "", "event" : "cpu_core/instructions/", "event-runtime" : 100, "pcnt-running" : 100.00}
+{"counter-value" : "500.000000", "unit" : "", "event" : "cpu_atom/instructions/", "event-runtime" : 100, "pcnt-running" : 100.00}
+{"counter-value" : "2000.000000", "unit" : "", "event" : "cycles:u", "event-runtime" : 100, "pcnt-running" : 100.00}
+{"counter-value" : "<not supported>", "unit" : "", "event" : "branch-misses", "event-runtime" : 0, "pcnt-running" : 100.00}
+{"counter-value" : "30.000000", "unit" : "", "event" : "cache-misses", "event-runtime" : 100, "pcnt-running" : 100.00}
+{"counter-value" : "7.000000", "unit" : "", "event" : "page-faults", "event-runtime" : 100, "pcnt-running" : 100.00}
+"#;
+
+    fn metric(value: f64) -> JsonNewMetric {
+        JsonNewMetric {
+            value: value.into(),
+            ..Default::default()
+        }
+    }
+
+    fn counters() -> PerfStatCounters {
+        PerfStatCounters {
+            benchmark_name: "./bench".parse().unwrap(),
+            metrics: AdapterMetrics {
+                inner: [
+                    (built_in::perf_stat::Instructions::name_id(), metric(1500.0)),
+                    (built_in::perf_stat::Cycles::name_id(), metric(2000.0)),
+                ]
+                .into_iter()
+                .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_perf_stat_measure() {
+        assert_eq!(
+            perf_stat_measure("instructions"),
+            Some(built_in::perf_stat::Instructions::name_id())
+        );
+        assert_eq!(
+            perf_stat_measure("cpu_core/instructions/"),
+            Some(built_in::perf_stat::Instructions::name_id())
+        );
+        assert_eq!(
+            perf_stat_measure("cycles:u"),
+            Some(built_in::perf_stat::Cycles::name_id())
+        );
+        assert_eq!(
+            perf_stat_measure("branch-misses"),
+            Some(built_in::perf_stat::BranchMisses::name_id())
+        );
+        assert_eq!(
+            perf_stat_measure("cache-misses"),
+            Some(built_in::perf_stat::CacheMisses::name_id())
+        );
+        assert_eq!(perf_stat_measure("page-faults"), None);
+    }
+
+    #[test]
+    fn test_parse_counters() {
+        let counters = parse_counters(PERF_OUTPUT).unwrap();
+        assert_eq!(counters.len(), 3);
+        assert_eq!(
+            counters.get(&built_in::perf_stat::Instructions::name_id()),
+            Some(&1500.0)
+        );
+        assert_eq!(
+            counters.get(&built_in::perf_stat::Cycles::name_id()),
+            Some(&2000.0)
+        );
+        assert_eq!(
+            counters.get(&built_in::perf_stat::CacheMisses::name_id()),
+            Some(&30.0)
+        );
+        assert_eq!(
+            counters.get(&built_in::perf_stat::BranchMisses::name_id()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_counters_invalid() {
+        assert!(parse_counters("{not json}").is_err());
+        assert!(parse_counters("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_to_single_benchmark() {
+        let mut results = AdapterResults::default();
+        let latency = built_in::generic::Latency::name_id();
+        results
+            .inner
+            .entry("bench_one".parse().unwrap())
+            .or_default()
+            .inner
+            .insert(latency.clone(), metric(1.0));
+        counters().add_to(&mut results);
+
+        assert_eq!(results.inner.len(), 1);
+        let metrics = &results
+            .inner
+            .get(&"bench_one".parse().unwrap())
+            .unwrap()
+            .inner;
+        assert_eq!(metrics.len(), 3);
+        assert_eq!(metrics.get(&latency), Some(&metric(1.0)));
+        assert_eq!(
+            metrics.get(&built_in::perf_stat::Instructions::name_id()),
+            Some(&metric(1500.0))
+        );
+    }
+
+    #[test]
+    fn test_add_to_many_benchmarks() {
+        let mut results = AdapterResults::default();
+        let latency = built_in::generic::Latency::name_id();
+        for name in ["bench_one", "bench_two"] {
+            results
+                .inner
+                .entry(name.parse().unwrap())
+                .or_default()
+                .inner
+                .insert(latency.clone(), metric(1.0));
+        }
+        counters().add_to(&mut results);
+
+        assert_eq!(results.inner.len(), 3);
+        for name in ["bench_one", "bench_two"] {
+            assert_eq!(
+                results
+                    .inner
+                    .get(&name.parse().unwrap())
+                    .unwrap()
+                    .inner
+                    .len(),
+                1
+            );
+        }
+        assert_eq!(
+            results
+                .inner
+                .get(&"./bench".parse().unwrap())
+                .unwrap()
+                .inner
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_add_to_empty() {
+        let mut results = AdapterResults::default();
+        counters().add_to(&mut results);
+        assert_eq!(results.inner.len(), 1);
+        assert_eq!(
+            results
+                .inner
+                .get(&"./bench".parse().unwrap())
+                .unwrap()
+                .inner
+                .len(),
+            2
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::{fmt, io::BufRead};
 
-use super::Output;
+use super::{capture::Captured, Output};
 
 #[derive(Debug, Clone)]
 pub struct Pipe(Output);
@@ -19,7 +19,7 @@ impl Pipe {
             None
         } else {
             Some(Self(Output {
-                stdout: stdin,
+                stdout: Captured::Memory(stdin),
                 ..Default::default()
             }))
         }
@@ -0,0 +1,69 @@
+#[cfg(unix)]
+use std::process::Stdio;
+use std::{fmt, io, process::ExitStatus, time::Duration};
+
+use tokio::process::Child;
+
+/// A limit on how long each iteration of the benchmark command may run
+#[derive(Debug, Clone, Copy)]
+pub struct Timeout {
+    duration: Duration,
+    kill_after: Option<Duration>,
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} second(s)", self.duration.as_secs())
+    }
+}
+
+impl Timeout {
+    pub fn new(timeout: Option<u64>, kill_after: Option<u64>) -> Option<Self> {
+        timeout.map(|timeout| Self {
+            duration: Duration::from_secs(timeout),
+            kill_after: kill_after.map(Duration::from_secs),
+        })
+    }
+
+    /// Wait for the child process to exit, killing it if it runs past the timeout.
+    /// Returns `None` if the child process timed out.
+    pub async fn wait(self, child: &mut Child) -> io::Result<Option<ExitStatus>> {
+        if let Ok(status) = tokio::time::timeout(self.duration, child.wait()).await {
+            return status.map(Some);
+        }
+
+        // Give the command a chance to exit gracefully before killing it
+        if let Some(kill_after) = self.kill_after {
+            signal(child, "TERM").await;
+            if tokio::time::timeout(kill_after, child.wait()).await.is_ok() {
+                return Ok(None);
+            }
+        }
+        signal(child, "KILL").await;
+        child.start_kill()?;
+        child.wait().await?;
+        Ok(None)
+    }
+}
+
+// The child process is the leader of its own process group,
+// so the signal is sent to the whole group to also stop any processes spawned by a shell.
+#[cfg(unix)]
+async fn signal(child: &Child, signal: &str) {
+    let Some(pid) = child.id() else {
+        return;
+    };
+    // Any failure is ignored, as the child process is also killed directly if needed
+    drop(
+        tokio::process::Command::new("kill")
+            .args(["-s", signal, "--", &format!("-{pid}")])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await,
+    );
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unused_async)]
+async fn signal(_child: &Child, _signal: &str) {}
@@ -0,0 +1,132 @@
+use bencher_json::{
+    ApiErrorKind, JsonTestbed, JsonTestbeds, NameId, NameIdKind, ResourceId, ResourceName,
+};
+
+use crate::{
+    bencher::{backend::PubBackend, BackendError},
+    cli_eprintln_quietable,
+};
+
+use super::{fingerprint, RunError};
+
+/// How to check the current machine against the declared testbed hardware spec
+#[derive(Debug, Clone, Copy)]
+pub enum TestbedCheck {
+    /// Warn if the current machine does not match
+    Warn,
+    /// Error if the current machine does not match
+    Strict,
+}
+
+impl TestbedCheck {
+    pub fn new(check_testbed: bool, strict_testbed: bool) -> Option<Self> {
+        if strict_testbed {
+            Some(Self::Strict)
+        } else if check_testbed {
+            Some(Self::Warn)
+        } else {
+            None
+        }
+    }
+
+    /// Check that the current machine matches the hardware spec declared for the testbed.
+    /// If the testbed does not exist yet or does not have a declared spec, then there is nothing to check.
+    pub async fn check(
+        self,
+        project: &ResourceId,
+        testbed: &NameId,
+        backend: &PubBackend,
+        log: bool,
+    ) -> Result<(), RunError> {
+        let Some(json_testbed) = get_testbed(project, testbed, backend).await? else {
+            return Ok(());
+        };
+        let Some(spec) = json_testbed.spec else {
+            return Ok(());
+        };
+        let mismatches = spec.mismatches(&fingerprint::testbed_spec());
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+
+        match self {
+            Self::Warn => {
+                for mismatch in mismatches {
+                    cli_eprintln_quietable!(
+                        log,
+                        "Warning: Testbed ({testbed}) {mismatch}",
+                        testbed = json_testbed.slug
+                    );
+                }
+                Ok(())
+            },
+            Self::Strict => Err(RunError::TestbedMismatch {
+                testbed: json_testbed.slug,
+                mismatches: mismatches
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }),
+        }
+    }
+}
+
+async fn get_testbed(
+    project: &ResourceId,
+    testbed: &NameId,
+    backend: &PubBackend,
+) -> Result<Option<JsonTestbed>, RunError> {
+    let result =
+        match NameIdKind::<ResourceName>::try_from(testbed).map_err(RunError::TestbedNameId)? {
+            NameIdKind::Uuid(uuid) => get_testbed_by_id(project, uuid.into(), backend).await,
+            NameIdKind::Slug(slug) => get_testbed_by_id(project, slug.into(), backend).await,
+            NameIdKind::Name(name) => backend
+                .send_with(|client| {
+                    let name = name.clone();
+                    async move {
+                        client
+                            .proj_testbeds_get()
+                            .project(project.clone())
+                            .name(name)
+                            .send()
+                            .await
+                    }
+                })
+                .await
+                .map(|json_testbeds: JsonTestbeds| json_testbeds.0.into_iter().next()),
+        };
+    match result {
+        Ok(json_testbed) => Ok(json_testbed),
+        // Neither the project nor the testbed have to exist before the first report
+        Err(err)
+            if err
+                .code()
+                .is_some_and(|code| code.kind == ApiErrorKind::NotFound) =>
+        {
+            Ok(None)
+        },
+        Err(err) => Err(RunError::GetTestbed(err)),
+    }
+}
+
+async fn get_testbed_by_id(
+    project: &ResourceId,
+    testbed: ResourceId,
+    backend: &PubBackend,
+) -> Result<Option<JsonTestbed>, BackendError> {
+    backend
+        .send_with(|client| {
+            let testbed = testbed.clone();
+            async move {
+                client
+                    .proj_testbed_get()
+                    .project(project.clone())
+                    .testbed(testbed)
+                    .send()
+                    .await
+            }
+        })
+        .await
+        .map(Some)
+}
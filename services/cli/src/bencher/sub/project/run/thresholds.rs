@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use bencher_client::types::JsonReportThresholds;
-use bencher_json::{Boundary, NameId, SampleSize, Window};
+use bencher_json::{Boundary, Decay, NameId, SampleSize, Window};
 
 use crate::{
     bencher::sub::project::threshold::model::Model,
@@ -42,6 +42,8 @@ pub enum ThresholdsError {
     ExtraLowerBoundaries(Vec<ElidedOption<Boundary>>),
     #[error("There are more upper boundaries than model tests")]
     ExtraUpperBoundaries(Vec<ElidedOption<Boundary>>),
+    #[error("There are more decay factors than model tests")]
+    ExtraDecays(Vec<ElidedOption<Decay>>),
 }
 
 impl TryFrom<CliRunThresholds> for Thresholds {
@@ -56,6 +58,7 @@ impl TryFrom<CliRunThresholds> for Thresholds {
             threshold_window,
             threshold_lower_boundary,
             threshold_upper_boundary,
+            threshold_decay,
             thresholds_reset,
         } = thresholds;
 
@@ -67,6 +70,7 @@ impl TryFrom<CliRunThresholds> for Thresholds {
         let mut windows = threshold_window.into_iter();
         let mut lower_boundaries = threshold_lower_boundary.into_iter();
         let mut upper_boundaries = threshold_upper_boundary.into_iter();
+        let mut decays = threshold_decay.into_iter();
         for measure in threshold_measure {
             let test = tests
                 .next()
@@ -76,6 +80,7 @@ impl TryFrom<CliRunThresholds> for Thresholds {
             let window = windows.next();
             let lower_boundary = lower_boundaries.next();
             let upper_boundary = upper_boundaries.next();
+            let decay = decays.next();
 
             let cli_model = CliModel {
                 test,
@@ -84,6 +89,7 @@ impl TryFrom<CliRunThresholds> for Thresholds {
                 window: window.and_then(Into::into),
                 lower_boundary: lower_boundary.and_then(Into::into),
                 upper_boundary: upper_boundary.and_then(Into::into),
+                decay: decay.and_then(Into::into),
             };
             let model = Model::try_from(cli_model).map_err(|err| ThresholdsError::BadModel {
                 measure: measure.clone(),
@@ -125,6 +131,10 @@ impl TryFrom<CliRunThresholds> for Thresholds {
                 remaining_upper_boundaries,
             ));
         }
+        let remaining_decays = decays.collect::<Vec<_>>();
+        if !remaining_decays.is_empty() {
+            return Err(ThresholdsError::ExtraDecays(remaining_decays));
+        }
 
         Ok(Self {
             // Do not short circuit early if there are no measures
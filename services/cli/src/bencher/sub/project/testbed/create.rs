@@ -1,5 +1,5 @@
 use bencher_client::types::JsonNewTestbed;
-use bencher_json::{ResourceId, ResourceName, Slug};
+use bencher_json::{project::testbed::JsonTestbedSpec, ResourceId, ResourceName, Slug};
 
 use crate::{
     bencher::{backend::AuthBackend, sub::SubCmd},
@@ -7,11 +7,14 @@ use crate::{
     CliError,
 };
 
+use super::{client_spec, testbed_spec};
+
 #[derive(Debug, Clone)]
 pub struct Create {
     pub project: ResourceId,
     pub name: ResourceName,
     pub slug: Option<Slug>,
+    pub spec: Option<JsonTestbedSpec>,
     pub backend: AuthBackend,
 }
 
@@ -23,12 +26,14 @@ impl TryFrom<CliTestbedCreate> for Create {
             project,
             name,
             slug,
+            spec,
             backend,
         } = create;
         Ok(Self {
             project,
             name,
             slug,
+            spec: testbed_spec(spec),
             backend: backend.try_into()?,
         })
     }
@@ -36,10 +41,13 @@ impl TryFrom<CliTestbedCreate> for Create {
 
 impl From<Create> for JsonNewTestbed {
     fn from(create: Create) -> Self {
-        let Create { name, slug, .. } = create;
+        let Create {
+            name, slug, spec, ..
+        } = create;
         Self {
             name: name.into(),
             slug: slug.map(Into::into),
+            spec: spec.map(client_spec),
         }
     }
 }
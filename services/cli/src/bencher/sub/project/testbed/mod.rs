@@ -1,4 +1,11 @@
-use crate::{bencher::sub::SubCmd, parser::project::testbed::CliTestbed, CliError};
+use bencher_client::types;
+use bencher_json::project::testbed::JsonTestbedSpec;
+
+use crate::{
+    bencher::sub::{project::run::fingerprint, SubCmd},
+    parser::project::testbed::{CliTestbed, CliTestbedSpec},
+    CliError,
+};
 
 mod create;
 mod delete;
@@ -40,3 +47,42 @@ impl SubCmd for Testbed {
         }
     }
 }
+
+/// The testbed hardware spec, if any of its fields are set.
+/// With `--detect-spec`, any fields that are not set are detected from the current machine.
+fn testbed_spec(spec: CliTestbedSpec) -> Option<JsonTestbedSpec> {
+    let CliTestbedSpec {
+        cpu,
+        cores,
+        memory,
+        os,
+        detect_spec,
+    } = spec;
+    let detected = if detect_spec {
+        fingerprint::testbed_spec()
+    } else {
+        JsonTestbedSpec::default()
+    };
+    let spec = JsonTestbedSpec {
+        cpu: cpu.or(detected.cpu),
+        cores: cores.or(detected.cores),
+        memory: memory.map(Into::into).or(detected.memory),
+        os: os.or(detected.os),
+    };
+    (!spec.is_empty()).then_some(spec)
+}
+
+fn client_spec(spec: JsonTestbedSpec) -> types::JsonTestbedSpec {
+    let JsonTestbedSpec {
+        cpu,
+        cores,
+        memory,
+        os,
+    } = spec;
+    types::JsonTestbedSpec {
+        cpu: cpu.map(Into::into),
+        cores,
+        memory: memory.map(|memory| u64::from(memory).into()),
+        os: os.map(Into::into),
+    }
+}
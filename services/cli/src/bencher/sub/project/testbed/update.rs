@@ -1,5 +1,5 @@
 use bencher_client::types::JsonUpdateTestbed;
-use bencher_json::{ResourceId, ResourceName, Slug};
+use bencher_json::{project::testbed::JsonTestbedSpec, ResourceId, ResourceName, Slug};
 
 use crate::{
     bencher::{backend::AuthBackend, sub::SubCmd},
@@ -7,12 +7,15 @@ use crate::{
     CliError,
 };
 
+use super::{client_spec, testbed_spec};
+
 #[derive(Debug, Clone)]
 pub struct Update {
     pub project: ResourceId,
     pub testbed: ResourceId,
     pub name: Option<ResourceName>,
     pub slug: Option<Slug>,
+    pub spec: Option<JsonTestbedSpec>,
     pub archived: Option<bool>,
     pub backend: AuthBackend,
 }
@@ -26,6 +29,8 @@ impl TryFrom<CliTestbedUpdate> for Update {
             testbed,
             name,
             slug,
+            spec,
+            clear_spec,
             archived,
             backend,
         } = create;
@@ -34,6 +39,12 @@ impl TryFrom<CliTestbedUpdate> for Update {
             testbed,
             name,
             slug,
+            // An empty spec removes the current spec
+            spec: if clear_spec {
+                Some(JsonTestbedSpec::default())
+            } else {
+                testbed_spec(spec)
+            },
             archived: archived.into(),
             backend: backend.try_into()?,
         })
@@ -45,12 +56,14 @@ impl From<Update> for JsonUpdateTestbed {
         let Update {
             name,
             slug,
+            spec,
             archived,
             ..
         } = update;
         Self {
             name: name.map(Into::into),
             slug: slug.map(Into::into),
+            spec: spec.map(client_spec),
             archived,
         }
     }
@@ -0,0 +1,810 @@
+use std::fmt;
+
+use bencher_client::types::JsonNewThreshold;
+use bencher_json::{
+    Boundary, Decay, JsonThreshold, ModelTest, NameId, ResourceId, SampleSize, Window,
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+
+use super::{model::Model, ThresholdError};
+use crate::{
+    bencher::{
+        backend::{AuthBackend, CheckedClient},
+        sub::SubCmd,
+    },
+    cli_println,
+    parser::project::threshold::CliThresholdApply,
+    CliError, ExitClass,
+};
+
+// The maximum number of thresholds that can be listed per request
+const THRESHOLDS_PER_PAGE: u8 = u8::MAX;
+
+#[derive(Debug, Clone)]
+pub struct Apply {
+    pub project: ResourceId,
+    pub file: Utf8PathBuf,
+    pub dry_run: bool,
+    pub backend: AuthBackend,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ApplyError {
+    #[error("Failed to read threshold specification file ({path}): {err}")]
+    ReadFile {
+        path: Utf8PathBuf,
+        err: std::io::Error,
+    },
+    #[error("Unsupported threshold specification file extension ({0}). Expected `.yaml`, `.yml`, or `.toml`.")]
+    FileExtension(Utf8PathBuf),
+    #[error("Failed to parse YAML threshold specification file ({path}): {err}")]
+    ParseYaml {
+        path: Utf8PathBuf,
+        err: serde_yaml::Error,
+    },
+    #[error("Failed to parse TOML threshold specification file ({path}): {err}")]
+    ParseToml {
+        path: Utf8PathBuf,
+        err: toml::de::Error,
+    },
+    #[error("Invalid threshold model for {key}: {err}")]
+    BadModel { key: ThresholdKey, err: String },
+    #[error("Threshold {0} is specified more than once")]
+    Duplicate(ThresholdKey),
+    #[error("Failed to list thresholds: {0}")]
+    ListThresholds(crate::BackendError),
+    #[error("Failed to create threshold {key}: {err}")]
+    CreateThreshold {
+        key: ThresholdKey,
+        err: crate::BackendError,
+    },
+    #[error("Failed to update threshold {key}: {err}")]
+    UpdateThreshold {
+        key: ThresholdKey,
+        err: crate::BackendError,
+    },
+    #[error("Failed to delete threshold {key}: {err}")]
+    DeleteThreshold {
+        key: ThresholdKey,
+        err: crate::BackendError,
+    },
+}
+
+impl ApplyError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::ReadFile { .. } => ExitClass::Failure,
+            Self::FileExtension(_) | Self::BadModel { .. } | Self::Duplicate(_) => ExitClass::Usage,
+            Self::ParseYaml { .. } | Self::ParseToml { .. } => ExitClass::Parse,
+            Self::ListThresholds(err)
+            | Self::CreateThreshold { err, .. }
+            | Self::UpdateThreshold { err, .. }
+            | Self::DeleteThreshold { err, .. } => err.exit_class(),
+        }
+    }
+}
+
+/// A declarative threshold specification file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThresholdSpec {
+    thresholds: Vec<ThresholdSpecEntry>,
+}
+
+/// Each combination of branch, testbed, and measure gets its own threshold with the same model
+// The model fields are listed explicitly instead of flattening `bencher_json::Model`,
+// as `deny_unknown_fields` is not supported alongside `flatten`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThresholdSpecEntry {
+    branch: OneOrMany<NameId>,
+    testbed: OneOrMany<NameId>,
+    measure: OneOrMany<NameId>,
+    test: ModelTest,
+    min_sample_size: Option<SampleSize>,
+    max_sample_size: Option<SampleSize>,
+    window: Option<Window>,
+    lower_boundary: Option<Boundary>,
+    upper_boundary: Option<Boundary>,
+    decay: Option<Decay>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::One(one) => vec![one],
+            Self::Many(many) => many,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdKey {
+    branch: String,
+    testbed: String,
+    measure: String,
+}
+
+impl fmt::Display for ThresholdKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} / {} / {}", self.branch, self.testbed, self.measure)
+    }
+}
+
+#[derive(Debug)]
+struct DesiredThreshold {
+    branch: NameId,
+    testbed: NameId,
+    measure: NameId,
+    model: bencher_json::Model,
+}
+
+#[derive(Debug)]
+enum Change {
+    Create(DesiredThreshold),
+    Update {
+        threshold: Box<JsonThreshold>,
+        model: bencher_json::Model,
+    },
+    Delete(Box<JsonThreshold>),
+}
+
+impl TryFrom<CliThresholdApply> for Apply {
+    type Error = CliError;
+
+    fn try_from(apply: CliThresholdApply) -> Result<Self, Self::Error> {
+        let CliThresholdApply {
+            project,
+            file,
+            dry_run,
+            backend,
+        } = apply;
+        Ok(Self {
+            project,
+            file,
+            dry_run,
+            backend: AuthBackend::try_from(backend)?.log(false),
+        })
+    }
+}
+
+impl SubCmd for Apply {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner()
+            .await
+            .map_err(ThresholdError::Apply)
+            .map_err(Into::into)
+    }
+}
+
+impl Apply {
+    async fn exec_inner(&self) -> Result<(), ApplyError> {
+        let desired = read_spec(&self.file)?;
+        let client = self
+            .backend
+            .checked_client()
+            .await
+            .map_err(ApplyError::ListThresholds)?;
+        let existing = self.list_thresholds(&client).await?;
+        let plan = plan(desired, existing);
+
+        if plan.is_empty() {
+            cli_println!("Thresholds are up to date. No changes to apply.");
+            return Ok(());
+        }
+        cli_println!("Threshold plan for {}:", self.project);
+        for change in &plan {
+            cli_println!("{change}");
+        }
+
+        // If performing a dry run, don't actually apply the plan
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let thresholds = client.project(self.project.clone()).thresholds();
+        for change in plan {
+            match change {
+                Change::Create(desired) => {
+                    let key = desired.key();
+                    let model = model_into_client(&key, desired.model)?;
+                    let threshold = JsonNewThreshold {
+                        branch: desired.branch.into(),
+                        testbed: desired.testbed.into(),
+                        measure: desired.measure.into(),
+                        test: model.test,
+                        min_sample_size: model.min_sample_size,
+                        max_sample_size: model.max_sample_size,
+                        window: model.window,
+                        lower_boundary: model.lower_boundary,
+                        upper_boundary: model.upper_boundary,
+                        decay: model.decay,
+                    };
+                    thresholds.create(threshold).await.map_err(|err| {
+                        ApplyError::CreateThreshold {
+                            key,
+                            err: client.error(err),
+                        }
+                    })?;
+                },
+                Change::Update { threshold, model } => {
+                    let key = threshold_key(&threshold);
+                    let model = model_into_client(&key, model)?;
+                    thresholds
+                        .update(threshold.uuid, model.into())
+                        .await
+                        .map_err(|err| ApplyError::UpdateThreshold {
+                            key,
+                            err: client.error(err),
+                        })?;
+                },
+                Change::Delete(threshold) => {
+                    thresholds.delete(threshold.uuid).await.map_err(|err| {
+                        ApplyError::DeleteThreshold {
+                            key: threshold_key(&threshold),
+                            err: client.error(err),
+                        }
+                    })?;
+                },
+            }
+        }
+        cli_println!("Applied threshold plan for {}.", self.project);
+
+        Ok(())
+    }
+
+    async fn list_thresholds(
+        &self,
+        client: &CheckedClient<'_>,
+    ) -> Result<Vec<JsonThreshold>, ApplyError> {
+        let thresholds = client.project(self.project.clone()).thresholds();
+        let mut existing = Vec::new();
+        for page in 1.. {
+            let json_thresholds = thresholds
+                .list(THRESHOLDS_PER_PAGE, page)
+                .await
+                .map_err(|err| ApplyError::ListThresholds(client.error(err)))?;
+            let count = json_thresholds.0.len();
+            existing.extend(json_thresholds.0);
+            if count < usize::from(THRESHOLDS_PER_PAGE) {
+                break;
+            }
+        }
+        Ok(existing)
+    }
+}
+
+fn read_spec(file: &Utf8Path) -> Result<Vec<DesiredThreshold>, ApplyError> {
+    let spec_str = std::fs::read_to_string(file).map_err(|err| ApplyError::ReadFile {
+        path: file.to_owned(),
+        err,
+    })?;
+    let spec: ThresholdSpec = match file.extension() {
+        Some("yaml" | "yml") => {
+            serde_yaml::from_str(&spec_str).map_err(|err| ApplyError::ParseYaml {
+                path: file.to_owned(),
+                err,
+            })?
+        },
+        Some("toml") => toml::from_str(&spec_str).map_err(|err| ApplyError::ParseToml {
+            path: file.to_owned(),
+            err,
+        })?,
+        _ => return Err(ApplyError::FileExtension(file.to_owned())),
+    };
+
+    let mut desired: Vec<DesiredThreshold> = Vec::new();
+    for entry in spec.thresholds {
+        let ThresholdSpecEntry {
+            branch,
+            testbed,
+            measure,
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            decay,
+        } = entry;
+        let model = bencher_json::Model {
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            decay,
+        };
+        let (testbeds, measures) = (testbed.into_vec(), measure.into_vec());
+        for branch in branch.into_vec() {
+            for testbed in &testbeds {
+                for measure in &measures {
+                    let threshold = DesiredThreshold {
+                        branch: branch.clone(),
+                        testbed: testbed.clone(),
+                        measure: measure.clone(),
+                        model,
+                    };
+                    let key = threshold.key();
+                    model.validate().map_err(|err| ApplyError::BadModel {
+                        key: key.clone(),
+                        err: err.to_string(),
+                    })?;
+                    if desired.iter().any(|d| d.key() == key) {
+                        return Err(ApplyError::Duplicate(key));
+                    }
+                    desired.push(threshold);
+                }
+            }
+        }
+    }
+    Ok(desired)
+}
+
+// Match the desired thresholds to the existing thresholds.
+// Desired thresholds without a match are created,
+// matches with a different model are updated,
+// and existing thresholds without a match are deleted.
+fn plan(desired: Vec<DesiredThreshold>, existing: Vec<JsonThreshold>) -> Vec<Change> {
+    let mut existing = existing.into_iter().map(Some).collect::<Vec<_>>();
+    let mut plan = Vec::new();
+    for desired_threshold in desired {
+        let matched = existing.iter_mut().find(|threshold| {
+            threshold
+                .as_ref()
+                .is_some_and(|threshold| desired_threshold.matches(threshold))
+        });
+        match matched.and_then(Option::take) {
+            Some(threshold) => {
+                if threshold.model.map(json_model) != Some(desired_threshold.model) {
+                    plan.push(Change::Update {
+                        threshold: Box::new(threshold),
+                        model: desired_threshold.model,
+                    });
+                }
+            },
+            None => plan.push(Change::Create(desired_threshold)),
+        }
+    }
+    plan.extend(
+        existing
+            .into_iter()
+            .flatten()
+            .map(|threshold| Change::Delete(Box::new(threshold))),
+    );
+    plan
+}
+
+impl DesiredThreshold {
+    fn key(&self) -> ThresholdKey {
+        ThresholdKey {
+            branch: self.branch.to_string(),
+            testbed: self.testbed.to_string(),
+            measure: self.measure.to_string(),
+        }
+    }
+
+    fn matches(&self, threshold: &JsonThreshold) -> bool {
+        matches_name_id(
+            &self.branch,
+            &threshold.branch.uuid,
+            &threshold.branch.slug,
+            &threshold.branch.name,
+        ) && matches_name_id(
+            &self.testbed,
+            &threshold.testbed.uuid,
+            &threshold.testbed.slug,
+            &threshold.testbed.name,
+        ) && matches_name_id(
+            &self.measure,
+            &threshold.measure.uuid,
+            &threshold.measure.slug,
+            &threshold.measure.name,
+        )
+    }
+}
+
+fn matches_name_id<U, S, N>(name_id: &NameId, uuid: &U, slug: &S, name: &N) -> bool
+where
+    U: ToString,
+    S: AsRef<str>,
+    N: AsRef<str>,
+{
+    let name_id = name_id.as_ref();
+    name_id == uuid.to_string() || name_id == slug.as_ref() || name_id == name.as_ref()
+}
+
+fn threshold_key(threshold: &JsonThreshold) -> ThresholdKey {
+    ThresholdKey {
+        branch: threshold.branch.name.to_string(),
+        testbed: threshold.testbed.name.to_string(),
+        measure: threshold.measure.name.to_string(),
+    }
+}
+
+fn json_model(model: bencher_json::JsonModel) -> bencher_json::Model {
+    bencher_json::Model {
+        test: model.test,
+        min_sample_size: model.min_sample_size,
+        max_sample_size: model.max_sample_size,
+        window: model.window,
+        lower_boundary: model.lower_boundary,
+        upper_boundary: model.upper_boundary,
+        decay: model.decay,
+    }
+}
+
+fn model_into_client(key: &ThresholdKey, model: bencher_json::Model) -> Result<Model, ApplyError> {
+    model
+        .try_into()
+        .map_err(|err: ThresholdError| ApplyError::BadModel {
+            key: key.clone(),
+            err: err.to_string(),
+        })
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Create(desired) => {
+                write!(
+                    f,
+                    "+ create {}: {}",
+                    desired.key(),
+                    ModelDisplay(&desired.model)
+                )
+            },
+            Self::Update { threshold, model } => {
+                let current = threshold.model.map(json_model);
+                write!(
+                    f,
+                    "~ update {} ({}): {} -> {}",
+                    threshold_key(threshold),
+                    threshold.uuid,
+                    current.as_ref().map_or_else(
+                        || "no model".to_owned(),
+                        |current| ModelDisplay(current).to_string()
+                    ),
+                    ModelDisplay(model)
+                )
+            },
+            Self::Delete(threshold) => {
+                write!(
+                    f,
+                    "- delete {} ({})",
+                    threshold_key(threshold),
+                    threshold.uuid
+                )
+            },
+        }
+    }
+}
+
+struct ModelDisplay<'m>(&'m bencher_json::Model);
+
+impl fmt::Display for ModelDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bencher_json::Model {
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            decay,
+        } = self.0;
+        let test = match test {
+            ModelTest::Static => "static",
+            ModelTest::Percentage => "percentage",
+            ModelTest::ZScore => "z_score",
+            ModelTest::TTest => "t_test",
+            ModelTest::LogNormal => "log_normal",
+            ModelTest::Iqr => "iqr",
+            ModelTest::DeltaIqr => "delta_iqr",
+            ModelTest::Ewma => "ewma",
+        };
+        let params = [
+            min_sample_size.map(|v| format!("min_sample_size: {v}")),
+            max_sample_size.map(|v| format!("max_sample_size: {v}")),
+            window.map(|v| format!("window: {v}")),
+            lower_boundary.map(|v| format!("lower_boundary: {v}")),
+            upper_boundary.map(|v| format!("upper_boundary: {v}")),
+            decay.map(|v| format!("decay: {v}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        if params.is_empty() {
+            write!(f, "{test}")
+        } else {
+            write!(f, "{test} ({})", params.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{JsonThreshold, ModelTest};
+    use camino::Utf8PathBuf;
+    use pretty_assertions::assert_eq;
+
+    use super::{plan, read_spec, ApplyError, Change, DesiredThreshold};
+
+    fn spec_file(name: &str, contents: &str) -> Utf8PathBuf {
+        let dir = Utf8PathBuf::try_from(std::env::temp_dir()).unwrap();
+        let file = dir.join(format!(
+            "bencher_threshold_apply_{}_{name}",
+            std::process::id()
+        ));
+        std::fs::write(&file, contents).unwrap();
+        file
+    }
+
+    fn model(test: ModelTest) -> bencher_json::Model {
+        bencher_json::Model {
+            test,
+            min_sample_size: None,
+            max_sample_size: None,
+            window: None,
+            lower_boundary: None,
+            upper_boundary: None,
+            decay: None,
+        }
+    }
+
+    fn desired(branch: &str, testbed: &str, measure: &str, test: ModelTest) -> DesiredThreshold {
+        DesiredThreshold {
+            branch: branch.parse().unwrap(),
+            testbed: testbed.parse().unwrap(),
+            measure: measure.parse().unwrap(),
+            model: model(test),
+        }
+    }
+
+    fn existing(branch: &str, testbed: &str, measure: &str, test: &str) -> JsonThreshold {
+        const PROJECT: &str = "00000000-0000-0000-0000-000000000000";
+        const CREATED: &str = "2024-01-01T00:00:00Z";
+        let uuid = || uuid_for(&[branch, testbed, measure, test]);
+        serde_json::from_value(serde_json::json!({
+            "uuid": uuid(),
+            "project": PROJECT,
+            "branch": {
+                "uuid": uuid(),
+                "project": PROJECT,
+                "name": branch,
+                "slug": branch,
+                "head": {
+                    "uuid": uuid(),
+                    "start_point": null,
+                    "version": null,
+                    "created": CREATED,
+                    "replaced": null,
+                },
+                "created": CREATED,
+                "modified": CREATED,
+                "archived": null,
+                "frozen_baseline": null,
+            },
+            "testbed": {
+                "uuid": uuid(),
+                "project": PROJECT,
+                "name": testbed,
+                "slug": testbed,
+                "spec": null,
+                "created": CREATED,
+                "modified": CREATED,
+                "archived": null,
+            },
+            "measure": {
+                "uuid": uuid(),
+                "project": PROJECT,
+                "name": measure,
+                "slug": measure,
+                "units": "nanoseconds (ns)",
+                "created": CREATED,
+                "modified": CREATED,
+                "archived": null,
+            },
+            "model": {
+                "uuid": uuid(),
+                "test": test,
+                "min_sample_size": null,
+                "max_sample_size": null,
+                "window": null,
+                "lower_boundary": null,
+                "upper_boundary": null,
+                "decay": null,
+                "created": CREATED,
+                "replaced": null,
+            },
+            "created": CREATED,
+            "modified": CREATED,
+        }))
+        .unwrap()
+    }
+
+    // A stable UUID for each test threshold, so that they can be told apart
+    fn uuid_for(parts: &[&str]) -> String {
+        let hash = parts
+            .iter()
+            .flat_map(|part| part.bytes())
+            .fold(0u64, |hash, byte| {
+                hash.wrapping_mul(31).wrapping_add(u64::from(byte))
+            });
+        format!("00000000-0000-0000-0000-{:012x}", hash & 0xffff_ffff_ffff)
+    }
+
+    #[test]
+    fn test_read_spec_yaml() {
+        let file = spec_file(
+            "expand.yaml",
+            r"
+thresholds:
+  - branch: main
+    testbed: [localhost, ci]
+    measure: latency
+    test: t_test
+    upper_boundary: 0.99
+",
+        );
+        let desired = read_spec(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let keys = desired
+            .iter()
+            .map(|d| d.key().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["main / localhost / latency", "main / ci / latency"]);
+        for desired in desired {
+            assert_eq!(desired.model.test, ModelTest::TTest);
+            assert!(desired.model.upper_boundary.is_some());
+            assert!(desired.model.lower_boundary.is_none());
+        }
+    }
+
+    #[test]
+    fn test_read_spec_toml() {
+        let file = spec_file(
+            "expand.toml",
+            r#"
+[[thresholds]]
+branch = ["main", "develop"]
+testbed = "localhost"
+measure = "latency"
+test = "percentage"
+upper_boundary = 0.1
+"#,
+        );
+        let desired = read_spec(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        assert_eq!(desired.len(), 2);
+        assert!(desired
+            .iter()
+            .all(|d| d.model.test == ModelTest::Percentage));
+    }
+
+    #[test]
+    fn test_read_spec_unknown_key() {
+        let file = spec_file(
+            "unknown.yaml",
+            r"
+thresholds:
+  - branch: main
+    testbed: localhost
+    measure: latency
+    test: t_test
+    uper_boundary: 0.99
+",
+        );
+        let err = read_spec(&file).unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+        assert!(matches!(err, ApplyError::ParseYaml { .. }), "{err}");
+    }
+
+    #[test]
+    fn test_read_spec_duplicate() {
+        let file = spec_file(
+            "duplicate.yaml",
+            r"
+thresholds:
+  - branch: main
+    testbed: localhost
+    measure: latency
+    test: t_test
+  - branch: main
+    testbed: localhost
+    measure: latency
+    test: z_score
+",
+        );
+        let err = read_spec(&file).unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+        assert!(matches!(err, ApplyError::Duplicate(_)), "{err}");
+    }
+
+    #[test]
+    fn test_read_spec_bad_model() {
+        let file = spec_file(
+            "bad_model.yaml",
+            r"
+thresholds:
+  - branch: main
+    testbed: localhost
+    measure: latency
+    test: t_test
+    lower_boundary: 0.5
+    upper_boundary: 1.5
+",
+        );
+        let err = read_spec(&file).unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+        assert!(
+            matches!(
+                err,
+                ApplyError::BadModel { .. } | ApplyError::ParseYaml { .. }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_read_spec_extension() {
+        let file = spec_file("spec.json", "{}");
+        let err = read_spec(&file).unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+        assert!(matches!(err, ApplyError::FileExtension(_)), "{err}");
+    }
+
+    #[test]
+    fn test_plan() {
+        let desired = vec![
+            // Unchanged
+            desired("main", "localhost", "latency", ModelTest::TTest),
+            // Updated
+            desired("main", "localhost", "throughput", ModelTest::Ewma),
+            // Created
+            desired("main", "ci", "latency", ModelTest::ZScore),
+        ];
+        let existing = vec![
+            existing("main", "localhost", "latency", "t_test"),
+            existing("main", "localhost", "throughput", "t_test"),
+            // Deleted
+            existing("develop", "localhost", "latency", "t_test"),
+        ];
+
+        let plan = plan(desired, existing);
+        assert_eq!(plan.len(), 3);
+        assert!(matches!(
+            &plan[0],
+            Change::Update { threshold, model }
+                if threshold.measure.slug.as_ref() == "throughput" && model.test == ModelTest::Ewma
+        ));
+        assert!(matches!(
+            &plan[1],
+            Change::Create(desired) if desired.testbed.as_ref() == "ci"
+        ));
+        assert!(matches!(
+            &plan[2],
+            Change::Delete(threshold) if threshold.branch.slug.as_ref() == "develop"
+        ));
+    }
+
+    #[test]
+    fn test_plan_up_to_date() {
+        let plan = plan(
+            vec![desired("main", "localhost", "latency", ModelTest::TTest)],
+            vec![existing("main", "localhost", "latency", "t_test")],
+        );
+        assert!(plan.is_empty());
+    }
+}
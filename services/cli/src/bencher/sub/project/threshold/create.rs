@@ -67,6 +67,7 @@ impl From<Create> for JsonNewThreshold {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
         } = model;
         Self {
             branch: branch.into(),
@@ -78,6 +79,7 @@ impl From<Create> for JsonNewThreshold {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
         }
     }
 }
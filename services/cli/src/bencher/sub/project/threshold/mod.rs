@@ -1,9 +1,11 @@
-use crate::{bencher::sub::SubCmd, parser::project::threshold::CliThreshold, CliError};
+use crate::{bencher::sub::SubCmd, parser::project::threshold::CliThreshold, CliError, ExitClass};
 
+mod apply;
 mod create;
 mod delete;
 mod list;
 pub mod model;
+mod recompute;
 mod update;
 mod view;
 
@@ -14,6 +16,8 @@ pub enum Threshold {
     View(view::View),
     Update(update::Update),
     Delete(delete::Delete),
+    Apply(apply::Apply),
+    Recompute(recompute::Recompute),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -22,6 +26,17 @@ pub enum ThresholdError {
     NoProject,
     #[error("Invalid threshold model: {0}")]
     BadModel(bencher_json::ValidError),
+    #[error("{0}")]
+    Apply(#[from] apply::ApplyError),
+}
+
+impl ThresholdError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::NoProject | Self::BadModel(_) => ExitClass::Usage,
+            Self::Apply(err) => err.exit_class(),
+        }
+    }
 }
 
 impl TryFrom<CliThreshold> for Threshold {
@@ -34,6 +49,8 @@ impl TryFrom<CliThreshold> for Threshold {
             CliThreshold::View(view) => Self::View(view.try_into()?),
             CliThreshold::Update(update) => Self::Update(update.try_into()?),
             CliThreshold::Delete(delete) => Self::Delete(delete.try_into()?),
+            CliThreshold::Apply(apply) => Self::Apply(apply.try_into()?),
+            CliThreshold::Recompute(recompute) => Self::Recompute(recompute.try_into()?),
         })
     }
 }
@@ -46,6 +63,8 @@ impl SubCmd for Threshold {
             Self::View(view) => view.exec().await,
             Self::Update(update) => update.exec().await,
             Self::Delete(delete) => delete.exec().await,
+            Self::Apply(apply) => apply.exec().await,
+            Self::Recompute(recompute) => recompute.exec().await,
         }
     }
 }
@@ -1,4 +1,6 @@
-use bencher_client::types::{Boundary, ModelTest, SampleSize, Window};
+use bencher_client::types::{
+    Boundary, Decay, JsonUpdateModel, JsonUpdateThreshold, ModelTest, SampleSize, Window,
+};
 
 use crate::parser::project::threshold::{CliModel, CliModelTest};
 
@@ -12,6 +14,7 @@ pub struct Model {
     pub window: Option<Window>,
     pub lower_boundary: Option<Boundary>,
     pub upper_boundary: Option<Boundary>,
+    pub decay: Option<Decay>,
 }
 
 impl TryFrom<CliModel> for Model {
@@ -25,6 +28,7 @@ impl TryFrom<CliModel> for Model {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
         } = model;
         bencher_json::Model {
             test: test.into(),
@@ -33,9 +37,26 @@ impl TryFrom<CliModel> for Model {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
         }
-        .validate()
-        .map_err(ThresholdError::BadModel)?;
+        .try_into()
+    }
+}
+
+impl TryFrom<bencher_json::Model> for Model {
+    type Error = ThresholdError;
+
+    fn try_from(model: bencher_json::Model) -> Result<Self, Self::Error> {
+        model.validate().map_err(ThresholdError::BadModel)?;
+        let bencher_json::Model {
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            decay,
+        } = model;
         Ok(Self {
             test: test.into(),
             min_sample_size: min_sample_size.map(Into::into),
@@ -43,6 +64,7 @@ impl TryFrom<CliModel> for Model {
             window: window.map(Into::into),
             lower_boundary: lower_boundary.map(Into::into),
             upper_boundary: upper_boundary.map(Into::into),
+            decay: decay.map(Into::into),
         })
     }
 }
@@ -57,6 +79,7 @@ impl From<CliModelTest> for bencher_json::ModelTest {
             CliModelTest::LogNormal => Self::LogNormal,
             CliModelTest::Iqr => Self::Iqr,
             CliModelTest::DeltaIqr => Self::DeltaIqr,
+            CliModelTest::Ewma => Self::Ewma,
         }
     }
 }
@@ -71,6 +94,7 @@ impl From<CliModelTest> for ModelTest {
             CliModelTest::LogNormal => Self::LogNormal,
             CliModelTest::Iqr => Self::Iqr,
             CliModelTest::DeltaIqr => Self::DeltaIqr,
+            CliModelTest::Ewma => Self::Ewma,
         }
     }
 }
@@ -84,6 +108,7 @@ impl From<Model> for bencher_client::types::Model {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
         } = model;
         #[allow(clippy::inconsistent_struct_constructor)]
         bencher_client::types::Model {
@@ -93,6 +118,34 @@ impl From<Model> for bencher_client::types::Model {
             window,
             lower_boundary,
             upper_boundary,
+            decay,
+        }
+    }
+}
+
+impl From<Model> for JsonUpdateThreshold {
+    fn from(model: Model) -> Self {
+        let Model {
+            test,
+            min_sample_size,
+            max_sample_size,
+            window,
+            lower_boundary,
+            upper_boundary,
+            decay,
+        } = model;
+        #[allow(clippy::inconsistent_struct_constructor)]
+        Self {
+            subtype_0: Some(JsonUpdateModel {
+                test,
+                min_sample_size,
+                max_sample_size,
+                window,
+                lower_boundary,
+                upper_boundary,
+                decay,
+            }),
+            subtype_1: None,
         }
     }
 }
@@ -0,0 +1,70 @@
+use bencher_client::types::JsonNewRecompute;
+use bencher_json::{DateTime, ResourceId, ThresholdUuid};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::project::threshold::CliThresholdRecompute,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Recompute {
+    pub project: ResourceId,
+    pub threshold: ThresholdUuid,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliThresholdRecompute> for Recompute {
+    type Error = CliError;
+
+    fn try_from(recompute: CliThresholdRecompute) -> Result<Self, Self::Error> {
+        let CliThresholdRecompute {
+            project,
+            threshold,
+            start_time,
+            end_time,
+            backend,
+        } = recompute;
+        Ok(Self {
+            project,
+            threshold,
+            start_time,
+            end_time,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Recompute> for JsonNewRecompute {
+    fn from(recompute: Recompute) -> Self {
+        let Recompute {
+            start_time,
+            end_time,
+            ..
+        } = recompute;
+        Self {
+            start_time: start_time.map(Into::into),
+            end_time: end_time.map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Recompute {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .proj_threshold_recompute_post()
+                    .project(self.project.clone())
+                    .threshold(self.threshold)
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
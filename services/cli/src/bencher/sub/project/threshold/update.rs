@@ -1,4 +1,4 @@
-use bencher_client::types::{JsonRemoveModel, JsonUpdateModel, JsonUpdateThreshold};
+use bencher_client::types::{JsonRemoveModel, JsonUpdateThreshold};
 use bencher_json::{ResourceId, ThresholdUuid};
 
 use super::model::Model;
@@ -31,6 +31,7 @@ impl TryFrom<CliThresholdUpdate> for Update {
                     window,
                     lower_boundary,
                     upper_boundary,
+                    decay,
                     remove_model,
                 },
             backend,
@@ -44,6 +45,7 @@ impl TryFrom<CliThresholdUpdate> for Update {
                 window,
                 lower_boundary,
                 upper_boundary,
+                decay,
             };
             Some(cli_model.try_into()?)
         } else if remove_model {
@@ -65,26 +67,7 @@ impl From<Update> for JsonUpdateThreshold {
     fn from(update: Update) -> Self {
         let Update { model, .. } = update;
         if let Some(model) = model {
-            let Model {
-                test,
-                min_sample_size,
-                max_sample_size,
-                window,
-                lower_boundary,
-                upper_boundary,
-            } = model;
-            #[allow(clippy::inconsistent_struct_constructor)]
-            Self {
-                subtype_0: Some(JsonUpdateModel {
-                    test,
-                    min_sample_size,
-                    max_sample_size,
-                    window,
-                    lower_boundary,
-                    upper_boundary,
-                }),
-                subtype_1: None,
-            }
+            model.into()
         } else {
             Self {
                 subtype_0: None,
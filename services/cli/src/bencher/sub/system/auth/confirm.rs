@@ -1,8 +1,9 @@
 use bencher_client::types::JsonConfirm;
-use bencher_json::Jwt;
+use bencher_json::{JsonAuthUser, Jwt};
 
 use crate::{
-    bencher::{backend::PubBackend, sub::SubCmd},
+    bencher::{backend::PubBackend, credential::CredentialStore, sub::SubCmd},
+    cli_println,
     parser::system::auth::CliAuthConfirm,
     CliError,
 };
@@ -10,6 +11,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Confirm {
     pub token: Jwt,
+    pub credential: CredentialStore,
     pub backend: PubBackend,
 }
 
@@ -19,10 +21,12 @@ impl TryFrom<CliAuthConfirm> for Confirm {
     fn try_from(confirm: CliAuthConfirm) -> Result<Self, Self::Error> {
         let CliAuthConfirm {
             confirm: token,
+            credential,
             backend,
         } = confirm;
         Ok(Self {
             token,
+            credential: CredentialStore::new(credential.no_keychain),
             backend: backend.try_into()?,
         })
     }
@@ -39,12 +43,19 @@ impl From<Confirm> for JsonConfirm {
 
 impl SubCmd for Confirm {
     async fn exec(&self) -> Result<(), CliError> {
-        let _json = self
+        let json_auth_user: JsonAuthUser = self
             .backend
-            .send(
-                |client| async move { client.auth_confirm_post().body(self.clone()).send().await },
-            )
+            .send_with(|client| async move {
+                client.auth_confirm_post().body(self.clone()).send().await
+            })
             .await?;
+        // Store the user token, so it does not have to be set for every command
+        let host = &self.backend.client().host;
+        self.credential.save(host, &json_auth_user.token)?;
+        cli_println!(
+            "Stored the API token for {host} in {store}",
+            store = self.credential
+        );
         Ok(())
     }
 }
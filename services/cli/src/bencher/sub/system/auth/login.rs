@@ -4,17 +4,23 @@ use bencher_client::types::PlanLevel;
 use bencher_json::{Email, Jwt};
 
 use crate::{
-    bencher::{backend::PubBackend, sub::SubCmd},
+    bencher::{
+        backend::{BackendError, PubBackend},
+        credential::CredentialStore,
+        sub::SubCmd,
+    },
+    cli_println,
     parser::system::auth::CliAuthLogin,
     CliError,
 };
 
 #[derive(Debug, Clone)]
 pub struct Login {
-    pub email: Email,
+    pub email: Option<Email>,
     #[cfg(feature = "plus")]
     pub plan: Option<PlanLevel>,
     pub invite: Option<Jwt>,
+    pub credential: CredentialStore,
     pub backend: PubBackend,
 }
 
@@ -27,6 +33,7 @@ impl TryFrom<CliAuthLogin> for Login {
             #[cfg(feature = "plus")]
             plan,
             invite,
+            credential,
             backend,
         } = login;
         Ok(Self {
@@ -34,36 +41,51 @@ impl TryFrom<CliAuthLogin> for Login {
             #[cfg(feature = "plus")]
             plan: plan.map(Into::into),
             invite,
+            credential: CredentialStore::new(credential.no_keychain),
             backend: backend.try_into()?,
         })
     }
 }
 
-impl From<Login> for JsonLogin {
-    fn from(login: Login) -> Self {
-        let Login {
-            email,
-            #[cfg(feature = "plus")]
-            plan,
-            invite,
-            ..
-        } = login;
-        Self {
-            email: email.into(),
+impl Login {
+    fn json_login(&self, email: &Email) -> JsonLogin {
+        JsonLogin {
+            email: email.clone().into(),
             #[cfg(feature = "plus")]
-            plan,
+            plan: self.plan,
             #[cfg(not(feature = "plus"))]
             plan: None,
-            invite: invite.map(Into::into),
+            invite: self.invite.clone().map(Into::into),
         }
     }
+
+    /// Store the given API token, so it does not have to be set for every command
+    fn store_token(&self) -> Result<(), CliError> {
+        let client = self.backend.client();
+        let token = client.token.as_ref().ok_or(BackendError::NoToken)?;
+        self.credential.save(&client.host, token)?;
+        cli_println!(
+            "Stored the API token for {host} in {store}",
+            host = client.host,
+            store = self.credential
+        );
+        Ok(())
+    }
 }
 
 impl SubCmd for Login {
     async fn exec(&self) -> Result<(), CliError> {
+        let Some(email) = &self.email else {
+            // An API token is required if there is no email
+            return self.store_token();
+        };
+        let json_login = self.json_login(email);
         let _json = self
             .backend
-            .send(|client| async move { client.auth_login_post().body(self.clone()).send().await })
+            .send(|client| {
+                let json_login = json_login.clone();
+                async move { client.auth_login_post().body(json_login).send().await }
+            })
             .await?;
         Ok(())
     }
@@ -0,0 +1,43 @@
+use crate::{
+    bencher::{backend::BackendError, credential::CredentialStore, sub::SubCmd},
+    cli_println,
+    parser::system::auth::CliAuthLogout,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Logout {
+    pub host: url::Url,
+}
+
+impl TryFrom<CliAuthLogout> for Logout {
+    type Error = CliError;
+
+    fn try_from(logout: CliAuthLogout) -> Result<Self, Self::Error> {
+        let CliAuthLogout { host } = logout;
+        Ok(Self {
+            host: host.try_into().map_err(BackendError::ParseHost)?,
+        })
+    }
+}
+
+impl SubCmd for Logout {
+    async fn exec(&self) -> Result<(), CliError> {
+        // Remove the API token from both stores,
+        // regardless of which one it was originally stored in
+        let mut deleted = false;
+        for store in [CredentialStore::Keychain, CredentialStore::File] {
+            if store.delete(&self.host)? {
+                cli_println!(
+                    "Removed the API token for {host} from {store}",
+                    host = self.host
+                );
+                deleted = true;
+            }
+        }
+        if !deleted {
+            cli_println!("No API token is stored for {host}", host = self.host);
+        }
+        Ok(())
+    }
+}
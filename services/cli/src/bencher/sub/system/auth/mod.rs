@@ -3,11 +3,13 @@ use crate::{bencher::sub::SubCmd, parser::system::auth::CliAuth, CliError};
 mod accept;
 mod confirm;
 mod login;
+mod logout;
 mod signup;
 
 use accept::Accept;
 use confirm::Confirm;
 use login::Login;
+use logout::Logout;
 use signup::Signup;
 
 #[derive(Debug)]
@@ -15,6 +17,7 @@ pub enum Auth {
     Signup(Signup),
     Login(Login),
     Confirm(Confirm),
+    Logout(Logout),
     Accept(Accept),
 }
 
@@ -26,6 +29,7 @@ impl TryFrom<CliAuth> for Auth {
             CliAuth::Signup(signup) => Self::Signup(signup.try_into()?),
             CliAuth::Login(login) => Self::Login(login.try_into()?),
             CliAuth::Confirm(confirm) => Self::Confirm(confirm.try_into()?),
+            CliAuth::Logout(logout) => Self::Logout(logout.try_into()?),
             CliAuth::Accept(accept) => Self::Accept(accept.try_into()?),
         })
     }
@@ -37,6 +41,7 @@ impl SubCmd for Auth {
             Self::Signup(signup) => signup.exec().await,
             Self::Login(login) => login.exec().await,
             Self::Confirm(confirm) => confirm.exec().await,
+            Self::Logout(logout) => logout.exec().await,
             Self::Accept(accept) => accept.exec().await,
         }
     }
@@ -0,0 +1,61 @@
+use bencher_client::types::JsonNewDownsample;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::system::server::CliDownsample,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Downsample {
+    pub after_months: Option<u32>,
+    pub dry_run: Option<bool>,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliDownsample> for Downsample {
+    type Error = CliError;
+
+    fn try_from(downsample: CliDownsample) -> Result<Self, Self::Error> {
+        let CliDownsample {
+            after_months,
+            dry_run,
+            backend,
+        } = downsample;
+        Ok(Self {
+            after_months,
+            dry_run: Some(dry_run),
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Downsample> for JsonNewDownsample {
+    fn from(downsample: Downsample) -> Self {
+        let Downsample {
+            after_months,
+            dry_run,
+            ..
+        } = downsample;
+        Self {
+            after_months,
+            dry_run,
+        }
+    }
+}
+
+impl SubCmd for Downsample {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json = self
+            .backend
+            .send(|client| async move {
+                client
+                    .server_downsample_post()
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
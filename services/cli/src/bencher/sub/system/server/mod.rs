@@ -2,18 +2,29 @@ use crate::{bencher::sub::SubCmd, parser::system::server::CliServer, CliError};
 
 mod backup;
 mod config;
+mod downsample;
+mod rbac;
 mod restart;
+mod secret;
+mod smoke;
 mod spec;
 mod stats;
 mod version;
 
+pub use rbac::RbacError;
+pub use smoke::SmokeError;
+
 #[derive(Debug)]
 pub enum Server {
     Version(version::Version),
     Spec(spec::Spec),
     Restart(restart::Restart),
     Config(config::Config),
+    Secret(secret::Secret),
     Backup(backup::Backup),
+    Downsample(downsample::Downsample),
+    Smoke(smoke::Smoke),
+    Rbac(rbac::Rbac),
     #[cfg(feature = "plus")]
     Stats(stats::ServerStats),
 }
@@ -27,7 +38,11 @@ impl TryFrom<CliServer> for Server {
             CliServer::Spec(spec) => Self::Spec(spec.try_into()?),
             CliServer::Restart(restart) => Self::Restart(restart.try_into()?),
             CliServer::Config(config) => Self::Config(config.try_into()?),
+            CliServer::Secret(secret) => Self::Secret(secret.try_into()?),
             CliServer::Backup(backup) => Self::Backup(backup.try_into()?),
+            CliServer::Downsample(downsample) => Self::Downsample(downsample.try_into()?),
+            CliServer::Smoke(smoke) => Self::Smoke(smoke.try_into()?),
+            CliServer::Rbac(rbac) => Self::Rbac(rbac.try_into()?),
             #[cfg(feature = "plus")]
             CliServer::Stats(stats) => Self::Stats(stats.try_into()?),
         })
@@ -41,7 +56,11 @@ impl SubCmd for Server {
             Self::Spec(spec) => spec.exec().await,
             Self::Restart(restart) => restart.exec().await,
             Self::Config(config) => config.exec().await,
+            Self::Secret(secret) => secret.exec().await,
             Self::Backup(backup) => backup.exec().await,
+            Self::Downsample(downsample) => downsample.exec().await,
+            Self::Smoke(smoke) => smoke.exec().await,
+            Self::Rbac(rbac) => rbac.exec().await,
             #[cfg(feature = "plus")]
             Self::Stats(stats) => stats.exec().await,
         }
@@ -0,0 +1,93 @@
+use bencher_json::JsonRbac;
+use tabled::Table;
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    cli_println,
+    parser::system::server::CliRbac,
+    CliError, ExitClass,
+};
+
+#[derive(Debug, Clone)]
+pub struct Rbac {
+    pub table: bool,
+    pub check: bool,
+    pub backend: PubBackend,
+}
+
+#[allow(clippy::absolute_paths)]
+#[derive(thiserror::Error, Debug)]
+pub enum RbacError {
+    #[error("Failed to get server RBAC permission matrix: {0}")]
+    GetRbac(crate::BackendError),
+    #[error(
+        "{count} endpoint(s) are missing from the server RBAC permission matrix:\n{endpoints}"
+    )]
+    Unmapped { count: usize, endpoints: String },
+}
+
+impl RbacError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::GetRbac(err) => err.exit_class(),
+            Self::Unmapped { .. } => ExitClass::Failure,
+        }
+    }
+}
+
+impl TryFrom<CliRbac> for Rbac {
+    type Error = CliError;
+
+    fn try_from(rbac: CliRbac) -> Result<Self, Self::Error> {
+        let CliRbac {
+            table,
+            check,
+            backend,
+        } = rbac;
+        Ok(Self {
+            table,
+            check,
+            backend: PubBackend::try_from(backend)?.log(!table),
+        })
+    }
+}
+
+impl SubCmd for Rbac {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl Rbac {
+    async fn exec_inner(&self) -> Result<(), RbacError> {
+        let json_rbac: JsonRbac = self
+            .backend
+            .send_with(|client| async move { client.server_rbac_get().send().await })
+            .await
+            .map_err(RbacError::GetRbac)?;
+
+        let unmapped = json_rbac
+            .unmapped()
+            .map(|endpoint| {
+                format!(
+                    "{method} {path} ({operation_id})",
+                    method = endpoint.method,
+                    path = endpoint.path,
+                    operation_id = endpoint.operation_id
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if self.table {
+            cli_println!("{}", Table::from(json_rbac));
+        }
+
+        if self.check && !unmapped.is_empty() {
+            return Err(RbacError::Unmapped {
+                count: unmapped.len(),
+                endpoints: unmapped.join("\n"),
+            });
+        }
+        Ok(())
+    }
+}
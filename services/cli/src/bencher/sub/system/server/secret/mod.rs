@@ -0,0 +1,26 @@
+use crate::{bencher::sub::SubCmd, parser::system::server::CliSecret, CliError};
+
+mod rotate;
+
+#[derive(Debug)]
+pub enum Secret {
+    Rotate(rotate::Rotate),
+}
+
+impl TryFrom<CliSecret> for Secret {
+    type Error = CliError;
+
+    fn try_from(secret: CliSecret) -> Result<Self, Self::Error> {
+        Ok(match secret {
+            CliSecret::Rotate(rotate) => Self::Rotate(rotate.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Secret {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::Rotate(rotate) => rotate.exec().await,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use bencher_client::types::JsonRotateSecret;
+use bencher_json::JsonSecretRotated;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::system::server::CliSecretRotate,
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Rotate {
+    pub grace_period: Option<u32>,
+    pub delay: u64,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliSecretRotate> for Rotate {
+    type Error = CliError;
+
+    fn try_from(rotate: CliSecretRotate) -> Result<Self, Self::Error> {
+        let CliSecretRotate {
+            grace_period,
+            delay,
+            backend,
+        } = rotate;
+        Ok(Self {
+            grace_period,
+            delay,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<Rotate> for JsonRotateSecret {
+    fn from(rotate: Rotate) -> Self {
+        let Rotate {
+            grace_period,
+            delay,
+            ..
+        } = rotate;
+        Self {
+            grace_period,
+            delay: Some(delay),
+        }
+    }
+}
+
+impl SubCmd for Rotate {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json: JsonSecretRotated = self
+            .backend
+            .send_with(|client| async move {
+                client
+                    .server_secret_rotate_post()
+                    .body(self.clone())
+                    .send()
+                    .await
+            })
+            .await?;
+        Ok(())
+    }
+}
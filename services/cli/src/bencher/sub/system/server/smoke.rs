@@ -0,0 +1,439 @@
+use bencher_client::types::{
+    Adapter, JsonNewOrganization, JsonNewProject, JsonNewReport, JsonNewTestbed, JsonNewThreshold,
+    JsonReportSettings, ModelTest,
+};
+use bencher_json::{
+    project::{
+        branch::BRANCH_MAIN_STR,
+        measure::built_in::{generic::Latency, BuiltInMeasure},
+    },
+    ApiErrorKind, BencherResource, Boundary, DateTime, JsonOrganization, JsonPerfQuery,
+    JsonProject, JsonReport, JsonTestbed, MetricsBuilder, NameId, ResourceId, ResourceName, Slug,
+};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    cli_println,
+    parser::system::server::CliSmoke,
+    CliError, ExitClass,
+};
+
+const SMOKE_NAME: &str = "Bencher Smoke Test";
+const SMOKE_SLUG_PREFIX: &str = "bencher-smoke";
+const SMOKE_TESTBED: &str = "smoke";
+const SMOKE_BENCHMARK: &str = "bencher::smoke";
+// The mock metric value is well above the static threshold upper boundary,
+// so the report must generate an alert.
+const SMOKE_VALUE: f64 = 1_000.0;
+const SMOKE_UPPER_BOUNDARY: f64 = 100.0;
+
+#[derive(Debug, Clone)]
+pub struct Smoke {
+    pub keep: bool,
+    pub backend: AuthBackend,
+}
+
+#[allow(clippy::absolute_paths)]
+#[derive(thiserror::Error, Debug)]
+pub enum SmokeError {
+    #[error("Failed to generate smoke test slug: {0}")]
+    Slug(bencher_json::ValidError),
+    #[error("Failed to generate smoke test name: {0}")]
+    Name(bencher_json::ValidError),
+    #[error("Failed to generate smoke test threshold boundary: {0}")]
+    Boundary(bencher_json::ValidError),
+    #[error("Failed to generate smoke test results: {0}")]
+    Results(bencher_json::MetricsBuilderError),
+    #[error("Failed to serialize smoke test results: {0}")]
+    SerializeResults(serde_json::Error),
+    #[error("Failed to create smoke test organization: {0}")]
+    CreateOrganization(crate::BackendError),
+    #[error("Failed to create smoke test project: {0}")]
+    CreateProject(crate::BackendError),
+    #[error("Failed to create smoke test testbed: {0}")]
+    CreateTestbed(crate::BackendError),
+    #[error("Failed to create smoke test threshold: {0}")]
+    CreateThreshold(crate::BackendError),
+    #[error("Failed to submit smoke test report: {0}")]
+    CreateReport(crate::BackendError),
+    #[error("Smoke test report ({0}) has no results")]
+    NoResults(bencher_json::ReportUuid),
+    #[error("Smoke test report ({0}) did not generate an alert for the static threshold")]
+    NoAlert(bencher_json::ReportUuid),
+    #[error("Failed to query smoke test perf: {0}")]
+    Perf(crate::BackendError),
+    #[error("Smoke test perf query did not return the metric for report ({0})")]
+    NoPerfMetric(bencher_json::ReportUuid),
+    #[error("Failed to tear down smoke test organization ({organization}): {err}")]
+    Teardown {
+        organization: Slug,
+        err: crate::BackendError,
+    },
+    #[error("{err}\n{teardown}")]
+    FailedTeardown {
+        err: Box<SmokeError>,
+        teardown: Box<SmokeError>,
+    },
+}
+
+impl SmokeError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::CreateOrganization(err)
+            | Self::CreateProject(err)
+            | Self::CreateTestbed(err)
+            | Self::CreateThreshold(err)
+            | Self::CreateReport(err)
+            | Self::Perf(err)
+            | Self::Teardown { err, .. } => err.exit_class(),
+            Self::FailedTeardown { err, .. } => err.exit_class(),
+            Self::Slug(_)
+            | Self::Name(_)
+            | Self::Boundary(_)
+            | Self::Results(_)
+            | Self::SerializeResults(_)
+            | Self::NoResults(_)
+            | Self::NoAlert(_)
+            | Self::NoPerfMetric(_) => ExitClass::Failure,
+        }
+    }
+}
+
+impl TryFrom<CliSmoke> for Smoke {
+    type Error = CliError;
+
+    fn try_from(smoke: CliSmoke) -> Result<Self, Self::Error> {
+        let CliSmoke { keep, backend } = smoke;
+        Ok(Self {
+            keep,
+            backend: AuthBackend::try_from(backend)?.log(false),
+        })
+    }
+}
+
+impl SubCmd for Smoke {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl Smoke {
+    async fn exec_inner(&self) -> Result<(), SmokeError> {
+        let json_organization = self.create_organization().await?;
+        cli_println!(
+            "Created smoke test organization: {}",
+            json_organization.slug
+        );
+
+        let result = self.smoke_test(&json_organization).await;
+
+        if self.keep {
+            cli_println!(
+                "Keeping smoke test organization: {}",
+                json_organization.slug
+            );
+            return result;
+        }
+        let teardown = self.teardown(&json_organization).await;
+        match (result, teardown) {
+            (Ok(()), Ok(())) => {
+                cli_println!("Smoke test passed");
+                Ok(())
+            },
+            (Err(err), Ok(())) | (Ok(()), Err(err)) => Err(err),
+            (Err(err), Err(teardown)) => Err(SmokeError::FailedTeardown {
+                err: Box::new(err),
+                teardown: Box::new(teardown),
+            }),
+        }
+    }
+
+    async fn smoke_test(&self, json_organization: &JsonOrganization) -> Result<(), SmokeError> {
+        let json_project = self.create_project(json_organization).await?;
+        cli_println!("Created smoke test project: {}", json_project.slug);
+
+        let json_testbed = self.create_testbed(&json_project).await?;
+        cli_println!("Created smoke test testbed: {}", json_testbed.slug);
+
+        self.create_threshold(&json_project, &json_testbed).await?;
+        cli_println!("Created smoke test static threshold");
+
+        let json_report = self.create_report(&json_project, &json_testbed).await?;
+        cli_println!("Submitted smoke test report: {}", json_report.uuid);
+
+        // The mock metric must exceed the static threshold upper boundary
+        if json_report.alerts.is_empty() {
+            return Err(SmokeError::NoAlert(json_report.uuid));
+        }
+        cli_println!(
+            "Smoke test report generated {} alert(s)",
+            json_report.alerts.len()
+        );
+
+        self.query_perf(&json_project, &json_report).await?;
+        cli_println!("Queried smoke test perf");
+
+        Ok(())
+    }
+
+    async fn create_organization(&self) -> Result<JsonOrganization, SmokeError> {
+        // Use a unique slug, so concurrent or abandoned smoke tests do not conflict
+        let slug: Slug = format!(
+            "{SMOKE_SLUG_PREFIX}-{timestamp}-{nonce:08x}",
+            timestamp = DateTime::now().timestamp(),
+            nonce = rand::random::<u32>()
+        )
+        .parse()
+        .map_err(SmokeError::Slug)?;
+        let json_new_organization = JsonNewOrganization {
+            name: smoke_name()?.into(),
+            slug: Some(slug.into()),
+        };
+        self.backend
+            .send_with(|client| {
+                let json_new_organization = json_new_organization.clone();
+                async move {
+                    client
+                        .organization_post()
+                        .body(json_new_organization)
+                        .send()
+                        .await
+                }
+            })
+            .await
+            .map_err(SmokeError::CreateOrganization)
+    }
+
+    async fn create_project(
+        &self,
+        json_organization: &JsonOrganization,
+    ) -> Result<JsonProject, SmokeError> {
+        let json_new_project = JsonNewProject {
+            name: smoke_name()?.into(),
+            slug: Some(json_organization.slug.clone().into()),
+            url: None,
+            visibility: None,
+            time_zone: None,
+            week_start: None,
+            strict: None,
+        };
+        self.backend
+            .send_with(|client| {
+                let json_new_project = json_new_project.clone();
+                async move {
+                    client
+                        .org_project_post()
+                        .organization(ResourceId::from(json_organization.uuid))
+                        .body(json_new_project)
+                        .send()
+                        .await
+                }
+            })
+            .await
+            .map_err(SmokeError::CreateProject)
+    }
+
+    async fn create_testbed(&self, json_project: &JsonProject) -> Result<JsonTestbed, SmokeError> {
+        let json_new_testbed = JsonNewTestbed {
+            name: SMOKE_TESTBED
+                .parse::<ResourceName>()
+                .map_err(SmokeError::Name)?
+                .into(),
+            slug: None,
+            spec: None,
+        };
+        self.backend
+            .send_with(|client| {
+                let json_new_testbed = json_new_testbed.clone();
+                async move {
+                    client
+                        .proj_testbed_post()
+                        .project(ResourceId::from(json_project.uuid))
+                        .body(json_new_testbed)
+                        .send()
+                        .await
+                }
+            })
+            .await
+            .map_err(SmokeError::CreateTestbed)
+    }
+
+    async fn create_threshold(
+        &self,
+        json_project: &JsonProject,
+        json_testbed: &JsonTestbed,
+    ) -> Result<(), SmokeError> {
+        let upper_boundary =
+            Boundary::try_from(SMOKE_UPPER_BOUNDARY).map_err(SmokeError::Boundary)?;
+        let client = self
+            .backend
+            .checked_client()
+            .await
+            .map_err(SmokeError::CreateThreshold)?;
+        client
+            .project(json_project.uuid)
+            .thresholds()
+            .create(JsonNewThreshold {
+                branch: main_branch()?.into(),
+                testbed: NameId::from(json_testbed.uuid).into(),
+                measure: Latency::name_id().into(),
+                test: ModelTest::Static,
+                min_sample_size: None,
+                max_sample_size: None,
+                window: None,
+                lower_boundary: None,
+                upper_boundary: Some(upper_boundary.into()),
+                decay: None,
+            })
+            .await
+            .map_err(|err| SmokeError::CreateThreshold(client.error(err)))?;
+        Ok(())
+    }
+
+    async fn create_report(
+        &self,
+        json_project: &JsonProject,
+        json_testbed: &JsonTestbed,
+    ) -> Result<JsonReport, SmokeError> {
+        let results = MetricsBuilder::new()
+            .benchmark(SMOKE_BENCHMARK)
+            .measure(Latency::SLUG_STR, SMOKE_VALUE)
+            .build()
+            .map_err(SmokeError::Results)?;
+        let results = serde_json::to_string(&results).map_err(SmokeError::SerializeResults)?;
+
+        let start_time = DateTime::now();
+        let client = self
+            .backend
+            .checked_client()
+            .await
+            .map_err(SmokeError::CreateReport)?;
+        client
+            .project(json_project.uuid)
+            .reports()
+            .create(JsonNewReport {
+                branch: main_branch()?.into(),
+                hash: None,
+                start_point: None,
+                reference_branch: None,
+                testbed: NameId::from(json_testbed.uuid).into(),
+                thresholds: None,
+                start_time: start_time.into(),
+                end_time: DateTime::now().into(),
+                tag: None,
+                results: vec![results],
+                settings: Some(JsonReportSettings {
+                    adapter: Some(Adapter::Json),
+                    average: None,
+                    fold: None,
+                    dispersion: None,
+                }),
+                strict: None,
+                warmup: None,
+                notes: None,
+                links: None,
+                failures: None,
+                git: None,
+                fingerprint: None,
+            })
+            .await
+            .map_err(|err| SmokeError::CreateReport(client.error(err)))
+    }
+
+    async fn query_perf(
+        &self,
+        json_project: &JsonProject,
+        json_report: &JsonReport,
+    ) -> Result<(), SmokeError> {
+        let Some(report_result) = json_report
+            .results
+            .first()
+            .and_then(|iteration| iteration.first())
+        else {
+            return Err(SmokeError::NoResults(json_report.uuid));
+        };
+        let Some(report_measure) = report_result.measures.first() else {
+            return Err(SmokeError::NoResults(json_report.uuid));
+        };
+
+        let json_perf_query = JsonPerfQuery {
+            branches: vec![json_report.branch.uuid],
+            heads: Vec::new(),
+            testbeds: vec![json_report.testbed.uuid],
+            benchmarks: vec![report_result.benchmark.uuid],
+            measures: vec![report_measure.measure.uuid],
+            start_time: None,
+            end_time: None,
+            tags: Vec::new(),
+            unit: None,
+        };
+        let client = self
+            .backend
+            .checked_client()
+            .await
+            .map_err(SmokeError::Perf)?;
+        let json_perf = client
+            .project(json_project.uuid)
+            .perf()
+            .get(&json_perf_query)
+            .await
+            .map_err(|err| SmokeError::Perf(client.error(err)))?;
+
+        let has_metric = json_perf.results.iter().any(|result| {
+            result
+                .metrics
+                .iter()
+                .any(|metric| metric.report == json_report.uuid)
+        });
+        if has_metric {
+            Ok(())
+        } else {
+            Err(SmokeError::NoPerfMetric(json_report.uuid))
+        }
+    }
+
+    // Deleting the organization also deletes all of its projects
+    async fn teardown(&self, json_organization: &JsonOrganization) -> Result<(), SmokeError> {
+        let organization = ResourceId::from(json_organization.uuid);
+        let result = self
+            .backend
+            .send(|client| {
+                let organization = organization.clone();
+                async move {
+                    client
+                        .organization_delete()
+                        .organization(organization)
+                        .send()
+                        .await
+                }
+            })
+            .await;
+        match result {
+            Ok(_) => {},
+            // A retried delete may have already removed the organization
+            Err(err)
+                if err.code().is_some_and(|code| {
+                    code.is_resource(ApiErrorKind::NotFound, BencherResource::Organization)
+                }) => {},
+            Err(err) => {
+                return Err(SmokeError::Teardown {
+                    organization: json_organization.slug.clone(),
+                    err,
+                })
+            },
+        }
+        cli_println!(
+            "Deleted smoke test organization: {}",
+            json_organization.slug
+        );
+        Ok(())
+    }
+}
+
+fn smoke_name() -> Result<ResourceName, SmokeError> {
+    SMOKE_NAME.parse().map_err(SmokeError::Name)
+}
+
+fn main_branch() -> Result<NameId, SmokeError> {
+    BRANCH_MAIN_STR.parse().map_err(SmokeError::Name)
+}
@@ -0,0 +1,101 @@
+use bencher_json::JsonSpec;
+
+use crate::{
+    bencher::{backend::PubBackend, sub::SubCmd},
+    cli_println,
+    parser::system::spec::{CliSpecExport, CliSpecFormat},
+    CliError, ExitClass,
+};
+
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub format: Format,
+    pub backend: PubBackend,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    TypeScript,
+    JsonSchema,
+    OpenApi,
+}
+
+#[allow(clippy::absolute_paths)]
+#[derive(thiserror::Error, Debug)]
+pub enum SpecError {
+    #[error("Failed to get server OpenAPI spec: {0}")]
+    GetSpec(crate::BackendError),
+    #[error("The server OpenAPI spec does not have any component schemas")]
+    NoSchemas,
+    #[error("Failed to serialize spec: {0}")]
+    Serialize(serde_json::Error),
+}
+
+impl SpecError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::GetSpec(err) => err.exit_class(),
+            Self::NoSchemas => ExitClass::Parse,
+            Self::Serialize(_) => ExitClass::Failure,
+        }
+    }
+}
+
+impl TryFrom<CliSpecExport> for Export {
+    type Error = CliError;
+
+    fn try_from(export: CliSpecExport) -> Result<Self, Self::Error> {
+        let CliSpecExport { format, backend } = export;
+        Ok(Self {
+            format: format.into(),
+            // Only the exported spec should be printed to stdout
+            backend: PubBackend::try_from(backend)?.log(false),
+        })
+    }
+}
+
+impl From<CliSpecFormat> for Format {
+    fn from(format: CliSpecFormat) -> Self {
+        let CliSpecFormat {
+            typescript,
+            json_schema,
+            openapi: _,
+        } = format;
+        if typescript {
+            Self::TypeScript
+        } else if json_schema {
+            Self::JsonSchema
+        } else {
+            Self::OpenApi
+        }
+    }
+}
+
+impl SubCmd for Export {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl Export {
+    async fn exec_inner(&self) -> Result<(), SpecError> {
+        let json_spec: JsonSpec = self
+            .backend
+            .send_with(|client| async move { client.server_spec_get().send().await })
+            .await
+            .map_err(SpecError::GetSpec)?;
+
+        let spec = match self.format {
+            Format::TypeScript => json_spec.typescript().ok_or(SpecError::NoSchemas)?,
+            Format::JsonSchema => {
+                let json_schema = json_spec.to_json_schema().ok_or(SpecError::NoSchemas)?;
+                serde_json::to_string_pretty(&json_schema).map_err(SpecError::Serialize)?
+            },
+            Format::OpenApi => {
+                serde_json::to_string_pretty(&json_spec).map_err(SpecError::Serialize)?
+            },
+        };
+        cli_println!("{}", spec.trim_end());
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{bencher::sub::SubCmd, parser::system::spec::CliSpec, CliError};
+
+mod export;
+
+pub use export::SpecError;
+
+#[derive(Debug)]
+pub enum Spec {
+    Export(export::Export),
+}
+
+impl TryFrom<CliSpec> for Spec {
+    type Error = CliError;
+
+    fn try_from(spec: CliSpec) -> Result<Self, Self::Error> {
+        Ok(match spec {
+            CliSpec::Export(export) => Self::Export(export.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Spec {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::Export(export) => export.exec().await,
+        }
+    }
+}
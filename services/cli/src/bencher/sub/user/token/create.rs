@@ -1,8 +1,11 @@
 use bencher_client::types::JsonNewToken;
-use bencher_json::{ResourceId, ResourceName};
+use bencher_json::{Cidr, ResourceId, ResourceName};
 
 use crate::{
-    bencher::{backend::AuthBackend, sub::SubCmd},
+    bencher::{
+        backend::AuthBackend,
+        sub::{SubCmd, TokenPolicyError},
+    },
     parser::user::token::CliTokenCreate,
     CliError,
 };
@@ -12,6 +15,7 @@ pub struct Create {
     pub user: ResourceId,
     pub name: ResourceName,
     pub ttl: Option<u32>,
+    pub allowed_ips: Vec<Cidr>,
     pub backend: AuthBackend,
 }
 
@@ -23,12 +27,14 @@ impl TryFrom<CliTokenCreate> for Create {
             user,
             name,
             ttl,
+            allowed_ips,
             backend,
         } = create;
         Ok(Self {
             user,
             name,
             ttl,
+            allowed_ips,
             backend: backend.try_into()?,
         })
     }
@@ -36,10 +42,17 @@ impl TryFrom<CliTokenCreate> for Create {
 
 impl From<Create> for JsonNewToken {
     fn from(create: Create) -> Self {
-        let Create { name, ttl, .. } = create;
+        let Create {
+            name,
+            ttl,
+            allowed_ips,
+            ..
+        } = create;
         Self {
             name: name.into(),
             ttl,
+            allowed_ips: (!allowed_ips.is_empty())
+                .then(|| allowed_ips.into_iter().map(Into::into).collect()),
         }
     }
 }
@@ -56,7 +69,14 @@ impl SubCmd for Create {
                     .send()
                     .await
             })
-            .await?;
+            .await
+            .map_err(|err| {
+                if TokenPolicyError::is_token_policy(&err) {
+                    CliError::from(TokenPolicyError(err))
+                } else {
+                    err.into()
+                }
+            })?;
         Ok(())
     }
 }
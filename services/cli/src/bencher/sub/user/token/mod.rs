@@ -1,16 +1,35 @@
-use crate::{bencher::sub::SubCmd, parser::user::token::CliToken, CliError};
+use bencher_json::{ApiErrorKind, BencherResource};
+
+use crate::{bencher::sub::SubCmd, parser::user::token::CliToken, BackendError, CliError};
 
 mod create;
 mod list;
+mod rotate;
 mod update;
 mod view;
 
+pub use rotate::RotateError;
+
+/// A new token was not allowed by the token policy for one of the user's organizations
+#[derive(thiserror::Error, Debug)]
+#[error("{0}\nHint: The token policy for an organization that the user is a member of does not allow this token. Organization members can view the policy with `bencher token-policy view ORGANIZATION`.")]
+pub struct TokenPolicyError(pub BackendError);
+
+impl TokenPolicyError {
+    pub fn is_token_policy(err: &BackendError) -> bool {
+        err.code().is_some_and(|code| {
+            code.is_resource(ApiErrorKind::Forbidden, BencherResource::TokenPolicy)
+        })
+    }
+}
+
 #[derive(Debug)]
 pub enum Token {
     List(list::List),
     Create(create::Create),
     View(view::View),
     Update(update::Update),
+    Rotate(rotate::Rotate),
 }
 
 impl TryFrom<CliToken> for Token {
@@ -22,6 +41,7 @@ impl TryFrom<CliToken> for Token {
             CliToken::Create(create) => Self::Create(create.try_into()?),
             CliToken::View(view) => Self::View(view.try_into()?),
             CliToken::Update(update) => Self::Update(update.try_into()?),
+            CliToken::Rotate(rotate) => Self::Rotate(rotate.try_into()?),
         })
     }
 }
@@ -33,6 +53,7 @@ impl SubCmd for Token {
             Self::Create(create) => create.exec().await,
             Self::View(view) => view.exec().await,
             Self::Update(update) => update.exec().await,
+            Self::Rotate(rotate) => rotate.exec().await,
         }
     }
 }
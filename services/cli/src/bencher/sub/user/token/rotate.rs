@@ -0,0 +1,285 @@
+use bencher_client::types::{JsonNewToken, JsonRevokeToken};
+use bencher_json::{JsonToken, ResourceId, TokenUuid};
+
+use crate::{
+    bencher::{
+        backend::AuthBackend,
+        sub::{SubCmd, TokenPolicyError},
+    },
+    cli_println,
+    parser::user::token::CliTokenRotate,
+    CliError, ExitClass,
+};
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+#[derive(Debug, Clone)]
+pub struct Rotate {
+    pub user: ResourceId,
+    pub token: TokenUuid,
+    pub ttl: Option<u32>,
+    pub revoke_after: Option<u32>,
+    pub backend: AuthBackend,
+}
+
+#[allow(clippy::absolute_paths)]
+#[derive(thiserror::Error, Debug)]
+pub enum RotateError {
+    #[error("Failed to get token ({token}) to rotate: {err}")]
+    GetToken {
+        token: TokenUuid,
+        err: crate::BackendError,
+    },
+    #[error("Failed to create new token to replace token ({token}): {err}")]
+    CreateToken {
+        token: TokenUuid,
+        err: crate::BackendError,
+    },
+    #[error("Failed to create new token to replace token ({token}): {err}")]
+    TokenPolicy {
+        token: TokenUuid,
+        err: TokenPolicyError,
+    },
+    #[error("Failed to verify new token ({new_token}), so the old token ({token}) was not revoked. The new token is still valid: {err}")]
+    VerifyToken {
+        token: TokenUuid,
+        new_token: TokenUuid,
+        err: crate::BackendError,
+    },
+    #[error("Failed to revoke old token ({token}) after creating new token ({new_token}). Both tokens are still valid: {err}")]
+    RevokeToken {
+        token: TokenUuid,
+        new_token: TokenUuid,
+        err: crate::BackendError,
+    },
+    #[error("Failed to serialize new token ({new_token}): {err}")]
+    SerializeToken {
+        new_token: TokenUuid,
+        err: serde_json::Error,
+    },
+}
+
+impl RotateError {
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::GetToken { err, .. }
+            | Self::CreateToken { err, .. }
+            | Self::VerifyToken { err, .. }
+            | Self::RevokeToken { err, .. } => err.exit_class(),
+            Self::TokenPolicy { .. } => ExitClass::Auth,
+            Self::SerializeToken { .. } => ExitClass::Failure,
+        }
+    }
+}
+
+impl TryFrom<CliTokenRotate> for Rotate {
+    type Error = CliError;
+
+    fn try_from(rotate: CliTokenRotate) -> Result<Self, Self::Error> {
+        let CliTokenRotate {
+            user,
+            uuid: token,
+            ttl,
+            revoke_after,
+            backend,
+        } = rotate;
+        Ok(Self {
+            user,
+            token,
+            ttl,
+            revoke_after,
+            // The new token is printed only once, as soon as it has been created
+            backend: AuthBackend::try_from(backend)?.log(false),
+        })
+    }
+}
+
+impl SubCmd for Rotate {
+    async fn exec(&self) -> Result<(), CliError> {
+        self.exec_inner().await.map_err(Into::into)
+    }
+}
+
+impl Rotate {
+    async fn exec_inner(&self) -> Result<(), RotateError> {
+        let old_token = self.get_token().await?;
+        let new_token = self.create_token(&old_token).await?;
+        // Print the new token before anything else can fail,
+        // otherwise it would be lost if verifying it or revoking the old token fails.
+        let json = serde_json::to_string_pretty(&new_token).map_err(|err| {
+            RotateError::SerializeToken {
+                new_token: new_token.uuid,
+                err,
+            }
+        })?;
+        cli_println!("{json}");
+
+        self.verify_token(&new_token).await?;
+        let revoked_token = self.revoke_token(&new_token).await?;
+        if let Some(revoked) = revoked_token.revoked {
+            cli_println!(
+                "Old token ({token}) is revoked as of: {revoked}",
+                token = self.token
+            );
+        }
+        Ok(())
+    }
+
+    async fn get_token(&self) -> Result<JsonToken, RotateError> {
+        self.backend
+            .send_with(|client| async move {
+                client
+                    .user_token_get()
+                    .user(self.user.clone())
+                    .token(self.token)
+                    .send()
+                    .await
+            })
+            .await
+            .map_err(|err| RotateError::GetToken {
+                token: self.token,
+                err,
+            })
+    }
+
+    async fn create_token(&self, old_token: &JsonToken) -> Result<JsonToken, RotateError> {
+        let ttl = rotated_ttl(self.ttl, old_token);
+        // Keep the same IP allowlist as the old token
+        let json_new_token = JsonNewToken {
+            name: old_token.name.clone().into(),
+            ttl,
+            allowed_ips: old_token
+                .allowed_ips
+                .clone()
+                .map(|allowed_ips| allowed_ips.into_iter().map(Into::into).collect()),
+        };
+        self.backend
+            .send_with(|client| {
+                let json_new_token = json_new_token.clone();
+                async move {
+                    client
+                        .user_token_post()
+                        .user(self.user.clone())
+                        .body(json_new_token)
+                        .send()
+                        .await
+                }
+            })
+            .await
+            .map_err(|err| {
+                if TokenPolicyError::is_token_policy(&err) {
+                    RotateError::TokenPolicy {
+                        token: self.token,
+                        err: TokenPolicyError(err),
+                    }
+                } else {
+                    RotateError::CreateToken {
+                        token: self.token,
+                        err,
+                    }
+                }
+            })
+    }
+
+    // Make sure the new token works before revoking the old one
+    async fn verify_token(&self, new_token: &JsonToken) -> Result<(), RotateError> {
+        let backend = self.backend.clone().with_token(new_token.token.clone());
+        let _json: JsonToken = backend
+            .send_with(|client| async move {
+                client
+                    .user_token_get()
+                    .user(self.user.clone())
+                    .token(new_token.uuid)
+                    .send()
+                    .await
+            })
+            .await
+            .map_err(|err| RotateError::VerifyToken {
+                token: self.token,
+                new_token: new_token.uuid,
+                err,
+            })?;
+        Ok(())
+    }
+
+    async fn revoke_token(&self, new_token: &JsonToken) -> Result<JsonToken, RotateError> {
+        let json_revoke_token = JsonRevokeToken {
+            after: revoke_after_seconds(self.revoke_after),
+        };
+        // Use the new token, in case the old token is about to expire
+        let backend = self.backend.clone().with_token(new_token.token.clone());
+        backend
+            .send_with(|client| {
+                let json_revoke_token = json_revoke_token.clone();
+                async move {
+                    client
+                        .user_token_revoke_post()
+                        .user(self.user.clone())
+                        .token(self.token)
+                        .body(json_revoke_token)
+                        .send()
+                        .await
+                }
+            })
+            .await
+            .map_err(|err| RotateError::RevokeToken {
+                token: self.token,
+                new_token: new_token.uuid,
+                err,
+            })
+    }
+}
+
+// Keep the same TTL as the old token, unless a new one is given
+fn rotated_ttl(ttl: Option<u32>, old_token: &JsonToken) -> Option<u32> {
+    ttl.or_else(|| {
+        let ttl = old_token.expiration.into_inner() - old_token.creation.into_inner();
+        u32::try_from(ttl.num_seconds()).ok()
+    })
+}
+
+fn revoke_after_seconds(revoke_after_days: Option<u32>) -> Option<u32> {
+    revoke_after_days.map(|days| days.saturating_mul(SECONDS_PER_DAY))
+}
+
+#[cfg(test)]
+mod test {
+    use bencher_json::{DateTime, JsonToken, Jwt, TokenUuid, UserUuid};
+    use pretty_assertions::assert_eq;
+
+    use super::{revoke_after_seconds, rotated_ttl, SECONDS_PER_DAY};
+
+    fn json_token(creation: i64, expiration: i64) -> JsonToken {
+        JsonToken {
+            uuid: TokenUuid::new(),
+            user: UserUuid::new(),
+            name: "token".parse().unwrap(),
+            token: Jwt::test_token(),
+            creation: DateTime::try_from(creation).unwrap(),
+            expiration: DateTime::try_from(expiration).unwrap(),
+            revoked: None,
+            allowed_ips: None,
+        }
+    }
+
+    #[test]
+    fn test_rotated_ttl() {
+        let old_token = json_token(1_000, 1_000 + i64::from(SECONDS_PER_DAY));
+        // Keep the TTL of the old token
+        assert_eq!(rotated_ttl(None, &old_token), Some(SECONDS_PER_DAY));
+        // Unless a new one is given
+        assert_eq!(rotated_ttl(Some(60), &old_token), Some(60));
+
+        // A TTL that does not fit is left to the server default
+        let old_token = json_token(1_000, 1_000 + i64::from(u32::MAX) + 1);
+        assert_eq!(rotated_ttl(None, &old_token), None);
+    }
+
+    #[test]
+    fn test_revoke_after_seconds() {
+        assert_eq!(revoke_after_seconds(None), None);
+        assert_eq!(revoke_after_seconds(Some(0)), Some(0));
+        assert_eq!(revoke_after_seconds(Some(7)), Some(7 * SECONDS_PER_DAY));
+        assert_eq!(revoke_after_seconds(Some(u32::MAX)), Some(u32::MAX));
+    }
+}
@@ -1,5 +1,5 @@
 use bencher_client::types::JsonUpdateToken;
-use bencher_json::{ResourceId, ResourceName, TokenUuid};
+use bencher_json::{Cidr, ResourceId, ResourceName, TokenUuid};
 
 use crate::{
     bencher::{backend::AuthBackend, sub::SubCmd},
@@ -12,6 +12,7 @@ pub struct Update {
     pub user: ResourceId,
     pub token: TokenUuid,
     pub name: Option<ResourceName>,
+    pub allowed_ips: Option<Vec<Cidr>>,
     pub backend: AuthBackend,
 }
 
@@ -23,12 +24,21 @@ impl TryFrom<CliTokenUpdate> for Update {
             user,
             uuid: token,
             name,
+            allowed_ips,
+            any_ip,
             backend,
         } = view;
+        // An empty allowlist removes the IP address restriction
+        let allowed_ips = if any_ip {
+            Some(Vec::new())
+        } else {
+            (!allowed_ips.is_empty()).then_some(allowed_ips)
+        };
         Ok(Self {
             user,
             token,
             name,
+            allowed_ips,
             backend: backend.try_into()?,
         })
     }
@@ -36,9 +46,13 @@ impl TryFrom<CliTokenUpdate> for Update {
 
 impl From<Update> for JsonUpdateToken {
     fn from(update: Update) -> Self {
-        let Update { name, .. } = update;
+        let Update {
+            name, allowed_ips, ..
+        } = update;
         Self {
             name: name.map(Into::into),
+            allowed_ips: allowed_ips
+                .map(|allowed_ips| allowed_ips.into_iter().map(Into::into).collect()),
         }
     }
 }
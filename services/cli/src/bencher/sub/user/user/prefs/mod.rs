@@ -0,0 +1,30 @@
+use crate::{bencher::sub::SubCmd, parser::user::prefs::CliUserPrefs, CliError};
+
+mod update;
+mod view;
+
+#[derive(Debug)]
+pub enum Prefs {
+    View(view::View),
+    Update(update::Update),
+}
+
+impl TryFrom<CliUserPrefs> for Prefs {
+    type Error = CliError;
+
+    fn try_from(prefs: CliUserPrefs) -> Result<Self, Self::Error> {
+        Ok(match prefs {
+            CliUserPrefs::View(view) => Self::View(view.try_into()?),
+            CliUserPrefs::Update(update) => Self::Update(update.try_into()?),
+        })
+    }
+}
+
+impl SubCmd for Prefs {
+    async fn exec(&self) -> Result<(), CliError> {
+        match self {
+            Self::View(view) => view.exec().await,
+            Self::Update(update) => update.exec().await,
+        }
+    }
+}
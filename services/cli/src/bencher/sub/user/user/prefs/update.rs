@@ -0,0 +1,130 @@
+use bencher_client::types::{DateFormat, JsonNewUserPrefs};
+use bencher_json::{JsonUserPrefs, ResourceId, ResourceName, TimeZone};
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::user::prefs::{CliDateFormat, CliUserPrefsUpdate},
+    CliError,
+};
+
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub user: ResourceId,
+    pub time_zone: Option<TimeZone>,
+    pub date_format: Option<DateFormat>,
+    pub time_units: Option<ResourceName>,
+    pub throughput_units: Option<ResourceName>,
+    pub size_units: Option<ResourceName>,
+    pub default_project: Option<ResourceId>,
+    pub reset: bool,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliUserPrefsUpdate> for Update {
+    type Error = CliError;
+
+    fn try_from(update: CliUserPrefsUpdate) -> Result<Self, Self::Error> {
+        let CliUserPrefsUpdate {
+            user,
+            time_zone,
+            date_format,
+            time_units,
+            throughput_units,
+            size_units,
+            default_project,
+            reset,
+            backend,
+        } = update;
+        Ok(Self {
+            user,
+            time_zone,
+            date_format: date_format.map(Into::into),
+            time_units,
+            throughput_units,
+            size_units,
+            default_project,
+            reset,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl From<CliDateFormat> for DateFormat {
+    fn from(date_format: CliDateFormat) -> Self {
+        match date_format {
+            CliDateFormat::Rfc3339 => Self::Rfc3339,
+            CliDateFormat::DateTime => Self::DateTime,
+            CliDateFormat::Date => Self::Date,
+        }
+    }
+}
+
+impl Update {
+    /// The preferences are replaced as a whole,
+    /// so any preference that is not being updated keeps its current value.
+    fn merge(&self, json_prefs: Option<JsonUserPrefs>) -> JsonNewUserPrefs {
+        let current = json_prefs
+            .map(bencher_json::JsonNewUserPrefs::from)
+            .unwrap_or_default();
+        JsonNewUserPrefs {
+            time_zone: self.time_zone.clone().or(current.time_zone).map(Into::into),
+            date_format: self
+                .date_format
+                .or_else(|| current.date_format.map(Into::into)),
+            time_units: self
+                .time_units
+                .clone()
+                .or(current.time_units)
+                .map(Into::into),
+            throughput_units: self
+                .throughput_units
+                .clone()
+                .or(current.throughput_units)
+                .map(Into::into),
+            size_units: self
+                .size_units
+                .clone()
+                .or(current.size_units)
+                .map(Into::into),
+            default_project: self
+                .default_project
+                .clone()
+                .or(current.default_project)
+                .map(Into::into),
+        }
+    }
+}
+
+impl SubCmd for Update {
+    async fn exec(&self) -> Result<(), CliError> {
+        let json_prefs = if self.reset {
+            None
+        } else {
+            let json_prefs: JsonUserPrefs = self
+                .backend
+                .clone()
+                .log(false)
+                .send_with(|client| async move {
+                    client.user_prefs_get().user(self.user.clone()).send().await
+                })
+                .await?;
+            Some(json_prefs)
+        };
+        let body = self.merge(json_prefs);
+        let _json = self
+            .backend
+            .send(|client| {
+                let body = body.clone();
+                async move {
+                    client
+                        .user_prefs_put()
+                        .user(self.user.clone())
+                        .body(body)
+                        .send()
+                        .await
+                }
+            })
+            .await?;
+        Ok(())
+    }
+}
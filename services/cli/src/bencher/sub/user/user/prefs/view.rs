@@ -0,0 +1,37 @@
+use bencher_json::ResourceId;
+
+use crate::{
+    bencher::{backend::AuthBackend, sub::SubCmd},
+    parser::user::prefs::CliUserPrefsView,
+    CliError,
+};
+
+#[derive(Debug)]
+pub struct View {
+    pub user: ResourceId,
+    pub backend: AuthBackend,
+}
+
+impl TryFrom<CliUserPrefsView> for View {
+    type Error = CliError;
+
+    fn try_from(view: CliUserPrefsView) -> Result<Self, Self::Error> {
+        let CliUserPrefsView { user, backend } = view;
+        Ok(Self {
+            user,
+            backend: backend.try_into()?,
+        })
+    }
+}
+
+impl SubCmd for View {
+    async fn exec(&self) -> Result<(), CliError> {
+        let _json =
+            self.backend
+                .send(|client| async move {
+                    client.user_prefs_get().user(self.user.clone()).send().await
+                })
+                .await?;
+        Ok(())
+    }
+}
@@ -5,8 +5,14 @@ pub enum CliError {
     #[error("{0}")]
     Backend(#[from] crate::bencher::BackendError),
     #[error("{0}")]
+    Credential(#[from] crate::bencher::CredentialError),
+    #[error("{0}")]
     Run(#[from] crate::bencher::sub::RunError),
     #[error("{0}")]
+    BranchTags(#[from] crate::bencher::sub::BranchTagsError),
+    #[error("{0}")]
+    Calibrate(#[from] crate::bencher::sub::CalibrateError),
+    #[error("{0}")]
     Archive(#[from] crate::bencher::sub::ArchiveError),
     #[error("{0}")]
     Threshold(#[from] crate::bencher::sub::ThresholdError),
@@ -15,8 +21,119 @@ pub enum CliError {
     #[error("{0}")]
     Mock(#[from] crate::bencher::sub::MockError),
     #[error("{0}")]
+    Import(#[from] crate::bencher::sub::ImportError),
+    #[error("{0}")]
+    Measure(#[from] crate::bencher::sub::MeasureError),
+    #[error("{0}")]
+    Report(#[from] crate::bencher::sub::ReportError),
+    #[error("{0}")]
     Docker(#[from] crate::bencher::sub::DockerError),
+    #[error("{0}")]
+    Smoke(#[from] crate::bencher::sub::SmokeError),
+    #[error("{0}")]
+    Rbac(#[from] crate::bencher::sub::RbacError),
+    #[error("{0}")]
+    Rotate(#[from] crate::bencher::sub::RotateError),
+    #[error("{0}")]
+    TokenPolicy(#[from] crate::bencher::sub::TokenPolicyError),
+    #[error("{0}")]
+    Spec(#[from] crate::bencher::sub::SpecError),
 
     #[error("Failed to serialize config: {0}")]
     SerializeConfig(serde_json::Error),
 }
+
+impl CliError {
+    /// The class of the failure, which determines the exit code
+    pub fn exit_class(&self) -> ExitClass {
+        match self {
+            Self::Backend(err) => err.exit_class(),
+            Self::Credential(_) | Self::Docker(_) | Self::SerializeConfig(_) => ExitClass::Failure,
+            Self::Run(err) => err.exit_class(),
+            Self::BranchTags(err) => err.exit_class(),
+            Self::Calibrate(err) => err.exit_class(),
+            Self::Archive(err) => err.exit_class(),
+            Self::Threshold(err) => err.exit_class(),
+            Self::Thresholds(_) | Self::Measure(_) => ExitClass::Usage,
+            Self::Mock(err) => err.exit_class(),
+            Self::Import(err) => err.exit_class(),
+            Self::Report(err) => err.exit_class(),
+            Self::Smoke(err) => err.exit_class(),
+            Self::Rbac(err) => err.exit_class(),
+            Self::Rotate(err) => err.exit_class(),
+            Self::TokenPolicy(_) => ExitClass::Auth,
+            Self::Spec(err) => err.exit_class(),
+        }
+    }
+
+    /// An actionable hint for how to fix the failure, if there is one.
+    /// Errors that already include a hint in their message do not have one.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Backend(err) => err.hint(),
+            Self::Run(err) => err.hint(),
+            Self::Calibrate(err) => err.hint(),
+            Self::Credential(_)
+            | Self::BranchTags(_)
+            | Self::Archive(_)
+            | Self::Threshold(_)
+            | Self::Thresholds(_)
+            | Self::Mock(_)
+            | Self::Import(_)
+            | Self::Measure(_)
+            | Self::Report(_)
+            | Self::Docker(_)
+            | Self::Smoke(_)
+            | Self::Rbac(_)
+            | Self::Rotate(_)
+            | Self::TokenPolicy(_)
+            | Self::Spec(_)
+            | Self::SerializeConfig(_) => None,
+        }
+    }
+}
+
+/// The class of a CLI failure.
+/// Each class has a stable exit code, so automation can tell apart
+/// a detected performance regression from a network failure.
+/// The exit codes are listed in the `--help` output as `EXIT_CODES_HELP`,
+/// so any changes must be made in both places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitClass {
+    /// Any other failure, such as a benchmark command that failed
+    Failure,
+    /// An invalid argument or value
+    Usage,
+    /// Alerts were generated with `--err` or `--err-on`
+    Alerts,
+    /// A missing or invalid API token or insufficient permissions
+    Auth,
+    /// The API server could not be reached or failed to process the request
+    Network,
+    /// The benchmark results, a file, or an API response could not be parsed
+    Parse,
+}
+
+impl ExitClass {
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Failure => 1,
+            // This is the same exit code that `clap` uses for invalid arguments
+            Self::Usage => 2,
+            Self::Alerts => 3,
+            Self::Auth => 4,
+            Self::Network => 5,
+            Self::Parse => 6,
+        }
+    }
+}
+
+pub const EXIT_CODES_HELP: &str = "Exit Codes:
+  0  Success
+  1  Failure, such as a benchmark command that failed
+  2  Usage, an invalid argument or value
+  3  Alerts, alerts were generated with `--err` or `--err-on`
+  4  Auth, a missing or invalid API token or insufficient permissions
+  5  Network, the API server could not be reached or failed to process the request
+  6  Parse, the benchmark results, a file, or an API response could not be parsed
+With `--passthrough-exit`, a failed benchmark command exits with its own exit code.";
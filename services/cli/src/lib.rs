@@ -7,14 +7,17 @@ pub use bencher::{
     sub::{MockError, RunError, ThresholdError},
     BackendError,
 };
-pub use error::CliError;
+pub use error::{CliError, ExitClass, EXIT_CODES_HELP};
 pub use parser::CliBencher;
 
 pub const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub async fn exec() -> Result<(), CliError> {
     let bencher = Bencher::new()?;
-    bencher.exec().await
+    let result = bencher.exec().await;
+    // Print the timings even if the command failed, as that is often when they are needed
+    bencher::backend::print_debug_timing();
+    result
 }
 
 // https://github.com/rust-lang/rust/issues/46016#issuecomment-1242039016
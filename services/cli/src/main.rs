@@ -13,9 +13,21 @@ async fn main() -> ExitCode {
         {
             ExitCode::SUCCESS
         },
+        // Pass through the benchmark command exit code, as long as it is a valid non-zero exit code
+        Err(err @ CliError::Run(RunError::PassthroughExit(code))) => {
+            eprintln!("\n{err}");
+            u8::try_from(code)
+                .ok()
+                .filter(|code| *code != 0)
+                .map_or(ExitCode::FAILURE, ExitCode::from)
+        },
+        // Exit with the stable exit code for the class of failure
         Err(err) => {
             eprintln!("\n{err}");
-            ExitCode::FAILURE
+            if let Some(hint) = err.hint() {
+                eprintln!("Hint: {hint}");
+            }
+            ExitCode::from(err.exit_class().code())
         },
     }
 }
@@ -12,18 +12,22 @@ pub mod user;
 
 use docker::{CliDown, CliLogs, CliUp};
 use mock::CliMock;
-use organization::{member::CliMember, CliOrganization};
+use organization::{member::CliMember, token_policy::CliTokenPolicy, CliOrganization};
 use project::{
-    alert::CliAlert, archive::CliArchive, benchmark::CliBenchmark, branch::CliBranch,
-    measure::CliMeasure, metric::CliMetric, perf::CliPerf, plot::CliPlot, report::CliReport,
-    run::CliRun, testbed::CliTestbed, threshold::CliThreshold, CliProject,
+    alert::CliAlert, annotation::CliAnnotation, archive::CliArchive, benchmark::CliBenchmark,
+    branch::CliBranch, calibrate::CliCalibrate, expectation::CliExpectation, import::CliImport,
+    measure::CliMeasure, metric::CliMetric, perf::CliPerf, plot::CliPlot, query::CliQuery,
+    report::CliReport, rule::CliRule, run::CliRun, testbed::CliTestbed, threshold::CliThreshold,
+    CliProject,
 };
-use system::{auth::CliAuth, server::CliServer};
+use system::{auth::CliAuth, server::CliServer, spec::CliSpec};
 use user::{token::CliToken, CliUser};
 
+use crate::EXIT_CODES_HELP;
+
 /// Bencher CLI
 #[derive(Parser, Debug)]
-#[clap(name = "bencher", author, version, about, long_about = None)]
+#[clap(name = "bencher", author, version, about, long_about = None, after_help = EXIT_CODES_HELP)]
 pub struct CliBencher {
     /// Bencher subcommands
     #[clap(subcommand)]
@@ -34,8 +38,12 @@ pub struct CliBencher {
 pub enum CliSub {
     /// Run benchmarks
     Run(Box<CliRun>),
+    /// Measure benchmark noise to recommend threshold settings
+    Calibrate(CliCalibrate),
     /// Generate mock benchmark data
     Mock(CliMock),
+    /// Import benchmark history from other benchmark trackers
+    Import(CliImport),
 
     /// Archive a dimension
     Archive(CliArchive),
@@ -59,6 +67,9 @@ pub enum CliSub {
     /// Organization metered subscription plan
     #[clap(subcommand)]
     Plan(organization::plan::CliOrganizationPlan),
+    /// Manage organization API token policy
+    #[clap(subcommand)]
+    TokenPolicy(CliTokenPolicy),
 
     /// Manage projects
     #[clap(subcommand)]
@@ -72,6 +83,18 @@ pub enum CliSub {
     /// Manage plots
     #[clap(subcommand)]
     Plot(CliPlot),
+    /// Manage saved perf queries
+    #[clap(subcommand)]
+    Query(CliQuery),
+    /// Manage annotations
+    #[clap(subcommand)]
+    Annotation(CliAnnotation),
+    /// Manage benchmark name rules
+    #[clap(subcommand)]
+    Rule(CliRule),
+    /// Manage expected regression windows
+    #[clap(subcommand)]
+    Expectation(CliExpectation),
 
     /// Manage branches
     #[clap(subcommand)]
@@ -85,6 +108,10 @@ pub enum CliSub {
     /// Manage measures
     #[clap(subcommand)]
     Measure(CliMeasure),
+    /// Deprecated: Use `measure` instead.
+    // TODO remove in due time
+    #[clap(subcommand, hide = true, alias = "metric-kinds")]
+    MetricKind(CliMeasure),
     /// Manage metrics
     #[clap(subcommand)]
     Metric(CliMetric),
@@ -106,6 +133,9 @@ pub enum CliSub {
     /// Server commands
     #[clap(subcommand)]
     Server(CliServer),
+    /// Export API type definitions
+    #[clap(subcommand)]
+    Spec(CliSpec),
 
     /// Server authentication & authorization
     #[clap(subcommand)]
@@ -113,12 +143,22 @@ pub enum CliSub {
 }
 
 #[allow(clippy::doc_markdown)]
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct CliBackend {
     /// Backend host URL
     #[clap(long, value_name = "URL", env = "BENCHER_HOST", default_value = BENCHER_API_URL_STR)]
     pub host: Url,
 
+    /// Fallback backend host URL(s) for failover, such as a mirrored read replica.
+    /// The healthy host with the lowest latency is used until it can not be reached.
+    #[clap(
+        long = "fallback-host",
+        value_name = "URL",
+        env = "BENCHER_FALLBACK_HOSTS",
+        value_delimiter = ','
+    )]
+    pub fallback_hosts: Vec<Url>,
+
     /// User API token
     #[clap(long, env = "BENCHER_API_TOKEN")]
     pub token: Option<Jwt>,
@@ -134,6 +174,17 @@ pub struct CliBackend {
     /// Strictly parse JSON responses
     #[clap(long)]
     pub strict: bool,
+
+    /// Record the time to first byte (TTFB) and total time for each API call
+    /// and print a summary to stderr when done
+    #[clap(long)]
+    pub debug_timing: bool,
+
+    /// Do not cache API responses on disk.
+    /// By default, responses are cached and revalidated with the API server,
+    /// so unchanged responses are not downloaded again.
+    #[clap(long, env = "BENCHER_NO_CACHE")]
+    pub no_cache: bool,
 }
 
 #[derive(Args, Debug)]
@@ -112,7 +112,6 @@ pub struct CliMemberRemove {
 /// Role within the organization
 #[derive(ValueEnum, Debug, Clone)]
 pub enum CliMemberRole {
-    // TODO Team Management
-    // Member,
+    Member,
     Leader,
 }
@@ -1,10 +1,11 @@
-use bencher_json::{ResourceId, ResourceName, Slug};
+use bencher_json::{DateTime, Jwt, NameId, ResourceId, ResourceName, Slug};
 use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::parser::CliBackend;
 
 pub mod member;
 pub mod plan;
+pub mod token_policy;
 pub mod usage;
 
 #[cfg(feature = "plus")]
@@ -33,6 +34,15 @@ pub enum CliOrganization {
     /// Check organization permission
     Allowed(CliOrganizationAllowed),
 
+    /// Transfer a project into an organization
+    Transfer(CliOrganizationTransfer),
+
+    /// Claim an unclaimed project for an organization
+    Claim(CliOrganizationClaim),
+
+    /// Query the same measure across multiple projects in an organization
+    Perf(CliOrganizationPerf),
+
     #[cfg(feature = "plus")]
     /// Check organization metrics usage
     Usage(CliOrganizationUsage),
@@ -102,7 +112,7 @@ pub struct CliOrganizationUpdate {
     /// Organization license
     /// To remove the current license key without replacing it, use an underscore (`_`).
     #[clap(long, value_name = "KEY")]
-    pub license: Option<ElidedOption<bencher_json::Jwt>>,
+    pub license: Option<ElidedOption<Jwt>>,
 
     #[clap(flatten)]
     pub backend: CliBackend,
@@ -136,6 +146,83 @@ pub enum CliOrganizationPermission {
     DeleteRole,
 }
 
+#[derive(Parser, Debug)]
+pub struct CliOrganizationTransfer {
+    /// Slug or UUID of the organization to transfer the project to
+    pub organization: ResourceId,
+
+    /// Project slug or UUID
+    #[clap(long)]
+    pub project: ResourceId,
+
+    /// New project name, if the organization already has a project with the same name
+    #[clap(long)]
+    pub name: Option<ResourceName>,
+
+    /// New project slug
+    #[clap(long)]
+    pub slug: Option<Slug>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliOrganizationClaim {
+    /// Slug or UUID of the organization to claim the project for
+    pub organization: ResourceId,
+
+    /// Claim token for the unclaimed project
+    #[clap(long)]
+    pub claim_token: Jwt,
+
+    /// New project name, if the organization already has a project with the same name
+    #[clap(long)]
+    pub name: Option<ResourceName>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliOrganizationPerf {
+    /// Organization slug or UUID
+    pub organization: ResourceId,
+
+    /// Project slugs or UUIDs.
+    /// If not provided, all of the projects in the organization that you are allowed to view are queried.
+    #[clap(long, value_delimiter = ',', value_name = "PROJECT")]
+    pub projects: Vec<ResourceId>,
+
+    /// Branch name, slug, or UUID in each project
+    #[clap(long)]
+    pub branch: NameId,
+
+    /// Testbed name, slug, or UUID in each project
+    #[clap(long)]
+    pub testbed: NameId,
+
+    /// Benchmark slug or UUID in each project.
+    /// If not provided, all of the benchmarks in each project are queried.
+    #[clap(long)]
+    pub benchmark: Option<ResourceId>,
+
+    /// Measure name, slug, or UUID in each project
+    #[clap(long)]
+    pub measure: NameId,
+
+    /// Start time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub start_time: Option<DateTime>,
+
+    /// End time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub end_time: Option<DateTime>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
 #[derive(Parser, Debug)]
 pub struct CliOrganizationDelete {
     /// Organization slug or UUID
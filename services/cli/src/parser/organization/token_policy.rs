@@ -0,0 +1,65 @@
+use bencher_json::ResourceId;
+use clap::{Parser, Subcommand};
+
+use crate::parser::CliBackend;
+
+#[derive(Subcommand, Debug)]
+pub enum CliTokenPolicy {
+    /// View an organization token policy
+    #[clap(alias = "get")]
+    View(CliTokenPolicyView),
+    /// Update an organization token policy
+    #[clap(alias = "edit")]
+    Update(CliTokenPolicyUpdate),
+    /// Revoke the API tokens of all organization members for the organization
+    Revoke(CliTokenPolicyRevoke),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTokenPolicyView {
+    /// Organization slug or UUID
+    pub organization: ResourceId,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+/// The whole policy is replaced, so any options that are not set are cleared.
+#[derive(Parser, Debug)]
+pub struct CliTokenPolicyUpdate {
+    /// Organization slug or UUID
+    pub organization: ResourceId,
+
+    /// Maximum time to live (seconds) for new tokens
+    #[clap(long, value_name = "SECONDS")]
+    pub max_ttl: Option<u32>,
+
+    /// Require new tokens to have a time to live
+    #[clap(long)]
+    pub require_ttl: bool,
+
+    /// Forbid new tokens that never expire, with a TTL longer than one year
+    #[clap(long)]
+    pub forbid_non_expiring: bool,
+
+    /// Only allow organization leaders to create new tokens
+    #[clap(long)]
+    pub admin_only: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTokenPolicyRevoke {
+    /// Organization slug or UUID
+    pub organization: ResourceId,
+
+    /// Grace period (days) before the tokens are revoked
+    /// (default: revoke immediately)
+    #[clap(long, value_name = "DAYS")]
+    pub revoke_after: Option<u32>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
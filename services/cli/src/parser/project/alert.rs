@@ -1,4 +1,6 @@
-use bencher_json::{AlertUuid, ResourceId};
+use bencher_json::{
+    project::alert::AlertStatsWindow, AlertUuid, DateTime, NameId, NonEmpty, ResourceId,
+};
 use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliBackend, CliPagination};
@@ -14,6 +16,10 @@ pub enum CliAlert {
     // Update an alert
     #[clap(alias = "edit")]
     Update(CliAlertUpdate),
+    /// Dismiss or silence all matching alerts
+    Dismiss(CliAlertDismiss),
+    /// View alert statistics over time
+    Stats(CliAlertStats),
 }
 
 #[derive(Parser, Debug)]
@@ -28,10 +34,18 @@ pub struct CliAlertList {
     #[clap(long)]
     pub status: Option<CliAlertStatus>,
 
+    /// Filter by alert severity
+    #[clap(long)]
+    pub severity: Option<CliAlertSeverity>,
+
     /// Filter for alerts with an archived branch, testbed, or measure
     #[clap(long)]
     pub archived: bool,
 
+    /// Watch for and print new alerts as they are created, polling at the given interval in seconds (default: 5, minimum: 1)
+    #[clap(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "5", value_parser = clap::value_parser!(u64).range(1..))]
+    pub watch: Option<u64>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -56,6 +70,17 @@ pub enum CliAlertStatus {
     Silenced,
 }
 
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliAlertSeverity {
+    /// Minor
+    Minor,
+    /// Major
+    Major,
+    /// Critical
+    Critical,
+}
+
 #[derive(Parser, Debug)]
 pub struct CliAlertView {
     /// Project slug or UUID
@@ -91,4 +116,75 @@ pub enum CliAlertStatusUpdate {
     Active,
     /// Dismissed
     Dismissed,
+    /// Silenced
+    Silenced,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAlertDismiss {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Justification for dismissing the alerts, stored for auditing
+    #[clap(long)]
+    pub reason: NonEmpty,
+
+    /// Only dismiss alerts for this branch name, slug, or UUID
+    #[clap(long)]
+    pub branch: Option<NameId>,
+
+    /// Only dismiss alerts for this testbed name, slug, or UUID
+    #[clap(long)]
+    pub testbed: Option<NameId>,
+
+    /// Only dismiss alerts for this measure name, slug, or UUID
+    #[clap(long)]
+    pub measure: Option<NameId>,
+
+    /// Only dismiss alerts of this severity
+    #[clap(long)]
+    pub severity: Option<CliAlertSeverity>,
+
+    /// Only dismiss alerts for reports that started before this time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub before: Option<DateTime>,
+
+    /// Only dismiss alerts for reports that started after this time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub after: Option<DateTime>,
+
+    /// Silence the alerts instead of dismissing them
+    #[clap(long)]
+    pub silence: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAlertStats {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Time window to count alerts over, in days (ex: `90d`) or weeks (ex: `12w`) (default: `90d`)
+    #[clap(long)]
+    pub window: Option<AlertStatsWindow>,
+
+    /// Size of the time buckets to count alerts in (default: `week`)
+    #[clap(long)]
+    pub bucket: Option<CliAlertStatsBucket>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliAlertStatsBucket {
+    /// Day
+    Day,
+    /// Week
+    Week,
+    /// Month
+    Month,
 }
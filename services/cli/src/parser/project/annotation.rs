@@ -0,0 +1,127 @@
+use bencher_json::{AnnotationUuid, DateTime, NonEmpty, ResourceId, ResourceName};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::{CliBackend, CliPagination, ElidedOption};
+
+#[derive(Subcommand, Debug)]
+pub enum CliAnnotation {
+    /// List annotations
+    #[clap(alias = "ls")]
+    List(CliAnnotationList),
+    /// Create an annotation
+    #[clap(alias = "add")]
+    Create(CliAnnotationCreate),
+    /// View an annotation
+    #[clap(alias = "get")]
+    View(CliAnnotationView),
+    // Update an annotation
+    #[clap(alias = "edit")]
+    Update(CliAnnotationUpdate),
+    /// Delete an annotation
+    #[clap(alias = "rm")]
+    Delete(CliAnnotationDelete),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAnnotationList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Annotation search string
+    #[clap(long, value_name = "QUERY")]
+    pub search: Option<String>,
+
+    /// Start time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub start_time: Option<DateTime>,
+
+    /// End time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub end_time: Option<DateTime>,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliAnnotationsSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliAnnotationsSort {
+    /// Time of the annotation
+    Time,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAnnotationCreate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// The title of the annotation.
+    /// Maximum length is 64 characters.
+    #[clap(long)]
+    pub title: ResourceName,
+
+    /// A longer description of the annotation.
+    #[clap(long)]
+    pub description: Option<NonEmpty>,
+
+    /// The time of the event that the annotation marks (seconds since epoch).
+    /// If not provided, the current time is used.
+    #[clap(long, value_name = "SECONDS")]
+    pub time: Option<DateTime>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAnnotationView {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Annotation UUID
+    pub annotation: AnnotationUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAnnotationUpdate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Annotation UUID
+    pub annotation: AnnotationUuid,
+
+    /// The new title of the annotation.
+    /// Maximum length is 64 characters.
+    #[clap(long)]
+    pub title: Option<ResourceName>,
+
+    /// The new description of the annotation.
+    /// To remove the current description without replacing it, use an underscore (`_`).
+    #[clap(long)]
+    pub description: Option<ElidedOption<NonEmpty>>,
+
+    /// The new time of the event that the annotation marks (seconds since epoch).
+    #[clap(long, value_name = "SECONDS")]
+    pub time: Option<DateTime>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAnnotationDelete {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Annotation UUID
+    pub annotation: AnnotationUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
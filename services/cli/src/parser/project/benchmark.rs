@@ -1,5 +1,5 @@
 use bencher_json::{BenchmarkName, ResourceId, Slug};
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliArchived, CliBackend, CliPagination};
 
@@ -8,6 +8,9 @@ pub enum CliBenchmark {
     /// List benchmarks
     #[clap(alias = "ls")]
     List(CliBenchmarkList),
+    /// Search benchmarks
+    #[clap(alias = "find")]
+    Search(CliBenchmarkSearch),
     /// Create a benchmark
     #[clap(alias = "add")]
     Create(CliBenchmarkCreate),
@@ -42,6 +45,40 @@ pub struct CliBenchmarkList {
     #[clap(long)]
     pub archived: bool,
 
+    /// Filter for flaky benchmarks
+    #[clap(long)]
+    pub flaky: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliBenchmarkSearch {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Search for benchmark names, slugs, or UUIDs
+    pub search: String,
+
+    /// Only match benchmarks that contain the search as a substring, sorted by name.
+    /// By default, benchmarks that contain the characters of the search in order are also matched
+    /// and the results are ranked by how closely they match.
+    #[clap(long)]
+    pub substring: bool,
+
+    /// The number of results per page (default 8) (max 255)
+    #[clap(long, value_name = "COUNT")]
+    pub per_page: Option<u8>,
+
+    /// Page number of the results to fetch
+    #[clap(long, value_name = "NUMBER")]
+    pub page: Option<u32>,
+
+    /// Only search archived benchmarks
+    #[clap(long)]
+    pub archived: bool,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -101,10 +138,41 @@ pub struct CliBenchmarkUpdate {
     #[clap(flatten)]
     pub archived: CliArchived,
 
+    #[clap(flatten)]
+    pub flaky: CliBenchmarkFlaky,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
 
+#[derive(Args, Debug)]
+#[clap(group(
+    ArgGroup::new("flaky")
+        .multiple(false)
+        .args(&["quarantine", "unquarantine"]),
+))]
+pub struct CliBenchmarkFlaky {
+    /// Mark as flaky, excluding the benchmark from alert generation
+    #[clap(long)]
+    pub quarantine: bool,
+
+    /// Unmark as flaky, including the benchmark in alert generation
+    #[clap(long)]
+    pub unquarantine: bool,
+}
+
+impl From<CliBenchmarkFlaky> for Option<bool> {
+    fn from(flaky: CliBenchmarkFlaky) -> Option<bool> {
+        match (flaky.quarantine, flaky.unquarantine) {
+            (false, false) => None,
+            (false, true) => Some(false),
+            (true, false) => Some(true),
+            #[allow(clippy::unreachable)]
+            (true, true) => unreachable!("Cannot set both `quarantine` and `unquarantine`"),
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 pub struct CliBenchmarkDelete {
     /// Project slug or UUID
@@ -1,7 +1,8 @@
-use bencher_json::{BranchName, GitHash, NameId, ResourceId, Slug};
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use bencher_json::{BranchName, DateTime, GitHash, HeadUuid, NameId, ResourceId, Slug};
+use camino::Utf8PathBuf;
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 
-use crate::parser::{CliArchived, CliBackend, CliPagination};
+use crate::parser::{CliArchived, CliBackend, CliDirection, CliPagination};
 
 #[derive(Subcommand, Debug)]
 pub enum CliBranch {
@@ -20,6 +21,12 @@ pub enum CliBranch {
     /// Delete a branch
     #[clap(alias = "rm")]
     Delete(CliBranchDelete),
+    /// View the start point lineage of a branch
+    Lineage(CliBranchLineage),
+    /// List the versions of a branch, including any that were never benchmarked
+    Versions(CliBranchVersions),
+    /// Record the git tags of a repository on a branch, to align charts to releases
+    Tags(CliBranchTags),
 }
 
 #[derive(Parser, Debug)]
@@ -107,6 +114,23 @@ pub struct CliBranchView {
     /// Branch slug or UUID
     pub branch: ResourceId,
 
+    /// Also list the versions of the current branch head,
+    /// with their git hash, report count, and report dates
+    #[clap(long)]
+    pub history: bool,
+
+    /// The direction to sort the versions by version number (default desc)
+    #[clap(long, requires = "history")]
+    pub direction: Option<CliDirection>,
+
+    /// The number of versions per page (default 8) (max 255)
+    #[clap(long, value_name = "COUNT", requires = "history")]
+    pub per_page: Option<u8>,
+
+    /// Page number of the versions to fetch
+    #[clap(long, value_name = "NUMBER", requires = "history")]
+    pub page: Option<u32>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -133,10 +157,47 @@ pub struct CliBranchUpdate {
     #[clap(flatten)]
     pub archived: CliArchived,
 
+    #[clap(flatten)]
+    pub frozen_baseline: CliFrozenBaseline,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
 
+#[derive(Args, Debug)]
+#[clap(group(
+    ArgGroup::new("frozen_baseline")
+        .multiple(false)
+        .args(&["freeze_baseline", "unfreeze_baseline"]),
+))]
+pub struct CliFrozenBaseline {
+    /// Freeze the threshold baselines for the branch, such as for a release branch.
+    /// All subsequent reports are compared against the metrics from before it was frozen.
+    #[clap(long)]
+    pub freeze_baseline: bool,
+
+    /// Unfreeze the threshold baselines for the branch
+    #[clap(long)]
+    pub unfreeze_baseline: bool,
+}
+
+impl From<CliFrozenBaseline> for Option<bool> {
+    fn from(frozen_baseline: CliFrozenBaseline) -> Option<bool> {
+        match (
+            frozen_baseline.freeze_baseline,
+            frozen_baseline.unfreeze_baseline,
+        ) {
+            (false, false) => None,
+            (false, true) => Some(false),
+            (true, false) => Some(true),
+            #[allow(clippy::unreachable)]
+            (true, true) => {
+                unreachable!("Cannot set both `freeze_baseline` and `unfreeze_baseline`")
+            },
+        }
+    }
+}
+
 #[allow(clippy::struct_field_names)]
 #[derive(Args, Debug)]
 pub struct CliStartPointUpdate {
@@ -182,3 +243,99 @@ pub struct CliBranchDelete {
     #[clap(flatten)]
     pub backend: CliBackend,
 }
+
+#[derive(Parser, Debug)]
+pub struct CliBranchLineage {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Branch slug or UUID
+    pub branch: ResourceId,
+
+    /// Branch head UUID
+    /// (default: current branch head)
+    #[clap(long)]
+    pub head: Option<HeadUuid>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliBranchVersions {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Branch slug or UUID
+    pub branch: ResourceId,
+
+    /// Branch head UUID
+    /// (default: current branch head)
+    #[clap(long)]
+    pub head: Option<HeadUuid>,
+
+    /// Only list versions that have (`true`) or have never (`false`) been benchmarked
+    #[clap(long)]
+    pub benchmarked: Option<bool>,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliBranchVersionsSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliBranchVersionsSort {
+    /// Version number
+    Number,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliBranchTags {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Branch slug or UUID
+    pub branch: ResourceId,
+
+    #[clap(flatten)]
+    pub source: CliBranchTagsSource,
+
+    /// Only record tags that start with this prefix (ie `v`)
+    #[clap(long)]
+    pub prefix: Option<String>,
+
+    /// Only record tags for commits at or after this time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub since: Option<DateTime>,
+
+    /// Print the tags that would be recorded without recording them
+    #[clap(long)]
+    pub dry_run: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Args, Debug)]
+#[clap(group(
+    ArgGroup::new("tags_source")
+        .multiple(false)
+        .args(&["git_dir", "github"]),
+))]
+pub struct CliBranchTagsSource {
+    /// Path to a local git checkout to scan for tags
+    /// (default: the current directory)
+    #[clap(long, value_name = "PATH")]
+    pub git_dir: Option<Utf8PathBuf>,
+
+    /// GitHub repository to list tags from using the GitHub API (ie `bencherdev/bencher`)
+    #[clap(long, value_name = "OWNER/REPO")]
+    pub github: Option<String>,
+
+    /// GitHub API token for a private repository or higher rate limits (used with `--github`)
+    #[clap(long, env = "GITHUB_TOKEN", hide_env_values = true)]
+    pub github_token: Option<String>,
+}
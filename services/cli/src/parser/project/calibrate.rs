@@ -0,0 +1,72 @@
+use clap::{Parser, ValueEnum};
+
+use super::run::{CliRunAdapter, CliRunAverage, CliRunCommand};
+
+#[derive(Parser, Debug)]
+pub struct CliCalibrate {
+    /// Benchmark harness adapter.
+    /// Multiple adapters may be given as a comma-separated list (ie `rust_bench,json`),
+    /// and each one is tried in order until one is able to parse the results.
+    #[clap(
+        value_enum,
+        long,
+        env = "BENCHER_ADAPTER",
+        value_delimiter = ',',
+        default_value = "magic"
+    )]
+    pub adapter: Vec<CliRunAdapter>,
+
+    /// Benchmark harness suggested central tendency (ie average)
+    #[clap(value_enum, long)]
+    pub average: Option<CliRunAverage>,
+
+    /// Number of calibration iterations
+    #[clap(long, value_name = "COUNT", default_value = "10", value_parser = clap::value_parser!(u32).range(2..))]
+    pub iter: u32,
+
+    /// Number of warm-up iterations to run before the calibration iterations (results are discarded)
+    #[clap(long, value_name = "COUNT", default_value = "0", requires = "command")]
+    pub warmup: u32,
+
+    /// Target false positive rate for each Measure in a Report (ie `0.01` for 1%)
+    #[clap(long, value_name = "RATE", default_value = "0.01", value_parser = check_false_positive_rate)]
+    pub false_positive_rate: f64,
+
+    /// Allow benchmark test failure (failed iterations are skipped)
+    #[clap(long)]
+    pub allow_failure: bool,
+
+    /// Format for the recommended threshold settings
+    #[clap(long, default_value = "human")]
+    pub format: CliCalibrateFormat,
+
+    /// Quiet mode, only output the recommended threshold settings to standard out
+    #[clap(short, long)]
+    pub quiet: bool,
+
+    #[clap(flatten)]
+    pub cmd: CliRunCommand,
+}
+
+/// Supported Calibration Formats
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliCalibrateFormat {
+    /// Human
+    Human,
+    /// JSON
+    Json,
+}
+
+fn check_false_positive_rate(arg: &str) -> Result<f64, String> {
+    let rate = arg
+        .parse::<f64>()
+        .map_err(|e| format!("Failed to parse false positive rate `{arg}`: {e}"))?;
+    if rate > 0.0 && rate < 0.5 {
+        Ok(rate)
+    } else {
+        Err(format!(
+            "Expected the false positive rate ({rate}) to be greater than 0 and less than 0.5"
+        ))
+    }
+}
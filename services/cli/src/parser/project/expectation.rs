@@ -0,0 +1,110 @@
+use bencher_json::{DateTime, ExpectationUuid, NameId, NonEmpty, ResourceId};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::{CliBackend, CliPagination};
+
+#[derive(Subcommand, Debug)]
+pub enum CliExpectation {
+    /// List expected regression windows
+    #[clap(alias = "ls")]
+    List(CliExpectationList),
+    /// Create an expected regression window
+    #[clap(alias = "add")]
+    Create(CliExpectationCreate),
+    /// View an expected regression window
+    #[clap(alias = "get")]
+    View(CliExpectationView),
+    /// Delete an expected regression window
+    #[clap(alias = "rm")]
+    Delete(CliExpectationDelete),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliExpectationList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Branch name, slug, or UUID
+    #[clap(long)]
+    pub branch: Option<NameId>,
+
+    /// Expected regression window search string
+    #[clap(long, value_name = "QUERY")]
+    pub search: Option<String>,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliExpectationsSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliExpectationsSort {
+    /// Creation date time of the expected regression window
+    Created,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliExpectationCreate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Branch name, slug, or UUID
+    #[clap(long)]
+    pub branch: NameId,
+
+    /// A regular expression to match against benchmark names.
+    /// Use `.*` to match all benchmarks.
+    #[clap(long, value_name = "REGEX")]
+    pub pattern: NonEmpty,
+
+    /// The reason that the regression is expected and accepted, such as added security checks.
+    /// This is recorded in an annotation for the project.
+    #[clap(long)]
+    pub reason: NonEmpty,
+
+    /// Reports that started at or after this time are in the window (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub start_time: Option<DateTime>,
+
+    /// Reports that started at or before this time are in the window (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub end_time: Option<DateTime>,
+
+    /// Reports for this branch version number or later are in the window
+    #[clap(long, value_name = "NUMBER")]
+    pub start_version: Option<u32>,
+
+    /// Reports for this branch version number or earlier are in the window
+    #[clap(long, value_name = "NUMBER")]
+    pub end_version: Option<u32>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliExpectationView {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Expected regression window UUID
+    pub expectation: ExpectationUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliExpectationDelete {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Expected regression window UUID
+    pub expectation: ExpectationUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
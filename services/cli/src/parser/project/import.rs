@@ -0,0 +1,51 @@
+use bencher_json::{NameId, ResourceId};
+use camino::Utf8PathBuf;
+use clap::{Parser, ValueEnum};
+
+use crate::parser::CliBackend;
+
+#[derive(Parser, Debug)]
+pub struct CliImport {
+    /// Project slug or UUID
+    #[clap(long, env = "BENCHER_PROJECT")]
+    pub project: ResourceId,
+
+    /// Format of the benchmark history to import
+    #[clap(value_enum, long)]
+    pub format: CliImportFormat,
+
+    /// Path to the file or directory of benchmark history to import
+    pub path: Utf8PathBuf,
+
+    /// Branch name, slug, or UUID.
+    /// If a name or slug is provided, the branch will be created if it does not exist.
+    /// If not provided, the branch from the benchmark history is used, if any.
+    /// Otherwise, `main` is used.
+    #[clap(long, env = "BENCHER_BRANCH")]
+    pub branch: Option<NameId>,
+
+    /// Testbed name, slug, or UUID.
+    /// If a name or slug is provided, the testbed will be created if it does not exist.
+    /// If not provided, the environment or machine from the benchmark history is used, if any.
+    /// Otherwise, `localhost` is used.
+    #[clap(long, env = "BENCHER_TESTBED")]
+    pub testbed: Option<NameId>,
+
+    /// Do a dry run (no data is saved)
+    #[clap(long)]
+    pub dry_run: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliImportFormat {
+    /// Codespeed JSON results export
+    Codespeed,
+    /// airspeed velocity (asv) results directory
+    Asv,
+    /// Google Benchmark JSON output file or directory of files
+    GoogleBenchmark,
+}
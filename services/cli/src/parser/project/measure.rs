@@ -1,4 +1,4 @@
-use bencher_json::{ResourceId, ResourceName, Slug};
+use bencher_json::{MeasureAliasUuid, ResourceId, ResourceName, Slug};
 use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliArchived, CliBackend, CliPagination};
@@ -20,6 +20,13 @@ pub enum CliMeasure {
     /// Delete a measure
     #[clap(alias = "rm")]
     Delete(CliMeasureDelete),
+    /// Merge another measure into a measure
+    Merge(CliMeasureMerge),
+    /// Manage measure aliases
+    #[clap(subcommand)]
+    Alias(CliMeasureAlias),
+    /// List the known units of measure and their conversions
+    Units(CliMeasureUnits),
 }
 
 #[derive(Parser, Debug)]
@@ -66,10 +73,15 @@ pub struct CliMeasureCreate {
     #[clap(long)]
     pub slug: Option<Slug>,
 
-    /// Units of measure
+    /// Units of measure.
+    /// Must be a known unit, such as `nanoseconds (ns)` or `ns`, unless `--custom-units` is set.
     #[clap(long)]
     pub units: ResourceName,
 
+    /// Allow units of measure that are not known units
+    #[clap(long)]
+    pub custom_units: bool,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -102,10 +114,15 @@ pub struct CliMeasureUpdate {
     #[clap(long)]
     pub slug: Option<Slug>,
 
-    /// Units of measure
+    /// Units of measure.
+    /// Must be a known unit, such as `nanoseconds (ns)` or `ns`, unless `--custom-units` is set.
     #[clap(long)]
     pub units: Option<ResourceName>,
 
+    /// Allow units of measure that are not known units
+    #[clap(long, requires = "units")]
+    pub custom_units: bool,
+
     #[clap(flatten)]
     pub archived: CliArchived,
 
@@ -124,3 +141,86 @@ pub struct CliMeasureDelete {
     #[clap(flatten)]
     pub backend: CliBackend,
 }
+
+#[derive(Parser, Debug)]
+pub struct CliMeasureMerge {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Slug or UUID of the measure to merge into
+    pub measure: ResourceId,
+
+    /// Slug or UUID of the measure to merge from.
+    /// This measure is deleted and its name and slug become aliases.
+    #[clap(long)]
+    pub from: ResourceId,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CliMeasureAlias {
+    /// List measure aliases
+    #[clap(alias = "ls")]
+    List(CliMeasureAliasList),
+    /// Create a measure alias
+    #[clap(alias = "add")]
+    Create(CliMeasureAliasCreate),
+    /// Delete a measure alias
+    #[clap(alias = "rm")]
+    Delete(CliMeasureAliasDelete),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliMeasureAliasList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Measure slug or UUID
+    pub measure: ResourceId,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliMeasureAliasCreate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Measure slug or UUID
+    pub measure: ResourceId,
+
+    /// Measure name used by an adapter that should map onto the measure
+    #[clap(long)]
+    pub alias: ResourceName,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliMeasureAliasDelete {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Measure slug or UUID
+    pub measure: ResourceId,
+
+    /// Measure alias UUID
+    pub alias: MeasureAliasUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliMeasureUnits {
+    /// Only show this unit and the known units that it can be converted into
+    pub units: Option<String>,
+
+    /// Convert this value from the given units into each of the known units that it can be converted into
+    #[clap(long, requires = "units")]
+    pub value: Option<f64>,
+}
@@ -0,0 +1,121 @@
+use bencher_json::{Email, ResourceId, UserName};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::{CliBackend, CliPagination};
+
+#[derive(Subcommand, Debug)]
+pub enum CliProjectMember {
+    /// List project members
+    #[clap(alias = "ls")]
+    List(CliProjectMemberList),
+    /// Invite a project member
+    Invite(CliProjectMemberInvite),
+    /// View a project member
+    #[clap(alias = "get")]
+    View(CliProjectMemberView),
+    /// Update a project member
+    #[clap(alias = "edit")]
+    Update(CliProjectMemberUpdate),
+    /// Remove a project member
+    #[clap(alias = "rm")]
+    Remove(CliProjectMemberRemove),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliProjectMemberList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Member name
+    #[clap(long)]
+    pub name: Option<UserName>,
+
+    /// Member search string
+    #[clap(long, value_name = "QUERY")]
+    pub search: Option<String>,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliProjectMembersSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliProjectMembersSort {
+    /// Name of the member
+    Name,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliProjectMemberInvite {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Name of user for invitation (optional)
+    #[clap(long)]
+    pub name: Option<UserName>,
+
+    /// Email for the invitation
+    #[clap(long)]
+    pub email: Email,
+
+    /// Member role
+    #[clap(value_enum, long)]
+    pub role: CliProjectMemberRole,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliProjectMemberView {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliProjectMemberUpdate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    /// Member role
+    #[clap(value_enum, long)]
+    pub role: Option<CliProjectMemberRole>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliProjectMemberRemove {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+/// Role within the project
+#[derive(ValueEnum, Debug, Clone)]
+pub enum CliProjectMemberRole {
+    /// Can view the project
+    Viewer,
+    /// Can view and submit reports to the project
+    Reporter,
+    /// Can manage the project and its members
+    Maintainer,
+}
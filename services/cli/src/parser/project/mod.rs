@@ -1,19 +1,26 @@
-use bencher_json::{ResourceId, ResourceName, Slug, Url};
-use clap::{Parser, Subcommand, ValueEnum};
+use bencher_json::{ResourceId, ResourceName, Slug, TimeZone, Url};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 
 use crate::parser::CliBackend;
 
 use super::{CliPagination, ElidedOption};
 
 pub mod alert;
+pub mod annotation;
 pub mod archive;
 pub mod benchmark;
 pub mod branch;
+pub mod calibrate;
+pub mod expectation;
+pub mod import;
 pub mod measure;
+pub mod member;
 pub mod metric;
 pub mod perf;
 pub mod plot;
+pub mod query;
 pub mod report;
+pub mod rule;
 pub mod run;
 pub mod testbed;
 pub mod threshold;
@@ -36,6 +43,9 @@ pub enum CliProject {
     Delete(CliProjectDelete),
     /// Check project permission
     Allowed(CliProjectAllowed),
+    /// Manage project members
+    #[clap(subcommand)]
+    Member(member::CliProjectMember),
 }
 
 #[derive(Parser, Debug)]
@@ -51,6 +61,10 @@ pub struct CliProjectList {
     #[clap(long, value_name = "QUERY")]
     pub search: Option<String>,
 
+    /// Project visibility
+    #[clap(long)]
+    pub visibility: Option<CliProjectVisibility>,
+
     #[clap(flatten)]
     pub pagination: CliPagination<CliProjectsSort>,
 
@@ -86,6 +100,18 @@ pub struct CliProjectCreate {
     #[clap(long, default_value = "public")]
     pub visibility: CliProjectVisibility,
 
+    /// Project time zone, as an IANA time zone name (ie `America/New_York`)
+    #[clap(long)]
+    pub time_zone: Option<TimeZone>,
+
+    /// First day of the project reporting week
+    #[clap(long)]
+    pub week_start: Option<CliProjectWeekStart>,
+
+    /// Reject reports with benchmarks or measures that do not already exist in the project
+    #[clap(long)]
+    pub strict: bool,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -96,6 +122,20 @@ pub enum CliProjectVisibility {
     Public,
     #[cfg(feature = "plus")]
     Private,
+    #[cfg(feature = "plus")]
+    Internal,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliProjectWeekStart {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
 }
 
 #[derive(Parser, Debug)]
@@ -103,6 +143,10 @@ pub struct CliProjectView {
     /// Project slug or UUID
     pub project: ResourceId,
 
+    /// Include resource counts and approximate storage usage
+    #[clap(long)]
+    pub stats: bool,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -131,10 +175,49 @@ pub struct CliProjectUpdate {
     #[clap(long)]
     pub visibility: Option<CliProjectVisibility>,
 
+    /// Project time zone, as an IANA time zone name (ie `America/New_York`)
+    #[clap(long)]
+    pub time_zone: Option<TimeZone>,
+
+    /// First day of the project reporting week
+    #[clap(long)]
+    pub week_start: Option<CliProjectWeekStart>,
+
+    #[clap(flatten)]
+    pub strict: CliProjectStrict,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
 
+#[derive(Args, Debug)]
+#[clap(group(
+    ArgGroup::new("project_strict")
+        .multiple(false)
+        .args(&["strict", "no_strict"]),
+))]
+pub struct CliProjectStrict {
+    /// Reject reports with benchmarks or measures that do not already exist in the project
+    #[clap(long)]
+    pub strict: bool,
+
+    /// Allow reports to create new benchmarks and measures
+    #[clap(long)]
+    pub no_strict: bool,
+}
+
+impl From<CliProjectStrict> for Option<bool> {
+    fn from(strict: CliProjectStrict) -> Option<bool> {
+        match (strict.strict, strict.no_strict) {
+            (false, false) => None,
+            (false, true) => Some(false),
+            (true, false) => Some(true),
+            #[allow(clippy::unreachable)]
+            (true, true) => unreachable!("Cannot set both `strict` and `no_strict`"),
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 pub struct CliProjectDelete {
     /// Project slug or UUID
@@ -162,6 +245,7 @@ pub struct CliProjectAllowed {
 #[clap(rename_all = "snake_case")]
 pub enum CliProjectPermission {
     View,
+    Report,
     Create,
     Edit,
     Delete,
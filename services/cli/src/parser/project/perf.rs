@@ -1,5 +1,6 @@
 use bencher_json::{
-    BenchmarkUuid, BranchUuid, DateTime, HeadUuid, MeasureUuid, ResourceId, TestbedUuid,
+    BenchmarkUuid, BranchUuid, DateTime, HeadUuid, MeasureUuid, NonEmpty, ResourceId, ResourceName,
+    TestbedUuid,
 };
 use clap::{Parser, ValueEnum};
 
@@ -40,10 +41,31 @@ pub struct CliPerf {
     #[clap(long, value_name = "SECONDS")]
     pub end_time: Option<DateTime>,
 
+    /// Only include results from reports with these release tags, such as `v1.42.0`
+    #[clap(long = "tag", value_name = "TAG")]
+    pub tags: Vec<NonEmpty>,
+
+    /// Convert the results into these units, such as `ms`.
+    /// Run `bencher measure units` to list the known units of measure.
+    #[clap(long)]
+    pub unit: Option<ResourceName>,
+
     /// Output results in a table
     #[clap(long)]
     pub table: Option<Option<CliPerfTableStyle>>,
 
+    /// Summarize each benchmark as a single table row,
+    /// with a sparkline of the results over the queried time window
+    /// and the current result compared to its baseline
+    #[clap(long, requires = "table")]
+    pub sparkline: bool,
+
+    /// User slug or UUID whose display preferences are used for the table output.
+    /// Results are converted into the preferred units, unless `--unit` is set,
+    /// and dates are shown in the preferred time zone and date format.
+    #[clap(long, env = "BENCHER_USER")]
+    pub user: Option<ResourceId>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -0,0 +1,209 @@
+use bencher_json::{
+    BenchmarkUuid, BranchUuid, Email, MeasureUuid, ResourceId, ResourceName, SavedQueryUuid,
+    TestbedUuid, Window,
+};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+
+use crate::parser::{CliBackend, CliPagination};
+
+#[derive(Subcommand, Debug)]
+pub enum CliQuery {
+    /// List saved queries
+    #[clap(alias = "ls")]
+    List(CliQueryList),
+    /// Create a saved query
+    #[clap(alias = "add")]
+    Create(CliQueryCreate),
+    /// View a saved query
+    #[clap(alias = "get")]
+    View(CliQueryView),
+    // Update a saved query
+    #[clap(alias = "edit")]
+    Update(CliQueryUpdate),
+    /// Delete a saved query
+    #[clap(alias = "rm")]
+    Delete(CliQueryDelete),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliQueryList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Saved query name
+    #[clap(long)]
+    pub name: Option<ResourceName>,
+
+    /// Saved query search string
+    #[clap(long, value_name = "QUERY")]
+    pub search: Option<String>,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliQueriesSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliQueriesSort {
+    /// Name of the saved query
+    Name,
+    /// Time the last summary was sent
+    LastSent,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliQueryCreate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// The name of the saved query.
+    /// Maximum length is 64 characters.
+    #[clap(long)]
+    pub name: ResourceName,
+
+    /// The window of time for the saved query, in seconds.
+    /// The window ends at the start of the day, in the project time zone, when the saved query is run.
+    #[clap(long, value_name = "SECONDS")]
+    pub window: Window,
+
+    /// The branches to include in the saved query.
+    /// At least one branch must be specified.
+    #[clap(long, required = true, value_name = "BRANCH")]
+    pub branches: Vec<BranchUuid>,
+
+    /// The testbeds to include in the saved query.
+    /// At least one testbed must be specified.
+    #[clap(long, required = true, value_name = "TESTBED")]
+    pub testbeds: Vec<TestbedUuid>,
+
+    /// The benchmarks to include in the saved query.
+    /// At least one benchmark must be specified.
+    #[clap(long, required = true, value_name = "BENCHMARK")]
+    pub benchmarks: Vec<BenchmarkUuid>,
+
+    /// The measures to include in the saved query.
+    /// At least one measure must be specified.
+    #[clap(long, required = true, value_name = "MEASURE")]
+    pub measures: Vec<MeasureUuid>,
+
+    /// How often to send a summary of the saved query (default: never)
+    #[clap(long)]
+    pub schedule: Option<CliQuerySchedule>,
+
+    /// An email address to send each summary to.
+    /// Can be specified multiple times.
+    #[clap(long = "email", value_name = "EMAIL")]
+    pub emails: Vec<Email>,
+
+    /// Also send each summary to the project webhook
+    #[clap(long)]
+    pub webhook: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+/// How often a summary is sent
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliQuerySchedule {
+    /// Never send a summary
+    Never,
+    /// Send a summary every day
+    Daily,
+    /// Send a summary on the first day of the project reporting week
+    Weekly,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliQueryView {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Saved query UUID
+    pub query: SavedQueryUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliQueryUpdate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Saved query UUID
+    pub query: SavedQueryUuid,
+
+    /// The new name of the saved query.
+    /// Maximum length is 64 characters.
+    #[clap(long)]
+    pub name: Option<ResourceName>,
+
+    /// The new window of time for the saved query, in seconds.
+    #[clap(long, value_name = "SECONDS")]
+    pub window: Option<Window>,
+
+    /// How often to send a summary of the saved query
+    #[clap(long)]
+    pub schedule: Option<CliQuerySchedule>,
+
+    /// An email address to send each summary to.
+    /// Can be specified multiple times.
+    /// This replaces all of the current email addresses.
+    #[clap(long = "email", value_name = "EMAIL")]
+    pub emails: Vec<Email>,
+
+    /// Stop sending summaries to any email addresses
+    #[clap(long, conflicts_with = "emails")]
+    pub no_emails: bool,
+
+    #[clap(flatten)]
+    pub webhook: CliQueryWebhook,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Args, Debug)]
+#[clap(group(
+    ArgGroup::new("query_webhook")
+        .multiple(false)
+        .args(&["webhook", "no_webhook"]),
+))]
+pub struct CliQueryWebhook {
+    /// Also send each summary to the project webhook
+    #[clap(long)]
+    pub webhook: bool,
+
+    /// Stop sending summaries to the project webhook
+    #[clap(long)]
+    pub no_webhook: bool,
+}
+
+impl From<CliQueryWebhook> for Option<bool> {
+    fn from(webhook: CliQueryWebhook) -> Option<bool> {
+        match (webhook.webhook, webhook.no_webhook) {
+            (false, false) => None,
+            (false, true) => Some(false),
+            (true, false) => Some(true),
+            #[allow(clippy::unreachable)]
+            (true, true) => unreachable!("Cannot set both `webhook` and `no_webhook`"),
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct CliQueryDelete {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Saved query UUID
+    pub query: SavedQueryUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
@@ -1,9 +1,9 @@
-use bencher_json::{DateTime, GitHash, NameId, ReportUuid, ResourceId};
+use bencher_json::{DateTime, GitHash, NameId, NonEmpty, ReportUuid, ResourceId};
 use clap::{Parser, Subcommand, ValueEnum};
 
 use super::{
     branch::CliStartPointUpdate,
-    run::{CliRunAdapter, CliRunAverage, CliRunFold, CliRunThresholds},
+    run::{CliRunAdapter, CliRunAverage, CliRunDispersion, CliRunFold, CliRunThresholds},
 };
 use crate::parser::{CliBackend, CliPagination};
 
@@ -36,6 +36,10 @@ pub struct CliReportList {
     #[clap(long)]
     pub testbed: Option<NameId>,
 
+    /// Release tag, such as `v1.42.0`
+    #[clap(long)]
+    pub tag: Option<NonEmpty>,
+
     /// Start time (seconds since epoch)
     #[clap(long, value_name = "SECONDS")]
     pub start_time: Option<DateTime>,
@@ -44,6 +48,18 @@ pub struct CliReportList {
     #[clap(long, value_name = "SECONDS")]
     pub end_time: Option<DateTime>,
 
+    /// Git hash, either full or a short hash of at least 4 characters
+    #[clap(long)]
+    pub hash: Option<String>,
+
+    /// Start branch version number (inclusive)
+    #[clap(long, value_name = "NUMBER")]
+    pub start_version: Option<u32>,
+
+    /// End branch version number (inclusive)
+    #[clap(long, value_name = "NUMBER")]
+    pub end_version: Option<u32>,
+
     #[clap(flatten)]
     pub pagination: CliPagination<CliReportsSort>,
 
@@ -60,6 +76,8 @@ pub struct CliReportList {
 pub enum CliReportsSort {
     /// Date time of the report
     DateTime,
+    /// Branch version number of the report
+    Version,
 }
 
 #[derive(Parser, Debug)]
@@ -78,6 +96,10 @@ pub struct CliReportCreate {
     #[clap(flatten)]
     pub start_point: CliStartPointUpdate,
 
+    /// Reference branch name, slug, or UUID to compare against instead of `branch`
+    #[clap(long)]
+    pub reference_branch: Option<NameId>,
+
     /// Testbed name, slug, or UUID
     #[clap(long)]
     pub testbed: NameId,
@@ -109,6 +131,10 @@ pub struct CliReportCreate {
     #[clap(value_enum, long)]
     pub fold: Option<CliRunFold>,
 
+    /// Keep each result as a separate iteration and then add a summary iteration with their central tendency and dispersion
+    #[clap(value_enum, long, conflicts_with = "fold")]
+    pub dispersion: Option<CliRunDispersion>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -121,6 +147,15 @@ pub struct CliReportView {
     /// Report UUID
     pub report: ReportUuid,
 
+    /// Output the results for each iteration in Bencher Metric Format (BMF) JSON,
+    /// as they were parsed from the benchmark harness output
+    #[clap(long)]
+    pub raw: bool,
+
+    /// Only output the results for the given zero-based iteration
+    #[clap(long, requires = "raw")]
+    pub iteration: Option<u32>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -0,0 +1,110 @@
+use bencher_json::{NonEmpty, ResourceId, RuleUuid};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::{CliBackend, CliPagination};
+
+#[derive(Subcommand, Debug)]
+pub enum CliRule {
+    /// List rules
+    #[clap(alias = "ls")]
+    List(CliRuleList),
+    /// Create a rule
+    #[clap(alias = "add")]
+    Create(CliRuleCreate),
+    /// View a rule
+    #[clap(alias = "get")]
+    View(CliRuleView),
+    // Update a rule
+    #[clap(alias = "edit")]
+    Update(CliRuleUpdate),
+    /// Delete a rule
+    #[clap(alias = "rm")]
+    Delete(CliRuleDelete),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliRuleList {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Rule search string
+    #[clap(long, value_name = "QUERY")]
+    pub search: Option<String>,
+
+    #[clap(flatten)]
+    pub pagination: CliPagination<CliRulesSort>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliRulesSort {
+    /// Creation date time of the rule
+    Created,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliRuleCreate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// A regular expression to match against benchmark names.
+    /// Every match in a benchmark name is replaced with the `--replacement`.
+    #[clap(long, value_name = "REGEX")]
+    pub pattern: NonEmpty,
+
+    /// The replacement for each match of the `--pattern`.
+    /// Capture groups can be referenced with `$1`, `$2`, or `$name`.
+    /// Set to an empty string to remove the matched text.
+    #[clap(long)]
+    pub replacement: String,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliRuleView {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Rule UUID
+    pub rule: RuleUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliRuleUpdate {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Rule UUID
+    pub rule: RuleUuid,
+
+    /// The new regular expression to match against benchmark names.
+    #[clap(long, value_name = "REGEX")]
+    pub pattern: Option<NonEmpty>,
+
+    /// The new replacement for each match of the pattern.
+    #[clap(long)]
+    pub replacement: Option<String>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliRuleDelete {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Rule UUID
+    pub rule: RuleUuid,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
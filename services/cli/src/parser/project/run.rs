@@ -1,18 +1,23 @@
 use bencher_json::{
-    project::testbed::TESTBED_LOCALHOST_STR, Boundary, DateTime, GitHash, NameId, ResourceId,
-    SampleSize, Window,
+    project::testbed::TESTBED_LOCALHOST_STR, Boundary, DateTime, Decay, GitHash, NameId, NonEmpty,
+    ResourceId, SampleSize, Url, Window,
 };
 use camino::Utf8PathBuf;
 use clap::{ArgGroup, Args, Parser, ValueEnum};
 
-use crate::parser::{CliBackend, ElidedOption};
+use crate::{
+    parser::{CliBackend, ElidedOption},
+    EXIT_CODES_HELP,
+};
 
-use super::threshold::CliModelTest;
+use super::{alert::CliAlertSeverity, threshold::CliModelTest};
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
+#[clap(after_help = EXIT_CODES_HELP)]
 #[allow(clippy::option_option, clippy::struct_excessive_bools)]
 pub struct CliRun {
     /// Project slug or UUID
+    /// Without an API token, the report is sent for an unclaimed project, which must be a slug.
     #[clap(long, env = "BENCHER_PROJECT")]
     pub project: ResourceId,
 
@@ -24,9 +29,17 @@ pub struct CliRun {
     #[clap(long, env = "BENCHER_TESTBED", default_value = TESTBED_LOCALHOST_STR)]
     pub testbed: NameId,
 
-    /// Benchmark harness adapter
-    #[clap(value_enum, long, env = "BENCHER_ADAPTER", default_value = "magic")]
-    pub adapter: CliRunAdapter,
+    /// Benchmark harness adapter.
+    /// Multiple adapters may be given as a comma-separated list (ie `rust_bench,json`),
+    /// and each one is tried in order until one is able to parse the results.
+    #[clap(
+        value_enum,
+        long,
+        env = "BENCHER_ADAPTER",
+        value_delimiter = ',',
+        default_value = "magic"
+    )]
+    pub adapter: Vec<CliRunAdapter>,
 
     /// Benchmark harness suggested central tendency (ie average)
     #[clap(value_enum, long)]
@@ -36,10 +49,19 @@ pub struct CliRun {
     #[clap(long, value_name = "COUNT", default_value = "1")]
     pub iter: usize,
 
+    /// Number of warm-up iterations to run before the measured run iterations (results are discarded)
+    #[clap(long, value_name = "COUNT", default_value = "0", requires = "command")]
+    pub warmup: u32,
+
     /// Fold multiple results into a single result using an aggregate function
     #[clap(value_enum, long, requires = "iter", value_name = "AGGREGATE_FUNCTION")]
     pub fold: Option<CliRunFold>,
 
+    /// Keep each iteration as a separate result and then add a summary iteration with their central tendency and dispersion
+    /// Only the summary iteration is checked against thresholds
+    #[clap(value_enum, long, requires = "iter", conflicts_with = "fold")]
+    pub dispersion: Option<CliRunDispersion>,
+
     /// Backdate the report (seconds since epoch)
     /// NOTE: This will NOT effect the ordering of past reports
     #[clap(long, value_name = "SECONDS")]
@@ -49,13 +71,84 @@ pub struct CliRun {
     #[clap(long)]
     pub allow_failure: bool,
 
+    /// Attach the exit code, wall time, and end of the standard error output
+    /// of each skipped failure to the report
+    #[clap(long, requires = "allow_failure")]
+    pub capture_failures: bool,
+
+    /// Allow benchmark command timeouts, skipping any iterations that time out like `--allow-failure`
+    #[clap(long, requires = "timeout")]
+    pub allow_timeout: bool,
+
+    /// Keep the results of a failed benchmark command and, after submitting the report,
+    /// exit with the benchmark command's own exit code
+    #[clap(long, conflicts_with = "allow_failure")]
+    pub passthrough_exit: bool,
+
     #[clap(flatten)]
     pub thresholds: CliRunThresholds,
 
-    /// Error on alert
-    #[clap(long)]
+    /// Error on alert (same as `--err-on minor`)
+    #[clap(long, conflicts_with = "err_on")]
     pub err: bool,
 
+    /// Error on alerts with at least the given severity
+    #[clap(value_enum, long, value_name = "SEVERITY")]
+    pub err_on: Option<CliAlertSeverity>,
+
+    /// Print a warning for alerts with at least the given severity that do not cause an error
+    #[clap(value_enum, long, value_name = "SEVERITY")]
+    pub warn_on: Option<CliAlertSeverity>,
+
+    /// Submit the results as one shard of a run that is split across multiple jobs.
+    /// Expected format is `INDEX/COUNT` (ie `1/4`), where the index starts at `1`.
+    /// The shards are merged into a single report once all of them have been received.
+    #[clap(long, value_name = "INDEX/COUNT", value_parser = check_shard, requires = "shard_key")]
+    pub shard: Option<CliRunShard>,
+
+    /// The key for the run that the shard is a part of, such as the CI run ID.
+    /// All of the shards for a run must use the same key.
+    /// This is ignored if `--shard` is not set,
+    /// so `BENCHER_SHARD_KEY` can be set for every job in a CI run.
+    #[clap(long, env = "BENCHER_SHARD_KEY")]
+    pub shard_key: Option<NonEmpty>,
+
+    /// Tag the report with a release identifier, such as `v1.42.0`.
+    /// Reports and perf queries can then be filtered by their release tag.
+    #[clap(long, env = "BENCHER_TAG")]
+    pub tag: Option<NonEmpty>,
+
+    /// Attach a free-form note to the report (may be used multiple times)
+    #[clap(long, value_name = "NOTE")]
+    pub note: Vec<NonEmpty>,
+
+    /// Attach a link to the report, such as the CI job or a flamegraph artifact (may be used multiple times)
+    #[clap(long, value_name = "URL")]
+    pub link: Vec<Url>,
+
+    /// Attach the `git` branch, tag, dirty working tree status,
+    /// and commit author, time, and subject message to the report
+    #[clap(long)]
+    pub git_metadata: bool,
+
+    /// Attach a hardware fingerprint of the machine to the report,
+    /// with the CPU model, frequency governor, and whether it is a virtual machine
+    #[clap(long)]
+    pub fingerprint: bool,
+
+    /// Reject the report if it has any benchmarks or measures that do not already exist in the project,
+    /// instead of creating them
+    #[clap(long)]
+    pub strict: bool,
+
+    /// Warn if the current machine does not match the hardware spec declared for the testbed
+    #[clap(long)]
+    pub check_testbed: bool,
+
+    /// Error if the current machine does not match the hardware spec declared for the testbed
+    #[clap(long)]
+    pub strict_testbed: bool,
+
     #[clap(flatten)]
     pub output: CliRunOutput,
 
@@ -63,6 +156,9 @@ pub struct CliRun {
     #[clap(flatten)]
     pub ci: CliRunCi,
 
+    #[clap(flatten)]
+    pub matrix: CliRunMatrix,
+
     #[clap(flatten)]
     pub cmd: CliRunCommand,
 
@@ -74,7 +170,7 @@ pub struct CliRun {
     pub backend: CliBackend,
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 #[allow(clippy::option_option)]
 pub struct CliRunBranch {
     /// Branch name, slug, or UUID.
@@ -113,12 +209,18 @@ pub struct CliRunBranch {
     #[clap(long, alias = "branch-reset")]
     pub start_point_reset: bool,
 
+    /// Compare against the baseline of the specified reference branch name, slug, or UUID instead of `branch`.
+    /// The reference branch must already exist, and its current head is always used.
+    /// If `branch` does not have a threshold, then the reference branch threshold is used.
+    #[clap(long)]
+    pub reference_branch: Option<NameId>,
+
     /// Deprecated: Do not use. This will soon be removed.
     #[clap(long, hide = true, alias = "else-branch", alias = "endif-branch")]
     pub deprecated: bool,
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 #[clap(group(
     ArgGroup::new("run_hash")
         .multiple(false)
@@ -134,7 +236,7 @@ pub struct CliRunHash {
     pub no_hash: bool,
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct CliRunThresholds {
     /// Threshold Measure name, slug, or UUID
     /// When specifying multiple Thresholds, all of the same options must be used for each Threshold.
@@ -171,22 +273,65 @@ pub struct CliRunThresholds {
     #[clap(long, requires = "threshold_test")]
     pub threshold_upper_boundary: Vec<ElidedOption<Boundary>>,
 
+    /// Decay factor (EWMA test only)
+    /// To ignore a this option when specifying multiple Thresholds, use an underscore (`_`).
+    #[clap(long, requires = "threshold_test")]
+    pub threshold_decay: Vec<ElidedOption<Decay>>,
+
     /// Reset all unspecified Thresholds for the `branch` and `testbed`
     /// If a Threshold already exists and is not specified, its current Model will be removed.
     #[clap(long)]
     pub thresholds_reset: bool,
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
+pub struct CliRunMatrix {
+    /// Run each combination of branch, testbed, and benchmark command in a matrix file (`.json`, `.toml`, or `.yaml`)
+    /// and submit a separate report for each one.
+    /// The rest of the arguments are shared by every combination.
+    #[clap(long, value_name = "PATH", conflicts_with_all = ["command", "shard"])]
+    pub matrix: Option<Utf8PathBuf>,
+
+    /// Run the combinations in the matrix file in parallel (requires: `--matrix`).
+    /// The benchmark harness output of each combination is not logged.
+    #[clap(long, requires = "matrix")]
+    pub matrix_parallel: bool,
+}
+
+#[derive(Args, Debug, Clone)]
 pub struct CliRunCommand {
-    /// Benchmark command output file path
+    /// Benchmark command output file path or glob pattern (ie `artifacts/*.json`)
+    /// If more than one file path or a glob pattern is given,
+    /// the results from each file are parsed by the adapter and combined into a single iteration.
     #[clap(long, conflicts_with = "file_size")]
-    pub file: Option<Utf8PathBuf>,
+    pub file: Option<Vec<Utf8PathBuf>>,
 
     /// Track the size of a file at the given file path
     #[clap(long, conflicts_with = "file")]
     pub file_size: Option<Vec<Utf8PathBuf>>,
 
+    /// Also track the size of each kind of section (text, data, rodata, bss) for object files (ELF, Mach-O, PE)
+    #[clap(long, requires = "file_size")]
+    pub file_size_sections: bool,
+
+    /// Also track the size of the given number of largest symbols for object files (ELF, Mach-O, PE)
+    #[clap(long, value_name = "COUNT", requires = "file_size")]
+    pub file_size_symbols: Option<usize>,
+
+    /// Wrap the benchmark command with `perf stat` to track hardware counters (Linux only)
+    #[clap(long, requires = "command", conflicts_with_all = ["file", "file_size"])]
+    pub perf_stat: bool,
+
+    /// Kill each iteration of the benchmark command if it runs longer than the given number of seconds
+    #[clap(long, value_name = "SECONDS", requires = "command", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// After a timeout, send the benchmark command a termination signal
+    /// and wait the given number of seconds for it to exit before killing it.
+    /// By default, the benchmark command is killed immediately.
+    #[clap(long, value_name = "SECONDS", requires = "timeout")]
+    pub kill_after: Option<u64>,
+
     #[clap(flatten)]
     pub sh_c: CliRunShell,
 
@@ -208,7 +353,7 @@ pub struct CliRunCommand {
     pub command: Option<Vec<String>>,
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct CliRunShell {
     /// Shell command path
     #[clap(long)]
@@ -256,13 +401,21 @@ pub enum CliRunAdapter {
     JsBenchmark,
     /// 🕸 JavaScript Time
     JsTime,
+    /// 🕸 JavaScript Node.js Test Runner
+    JsNodeTest,
+    /// 🕸 JavaScript Tinybench
+    JsTinybench,
     // TODO remove in due time
     #[clap(hide = true)]
     Python,
     /// 🐍 Python ASV
     PythonAsv,
+    /// 🐍 Python pyperf
+    PythonPyperf,
     /// 🐍 Python Pytest
     PythonPytest,
+    /// 🐍 Python richbench
+    PythonRichbench,
     // TODO remove in due time
     #[clap(hide = true)]
     Ruby,
@@ -310,7 +463,17 @@ pub enum CliRunFold {
     Median,
 }
 
-#[derive(Args, Debug)]
+/// Supported Dispersion Measures
+#[derive(ValueEnum, Debug, Clone)]
+#[clap(rename_all = "snake_case")]
+pub enum CliRunDispersion {
+    /// Standard deviation, stored alongside the mean
+    Stddev,
+    /// Interquartile range, stored alongside the median
+    Iqr,
+}
+
+#[derive(Args, Debug, Clone)]
 pub struct CliRunOutput {
     /// Format for the final Report
     #[clap(long, default_value = "human")]
@@ -318,6 +481,10 @@ pub struct CliRunOutput {
     /// Quite mode, only output the final Report to standard out
     #[clap(short, long)]
     pub quiet: bool,
+    /// User slug or UUID whose display preferences are used for the final Report,
+    /// such as the preferred units and date format
+    #[clap(long, env = "BENCHER_USER")]
+    pub user: Option<ResourceId>,
 }
 
 /// Supported Report Formats
@@ -330,10 +497,13 @@ pub enum CliRunFormat {
     Json,
     /// HTML
     Html,
+    /// NDJSON event stream, with one JSON event per line as the run progresses.
+    /// This implies `--quiet`.
+    Ndjson,
 }
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 #[clap(group(
     ArgGroup::new("ci_cd")
         .multiple(false)
@@ -358,6 +528,22 @@ pub struct CliRunCi {
     /// Issue number for posting results to CI (requires: `--github-actions`)
     #[clap(long, requires = "ci_cd")]
     pub ci_number: Option<u64>,
+    /// Custom Tera template file for the CI comment (requires: `--github-actions`)
+    #[clap(long, value_name = "PATH", requires = "ci_cd")]
+    pub ci_template: Option<Utf8PathBuf>,
+    /// Locale bundle file (`.json` or `.toml`) to translate the CI comment labels (requires: `--github-actions`)
+    #[clap(long, value_name = "PATH", requires = "ci_cd")]
+    pub ci_locale: Option<Utf8PathBuf>,
+    /// Hide a column of the CI comment results tables. May be used multiple times. (requires: `--github-actions`)
+    #[clap(long, value_name = "COLUMN", requires = "ci_cd")]
+    pub ci_hide_column: Vec<CliRunCiColumn>,
+    /// Put the results for each Measure in its own collapsible section of the CI comment (requires: `--github-actions`)
+    #[clap(long, requires = "ci_cd")]
+    pub ci_collapse_measures: bool,
+    /// Add a column to the CI comment results tables that compares against a prior Report for the same Branch and Testbed:
+    /// `tag` for the most recent Report with a release tag or `git` tag or `N` for the Report from `N` Reports ago (requires: `--github-actions`)
+    #[clap(long, value_name = "PRIOR", value_parser = check_ci_compare, requires = "ci_cd")]
+    pub ci_compare: Option<CliRunCiCompare>,
     /// CAUTION: Override safety checks and accept that you are vulnerable to pwn requests (requires: `--github-actions`)
     #[clap(long, requires = "ci_cd", hide = true)]
     pub ci_i_am_vulnerable_to_pwn_requests: bool,
@@ -366,3 +552,111 @@ pub struct CliRunCi {
     #[clap(long, alias = "ci-no-metrics", hide = true)]
     pub ci_deprecated: bool,
 }
+
+/// Optional CI comment columns
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliRunCiColumn {
+    /// Links to the plot, alert, and threshold
+    View,
+    /// Percent change from the baseline
+    PercentChange,
+    /// Measure units
+    Units,
+    /// Lower boundary limit
+    LowerBoundary,
+    /// Upper boundary limit
+    UpperBoundary,
+}
+
+/// The prior Report to compare against in the CI comment
+#[derive(Debug, Clone, Copy)]
+pub enum CliRunCiCompare {
+    /// The most recent Report with a release tag or `git` tag
+    Tag,
+    /// The Report from this many Reports ago
+    ReportsAgo(u8),
+}
+
+/// Check that input argument is either `tag` or a number of Reports ago
+fn check_ci_compare(arg: &str) -> Result<CliRunCiCompare, String> {
+    if arg == "tag" {
+        return Ok(CliRunCiCompare::Tag);
+    }
+    let reports_ago = arg.parse::<u8>().map_err(|e| {
+        format!("Expected `tag` or a number of Reports ago (1 to 255) but got `{arg}`: {e}")
+    })?;
+    if reports_ago == 0 {
+        return Err("Expected the number of Reports ago to be at least 1".to_owned());
+    }
+    Ok(CliRunCiCompare::ReportsAgo(reports_ago))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CliRunShard {
+    pub index: u32,
+    pub count: u32,
+}
+
+/// Check that input argument is in the form `INDEX/COUNT`
+fn check_shard(arg: &str) -> Result<CliRunShard, String> {
+    let Some((index, count)) = arg.split_once('/') else {
+        return Err(format!(
+            "Expected the shard to be in the form `INDEX/COUNT` (ie `1/4`) but got `{arg}`"
+        ));
+    };
+    let index = index
+        .parse::<u32>()
+        .map_err(|e| format!("Failed to parse shard index `{index}`: {e}"))?;
+    let count = count
+        .parse::<u32>()
+        .map_err(|e| format!("Failed to parse shard count `{count}`: {e}"))?;
+    if index == 0 || index > count {
+        return Err(format!(
+            "Expected the shard index ({index}) to be between 1 and the shard count ({count})"
+        ));
+    }
+    Ok(CliRunShard { index, count })
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::{check_shard, CliRun};
+
+    #[test]
+    fn test_check_shard() {
+        let shard = check_shard("2/4").unwrap();
+        assert_eq!((shard.index, shard.count), (2, 4));
+        let shard = check_shard("1/1").unwrap();
+        assert_eq!((shard.index, shard.count), (1, 1));
+
+        for arg in ["", "2", "0/4", "5/4", "a/4", "2/b", "-1/4"] {
+            assert!(check_shard(arg).is_err(), "{arg}");
+        }
+    }
+
+    #[test]
+    fn test_shard_key_without_shard() {
+        // The shard key may be set for every job in a CI run, even the ones that are not sharded
+        let run =
+            CliRun::try_parse_from(["run", "--project", "project", "--shard-key", "key", "echo"])
+                .unwrap();
+        assert!(run.shard.is_none());
+        assert_eq!(run.shard_key.unwrap().as_ref(), "key");
+
+        let run = CliRun::try_parse_from([
+            "run",
+            "--project",
+            "project",
+            "--shard",
+            "1/2",
+            "--shard-key",
+            "key",
+            "echo",
+        ])
+        .unwrap();
+        assert!(run.shard.is_some());
+    }
+}
@@ -1,5 +1,5 @@
-use bencher_json::{ResourceId, ResourceName, Slug};
-use clap::{Parser, Subcommand, ValueEnum};
+use bencher_json::{NonEmpty, ResourceId, ResourceName, Slug};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliArchived, CliBackend, CliPagination};
 
@@ -66,10 +66,36 @@ pub struct CliTestbedCreate {
     #[clap(long)]
     pub slug: Option<Slug>,
 
+    #[clap(flatten)]
+    pub spec: CliTestbedSpec,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
 
+#[derive(Args, Debug)]
+pub struct CliTestbedSpec {
+    /// Testbed CPU model name
+    #[clap(long)]
+    pub cpu: Option<NonEmpty>,
+
+    /// Testbed number of logical CPU cores
+    #[clap(long)]
+    pub cores: Option<u32>,
+
+    /// Testbed RAM in bytes
+    #[clap(long, value_name = "BYTES")]
+    pub memory: Option<u64>,
+
+    /// Testbed operating system, such as `linux`, `macos`, or `windows`
+    #[clap(long)]
+    pub os: Option<NonEmpty>,
+
+    /// Detect any of the testbed hardware spec fields that are not set from the current machine
+    #[clap(long)]
+    pub detect_spec: bool,
+}
+
 #[derive(Parser, Debug)]
 pub struct CliTestbedView {
     /// Project slug or UUID
@@ -98,6 +124,14 @@ pub struct CliTestbedUpdate {
     #[clap(long)]
     pub slug: Option<Slug>,
 
+    #[clap(flatten)]
+    pub spec: CliTestbedSpec,
+
+    /// Remove the current testbed hardware spec.
+    /// Otherwise, any testbed hardware spec fields that are set replace the entire current hardware spec.
+    #[clap(long, conflicts_with_all = ["cpu", "cores", "memory", "os", "detect_spec"])]
+    pub clear_spec: bool,
+
     #[clap(flatten)]
     pub archived: CliArchived,
 
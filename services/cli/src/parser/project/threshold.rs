@@ -1,4 +1,7 @@
-use bencher_json::{Boundary, ModelUuid, NameId, ResourceId, SampleSize, ThresholdUuid, Window};
+use bencher_json::{
+    Boundary, DateTime, Decay, ModelUuid, NameId, ResourceId, SampleSize, ThresholdUuid, Window,
+};
+use camino::Utf8PathBuf;
 use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliBackend, CliPagination};
@@ -20,6 +23,10 @@ pub enum CliThreshold {
     /// Delete a threshold
     #[clap(alias = "rm")]
     Delete(CliThresholdDelete),
+    /// Apply a threshold specification file
+    Apply(CliThresholdApply),
+    /// Recompute the boundaries for a threshold
+    Recompute(CliThresholdRecompute),
 }
 
 #[derive(Parser, Debug)]
@@ -125,6 +132,10 @@ pub struct CliModel {
     /// Upper boundary
     #[clap(long, value_name = "BOUNDARY")]
     pub upper_boundary: Option<Boundary>,
+
+    /// Decay factor (EWMA test only)
+    #[clap(long, value_name = "DECAY")]
+    pub decay: Option<Decay>,
 }
 
 /// Supported threshold model tests
@@ -147,6 +158,8 @@ pub enum CliModelTest {
     Iqr,
     /// Delta interquartile range (ΔIQR)
     DeltaIqr,
+    /// Exponentially weighted moving average (EWMA)
+    Ewma,
 }
 
 #[derive(Parser, Debug)]
@@ -212,6 +225,10 @@ pub struct CliUpdateModel {
     #[clap(long, requires = "test", value_name = "BOUNDARY")]
     pub upper_boundary: Option<Boundary>,
 
+    /// Decay factor (EWMA test only)
+    #[clap(long, requires = "test", value_name = "DECAY")]
+    pub decay: Option<Decay>,
+
     /// Remove the threshold model
     #[clap(long)]
     pub remove_model: bool,
@@ -228,3 +245,40 @@ pub struct CliThresholdDelete {
     #[clap(flatten)]
     pub backend: CliBackend,
 }
+
+#[derive(Parser, Debug)]
+pub struct CliThresholdRecompute {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Threshold UUID
+    pub threshold: ThresholdUuid,
+
+    /// Only recompute boundaries for reports that started at or after this time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub start_time: Option<DateTime>,
+
+    /// Only recompute boundaries for reports that started at or before this time (seconds since epoch)
+    #[clap(long, value_name = "SECONDS")]
+    pub end_time: Option<DateTime>,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliThresholdApply {
+    /// Project slug or UUID
+    pub project: ResourceId,
+
+    /// Threshold specification file path (YAML or TOML)
+    #[clap(long)]
+    pub file: Utf8PathBuf,
+
+    /// Print the plan without applying it
+    #[clap(long)]
+    pub dry_run: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
@@ -1,5 +1,5 @@
-use bencher_json::{Email, Jwt, Slug, UserName};
-use clap::{Parser, Subcommand};
+use bencher_json::{Email, Jwt, Slug, Url, UserName, BENCHER_API_URL_STR};
+use clap::{Args, Parser, Subcommand};
 
 #[cfg(feature = "plus")]
 use crate::parser::organization::plan::CliPlanLevel;
@@ -13,6 +13,8 @@ pub enum CliAuth {
     Login(CliAuthLogin),
     // Confirm token
     Confirm(CliAuthConfirm),
+    // Remove the stored API token
+    Logout(CliAuthLogout),
     // Accept invite
     Accept(CliAuthAccept),
 }
@@ -50,8 +52,11 @@ pub struct CliAuthSignup {
 
 #[derive(Parser, Debug)]
 pub struct CliAuthLogin {
-    /// User email
-    pub email: Email,
+    /// User email.
+    /// Not required if an API token is given with `--token`,
+    /// in which case the API token is stored for future commands.
+    #[clap(required_unless_present = "token")]
+    pub email: Option<Email>,
 
     #[cfg(feature = "plus")]
     /// Plan level
@@ -62,6 +67,9 @@ pub struct CliAuthLogin {
     #[clap(long)]
     pub invite: Option<Jwt>,
 
+    #[clap(flatten)]
+    pub credential: CliAuthCredential,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -71,10 +79,28 @@ pub struct CliAuthConfirm {
     /// Email confirmation JWT (JSON Web Token)
     pub confirm: Jwt,
 
+    #[clap(flatten)]
+    pub credential: CliAuthCredential,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
 
+#[derive(Args, Debug)]
+pub struct CliAuthCredential {
+    /// Store the API token in a config file that is only readable by the current user,
+    /// instead of the OS keychain (macOS Keychain, Windows Credential Manager, or Secret Service)
+    #[clap(long)]
+    pub no_keychain: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliAuthLogout {
+    /// Backend host URL
+    #[clap(long, value_name = "URL", env = "BENCHER_HOST", default_value = BENCHER_API_URL_STR)]
+    pub host: Url,
+}
+
 #[derive(Parser, Debug)]
 pub struct CliAuthAccept {
     /// Organization membership invitation JWT (JSON Web Token)
@@ -13,8 +13,17 @@ pub enum CliServer {
     /// Manager server config
     #[clap(subcommand)]
     Config(CliConfig),
+    /// Manage server secret key
+    #[clap(subcommand)]
+    Secret(CliSecret),
     /// Backup database
     Backup(CliBackup),
+    /// Downsample old metrics into daily aggregates
+    Downsample(CliDownsample),
+    /// Run an end-to-end smoke test against the server
+    Smoke(CliSmoke),
+    /// Server RBAC permission matrix for every endpoint
+    Rbac(CliRbac),
     #[cfg(feature = "plus")]
     /// Server usage statistics
     Stats(CliServerStats),
@@ -78,6 +87,26 @@ pub struct CliConfigConsole {
     pub backend: CliBackend,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum CliSecret {
+    /// Rotate server secret key and restart
+    Rotate(CliSecretRotate),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliSecretRotate {
+    /// Seconds that tokens signed with the previous secret key are still valid (default 7 days)
+    #[clap(long)]
+    pub grace_period: Option<u32>,
+
+    /// Server restart delay seconds
+    #[clap(long, default_value = "3")]
+    pub delay: u64,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
 #[derive(Parser, Debug)]
 pub struct CliBackup {
     /// Compress database backup with gzip
@@ -105,6 +134,45 @@ pub enum CliBackupDataStore {
     AwsS3,
 }
 
+#[derive(Parser, Debug)]
+pub struct CliDownsample {
+    /// Downsample metrics from reports that started more than this many months ago
+    /// (default: server `downsample` config)
+    #[clap(long)]
+    pub after_months: Option<u32>,
+
+    /// Only count the metrics that would be downsampled
+    #[clap(long)]
+    pub dry_run: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliSmoke {
+    /// Keep the temporary smoke test organization instead of deleting it
+    #[clap(long)]
+    pub keep: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliRbac {
+    /// Output the permission matrix in a table
+    #[clap(long)]
+    pub table: bool,
+
+    /// Fail if any endpoint is missing from the permission matrix
+    #[clap(long)]
+    pub check: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
 #[cfg(feature = "plus")]
 #[derive(Parser, Debug)]
 pub struct CliServerStats {
@@ -0,0 +1,38 @@
+use clap::{ArgGroup, Args, Parser, Subcommand};
+
+use crate::parser::CliBackend;
+
+#[derive(Subcommand, Debug)]
+pub enum CliSpec {
+    /// Export the API types from the server `OpenAPI` spec
+    Export(CliSpecExport),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliSpecExport {
+    #[clap(flatten)]
+    pub format: CliSpecFormat,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Args, Debug)]
+#[clap(group(
+    ArgGroup::new("spec_format")
+        .multiple(false)
+        .args(&["typescript", "json_schema", "openapi"]),
+))]
+pub struct CliSpecFormat {
+    /// Export TypeScript type definitions
+    #[clap(long)]
+    pub typescript: bool,
+
+    /// Export a JSON Schema (draft-07) with a definition for each type
+    #[clap(long)]
+    pub json_schema: bool,
+
+    /// Export the full `OpenAPI` spec (default)
+    #[clap(long)]
+    pub openapi: bool,
+}
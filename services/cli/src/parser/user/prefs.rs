@@ -0,0 +1,65 @@
+use bencher_json::{ResourceId, ResourceName, TimeZone};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::parser::CliBackend;
+
+#[derive(Subcommand, Debug)]
+pub enum CliUserPrefs {
+    /// View user display preferences
+    #[clap(alias = "get")]
+    View(CliUserPrefsView),
+    /// Update user display preferences
+    #[clap(alias = "edit")]
+    Update(CliUserPrefsUpdate),
+}
+
+#[derive(Parser, Debug)]
+pub struct CliUserPrefsView {
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliUserPrefsUpdate {
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    /// Time zone to display dates in, as an IANA time zone name (ie `America/New_York`)
+    #[clap(long)]
+    pub time_zone: Option<TimeZone>,
+    /// Format to display dates in
+    #[clap(value_enum, long)]
+    pub date_format: Option<CliDateFormat>,
+    /// Units to display time measures in (ie `ms`)
+    #[clap(long, value_name = "UNITS")]
+    pub time_units: Option<ResourceName>,
+    /// Units to display throughput measures in (ie `ops/s`)
+    #[clap(long, value_name = "UNITS")]
+    pub throughput_units: Option<ResourceName>,
+    /// Units to display size measures in (ie `MiB`)
+    #[clap(long, value_name = "UNITS")]
+    pub size_units: Option<ResourceName>,
+    /// Default project slug or UUID
+    #[clap(long, value_name = "PROJECT")]
+    pub default_project: Option<ResourceId>,
+    /// Clear all of the existing preferences before applying any of the other options
+    #[clap(long)]
+    pub reset: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[clap(rename_all = "snake_case")]
+pub enum CliDateFormat {
+    /// RFC 3339 (ie `2024-11-24T08:12:17+00:00`)
+    Rfc3339,
+    /// Date and time (ie `2024-11-24 08:12:17`)
+    DateTime,
+    /// Date only (ie `2024-11-24`)
+    Date,
+}
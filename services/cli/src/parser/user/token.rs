@@ -1,4 +1,4 @@
-use bencher_json::{ResourceId, ResourceName, TokenUuid};
+use bencher_json::{Cidr, ResourceId, ResourceName, TokenUuid};
 use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::parser::{CliBackend, CliPagination};
@@ -17,6 +17,8 @@ pub enum CliToken {
     // Update a token
     #[clap(alias = "edit")]
     Update(CliTokenUpdate),
+    /// Rotate a token
+    Rotate(CliTokenRotate),
 }
 
 #[derive(Parser, Debug)]
@@ -59,6 +61,11 @@ pub struct CliTokenCreate {
     #[clap(long)]
     pub ttl: Option<u32>,
 
+    /// IP address or network in CIDR notation that is allowed to use the token
+    /// (default: any IP address)
+    #[clap(long = "allowed-ip", value_delimiter = ',', value_name = "CIDR")]
+    pub allowed_ips: Vec<Cidr>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -87,6 +94,37 @@ pub struct CliTokenUpdate {
     #[clap(long)]
     pub name: Option<ResourceName>,
 
+    /// IP address or network in CIDR notation that is allowed to use the token.
+    /// Replaces any existing IP allowlist.
+    #[clap(long = "allowed-ip", value_delimiter = ',', value_name = "CIDR")]
+    pub allowed_ips: Vec<Cidr>,
+
+    /// Remove the IP allowlist, so the token can be used from any IP address
+    #[clap(long, conflicts_with = "allowed_ips")]
+    pub any_ip: bool,
+
+    #[clap(flatten)]
+    pub backend: CliBackend,
+}
+
+#[derive(Parser, Debug)]
+pub struct CliTokenRotate {
+    /// User slug or UUID
+    pub user: ResourceId,
+
+    /// Token UUID
+    pub uuid: TokenUuid,
+
+    /// Time to live (seconds) for the new token
+    /// (default: same as the old token)
+    #[clap(long)]
+    pub ttl: Option<u32>,
+
+    /// Grace period (days) before the old token is revoked
+    /// (default: revoke immediately)
+    #[clap(long, value_name = "DAYS")]
+    pub revoke_after: Option<u32>,
+
     #[clap(flatten)]
     pub backend: CliBackend,
 }
@@ -0,0 +1,10 @@
+// Compiled with `cc -O0 -fno-asynchronous-unwind-tables -fno-pic -c object.c -o object.o`
+// for the `bencher run --file-size` section and symbol size tests.
+
+int counter = 1;
+long zeroed[4];
+const char greeting[] = "hello, world";
+
+int add(int a, int b) { return a + b; }
+
+int main(void) { return add(counter, greeting[0]) + (int)zeroed[0]; }
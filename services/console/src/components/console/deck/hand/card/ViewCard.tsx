@@ -1,5 +1,12 @@
 import type { Params } from "astro";
-import { Match, Show, Switch, createMemo, createResource } from "solid-js";
+import {
+	For,
+	Match,
+	Show,
+	Switch,
+	createMemo,
+	createResource,
+} from "solid-js";
 import { Display } from "../../../../../config/types";
 import type CardConfig from "./CardConfig";
 import { authUser } from "../../../../../util/auth";
@@ -9,8 +16,9 @@ import {
 	ModelTest,
 	type JsonBranch,
 	type JsonProject,
+	type JsonReportFailure,
 } from "../../../../../types/bencher";
-import { BACK_PARAM, encodePath } from "../../../../../util/url";
+import { BACK_PARAM, encodePath, isWebLink } from "../../../../../util/url";
 import * as Sentry from "@sentry/astro";
 import { fmtDateTime, resourcePath } from "../../../../../config/util";
 import { testFragment } from "../../../../field/kinds/Model";
@@ -189,6 +197,41 @@ const ViewCard = (props: Props) => {
 									<Match when={props.card?.display === Display.MODEL_TEST}>
 										<ModelTestCard {...props} />
 									</Match>
+									<Match when={props.card?.display === Display.NOTES}>
+										<For each={props.value as string[]}>
+											{(note) => <p>{note}</p>}
+										</For>
+									</Match>
+									<Match when={props.card?.display === Display.LINKS}>
+										<For each={props.value as string[]}>
+											{(link) => (
+												<p>
+													<Show when={isWebLink(link)} fallback={link}>
+														<a href={link} target="_blank" rel="noreferrer">
+															{link}
+														</a>
+													</Show>
+												</p>
+											)}
+										</For>
+									</Match>
+									<Match when={props.card?.display === Display.FAILURES}>
+										<For each={props.value as JsonReportFailure[]}>
+											{(failure) => (
+												<>
+													<p>
+														Iteration {failure.iteration}: exit code{" "}
+														{failure.exit_code} after{" "}
+														{(failure.wall_time / 1_000_000_000).toFixed(3)}{" "}
+														seconds
+													</p>
+													<Show when={failure.stderr}>
+														<pre>{failure.stderr}</pre>
+													</Show>
+												</>
+											)}
+										</For>
+									</Match>
 								</Switch>
 							</Show>
 						</div>
@@ -345,10 +388,18 @@ const AdapterCard = (props: Props) => {
 						return "-javascript-benchmark";
 					case Adapter.JsTime:
 						return "-javascript-time";
+					case Adapter.JsNodeTest:
+						return "-javascript-nodejs-test-runner";
+					case Adapter.JsTinybench:
+						return "-javascript-tinybench";
 					case Adapter.PythonAsv:
 						return "-python-asv";
+					case Adapter.PythonPyperf:
+						return "-python-pyperf";
 					case Adapter.PythonPytest:
 						return "-python-pytest";
+					case Adapter.PythonRichbench:
+						return "-python-richbench";
 					case Adapter.RubyBenchmark:
 						return "%EF%B8%8F-ruby-benchmark";
 					case Adapter.RustBench:
@@ -390,10 +441,18 @@ const AdapterCard = (props: Props) => {
 							return "JavaScript Benchmark.js";
 						case Adapter.JsTime:
 							return "JavaScript console.time/console.timeEnd";
+						case Adapter.JsNodeTest:
+							return "JavaScript Node.js test runner";
+						case Adapter.JsTinybench:
+							return "JavaScript tinybench";
 						case Adapter.PythonAsv:
 							return "Python airspeed velocity (asv)";
+						case Adapter.PythonPyperf:
+							return "Python pyperf";
 						case Adapter.PythonPytest:
 							return "Python pytest-benchmark";
+						case Adapter.PythonRichbench:
+							return "Python richbench";
 						case Adapter.RubyBenchmark:
 							return "Ruby Benchmark";
 						case Adapter.RustBench:
@@ -445,6 +504,8 @@ const ModelTestCard = (props: Props) => {
 							return "Interquartile Range (IQR)";
 						case ModelTest.DeltaIqr:
 							return "Delta Interquartile Range (ΔIQR)";
+						case ModelTest.Ewma:
+							return "Exponentially Weighted Moving Average (EWMA)";
 						default:
 							return `${props.value}`;
 					}
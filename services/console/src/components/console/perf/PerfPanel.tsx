@@ -645,6 +645,7 @@ const PerfPanel = (props: Props) => {
 			start_time?: undefined | string;
 			end_time?: undefined | string;
 			search?: undefined | string;
+			fuzzy?: boolean;
 			refresh: number;
 			token: string;
 		},
@@ -677,6 +678,9 @@ const PerfPanel = (props: Props) => {
 		}
 		if (fetcher.search) {
 			search_params.set("search", fetcher.search.trim());
+			if (fetcher.fuzzy) {
+				search_params.set("fuzzy", "true");
+			}
 		}
 		const path = `/v0/projects/${
 			fetcher.project_slug
@@ -808,6 +812,7 @@ const PerfPanel = (props: Props) => {
 			per_page: benchmarks_per_page(),
 			page: benchmarks_page(),
 			search: benchmarks_search(),
+			fuzzy: true,
 			refresh: refresh(),
 			token: user?.token,
 		};
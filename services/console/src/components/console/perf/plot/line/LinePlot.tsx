@@ -407,6 +407,31 @@ const LinePlot = (props: Props) => {
 				),
 			);
 		}
+		// Annotations only have a time, so they can only be placed on a date time x-axis.
+		if (
+			metrics_found &&
+			props.x_axis() === XAxis.DateTime &&
+			Array.isArray(json_perf.annotations)
+		) {
+			const annotation_data = json_perf.annotations.map((annotation) => {
+				return {
+					date_time: new Date(annotation.time),
+					title: annotation.title,
+					description: annotation.description,
+				};
+			});
+			plot_arrays.push(
+				Plot.ruleX(annotation_data, {
+					x: "date_time",
+					stroke: "gray",
+					strokeDasharray: "4,4",
+					title: (datum) =>
+						datum.description
+							? `${datum.title}\n${datum.description}`
+							: datum.title,
+				}),
+			);
+		}
 		// This allows the alert images to appear on top of the plot lines.
 		plot_arrays.push(...warn_arrays, ...alert_arrays);
 
@@ -3,6 +3,7 @@ import { ModelTest } from "../../../types/bencher";
 import {
 	validBoundary,
 	validCdfBoundary,
+	validDecay,
 	validIqrBoundary,
 	validPercentageBoundary,
 	validSampleSize,
@@ -113,6 +114,13 @@ const STATISTIC_FIELDS = {
 		help: "Must be an integer greater than zero",
 		validate: validU32,
 	},
+	decay: {
+		type: "input",
+		placeholder: "0.2",
+		icon: "fas fa-hourglass-half",
+		help: "Must be between 0.00 and 1.00 (lower is smoother; higher favors recent Metrics)",
+		validate: validDecay,
+	},
 };
 
 const testValue = (selected: ModelTest) => {
@@ -147,6 +155,10 @@ const testValue = (selected: ModelTest) => {
 				value: ModelTest.DeltaIqr,
 				option: "Delta Interquartile Range (ΔIQR)",
 			},
+			{
+				value: ModelTest.Ewma,
+				option: "Exponentially Weighted Moving Average (EWMA)",
+			},
 		],
 	};
 };
@@ -197,6 +209,8 @@ export const testFragment = (modelTest: ModelTest) => {
 			return "iqr-thresholds";
 		case ModelTest.DeltaIqr:
 			return "delta-iqr-thresholds";
+		case ModelTest.Ewma:
+			return "exponentially-weighted-moving-average";
 	}
 };
 
@@ -340,6 +354,19 @@ const FIELDS = {
 	[ModelTest.LogNormal]: cdfConfig(ModelTest.LogNormal),
 	[ModelTest.Iqr]: iqrConfig(ModelTest.Iqr),
 	[ModelTest.DeltaIqr]: iqrConfig(ModelTest.DeltaIqr),
+	[ModelTest.Ewma]: [
+		...cdfConfig(ModelTest.Ewma),
+		{
+			kind: FieldKind.NUMBER,
+			label: "Decay Factor",
+			key: "decay",
+			value: "",
+			valid: true,
+			validate: true,
+			nullable: true,
+			config: STATISTIC_FIELDS.decay,
+		},
+	],
 };
 
 const initForm = (fields: object[]) => {
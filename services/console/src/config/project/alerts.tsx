@@ -202,6 +202,12 @@ const alertsConfig = {
 					keys: ["threshold", "model", "window"],
 					display: Display.RAW,
 				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Decay Factor",
+					keys: ["threshold", "model", "decay"],
+					display: Display.RAW,
+				},
 			],
 			buttons: [
 				{
@@ -132,6 +132,12 @@ const alertsPubConfig = {
 				keys: ["threshold", "model", "window"],
 				display: Display.RAW,
 			},
+			{
+				kind: Card.NESTED_FIELD,
+				label: "Decay Factor",
+				keys: ["threshold", "model", "decay"],
+				display: Display.RAW,
+			},
 		],
 	},
 };
@@ -1,12 +1,17 @@
 import type { Params } from "astro";
 import FieldKind from "../../components/field/kind";
-import { type JsonProject, Visibility } from "../../types/bencher";
+import { type JsonProject, Visibility, Weekday } from "../../types/bencher";
 import {
 	isAllowedOrganizationCreate,
 	isAllowedProjectDelete,
 	isAllowedProjectEdit,
 } from "../../util/auth";
-import { validResourceName, validOptionUrl, validSlug } from "../../util/valid";
+import {
+	validResourceName,
+	validOptionUrl,
+	validSlug,
+	validTimeZone,
+} from "../../util/valid";
 import { ActionButton, Button, Card, Display, Operation, Row } from "../types";
 import { addPath, parentPath } from "../util";
 
@@ -35,6 +40,20 @@ const PROJECT_FIELDS = {
 	visibility: {
 		icon: "fas fa-eye",
 	},
+	time_zone: {
+		type: "text",
+		placeholder: "UTC",
+		icon: "fas fa-globe",
+		help: "Must be a valid IANA time zone name (ie America/New_York)",
+		validate: validTimeZone,
+	},
+	week_start: {
+		icon: "fas fa-calendar-week",
+	},
+	strict: {
+		label: "Strict Mode",
+		help: "Reject reports with benchmarks or measures that do not already exist",
+	},
 };
 
 const VISIBILITY_VALUE = {
@@ -48,6 +67,44 @@ const VISIBILITY_VALUE = {
 			value: Visibility.Private,
 			option: "Private",
 		},
+		{
+			value: Visibility.Internal,
+			option: "Internal",
+		},
+	],
+};
+
+const WEEK_START_VALUE = {
+	selected: Weekday.Monday,
+	options: [
+		{
+			value: Weekday.Monday,
+			option: "Monday",
+		},
+		{
+			value: Weekday.Tuesday,
+			option: "Tuesday",
+		},
+		{
+			value: Weekday.Wednesday,
+			option: "Wednesday",
+		},
+		{
+			value: Weekday.Thursday,
+			option: "Thursday",
+		},
+		{
+			value: Weekday.Friday,
+			option: "Friday",
+		},
+		{
+			value: Weekday.Saturday,
+			option: "Saturday",
+		},
+		{
+			value: Weekday.Sunday,
+			option: "Sunday",
+		},
 	],
 };
 
@@ -233,6 +290,52 @@ const projectsConfig = {
 						config: PROJECT_FIELDS.visibility,
 					},
 				},
+				{
+					kind: Card.FIELD,
+					label: "Time Zone",
+					key: "time_zone",
+					display: Display.RAW,
+					is_allowed: isAllowedProjectEdit,
+					field: {
+						kind: FieldKind.INPUT,
+						label: "Time Zone",
+						key: "time_zone",
+						value: "",
+						valid: null,
+						validate: true,
+						config: PROJECT_FIELDS.time_zone,
+					},
+				},
+				{
+					kind: Card.FIELD,
+					label: "Week Start",
+					key: "week_start",
+					display: Display.SELECT,
+					is_allowed: isAllowedProjectEdit,
+					field: {
+						kind: FieldKind.SELECT,
+						label: "Week Start",
+						key: "week_start",
+						value: WEEK_START_VALUE,
+						validate: false,
+						config: PROJECT_FIELDS.week_start,
+					},
+				},
+				{
+					kind: Card.FIELD,
+					label: "Strict Mode",
+					key: "strict",
+					display: Display.SWITCH,
+					is_allowed: isAllowedProjectEdit,
+					field: {
+						kind: FieldKind.SWITCH,
+						label: "Strict Mode",
+						key: "strict",
+						value: false,
+						validate: false,
+						config: PROJECT_FIELDS.strict,
+					},
+				},
 			],
 			buttons: [
 				{
@@ -55,6 +55,24 @@ const reportsPubConfig = {
 				key: "adapter",
 				display: Display.ADAPTER,
 			},
+			{
+				kind: Card.FIELD,
+				label: "Notes",
+				key: "notes",
+				display: Display.NOTES,
+			},
+			{
+				kind: Card.FIELD,
+				label: "Links",
+				key: "links",
+				display: Display.LINKS,
+			},
+			{
+				kind: Card.FIELD,
+				label: "Failures",
+				key: "failures",
+				display: Display.FAILURES,
+			},
 			{
 				kind: Card.REPORT,
 			},
@@ -259,6 +259,12 @@ const thresholdsConfig = {
 					keys: ["model", "window"],
 					display: Display.RAW,
 				},
+				{
+					kind: Card.NESTED_FIELD,
+					label: "Decay Factor",
+					keys: ["model", "decay"],
+					display: Display.RAW,
+				},
 			],
 			buttons: [
 				{
@@ -84,6 +84,12 @@ const thresholdsPubConfig = {
 				keys: ["model", "window"],
 				display: Display.RAW,
 			},
+			{
+				kind: Card.NESTED_FIELD,
+				label: "Decay Factor",
+				keys: ["model", "decay"],
+				display: Display.RAW,
+			},
 		],
 	},
 };
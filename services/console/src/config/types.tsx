@@ -150,6 +150,9 @@ export enum Display {
 	ADAPTER = "adapter",
 	THRESHOLD = "threshold",
 	MODEL_TEST = "model_test",
+	NOTES = "notes",
+	LINKS = "links",
+	FAILURES = "failures",
 }
 
 export enum PerfTab {
@@ -34,6 +34,7 @@ export interface JsonBenchmark {
 	created: string;
 	modified: string;
 	archived?: string;
+	flaky?: string;
 }
 
 export type ResourceName = string;
@@ -56,6 +57,19 @@ export interface JsonMetric {
 	upper_value?: number;
 }
 
+/**
+ * A metric that is the daily aggregate of downsampled metrics.
+ * The value, lower value, and upper value of the metric are the means of the aggregated metrics.
+ */
+export interface JsonMetricDownsample {
+	/** The number of metrics that were aggregated */
+	sample_count: number;
+	/** The minimum value of the aggregated metrics */
+	min_value: number;
+	/** The maximum value of the aggregated metrics */
+	max_value: number;
+}
+
 export enum ModelTest {
 	Static = "static",
 	Percentage = "percentage",
@@ -64,6 +78,7 @@ export enum ModelTest {
 	LogNormal = "log_normal",
 	Iqr = "iqr",
 	DeltaIqr = "delta_iqr",
+	Ewma = "ewma",
 }
 
 export type SampleSize = number;
@@ -72,6 +87,8 @@ export type Window = number;
 
 export type Boundary = number;
 
+export type Decay = number;
+
 export interface JsonModel {
 	uuid: Uuid;
 	test: ModelTest;
@@ -80,6 +97,7 @@ export interface JsonModel {
 	window?: Window;
 	lower_boundary?: Boundary;
 	upper_boundary?: Boundary;
+	decay?: Decay;
 	created: string;
 	replaced?: string;
 }
@@ -102,6 +120,8 @@ export interface JsonReportMeasure {
 	metric: JsonMetric;
 	threshold?: JsonThresholdModel;
 	boundary?: JsonBoundary;
+	/** When the measure of the benchmark was detected as flaky, if it is flaky. */
+	flaky?: string;
 }
 
 export interface JsonReportResult {
@@ -146,6 +166,210 @@ export interface JsonBranch {
 	created: string;
 	modified: string;
 	archived?: string;
+	/**
+	 * The time that the threshold baselines for the branch were frozen, if they are.
+	 * Reports on a frozen branch are compared against the metrics from before this time.
+	 */
+	frozen_baseline?: string;
+}
+
+export interface JsonVersionRange {
+	first: JsonVersion;
+	last: JsonVersion;
+	count: number;
+}
+
+export interface JsonBranchVersion {
+	number: VersionNumber;
+	hash?: GitHash;
+	/** The time the version was recorded from a git push, if any. */
+	pushed?: string;
+	/**
+	 * The number of reports for the version on the branch head.
+	 * A version without any reports has never been benchmarked on the branch head.
+	 */
+	reports: number;
+	/** The start time of the first report for the version on the branch head, if any. */
+	first_report?: string;
+	/** The start time of the last report for the version on the branch head, if any. */
+	last_report?: string;
+}
+
+export interface JsonNewBranchTag {
+	/**
+	 * The name of the git tag.
+	 * Maximum length is 64 characters.
+	 */
+	name: ResourceName;
+	/** The full `git` commit hash that the tag points to. */
+	hash: GitHash;
+	/** The time of the tag, such as the time of the commit that it points to. */
+	time: string;
+}
+
+export interface JsonNewBranchTags {
+	/**
+	 * The git tags to record on the branch.
+	 * Maximum number of tags is 1024.
+	 */
+	tags: JsonNewBranchTag[];
+}
+
+/** The result of recording git tags on a branch. */
+export interface JsonBranchTags {
+	branch: Uuid;
+	/**
+	 * The annotations that were created for the tags.
+	 * Tags that already have an annotation with the same title and time are skipped.
+	 */
+	annotations: JsonAnnotation[];
+	/**
+	 * The versions that were recorded for the tags.
+	 * Tags for a commit that already has a version on the branch are skipped.
+	 */
+	versions: JsonVersion[];
+}
+
+/**
+ * A GitHub webhook for a project.
+ * Each git push to a branch that already exists in the project
+ * is recorded as a version before it has been benchmarked.
+ */
+export interface JsonGitHubWebhook {
+	project: Uuid;
+	/**
+	 * The secret used to sign each webhook delivery.
+	 * It is only returned when the webhook is created or its secret is rotated.
+	 */
+	secret?: Secret;
+	created: string;
+	modified: string;
+}
+
+/** The result of a GitHub webhook delivery. */
+export interface JsonGitHubDelivery {
+	project: Uuid;
+	/** The GitHub event type of the delivery. */
+	event: string;
+	/** The branch that was pushed to, if it exists in the project. */
+	branch?: Uuid;
+	/**
+	 * The versions that were recorded for the push.
+	 * Commits that already have a version on the branch are skipped.
+	 */
+	versions: JsonVersion[];
+}
+
+export interface JsonNewWebhook {
+	/** The URL that each new alert for the project is delivered to. */
+	url: Url;
+}
+
+/**
+ * An alert webhook for a project.
+ * Each new alert for the project is delivered to the webhook URL.
+ */
+export interface JsonWebhook {
+	project: Uuid;
+	url: Url;
+	/**
+	 * The secret used to sign each webhook delivery.
+	 * It is only returned when the webhook is created or replaced.
+	 */
+	secret?: Secret;
+	created: string;
+	modified: string;
+}
+
+/** A delivery of an alert to the webhook of a project. */
+export interface JsonWebhookDelivery {
+	uuid: Uuid;
+	project: Uuid;
+	alert: Uuid;
+	/** The number of times that delivery has been attempted. */
+	attempts: number;
+	/** The HTTP status code of the response to the last attempt, if a response was received. */
+	response_status?: number;
+	/** The error from the last attempt, if any. */
+	last_error?: string;
+	/** The time that the delivery ran out of attempts, if it has. */
+	failed?: string;
+	created: string;
+	modified: string;
+}
+
+/** The kind of event that a webhook delivery is for. */
+export enum WebhookEvent {
+	/** A new alert was created. */
+	AlertCreated = "alert_created",
+	/** A scheduled summary of a saved query was sent. */
+	SavedQuery = "saved_query",
+}
+
+/** An alert in a correlated alert group. */
+export interface JsonWebhookGroupAlert {
+	alert: Uuid;
+	severity: AlertSeverity;
+	status: AlertStatus;
+	limit: BoundaryLimit;
+	measure: ResourceName;
+}
+
+/** A group of correlated alerts for the measures of a single benchmark that regressed together. */
+export interface JsonWebhookAlertGroup {
+	uuid: Uuid;
+	/** The other alerts in the group. */
+	alerts: JsonWebhookGroupAlert[];
+}
+
+/**
+ * The JSON body sent to the webhook URL.
+ * It is signed with the webhook secret in the `X-Bencher-Signature-256` header.
+ */
+export interface JsonWebhookPayload {
+	event: WebhookEvent;
+	delivery: Uuid;
+	project: Uuid;
+	alert: Uuid;
+	severity: AlertSeverity;
+	status: AlertStatus;
+	limit: BoundaryLimit;
+	branch: BranchName;
+	testbed: ResourceName;
+	benchmark: BenchmarkName;
+	measure: ResourceName;
+	/**
+	 * The correlated alert group, if multiple measures for the benchmark regressed together.
+	 * Only a single delivery is sent for a group, for its most severe alert,
+	 * and the other alerts in the group are included here.
+	 */
+	group?: JsonWebhookAlertGroup;
+}
+
+export interface JsonLineageHead {
+	branch: Uuid;
+	name: BranchName;
+	slug: Slug;
+	head: Uuid;
+	/** The version of the next head in the lineage that this head was started from, if any. */
+	start_point?: JsonVersion;
+	/** The range of versions that were shallow copied from the start point when the head was created. */
+	cloned?: JsonVersionRange;
+	/** The range of versions that were created on this head. */
+	native?: JsonVersionRange;
+	created: string;
+	replaced?: string;
+}
+
+export interface JsonTestbedSpec {
+	/** The CPU model name, such as `AMD EPYC 7763 64-Core Processor`. */
+	cpu?: NonEmpty;
+	/** The number of logical CPU cores. */
+	cores?: number;
+	/** The amount of RAM, in bytes. */
+	memory?: number;
+	/** The operating system, such as `linux`, `macos`, or `windows`. */
+	os?: NonEmpty;
 }
 
 export interface JsonTestbed {
@@ -153,6 +377,8 @@ export interface JsonTestbed {
 	project: Uuid;
 	name: ResourceName;
 	slug: Slug;
+	/** The hardware specification of the testbed, if one has been declared. */
+	spec?: JsonTestbedSpec;
 	created: string;
 	modified: string;
 	archived?: string;
@@ -179,10 +405,19 @@ export enum AlertStatus {
 	Active = "active",
 	/** The alert has been dismissed by a user. */
 	Dismissed = "dismissed",
-	/** The alert has been silenced by the system. */
+	/** The alert has been silenced by the system or in bulk by a user. */
 	Silenced = "silenced",
 }
 
+export enum AlertSeverity {
+	/** The metric exceeded the boundary limit by less than the major multiplier. */
+	Minor = "minor",
+	/** The metric exceeded the boundary limit by at least the major multiplier. */
+	Major = "major",
+	/** The metric exceeded the boundary limit by at least the critical multiplier. */
+	Critical = "critical",
+}
+
 export interface JsonAlert {
 	uuid: Uuid;
 	report: Uuid;
@@ -192,7 +427,13 @@ export interface JsonAlert {
 	threshold: JsonThreshold;
 	boundary: JsonBoundary;
 	limit: BoundaryLimit;
+	severity: AlertSeverity;
 	status: AlertStatus;
+	/**
+	 * The correlated alert group, if multiple measures for the benchmark regressed together.
+	 * All of the alerts in a group are for the same report iteration and benchmark.
+	 */
+	group?: Uuid;
 	created: string;
 	modified: string;
 }
@@ -209,6 +450,12 @@ export type Index = number;
 
 export type Jwt = string;
 
+/**
+ * An IPv4 or IPv6 network in CIDR notation, such as `10.0.0.0/8` or `2001:db8::/32`.
+ * A bare IP address is treated as a single host network.
+ */
+export type Cidr = string;
+
 export type NonEmpty = string;
 
 export type CardCvc = string;
@@ -231,11 +478,16 @@ export type ResourceId = string;
 
 export type Secret = string;
 
+/** An IANA time zone name (ie `America/New_York`) */
+export type TimeZone = string;
+
 export type Url = string;
 
 export type UserName = string;
 
 export enum OrganizationRole {
+	/** The organization member role. */
+	Member = "member",
 	/** The organization leader role. */
 	Leader = "leader",
 }
@@ -262,6 +514,20 @@ export interface JsonOrganization {
 	modified: string;
 }
 
+export interface JsonOrganizationPerfProject {
+	project: JsonProject;
+	/** The results will be empty if the project does not have the branch, testbed, benchmark, or measure. */
+	results: JsonPerfMetrics[];
+}
+
+export interface JsonOrganizationPerf {
+	organization: JsonOrganization;
+	start_time?: string;
+	end_time?: string;
+	/** The results for each project, as a separate series per project. */
+	projects: JsonOrganizationPerfProject[];
+}
+
 export enum PlanLevel {
 	Free = "free",
 	Team = "team",
@@ -370,6 +636,8 @@ export enum UpdateAlertStatus {
 	Active = "active",
 	/** The alert has been dismissed by a user. */
 	Dismissed = "dismissed",
+	/** The alert has been silenced by a user. */
+	Silenced = "silenced",
 }
 
 export interface JsonUpdateAlert {
@@ -377,13 +645,125 @@ export interface JsonUpdateAlert {
 	status?: UpdateAlertStatus;
 }
 
+export interface JsonBulkUpdateAlerts {
+	/**
+	 * The new status of the matching alerts.
+	 * If `active`, then dismissed alerts are matched.
+	 * Otherwise, active alerts are matched.
+	 */
+	status: UpdateAlertStatus;
+	/**
+	 * The justification for updating the alerts, such as a known infrastructure incident.
+	 * It is stored and logged for auditing.
+	 */
+	reason: NonEmpty;
+	/** Only match alerts for this branch name, slug, or UUID. */
+	branch?: NameId;
+	/** Only match alerts for this testbed name, slug, or UUID. */
+	testbed?: NameId;
+	/** Only match alerts for this measure name, slug, or UUID. */
+	measure?: NameId;
+	/** Only match alerts of this severity. */
+	severity?: AlertSeverity;
+	/** Only match alerts for reports that started before this date time. */
+	before?: string;
+	/** Only match alerts for reports that started after this date time. */
+	after?: string;
+}
+
+/** The record of a bulk update of the alerts for a project */
+export interface JsonAlertUpdate {
+	uuid: Uuid;
+	project: Uuid;
+	/** The user that updated the alerts. */
+	user: Uuid;
+	status: AlertStatus;
+	reason: NonEmpty;
+	/** The number of alerts that were updated. */
+	alerts: number;
+	created: string;
+}
+
 export interface JsonPerfAlert {
 	uuid: Uuid;
 	limit: BoundaryLimit;
+	severity: AlertSeverity;
 	status: AlertStatus;
 	modified: string;
 }
 
+export interface JsonNewAnnotation {
+	/**
+	 * The title of the annotation.
+	 * Maximum length is 64 characters.
+	 */
+	title: ResourceName;
+	/** A longer description of the annotation. */
+	description?: NonEmpty;
+	/**
+	 * The time of the event that the annotation marks.
+	 * If not provided, the current time is used.
+	 */
+	time?: string;
+}
+
+export interface JsonAnnotation {
+	uuid: Uuid;
+	project: Uuid;
+	title: ResourceName;
+	description?: NonEmpty;
+	time: string;
+	created: string;
+	modified: string;
+}
+
+/**
+ * An expected regression window.
+ * Benchmarks that match the `pattern` on the branch do not generate alerts
+ * for reports within the time range and version range of the window.
+ */
+export interface JsonNewExpectation {
+	/** The name, slug, or UUID for the branch. */
+	branch: NameId;
+	/**
+	 * A regular expression to match against benchmark names.
+	 * Use `.*` to match all benchmarks.
+	 */
+	pattern: NonEmpty;
+	/**
+	 * The reason that the regression is expected and accepted, such as added security checks.
+	 * This is recorded in an annotation for the project.
+	 */
+	reason: NonEmpty;
+	/** Reports that started at or after this time are in the window. */
+	start_time?: string;
+	/** Reports that started at or before this time are in the window. */
+	end_time?: string;
+	/** Reports for this branch version number or later are in the window. */
+	start_version?: VersionNumber;
+	/** Reports for this branch version number or earlier are in the window. */
+	end_version?: VersionNumber;
+}
+
+export interface JsonExpectation {
+	uuid: Uuid;
+	project: Uuid;
+	branch: JsonBranch;
+	pattern: NonEmpty;
+	reason: NonEmpty;
+	start_time?: string;
+	end_time?: string;
+	start_version?: VersionNumber;
+	end_version?: VersionNumber;
+	/**
+	 * The annotation that was recorded when the expected regression window was created.
+	 * This is unset if the annotation has since been deleted.
+	 */
+	annotation?: Uuid;
+	created: string;
+	modified: string;
+}
+
 export interface JsonOneMetric {
 	uuid: Uuid;
 	report: Uuid;
@@ -395,6 +775,8 @@ export interface JsonOneMetric {
 	benchmark: JsonBenchmark;
 	measure: JsonMeasure;
 	metric: JsonMetric;
+	/** Only set if the metric is a daily aggregate of downsampled metrics */
+	downsample?: JsonMetricDownsample;
 	threshold?: JsonThresholdModel;
 	boundary?: JsonBoundary;
 	alert?: JsonPerfAlert;
@@ -403,6 +785,17 @@ export interface JsonOneMetric {
 export enum Visibility {
 	Public = "public",
 	Private = "private",
+	Internal = "internal",
+}
+
+export enum Weekday {
+	Monday = "monday",
+	Tuesday = "tuesday",
+	Wednesday = "wednesday",
+	Thursday = "thursday",
+	Friday = "friday",
+	Saturday = "saturday",
+	Sunday = "sunday",
 }
 
 export interface JsonNewProject {
@@ -428,6 +821,45 @@ export interface JsonNewProject {
 	 * Creating a `private` project requires a valid Bencher Plus subscription.
 	 */
 	visibility?: Visibility;
+	/**
+	 * The time zone for the project, as an IANA time zone name (ie `America/New_York`).
+	 * Alert statistics buckets, saved query summaries, and metric downsampling are aligned to days in this time zone.
+	 * If not provided, `UTC` is used.
+	 */
+	time_zone?: TimeZone;
+	/**
+	 * The first day of the reporting week for the project.
+	 * Weekly alert statistics buckets and weekly saved query summaries start on this day.
+	 * If not provided, `monday` is used.
+	 */
+	week_start?: Weekday;
+	/**
+	 * Reject reports with benchmarks or measures that do not already exist in the project.
+	 * If not provided, `false` is used.
+	 */
+	strict?: boolean;
+}
+
+export interface JsonProjectStats {
+	/** The number of branches in the project. */
+	branches: number;
+	/** The number of testbeds in the project. */
+	testbeds: number;
+	/** The number of benchmarks in the project. */
+	benchmarks: number;
+	/** The number of measures in the project. */
+	measures: number;
+	/** The number of reports in the project. */
+	reports: number;
+	/** The number of metrics in the project. */
+	metrics: number;
+	/** The number of threshold boundaries in the project. */
+	boundaries: number;
+	/**
+	 * The approximate number of bytes of database storage used by the project.
+	 * This is estimated from the number of rows, so it does not include indexes or free pages.
+	 */
+	storage: number;
 }
 
 export interface JsonProject {
@@ -437,8 +869,17 @@ export interface JsonProject {
 	slug: Slug;
 	url?: Url;
 	visibility: Visibility;
+	time_zone: TimeZone;
+	week_start: Weekday;
+	/** Whether reports with benchmarks or measures that do not already exist in the project are rejected. */
+	strict: boolean;
 	created: string;
 	modified: string;
+	/**
+	 * Resource counts and storage usage for the project.
+	 * Only included when requested.
+	 */
+	stats?: JsonProjectStats;
 }
 
 /**
@@ -453,6 +894,8 @@ export interface JsonPerfQuery {
 	measures: Uuid[];
 	start_time?: string;
 	end_time?: string;
+	tags: NonEmpty[];
+	unit?: ResourceName;
 }
 
 export interface JsonPerfMetric {
@@ -480,6 +923,89 @@ export interface JsonPerf {
 	start_time?: string;
 	end_time?: string;
 	results: JsonPerfMetrics[];
+	/** The project annotations within the queried time range. */
+	annotations?: JsonAnnotation[];
+}
+
+export interface JsonPerfFingerprint {
+	/**
+	 * The hardware fingerprint.
+	 * Reports without a hardware fingerprint are grouped together, with all of the fingerprint fields unset.
+	 */
+	fingerprint: JsonReportFingerprint;
+	/** The number of reports with the hardware fingerprint. */
+	reports: number;
+	/**
+	 * The number of benchmarks with at least two metrics for the hardware fingerprint.
+	 * Only these benchmarks have a coefficient of variation.
+	 */
+	benchmarks: number;
+	/**
+	 * The mean of the coefficient of variation for each benchmark.
+	 * The coefficient of variation is the sample standard deviation divided by the mean of the metrics for a benchmark.
+	 */
+	mean_cv?: number;
+	/** The median of the coefficient of variation for each benchmark. */
+	median_cv?: number;
+	/** The maximum of the coefficient of variation for each benchmark. */
+	max_cv?: number;
+}
+
+export interface JsonPerfFingerprints {
+	project: JsonProject;
+	branch: JsonBranch;
+	testbed?: JsonTestbed;
+	measure: JsonMeasure;
+	start_time?: string;
+	end_time?: string;
+	/** The variance of the metrics for each hardware fingerprint, ordered from the most to the least noisy. */
+	fingerprints: JsonPerfFingerprint[];
+}
+
+export interface JsonPerfRollup {
+	report: Uuid;
+	iteration: Iteration;
+	start_time: string;
+	end_time: string;
+	version: JsonVersion;
+	/** The number of benchmarks in the group with a metric for the report iteration. */
+	benchmarks: number;
+	/** The mean of the metric values for the benchmarks. */
+	mean: number;
+	/** The maximum of the metric values for the benchmarks. */
+	max: number;
+}
+
+export interface JsonPerfGroup {
+	/** The last segment of the group path, such as `json`. */
+	name: string;
+	/** The full group path, such as `parser::json`. */
+	path: string;
+	/** The number of benchmarks in the group, including all of its subgroups. */
+	benchmarks: number;
+	/**
+	 * The rollup of the metrics for every benchmark in the group, including all of its subgroups.
+	 * There is one rollup for each report iteration, ordered by version number, report start time, and iteration.
+	 */
+	metrics: JsonPerfRollup[];
+	/** The subgroups of the group, ordered by name. */
+	groups: JsonPerfGroup[];
+}
+
+export interface JsonPerfGroups {
+	project: JsonProject;
+	branch: JsonBranch;
+	testbed: JsonTestbed;
+	measure: JsonMeasure;
+	separator: string;
+	start_time?: string;
+	end_time?: string;
+	/**
+	 * The root of the benchmark group tree.
+	 * If a group was queried, then it is the root group.
+	 * Otherwise, the root group has an empty path and includes every benchmark.
+	 */
+	group: JsonPerfGroup;
 }
 
 export enum XAxis {
@@ -553,6 +1079,142 @@ export interface JsonPlot {
 	modified: string;
 }
 
+export interface JsonNewSavedQuery {
+	/**
+	 * The name of the saved query.
+	 * Maximum length is 64 characters.
+	 */
+	name: ResourceName;
+	/**
+	 * The window of time for the saved query, in seconds.
+	 * The window ends at the start of the day, in the project time zone, when the saved query is run.
+	 */
+	window: Window;
+	/**
+	 * The branches to include in the saved query.
+	 * At least one branch must be specified.
+	 */
+	branches: Uuid[];
+	/**
+	 * The testbeds to include in the saved query.
+	 * At least one testbed must be specified.
+	 */
+	testbeds: Uuid[];
+	/**
+	 * The benchmarks to include in the saved query.
+	 * At least one benchmark must be specified.
+	 */
+	benchmarks: Uuid[];
+	/**
+	 * The measures to include in the saved query.
+	 * At least one measure must be specified.
+	 */
+	measures: Uuid[];
+	/**
+	 * How often to send a summary of the saved query.
+	 * If not provided, `never` is used.
+	 */
+	schedule?: SavedQuerySchedule;
+	/**
+	 * The email addresses to send each summary to.
+	 * Each must belong to a member of the project or its organization.
+	 */
+	emails?: Email[];
+	/** Also send each summary to the project webhook. */
+	webhook?: boolean;
+}
+
+/** How often a summary of a saved query is sent. */
+export enum SavedQuerySchedule {
+	/** A summary is never sent. */
+	Never = "never",
+	/** A summary is sent every day. */
+	Daily = "daily",
+	/** A summary is sent once a week, on the first day of the reporting week for the project. */
+	Weekly = "weekly",
+}
+
+/**
+ * A saved perf query for a project.
+ * A summary of the saved query can be sent on a schedule.
+ */
+export interface JsonSavedQuery {
+	uuid: Uuid;
+	project: Uuid;
+	name: ResourceName;
+	window: Window;
+	branches: Uuid[];
+	testbeds: Uuid[];
+	benchmarks: Uuid[];
+	measures: Uuid[];
+	schedule: SavedQuerySchedule;
+	emails: Email[];
+	webhook: boolean;
+	/** The time that the last scheduled summary was sent, if any. */
+	last_sent?: string;
+	created: string;
+	modified: string;
+}
+
+export interface JsonUpdateSavedQuery {
+	/**
+	 * The new name of the saved query.
+	 * Maximum length is 64 characters.
+	 */
+	name?: ResourceName;
+	/** The new window of time for the saved query, in seconds. */
+	window?: Window;
+	/** The new schedule for sending a summary of the saved query. */
+	schedule?: SavedQuerySchedule;
+	/**
+	 * The new email addresses to send each summary to.
+	 * Each must belong to a member of the project or its organization.
+	 * An empty list stops sending emails.
+	 */
+	emails?: Email[];
+	/** Whether to also send each summary to the project webhook. */
+	webhook?: boolean;
+}
+
+/**
+ * A summary of the results of a saved query for a single
+ * branch, testbed, benchmark, and measure permutation.
+ */
+export interface JsonSavedQueryResult {
+	branch: BranchName;
+	testbed: ResourceName;
+	benchmark: BenchmarkName;
+	measure: ResourceName;
+	units: ResourceName;
+	/** The number of metrics in the window. */
+	count: number;
+	/** The value of the first metric in the window. */
+	first: number;
+	/** The value of the latest metric in the window. */
+	latest: number;
+	/**
+	 * The percent change from the first to the latest metric value.
+	 * This is not included if the first value is zero.
+	 */
+	change?: number;
+}
+
+/**
+ * The JSON body sent to the project webhook for a saved query summary.
+ * It is signed with the webhook secret in the `X-Bencher-Signature-256` header.
+ */
+export interface JsonSavedQueryPayload {
+	event: WebhookEvent;
+	saved_query: Uuid;
+	project: Uuid;
+	name: ResourceName;
+	start_time: string;
+	end_time: string;
+	results: JsonSavedQueryResult[];
+	/** A chart of the results as a base64 encoded JPEG image. */
+	image: string;
+}
+
 export interface JsonPubUser {
 	uuid: Uuid;
 	name: UserName;
@@ -579,29 +1241,105 @@ export enum Adapter {
 	Js = "js",
 	JsBenchmark = "js_benchmark",
 	JsTime = "js_time",
+	JsNodeTest = "js_node_test",
+	JsTinybench = "js_tinybench",
 	Python = "python",
 	PythonAsv = "python_asv",
 	PythonPytest = "python_pytest",
+	PythonPyperf = "python_pyperf",
+	PythonRichbench = "python_richbench",
 	Ruby = "ruby",
 	RubyBenchmark = "ruby_benchmark",
 	Shell = "shell",
 	ShellHyperfine = "shell_hyperfine",
 }
 
+export interface JsonReportWarmup {
+	/** The number of warm-up iterations. */
+	iterations: number;
+	/** The total wall time of all warm-up iterations in nanoseconds. */
+	wall_time: number;
+}
+
+export interface JsonReportGit {
+	/**
+	 * The name of the checked out `git` branch.
+	 * This is not set for a detached `HEAD`.
+	 */
+	branch?: NonEmpty;
+	/** A `git` tag that points at the commit. */
+	tag?: NonEmpty;
+	/** Whether the working tree had uncommitted changes to tracked files. */
+	dirty?: boolean;
+	/** The commit author, as `Name <email>`. */
+	author?: NonEmpty;
+	/** The commit time. */
+	commit_time?: string;
+	/** The first line of the commit message. */
+	subject?: NonEmpty;
+}
+
+export interface JsonReportFingerprint {
+	/** The CPU model name, such as `AMD EPYC 7763 64-Core Processor`. */
+	cpu_model?: NonEmpty;
+	/** The CPU frequency scaling governor, such as `performance` or `powersave`. */
+	governor?: NonEmpty;
+	/** Whether the machine is a virtual machine. */
+	virtualized?: boolean;
+	/** Hints about the hypervisor or platform, such as `KVM` or `Amazon EC2`. */
+	hypervisor?: NonEmpty;
+}
+
+export interface JsonReportFailure {
+	/** The zero-based index of the benchmark iteration that failed. */
+	iteration: number;
+	/** The exit code of the benchmark command. */
+	exit_code: number;
+	/** The wall time of the failed iteration in nanoseconds. */
+	wall_time: number;
+	/**
+	 * The end of the standard error output of the benchmark command.
+	 * At most `4096` bytes are kept.
+	 */
+	stderr?: string;
+}
+
 export interface JsonReport {
 	uuid: Uuid;
-	user: JsonPubUser;
+	/**
+	 * The user that submitted the report.
+	 * Reports for unclaimed projects may be submitted anonymously.
+	 */
+	user?: JsonPubUser;
 	project: JsonProject;
 	branch: JsonBranch;
 	testbed: JsonTestbed;
 	start_time: string;
 	end_time: string;
+	tag?: NonEmpty;
+	warmup?: JsonReportWarmup;
+	git?: JsonReportGit;
+	fingerprint?: JsonReportFingerprint;
+	notes?: NonEmpty[];
+	links?: Url[];
+	failures?: JsonReportFailure[];
 	adapter: Adapter;
 	results: JsonReportResults;
 	alerts: JsonReportAlerts;
 	created: string;
 }
 
+/**
+ * A report hook for a project.
+ * The hook script is run on the results of each report at ingest time.
+ */
+export interface JsonReportHook {
+	project: Uuid;
+	script: NonEmpty;
+	created: string;
+	modified: string;
+}
+
 export interface JsonSignup {
 	name: UserName;
 	slug?: Slug;
@@ -707,6 +1445,51 @@ export interface JsonUpdateUser {
 	locked?: boolean;
 }
 
+export enum DateFormat {
+	/** `2024-11-24T08:12:17+00:00` */
+	Rfc3339 = "rfc3339",
+	/** `2024-11-24 08:12:17` */
+	DateTime = "date_time",
+	/** `2024-11-24` */
+	Date = "date",
+}
+
+/**
+ * The display preferences for a user.
+ * They are used when displaying dates and metrics,
+ * such as in the CLI table output and the report comment.
+ */
+export interface JsonNewUserPrefs {
+	/**
+	 * The time zone to display dates in, as an IANA time zone name (ie `America/New_York`).
+	 * If not set, dates are displayed in UTC.
+	 */
+	time_zone?: TimeZone;
+	/**
+	 * The format to display dates in.
+	 * If not set, dates are displayed in RFC 3339 format.
+	 */
+	date_format?: DateFormat;
+	/** The units to display time measures in, such as `ms`. */
+	time_units?: ResourceName;
+	/** The units to display throughput measures in, such as `ops/s`. */
+	throughput_units?: ResourceName;
+	/** The units to display size measures in, such as `MiB`. */
+	size_units?: ResourceName;
+	/** The slug or UUID of the default project for the user. */
+	default_project?: ResourceId;
+}
+
+export interface JsonUserPrefs {
+	user: Uuid;
+	time_zone?: TimeZone;
+	date_format?: DateFormat;
+	time_units?: ResourceName;
+	throughput_units?: ResourceName;
+	size_units?: ResourceName;
+	default_project?: Uuid;
+}
+
 export interface JsonNewToken {
 	/**
 	 * The name of the token.
@@ -718,6 +1501,11 @@ export interface JsonNewToken {
 	 * If not provided, the token will not expire for over 128 years.
 	 */
 	ttl?: number;
+	/**
+	 * The IP addresses or networks, in CIDR notation, that are allowed to use the token.
+	 * If not provided or empty, the token can be used from any IP address.
+	 */
+	allowed_ips?: Cidr[];
 }
 
 export interface JsonToken {
@@ -727,6 +1515,79 @@ export interface JsonToken {
 	token: Jwt;
 	creation: string;
 	expiration: string;
+	/** The time at which the token was or will be revoked, if any. */
+	revoked?: string;
+	/**
+	 * The IP addresses or networks, in CIDR notation, that are allowed to use the token.
+	 * If not set, the token can be used from any IP address.
+	 */
+	allowed_ips?: Cidr[];
+}
+
+export interface JsonRevokeToken {
+	/**
+	 * The grace period in seconds before the token is revoked.
+	 * This allows for the token to be rotated without breaking any running jobs.
+	 * If not provided, the token is revoked immediately.
+	 */
+	after?: number;
+}
+
+/**
+ * The policy for API tokens created by members of an organization.
+ * If a user is a member of multiple organizations,
+ * then their new tokens must satisfy the policies for all of them.
+ */
+export interface JsonTokenPolicy {
+	organization: Uuid;
+	/**
+	 * The maximum time-to-live (TTL) in seconds for new tokens.
+	 * New tokens without a TTL are given this TTL.
+	 */
+	max_ttl?: number;
+	/** New tokens must be created with a TTL. */
+	require_ttl: boolean;
+	/**
+	 * New tokens may not be created with a TTL longer than one year,
+	 * which in practice never expires.
+	 */
+	forbid_non_expiring: boolean;
+	/** Only organization leaders may create new tokens. */
+	admin_only: boolean;
+	/** The time the policy was last modified, if it has ever been set. */
+	modified?: string;
+}
+
+export interface JsonUpdateTokenPolicy {
+	/**
+	 * The maximum time-to-live (TTL) in seconds for new tokens.
+	 * If not provided, there is no maximum.
+	 */
+	max_ttl?: number;
+	/**
+	 * New tokens must be created with a TTL.
+	 * If not provided, defaults to `false`.
+	 */
+	require_ttl?: boolean;
+	/**
+	 * New tokens may not be created with a TTL longer than one year,
+	 * which in practice never expires.
+	 * If not provided, defaults to `false`.
+	 */
+	forbid_non_expiring?: boolean;
+	/**
+	 * Only organization leaders may create new tokens.
+	 * If not provided, defaults to `false`.
+	 */
+	admin_only?: boolean;
+}
+
+export interface JsonRevokedTokens {
+	organization: Uuid;
+	/** The number of tokens that were revoked. */
+	tokens: number;
+	/** The time at which the tokens were or will be revoked. */
+	revoked: string;
 }
 
 export enum OrganizationPermission {
@@ -743,6 +1604,7 @@ export enum OrganizationPermission {
 
 export enum ProjectPermission {
 	View = "view",
+	Report = "report",
 	Create = "create",
 	Edit = "edit",
 	Delete = "delete",
@@ -753,6 +1615,90 @@ export enum ProjectPermission {
 	DeleteRole = "delete_role",
 }
 
+export enum ProjectRole {
+	/**
+	 * The project viewer role.
+	 * Viewers can view the project, even if it is private.
+	 */
+	Viewer = "viewer",
+	/**
+	 * The project reporter role.
+	 * Reporters can also submit reports to the project.
+	 */
+	Reporter = "reporter",
+	/**
+	 * The project maintainer role.
+	 * Maintainers can also manage the project and its members.
+	 */
+	Maintainer = "maintainer",
+}
+
+export interface JsonNewProjectMember {
+	/** The user name for the invitee. */
+	name?: UserName;
+	/**
+	 * The email for the invitee.
+	 * This will be used to both send the invite
+	 * and to create the user account if they do not exist.
+	 * The invitee does not need to be a member of the project's organization.
+	 */
+	email: Email;
+	/** The project role for the invitee. */
+	role: ProjectRole;
+}
+
+export interface JsonProjectMember {
+	/** The member UUID. */
+	uuid: Uuid;
+	/** The member user name. */
+	name: UserName;
+	/** The member slug. */
+	slug: Slug;
+	/** The member email. */
+	email: Email;
+	/** The member project role. */
+	role: ProjectRole;
+	/** The date time the member was created. */
+	created: string;
+	/** The date time the member was last modified. */
+	modified: string;
+}
+
+export interface JsonUpdateProjectMember {
+	/** The new project role for the member. */
+	role?: ProjectRole;
+}
+
+export interface JsonNewRule {
+	/**
+	 * A regular expression to match against benchmark names.
+	 * Every match in a benchmark name is replaced with the `replacement`.
+	 */
+	pattern: NonEmpty;
+	/**
+	 * The replacement for each match of the `pattern`.
+	 * Capture groups from the `pattern` can be referenced with `$1`, `$2`, or `$name`.
+	 * Set to an empty string to remove the matched text.
+	 */
+	replacement: string;
+}
+
+export interface JsonRule {
+	uuid: Uuid;
+	project: Uuid;
+	pattern: NonEmpty;
+	replacement: string;
+	created: string;
+	modified: string;
+}
+
+export interface JsonUpdateRule {
+	/** The new regular expression to match against benchmark names. */
+	pattern?: NonEmpty;
+	/** The new replacement for each match of the `pattern`. */
+	replacement?: string;
+}
+
 export enum PerfQueryKey {
 	Branches = "branches",
 	Heads = "heads",
@@ -761,6 +1707,8 @@ export enum PerfQueryKey {
 	Measures = "measures",
 	StartTime = "start_time",
 	EndTime = "end_time",
+	Tags = "tags",
+	Unit = "unit",
 }
 
 export enum PlotKey {
@@ -1,4 +1,5 @@
 import axios from "axios";
+import { BENCHER_VERSION } from "./ext";
 
 export const X_TOTAL_COUNT = "x-total-count";
 
@@ -105,6 +106,9 @@ export const deleteOptions = (
 
 const HEADERS_CONTENT_TYPE = {
 	"Content-Type": "application/json",
+	// Request the API version that matches this console,
+	// otherwise the oldest supported API version is used.
+	Accept: `application/vnd.bencher.v${BENCHER_VERSION}+json, application/json`,
 };
 
 const headers = (token: undefined | null | string) => {
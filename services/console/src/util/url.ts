@@ -306,3 +306,13 @@ export const decodePath = (fallback: string) => {
 		return fallback;
 	}
 };
+
+// Only http and https links are safe to render as anchors
+export const isWebLink = (link: string) => {
+	try {
+		const protocol = new URL(link).protocol;
+		return protocol === "http:" || protocol === "https:";
+	} catch (_e) {
+		return false;
+	}
+};
@@ -4,6 +4,7 @@ import {
 	is_valid_branch_name,
 	is_valid_card_cvc,
 	is_valid_card_number,
+	is_valid_decay,
 	is_valid_email,
 	is_valid_expiration_month,
 	is_valid_expiration_year,
@@ -14,6 +15,7 @@ import {
 	is_valid_plan_level,
 	is_valid_sample_size,
 	is_valid_slug,
+	is_valid_time_zone,
 	is_valid_url,
 	is_valid_user_name,
 	is_valid_uuid,
@@ -70,6 +72,9 @@ export const validNonEmpty = (non_empty: string): boolean =>
 export const validSlug = (slug: undefined | null | string): boolean =>
 	validOptionString(slug, is_valid_slug);
 
+export const validTimeZone = (time_zone: string): boolean =>
+	validString(time_zone, is_valid_time_zone);
+
 export const validEmail = (email: undefined | null | string): boolean =>
 	validOptionString(email, is_valid_email);
 
@@ -138,6 +143,9 @@ export const validCdfBoundary = (boundary: string): boolean =>
 export const validIqrBoundary = (boundary: string): boolean =>
 	validateNumber(boundary, is_valid_iqr_boundary);
 
+export const validDecay = (decay: string): boolean =>
+	validateNumber(decay, is_valid_decay);
+
 export const validSampleSize = (sample_size: string) =>
 	validU32(sample_size) && validateNumber(sample_size, is_valid_sample_size);
 
@@ -47,6 +47,9 @@ impl Spec {
                 "reports" => TagDetails { description: Some("Reports".into()), external_docs: None},
                 "perf" => TagDetails { description: Some("Perf Metrics".into()), external_docs: None},
                 "plots" => TagDetails { description: Some("Plots".into()), external_docs: None},
+                "annotations" => TagDetails { description: Some("Annotations".into()), external_docs: None},
+                "rules" => TagDetails { description: Some("Rules".into()), external_docs: None},
+                "expectations" => TagDetails { description: Some("Expected Regressions".into()), external_docs: None},
                 "branches" => TagDetails { description: Some("Branches".into()), external_docs: None},
                 "testbeds" => TagDetails { description: Some("Testbeds".into()), external_docs: None},
                 "benchmarks" => TagDetails { description: Some("Benchmarks".into()), external_docs: None},